@@ -16,7 +16,12 @@ use {
         slot_history::{self, SlotHistory},
         stake_history::{StakeHistory, StakeHistoryEntry},
         sysvar::{
-            self, epoch_rewards::EpochRewards, last_restart_slot::LastRestartSlot, rewards::Rewards,
+            self,
+            epoch_rewards::EpochRewards,
+            epoch_stake::EpochStake,
+            last_restart_slot::LastRestartSlot,
+            restart_history::{RestartEntry, RestartHistory},
+            rewards::Rewards,
         },
     },
 };
@@ -95,6 +100,16 @@ pub fn parse_sysvar(data: &[u8], pubkey: &Pubkey) -> Result<SysvarAccountType, P
             deserialize::<EpochRewards>(data)
                 .ok()
                 .map(SysvarAccountType::EpochRewards)
+        } else if pubkey == &sysvar::epoch_stake::id() {
+            deserialize::<EpochStake>(data)
+                .ok()
+                .map(|epoch_stake| SysvarAccountType::EpochStake(epoch_stake.into()))
+        } else if pubkey == &sysvar::restart_history::id() {
+            deserialize::<RestartHistory>(data)
+                .ok()
+                .map(|restart_history| {
+                    SysvarAccountType::RestartHistory(restart_history.restarts().to_vec())
+                })
         } else {
             None
         }
@@ -120,6 +135,8 @@ pub enum SysvarAccountType {
     StakeHistory(Vec<UiStakeHistoryEntry>),
     LastRestartSlot(UiLastRestartSlot),
     EpochRewards(EpochRewards),
+    EpochStake(UiEpochStake),
+    RestartHistory(Vec<RestartEntry>),
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Default)]
@@ -239,6 +256,38 @@ pub struct UiLastRestartSlot {
     pub last_restart_slot: Slot,
 }
 
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct UiEpochStake {
+    pub epoch: Epoch,
+    pub total_stake: u64,
+    pub vote_accounts: Vec<UiVoteAccountStake>,
+}
+
+impl From<EpochStake> for UiEpochStake {
+    fn from(epoch_stake: EpochStake) -> Self {
+        Self {
+            epoch: epoch_stake.epoch,
+            total_stake: epoch_stake.total_stake,
+            vote_accounts: epoch_stake
+                .vote_accounts
+                .into_iter()
+                .map(|vote_account| UiVoteAccountStake {
+                    vote_pubkey: vote_account.vote_pubkey.to_string(),
+                    stake: vote_account.stake,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct UiVoteAccountStake {
+    pub vote_pubkey: String,
+    pub stake: u64,
+}
+
 #[cfg(test)]
 mod test {
     #[allow(deprecated)]
@@ -381,5 +430,120 @@ mod test {
             parse_sysvar(&epoch_rewards_sysvar.data, &sysvar::epoch_rewards::id()).unwrap(),
             SysvarAccountType::EpochRewards(epoch_rewards),
         );
+
+        let vote_pubkey = solana_sdk::pubkey::new_rand();
+        let epoch_stake = EpochStake::new(3, 100, [(vote_pubkey, 60)]);
+        let epoch_stake_sysvar = create_account_for_test(&epoch_stake);
+        assert_eq!(
+            parse_sysvar(&epoch_stake_sysvar.data, &sysvar::epoch_stake::id()).unwrap(),
+            SysvarAccountType::EpochStake(UiEpochStake {
+                epoch: 3,
+                total_stake: 100,
+                vote_accounts: vec![UiVoteAccountStake {
+                    vote_pubkey: vote_pubkey.to_string(),
+                    stake: 60,
+                }],
+            }),
+        );
+
+        let mut restart_history = RestartHistory::default();
+        restart_history.add(10, 1_000);
+        restart_history.add(20, 2_000);
+        let restart_history_sysvar = create_account_for_test(&restart_history);
+        assert_eq!(
+            parse_sysvar(&restart_history_sysvar.data, &sysvar::restart_history::id()).unwrap(),
+            SysvarAccountType::RestartHistory(vec![
+                RestartEntry {
+                    slot: 20,
+                    unix_timestamp: 2_000,
+                },
+                RestartEntry {
+                    slot: 10,
+                    unix_timestamp: 1_000,
+                },
+            ]),
+        );
+    }
+
+    #[test]
+    fn test_sysvar_json_field_names() {
+        let json = |sysvar: SysvarAccountType| serde_json::to_value(sysvar).unwrap();
+
+        let clock = Clock {
+            slot: 1,
+            epoch_start_timestamp: 2,
+            epoch: 3,
+            leader_schedule_epoch: 4,
+            unix_timestamp: 5,
+        };
+        assert_eq!(
+            json(SysvarAccountType::Clock(clock.into())),
+            serde_json::json!({
+                "type": "clock",
+                "info": {
+                    "slot": 1,
+                    "epochStartTimestamp": 2,
+                    "epoch": 3,
+                    "leaderScheduleEpoch": 4,
+                    "unixTimestamp": 5,
+                },
+            })
+        );
+
+        let epoch_rewards = EpochRewards {
+            total_rewards: 100,
+            distributed_rewards: 20,
+            distribution_complete_block_height: 42,
+        };
+        assert_eq!(
+            json(SysvarAccountType::EpochRewards(epoch_rewards)),
+            serde_json::json!({
+                "type": "epochRewards",
+                "info": {
+                    "total_rewards": 100,
+                    "distributed_rewards": 20,
+                    "distribution_complete_block_height": 42,
+                },
+            })
+        );
+
+        assert_eq!(
+            json(SysvarAccountType::LastRestartSlot(UiLastRestartSlot {
+                last_restart_slot: 1282,
+            })),
+            serde_json::json!({
+                "type": "lastRestartSlot",
+                "info": {"lastRestartSlot": 1282},
+            })
+        );
+
+        // the unused field of the rewards sysvar is not exposed
+        let rewards = Rewards {
+            validator_point_value: 0.5,
+            unused: 1.0,
+        };
+        assert_eq!(
+            json(SysvarAccountType::Rewards(rewards.into())),
+            serde_json::json!({
+                "type": "rewards",
+                "info": {"validatorPointValue": 0.5},
+            })
+        );
+
+        let restart_history_sysvar = create_account_for_test(&{
+            let mut restart_history = RestartHistory::default();
+            restart_history.add(10, 1_000);
+            restart_history
+        });
+        assert_eq!(
+            serde_json::to_value(
+                parse_sysvar(&restart_history_sysvar.data, &sysvar::restart_history::id()).unwrap()
+            )
+            .unwrap(),
+            serde_json::json!({
+                "type": "restartHistory",
+                "info": [{"slot": 10, "unix_timestamp": 1_000}],
+            })
+        );
     }
 }
@@ -6,7 +6,10 @@ use {
     },
     solana_sdk::pubkey::Pubkey,
     spl_token_2022::{
-        extension::{BaseStateWithExtensions, StateWithExtensions},
+        extension::{
+            interest_bearing_mint::InterestBearingConfig, BaseStateWithExtensions,
+            StateWithExtensions,
+        },
         generic_token_account::GenericTokenAccount,
         solana_program::{
             program_option::COption, program_pack::Pack, pubkey::Pubkey as SplTokenPubkey,
@@ -258,6 +261,27 @@ pub fn token_amount_to_ui_amount(amount: u64, decimals: u8) -> UiTokenAmount {
     }
 }
 
+/// The ui amount of `amount` tokens of a mint accruing interest as configured
+/// by `interest_bearing_config`, including the interest accrued by `unix_timestamp`
+pub fn token_amount_to_ui_amount_with_interest(
+    amount: u64,
+    decimals: u8,
+    interest_bearing_config: &InterestBearingConfig,
+    unix_timestamp: i64,
+) -> UiTokenAmount {
+    let Some(ui_amount_string) =
+        interest_bearing_config.amount_to_ui_amount(amount, decimals, unix_timestamp)
+    else {
+        return token_amount_to_ui_amount(amount, decimals);
+    };
+    UiTokenAmount {
+        ui_amount: f64::from_str(&ui_amount_string).ok(),
+        decimals,
+        amount: amount.to_string(),
+        ui_amount_string,
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct UiMint {
@@ -461,6 +485,36 @@ mod test {
         assert_eq!(token_amount.ui_amount, None);
     }
 
+    #[test]
+    fn test_ui_token_amount_with_interest() {
+        const SECONDS_PER_YEAR: i64 = 31_556_736;
+        let interest_bearing_config = InterestBearingConfig {
+            rate_authority: OptionalNonZeroPubkey::default(),
+            initialization_timestamp: 0.into(),
+            pre_update_average_rate: 500.into(),
+            last_update_timestamp: 0.into(),
+            current_rate: 500.into(),
+        };
+
+        // no interest accrued yet
+        let token_amount =
+            token_amount_to_ui_amount_with_interest(150, 2, &interest_bearing_config, 0);
+        assert_eq!(token_amount, token_amount_to_ui_amount(150, 2));
+
+        // about 5% accrued over a year
+        let token_amount = token_amount_to_ui_amount_with_interest(
+            100,
+            2,
+            &interest_bearing_config,
+            SECONDS_PER_YEAR,
+        );
+        assert_eq!(token_amount.amount, "100");
+        assert_eq!(token_amount.decimals, 2);
+        let ui_amount = token_amount.ui_amount.unwrap();
+        assert!(ui_amount > 1.05 && ui_amount < 1.06);
+        assert_eq!(token_amount.ui_amount_string, ui_amount.to_string());
+    }
+
     #[test]
     fn test_ui_token_amount_real_string_zero() {
         assert_eq!(&real_number_string(0, 0), "0");
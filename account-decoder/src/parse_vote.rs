@@ -47,6 +47,15 @@ pub fn parse_vote(data: &[u8]) -> Result<VoteAccountType, ParseAccountError> {
             },
         )
         .collect();
+    let authorized_voter_history = vote_state
+        .authorized_voter_history()
+        .into_iter()
+        .map(|epochs| UiAuthorizedVoterEpochs {
+            authorized_voter: epochs.authorized_voter.to_string(),
+            start_epoch: epochs.start_epoch,
+            end_epoch: epochs.end_epoch,
+        })
+        .collect();
     Ok(VoteAccountType::Vote(UiVoteState {
         node_pubkey: vote_state.node_pubkey.to_string(),
         authorized_withdrawer: vote_state.authorized_withdrawer.to_string(),
@@ -57,6 +66,7 @@ pub fn parse_vote(data: &[u8]) -> Result<VoteAccountType, ParseAccountError> {
         prior_voters,
         epoch_credits,
         last_timestamp: vote_state.last_timestamp,
+        authorized_voter_history,
     }))
 }
 
@@ -80,6 +90,8 @@ pub struct UiVoteState {
     prior_voters: Vec<UiPriorVoters>,
     epoch_credits: Vec<UiEpochCredits>,
     last_timestamp: BlockTimestamp,
+    #[serde(default)]
+    authorized_voter_history: Vec<UiAuthorizedVoterEpochs>,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
@@ -113,6 +125,14 @@ struct UiPriorVoters {
     target_epoch: Epoch,
 }
 
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+struct UiAuthorizedVoterEpochs {
+    authorized_voter: String,
+    start_epoch: Epoch,
+    end_epoch: Option<Epoch>,
+}
+
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 struct UiEpochCredits {
@@ -123,7 +143,13 @@ struct UiEpochCredits {
 
 #[cfg(test)]
 mod test {
-    use {super::*, solana_sdk::vote::state::VoteStateVersions};
+    use {
+        super::*,
+        solana_sdk::{
+            clock::Clock,
+            vote::state::{VoteInit, VoteStateVersions},
+        },
+    };
 
     #[test]
     fn test_parse_vote() {
@@ -144,4 +170,42 @@ mod test {
         let bad_data = vec![0; 4];
         assert!(parse_vote(&bad_data).is_err());
     }
+
+    #[test]
+    fn test_parse_vote_authorized_voter_history() {
+        let original_voter = Pubkey::new_unique();
+        let new_voter = Pubkey::new_unique();
+        let mut vote_state = VoteState::new(
+            &VoteInit {
+                node_pubkey: original_voter,
+                authorized_voter: original_voter,
+                authorized_withdrawer: original_voter,
+                commission: 0,
+            },
+            &Clock::default(),
+        );
+        vote_state
+            .set_new_authorized_voter(&new_voter, 0, 1, |_| Ok(()))
+            .unwrap();
+        let mut vote_account_data: Vec<u8> = vec![0; VoteState::size_of()];
+        let versioned = VoteStateVersions::new_current(vote_state);
+        VoteState::serialize(&versioned, &mut vote_account_data).unwrap();
+
+        let VoteAccountType::Vote(ui_vote_state) = parse_vote(&vote_account_data).unwrap();
+        assert_eq!(
+            ui_vote_state.authorized_voter_history,
+            vec![
+                UiAuthorizedVoterEpochs {
+                    authorized_voter: original_voter.to_string(),
+                    start_epoch: 0,
+                    end_epoch: Some(1),
+                },
+                UiAuthorizedVoterEpochs {
+                    authorized_voter: new_voter.to_string(),
+                    start_epoch: 1,
+                    end_epoch: None,
+                },
+            ]
+        );
+    }
 }
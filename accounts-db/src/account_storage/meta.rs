@@ -146,7 +146,7 @@ impl<'storage> StoredAccountMeta<'storage> {
         }
     }
 
-    pub fn data(&self) -> &'storage [u8] {
+    pub fn data(&self) -> &[u8] {
         match self {
             Self::AppendVec(av) => av.data(),
             Self::Hot(hot) => hot.data(),
@@ -160,6 +160,14 @@ impl<'storage> StoredAccountMeta<'storage> {
         }
     }
 
+    /// Whether the account data is stored compressed
+    pub fn is_compressed(&self) -> bool {
+        match self {
+            Self::AppendVec(av) => av.is_compressed(),
+            Self::Hot(_) => false,
+        }
+    }
+
     pub fn write_version(&self) -> StoredMetaWriteVersion {
         match self {
             Self::AppendVec(av) => av.write_version(),
@@ -34,7 +34,10 @@ use {
             hash_map::{self},
             BinaryHeap, HashMap, HashSet,
         },
-        ops::RangeBounds,
+        ops::{
+            Bound::{Excluded, Unbounded},
+            RangeBounds,
+        },
         sync::{
             atomic::{AtomicUsize, Ordering},
             Arc, Mutex,
@@ -107,6 +110,9 @@ pub struct LoadedTransaction {
     pub program_indices: TransactionProgramIndices,
     pub rent: TransactionRent,
     pub rent_debits: RentDebits,
+    /// Index of the account which paid the fees, the first signer unless the
+    /// transaction is sponsored
+    pub fee_payer_index: usize,
 }
 
 pub type TransactionLoadResult = (Result<LoadedTransaction>, Option<NonceFull>);
@@ -377,6 +383,50 @@ impl Accounts {
             .map(|_| collector)
     }
 
+    /// Load at most `limit` accounts owned by `program_id` which pass `filter`, with pubkeys
+    /// below `before`, in descending pubkey order. The accounts index is walked in order, and
+    /// the scan stops as soon as `limit` accounts are found.
+    pub fn load_by_program_with_filter_before<F: Fn(&AccountSharedData) -> bool>(
+        &self,
+        ancestors: &Ancestors,
+        bank_id: BankId,
+        program_id: &Pubkey,
+        filter: F,
+        before: Option<&Pubkey>,
+        limit: usize,
+        config: &ScanConfig,
+    ) -> ScanResult<Vec<TransactionAccount>> {
+        let mut collector = Vec::new();
+        if limit == 0 {
+            return Ok(collector);
+        }
+        let config = ScanConfig {
+            collect_all_unsorted: false,
+            descending: true,
+            ..config.recreate_with_abort()
+        };
+        let range = (
+            Unbounded,
+            before.map_or(Unbounded, |before| Excluded(*before)),
+        );
+        self.accounts_db
+            .checked_range_scan_accounts(
+                ancestors,
+                bank_id,
+                range,
+                |some_account_tuple| {
+                    Self::load_while_filtering(&mut collector, some_account_tuple, |account| {
+                        account.owner() == program_id && filter(account)
+                    });
+                    if collector.len() >= limit {
+                        config.abort();
+                    }
+                },
+                &config,
+            )
+            .map(|_| collector)
+    }
+
     fn calc_scan_result_size(account: &AccountSharedData) -> usize {
         account.data().len()
             + std::mem::size_of::<AccountSharedData>()
@@ -645,12 +695,14 @@ impl Accounts {
         });
     }
 
-    /// Store the accounts into the DB
+    /// Store the accounts into the DB. `ancestors` are those of `slot`, and
+    /// tell which of the accounts the transactions closed.
     // allow(clippy) needed for various gating flags
     #[allow(clippy::too_many_arguments)]
     pub fn store_cached(
         &self,
         slot: Slot,
+        ancestors: &Ancestors,
         txs: &[SanitizedTransaction],
         res: &[TransactionExecutionResult],
         loaded: &mut [TransactionLoadResult],
@@ -659,8 +711,11 @@ impl Accounts {
     ) {
         let (accounts_to_store, transactions) =
             self.collect_accounts_to_store(txs, res, loaded, durable_nonce, lamports_per_signature);
-        self.accounts_db
-            .store_cached_inline_update_index((slot, &accounts_to_store[..]), Some(&transactions));
+        self.accounts_db.store_cached_inline_update_index(
+            (slot, &accounts_to_store[..]),
+            Some(&transactions),
+            ancestors,
+        );
     }
 
     pub fn store_accounts_cached<'a, T: ReadableAccount + Sync + ZeroLamport + 'a>(
@@ -715,15 +770,12 @@ impl Accounts {
 
             let message = tx.message();
             let loaded_transaction = tx_load_result.as_mut().unwrap();
-            let mut fee_payer_index = None;
+            let fee_payer_index = loaded_transaction.fee_payer_index;
             for (i, (address, account)) in (0..message.account_keys().len())
                 .zip(loaded_transaction.accounts.iter_mut())
                 .filter(|(i, _)| message.is_non_loader_key(*i))
             {
-                if fee_payer_index.is_none() {
-                    fee_payer_index = Some(i);
-                }
-                let is_fee_payer = Some(i) == fee_payer_index;
+                let is_fee_payer = i == fee_payer_index;
                 if message.is_writable(i) {
                     let is_nonce_account = prepare_if_nonce_account(
                         address,
@@ -846,6 +898,7 @@ mod tests {
                 status,
                 log_messages: None,
                 inner_instructions: None,
+                instruction_compute_units: None,
                 durable_nonce_fee: nonce.map(DurableNonceFee::from),
                 return_data: None,
                 executed_units: 0,
@@ -1054,6 +1107,42 @@ mod tests {
         assert_eq!(loaded, vec![]);
     }
 
+    #[test]
+    fn test_load_by_program_with_filter_before() {
+        let accounts_db = AccountsDb::new_single_for_tests();
+        let accounts = Accounts::new(Arc::new(accounts_db));
+        let program_id = Pubkey::from([9; 32]);
+
+        for i in 1..=5 {
+            let account = AccountSharedData::new(i, 0, &program_id);
+            accounts.store_slow_uncached(0, &Pubkey::from([i as u8; 32]), &account);
+        }
+        let account = AccountSharedData::new(6, 0, &Pubkey::from([8; 32]));
+        accounts.store_slow_uncached(0, &Pubkey::from([6; 32]), &account);
+
+        let ancestors = vec![(0, 0)].into_iter().collect();
+        let load_pubkeys = |before: Option<&Pubkey>, limit| {
+            accounts
+                .load_by_program_with_filter_before(
+                    &ancestors,
+                    0,
+                    &program_id,
+                    |account| account.lamports() != 4,
+                    before,
+                    limit,
+                    &ScanConfig::default(),
+                )
+                .unwrap()
+                .into_iter()
+                .map(|(pubkey, _account)| pubkey.to_bytes()[0])
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(load_pubkeys(None, 10), vec![5, 3, 2, 1]);
+        assert_eq!(load_pubkeys(Some(&Pubkey::from([5; 32])), 2), vec![3, 2]);
+        assert_eq!(load_pubkeys(Some(&Pubkey::from([2; 32])), 2), vec![1]);
+        assert_eq!(load_pubkeys(None, 0), Vec::<u8>::new());
+    }
+
     #[test]
     fn test_accounts_empty_bank_hash_stats() {
         let accounts_db = AccountsDb::new_single_for_tests();
@@ -1536,6 +1625,7 @@ mod tests {
                 program_indices: vec![],
                 rent: 0,
                 rent_debits: RentDebits::default(),
+                fee_payer_index: 0,
             }),
             None,
         );
@@ -1546,6 +1636,7 @@ mod tests {
                 program_indices: vec![],
                 rent: 0,
                 rent_debits: RentDebits::default(),
+                fee_payer_index: 0,
             }),
             None,
         );
@@ -1924,6 +2015,7 @@ mod tests {
                 program_indices: vec![],
                 rent: 0,
                 rent_debits: RentDebits::default(),
+                fee_payer_index: 0,
             }),
             nonce.clone(),
         );
@@ -2030,6 +2122,7 @@ mod tests {
                 program_indices: vec![],
                 rent: 0,
                 rent_debits: RentDebits::default(),
+                fee_payer_index: 0,
             }),
             nonce.clone(),
         );
@@ -35,10 +35,10 @@ use {
             AccountsFile, AccountsFileError, MatchAccountOwnerError, ALIGN_BOUNDARY_OFFSET,
         },
         accounts_hash::{
-            AccountHash, AccountsDeltaHash, AccountsHash, AccountsHashKind, AccountsHasher,
-            CalcAccountsHashConfig, CalculateHashIntermediate, HashStats, IncrementalAccountsHash,
-            SerdeAccountsDeltaHash, SerdeAccountsHash, SerdeIncrementalAccountsHash,
-            ZeroLamportAccounts,
+            AccountHash, AccountLtHash, AccountsDeltaHash, AccountsHash, AccountsHashKind,
+            AccountsHasher, AccountsLtHash, CalcAccountsHashConfig, CalculateHashIntermediate,
+            HashStats, IncrementalAccountsHash, SerdeAccountsDeltaHash, SerdeAccountsHash,
+            SerdeIncrementalAccountsHash, ZeroLamportAccounts,
         },
         accounts_index::{
             AccountIndexGetResult, AccountMapEntry, AccountSecondaryIndexes, AccountsIndex,
@@ -53,10 +53,13 @@ use {
         active_stats::{ActiveStatItem, ActiveStats},
         ancestors::Ancestors,
         ancient_append_vecs::{
-            get_ancient_append_vec_capacity, is_ancient, AccountsToStore, StorageSelector,
+            get_ancient_append_vec_capacity, is_ancient, AccountsToStore, AncientStorageTuning,
+            AncientStorageUsage, StorageSelector,
         },
         append_vec::{
-            aligned_stored_size, AppendVec, APPEND_VEC_MMAPPED_FILES_OPEN, STORE_META_OVERHEAD,
+            aligned_stored_size,
+            compression::{AccountDataCompressionConfig, COMPRESSION_STATS},
+            AppendVec, APPEND_VEC_MMAPPED_FILES_OPEN, STORE_META_OVERHEAD,
         },
         cache_hash_data::{
             CacheHashData, CacheHashDataFileReference, DeletionPolicy as CacheHashDeletionPolicy,
@@ -64,6 +67,7 @@ use {
         contains::Contains,
         epoch_accounts_hash::EpochAccountsHashManager,
         in_mem_accounts_index::StartupStats,
+        lattice_hash::LtHash,
         partitioned_rewards::{PartitionedEpochRewardsConfig, TestPartitionedEpochRewards},
         pubkey_bins::PubkeyBinCalculator24,
         read_only_accounts_cache::ReadOnlyAccountsCache,
@@ -106,7 +110,7 @@ use {
         ops::{Range, RangeBounds},
         path::{Path, PathBuf},
         sync::{
-            atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicUsize, Ordering},
+            atomic::{AtomicBool, AtomicI64, AtomicU32, AtomicU64, AtomicUsize, Ordering},
             Arc, Condvar, Mutex, RwLock,
         },
         thread::{sleep, Builder},
@@ -247,7 +251,11 @@ impl<'a> ShrinkCollectRefs<'a> for AliveAccounts<'a> {
         _slot_list: &[(Slot, AccountInfo)],
     ) {
         self.accounts.push(account);
-        self.bytes = self.bytes.saturating_add(account.stored_size());
+        // the space the account takes when it is rewritten, which is more than its stored size
+        // if its data is stored compressed and is rewritten uncompressed
+        self.bytes = self
+            .bytes
+            .saturating_add(aligned_stored_size(account.data_len() as usize));
     }
     fn len(&self) -> usize {
         self.accounts.len()
@@ -496,7 +504,10 @@ pub const ACCOUNTS_DB_CONFIG_FOR_TESTING: AccountsDbConfig = AccountsDbConfig {
     accounts_hash_cache_path: None,
     shrink_paths: None,
     write_cache_limit_bytes: None,
+    read_cache_limit_bytes: None,
     ancient_append_vec_offset: None,
+    ancient_storage_tuning: None,
+    account_data_compression: None,
     skip_initial_hash_calc: false,
     exhaustively_verify_refcounts: false,
     create_ancient_storage: CreateAncientStorage::Pack,
@@ -509,7 +520,10 @@ pub const ACCOUNTS_DB_CONFIG_FOR_BENCHMARKS: AccountsDbConfig = AccountsDbConfig
     accounts_hash_cache_path: None,
     shrink_paths: None,
     write_cache_limit_bytes: None,
+    read_cache_limit_bytes: None,
     ancient_append_vec_offset: None,
+    ancient_storage_tuning: None,
+    account_data_compression: None,
     skip_initial_hash_calc: false,
     exhaustively_verify_refcounts: false,
     create_ancient_storage: CreateAncientStorage::Pack,
@@ -551,9 +565,15 @@ pub struct AccountsDbConfig {
     pub accounts_hash_cache_path: Option<PathBuf>,
     pub shrink_paths: Option<Vec<PathBuf>>,
     pub write_cache_limit_bytes: Option<u64>,
+    /// if None, the read only accounts cache is bounded to 400M bytes
+    pub read_cache_limit_bytes: Option<usize>,
     /// if None, ancient append vecs are set to ANCIENT_APPEND_VEC_DEFAULT_OFFSET
     /// Some(offset) means include slots up to (max_slot - (slots_per_epoch - 'offset'))
     pub ancient_append_vec_offset: Option<i64>,
+    /// if None, ancient storages are packed and shrunk with the default thresholds
+    pub ancient_storage_tuning: Option<AncientStorageTuning>,
+    /// if None, account data is stored uncompressed
+    pub account_data_compression: Option<AccountDataCompressionConfig>,
     pub test_skip_rewrites_but_include_in_bank_hash: bool,
     pub skip_initial_hash_calc: bool,
     pub exhaustively_verify_refcounts: bool,
@@ -919,6 +939,9 @@ impl<'a> LoadedAccount<'a> {
 
     pub fn take_account(self) -> AccountSharedData {
         match self {
+            LoadedAccount::Stored(StoredAccountMeta::AppendVec(stored_account_meta)) => {
+                stored_account_meta.into_account_shared_data()
+            }
             LoadedAccount::Stored(stored_account_meta) => {
                 stored_account_meta.to_account_shared_data()
             }
@@ -935,6 +958,14 @@ impl<'a> LoadedAccount<'a> {
             LoadedAccount::Cached(_) => true,
         }
     }
+
+    /// Whether the account data is stored compressed
+    pub fn is_compressed(&self) -> bool {
+        match self {
+            LoadedAccount::Stored(stored_account_meta) => stored_account_meta.is_compressed(),
+            LoadedAccount::Cached(_) => false,
+        }
+    }
 }
 
 impl<'a> ReadableAccount for LoadedAccount<'a> {
@@ -1034,9 +1065,28 @@ pub struct AccountStorageEntry {
 
 impl AccountStorageEntry {
     pub fn new(path: &Path, slot: Slot, id: AppendVecId, file_size: u64) -> Self {
+        Self::new_with_compression(
+            path,
+            slot,
+            id,
+            file_size,
+            AccountDataCompressionConfig::default(),
+        )
+    }
+
+    /// Create a storage which compresses account data as `compression` configures
+    pub fn new_with_compression(
+        path: &Path,
+        slot: Slot,
+        id: AppendVecId,
+        file_size: u64,
+        compression: AccountDataCompressionConfig,
+    ) -> Self {
         let tail = AccountsFile::file_name(slot, id);
         let path = Path::new(path).join(tail);
-        let accounts = AccountsFile::AppendVec(AppendVec::new(&path, true, file_size as usize));
+        let accounts = AccountsFile::AppendVec(
+            AppendVec::new(&path, true, file_size as usize).with_compression(compression),
+        );
 
         Self {
             id: AtomicAppendVecId::new(id),
@@ -1358,7 +1408,14 @@ pub struct AccountsDb {
 
     /// Some(offset) iff we want to squash old append vecs together into 'ancient append vecs'
     /// Some(offset) means for slots up to (max_slot - (slots_per_epoch - 'offset')), put them in ancient append vecs
-    pub ancient_append_vec_offset: Option<i64>,
+    /// The offset may be adjusted at runtime, but ancient append vecs cannot be enabled or disabled.
+    pub(crate) ancient_append_vec_offset: Option<AtomicI64>,
+
+    /// thresholds for packing and shrinking ancient storages
+    ancient_storage_tuning: RwLock<AncientStorageTuning>,
+
+    /// which account data new storages compress
+    account_data_compression: AccountDataCompressionConfig,
 
     /// true iff we want to skip the initial hash calculation on startup
     pub skip_initial_hash_calc: bool,
@@ -1931,6 +1988,12 @@ pub(crate) struct ShrinkAncientStats {
     pub(crate) slots_considered: AtomicU64,
     pub(crate) ancient_scanned: AtomicU64,
     pub(crate) bytes_ancient_created: AtomicU64,
+    /// # of storages in ancient slots, as of the last ancient shrink
+    pub(crate) ancient_storages: AtomicU64,
+    /// total capacity of storages in ancient slots, as of the last ancient shrink
+    pub(crate) ancient_storage_bytes: AtomicU64,
+    /// bytes of storages in ancient slots not occupied by alive accounts, as of the last ancient shrink
+    pub(crate) ancient_storage_wasted_bytes: AtomicU64,
 }
 
 #[derive(Debug, Default)]
@@ -2239,6 +2302,21 @@ impl ShrinkAncientStats {
                 self.bytes_ancient_created.swap(0, Ordering::Relaxed) as i64,
                 i64
             ),
+            (
+                "ancient_storages",
+                self.ancient_storages.load(Ordering::Relaxed) as i64,
+                i64
+            ),
+            (
+                "ancient_storage_bytes",
+                self.ancient_storage_bytes.load(Ordering::Relaxed) as i64,
+                i64
+            ),
+            (
+                "ancient_storage_wasted_bytes",
+                self.ancient_storage_wasted_bytes.load(Ordering::Relaxed) as i64,
+                i64
+            ),
         );
     }
 }
@@ -2418,6 +2496,8 @@ impl AccountsDb {
             active_stats: ActiveStats::default(),
             skip_initial_hash_calc: false,
             ancient_append_vec_offset: None,
+            ancient_storage_tuning: RwLock::default(),
+            account_data_compression: AccountDataCompressionConfig::default(),
             accounts_index,
             storage: AccountStorage::default(),
             accounts_cache: AccountsCache::default(),
@@ -2518,7 +2598,16 @@ impl AccountsDb {
         let ancient_append_vec_offset = accounts_db_config
             .as_ref()
             .and_then(|config| config.ancient_append_vec_offset)
-            .or(ANCIENT_APPEND_VEC_DEFAULT_OFFSET);
+            .or(ANCIENT_APPEND_VEC_DEFAULT_OFFSET)
+            .map(AtomicI64::new);
+        let ancient_storage_tuning = accounts_db_config
+            .as_ref()
+            .and_then(|config| config.ancient_storage_tuning)
+            .unwrap_or_default();
+        let account_data_compression = accounts_db_config
+            .as_ref()
+            .and_then(|config| config.account_data_compression)
+            .unwrap_or_default();
 
         let exhaustively_verify_refcounts = accounts_db_config
             .as_ref()
@@ -2548,6 +2637,8 @@ impl AccountsDb {
             paths,
             skip_initial_hash_calc,
             ancient_append_vec_offset,
+            ancient_storage_tuning: RwLock::new(ancient_storage_tuning),
+            account_data_compression,
             cluster_type: Some(*cluster_type),
             account_indexes,
             shrink_ratio,
@@ -2573,6 +2664,13 @@ impl AccountsDb {
             new.paths = paths;
             new.temp_paths = Some(temp_dirs);
         };
+        if let Some(read_cache_limit_bytes) = accounts_db_config
+            .as_ref()
+            .and_then(|config| config.read_cache_limit_bytes)
+        {
+            new.read_only_accounts_cache
+                .set_max_data_size(read_cache_limit_bytes);
+        }
         new.shrink_paths = accounts_db_config
             .as_ref()
             .and_then(|config| config.shrink_paths.clone())
@@ -2603,7 +2701,13 @@ impl AccountsDb {
     }
 
     fn new_storage_entry(&self, slot: Slot, path: &Path, size: u64) -> AccountStorageEntry {
-        AccountStorageEntry::new(path, slot, self.next_id(), size)
+        AccountStorageEntry::new_with_compression(
+            path,
+            slot,
+            self.next_id(),
+            size,
+            self.account_data_compression,
+        )
     }
 
     pub fn expected_cluster_type(&self) -> ClusterType {
@@ -2897,7 +3001,7 @@ impl AccountsDb {
         max_root_inclusive: Slot,
     ) -> Slot {
         let mut result = max_root_inclusive;
-        if let Some(offset) = self.ancient_append_vec_offset {
+        if let Some(offset) = self.ancient_append_vec_offset() {
             result = Self::apply_offset_to_slot(result, offset);
         }
         result = Self::apply_offset_to_slot(
@@ -4068,6 +4172,7 @@ impl AccountsDb {
 
         Self::update_shrink_stats(&self.shrink_stats, stats_sub, true);
         self.shrink_stats.report();
+        COMPRESSION_STATS.report();
     }
 
     pub(crate) fn update_shrink_stats(
@@ -4327,12 +4432,79 @@ impl AccountsDb {
         let can_randomly_shrink = true;
         let sorted_slots = self.get_sorted_potential_ancient_slots(oldest_non_ancient_slot);
         if self.create_ancient_storage == CreateAncientStorage::Append {
-            self.combine_ancient_slots(sorted_slots, can_randomly_shrink);
+            self.combine_ancient_slots(sorted_slots.clone(), can_randomly_shrink);
+        } else {
+            self.combine_ancient_slots_packed(sorted_slots.clone(), can_randomly_shrink);
+        }
+        self.update_ancient_storage_usage(&sorted_slots);
+    }
+
+    /// measure the storages remaining in the ancient 'sorted_slots' once they have been combined
+    fn update_ancient_storage_usage(&self, sorted_slots: &[Slot]) {
+        let mut usage = AncientStorageUsage::default();
+        for slot in sorted_slots {
+            if let Some(storage) = self.storage.get_slot_storage_entry(*slot) {
+                let capacity = storage.capacity();
+                saturating_add_assign!(usage.storages, 1);
+                saturating_add_assign!(usage.bytes, capacity);
+                saturating_add_assign!(
+                    usage.wasted_bytes,
+                    capacity.saturating_sub(storage.alive_bytes() as u64)
+                );
+            }
+        }
+        let stats = &self.shrink_ancient_stats;
+        stats
+            .ancient_storages
+            .store(usage.storages, Ordering::Relaxed);
+        stats
+            .ancient_storage_bytes
+            .store(usage.bytes, Ordering::Relaxed);
+        stats
+            .ancient_storage_wasted_bytes
+            .store(usage.wasted_bytes, Ordering::Relaxed);
+    }
+
+    /// The size of the storages in ancient slots, as of the last ancient shrink
+    pub fn ancient_storage_usage(&self) -> AncientStorageUsage {
+        let stats = &self.shrink_ancient_stats;
+        AncientStorageUsage {
+            storages: stats.ancient_storages.load(Ordering::Relaxed),
+            bytes: stats.ancient_storage_bytes.load(Ordering::Relaxed),
+            wasted_bytes: stats.ancient_storage_wasted_bytes.load(Ordering::Relaxed),
+        }
+    }
+
+    /// The offset from an epoch before the highest root at which slots become ancient.
+    /// None if ancient append vecs are disabled.
+    pub fn ancient_append_vec_offset(&self) -> Option<i64> {
+        self.ancient_append_vec_offset
+            .as_ref()
+            .map(|offset| offset.load(Ordering::Relaxed))
+    }
+
+    /// Change the offset at which slots become ancient.
+    /// Returns false, without changing anything, if ancient append vecs are disabled.
+    pub fn set_ancient_append_vec_offset(&self, offset: i64) -> bool {
+        if let Some(current) = &self.ancient_append_vec_offset {
+            current.store(offset, Ordering::Relaxed);
+            true
         } else {
-            self.combine_ancient_slots_packed(sorted_slots, can_randomly_shrink);
+            false
         }
     }
 
+    /// The current thresholds for packing and shrinking ancient storages
+    pub fn ancient_storage_tuning(&self) -> AncientStorageTuning {
+        *self.ancient_storage_tuning.read().unwrap()
+    }
+
+    /// Change the thresholds for packing and shrinking ancient storages.
+    /// They take effect from the next ancient shrink.
+    pub fn set_ancient_storage_tuning(&self, tuning: AncientStorageTuning) {
+        *self.ancient_storage_tuning.write().unwrap() = tuning;
+    }
+
     /// 'accounts' that exist in the current slot we are combining into a different ancient slot
     /// 'existing_ancient_pubkeys': pubkeys that exist currently in the ancient append vec slot
     /// returns the pubkeys that are in 'accounts' that are already in 'existing_ancient_pubkeys'
@@ -4424,7 +4596,7 @@ impl AccountsDb {
             let mut alive_ratio = 0;
             let is_candidate = if written_bytes > 0 {
                 alive_ratio = (storage.alive_bytes() as u64) * 100 / written_bytes;
-                alive_ratio < 90
+                alive_ratio < self.ancient_storage_tuning().shrink_alive_percent
             } else {
                 false
             };
@@ -4696,6 +4868,7 @@ impl AccountsDb {
                         &shrink_candidates_slots,
                         shrink_ratio,
                         self.ancient_append_vec_offset
+                            .as_ref()
                             .map(|_| oldest_non_ancient_slot),
                     );
                 (shrink_slots, Some(shrink_slots_next_batch))
@@ -4809,7 +4982,50 @@ impl AccountsDb {
                 let account_slot = self
                     .get_account_accessor(slot, pubkey, &account_info.storage_location())
                     .get_loaded_account()
-                    .map(|loaded_account| (pubkey, loaded_account.take_account(), slot));
+                    .map(|loaded_account| {
+                        (
+                            pubkey,
+                            self.take_scanned_account(pubkey, slot, loaded_account),
+                            slot,
+                        )
+                    });
+                scan_func(account_slot)
+            },
+            config,
+        )?;
+
+        Ok(())
+    }
+
+    /// Like `scan_accounts`, but only scans the accounts with pubkeys in `range`
+    pub fn checked_range_scan_accounts<F, R>(
+        &self,
+        ancestors: &Ancestors,
+        bank_id: BankId,
+        range: R,
+        mut scan_func: F,
+        config: &ScanConfig,
+    ) -> ScanResult<()>
+    where
+        F: FnMut(Option<(&Pubkey, AccountSharedData, Slot)>),
+        R: RangeBounds<Pubkey> + std::fmt::Debug,
+    {
+        // This can error out if the slots being scanned over are aborted
+        self.accounts_index.checked_range_scan_accounts(
+            ancestors,
+            bank_id,
+            range,
+            |pubkey, (account_info, slot)| {
+                let account_slot = self
+                    .get_account_accessor(slot, pubkey, &account_info.storage_location())
+                    .get_loaded_account()
+                    .map(|loaded_account| {
+                        (
+                            pubkey,
+                            self.take_scanned_account(pubkey, slot, loaded_account),
+                            slot,
+                        )
+                    });
                 scan_func(account_slot)
             },
             config,
@@ -4818,6 +5034,29 @@ impl AccountsDb {
         Ok(())
     }
 
+    /// The account `loaded_account` of `pubkey` in `slot`, found by a scan.
+    /// Compressed accounts are loaded through the read only cache like other
+    /// loads, so scans do not decompress them again and again.
+    fn take_scanned_account(
+        &self,
+        pubkey: &Pubkey,
+        slot: Slot,
+        loaded_account: LoadedAccount,
+    ) -> AccountSharedData {
+        if !loaded_account.is_compressed() {
+            return loaded_account.take_account();
+        }
+        if let Some(account) = self.read_only_accounts_cache.load(*pubkey, slot) {
+            return account;
+        }
+        let account = loaded_account.take_account();
+        // see do_load_with_populate_read_cache() for why storing accounts
+        // loaded from storages in the read only cache is safe
+        self.read_only_accounts_cache
+            .store(*pubkey, slot, account.clone());
+        account
+    }
+
     pub fn unchecked_scan_accounts<F>(
         &self,
         metric_name: &'static str,
@@ -4872,7 +5111,13 @@ impl AccountsDb {
                 if let Some(account_slot) = self
                     .get_account_accessor(slot, pubkey, &account_info.storage_location())
                     .get_loaded_account()
-                    .map(|loaded_account| (pubkey, loaded_account.take_account(), slot))
+                    .map(|loaded_account| {
+                        (
+                            pubkey,
+                            self.take_scanned_account(pubkey, slot, loaded_account),
+                            slot,
+                        )
+                    })
                 {
                     scan_func(Some(account_slot))
                 }
@@ -4895,6 +5140,7 @@ impl AccountsDb {
             IndexKey::ProgramId(key) => key,
             IndexKey::SplTokenMint(key) => key,
             IndexKey::SplTokenOwner(key) => key,
+            IndexKey::ProgramIdDataSize(key, _) => key,
         };
         if !self.account_indexes.include_key(key) {
             // the requested key was not indexed in the secondary index, so do a normal scan
@@ -4911,7 +5157,13 @@ impl AccountsDb {
                 let account_slot = self
                     .get_account_accessor(slot, pubkey, &account_info.storage_location())
                     .get_loaded_account()
-                    .map(|loaded_account| (pubkey, loaded_account.take_account(), slot));
+                    .map(|loaded_account| {
+                        (
+                            pubkey,
+                            self.take_scanned_account(pubkey, slot, loaded_account),
+                            slot,
+                        )
+                    });
                 scan_func(account_slot)
             },
             config,
@@ -5407,6 +5659,26 @@ impl AccountsDb {
         )
     }
 
+    /// The current upper bound, in bytes, on the read only accounts cache
+    pub fn read_only_accounts_cache_max_data_size(&self) -> usize {
+        self.read_only_accounts_cache.max_data_size()
+    }
+
+    /// The number of bytes and entries currently held in the read only accounts cache
+    pub fn read_only_accounts_cache_usage(&self) -> (usize, usize) {
+        (
+            self.read_only_accounts_cache.data_size(),
+            self.read_only_accounts_cache.cache_len(),
+        )
+    }
+
+    /// Change the upper bound, in bytes, on the read only accounts cache.
+    /// Lowering the bound evicts least recently used entries immediately.
+    pub fn set_read_only_accounts_cache_max_data_size(&self, max_data_size: usize) {
+        self.read_only_accounts_cache
+            .set_max_data_size(max_data_size);
+    }
+
     /// remove all entries from the read only accounts cache
     /// useful for benches/tests
     pub fn flush_read_only_cache_for_tests(&self) {
@@ -6166,6 +6438,134 @@ impl AccountsDb {
         AccountHash(Hash::new_from_array(hasher.finalize().into()))
     }
 
+    /// The lattice hash of `account`, which does not depend on its rent epoch.
+    /// Zero-lamport accounts hash to the identity, so they are not part of
+    /// the accounts lattice hash.
+    pub fn lt_hash_account<T: ReadableAccount>(account: &T, pubkey: &Pubkey) -> AccountLtHash {
+        if account.lamports() == 0 {
+            return AccountLtHash(LtHash::identity());
+        }
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(&account.lamports().to_le_bytes());
+        hasher.update(account.data());
+        hasher.update(&[account.executable().into()]);
+        hasher.update(account.owner().as_ref());
+        hasher.update(pubkey.as_ref());
+        AccountLtHash(LtHash::with(&hasher))
+    }
+
+    /// Calculates the lattice hash of the accounts in `storages` from
+    /// scratch, from the newest version of each account, in the same way the
+    /// accounts hash is calculated from them
+    pub fn calculate_accounts_lt_hash_from_storages(
+        &self,
+        storages: &[Arc<AccountStorageEntry>],
+    ) -> AccountsLtHash {
+        // storages are scanned in parallel into bins by pubkey, so each bin
+        // can find the newest version of its accounts independently
+        const BINS: usize = 4096;
+        let bin_calculator = PubkeyBinCalculator24::new(BINS);
+        let mut storages: Vec<_> = storages.iter().collect();
+        storages.sort_unstable_by_key(|storage| std::cmp::Reverse(storage.slot()));
+
+        self.thread_pool_clean.install(|| {
+            let bins = storages
+                .par_iter()
+                .enumerate()
+                .fold(
+                    || vec![Vec::new(); BINS],
+                    |mut bins, (storage_index, storage)| {
+                        storage.accounts.account_iter().for_each(|account| {
+                            let pubkey = *account.pubkey();
+                            bins[bin_calculator.bin_from_pubkey(&pubkey)].push((
+                                pubkey,
+                                storage_index,
+                                account.offset(),
+                            ));
+                        });
+                        bins
+                    },
+                )
+                .reduce(
+                    || vec![Vec::new(); BINS],
+                    |mut bins, other| {
+                        bins.iter_mut()
+                            .zip(other)
+                            .for_each(|(bin, mut other)| bin.append(&mut other));
+                        bins
+                    },
+                );
+
+            bins.into_par_iter()
+                .map(|mut bin| {
+                    // the newest storage comes first, and a later version of
+                    // an account in the same storage replaces an earlier one
+                    bin.sort_unstable_by_key(|(pubkey, storage_index, offset)| {
+                        (*pubkey, *storage_index, std::cmp::Reverse(*offset))
+                    });
+                    bin.dedup_by_key(|(pubkey, _, _)| *pubkey);
+                    let mut accounts_lt_hash = AccountsLtHash::identity();
+                    for (pubkey, storage_index, offset) in bin {
+                        let (account, _) = storages[storage_index]
+                            .accounts
+                            .get_account(offset)
+                            .expect("account must exist at the offset it was scanned at");
+                        accounts_lt_hash
+                            .0
+                            .mix_in(&Self::lt_hash_account(&account, &pubkey).0);
+                    }
+                    accounts_lt_hash
+                })
+                .reduce(AccountsLtHash::identity, |mut accum, accounts_lt_hash| {
+                    accum.0.mix_in(&accounts_lt_hash.0);
+                    accum
+                })
+        })
+    }
+
+    /// Calculates the lattice hash from scratch, from all accounts which
+    /// `ancestors` can load, in the same way the accounts hash is calculated
+    /// from the index
+    pub fn calculate_accounts_lt_hash_from_index(&self, ancestors: &Ancestors) -> AccountsLtHash {
+        self.thread_pool_clean.install(|| {
+            self.accounts_index
+                .account_maps
+                .par_iter()
+                .map(|map| {
+                    let mut accounts_lt_hash = AccountsLtHash::identity();
+                    for pubkey in map.keys() {
+                        if let AccountIndexGetResult::Found(lock, index) =
+                            self.accounts_index.get(&pubkey, Some(ancestors), None)
+                        {
+                            let (slot, account_info) = &lock.slot_list()[index];
+                            if account_info.is_zero_lamport() {
+                                continue;
+                            }
+                            // Because we're keeping the `lock' here, there is no
+                            // need to use retry_to_get_account_accessor()
+                            if let Some(loaded_account) = self
+                                .get_account_accessor(
+                                    *slot,
+                                    &pubkey,
+                                    &account_info.storage_location(),
+                                )
+                                .get_loaded_account()
+                            {
+                                accounts_lt_hash
+                                    .0
+                                    .mix_in(&Self::lt_hash_account(&loaded_account, &pubkey).0);
+                            }
+                        }
+                    }
+                    accounts_lt_hash
+                })
+                .reduce(AccountsLtHash::identity, |mut accum, accounts_lt_hash| {
+                    accum.0.mix_in(&accounts_lt_hash.0);
+                    accum
+                })
+        })
+    }
+
     fn bulk_assign_write_version(&self, count: usize) -> StoredMetaWriteVersion {
         self.write_version
             .fetch_add(count as StoredMetaWriteVersion, Ordering::AcqRel)
@@ -6638,6 +7038,7 @@ impl AccountsDb {
         slot: Slot,
         accounts_and_meta_to_store: &impl StorableAccounts<'b, T>,
         txn_iter: Box<dyn std::iter::Iterator<Item = &Option<&SanitizedTransaction>> + 'a>,
+        ancestors: Option<&Ancestors>,
         mut write_version_producer: P,
     ) -> Vec<AccountInfo>
     where
@@ -6657,6 +7058,7 @@ impl AccountsDb {
                     &account,
                     txn,
                     accounts_and_meta_to_store.pubkey(i),
+                    ancestors,
                     &mut write_version_producer,
                 );
 
@@ -6688,6 +7090,7 @@ impl AccountsDb {
         mut write_version_producer: P,
         store_to: &StoreTo,
         transactions: Option<&[Option<&'a SanitizedTransaction>]>,
+        ancestors: Option<&Ancestors>,
     ) -> Vec<AccountInfo> {
         let mut calc_stored_meta_time = Measure::start("calc_stored_meta");
         let slot = accounts.target_slot();
@@ -6711,7 +7114,13 @@ impl AccountsDb {
                         None => Box::new(std::iter::repeat(&None).take(accounts.len())),
                     };
 
-                self.write_accounts_to_cache(slot, accounts, txn_iter, write_version_producer)
+                self.write_accounts_to_cache(
+                    slot,
+                    accounts,
+                    txn_iter,
+                    ancestors,
+                    write_version_producer,
+                )
             }
             StoreTo::Storage(storage) => {
                 if accounts.has_hash_and_write_version() {
@@ -8399,11 +8808,14 @@ impl AccountsDb {
             accounts,
             &StoreTo::Cache,
             transactions,
+            None,
             StoreReclaims::Default,
             UpdateIndexThreadSelection::PoolWithThreshold,
         );
     }
 
+    /// Stores the accounts written by committed `transactions`. The
+    /// `ancestors` of the slot tell which of the accounts are closed.
     pub(crate) fn store_cached_inline_update_index<
         'a,
         T: ReadableAccount + Sync + ZeroLamport + 'a,
@@ -8411,11 +8823,13 @@ impl AccountsDb {
         &self,
         accounts: impl StorableAccounts<'a, T>,
         transactions: Option<&'a [Option<&'a SanitizedTransaction>]>,
+        ancestors: &Ancestors,
     ) {
         self.store(
             accounts,
             &StoreTo::Cache,
             transactions,
+            Some(ancestors),
             StoreReclaims::Default,
             UpdateIndexThreadSelection::Inline,
         );
@@ -8429,6 +8843,7 @@ impl AccountsDb {
             (slot, accounts),
             &StoreTo::Storage(&storage),
             None,
+            None,
             StoreReclaims::Default,
             UpdateIndexThreadSelection::PoolWithThreshold,
         );
@@ -8439,6 +8854,7 @@ impl AccountsDb {
         accounts: impl StorableAccounts<'a, T>,
         store_to: &StoreTo,
         transactions: Option<&'a [Option<&'a SanitizedTransaction>]>,
+        ancestors: Option<&Ancestors>,
         reclaim: StoreReclaims,
         update_index_thread_selection: UpdateIndexThreadSelection,
     ) {
@@ -8476,6 +8892,7 @@ impl AccountsDb {
             None::<Vec<AccountHash>>,
             store_to,
             transactions,
+            ancestors,
             reclaim,
             update_index_thread_selection,
         );
@@ -8544,6 +8961,11 @@ impl AccountsDb {
                     self.read_only_accounts_cache.data_size(),
                     i64
                 ),
+                (
+                    "read_only_accounts_cache_max_data_size",
+                    self.read_only_accounts_cache.max_data_size(),
+                    i64
+                ),
                 ("read_only_accounts_cache_hits", read_only_cache_hits, i64),
                 (
                     "read_only_accounts_cache_misses",
@@ -8630,6 +9052,7 @@ impl AccountsDb {
         hashes: Option<Vec<impl Borrow<AccountHash>>>,
         store_to: &StoreTo,
         transactions: Option<&'a [Option<&'a SanitizedTransaction>]>,
+        ancestors: Option<&Ancestors>,
         reclaim: StoreReclaims,
         update_index_thread_selection: UpdateIndexThreadSelection,
     ) {
@@ -8648,6 +9071,7 @@ impl AccountsDb {
             store_to,
             reset_accounts,
             transactions,
+            ancestors,
             reclaim,
             update_index_thread_selection,
         );
@@ -8672,6 +9096,7 @@ impl AccountsDb {
             &StoreTo::Storage(storage),
             reset_accounts,
             None,
+            None,
             reclaim,
             UpdateIndexThreadSelection::PoolWithThreshold,
         )
@@ -8685,6 +9110,7 @@ impl AccountsDb {
         store_to: &StoreTo,
         reset_accounts: bool,
         transactions: Option<&[Option<&SanitizedTransaction>]>,
+        ancestors: Option<&Ancestors>,
         reclaim: StoreReclaims,
         update_index_thread_selection: UpdateIndexThreadSelection,
     ) -> StoreAccountsTiming {
@@ -8708,6 +9134,7 @@ impl AccountsDb {
             write_version_producer,
             store_to,
             transactions,
+            ancestors,
         );
         store_accounts_time.stop();
         self.stats
@@ -9561,6 +9988,7 @@ impl AccountsDb {
             (slot, accounts),
             &StoreTo::Cache,
             None,
+            None,
             StoreReclaims::Default,
             UpdateIndexThreadSelection::PoolWithThreshold,
         );
@@ -9766,7 +10194,10 @@ pub mod tests {
                 tests::*, AccountSecondaryIndexesIncludeExclude, ReadAccountMapEntry, RefCount,
             },
             ancient_append_vecs,
-            append_vec::{test_utils::TempFile, AppendVecStoredAccountMeta},
+            append_vec::{
+                compression::AccountDataCompression, test_utils::TempFile,
+                AppendVecStoredAccountMeta,
+            },
             cache_hash_data::CacheHashDataFile,
             inline_spl_token,
         },
@@ -9783,7 +10214,7 @@ pub mod tests {
         std::{
             iter::FromIterator,
             str::FromStr,
-            sync::atomic::AtomicBool,
+            sync::{atomic::AtomicBool, OnceLock},
             thread::{self, Builder, JoinHandle},
         },
         test_case::test_case,
@@ -10017,6 +10448,8 @@ pub mod tests {
             // account data
             account_meta: &account_meta,
             data: account.data(),
+            compression: AccountDataCompression::None,
+            decompressed_data: OnceLock::default(),
             offset,
             stored_size: account_size,
             hash: &hash,
@@ -10112,6 +10545,8 @@ pub mod tests {
             meta: &meta,
             account_meta: &account_meta,
             data: &data,
+            compression: AccountDataCompression::None,
+            decompressed_data: OnceLock::default(),
             offset,
             stored_size,
             hash: &hash,
@@ -10120,6 +10555,8 @@ pub mod tests {
             meta: &meta2,
             account_meta: &account_meta,
             data: &data,
+            compression: AccountDataCompression::None,
+            decompressed_data: OnceLock::default(),
             offset,
             stored_size,
             hash: &hash,
@@ -10128,6 +10565,8 @@ pub mod tests {
             meta: &meta3,
             account_meta: &account_meta,
             data: &data,
+            compression: AccountDataCompression::None,
+            decompressed_data: OnceLock::default(),
             offset,
             stored_size,
             hash: &hash,
@@ -10136,6 +10575,8 @@ pub mod tests {
             meta: &meta4,
             account_meta: &account_meta,
             data: &data,
+            compression: AccountDataCompression::None,
+            decompressed_data: OnceLock::default(),
             offset,
             stored_size,
             hash: &hash,
@@ -12437,6 +12878,8 @@ pub mod tests {
             meta: &meta,
             account_meta: &account_meta,
             data: &data,
+            compression: AccountDataCompression::None,
+            decompressed_data: OnceLock::default(),
             offset,
             stored_size,
             hash: &hash,
@@ -12483,6 +12926,8 @@ pub mod tests {
             meta: &meta,
             account_meta: &account_meta,
             data: &data,
+            compression: AccountDataCompression::None,
+            decompressed_data: OnceLock::default(),
             offset,
             stored_size: CACHE_VIRTUAL_STORED_SIZE as usize,
             hash: &hash,
@@ -12504,6 +12949,77 @@ pub mod tests {
         );
     }
 
+    #[test]
+    fn test_lt_hash_account() {
+        let pubkey = Pubkey::new_unique();
+        let account = AccountSharedData::new(1, 10, &Pubkey::new_unique());
+        let account_lt_hash = AccountsDb::lt_hash_account(&account, &pubkey);
+        assert_ne!(account_lt_hash.0, LtHash::identity());
+        assert_ne!(
+            AccountsDb::lt_hash_account(&account, &Pubkey::new_unique()),
+            account_lt_hash
+        );
+
+        // the rent epoch is not hashed
+        let mut rent_epoch_account = account.clone();
+        rent_epoch_account.set_rent_epoch(42);
+        assert_eq!(
+            AccountsDb::lt_hash_account(&rent_epoch_account, &pubkey),
+            account_lt_hash
+        );
+
+        let zero_lamport_account = AccountSharedData::new(0, 10, &Pubkey::new_unique());
+        assert_eq!(
+            AccountsDb::lt_hash_account(&zero_lamport_account, &pubkey).0,
+            LtHash::identity()
+        );
+    }
+
+    #[test]
+    fn test_calculate_accounts_lt_hash_from_storages() {
+        let db = AccountsDb::new_single_for_tests();
+        let pubkey = Pubkey::new_unique();
+        let other_pubkey = Pubkey::new_unique();
+        let closed_pubkey = Pubkey::new_unique();
+        let account = AccountSharedData::new(1, 10, &Pubkey::default());
+        let updated_account = AccountSharedData::new(2, 10, &Pubkey::default());
+        db.store_for_tests(
+            0,
+            &[
+                (&pubkey, &account),
+                (&other_pubkey, &account),
+                (&closed_pubkey, &account),
+            ],
+        );
+        db.add_root_and_flush_write_cache(0);
+        db.store_for_tests(
+            1,
+            &[
+                (&pubkey, &updated_account),
+                (&closed_pubkey, &AccountSharedData::default()),
+            ],
+        );
+        db.add_root_and_flush_write_cache(1);
+
+        // only the newest version of each account is hashed
+        let mut expected = AccountsLtHash::identity();
+        expected
+            .0
+            .mix_in(&AccountsDb::lt_hash_account(&updated_account, &pubkey).0);
+        expected
+            .0
+            .mix_in(&AccountsDb::lt_hash_account(&account, &other_pubkey).0);
+        let (storages, _slots) = db.get_snapshot_storages(..=1);
+        assert_eq!(
+            db.calculate_accounts_lt_hash_from_storages(&storages),
+            expected
+        );
+        assert_eq!(
+            db.calculate_accounts_lt_hash_from_index(&Ancestors::default()),
+            expected
+        );
+    }
+
     #[test]
     fn test_bank_hash_stats() {
         solana_logger::setup();
@@ -12550,6 +13066,7 @@ pub mod tests {
             Some(vec![&AccountHash(Hash::default())]),
             &StoreTo::Storage(&db.find_storage_candidate(some_slot, 1)),
             None,
+            None,
             StoreReclaims::Default,
             UpdateIndexThreadSelection::PoolWithThreshold,
         );
@@ -12783,6 +13300,7 @@ pub mod tests {
             Some(vec![&some_hash]),
             &StoreTo::Storage(&db.find_storage_candidate(some_slot, 1)),
             None,
+            None,
             StoreReclaims::Default,
             UpdateIndexThreadSelection::PoolWithThreshold,
         );
@@ -13961,6 +14479,66 @@ pub mod tests {
         assert_eq!(db.read_only_accounts_cache.cache_len(), 1);
     }
 
+    #[test]
+    fn test_read_only_accounts_cache_scan_compressed() {
+        let mut db = AccountsDb::new_single_for_tests();
+        db.account_data_compression = AccountDataCompressionConfig {
+            compression: AccountDataCompression::Lz4,
+            min_data_len: 1024,
+        };
+
+        let compressed_key = Pubkey::new_unique();
+        let mut compressed_account =
+            AccountSharedData::new(1, 0, AccountSharedData::default().owner());
+        compressed_account.set_data_from_slice(&[7; 4096]);
+        let uncompressed_key = Pubkey::new_unique();
+        let uncompressed_account =
+            AccountSharedData::new(1, 1, AccountSharedData::default().owner());
+        db.store_cached(
+            (
+                0,
+                &[
+                    (&compressed_key, &compressed_account),
+                    (&uncompressed_key, &uncompressed_account),
+                ][..],
+            ),
+            None,
+        );
+        db.add_root(0);
+        db.flush_accounts_cache(true, None);
+        assert!(db
+            .storage
+            .get_slot_storage_entry(0)
+            .unwrap()
+            .accounts
+            .has_compressed_accounts());
+
+        // scans load compressed accounts through the read only cache
+        for _ in 0..2 {
+            let mut accounts = Vec::new();
+            db.scan_accounts(
+                &Ancestors::default(),
+                0,
+                |maybe_account| {
+                    if let Some((pubkey, account, _slot)) = maybe_account {
+                        accounts.push((*pubkey, account));
+                    }
+                },
+                &ScanConfig::default(),
+            )
+            .unwrap();
+            accounts.sort_unstable_by_key(|(pubkey, _)| *pubkey);
+            let mut expected = vec![
+                (compressed_key, compressed_account.clone()),
+                (uncompressed_key, uncompressed_account.clone()),
+            ];
+            expected.sort_unstable_by_key(|(pubkey, _)| *pubkey);
+            assert_eq!(accounts, expected);
+            assert_eq!(db.read_only_accounts_cache.cache_len(), 1);
+            assert!(db.read_only_accounts_cache.in_cache(&compressed_key, 0));
+        }
+    }
+
     #[test]
     fn test_account_matches_owners() {
         let db = Arc::new(AccountsDb::new_single_for_tests());
@@ -16149,7 +16727,7 @@ pub mod tests {
             expected(0)
         );
         // ancient append vecs enabled (but at 0 offset), so can be non-zero
-        db.ancient_append_vec_offset = Some(0);
+        db.ancient_append_vec_offset = Some(AtomicI64::new(0));
         // 0..=(slots_per_epoch - 1) are all non-ancient
         assert_eq!(
             db.get_oldest_non_ancient_slot_for_hash_calc_scan(slots_per_epoch - 1, &config),
@@ -16782,7 +17360,7 @@ pub mod tests {
                 // before any roots are added, we expect the oldest non-ancient slot to be 0
                 assert_eq!(0, db.get_oldest_non_ancient_slot(&epoch_schedule));
 
-                let ancient_append_vec_offset = db.ancient_append_vec_offset.unwrap();
+                let ancient_append_vec_offset = db.ancient_append_vec_offset().unwrap();
                 assert_ne!(ancient_append_vec_offset, 0);
                 // try a few values to simulate a real validator
                 for inc in [0, 1, 2, 3, 4, 5, 8, 10, 10, 11, 200, 201, 1_000] {
@@ -16891,7 +17469,7 @@ pub mod tests {
     #[test]
     fn test_get_sorted_potential_ancient_slots() {
         let db = AccountsDb::new_single_for_tests();
-        let ancient_append_vec_offset = db.ancient_append_vec_offset.unwrap();
+        let ancient_append_vec_offset = db.ancient_append_vec_offset().unwrap();
         let epoch_schedule = EpochSchedule::default();
         let oldest_non_ancient_slot = db.get_oldest_non_ancient_slot(&epoch_schedule);
         assert!(db
@@ -2,11 +2,15 @@ use {
     crate::{
         account_storage::meta::{StoredAccountMeta, StoredMeta},
         accounts_db::AccountsDb,
+        ancestors::Ancestors,
     },
     solana_measure::measure::Measure,
     solana_metrics::*,
     solana_sdk::{
-        account::AccountSharedData, clock::Slot, pubkey::Pubkey, transaction::SanitizedTransaction,
+        account::{AccountSharedData, ReadableAccount},
+        clock::Slot,
+        pubkey::Pubkey,
+        transaction::SanitizedTransaction,
     },
     std::collections::{HashMap, HashSet},
 };
@@ -60,24 +64,37 @@ impl AccountsDb {
         notify_stats.report();
     }
 
+    /// Notifies of an account update at `slot`, before it is stored. If the
+    /// `ancestors` of `slot` are known, i.e. when committing transactions, an
+    /// update to zero lamports of an account which exists on the fork is also
+    /// notified of as the account being closed.
     pub fn notify_account_at_accounts_update<P>(
         &self,
         slot: Slot,
         account: &AccountSharedData,
         txn: &Option<&SanitizedTransaction>,
         pubkey: &Pubkey,
+        ancestors: Option<&Ancestors>,
         write_version_producer: &mut P,
     ) where
         P: Iterator<Item = u64>,
     {
         if let Some(accounts_update_notifier) = &self.accounts_update_notifier {
+            let write_version = write_version_producer.next().unwrap();
             accounts_update_notifier.notify_account_update(
                 slot,
                 account,
                 txn,
                 pubkey,
-                write_version_producer.next().unwrap(),
+                write_version,
             );
+            let is_closed = account.lamports() == 0
+                && ancestors.is_some_and(|ancestors| {
+                    self.load_with_fixed_root(ancestors, pubkey).is_some()
+                });
+            if is_closed {
+                accounts_update_notifier.notify_account_closed(slot, txn, pubkey, write_version);
+            }
         }
     }
 
@@ -159,6 +176,7 @@ pub mod tests {
             accounts_update_notifier_interface::{
                 AccountsUpdateNotifier, AccountsUpdateNotifierInterface,
             },
+            ancestors::Ancestors,
         },
         dashmap::DashMap,
         solana_sdk::{
@@ -182,6 +200,7 @@ pub mod tests {
     #[derive(Debug, Default)]
     struct GeyserTestPlugin {
         pub accounts_notified: DashMap<Pubkey, Vec<(Slot, AccountSharedData)>>,
+        pub accounts_closed: DashMap<Pubkey, Vec<(Slot, u64)>>,
         pub is_startup_done: AtomicBool,
     }
 
@@ -201,6 +220,19 @@ pub mod tests {
                 .push((slot, account.clone()));
         }
 
+        fn notify_account_closed(
+            &self,
+            slot: Slot,
+            _txn: &Option<&SanitizedTransaction>,
+            pubkey: &Pubkey,
+            write_version: u64,
+        ) {
+            self.accounts_closed
+                .entry(*pubkey)
+                .or_default()
+                .push((slot, write_version));
+        }
+
         /// Notified when the AccountsDb is initialized at start when restored
         /// from a snapshot.
         fn notify_account_restore_from_snapshot(&self, slot: Slot, account: &StoredAccountMeta) {
@@ -395,4 +427,74 @@ pub mod tests {
         );
         assert_eq!(notifier.accounts_notified.get(&key3).unwrap()[0].0, slot1);
     }
+
+    #[test]
+    fn test_notify_account_closed() {
+        let mut accounts = AccountsDb::new_single_for_tests();
+        let notifier = Arc::new(GeyserTestPlugin::default());
+        accounts.set_geyser_plugin_notifer(Some(notifier.clone()));
+
+        let key = solana_sdk::pubkey::new_rand();
+        let new_key = solana_sdk::pubkey::new_rand();
+        let account = AccountSharedData::new(1, 1, AccountSharedData::default().owner());
+        let closed_account = AccountSharedData::new(0, 0, AccountSharedData::default().owner());
+        let store = |slot: Slot, ancestors: &[Slot], key: &Pubkey, account: &AccountSharedData| {
+            let ancestors = Ancestors::from(ancestors.to_vec());
+            accounts.store_cached_inline_update_index(
+                (slot, &[(key, account)][..]),
+                None,
+                &ancestors,
+            );
+        };
+        let closed_slots = |key: &Pubkey| {
+            notifier
+                .accounts_closed
+                .get(key)
+                .map(|closed| closed.iter().map(|(slot, _)| *slot).collect::<Vec<_>>())
+                .unwrap_or_default()
+        };
+
+        let slot0 = 0;
+        store(slot0, &[slot0], &key, &account);
+        // an account which never existed is not closed by updating it to zero
+        // lamports
+        store(slot0, &[slot0], &new_key, &closed_account);
+        assert!(closed_slots(&key).is_empty());
+        assert!(closed_slots(&new_key).is_empty());
+
+        let slot1 = 1;
+        store(slot1, &[slot0, slot1], &key, &closed_account);
+        // the account is reopened in the same slot, after it was closed, so
+        // it is closed again by the next update to zero lamports
+        store(slot1, &[slot0, slot1], &key, &account);
+        store(slot1, &[slot0, slot1], &key, &closed_account);
+        store(slot1, &[slot0, slot1], &new_key, &account);
+        assert_eq!(closed_slots(&key), vec![slot1, slot1]);
+
+        // updating a closed account to zero lamports does not close it again
+        let slot2 = 2;
+        store(slot2, &[slot0, slot1, slot2], &key, &closed_account);
+        assert_eq!(closed_slots(&key), vec![slot1, slot1]);
+
+        // on a fork off slot 0, the account still exists, while the account
+        // opened at slot 1 does not
+        let slot3 = 3;
+        store(slot3, &[slot0, slot3], &key, &closed_account);
+        store(slot3, &[slot0, slot3], &new_key, &closed_account);
+        assert_eq!(closed_slots(&key), vec![slot1, slot1, slot3]);
+        assert!(closed_slots(&new_key).is_empty());
+
+        // stores outside of transaction processing do not tell closures
+        let slot4 = 4;
+        accounts.store_cached((slot4, &[(&new_key, &closed_account)][..]), None);
+        assert!(closed_slots(&new_key).is_empty());
+
+        // each closure is ordered among the account updates by their write
+        // version
+        let updates = notifier.accounts_notified.get(&key).unwrap();
+        assert_eq!(updates.len(), 6);
+        let closed = notifier.accounts_closed.get(&key).unwrap();
+        assert!(closed[0].1 < closed[1].1);
+        assert!(closed[1].1 < closed[2].1);
+    }
 }
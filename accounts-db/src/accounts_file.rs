@@ -103,6 +103,13 @@ impl AccountsFile {
         }
     }
 
+    /// Whether any account stored in this file has compressed data
+    pub fn has_compressed_accounts(&self) -> bool {
+        match self {
+            Self::AppendVec(av) => av.has_compressed_accounts(),
+        }
+    }
+
     pub fn is_recyclable(&self) -> bool {
         match self {
             Self::AppendVec(_) => true,
@@ -3,6 +3,7 @@ use {
         accounts_db::{AccountStorageEntry, PUBKEY_BINS_FOR_CALCULATING_HASHES},
         active_stats::{ActiveStatItem, ActiveStats},
         ancestors::Ancestors,
+        lattice_hash::LtHash,
         pubkey_bins::PubkeyBinCalculator24,
     },
     bytemuck::{Pod, Zeroable},
@@ -1270,6 +1271,29 @@ pub struct IncrementalAccountsHash(pub Hash);
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub struct AccountsDeltaHash(pub Hash);
 
+/// Lattice hash of an account
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct AccountLtHash(pub LtHash);
+
+/// Lattice hash of all accounts, maintained incrementally from the accounts
+/// written in each slot
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct AccountsLtHash(pub LtHash);
+
+impl AccountsLtHash {
+    /// The lattice hash of no accounts
+    pub const fn identity() -> Self {
+        Self(LtHash::identity())
+    }
+}
+
+#[cfg(RUSTC_WITH_SPECIALIZATION)]
+impl solana_frozen_abi::abi_example::AbiExample for AccountsLtHash {
+    fn example() -> Self {
+        Self::identity()
+    }
+}
+
 /// Snapshot serde-safe accounts delta hash
 #[derive(Clone, Default, Debug, Serialize, Deserialize, PartialEq, Eq, AbiExample)]
 pub struct SerdeAccountsDeltaHash(pub Hash);
@@ -100,6 +100,10 @@ pub struct ScanConfig {
     /// true to allow return of all matching items and allow them to be unsorted.
     /// This is more efficient.
     pub collect_all_unsorted: bool,
+
+    /// true to scan in descending pubkey order, rather than ascending.
+    /// Ignored if `collect_all_unsorted` is true.
+    pub descending: bool,
 }
 
 impl ScanConfig {
@@ -122,6 +126,7 @@ impl ScanConfig {
         ScanConfig {
             abort: Some(self.abort.as_ref().map(Arc::clone).unwrap_or_default()),
             collect_all_unsorted: self.collect_all_unsorted,
+            descending: self.descending,
         }
     }
 
@@ -166,6 +171,8 @@ pub enum IndexKey {
     ProgramId(Pubkey),
     SplTokenMint(Pubkey),
     SplTokenOwner(Pubkey),
+    /// Program id and account data length
+    ProgramIdDataSize(Pubkey, u64),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -173,6 +180,9 @@ pub enum AccountIndex {
     ProgramId,
     SplTokenMint,
     SplTokenOwner,
+    /// Indexed by program id and account data length, to serve program account scans filtered by
+    /// data size
+    ProgramIdDataSize,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -501,6 +511,7 @@ pub struct AccountsIndexIterator<'a, T: IndexValue, U: DiskIndexValue + From<T>
     end_bound: Bound<Pubkey>,
     is_finished: bool,
     collect_all_unsorted: bool,
+    descending: bool,
 }
 
 impl<'a, T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> AccountsIndexIterator<'a, T, U> {
@@ -508,13 +519,18 @@ impl<'a, T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> AccountsIndexIter
         map: &AccountMaps<T, U>,
         range: R,
         collect_all_unsorted: bool,
+        descending: bool,
     ) -> Vec<(Pubkey, AccountMapEntry<T>)>
     where
         R: RangeBounds<Pubkey> + std::fmt::Debug,
     {
         let mut result = map.items(&range);
         if !collect_all_unsorted {
-            result.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+            if descending {
+                result.sort_unstable_by(|a, b| b.0.cmp(&a.0));
+            } else {
+                result.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+            }
         }
         result
     }
@@ -583,6 +599,7 @@ impl<'a, T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> AccountsIndexIter
             is_finished: false,
             bin_calculator: &index.bin_calculator,
             collect_all_unsorted,
+            descending: false,
         }
     }
 
@@ -612,12 +629,21 @@ impl<'a, T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> Iterator
             return None;
         }
         let (start_bin, bin_range) = self.bin_start_and_range();
+        let bins = self.account_maps.iter().skip(start_bin).take(bin_range);
+        // bins hold consecutive ranges of pubkeys, so visit them in reverse to scan descending
+        let descending = self.descending && !self.collect_all_unsorted;
+        let bins: Box<dyn Iterator<Item = _>> = if descending {
+            Box::new(bins.rev())
+        } else {
+            Box::new(bins)
+        };
         let mut chunk = Vec::with_capacity(ITER_BATCH_SIZE);
-        'outer: for i in self.account_maps.iter().skip(start_bin).take(bin_range) {
+        'outer: for i in bins {
             for (pubkey, account_map_entry) in Self::range(
                 &i,
                 (self.start_bound, self.end_bound),
                 self.collect_all_unsorted,
+                descending,
             ) {
                 if chunk.len() >= ITER_BATCH_SIZE && !self.collect_all_unsorted {
                     break 'outer;
@@ -634,7 +660,11 @@ impl<'a, T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> Iterator
             self.is_finished = true;
         }
 
-        self.start_bound = Excluded(chunk.last().unwrap().0);
+        if descending {
+            self.end_bound = Excluded(chunk.last().unwrap().0);
+        } else {
+            self.start_bound = Excluded(chunk.last().unwrap().0);
+        }
         Some(chunk)
     }
 }
@@ -682,6 +712,7 @@ pub struct AccountsIndex<T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> {
     program_id_index: SecondaryIndex<DashMapSecondaryIndexEntry>,
     spl_token_mint_index: SecondaryIndex<DashMapSecondaryIndexEntry>,
     spl_token_owner_index: SecondaryIndex<RwLockSecondaryIndexEntry>,
+    program_id_data_size_index: SecondaryIndex<DashMapSecondaryIndexEntry, (Pubkey, u64)>,
     pub roots_tracker: RwLock<RootsTracker>,
     ongoing_scan_roots: RwLock<BTreeMap<Slot, u64>>,
     // Each scan has some latest slot `S` that is the tip of the fork the scan
@@ -736,6 +767,9 @@ impl<T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> AccountsIndex<T, U> {
             spl_token_owner_index: SecondaryIndex::<RwLockSecondaryIndexEntry>::new(
                 "spl_token_owner_index_stats",
             ),
+            program_id_data_size_index: SecondaryIndex::<DashMapSecondaryIndexEntry, _>::new(
+                "program_id_data_size_index_stats",
+            ),
             roots_tracker: RwLock::<RootsTracker>::default(),
             ongoing_scan_roots: RwLock::<BTreeMap<Slot, u64>>::default(),
             removed_bank_ids: Mutex::<HashSet<BankId>>::default(),
@@ -770,11 +804,14 @@ impl<T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> AccountsIndex<T, U> {
         (account_maps, bin_calculator, storage)
     }
 
-    fn iter<R>(&self, range: Option<&R>, collect_all_unsorted: bool) -> AccountsIndexIterator<T, U>
+    fn iter<R>(&self, range: Option<&R>, config: &ScanConfig) -> AccountsIndexIterator<T, U>
     where
         R: RangeBounds<Pubkey>,
     {
-        AccountsIndexIterator::new(self, range, collect_all_unsorted)
+        AccountsIndexIterator {
+            descending: config.descending,
+            ..AccountsIndexIterator::new(self, range, config.collect_all_unsorted)
+        }
     }
 
     /// is the accounts index using disk as a backing store
@@ -988,6 +1025,16 @@ impl<T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> AccountsIndex<T, U> {
                     config,
                 );
             }
+            ScanTypes::Indexed(IndexKey::ProgramIdDataSize(program_id, data_size)) => {
+                self.do_scan_secondary_index(
+                    ancestors,
+                    func,
+                    &self.program_id_data_size_index,
+                    &(program_id, data_size),
+                    Some(max_root),
+                    config,
+                );
+            }
         }
 
         {
@@ -1056,7 +1103,7 @@ impl<T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> AccountsIndex<T, U> {
         let mut read_lock_elapsed = 0;
         let mut iterator_elapsed = 0;
         let mut iterator_timer = Measure::start("iterator_elapsed");
-        for pubkey_list in self.iter(range.as_ref(), config.collect_all_unsorted) {
+        for pubkey_list in self.iter(range.as_ref(), config) {
             iterator_timer.stop();
             iterator_elapsed += iterator_timer.as_us();
             for (pubkey, list) in pubkey_list {
@@ -1098,12 +1145,13 @@ impl<T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> AccountsIndex<T, U> {
     fn do_scan_secondary_index<
         F,
         SecondaryIndexEntryType: SecondaryIndexEntry + Default + Sync + Send,
+        SecondaryIndexKeyType: SecondaryIndexKey,
     >(
         &self,
         ancestors: &Ancestors,
         mut func: F,
-        index: &SecondaryIndex<SecondaryIndexEntryType>,
-        index_key: &Pubkey,
+        index: &SecondaryIndex<SecondaryIndexEntryType, SecondaryIndexKeyType>,
+        index_key: &SecondaryIndexKeyType,
         max_root: Option<Slot>,
         config: &ScanConfig,
     ) where
@@ -1211,6 +1259,31 @@ impl<T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> AccountsIndex<T, U> {
         );
     }
 
+    /// call func with every pubkey and index visible from a given set of ancestors with range,
+    /// checking that the scanned bank was not removed like `scan_accounts`
+    pub(crate) fn checked_range_scan_accounts<F, R>(
+        &self,
+        ancestors: &Ancestors,
+        scan_bank_id: BankId,
+        range: R,
+        func: F,
+        config: &ScanConfig,
+    ) -> Result<(), ScanError>
+    where
+        F: FnMut(&Pubkey, (&T, Slot)),
+        R: RangeBounds<Pubkey> + std::fmt::Debug,
+    {
+        // Pass "" not to log metrics, so RPC doesn't get spammy
+        self.do_checked_scan_accounts(
+            "",
+            ancestors,
+            scan_bank_id,
+            func,
+            ScanTypes::Unindexed(Some(range)),
+            config,
+        )
+    }
+
     /// call func with every pubkey and index visible from a given set of ancestors with range
     /// Only guaranteed to be safe when called from rent collection
     pub(crate) fn range_scan_accounts<F, R>(
@@ -1510,6 +1583,14 @@ impl<T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> AccountsIndex<T, U> {
                 .index
                 .get(index_key)
                 .map(|x| x.len()),
+            // Total over all the data sizes of the program's accounts
+            AccountIndex::ProgramIdDataSize => self
+                .program_id_data_size_index
+                .index
+                .iter()
+                .filter(|entry| entry.key().0 == *index_key)
+                .map(|entry| entry.value().len())
+                .reduce(|total, len| total.saturating_add(len)),
         }
     }
 
@@ -1527,6 +1608,10 @@ impl<T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> AccountsIndex<T, U> {
             info!("secondary index: {:?}", AccountIndex::SplTokenOwner);
             self.spl_token_owner_index.log_contents();
         }
+        if !self.program_id_data_size_index.index.is_empty() {
+            info!("secondary index: {:?}", AccountIndex::ProgramIdDataSize);
+            self.program_id_data_size_index.log_contents();
+        }
     }
 
     pub(crate) fn update_secondary_indexes(
@@ -1547,6 +1632,12 @@ impl<T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> AccountsIndex<T, U> {
         {
             self.program_id_index.insert(account_owner, pubkey);
         }
+        if account_indexes.contains(&AccountIndex::ProgramIdDataSize)
+            && account_indexes.include_key(account_owner)
+        {
+            self.program_id_data_size_index
+                .insert(&(*account_owner, account_data.len() as u64), pubkey);
+        }
         // Note because of the below check below on the account data length, when an
         // account hits zero lamports and is reset to AccountSharedData::Default, then we skip
         // the below updates to the secondary indexes.
@@ -1805,6 +1896,11 @@ impl<T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> AccountsIndex<T, U> {
         if account_indexes.contains(&AccountIndex::SplTokenMint) {
             self.spl_token_mint_index.remove_by_inner_key(inner_key);
         }
+
+        if account_indexes.contains(&AccountIndex::ProgramIdDataSize) {
+            self.program_id_data_size_index
+                .remove_by_inner_key(inner_key);
+        }
     }
 
     fn purge_older_root_entries(
@@ -3000,6 +3096,52 @@ pub mod tests {
         );
     }
 
+    #[test]
+    fn test_checked_range_scan_accounts_descending() {
+        let (index, mut pubkeys) = setup_accounts_index_keys(3 * ITER_BATCH_SIZE);
+        pubkeys.sort();
+        let ancestors = Ancestors::default();
+        let config = ScanConfig {
+            descending: true,
+            ..ScanConfig::default()
+        };
+
+        let mut scanned_keys = vec![];
+        index
+            .checked_range_scan_accounts(
+                &ancestors,
+                0,
+                (Unbounded, Excluded(pubkeys[2 * ITER_BATCH_SIZE])),
+                |pubkey, _index| scanned_keys.push(*pubkey),
+                &config,
+            )
+            .unwrap();
+        let expected_keys: Vec<_> = pubkeys[..2 * ITER_BATCH_SIZE].iter().rev().collect();
+        assert_eq!(scanned_keys.iter().collect::<Vec<_>>(), expected_keys);
+
+        // the scan stops once aborted
+        let config = config.recreate_with_abort();
+        let mut scanned_keys = vec![];
+        index
+            .checked_range_scan_accounts(
+                &ancestors,
+                0,
+                ..,
+                |pubkey, _index| {
+                    scanned_keys.push(*pubkey);
+                    if scanned_keys.len() == 3 {
+                        config.abort();
+                    }
+                },
+                &config,
+            )
+            .unwrap();
+        assert_eq!(
+            scanned_keys.iter().collect::<Vec<_>>(),
+            pubkeys.iter().rev().take(3).collect::<Vec<_>>()
+        );
+    }
+
     fn run_test_scan_accounts(num_pubkeys: usize) {
         let (index, _) = setup_accounts_index_keys(num_pubkeys);
         let ancestors = Ancestors::default();
@@ -3028,7 +3170,8 @@ pub mod tests {
     #[test]
     fn test_accounts_iter_finished() {
         let (index, _) = setup_accounts_index_keys(0);
-        let mut iter = index.iter(None::<&Range<Pubkey>>, COLLECT_ALL_UNSORTED_FALSE);
+        let config = ScanConfig::new(COLLECT_ALL_UNSORTED_FALSE);
+        let mut iter = index.iter(None::<&Range<Pubkey>>, &config);
         assert!(iter.next().is_none());
         let mut gc = vec![];
         index.upsert(
@@ -3538,12 +3681,13 @@ pub mod tests {
         assert_eq!(slot_list, vec![(5, true), (9, true)]);
     }
 
-    fn check_secondary_index_mapping_correct<SecondaryIndexEntryType>(
-        secondary_index: &SecondaryIndex<SecondaryIndexEntryType>,
-        secondary_index_keys: &[Pubkey],
+    fn check_secondary_index_mapping_correct<SecondaryIndexEntryType, SecondaryIndexKeyType>(
+        secondary_index: &SecondaryIndex<SecondaryIndexEntryType, SecondaryIndexKeyType>,
+        secondary_index_keys: &[SecondaryIndexKeyType],
         account_key: &Pubkey,
     ) where
         SecondaryIndexEntryType: SecondaryIndexEntry + Default + Sync + Send,
+        SecondaryIndexKeyType: SecondaryIndexKey,
     {
         // Check secondary index has unique mapping from secondary index key
         // to the account key and slot
@@ -3694,6 +3838,86 @@ pub mod tests {
         }
     }
 
+    #[test]
+    fn test_program_id_data_size_secondary_index() {
+        let index = AccountsIndex::<bool, bool>::default_for_tests();
+        let secondary_index = &index.program_id_data_size_index;
+        let mut secondary_indexes = AccountSecondaryIndexes::default();
+        secondary_indexes
+            .indexes
+            .insert(AccountIndex::ProgramIdDataSize);
+        let account_key = Pubkey::new_unique();
+        let program_id = Pubkey::new_unique();
+
+        // Inserting the same index multiple times should be ok
+        for _ in 0..2 {
+            index.upsert(
+                0,
+                0,
+                &account_key,
+                &AccountSharedData::new(1, 10, &program_id),
+                &secondary_indexes,
+                true,
+                &mut vec![],
+                UPSERT_POPULATE_RECLAIMS,
+            );
+            check_secondary_index_mapping_correct(
+                secondary_index,
+                &[(program_id, 10)],
+                &account_key,
+            );
+        }
+        assert!(secondary_index.get(&(program_id, 11)).is_empty());
+        assert_eq!(
+            index.get_index_key_size(&AccountIndex::ProgramIdDataSize, &program_id),
+            Some(1)
+        );
+
+        // Resizing the account in a later slot indexes it under both data sizes
+        index.upsert(
+            1,
+            1,
+            &account_key,
+            &AccountSharedData::new(1, 20, &program_id),
+            &secondary_indexes,
+            true,
+            &mut vec![],
+            UPSERT_POPULATE_RECLAIMS,
+        );
+        assert_eq!(secondary_index.get(&(program_id, 10)), vec![account_key]);
+        assert_eq!(secondary_index.get(&(program_id, 20)), vec![account_key]);
+        assert_eq!(
+            index.get_index_key_size(&AccountIndex::ProgramIdDataSize, &program_id),
+            Some(2)
+        );
+        assert_eq!(
+            index.get_index_key_size(&AccountIndex::ProgramIdDataSize, &Pubkey::new_unique()),
+            None
+        );
+
+        // Excluded program ids are not indexed
+        let excluded_account_key = Pubkey::new_unique();
+        let excluded_program_id = Pubkey::new_unique();
+        secondary_indexes.keys = Some(AccountSecondaryIndexesIncludeExclude {
+            keys: [excluded_program_id].into_iter().collect(),
+            exclude: true,
+        });
+        index.update_secondary_indexes(
+            &excluded_account_key,
+            &AccountSharedData::new(1, 10, &excluded_program_id),
+            &secondary_indexes,
+        );
+        assert!(secondary_index.get(&(excluded_program_id, 10)).is_empty());
+        secondary_indexes.keys = None;
+
+        index.slot_list_mut(&account_key, |slot_list| slot_list.clear());
+
+        // Everything should be deleted
+        let _ = index.handle_dead_keys(&[&account_key], &secondary_indexes);
+        assert!(secondary_index.index.is_empty());
+        assert!(secondary_index.reverse_index.is_empty());
+    }
+
     fn run_test_secondary_indexes_same_slot_and_forks<
         SecondaryIndexEntryType: SecondaryIndexEntry + Default + Sync + Send,
     >(
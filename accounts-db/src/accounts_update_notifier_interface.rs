@@ -17,6 +17,17 @@ pub trait AccountsUpdateNotifierInterface: std::fmt::Debug {
         write_version: u64,
     );
 
+    /// Notified when a transaction updates an account which exists on the fork
+    /// to zero lamports, right after the update itself is notified with the
+    /// same write version
+    fn notify_account_closed(
+        &self,
+        slot: Slot,
+        txn: &Option<&SanitizedTransaction>,
+        pubkey: &Pubkey,
+        write_version: u64,
+    );
+
     /// Notified when the AccountsDb is initialized at start when restored
     /// from a snapshot.
     fn notify_account_restore_from_snapshot(&self, slot: Slot, account: &StoredAccountMeta);
@@ -28,6 +28,64 @@ use {
     },
 };
 
+/// default number of ancient storages to aim for when packing
+pub const DEFAULT_MAX_ANCIENT_STORAGES: usize = 10_000;
+/// default percent of the dead ancient data to reclaim each packing pass
+pub const DEFAULT_ANCIENT_PERCENT_OF_ALIVE_SHRUNK_DATA: u64 = 55;
+/// default alive percent below which an ancient storage is shrunk
+pub const DEFAULT_ANCIENT_SHRINK_ALIVE_PERCENT: u64 = 90;
+
+/// Thresholds for packing and shrinking ancient storages.
+/// These trade disk i/o against disk footprint and may be adjusted while running.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AncientStorageTuning {
+    /// number of ancient slots we should aim to have when packing. If we have more than this, combine further.
+    pub max_ancient_slots: usize,
+    /// shrink enough ancient storages per packing pass to realize this% of the total dead data that needs to be shrunk
+    pub percent_of_alive_shrunk_data: u64,
+    /// ancient storages with less than this% of their bytes alive are shrunk
+    pub shrink_alive_percent: u64,
+}
+
+impl Default for AncientStorageTuning {
+    fn default() -> Self {
+        Self {
+            max_ancient_slots: DEFAULT_MAX_ANCIENT_STORAGES,
+            percent_of_alive_shrunk_data: DEFAULT_ANCIENT_PERCENT_OF_ALIVE_SHRUNK_DATA,
+            shrink_alive_percent: DEFAULT_ANCIENT_SHRINK_ALIVE_PERCENT,
+        }
+    }
+}
+
+impl AncientStorageTuning {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.percent_of_alive_shrunk_data > 100 {
+            return Err(format!(
+                "percent of alive shrunk data must be at most 100, not {}",
+                self.percent_of_alive_shrunk_data
+            ));
+        }
+        if self.shrink_alive_percent > 100 {
+            return Err(format!(
+                "shrink alive percent must be at most 100, not {}",
+                self.shrink_alive_percent
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Size of the ancient storages as of the most recent ancient shrink pass
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct AncientStorageUsage {
+    /// # of storages in ancient slots
+    pub storages: u64,
+    /// total capacity of those storages
+    pub bytes: u64,
+    /// bytes of those storages not occupied by alive accounts
+    pub wasted_bytes: u64,
+}
+
 /// ancient packing algorithm tuning per pass
 #[derive(Debug)]
 struct PackedAncientStorageTuning {
@@ -81,6 +139,7 @@ impl AncientSlotInfos {
         slot: Slot,
         storage: Arc<AccountStorageEntry>,
         can_randomly_shrink: bool,
+        shrink_alive_percent: u64,
     ) -> bool {
         let mut was_randomly_shrunk = false;
         let alive_bytes = storage.alive_bytes() as u64;
@@ -88,7 +147,7 @@ impl AncientSlotInfos {
             let capacity = storage.accounts.capacity();
             let should_shrink = if capacity > 0 {
                 let alive_ratio = alive_bytes * 100 / capacity;
-                alive_ratio < 90
+                alive_ratio < shrink_alive_percent
                     || if can_randomly_shrink && thread_rng().gen_range(0..10000) == 0 {
                         was_randomly_shrunk = true;
                         true
@@ -248,11 +307,14 @@ impl AccountsDb {
         sorted_slots: Vec<Slot>,
         can_randomly_shrink: bool,
     ) {
+        let AncientStorageTuning {
+            max_ancient_slots,
+            percent_of_alive_shrunk_data,
+            ..
+        } = self.ancient_storage_tuning();
         let tuning = PackedAncientStorageTuning {
-            // only allow 10k slots old enough to be ancient
-            max_ancient_slots: 10_000,
-            // re-combine/shrink 55% of the data savings this pass
-            percent_of_alive_shrunk_data: 55,
+            max_ancient_slots,
+            percent_of_alive_shrunk_data,
             ideal_storage_size: NonZeroU64::new(get_ancient_append_vec_capacity()).unwrap(),
             can_randomly_shrink,
         };
@@ -467,10 +529,11 @@ impl AccountsDb {
             ..AncientSlotInfos::default()
         };
         let mut randoms = 0;
+        let shrink_alive_percent = self.ancient_storage_tuning().shrink_alive_percent;
 
         for slot in &slots {
             if let Some(storage) = self.storage.get_slot_storage_entry(*slot) {
-                if infos.add(*slot, storage, can_randomly_shrink) {
+                if infos.add(*slot, storage, can_randomly_shrink, shrink_alive_percent) {
                     randoms += 1;
                 }
             }
@@ -988,7 +1051,10 @@ pub mod tests {
                 ShrinkCollectRefs, MAX_RECYCLE_STORES,
             },
             accounts_index::UpsertReclaim,
-            append_vec::{aligned_stored_size, AppendVec, AppendVecStoredAccountMeta},
+            append_vec::{
+                aligned_stored_size, compression::AccountDataCompression, AppendVec,
+                AppendVecStoredAccountMeta,
+            },
             storable_accounts::StorableAccountsBySlot,
         },
         solana_sdk::{
@@ -996,7 +1062,7 @@ pub mod tests {
             hash::Hash,
             pubkey::Pubkey,
         },
-        std::ops::Range,
+        std::{ops::Range, sync::OnceLock},
         strum::IntoEnumIterator,
         strum_macros::EnumIter,
     };
@@ -2052,6 +2118,8 @@ pub mod tests {
             // account data
             account_meta: &account_meta,
             data: account.data(),
+            compression: AccountDataCompression::None,
+            decompressed_data: OnceLock::default(),
             offset,
             stored_size: account_size,
             hash: &hash,
@@ -2162,7 +2230,12 @@ pub mod tests {
                 match method {
                     TestCollectInfo::Add => {
                         // test lower level 'add'
-                        infos.add(slot1, Arc::clone(&storage), can_randomly_shrink);
+                        infos.add(
+                            slot1,
+                            Arc::clone(&storage),
+                            can_randomly_shrink,
+                            DEFAULT_ANCIENT_SHRINK_ALIVE_PERCENT,
+                        );
                     }
                     TestCollectInfo::CalcAncientSlotInfo => {
                         infos = db.calc_ancient_slot_info(vec![slot1], can_randomly_shrink);
@@ -2212,7 +2285,12 @@ pub mod tests {
             let mut infos = AncientSlotInfos::default();
             let storage = db.storage.get_slot_storage_entry(slot1).unwrap();
             if call_add {
-                infos.add(slot1, Arc::clone(&storage), can_randomly_shrink);
+                infos.add(
+                    slot1,
+                    Arc::clone(&storage),
+                    can_randomly_shrink,
+                    DEFAULT_ANCIENT_SHRINK_ALIVE_PERCENT,
+                );
             } else {
                 infos = db.calc_ancient_slot_info(vec![slot1], can_randomly_shrink);
             }
@@ -2223,6 +2301,43 @@ pub mod tests {
         }
     }
 
+    #[test]
+    fn test_calc_ancient_slot_info_tuning() {
+        let can_randomly_shrink = false;
+        let (db, slot1) = create_db_with_storages_and_index(true, 1, None);
+        // the storage is mostly empty, so it is shrunk with the default tuning
+        let infos = db.calc_ancient_slot_info(vec![slot1], can_randomly_shrink);
+        assert_eq!(infos.shrink_indexes, vec![0]);
+
+        let tuning = AncientStorageTuning {
+            shrink_alive_percent: 0,
+            ..AncientStorageTuning::default()
+        };
+        db.set_ancient_storage_tuning(tuning);
+        assert_eq!(db.ancient_storage_tuning(), tuning);
+        let infos = db.calc_ancient_slot_info(vec![slot1], can_randomly_shrink);
+        assert_eq!(infos.all_infos.len(), 1);
+        assert!(infos.shrink_indexes.is_empty());
+        assert_eq!(infos.total_alive_bytes_shrink, 0);
+    }
+
+    #[test]
+    fn test_ancient_storage_tuning_validate() {
+        assert!(AncientStorageTuning::default().validate().is_ok());
+        for tuning in [
+            AncientStorageTuning {
+                percent_of_alive_shrunk_data: 101,
+                ..AncientStorageTuning::default()
+            },
+            AncientStorageTuning {
+                shrink_alive_percent: 101,
+                ..AncientStorageTuning::default()
+            },
+        ] {
+            assert!(tuning.validate().is_err());
+        }
+    }
+
     #[test]
     fn test_calc_ancient_slot_info_several() {
         let can_randomly_shrink = false;
@@ -12,6 +12,7 @@ use {
         },
         accounts_file::{AccountsFileError, MatchAccountOwnerError, Result, ALIGN_BOUNDARY_OFFSET},
         accounts_hash::AccountHash,
+        append_vec::compression::{AccountDataCompression, AccountDataCompressionConfig},
         storable_accounts::StorableAccounts,
         u64_align,
     },
@@ -31,13 +32,14 @@ use {
         mem,
         path::{Path, PathBuf},
         sync::{
-            atomic::{AtomicU64, AtomicUsize, Ordering},
-            Mutex,
+            atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+            Mutex, OnceLock,
         },
     },
     thiserror::Error,
 };
 
+pub mod compression;
 pub mod test_utils;
 
 /// size of the fixed sized fields in an append vec
@@ -106,17 +108,36 @@ impl<'append_vec> Iterator for AppendVecAccountsIter<'append_vec> {
 
 /// References to account data stored elsewhere. Getting an `Account` requires cloning
 /// (see `StoredAccountMeta::clone_account()`).
-#[derive(PartialEq, Eq, Debug)]
+#[derive(Debug)]
 pub struct AppendVecStoredAccountMeta<'append_vec> {
     pub meta: &'append_vec StoredMeta,
     /// account data
     pub account_meta: &'append_vec AccountMeta,
+    /// the stored bytes of the account data, compressed unless `compression` is None
     pub(crate) data: &'append_vec [u8],
+    pub(crate) compression: AccountDataCompression,
+    /// the account data decompressed, the first time it is read
+    pub(crate) decompressed_data: OnceLock<Vec<u8>>,
     pub(crate) offset: usize,
     pub(crate) stored_size: usize,
     pub(crate) hash: &'append_vec AccountHash,
 }
 
+impl<'append_vec> PartialEq for AppendVecStoredAccountMeta<'append_vec> {
+    fn eq(&self, other: &Self) -> bool {
+        // whether the data was decompressed yet does not matter
+        self.meta == other.meta
+            && self.account_meta == other.account_meta
+            && self.data == other.data
+            && self.compression == other.compression
+            && self.offset == other.offset
+            && self.stored_size == other.stored_size
+            && self.hash == other.hash
+    }
+}
+
+impl<'append_vec> Eq for AppendVecStoredAccountMeta<'append_vec> {}
+
 impl<'append_vec> AppendVecStoredAccountMeta<'append_vec> {
     pub fn pubkey(&self) -> &'append_vec Pubkey {
         &self.meta.pubkey
@@ -134,12 +155,46 @@ impl<'append_vec> AppendVecStoredAccountMeta<'append_vec> {
         self.offset
     }
 
-    pub fn data(&self) -> &'append_vec [u8] {
-        self.data
+    pub fn data(&self) -> &[u8] {
+        match self.compression {
+            AccountDataCompression::None => self.data,
+            data_compression => self.decompressed_data.get_or_init(|| {
+                // sanitizing the append vec only checks the header of compressed
+                // data, so corrupt compressed data is only detected here
+                compression::decompress(data_compression, self.data)
+                    .expect("compressed account data must be valid")
+            }),
+        }
     }
 
     pub fn data_len(&self) -> u64 {
-        self.meta.data_len
+        match self.compression {
+            AccountDataCompression::None => self.meta.data_len,
+            _ => compression::uncompressed_len(self.data).unwrap_or_default() as u64,
+        }
+    }
+
+    /// Whether the account data is stored compressed
+    pub fn is_compressed(&self) -> bool {
+        self.compression != AccountDataCompression::None
+    }
+
+    /// The account, taking the decompressed data of a compressed account
+    /// instead of copying it
+    pub(crate) fn into_account_shared_data(self) -> AccountSharedData {
+        if !self.is_compressed() {
+            return self.to_account_shared_data();
+        }
+        let lamports = self.lamports();
+        let owner = *self.owner();
+        let executable = self.executable();
+        let rent_epoch = self.rent_epoch();
+        let data = match self.decompressed_data.into_inner() {
+            Some(data) => data,
+            None => compression::decompress(self.compression, self.data)
+                .expect("compressed account data must be valid"),
+        };
+        AccountSharedData::create(lamports, data, owner, executable, rent_epoch)
     }
 
     pub fn write_version(&self) -> StoredMetaWriteVersion {
@@ -155,7 +210,16 @@ impl<'append_vec> AppendVecStoredAccountMeta<'append_vec> {
     }
 
     pub(crate) fn sanitize(&self) -> bool {
-        self.sanitize_executable() && self.sanitize_lamports()
+        self.sanitize_compressed_data() && self.sanitize_executable() && self.sanitize_lamports()
+    }
+
+    /// Only the header of compressed data is checked, decompressing every
+    /// account when the append vec is opened would take too long
+    fn sanitize_compressed_data(&self) -> bool {
+        match self.compression {
+            AccountDataCompression::None => true,
+            _ => compression::uncompressed_len(self.data).is_some(),
+        }
     }
 
     fn sanitize_executable(&self) -> bool {
@@ -183,7 +247,7 @@ impl<'append_vec> ReadableAccount for AppendVecStoredAccountMeta<'append_vec> {
     fn lamports(&self) -> u64 {
         self.account_meta.lamports
     }
-    fn data(&self) -> &'append_vec [u8] {
+    fn data(&self) -> &[u8] {
         self.data()
     }
     fn owner(&self) -> &'append_vec Pubkey {
@@ -217,6 +281,12 @@ pub struct AppendVec {
 
     /// The number of bytes available for storing items.
     file_size: u64,
+
+    /// Which account data is compressed when accounts are appended
+    compression: AccountDataCompressionConfig,
+
+    /// true if any account stored in this append vec has compressed data
+    has_compressed_accounts: AtomicBool,
 }
 
 lazy_static! {
@@ -289,9 +359,18 @@ impl AppendVec {
             append_lock: Mutex::new(()),
             current_len: AtomicUsize::new(initial_len),
             file_size: size as u64,
+            compression: AccountDataCompressionConfig::default(),
+            has_compressed_accounts: AtomicBool::default(),
         }
     }
 
+    /// Compress account data as `compression` configures when accounts are appended.
+    /// Accounts already stored are read as they were stored either way.
+    pub fn with_compression(mut self, compression: AccountDataCompressionConfig) -> Self {
+        self.compression = compression;
+        self
+    }
+
     fn sanitize_len_and_size(current_len: usize, file_size: usize) -> Result<()> {
         if file_size == 0 {
             Err(AccountsFileError::AppendVecError(
@@ -323,6 +402,14 @@ impl AppendVec {
         // See UNSAFE usage in `append_ptr`
         let _lock = self.append_lock.lock().unwrap();
         self.current_len.store(0, Ordering::Release);
+        self.has_compressed_accounts.store(false, Ordering::Release);
+    }
+
+    /// Whether any account stored in this append vec has compressed data.
+    /// Snapshots of such append vecs can only be loaded by versions which
+    /// support compression.
+    pub fn has_compressed_accounts(&self) -> bool {
+        self.has_compressed_accounts.load(Ordering::Acquire)
     }
 
     /// how many more bytes can be stored in this append vec
@@ -389,6 +476,8 @@ impl AppendVec {
             append_lock: Mutex::new(()),
             current_len: AtomicUsize::new(current_len),
             file_size,
+            compression: AccountDataCompressionConfig::default(),
+            has_compressed_accounts: AtomicBool::default(),
         })
     }
 
@@ -405,6 +494,9 @@ impl AppendVec {
             if !account.sanitize() {
                 return (false, num_accounts);
             }
+            if account.is_compressed() {
+                self.has_compressed_accounts.store(true, Ordering::Release);
+            }
             offset = next_offset;
             num_accounts += 1;
         }
@@ -489,13 +581,16 @@ impl AppendVec {
         let (meta, next): (&StoredMeta, _) = self.get_type(offset)?;
         let (account_meta, next): (&AccountMeta, _) = self.get_type(next)?;
         let (hash, next): (&AccountHash, _) = self.get_type(next)?;
-        let (data, next) = self.get_slice(next, meta.data_len as usize)?;
+        let (data_compression, stored_data_len) = compression::decode_data_len(meta.data_len)?;
+        let (data, next) = self.get_slice(next, stored_data_len as usize)?;
         let stored_size = next - offset;
         Some((
             StoredAccountMeta::AppendVec(AppendVecStoredAccountMeta {
                 meta,
                 account_meta,
                 data,
+                compression: data_compression,
+                decompressed_data: OnceLock::default(),
                 offset,
                 stored_size,
                 hash,
@@ -602,20 +697,24 @@ impl AppendVec {
                 })
                 .unwrap_or_default();
 
+            let data = account.map(|account| account.data()).unwrap_or_default();
+            let compressed = self.compression.compress(data);
+            let (stored_data_len, stored_data) = match &compressed {
+                Some((data_compression, compressed_data)) => (
+                    compression::encode_data_len(*data_compression, compressed_data.len()),
+                    compressed_data.as_slice(),
+                ),
+                None => (data.len() as u64, data),
+            };
             let stored_meta = StoredMeta {
                 pubkey: *pubkey,
-                data_len: account
-                    .map(|account| account.data().len())
-                    .unwrap_or_default() as u64,
+                data_len: stored_data_len,
                 write_version_obsolete,
             };
             let meta_ptr = &stored_meta as *const StoredMeta;
             let account_meta_ptr = &account_meta as *const AccountMeta;
-            let data_len = stored_meta.data_len as usize;
-            let data_ptr = account
-                .map(|account| account.data())
-                .unwrap_or_default()
-                .as_ptr();
+            let data_len = stored_data.len();
+            let data_ptr = stored_data.as_ptr();
             let hash_ptr = bytemuck::bytes_of(hash).as_ptr();
             let ptrs = [
                 (meta_ptr as *const u8, mem::size_of::<StoredMeta>()),
@@ -624,6 +723,9 @@ impl AppendVec {
                 (data_ptr, data_len),
             ];
             if let Some(res) = self.append_ptrs_locked(&mut offset, &ptrs) {
+                if compressed.is_some() {
+                    self.has_compressed_accounts.store(true, Ordering::Release);
+                }
                 offsets.push(res)
             } else {
                 break;
@@ -1228,6 +1330,96 @@ pub mod tests {
         assert_matches!(result, Err(ref message) if message.to_string().contains("incorrect layout/length/data"));
     }
 
+    fn compressible_test_account(sample: usize) -> (StoredMeta, AccountSharedData) {
+        let (mut stored_meta, mut account) = create_test_account(sample);
+        account.set_data_from_slice(&[sample as u8; 4096]);
+        stored_meta.data_len = 4096;
+        (stored_meta, account)
+    }
+
+    #[test]
+    fn test_append_vec_compressed() {
+        for compression in [AccountDataCompression::Lz4, AccountDataCompression::Zstd] {
+            let file = get_append_vec_path("test_append_vec_compressed");
+            let path = &file.path;
+            let accounts_len = {
+                // wrap AppendVec in ManuallyDrop to ensure we do not remove the backing file when dropped
+                let av =
+                    ManuallyDrop::new(AppendVec::new(path, true, 1024 * 1024).with_compression(
+                        AccountDataCompressionConfig {
+                            compression,
+                            min_data_len: 1024,
+                        },
+                    ));
+                // too short to be compressed
+                let short_account = create_test_account(10);
+                let short_offset = av.append_account_test(&short_account).unwrap();
+                assert!(!av.has_compressed_accounts());
+                let long_account = compressible_test_account(11);
+                let long_offset = av.append_account_test(&long_account).unwrap();
+
+                assert_eq!(av.get_account_test(short_offset).unwrap(), short_account);
+                let (stored_account, _) = av.get_account(long_offset).unwrap();
+                let StoredAccountMeta::AppendVec(stored) = &stored_account else {
+                    panic!("StoredAccountMeta can only be AppendVec in this test.");
+                };
+                assert!(stored.is_compressed());
+                assert!(stored.stored_size() < aligned_stored_size(4096));
+                assert_eq!(stored_account.data_len(), 4096);
+                assert_eq!(stored_account.to_account_shared_data(), long_account.1);
+                assert_eq!(stored.into_account_shared_data(), long_account.1);
+                assert!(av.has_compressed_accounts());
+
+                av.flush().unwrap();
+                av.len()
+            };
+
+            // compressed accounts are read back once the append vec is reopened
+            let (av, num_accounts) = AppendVec::new_from_file(path, accounts_len).unwrap();
+            assert_eq!(num_accounts, 2);
+            assert!(av.has_compressed_accounts());
+            let accounts = av.accounts(0);
+            assert_eq!(accounts[0].data(), &[10; 10]);
+            assert_eq!(accounts[1].data(), &[11; 4096]);
+        }
+    }
+
+    #[test]
+    fn test_new_from_file_crafted_compression() {
+        let file = get_append_vec_path("test_new_from_file_crafted_compression");
+        let path = &file.path;
+        let accounts_len = {
+            // wrap AppendVec in ManuallyDrop to ensure we do not remove the backing file when dropped
+            let av = ManuallyDrop::new(AppendVec::new(path, true, 1024 * 1024).with_compression(
+                AccountDataCompressionConfig {
+                    compression: AccountDataCompression::Lz4,
+                    min_data_len: 1024,
+                },
+            ));
+            av.append_account_test(&compressible_test_account(10))
+                .unwrap();
+
+            // claim the compressed data is too short to hold its uncompressed length
+            let accounts = av.accounts(0);
+            let StoredAccountMeta::AppendVec(account) = accounts.first().unwrap() else {
+                panic!("StoredAccountMeta can only be AppendVec in this test.");
+            };
+            account
+                .set_data_len_unsafe(compression::encode_data_len(AccountDataCompression::Lz4, 4));
+
+            let accounts = av.accounts(0);
+            let StoredAccountMeta::AppendVec(account) = accounts.first().unwrap() else {
+                panic!("StoredAccountMeta can only be AppendVec in this test.");
+            };
+            assert!(!account.sanitize_compressed_data());
+
+            av.flush().unwrap();
+            av.len()
+        };
+        let result = AppendVec::new_from_file(path, accounts_len);
+        assert_matches!(result, Err(ref message) if message.to_string().contains("incorrect layout/length/data"));
+    }
+
     #[test]
     fn test_new_from_file_too_large_data_len() {
         let file = get_append_vec_path("test_new_from_file_too_large_data_len");
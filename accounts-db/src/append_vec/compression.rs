@@ -0,0 +1,291 @@
+//! Compression of account data stored in append vecs.
+//!
+//! Storages created with compression enabled store the data of accounts above
+//! a size threshold compressed. Each compressed account is flagged in the top
+//! bits of its `StoredMeta::data_len`, which otherwise hold the length of the
+//! stored bytes: the uncompressed length, as a little endian `u64`, followed
+//! by the compressed data. Accounts without the flag are stored as before, so
+//! storages written without compression, or by older versions, are read as
+//! is, and a storage may mix compressed and uncompressed accounts.
+//!
+//! Turning compression on or off only affects storages written afterwards:
+//! flushed slots, and storages rewritten by shrink and ancient packing. Older
+//! versions cannot read compressed storages, so snapshots taken while any
+//! remain are given snapshot version 1.3.0, which older versions reject
+//! instead of misreading the storages. Once compression is turned off and the
+//! last compressed storage has been rewritten, snapshots are given the
+//! configured snapshot version again.
+
+use {
+    solana_measure::measure::Measure,
+    solana_sdk::system_instruction::MAX_PERMITTED_DATA_LENGTH,
+    std::{
+        mem,
+        str::FromStr,
+        sync::atomic::{AtomicU64, Ordering},
+    },
+};
+
+/// Account data shorter than this is not compressed by default
+pub const DEFAULT_MIN_COMPRESSED_DATA_LEN: usize = 1024;
+
+/// Bits of `StoredMeta::data_len` holding the compression of the data
+const COMPRESSION_SHIFT: u32 = 62;
+const STORED_DATA_LEN_MASK: u64 = (1 << COMPRESSION_SHIFT) - 1;
+
+/// Size of the uncompressed length stored before compressed data
+const UNCOMPRESSED_LEN_SIZE: usize = mem::size_of::<u64>();
+
+/// zstd level, favoring decompression speed since account data is read far more often than it is written
+const ZSTD_LEVEL: i32 = 3;
+
+/// How account data is compressed
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum AccountDataCompression {
+    #[default]
+    None,
+    Lz4,
+    Zstd,
+}
+
+impl AccountDataCompression {
+    fn from_bits(bits: u64) -> Option<Self> {
+        match bits {
+            0 => Some(Self::None),
+            1 => Some(Self::Lz4),
+            2 => Some(Self::Zstd),
+            _ => None,
+        }
+    }
+
+    fn bits(&self) -> u64 {
+        match self {
+            Self::None => 0,
+            Self::Lz4 => 1,
+            Self::Zstd => 2,
+        }
+    }
+}
+
+impl FromStr for AccountDataCompression {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "none" => Ok(Self::None),
+            "lz4" => Ok(Self::Lz4),
+            "zstd" => Ok(Self::Zstd),
+            _ => Err(format!("unknown account data compression: {s}")),
+        }
+    }
+}
+
+/// Which account data a storage compresses when accounts are appended to it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AccountDataCompressionConfig {
+    pub compression: AccountDataCompression,
+    /// Account data shorter than this is stored uncompressed
+    pub min_data_len: usize,
+}
+
+impl Default for AccountDataCompressionConfig {
+    fn default() -> Self {
+        Self {
+            compression: AccountDataCompression::None,
+            min_data_len: DEFAULT_MIN_COMPRESSED_DATA_LEN,
+        }
+    }
+}
+
+impl AccountDataCompressionConfig {
+    /// The bytes to store for `data`, with their compression, or None if
+    /// `data` is stored uncompressed because it is too short or does not
+    /// compress.
+    pub(crate) fn compress(&self, data: &[u8]) -> Option<(AccountDataCompression, Vec<u8>)> {
+        if self.compression == AccountDataCompression::None || data.len() < self.min_data_len {
+            return None;
+        }
+
+        let mut measure = Measure::start("compress");
+        let mut stored = Vec::with_capacity(UNCOMPRESSED_LEN_SIZE + data.len() / 2);
+        stored.extend_from_slice(&(data.len() as u64).to_le_bytes());
+        let compressed = match self.compression {
+            AccountDataCompression::None => unreachable!(),
+            AccountDataCompression::Lz4 => lz4::block::compress(data, None, false).ok()?,
+            AccountDataCompression::Zstd => zstd::bulk::compress(data, ZSTD_LEVEL).ok()?,
+        };
+        stored.extend_from_slice(&compressed);
+        measure.stop();
+
+        let stats = &COMPRESSION_STATS;
+        stats
+            .compress_us
+            .fetch_add(measure.as_us(), Ordering::Relaxed);
+        if stored.len() >= data.len() {
+            stats
+                .incompressible_accounts
+                .fetch_add(1, Ordering::Relaxed);
+            return None;
+        }
+        stats.compressed_accounts.fetch_add(1, Ordering::Relaxed);
+        stats
+            .uncompressed_bytes
+            .fetch_add(data.len() as u64, Ordering::Relaxed);
+        stats
+            .compressed_bytes
+            .fetch_add(stored.len() as u64, Ordering::Relaxed);
+        Some((self.compression, stored))
+    }
+}
+
+/// The `StoredMeta::data_len` of `stored_len` bytes of data stored with `compression`
+pub(crate) fn encode_data_len(compression: AccountDataCompression, stored_len: usize) -> u64 {
+    (compression.bits() << COMPRESSION_SHIFT) | stored_len as u64
+}
+
+/// The compression and the length of the stored bytes of data whose
+/// `StoredMeta::data_len` is `data_len`, or None if `data_len` is invalid
+pub(crate) fn decode_data_len(data_len: u64) -> Option<(AccountDataCompression, u64)> {
+    let compression = AccountDataCompression::from_bits(data_len >> COMPRESSION_SHIFT)?;
+    Some((compression, data_len & STORED_DATA_LEN_MASK))
+}
+
+/// The uncompressed length of the compressed `stored` bytes, or None if they are invalid
+pub(crate) fn uncompressed_len(stored: &[u8]) -> Option<usize> {
+    let uncompressed_len =
+        u64::from_le_bytes(stored.get(..UNCOMPRESSED_LEN_SIZE)?.try_into().ok()?);
+    (uncompressed_len <= MAX_PERMITTED_DATA_LENGTH).then_some(uncompressed_len as usize)
+}
+
+/// The account data compressed with `compression` into `stored`, or None if they are invalid
+pub(crate) fn decompress(compression: AccountDataCompression, stored: &[u8]) -> Option<Vec<u8>> {
+    let uncompressed_len = uncompressed_len(stored)?;
+    let compressed = &stored[UNCOMPRESSED_LEN_SIZE..];
+
+    let mut measure = Measure::start("decompress");
+    let data = match compression {
+        AccountDataCompression::None => return None,
+        AccountDataCompression::Lz4 => {
+            lz4::block::decompress(compressed, Some(uncompressed_len as i32)).ok()?
+        }
+        AccountDataCompression::Zstd => {
+            zstd::bulk::decompress(compressed, uncompressed_len).ok()?
+        }
+    };
+    measure.stop();
+
+    let stats = &COMPRESSION_STATS;
+    stats.decompressions.fetch_add(1, Ordering::Relaxed);
+    stats
+        .decompress_us
+        .fetch_add(measure.as_us(), Ordering::Relaxed);
+    (data.len() == uncompressed_len).then_some(data)
+}
+
+#[derive(Debug, Default)]
+pub struct AccountDataCompressionStats {
+    /// # of accounts stored compressed
+    compressed_accounts: AtomicU64,
+    /// # of accounts stored uncompressed because compressing did not make them smaller
+    incompressible_accounts: AtomicU64,
+    /// data bytes of the accounts stored compressed, before compression
+    uncompressed_bytes: AtomicU64,
+    /// data bytes of the accounts stored compressed, after compression
+    compressed_bytes: AtomicU64,
+    compress_us: AtomicU64,
+    /// # of times compressed account data was read
+    decompressions: AtomicU64,
+    decompress_us: AtomicU64,
+}
+
+impl AccountDataCompressionStats {
+    pub fn report(&self) {
+        let uncompressed_bytes = self.uncompressed_bytes.swap(0, Ordering::Relaxed);
+        let compressed_bytes = self.compressed_bytes.swap(0, Ordering::Relaxed);
+        let compression_ratio = if compressed_bytes > 0 {
+            uncompressed_bytes as f64 / compressed_bytes as f64
+        } else {
+            0.0
+        };
+        datapoint_info!(
+            "accounts_db-account_data_compression",
+            (
+                "compressed_accounts",
+                self.compressed_accounts.swap(0, Ordering::Relaxed),
+                i64
+            ),
+            (
+                "incompressible_accounts",
+                self.incompressible_accounts.swap(0, Ordering::Relaxed),
+                i64
+            ),
+            ("uncompressed_bytes", uncompressed_bytes, i64),
+            ("compressed_bytes", compressed_bytes, i64),
+            ("compression_ratio", compression_ratio, f64),
+            (
+                "compress_us",
+                self.compress_us.swap(0, Ordering::Relaxed),
+                i64
+            ),
+            (
+                "decompressions",
+                self.decompressions.swap(0, Ordering::Relaxed),
+                i64
+            ),
+            (
+                "decompress_us",
+                self.decompress_us.swap(0, Ordering::Relaxed),
+                i64
+            ),
+        );
+    }
+}
+
+lazy_static! {
+    pub static ref COMPRESSION_STATS: AccountDataCompressionStats =
+        AccountDataCompressionStats::default();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_data_len() {
+        for compression in [
+            AccountDataCompression::None,
+            AccountDataCompression::Lz4,
+            AccountDataCompression::Zstd,
+        ] {
+            let data_len = encode_data_len(compression, 1234);
+            assert_eq!(decode_data_len(data_len), Some((compression, 1234)));
+        }
+        assert_eq!(decode_data_len(u64::MAX), None);
+    }
+
+    #[test]
+    fn test_compress() {
+        let compressible = vec![7; 4096];
+        let incompressible: Vec<u8> = (0..4096).map(|_| rand::random()).collect();
+        for compression in [AccountDataCompression::Lz4, AccountDataCompression::Zstd] {
+            let config = AccountDataCompressionConfig {
+                compression,
+                min_data_len: 1024,
+            };
+            let (stored_compression, stored) = config.compress(&compressible).unwrap();
+            assert_eq!(stored_compression, compression);
+            assert!(stored.len() < compressible.len());
+            assert_eq!(uncompressed_len(&stored), Some(compressible.len()));
+            assert_eq!(decompress(compression, &stored).unwrap(), compressible);
+            // corrupt data is not decompressed
+            assert_eq!(decompress(compression, &stored[..stored.len() - 1]), None);
+
+            assert_eq!(config.compress(&incompressible), None);
+            assert_eq!(config.compress(&compressible[..1023]), None);
+        }
+        assert_eq!(
+            AccountDataCompressionConfig::default().compress(&compressible),
+            None
+        );
+    }
+}
@@ -0,0 +1,131 @@
+//! A homomorphic hash over a set of elements, based on lattices.
+//!
+//! An `LtHash` is a vector of `u16`s, added and subtracted element-wise with
+//! wrapping arithmetic. The hash of a set is the sum of the hashes of its
+//! elements, so an element is added to or removed from the set by mixing its
+//! hash in or out, in any order, without rehashing the rest of the set.
+
+use {
+    solana_sdk::hash::Hash,
+    std::fmt::{self, Debug, Display},
+};
+
+/// A lattice-based homomorphic hash
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct LtHash(pub [u16; LtHash::NUM_ELEMENTS]);
+
+impl LtHash {
+    pub const NUM_ELEMENTS: usize = 1024;
+
+    /// The hash of the empty set
+    #[must_use]
+    pub const fn identity() -> Self {
+        Self([0; Self::NUM_ELEMENTS])
+    }
+
+    /// The hash of the input already fed to `hasher`, read from its
+    /// extendable output
+    #[must_use]
+    pub fn with(hasher: &blake3::Hasher) -> Self {
+        let mut reader = hasher.finalize_xof();
+        let mut bytes = [0u8; Self::NUM_ELEMENTS * 2];
+        reader.fill(&mut bytes);
+        let mut elements = [0u16; Self::NUM_ELEMENTS];
+        for (element, bytes) in elements.iter_mut().zip(bytes.chunks_exact(2)) {
+            *element = u16::from_le_bytes([bytes[0], bytes[1]]);
+        }
+        Self(elements)
+    }
+
+    /// Adds the element hashed as `other` to this set
+    pub fn mix_in(&mut self, other: &Self) {
+        for (a, b) in self.0.iter_mut().zip(other.0.iter()) {
+            *a = a.wrapping_add(*b);
+        }
+    }
+
+    /// Removes the element hashed as `other` from this set
+    pub fn mix_out(&mut self, other: &Self) {
+        for (a, b) in self.0.iter_mut().zip(other.0.iter()) {
+            *a = a.wrapping_sub(*b);
+        }
+    }
+
+    /// A short digest of this hash, e.g. to hash into the bank hash
+    #[must_use]
+    pub fn checksum(&self) -> Checksum {
+        let hash = blake3::hash(bytemuck::cast_slice(&self.0));
+        Checksum(Hash::new_from_array(hash.into()))
+    }
+}
+
+impl Debug for LtHash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // the elements are too many to be useful in logs
+        f.debug_tuple("LtHash").field(&self.checksum().0).finish()
+    }
+}
+
+/// Digest of an `LtHash`
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Checksum(pub Hash);
+
+impl Display for Checksum {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_random() -> LtHash {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(&rand::random::<[u8; 32]>());
+        LtHash::with(&hasher)
+    }
+
+    #[test]
+    fn test_mix_in_out() {
+        let a = new_random();
+        let b = new_random();
+        let c = new_random();
+
+        // the order elements are mixed in does not matter
+        let mut abc = LtHash::identity();
+        abc.mix_in(&a);
+        abc.mix_in(&b);
+        abc.mix_in(&c);
+        let mut cba = LtHash::identity();
+        cba.mix_in(&c);
+        cba.mix_in(&b);
+        cba.mix_in(&a);
+        assert_eq!(abc, cba);
+        assert_eq!(abc.checksum(), cba.checksum());
+
+        // mixing out an element undoes mixing it in
+        let mut ac = abc;
+        ac.mix_out(&b);
+        let mut expected = a;
+        expected.mix_in(&c);
+        assert_eq!(ac, expected);
+        assert_ne!(ac.checksum(), abc.checksum());
+
+        ac.mix_out(&a);
+        ac.mix_out(&c);
+        assert_eq!(ac, LtHash::identity());
+    }
+
+    #[test]
+    fn test_with() {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(b"account");
+        assert_eq!(LtHash::with(&hasher), LtHash::with(&hasher));
+        assert_ne!(LtHash::with(&hasher), LtHash::identity());
+
+        let mut other_hasher = blake3::Hasher::new();
+        other_hasher.update(b"other account");
+        assert_ne!(LtHash::with(&hasher), LtHash::with(&other_hasher));
+    }
+}
@@ -65,7 +65,8 @@ pub(crate) struct ReadOnlyAccountsCache {
     /// always sorted in the order that they have last been accessed. When doing
     /// LRU eviction, cache entries are evicted from the front of the queue.
     queue: Mutex<IndexList<ReadOnlyCacheKey>>,
-    max_data_size: usize,
+    /// Upper bound on data_size, adjustable at runtime
+    max_data_size: AtomicUsize,
     data_size: AtomicUsize,
     // read only cache does not update lru on read of an entry unless it has been at least this many ms since the last lru update
     ms_to_skip_lru_update: u32,
@@ -77,7 +78,7 @@ pub(crate) struct ReadOnlyAccountsCache {
 impl ReadOnlyAccountsCache {
     pub(crate) fn new(max_data_size: usize, ms_to_skip_lru_update: u32) -> Self {
         Self {
-            max_data_size,
+            max_data_size: AtomicUsize::new(max_data_size),
             cache: DashMap::default(),
             queue: Mutex::<IndexList<ReadOnlyCacheKey>>::default(),
             data_size: AtomicUsize::default(),
@@ -157,9 +158,13 @@ impl ReadOnlyAccountsCache {
                 entry.set_index(queue.insert_last(key));
             }
         };
-        // Evict entries from the front of the queue.
+        self.evict();
+    }
+
+    /// Evict entries from the front of the queue until data_size fits max_data_size.
+    fn evict(&self) {
         let mut num_evicts = 0;
-        while self.data_size.load(Ordering::Relaxed) > self.max_data_size {
+        while self.data_size.load(Ordering::Relaxed) > self.max_data_size() {
             let Some(&(pubkey, slot)) = self.queue.lock().unwrap().get_first() else {
                 break;
             };
@@ -188,6 +193,18 @@ impl ReadOnlyAccountsCache {
         self.data_size.load(Ordering::Relaxed)
     }
 
+    pub(crate) fn max_data_size(&self) -> usize {
+        self.max_data_size.load(Ordering::Relaxed)
+    }
+
+    /// Change the upper bound on the cache's data size.
+    /// If the cache currently holds more than `max_data_size`, the least
+    /// recently used entries are evicted immediately.
+    pub(crate) fn set_max_data_size(&self, max_data_size: usize) {
+        self.max_data_size.store(max_data_size, Ordering::Relaxed);
+        self.evict();
+    }
+
     pub(crate) fn get_and_reset_stats(&self) -> (u64, u64, u64, u64) {
         self.stats.get_and_reset_stats()
     }
@@ -312,6 +329,49 @@ mod tests {
         assert_eq!(2, cache.cache_len());
     }
 
+    #[test]
+    fn test_read_only_accounts_cache_set_max_data_size() {
+        let data_size = 100;
+        let per_account_size = CACHE_ENTRY_SIZE + data_size;
+        let cache = ReadOnlyAccountsCache::new(
+            per_account_size * 3,
+            READ_ONLY_CACHE_MS_TO_SKIP_LRU_UPDATE_FOR_TESTS,
+        );
+        let slot = 0;
+        let account = AccountSharedData::from(Account {
+            data: vec![0; data_size],
+            ..Account::default()
+        });
+        let keys: Vec<_> = repeat_with(Pubkey::new_unique).take(3).collect();
+        for key in &keys {
+            cache.store(*key, slot, account.clone());
+        }
+        assert_eq!(3, cache.cache_len());
+        assert_eq!(per_account_size * 3, cache.max_data_size());
+
+        // touch the first key so the second becomes the least recently used
+        assert!(cache.load(keys[0], slot).is_some());
+
+        // shrinking evicts immediately, oldest first
+        cache.set_max_data_size(per_account_size * 2);
+        assert_eq!(per_account_size * 2, cache.max_data_size());
+        assert_eq!(2, cache.cache_len());
+        assert_eq!(per_account_size * 2, cache.data_size());
+        assert!(!cache.in_cache(&keys[1], slot));
+        assert!(cache.in_cache(&keys[0], slot));
+        assert!(cache.in_cache(&keys[2], slot));
+
+        // growing allows more entries without evicting
+        cache.set_max_data_size(per_account_size * 3);
+        cache.store(keys[1], slot, account);
+        assert_eq!(3, cache.cache_len());
+
+        // zero capacity empties the cache
+        cache.set_max_data_size(0);
+        assert_eq!(0, cache.cache_len());
+        assert_eq!(0, cache.data_size());
+    }
+
     /// tests like to deterministically update lru always
     const READ_ONLY_CACHE_MS_TO_SKIP_LRU_UPDATE_FOR_TESTS: u32 = 0;
 
@@ -5,6 +5,7 @@ use {
     std::{
         collections::HashSet,
         fmt::Debug,
+        hash::Hash,
         sync::{
             atomic::{AtomicU64, Ordering},
             RwLock,
@@ -16,7 +17,12 @@ use {
 // if the key had different account data for the indexed key across different
 // slots. As this is rare, it should be ok to use a Vec here over a HashSet, even
 // though we are running some key existence checks.
-pub type SecondaryReverseIndexEntry = RwLock<Vec<Pubkey>>;
+pub type SecondaryReverseIndexEntry<SecondaryIndexKeyType = Pubkey> =
+    RwLock<Vec<SecondaryIndexKeyType>>;
+
+/// The type of the keys a secondary index maps to account pubkeys
+pub trait SecondaryIndexKey: Debug + Default + Copy + Ord + Hash + Sync + Send {}
+impl<T: Debug + Default + Copy + Ord + Hash + Sync + Send> SecondaryIndexKey for T {}
 
 pub trait SecondaryIndexEntry: Debug {
     fn insert_if_not_exists(&self, key: &Pubkey, inner_keys_count: &AtomicU64);
@@ -100,16 +106,21 @@ impl SecondaryIndexEntry for RwLockSecondaryIndexEntry {
 }
 
 #[derive(Debug, Default)]
-pub struct SecondaryIndex<SecondaryIndexEntryType: SecondaryIndexEntry + Default + Sync + Send> {
+pub struct SecondaryIndex<
+    SecondaryIndexEntryType: SecondaryIndexEntry + Default + Sync + Send,
+    SecondaryIndexKeyType: SecondaryIndexKey = Pubkey,
+> {
     metrics_name: &'static str,
     // Map from index keys to index values
-    pub index: DashMap<Pubkey, SecondaryIndexEntryType>,
-    pub reverse_index: DashMap<Pubkey, SecondaryReverseIndexEntry>,
+    pub index: DashMap<SecondaryIndexKeyType, SecondaryIndexEntryType>,
+    pub reverse_index: DashMap<Pubkey, SecondaryReverseIndexEntry<SecondaryIndexKeyType>>,
     stats: SecondaryIndexStats,
 }
 
-impl<SecondaryIndexEntryType: SecondaryIndexEntry + Default + Sync + Send>
-    SecondaryIndex<SecondaryIndexEntryType>
+impl<
+        SecondaryIndexEntryType: SecondaryIndexEntry + Default + Sync + Send,
+        SecondaryIndexKeyType: SecondaryIndexKey,
+    > SecondaryIndex<SecondaryIndexEntryType, SecondaryIndexKeyType>
 {
     pub fn new(metrics_name: &'static str) -> Self {
         Self {
@@ -118,7 +129,7 @@ impl<SecondaryIndexEntryType: SecondaryIndexEntry + Default + Sync + Send>
         }
     }
 
-    pub fn insert(&self, key: &Pubkey, inner_key: &Pubkey) {
+    pub fn insert(&self, key: &SecondaryIndexKeyType, inner_key: &Pubkey) {
         {
             let pubkeys_map = self
                 .index
@@ -164,7 +175,7 @@ impl<SecondaryIndexEntryType: SecondaryIndexEntry + Default + Sync + Send>
     }
 
     // Only safe to call from `remove_by_inner_key()` due to asserts
-    fn remove_index_entries(&self, outer_key: &Pubkey, removed_inner_key: &Pubkey) {
+    fn remove_index_entries(&self, outer_key: &SecondaryIndexKeyType, removed_inner_key: &Pubkey) {
         let is_outer_key_empty = {
             let inner_key_map = self
                 .index
@@ -191,7 +202,7 @@ impl<SecondaryIndexEntryType: SecondaryIndexEntry + Default + Sync + Send>
     pub fn remove_by_inner_key(&self, inner_key: &Pubkey) {
         // Save off which keys in `self.index` had slots removed so we can remove them
         // after we purge the reverse index
-        let mut removed_outer_keys: HashSet<Pubkey> = HashSet::new();
+        let mut removed_outer_keys: HashSet<SecondaryIndexKeyType> = HashSet::new();
 
         // Check if the entry for `inner_key` in the reverse index is empty
         // and can be removed
@@ -215,7 +226,7 @@ impl<SecondaryIndexEntryType: SecondaryIndexEntry + Default + Sync + Send>
             .fetch_sub(removed_outer_keys.len() as u64, Ordering::Relaxed);
     }
 
-    pub fn get(&self, key: &Pubkey) -> Vec<Pubkey> {
+    pub fn get(&self, key: &SecondaryIndexKeyType) -> Vec<Pubkey> {
         if let Some(inner_keys_map) = self.index.get(key) {
             inner_keys_map.keys()
         } else {
@@ -235,6 +246,6 @@ impl<SecondaryIndexEntryType: SecondaryIndexEntry + Default + Sync + Send>
             .iter()
             .rev()
             .take(20)
-            .for_each(|(v, k)| info!("owner: {}, accounts: {}", k, v));
+            .for_each(|(v, k)| info!("owner: {:?}, accounts: {}", k, v));
     }
 }
@@ -286,12 +286,13 @@ pub mod tests {
         super::*,
         crate::{
             account_storage::meta::{AccountMeta, StoredAccountMeta, StoredMeta},
-            append_vec::AppendVecStoredAccountMeta,
+            append_vec::{compression::AccountDataCompression, AppendVecStoredAccountMeta},
         },
         solana_sdk::{
             account::{accounts_equal, AccountSharedData, WritableAccount},
             hash::Hash,
         },
+        std::sync::OnceLock,
     };
 
     fn compare<
@@ -338,6 +339,8 @@ pub mod tests {
             meta: &meta,
             account_meta: &account_meta,
             data: &data,
+            compression: AccountDataCompression::None,
+            decompressed_data: OnceLock::default(),
             offset,
             stored_size,
             hash: &hash,
@@ -393,6 +396,8 @@ pub mod tests {
                             meta: &raw.3,
                             account_meta: &raw.4,
                             data: &data,
+                            compression: AccountDataCompression::None,
+                            decompressed_data: OnceLock::default(),
                             offset,
                             stored_size,
                             hash: &hash,
@@ -492,6 +497,8 @@ pub mod tests {
                     meta: &raw[entry as usize].2,
                     account_meta: &raw[entry as usize].3,
                     data: &data,
+                    compression: AccountDataCompression::None,
+                    decompressed_data: OnceLock::default(),
                     offset,
                     stored_size,
                     hash: &hashes[entry as usize],
@@ -3,7 +3,9 @@
     since = "1.18.0",
     note = "Please use `solana_sdk::inner_instruction` types instead"
 )]
-pub use solana_sdk::inner_instruction::{InnerInstruction, InnerInstructionsList};
+pub use solana_sdk::inner_instruction::{
+    InnerInstruction, InnerInstructionsList, InstructionComputeUnitsList,
+};
 use {
     solana_program_runtime::loaded_programs::LoadedProgramsForTxBatch,
     solana_sdk::{
@@ -74,6 +76,8 @@ pub struct TransactionExecutionDetails {
     pub status: transaction::Result<()>,
     pub log_messages: Option<Vec<String>>,
     pub inner_instructions: Option<InnerInstructionsList>,
+    /// Compute units consumed by each instruction, recorded along with `inner_instructions`
+    pub instruction_compute_units: Option<InstructionComputeUnitsList>,
     pub durable_nonce_fee: Option<DurableNonceFee>,
     pub return_data: Option<TransactionReturnData>,
     pub executed_units: u64,
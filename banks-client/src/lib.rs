@@ -7,7 +7,9 @@
 
 pub use {
     crate::error::BanksClientError,
-    solana_banks_interface::{BanksClient as TarpcClient, TransactionStatus},
+    solana_banks_interface::{
+        BanksClient as TarpcClient, TransactionSimulationConfig, TransactionStatus,
+    },
 };
 use {
     borsh::BorshDeserialize,
@@ -164,6 +166,18 @@ impl BanksClient {
             .map_err(Into::into)
     }
 
+    pub fn simulate_transaction_with_config_and_context(
+        &mut self,
+        ctx: Context,
+        transaction: impl Into<VersionedTransaction>,
+        config: TransactionSimulationConfig,
+    ) -> impl Future<Output = Result<BanksTransactionResultWithSimulation, BanksClientError>> + '_
+    {
+        self.inner
+            .simulate_transaction_with_config_and_context(ctx, transaction.into(), config)
+            .map_err(Into::into)
+    }
+
     pub fn get_account_with_commitment_and_context(
         &mut self,
         ctx: Context,
@@ -357,6 +371,22 @@ impl BanksClient {
         self.simulate_transaction_with_commitment(transaction, CommitmentLevel::default())
     }
 
+    /// Simulate a transaction like the preflight checks of RPC, returning its
+    /// logs, compute units consumed and return data without processing it
+    pub async fn simulate_transaction_with_config(
+        &mut self,
+        transaction: impl Into<VersionedTransaction>,
+        config: TransactionSimulationConfig,
+    ) -> Result<BanksTransactionResultWithSimulation, BanksClientError> {
+        if config.sig_verify && config.replace_recent_blockhash {
+            return Err(BanksClientError::ClientError(
+                "sig_verify may not be used with replace_recent_blockhash",
+            ));
+        }
+        self.simulate_transaction_with_config_and_context(context::current(), transaction, config)
+            .await
+    }
+
     /// Return the most recent rooted slot. All transactions at or below this slot
     /// are said to be finalized. The cluster will not fork to a higher slot.
     pub fn get_root_slot(&mut self) -> impl Future<Output = Result<Slot, BanksClientError>> + '_ {
@@ -558,7 +588,10 @@ mod tests {
             genesis_utils::create_genesis_config,
         },
         solana_sdk::{
-            message::Message, signature::Signer, system_instruction, transaction::Transaction,
+            message::Message,
+            signature::Signer,
+            system_instruction,
+            transaction::{Transaction, TransactionError},
         },
         std::sync::{Arc, RwLock},
         tarpc::transport,
@@ -613,6 +646,100 @@ mod tests {
         })
     }
 
+    #[test]
+    #[allow(clippy::result_large_err)]
+    fn test_banks_server_simulate_transaction_with_config() -> Result<(), BanksClientError> {
+        let genesis = create_genesis_config(10);
+        let bank = Bank::new_for_tests(&genesis.genesis_config);
+        let slot = bank.slot();
+        let block_commitment_cache = Arc::new(RwLock::new(
+            BlockCommitmentCache::new_for_tests_with_slots(slot, slot),
+        ));
+        let bank_forks = BankForks::new_rw_arc(bank);
+
+        let mint_pubkey = genesis.mint_keypair.pubkey();
+        let bob_pubkey = solana_sdk::pubkey::new_rand();
+        let instruction = system_instruction::transfer(&mint_pubkey, &bob_pubkey, 1);
+        let message = Message::new(&[instruction], Some(&mint_pubkey));
+
+        Runtime::new()?.block_on(async {
+            let client_transport =
+                start_local_server(bank_forks, block_commitment_cache, Duration::from_millis(1))
+                    .await;
+            let mut banks_client = start_client(client_transport).await?;
+
+            // The blockhash is replaced, so the transaction may be signed with any blockhash
+            let transaction = Transaction::new(&[&genesis.mint_keypair], message, Hash::default());
+            let config = TransactionSimulationConfig {
+                replace_recent_blockhash: true,
+                accounts: Some(vec![bob_pubkey]),
+                ..TransactionSimulationConfig::default()
+            };
+            let simulation_result = banks_client
+                .simulate_transaction_with_config(transaction.clone(), config.clone())
+                .await?;
+            assert!(simulation_result.result.unwrap().is_ok());
+            let simulation_details = simulation_result.simulation_details.unwrap();
+            assert_eq!(
+                simulation_details.logs,
+                vec![
+                    "Program 11111111111111111111111111111111 invoke [1]".to_string(),
+                    "Program 11111111111111111111111111111111 success".to_string(),
+                ]
+            );
+            assert!(simulation_details.units_consumed > 0);
+            assert_eq!(simulation_details.return_data, None);
+            assert_eq!(simulation_details.inner_instructions, None);
+            let accounts = simulation_details.accounts.unwrap();
+            assert_eq!(accounts.len(), 1);
+            assert_eq!(accounts[0].as_ref().unwrap().lamports, 1);
+
+            // The transaction was not processed
+            assert_eq!(banks_client.get_balance(bob_pubkey).await?, 0);
+
+            // The signatures no longer match the transaction once its blockhash is replaced
+            assert!(matches!(
+                banks_client
+                    .simulate_transaction_with_config(
+                        transaction.clone(),
+                        TransactionSimulationConfig {
+                            sig_verify: true,
+                            ..config.clone()
+                        },
+                    )
+                    .await,
+                Err(BanksClientError::ClientError(_))
+            ));
+
+            let recent_blockhash = banks_client.get_latest_blockhash().await?;
+            let mut transaction = Transaction::new(
+                &[&genesis.mint_keypair],
+                transaction.message,
+                recent_blockhash,
+            );
+            transaction.signatures[0] = Signature::default();
+            let simulation_result = banks_client
+                .simulate_transaction_with_config(transaction.clone(), config)
+                .await?;
+            assert!(simulation_result.result.unwrap().is_ok());
+            let simulation_result = banks_client
+                .simulate_transaction_with_config(
+                    transaction,
+                    TransactionSimulationConfig {
+                        sig_verify: true,
+                        ..TransactionSimulationConfig::default()
+                    },
+                )
+                .await?;
+            assert_eq!(
+                simulation_result.result,
+                Some(Err(TransactionError::SignatureFailure))
+            );
+            assert_eq!(simulation_result.simulation_details, None);
+            Ok(())
+        })
+    }
+
     #[test]
     #[allow(clippy::result_large_err)]
     fn test_banks_server_transfer_via_client() -> Result<(), BanksClientError> {
@@ -8,7 +8,7 @@ use {
         commitment_config::CommitmentLevel,
         fee_calculator::FeeCalculator,
         hash::Hash,
-        inner_instruction::InnerInstructions,
+        inner_instruction::{InnerInstructions, InstructionComputeUnitsList},
         message::Message,
         pubkey::Pubkey,
         signature::Signature,
@@ -39,6 +39,27 @@ pub struct TransactionSimulationDetails {
     pub units_consumed: u64,
     pub return_data: Option<TransactionReturnData>,
     pub inner_instructions: Option<Vec<InnerInstructions>>,
+    /// Compute units consumed by each instruction and its inner instructions
+    pub instruction_compute_units: Option<InstructionComputeUnitsList>,
+    /// The state after the simulation of the accounts requested by
+    /// `TransactionSimulationConfig::accounts`
+    pub accounts: Option<Vec<Option<Account>>>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionSimulationConfig {
+    /// Verify the signatures of the transaction, which conflicts with
+    /// `replace_recent_blockhash`
+    pub sig_verify: bool,
+    /// Replace the recent blockhash of the transaction with the latest
+    /// blockhash of the bank
+    pub replace_recent_blockhash: bool,
+    pub commitment: CommitmentLevel,
+    /// Record the inner instructions and their compute units
+    pub inner_instructions: bool,
+    /// Return the state of these accounts after the simulation
+    pub accounts: Option<Vec<Pubkey>>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -90,6 +111,10 @@ pub trait Banks {
         transaction: VersionedTransaction,
         commitment: CommitmentLevel,
     ) -> BanksTransactionResultWithSimulation;
+    async fn simulate_transaction_with_config_and_context(
+        transaction: VersionedTransaction,
+        config: TransactionSimulationConfig,
+    ) -> BanksTransactionResultWithSimulation;
     async fn get_account_with_commitment_and_context(
         address: Pubkey,
         commitment: CommitmentLevel,
@@ -6,7 +6,7 @@ use {
     solana_banks_interface::{
         Banks, BanksRequest, BanksResponse, BanksTransactionResultWithMetadata,
         BanksTransactionResultWithSimulation, TransactionConfirmationStatus, TransactionMetadata,
-        TransactionSimulationDetails, TransactionStatus,
+        TransactionSimulationConfig, TransactionSimulationDetails, TransactionStatus,
     },
     solana_client::connection_cache::ConnectionCache,
     solana_runtime::{
@@ -174,6 +174,26 @@ fn simulate_transaction(
     bank: &Bank,
     transaction: VersionedTransaction,
 ) -> BanksTransactionResultWithSimulation {
+    simulate_transaction_with_config(
+        bank,
+        transaction,
+        &TransactionSimulationConfig {
+            inner_instructions: true,
+            ..TransactionSimulationConfig::default()
+        },
+    )
+}
+
+fn simulate_transaction_with_config(
+    bank: &Bank,
+    mut transaction: VersionedTransaction,
+    config: &TransactionSimulationConfig,
+) -> BanksTransactionResultWithSimulation {
+    if config.replace_recent_blockhash {
+        transaction
+            .message
+            .set_recent_blockhash(bank.last_blockhash());
+    }
     let sanitized_transaction = match SanitizedTransaction::try_create(
         transaction,
         MessageHash::Compute,
@@ -188,20 +208,44 @@ fn simulate_transaction(
         }
         Ok(tx) => tx,
     };
+    if config.sig_verify {
+        if let Err(err) = verify_transaction(&sanitized_transaction, &bank.feature_set) {
+            return BanksTransactionResultWithSimulation {
+                result: Some(Err(err)),
+                simulation_details: None,
+            };
+        }
+    }
     let TransactionSimulationResult {
         result,
         logs,
-        post_simulation_accounts: _,
+        post_simulation_accounts,
         units_consumed,
         return_data,
         inner_instructions,
-    } = bank.simulate_transaction_unchecked(&sanitized_transaction, false);
-
+        instruction_compute_units,
+    } = bank.simulate_transaction_unchecked(&sanitized_transaction, config.inner_instructions);
+
+    let accounts = config.accounts.as_ref().map(|addresses| {
+        addresses
+            .iter()
+            .map(|address| {
+                post_simulation_accounts
+                    .iter()
+                    .find(|(pubkey, _account)| pubkey == address)
+                    .map(|(_pubkey, account)| account.clone())
+                    .or_else(|| bank.get_account(address))
+                    .map(Account::from)
+            })
+            .collect()
+    });
     let simulation_details = TransactionSimulationDetails {
         logs,
         units_consumed,
         return_data,
         inner_instructions,
+        instruction_compute_units,
+        accounts,
     };
     BanksTransactionResultWithSimulation {
         result: Some(result),
@@ -321,6 +365,15 @@ impl Banks for BanksServer {
         simulate_transaction(&self.bank(commitment), transaction)
     }
 
+    async fn simulate_transaction_with_config_and_context(
+        self,
+        _: Context,
+        transaction: VersionedTransaction,
+        config: TransactionSimulationConfig,
+    ) -> BanksTransactionResultWithSimulation {
+        simulate_transaction_with_config(&self.bank(config.commitment), transaction, &config)
+    }
+
     async fn process_transaction_with_commitment_and_context(
         self,
         _: Context,
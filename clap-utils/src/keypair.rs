@@ -20,7 +20,7 @@ use {
     rpassword::prompt_password,
     solana_remote_wallet::{
         locator::{Locator as RemoteWalletLocator, LocatorError as RemoteWalletLocatorError},
-        remote_keypair::generate_remote_keypair,
+        remote_keypair::{generate_http_signer_keypair, generate_remote_keypair},
         remote_wallet::{maybe_wallet_manager, RemoteWalletError, RemoteWalletManager},
     },
     solana_sdk::{
@@ -400,6 +400,9 @@ const SIGNER_SOURCE_FILEPATH: &str = "file";
 const SIGNER_SOURCE_USB: &str = "usb";
 const SIGNER_SOURCE_STDIN: &str = "stdin";
 const SIGNER_SOURCE_PUBKEY: &str = "pubkey";
+const SIGNER_SOURCE_HTTP: &str = "http";
+const SIGNER_SOURCE_HTTPS: &str = "https";
+const SIGNER_SOURCE_REMOTE: &str = "remote";
 
 pub(crate) enum SignerSourceKind {
     Prompt,
@@ -407,6 +410,8 @@ pub(crate) enum SignerSourceKind {
     Usb(RemoteWalletLocator),
     Stdin,
     Pubkey(Pubkey),
+    /// URL of a remote signing service
+    Remote(String),
 }
 
 impl AsRef<str> for SignerSourceKind {
@@ -417,6 +422,7 @@ impl AsRef<str> for SignerSourceKind {
             Self::Usb(_) => SIGNER_SOURCE_USB,
             Self::Stdin => SIGNER_SOURCE_STDIN,
             Self::Pubkey(_) => SIGNER_SOURCE_PUBKEY,
+            Self::Remote(_) => SIGNER_SOURCE_REMOTE,
         }
     }
 }
@@ -483,6 +489,16 @@ pub(crate) fn parse_signer_source<S: AsRef<str>>(
                         legacy: false,
                     }),
                     SIGNER_SOURCE_STDIN => Ok(SignerSource::new(SignerSourceKind::Stdin)),
+                    SIGNER_SOURCE_HTTP | SIGNER_SOURCE_HTTPS => {
+                        let url = source
+                            .split_once('?')
+                            .map_or(source.as_str(), |(url, _)| url);
+                        Ok(SignerSource {
+                            kind: SignerSourceKind::Remote(url.to_string()),
+                            derivation_path: DerivationPath::from_uri_key_query(&uri)?,
+                            legacy: false,
+                        })
+                    }
                     _ => {
                         #[cfg(target_family = "windows")]
                         // On Windows, an absolute path's drive letter will be parsed as the URI
@@ -801,6 +817,11 @@ pub fn signer_from_path_with_config(
                 Err(RemoteWalletError::NoDeviceFound.into())
             }
         }
+        SignerSourceKind::Remote(url) => Ok(Box::new(generate_http_signer_keypair(
+            url,
+            derivation_path.unwrap_or_default(),
+            matches.is_present("confirm_key"),
+        )?)),
         SignerSourceKind::Pubkey(pubkey) => {
             let presigner = pubkeys_sigs_of(matches, SIGNER_ARG.name)
                 .as_ref()
@@ -930,6 +951,15 @@ pub fn resolve_signer_from_path(
                 Err(RemoteWalletError::NoDeviceFound.into())
             }
         }
+        SignerSourceKind::Remote(url) => {
+            let path = generate_http_signer_keypair(
+                url,
+                derivation_path.unwrap_or_default(),
+                matches.is_present("confirm_key"),
+            )
+            .map(|keypair| keypair.path)?;
+            Ok(Some(path))
+        }
         _ => Ok(Some(path.to_string())),
     }
 }
@@ -1253,6 +1283,13 @@ mod tests {
                 derivation_path: d,
                 legacy: false,
             } if u == expected_locator && d == expected_derivation_path);
+        let remote = "https://signer.example.com/v1?key=0/0".to_string();
+        let expected_derivation_path = Some(DerivationPath::new_bip44(Some(0), Some(0)));
+        assert_matches!(parse_signer_source(remote).unwrap(), SignerSource {
+                kind: SignerSourceKind::Remote(u),
+                derivation_path: d,
+                legacy: false,
+            } if u == "https://signer.example.com/v1" && d == expected_derivation_path);
         // Catchall into SignerSource::Filepath fails
         let junk = "sometextthatisnotapubkeyorfile".to_string();
         assert!(Pubkey::from_str(&junk).is_err());
@@ -22,6 +22,9 @@ const SIGNER_SOURCE_FILEPATH: &str = "file";
 const SIGNER_SOURCE_USB: &str = "usb";
 const SIGNER_SOURCE_STDIN: &str = "stdin";
 const SIGNER_SOURCE_PUBKEY: &str = "pubkey";
+const SIGNER_SOURCE_HTTP: &str = "http";
+const SIGNER_SOURCE_HTTPS: &str = "https";
+const SIGNER_SOURCE_REMOTE: &str = "remote";
 
 #[derive(Debug, Error)]
 pub enum SignerSourceError {
@@ -44,6 +47,8 @@ pub enum SignerSourceKind {
     Usb(RemoteWalletLocator),
     Stdin,
     Pubkey(Pubkey),
+    /// URL of a remote signing service
+    Remote(String),
 }
 
 impl AsRef<str> for SignerSourceKind {
@@ -54,6 +59,7 @@ impl AsRef<str> for SignerSourceKind {
             Self::Usb(_) => SIGNER_SOURCE_USB,
             Self::Stdin => SIGNER_SOURCE_STDIN,
             Self::Pubkey(_) => SIGNER_SOURCE_PUBKEY,
+            Self::Remote(_) => SIGNER_SOURCE_REMOTE,
         }
     }
 }
@@ -130,6 +136,16 @@ impl SignerSource {
                             legacy: false,
                         }),
                         SIGNER_SOURCE_STDIN => Ok(SignerSource::new(SignerSourceKind::Stdin)),
+                        SIGNER_SOURCE_HTTP | SIGNER_SOURCE_HTTPS => {
+                            let url = source
+                                .split_once('?')
+                                .map_or(source.as_str(), |(url, _)| url);
+                            Ok(SignerSource {
+                                kind: SignerSourceKind::Remote(url.to_string()),
+                                derivation_path: DerivationPath::from_uri_key_query(&uri)?,
+                                legacy: false,
+                            })
+                        }
                         _ => {
                             #[cfg(target_family = "windows")]
                             // On Windows, an absolute path's drive letter will be parsed as the URI
@@ -167,6 +183,7 @@ pub struct SignerSourceParserBuilder {
     allow_usb: bool,
     allow_stdin: bool,
     allow_pubkey: bool,
+    allow_remote: bool,
     allow_legacy: bool,
 }
 
@@ -177,6 +194,7 @@ impl SignerSourceParserBuilder {
         self.allow_usb = true;
         self.allow_stdin = true;
         self.allow_pubkey = true;
+        self.allow_remote = true;
         self.allow_legacy = true;
         self
     }
@@ -206,6 +224,11 @@ impl SignerSourceParserBuilder {
         self
     }
 
+    pub fn allow_remote(mut self) -> Self {
+        self.allow_remote = true;
+        self
+    }
+
     pub fn allow_legacy(mut self) -> Self {
         self.allow_legacy = true;
         self
@@ -224,6 +247,7 @@ impl SignerSourceParserBuilder {
                     SignerSourceKind::Usb(_) if self.allow_usb => Ok(signer_source),
                     SignerSourceKind::Stdin if self.allow_stdin => Ok(signer_source),
                     SignerSourceKind::Pubkey(_) if self.allow_pubkey => Ok(signer_source),
+                    SignerSourceKind::Remote(_) if self.allow_remote => Ok(signer_source),
                     _ => Err(SignerSourceError::UnsupportedSource),
                 }
             },
@@ -473,6 +497,13 @@ mod tests {
                 derivation_path: d,
                 legacy: false,
             } if u == expected_locator && d == expected_derivation_path);
+        let remote = "https://signer.example.com/v1?key=0/0".to_string();
+        let expected_derivation_path = Some(DerivationPath::new_bip44(Some(0), Some(0)));
+        assert_matches!(SignerSource::parse(remote).unwrap(), SignerSource {
+                kind: SignerSourceKind::Remote(u),
+                derivation_path: d,
+                legacy: false,
+            } if u == "https://signer.example.com/v1" && d == expected_derivation_path);
         // Catchall into SignerSource::Filepath fails
         let junk = "sometextthatisnotapubkeyorfile".to_string();
         assert!(Pubkey::from_str(&junk).is_err());
@@ -19,7 +19,7 @@ use {
     clap::ArgMatches,
     rpassword::prompt_password,
     solana_remote_wallet::{
-        remote_keypair::generate_remote_keypair,
+        remote_keypair::{generate_http_signer_keypair, generate_remote_keypair},
         remote_wallet::{maybe_wallet_manager, RemoteWalletError, RemoteWalletManager},
     },
     solana_sdk::{
@@ -681,6 +681,14 @@ pub fn signer_from_source_with_config(
                 Err(RemoteWalletError::NoDeviceFound.into())
             }
         }
+        SignerSourceKind::Remote(url) => {
+            let confirm_key = matches.try_contains_id("confirm_key").unwrap_or(false);
+            Ok(Box::new(generate_http_signer_keypair(
+                url.clone(),
+                derivation_path.clone().unwrap_or_default(),
+                confirm_key,
+            )?))
+        }
         SignerSourceKind::Pubkey(pubkey) => {
             let presigner = try_pubkeys_sigs_of(matches, SIGNER_ARG.name)?
                 .as_ref()
@@ -830,6 +838,16 @@ pub fn resolve_signer_from_source(
                 Err(RemoteWalletError::NoDeviceFound.into())
             }
         }
+        SignerSourceKind::Remote(url) => {
+            let confirm_key = matches.try_contains_id("confirm_key").unwrap_or(false);
+            let path = generate_http_signer_keypair(
+                url.clone(),
+                derivation_path.clone().unwrap_or_default(),
+                confirm_key,
+            )
+            .map(|keypair| keypair.path)?;
+            Ok(Some(path))
+        }
         SignerSourceKind::Pubkey(pubkey) => Ok(Some(pubkey.to_string())),
     }
 }
@@ -956,6 +956,140 @@ impl fmt::Display for CliKeyedStakeState {
     }
 }
 
+/// The stake accounts of an authority, with their stake summed per vote account
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliStakeInventory {
+    pub stake_accounts: Vec<CliKeyedStakeState>,
+    pub vote_account_totals: Vec<CliVoteAccountStakeTotals>,
+    pub totals: CliStakeTotals,
+    #[serde(skip_serializing)]
+    pub use_lamports_unit: bool,
+}
+
+impl CliStakeInventory {
+    pub fn new(stake_accounts: Vec<CliKeyedStakeState>, use_lamports_unit: bool) -> Self {
+        let mut totals = CliStakeTotals::default();
+        let mut vote_account_totals = BTreeMap::<String, CliStakeTotals>::new();
+        for CliKeyedStakeState { stake_state, .. } in &stake_accounts {
+            totals.add(stake_state);
+            if let Some(vote_account_address) = &stake_state.delegated_vote_account_address {
+                vote_account_totals
+                    .entry(vote_account_address.clone())
+                    .or_default()
+                    .add(stake_state);
+            }
+        }
+        let mut vote_account_totals: Vec<_> = vote_account_totals
+            .into_iter()
+            .map(|(vote_account_address, totals)| CliVoteAccountStakeTotals {
+                vote_account_address,
+                totals,
+            })
+            .collect();
+        vote_account_totals.sort_by(|a, b| {
+            b.totals
+                .delegated_stake
+                .cmp(&a.totals.delegated_stake)
+                .then_with(|| a.vote_account_address.cmp(&b.vote_account_address))
+        });
+        Self {
+            stake_accounts,
+            vote_account_totals,
+            totals,
+            use_lamports_unit,
+        }
+    }
+
+    fn write_totals(&self, w: &mut dyn fmt::Write) -> fmt::Result {
+        let balance = |lamports| build_balance_message(lamports, self.use_lamports_unit, true);
+        let write_totals = |w: &mut dyn fmt::Write, name: &str, totals: &CliStakeTotals| {
+            writeln!(
+                w,
+                "{:<44}  {:>8}  {:>20}  {:>20}  {:>20}  {:>20}",
+                name,
+                totals.num_stake_accounts,
+                balance(totals.delegated_stake),
+                balance(totals.active_stake),
+                balance(totals.activating_stake),
+                balance(totals.deactivating_stake),
+            )
+        };
+
+        writeln!(w)?;
+        writeln!(
+            w,
+            "{}",
+            style(format!(
+                "{:<44}  {:>8}  {:>20}  {:>20}  {:>20}  {:>20}",
+                "Vote Account", "Accounts", "Delegated", "Active", "Activating", "Deactivating",
+            ))
+            .bold()
+        )?;
+        for CliVoteAccountStakeTotals {
+            vote_account_address,
+            totals,
+        } in &self.vote_account_totals
+        {
+            write_totals(w, vote_account_address, totals)?;
+        }
+        write_totals(w, "Total", &self.totals)?;
+        writeln!(w)?;
+        writeln_name_value(w, "Total Balance:", &balance(self.totals.account_balance))
+    }
+}
+
+impl QuietDisplay for CliStakeInventory {}
+impl VerboseDisplay for CliStakeInventory {
+    fn write_str(&self, w: &mut dyn std::fmt::Write) -> std::fmt::Result {
+        for state in &self.stake_accounts {
+            writeln!(w)?;
+            VerboseDisplay::write_str(state, w)?;
+        }
+        self.write_totals(w)
+    }
+}
+
+impl fmt::Display for CliStakeInventory {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for state in &self.stake_accounts {
+            writeln!(f)?;
+            write!(f, "{state}")?;
+        }
+        self.write_totals(f)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliVoteAccountStakeTotals {
+    pub vote_account_address: String,
+    #[serde(flatten)]
+    pub totals: CliStakeTotals,
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliStakeTotals {
+    pub num_stake_accounts: usize,
+    pub account_balance: u64,
+    pub delegated_stake: u64,
+    pub active_stake: u64,
+    pub activating_stake: u64,
+    pub deactivating_stake: u64,
+}
+
+impl CliStakeTotals {
+    fn add(&mut self, stake_state: &CliStakeState) {
+        self.num_stake_accounts += 1;
+        self.account_balance += stake_state.account_balance;
+        self.delegated_stake += stake_state.delegated_stake.unwrap_or_default();
+        self.active_stake += stake_state.active_stake.unwrap_or_default();
+        self.activating_stake += stake_state.activating_stake.unwrap_or_default();
+        self.deactivating_stake += stake_state.deactivating_stake.unwrap_or_default();
+    }
+}
+
 #[derive(Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CliEpochReward {
@@ -1878,6 +2012,93 @@ impl fmt::Display for CliSignature {
     }
 }
 
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliSimulatedTransaction {
+    pub fee: u64,
+    pub units_consumed: Option<u64>,
+    pub err: Option<String>,
+    pub balance_changes: Vec<CliSimulatedBalanceChange>,
+    pub logs: Vec<String>,
+}
+
+impl CliSimulatedTransaction {
+    fn write(&self, f: &mut dyn fmt::Write, show_logs: bool) -> fmt::Result {
+        writeln!(f)?;
+        writeln_name_value(
+            f,
+            "Simulated Fee:",
+            &build_balance_message(self.fee, false, true),
+        )?;
+        if let Some(units_consumed) = self.units_consumed {
+            writeln_name_value(
+                f,
+                "Simulated Compute Units Consumed:",
+                &units_consumed.to_string(),
+            )?;
+        }
+        writeln_name_value(
+            f,
+            "Simulated Status:",
+            &match &self.err {
+                None => "Ok".to_string(),
+                Some(err) => format!("Error: {err}"),
+            },
+        )?;
+        if !self.balance_changes.is_empty() {
+            writeln!(f, "Balance Changes:")?;
+            for balance_change in &self.balance_changes {
+                let change = balance_change
+                    .post_balance
+                    .abs_diff(balance_change.pre_balance);
+                writeln!(
+                    f,
+                    "  {}: {} -> {} ({}{})",
+                    balance_change.address,
+                    build_balance_message(balance_change.pre_balance, false, true),
+                    build_balance_message(balance_change.post_balance, false, true),
+                    if balance_change.post_balance < balance_change.pre_balance {
+                        "-"
+                    } else {
+                        "+"
+                    },
+                    build_balance_message(change, false, true),
+                )?;
+            }
+        }
+        if show_logs && !self.logs.is_empty() {
+            writeln!(f, "Log Messages:")?;
+            for log in &self.logs {
+                writeln!(f, "  {log}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl QuietDisplay for CliSimulatedTransaction {}
+impl VerboseDisplay for CliSimulatedTransaction {
+    fn write_str(&self, w: &mut dyn std::fmt::Write) -> std::fmt::Result {
+        self.write(w, true)
+    }
+}
+
+impl fmt::Display for CliSimulatedTransaction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.write(f, false)
+    }
+}
+
+/// The balance of an account before and after a simulated transaction, in
+/// lamports
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliSimulatedBalanceChange {
+    pub address: String,
+    pub pre_balance: u64,
+    pub post_balance: u64,
+}
+
 #[derive(Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CliAccountBalances {
@@ -2342,6 +2563,68 @@ impl fmt::Display for CliUpgradeableProgramExtended {
     }
 }
 
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliProgramVerification {
+    pub program_id: String,
+    pub program_hash: String,
+    pub program_len: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub local_program_location: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub local_program_hash: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub local_program_len: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected_hash: Option<String>,
+    pub mismatch_offsets: Vec<usize>,
+    pub verified: bool,
+}
+impl QuietDisplay for CliProgramVerification {}
+impl VerboseDisplay for CliProgramVerification {}
+impl fmt::Display for CliProgramVerification {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f)?;
+        writeln_name_value(f, "Program Id:", &self.program_id)?;
+        writeln_name_value(f, "Program Hash:", &self.program_hash)?;
+        writeln_name_value(
+            f,
+            "Program Length:",
+            &format!("{} ({:#x}) bytes", self.program_len, self.program_len),
+        )?;
+        if let Some(local_program_location) = &self.local_program_location {
+            writeln_name_value(f, "Local Program:", local_program_location)?;
+        }
+        if let Some(local_program_hash) = &self.local_program_hash {
+            writeln_name_value(f, "Local Program Hash:", local_program_hash)?;
+        }
+        if let Some(local_program_len) = self.local_program_len {
+            writeln_name_value(
+                f,
+                "Local Program Length:",
+                &format!("{local_program_len} ({local_program_len:#x}) bytes"),
+            )?;
+        }
+        if let Some(expected_hash) = &self.expected_hash {
+            writeln_name_value(f, "Expected Hash:", expected_hash)?;
+        }
+        if !self.mismatch_offsets.is_empty() {
+            writeln_name_value(
+                f,
+                "Mismatch Offsets:",
+                &self
+                    .mismatch_offsets
+                    .iter()
+                    .map(|offset| format!("{offset:#x}"))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            )?;
+        }
+        writeln_name_value(f, "Verified:", if self.verified { "yes" } else { "no" })?;
+        Ok(())
+    }
+}
+
 #[derive(Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CliUpgradeableBuffer {
@@ -3387,4 +3670,64 @@ mod tests {
         assert_eq!(s, "Account Balance: 0.00001 SOL\nValidator Identity: 11111111111111111111111111111111\nVote Authority: {}\nWithdraw Authority: \nCredits: 0\nCommission: 0%\nRoot Slot: ~\nRecent Timestamp: 1970-01-01T00:00:00Z from slot 0\nEpoch Rewards:\nEpoch,Reward Slot,Time,Amount,New Balance,Percent Change,APR,Commission\n1,100,1970-01-01 00:00:00 UTC,0.00000001,0.0000001,11%,10.00%,1%\n2,200,1970-01-12 13:46:40 UTC,0.000000012,0.0000001,11%,13.00%,1%\n");
         println!("{s}");
     }
+
+    #[test]
+    fn test_stake_inventory() {
+        let vote_account = Pubkey::new_unique().to_string();
+        let other_vote_account = Pubkey::new_unique().to_string();
+        let stake_account =
+            |vote_account: Option<&String>, delegated_stake, active_stake| CliKeyedStakeState {
+                stake_pubkey: Pubkey::new_unique().to_string(),
+                stake_state: CliStakeState {
+                    account_balance: 1_000,
+                    delegated_stake,
+                    delegated_vote_account_address: vote_account.cloned(),
+                    active_stake,
+                    activating_stake: delegated_stake
+                        .zip(active_stake)
+                        .map(|(delegated, active)| delegated - active),
+                    ..CliStakeState::default()
+                },
+            };
+        let inventory = CliStakeInventory::new(
+            vec![
+                stake_account(Some(&vote_account), Some(400), Some(100)),
+                stake_account(Some(&other_vote_account), Some(800), Some(800)),
+                stake_account(Some(&vote_account), Some(500), Some(500)),
+                stake_account(None, None, None),
+            ],
+            true,
+        );
+        assert_eq!(inventory.vote_account_totals.len(), 2);
+        assert_eq!(
+            inventory.vote_account_totals[0].vote_account_address,
+            vote_account
+        );
+        assert_eq!(
+            inventory.vote_account_totals[0].totals,
+            CliStakeTotals {
+                num_stake_accounts: 2,
+                account_balance: 2_000,
+                delegated_stake: 900,
+                active_stake: 600,
+                activating_stake: 300,
+                deactivating_stake: 0,
+            }
+        );
+        assert_eq!(
+            inventory.vote_account_totals[1].vote_account_address,
+            other_vote_account
+        );
+        assert_eq!(
+            inventory.totals,
+            CliStakeTotals {
+                num_stake_accounts: 4,
+                account_balance: 4_000,
+                delegated_stake: 1_700,
+                active_stake: 1_400,
+                activating_stake: 300,
+                deactivating_stake: 0,
+            }
+        );
+    }
 }
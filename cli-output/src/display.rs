@@ -819,6 +819,7 @@ mod test {
                 data: vec![1, 2, 3],
             }),
             compute_units_consumed: Some(1234u64),
+            stake_changes: None,
         };
 
         let output = {
@@ -898,6 +899,7 @@ Rewards:
                 data: vec![1, 2, 3],
             }),
             compute_units_consumed: Some(2345u64),
+            stake_changes: None,
         };
 
         let output = {
@@ -101,6 +101,15 @@ pub fn get_clap_app<'ab, 'v>(name: &str, about: &'ab str, version: &'v str) -> A
                 .conflicts_with("use_quic")
                 .help("Use UDP when sending transactions."),
         )
+        .arg(
+            Arg::with_name("simulate_only")
+                .long("simulate-only")
+                .global(true)
+                .help(
+                    "Simulate the transaction of the command instead of sending it, reporting \
+                     its fee, the compute units it consumes and the balances it changes",
+                ),
+        )
         .arg(
             Arg::with_name("no_address_labels")
                 .long("no-address-labels")
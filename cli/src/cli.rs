@@ -1,7 +1,8 @@
 use {
     crate::{
         address_lookup_table::*, clap_app::*, cluster_query::*, feature::*, inflation::*, nonce::*,
-        program::*, program_v4::*, spend_utils::*, stake::*, validator_info::*, vote::*, wallet::*,
+        program::*, program_v4::*, simulate::supports_simulate_only, spend_utils::*, stake::*,
+        validator_info::*, vote::*, wallet::*,
     },
     clap::{crate_description, crate_name, value_t_or_exit, ArgMatches, Shell},
     log::*,
@@ -109,6 +110,7 @@ pub enum CliCommand {
         use_lamports_unit: bool,
         vote_account_pubkeys: Option<Vec<Pubkey>>,
         withdraw_authority: Option<Pubkey>,
+        by_authority: Option<Pubkey>,
     },
     ShowValidators {
         use_lamports_unit: bool,
@@ -139,6 +141,7 @@ pub enum CliCommand {
         memo: Option<String>,
         new_authority: Pubkey,
         compute_unit_price: Option<u64>,
+        advance: bool,
     },
     CreateNonceAccount {
         nonce_account: SignerIndex,
@@ -255,7 +258,9 @@ pub enum CliCommand {
     },
     ShowStakeHistory {
         use_lamports_unit: bool,
-        limit_results: usize,
+        limit_results: Option<usize>,
+        first_epoch: Option<Epoch>,
+        last_epoch: Option<Epoch>,
     },
     ShowStakeAccount {
         pubkey: Pubkey,
@@ -519,6 +524,7 @@ pub struct CliConfig<'a> {
     pub confirm_transaction_initial_timeout: Duration,
     pub address_labels: HashMap<String, String>,
     pub use_quic: bool,
+    pub simulate_only: bool,
 }
 
 impl CliConfig<'_> {
@@ -567,6 +573,7 @@ impl Default for CliConfig<'_> {
             ),
             address_labels: HashMap::new(),
             use_quic: !DEFAULT_TPU_ENABLE_UDP,
+            simulate_only: false,
         }
     }
 }
@@ -873,6 +880,10 @@ pub fn process_command(config: &CliConfig) -> ProcessResult {
         config.rpc_client.as_ref().unwrap().clone()
     };
 
+    if config.simulate_only && !supports_simulate_only(&config.command) {
+        return Err("This command does not support --simulate-only".into());
+    }
+
     match &config.command {
         // Cluster Query Commands
         // Get address of this client
@@ -956,12 +967,14 @@ pub fn process_command(config: &CliConfig) -> ProcessResult {
             use_lamports_unit,
             vote_account_pubkeys,
             withdraw_authority,
+            by_authority,
         } => process_show_stakes(
             &rpc_client,
             config,
             *use_lamports_unit,
             vote_account_pubkeys.as_deref(),
             withdraw_authority.as_ref(),
+            by_authority.as_ref(),
         ),
         CliCommand::WaitForMaxStake { max_stake_percent } => {
             process_wait_for_max_stake(&rpc_client, config, *max_stake_percent)
@@ -1012,6 +1025,7 @@ pub fn process_command(config: &CliConfig) -> ProcessResult {
             memo,
             new_authority,
             compute_unit_price,
+            advance,
         } => process_authorize_nonce_account(
             &rpc_client,
             config,
@@ -1020,6 +1034,7 @@ pub fn process_command(config: &CliConfig) -> ProcessResult {
             memo.as_ref(),
             new_authority,
             compute_unit_price.as_ref(),
+            *advance,
         ),
         // Create nonce account
         CliCommand::CreateNonceAccount {
@@ -1291,7 +1306,16 @@ pub fn process_command(config: &CliConfig) -> ProcessResult {
         CliCommand::ShowStakeHistory {
             use_lamports_unit,
             limit_results,
-        } => process_show_stake_history(&rpc_client, config, *use_lamports_unit, *limit_results),
+            first_epoch,
+            last_epoch,
+        } => process_show_stake_history(
+            &rpc_client,
+            config,
+            *use_lamports_unit,
+            *limit_results,
+            *first_epoch,
+            *last_epoch,
+        ),
         CliCommand::StakeAuthorize {
             stake_account_pubkey,
             ref new_authorizations,
@@ -354,6 +354,14 @@ impl ClusterQuerySubCommands for App<'_, '_> {
                         .value_name("PUBKEY")
                         .long("withdraw-authority"),
                     "Only show stake accounts with the provided withdraw authority."
+                ))
+                .arg(pubkey!(
+                    Arg::with_name("by_authority")
+                        .value_name("PUBKEY")
+                        .long("by-authority")
+                        .conflicts_with("withdraw_authority"),
+                    "Only show stake accounts with the provided stake or withdraw authority, \
+                     and total their stake per vote account."
                 )),
         )
         .subcommand(
@@ -655,11 +663,13 @@ pub fn parse_show_stakes(
     let vote_account_pubkeys =
         pubkeys_of_multiple_signers(matches, "vote_account_pubkeys", wallet_manager)?;
     let withdraw_authority = pubkey_of(matches, "withdraw_authority");
+    let by_authority = pubkey_of(matches, "by_authority");
     Ok(CliCommandInfo {
         command: CliCommand::ShowStakes {
             use_lamports_unit,
             vote_account_pubkeys,
             withdraw_authority,
+            by_authority,
         },
         signers: vec![],
     })
@@ -1775,6 +1785,7 @@ pub fn process_show_stakes(
     use_lamports_unit: bool,
     vote_account_pubkeys: Option<&[Pubkey]>,
     withdraw_authority_pubkey: Option<&Pubkey>,
+    by_authority_pubkey: Option<&Pubkey>,
 ) -> ProcessResult {
     use crate::stake::build_stake_state;
 
@@ -1815,8 +1826,30 @@ pub fn process_show_stakes(
         filters.push(withdrawer_filter);
     }
 
-    let all_stake_accounts = rpc_client
-        .get_program_accounts_with_config(&stake::program::id(), program_accounts_config)?;
+    let all_stake_accounts = if let Some(by_authority_pubkey) = by_authority_pubkey {
+        // The filters of a query must all match, so query the accounts of
+        // each authority separately: `Meta::authorized::staker` begins at
+        // byte offset 12, and `Meta::authorized::withdrawer` at 44
+        let mut all_stake_accounts = BTreeMap::new();
+        for offset in [12, 44] {
+            let mut program_accounts_config = program_accounts_config.clone();
+            program_accounts_config
+                .filters
+                .get_or_insert(vec![])
+                .push(RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+                    offset,
+                    by_authority_pubkey.as_ref(),
+                )));
+            all_stake_accounts.extend(rpc_client.get_program_accounts_with_config(
+                &stake::program::id(),
+                program_accounts_config,
+            )?);
+        }
+        all_stake_accounts.into_iter().collect()
+    } else {
+        rpc_client
+            .get_program_accounts_with_config(&stake::program::id(), program_accounts_config)?
+    };
     let stake_history_account = rpc_client.get_account(&stake_history::id())?;
     let clock_account = rpc_client.get_account(&sysvar::clock::id())?;
     let clock: Clock = from_account(&clock_account).ok_or_else(|| {
@@ -1874,9 +1907,15 @@ pub fn process_show_stakes(
             }
         }
     }
-    Ok(config
-        .output_format
-        .formatted_string(&CliStakeVec::new(stake_accounts)))
+    if by_authority_pubkey.is_some() {
+        Ok(config
+            .output_format
+            .formatted_string(&CliStakeInventory::new(stake_accounts, use_lamports_unit)))
+    } else {
+        Ok(config
+            .output_format
+            .formatted_string(&CliStakeVec::new(stake_accounts)))
+    }
 }
 
 pub fn process_wait_for_max_stake(
@@ -2351,6 +2390,35 @@ mod tests {
             }
         );
 
+        let authority = Pubkey::new_unique();
+        let test_stakes = test_commands.clone().get_matches_from(vec![
+            "test",
+            "stakes",
+            "--by-authority",
+            &authority.to_string(),
+        ]);
+        assert_eq!(
+            parse_command(&test_stakes, &default_signer, &mut None).unwrap(),
+            CliCommandInfo {
+                command: CliCommand::ShowStakes {
+                    use_lamports_unit: false,
+                    vote_account_pubkeys: None,
+                    withdraw_authority: None,
+                    by_authority: Some(authority),
+                },
+                signers: vec![],
+            }
+        );
+        let test_stakes = test_commands.clone().get_matches_from_safe(vec![
+            "test",
+            "stakes",
+            "--by-authority",
+            &authority.to_string(),
+            "--withdraw-authority",
+            &authority.to_string(),
+        ]);
+        assert!(test_stakes.is_err());
+
         let test_total_supply = test_commands
             .clone()
             .get_matches_from(vec!["test", "total-supply"]);
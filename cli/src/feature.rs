@@ -46,6 +46,7 @@ pub enum FeatureCliCommand {
     Status {
         features: Vec<Pubkey>,
         display_all: bool,
+        diff_against: Option<String>,
     },
     Activate {
         feature: Pubkey,
@@ -184,6 +185,117 @@ impl fmt::Display for CliFeatures {
 impl QuietDisplay for CliFeatures {}
 impl VerboseDisplay for CliFeatures {}
 
+#[derive(Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CliFeatureClusterStatus {
+    #[serde(flatten)]
+    pub status: CliFeatureStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub since_epoch: Option<Epoch>,
+}
+
+impl CliFeatureClusterStatus {
+    fn new(status: CliFeatureStatus, epoch_schedule: &EpochSchedule) -> Self {
+        let since_epoch = match status {
+            CliFeatureStatus::Active(activation_slot) => {
+                Some(epoch_schedule.get_epoch(activation_slot))
+            }
+            _ => None,
+        };
+        Self {
+            status,
+            since_epoch,
+        }
+    }
+
+    fn same_state(&self, other: &Self) -> bool {
+        matches!(
+            (&self.status, &other.status),
+            (CliFeatureStatus::Inactive, CliFeatureStatus::Inactive)
+                | (CliFeatureStatus::Pending, CliFeatureStatus::Pending)
+                | (CliFeatureStatus::Active(_), CliFeatureStatus::Active(_))
+        )
+    }
+}
+
+impl fmt::Display for CliFeatureClusterStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (&self.status, self.since_epoch) {
+            (CliFeatureStatus::Inactive, _) => write!(f, "inactive"),
+            (CliFeatureStatus::Pending, _) => write!(f, "pending"),
+            (CliFeatureStatus::Active(activation_slot), Some(activation_epoch)) => {
+                write!(
+                    f,
+                    "active since epoch {activation_epoch} (slot {activation_slot})"
+                )
+            }
+            (CliFeatureStatus::Active(activation_slot), None) => {
+                write!(f, "active since slot {activation_slot}")
+            }
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CliFeatureDiff {
+    pub id: String,
+    pub description: String,
+    pub status: CliFeatureClusterStatus,
+    pub diff_status: CliFeatureClusterStatus,
+    pub matches: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliFeaturesDiff {
+    pub url: String,
+    pub diff_url: String,
+    pub features: Vec<CliFeatureDiff>,
+    pub num_matching: usize,
+    pub num_mismatched: usize,
+}
+
+impl fmt::Display for CliFeaturesDiff {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Cluster:      {}", self.url)?;
+        writeln!(f, "Diff cluster: {}", self.diff_url)?;
+        if !self.features.is_empty() {
+            writeln!(
+                f,
+                "{}",
+                style(format!(
+                    "{:<44} | {:<40} | {:<40} | {}",
+                    "Feature", "Cluster Status", "Diff Cluster Status", "Description"
+                ))
+                .bold()
+            )?;
+        }
+        for feature in &self.features {
+            let line = format!(
+                "{:<44} | {:<40} | {:<40} | {}",
+                feature.id,
+                feature.status.to_string(),
+                feature.diff_status.to_string(),
+                feature.description,
+            );
+            if feature.matches {
+                writeln!(f, "{line}")?;
+            } else {
+                writeln!(f, "{}", style(line).yellow())?;
+            }
+        }
+        writeln!(
+            f,
+            "\n{} features match, {} features differ",
+            self.num_matching, self.num_mismatched
+        )
+    }
+}
+
+impl QuietDisplay for CliFeaturesDiff {}
+impl VerboseDisplay for CliFeaturesDiff {}
+
 #[derive(Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CliClusterFeatureSets {
@@ -441,10 +553,21 @@ impl FeatureSubCommands for App<'_, '_> {
                                 .multiple(true)
                                 .help("Feature status to query [default: all known features]"),
                         )
+                        .arg(Arg::with_name("display_all").long("display-all").help(
+                            "display all features regardless of age. With \
+                                     --diff-against, also display features whose status \
+                                     matches on both clusters",
+                        ))
                         .arg(
-                            Arg::with_name("display_all")
-                                .long("display-all")
-                                .help("display all features regardless of age"),
+                            Arg::with_name("diff_against")
+                                .long("diff-against")
+                                .value_name("URL_OR_MONIKER")
+                                .takes_value(true)
+                                .validator(is_url_or_moniker)
+                                .help(
+                                    "Compare feature status against the cluster at this JSON RPC \
+                                     URL or moniker ([mainnet-beta, testnet, devnet, localhost])",
+                                ),
                         ),
                 )
                 .subcommand(
@@ -537,11 +660,15 @@ pub fn parse_feature_subcommand(
             };
             let display_all =
                 matches.is_present("display_all") || features.len() < FEATURE_NAMES.len();
+            let diff_against = matches
+                .value_of("diff_against")
+                .map(normalize_to_url_if_moniker);
             features.sort();
             CliCommandInfo {
                 command: CliCommand::Feature(FeatureCliCommand::Status {
                     features,
                     display_all,
+                    diff_against,
                 }),
                 signers: vec![],
             }
@@ -560,7 +687,13 @@ pub fn process_feature_subcommand(
         FeatureCliCommand::Status {
             features,
             display_all,
+            diff_against: None,
         } => process_status(rpc_client, config, features, *display_all),
+        FeatureCliCommand::Status {
+            features,
+            display_all,
+            diff_against: Some(diff_url),
+        } => process_status_diff(rpc_client, config, features, *display_all, diff_url),
         FeatureCliCommand::Activate {
             feature,
             cluster,
@@ -911,6 +1044,82 @@ fn process_status(
     Ok(config.output_format.formatted_string(&feature_set))
 }
 
+fn get_feature_statuses(
+    rpc_client: &RpcClient,
+    feature_ids: &[Pubkey],
+) -> Result<Vec<CliFeatureStatus>, ClientError> {
+    let mut statuses = Vec::with_capacity(feature_ids.len());
+    for feature_ids in feature_ids.chunks(MAX_MULTIPLE_ACCOUNTS) {
+        statuses.extend(
+            rpc_client
+                .get_multiple_accounts(feature_ids)?
+                .into_iter()
+                .map(|account| {
+                    account
+                        .and_then(status_from_account)
+                        .unwrap_or(CliFeatureStatus::Inactive)
+                }),
+        );
+    }
+    Ok(statuses)
+}
+
+fn process_status_diff(
+    rpc_client: &RpcClient,
+    config: &CliConfig,
+    feature_ids: &[Pubkey],
+    display_all: bool,
+    diff_url: &str,
+) -> ProcessResult {
+    let diff_rpc_client = RpcClient::new_with_commitment(diff_url.to_string(), config.commitment);
+
+    let epoch_schedule = rpc_client.get_epoch_schedule()?;
+    let diff_epoch_schedule = diff_rpc_client.get_epoch_schedule()?;
+    let statuses = get_feature_statuses(rpc_client, feature_ids)?;
+    let diff_statuses = get_feature_statuses(&diff_rpc_client, feature_ids)?;
+
+    let mut num_matching = 0;
+    let mut features = feature_ids
+        .iter()
+        .zip(statuses.into_iter().zip(diff_statuses))
+        .filter_map(|(feature_id, (status, diff_status))| {
+            let status = CliFeatureClusterStatus::new(status, &epoch_schedule);
+            let diff_status = CliFeatureClusterStatus::new(diff_status, &diff_epoch_schedule);
+            let matches = status.same_state(&diff_status);
+            if matches {
+                num_matching += 1;
+                if !display_all {
+                    return None;
+                }
+            }
+            Some(CliFeatureDiff {
+                id: feature_id.to_string(),
+                description: FEATURE_NAMES.get(feature_id).unwrap().to_string(),
+                status,
+                diff_status,
+                matches,
+            })
+        })
+        .collect::<Vec<_>>();
+    features.sort_unstable_by(|a, b| {
+        (a.matches, &a.status.status, &a.diff_status.status, &a.id).cmp(&(
+            b.matches,
+            &b.status.status,
+            &b.diff_status.status,
+            &b.id,
+        ))
+    });
+
+    let features_diff = CliFeaturesDiff {
+        url: rpc_client.url(),
+        diff_url: diff_rpc_client.url(),
+        num_mismatched: feature_ids.len() - num_matching,
+        num_matching,
+        features,
+    };
+    Ok(config.output_format.formatted_string(&features_diff))
+}
+
 fn process_activate(
     rpc_client: &RpcClient,
     config: &CliConfig,
@@ -35,6 +35,7 @@ pub mod memo;
 pub mod nonce;
 pub mod program;
 pub mod program_v4;
+pub mod simulate;
 pub mod spend_utils;
 pub mod stake;
 pub mod test_utils;
@@ -231,6 +231,7 @@ pub fn parse_args<'a>(
             confirm_transaction_initial_timeout,
             address_labels,
             use_quic,
+            simulate_only: matches.is_present("simulate_only"),
         },
         signers,
     ))
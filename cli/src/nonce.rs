@@ -29,9 +29,9 @@ use {
         nonce::{self, State},
         pubkey::Pubkey,
         system_instruction::{
-            advance_nonce_account, authorize_nonce_account, create_nonce_account,
-            create_nonce_account_with_seed, upgrade_nonce_account, withdraw_nonce_account,
-            SystemError,
+            advance_and_authorize_nonce_account, advance_nonce_account, authorize_nonce_account,
+            create_nonce_account, create_nonce_account_with_seed, upgrade_nonce_account,
+            withdraw_nonce_account, SystemError,
         },
         system_program,
         transaction::Transaction,
@@ -63,6 +63,15 @@ impl NonceSubCommands for App<'_, '_> {
                     "Account to be granted authority of the nonce account."
                 ))
                 .arg(nonce_authority_arg())
+                .arg(
+                    Arg::with_name("advance")
+                        .long("advance")
+                        .takes_value(false)
+                        .help(
+                            "Advance the stored nonce in the same instruction, invalidating any \
+                             transactions signed with the current nonce",
+                        ),
+                )
                 .arg(memo_arg())
                 .arg(compute_unit_price_arg()),
         )
@@ -220,6 +229,7 @@ pub fn parse_authorize_nonce_account(
         wallet_manager,
     )?;
     let compute_unit_price = value_of(matches, COMPUTE_UNIT_PRICE_ARG.name);
+    let advance = matches.is_present("advance");
 
     Ok(CliCommandInfo {
         command: CliCommand::AuthorizeNonceAccount {
@@ -228,6 +238,7 @@ pub fn parse_authorize_nonce_account(
             memo,
             new_authority,
             compute_unit_price,
+            advance,
         },
         signers: signer_info.signers,
     })
@@ -411,17 +422,19 @@ pub fn process_authorize_nonce_account(
     memo: Option<&String>,
     new_authority: &Pubkey,
     compute_unit_price: Option<&u64>,
+    advance: bool,
 ) -> ProcessResult {
     let latest_blockhash = rpc_client.get_latest_blockhash()?;
 
     let nonce_authority = config.signers[nonce_authority];
-    let ixs = vec![authorize_nonce_account(
-        nonce_account,
-        &nonce_authority.pubkey(),
-        new_authority,
-    )]
-    .with_memo(memo)
-    .with_compute_unit_price(compute_unit_price);
+    let ix = if advance {
+        advance_and_authorize_nonce_account(nonce_account, &nonce_authority.pubkey(), new_authority)
+    } else {
+        authorize_nonce_account(nonce_account, &nonce_authority.pubkey(), new_authority)
+    };
+    let ixs = vec![ix]
+        .with_memo(memo)
+        .with_compute_unit_price(compute_unit_price);
     let message = Message::new(&ixs, Some(&config.signers[0].pubkey()));
     let mut tx = Transaction::new_unsigned(message);
     tx.try_sign(&config.signers, latest_blockhash)?;
@@ -727,6 +740,30 @@ mod tests {
                     memo: None,
                     new_authority: Pubkey::default(),
                     compute_unit_price: None,
+                    advance: false,
+                },
+                signers: vec![read_keypair_file(&default_keypair_file).unwrap().into()],
+            }
+        );
+
+        // Test AuthorizeNonceAccount Subcommand with advance
+        let test_authorize_nonce_account = test_commands.clone().get_matches_from(vec![
+            "test",
+            "authorize-nonce-account",
+            &keypair_file,
+            &Pubkey::default().to_string(),
+            "--advance",
+        ]);
+        assert_eq!(
+            parse_command(&test_authorize_nonce_account, &default_signer, &mut None).unwrap(),
+            CliCommandInfo {
+                command: CliCommand::AuthorizeNonceAccount {
+                    nonce_account: nonce_account_pubkey,
+                    nonce_authority: 0,
+                    memo: None,
+                    new_authority: Pubkey::default(),
+                    compute_unit_price: None,
+                    advance: true,
                 },
                 signers: vec![read_keypair_file(&default_keypair_file).unwrap().into()],
             }
@@ -750,6 +787,7 @@ mod tests {
                     memo: None,
                     new_authority: Pubkey::default(),
                     compute_unit_price: None,
+                    advance: false,
                 },
                 signers: vec![
                     read_keypair_file(&default_keypair_file).unwrap().into(),
@@ -984,6 +1022,7 @@ mod tests {
                     memo: None,
                     new_authority: Pubkey::default(),
                     compute_unit_price: Some(99),
+                    advance: false,
                 },
                 signers: vec![
                     read_keypair_file(&default_keypair_file).unwrap().into(),
@@ -5,12 +5,15 @@ use {
             log_instruction_custom_error, CliCommand, CliCommandInfo, CliConfig, CliError,
             ProcessResult,
         },
+        simulate::simulate_transaction,
     },
     bip39::{Language, Mnemonic, MnemonicType, Seed},
     clap::{App, AppSettings, Arg, ArgMatches, SubCommand},
     log::*,
     solana_account_decoder::{UiAccountEncoding, UiDataSliceConfig},
-    solana_bpf_loader_program::syscalls::create_program_runtime_environment_v1,
+    solana_bpf_loader_program::{
+        diagnostics::DeployDiagnostic, syscalls::create_program_runtime_environment_v1,
+    },
     solana_clap_utils::{
         self,
         fee_payer::{fee_payer_arg, FEE_PAYER_ARG},
@@ -22,9 +25,9 @@ use {
     },
     solana_cli_output::{
         return_signers_with_config, CliProgram, CliProgramAccountType, CliProgramAuthority,
-        CliProgramBuffer, CliProgramId, CliUpgradeableBuffer, CliUpgradeableBuffers,
-        CliUpgradeableProgram, CliUpgradeableProgramClosed, CliUpgradeableProgramExtended,
-        CliUpgradeablePrograms, ReturnSignersConfig,
+        CliProgramBuffer, CliProgramId, CliProgramVerification, CliUpgradeableBuffer,
+        CliUpgradeableBuffers, CliUpgradeableProgram, CliUpgradeableProgramClosed,
+        CliUpgradeableProgramExtended, CliUpgradeablePrograms, ReturnSignersConfig,
     },
     solana_client::{
         connection_cache::ConnectionCache,
@@ -49,6 +52,7 @@ use {
         bpf_loader, bpf_loader_deprecated,
         bpf_loader_upgradeable::{self, UpgradeableLoaderState},
         feature_set::FeatureSet,
+        hash::{hash, Hash},
         instruction::{Instruction, InstructionError},
         loader_instruction,
         message::Message,
@@ -90,6 +94,8 @@ pub enum ProgramCliCommand {
         max_len: Option<usize>,
         allow_excessive_balance: bool,
         skip_fee_check: bool,
+        expected_buffer_hash: Option<Hash>,
+        expected_program_location: Option<String>,
     },
     Upgrade {
         fee_payer_signer_index: SignerIndex,
@@ -99,6 +105,8 @@ pub enum ProgramCliCommand {
         sign_only: bool,
         dump_transaction_message: bool,
         blockhash_query: BlockhashQuery,
+        expected_buffer_hash: Option<Hash>,
+        expected_program_location: Option<String>,
     },
     WriteBuffer {
         program_location: String,
@@ -136,6 +144,11 @@ pub enum ProgramCliCommand {
         account_pubkey: Option<Pubkey>,
         output_location: String,
     },
+    Verify {
+        account_pubkey: Pubkey,
+        program_location: Option<String>,
+        expected_hash: Option<Hash>,
+    },
     Close {
         account_pubkey: Option<Pubkey>,
         recipient_pubkey: Pubkey,
@@ -230,6 +243,28 @@ impl ProgramSubCommands for App<'_, '_> {
                                     "Use the designated program id even if the account already \
                                      holds a large balance of SOL",
                                 ),
+                        )
+                        .arg(
+                            Arg::with_name("expected_buffer_hash")
+                                .long("expected-buffer-hash")
+                                .value_name("HASH")
+                                .takes_value(true)
+                                .validator(is_hash)
+                                .help(
+                                    "Abort the deployment unless the sha256 hash of the buffer's \
+                                     program data matches this hash",
+                                ),
+                        )
+                        .arg(
+                            Arg::with_name("expected_program_location")
+                                .long("expected-program")
+                                .value_name("PROGRAM_FILEPATH")
+                                .takes_value(true)
+                                .requires("expected_buffer_hash")
+                                .help(
+                                    "/path/to/program.so the buffer is expected to hold, whose \
+                                     length and hash are verified against the buffer [default: the program being deployed]",
+                                ),
                         ),
                 )
                 .subcommand(
@@ -260,6 +295,29 @@ impl ProgramSubCommands for App<'_, '_> {
                                     "Upgrade authority [default: the default configured keypair]",
                                 ),
                         )
+                        .arg(
+                            Arg::with_name("expected_buffer_hash")
+                                .long("expected-buffer-hash")
+                                .value_name("HASH")
+                                .takes_value(true)
+                                .validator(is_hash)
+                                .requires("expected_program_location")
+                                .help(
+                                    "Abort the upgrade unless the sha256 hash of the buffer's \
+                                     program data matches this hash",
+                                ),
+                        )
+                        .arg(
+                            Arg::with_name("expected_program_location")
+                                .long("expected-program")
+                                .value_name("PROGRAM_FILEPATH")
+                                .takes_value(true)
+                                .requires("expected_buffer_hash")
+                                .help(
+                                    "/path/to/program.so the buffer is expected to hold, whose \
+                                     length and hash are verified against the buffer",
+                                ),
+                        )
                         .offline_args(),
                 )
                 .subcommand(
@@ -453,6 +511,41 @@ impl ProgramSubCommands for App<'_, '_> {
                                 .help("/path/to/program.so"),
                         ),
                 )
+                .subcommand(
+                    SubCommand::with_name("verify")
+                        .about(
+                            "Verify that the program data deployed on chain matches a local \
+                             program or hash",
+                        )
+                        .arg(
+                            Arg::with_name("account")
+                                .index(1)
+                                .value_name("ACCOUNT_ADDRESS")
+                                .takes_value(true)
+                                .required(true)
+                                .validator(is_valid_pubkey)
+                                .help("Address of the buffer or program"),
+                        )
+                        .arg(
+                            Arg::with_name("program_location")
+                                .index(2)
+                                .value_name("PROGRAM_FILEPATH")
+                                .takes_value(true)
+                                .required_unless("expected_hash")
+                                .help("/path/to/program.so"),
+                        )
+                        .arg(
+                            Arg::with_name("expected_hash")
+                                .long("expected-hash")
+                                .value_name("HASH")
+                                .takes_value(true)
+                                .validator(is_hash)
+                                .help(
+                                    "Expected sha256 hash of the program data, without the \
+                                     padding of its deployment",
+                                ),
+                        ),
+                )
                 .subcommand(
                     SubCommand::with_name("close")
                         .about("Close a program or buffer account and withdraw all lamports")
@@ -609,6 +702,10 @@ pub fn parse_program_subcommand(
                     max_len,
                     allow_excessive_balance: matches.is_present("allow_excessive_balance"),
                     skip_fee_check,
+                    expected_buffer_hash: value_of(matches, "expected_buffer_hash"),
+                    expected_program_location: matches
+                        .value_of("expected_program_location")
+                        .map(|location| location.to_string()),
                 }),
                 signers: signer_info.signers,
             }
@@ -650,6 +747,10 @@ pub fn parse_program_subcommand(
                     sign_only,
                     dump_transaction_message,
                     blockhash_query,
+                    expected_buffer_hash: value_of(matches, "expected_buffer_hash"),
+                    expected_program_location: matches
+                        .value_of("expected_program_location")
+                        .map(|location| location.to_string()),
                 }),
                 signers: signer_info.signers,
             }
@@ -801,6 +902,14 @@ pub fn parse_program_subcommand(
             }),
             signers: vec![],
         },
+        ("verify", Some(matches)) => CliCommandInfo {
+            command: CliCommand::Program(ProgramCliCommand::Verify {
+                account_pubkey: pubkey_of(matches, "account").unwrap(),
+                program_location: matches.value_of("program_location").map(str::to_string),
+                expected_hash: value_of(matches, "expected_hash"),
+            }),
+            signers: vec![],
+        },
         ("close", Some(matches)) => {
             let account_pubkey = if matches.is_present("buffers") {
                 None
@@ -884,6 +993,8 @@ pub fn process_program_subcommand(
             max_len,
             allow_excessive_balance,
             skip_fee_check,
+            expected_buffer_hash,
+            expected_program_location,
         } => process_program_deploy(
             rpc_client,
             config,
@@ -898,6 +1009,8 @@ pub fn process_program_subcommand(
             *max_len,
             *allow_excessive_balance,
             *skip_fee_check,
+            *expected_buffer_hash,
+            expected_program_location.as_deref(),
         ),
         ProgramCliCommand::Upgrade {
             fee_payer_signer_index,
@@ -907,6 +1020,8 @@ pub fn process_program_subcommand(
             sign_only,
             dump_transaction_message,
             blockhash_query,
+            expected_buffer_hash,
+            expected_program_location,
         } => process_program_upgrade(
             rpc_client,
             config,
@@ -917,6 +1032,8 @@ pub fn process_program_subcommand(
             *sign_only,
             *dump_transaction_message,
             blockhash_query,
+            *expected_buffer_hash,
+            expected_program_location.as_deref(),
         ),
         ProgramCliCommand::WriteBuffer {
             program_location,
@@ -993,6 +1110,17 @@ pub fn process_program_subcommand(
             account_pubkey,
             output_location,
         } => process_dump(&rpc_client, config, *account_pubkey, output_location),
+        ProgramCliCommand::Verify {
+            account_pubkey,
+            program_location,
+            expected_hash,
+        } => process_verify(
+            &rpc_client,
+            config,
+            account_pubkey,
+            program_location.as_deref(),
+            *expected_hash,
+        ),
         ProgramCliCommand::Close {
             account_pubkey,
             recipient_pubkey,
@@ -1061,6 +1189,8 @@ fn process_program_deploy(
     max_len: Option<usize>,
     allow_excessive_balance: bool,
     skip_fee_check: bool,
+    expected_buffer_hash: Option<Hash>,
+    expected_program_location: Option<&str>,
 ) -> ProcessResult {
     let fee_payer_signer = config.signers[fee_payer_signer_index];
     let upgrade_authority_signer = config.signers[upgrade_authority_signer_index];
@@ -1164,6 +1294,14 @@ fn process_program_deploy(
     } else {
         return Err("Program location required if buffer not supplied".into());
     };
+    let expected_buffer_hash = expected_buffer_hash
+        .map(|expected_hash| {
+            expected_program_hash_check(
+                expected_hash,
+                expected_program_location.or(program_location.as_deref()),
+            )
+        })
+        .transpose()?;
     let program_data_max_len = if let Some(len) = max_len {
         if program_len > len {
             return Err(
@@ -1200,6 +1338,7 @@ fn process_program_deploy(
             upgrade_authority_signer,
             allow_excessive_balance,
             skip_fee_check,
+            expected_buffer_hash,
         )
     } else {
         do_process_program_upgrade(
@@ -1214,6 +1353,7 @@ fn process_program_deploy(
             &buffer_pubkey,
             buffer_signer,
             skip_fee_check,
+            expected_buffer_hash,
         )
     };
     if result.is_ok() && is_final {
@@ -1284,6 +1424,46 @@ fn fetch_buffer_len(
     }
 }
 
+/// Check the local program the buffer is expected to hold against the
+/// expected hash, and pair the hash with the program's exact length. The
+/// length can't be recovered from the buffer itself, since a program may
+/// legitimately end in zero bytes which are indistinguishable from the
+/// buffer's padding.
+fn expected_program_hash_check(
+    expected_hash: Hash,
+    program_location: Option<&str>,
+) -> Result<(Hash, u32), Box<dyn std::error::Error>> {
+    let program_location =
+        program_location.ok_or("Program location required to verify the expected buffer hash")?;
+    let program_data = read_and_verify_elf(program_location)?;
+    let program_hash = hash(&program_data);
+    if program_hash != expected_hash {
+        return Err(format!(
+            "Program hash {program_hash} does not match expected buffer hash {expected_hash}"
+        )
+        .into());
+    }
+    let program_len = u32::try_from(program_data.len())
+        .map_err(|_| format!("Program {program_location} is too large"))?;
+    Ok((expected_hash, program_len))
+}
+
+/// Prepend the instruction checking the buffer's contents, if requested, to
+/// the instructions deploying or upgrading from it
+fn with_buffer_hash_check(
+    buffer_pubkey: &Pubkey,
+    expected_hash: Option<(Hash, u32)>,
+    instructions: Vec<Instruction>,
+) -> Vec<Instruction> {
+    expected_hash
+        .map(|(expected_hash, program_len)| {
+            bpf_loader_upgradeable::verify_buffer_hash(buffer_pubkey, expected_hash, program_len)
+        })
+        .into_iter()
+        .chain(instructions)
+        .collect()
+}
+
 /// Upgrade existing program using upgradeable loader
 #[allow(clippy::too_many_arguments)]
 fn process_program_upgrade(
@@ -1296,18 +1476,28 @@ fn process_program_upgrade(
     sign_only: bool,
     dump_transaction_message: bool,
     blockhash_query: &BlockhashQuery,
+    expected_buffer_hash: Option<Hash>,
+    expected_program_location: Option<&str>,
 ) -> ProcessResult {
     let fee_payer_signer = config.signers[fee_payer_signer_index];
     let upgrade_authority_signer = config.signers[upgrade_authority_signer_index];
 
+    let expected_buffer_hash = expected_buffer_hash
+        .map(|expected_hash| expected_program_hash_check(expected_hash, expected_program_location))
+        .transpose()?;
+
     let blockhash = blockhash_query.get_blockhash(&rpc_client, config.commitment)?;
     let message = Message::new_with_blockhash(
-        &[bpf_loader_upgradeable::upgrade(
-            &program_id,
+        &with_buffer_hash_check(
             &buffer_pubkey,
-            &upgrade_authority_signer.pubkey(),
-            &fee_payer_signer.pubkey(),
-        )],
+            expected_buffer_hash,
+            vec![bpf_loader_upgradeable::upgrade(
+                &program_id,
+                &buffer_pubkey,
+                &upgrade_authority_signer.pubkey(),
+                &fee_payer_signer.pubkey(),
+            )],
+        ),
         Some(&fee_payer_signer.pubkey()),
         &blockhash,
     );
@@ -1337,6 +1527,9 @@ fn process_program_upgrade(
         let mut tx = Transaction::new_unsigned(message);
         let signers = &[fee_payer_signer, upgrade_authority_signer];
         tx.try_sign(signers, blockhash)?;
+        if config.simulate_only {
+            return simulate_transaction(&rpc_client, config, &tx);
+        }
         let final_tx_sig = rpc_client
             .send_and_confirm_transaction_with_spinner(&tx)
             .map_err(|e| format!("Upgrading program failed: {e}"))?;
@@ -1423,6 +1616,7 @@ fn process_write_buffer(
         buffer_authority,
         true,
         skip_fee_check,
+        None,
     );
     if result.is_err() && buffer_signer_index.is_none() && buffer_signer.is_some() {
         report_ephemeral_mnemonic(words, mnemonic);
@@ -1474,6 +1668,9 @@ fn process_set_authority(
     };
 
     tx.try_sign(&[config.signers[0], authority_signer], blockhash)?;
+    if config.simulate_only {
+        return simulate_transaction(rpc_client, config, &tx);
+    }
     rpc_client
         .send_and_confirm_transaction_with_spinner_and_config(
             &tx,
@@ -1524,6 +1721,9 @@ fn process_set_authority_checked(
         &[config.signers[0], authority_signer, new_authority_signer],
         blockhash,
     )?;
+    if config.simulate_only {
+        return simulate_transaction(rpc_client, config, &tx);
+    }
     rpc_client
         .send_and_confirm_transaction_with_spinner_and_config(
             &tx,
@@ -1779,64 +1979,156 @@ fn process_show(
     }
 }
 
-fn process_dump(
+/// The program data of the program or buffer `account_pubkey`, as deployed,
+/// with the padding up to the maximum length of the program
+fn fetch_program_data(
     rpc_client: &RpcClient,
     config: &CliConfig,
-    account_pubkey: Option<Pubkey>,
-    output_location: &str,
-) -> ProcessResult {
-    if let Some(account_pubkey) = account_pubkey {
-        if let Some(account) = rpc_client
-            .get_account_with_commitment(&account_pubkey, config.commitment)?
-            .value
+    account_pubkey: &Pubkey,
+) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let Some(account) = rpc_client
+        .get_account_with_commitment(account_pubkey, config.commitment)?
+        .value
+    else {
+        return Err(format!("Unable to find the account {account_pubkey}").into());
+    };
+    if account.owner == bpf_loader::id() || account.owner == bpf_loader_deprecated::id() {
+        Ok(account.data)
+    } else if account.owner == bpf_loader_upgradeable::id() {
+        if let Ok(UpgradeableLoaderState::Program {
+            programdata_address,
+        }) = account.state()
         {
-            if account.owner == bpf_loader::id() || account.owner == bpf_loader_deprecated::id() {
-                let mut f = File::create(output_location)?;
-                f.write_all(&account.data)?;
-                Ok(format!("Wrote program to {output_location}"))
-            } else if account.owner == bpf_loader_upgradeable::id() {
-                if let Ok(UpgradeableLoaderState::Program {
-                    programdata_address,
-                }) = account.state()
+            if let Some(programdata_account) = rpc_client
+                .get_account_with_commitment(&programdata_address, config.commitment)?
+                .value
+            {
+                if let Ok(UpgradeableLoaderState::ProgramData { .. }) = programdata_account.state()
                 {
-                    if let Some(programdata_account) = rpc_client
-                        .get_account_with_commitment(&programdata_address, config.commitment)?
-                        .value
-                    {
-                        if let Ok(UpgradeableLoaderState::ProgramData { .. }) =
-                            programdata_account.state()
-                        {
-                            let offset = UpgradeableLoaderState::size_of_programdata_metadata();
-                            let program_data = &programdata_account.data[offset..];
-                            let mut f = File::create(output_location)?;
-                            f.write_all(program_data)?;
-                            Ok(format!("Wrote program to {output_location}"))
-                        } else {
-                            Err(format!("Program {account_pubkey} has been closed").into())
-                        }
-                    } else {
-                        Err(format!("Program {account_pubkey} has been closed").into())
-                    }
-                } else if let Ok(UpgradeableLoaderState::Buffer { .. }) = account.state() {
-                    let offset = UpgradeableLoaderState::size_of_buffer_metadata();
-                    let program_data = &account.data[offset..];
-                    let mut f = File::create(output_location)?;
-                    f.write_all(program_data)?;
-                    Ok(format!("Wrote program to {output_location}"))
+                    let offset = UpgradeableLoaderState::size_of_programdata_metadata();
+                    Ok(programdata_account.data[offset..].to_vec())
                 } else {
-                    Err(format!(
-                        "{account_pubkey} is not an upgradeable loader buffer or program account"
-                    )
-                    .into())
+                    Err(format!("Program {account_pubkey} has been closed").into())
                 }
             } else {
-                Err(format!("{account_pubkey} is not an SBF program").into())
+                Err(format!("Program {account_pubkey} has been closed").into())
             }
+        } else if let Ok(UpgradeableLoaderState::Buffer { .. }) = account.state() {
+            let offset = UpgradeableLoaderState::size_of_buffer_metadata();
+            Ok(account.data[offset..].to_vec())
         } else {
-            Err(format!("Unable to find the account {account_pubkey}").into())
+            Err(
+                format!("{account_pubkey} is not an upgradeable loader buffer or program account")
+                    .into(),
+            )
         }
     } else {
-        Err("No account specified".into())
+        Err(format!("{account_pubkey} is not an SBF program").into())
+    }
+}
+
+fn process_dump(
+    rpc_client: &RpcClient,
+    config: &CliConfig,
+    account_pubkey: Option<Pubkey>,
+    output_location: &str,
+) -> ProcessResult {
+    let Some(account_pubkey) = account_pubkey else {
+        return Err("No account specified".into());
+    };
+    let program_data = fetch_program_data(rpc_client, config, &account_pubkey)?;
+    let mut f = File::create(output_location)?;
+    f.write_all(&program_data)?;
+    Ok(format!("Wrote program to {output_location}"))
+}
+
+/// Strips the zeros deployments pad program data with up to the maximum
+/// length of the program. With the length of the local program, only the
+/// bytes past it are stripped, so that a program ending with zeros still
+/// matches.
+fn strip_program_data_padding(program_data: &[u8], local_program_len: Option<usize>) -> &[u8] {
+    if let Some(local_program_len) = local_program_len {
+        if program_data.len() >= local_program_len
+            && program_data[local_program_len..]
+                .iter()
+                .all(|byte| *byte == 0)
+        {
+            return &program_data[..local_program_len];
+        }
+    }
+    let len = program_data
+        .iter()
+        .rposition(|byte| *byte != 0)
+        .map_or(0, |index| index.saturating_add(1));
+    &program_data[..len]
+}
+
+/// The first offsets, up to `max_offsets`, at which `program_data` and
+/// `local_program_data` differ, including the offset at which the shorter of
+/// them ends
+fn program_data_mismatch_offsets(
+    program_data: &[u8],
+    local_program_data: &[u8],
+    max_offsets: usize,
+) -> Vec<usize> {
+    let common_len = program_data.len().min(local_program_data.len());
+    program_data
+        .iter()
+        .zip(local_program_data)
+        .enumerate()
+        .filter_map(|(offset, (byte, local_byte))| (byte != local_byte).then_some(offset))
+        .chain((program_data.len() != local_program_data.len()).then_some(common_len))
+        .take(max_offsets)
+        .collect()
+}
+
+fn process_verify(
+    rpc_client: &RpcClient,
+    config: &CliConfig,
+    account_pubkey: &Pubkey,
+    program_location: Option<&str>,
+    expected_hash: Option<Hash>,
+) -> ProcessResult {
+    const MAX_MISMATCH_OFFSETS: usize = 16;
+
+    let local_program_data = program_location
+        .map(|program_location| {
+            std::fs::read(program_location)
+                .map_err(|err| format!("Unable to read program file: {err}"))
+        })
+        .transpose()?;
+    let program_data = fetch_program_data(rpc_client, config, account_pubkey)?;
+    let program_data =
+        strip_program_data_padding(&program_data, local_program_data.as_ref().map(Vec::len));
+    let program_hash = hash(program_data);
+
+    let mismatch_offsets = local_program_data
+        .as_ref()
+        .map(|local_program_data| {
+            program_data_mismatch_offsets(program_data, local_program_data, MAX_MISMATCH_OFFSETS)
+        })
+        .unwrap_or_default();
+    let verified = mismatch_offsets.is_empty()
+        && expected_hash.map_or(true, |expected_hash| expected_hash == program_hash);
+
+    let verification = CliProgramVerification {
+        program_id: account_pubkey.to_string(),
+        program_hash: program_hash.to_string(),
+        program_len: program_data.len(),
+        local_program_location: program_location.map(str::to_string),
+        local_program_hash: local_program_data
+            .as_ref()
+            .map(|local_program_data| hash(local_program_data).to_string()),
+        local_program_len: local_program_data.as_ref().map(Vec::len),
+        expected_hash: expected_hash.map(|expected_hash| expected_hash.to_string()),
+        mismatch_offsets,
+        verified,
+    };
+    let output = config.output_format.formatted_string(&verification);
+    if verified {
+        Ok(output)
+    } else {
+        Err(format!("{output}\nProgram {account_pubkey} does not match").into())
     }
 }
 
@@ -2087,6 +2379,9 @@ fn process_extend_program(
     ));
 
     tx.try_sign(&[config.signers[0]], blockhash)?;
+    if config.simulate_only {
+        return simulate_transaction(rpc_client, config, &tx);
+    }
     let result = rpc_client.send_and_confirm_transaction_with_spinner_and_config(
         &tx,
         config.commitment,
@@ -2148,6 +2443,7 @@ fn do_process_program_write_and_deploy(
     buffer_authority_signer: &dyn Signer,
     allow_excessive_balance: bool,
     skip_fee_check: bool,
+    expected_buffer_hash: Option<(Hash, u32)>,
 ) -> ProcessResult {
     let blockhash = rpc_client.get_latest_blockhash()?;
 
@@ -2227,16 +2523,20 @@ fn do_process_program_write_and_deploy(
     let final_message = if let Some(program_signers) = program_signers {
         let message = if loader_id == &bpf_loader_upgradeable::id() {
             Message::new_with_blockhash(
-                &bpf_loader_upgradeable::deploy_with_max_program_len(
-                    &fee_payer_signer.pubkey(),
-                    &program_signers[0].pubkey(),
+                &with_buffer_hash_check(
                     buffer_pubkey,
-                    &program_signers[1].pubkey(),
-                    rpc_client.get_minimum_balance_for_rent_exemption(
-                        UpgradeableLoaderState::size_of_program(),
+                    expected_buffer_hash,
+                    bpf_loader_upgradeable::deploy_with_max_program_len(
+                        &fee_payer_signer.pubkey(),
+                        &program_signers[0].pubkey(),
+                        buffer_pubkey,
+                        &program_signers[1].pubkey(),
+                        rpc_client.get_minimum_balance_for_rent_exemption(
+                            UpgradeableLoaderState::size_of_program(),
+                        )?,
+                        program_data_max_len,
                     )?,
-                    program_data_max_len,
-                )?,
+                ),
                 Some(&fee_payer_signer.pubkey()),
                 &blockhash,
             )
@@ -2303,6 +2603,7 @@ fn do_process_program_upgrade(
     buffer_pubkey: &Pubkey,
     buffer_signer: Option<&dyn Signer>,
     skip_fee_check: bool,
+    expected_buffer_hash: Option<(Hash, u32)>,
 ) -> ProcessResult {
     let blockhash = rpc_client.get_latest_blockhash()?;
 
@@ -2375,12 +2676,16 @@ fn do_process_program_upgrade(
 
     // Create and add final message
     let final_message = Message::new_with_blockhash(
-        &[bpf_loader_upgradeable::upgrade(
-            program_id,
+        &with_buffer_hash_check(
             buffer_pubkey,
-            &upgrade_authority.pubkey(),
-            &fee_payer_signer.pubkey(),
-        )],
+            expected_buffer_hash,
+            vec![bpf_loader_upgradeable::upgrade(
+                program_id,
+                buffer_pubkey,
+                &upgrade_authority.pubkey(),
+                &fee_payer_signer.pubkey(),
+            )],
+        ),
         Some(&fee_payer_signer.pubkey()),
         &blockhash,
     );
@@ -2434,11 +2739,11 @@ fn read_and_verify_elf(program_location: &str) -> Result<Vec<u8>, Box<dyn std::e
     .unwrap();
     let executable =
         Executable::<InvokeContext>::from_elf(&program_data, Arc::new(program_runtime_environment))
-            .map_err(|err| format!("ELF error: {err}"))?;
+            .map_err(|err| DeployDiagnostic::new(&err).to_string())?;
 
     executable
         .verify::<RequisiteVerifier>()
-        .map_err(|err| format!("ELF error: {err}"))?;
+        .map_err(|err| DeployDiagnostic::from_verifier_error(&err).to_string())?;
 
     Ok(program_data)
 }
@@ -2727,6 +3032,8 @@ mod tests {
                     max_len: None,
                     allow_excessive_balance: false,
                     skip_fee_check: false,
+                    expected_buffer_hash: None,
+                    expected_program_location: None,
                 }),
                 signers: vec![read_keypair_file(&keypair_file).unwrap().into()],
             }
@@ -2755,11 +3062,87 @@ mod tests {
                     max_len: Some(42),
                     allow_excessive_balance: false,
                     skip_fee_check: false,
+                    expected_buffer_hash: None,
+                    expected_program_location: None,
+                }),
+                signers: vec![read_keypair_file(&keypair_file).unwrap().into()],
+            }
+        );
+
+        let expected_buffer_hash = Hash::new_unique();
+        let test_command = test_commands.clone().get_matches_from(vec![
+            "test",
+            "program",
+            "deploy",
+            "/Users/test/program.so",
+            "--expected-buffer-hash",
+            &expected_buffer_hash.to_string(),
+        ]);
+        assert_eq!(
+            parse_command(&test_command, &default_signer, &mut None).unwrap(),
+            CliCommandInfo {
+                command: CliCommand::Program(ProgramCliCommand::Deploy {
+                    program_location: Some("/Users/test/program.so".to_string()),
+                    fee_payer_signer_index: 0,
+                    buffer_signer_index: None,
+                    buffer_pubkey: None,
+                    program_signer_index: None,
+                    program_pubkey: None,
+                    upgrade_authority_signer_index: 0,
+                    is_final: false,
+                    max_len: None,
+                    allow_excessive_balance: false,
+                    skip_fee_check: false,
+                    expected_buffer_hash: Some(expected_buffer_hash),
+                    expected_program_location: None,
+                }),
+                signers: vec![read_keypair_file(&keypair_file).unwrap().into()],
+            }
+        );
+
+        let test_command = test_commands.clone().get_matches_from(vec![
+            "test",
+            "program",
+            "deploy",
+            "/Users/test/program.so",
+            "--expected-buffer-hash",
+            &expected_buffer_hash.to_string(),
+            "--expected-program",
+            "/Users/test/reviewed_program.so",
+        ]);
+        assert_eq!(
+            parse_command(&test_command, &default_signer, &mut None).unwrap(),
+            CliCommandInfo {
+                command: CliCommand::Program(ProgramCliCommand::Deploy {
+                    program_location: Some("/Users/test/program.so".to_string()),
+                    fee_payer_signer_index: 0,
+                    buffer_signer_index: None,
+                    buffer_pubkey: None,
+                    program_signer_index: None,
+                    program_pubkey: None,
+                    upgrade_authority_signer_index: 0,
+                    is_final: false,
+                    max_len: None,
+                    allow_excessive_balance: false,
+                    skip_fee_check: false,
+                    expected_buffer_hash: Some(expected_buffer_hash),
+                    expected_program_location: Some("/Users/test/reviewed_program.so".to_string()),
                 }),
                 signers: vec![read_keypair_file(&keypair_file).unwrap().into()],
             }
         );
 
+        // the expected program is only meaningful with an expected hash
+        let test_command = test_commands.clone().get_matches_from_safe(vec![
+            "test",
+            "program",
+            "deploy",
+            "/Users/test/program.so",
+            "--expected-program",
+            "/Users/test/reviewed_program.so",
+        ]);
+        assert!(test_command.is_err());
+
         let buffer_keypair = Keypair::new();
         let buffer_keypair_file = make_tmp_path("buffer_keypair_file");
         write_keypair_file(&buffer_keypair, &buffer_keypair_file).unwrap();
@@ -2785,6 +3168,8 @@ mod tests {
                     max_len: None,
                     allow_excessive_balance: false,
                     skip_fee_check: false,
+                    expected_buffer_hash: None,
+                    expected_program_location: None,
                 }),
                 signers: vec![
                     read_keypair_file(&keypair_file).unwrap().into(),
@@ -2817,6 +3202,8 @@ mod tests {
                     max_len: None,
                     allow_excessive_balance: false,
                     skip_fee_check: false,
+                    expected_buffer_hash: None,
+                    expected_program_location: None,
                 }),
                 signers: vec![read_keypair_file(&keypair_file).unwrap().into()],
             }
@@ -2848,6 +3235,8 @@ mod tests {
                     max_len: None,
                     allow_excessive_balance: false,
                     skip_fee_check: false,
+                    expected_buffer_hash: None,
+                    expected_program_location: None,
                 }),
                 signers: vec![
                     read_keypair_file(&keypair_file).unwrap().into(),
@@ -2882,6 +3271,8 @@ mod tests {
                     max_len: None,
                     allow_excessive_balance: false,
                     skip_fee_check: false,
+                    expected_buffer_hash: None,
+                    expected_program_location: None,
                 }),
                 signers: vec![
                     read_keypair_file(&keypair_file).unwrap().into(),
@@ -2912,6 +3303,8 @@ mod tests {
                     max_len: None,
                     skip_fee_check: false,
                     allow_excessive_balance: false,
+                    expected_buffer_hash: None,
+                    expected_program_location: None,
                 }),
                 signers: vec![read_keypair_file(&keypair_file).unwrap().into()],
             }
@@ -3534,6 +3927,110 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_cli_parse_verify() {
+        let test_commands = get_clap_app("test", "desc", "version");
+
+        let default_keypair = Keypair::new();
+        let keypair_file = make_tmp_path("keypair_file");
+        write_keypair_file(&default_keypair, &keypair_file).unwrap();
+        let default_signer = DefaultSigner::new("", &keypair_file);
+
+        let program_pubkey = Pubkey::new_unique();
+        let expected_hash = Hash::new_unique();
+
+        // with a local program
+        let test_command = test_commands.clone().get_matches_from(vec![
+            "test",
+            "program",
+            "verify",
+            &program_pubkey.to_string(),
+            "/Users/test/program.so",
+        ]);
+        assert_eq!(
+            parse_command(&test_command, &default_signer, &mut None).unwrap(),
+            CliCommandInfo {
+                command: CliCommand::Program(ProgramCliCommand::Verify {
+                    account_pubkey: program_pubkey,
+                    program_location: Some("/Users/test/program.so".to_string()),
+                    expected_hash: None,
+                }),
+                signers: vec![],
+            }
+        );
+
+        // with an expected hash
+        let test_command = test_commands.clone().get_matches_from(vec![
+            "test",
+            "program",
+            "verify",
+            &program_pubkey.to_string(),
+            "--expected-hash",
+            &expected_hash.to_string(),
+        ]);
+        assert_eq!(
+            parse_command(&test_command, &default_signer, &mut None).unwrap(),
+            CliCommandInfo {
+                command: CliCommand::Program(ProgramCliCommand::Verify {
+                    account_pubkey: program_pubkey,
+                    program_location: None,
+                    expected_hash: Some(expected_hash),
+                }),
+                signers: vec![],
+            }
+        );
+
+        // without either
+        assert!(test_commands
+            .clone()
+            .get_matches_from_safe(vec![
+                "test",
+                "program",
+                "verify",
+                &program_pubkey.to_string(),
+            ])
+            .is_err());
+    }
+
+    #[test]
+    fn test_strip_program_data_padding() {
+        let program_data = [1, 0, 2, 0, 0, 0];
+        assert_eq!(strip_program_data_padding(&program_data, None), &[1, 0, 2]);
+        // the local program ends with zeros too
+        assert_eq!(
+            strip_program_data_padding(&program_data, Some(4)),
+            &[1, 0, 2, 0]
+        );
+        // the local program is shorter than the deployed one
+        assert_eq!(
+            strip_program_data_padding(&program_data, Some(2)),
+            &[1, 0, 2]
+        );
+        // the local program is longer than the deployed one
+        assert_eq!(
+            strip_program_data_padding(&program_data, Some(8)),
+            &[1, 0, 2]
+        );
+        assert!(strip_program_data_padding(&[0; 4], None).is_empty());
+    }
+
+    #[test]
+    fn test_program_data_mismatch_offsets() {
+        assert!(program_data_mismatch_offsets(&[1, 2, 3], &[1, 2, 3], 16).is_empty());
+        assert_eq!(
+            program_data_mismatch_offsets(&[1, 2, 3, 4], &[1, 0, 3, 0], 16),
+            vec![1, 3]
+        );
+        assert_eq!(
+            program_data_mismatch_offsets(&[1, 2, 3], &[1, 0, 3, 4, 5], 16),
+            vec![1, 3]
+        );
+        assert_eq!(
+            program_data_mismatch_offsets(&[0; 8], &[1; 8], 3),
+            vec![0, 1, 2]
+        );
+    }
+
     #[test]
     fn test_cli_parse_extend_program() {
         let test_commands = get_clap_app("test", "desc", "version");
@@ -3600,6 +4097,8 @@ mod tests {
                 max_len: None,
                 allow_excessive_balance: false,
                 skip_fee_check: false,
+                expected_buffer_hash: None,
+                expected_program_location: None,
             }),
             signers: vec![&default_keypair],
             output_format: OutputFormat::JsonCompact,
@@ -0,0 +1,251 @@
+//! Dry runs of the transactions of commands, for `--simulate-only`
+
+use {
+    crate::{
+        cli::{CliCommand, CliConfig, ProcessResult},
+        program::ProgramCliCommand,
+    },
+    solana_account_decoder::UiAccountEncoding,
+    solana_cli_output::{CliSimulatedBalanceChange, CliSimulatedTransaction},
+    solana_rpc_client::rpc_client::RpcClient,
+    solana_rpc_client_api::config::{
+        RpcSimulateTransactionAccountsConfig, RpcSimulateTransactionConfig,
+    },
+    solana_sdk::transaction::Transaction,
+};
+
+/// Whether `command` simulates its transaction instead of sending it when run
+/// with `--simulate-only`. Commands sending several transactions, such as
+/// program deployments, do not.
+pub fn supports_simulate_only(command: &CliCommand) -> bool {
+    matches!(
+        command,
+        CliCommand::Transfer { .. }
+            | CliCommand::CreateStakeAccount { .. }
+            | CliCommand::DeactivateStake { .. }
+            | CliCommand::DelegateStake { .. }
+            | CliCommand::SplitStake { .. }
+            | CliCommand::MergeStake { .. }
+            | CliCommand::StakeAuthorize { .. }
+            | CliCommand::StakeSetLockup { .. }
+            | CliCommand::WithdrawStake { .. }
+            | CliCommand::Program(
+                ProgramCliCommand::Upgrade { .. }
+                    | ProgramCliCommand::SetBufferAuthority { .. }
+                    | ProgramCliCommand::SetUpgradeAuthority { .. }
+                    | ProgramCliCommand::SetUpgradeAuthorityChecked { .. }
+                    | ProgramCliCommand::ExtendProgram { .. }
+            )
+    )
+}
+
+/// Simulates `transaction` instead of sending it, reporting its fee, the
+/// compute units it consumes and the balances it changes. Fails if the
+/// simulated transaction fails.
+pub fn simulate_transaction(
+    rpc_client: &RpcClient,
+    config: &CliConfig,
+    transaction: &Transaction,
+) -> ProcessResult {
+    let fee = rpc_client.get_fee_for_message(&transaction.message)?;
+    let addresses: Vec<String> = transaction
+        .message
+        .account_keys
+        .iter()
+        .map(|pubkey| pubkey.to_string())
+        .collect();
+    let result = rpc_client
+        .simulate_transaction_with_config(
+            transaction,
+            RpcSimulateTransactionConfig {
+                sig_verify: true,
+                commitment: Some(config.commitment),
+                accounts: Some(RpcSimulateTransactionAccountsConfig {
+                    encoding: Some(UiAccountEncoding::Base64),
+                    addresses: addresses.clone(),
+                    post_data_diff: true,
+                }),
+                ..RpcSimulateTransactionConfig::default()
+            },
+        )?
+        .value;
+
+    let balance_changes = result
+        .account_diffs
+        .unwrap_or_default()
+        .into_iter()
+        .zip(addresses)
+        .filter_map(|(account_diff, address)| {
+            let account_diff = account_diff?;
+            (account_diff.pre_lamports != account_diff.post_lamports).then_some(
+                CliSimulatedBalanceChange {
+                    address,
+                    pre_balance: account_diff.pre_lamports,
+                    post_balance: account_diff.post_lamports,
+                },
+            )
+        })
+        .collect();
+    let simulated_transaction = CliSimulatedTransaction {
+        fee,
+        units_consumed: result.units_consumed,
+        err: result.err.as_ref().map(|err| err.to_string()),
+        balance_changes,
+        logs: result.logs.unwrap_or_default(),
+    };
+    let output = config
+        .output_format
+        .formatted_string(&simulated_transaction);
+    match result.err {
+        None => Ok(output),
+        Some(err) => Err(format!("{output}\nSimulated transaction failed: {err}").into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        serde_json::json,
+        solana_cli_output::OutputFormat,
+        solana_rpc_client_api::{
+            request::RpcRequest,
+            response::{
+                Response, RpcResponseContext, RpcSimulateTransactionAccountDiff,
+                RpcSimulateTransactionResult,
+            },
+        },
+        solana_sdk::{
+            hash::Hash,
+            instruction::InstructionError,
+            pubkey::Pubkey,
+            signature::{Keypair, Signer},
+            system_instruction,
+            transaction::TransactionError,
+        },
+        std::collections::HashMap,
+    };
+
+    fn account_diff(pre_lamports: u64, post_lamports: u64) -> RpcSimulateTransactionAccountDiff {
+        RpcSimulateTransactionAccountDiff {
+            pre_lamports,
+            post_lamports,
+            pre_owner: Pubkey::default().to_string(),
+            post_owner: Pubkey::default().to_string(),
+            pre_data_len: 0,
+            post_data_len: 0,
+            data_diffs: vec![],
+        }
+    }
+
+    fn mock_rpc_client(
+        err: Option<TransactionError>,
+        account_diffs: Vec<Option<RpcSimulateTransactionAccountDiff>>,
+    ) -> RpcClient {
+        let context = RpcResponseContext {
+            slot: 1,
+            api_version: None,
+        };
+        let mut mocks = HashMap::new();
+        mocks.insert(
+            RpcRequest::GetFeeForMessage,
+            json!(Response {
+                context: context.clone(),
+                value: json!(5_000),
+            }),
+        );
+        mocks.insert(
+            RpcRequest::SimulateTransaction,
+            json!(Response {
+                context,
+                value: RpcSimulateTransactionResult {
+                    err,
+                    logs: Some(vec!["Program log: transfer".to_string()]),
+                    accounts: None,
+                    units_consumed: Some(150),
+                    return_data: None,
+                    inner_instructions: None,
+                    account_diffs: Some(account_diffs),
+                    instruction_compute_units: None,
+                    structured_logs: None,
+                },
+            }),
+        );
+        RpcClient::new_mock_with_mocks("".to_string(), mocks)
+    }
+
+    #[test]
+    fn test_simulate_transaction() {
+        let from = Keypair::new();
+        let to = Pubkey::new_unique();
+        let transaction = Transaction::new_signed_with_payer(
+            &[system_instruction::transfer(&from.pubkey(), &to, 42)],
+            Some(&from.pubkey()),
+            &[&from],
+            Hash::default(),
+        );
+        let config = CliConfig {
+            output_format: OutputFormat::Json,
+            ..CliConfig::default()
+        };
+
+        // the system program is left untouched
+        let rpc_client = mock_rpc_client(
+            None,
+            vec![
+                Some(account_diff(10_000, 4_958)),
+                Some(account_diff(0, 42)),
+                None,
+            ],
+        );
+        let output = simulate_transaction(&rpc_client, &config, &transaction).unwrap();
+        let simulated_transaction: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(
+            simulated_transaction,
+            json!({
+                "fee": 5_000,
+                "unitsConsumed": 150,
+                "err": null,
+                "balanceChanges": [
+                    {
+                        "address": from.pubkey().to_string(),
+                        "preBalance": 10_000,
+                        "postBalance": 4_958,
+                    },
+                    {
+                        "address": to.to_string(),
+                        "preBalance": 0,
+                        "postBalance": 42,
+                    },
+                ],
+                "logs": ["Program log: transfer"],
+            })
+        );
+
+        let rpc_client = mock_rpc_client(
+            Some(TransactionError::InstructionError(
+                0,
+                InstructionError::InsufficientFunds,
+            )),
+            vec![None; 3],
+        );
+        assert!(simulate_transaction(&rpc_client, &config, &transaction).is_err());
+    }
+
+    #[test]
+    fn test_supports_simulate_only() {
+        assert!(supports_simulate_only(&CliCommand::Program(
+            ProgramCliCommand::ExtendProgram {
+                program_pubkey: Pubkey::new_unique(),
+                additional_bytes: 42,
+            }
+        )));
+        assert!(!supports_simulate_only(&CliCommand::Address));
+        assert!(!supports_simulate_only(&CliCommand::Program(
+            ProgramCliCommand::Dump {
+                account_pubkey: Some(Pubkey::new_unique()),
+                output_location: "program.so".to_string(),
+            }
+        )));
+    }
+}
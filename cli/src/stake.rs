@@ -9,6 +9,7 @@ use {
         feature::get_feature_activation_epoch,
         memo::WithMemo,
         nonce::check_nonce_account,
+        simulate::simulate_transaction,
         spend_utils::{resolve_spend_tx_and_check_account_balances, SpendAmount},
     },
     clap::{value_t, App, Arg, ArgGroup, ArgMatches, SubCommand},
@@ -40,7 +41,7 @@ use {
     solana_sdk::{
         account::from_account,
         account_utils::StateMut,
-        clock::{Clock, UnixTimestamp, SECONDS_PER_DAY},
+        clock::{Clock, Epoch, UnixTimestamp, SECONDS_PER_DAY},
         commitment_config::CommitmentConfig,
         epoch_schedule::EpochSchedule,
         feature_set,
@@ -59,7 +60,7 @@ use {
         sysvar::{clock, stake_history},
         transaction::Transaction,
     },
-    std::{ops::Deref, rc::Rc},
+    std::rc::Rc,
 };
 
 pub const STAKE_AUTHORITY_ARG: ArgConstant<'static> = ArgConstant {
@@ -803,11 +804,21 @@ impl StakeSubCommands for App<'_, '_> {
                         .long("limit")
                         .takes_value(true)
                         .value_name("NUM")
-                        .default_value("10")
                         .validator(|s| s.parse::<usize>().map(|_| ()).map_err(|e| e.to_string()))
                         .help(
-                            "Display NUM recent epochs worth of stake history in text mode. 0 for \
-                             all",
+                            "Display NUM recent epochs worth of stake history. 0 for all \
+                             [default: 10 in text mode, all in JSON mode]",
+                        ),
+                )
+                .arg(
+                    Arg::with_name("epoch_range")
+                        .long("epoch-range")
+                        .takes_value(true)
+                        .value_name("FIRST..LAST")
+                        .validator(|s| parse_epoch_range(&s).map(|_| ()))
+                        .help(
+                            "Only display epochs within this inclusive range. Either bound may \
+                             be omitted, eg. 500.. or ..510",
                         ),
                 ),
         )
@@ -1380,13 +1391,48 @@ pub fn parse_show_stake_account(
     })
 }
 
+/// Parses an inclusive `FIRST..LAST` epoch range, where either bound may be omitted
+fn parse_epoch_range(value: &str) -> Result<(Option<Epoch>, Option<Epoch>), String> {
+    let (first, last) = value
+        .split_once("..")
+        .ok_or_else(|| format!("Unable to parse epoch range {value:?}, expected FIRST..LAST"))?;
+    let parse_bound = |bound: &str| -> Result<Option<Epoch>, String> {
+        if bound.is_empty() {
+            Ok(None)
+        } else {
+            bound
+                .parse::<Epoch>()
+                .map(Some)
+                .map_err(|err| format!("Unable to parse epoch {bound:?}: {err}"))
+        }
+    };
+    let first = parse_bound(first)?;
+    let last = parse_bound(last)?;
+    if let (Some(first), Some(last)) = (first, last) {
+        if first > last {
+            return Err(format!(
+                "First epoch {first} must not be greater than last epoch {last}"
+            ));
+        }
+    }
+    Ok((first, last))
+}
+
 pub fn parse_show_stake_history(matches: &ArgMatches<'_>) -> Result<CliCommandInfo, CliError> {
     let use_lamports_unit = matches.is_present("lamports");
-    let limit_results = value_of(matches, "limit").unwrap();
+    let limit_results = value_of(matches, "limit");
+    let (first_epoch, last_epoch) = matches
+        .value_of("epoch_range")
+        .map(parse_epoch_range)
+        .transpose()
+        .map_err(CliError::BadParameter)?
+        .unwrap_or_default();
     Ok(CliCommandInfo {
         command: CliCommand::ShowStakeHistory {
             use_lamports_unit,
             limit_results,
+            first_epoch,
+            last_epoch,
         },
         signers: vec![],
     })
@@ -1548,6 +1594,9 @@ pub fn process_create_stake_account(
         )
     } else {
         tx.try_sign(&config.signers, recent_blockhash)?;
+        if config.simulate_only {
+            return simulate_transaction(rpc_client, config, &tx);
+        }
         let result = rpc_client.send_and_confirm_transaction_with_spinner(&tx);
         log_instruction_custom_error::<SystemError>(result, config)
     }
@@ -1680,6 +1729,9 @@ pub fn process_stake_authorize(
             &tx.message,
             config.commitment,
         )?;
+        if config.simulate_only {
+            return simulate_transaction(rpc_client, config, &tx);
+        }
         let result = if no_wait {
             rpc_client.send_transaction(&tx)
         } else {
@@ -1823,6 +1875,9 @@ pub fn process_deactivate_stake_account(
             &tx.message,
             config.commitment,
         )?;
+        if config.simulate_only {
+            return simulate_transaction(rpc_client, config, &tx);
+        }
         let result = rpc_client.send_and_confirm_transaction_with_spinner(&tx);
         log_instruction_custom_error::<StakeError>(result, config)
     }
@@ -1922,6 +1977,9 @@ pub fn process_withdraw_stake(
             &tx.message,
             config.commitment,
         )?;
+        if config.simulate_only {
+            return simulate_transaction(rpc_client, config, &tx);
+        }
         let result = rpc_client.send_and_confirm_transaction_with_spinner(&tx);
         log_instruction_custom_error::<StakeError>(result, config)
     }
@@ -2080,6 +2138,9 @@ pub fn process_split_stake(
             &tx.message,
             config.commitment,
         )?;
+        if config.simulate_only {
+            return simulate_transaction(rpc_client, config, &tx);
+        }
         let result = rpc_client.send_and_confirm_transaction_with_spinner(&tx);
         log_instruction_custom_error::<StakeError>(result, config)
     }
@@ -2186,6 +2247,9 @@ pub fn process_merge_stake(
             &tx.message,
             config.commitment,
         )?;
+        if config.simulate_only {
+            return simulate_transaction(rpc_client, config, &tx);
+        }
         let result = rpc_client.send_and_confirm_transaction_with_spinner_and_config(
             &tx,
             config.commitment,
@@ -2281,6 +2345,9 @@ pub fn process_stake_set_lockup(
             &tx.message,
             config.commitment,
         )?;
+        if config.simulate_only {
+            return simulate_transaction(rpc_client, config, &tx);
+        }
         let result = rpc_client.send_and_confirm_transaction_with_spinner(&tx);
         log_instruction_custom_error::<StakeError>(result, config)
     }
@@ -2585,7 +2652,9 @@ pub fn process_show_stake_history(
     rpc_client: &RpcClient,
     config: &CliConfig,
     use_lamports_unit: bool,
-    limit_results: usize,
+    limit_results: Option<usize>,
+    first_epoch: Option<Epoch>,
+    last_epoch: Option<Epoch>,
 ) -> ProcessResult {
     let stake_history_account = rpc_client.get_account(&stake_history::id())?;
     let stake_history =
@@ -2593,20 +2662,18 @@ pub fn process_show_stake_history(
             CliError::RpcRequestError("Failed to deserialize stake history".to_string())
         })?;
 
-    let limit_results = match config.output_format {
-        OutputFormat::Json | OutputFormat::JsonCompact => std::usize::MAX,
-        _ => {
-            if limit_results == 0 {
-                std::usize::MAX
-            } else {
-                limit_results
-            }
-        }
+    let limit_results = match (limit_results, &config.output_format) {
+        (Some(0), _) | (None, OutputFormat::Json | OutputFormat::JsonCompact) => std::usize::MAX,
+        (Some(limit_results), _) => limit_results,
+        (None, _) => 10,
     };
-    let mut entries: Vec<CliStakeHistoryEntry> = vec![];
-    for entry in stake_history.deref().iter().take(limit_results) {
-        entries.push(entry.into());
-    }
+    let first_epoch = first_epoch.unwrap_or(Epoch::MIN);
+    let last_epoch = last_epoch.unwrap_or(Epoch::MAX);
+    let entries: Vec<CliStakeHistoryEntry> = stake_history
+        .iter_range(first_epoch..=last_epoch)
+        .take(limit_results)
+        .map(|entry| entry.into())
+        .collect();
     let stake_history_output = CliStakeHistory {
         entries,
         use_lamports_unit,
@@ -2763,6 +2830,9 @@ pub fn process_delegate_stake(
             &tx.message,
             config.commitment,
         )?;
+        if config.simulate_only {
+            return simulate_transaction(rpc_client, config, &tx);
+        }
         let result = rpc_client.send_and_confirm_transaction_with_spinner(&tx);
         log_instruction_custom_error::<StakeError>(result, config)
     }
@@ -2810,6 +2880,74 @@ mod tests {
         (String::from(tmp_file.path().to_str().unwrap()), tmp_file)
     }
 
+    #[test]
+    fn test_parse_epoch_range() {
+        assert_eq!(parse_epoch_range("500..510"), Ok((Some(500), Some(510))));
+        assert_eq!(parse_epoch_range("500..500"), Ok((Some(500), Some(500))));
+        assert_eq!(parse_epoch_range("500.."), Ok((Some(500), None)));
+        assert_eq!(parse_epoch_range("..510"), Ok((None, Some(510))));
+        assert_eq!(parse_epoch_range(".."), Ok((None, None)));
+        assert!(parse_epoch_range("510..500").is_err());
+        assert!(parse_epoch_range("500").is_err());
+        assert!(parse_epoch_range("500-510").is_err());
+        assert!(parse_epoch_range("a..510").is_err());
+    }
+
+    #[test]
+    fn test_parse_show_stake_history() {
+        let test_commands = get_clap_app("test", "desc", "version");
+        let default_keypair = Keypair::new();
+        let (default_keypair_file, mut tmp_file) = make_tmp_file();
+        write_keypair(&default_keypair, tmp_file.as_file_mut()).unwrap();
+        let default_signer = DefaultSigner::new("", &default_keypair_file);
+
+        let test_stake_history = test_commands
+            .clone()
+            .get_matches_from(vec!["test", "stake-history"]);
+        assert_eq!(
+            parse_command(&test_stake_history, &default_signer, &mut None).unwrap(),
+            CliCommandInfo {
+                command: CliCommand::ShowStakeHistory {
+                    use_lamports_unit: false,
+                    limit_results: None,
+                    first_epoch: None,
+                    last_epoch: None,
+                },
+                signers: vec![],
+            }
+        );
+
+        let test_stake_history = test_commands.clone().get_matches_from(vec![
+            "test",
+            "stake-history",
+            "--lamports",
+            "--limit",
+            "3",
+            "--epoch-range",
+            "100..",
+        ]);
+        assert_eq!(
+            parse_command(&test_stake_history, &default_signer, &mut None).unwrap(),
+            CliCommandInfo {
+                command: CliCommand::ShowStakeHistory {
+                    use_lamports_unit: true,
+                    limit_results: Some(3),
+                    first_epoch: Some(100),
+                    last_epoch: None,
+                },
+                signers: vec![],
+            }
+        );
+
+        let test_stake_history = test_commands.clone().get_matches_from_safe(vec![
+            "test",
+            "stake-history",
+            "--epoch-range",
+            "110..100",
+        ]);
+        assert!(test_stake_history.is_err());
+    }
+
     #[test]
     #[allow(clippy::cognitive_complexity)]
     fn test_parse_command() {
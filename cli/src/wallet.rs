@@ -7,6 +7,7 @@ use {
         compute_unit_price::WithComputeUnitPrice,
         memo::WithMemo,
         nonce::check_nonce_account,
+        simulate::simulate_transaction,
         spend_utils::{resolve_spend_tx_and_check_account_balances, SpendAmount},
     },
     clap::{value_t_or_exit, App, Arg, ArgMatches, SubCommand},
@@ -979,6 +980,9 @@ pub fn process_transfer(
         }
 
         tx.try_sign(&config.signers, recent_blockhash)?;
+        if config.simulate_only {
+            return simulate_transaction(rpc_client, config, &tx);
+        }
         let result = if no_wait {
             rpc_client.send_transaction(&tx)
         } else {
@@ -198,6 +198,7 @@ fn full_battery_tests(
         memo: None,
         new_authority: new_authority.pubkey(),
         compute_unit_price: None,
+        advance: false,
     };
     process_command(&config_payer).unwrap();
 
@@ -251,6 +251,7 @@ fn test_transfer() {
         memo: None,
         new_authority: offline_pubkey,
         compute_unit_price: None,
+        advance: false,
     };
     process_command(&config).unwrap();
     check_balance!(
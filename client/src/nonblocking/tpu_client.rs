@@ -1,6 +1,9 @@
 pub use solana_tpu_client::nonblocking::tpu_client::{LeaderTpuService, TpuSenderError};
 use {
-    crate::{connection_cache::ConnectionCache, tpu_client::TpuClientConfig},
+    crate::{
+        connection_cache::ConnectionCache,
+        tpu_client::{BundleStatus, SentBundle, TpuClientConfig},
+    },
     solana_connection_cache::connection_cache::{
         ConnectionCache as BackendConnectionCache, ConnectionManager, ConnectionPool,
         NewConnectionConfig,
@@ -9,6 +12,7 @@ use {
     solana_rpc_client::nonblocking::rpc_client::RpcClient,
     solana_sdk::{
         message::Message,
+        signer::async_signer::AsyncSigner,
         signers::Signers,
         transaction::{Transaction, TransactionError},
         transport::Result as TransportResult,
@@ -75,6 +79,17 @@ where
             .try_send_wire_transaction_batch(wire_transactions)
             .await
     }
+
+    /// Serialize and send a bundle of transactions to the leader of the current slot, to land in
+    /// the same block. The leader may receive and process the transactions in any order.
+    pub async fn send_bundle(&self, transactions: &[Transaction]) -> Result<SentBundle> {
+        self.tpu_client.send_bundle(transactions).await
+    }
+
+    /// Fetch the status of a bundle sent by `send_bundle`
+    pub async fn get_bundle_status(&self, bundle: &SentBundle) -> Result<BundleStatus> {
+        self.tpu_client.get_bundle_status(bundle).await
+    }
 }
 
 impl TpuClient<QuicPool, QuicConnectionManager, QuicConfig> {
@@ -131,6 +146,16 @@ where
             .await
     }
 
+    pub async fn send_and_confirm_messages_with_async_signers(
+        &self,
+        messages: &[Message],
+        signers: &[&dyn AsyncSigner],
+    ) -> Result<Vec<Option<TransactionError>>> {
+        self.tpu_client
+            .send_and_confirm_messages_with_async_signers(messages, signers)
+            .await
+    }
+
     pub fn rpc_client(&self) -> &RpcClient {
         self.tpu_client.rpc_client()
     }
@@ -17,7 +17,10 @@ use {
 };
 pub use {
     crate::nonblocking::tpu_client::TpuSenderError,
-    solana_tpu_client::tpu_client::{TpuClientConfig, DEFAULT_FANOUT_SLOTS, MAX_FANOUT_SLOTS},
+    solana_tpu_client::tpu_client::{
+        BundleStatus, SentBundle, TpuClientConfig, DEFAULT_FANOUT_SLOTS, MAX_BUNDLE_SIZE,
+        MAX_FANOUT_SLOTS,
+    },
 };
 
 /// Client which sends transactions directly to the current leader's TPU port over UDP.
@@ -67,6 +70,17 @@ where
     pub fn try_send_wire_transaction(&self, wire_transaction: Vec<u8>) -> TransportResult<()> {
         self.tpu_client.try_send_wire_transaction(wire_transaction)
     }
+
+    /// Serialize and send a bundle of transactions to the leader of the current slot, to land in
+    /// the same block. The leader may receive and process the transactions in any order.
+    pub fn send_bundle(&self, transactions: &[Transaction]) -> Result<SentBundle> {
+        self.tpu_client.send_bundle(transactions)
+    }
+
+    /// Fetch the status of a bundle sent by `send_bundle`
+    pub fn get_bundle_status(&self, bundle: &SentBundle) -> Result<BundleStatus> {
+        self.tpu_client.get_bundle_status(bundle)
+    }
 }
 
 impl TpuClient<QuicPool, QuicConnectionManager, QuicConfig> {
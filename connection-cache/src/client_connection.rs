@@ -28,6 +28,12 @@ pub struct ClientStats {
     pub prepare_connection_us: AtomicU64,
     /// Count of packets successfully sent
     pub successful_packets: AtomicU64,
+    /// Count of connection handshakes that did not complete within the handshake timeout
+    pub handshake_timeouts: AtomicU64,
+    /// Count of reconnections made after a connection failed
+    pub connection_retries: AtomicU64,
+    /// The time spent backing off before reconnecting
+    pub connection_backoff_ms: AtomicU64,
 }
 
 pub trait ClientConnection: Sync + Send {
@@ -72,6 +72,18 @@ impl ConnectionCacheStats {
             client_stats.prepare_connection_us.load(Ordering::Relaxed),
             Ordering::Relaxed,
         );
+        self.total_client_stats.handshake_timeouts.fetch_add(
+            client_stats.handshake_timeouts.load(Ordering::Relaxed),
+            Ordering::Relaxed,
+        );
+        self.total_client_stats.connection_retries.fetch_add(
+            client_stats.connection_retries.load(Ordering::Relaxed),
+            Ordering::Relaxed,
+        );
+        self.total_client_stats.connection_backoff_ms.fetch_add(
+            client_stats.connection_backoff_ms.load(Ordering::Relaxed),
+            Ordering::Relaxed,
+        );
         self.sent_packets
             .fetch_add(num_packets as u64, Ordering::Relaxed);
         self.total_batches.fetch_add(1, Ordering::Relaxed);
@@ -187,6 +199,27 @@ impl ConnectionCacheStats {
                     .swap(0, Ordering::Relaxed),
                 i64
             ),
+            (
+                "handshake_timeouts",
+                self.total_client_stats
+                    .handshake_timeouts
+                    .swap(0, Ordering::Relaxed),
+                i64
+            ),
+            (
+                "connection_retries",
+                self.total_client_stats
+                    .connection_retries
+                    .swap(0, Ordering::Relaxed),
+                i64
+            ),
+            (
+                "connection_backoff_ms",
+                self.total_client_stats
+                    .connection_backoff_ms
+                    .swap(0, Ordering::Relaxed),
+                i64
+            ),
             (
                 "congestion_events",
                 self.total_client_stats.congestion_events.load_and_reset(),
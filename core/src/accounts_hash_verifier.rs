@@ -5,7 +5,7 @@ use {
     solana_accounts_db::{
         accounts_db::CalcAccountsHashKind,
         accounts_hash::{
-            AccountsHash, AccountsHashKind, CalcAccountsHashConfig, HashStats,
+            AccountsHash, AccountsHashKind, AccountsLtHash, CalcAccountsHashConfig, HashStats,
             IncrementalAccountsHash,
         },
         sorted_storages::SortedStorages,
@@ -440,9 +440,46 @@ impl AccountsHashVerifier {
             ("calculate_hash", measure_hash_us, i64),
         );
 
+        if let Some(accounts_lt_hash) = accounts_package.accounts_lt_hash {
+            Self::verify_accounts_lt_hash(accounts_package, &accounts_hash, &accounts_lt_hash);
+        }
+
         (accounts_hash, lamports)
     }
 
+    /// While the accounts lattice hash is rolled out, cross-check the one the
+    /// bank maintained incrementally against one calculated from scratch from
+    /// the same storages as the accounts hash.
+    ///
+    /// A mismatch is reported rather than fatal, since the bank hash the
+    /// cluster agreed on already includes the incremental one.
+    fn verify_accounts_lt_hash(
+        accounts_package: &AccountsPackage,
+        accounts_hash: &AccountsHash,
+        accounts_lt_hash: &AccountsLtHash,
+    ) {
+        let (calculated_accounts_lt_hash, calculate_us) = measure_us!(accounts_package
+            .accounts
+            .accounts_db
+            .calculate_accounts_lt_hash_from_storages(&accounts_package.snapshot_storages));
+        let is_match = calculated_accounts_lt_hash == *accounts_lt_hash;
+        if !is_match {
+            error!(
+                "accounts lattice hash mismatch at slot {}: incremental checksum: {}, calculated checksum: {}, accounts hash: {}",
+                accounts_package.slot,
+                accounts_lt_hash.0.checksum(),
+                calculated_accounts_lt_hash.0.checksum(),
+                accounts_hash.0,
+            );
+        }
+        datapoint_info!(
+            "accounts_hash_verifier-accounts_lt_hash",
+            ("slot", accounts_package.slot, i64),
+            ("is_match", is_match, bool),
+            ("calculate_us", calculate_us, i64),
+        );
+    }
+
     fn _calculate_incremental_accounts_hash(
         accounts_package: &AccountsPackage,
         base_slot: Slot,
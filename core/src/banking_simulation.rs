@@ -0,0 +1,375 @@
+//! Offline simulation of block production, feeding recorded transactions
+//! through BankingStage's transaction scheduler to evaluate how much of them
+//! fit in a block.
+//!
+//! Transactions are not executed: the simulated workers only charge each
+//! scheduled transaction's cost against the block's cost limits, as the
+//! consume workers' QoS checks would.
+
+use {
+    crate::banking_stage::{
+        scheduler_messages::{ConsumeWork, FinishedConsumeWork, TransactionId},
+        transaction_scheduler::{
+            prio_graph_scheduler::PrioGraphScheduler, scheduler_controller::SchedulerController,
+            transaction_state::SanitizedTransactionTTL,
+            transaction_state_container::TransactionStateContainer,
+        },
+    },
+    crossbeam_channel::{unbounded, Receiver},
+    solana_cost_model::{
+        cost_model::CostModel,
+        cost_tracker::{CostTracker, CostTrackerError},
+    },
+    solana_program_runtime::compute_budget_processor::process_compute_budget_instructions,
+    solana_sdk::{
+        clock::Slot,
+        feature_set::FeatureSet,
+        fee::{FeeBudgetLimits, FeeStructure},
+        saturating_add_assign,
+        transaction::SanitizedTransaction,
+    },
+};
+
+/// Outcome of simulating the production of a block
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SimulatedBlock {
+    /// Number of transactions fed to the simulation
+    pub num_transactions: usize,
+    /// Number of simple vote transactions, which are charged to the block
+    /// ahead of the scheduler as the vote threads would
+    pub num_votes: usize,
+    /// Number of transactions that fit in the block, including votes
+    pub num_included: usize,
+    /// Number of transactions dropped because their compute budget
+    /// instructions are invalid
+    pub num_dropped_on_compute_budget: usize,
+    /// Number of transactions dropped because they would exceed the block
+    /// cost limit
+    pub num_dropped_on_block_limit: usize,
+    /// Number of transactions dropped because they would exceed the cost
+    /// limit of one of their writable accounts
+    pub num_dropped_on_account_limit: usize,
+    /// Number of transactions dropped because they would exceed the vote
+    /// cost limit
+    pub num_dropped_on_vote_limit: usize,
+    /// Number of transactions dropped because they would exceed the account
+    /// data size limits
+    pub num_dropped_on_account_data_limit: usize,
+    /// Number of transactions left unscheduled when the scheduler stopped
+    /// making progress
+    pub num_unscheduled: usize,
+    /// Number of scheduling passes needed to drain the scheduler
+    pub num_scheduling_passes: usize,
+    /// Cost of the transactions included in the block
+    pub block_cost: u64,
+    pub block_cost_limit: u64,
+    /// Cost of the transactions included by each worker
+    pub cost_per_worker: Vec<u64>,
+}
+
+impl SimulatedBlock {
+    pub fn num_dropped(&self) -> usize {
+        self.num_dropped_on_compute_budget
+            .saturating_add(self.num_dropped_on_block_limit)
+            .saturating_add(self.num_dropped_on_account_limit)
+            .saturating_add(self.num_dropped_on_vote_limit)
+            .saturating_add(self.num_dropped_on_account_data_limit)
+            .saturating_add(self.num_unscheduled)
+    }
+
+    /// Percentage of the block cost limit used by the included transactions
+    pub fn block_fill_percent(&self) -> f64 {
+        if self.block_cost_limit == 0 {
+            return 0.0;
+        }
+        self.block_cost as f64 * 100.0 / self.block_cost_limit as f64
+    }
+
+    /// Adds the counts and costs of `other`, e.g. to total several blocks
+    pub fn accumulate(&mut self, other: &SimulatedBlock) {
+        saturating_add_assign!(self.num_transactions, other.num_transactions);
+        saturating_add_assign!(self.num_votes, other.num_votes);
+        saturating_add_assign!(self.num_included, other.num_included);
+        saturating_add_assign!(
+            self.num_dropped_on_compute_budget,
+            other.num_dropped_on_compute_budget
+        );
+        saturating_add_assign!(
+            self.num_dropped_on_block_limit,
+            other.num_dropped_on_block_limit
+        );
+        saturating_add_assign!(
+            self.num_dropped_on_account_limit,
+            other.num_dropped_on_account_limit
+        );
+        saturating_add_assign!(
+            self.num_dropped_on_vote_limit,
+            other.num_dropped_on_vote_limit
+        );
+        saturating_add_assign!(
+            self.num_dropped_on_account_data_limit,
+            other.num_dropped_on_account_data_limit
+        );
+        saturating_add_assign!(self.num_unscheduled, other.num_unscheduled);
+        saturating_add_assign!(self.num_scheduling_passes, other.num_scheduling_passes);
+        saturating_add_assign!(self.block_cost, other.block_cost);
+        saturating_add_assign!(self.block_cost_limit, other.block_cost_limit);
+        if self.cost_per_worker.len() < other.cost_per_worker.len() {
+            self.cost_per_worker.resize(other.cost_per_worker.len(), 0);
+        }
+        for (cost, other_cost) in self.cost_per_worker.iter_mut().zip(&other.cost_per_worker) {
+            saturating_add_assign!(*cost, *other_cost);
+        }
+    }
+
+    fn count_dropped(&mut self, err: CostTrackerError) {
+        let num_dropped = match err {
+            CostTrackerError::WouldExceedBlockMaxLimit => &mut self.num_dropped_on_block_limit,
+            CostTrackerError::WouldExceedAccountMaxLimit => &mut self.num_dropped_on_account_limit,
+            CostTrackerError::WouldExceedVoteMaxLimit => &mut self.num_dropped_on_vote_limit,
+            CostTrackerError::WouldExceedAccountDataBlockLimit
+            | CostTrackerError::WouldExceedAccountDataTotalLimit => {
+                &mut self.num_dropped_on_account_data_limit
+            }
+        };
+        saturating_add_assign!(*num_dropped, 1);
+    }
+}
+
+/// Runs BankingStage's transaction scheduler over a block's transactions,
+/// with simulated consume workers
+pub struct BankingSimulator {
+    num_workers: usize,
+    feature_set: FeatureSet,
+    fee_structure: FeeStructure,
+}
+
+impl BankingSimulator {
+    pub fn new(num_workers: usize, feature_set: FeatureSet) -> Self {
+        assert!(num_workers > 0, "at least one worker is required");
+        Self {
+            num_workers,
+            feature_set,
+            fee_structure: FeeStructure::default(),
+        }
+    }
+
+    /// Simulates producing a block out of `transactions`, which are all
+    /// assumed to be received before the block starts, charging the included
+    /// transactions to `cost_tracker`
+    pub fn simulate_block(
+        &self,
+        transactions: Vec<SanitizedTransaction>,
+        mut cost_tracker: CostTracker,
+    ) -> SimulatedBlock {
+        let (consume_work_senders, consume_work_receivers): (Vec<_>, Vec<_>) =
+            (0..self.num_workers).map(|_| unbounded()).unzip();
+        let (finished_consume_work_sender, finished_consume_work_receiver) = unbounded();
+        let mut scheduler =
+            PrioGraphScheduler::new(consume_work_senders, finished_consume_work_receiver);
+        let mut container = TransactionStateContainer::with_capacity(transactions.len().max(1));
+        let mut block = SimulatedBlock {
+            num_transactions: transactions.len(),
+            cost_per_worker: vec![0; self.num_workers],
+            ..SimulatedBlock::default()
+        };
+
+        for (index, transaction) in transactions.into_iter().enumerate() {
+            if transaction.is_simple_vote_transaction() {
+                saturating_add_assign!(block.num_votes, 1);
+                self.charge_transaction(&transaction, &mut cost_tracker, &mut block);
+                continue;
+            }
+            let Ok(compute_budget) = process_compute_budget_instructions(
+                transaction.message().program_instructions_iter(),
+            ) else {
+                saturating_add_assign!(block.num_dropped_on_compute_budget, 1);
+                continue;
+            };
+            let (priority, cost) = SchedulerController::calculate_priority_and_cost(
+                &transaction,
+                &FeeBudgetLimits::from(compute_budget),
+                &self.fee_structure,
+                &self.feature_set,
+            );
+            let transaction_ttl = SanitizedTransactionTTL {
+                transaction,
+                max_age_slot: Slot::MAX,
+            };
+            container.insert_new_transaction(
+                TransactionId::new(index as u64),
+                transaction_ttl,
+                priority,
+                cost,
+            );
+        }
+
+        while !container.is_empty() {
+            let scheduling_summary = scheduler
+                .schedule(&mut container, |_, results| results.fill(true), |_| true)
+                .expect("simulated workers must be connected");
+            if scheduling_summary.num_scheduled == 0 {
+                break;
+            }
+            saturating_add_assign!(block.num_scheduling_passes, 1);
+
+            // Let the workers take turns consuming their next batch, so that
+            // they share the block as they would if running concurrently
+            loop {
+                let mut num_consumed = 0;
+                for (worker, receiver) in consume_work_receivers.iter().enumerate() {
+                    let Some(work) = self.consume(worker, receiver, &mut cost_tracker, &mut block)
+                    else {
+                        continue;
+                    };
+                    num_consumed += 1;
+                    finished_consume_work_sender
+                        .send(FinishedConsumeWork {
+                            work,
+                            retryable_indexes: vec![],
+                        })
+                        .expect("scheduler must be connected");
+                }
+                if num_consumed == 0 {
+                    break;
+                }
+            }
+            scheduler
+                .receive_completed(&mut container)
+                .expect("simulated workers must be connected");
+        }
+
+        while let Some(id) = container.pop() {
+            container.remove_by_id(&id.id);
+            saturating_add_assign!(block.num_unscheduled, 1);
+        }
+        block.block_cost = cost_tracker.block_cost();
+        block.block_cost_limit = cost_tracker.block_cost_limit();
+        block
+    }
+
+    /// Consumes the next batch scheduled to `worker`, if any
+    fn consume(
+        &self,
+        worker: usize,
+        receiver: &Receiver<ConsumeWork>,
+        cost_tracker: &mut CostTracker,
+        block: &mut SimulatedBlock,
+    ) -> Option<ConsumeWork> {
+        let work = receiver.try_recv().ok()?;
+        for transaction in &work.transactions {
+            if let Some(cost) = self.charge_transaction(transaction, cost_tracker, block) {
+                saturating_add_assign!(block.cost_per_worker[worker], cost);
+            }
+        }
+        Some(work)
+    }
+
+    /// Returns the cost of `transaction` if it fits in the block
+    fn charge_transaction(
+        &self,
+        transaction: &SanitizedTransaction,
+        cost_tracker: &mut CostTracker,
+        block: &mut SimulatedBlock,
+    ) -> Option<u64> {
+        let transaction_cost = CostModel::calculate_cost(transaction, &self.feature_set);
+        match cost_tracker.try_add(&transaction_cost) {
+            Ok(_) => {
+                saturating_add_assign!(block.num_included, 1);
+                Some(transaction_cost.sum())
+            }
+            Err(err) => {
+                block.count_dropped(err);
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_sdk::{
+            compute_budget::ComputeBudgetInstruction,
+            hash::Hash,
+            message::Message,
+            pubkey::Pubkey,
+            signature::{Keypair, Signer},
+            system_instruction,
+            transaction::Transaction,
+        },
+    };
+
+    fn prioritized_transfer(
+        from_keypair: &Keypair,
+        to_pubkey: &Pubkey,
+        compute_unit_price: u64,
+    ) -> SanitizedTransaction {
+        let ixs = vec![
+            system_instruction::transfer(&from_keypair.pubkey(), to_pubkey, 1),
+            ComputeBudgetInstruction::set_compute_unit_price(compute_unit_price),
+        ];
+        let message = Message::new(&ixs, Some(&from_keypair.pubkey()));
+        let transaction = Transaction::new(&[from_keypair], message, Hash::default());
+        SanitizedTransaction::from_transaction_for_tests(transaction)
+    }
+
+    #[test]
+    fn test_simulate_block() {
+        let simulator = BankingSimulator::new(4, FeatureSet::all_enabled());
+        let payer = Keypair::new();
+        let transactions: Vec<_> =
+            (0..10)
+                .map(|price| prioritized_transfer(&payer, &Pubkey::new_unique(), price))
+                .chain((0..10).map(|price| {
+                    prioritized_transfer(&Keypair::new(), &Pubkey::new_unique(), price)
+                }))
+                .collect();
+        let expected_block_cost: u64 = transactions
+            .iter()
+            .map(|transaction| {
+                CostModel::calculate_cost(transaction, &FeatureSet::all_enabled()).sum()
+            })
+            .sum();
+
+        let block = simulator.simulate_block(transactions, CostTracker::default());
+        assert_eq!(block.num_transactions, 20);
+        assert_eq!(block.num_included, 20);
+        assert_eq!(block.num_dropped(), 0);
+        assert_eq!(block.block_cost, expected_block_cost);
+        assert_eq!(
+            block.cost_per_worker.iter().sum::<u64>(),
+            expected_block_cost
+        );
+        assert!(block.block_fill_percent() > 0.0);
+
+        let mut total = SimulatedBlock::default();
+        total.accumulate(&block);
+        total.accumulate(&block);
+        assert_eq!(total.num_included, 40);
+        assert_eq!(total.cost_per_worker.len(), 4);
+        assert_eq!(total.block_cost, 2 * expected_block_cost);
+        assert_eq!(total.block_fill_percent(), block.block_fill_percent());
+    }
+
+    #[test]
+    fn test_simulate_block_cost_limits() {
+        let simulator = BankingSimulator::new(2, FeatureSet::all_enabled());
+        let payer = Keypair::new();
+        let low_priority = prioritized_transfer(&payer, &Pubkey::new_unique(), 1);
+        let high_priority = prioritized_transfer(&payer, &Pubkey::new_unique(), 1_000);
+        let transaction_cost =
+            CostModel::calculate_cost(&high_priority, &FeatureSet::all_enabled()).sum();
+
+        // Only one of the transactions fits, and it is the highest priority one
+        let mut cost_tracker = CostTracker::default();
+        cost_tracker.set_limits(u64::MAX, transaction_cost, u64::MAX);
+        let block = simulator.simulate_block(vec![low_priority, high_priority], cost_tracker);
+        assert_eq!(block.num_included, 1);
+        assert_eq!(block.num_dropped_on_block_limit, 1);
+        assert_eq!(block.num_dropped(), 1);
+        assert_eq!(block.block_cost, transaction_cost);
+        assert_eq!(block.block_fill_percent(), 100.0);
+    }
+}
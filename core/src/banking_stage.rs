@@ -4,10 +4,12 @@
 
 use {
     self::{
+        account_contention::AccountContentionTracker,
         committer::Committer,
         consumer::Consumer,
         decision_maker::{BufferedPacketsDecision, DecisionMaker},
         forwarder::Forwarder,
+        forwarding_policy::ForwardingPolicy,
         latest_unprocessed_votes::{LatestUnprocessedVotes, VoteSource},
         leader_slot_metrics::LeaderSlotMetricsTracker,
         packet_receiver::PacketReceiver,
@@ -51,8 +53,10 @@ use {
 };
 
 // Below modules are pub to allow use by banking_stage bench
+pub mod account_contention;
 pub mod committer;
 pub mod consumer;
+pub mod forwarding_policy;
 pub mod leader_slot_metrics;
 pub mod qos_service;
 pub mod unprocessed_packet_batches;
@@ -71,8 +75,8 @@ mod packet_deserializer;
 mod packet_receiver;
 mod read_write_account_set;
 #[allow(dead_code)]
-mod scheduler_messages;
-mod transaction_scheduler;
+pub(crate) mod scheduler_messages;
+pub(crate) mod transaction_scheduler;
 
 // Fixed thread size seems to be fastest on GCP setup
 pub const NUM_THREADS: u32 = 6;
@@ -336,6 +340,8 @@ impl BankingStage {
         connection_cache: Arc<ConnectionCache>,
         bank_forks: Arc<RwLock<BankForks>>,
         prioritization_fee_cache: &Arc<PrioritizationFeeCache>,
+        forwarding_policy: Arc<RwLock<ForwardingPolicy>>,
+        account_contention: Arc<AccountContentionTracker>,
     ) -> Self {
         Self::new_num_threads(
             block_production_method,
@@ -351,6 +357,8 @@ impl BankingStage {
             connection_cache,
             bank_forks,
             prioritization_fee_cache,
+            forwarding_policy,
+            account_contention,
         )
     }
 
@@ -369,6 +377,8 @@ impl BankingStage {
         connection_cache: Arc<ConnectionCache>,
         bank_forks: Arc<RwLock<BankForks>>,
         prioritization_fee_cache: &Arc<PrioritizationFeeCache>,
+        forwarding_policy: Arc<RwLock<ForwardingPolicy>>,
+        account_contention: Arc<AccountContentionTracker>,
     ) -> Self {
         match block_production_method {
             BlockProductionMethod::ThreadLocalMultiIterator => {
@@ -385,6 +395,8 @@ impl BankingStage {
                     connection_cache,
                     bank_forks,
                     prioritization_fee_cache,
+                    forwarding_policy,
+                    account_contention,
                 )
             }
             BlockProductionMethod::CentralScheduler => Self::new_central_scheduler(
@@ -400,6 +412,8 @@ impl BankingStage {
                 connection_cache,
                 bank_forks,
                 prioritization_fee_cache,
+                forwarding_policy,
+                account_contention,
             ),
         }
     }
@@ -418,6 +432,8 @@ impl BankingStage {
         connection_cache: Arc<ConnectionCache>,
         bank_forks: Arc<RwLock<BankForks>>,
         prioritization_fee_cache: &Arc<PrioritizationFeeCache>,
+        forwarding_policy: Arc<RwLock<ForwardingPolicy>>,
+        account_contention: Arc<AccountContentionTracker>,
     ) -> Self {
         assert!(num_threads >= MIN_TOTAL_THREADS);
         // Single thread to generate entries from many banks.
@@ -470,6 +486,8 @@ impl BankingStage {
                     cluster_info.clone(),
                     connection_cache.clone(),
                     data_budget.clone(),
+                    forwarding_policy.clone(),
+                    account_contention.clone(),
                 );
 
                 Self::spawn_thread_local_multi_iterator_thread(
@@ -482,6 +500,7 @@ impl BankingStage {
                     log_messages_bytes_limit,
                     forwarder,
                     unprocessed_transaction_storage,
+                    account_contention.clone(),
                 )
             })
             .collect();
@@ -502,6 +521,8 @@ impl BankingStage {
         connection_cache: Arc<ConnectionCache>,
         bank_forks: Arc<RwLock<BankForks>>,
         prioritization_fee_cache: &Arc<PrioritizationFeeCache>,
+        forwarding_policy: Arc<RwLock<ForwardingPolicy>>,
+        account_contention: Arc<AccountContentionTracker>,
     ) -> Self {
         assert!(num_threads >= MIN_TOTAL_THREADS);
         // Single thread to generate entries from many banks.
@@ -541,11 +562,14 @@ impl BankingStage {
                     cluster_info.clone(),
                     connection_cache.clone(),
                     data_budget.clone(),
+                    forwarding_policy.clone(),
+                    account_contention.clone(),
                 ),
                 UnprocessedTransactionStorage::new_vote_storage(
                     latest_unprocessed_votes.clone(),
                     vote_source,
                 ),
+                account_contention.clone(),
             ));
         }
 
@@ -567,6 +591,7 @@ impl BankingStage {
                     poh_recorder.read().unwrap().new_recorder(),
                     QosService::new(id),
                     log_messages_bytes_limit,
+                    account_contention.clone(),
                 ),
                 finished_work_sender.clone(),
                 poh_recorder.read().unwrap().new_leader_bank_notifier(),
@@ -594,6 +619,7 @@ impl BankingStage {
                 bank_forks,
                 scheduler,
                 worker_metrics,
+                account_contention,
             );
             Builder::new()
                 .name("solBnkTxSched".to_string())
@@ -620,6 +646,7 @@ impl BankingStage {
         log_messages_bytes_limit: Option<usize>,
         forwarder: Forwarder,
         unprocessed_transaction_storage: UnprocessedTransactionStorage,
+        account_contention: Arc<AccountContentionTracker>,
     ) -> JoinHandle<()> {
         let mut packet_receiver = PacketReceiver::new(id, packet_receiver, bank_forks);
         let consumer = Consumer::new(
@@ -627,6 +654,7 @@ impl BankingStage {
             transaction_recorder,
             QosService::new(id),
             log_messages_bytes_limit,
+            account_contention,
         );
 
         Builder::new()
@@ -871,6 +899,8 @@ mod tests {
                 Arc::new(ConnectionCache::new("connection_cache_test")),
                 bank_forks,
                 &Arc::new(PrioritizationFeeCache::new(0u64)),
+                Arc::default(),
+                Arc::default(),
             );
             drop(non_vote_sender);
             drop(tpu_vote_sender);
@@ -926,6 +956,8 @@ mod tests {
                 Arc::new(ConnectionCache::new("connection_cache_test")),
                 bank_forks,
                 &Arc::new(PrioritizationFeeCache::new(0u64)),
+                Arc::default(),
+                Arc::default(),
             );
             trace!("sending bank");
             drop(non_vote_sender);
@@ -1005,6 +1037,8 @@ mod tests {
                 Arc::new(ConnectionCache::new("connection_cache_test")),
                 bank_forks,
                 &Arc::new(PrioritizationFeeCache::new(0u64)),
+                Arc::default(),
+                Arc::default(),
             );
 
             // fund another account so we can send 2 good transactions in a single batch.
@@ -1175,6 +1209,8 @@ mod tests {
                     Arc::new(ConnectionCache::new("connection_cache_test")),
                     bank_forks,
                     &Arc::new(PrioritizationFeeCache::new(0u64)),
+                    Arc::default(),
+                    Arc::default(),
                 );
 
                 // wait for banking_stage to eat the packets
@@ -1367,6 +1403,8 @@ mod tests {
                 Arc::new(ConnectionCache::new("connection_cache_test")),
                 bank_forks,
                 &Arc::new(PrioritizationFeeCache::new(0u64)),
+                Arc::default(),
+                Arc::default(),
             );
 
             let keypairs = (0..100).map(|_| Keypair::new()).collect_vec();
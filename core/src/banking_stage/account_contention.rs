@@ -0,0 +1,320 @@
+//! Which accounts held up the transactions that banking stage could not
+//! process right away, counted per window of slots.
+
+use {
+    solana_sdk::{
+        clock::Slot,
+        message::{SanitizedMessage, VersionedMessage},
+        pubkey::Pubkey,
+    },
+    std::{collections::HashMap, mem, sync::Mutex},
+};
+
+/// Number of slots counted in one window, about a minute
+pub const DEFAULT_CONTENTION_WINDOW_SLOTS: Slot = 150;
+
+/// Maximum number of accounts counted in one window. Once it is reached, only
+/// the accounts already counted are, so that spam cannot grow the windows.
+pub const MAX_CONTENTION_WINDOW_ACCOUNTS: usize = 100_000;
+
+/// How often the transactions which write an account were held up
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AccountContention {
+    /// Number of transactions which could not be scheduled or executed because
+    /// another transaction held a lock on one of their accounts
+    pub lock_conflicts: u64,
+    /// Number of transactions which were buffered to be retried later
+    pub retries: u64,
+    /// Number of transactions which were forwarded to upcoming leaders
+    pub forwarded: u64,
+}
+
+impl AccountContention {
+    pub fn total(&self) -> u64 {
+        self.lock_conflicts
+            .saturating_add(self.retries)
+            .saturating_add(self.forwarded)
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ContentionWindow {
+    /// First slot of the window
+    pub start_slot: Slot,
+    /// Number of slots of the window
+    pub num_slots: Slot,
+    pub accounts: HashMap<Pubkey, AccountContention>,
+}
+
+impl ContentionWindow {
+    fn new(start_slot: Slot, num_slots: Slot) -> Self {
+        Self {
+            start_slot,
+            num_slots,
+            accounts: HashMap::default(),
+        }
+    }
+
+    /// The `max_accounts` most contended accounts, most contended first
+    pub fn most_contended(&self, max_accounts: usize) -> Vec<(Pubkey, AccountContention)> {
+        let mut accounts: Vec<_> = self
+            .accounts
+            .iter()
+            .map(|(account, contention)| (*account, *contention))
+            .collect();
+        accounts.sort_unstable_by(|(a_account, a), (b_account, b)| {
+            b.total()
+                .cmp(&a.total())
+                .then_with(|| a_account.cmp(b_account))
+        });
+        accounts.truncate(max_accounts);
+        accounts
+    }
+}
+
+#[derive(Debug, Default)]
+struct ContentionWindows {
+    current: ContentionWindow,
+    previous: Option<ContentionWindow>,
+}
+
+/// Counts, per window of slots, how often transactions writing each account
+/// were held up by lock conflicts, retried or forwarded. Only the current and
+/// the previous window are kept.
+#[derive(Debug)]
+pub struct AccountContentionTracker {
+    window_slots: Slot,
+    windows: Mutex<ContentionWindows>,
+}
+
+impl Default for AccountContentionTracker {
+    fn default() -> Self {
+        Self::new(DEFAULT_CONTENTION_WINDOW_SLOTS)
+    }
+}
+
+impl AccountContentionTracker {
+    pub fn new(window_slots: Slot) -> Self {
+        let window_slots = window_slots.max(1);
+        Self {
+            window_slots,
+            windows: Mutex::new(ContentionWindows {
+                current: ContentionWindow::new(0, window_slots),
+                previous: None,
+            }),
+        }
+    }
+
+    pub fn window_slots(&self) -> Slot {
+        self.window_slots
+    }
+
+    /// Count a lock conflict in `slot` for each of the `accounts`
+    pub fn record_lock_conflicts<'a>(
+        &self,
+        slot: Slot,
+        accounts: impl IntoIterator<Item = &'a Pubkey>,
+    ) {
+        self.record(slot, accounts, |contention| {
+            contention.lock_conflicts = contention.lock_conflicts.saturating_add(1)
+        })
+    }
+
+    /// Count a retry in `slot` for each of the `accounts`
+    pub fn record_retries<'a>(&self, slot: Slot, accounts: impl IntoIterator<Item = &'a Pubkey>) {
+        self.record(slot, accounts, |contention| {
+            contention.retries = contention.retries.saturating_add(1)
+        })
+    }
+
+    /// Count a forwarded transaction in `slot` for each of the `accounts`
+    pub fn record_forwarded<'a>(&self, slot: Slot, accounts: impl IntoIterator<Item = &'a Pubkey>) {
+        self.record(slot, accounts, |contention| {
+            contention.forwarded = contention.forwarded.saturating_add(1)
+        })
+    }
+
+    /// The current window, followed by the previous one if it was counted
+    pub fn windows(&self) -> Vec<ContentionWindow> {
+        let windows = self.windows.lock().unwrap();
+        std::iter::once(windows.current.clone())
+            .chain(windows.previous.clone())
+            .collect()
+    }
+
+    fn record<'a>(
+        &self,
+        slot: Slot,
+        accounts: impl IntoIterator<Item = &'a Pubkey>,
+        count: impl Fn(&mut AccountContention),
+    ) {
+        let mut accounts = accounts.into_iter().peekable();
+        if accounts.peek().is_none() {
+            return;
+        }
+
+        let mut windows = self.windows.lock().unwrap();
+        let Some(window) = Self::window_for_slot(self.window_slots, &mut windows, slot) else {
+            return;
+        };
+        for account in accounts {
+            if window.accounts.len() < MAX_CONTENTION_WINDOW_ACCOUNTS
+                || window.accounts.contains_key(account)
+            {
+                count(window.accounts.entry(*account).or_default());
+            }
+        }
+    }
+
+    /// The window `slot` is counted in, after moving on to it if `slot` is
+    /// past the current window. `None` if `slot` is older than the windows kept.
+    fn window_for_slot(
+        window_slots: Slot,
+        windows: &mut ContentionWindows,
+        slot: Slot,
+    ) -> Option<&mut ContentionWindow> {
+        let start_slot = slot.saturating_sub(slot % window_slots);
+        let current_start_slot = windows.current.start_slot;
+        if start_slot > current_start_slot {
+            let current = mem::replace(
+                &mut windows.current,
+                ContentionWindow::new(start_slot, window_slots),
+            );
+            // a window without any events in between is not kept
+            windows.previous =
+                (current.start_slot.saturating_add(window_slots) == start_slot).then_some(current);
+        }
+
+        if start_slot == windows.current.start_slot {
+            Some(&mut windows.current)
+        } else {
+            windows
+                .previous
+                .as_mut()
+                .filter(|previous| previous.start_slot == start_slot)
+        }
+    }
+}
+
+/// The accounts which `message` write locks
+pub(crate) fn writable_accounts(message: &SanitizedMessage) -> impl Iterator<Item = &Pubkey> {
+    message
+        .account_keys()
+        .iter()
+        .enumerate()
+        .filter(move |(index, _)| message.is_writable(*index))
+        .map(|(_, account)| account)
+}
+
+/// The static accounts which `message` may write lock, before resolving its
+/// address lookup tables
+pub(crate) fn maybe_writable_static_accounts(
+    message: &VersionedMessage,
+) -> impl Iterator<Item = &Pubkey> {
+    message
+        .static_account_keys()
+        .iter()
+        .enumerate()
+        .filter(move |(index, _)| message.is_maybe_writable(*index))
+        .map(|(_, account)| account)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record() {
+        let tracker = AccountContentionTracker::new(10);
+        let hot = Pubkey::new_unique();
+        let warm = Pubkey::new_unique();
+        assert_eq!(tracker.windows(), vec![ContentionWindow::new(0, 10)]);
+
+        tracker.record_lock_conflicts(3, [&hot, &warm]);
+        tracker.record_lock_conflicts(4, [&hot]);
+        tracker.record_retries(9, [&hot]);
+        tracker.record_forwarded(5, [&warm]);
+        tracker.record_retries(5, &[] as &[Pubkey]);
+
+        let windows = tracker.windows();
+        assert_eq!(windows.len(), 1);
+        assert_eq!(windows[0].start_slot, 0);
+        assert_eq!(
+            windows[0].most_contended(usize::MAX),
+            vec![
+                (
+                    hot,
+                    AccountContention {
+                        lock_conflicts: 2,
+                        retries: 1,
+                        forwarded: 0,
+                    }
+                ),
+                (
+                    warm,
+                    AccountContention {
+                        lock_conflicts: 1,
+                        retries: 0,
+                        forwarded: 1,
+                    }
+                ),
+            ]
+        );
+        assert_eq!(windows[0].most_contended(1).len(), 1);
+        assert_eq!(windows[0].most_contended(1)[0].0, hot);
+    }
+
+    #[test]
+    fn test_windows() {
+        let tracker = AccountContentionTracker::new(10);
+        let account = Pubkey::new_unique();
+
+        tracker.record_retries(5, [&account]);
+        tracker.record_retries(12, [&account]);
+        tracker.record_retries(12, [&account]);
+        // late events are counted in the previous window
+        tracker.record_retries(9, [&account]);
+        let windows = tracker.windows();
+        assert_eq!(
+            windows
+                .iter()
+                .map(|window| (window.start_slot, window.accounts[&account].retries))
+                .collect::<Vec<_>>(),
+            vec![(10, 2), (0, 2)]
+        );
+
+        // events older than the previous window are dropped
+        tracker.record_retries(21, [&account]);
+        tracker.record_retries(2, [&account]);
+        let windows = tracker.windows();
+        assert_eq!(
+            windows
+                .iter()
+                .map(|window| (window.start_slot, window.accounts[&account].retries))
+                .collect::<Vec<_>>(),
+            vec![(20, 1), (10, 2)]
+        );
+
+        // the previous window is dropped when windows are skipped
+        tracker.record_retries(45, [&account]);
+        let windows = tracker.windows();
+        assert_eq!(windows.len(), 1);
+        assert_eq!(windows[0].start_slot, 40);
+    }
+
+    #[test]
+    fn test_max_window_accounts() {
+        let tracker = AccountContentionTracker::default();
+        let accounts: Vec<_> = (0..MAX_CONTENTION_WINDOW_ACCOUNTS)
+            .map(|_| Pubkey::new_unique())
+            .collect();
+        tracker.record_lock_conflicts(0, &accounts);
+
+        let new_account = Pubkey::new_unique();
+        tracker.record_lock_conflicts(0, [&new_account, &accounts[0]]);
+        let windows = tracker.windows();
+        assert_eq!(windows[0].accounts.len(), MAX_CONTENTION_WINDOW_ACCOUNTS);
+        assert!(!windows[0].accounts.contains_key(&new_account));
+        assert_eq!(windows[0].accounts[&accounts[0]].lock_conflicts, 2);
+    }
+}
@@ -6,7 +6,9 @@ use {
         transaction_results::{TransactionExecutionResult, TransactionResults},
     },
     solana_ledger::{
-        blockstore_processor::TransactionStatusSender, token_balances::collect_token_balances,
+        blockstore_processor::TransactionStatusSender,
+        stake_delegations::collect_stake_delegations,
+        token_balances::{collect_token_balances, TokenMintInfo},
     },
     solana_measure::measure_us,
     solana_runtime::{
@@ -17,7 +19,9 @@ use {
     },
     solana_sdk::{hash::Hash, pubkey::Pubkey, saturating_add_assign},
     solana_transaction_status::{
-        token_balances::TransactionTokenBalancesSet, TransactionTokenBalance,
+        stake_changes::{TransactionStakeDelegation, TransactionStakeDelegationsSet},
+        token_balances::TransactionTokenBalancesSet,
+        TransactionTokenBalance,
     },
     solana_vote::vote_sender_types::ReplayVoteSender,
     std::{collections::HashMap, sync::Arc},
@@ -33,7 +37,8 @@ pub enum CommitTransactionDetails {
 pub(super) struct PreBalanceInfo {
     pub native: Vec<Vec<u64>>,
     pub token: Vec<Vec<TransactionTokenBalance>>,
-    pub mint_decimals: HashMap<Pubkey, u8>,
+    pub mint_infos: HashMap<Pubkey, TokenMintInfo>,
+    pub stake: Vec<Vec<TransactionStakeDelegation>>,
 }
 
 #[derive(Clone)]
@@ -144,7 +149,8 @@ impl Committer {
             let txs = batch.sanitized_transactions().to_vec();
             let post_balances = bank.collect_balances(batch);
             let post_token_balances =
-                collect_token_balances(bank, batch, &mut pre_balance_info.mint_decimals);
+                collect_token_balances(bank, batch, &mut pre_balance_info.mint_infos);
+            let post_stake_delegations = collect_stake_delegations(bank, batch);
             let mut transaction_index = starting_transaction_index.unwrap_or_default();
             let batch_transaction_indexes: Vec<_> = tx_results
                 .execution_results
@@ -171,6 +177,10 @@ impl Committer {
                     std::mem::take(&mut pre_balance_info.token),
                     post_token_balances,
                 ),
+                TransactionStakeDelegationsSet::new(
+                    std::mem::take(&mut pre_balance_info.stake),
+                    post_stake_delegations,
+                ),
                 tx_results.rent_debits,
                 batch_transaction_indexes,
             );
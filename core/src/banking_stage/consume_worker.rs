@@ -309,6 +309,7 @@ impl ConsumeWorkerMetrics {
             would_exceed_account_data_block_limit,
             max_loaded_accounts_data_size_exceeded,
             program_execution_temporarily_restricted,
+            max_slot_exceeded,
         }: &TransactionErrorMetrics,
     ) {
         self.error_metrics
@@ -380,6 +381,9 @@ impl ConsumeWorkerMetrics {
         self.error_metrics
             .program_execution_temporarily_restricted
             .fetch_add(*program_execution_temporarily_restricted, Ordering::Relaxed);
+        self.error_metrics
+            .max_slot_exceeded
+            .fetch_add(*max_slot_exceeded, Ordering::Relaxed);
     }
 }
 
@@ -540,6 +544,7 @@ struct ConsumeWorkerTransactionErrorMetrics {
     would_exceed_account_data_block_limit: AtomicUsize,
     max_loaded_accounts_data_size_exceeded: AtomicUsize,
     program_execution_temporarily_restricted: AtomicUsize,
+    max_slot_exceeded: AtomicUsize,
 }
 
 impl ConsumeWorkerTransactionErrorMetrics {
@@ -648,6 +653,11 @@ impl ConsumeWorkerTransactionErrorMetrics {
                     .swap(0, Ordering::Relaxed),
                 i64
             ),
+            (
+                "max_slot_exceeded",
+                self.max_slot_exceeded.swap(0, Ordering::Relaxed),
+                i64
+            ),
         );
     }
 }
@@ -730,7 +740,13 @@ mod tests {
             replay_vote_sender,
             Arc::new(PrioritizationFeeCache::new(0u64)),
         );
-        let consumer = Consumer::new(committer, recorder, QosService::new(1), None);
+        let consumer = Consumer::new(
+            committer,
+            recorder,
+            QosService::new(1),
+            None,
+            Arc::default(),
+        );
 
         let (consume_sender, consume_receiver) = unbounded();
         let (consumed_sender, consumed_receiver) = unbounded();
@@ -76,6 +76,12 @@ impl ForwardBatch {
             .map(|immutable_packet| immutable_packet.original_packet())
     }
 
+    pub fn get_forwardable_immutable_packets(
+        &self,
+    ) -> impl Iterator<Item = &Arc<ImmutableDeserializedPacket>> {
+        self.forwardable_packets.iter()
+    }
+
     pub fn len(&self) -> usize {
         self.forwardable_packets.len()
     }
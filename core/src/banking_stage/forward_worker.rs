@@ -156,6 +156,8 @@ mod tests {
             cluster_info,
             Arc::new(ConnectionCache::new("test")),
             Arc::default(),
+            Arc::default(),
+            Arc::default(),
         );
 
         let (forward_sender, forward_receiver) = unbounded();
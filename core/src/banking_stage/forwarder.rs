@@ -1,14 +1,17 @@
 use {
     super::{
+        account_contention::{maybe_writable_static_accounts, AccountContentionTracker},
         forward_packet_batches_by_accounts::ForwardPacketBatchesByAccounts,
+        forwarding_policy::{ForwardingPolicy, ForwardingPolicyMetrics},
         leader_slot_metrics::LeaderSlotMetricsTracker,
-        unprocessed_transaction_storage::UnprocessedTransactionStorage, BankingStageStats,
-        ForwardOption,
+        unprocessed_transaction_storage::UnprocessedTransactionStorage,
+        BankingStageStats, ForwardOption,
     },
     crate::{
-        next_leader::{next_leader, next_leader_tpu_vote},
+        next_leader::{next_leader_tpu_vote, upcoming_leaders},
         tracer_packet_stats::TracerPacketStats,
     },
+    rand::thread_rng,
     solana_client::{connection_cache::ConnectionCache, tpu_connection::TpuConnection},
     solana_gossip::cluster_info::ClusterInfo,
     solana_measure::measure_us,
@@ -31,6 +34,9 @@ pub(crate) struct Forwarder {
     cluster_info: Arc<ClusterInfo>,
     connection_cache: Arc<ConnectionCache>,
     data_budget: Arc<DataBudget>,
+    forwarding_policy: Arc<RwLock<ForwardingPolicy>>,
+    forwarding_policy_metrics: ForwardingPolicyMetrics,
+    account_contention: Arc<AccountContentionTracker>,
 }
 
 impl Forwarder {
@@ -40,6 +46,8 @@ impl Forwarder {
         cluster_info: Arc<ClusterInfo>,
         connection_cache: Arc<ConnectionCache>,
         data_budget: Arc<DataBudget>,
+        forwarding_policy: Arc<RwLock<ForwardingPolicy>>,
+        account_contention: Arc<AccountContentionTracker>,
     ) -> Self {
         Self {
             poh_recorder,
@@ -48,6 +56,9 @@ impl Forwarder {
             cluster_info,
             connection_cache,
             data_budget,
+            forwarding_policy,
+            forwarding_policy_metrics: ForwardingPolicyMetrics::default(),
+            account_contention,
         }
     }
 
@@ -63,7 +74,10 @@ impl Forwarder {
 
         // get current root bank from bank_forks, use it to sanitize transaction and
         // load all accounts from address loader;
-        let current_bank = self.bank_forks.read().unwrap().root_bank();
+        let (current_bank, highest_slot) = {
+            let bank_forks = self.bank_forks.read().unwrap();
+            (bank_forks.root_bank(), bank_forks.highest_slot())
+        };
 
         let mut forward_packet_batches_by_accounts =
             ForwardPacketBatchesByAccounts::new_with_default_batch_limits();
@@ -127,6 +141,19 @@ impl Forwarder {
                     slot_metrics_tracker.increment_successful_forwarded_packets_count(
                         sucessful_forwarded_packets_count as u64,
                     );
+                    if let ForwardOption::ForwardTransaction = forward_option {
+                        self.account_contention.record_forwarded(
+                            highest_slot,
+                            forward_batch
+                                .get_forwardable_immutable_packets()
+                                .filter(|packet| !packet.original_packet().meta().forwarded())
+                                .flat_map(|packet| {
+                                    maybe_writable_static_accounts(
+                                        &packet.transaction().get_message().message,
+                                    )
+                                }),
+                        );
+                    }
                 }
             });
 
@@ -141,9 +168,10 @@ impl Forwarder {
         }
     }
 
-    /// Forwards all valid, unprocessed packets in the iterator, up to a rate limit.
+    /// Forwards all valid, unprocessed packets in the iterator, up to a rate limit,
+    /// to each of the leaders selected for the `forward_option`.
     /// Returns whether forwarding succeeded, the number of attempted forwarded packets
-    /// if any, the time spent forwarding in us, and the leader pubkey if any.
+    /// if any, the time spent forwarding in us, and the first leader pubkey if any.
     pub(crate) fn forward_packets<'a>(
         &self,
         forward_option: &ForwardOption,
@@ -154,14 +182,26 @@ impl Forwarder {
         u64,
         Option<Pubkey>,
     ) {
-        let Some((leader_pubkey, addr)) = self.get_leader_and_addr(forward_option) else {
+        let forwarding_policy = *self.forwarding_policy.read().unwrap();
+        let leaders_and_addrs = self.get_leaders_and_addrs(forward_option, &forwarding_policy);
+        let Some((leader_pubkey, _)) = leaders_and_addrs.first().copied() else {
+            if let ForwardOption::ForwardTransaction = forward_option {
+                self.forwarding_policy_metrics
+                    .increment_not_forwarded(forwardable_packets.count());
+                self.forwarding_policy_metrics
+                    .maybe_report(&forwarding_policy);
+            }
             return (Ok(()), 0, 0, None);
         };
 
         self.update_data_budget();
+        let num_leaders = leaders_and_addrs.len();
         let packet_vec: Vec<_> = forwardable_packets
             .filter(|p| !p.meta().forwarded())
-            .filter(|p| self.data_budget.take(p.meta().size))
+            .filter(|p| {
+                self.data_budget
+                    .take(p.meta().size.saturating_mul(num_leaders))
+            })
             .filter_map(|p| p.data(..).map(|data| data.to_vec()))
             .collect();
 
@@ -171,11 +211,21 @@ impl Forwarder {
         // when there's an error sending the batch. This was left as-is for now
         // in favor of shipping Quic support, which was considered higher-priority
         let (res, forward_us) = if !packet_vec.is_empty() {
-            measure_us!(self.forward(forward_option, packet_vec, &addr))
+            measure_us!(leaders_and_addrs
+                .iter()
+                .map(|(_, addr)| self.forward(forward_option, packet_vec.clone(), addr))
+                .fold(Ok(()), Result::and))
         } else {
             (Ok(()), 0)
         };
 
+        if let ForwardOption::ForwardTransaction = forward_option {
+            self.forwarding_policy_metrics
+                .increment_forwarded(packet_vec_len, num_leaders);
+            self.forwarding_policy_metrics
+                .maybe_report(&forwarding_policy);
+        }
+
         (res, packet_vec_len, forward_us, Some(leader_pubkey))
     }
 
@@ -216,17 +266,45 @@ impl Forwarder {
         (res, num_packets, leader_pubkey)
     }
 
-    /// Get the pubkey and socket address for the leader to forward to
-    fn get_leader_and_addr(&self, forward_option: &ForwardOption) -> Option<(Pubkey, SocketAddr)> {
+    /// Get the pubkeys and socket addresses of the leaders to forward to.
+    /// Votes are always forwarded to the next leader, transactions to the
+    /// leaders selected by the `forwarding_policy`.
+    fn get_leaders_and_addrs(
+        &self,
+        forward_option: &ForwardOption,
+        forwarding_policy: &ForwardingPolicy,
+    ) -> Vec<(Pubkey, SocketAddr)> {
         match forward_option {
-            ForwardOption::NotForward => None,
+            ForwardOption::NotForward => vec![],
             ForwardOption::ForwardTransaction => {
-                next_leader(&self.cluster_info, &self.poh_recorder, |node| {
-                    node.tpu_forwards(self.connection_cache.protocol())
-                })
+                if let ForwardingPolicy::Disabled = forwarding_policy {
+                    return vec![];
+                }
+                let upcoming_leaders =
+                    upcoming_leaders(&self.poh_recorder, forwarding_policy.num_leaders());
+                let staked_nodes = match forwarding_policy {
+                    ForwardingPolicy::StakeWeighted(_) => {
+                        self.bank_forks.read().unwrap().root_bank().staked_nodes()
+                    }
+                    _ => Arc::default(),
+                };
+                forwarding_policy
+                    .select_leaders(&upcoming_leaders, &staked_nodes, &mut thread_rng())
+                    .into_iter()
+                    .filter_map(|leader_pubkey| {
+                        self.cluster_info
+                            .lookup_contact_info(&leader_pubkey, |node| {
+                                node.tpu_forwards(self.connection_cache.protocol())
+                            })?
+                            .map(|addr| (leader_pubkey, addr))
+                            .ok()
+                    })
+                    .collect()
             }
             ForwardOption::ForwardTpuVote => {
                 next_leader_tpu_vote(&self.cluster_info, &self.poh_recorder)
+                    .into_iter()
+                    .collect()
             }
         }
     }
@@ -372,6 +450,8 @@ mod tests {
                 cluster_info.clone(),
                 Arc::new(ConnectionCache::new("connection_cache_test")),
                 Arc::new(data_budget),
+                Arc::default(),
+                Arc::default(),
             );
             let unprocessed_packet_batches: UnprocessedPacketBatches =
                 UnprocessedPacketBatches::from_iter(
@@ -450,12 +530,15 @@ mod tests {
             ("fwd-no-hold", false, vec![], 0),
         ];
 
+        let account_contention = Arc::<AccountContentionTracker>::default();
         let forwarder = Forwarder::new(
             poh_recorder,
             bank_forks,
             cluster_info,
             Arc::new(connection_cache),
             Arc::new(DataBudget::default()),
+            Arc::default(),
+            account_contention.clone(),
         );
         for (name, hold, expected_ids, expected_num_unprocessed) in test_cases {
             let stats = BankingStageStats::default();
@@ -490,6 +573,12 @@ mod tests {
             assert_eq!(num_unprocessed_packets, expected_num_unprocessed, "{name}");
         }
 
+        // only the packet which was not forwarded before is counted
+        let windows = account_contention.windows();
+        for account in [keypair.pubkey(), pubkey] {
+            assert_eq!(windows[0].accounts[&account].forwarded, 1);
+        }
+
         exit.store(true, Ordering::Relaxed);
         poh_service.join().unwrap();
     }
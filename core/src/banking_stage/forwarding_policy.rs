@@ -0,0 +1,257 @@
+//! How a node that is not the leader forwards the transactions it receives.
+
+use {
+    rand::{distributions::WeightedIndex, prelude::Distribution, Rng},
+    solana_sdk::{pubkey::Pubkey, timing::AtomicInterval},
+    std::{
+        collections::HashMap,
+        fmt,
+        str::FromStr,
+        sync::atomic::{AtomicUsize, Ordering},
+    },
+};
+
+/// Number of upcoming leaders considered by `stake-weighted` when none is given
+pub const DEFAULT_STAKE_WEIGHTED_LEADERS: usize = 4;
+
+/// Maximum number of upcoming leaders a policy may consider
+pub const MAX_FORWARDING_LEADERS: usize = 16;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ForwardingPolicy {
+    /// Forward to each of the next `n` distinct leaders
+    NextLeaders(usize),
+    /// Forward to one of the next `n` distinct leaders, selected at random in
+    /// proportion to its stake
+    StakeWeighted(usize),
+    /// Do not forward transactions
+    Disabled,
+}
+
+impl Default for ForwardingPolicy {
+    fn default() -> Self {
+        Self::NextLeaders(1)
+    }
+}
+
+impl ForwardingPolicy {
+    pub const NAMES: &'static [&'static str] = &["next-leaders", "stake-weighted", "disabled"];
+
+    /// Name of the policy, as used in metrics
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::NextLeaders(_) => "next_leaders",
+            Self::StakeWeighted(_) => "stake_weighted",
+            Self::Disabled => "disabled",
+        }
+    }
+
+    /// Number of upcoming leaders the policy selects from
+    pub fn num_leaders(&self) -> usize {
+        match self {
+            Self::NextLeaders(n) | Self::StakeWeighted(n) => *n,
+            Self::Disabled => 0,
+        }
+    }
+
+    /// Select the leaders to forward to from the `upcoming_leaders`, which are
+    /// expected to be distinct and in leader schedule order.
+    pub fn select_leaders<R: Rng>(
+        &self,
+        upcoming_leaders: &[Pubkey],
+        staked_nodes: &HashMap<Pubkey, u64>,
+        rng: &mut R,
+    ) -> Vec<Pubkey> {
+        let upcoming_leaders = &upcoming_leaders[..self.num_leaders().min(upcoming_leaders.len())];
+        match self {
+            Self::NextLeaders(_) => upcoming_leaders.to_vec(),
+            Self::StakeWeighted(_) => {
+                let stakes = upcoming_leaders
+                    .iter()
+                    .map(|leader| staked_nodes.get(leader).copied().unwrap_or_default());
+                match WeightedIndex::new(stakes) {
+                    Ok(weighted_index) => vec![upcoming_leaders[weighted_index.sample(rng)]],
+                    // None of the leaders are staked, fall back to the next one
+                    Err(_) => upcoming_leaders.first().copied().into_iter().collect(),
+                }
+            }
+            Self::Disabled => vec![],
+        }
+    }
+}
+
+impl FromStr for ForwardingPolicy {
+    type Err = String;
+
+    /// Parses `next-leaders[:N]`, `stake-weighted[:N]` or `disabled`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, num_leaders) = match s.split_once(':') {
+            Some((name, num_leaders)) => {
+                let num_leaders = num_leaders
+                    .parse::<usize>()
+                    .map_err(|err| format!("invalid number of leaders `{num_leaders}`: {err}"))?;
+                if num_leaders == 0 || num_leaders > MAX_FORWARDING_LEADERS {
+                    return Err(format!(
+                        "number of leaders must be between 1 and {MAX_FORWARDING_LEADERS}"
+                    ));
+                }
+                (name, Some(num_leaders))
+            }
+            None => (s, None),
+        };
+        match (name, num_leaders) {
+            ("next-leaders", num_leaders) => Ok(Self::NextLeaders(num_leaders.unwrap_or(1))),
+            ("stake-weighted", num_leaders) => Ok(Self::StakeWeighted(
+                num_leaders.unwrap_or(DEFAULT_STAKE_WEIGHTED_LEADERS),
+            )),
+            ("disabled", None) => Ok(Self::Disabled),
+            _ => Err(format!("unknown forwarding policy `{s}`")),
+        }
+    }
+}
+
+impl fmt::Display for ForwardingPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::NextLeaders(n) => write!(f, "next-leaders:{n}"),
+            Self::StakeWeighted(n) => write!(f, "stake-weighted:{n}"),
+            Self::Disabled => write!(f, "disabled"),
+        }
+    }
+}
+
+/// Counts of the transactions forwarded under the active `ForwardingPolicy`
+#[derive(Debug, Default)]
+pub(crate) struct ForwardingPolicyMetrics {
+    last_report: AtomicInterval,
+    /// Number of packets sent, counting each leader a packet was sent to
+    forwarded_packets_count: AtomicUsize,
+    /// Number of leaders forwarded to
+    forwarded_leaders_count: AtomicUsize,
+    /// Number of packets not forwarded because no leader was selected
+    not_forwarded_packets_count: AtomicUsize,
+}
+
+impl ForwardingPolicyMetrics {
+    pub(crate) fn increment_forwarded(&self, num_packets: usize, num_leaders: usize) {
+        self.forwarded_packets_count
+            .fetch_add(num_packets.saturating_mul(num_leaders), Ordering::Relaxed);
+        self.forwarded_leaders_count
+            .fetch_add(num_leaders, Ordering::Relaxed);
+    }
+
+    pub(crate) fn increment_not_forwarded(&self, num_packets: usize) {
+        self.not_forwarded_packets_count
+            .fetch_add(num_packets, Ordering::Relaxed);
+    }
+
+    pub(crate) fn maybe_report(&self, policy: &ForwardingPolicy) {
+        const REPORT_INTERVAL_MS: u64 = 1000;
+        if self.last_report.should_update(REPORT_INTERVAL_MS) {
+            datapoint_info!(
+                "banking_stage-forwarding_policy",
+                "policy" => policy.name(),
+                ("num_leaders", policy.num_leaders(), i64),
+                (
+                    "forwarded_packets_count",
+                    self.forwarded_packets_count.swap(0, Ordering::Relaxed),
+                    i64
+                ),
+                (
+                    "forwarded_leaders_count",
+                    self.forwarded_leaders_count.swap(0, Ordering::Relaxed),
+                    i64
+                ),
+                (
+                    "not_forwarded_packets_count",
+                    self.not_forwarded_packets_count.swap(0, Ordering::Relaxed),
+                    i64
+                )
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, rand::thread_rng};
+
+    #[test]
+    fn test_forwarding_policy_from_str() {
+        assert_eq!(
+            "next-leaders".parse::<ForwardingPolicy>(),
+            Ok(ForwardingPolicy::NextLeaders(1))
+        );
+        assert_eq!(
+            "next-leaders:3".parse::<ForwardingPolicy>(),
+            Ok(ForwardingPolicy::NextLeaders(3))
+        );
+        assert_eq!(
+            "stake-weighted".parse::<ForwardingPolicy>(),
+            Ok(ForwardingPolicy::StakeWeighted(
+                DEFAULT_STAKE_WEIGHTED_LEADERS
+            ))
+        );
+        assert_eq!(
+            "disabled".parse::<ForwardingPolicy>(),
+            Ok(ForwardingPolicy::Disabled)
+        );
+        assert!("next-leaders:0".parse::<ForwardingPolicy>().is_err());
+        assert!("next-leaders:17".parse::<ForwardingPolicy>().is_err());
+        assert!("next-leaders:x".parse::<ForwardingPolicy>().is_err());
+        assert!("disabled:1".parse::<ForwardingPolicy>().is_err());
+        assert!("everyone".parse::<ForwardingPolicy>().is_err());
+
+        for policy in [
+            ForwardingPolicy::NextLeaders(2),
+            ForwardingPolicy::StakeWeighted(4),
+            ForwardingPolicy::Disabled,
+        ] {
+            assert_eq!(policy.to_string().parse::<ForwardingPolicy>(), Ok(policy));
+        }
+    }
+
+    #[test]
+    fn test_select_leaders() {
+        let mut rng = thread_rng();
+        let leaders: Vec<_> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        let mut staked_nodes = HashMap::new();
+
+        assert_eq!(
+            ForwardingPolicy::NextLeaders(2).select_leaders(&leaders, &staked_nodes, &mut rng),
+            leaders[..2]
+        );
+        assert_eq!(
+            ForwardingPolicy::NextLeaders(4).select_leaders(&leaders, &staked_nodes, &mut rng),
+            leaders
+        );
+        assert!(ForwardingPolicy::Disabled
+            .select_leaders(&leaders, &staked_nodes, &mut rng)
+            .is_empty());
+
+        // Without stake, fall back to the next leader
+        assert_eq!(
+            ForwardingPolicy::StakeWeighted(3).select_leaders(&leaders, &staked_nodes, &mut rng),
+            [leaders[0]]
+        );
+        // Only the staked leader is ever selected
+        staked_nodes.insert(leaders[1], 100);
+        for _ in 0..10 {
+            assert_eq!(
+                ForwardingPolicy::StakeWeighted(3).select_leaders(
+                    &leaders,
+                    &staked_nodes,
+                    &mut rng
+                ),
+                [leaders[1]]
+            );
+        }
+        // Leaders past the policy's window are never selected
+        staked_nodes.insert(leaders[2], 100);
+        staked_nodes.remove(&leaders[1]);
+        assert_eq!(
+            ForwardingPolicy::StakeWeighted(2).select_leaders(&leaders, &staked_nodes, &mut rng),
+            [leaders[0]]
+        );
+    }
+}
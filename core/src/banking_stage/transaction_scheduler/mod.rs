@@ -8,6 +8,6 @@ mod thread_aware_account_locks;
 mod transaction_id_generator;
 mod transaction_priority_id;
 #[allow(dead_code)]
-mod transaction_state;
+pub(crate) mod transaction_state;
 #[allow(dead_code)]
-mod transaction_state_container;
+pub(crate) mod transaction_state_container;
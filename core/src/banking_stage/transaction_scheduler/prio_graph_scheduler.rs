@@ -139,6 +139,9 @@ impl PrioGraphScheduler {
         let mut num_scheduled: usize = 0;
         let mut num_sent: usize = 0;
         let mut num_unschedulable: usize = 0;
+        let mut num_unschedulable_lock_conflicts: usize = 0;
+        let mut lock_conflict_accounts = Vec::new();
+        let mut num_scheduled_per_thread = vec![0; num_threads];
         while num_scheduled < MAX_TRANSACTIONS_PER_SCHEDULING_PASS {
             // If nothing is in the main-queue of the `PrioGraph` then there's nothing left to schedule.
             if prio_graph.is_empty() {
@@ -171,8 +174,8 @@ impl PrioGraphScheduler {
                 // Schedule the transaction if it can be.
                 let transaction_locks = transaction.get_account_locks_unchecked();
                 let Some(thread_id) = self.account_locks.try_lock_accounts(
-                    transaction_locks.writable.into_iter(),
-                    transaction_locks.readonly.into_iter(),
+                    transaction_locks.writable.iter().copied(),
+                    transaction_locks.readonly.iter().copied(),
                     ThreadSet::any(num_threads),
                     |thread_set| {
                         Self::select_thread(
@@ -185,10 +188,14 @@ impl PrioGraphScheduler {
                     blocking_locks.take_locks(transaction.message());
                     unschedulable_ids.push(id);
                     saturating_add_assign!(num_unschedulable, 1);
+                    saturating_add_assign!(num_unschedulable_lock_conflicts, 1);
+                    lock_conflict_accounts
+                        .extend(transaction_locks.writable.iter().map(|account| **account));
                     continue;
                 };
 
                 saturating_add_assign!(num_scheduled, 1);
+                saturating_add_assign!(num_scheduled_per_thread[thread_id], 1);
 
                 let sanitized_transaction_ttl = transaction_state.transition_to_pending();
                 let cost = transaction_state.cost();
@@ -247,11 +254,24 @@ impl PrioGraphScheduler {
         Ok(SchedulingSummary {
             num_scheduled,
             num_unschedulable,
+            num_unschedulable_lock_conflicts,
+            lock_conflict_accounts,
             num_filtered_out,
+            num_scheduled_per_thread,
             filter_time_us: total_filter_time_us,
         })
     }
 
+    /// Returns the number of transactions that are in flight for each thread.
+    pub(crate) fn num_in_flight_per_thread(&self) -> &[usize] {
+        self.in_flight_tracker.num_in_flight_per_thread()
+    }
+
+    /// Returns the number of cus that are in flight for each thread.
+    pub(crate) fn cus_in_flight_per_thread(&self) -> &[u64] {
+        self.in_flight_tracker.cus_in_flight_per_thread()
+    }
+
     /// Receive completed batches of transactions without blocking.
     /// Returns (num_transactions, num_retryable_transactions) on success.
     pub fn receive_completed(
@@ -433,10 +453,19 @@ pub(crate) struct SchedulingSummary {
     pub num_scheduled: usize,
     /// Number of transactions that were not scheduled due to conflicts.
     pub num_unschedulable: usize,
+    /// Number of the unschedulable transactions whose account locks are held
+    /// by more than one thread, rather than blocked behind another
+    /// unschedulable transaction.
+    pub num_unschedulable_lock_conflicts: usize,
+    /// Writable accounts of the transactions counted in
+    /// `num_unschedulable_lock_conflicts`, once per transaction.
+    pub lock_conflict_accounts: Vec<Pubkey>,
     /// Number of transactions that were dropped due to filter.
     pub num_filtered_out: usize,
     /// Time spent filtering transactions
     pub filter_time_us: u64,
+    /// Number of transactions scheduled onto each thread.
+    pub num_scheduled_per_thread: Vec<usize>,
 }
 
 struct Batches {
@@ -689,6 +718,7 @@ mod tests {
             .unwrap();
         assert_eq!(scheduling_summary.num_scheduled, 4);
         assert_eq!(scheduling_summary.num_unschedulable, 0);
+        assert_eq!(scheduling_summary.num_scheduled_per_thread, [2, 2]);
         assert_eq!(collect_work(&work_receivers[0]).1, [txids!([3, 1])]);
         assert_eq!(collect_work(&work_receivers[1]).1, [txids!([2, 0])]);
     }
@@ -730,6 +760,14 @@ mod tests {
             .unwrap();
         assert_eq!(scheduling_summary.num_scheduled, 4);
         assert_eq!(scheduling_summary.num_unschedulable, 2);
+        // [4] is blocked by locks held on both threads, [5] is blocked behind [4]
+        assert_eq!(scheduling_summary.num_unschedulable_lock_conflicts, 1);
+        assert_eq!(
+            scheduling_summary.lock_conflict_accounts,
+            [accounts[1].pubkey(), accounts[2].pubkey()]
+        );
+        assert_eq!(scheduling_summary.num_scheduled_per_thread, [2, 2]);
+        assert_eq!(scheduler.num_in_flight_per_thread(), [2, 2]);
         let (thread_0_work, thread_0_ids) = collect_work(&work_receivers[0]);
         assert_eq!(thread_0_ids, [txids!([0]), txids!([2])]);
         assert_eq!(
@@ -757,6 +795,8 @@ mod tests {
             .unwrap();
         assert_eq!(scheduling_summary.num_scheduled, 2);
         assert_eq!(scheduling_summary.num_unschedulable, 0);
+        assert_eq!(scheduling_summary.num_scheduled_per_thread, [0, 2]);
+        assert_eq!(scheduler.num_in_flight_per_thread(), [1, 4]);
 
         assert_eq!(
             collect_work(&work_receivers[1]).1,
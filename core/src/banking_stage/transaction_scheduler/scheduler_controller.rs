@@ -9,6 +9,7 @@ use {
         transaction_state_container::TransactionStateContainer,
     },
     crate::banking_stage::{
+        account_contention::AccountContentionTracker,
         consume_worker::ConsumeWorkerMetrics,
         consumer::Consumer,
         decision_maker::{BufferedPacketsDecision, DecisionMaker},
@@ -24,8 +25,11 @@ use {
     solana_runtime::{bank::Bank, bank_forks::BankForks},
     solana_sdk::{
         clock::MAX_PROCESSING_AGE,
-        feature_set::include_loaded_accounts_data_size_in_fee_calculation, fee::FeeBudgetLimits,
-        saturating_add_assign, timing::AtomicInterval, transaction::SanitizedTransaction,
+        feature_set::{include_loaded_accounts_data_size_in_fee_calculation, FeatureSet},
+        fee::{FeeBudgetLimits, FeeStructure},
+        saturating_add_assign,
+        timing::AtomicInterval,
+        transaction::SanitizedTransaction,
     },
     solana_svm::transaction_error_metrics::TransactionErrorMetrics,
     std::{
@@ -52,8 +56,12 @@ pub(crate) struct SchedulerController {
     count_metrics: SchedulerCountMetrics,
     /// Metrics tracking time spent in different code sections.
     timing_metrics: SchedulerTimingMetrics,
+    /// Metrics tracking the queues of each worker thread.
+    thread_metrics: SchedulerThreadMetrics,
     /// Metric report handles for the worker threads.
     worker_metrics: Vec<Arc<ConsumeWorkerMetrics>>,
+    /// Counts the accounts of transactions held up by lock conflicts.
+    account_contention: Arc<AccountContentionTracker>,
 }
 
 impl SchedulerController {
@@ -63,6 +71,7 @@ impl SchedulerController {
         bank_forks: Arc<RwLock<BankForks>>,
         scheduler: PrioGraphScheduler,
         worker_metrics: Vec<Arc<ConsumeWorkerMetrics>>,
+        account_contention: Arc<AccountContentionTracker>,
     ) -> Self {
         Self {
             decision_maker,
@@ -73,7 +82,9 @@ impl SchedulerController {
             scheduler,
             count_metrics: SchedulerCountMetrics::default(),
             timing_metrics: SchedulerTimingMetrics::default(),
+            thread_metrics: SchedulerThreadMetrics::default(),
             worker_metrics,
+            account_contention,
         }
     }
 
@@ -105,6 +116,7 @@ impl SchedulerController {
                 .update_priority_stats(self.container.get_min_max_priority());
             self.count_metrics.maybe_report_and_reset(should_report);
             self.timing_metrics.maybe_report_and_reset(should_report);
+            self.thread_metrics.maybe_report_and_reset(should_report);
             self.worker_metrics
                 .iter()
                 .for_each(|metrics| metrics.maybe_report_and_reset());
@@ -135,10 +147,23 @@ impl SchedulerController {
                     self.count_metrics.num_unschedulable,
                     scheduling_summary.num_unschedulable
                 );
+                saturating_add_assign!(
+                    self.count_metrics.num_unschedulable_lock_conflicts,
+                    scheduling_summary.num_unschedulable_lock_conflicts
+                );
+                self.account_contention.record_lock_conflicts(
+                    bank_start.working_bank.slot(),
+                    &scheduling_summary.lock_conflict_accounts,
+                );
                 saturating_add_assign!(
                     self.count_metrics.num_schedule_filtered_out,
                     scheduling_summary.num_filtered_out
                 );
+                self.thread_metrics.update(
+                    &scheduling_summary.num_scheduled_per_thread,
+                    self.scheduler.num_in_flight_per_thread(),
+                    self.scheduler.cus_in_flight_per_thread(),
+                );
                 saturating_add_assign!(
                     self.timing_metrics.schedule_filter_time_us,
                     scheduling_summary.filter_time_us
@@ -351,8 +376,12 @@ impl SchedulerController {
                 saturating_add_assign!(post_transaction_check_count, 1);
                 let transaction_id = self.transaction_id_generator.next();
 
-                let (priority, cost) =
-                    Self::calculate_priority_and_cost(&transaction, &fee_budget_limits, &bank);
+                let (priority, cost) = Self::calculate_priority_and_cost(
+                    &transaction,
+                    &fee_budget_limits,
+                    &bank.fee_structure,
+                    &bank.feature_set,
+                );
                 let transaction_ttl = SanitizedTransactionTTL {
                     transaction,
                     max_age_slot: last_slot_in_epoch,
@@ -409,18 +438,18 @@ impl SchedulerController {
     /// from user input. They should never be zero.
     /// Any difference in the prioritization is negligible for
     /// the current transaction costs.
-    fn calculate_priority_and_cost(
+    pub(crate) fn calculate_priority_and_cost(
         transaction: &SanitizedTransaction,
         fee_budget_limits: &FeeBudgetLimits,
-        bank: &Bank,
+        fee_structure: &FeeStructure,
+        feature_set: &FeatureSet,
     ) -> (u64, u64) {
-        let cost = CostModel::calculate_cost(transaction, &bank.feature_set).sum();
-        let fee = bank.fee_structure.calculate_fee(
+        let cost = CostModel::calculate_cost(transaction, feature_set).sum();
+        let fee = fee_structure.calculate_fee(
             transaction.message(),
             5_000, // this just needs to be non-zero
             fee_budget_limits,
-            bank.feature_set
-                .is_active(&include_loaded_accounts_data_size_in_fee_calculation::id()),
+            feature_set.is_active(&include_loaded_accounts_data_size_in_fee_calculation::id()),
         );
 
         // We need a multiplier here to avoid rounding down too aggressively.
@@ -450,6 +479,9 @@ struct SchedulerCountMetrics {
     num_scheduled: usize,
     /// Number of transactions that were unschedulable.
     num_unschedulable: usize,
+    /// Number of transactions that were unschedulable because their account
+    /// locks were held by multiple threads.
+    num_unschedulable_lock_conflicts: usize,
     /// Number of transactions that were filtered out during scheduling.
     num_schedule_filtered_out: usize,
     /// Number of completed transactions received from workers.
@@ -496,6 +528,11 @@ impl SchedulerCountMetrics {
             ("num_buffered", self.num_buffered, i64),
             ("num_scheduled", self.num_scheduled, i64),
             ("num_unschedulable", self.num_unschedulable, i64),
+            (
+                "num_unschedulable_lock_conflicts",
+                self.num_unschedulable_lock_conflicts,
+                i64
+            ),
             (
                 "num_schedule_filtered_out",
                 self.num_schedule_filtered_out,
@@ -536,6 +573,7 @@ impl SchedulerCountMetrics {
             || self.num_buffered != 0
             || self.num_scheduled != 0
             || self.num_unschedulable != 0
+            || self.num_unschedulable_lock_conflicts != 0
             || self.num_schedule_filtered_out != 0
             || self.num_finished != 0
             || self.num_retryable != 0
@@ -553,6 +591,7 @@ impl SchedulerCountMetrics {
         self.num_buffered = 0;
         self.num_scheduled = 0;
         self.num_unschedulable = 0;
+        self.num_unschedulable_lock_conflicts = 0;
         self.num_schedule_filtered_out = 0;
         self.num_finished = 0;
         self.num_retryable = 0;
@@ -660,6 +699,85 @@ impl SchedulerTimingMetrics {
     }
 }
 
+/// Per-thread queue metrics, showing how scheduled work is balanced across
+/// the worker threads.
+#[derive(Default)]
+struct SchedulerThreadMetrics {
+    interval: AtomicInterval,
+    /// Number of transactions scheduled onto each thread.
+    num_scheduled: Vec<usize>,
+    /// Max number of transactions in flight on each thread after scheduling.
+    max_num_in_flight: Vec<usize>,
+    /// Max number of CUs in flight on each thread after scheduling.
+    max_cus_in_flight: Vec<u64>,
+}
+
+impl SchedulerThreadMetrics {
+    fn update(
+        &mut self,
+        num_scheduled_per_thread: &[usize],
+        num_in_flight_per_thread: &[usize],
+        cus_in_flight_per_thread: &[u64],
+    ) {
+        let num_threads = num_scheduled_per_thread.len();
+        self.num_scheduled.resize(num_threads, 0);
+        self.max_num_in_flight.resize(num_threads, 0);
+        self.max_cus_in_flight.resize(num_threads, 0);
+
+        for (thread_id, num_scheduled) in num_scheduled_per_thread.iter().enumerate() {
+            saturating_add_assign!(self.num_scheduled[thread_id], *num_scheduled);
+        }
+        for (max, num_in_flight) in self
+            .max_num_in_flight
+            .iter_mut()
+            .zip(num_in_flight_per_thread)
+        {
+            *max = (*max).max(*num_in_flight);
+        }
+        for (max, cus_in_flight) in self
+            .max_cus_in_flight
+            .iter_mut()
+            .zip(cus_in_flight_per_thread)
+        {
+            *max = (*max).max(*cus_in_flight);
+        }
+    }
+
+    fn maybe_report_and_reset(&mut self, should_report: bool) {
+        const REPORT_INTERVAL_MS: u64 = 1000;
+        if self.interval.should_update(REPORT_INTERVAL_MS) {
+            if should_report {
+                self.report();
+            }
+            self.reset();
+        }
+    }
+
+    fn report(&self) {
+        for (thread_id, ((num_scheduled, max_num_in_flight), max_cus_in_flight)) in self
+            .num_scheduled
+            .iter()
+            .zip(&self.max_num_in_flight)
+            .zip(&self.max_cus_in_flight)
+            .enumerate()
+        {
+            datapoint_info!(
+                "banking_stage_scheduler_thread_counts",
+                "thread" => thread_id.to_string(),
+                ("num_scheduled", *num_scheduled, i64),
+                ("max_num_in_flight", *max_num_in_flight, i64),
+                ("max_cus_in_flight", *max_cus_in_flight, i64)
+            );
+        }
+    }
+
+    fn reset(&mut self) {
+        self.num_scheduled.fill(0);
+        self.max_num_in_flight.fill(0);
+        self.max_cus_in_flight.fill(0);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use {
@@ -760,6 +878,7 @@ mod tests {
             bank_forks,
             PrioGraphScheduler::new(consume_work_senders, finished_consume_work_receiver),
             vec![], // no actual workers with metrics to report, this can be empty
+            Arc::default(),
         );
 
         (test_frame, scheduler_controller)
@@ -1159,4 +1278,19 @@ mod tests {
             .collect_vec();
         assert_eq!(message_hashes, vec![&tx1_hash]);
     }
+
+    #[test]
+    fn test_scheduler_thread_metrics() {
+        let mut thread_metrics = SchedulerThreadMetrics::default();
+        thread_metrics.update(&[2, 0], &[2, 1], &[200, 100]);
+        thread_metrics.update(&[1, 3], &[1, 4], &[100, 400]);
+        assert_eq!(thread_metrics.num_scheduled, [3, 3]);
+        assert_eq!(thread_metrics.max_num_in_flight, [2, 4]);
+        assert_eq!(thread_metrics.max_cus_in_flight, [200, 400]);
+
+        thread_metrics.reset();
+        assert_eq!(thread_metrics.num_scheduled, [0, 0]);
+        assert_eq!(thread_metrics.max_num_in_flight, [0, 0]);
+        assert_eq!(thread_metrics.max_cus_in_flight, [0, 0]);
+    }
 }
@@ -3,7 +3,10 @@ use {
         cluster_info::ClusterInfo, legacy_contact_info::LegacyContactInfo as ContactInfo,
     },
     solana_poh::poh_recorder::PohRecorder,
-    solana_sdk::{clock::FORWARD_TRANSACTIONS_TO_LEADER_AT_SLOT_OFFSET, pubkey::Pubkey},
+    solana_sdk::{
+        clock::{FORWARD_TRANSACTIONS_TO_LEADER_AT_SLOT_OFFSET, NUM_CONSECUTIVE_LEADER_SLOTS},
+        pubkey::Pubkey,
+    },
     std::{net::SocketAddr, sync::RwLock},
 };
 
@@ -31,3 +34,25 @@ where
         .map(|addr| (leader_pubkey, addr))
         .ok()
 }
+
+/// Returns up to `num_leaders` distinct leaders, in leader schedule order,
+/// starting with the leader that transactions are forwarded to.
+pub(crate) fn upcoming_leaders(
+    poh_recorder: &RwLock<PohRecorder>,
+    num_leaders: usize,
+) -> Vec<Pubkey> {
+    let poh_recorder = poh_recorder.read().unwrap();
+    let mut leaders = Vec::with_capacity(num_leaders);
+    for n in 0..num_leaders as u64 {
+        let Some(leader) = poh_recorder.leader_after_n_slots(
+            FORWARD_TRANSACTIONS_TO_LEADER_AT_SLOT_OFFSET
+                .saturating_add(n.saturating_mul(NUM_CONSECUTIVE_LEADER_SLOTS)),
+        ) else {
+            break;
+        };
+        if !leaders.contains(&leader) {
+            leaders.push(leader);
+        }
+    }
+    leaders
+}
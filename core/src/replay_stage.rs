@@ -35,12 +35,17 @@ use {
     crossbeam_channel::{Receiver, RecvTimeoutError, Sender},
     lazy_static::lazy_static,
     rayon::{prelude::*, ThreadPool},
+    solana_cost_model::block_cost_limits::BlockProductionLimits,
     solana_entry::entry::VerifyRecyclers,
-    solana_geyser_plugin_manager::block_metadata_notifier_interface::BlockMetadataNotifierArc,
+    solana_geyser_plugin_manager::{
+        block_metadata_notifier_interface::BlockMetadataNotifierArc,
+        sysvar_notifier_interface::SysvarNotifierArc,
+    },
     solana_gossip::cluster_info::ClusterInfo,
     solana_ledger::{
         block_error::BlockError,
         blockstore::Blockstore,
+        blockstore_meta::{EpochRewardsPartitionsMeta, EpochStakeHistoryMeta},
         blockstore_processor::{
             self, BlockstoreProcessorError, ConfirmationProgress, ExecuteBatchesInternalMetrics,
             TransactionStatusSender,
@@ -66,18 +71,22 @@ use {
         prioritization_fee_cache::PrioritizationFeeCache,
     },
     solana_sdk::{
+        account::from_account,
         clock::{BankId, Slot, MAX_PROCESSING_AGE, NUM_CONSECUTIVE_LEADER_SLOTS},
+        epoch_rewards::EpochRewards,
         feature_set,
         genesis_config::ClusterType,
         hash::Hash,
         pubkey::Pubkey,
         saturating_add_assign,
         signature::{Keypair, Signature, Signer},
+        stake_history::StakeHistory,
+        sysvar,
         timing::timestamp,
         transaction::Transaction,
     },
     solana_vote::vote_sender_types::ReplayVoteSender,
-    solana_vote_program::vote_state::VoteTransaction,
+    solana_vote_program::vote_state::{TowerSync, VoteTransaction},
     std::{
         collections::{HashMap, HashSet},
         result,
@@ -296,6 +305,7 @@ pub struct ReplayStageConfig {
     // duplicate voting which can lead to slashing.
     pub wait_to_vote_slot: Option<Slot>,
     pub replay_slots_concurrently: bool,
+    pub block_production_limits: Arc<RwLock<BlockProductionLimits>>,
 }
 
 #[derive(Default)]
@@ -539,6 +549,7 @@ impl ReplayStage {
         voting_sender: Sender<VoteOp>,
         drop_bank_sender: Sender<Vec<Arc<Bank>>>,
         block_metadata_notifier: Option<BlockMetadataNotifierArc>,
+        sysvar_notifier: Option<SysvarNotifierArc>,
         log_messages_bytes_limit: Option<usize>,
         prioritization_fee_cache: Arc<PrioritizationFeeCache>,
         dumped_slots_sender: DumpedSlotsSender,
@@ -564,6 +575,7 @@ impl ReplayStage {
             tower_storage,
             wait_to_vote_slot,
             replay_slots_concurrently,
+            block_production_limits,
         } = config;
 
         trace!("replay stage");
@@ -671,6 +683,7 @@ impl ReplayStage {
                     &mut duplicate_slots_to_repair,
                     &ancestor_hashes_replay_update_sender,
                     block_metadata_notifier.clone(),
+                    sysvar_notifier.clone(),
                     &mut replay_timing,
                     log_messages_bytes_limit,
                     replay_slots_concurrently,
@@ -1091,6 +1104,7 @@ impl ReplayStage {
                         &banking_tracer,
                         has_new_vote_been_rooted,
                         transaction_status_sender.is_some(),
+                        &block_production_limits,
                     );
 
                     let poh_bank = poh_recorder.read().unwrap().bank();
@@ -1918,6 +1932,7 @@ impl ReplayStage {
         banking_tracer: &Arc<BankingTracer>,
         has_new_vote_been_rooted: bool,
         track_transaction_indexes: bool,
+        block_production_limits: &RwLock<BlockProductionLimits>,
     ) {
         // all the individual calls to poh_recorder.read() are designed to
         // increase granularity, decrease contention
@@ -2040,6 +2055,12 @@ impl ReplayStage {
             // new()-ing of its child bank
             banking_tracer.hash_event(parent.slot(), &parent.last_blockhash(), &parent.hash());
 
+            // only the blocks produced by this node are packed to its own limits
+            tpu_bank
+                .write_cost_tracker()
+                .unwrap()
+                .apply_block_production_limits(&block_production_limits.read().unwrap());
+
             let tpu_bank = bank_forks.write().unwrap().insert(tpu_bank);
             poh_recorder
                 .write()
@@ -2254,6 +2275,7 @@ impl ReplayStage {
             blockstore
                 .set_roots(rooted_slots.iter())
                 .expect("Ledger set roots failed");
+            Self::record_epoch_stake_history(&rooted_banks, blockstore);
             let highest_super_majority_root = Some(
                 block_commitment_cache
                     .read()
@@ -2405,7 +2427,14 @@ impl ReplayStage {
         // Compact the vote state update before sending
         let vote = match vote {
             VoteTransaction::VoteStateUpdate(vote_state_update) => {
-                VoteTransaction::CompactVoteStateUpdate(vote_state_update)
+                if bank
+                    .feature_set
+                    .is_active(&feature_set::enable_tower_sync_ix::id())
+                {
+                    VoteTransaction::TowerSync(TowerSync::from(vote_state_update))
+                } else {
+                    VoteTransaction::CompactVoteStateUpdate(vote_state_update)
+                }
             }
             vote => vote,
         };
@@ -2845,6 +2874,7 @@ impl ReplayStage {
         duplicate_slots_to_repair: &mut DuplicateSlotsToRepair,
         ancestor_hashes_replay_update_sender: &AncestorHashesReplayUpdateSender,
         block_metadata_notifier: Option<BlockMetadataNotifierArc>,
+        sysvar_notifier: Option<SysvarNotifierArc>,
         replay_result_vec: &[ReplaySlotFromBlockstore],
         purge_repair_slot_counter: &mut PurgeRepairSlotCounter,
     ) -> bool {
@@ -3054,6 +3084,9 @@ impl ReplayStage {
                         r_replay_progress.num_entries as u64,
                     )
                 }
+                if let Some(ref sysvar_notifier) = sysvar_notifier {
+                    Self::notify_sysvars(bank, sysvar_notifier);
+                }
                 bank_complete_time.stop();
 
                 r_replay_stats.report_stats(
@@ -3077,6 +3110,77 @@ impl ReplayStage {
         did_complete_bank
     }
 
+    /// Record the stake history entry and rewards partitions of every epoch whose
+    /// boundary was crossed by the newly rooted banks
+    fn record_epoch_stake_history(rooted_banks: &[Arc<Bank>], blockstore: &Blockstore) {
+        for bank in rooted_banks {
+            let parent_epoch = bank.epoch_schedule().get_epoch(bank.parent_slot());
+            if parent_epoch >= bank.epoch() {
+                continue;
+            }
+            let Some(stake_history_entry) = bank
+                .get_account(&sysvar::stake_history::id())
+                .and_then(|account| from_account::<StakeHistory, _>(&account))
+                .and_then(|stake_history| stake_history.get(parent_epoch).cloned())
+            else {
+                continue;
+            };
+            let rewards_partitions = bank.epoch_rewards_partitions().map(
+                |(distribution_starting_block_height, num_partitions)| {
+                    let total_rewards = bank
+                        .get_account(&sysvar::epoch_rewards::id())
+                        .and_then(|account| from_account::<EpochRewards, _>(&account))
+                        .map(|epoch_rewards| epoch_rewards.total_rewards)
+                        .unwrap_or_default();
+                    EpochRewardsPartitionsMeta {
+                        total_rewards,
+                        distribution_starting_block_height,
+                        num_partitions,
+                    }
+                },
+            );
+            let epoch_stake_history = EpochStakeHistoryMeta {
+                slot: bank.slot(),
+                parent_slot: bank.parent_slot(),
+                stake_history_entry,
+                rewards_partitions,
+            };
+            if let Err(err) =
+                blockstore.write_epoch_stake_history(parent_epoch, &epoch_stake_history)
+            {
+                warn!("failed to record stake history for epoch {parent_epoch}: {err:?}");
+            }
+        }
+    }
+
+    fn notify_sysvars(bank: &Bank, sysvar_notifier: &SysvarNotifierArc) {
+        let clock = bank.clock();
+        // Only report EpochRewards when it was written in this slot, ie. while
+        // partitioned rewards are being distributed
+        let epoch_rewards = bank
+            .get_account_modified_slot(&sysvar::epoch_rewards::id())
+            .filter(|(_, modified_slot)| *modified_slot == bank.slot())
+            .and_then(|(account, _)| from_account::<EpochRewards, _>(&account));
+        sysvar_notifier.notify_sysvar_updates(bank.slot(), &clock, epoch_rewards.as_ref());
+
+        let parent_epoch = bank.epoch_schedule().get_epoch(bank.parent_slot());
+        if parent_epoch < bank.epoch() {
+            let stake_history = bank
+                .get_account(&sysvar::stake_history::id())
+                .and_then(|account| from_account::<StakeHistory, _>(&account));
+            sysvar_notifier.notify_epoch_boundary(
+                bank.parent_slot(),
+                parent_epoch,
+                bank.slot(),
+                bank.epoch(),
+                &clock,
+                stake_history
+                    .as_ref()
+                    .and_then(|stake_history| stake_history.get(parent_epoch)),
+            );
+        }
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn replay_active_banks(
         blockstore: &Blockstore,
@@ -3103,6 +3207,7 @@ impl ReplayStage {
         duplicate_slots_to_repair: &mut DuplicateSlotsToRepair,
         ancestor_hashes_replay_update_sender: &AncestorHashesReplayUpdateSender,
         block_metadata_notifier: Option<BlockMetadataNotifierArc>,
+        sysvar_notifier: Option<SysvarNotifierArc>,
         replay_timing: &mut ReplayTiming,
         log_messages_bytes_limit: Option<usize>,
         replay_slots_concurrently: bool,
@@ -3176,6 +3281,7 @@ impl ReplayStage {
                 duplicate_slots_to_repair,
                 ancestor_hashes_replay_update_sender,
                 block_metadata_notifier,
+                sysvar_notifier,
                 &replay_result_vec,
                 purge_repair_slot_counter,
             )
@@ -4240,6 +4346,7 @@ pub(crate) mod tests {
         crossbeam_channel::unbounded,
         itertools::Itertools,
         solana_entry::entry::{self, Entry},
+        solana_geyser_plugin_manager::sysvar_notifier_interface::SysvarNotifier,
         solana_gossip::{cluster_info::Node, crds::Cursor},
         solana_ledger::{
             blockstore::{entries_to_test_shreds, make_slot_entries, BlockstoreError},
@@ -4258,12 +4365,13 @@ pub(crate) mod tests {
             genesis_utils::{GenesisConfigInfo, ValidatorVoteKeypairs},
         },
         solana_sdk::{
-            clock::NUM_CONSECUTIVE_LEADER_SLOTS,
+            clock::{Clock, Epoch, NUM_CONSECUTIVE_LEADER_SLOTS},
             genesis_config,
             hash::{hash, Hash},
             instruction::InstructionError,
             poh_config::PohConfig,
             signature::{Keypair, Signer},
+            stake_history::StakeHistoryEntry,
             system_transaction,
             transaction::TransactionError,
         },
@@ -4276,7 +4384,7 @@ pub(crate) mod tests {
         std::{
             fs::remove_dir_all,
             iter,
-            sync::{atomic::AtomicU64, Arc, RwLock},
+            sync::{atomic::AtomicU64, Arc, Mutex, RwLock},
         },
         trees::{tr, Tree},
     };
@@ -8598,4 +8706,64 @@ pub(crate) mod tests {
         assert_eq!(reset_fork, Some(4));
         assert_eq!(failures, vec![HeaviestForkFailures::LockedOut(4),]);
     }
+
+    /// Records the slots of sysvar updates and the epochs of epoch boundaries
+    #[derive(Default)]
+    struct RecordingSysvarNotifier {
+        sysvar_update_slots: Mutex<Vec<Slot>>,
+        epoch_boundaries: Mutex<Vec<(Epoch, Epoch)>>,
+    }
+
+    impl SysvarNotifier for RecordingSysvarNotifier {
+        fn notify_sysvar_updates(
+            &self,
+            slot: Slot,
+            clock: &Clock,
+            _epoch_rewards: Option<&EpochRewards>,
+        ) {
+            assert_eq!(clock.slot, slot);
+            self.sysvar_update_slots.lock().unwrap().push(slot);
+        }
+
+        fn notify_epoch_boundary(
+            &self,
+            parent_slot: Slot,
+            parent_epoch: Epoch,
+            slot: Slot,
+            epoch: Epoch,
+            clock: &Clock,
+            _stake_history_entry: Option<&StakeHistoryEntry>,
+        ) {
+            assert!(parent_slot < slot);
+            assert_eq!(clock.epoch, epoch);
+            self.epoch_boundaries
+                .lock()
+                .unwrap()
+                .push((parent_epoch, epoch));
+        }
+    }
+
+    #[test]
+    fn test_notify_sysvars() {
+        let genesis_config = create_genesis_config(10_000).genesis_config;
+        let bank0 = Arc::new(Bank::new_for_tests(&genesis_config));
+        let recorder = Arc::new(RecordingSysvarNotifier::default());
+        let sysvar_notifier: SysvarNotifierArc = recorder.clone();
+
+        // Not an epoch boundary
+        let bank1 = Arc::new(Bank::new_from_parent(bank0, &Pubkey::default(), 1));
+        ReplayStage::notify_sysvars(&bank1, &sysvar_notifier);
+        assert_eq!(*recorder.sysvar_update_slots.lock().unwrap(), vec![1]);
+        assert!(recorder.epoch_boundaries.lock().unwrap().is_empty());
+
+        // The first slot of the next epoch
+        let epoch1_slot = bank1.epoch_schedule().get_first_slot_in_epoch(1);
+        let bank2 = Bank::new_from_parent(bank1, &Pubkey::default(), epoch1_slot);
+        ReplayStage::notify_sysvars(&bank2, &sysvar_notifier);
+        assert_eq!(
+            *recorder.sysvar_update_slots.lock().unwrap(),
+            vec![1, epoch1_slot]
+        );
+        assert_eq!(*recorder.epoch_boundaries.lock().unwrap(), vec![(0, 1)]);
+    }
 }
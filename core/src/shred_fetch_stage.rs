@@ -120,6 +120,14 @@ impl ShredFetchStage {
                     &epoch_schedule,
                 )
             };
+            let enable_shred_erasure_config = |shred_slot| {
+                check_feature_activation(
+                    &feature_set::enable_shred_erasure_config::id(),
+                    shred_slot,
+                    &feature_set,
+                    &epoch_schedule,
+                )
+            };
             let turbine_disabled = turbine_disabled.load(Ordering::Relaxed);
             for packet in packet_batch.iter_mut().filter(|p| !p.meta().discard()) {
                 if turbine_disabled
@@ -130,6 +138,7 @@ impl ShredFetchStage {
                         shred_version,
                         should_drop_legacy_shreds,
                         enable_chained_merkle_shreds,
+                        enable_shred_erasure_config,
                         &mut stats,
                     )
                 {
@@ -469,6 +478,7 @@ mod tests {
             shred_version,
             |_| false, // should_drop_legacy_shreds
             |_| true,  // enable_chained_merkle_shreds
+            |_| true,  // enable_shred_erasure_config
             &mut stats,
         ));
         let coding = solana_ledger::shred::Shredder::generate_coding_shreds(
@@ -484,6 +494,7 @@ mod tests {
             shred_version,
             |_| false, // should_drop_legacy_shreds
             |_| true,  // enable_chained_merkle_shreds
+            |_| true,  // enable_shred_erasure_config
             &mut stats,
         ));
     }
@@ -507,6 +518,7 @@ mod tests {
             shred_version,
             |_| false, // should_drop_legacy_shreds
             |_| true,  // enable_chained_merkle_shreds
+            |_| true,  // enable_shred_erasure_config
             &mut stats,
         ));
         assert_eq!(stats.index_overrun, 1);
@@ -530,6 +542,7 @@ mod tests {
             shred_version,
             |_| false, // should_drop_legacy_shreds
             |_| true,  // enable_chained_merkle_shreds
+            |_| true,  // enable_shred_erasure_config
             &mut stats,
         ));
         assert_eq!(stats.slot_out_of_range, 1);
@@ -541,6 +554,7 @@ mod tests {
             345,       // shred_version
             |_| false, // should_drop_legacy_shreds
             |_| true,  // enable_chained_merkle_shreds
+            |_| true,  // enable_shred_erasure_config
             &mut stats,
         ));
         assert_eq!(stats.shred_version_mismatch, 1);
@@ -553,6 +567,7 @@ mod tests {
             shred_version,
             |_| false, // should_drop_legacy_shreds
             |_| true,  // enable_chained_merkle_shreds
+            |_| true,  // enable_shred_erasure_config
             &mut stats,
         ));
 
@@ -576,6 +591,7 @@ mod tests {
             shred_version,
             |_| false, // should_drop_legacy_shreds
             |_| true,  // enable_chained_merkle_shreds
+            |_| true,  // enable_shred_erasure_config
             &mut stats,
         ));
 
@@ -589,6 +605,7 @@ mod tests {
             shred_version,
             |_| false, // should_drop_legacy_shreds
             |_| true,  // enable_chained_merkle_shreds
+            |_| true,  // enable_shred_erasure_config
             &mut stats,
         ));
     }
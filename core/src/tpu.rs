@@ -4,7 +4,10 @@
 pub use solana_sdk::net::DEFAULT_TPU_COALESCE;
 use {
     crate::{
-        banking_stage::BankingStage,
+        banking_stage::{
+            account_contention::AccountContentionTracker, forwarding_policy::ForwardingPolicy,
+            BankingStage,
+        },
         banking_trace::{BankingTracer, TracerThread},
         cluster_info_vote_listener::{
             ClusterInfoVoteListener, DuplicateConfirmedSlotsSender, GossipVerifiedVoteHashSender,
@@ -37,7 +40,10 @@ use {
         quic::{spawn_server, SpawnServerResult, MAX_STAKED_CONNECTIONS, MAX_UNSTAKED_CONNECTIONS},
         streamer::StakedNodes,
     },
-    solana_turbine::broadcast_stage::{BroadcastStage, BroadcastStageType},
+    solana_turbine::{
+        broadcast_stage::{BroadcastStage, BroadcastStageType},
+        cluster_nodes::TurbineTreeOverride,
+    },
     solana_vote::vote_sender_types::{ReplayVoteReceiver, ReplayVoteSender},
     std::{
         collections::HashMap,
@@ -105,11 +111,14 @@ impl Tpu {
         log_messages_bytes_limit: Option<usize>,
         staked_nodes: &Arc<RwLock<StakedNodes>>,
         shared_staked_nodes_overrides: Arc<RwLock<HashMap<Pubkey, u64>>>,
+        forwarding_policy: Arc<RwLock<ForwardingPolicy>>,
+        account_contention: Arc<AccountContentionTracker>,
         banking_tracer: Arc<BankingTracer>,
         tracer_thread_hdl: TracerThread,
         tpu_enable_udp: bool,
         prioritization_fee_cache: &Arc<PrioritizationFeeCache>,
         block_production_method: BlockProductionMethod,
+        turbine_tree_override: Option<TurbineTreeOverride>,
         _generator_config: Option<GeneratorConfig>, /* vestigial code for replay invalidator */
     ) -> (Self, Vec<Arc<dyn NotifyKeyUpdate + Sync + Send>>) {
         let TpuSockets {
@@ -229,6 +238,8 @@ impl Tpu {
             connection_cache.clone(),
             bank_forks.clone(),
             prioritization_fee_cache,
+            forwarding_policy,
+            account_contention,
         );
 
         let (entry_receiver, tpu_entry_notifier) =
@@ -255,6 +266,7 @@ impl Tpu {
             bank_forks,
             shred_version,
             turbine_quic_endpoint_sender,
+            turbine_tree_override,
         );
 
         (
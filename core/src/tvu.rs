@@ -28,14 +28,20 @@ use {
     bytes::Bytes,
     crossbeam_channel::{unbounded, Receiver, Sender},
     solana_client::connection_cache::ConnectionCache,
-    solana_geyser_plugin_manager::block_metadata_notifier_interface::BlockMetadataNotifierArc,
+    solana_cost_model::block_cost_limits::BlockProductionLimits,
+    solana_geyser_plugin_manager::{
+        block_metadata_notifier_interface::BlockMetadataNotifierArc,
+        sysvar_notifier_interface::SysvarNotifierArc,
+    },
     solana_gossip::{
         cluster_info::ClusterInfo, duplicate_shred_handler::DuplicateShredHandler,
         duplicate_shred_listener::DuplicateShredListener,
     },
     solana_ledger::{
-        blockstore::Blockstore, blockstore_cleanup_service::BlockstoreCleanupService,
-        blockstore_processor::TransactionStatusSender, entry_notifier_service::EntryNotifierSender,
+        blockstore::Blockstore,
+        blockstore_cleanup_service::{BlockstoreCleanupService, BlockstoreRetention},
+        blockstore_processor::TransactionStatusSender,
+        entry_notifier_service::EntryNotifierSender,
         leader_schedule_cache::LeaderScheduleCache,
     },
     solana_poh::poh_recorder::PohRecorder,
@@ -48,7 +54,7 @@ use {
         commitment::BlockCommitmentCache, prioritization_fee_cache::PrioritizationFeeCache,
     },
     solana_sdk::{clock::Slot, pubkey::Pubkey, signature::Keypair},
-    solana_turbine::retransmit_stage::RetransmitStage,
+    solana_turbine::{cluster_nodes::TurbineTreeOverride, retransmit_stage::RetransmitStage},
     solana_vote::vote_sender_types::ReplayVoteSender,
     std::{
         collections::HashSet,
@@ -84,6 +90,8 @@ pub struct TvuSockets {
 #[derive(Default)]
 pub struct TvuConfig {
     pub max_ledger_shreds: Option<u64>,
+    /// Retain complete epochs in the blockstore instead of `max_ledger_shreds`
+    pub max_ledger_epochs: Option<u64>,
     pub shred_version: u16,
     // Validators from which repairs are requested
     pub repair_validators: Option<HashSet<Pubkey>>,
@@ -91,6 +99,10 @@ pub struct TvuConfig {
     pub repair_whitelist: Arc<RwLock<HashSet<Pubkey>>>,
     pub wait_for_vote_to_start_leader: bool,
     pub replay_slots_concurrently: bool,
+    // Overrides the turbine retransmit tree, for tests and simulations
+    pub turbine_tree_override: Option<TurbineTreeOverride>,
+    // Limits which this node packs its leader blocks to
+    pub block_production_limits: Arc<RwLock<BlockProductionLimits>>,
 }
 
 impl Tvu {
@@ -132,6 +144,7 @@ impl Tvu {
         tvu_config: TvuConfig,
         max_slots: &Arc<MaxSlots>,
         block_metadata_notifier: Option<BlockMetadataNotifierArc>,
+        sysvar_notifier: Option<SysvarNotifierArc>,
         wait_to_vote_slot: Option<Slot>,
         accounts_background_request_sender: AbsRequestSender,
         log_messages_bytes_limit: Option<usize>,
@@ -191,6 +204,7 @@ impl Tvu {
             retransmit_receiver,
             max_slots.clone(),
             Some(rpc_subscriptions.clone()),
+            tvu_config.turbine_tree_override,
         );
 
         let (ancestor_duplicate_slots_sender, ancestor_duplicate_slots_receiver) = unbounded();
@@ -266,6 +280,7 @@ impl Tvu {
             tower_storage: tower_storage.clone(),
             wait_to_vote_slot,
             replay_slots_concurrently: tvu_config.replay_slots_concurrently,
+            block_production_limits: tvu_config.block_production_limits.clone(),
         };
 
         let (voting_sender, voting_receiver) = unbounded();
@@ -314,6 +329,7 @@ impl Tvu {
             voting_sender,
             drop_bank_sender,
             block_metadata_notifier,
+            sysvar_notifier,
             log_messages_bytes_limit,
             prioritization_fee_cache.clone(),
             dumped_slots_sender,
@@ -321,11 +337,25 @@ impl Tvu {
             popular_pruned_forks_receiver,
         )?;
 
-        let blockstore_cleanup_service = tvu_config.max_ledger_shreds.map(|max_ledger_shreds| {
-            BlockstoreCleanupService::new(
+        let blockstore_retention = match tvu_config.max_ledger_epochs {
+            Some(num_epochs) => Some(BlockstoreRetention::Epochs {
+                num_epochs,
+                epoch_schedule: bank_forks
+                    .read()
+                    .unwrap()
+                    .root_bank()
+                    .epoch_schedule()
+                    .clone(),
+            }),
+            None => tvu_config
+                .max_ledger_shreds
+                .map(BlockstoreRetention::MaxShreds),
+        };
+        let blockstore_cleanup_service = blockstore_retention.map(|retention| {
+            BlockstoreCleanupService::new_with_retention(
                 blockstore_cleanup_slot_receiver,
                 blockstore.clone(),
-                max_ledger_shreds,
+                retention,
                 exit.clone(),
             )
         });
@@ -496,6 +526,7 @@ pub mod tests {
             &Arc::new(MaxSlots::default()),
             None,
             None,
+            None,
             AbsRequestSender::default(),
             None,
             &Arc::new(ConnectionCache::new("connection_cache_test")),
@@ -5,6 +5,9 @@ use {
     crate::{
         accounts_hash_verifier::AccountsHashVerifier,
         admin_rpc_post_init::AdminRpcRequestMetadataPostInit,
+        banking_stage::{
+            account_contention::AccountContentionTracker, forwarding_policy::ForwardingPolicy,
+        },
         banking_trace::{self, BankingTracer},
         cache_block_meta_service::{CacheBlockMetaSender, CacheBlockMetaService},
         cluster_info_vote_listener::VoteTracker,
@@ -38,6 +41,7 @@ use {
         utils::{move_and_async_delete_path, move_and_async_delete_path_contents},
     },
     solana_client::connection_cache::{ConnectionCache, Protocol},
+    solana_cost_model::block_cost_limits::BlockProductionLimits,
     solana_entry::poh::compute_hash_time_ns,
     solana_geyser_plugin_manager::{
         geyser_plugin_service::GeyserPluginService, GeyserPluginManagerRequest,
@@ -48,6 +52,7 @@ use {
             DEFAULT_CONTACT_SAVE_INTERVAL_MILLIS,
         },
         crds_gossip_pull::CRDS_GOSSIP_PULL_CRDS_TIMEOUT_MS,
+        crds_gossip_push::CrdsGossipPushConfig,
         gossip_service::GossipService,
         legacy_contact_info::LegacyContactInfo as ContactInfo,
     },
@@ -116,7 +121,9 @@ use {
     solana_send_transaction_service::send_transaction_service,
     solana_streamer::{socket::SocketAddrSpace, streamer::StakedNodes},
     solana_svm::runtime_config::RuntimeConfig,
-    solana_turbine::{self, broadcast_stage::BroadcastStageType},
+    solana_turbine::{
+        self, broadcast_stage::BroadcastStageType, cluster_nodes::TurbineTreeOverride,
+    },
     solana_unified_scheduler_pool::DefaultSchedulerPool,
     solana_vote_program::vote_state,
     solana_wen_restart::wen_restart::wait_for_wen_restart,
@@ -211,8 +218,12 @@ pub struct ValidatorConfig {
     pub pubsub_config: PubSubConfig,
     pub snapshot_config: SnapshotConfig,
     pub max_ledger_shreds: Option<u64>,
+    /// Retain this many complete epochs in the blockstore, rather than `max_ledger_shreds`
+    pub max_ledger_epochs: Option<u64>,
     pub broadcast_stage_type: BroadcastStageType,
     pub turbine_disabled: Arc<AtomicBool>,
+    /// Overrides the turbine retransmit tree; for tests and simulations
+    pub turbine_tree_override: Option<TurbineTreeOverride>,
     pub enforce_ulimit_nofile: bool,
     pub fixed_leader_schedule: Option<FixedSchedule>,
     pub wait_for_supermajority: Option<Slot>,
@@ -232,6 +243,9 @@ pub struct ValidatorConfig {
     pub debug_keys: Option<Arc<HashSet<Pubkey>>>,
     pub contact_debug_interval: u64,
     pub contact_save_interval: u64,
+    pub gossip_push_config: CrdsGossipPushConfig,
+    /// Application-defined metadata published through gossip, if any
+    pub node_metadata: Option<String>,
     pub send_transaction_service_config: send_transaction_service::Config,
     pub no_poh_speed_test: bool,
     pub no_os_memory_stats_reporting: bool,
@@ -249,6 +263,8 @@ pub struct ValidatorConfig {
     pub accounts_db_force_initial_clean: bool,
     pub tpu_coalesce: Duration,
     pub staked_nodes_overrides: Arc<RwLock<HashMap<Pubkey, u64>>>,
+    pub forwarding_policy: Arc<RwLock<ForwardingPolicy>>,
+    pub block_production_limits: Arc<RwLock<BlockProductionLimits>>,
     pub validator_exit: Arc<RwLock<Exit>>,
     pub no_wait_for_vote_to_start_leader: bool,
     pub accounts_shrink_ratio: AccountShrinkThreshold,
@@ -273,6 +289,7 @@ impl Default for ValidatorConfig {
             expected_shred_version: None,
             voting_disabled: false,
             max_ledger_shreds: None,
+            max_ledger_epochs: None,
             account_paths: Vec::new(),
             account_snapshot_paths: Vec::new(),
             rpc_config: JsonRpcConfig::default(),
@@ -282,6 +299,7 @@ impl Default for ValidatorConfig {
             snapshot_config: SnapshotConfig::new_load_only(),
             broadcast_stage_type: BroadcastStageType::Standard,
             turbine_disabled: Arc::<AtomicBool>::default(),
+            turbine_tree_override: None,
             enforce_ulimit_nofile: true,
             fixed_leader_schedule: None,
             wait_for_supermajority: None,
@@ -299,6 +317,8 @@ impl Default for ValidatorConfig {
             debug_keys: None,
             contact_debug_interval: DEFAULT_CONTACT_DEBUG_INTERVAL_MILLIS,
             contact_save_interval: DEFAULT_CONTACT_SAVE_INTERVAL_MILLIS,
+            gossip_push_config: CrdsGossipPushConfig::default(),
+            node_metadata: None,
             send_transaction_service_config: send_transaction_service::Config::default(),
             no_poh_speed_test: true,
             no_os_memory_stats_reporting: true,
@@ -315,6 +335,8 @@ impl Default for ValidatorConfig {
             accounts_db_force_initial_clean: false,
             tpu_coalesce: DEFAULT_TPU_COALESCE,
             staked_nodes_overrides: Arc::new(RwLock::new(HashMap::new())),
+            forwarding_policy: Arc::new(RwLock::new(ForwardingPolicy::default())),
+            block_production_limits: Arc::new(RwLock::new(BlockProductionLimits::default())),
             validator_exit: Arc::new(RwLock::new(Exit::default())),
             no_wait_for_vote_to_start_leader: true,
             accounts_shrink_ratio: AccountShrinkThreshold::default(),
@@ -651,13 +673,19 @@ impl Validator {
             .as_ref()
             .and_then(|geyser_plugin_service| geyser_plugin_service.get_block_metadata_notifier());
 
+        let sysvar_notifier = geyser_plugin_service
+            .as_ref()
+            .and_then(|geyser_plugin_service| geyser_plugin_service.get_sysvar_notifier());
+
         info!(
             "Geyser plugin: accounts_update_notifier: {}, \
             transaction_notifier: {}, \
-            entry_notifier: {}",
+            entry_notifier: {}, \
+            sysvar_notifier: {}",
             accounts_update_notifier.is_some(),
             transaction_notifier.is_some(),
-            entry_notifier.is_some()
+            entry_notifier.is_some(),
+            sysvar_notifier.is_some()
         );
 
         let system_monitor_service = Some(SystemMonitorService::new(
@@ -736,8 +764,14 @@ impl Validator {
             socket_addr_space,
         );
         cluster_info.set_contact_debug_interval(config.contact_debug_interval);
+        cluster_info.set_push_config(config.gossip_push_config);
         cluster_info.set_entrypoints(cluster_entrypoints);
         cluster_info.restore_contact_info(ledger_path, config.contact_save_interval);
+        if let Some(node_metadata) = &config.node_metadata {
+            cluster_info
+                .push_node_metadata(node_metadata.clone())
+                .map_err(|err| format!("Invalid node metadata: {err}"))?;
+        }
         let cluster_info = Arc::new(cluster_info);
 
         assert!(is_snapshot_config_valid(
@@ -1282,14 +1316,18 @@ impl Validator {
             duplicate_confirmed_slots_receiver,
             TvuConfig {
                 max_ledger_shreds: config.max_ledger_shreds,
+                max_ledger_epochs: config.max_ledger_epochs,
                 shred_version: node.info.shred_version(),
                 repair_validators: config.repair_validators.clone(),
                 repair_whitelist: config.repair_whitelist.clone(),
                 wait_for_vote_to_start_leader,
                 replay_slots_concurrently: config.replay_slots_concurrently,
+                turbine_tree_override: config.turbine_tree_override.clone(),
+                block_production_limits: config.block_production_limits.clone(),
             },
             &max_slots,
             block_metadata_notifier,
+            sysvar_notifier,
             config.wait_to_vote_slot,
             accounts_background_request_sender,
             config.runtime_config.log_messages_bytes_limit,
@@ -1318,6 +1356,7 @@ impl Validator {
             };
         }
 
+        let account_contention = Arc::new(AccountContentionTracker::default());
         let (tpu, mut key_notifies) = Tpu::new(
             &cluster_info,
             &poh_recorder,
@@ -1353,11 +1392,14 @@ impl Validator {
             config.runtime_config.log_messages_bytes_limit,
             &staked_nodes,
             config.staked_nodes_overrides.clone(),
+            config.forwarding_policy.clone(),
+            account_contention.clone(),
             banking_tracer,
             tracer_thread,
             tpu_enable_udp,
             &prioritization_fee_cache,
             config.block_production_method.clone(),
+            config.turbine_tree_override.clone(),
             config.generator_config.clone(),
         );
 
@@ -1380,6 +1422,7 @@ impl Validator {
             repair_socket: Arc::new(node.sockets.repair),
             outstanding_repair_requests,
             cluster_slots,
+            account_contention,
         });
 
         Ok(Self {
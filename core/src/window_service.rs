@@ -27,7 +27,10 @@ use {
     },
     solana_measure::measure::Measure,
     solana_metrics::inc_new_counter_error,
-    solana_perf::packet::{Packet, PacketBatch},
+    solana_perf::{
+        deduper::Deduper,
+        packet::{Packet, PacketBatch},
+    },
     solana_rayon_threadlimit::get_thread_count,
     solana_runtime::bank_forks::BankForks,
     solana_sdk::{
@@ -49,6 +52,12 @@ use {
     tokio::sync::mpsc::Sender as AsyncSender,
 };
 
+// Shreds are deduplicated before insertion to save blockstore writes for the
+// copies received through both turbine and repair, or repaired more than once.
+const DEDUPER_FALSE_POSITIVE_RATE: f64 = 0.001;
+const DEDUPER_NUM_BITS: u64 = 63_999_979; // 8MB
+const DEDUPER_RESET_CYCLE: Duration = Duration::from_secs(2 * 60);
+
 type ShredPayload = Vec<u8>;
 type DuplicateSlotSender = Sender<Slot>;
 pub(crate) type DuplicateSlotReceiver = Receiver<Slot>;
@@ -63,6 +72,7 @@ struct WindowServiceMetrics {
     shred_receiver_elapsed_us: u64,
     prune_shreds_elapsed_us: u64,
     num_shreds_pruned_invalid_repair: usize,
+    num_shreds_deduped: usize,
     num_errors: u64,
     num_errors_blockstore: u64,
     num_errors_cross_beam_recv_timeout: u64,
@@ -100,6 +110,7 @@ impl WindowServiceMetrics {
                 self.num_shreds_pruned_invalid_repair,
                 i64
             ),
+            ("num_shreds_deduped", self.num_shreds_deduped, i64),
             ("num_errors", self.num_errors, i64),
             ("num_errors_blockstore", self.num_errors_blockstore, i64),
             ("num_errors_other", self.num_errors_other, i64),
@@ -275,6 +286,30 @@ fn prune_shreds_invalid_repair(
     assert_eq!(shreds.len(), repair_infos.len());
 }
 
+// Removes the shreds from turbine which were already received.
+fn dedup_shreds(
+    deduper: &Deduper<2, [u8]>,
+    shreds: &mut Vec<Shred>,
+    repair_infos: &mut Vec<Option<RepairMeta>>,
+) {
+    assert_eq!(shreds.len(), repair_infos.len());
+    // The whole payload is the key, so that conflicting versions of a shred
+    // still reach blockstore for duplicate slot detection.
+    // Repair responses matched an outstanding request in
+    // prune_shreds_invalid_repair and are never dropped: shreds are repaired
+    // again after their slot is dumped from blockstore, e.g. for duplicate
+    // slot resolution. They are still recorded, so that copies from turbine
+    // arriving later are dropped.
+    (*shreds, *repair_infos) = std::mem::take(shreds)
+        .into_iter()
+        .zip(std::mem::take(repair_infos))
+        .filter(|(shred, repair_info)| {
+            let is_duplicate = deduper.dedup(shred.payload());
+            repair_info.is_some() || !is_duplicate
+        })
+        .unzip();
+}
+
 #[allow(clippy::too_many_arguments)]
 fn run_insert<F>(
     thread_pool: &ThreadPool,
@@ -288,6 +323,7 @@ fn run_insert<F>(
     retransmit_sender: &Sender<Vec<ShredPayload>>,
     outstanding_requests: &RwLock<OutstandingShredRepairs>,
     reed_solomon_cache: &ReedSolomonCache,
+    deduper: &Deduper<2, [u8]>,
 ) -> Result<()>
 where
     F: Fn(PossibleDuplicateShred),
@@ -335,6 +371,9 @@ where
     let num_shreds = shreds.len();
     prune_shreds_invalid_repair(&mut shreds, &mut repair_infos, outstanding_requests);
     ws_metrics.num_shreds_pruned_invalid_repair = num_shreds - shreds.len();
+    let num_shreds = shreds.len();
+    dedup_shreds(deduper, &mut shreds, &mut repair_infos);
+    ws_metrics.num_shreds_deduped += num_shreds - shreds.len();
     let repairs: Vec<_> = repair_infos
         .iter()
         .map(|repair_info| repair_info.is_some())
@@ -494,7 +533,10 @@ impl WindowService {
                 let mut metrics = BlockstoreInsertionMetrics::default();
                 let mut ws_metrics = WindowServiceMetrics::default();
                 let mut last_print = Instant::now();
+                let mut rng = rand::thread_rng();
+                let mut deduper = Deduper::<2, [u8]>::new(&mut rng, DEDUPER_NUM_BITS);
                 while !exit.load(Ordering::Relaxed) {
+                    deduper.maybe_reset(&mut rng, DEDUPER_FALSE_POSITIVE_RATE, DEDUPER_RESET_CYCLE);
                     if let Err(e) = run_insert(
                         &thread_pool,
                         &verified_receiver,
@@ -507,6 +549,7 @@ impl WindowService {
                         &retransmit_sender,
                         &outstanding_requests,
                         &reed_solomon_cache,
+                        &deduper,
                     ) {
                         ws_metrics.record_error(&e);
                         if Self::should_exit_on_error(e, &handle_error) {
@@ -758,4 +801,66 @@ mod test {
         assert!(repair_infos[0].is_none());
         assert_eq!(repair_infos[1].as_ref().unwrap().nonce, nonce);
     }
+
+    #[test]
+    fn test_dedup_shreds() {
+        let mut rng = rand::thread_rng();
+        let deduper = Deduper::<2, [u8]>::new(&mut rng, /*num_bits:*/ 640_007);
+        let keypair = Keypair::new();
+        let entries = create_ticks(5, 0, Hash::default());
+        let shred = local_entries_to_shred(&entries, 5, 4, &keypair).swap_remove(0);
+        let other_shred = local_entries_to_shred(&entries, 6, 5, &keypair).swap_remove(0);
+        let conflicting_shred =
+            local_entries_to_shred(&create_ticks(5, 0, Hash::new_unique()), 5, 4, &keypair)
+                .swap_remove(0);
+        assert_eq!(shred.id(), conflicting_shred.id());
+
+        // the same shred from turbine twice
+        let mut shreds = vec![shred.clone(), other_shred.clone(), shred.clone()];
+        let mut repair_infos = vec![None, None, None];
+        dedup_shreds(&deduper, &mut shreds, &mut repair_infos);
+        assert_eq!(shreds, vec![shred.clone(), other_shred.clone()]);
+        assert!(repair_infos.iter().all(Option::is_none));
+
+        // conflicting versions of a shred are not deduplicated
+        let mut shreds = vec![conflicting_shred.clone(), other_shred.clone()];
+        let mut repair_infos = vec![None, None];
+        dedup_shreds(&deduper, &mut shreds, &mut repair_infos);
+        assert_eq!(shreds, vec![conflicting_shred]);
+        assert_eq!(repair_infos.len(), 1);
+        assert!(repair_infos[0].is_none());
+    }
+
+    #[test]
+    fn test_dedup_shreds_repair_after_dump() {
+        let mut rng = rand::thread_rng();
+        let deduper = Deduper::<2, [u8]>::new(&mut rng, /*num_bits:*/ 640_007);
+        let keypair = Keypair::new();
+        let entries = create_ticks(5, 0, Hash::default());
+        let shred = local_entries_to_shred(&entries, 5, 4, &keypair).swap_remove(0);
+
+        // the shred is received from turbine and inserted
+        let mut shreds = vec![shred.clone()];
+        let mut repair_infos = vec![None];
+        dedup_shreds(&deduper, &mut shreds, &mut repair_infos);
+        assert_eq!(shreds, vec![shred.clone()]);
+
+        // after the slot is dumped, the shred is repaired again, possibly
+        // more than once, and each repair response reaches blockstore
+        for nonce in 0..2 {
+            let mut shreds = vec![shred.clone()];
+            let mut repair_infos = vec![Some(RepairMeta { nonce })];
+            dedup_shreds(&deduper, &mut shreds, &mut repair_infos);
+            assert_eq!(shreds, vec![shred.clone()]);
+            assert_eq!(repair_infos.len(), 1);
+            assert_eq!(repair_infos[0].as_ref().unwrap().nonce, nonce);
+        }
+
+        // while copies from turbine are still dropped
+        let mut shreds = vec![shred];
+        let mut repair_infos = vec![None];
+        dedup_shreds(&deduper, &mut shreds, &mut repair_infos);
+        assert!(shreds.is_empty());
+        assert!(repair_infos.is_empty());
+    }
 }
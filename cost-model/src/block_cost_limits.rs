@@ -4,9 +4,13 @@ use {
     lazy_static::lazy_static,
     solana_sdk::{
         address_lookup_table, bpf_loader, bpf_loader_deprecated, bpf_loader_upgradeable,
-        compute_budget, ed25519_program, loader_v4, pubkey::Pubkey, secp256k1_program,
+        compute_budget, ed25519_program,
+        feature_set::{self, FeatureSet},
+        loader_v4,
+        pubkey::Pubkey,
+        secp256k1_program,
     },
-    std::collections::HashMap,
+    std::{collections::HashMap, fmt},
 };
 
 /// Static configurations:
@@ -78,6 +82,126 @@ pub const MAX_VOTE_UNITS: u64 = (MAX_BLOCK_UNITS as f64 * 0.75_f64) as u64;
 #[cfg(test)]
 static_assertions::const_assert_eq!(MAX_VOTE_UNITS, 36_000_000);
 
+/// Number of compute units for one write lock once
+/// `cost_model_write_lock_hotspot_limits` is active, so that transactions
+/// locking many accounts are packed less densely
+pub const HOTSPOT_WRITE_LOCK_UNITS: u64 = COMPUTE_UNIT_TO_US_RATIO * 20;
+
+/// Number of compute units that a writable account in a block is allowed once
+/// `cost_model_write_lock_hotspot_limits` is active, sets at 75% of
+/// MAX_WRITABLE_ACCOUNT_UNITS so that a single hot account cannot take up as
+/// much of a block's replay time
+pub const HOTSPOT_MAX_WRITABLE_ACCOUNT_UNITS: u64 = MAX_WRITABLE_ACCOUNT_UNITS / 4 * 3;
+
+#[cfg(test)]
+static_assertions::const_assert_eq!(HOTSPOT_MAX_WRITABLE_ACCOUNT_UNITS, 9_000_000);
+
+/// The per-write-lock cost and the per-account limit, which are
+/// controlled by feature activations
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WriteLockCostLimits {
+    pub write_lock_units: u64,
+    pub account_cost_limit: u64,
+}
+
+impl Default for WriteLockCostLimits {
+    fn default() -> Self {
+        Self {
+            write_lock_units: WRITE_LOCK_UNITS,
+            account_cost_limit: MAX_WRITABLE_ACCOUNT_UNITS,
+        }
+    }
+}
+
+impl WriteLockCostLimits {
+    pub fn new(feature_set: &FeatureSet) -> Self {
+        if feature_set.is_active(&feature_set::cost_model_write_lock_hotspot_limits::id()) {
+            Self {
+                write_lock_units: HOTSPOT_WRITE_LOCK_UNITS,
+                account_cost_limit: HOTSPOT_MAX_WRITABLE_ACCOUNT_UNITS,
+            }
+        } else {
+            Self::default()
+        }
+    }
+}
+
+/// Limits below the protocol limits which a block producer packs its own
+/// blocks to, e.g. to react to an incident. Blocks are still validated against
+/// the protocol limits. A limit of `None` is the protocol limit.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BlockProductionLimits {
+    /// Number of compute units of a block
+    pub block_cost_limit: Option<u64>,
+    /// Number of compute units that a writable account in a block is allowed
+    pub account_cost_limit: Option<u64>,
+    /// Number of compute units that vote transactions in a block are allowed,
+    /// the rest of the block is left to other transactions
+    pub vote_cost_limit: Option<u64>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum BlockProductionLimitsError {
+    /// The limit `name` is zero or above `max_limit`, which is the protocol
+    /// limit or the block limit
+    OutOfBounds {
+        name: &'static str,
+        limit: u64,
+        max_limit: u64,
+    },
+}
+
+impl fmt::Display for BlockProductionLimitsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::OutOfBounds {
+                name,
+                limit,
+                max_limit,
+            } => write!(
+                f,
+                "{name} of {limit} compute units is not between 1 and {max_limit}"
+            ),
+        }
+    }
+}
+
+impl BlockProductionLimits {
+    /// Check that each limit is within the protocol limits, given the
+    /// writable account limit of the active features, and within the block
+    /// limit
+    pub fn check(
+        &self,
+        protocol_account_cost_limit: u64,
+    ) -> Result<(), BlockProductionLimitsError> {
+        let block_cost_limit = self.block_cost_limit.unwrap_or(MAX_BLOCK_UNITS);
+        for (name, limit, max_limit) in [
+            ("block limit", self.block_cost_limit, MAX_BLOCK_UNITS),
+            (
+                "writable account limit",
+                self.account_cost_limit,
+                protocol_account_cost_limit.min(block_cost_limit),
+            ),
+            (
+                "vote limit",
+                self.vote_cost_limit,
+                MAX_VOTE_UNITS.min(block_cost_limit),
+            ),
+        ] {
+            if let Some(limit) = limit {
+                if limit == 0 || limit > max_limit {
+                    return Err(BlockProductionLimitsError::OutOfBounds {
+                        name,
+                        limit,
+                        max_limit,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
 /// The maximum allowed size, in bytes, that accounts data can grow, per block.
 /// This can also be thought of as the maximum size of new allocations per block.
 pub const MAX_BLOCK_ACCOUNTS_DATA_SIZE_DELTA: u64 = 100_000_000;
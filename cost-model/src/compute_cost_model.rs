@@ -0,0 +1,206 @@
+//! `compute_cost_model` collects the compute unit costs which the cost model
+//! charges for transactions and which the runtime charges for builtin
+//! programs and syscalls, so that the scheduler, the cost tracker, fee
+//! estimators and simulators all query the same table.
+//!
+
+use {
+    crate::block_cost_limits::*,
+    solana_program_runtime::compute_budget::ComputeBudget,
+    solana_sdk::{feature_set::FeatureSet, pubkey::Pubkey},
+};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ComputeCostModel {
+    compute_budget: ComputeBudget,
+    write_lock_cost_limits: WriteLockCostLimits,
+}
+
+impl ComputeCostModel {
+    /// Query the syscall costs of `compute_budget` instead of the default ones
+    pub fn new(compute_budget: ComputeBudget) -> Self {
+        Self {
+            compute_budget,
+            ..Self::default()
+        }
+    }
+
+    /// Query the costs and limits which `feature_set` has changed
+    pub fn with_feature_set(mut self, feature_set: &FeatureSet) -> Self {
+        self.write_lock_cost_limits = WriteLockCostLimits::new(feature_set);
+        self
+    }
+
+    /// Number of compute units for one signature verification
+    pub fn signature_cost(&self) -> u64 {
+        SIGNATURE_COST
+    }
+
+    /// Number of compute units for one write lock
+    pub fn write_lock_cost(&self) -> u64 {
+        self.write_lock_cost_limits.write_lock_units
+    }
+
+    /// Number of compute units that a writable account in a block is allowed
+    pub fn account_cost_limit(&self) -> u64 {
+        self.write_lock_cost_limits.account_cost_limit
+    }
+
+    /// Number of instruction data bytes per compute unit
+    pub fn instruction_data_bytes_cost(&self) -> u64 {
+        INSTRUCTION_DATA_BYTES_COST
+    }
+
+    /// Number of compute units per 32k page of heap or loaded account data
+    pub fn heap_cost(&self) -> u64 {
+        self.compute_budget.heap_cost
+    }
+
+    /// Number of compute units for an instruction of the builtin program
+    /// `program_id`, or `None` if it is not a builtin program
+    pub fn builtin_instruction_cost(&self, program_id: &Pubkey) -> Option<u64> {
+        BUILT_IN_INSTRUCTION_COSTS.get(program_id).copied()
+    }
+
+    /// Number of compute units for an instruction of each builtin program,
+    /// sorted by program id
+    pub fn builtin_instruction_costs(&self) -> Vec<(Pubkey, u64)> {
+        let mut builtin_instruction_costs: Vec<_> = BUILT_IN_INSTRUCTION_COSTS
+            .iter()
+            .map(|(program_id, cost)| (*program_id, *cost))
+            .collect();
+        builtin_instruction_costs.sort_unstable();
+        builtin_instruction_costs
+    }
+
+    /// Number of compute units for the syscall cost named `name`, see
+    /// [`Self::syscall_costs`] for the names
+    pub fn syscall_cost(&self, name: &str) -> Option<u64> {
+        self.syscall_costs()
+            .into_iter()
+            .find(|(cost_name, _)| *cost_name == name)
+            .map(|(_, cost)| cost)
+    }
+
+    /// Number of compute units charged by syscalls, named after the
+    /// `ComputeBudget` field each of them is configured by
+    pub fn syscall_costs(&self) -> Vec<(&'static str, u64)> {
+        macro_rules! costs {
+            ($($field:ident),* $(,)?) => {
+                vec![$((stringify!($field), self.compute_budget.$field)),*]
+            };
+        }
+        costs!(
+            syscall_base_cost,
+            log_64_units,
+            log_pubkey_units,
+            create_program_address_units,
+            invoke_units,
+            cpi_bytes_per_unit,
+            sha256_base_cost,
+            sha256_byte_cost,
+            sysvar_base_cost,
+            mem_op_base_cost,
+            secp256k1_recover_cost,
+            curve25519_edwards_validate_point_cost,
+            curve25519_edwards_add_cost,
+            curve25519_edwards_subtract_cost,
+            curve25519_edwards_multiply_cost,
+            curve25519_edwards_msm_base_cost,
+            curve25519_edwards_msm_incremental_cost,
+            curve25519_ristretto_validate_point_cost,
+            curve25519_ristretto_add_cost,
+            curve25519_ristretto_subtract_cost,
+            curve25519_ristretto_multiply_cost,
+            curve25519_ristretto_msm_base_cost,
+            curve25519_ristretto_msm_incremental_cost,
+            alt_bn128_addition_cost,
+            alt_bn128_multiplication_cost,
+            alt_bn128_pairing_one_pair_cost_first,
+            alt_bn128_pairing_one_pair_cost_other,
+            alt_bn128_g1_compress,
+            alt_bn128_g1_decompress,
+            alt_bn128_g2_compress,
+            alt_bn128_g2_decompress,
+            big_modular_exponentiation_cost,
+            poseidon_cost_coefficient_a,
+            poseidon_cost_coefficient_c,
+            get_remaining_compute_units_cost,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, solana_sdk::system_program};
+
+    #[test]
+    fn test_builtin_instruction_costs() {
+        let compute_cost_model = ComputeCostModel::default();
+        assert_eq!(
+            compute_cost_model.builtin_instruction_cost(&system_program::id()),
+            Some(solana_system_program::system_processor::DEFAULT_COMPUTE_UNITS)
+        );
+        assert_eq!(
+            compute_cost_model.builtin_instruction_cost(&Pubkey::new_unique()),
+            None
+        );
+
+        let builtin_instruction_costs = compute_cost_model.builtin_instruction_costs();
+        assert_eq!(
+            builtin_instruction_costs.len(),
+            BUILT_IN_INSTRUCTION_COSTS.len()
+        );
+        for (program_id, cost) in builtin_instruction_costs {
+            assert_eq!(
+                compute_cost_model.builtin_instruction_cost(&program_id),
+                Some(cost)
+            );
+        }
+    }
+
+    #[test]
+    fn test_write_lock_cost_limits() {
+        let compute_cost_model = ComputeCostModel::default();
+        assert_eq!(compute_cost_model.write_lock_cost(), WRITE_LOCK_UNITS);
+        assert_eq!(
+            compute_cost_model.account_cost_limit(),
+            MAX_WRITABLE_ACCOUNT_UNITS
+        );
+
+        let compute_cost_model = compute_cost_model.with_feature_set(&FeatureSet::default());
+        assert_eq!(compute_cost_model.write_lock_cost(), WRITE_LOCK_UNITS);
+
+        let compute_cost_model = compute_cost_model.with_feature_set(&FeatureSet::all_enabled());
+        assert_eq!(
+            compute_cost_model.write_lock_cost(),
+            HOTSPOT_WRITE_LOCK_UNITS
+        );
+        assert_eq!(
+            compute_cost_model.account_cost_limit(),
+            HOTSPOT_MAX_WRITABLE_ACCOUNT_UNITS
+        );
+    }
+
+    #[test]
+    fn test_syscall_costs() {
+        let compute_budget = ComputeBudget {
+            sha256_base_cost: 42,
+            ..ComputeBudget::default()
+        };
+        let compute_cost_model = ComputeCostModel::new(compute_budget);
+        assert_eq!(
+            compute_cost_model.syscall_cost("sha256_base_cost"),
+            Some(42)
+        );
+        assert_eq!(
+            compute_cost_model.syscall_cost("log_64_units"),
+            Some(compute_budget.log_64_units)
+        );
+        assert_eq!(compute_cost_model.syscall_cost("compute_unit_limit"), None);
+        assert_eq!(
+            ComputeCostModel::default().syscall_cost("sha256_base_cost"),
+            Some(ComputeBudget::default().sha256_base_cost)
+        );
+    }
+}
@@ -6,14 +6,11 @@
 //!
 
 use {
-    crate::{block_cost_limits::*, transaction_cost::*},
+    crate::{block_cost_limits::*, compute_cost_model::ComputeCostModel, transaction_cost::*},
     log::*,
-    solana_program_runtime::{
-        compute_budget::DEFAULT_HEAP_COST,
-        compute_budget_processor::{
-            process_compute_budget_instructions, DEFAULT_INSTRUCTION_COMPUTE_UNIT_LIMIT,
-            MAX_COMPUTE_UNIT_LIMIT,
-        },
+    solana_program_runtime::compute_budget_processor::{
+        process_compute_budget_instructions, DEFAULT_INSTRUCTION_COMPUTE_UNIT_LIMIT,
+        MAX_COMPUTE_UNIT_LIMIT,
     },
     solana_sdk::{
         borsh1::try_from_slice_unchecked,
@@ -54,7 +51,7 @@ impl CostModel {
     }
 
     fn get_signature_cost(transaction: &SanitizedTransaction) -> u64 {
-        transaction.signatures().len() as u64 * SIGNATURE_COST
+        transaction.signatures().len() as u64 * ComputeCostModel::default().signature_cost()
     }
 
     fn get_writable_accounts(transaction: &SanitizedTransaction) -> Vec<Pubkey> {
@@ -85,7 +82,10 @@ impl CostModel {
             } else {
                 tx_cost.writable_accounts.len() as u64
             };
-        tx_cost.write_lock_cost = WRITE_LOCK_UNITS.saturating_mul(num_write_locks);
+        tx_cost.write_lock_cost = ComputeCostModel::default()
+            .with_feature_set(feature_set)
+            .write_lock_cost()
+            .saturating_mul(num_write_locks);
     }
 
     fn get_transaction_cost(
@@ -93,6 +93,7 @@ impl CostModel {
         transaction: &SanitizedTransaction,
         feature_set: &FeatureSet,
     ) {
+        let compute_cost_model = ComputeCostModel::default();
         let mut builtin_costs = 0u64;
         let mut bpf_costs = 0u64;
         let mut loaded_accounts_data_size_cost = 0u64;
@@ -101,8 +102,8 @@ impl CostModel {
 
         for (program_id, instruction) in transaction.message().program_instructions_iter() {
             // to keep the same behavior, look for builtin first
-            if let Some(builtin_cost) = BUILT_IN_INSTRUCTION_COSTS.get(program_id) {
-                builtin_costs = builtin_costs.saturating_add(*builtin_cost);
+            if let Some(builtin_cost) = compute_cost_model.builtin_instruction_cost(program_id) {
+                builtin_costs = builtin_costs.saturating_add(builtin_cost);
             } else {
                 bpf_costs = bpf_costs
                     .saturating_add(u64::from(DEFAULT_INSTRUCTION_COMPUTE_UNIT_LIMIT))
@@ -141,7 +142,7 @@ impl CostModel {
                 {
                     loaded_accounts_data_size_cost = FeeStructure::calculate_memory_usage_cost(
                         usize::try_from(compute_budget_limits.loaded_accounts_bytes).unwrap(),
-                        DEFAULT_HEAP_COST,
+                        compute_cost_model.heap_cost(),
                     )
                 }
             }
@@ -154,7 +155,8 @@ impl CostModel {
         tx_cost.builtins_execution_cost = builtin_costs;
         tx_cost.bpf_execution_cost = bpf_costs;
         tx_cost.loaded_accounts_data_size_cost = loaded_accounts_data_size_cost;
-        tx_cost.data_bytes_cost = data_bytes_len_total / INSTRUCTION_DATA_BYTES_COST;
+        tx_cost.data_bytes_cost =
+            data_bytes_len_total / compute_cost_model.instruction_data_bytes_cost();
     }
 
     fn calculate_account_data_size_on_deserialized_system_instruction(
@@ -359,11 +361,28 @@ mod tests {
         {
             let tx_cost =
                 CostModel::calculate_cost(&simple_transaction, &FeatureSet::all_enabled());
-            assert_eq!(2 * WRITE_LOCK_UNITS, tx_cost.write_lock_cost());
+            assert_eq!(2 * HOTSPOT_WRITE_LOCK_UNITS, tx_cost.write_lock_cost());
             assert_eq!(1, tx_cost.writable_accounts().len());
         }
     }
 
+    #[test]
+    fn test_cost_model_write_lock_hotspot_limits() {
+        let (mint_keypair, start_hash) = test_setup();
+        let simple_transaction = SanitizedTransaction::from_transaction_for_tests(
+            system_transaction::transfer(&mint_keypair, &Pubkey::new_unique(), 2, start_hash),
+        );
+
+        let mut feature_set = FeatureSet::all_enabled();
+        feature_set.deactivate(&feature_set::cost_model_write_lock_hotspot_limits::id());
+        let tx_cost = CostModel::calculate_cost(&simple_transaction, &feature_set);
+        assert_eq!(2 * WRITE_LOCK_UNITS, tx_cost.write_lock_cost());
+
+        feature_set.activate(&feature_set::cost_model_write_lock_hotspot_limits::id(), 0);
+        let tx_cost = CostModel::calculate_cost(&simple_transaction, &feature_set);
+        assert_eq!(2 * HOTSPOT_WRITE_LOCK_UNITS, tx_cost.write_lock_cost());
+    }
+
     #[test]
     fn test_cost_model_compute_budget_transaction() {
         let (mint_keypair, start_hash) = test_setup();
@@ -552,7 +571,7 @@ mod tests {
             start_hash,
         ));
 
-        let expected_account_cost = WRITE_LOCK_UNITS * 2;
+        let expected_account_cost = HOTSPOT_WRITE_LOCK_UNITS * 2;
         let expected_execution_cost = BUILT_IN_INSTRUCTION_COSTS
             .get(&system_program::id())
             .unwrap();
@@ -622,7 +641,7 @@ mod tests {
 
         let feature_set = FeatureSet::all_enabled();
         assert!(feature_set.is_active(&include_loaded_accounts_data_size_in_fee_calculation::id()));
-        let expected_account_cost = WRITE_LOCK_UNITS * 2;
+        let expected_account_cost = HOTSPOT_WRITE_LOCK_UNITS * 2;
         let expected_execution_cost = BUILT_IN_INSTRUCTION_COSTS
             .get(&system_program::id())
             .unwrap()
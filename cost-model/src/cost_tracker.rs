@@ -58,6 +58,8 @@ pub struct CostTracker {
     vote_cost: u64,
     transaction_count: u64,
     account_data_size: u64,
+    /// number of transactions rejected by each writable account at its limit
+    account_limit_hits: HashMap<Pubkey, u64>,
 }
 
 impl Default for CostTracker {
@@ -77,6 +79,7 @@ impl Default for CostTracker {
             vote_cost: 0,
             transaction_count: 0,
             account_data_size: 0,
+            account_limit_hits: HashMap::new(),
         }
     }
 }
@@ -94,8 +97,41 @@ impl CostTracker {
         self.vote_cost_limit = vote_cost_limit;
     }
 
+    /// allows to adjust the writable account limit, e.g. after feature activations
+    pub fn set_account_cost_limit(&mut self, account_cost_limit: u64) {
+        self.account_cost_limit = account_cost_limit;
+    }
+
+    pub fn account_cost_limit(&self) -> u64 {
+        self.account_cost_limit
+    }
+
+    pub fn block_cost_limit(&self) -> u64 {
+        self.block_cost_limit
+    }
+
+    pub fn vote_cost_limit(&self) -> u64 {
+        self.vote_cost_limit
+    }
+
+    /// Lower the limits to the ones that a block producer chose for its own
+    /// blocks; limits are never raised above the current ones
+    pub fn apply_block_production_limits(&mut self, limits: &BlockProductionLimits) {
+        let lower = |limit: u64, production_limit: Option<u64>| {
+            production_limit.map_or(limit, |production_limit| production_limit.min(limit))
+        };
+        self.account_cost_limit = lower(self.account_cost_limit, limits.account_cost_limit);
+        self.block_cost_limit = lower(self.block_cost_limit, limits.block_cost_limit);
+        self.vote_cost_limit = lower(self.vote_cost_limit, limits.vote_cost_limit);
+    }
+
     pub fn try_add(&mut self, tx_cost: &TransactionCost) -> Result<u64, CostTrackerError> {
-        self.would_fit(tx_cost)?;
+        self.would_fit(tx_cost).map_err(|err| {
+            if err == CostTrackerError::WouldExceedAccountMaxLimit {
+                self.record_account_limit_hits(tx_cost);
+            }
+            err
+        })?;
         self.add_transaction_cost(tx_cost);
         Ok(self.block_cost)
     }
@@ -131,6 +167,10 @@ impl CostTracker {
         self.block_cost
     }
 
+    pub fn block_cost_limit(&self) -> u64 {
+        self.block_cost_limit
+    }
+
     pub fn transaction_count(&self) -> u64 {
         self.transaction_count
     }
@@ -142,6 +182,7 @@ impl CostTracker {
         }
 
         let (costliest_account, costliest_account_cost) = self.find_costliest_account();
+        let (most_limited_account, most_limited_account_hits) = self.find_most_limited_account();
 
         datapoint_info!(
             "cost_tracker_stats",
@@ -153,9 +194,54 @@ impl CostTracker {
             ("costliest_account", costliest_account.to_string(), String),
             ("costliest_account_cost", costliest_account_cost as i64, i64),
             ("account_data_size", self.account_data_size, i64),
+            ("account_cost_limit", self.account_cost_limit, i64),
+            (
+                "number_of_accounts_at_limit",
+                self.account_limit_hits.len() as i64,
+                i64
+            ),
+            (
+                "account_limit_hits",
+                self.account_limit_hits.values().sum::<u64>() as i64,
+                i64
+            ),
+            (
+                "most_limited_account",
+                most_limited_account.to_string(),
+                String
+            ),
+            (
+                "most_limited_account_hits",
+                most_limited_account_hits as i64,
+                i64
+            ),
         );
     }
 
+    fn find_most_limited_account(&self) -> (Pubkey, u64) {
+        self.account_limit_hits
+            .iter()
+            .max_by_key(|(_, &hits)| hits)
+            .map(|(&pubkey, &hits)| (pubkey, hits))
+            .unwrap_or_default()
+    }
+
+    /// Count a rejected transaction against each of its writable accounts
+    /// which would exceed the account limit
+    fn record_account_limit_hits(&mut self, tx_cost: &TransactionCost) {
+        let cost = tx_cost.sum();
+        for account_key in tx_cost.writable_accounts().iter() {
+            let account_cost = self
+                .cost_by_writable_accounts
+                .get(account_key)
+                .copied()
+                .unwrap_or_default();
+            if account_cost.saturating_add(cost) > self.account_cost_limit {
+                *self.account_limit_hits.entry(*account_key).or_insert(0) += 1;
+            }
+        }
+    }
+
     fn find_costliest_account(&self) -> (Pubkey, u64) {
         self.cost_by_writable_accounts
             .iter()
@@ -500,6 +586,36 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cost_tracker_account_limit_hits() {
+        let (mint_keypair, start_hash) = test_setup();
+        let (_tx, tx_cost) = build_simple_transaction(&mint_keypair, &start_hash);
+        let cost = tx_cost.sum();
+
+        // build testee to have capacity for two transactions, but only one per account
+        let mut testee = CostTracker::new(cost * 2, cost * 2, cost * 2);
+        testee.set_account_cost_limit(cost);
+        assert_eq!(cost, testee.account_cost_limit());
+        assert!(testee.try_add(&tx_cost).is_ok());
+        assert_eq!(testee.find_most_limited_account(), (Pubkey::default(), 0));
+
+        for hits in 1..=2 {
+            assert_eq!(
+                testee.try_add(&tx_cost),
+                Err(CostTrackerError::WouldExceedAccountMaxLimit)
+            );
+            assert_eq!(
+                testee.find_most_limited_account(),
+                (mint_keypair.pubkey(), hits)
+            );
+        }
+        assert_eq!(cost, testee.block_cost());
+
+        // raising the limit makes room for the transaction
+        testee.set_account_cost_limit(cost * 2);
+        assert!(testee.try_add(&tx_cost).is_ok());
+    }
+
     #[test]
     fn test_cost_tracker_reach_vote_limit() {
         let (mint_keypair, start_hash) = test_setup();
@@ -822,4 +938,103 @@ mod tests {
         assert_eq!(0, cost_tracker.vote_cost);
         assert_eq!(0, cost_tracker.account_data_size);
     }
+
+    #[test]
+    fn test_apply_block_production_limits() {
+        let mut cost_tracker = CostTracker::default();
+        cost_tracker.apply_block_production_limits(&BlockProductionLimits::default());
+        assert_eq!(
+            cost_tracker.account_cost_limit(),
+            MAX_WRITABLE_ACCOUNT_UNITS
+        );
+        assert_eq!(cost_tracker.block_cost_limit(), MAX_BLOCK_UNITS);
+        assert_eq!(cost_tracker.vote_cost_limit(), MAX_VOTE_UNITS);
+
+        cost_tracker.apply_block_production_limits(&BlockProductionLimits {
+            block_cost_limit: Some(MAX_BLOCK_UNITS / 2),
+            account_cost_limit: Some(MAX_WRITABLE_ACCOUNT_UNITS + 1),
+            vote_cost_limit: None,
+        });
+        assert_eq!(cost_tracker.block_cost_limit(), MAX_BLOCK_UNITS / 2);
+        // limits are never raised
+        assert_eq!(
+            cost_tracker.account_cost_limit(),
+            MAX_WRITABLE_ACCOUNT_UNITS
+        );
+        assert_eq!(cost_tracker.vote_cost_limit(), MAX_VOTE_UNITS);
+
+        let (_mint_keypair, start_hash) = test_setup();
+        let (_tx, tx_cost) = build_simple_transaction(&Keypair::new(), &start_hash);
+        cost_tracker.apply_block_production_limits(&BlockProductionLimits {
+            block_cost_limit: Some(tx_cost.sum()),
+            ..BlockProductionLimits::default()
+        });
+        assert!(cost_tracker.try_add(&tx_cost).is_ok());
+        let (_tx, tx_cost) = build_simple_transaction(&Keypair::new(), &start_hash);
+        assert_eq!(
+            cost_tracker.try_add(&tx_cost),
+            Err(CostTrackerError::WouldExceedBlockMaxLimit)
+        );
+    }
+
+    #[test]
+    fn test_check_block_production_limits() {
+        assert_eq!(
+            BlockProductionLimits::default().check(MAX_WRITABLE_ACCOUNT_UNITS),
+            Ok(())
+        );
+        assert_eq!(
+            BlockProductionLimits {
+                block_cost_limit: Some(MAX_BLOCK_UNITS),
+                account_cost_limit: Some(HOTSPOT_MAX_WRITABLE_ACCOUNT_UNITS),
+                vote_cost_limit: Some(1),
+            }
+            .check(HOTSPOT_MAX_WRITABLE_ACCOUNT_UNITS),
+            Ok(())
+        );
+        assert_eq!(
+            BlockProductionLimits {
+                block_cost_limit: Some(MAX_BLOCK_UNITS + 1),
+                ..BlockProductionLimits::default()
+            }
+            .check(MAX_WRITABLE_ACCOUNT_UNITS),
+            Err(BlockProductionLimitsError::OutOfBounds {
+                name: "block limit",
+                limit: MAX_BLOCK_UNITS + 1,
+                max_limit: MAX_BLOCK_UNITS,
+            })
+        );
+        assert_eq!(
+            BlockProductionLimits {
+                account_cost_limit: Some(MAX_WRITABLE_ACCOUNT_UNITS),
+                ..BlockProductionLimits::default()
+            }
+            .check(HOTSPOT_MAX_WRITABLE_ACCOUNT_UNITS),
+            Err(BlockProductionLimitsError::OutOfBounds {
+                name: "writable account limit",
+                limit: MAX_WRITABLE_ACCOUNT_UNITS,
+                max_limit: HOTSPOT_MAX_WRITABLE_ACCOUNT_UNITS,
+            })
+        );
+        // the vote limit may not exceed a lowered block limit
+        assert_eq!(
+            BlockProductionLimits {
+                block_cost_limit: Some(1_000_000),
+                vote_cost_limit: Some(2_000_000),
+                ..BlockProductionLimits::default()
+            }
+            .check(MAX_WRITABLE_ACCOUNT_UNITS),
+            Err(BlockProductionLimitsError::OutOfBounds {
+                name: "vote limit",
+                limit: 2_000_000,
+                max_limit: 1_000_000,
+            })
+        );
+        assert!(BlockProductionLimits {
+            vote_cost_limit: Some(0),
+            ..BlockProductionLimits::default()
+        }
+        .check(MAX_WRITABLE_ACCOUNT_UNITS)
+        .is_err());
+    }
 }
@@ -3,7 +3,10 @@
 //! transactions within it. Entries cannot be reordered, and its field `num_hashes`
 //! represents an approximate amount of time since the last Entry was created.
 use {
-    crate::poh::Poh,
+    crate::{
+        poh::Poh,
+        poh_verifier::{poh_verifier, PendingPohVerification},
+    },
     crossbeam_channel::{Receiver, Sender},
     dlopen2::symbor::{Container, SymBorApi, Symbol},
     lazy_static::lazy_static,
@@ -280,6 +283,8 @@ pub struct GpuVerificationData {
 pub enum DeviceVerificationData {
     Cpu(),
     Gpu(GpuVerificationData),
+    /// Started by a registered `PohVerifier`
+    External(Option<Box<dyn PendingPohVerification>>),
 }
 
 pub struct EntryVerificationState {
@@ -351,6 +356,29 @@ pub enum EntryVerificationStatus {
 }
 
 impl EntryVerificationState {
+    /// A verification which completed in `poh_duration_us`
+    pub fn new_complete(verified: bool, poh_duration_us: u64) -> Self {
+        Self {
+            verification_status: if verified {
+                EntryVerificationStatus::Success
+            } else {
+                EntryVerificationStatus::Failure
+            },
+            poh_duration_us,
+            device_verification_data: DeviceVerificationData::Cpu(),
+        }
+    }
+
+    /// A verification which completes asynchronously in `pending`, after
+    /// `poh_duration_us` were spent starting it
+    pub fn new_pending(pending: Box<dyn PendingPohVerification>, poh_duration_us: u64) -> Self {
+        Self {
+            verification_status: EntryVerificationStatus::Pending,
+            poh_duration_us,
+            device_verification_data: DeviceVerificationData::External(Some(pending)),
+        }
+    }
+
     pub fn status(&self) -> EntryVerificationStatus {
         self.verification_status
     }
@@ -396,6 +424,20 @@ impl EntryVerificationState {
                 };
                 res
             }
+            DeviceVerificationData::External(pending) => {
+                let Some(pending) = pending.take() else {
+                    return self.verification_status == EntryVerificationStatus::Success;
+                };
+                let finish_time = Instant::now();
+                let res = pending.finish();
+                self.poh_duration_us += timing::duration_as_us(&finish_time.elapsed());
+                self.verification_status = if res {
+                    EntryVerificationStatus::Success
+                } else {
+                    EntryVerificationStatus::Failure
+                };
+                res
+            }
             DeviceVerificationData::Cpu() => {
                 self.verification_status == EntryVerificationStatus::Success
             }
@@ -443,8 +485,8 @@ pub fn start_verify_transactions(
     // We will also use the CPU if no acceleration API is used or if we're skipping
     // the signature verification as we'd have nothing to do on the GPU in that case.
     // TODO: make the CPU-to GPU crossover point dynamic, perhaps based on similar future
-    // heuristics to what might be used in sigverify::ed25519_verify when a dynamic crossover
-    // is introduced for that function (see TODO in sigverify::ed25519_verify)
+    // heuristics to what might be used by sigverify_backend::GpuSigVerifyBackend when a dynamic
+    // crossover is introduced there (see TODO in sigverify_backend)
     let use_cpu = skip_verification
         || api.is_none()
         || entries
@@ -615,6 +657,102 @@ fn compare_hashes(computed_hash: Hash, ref_entry: &Entry) -> bool {
     actual == ref_entry.hash
 }
 
+/// Starts verifying `entries` on the GPU with the perf libs, or verifies them
+/// on the CPU if the perf libs are not loaded
+pub(crate) fn start_verify_gpu(
+    entries: &[Entry],
+    start_hash: &Hash,
+    recyclers: VerifyRecyclers,
+) -> EntryVerificationState {
+    let start = Instant::now();
+    let Some(api) = perf_libs::api() else {
+        return entries.verify_cpu(start_hash);
+    };
+    inc_new_counter_info!("entry_verify-num_entries", entries.len());
+
+    let genesis = [Entry {
+        num_hashes: 0,
+        hash: *start_hash,
+        transactions: vec![],
+    }];
+
+    let hashes: Vec<Hash> = genesis
+        .iter()
+        .chain(entries)
+        .map(|entry| entry.hash)
+        .take(entries.len())
+        .collect();
+
+    let mut hashes_pinned = recyclers.hash_recycler.allocate("poh_verify_hash");
+    hashes_pinned.set_pinnable();
+    hashes_pinned.resize(hashes.len(), Hash::default());
+    hashes_pinned.copy_from_slice(&hashes);
+
+    let mut num_hashes_vec = recyclers
+        .tick_count_recycler
+        .allocate("poh_verify_num_hashes");
+    num_hashes_vec.reserve_and_pin(cmp::max(1, entries.len()));
+    for entry in entries {
+        num_hashes_vec.push(entry.num_hashes.saturating_sub(1));
+    }
+
+    let length = entries.len();
+    let hashes = Arc::new(Mutex::new(hashes_pinned));
+    let hashes_clone = hashes.clone();
+
+    let gpu_verify_thread = thread::Builder::new()
+        .name("solGpuPohVerify".into())
+        .spawn(move || {
+            let mut hashes = hashes_clone.lock().unwrap();
+            let gpu_wait = Instant::now();
+            let res;
+            unsafe {
+                res = (api.poh_verify_many)(
+                    hashes.as_mut_ptr() as *mut u8,
+                    num_hashes_vec.as_ptr(),
+                    length,
+                    1,
+                );
+            }
+            assert!(res == 0, "GPU PoH verify many failed");
+            inc_new_counter_info!(
+                "entry_verify-gpu_thread",
+                timing::duration_as_us(&gpu_wait.elapsed()) as usize
+            );
+            timing::duration_as_us(&gpu_wait.elapsed())
+        })
+        .unwrap();
+
+    let verifications = PAR_THREAD_POOL.install(|| {
+        entries
+            .into_par_iter()
+            .map(|entry| {
+                let answer = entry.hash;
+                let action = if entry.transactions.is_empty() {
+                    if entry.num_hashes == 0 {
+                        VerifyAction::None
+                    } else {
+                        VerifyAction::Tick
+                    }
+                } else {
+                    VerifyAction::Mixin(hash_transactions(&entry.transactions))
+                };
+                (action, answer)
+            })
+            .collect()
+    });
+    let device_verification_data = DeviceVerificationData::Gpu(GpuVerificationData {
+        thread_h: Some(gpu_verify_thread),
+        verifications: Some(verifications),
+        hashes: Some(hashes),
+    });
+    EntryVerificationState {
+        verification_status: EntryVerificationStatus::Pending,
+        poh_duration_us: timing::duration_as_us(&start.elapsed()),
+        device_verification_data,
+    }
+}
+
 // an EntrySlice is a slice of Entries
 pub trait EntrySlice {
     /// Verifies the hashes and counts of a slice of transactions are all consistent.
@@ -780,92 +918,7 @@ impl EntrySlice for [Entry] {
         start_hash: &Hash,
         recyclers: VerifyRecyclers,
     ) -> EntryVerificationState {
-        let start = Instant::now();
-        let Some(api) = perf_libs::api() else {
-            return self.verify_cpu(start_hash);
-        };
-        inc_new_counter_info!("entry_verify-num_entries", self.len());
-
-        let genesis = [Entry {
-            num_hashes: 0,
-            hash: *start_hash,
-            transactions: vec![],
-        }];
-
-        let hashes: Vec<Hash> = genesis
-            .iter()
-            .chain(self)
-            .map(|entry| entry.hash)
-            .take(self.len())
-            .collect();
-
-        let mut hashes_pinned = recyclers.hash_recycler.allocate("poh_verify_hash");
-        hashes_pinned.set_pinnable();
-        hashes_pinned.resize(hashes.len(), Hash::default());
-        hashes_pinned.copy_from_slice(&hashes);
-
-        let mut num_hashes_vec = recyclers
-            .tick_count_recycler
-            .allocate("poh_verify_num_hashes");
-        num_hashes_vec.reserve_and_pin(cmp::max(1, self.len()));
-        for entry in self {
-            num_hashes_vec.push(entry.num_hashes.saturating_sub(1));
-        }
-
-        let length = self.len();
-        let hashes = Arc::new(Mutex::new(hashes_pinned));
-        let hashes_clone = hashes.clone();
-
-        let gpu_verify_thread = thread::Builder::new()
-            .name("solGpuPohVerify".into())
-            .spawn(move || {
-                let mut hashes = hashes_clone.lock().unwrap();
-                let gpu_wait = Instant::now();
-                let res;
-                unsafe {
-                    res = (api.poh_verify_many)(
-                        hashes.as_mut_ptr() as *mut u8,
-                        num_hashes_vec.as_ptr(),
-                        length,
-                        1,
-                    );
-                }
-                assert!(res == 0, "GPU PoH verify many failed");
-                inc_new_counter_info!(
-                    "entry_verify-gpu_thread",
-                    timing::duration_as_us(&gpu_wait.elapsed()) as usize
-                );
-                timing::duration_as_us(&gpu_wait.elapsed())
-            })
-            .unwrap();
-
-        let verifications = PAR_THREAD_POOL.install(|| {
-            self.into_par_iter()
-                .map(|entry| {
-                    let answer = entry.hash;
-                    let action = if entry.transactions.is_empty() {
-                        if entry.num_hashes == 0 {
-                            VerifyAction::None
-                        } else {
-                            VerifyAction::Tick
-                        }
-                    } else {
-                        VerifyAction::Mixin(hash_transactions(&entry.transactions))
-                    };
-                    (action, answer)
-                })
-                .collect()
-        });
-        let device_verification_data = DeviceVerificationData::Gpu(GpuVerificationData {
-            thread_h: Some(gpu_verify_thread),
-            verifications: Some(verifications),
-            hashes: Some(hashes),
-        });
-        EntryVerificationState {
-            verification_status: EntryVerificationStatus::Pending,
-            poh_duration_us: timing::duration_as_us(&start.elapsed()),
-            device_verification_data,
-        }
+        poh_verifier().start_verify(self, start_hash, recyclers)
     }
 
     fn verify_tick_hash_count(&self, tick_hash_count: &mut u64, hashes_per_tick: u64) -> bool {
@@ -1,5 +1,6 @@
 #![allow(clippy::arithmetic_side_effects)]
 pub mod entry;
 pub mod poh;
+pub mod poh_verifier;
 
 extern crate log;
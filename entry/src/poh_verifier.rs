@@ -0,0 +1,200 @@
+//! Backends verifying the proof of history of entries.
+//!
+//! Entries are verified with the backend returned by [`poh_verifier`]: the
+//! one registered with [`register_poh_verifier`] if any, so that a validator
+//! can use other hardware without changes to replay, otherwise the GPU
+//! backend if the perf libs are loaded, and the CPU backend if not.
+
+use {
+    crate::entry::{start_verify_gpu, Entry, EntrySlice, EntryVerificationState, VerifyRecyclers},
+    lazy_static::lazy_static,
+    log::*,
+    solana_perf::perf_libs,
+    solana_sdk::hash::Hash,
+    std::sync::{Arc, RwLock},
+};
+
+/// Verifies that the hashes of entries chain from the previous ones
+pub trait PohVerifier: Send + Sync {
+    /// Name of the backend, for logs
+    fn name(&self) -> &'static str;
+
+    /// Starts verifying that the hashes of `entries` chain from `start_hash`.
+    /// The verification may complete asynchronously, see
+    /// [`EntryVerificationState::new_pending`].
+    fn start_verify(
+        &self,
+        entries: &[Entry],
+        start_hash: &Hash,
+        recyclers: VerifyRecyclers,
+    ) -> EntryVerificationState;
+}
+
+/// A verification started by a `PohVerifier` which completes asynchronously
+pub trait PendingPohVerification: Send {
+    /// Waits for the verification to complete, returning whether the entries are valid
+    fn finish(self: Box<Self>) -> bool;
+}
+
+/// Verifies entries on the rayon thread pool, with the SIMD perf libs if they
+/// are loaded
+#[derive(Debug, Default)]
+pub struct CpuPohVerifier;
+
+impl PohVerifier for CpuPohVerifier {
+    fn name(&self) -> &'static str {
+        "cpu"
+    }
+
+    fn start_verify(
+        &self,
+        entries: &[Entry],
+        start_hash: &Hash,
+        _recyclers: VerifyRecyclers,
+    ) -> EntryVerificationState {
+        entries.verify_cpu(start_hash)
+    }
+}
+
+/// Verifies entries on the GPU with the perf libs, or on the CPU if they are
+/// not loaded
+#[derive(Debug, Default)]
+pub struct GpuPohVerifier;
+
+impl PohVerifier for GpuPohVerifier {
+    fn name(&self) -> &'static str {
+        "gpu"
+    }
+
+    fn start_verify(
+        &self,
+        entries: &[Entry],
+        start_hash: &Hash,
+        recyclers: VerifyRecyclers,
+    ) -> EntryVerificationState {
+        start_verify_gpu(entries, start_hash, recyclers)
+    }
+}
+
+lazy_static! {
+    static ref REGISTERED_POH_VERIFIER: RwLock<Option<Arc<dyn PohVerifier>>> = RwLock::default();
+    static ref CPU_POH_VERIFIER: Arc<dyn PohVerifier> = Arc::new(CpuPohVerifier);
+    static ref GPU_POH_VERIFIER: Arc<dyn PohVerifier> = Arc::new(GpuPohVerifier);
+}
+
+/// Verify entries with `verifier` instead of the default backends
+pub fn register_poh_verifier(verifier: Arc<dyn PohVerifier>) {
+    info!("registering PoH verifier: {}", verifier.name());
+    *REGISTERED_POH_VERIFIER.write().unwrap() = Some(verifier);
+}
+
+/// The backend entries are verified with
+pub fn poh_verifier() -> Arc<dyn PohVerifier> {
+    if let Some(verifier) = REGISTERED_POH_VERIFIER.read().unwrap().as_ref() {
+        return verifier.clone();
+    }
+    if perf_libs::api().is_some() {
+        GPU_POH_VERIFIER.clone()
+    } else {
+        CPU_POH_VERIFIER.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::entry::{create_ticks, EntryVerificationStatus},
+        std::{
+            sync::atomic::{AtomicUsize, Ordering},
+            thread::{self, JoinHandle},
+        },
+    };
+
+    struct PendingThread(JoinHandle<bool>);
+
+    impl PendingPohVerification for PendingThread {
+        fn finish(self: Box<Self>) -> bool {
+            self.0.join().unwrap()
+        }
+    }
+
+    /// Verifies entries on a separate thread, counting the verifications
+    #[derive(Default)]
+    struct ThreadPohVerifier {
+        num_verifications: AtomicUsize,
+    }
+
+    impl PohVerifier for ThreadPohVerifier {
+        fn name(&self) -> &'static str {
+            "thread"
+        }
+
+        fn start_verify(
+            &self,
+            entries: &[Entry],
+            start_hash: &Hash,
+            _recyclers: VerifyRecyclers,
+        ) -> EntryVerificationState {
+            self.num_verifications.fetch_add(1, Ordering::Relaxed);
+            let entries = entries.to_vec();
+            let start_hash = *start_hash;
+            let thread = thread::spawn(move || entries.verify_cpu(&start_hash).finish_verify());
+            EntryVerificationState::new_pending(Box::new(PendingThread(thread)), 0)
+        }
+    }
+
+    /// Unregisters the registered verifier when dropped, so that it does not
+    /// outlive the test registering it, even if the test panics
+    struct RegisteredPohVerifierGuard;
+
+    impl RegisteredPohVerifierGuard {
+        fn register(verifier: Arc<dyn PohVerifier>) -> Self {
+            register_poh_verifier(verifier);
+            Self
+        }
+    }
+
+    impl Drop for RegisteredPohVerifierGuard {
+        fn drop(&mut self) {
+            if let Ok(mut registered_verifier) = REGISTERED_POH_VERIFIER.write() {
+                *registered_verifier = None;
+            }
+        }
+    }
+
+    #[test]
+    fn test_cpu_poh_verifier() {
+        let start_hash = Hash::new_unique();
+        let entries = create_ticks(10, 4, start_hash);
+        let mut state =
+            CpuPohVerifier.start_verify(&entries, &start_hash, VerifyRecyclers::default());
+        assert_eq!(state.status(), EntryVerificationStatus::Success);
+        assert!(state.finish_verify());
+
+        let mut state =
+            CpuPohVerifier.start_verify(&entries, &Hash::default(), VerifyRecyclers::default());
+        assert_eq!(state.status(), EntryVerificationStatus::Failure);
+        assert!(!state.finish_verify());
+    }
+
+    #[test]
+    fn test_register_poh_verifier() {
+        let verifier = Arc::new(ThreadPohVerifier::default());
+        let guard = RegisteredPohVerifierGuard::register(verifier.clone());
+        assert_eq!(poh_verifier().name(), "thread");
+
+        let start_hash = Hash::new_unique();
+        let entries = create_ticks(10, 4, start_hash);
+        let mut state = entries.start_verify(&start_hash, VerifyRecyclers::default());
+        assert_eq!(state.status(), EntryVerificationStatus::Pending);
+        assert!(state.finish_verify());
+        assert_eq!(state.status(), EntryVerificationStatus::Success);
+        assert!(!entries.verify(&Hash::default()));
+        // other tests may verify entries with the registered verifier too
+        assert!(verifier.num_verifications.load(Ordering::Relaxed) >= 2);
+
+        drop(guard);
+        assert_ne!(poh_verifier().name(), "thread");
+    }
+}
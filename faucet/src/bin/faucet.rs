@@ -1,15 +1,19 @@
 use {
     clap::{crate_description, crate_name, values_t, App, Arg},
     log::*,
-    solana_clap_utils::input_parsers::{lamports_of_sol, value_of},
+    solana_clap_utils::{
+        input_parsers::{lamports_of_sol, pubkeys_of, value_of},
+        input_validators::is_pubkey,
+    },
     solana_faucet::{
-        faucet::{run_faucet, Faucet, FAUCET_PORT},
+        faucet::{run_faucet, Faucet, FaucetConfig, FAUCET_PORT},
         socketaddr,
     },
     solana_sdk::signature::read_keypair_file,
     std::{
         collections::HashSet,
         net::{IpAddr, Ipv4Addr, SocketAddr},
+        path::PathBuf,
         sync::{Arc, Mutex},
         thread,
     },
@@ -39,7 +43,7 @@ async fn main() {
                 .long("slice")
                 .value_name("SECS")
                 .takes_value(true)
-                .help("Time slice over which to limit requests to faucet"),
+                .help("Sliding time window over which to limit requests to faucet"),
         )
         .arg(
             Arg::with_name("per_time_cap")
@@ -47,7 +51,25 @@ async fn main() {
                 .alias("cap")
                 .value_name("NUM")
                 .takes_value(true)
-                .help("Request limit for time slice, in SOL"),
+                .help("Request limit for time slice, in SOL, per IP address and per recipient"),
+        )
+        .arg(
+            Arg::with_name("per_ip_cap")
+                .long("per-ip-cap")
+                .value_name("NUM")
+                .takes_value(true)
+                .help(
+                    "Request limit for time slice per IP address, in SOL [default: --per-time-cap]",
+                ),
+        )
+        .arg(
+            Arg::with_name("per_recipient_cap")
+                .long("per-recipient-cap")
+                .value_name("NUM")
+                .takes_value(true)
+                .help(
+                    "Request limit for time slice per recipient, in SOL [default: --per-time-cap]",
+                ),
         )
         .arg(
             Arg::with_name("per_request_cap")
@@ -67,6 +89,28 @@ async fn main() {
                     recipient address will be used to check request limits instead",
                 ),
         )
+        .arg(
+            Arg::with_name("allowed_recipient")
+                .long("allow-recipient")
+                .value_name("PUBKEY")
+                .takes_value(true)
+                .multiple(true)
+                .validator(is_pubkey)
+                .help(
+                    "Allow requests to a particular recipient without the per-recipient \
+                    request limit; the request IP address is still limited",
+                ),
+        )
+        .arg(
+            Arg::with_name("request_log")
+                .long("request-log")
+                .value_name("PATH")
+                .takes_value(true)
+                .help(
+                    "File in which to keep the requests within the time slice, \
+                    so that request limits persist across restarts",
+                ),
+        )
         .get_matches();
 
     let faucet_keypair = read_keypair_file(matches.value_of("keypair").unwrap())
@@ -74,29 +118,41 @@ async fn main() {
 
     let time_slice = value_of(&matches, "slice");
     let per_time_cap = lamports_of_sol(&matches, "per_time_cap");
+    let per_ip_cap = lamports_of_sol(&matches, "per_ip_cap").or(per_time_cap);
+    let per_recipient_cap = lamports_of_sol(&matches, "per_recipient_cap").or(per_time_cap);
     let per_request_cap = lamports_of_sol(&matches, "per_request_cap");
 
     let allowed_ips: HashSet<_> = values_t!(matches.values_of("allowed_ip"), IpAddr)
         .unwrap_or_default()
         .into_iter()
         .collect();
+    let allowed_recipients: HashSet<_> = pubkeys_of(&matches, "allowed_recipient")
+        .unwrap_or_default()
+        .into_iter()
+        .collect();
+    let request_log_path = matches.value_of("request_log").map(PathBuf::from);
 
     let faucet_addr = socketaddr!(Ipv4Addr::UNSPECIFIED, FAUCET_PORT);
 
-    let faucet = Arc::new(Mutex::new(Faucet::new_with_allowed_ips(
+    let faucet = Arc::new(Mutex::new(Faucet::new_with_config(
         faucet_keypair,
-        time_slice,
-        per_time_cap,
-        per_request_cap,
-        allowed_ips,
+        FaucetConfig {
+            time_slice,
+            per_request_cap,
+            per_ip_cap,
+            per_recipient_cap,
+            allowed_ips,
+            allowed_recipients,
+            request_log_path,
+        },
     )));
 
     let faucet1 = faucet.clone();
     thread::spawn(move || loop {
         let time = faucet1.lock().unwrap().time_slice;
         thread::sleep(time);
-        debug!("clearing ip cache");
-        faucet1.lock().unwrap().clear_caches();
+        debug!("pruning request caches");
+        faucet1.lock().unwrap().prune_caches();
     });
 
     run_faucet(faucet, faucet_addr, None).await;
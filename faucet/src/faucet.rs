@@ -1,10 +1,11 @@
 //! The `faucet` module provides an object for launching a Solana Faucet,
 //! which is the custodian of any remaining lamports in a mint.
 //! The Solana Faucet builds and sends airdrop transactions,
-//! checking requests against a single-request cap and a per-IP limit
-//! for a given time time_slice.
+//! checking requests against a single-request cap and per-IP and
+//! per-recipient limits over a sliding time window.
 
 use {
+    crate::request_log::{timestamp, RequestLog, RequestLogFile, RequestLogs, RequestRecord},
     bincode::{deserialize, serialize, serialized_size},
     byteorder::{ByteOrder, LittleEndian},
     crossbeam_channel::{unbounded, Sender},
@@ -26,6 +27,7 @@ use {
         collections::{HashMap, HashSet},
         io::{Read, Write},
         net::{IpAddr, Ipv4Addr, SocketAddr, TcpStream},
+        path::PathBuf,
         sync::{Arc, Mutex},
         thread,
         time::Duration,
@@ -89,14 +91,35 @@ pub enum FaucetTransaction {
     Memo((Transaction, String)),
 }
 
+#[derive(Debug, Default, Clone)]
+pub struct FaucetConfig {
+    /// Seconds of the sliding window over which airdrops are limited,
+    /// `TIME_SLICE` if not set
+    pub time_slice: Option<u64>,
+    /// Lamports a single request may ask for
+    pub per_request_cap: Option<u64>,
+    /// Lamports an IP address may request within the window
+    pub per_ip_cap: Option<u64>,
+    /// Lamports a recipient may receive within the window
+    pub per_recipient_cap: Option<u64>,
+    /// IP addresses exempt from the per-IP cap
+    pub allowed_ips: HashSet<IpAddr>,
+    /// Recipients exempt from the per-recipient cap
+    pub allowed_recipients: HashSet<Pubkey>,
+    /// File persisting the airdrops within the window across restarts
+    pub request_log_path: Option<PathBuf>,
+}
+
 pub struct Faucet {
     faucet_keypair: Keypair,
-    ip_cache: HashMap<IpAddr, u64>,
-    address_cache: HashMap<Pubkey, u64>,
+    request_logs: RequestLogs,
     pub time_slice: Duration,
-    per_time_cap: Option<u64>,
     per_request_cap: Option<u64>,
+    per_ip_cap: Option<u64>,
+    per_recipient_cap: Option<u64>,
     allowed_ips: HashSet<IpAddr>,
+    allowed_recipients: HashSet<Pubkey>,
+    request_log_file: Option<RequestLogFile>,
 }
 
 impl Faucet {
@@ -122,36 +145,96 @@ impl Faucet {
         per_request_cap: Option<u64>,
         allowed_ips: HashSet<IpAddr>,
     ) -> Self {
-        let time_slice = Duration::new(time_input.unwrap_or(TIME_SLICE), 0);
-        if let Some((per_request_cap, per_time_cap)) = per_request_cap.zip(per_time_cap) {
-            if per_time_cap < per_request_cap {
-                warn!(
-                    "per_time_cap {} SOL < per_request_cap {} SOL; \
-                    maximum single requests will fail",
-                    lamports_to_sol(per_time_cap),
-                    lamports_to_sol(per_request_cap),
-                );
+        Self::new_with_config(
+            faucet_keypair,
+            FaucetConfig {
+                time_slice: time_input,
+                per_request_cap,
+                per_ip_cap: per_time_cap,
+                per_recipient_cap: per_time_cap,
+                allowed_ips,
+                ..FaucetConfig::default()
+            },
+        )
+    }
+
+    pub fn new_with_config(faucet_keypair: Keypair, config: FaucetConfig) -> Self {
+        let FaucetConfig {
+            time_slice,
+            per_request_cap,
+            per_ip_cap,
+            per_recipient_cap,
+            allowed_ips,
+            allowed_recipients,
+            request_log_path,
+        } = config;
+        let time_slice = Duration::new(time_slice.unwrap_or(TIME_SLICE), 0);
+        if let Some(per_request_cap) = per_request_cap {
+            for (name, cap) in [
+                ("per_ip_cap", per_ip_cap),
+                ("per_recipient_cap", per_recipient_cap),
+            ] {
+                if let Some(cap) = cap.filter(|cap| *cap < per_request_cap) {
+                    warn!(
+                        "{} {} SOL < per_request_cap {} SOL; \
+                        maximum single requests will fail",
+                        name,
+                        lamports_to_sol(cap),
+                        lamports_to_sol(per_request_cap),
+                    );
+                }
             }
         }
+        let mut request_logs = match &request_log_path {
+            Some(path) => RequestLogs::load(path).unwrap_or_else(|err| {
+                warn!(
+                    "Unable to load faucet request log from {}: {}",
+                    path.display(),
+                    err
+                );
+                RequestLogs::default()
+            }),
+            None => RequestLogs::default(),
+        };
+        request_logs.prune(timestamp(), time_slice.as_secs());
+        let request_log_file = request_log_path.and_then(|path| {
+            RequestLogFile::create(path.clone(), &request_logs)
+                .map_err(|err| {
+                    warn!(
+                        "Unable to open faucet request log at {}: {}",
+                        path.display(),
+                        err
+                    )
+                })
+                .ok()
+        });
         Self {
             faucet_keypair,
-            ip_cache: HashMap::new(),
-            address_cache: HashMap::new(),
+            request_logs,
             time_slice,
-            per_time_cap,
             per_request_cap,
+            per_ip_cap,
+            per_recipient_cap,
             allowed_ips,
+            allowed_recipients,
+            request_log_file,
         }
     }
 
-    pub fn check_time_request_limit<T: LimitByTime + std::fmt::Display>(
+    /// Checks that airdropping `request_amount` more lamports keeps `to`
+    /// within its cap over the window ending at `now`
+    fn check_request_limit<T: LimitByTime + std::fmt::Display>(
         &mut self,
         request_amount: u64,
-        to: T,
+        to: &T,
+        now: u64,
     ) -> Result<(), FaucetError> {
-        let new_total = to.check_cache(self, request_amount);
+        let window = self.time_slice.as_secs();
+        let request_log = to.request_log(self);
+        request_log.prune(now, window);
+        let new_total = request_log.total().saturating_add(request_amount);
         to.datapoint_info(request_amount, new_total);
-        if let Some(cap) = self.per_time_cap {
+        if let Some(cap) = to.cap(self) {
             if new_total > cap {
                 return Err(FaucetError::PerTimeCapExceeded(
                     lamports_to_sol(request_amount),
@@ -164,12 +247,55 @@ impl Faucet {
         Ok(())
     }
 
+    pub fn check_time_request_limit<T: LimitByTime + std::fmt::Display>(
+        &mut self,
+        request_amount: u64,
+        to: T,
+    ) -> Result<(), FaucetError> {
+        let now = timestamp();
+        self.check_request_limit(request_amount, &to, now)?;
+        to.request_log(self).record(now, request_amount);
+        Ok(())
+    }
+
     pub fn clear_caches(&mut self) {
-        self.ip_cache.clear();
-        self.address_cache.clear();
+        self.request_logs.clear();
+        self.compact_request_log();
+    }
+
+    /// Forgets the airdrops that left the window, and drops them from the
+    /// request log file
+    pub fn prune_caches(&mut self) {
+        self.request_logs
+            .prune(timestamp(), self.time_slice.as_secs());
+        self.compact_request_log();
+    }
+
+    fn compact_request_log(&mut self) {
+        if let Some(file) = &mut self.request_log_file {
+            if let Err(err) = file.compact(&self.request_logs) {
+                warn!(
+                    "Unable to compact faucet request log at {}: {}",
+                    file.path().display(),
+                    err
+                );
+            }
+        }
     }
 
-    /// Checks per-request and per-time-ip limits; if both pass, this method returns a signed
+    fn append_request_log(&mut self, records: &[RequestRecord]) {
+        if let Some(file) = &mut self.request_log_file {
+            if let Err(err) = file.append(records) {
+                warn!(
+                    "Unable to append to faucet request log at {}: {}",
+                    file.path().display(),
+                    err
+                );
+            }
+        }
+    }
+
+    /// Checks per-request, per-IP and per-recipient limits; if all pass, this method returns a signed
     /// SystemProgram::Transfer transaction from the faucet keypair to the requested recipient. If
     /// the request exceeds this per-request limit, this method returns a signed SPL Memo
     /// transaction with the memo: `"request too large; req: <REQUEST> SOL cap: <CAP> SOL"`
@@ -213,10 +339,37 @@ impl Faucet {
                         )));
                     }
                 }
-                if !ip.is_loopback() && !self.allowed_ips.contains(&ip) {
-                    self.check_time_request_limit(lamports, ip)?;
+                // Only record the airdrop once it passes all limits
+                let now = timestamp();
+                let limit_ip = !ip.is_loopback() && !self.allowed_ips.contains(&ip);
+                let limit_to = !self.allowed_recipients.contains(&to);
+                if limit_ip {
+                    self.check_request_limit(lamports, &ip, now)?;
+                }
+                if limit_to {
+                    self.check_request_limit(lamports, &to, now)?;
+                }
+                let mut records = Vec::with_capacity(2);
+                if limit_ip {
+                    records.push(RequestRecord::Ip {
+                        ip,
+                        timestamp: now,
+                        lamports,
+                    });
+                }
+                if limit_to {
+                    records.push(RequestRecord::Address {
+                        address: to,
+                        timestamp: now,
+                        lamports,
+                    });
+                }
+                for record in &records {
+                    self.request_logs.record(*record);
+                }
+                if !records.is_empty() {
+                    self.append_request_log(&records);
                 }
-                self.check_time_request_limit(lamports, to)?;
 
                 let transfer_instruction =
                     system_instruction::transfer(&mint_pubkey, &to, lamports);
@@ -448,17 +601,18 @@ async fn process(
 }
 
 pub trait LimitByTime {
-    fn check_cache(&self, faucet: &mut Faucet, request_amount: u64) -> u64;
+    fn request_log<'a>(&self, faucet: &'a mut Faucet) -> &'a mut RequestLog;
+    fn cap(&self, faucet: &Faucet) -> Option<u64>;
     fn datapoint_info(&self, request_amount: u64, new_total: u64);
 }
 
 impl LimitByTime for IpAddr {
-    fn check_cache(&self, faucet: &mut Faucet, request_amount: u64) -> u64 {
-        *faucet
-            .ip_cache
-            .entry(*self)
-            .and_modify(|total| *total = total.saturating_add(request_amount))
-            .or_insert(request_amount)
+    fn request_log<'a>(&self, faucet: &'a mut Faucet) -> &'a mut RequestLog {
+        faucet.request_logs.ip_requests.entry(*self).or_default()
+    }
+
+    fn cap(&self, faucet: &Faucet) -> Option<u64> {
+        faucet.per_ip_cap
     }
 
     fn datapoint_info(&self, request_amount: u64, new_total: u64) {
@@ -472,12 +626,16 @@ impl LimitByTime for IpAddr {
 }
 
 impl LimitByTime for Pubkey {
-    fn check_cache(&self, faucet: &mut Faucet, request_amount: u64) -> u64 {
-        *faucet
-            .address_cache
+    fn request_log<'a>(&self, faucet: &'a mut Faucet) -> &'a mut RequestLog {
+        faucet
+            .request_logs
+            .address_requests
             .entry(*self)
-            .and_modify(|total| *total = total.saturating_add(request_amount))
-            .or_insert(request_amount)
+            .or_default()
+    }
+
+    fn cap(&self, faucet: &Faucet) -> Option<u64> {
+        faucet.per_recipient_cap
     }
 
     fn datapoint_info(&self, request_amount: u64, new_total: u64) {
@@ -514,20 +672,20 @@ mod tests {
         let keypair = Keypair::new();
         let mut faucet = Faucet::new(keypair, None, None, None);
         let ip = socketaddr!(Ipv4Addr::LOCALHOST, 0).ip();
-        assert_eq!(faucet.ip_cache.len(), 0);
+        assert_eq!(faucet.request_logs.ip_requests.len(), 0);
         faucet.check_time_request_limit(1, ip).unwrap();
-        assert_eq!(faucet.ip_cache.len(), 1);
+        assert_eq!(faucet.request_logs.ip_requests.len(), 1);
         faucet.clear_caches();
-        assert_eq!(faucet.ip_cache.len(), 0);
-        assert!(faucet.ip_cache.is_empty());
+        assert_eq!(faucet.request_logs.ip_requests.len(), 0);
+        assert!(faucet.request_logs.ip_requests.is_empty());
 
         let address = Pubkey::new_unique();
-        assert_eq!(faucet.address_cache.len(), 0);
+        assert_eq!(faucet.request_logs.address_requests.len(), 0);
         faucet.check_time_request_limit(1, address).unwrap();
-        assert_eq!(faucet.address_cache.len(), 1);
+        assert_eq!(faucet.request_logs.address_requests.len(), 1);
         faucet.clear_caches();
-        assert_eq!(faucet.address_cache.len(), 0);
-        assert!(faucet.address_cache.is_empty());
+        assert_eq!(faucet.request_logs.address_requests.len(), 0);
+        assert!(faucet.request_logs.address_requests.is_empty());
     }
 
     #[test]
@@ -538,7 +696,8 @@ mod tests {
         let per_request_cap: Option<u64> = Some(100);
         let faucet = Faucet::new(keypair, time_slice, per_time_cap, per_request_cap);
         assert_eq!(faucet.time_slice, Duration::new(TIME_SLICE, 0));
-        assert_eq!(faucet.per_time_cap, per_time_cap);
+        assert_eq!(faucet.per_ip_cap, per_time_cap);
+        assert_eq!(faucet.per_recipient_cap, per_time_cap);
         assert_eq!(faucet.per_request_cap, per_request_cap);
     }
 
@@ -647,6 +806,66 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_faucet_limits_with_config() {
+        let ledger_dir = tempfile::TempDir::new().unwrap();
+        let request_log_path = ledger_dir.path().join("faucet-requests.bin");
+        let allowed_recipient = Pubkey::new_unique();
+        let config = FaucetConfig {
+            per_ip_cap: Some(4),
+            per_recipient_cap: Some(2),
+            allowed_recipients: HashSet::from([allowed_recipient]),
+            request_log_path: Some(request_log_path.clone()),
+            ..FaucetConfig::default()
+        };
+        let request = |lamports, to| FaucetRequest::GetAirdrop {
+            lamports,
+            to,
+            blockhash: Hash::default(),
+        };
+        let ip = socketaddr!([203, 0, 113, 1], 1234).ip();
+        let to = Pubkey::new_unique();
+
+        let mut faucet = Faucet::new_with_config(Keypair::new(), config.clone());
+        faucet
+            .build_airdrop_transaction(request(2, to), ip)
+            .unwrap();
+        // the recipient cap is reached, and the rejected request is not counted
+        // towards the IP cap
+        assert!(faucet
+            .build_airdrop_transaction(request(1, to), ip)
+            .is_err());
+        // allowed recipients are only limited by the IP cap
+        faucet
+            .build_airdrop_transaction(request(2, allowed_recipient), ip)
+            .unwrap();
+        assert!(faucet
+            .build_airdrop_transaction(request(1, allowed_recipient), ip)
+            .is_err());
+
+        // the limits persist across restarts
+        let mut faucet = Faucet::new_with_config(Keypair::new(), config.clone());
+        assert_eq!(faucet.request_logs.ip_requests[&ip].total(), 4);
+        assert!(faucet
+            .build_airdrop_transaction(request(1, Pubkey::new_unique()), ip)
+            .is_err());
+        let other_ip = socketaddr!([203, 0, 113, 2], 1234).ip();
+        assert!(faucet
+            .build_airdrop_transaction(request(1, to), other_ip)
+            .is_err());
+
+        // airdrops leave the window once it elapses
+        let faucet = Faucet::new_with_config(
+            Keypair::new(),
+            FaucetConfig {
+                time_slice: Some(0),
+                ..config
+            },
+        );
+        assert!(faucet.request_logs.ip_requests.is_empty());
+        assert!(faucet.request_logs.address_requests.is_empty());
+    }
+
     #[test]
     fn test_process_faucet_request() {
         let to = solana_sdk::pubkey::new_rand();
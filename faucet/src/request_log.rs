@@ -0,0 +1,353 @@
+//! The `request_log` module tracks the airdrops granted to IP addresses and
+//! recipients over a sliding window, and persists them across faucet restarts.
+//!
+//! Each airdrop is appended to the request log file as it is granted, so that
+//! recording it costs the same however many airdrops the window holds. The
+//! file is compacted down to the airdrops still within the window whenever
+//! the request logs are pruned.
+
+use {
+    log::*,
+    serde_derive::{Deserialize, Serialize},
+    solana_sdk::pubkey::Pubkey,
+    std::{
+        collections::{HashMap, VecDeque},
+        fs::{self, File, OpenOptions},
+        hash::Hash,
+        io::{self, BufRead, BufReader, BufWriter, Write},
+        net::IpAddr,
+        path::{Path, PathBuf},
+        time::{SystemTime, UNIX_EPOCH},
+    },
+};
+
+/// Seconds since the UNIX epoch
+pub fn timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Airdrops granted to an IP address or a recipient, oldest first
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct RequestLog {
+    /// Timestamp and lamports of each airdrop
+    requests: VecDeque<(u64, u64)>,
+}
+
+impl RequestLog {
+    /// Forgets the airdrops granted `window` seconds or more before `now`
+    pub fn prune(&mut self, now: u64, window: u64) {
+        let start = now.saturating_sub(window);
+        while let Some((timestamp, _)) = self.requests.front() {
+            if *timestamp > start {
+                break;
+            }
+            self.requests.pop_front();
+        }
+    }
+
+    /// Lamports airdropped within the window
+    pub fn total(&self) -> u64 {
+        self.requests
+            .iter()
+            .fold(0, |total, (_, lamports)| total.saturating_add(*lamports))
+    }
+
+    pub fn record(&mut self, now: u64, lamports: u64) {
+        self.requests.push_back((now, lamports));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.requests.is_empty()
+    }
+}
+
+/// An airdrop granted to an IP address or a recipient, as appended to the
+/// request log file
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RequestRecord {
+    Ip {
+        ip: IpAddr,
+        timestamp: u64,
+        lamports: u64,
+    },
+    Address {
+        address: Pubkey,
+        timestamp: u64,
+        lamports: u64,
+    },
+}
+
+fn serialize_records<'a>(
+    writer: &mut impl Write,
+    records: impl IntoIterator<Item = &'a RequestRecord>,
+) -> io::Result<()> {
+    for record in records {
+        bincode::serialize_into(&mut *writer, record)
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+    }
+    Ok(())
+}
+
+/// Request logs of the faucet, as persisted across restarts
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct RequestLogs {
+    pub ip_requests: HashMap<IpAddr, RequestLog>,
+    pub address_requests: HashMap<Pubkey, RequestLog>,
+}
+
+fn prune_logs<K: Eq + Hash>(logs: &mut HashMap<K, RequestLog>, now: u64, window: u64) {
+    logs.retain(|_, log| {
+        log.prune(now, window);
+        !log.is_empty()
+    });
+}
+
+impl RequestLogs {
+    /// Replays the airdrops appended at `path`, or returns empty logs if there
+    /// are none. A record left incomplete by a crash while appending it is
+    /// ignored.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let file = match File::open(path) {
+            Ok(file) => file,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(err) => return Err(err),
+        };
+        let mut reader = BufReader::new(file);
+        let mut logs = Self::default();
+        while !reader.fill_buf()?.is_empty() {
+            match bincode::deserialize_from(&mut reader) {
+                Ok(record) => logs.record(record),
+                Err(err) => match *err {
+                    bincode::ErrorKind::Io(err) if err.kind() == io::ErrorKind::UnexpectedEof => {
+                        warn!("Ignoring incomplete faucet request record");
+                        break;
+                    }
+                    err => return Err(io::Error::new(io::ErrorKind::InvalidData, err)),
+                },
+            }
+        }
+        Ok(logs)
+    }
+
+    /// Saves the request logs at `path`, replacing the previous ones at once
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let tmp_path = path.with_extension("tmp");
+        {
+            let mut writer = BufWriter::new(File::create(&tmp_path)?);
+            serialize_records(&mut writer, &self.records())?;
+            writer.flush()?;
+        }
+        fs::rename(&tmp_path, path)
+    }
+
+    pub fn record(&mut self, record: RequestRecord) {
+        match record {
+            RequestRecord::Ip {
+                ip,
+                timestamp,
+                lamports,
+            } => self.ip_requests.entry(ip).or_default(),
+            RequestRecord::Address {
+                address,
+                timestamp,
+                lamports,
+            } => self.address_requests.entry(address).or_default(),
+        }
+        .record(timestamp, lamports)
+    }
+
+    /// Airdrops within the window, oldest first
+    fn records(&self) -> Vec<RequestRecord> {
+        let ip_records = self.ip_requests.iter().flat_map(|(ip, log)| {
+            log.requests
+                .iter()
+                .map(|(timestamp, lamports)| RequestRecord::Ip {
+                    ip: *ip,
+                    timestamp: *timestamp,
+                    lamports: *lamports,
+                })
+        });
+        let address_records = self.address_requests.iter().flat_map(|(address, log)| {
+            log.requests
+                .iter()
+                .map(|(timestamp, lamports)| RequestRecord::Address {
+                    address: *address,
+                    timestamp: *timestamp,
+                    lamports: *lamports,
+                })
+        });
+        let mut records: Vec<_> = ip_records.chain(address_records).collect();
+        records.sort_by_key(|record| match record {
+            RequestRecord::Ip { timestamp, .. } | RequestRecord::Address { timestamp, .. } => {
+                *timestamp
+            }
+        });
+        records
+    }
+
+    /// Forgets the airdrops granted `window` seconds or more before `now`
+    pub fn prune(&mut self, now: u64, window: u64) {
+        prune_logs(&mut self.ip_requests, now, window);
+        prune_logs(&mut self.address_requests, now, window);
+        trace!(
+            "request logs: {} IP addresses, {} recipients",
+            self.ip_requests.len(),
+            self.address_requests.len()
+        );
+    }
+
+    pub fn clear(&mut self) {
+        self.ip_requests.clear();
+        self.address_requests.clear();
+    }
+}
+
+/// Request log file the faucet appends its airdrops to
+#[derive(Debug)]
+pub struct RequestLogFile {
+    path: PathBuf,
+    file: File,
+}
+
+impl RequestLogFile {
+    /// Rewrites the file at `path` with `logs`, and opens it to append to
+    pub fn create(path: PathBuf, logs: &RequestLogs) -> io::Result<Self> {
+        logs.save(&path)?;
+        let file = OpenOptions::new().append(true).open(&path)?;
+        Ok(Self { path, file })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Appends `records` to the file with a single write
+    pub fn append(&mut self, records: &[RequestRecord]) -> io::Result<()> {
+        let mut buf = vec![];
+        serialize_records(&mut buf, records)?;
+        self.file.write_all(&buf)
+    }
+
+    /// Rewrites the file with only the airdrops still in `logs`
+    pub fn compact(&mut self, logs: &RequestLogs) -> io::Result<()> {
+        *self = Self::create(self.path.clone(), logs)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, tempfile::TempDir};
+
+    #[test]
+    fn test_request_log_sliding_window() {
+        let mut log = RequestLog::default();
+        log.record(100, 1);
+        log.record(130, 2);
+        log.record(150, 4);
+        log.prune(150, 60);
+        assert_eq!(log.total(), 7);
+
+        // the first airdrop leaves the window 60 seconds after it was granted
+        log.prune(160, 60);
+        assert_eq!(log.total(), 6);
+        log.prune(209, 60);
+        assert_eq!(log.total(), 4);
+        log.prune(210, 60);
+        assert!(log.is_empty());
+    }
+
+    #[test]
+    fn test_request_logs_save_load() {
+        let ledger_dir = TempDir::new().unwrap();
+        let path = ledger_dir.path().join("faucet-requests.bin");
+        assert_eq!(RequestLogs::load(&path).unwrap(), RequestLogs::default());
+
+        let mut logs = RequestLogs::default();
+        let ip = "203.0.113.1".parse().unwrap();
+        logs.ip_requests.entry(ip).or_default().record(100, 1);
+        logs.address_requests
+            .entry(Pubkey::new_unique())
+            .or_default()
+            .record(200, 2);
+        logs.save(&path).unwrap();
+        let mut loaded = RequestLogs::load(&path).unwrap();
+        assert_eq!(loaded, logs);
+
+        loaded.prune(200, 60);
+        assert!(loaded.ip_requests.is_empty());
+        assert_eq!(loaded.address_requests.len(), 1);
+
+        fs::write(&path, b"junk").unwrap();
+        assert!(RequestLogs::load(&path).is_err());
+    }
+
+    #[test]
+    fn test_request_log_file_append_compact() {
+        let ledger_dir = TempDir::new().unwrap();
+        let path = ledger_dir.path().join("faucet-requests.bin");
+        let mut logs = RequestLogs::default();
+        let mut file = RequestLogFile::create(path.clone(), &logs).unwrap();
+
+        let ip = "203.0.113.1".parse().unwrap();
+        let address = Pubkey::new_unique();
+        let records = [
+            RequestRecord::Ip {
+                ip,
+                timestamp: 100,
+                lamports: 1,
+            },
+            RequestRecord::Address {
+                address,
+                timestamp: 100,
+                lamports: 1,
+            },
+            RequestRecord::Ip {
+                ip,
+                timestamp: 200,
+                lamports: 2,
+            },
+        ];
+        for record in records {
+            logs.record(record);
+            file.append(&[record]).unwrap();
+        }
+        assert_eq!(RequestLogs::load(&path).unwrap(), logs);
+
+        // a record cut short by a crash is dropped, and the ones before it kept
+        let len = fs::metadata(&path).unwrap().len();
+        let truncated = ledger_dir.path().join("truncated.bin");
+        fs::copy(&path, &truncated).unwrap();
+        File::options()
+            .write(true)
+            .open(&truncated)
+            .unwrap()
+            .set_len(len - 1)
+            .unwrap();
+        let mut expected = RequestLogs::default();
+        records[..2]
+            .iter()
+            .for_each(|record| expected.record(*record));
+        assert_eq!(RequestLogs::load(&truncated).unwrap(), expected);
+
+        // compacting drops the airdrops that left the window, and appending
+        // resumes after them
+        logs.prune(200, 60);
+        file.compact(&logs).unwrap();
+        assert!(fs::metadata(&path).unwrap().len() < len);
+        let record = RequestRecord::Address {
+            address,
+            timestamp: 210,
+            lamports: 3,
+        };
+        logs.record(record);
+        file.append(&[record]).unwrap();
+        let loaded = RequestLogs::load(&path).unwrap();
+        assert_eq!(loaded, logs);
+        assert_eq!(loaded.ip_requests[&ip].total(), 2);
+        assert_eq!(loaded.address_requests[&address].total(), 3);
+    }
+}
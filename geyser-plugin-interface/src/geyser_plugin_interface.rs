@@ -4,11 +4,15 @@
 /// creates the implementation of the plugin.
 use {
     solana_sdk::{
-        clock::{Slot, UnixTimestamp},
+        clock::{Clock, Epoch, Slot, UnixTimestamp},
+        epoch_rewards::EpochRewards,
+        inner_instruction::InstructionComputeUnits,
         signature::Signature,
+        stake_history::StakeHistoryEntry,
         transaction::SanitizedTransaction,
+        transaction_context::TransactionReturnData,
     },
-    solana_transaction_status::{Reward, TransactionStatusMeta},
+    solana_transaction_status::{InnerInstructions, Reward, TransactionStatusMeta},
     std::{any::Any, error, io},
     thiserror::Error,
 };
@@ -122,6 +126,32 @@ pub enum ReplicaAccountInfoVersions<'a> {
     V0_0_3(&'a ReplicaAccountInfoV3<'a>),
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[repr(C)]
+/// Information about an account being closed, i.e. updated from a nonzero
+/// balance to zero lamports. An account which is updated again with lamports
+/// after it was closed is reopened with the contents of that update, and may
+/// then be closed again.
+pub struct ReplicaAccountClosedInfo<'a> {
+    /// The Pubkey for the account
+    pub pubkey: &'a [u8],
+
+    /// The write_version of the account update closing the account, which
+    /// orders the closure among the other updates of the account.
+    pub write_version: u64,
+
+    /// Reference to transaction closing this account
+    pub txn: Option<&'a SanitizedTransaction>,
+}
+
+/// A wrapper to future-proof ReplicaAccountClosedInfo handling. To make a change to the
+/// structure of ReplicaAccountClosedInfo, add an new enum variant wrapping a newer version,
+/// which will force plugin implementations to handle the change.
+#[repr(u32)]
+pub enum ReplicaAccountClosedInfoVersions<'a> {
+    V0_0_1(&'a ReplicaAccountClosedInfo<'a>),
+}
+
 /// Information about a transaction
 #[derive(Clone, Debug)]
 #[repr(C)]
@@ -159,6 +189,39 @@ pub struct ReplicaTransactionInfoV2<'a> {
     pub index: usize,
 }
 
+/// Information about a transaction, including index in block, the inner
+/// instructions and compute units consumed by each of its instructions, and
+/// its return data
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct ReplicaTransactionInfoV3<'a> {
+    /// The first signature of the transaction, used for identifying the transaction.
+    pub signature: &'a Signature,
+
+    /// Indicates if the transaction is a simple vote transaction.
+    pub is_vote: bool,
+
+    /// The sanitized transaction.
+    pub transaction: &'a SanitizedTransaction,
+
+    /// Metadata of the transaction status.
+    pub transaction_status_meta: &'a TransactionStatusMeta,
+
+    /// The transaction's index in the block
+    pub index: usize,
+
+    /// The instructions invoked by each of the transaction's instructions which
+    /// invoked any, if they were recorded.
+    pub inner_instructions: Option<&'a [InnerInstructions]>,
+
+    /// The compute units consumed by each of the transaction's instructions and
+    /// by each of their inner instructions, in order, if they were recorded.
+    pub instruction_compute_units: Option<&'a [InstructionComputeUnits]>,
+
+    /// The data returned by the transaction, if any.
+    pub return_data: Option<&'a TransactionReturnData>,
+}
+
 /// A wrapper to future-proof ReplicaTransactionInfo handling.
 /// If there were a change to the structure of ReplicaTransactionInfo,
 /// there would be new enum entry for the newer version, forcing
@@ -167,6 +230,7 @@ pub struct ReplicaTransactionInfoV2<'a> {
 pub enum ReplicaTransactionInfoVersions<'a> {
     V0_0_1(&'a ReplicaTransactionInfo<'a>),
     V0_0_2(&'a ReplicaTransactionInfoV2<'a>),
+    V0_0_3(&'a ReplicaTransactionInfoV3<'a>),
 }
 
 #[derive(Clone, Debug)]
@@ -258,6 +322,55 @@ pub enum ReplicaBlockInfoVersions<'a> {
     V0_0_3(&'a ReplicaBlockInfoV3<'a>),
 }
 
+/// The decoded contents of a sysvar account that was updated in a slot
+#[derive(Clone, Debug)]
+#[repr(u32)]
+pub enum ReplicaSysvar<'a> {
+    Clock(&'a Clock),
+    EpochRewards(&'a EpochRewards),
+}
+
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct ReplicaSysvarInfo<'a> {
+    /// The slot in which the sysvar was updated
+    pub slot: Slot,
+    /// The Pubkey of the sysvar account
+    pub pubkey: &'a [u8],
+    /// The decoded sysvar contents
+    pub sysvar: ReplicaSysvar<'a>,
+}
+
+/// A wrapper to future-proof ReplicaSysvarInfo handling. To make a change to the structure of
+/// ReplicaSysvarInfo, add an new enum variant wrapping a newer version, which will force plugin
+/// implementations to handle the change.
+#[repr(u32)]
+pub enum ReplicaSysvarInfoVersions<'a> {
+    V0_0_1(&'a ReplicaSysvarInfo<'a>),
+}
+
+/// Information about the first slot of a new epoch
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct ReplicaEpochBoundaryInfo<'a> {
+    pub parent_slot: Slot,
+    pub parent_epoch: Epoch,
+    pub slot: Slot,
+    pub epoch: Epoch,
+    /// The Clock sysvar as of the first slot of the new epoch
+    pub clock: &'a Clock,
+    /// The stake history entry recorded for the parent epoch, if any
+    pub stake_history_entry: Option<&'a StakeHistoryEntry>,
+}
+
+/// A wrapper to future-proof ReplicaEpochBoundaryInfo handling. To make a change to the
+/// structure of ReplicaEpochBoundaryInfo, add an new enum variant wrapping a newer version,
+/// which will force plugin implementations to handle the change.
+#[repr(u32)]
+pub enum ReplicaEpochBoundaryInfoVersions<'a> {
+    V0_0_1(&'a ReplicaEpochBoundaryInfo<'a>),
+}
+
 /// Errors returned by plugin calls
 #[derive(Error, Debug)]
 #[repr(u32)]
@@ -380,6 +493,21 @@ pub trait GeyserPlugin: Any + Send + Sync + std::fmt::Debug {
         Ok(())
     }
 
+    /// Called when an account is closed at a slot during transaction
+    /// processing, right after the `update_account` call which updated it to
+    /// zero lamports. Only an account which had lamports on the fork of the
+    /// slot is closed, so updating an account which did not exist or was
+    /// already closed to zero lamports is not notified of. A later
+    /// `update_account` call with lamports reopens the account.
+    #[allow(unused_variables)]
+    fn notify_account_closed(
+        &self,
+        account: ReplicaAccountClosedInfoVersions,
+        slot: Slot,
+    ) -> Result<()> {
+        Ok(())
+    }
+
     /// Called when all accounts are notified of during startup.
     fn notify_end_of_startup(&self) -> Result<()> {
         Ok(())
@@ -418,6 +546,18 @@ pub trait GeyserPlugin: Any + Send + Sync + std::fmt::Debug {
         Ok(())
     }
 
+    /// Called when a sysvar account is updated in a slot.
+    #[allow(unused_variables)]
+    fn notify_sysvar_update(&self, sysvar: ReplicaSysvarInfoVersions) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called when the first slot of a new epoch is processed.
+    #[allow(unused_variables)]
+    fn notify_epoch_boundary(&self, boundary: ReplicaEpochBoundaryInfoVersions) -> Result<()> {
+        Ok(())
+    }
+
     /// Check if the plugin is interested in account data
     /// Default is true -- if the plugin is not interested in
     /// account data, please return false.
@@ -438,4 +578,11 @@ pub trait GeyserPlugin: Any + Send + Sync + std::fmt::Debug {
     fn entry_notifications_enabled(&self) -> bool {
         false
     }
+
+    /// Check if the plugin is interested in sysvar updates and epoch boundaries
+    /// Default is false -- if the plugin is interested in
+    /// sysvar data, return true.
+    fn sysvar_notifications_enabled(&self) -> bool {
+        false
+    }
 }
@@ -7,7 +7,8 @@ use {
         accounts_update_notifier_interface::AccountsUpdateNotifierInterface,
     },
     solana_geyser_plugin_interface::geyser_plugin_interface::{
-        ReplicaAccountInfoV3, ReplicaAccountInfoVersions,
+        ReplicaAccountClosedInfo, ReplicaAccountClosedInfoVersions, ReplicaAccountInfoV3,
+        ReplicaAccountInfoVersions,
     },
     solana_measure::measure::Measure,
     solana_metrics::*,
@@ -40,6 +41,21 @@ impl AccountsUpdateNotifierInterface for AccountsUpdateNotifierImpl {
         }
     }
 
+    fn notify_account_closed(
+        &self,
+        slot: Slot,
+        txn: &Option<&SanitizedTransaction>,
+        pubkey: &Pubkey,
+        write_version: u64,
+    ) {
+        let account_closed_info = ReplicaAccountClosedInfo {
+            pubkey: pubkey.as_ref(),
+            write_version,
+            txn: *txn,
+        };
+        self.notify_plugins_of_account_closed(account_closed_info, slot);
+    }
+
     fn notify_account_restore_from_snapshot(&self, slot: Slot, account: &StoredAccountMeta) {
         let mut measure_all = Measure::start("geyser-plugin-notify-account-restore-all");
         let mut measure_copy = Measure::start("geyser-plugin-copy-stored-account-info");
@@ -192,4 +208,43 @@ impl AccountsUpdateNotifierImpl {
             100000
         );
     }
+
+    fn notify_plugins_of_account_closed(&self, account: ReplicaAccountClosedInfo, slot: Slot) {
+        let mut measure = Measure::start("geyser-plugin-notify_plugins_of_account_closed");
+        let plugin_manager = self.plugin_manager.read().unwrap();
+
+        if plugin_manager.plugins.is_empty() {
+            return;
+        }
+        for plugin in plugin_manager.plugins.iter() {
+            match plugin
+                .notify_account_closed(ReplicaAccountClosedInfoVersions::V0_0_1(&account), slot)
+            {
+                Err(err) => {
+                    error!(
+                        "Failed to notify closed account {} at slot {}, error: {} to plugin {}",
+                        bs58::encode(account.pubkey).into_string(),
+                        slot,
+                        err,
+                        plugin.name()
+                    )
+                }
+                Ok(_) => {
+                    trace!(
+                        "Successfully notified closed account {} at slot {} to plugin {}",
+                        bs58::encode(account.pubkey).into_string(),
+                        slot,
+                        plugin.name()
+                    );
+                }
+            }
+        }
+        measure.stop();
+        inc_new_counter_debug!(
+            "geyser-plugin-notify_plugins_of_account_closed-us",
+            measure.as_us() as usize,
+            100000,
+            100000
+        );
+    }
 }
@@ -100,6 +100,16 @@ impl GeyserPluginManager {
         false
     }
 
+    /// Check if there is any plugin interested in sysvar data
+    pub fn sysvar_notifications_enabled(&self) -> bool {
+        for plugin in &self.plugins {
+            if plugin.sysvar_notifications_enabled() {
+                return true;
+            }
+        }
+        false
+    }
+
     /// Admin RPC request handler
     pub(crate) fn list_plugins(&self) -> JsonRpcResult<Vec<String>> {
         Ok(self.plugins.iter().map(|p| p.name().to_owned()).collect())
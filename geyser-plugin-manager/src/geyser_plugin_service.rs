@@ -7,6 +7,8 @@ use {
         geyser_plugin_manager::{GeyserPluginManager, GeyserPluginManagerRequest},
         slot_status_notifier::SlotStatusNotifierImpl,
         slot_status_observer::SlotStatusObserver,
+        sysvar_notifier::SysvarNotifierImpl,
+        sysvar_notifier_interface::SysvarNotifierArc,
         transaction_notifier::TransactionNotifierImpl,
     },
     crossbeam_channel::Receiver,
@@ -37,6 +39,7 @@ pub struct GeyserPluginService {
     transaction_notifier: Option<TransactionNotifierArc>,
     entry_notifier: Option<EntryNotifierArc>,
     block_metadata_notifier: Option<BlockMetadataNotifierArc>,
+    sysvar_notifier: Option<SysvarNotifierArc>,
 }
 
 impl GeyserPluginService {
@@ -81,6 +84,7 @@ impl GeyserPluginService {
             plugin_manager.account_data_notifications_enabled();
         let transaction_notifications_enabled = plugin_manager.transaction_notifications_enabled();
         let entry_notifications_enabled = plugin_manager.entry_notifications_enabled();
+        let sysvar_notifications_enabled = plugin_manager.sysvar_notifications_enabled();
         let plugin_manager = Arc::new(RwLock::new(plugin_manager));
 
         let accounts_update_notifier: Option<AccountsUpdateNotifier> =
@@ -107,6 +111,13 @@ impl GeyserPluginService {
             None
         };
 
+        let sysvar_notifier: Option<SysvarNotifierArc> = if sysvar_notifications_enabled {
+            let sysvar_notifier = SysvarNotifierImpl::new(plugin_manager.clone());
+            Some(Arc::new(sysvar_notifier))
+        } else {
+            None
+        };
+
         let (slot_status_observer, block_metadata_notifier): (
             Option<SlotStatusObserver>,
             Option<BlockMetadataNotifierArc>,
@@ -143,6 +154,7 @@ impl GeyserPluginService {
             transaction_notifier,
             entry_notifier,
             block_metadata_notifier,
+            sysvar_notifier,
         })
     }
 
@@ -172,6 +184,10 @@ impl GeyserPluginService {
         self.block_metadata_notifier.clone()
     }
 
+    pub fn get_sysvar_notifier(&self) -> Option<SysvarNotifierArc> {
+        self.sysvar_notifier.clone()
+    }
+
     pub fn join(self) -> thread::Result<()> {
         if let Some(mut slot_status_observer) = self.slot_status_observer {
             slot_status_observer.join()?;
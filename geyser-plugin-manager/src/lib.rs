@@ -6,6 +6,8 @@ pub mod geyser_plugin_manager;
 pub mod geyser_plugin_service;
 pub mod slot_status_notifier;
 pub mod slot_status_observer;
+pub mod sysvar_notifier;
+pub mod sysvar_notifier_interface;
 pub mod transaction_notifier;
 
 pub use geyser_plugin_manager::GeyserPluginManagerRequest;
@@ -0,0 +1,281 @@
+use {
+    crate::{
+        geyser_plugin_manager::GeyserPluginManager, sysvar_notifier_interface::SysvarNotifier,
+    },
+    log::*,
+    solana_geyser_plugin_interface::geyser_plugin_interface::{
+        ReplicaEpochBoundaryInfo, ReplicaEpochBoundaryInfoVersions, ReplicaSysvar,
+        ReplicaSysvarInfo, ReplicaSysvarInfoVersions,
+    },
+    solana_measure::measure::Measure,
+    solana_metrics::*,
+    solana_sdk::{
+        clock::{Clock, Epoch, Slot},
+        epoch_rewards::EpochRewards,
+        pubkey::Pubkey,
+        stake_history::StakeHistoryEntry,
+        sysvar,
+    },
+    std::sync::{Arc, RwLock},
+};
+
+pub(crate) struct SysvarNotifierImpl {
+    plugin_manager: Arc<RwLock<GeyserPluginManager>>,
+}
+
+impl SysvarNotifier for SysvarNotifierImpl {
+    /// Notify the sysvars updated in a slot
+    fn notify_sysvar_updates(
+        &self,
+        slot: Slot,
+        clock: &Clock,
+        epoch_rewards: Option<&EpochRewards>,
+    ) {
+        let plugin_manager = self.plugin_manager.read().unwrap();
+        if plugin_manager.plugins.is_empty() {
+            return;
+        }
+
+        let mut sysvar_infos = vec![Self::build_replica_sysvar_info(
+            slot,
+            &sysvar::clock::ID,
+            ReplicaSysvar::Clock(clock),
+        )];
+        if let Some(epoch_rewards) = epoch_rewards {
+            sysvar_infos.push(Self::build_replica_sysvar_info(
+                slot,
+                &sysvar::epoch_rewards::ID,
+                ReplicaSysvar::EpochRewards(epoch_rewards),
+            ));
+        }
+
+        for plugin in plugin_manager.plugins.iter() {
+            if !plugin.sysvar_notifications_enabled() {
+                continue;
+            }
+            let mut measure = Measure::start("geyser-plugin-update-sysvar");
+            for sysvar_info in &sysvar_infos {
+                let sysvar_info = ReplicaSysvarInfoVersions::V0_0_1(sysvar_info);
+                match plugin.notify_sysvar_update(sysvar_info) {
+                    Err(err) => {
+                        error!(
+                            "Failed to update sysvar at slot {}, error: {} to plugin {}",
+                            slot,
+                            err,
+                            plugin.name()
+                        )
+                    }
+                    Ok(_) => {
+                        trace!(
+                            "Successfully updated sysvar at slot {} to plugin {}",
+                            slot,
+                            plugin.name()
+                        );
+                    }
+                }
+            }
+            measure.stop();
+            inc_new_counter_debug!(
+                "geyser-plugin-update-sysvar-us",
+                measure.as_us() as usize,
+                1000,
+                1000
+            );
+        }
+    }
+
+    /// Notify that the slot is the first slot of a new epoch
+    fn notify_epoch_boundary(
+        &self,
+        parent_slot: Slot,
+        parent_epoch: Epoch,
+        slot: Slot,
+        epoch: Epoch,
+        clock: &Clock,
+        stake_history_entry: Option<&StakeHistoryEntry>,
+    ) {
+        let plugin_manager = self.plugin_manager.read().unwrap();
+        if plugin_manager.plugins.is_empty() {
+            return;
+        }
+
+        let boundary_info = ReplicaEpochBoundaryInfo {
+            parent_slot,
+            parent_epoch,
+            slot,
+            epoch,
+            clock,
+            stake_history_entry,
+        };
+
+        for plugin in plugin_manager.plugins.iter() {
+            if !plugin.sysvar_notifications_enabled() {
+                continue;
+            }
+            let mut measure = Measure::start("geyser-plugin-notify-epoch-boundary");
+            let boundary_info = ReplicaEpochBoundaryInfoVersions::V0_0_1(&boundary_info);
+            match plugin.notify_epoch_boundary(boundary_info) {
+                Err(err) => {
+                    error!(
+                        "Failed to notify epoch boundary {} at slot {}, error: {} to plugin {}",
+                        epoch,
+                        slot,
+                        err,
+                        plugin.name()
+                    )
+                }
+                Ok(_) => {
+                    trace!(
+                        "Successfully notified epoch boundary {} at slot {} to plugin {}",
+                        epoch,
+                        slot,
+                        plugin.name()
+                    );
+                }
+            }
+            measure.stop();
+            inc_new_counter_debug!(
+                "geyser-plugin-notify-epoch-boundary-us",
+                measure.as_us() as usize,
+                1000,
+                1000
+            );
+        }
+    }
+}
+
+impl SysvarNotifierImpl {
+    fn build_replica_sysvar_info<'a>(
+        slot: Slot,
+        pubkey: &'a Pubkey,
+        sysvar: ReplicaSysvar<'a>,
+    ) -> ReplicaSysvarInfo<'a> {
+        ReplicaSysvarInfo {
+            slot,
+            pubkey: pubkey.as_ref(),
+            sysvar,
+        }
+    }
+
+    pub fn new(plugin_manager: Arc<RwLock<GeyserPluginManager>>) -> Self {
+        Self { plugin_manager }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::geyser_plugin_manager::LoadedGeyserPlugin,
+        solana_geyser_plugin_interface::geyser_plugin_interface::{GeyserPlugin, Result},
+        std::sync::Mutex,
+    };
+
+    /// Records the sysvar updates and epoch boundaries it is notified of
+    #[derive(Debug, Default)]
+    struct RecordingPlugin {
+        enabled: bool,
+        sysvar_updates: Arc<Mutex<Vec<(Slot, Pubkey)>>>,
+        epoch_boundaries: Arc<Mutex<Vec<(Epoch, Epoch, Option<StakeHistoryEntry>)>>>,
+    }
+
+    impl GeyserPlugin for RecordingPlugin {
+        fn name(&self) -> &'static str {
+            "recording"
+        }
+
+        fn notify_sysvar_update(&self, sysvar: ReplicaSysvarInfoVersions) -> Result<()> {
+            let ReplicaSysvarInfoVersions::V0_0_1(info) = sysvar;
+            let pubkey = Pubkey::try_from(info.pubkey).unwrap();
+            match info.sysvar {
+                ReplicaSysvar::Clock(_) => assert_eq!(pubkey, sysvar::clock::id()),
+                ReplicaSysvar::EpochRewards(_) => {
+                    assert_eq!(pubkey, sysvar::epoch_rewards::id())
+                }
+            }
+            self.sysvar_updates
+                .lock()
+                .unwrap()
+                .push((info.slot, pubkey));
+            Ok(())
+        }
+
+        fn notify_epoch_boundary(&self, boundary: ReplicaEpochBoundaryInfoVersions) -> Result<()> {
+            let ReplicaEpochBoundaryInfoVersions::V0_0_1(info) = boundary;
+            self.epoch_boundaries.lock().unwrap().push((
+                info.parent_epoch,
+                info.epoch,
+                info.stake_history_entry.cloned(),
+            ));
+            Ok(())
+        }
+
+        fn sysvar_notifications_enabled(&self) -> bool {
+            self.enabled
+        }
+    }
+
+    fn notifier_with_plugin(plugin: RecordingPlugin) -> SysvarNotifierImpl {
+        let mut plugin_manager = GeyserPluginManager::new();
+        plugin_manager
+            .plugins
+            .push(LoadedGeyserPlugin::new(Box::new(plugin), None));
+        SysvarNotifierImpl::new(Arc::new(RwLock::new(plugin_manager)))
+    }
+
+    #[test]
+    fn test_notify_sysvar_updates() {
+        let plugin = RecordingPlugin {
+            enabled: true,
+            ..RecordingPlugin::default()
+        };
+        let sysvar_updates = plugin.sysvar_updates.clone();
+        let notifier = notifier_with_plugin(plugin);
+
+        notifier.notify_sysvar_updates(1, &Clock::default(), None);
+        assert_eq!(
+            *sysvar_updates.lock().unwrap(),
+            vec![(1, sysvar::clock::id())]
+        );
+
+        notifier.notify_sysvar_updates(2, &Clock::default(), Some(&EpochRewards::default()));
+        assert_eq!(
+            *sysvar_updates.lock().unwrap(),
+            vec![
+                (1, sysvar::clock::id()),
+                (2, sysvar::clock::id()),
+                (2, sysvar::epoch_rewards::id()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_notify_epoch_boundary() {
+        let plugin = RecordingPlugin {
+            enabled: true,
+            ..RecordingPlugin::default()
+        };
+        let epoch_boundaries = plugin.epoch_boundaries.clone();
+        let notifier = notifier_with_plugin(plugin);
+
+        let stake_history_entry = StakeHistoryEntry::with_effective(42);
+        notifier.notify_epoch_boundary(31, 0, 32, 1, &Clock::default(), Some(&stake_history_entry));
+        assert_eq!(
+            *epoch_boundaries.lock().unwrap(),
+            vec![(0, 1, Some(stake_history_entry))]
+        );
+    }
+
+    #[test]
+    fn test_notifications_disabled() {
+        let plugin = RecordingPlugin::default();
+        let sysvar_updates = plugin.sysvar_updates.clone();
+        let epoch_boundaries = plugin.epoch_boundaries.clone();
+        let notifier = notifier_with_plugin(plugin);
+
+        notifier.notify_sysvar_updates(1, &Clock::default(), None);
+        notifier.notify_epoch_boundary(31, 0, 32, 1, &Clock::default(), None);
+        assert!(sysvar_updates.lock().unwrap().is_empty());
+        assert!(epoch_boundaries.lock().unwrap().is_empty());
+    }
+}
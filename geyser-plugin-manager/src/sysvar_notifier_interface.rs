@@ -0,0 +1,32 @@
+use {
+    solana_sdk::{
+        clock::{Clock, Epoch, Slot},
+        epoch_rewards::EpochRewards,
+        stake_history::StakeHistoryEntry,
+    },
+    std::sync::Arc,
+};
+
+/// Interface for notifying sysvar updates and epoch boundaries
+pub trait SysvarNotifier {
+    /// Notify the sysvars updated in a slot
+    fn notify_sysvar_updates(
+        &self,
+        slot: Slot,
+        clock: &Clock,
+        epoch_rewards: Option<&EpochRewards>,
+    );
+
+    /// Notify that the slot is the first slot of a new epoch
+    fn notify_epoch_boundary(
+        &self,
+        parent_slot: Slot,
+        parent_epoch: Epoch,
+        slot: Slot,
+        epoch: Epoch,
+        clock: &Clock,
+        stake_history_entry: Option<&StakeHistoryEntry>,
+    );
+}
+
+pub type SysvarNotifierArc = Arc<dyn SysvarNotifier + Sync + Send>;
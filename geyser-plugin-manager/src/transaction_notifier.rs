@@ -3,12 +3,15 @@ use {
     crate::geyser_plugin_manager::GeyserPluginManager,
     log::*,
     solana_geyser_plugin_interface::geyser_plugin_interface::{
-        ReplicaTransactionInfoV2, ReplicaTransactionInfoVersions,
+        ReplicaTransactionInfoV3, ReplicaTransactionInfoVersions,
     },
     solana_measure::measure::Measure,
     solana_metrics::*,
     solana_rpc::transaction_notifier_interface::TransactionNotifier,
-    solana_sdk::{clock::Slot, signature::Signature, transaction::SanitizedTransaction},
+    solana_sdk::{
+        clock::Slot, inner_instruction::InstructionComputeUnitsList, signature::Signature,
+        transaction::SanitizedTransaction,
+    },
     solana_transaction_status::TransactionStatusMeta,
     std::sync::{Arc, RwLock},
 };
@@ -28,6 +31,7 @@ impl TransactionNotifier for TransactionNotifierImpl {
         index: usize,
         signature: &Signature,
         transaction_status_meta: &TransactionStatusMeta,
+        instruction_compute_units: Option<&InstructionComputeUnitsList>,
         transaction: &SanitizedTransaction,
     ) {
         let mut measure = Measure::start("geyser-plugin-notify_plugins_of_transaction_info");
@@ -35,6 +39,7 @@ impl TransactionNotifier for TransactionNotifierImpl {
             index,
             signature,
             transaction_status_meta,
+            instruction_compute_units,
             transaction,
         );
 
@@ -49,7 +54,7 @@ impl TransactionNotifier for TransactionNotifierImpl {
                 continue;
             }
             match plugin.notify_transaction(
-                ReplicaTransactionInfoVersions::V0_0_2(&transaction_log_info),
+                ReplicaTransactionInfoVersions::V0_0_3(&transaction_log_info),
                 slot,
             ) {
                 Err(err) => {
@@ -86,14 +91,18 @@ impl TransactionNotifierImpl {
         index: usize,
         signature: &'a Signature,
         transaction_status_meta: &'a TransactionStatusMeta,
+        instruction_compute_units: Option<&'a InstructionComputeUnitsList>,
         transaction: &'a SanitizedTransaction,
-    ) -> ReplicaTransactionInfoV2<'a> {
-        ReplicaTransactionInfoV2 {
+    ) -> ReplicaTransactionInfoV3<'a> {
+        ReplicaTransactionInfoV3 {
             index,
             signature,
             is_vote: transaction.is_simple_vote_transaction(),
             transaction,
             transaction_status_meta,
+            inner_instructions: transaction_status_meta.inner_instructions.as_deref(),
+            instruction_compute_units: instruction_compute_units.map(Vec::as_slice),
+            return_data: transaction_status_meta.return_data.as_ref(),
         }
     }
 }
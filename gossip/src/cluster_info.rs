@@ -31,6 +31,7 @@ use {
         crds_gossip_pull::{
             CrdsFilter, CrdsTimeouts, ProcessPullStats, CRDS_GOSSIP_PULL_CRDS_TIMEOUT_MS,
         },
+        crds_gossip_push::{CrdsGossipPush, CrdsGossipPushConfig},
         crds_value::{
             self, CrdsData, CrdsValue, CrdsValueLabel, EpochSlotsIndex, LowestSlot, NodeInstance,
             SnapshotHashes, Version, Vote, MAX_WALLCLOCK,
@@ -38,6 +39,7 @@ use {
         duplicate_shred::DuplicateShred,
         epoch_slots::EpochSlots,
         gossip_error::GossipError,
+        node_metadata::{NodeMetadata, NodeMetadataError, NODE_METADATA_MIN_UPDATE_INTERVAL_MS},
         ping_pong::{self, PingCache, Pong},
         restart_crds_values::{
             RestartHeaviestFork, RestartLastVotedForkSlots, RestartLastVotedForkSlotsError,
@@ -389,7 +391,8 @@ fn retain_staked(values: &mut Vec<CrdsValue>, stakes: &HashMap<Pubkey, u64>) {
             | CrdsData::LegacyVersion(_)
             | CrdsData::DuplicateShred(_, _)
             | CrdsData::RestartHeaviestFork(_)
-            | CrdsData::RestartLastVotedForkSlots(_) => {
+            | CrdsData::RestartLastVotedForkSlots(_)
+            | CrdsData::NodeMetadata(_) => {
                 let stake = stakes.get(&value.pubkey()).copied();
                 stake.unwrap_or_default() >= MIN_STAKE_FOR_GOSSIP
             }
@@ -434,6 +437,12 @@ impl ClusterInfo {
         self.contact_debug_interval = new;
     }
 
+    /// Replaces the gossip push and prune parameters; intended to be called
+    /// before gossip starts, as it resets the push active set.
+    pub fn set_push_config(&mut self, config: CrdsGossipPushConfig) {
+        self.gossip.push = CrdsGossipPush::new(config);
+    }
+
     pub fn socket_addr_space(&self) -> &SocketAddrSpace {
         &self.socket_addr_space
     }
@@ -997,6 +1006,24 @@ impl ClusterInfo {
         ));
     }
 
+    /// Publishes the application-defined metadata of this node, replacing
+    /// any previously published. Fails if the metadata was published less
+    /// than `NODE_METADATA_MIN_UPDATE_INTERVAL_MS` ago.
+    pub fn push_node_metadata(&self, metadata: String) -> Result<(), NodeMetadataError> {
+        let now = timestamp();
+        let node_metadata = NodeMetadata::new(self.id(), now, metadata)?;
+        if let Some(prev) = self.get_node_metadata(&self.id()) {
+            if now.saturating_sub(prev.wallclock) < NODE_METADATA_MIN_UPDATE_INTERVAL_MS {
+                return Err(NodeMetadataError::RateLimited);
+            }
+        }
+        self.push_message(CrdsValue::new_signed(
+            CrdsData::NodeMetadata(node_metadata),
+            &self.keypair(),
+        ));
+        Ok(())
+    }
+
     fn time_gossip_read_lock<'a>(
         &'a self,
         label: &'static str,
@@ -1280,6 +1307,12 @@ impl ClusterInfo {
         Some(version.version.clone().into())
     }
 
+    /// Returns the application-defined metadata published by the node.
+    pub fn get_node_metadata(&self, pubkey: &Pubkey) -> Option<NodeMetadata> {
+        let gossip_crds = self.gossip.crds.read().unwrap();
+        gossip_crds.get::<&NodeMetadata>(*pubkey).cloned()
+    }
+
     fn check_socket_addr_space<E>(&self, addr: &Result<SocketAddr, E>) -> bool {
         addr.as_ref()
             .map(|addr| self.socket_addr_space.check(addr))
@@ -3155,6 +3188,7 @@ mod tests {
             crds_gossip_pull::tests::MIN_NUM_BLOOM_FILTERS,
             crds_value::{AccountsHashes, CrdsValue, CrdsValueLabel, Vote as CrdsVote},
             duplicate_shred::{self, tests::new_rand_shred, MAX_DUPLICATE_SHREDS},
+            node_metadata::MAX_NODE_METADATA_BYTES,
         },
         itertools::izip,
         solana_ledger::shred::Shredder,
@@ -4682,4 +4716,53 @@ mod tests {
         assert_eq!(heaviest_forks.len(), 1);
         assert_eq!(heaviest_forks[0].from, pubkey2);
     }
+
+    #[test]
+    fn test_push_node_metadata() {
+        let keypair = Arc::new(Keypair::new());
+        let pubkey = keypair.pubkey();
+        let contact_info = ContactInfo::new_localhost(&pubkey, 0);
+        let cluster_info = ClusterInfo::new(contact_info, keypair, SocketAddrSpace::Unspecified);
+        assert_eq!(cluster_info.get_node_metadata(&pubkey), None);
+
+        assert_eq!(
+            cluster_info.push_node_metadata("x".repeat(MAX_NODE_METADATA_BYTES + 1)),
+            Err(NodeMetadataError::TooLarge(MAX_NODE_METADATA_BYTES + 1))
+        );
+        let metadata = "operator: foo@example.com".to_string();
+        cluster_info.push_node_metadata(metadata.clone()).unwrap();
+        cluster_info.flush_push_queue();
+        let node_metadata = cluster_info.get_node_metadata(&pubkey).unwrap();
+        assert_eq!(node_metadata.from, pubkey);
+        assert_eq!(node_metadata.metadata(), metadata);
+
+        // Updates are rate limited.
+        assert_eq!(
+            cluster_info.push_node_metadata("bar".to_string()),
+            Err(NodeMetadataError::RateLimited)
+        );
+        let mut rng = rand::thread_rng();
+        let other =
+            NodeMetadata::new(pubkey, node_metadata.wallclock + 1, "bar".to_string()).unwrap();
+        {
+            let mut gossip_crds = cluster_info.gossip.crds.write().unwrap();
+            assert!(gossip_crds
+                .insert(
+                    CrdsValue::new_unsigned(CrdsData::NodeMetadata(other)),
+                    timestamp(),
+                    GossipRoute::PushMessage(&Pubkey::new_unique()),
+                )
+                .is_err());
+            let other = NodeMetadata::new_rand(&mut rng, None);
+            assert!(gossip_crds
+                .insert(
+                    CrdsValue::new_unsigned(CrdsData::NodeMetadata(other.clone())),
+                    timestamp(),
+                    GossipRoute::LocalMessage,
+                )
+                .is_ok());
+            assert_eq!(gossip_crds.get::<&NodeMetadata>(other.from), Some(&other));
+        }
+        assert_eq!(cluster_info.get_node_metadata(&pubkey), Some(node_metadata));
+    }
 }
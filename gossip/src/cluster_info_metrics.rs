@@ -601,6 +601,29 @@ pub(crate) fn submit_gossip_stats(
             i64
         ),
     );
+    let push_config = gossip.push.config();
+    let (num_push_values, num_push_duplicates) = gossip.push.take_received_stats();
+    datapoint_info!(
+        "cluster_info_push_stats",
+        ("push_fanout", push_config.push_fanout, i64),
+        (
+            "stake_weight_exponent",
+            push_config.stake_weight_exponent,
+            i64
+        ),
+        ("prune_timeout_ms", push_config.prune_timeout, i64),
+        ("num_values", num_push_values, i64),
+        ("num_duplicates", num_push_duplicates, i64),
+        (
+            "duplicate_rate",
+            if num_push_values == 0 {
+                0.0
+            } else {
+                num_push_duplicates as f64 / num_push_values as f64
+            },
+            f64
+        ),
+    );
     datapoint_info!(
         "cluster_info_crds_stats",
         ("LegacyContactInfo-push", crds_stats.push.counts[0], i64),
@@ -639,6 +662,8 @@ pub(crate) fn submit_gossip_stats(
         ),
         ("RestartHeaviestFork-push", crds_stats.push.counts[13], i64),
         ("RestartHeaviestFork-pull", crds_stats.pull.counts[13], i64),
+        ("NodeMetadata-push", crds_stats.push.counts[14], i64),
+        ("NodeMetadata-pull", crds_stats.pull.counts[14], i64),
         (
             "all-push",
             crds_stats.push.counts.iter().sum::<usize>(),
@@ -688,6 +713,8 @@ pub(crate) fn submit_gossip_stats(
         ),
         ("RestartHeaviestFork-push", crds_stats.push.fails[13], i64),
         ("RestartHeaviestFork-pull", crds_stats.pull.fails[13], i64),
+        ("NodeMetadata-push", crds_stats.push.fails[14], i64),
+        ("NodeMetadata-pull", crds_stats.pull.fails[14], i64),
         ("all-push", crds_stats.push.fails.iter().sum::<usize>(), i64),
         ("all-pull", crds_stats.pull.fails.iter().sum::<usize>(), i64),
     );
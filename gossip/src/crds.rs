@@ -103,7 +103,7 @@ pub enum GossipRoute<'a> {
     PushMessage(/*from:*/ &'a Pubkey),
 }
 
-type CrdsCountsArray = [usize; 14];
+type CrdsCountsArray = [usize; 15];
 
 pub(crate) struct CrdsDataStats {
     pub(crate) counts: CrdsCountsArray,
@@ -190,6 +190,12 @@ fn overrides(value: &CrdsValue, other: &VersionedCrdsValue) -> bool {
             return out;
         }
     }
+    // Updates of node metadata are rate limited.
+    if let CrdsData::NodeMetadata(value) = &value.data {
+        if let Some(out) = value.overrides(&other.value) {
+            return out;
+        }
+    }
     match value.wallclock().cmp(&other.value.wallclock()) {
         Ordering::Less => false,
         Ordering::Greater => true,
@@ -723,6 +729,7 @@ impl CrdsDataStats {
             CrdsData::ContactInfo(_) => 11,
             CrdsData::RestartLastVotedForkSlots(_) => 12,
             CrdsData::RestartHeaviestFork(_) => 13,
+            CrdsData::NodeMetadata(_) => 14,
             // Update CrdsCountsArray if new items are added here.
         }
     }
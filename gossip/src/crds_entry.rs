@@ -5,6 +5,7 @@ use {
             CrdsData, CrdsValue, CrdsValueLabel, LegacyVersion, LowestSlot, SnapshotHashes, Version,
         },
         legacy_contact_info::LegacyContactInfo,
+        node_metadata::NodeMetadata,
     },
     indexmap::IndexMap,
     solana_sdk::pubkey::Pubkey,
@@ -55,6 +56,7 @@ impl_crds_entry!(VersionedCrdsValue, |entry| entry);
 impl_crds_entry!(LegacyContactInfo, CrdsData::LegacyContactInfo(node), node);
 impl_crds_entry!(LegacyVersion, CrdsData::LegacyVersion(version), version);
 impl_crds_entry!(LowestSlot, CrdsData::LowestSlot(_, slot), slot);
+impl_crds_entry!(NodeMetadata, CrdsData::NodeMetadata(metadata), metadata);
 impl_crds_entry!(Version, CrdsData::Version(version), version);
 impl_crds_entry!(
     SnapshotHashes,
@@ -42,15 +42,62 @@ use {
     },
 };
 
-const CRDS_GOSSIP_PUSH_FANOUT: usize = 9;
+pub const CRDS_GOSSIP_PUSH_FANOUT: usize = 9;
 // With a fanout of 9, a 2000 node cluster should only take ~3.5 hops to converge.
 // However since pushes are stake weighed, some trailing nodes
 // might need more time to receive values. 30 seconds should be plenty.
 pub const CRDS_GOSSIP_PUSH_MSG_TIMEOUT_MS: u64 = 30000;
-const CRDS_GOSSIP_PRUNE_MSG_TIMEOUT_MS: u64 = 500;
+pub const CRDS_GOSSIP_PRUNE_MSG_TIMEOUT_MS: u64 = 500;
 const CRDS_GOSSIP_PRUNE_STAKE_THRESHOLD_PCT: f64 = 0.15;
 const CRDS_GOSSIP_PRUNE_MIN_INGRESS_NODES: usize = 2;
-const CRDS_GOSSIP_PUSH_ACTIVE_SET_SIZE: usize = CRDS_GOSSIP_PUSH_FANOUT + 3;
+// Number of nodes in the push active set in excess of the push fanout.
+const CRDS_GOSSIP_PUSH_ACTIVE_SET_EXTRA_NODES: usize = 3;
+// Exponent of the stake bucket in the push active set sampling weights.
+pub const CRDS_GOSSIP_PUSH_STAKE_WEIGHT_EXPONENT: u32 = 2;
+pub const MAX_CRDS_GOSSIP_PUSH_FANOUT: usize = 32;
+pub const MAX_CRDS_GOSSIP_PUSH_STAKE_WEIGHT_EXPONENT: u32 = 4;
+
+/// Tunable parameters of gossip push and prune
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CrdsGossipPushConfig {
+    /// Number of nodes each value is pushed to
+    pub push_fanout: usize,
+    /// Exponent of the stake bucket of nodes when sampling the push active
+    /// set; 0 samples nodes regardless of their stake
+    pub stake_weight_exponent: u32,
+    /// How long, in milliseconds, a prune message remains valid
+    pub prune_timeout: u64,
+}
+
+impl Default for CrdsGossipPushConfig {
+    fn default() -> Self {
+        Self {
+            push_fanout: CRDS_GOSSIP_PUSH_FANOUT,
+            stake_weight_exponent: CRDS_GOSSIP_PUSH_STAKE_WEIGHT_EXPONENT,
+            prune_timeout: CRDS_GOSSIP_PRUNE_MSG_TIMEOUT_MS,
+        }
+    }
+}
+
+impl CrdsGossipPushConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.push_fanout == 0 || self.push_fanout > MAX_CRDS_GOSSIP_PUSH_FANOUT {
+            return Err(format!(
+                "push fanout must be between 1 and {MAX_CRDS_GOSSIP_PUSH_FANOUT}"
+            ));
+        }
+        if self.stake_weight_exponent > MAX_CRDS_GOSSIP_PUSH_STAKE_WEIGHT_EXPONENT {
+            return Err(format!(
+                "stake weight exponent must be at most \
+                 {MAX_CRDS_GOSSIP_PUSH_STAKE_WEIGHT_EXPONENT}"
+            ));
+        }
+        if self.prune_timeout == 0 {
+            return Err("prune timeout must be greater than 0".to_string());
+        }
+        Ok(())
+    }
+}
 
 pub struct CrdsGossipPush {
     /// Max bytes per message
@@ -64,6 +111,7 @@ pub struct CrdsGossipPush {
     /// currently have this node in their `active_set`
     received_cache: Mutex<ReceivedCache>,
     push_fanout: usize,
+    stake_weight_exponent: u32,
     pub(crate) msg_timeout: u64,
     pub prune_timeout: u64,
     pub num_total: AtomicUsize,
@@ -73,22 +121,49 @@ pub struct CrdsGossipPush {
 
 impl Default for CrdsGossipPush {
     fn default() -> Self {
+        Self::new(CrdsGossipPushConfig::default())
+    }
+}
+impl CrdsGossipPush {
+    pub fn new(config: CrdsGossipPushConfig) -> Self {
+        let CrdsGossipPushConfig {
+            push_fanout,
+            stake_weight_exponent,
+            prune_timeout,
+        } = config;
         Self {
             // Allow upto 64 Crds Values per PUSH
             max_bytes: PACKET_DATA_SIZE * 64,
             active_set: RwLock::default(),
             crds_cursor: Mutex::default(),
             received_cache: Mutex::new(ReceivedCache::new(2 * CRDS_UNIQUE_PUBKEY_CAPACITY)),
-            push_fanout: CRDS_GOSSIP_PUSH_FANOUT,
+            push_fanout,
+            stake_weight_exponent,
             msg_timeout: CRDS_GOSSIP_PUSH_MSG_TIMEOUT_MS,
-            prune_timeout: CRDS_GOSSIP_PRUNE_MSG_TIMEOUT_MS,
+            prune_timeout,
             num_total: AtomicUsize::default(),
             num_old: AtomicUsize::default(),
             num_pushes: AtomicUsize::default(),
         }
     }
-}
-impl CrdsGossipPush {
+
+    pub fn config(&self) -> CrdsGossipPushConfig {
+        CrdsGossipPushConfig {
+            push_fanout: self.push_fanout,
+            stake_weight_exponent: self.stake_weight_exponent,
+            prune_timeout: self.prune_timeout,
+        }
+    }
+
+    /// Returns the number of values received in push messages, and how many
+    /// of those were duplicates or failed to insert, since the last call
+    pub(crate) fn take_received_stats(&self) -> (usize, usize) {
+        (
+            self.num_total.swap(0, Ordering::Relaxed),
+            self.num_old.swap(0, Ordering::Relaxed),
+        )
+    }
+
     pub fn num_pending(&self, crds: &RwLock<Crds>) -> usize {
         let mut cursor: Cursor = *self.crds_cursor.lock().unwrap();
         crds.read().unwrap().get_entries(&mut cursor).count()
@@ -276,10 +351,11 @@ impl CrdsGossipPush {
         let mut active_set = self.active_set.write().unwrap();
         active_set.rotate(
             &mut rng,
-            CRDS_GOSSIP_PUSH_ACTIVE_SET_SIZE,
+            self.push_fanout + CRDS_GOSSIP_PUSH_ACTIVE_SET_EXTRA_NODES,
             cluster_size,
             &nodes,
             stakes,
+            self.stake_weight_exponent,
         )
     }
 }
@@ -618,4 +694,42 @@ mod tests {
             .process_push_message(&crds, vec![(Pubkey::default(), vec![value])], 0)
             .is_empty());
     }
+
+    #[test]
+    fn test_push_config() {
+        let config = CrdsGossipPushConfig {
+            push_fanout: 4,
+            stake_weight_exponent: 0,
+            prune_timeout: 1_000,
+        };
+        assert_eq!(config.validate(), Ok(()));
+        let push = CrdsGossipPush::new(config);
+        assert_eq!(push.config(), config);
+        assert_eq!(push.prune_timeout, 1_000);
+        assert_eq!(
+            CrdsGossipPush::default().config(),
+            CrdsGossipPushConfig::default()
+        );
+
+        for config in [
+            CrdsGossipPushConfig {
+                push_fanout: 0,
+                ..config
+            },
+            CrdsGossipPushConfig {
+                push_fanout: MAX_CRDS_GOSSIP_PUSH_FANOUT + 1,
+                ..config
+            },
+            CrdsGossipPushConfig {
+                stake_weight_exponent: MAX_CRDS_GOSSIP_PUSH_STAKE_WEIGHT_EXPONENT + 1,
+                ..config
+            },
+            CrdsGossipPushConfig {
+                prune_timeout: 0,
+                ..config
+            },
+        ] {
+            assert!(config.validate().is_err());
+        }
+    }
 }
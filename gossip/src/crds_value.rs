@@ -6,6 +6,7 @@ use {
         duplicate_shred::{DuplicateShred, DuplicateShredIndex, MAX_DUPLICATE_SHREDS},
         epoch_slots::EpochSlots,
         legacy_contact_info::LegacyContactInfo,
+        node_metadata::NodeMetadata,
         restart_crds_values::{RestartHeaviestFork, RestartLastVotedForkSlots},
     },
     bincode::{serialize, serialized_size},
@@ -97,6 +98,7 @@ pub enum CrdsData {
     ContactInfo(ContactInfo),
     RestartLastVotedForkSlots(RestartLastVotedForkSlots),
     RestartHeaviestFork(RestartHeaviestFork),
+    NodeMetadata(NodeMetadata),
 }
 
 impl Sanitize for CrdsData {
@@ -137,6 +139,7 @@ impl Sanitize for CrdsData {
             CrdsData::ContactInfo(node) => node.sanitize(),
             CrdsData::RestartLastVotedForkSlots(slots) => slots.sanitize(),
             CrdsData::RestartHeaviestFork(fork) => fork.sanitize(),
+            CrdsData::NodeMetadata(metadata) => metadata.sanitize(),
         }
     }
 }
@@ -150,7 +153,7 @@ pub(crate) fn new_rand_timestamp<R: Rng>(rng: &mut R) -> u64 {
 impl CrdsData {
     /// New random CrdsData for tests and benchmarks.
     fn new_rand<R: Rng>(rng: &mut R, pubkey: Option<Pubkey>) -> CrdsData {
-        let kind = rng.gen_range(0..9);
+        let kind = rng.gen_range(0..10);
         // TODO: Implement other kinds of CrdsData here.
         // TODO: Assign ranges to each arm proportional to their frequency in
         // the mainnet crds table.
@@ -166,6 +169,7 @@ impl CrdsData {
                 rng, pubkey,
             )),
             7 => CrdsData::RestartHeaviestFork(RestartHeaviestFork::new_rand(rng, pubkey)),
+            8 => CrdsData::NodeMetadata(NodeMetadata::new_rand(rng, pubkey)),
             _ => CrdsData::EpochSlots(
                 rng.gen_range(0..MAX_EPOCH_SLOTS),
                 EpochSlots::new_rand(rng, pubkey),
@@ -512,6 +516,7 @@ pub enum CrdsValueLabel {
     ContactInfo(Pubkey),
     RestartLastVotedForkSlots(Pubkey),
     RestartHeaviestFork(Pubkey),
+    NodeMetadata(Pubkey),
 }
 
 impl fmt::Display for CrdsValueLabel {
@@ -541,6 +546,7 @@ impl fmt::Display for CrdsValueLabel {
             CrdsValueLabel::RestartHeaviestFork(_) => {
                 write!(f, "RestartHeaviestFork({})", self.pubkey())
             }
+            CrdsValueLabel::NodeMetadata(_) => write!(f, "NodeMetadata({})", self.pubkey()),
         }
     }
 }
@@ -562,6 +568,7 @@ impl CrdsValueLabel {
             CrdsValueLabel::ContactInfo(pubkey) => *pubkey,
             CrdsValueLabel::RestartLastVotedForkSlots(p) => *p,
             CrdsValueLabel::RestartHeaviestFork(p) => *p,
+            CrdsValueLabel::NodeMetadata(p) => *p,
         }
     }
 }
@@ -614,6 +621,7 @@ impl CrdsValue {
             CrdsData::ContactInfo(node) => node.wallclock(),
             CrdsData::RestartLastVotedForkSlots(slots) => slots.wallclock,
             CrdsData::RestartHeaviestFork(fork) => fork.wallclock,
+            CrdsData::NodeMetadata(metadata) => metadata.wallclock,
         }
     }
     pub fn pubkey(&self) -> Pubkey {
@@ -632,6 +640,7 @@ impl CrdsValue {
             CrdsData::ContactInfo(node) => *node.pubkey(),
             CrdsData::RestartLastVotedForkSlots(slots) => slots.from,
             CrdsData::RestartHeaviestFork(fork) => fork.from,
+            CrdsData::NodeMetadata(metadata) => metadata.from,
         }
     }
     pub fn label(&self) -> CrdsValueLabel {
@@ -654,6 +663,7 @@ impl CrdsValue {
                 CrdsValueLabel::RestartLastVotedForkSlots(self.pubkey())
             }
             CrdsData::RestartHeaviestFork(_) => CrdsValueLabel::RestartHeaviestFork(self.pubkey()),
+            CrdsData::NodeMetadata(_) => CrdsValueLabel::NodeMetadata(self.pubkey()),
         }
     }
     pub fn contact_info(&self) -> Option<&LegacyContactInfo> {
@@ -93,13 +93,15 @@ pub enum Error {
 /// Check that `shred1` and `shred2` indicate a valid duplicate proof
 ///     - Must be for the same slot
 ///     - Must both sigverify for the correct leader
-///     - Must have a merkle root conflict, otherwise `shred1` and `shred2` must have the same `shred_type`
+///     - Must have a merkle root conflict or conflicting erasure configs, otherwise `shred1` and
+///       `shred2` must have the same `shred_type`. Coding shreds and data shreds with an erasure
+///       config header carry the erasure config of their fec set
 ///     - If `shred1` and `shred2` share the same index they must be not equal
 ///     - If `shred1` and `shred2` do not share the same index and are data shreds
 ///       verify that they indicate an index conflict. One of them must be the
 ///       LAST_SHRED_IN_SLOT, however the other shred must have a higher index.
-///     - If `shred1` and `shred2` do not share the same index and are coding shreds
-///       verify that they have conflicting erasure metas
+///     - Coding shreds which do not share the same index are not a valid duplicate proof
+///       unless their erasure configs conflict
 fn check_shreds<F>(leader_schedule: Option<F>, shred1: &Shred, shred2: &Shred) -> Result<(), Error>
 where
     F: FnOnce(Slot) -> Option<Pubkey>,
@@ -126,6 +128,19 @@ where
         return Ok(());
     }
 
+    // This mirrors the current logic in blockstore to detect shreds with conflicting
+    // erasure sets. However this is not technically exhaustive, as any 2 shreds with
+    // different but overlapping erasure sets can be considered duplicate and need not be
+    // a part of the same fec set. Further work to enhance detection is planned in
+    // https://github.com/solana-labs/solana/issues/33037
+    if shred1.fec_set_index() == shred2.fec_set_index()
+        && shred1.erasure_set_info().is_some()
+        && shred2.erasure_set_info().is_some()
+        && !ErasureMeta::check_erasure_consistency(shred1, shred2)
+    {
+        return Ok(());
+    }
+
     if shred1.shred_type() != shred2.shred_type() {
         return Err(Error::ShredTypeMismatch);
     }
@@ -147,16 +162,6 @@ where
         return Err(Error::InvalidLastIndexConflict);
     }
 
-    // This mirrors the current logic in blockstore to detect coding shreds with conflicting
-    // erasure sets. However this is not technically exhaustive, as any 2 shreds with
-    // different but overlapping erasure sets can be considered duplicate and need not be
-    // a part of the same fec set. Further work to enhance detection is planned in
-    // https://github.com/solana-labs/solana/issues/33037
-    if shred1.fec_set_index() == shred2.fec_set_index()
-        && !ErasureMeta::check_erasure_consistency(shred1, shred2)
-    {
-        return Ok(());
-    }
     Err(Error::InvalidErasureMetaConflict)
 }
 
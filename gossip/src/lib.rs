@@ -21,6 +21,7 @@ pub mod gossip_error;
 pub mod gossip_service;
 #[macro_use]
 pub mod legacy_contact_info;
+pub mod node_metadata;
 pub mod ping_pong;
 mod push_active_set;
 mod received_cache;
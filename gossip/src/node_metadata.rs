@@ -0,0 +1,149 @@
+//! Application-defined metadata which a node opts into publishing through
+//! gossip, e.g. operator contact info or the endpoints of sidecar services.
+//!
+//! Each node has at most one entry in the crds table, which is signed by the
+//! node as any other crds value. Its size is bounded, and an updated entry is
+//! only propagated once the previous one is at least
+//! `NODE_METADATA_MIN_UPDATE_INTERVAL_MS` old.
+
+use {
+    crate::crds_value::{new_rand_timestamp, sanitize_wallclock, CrdsData, CrdsValue},
+    rand::Rng,
+    solana_sdk::{
+        pubkey::Pubkey,
+        sanitize::{Sanitize, SanitizeError},
+    },
+    thiserror::Error,
+};
+
+/// Maximum size in bytes of the metadata published by a node
+pub const MAX_NODE_METADATA_BYTES: usize = 512;
+
+/// Minimum interval between updates of a node's metadata
+pub const NODE_METADATA_MIN_UPDATE_INTERVAL_MS: u64 = 60 * 1000;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, AbiExample)]
+pub struct NodeMetadata {
+    pub from: Pubkey,
+    pub wallclock: u64,
+    metadata: String,
+}
+
+#[derive(Debug, PartialEq, Eq, Error)]
+pub enum NodeMetadataError {
+    #[error("node metadata is {0} bytes, exceeding the maximum of {MAX_NODE_METADATA_BYTES}")]
+    TooLarge(usize),
+    #[error("node metadata was updated less than {NODE_METADATA_MIN_UPDATE_INTERVAL_MS}ms ago")]
+    RateLimited,
+}
+
+impl NodeMetadata {
+    pub fn new(from: Pubkey, now: u64, metadata: String) -> Result<Self, NodeMetadataError> {
+        if metadata.len() > MAX_NODE_METADATA_BYTES {
+            return Err(NodeMetadataError::TooLarge(metadata.len()));
+        }
+        Ok(Self {
+            from,
+            wallclock: now,
+            metadata,
+        })
+    }
+
+    /// New random NodeMetadata for tests and benchmarks.
+    pub(crate) fn new_rand<R: Rng>(rng: &mut R, pubkey: Option<Pubkey>) -> Self {
+        let len = rng.gen_range(0..MAX_NODE_METADATA_BYTES + 1);
+        let metadata = std::iter::repeat_with(|| rng.gen_range(b'a'..=b'z') as char)
+            .take(len)
+            .collect();
+        Self {
+            from: pubkey.unwrap_or_else(solana_sdk::pubkey::new_rand),
+            wallclock: new_rand_timestamp(rng),
+            metadata,
+        }
+    }
+
+    pub fn metadata(&self) -> &str {
+        &self.metadata
+    }
+
+    // Returns whether this value replaces the crds-value if both are the
+    // metadata of the same node and this value is more recent, i.e. if the
+    // update is not rate limited. Otherwise returns None and the wallclocks
+    // decide.
+    pub(crate) fn overrides(&self, other: &CrdsValue) -> Option<bool> {
+        let CrdsData::NodeMetadata(other) = &other.data else {
+            return None;
+        };
+        if self.from != other.from || self.wallclock <= other.wallclock {
+            return None;
+        }
+        Some(self.wallclock.saturating_sub(other.wallclock) >= NODE_METADATA_MIN_UPDATE_INTERVAL_MS)
+    }
+}
+
+impl Sanitize for NodeMetadata {
+    fn sanitize(&self) -> Result<(), SanitizeError> {
+        sanitize_wallclock(self.wallclock)?;
+        if self.metadata.len() > MAX_NODE_METADATA_BYTES {
+            return Err(SanitizeError::ValueOutOfBounds);
+        }
+        self.from.sanitize()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, solana_sdk::timing::timestamp};
+
+    #[test]
+    fn test_node_metadata_size_limit() {
+        let pubkey = Pubkey::new_unique();
+        let metadata = "x".repeat(MAX_NODE_METADATA_BYTES);
+        let node_metadata = NodeMetadata::new(pubkey, timestamp(), metadata.clone()).unwrap();
+        assert_eq!(node_metadata.metadata(), metadata);
+        assert_eq!(node_metadata.sanitize(), Ok(()));
+        assert_eq!(
+            NodeMetadata::new(pubkey, timestamp(), format!("{metadata}x")),
+            Err(NodeMetadataError::TooLarge(MAX_NODE_METADATA_BYTES + 1))
+        );
+        // Values received through gossip are sanitized.
+        let node_metadata = NodeMetadata {
+            metadata: format!("{metadata}x"),
+            ..node_metadata
+        };
+        assert_eq!(
+            node_metadata.sanitize(),
+            Err(SanitizeError::ValueOutOfBounds)
+        );
+    }
+
+    #[test]
+    fn test_node_metadata_overrides() {
+        let mut rng = rand::thread_rng();
+        let pubkey = Pubkey::new_unique();
+        let now = timestamp();
+        let make_crds_value = |node_metadata: NodeMetadata| {
+            CrdsValue::new_unsigned(CrdsData::NodeMetadata(node_metadata))
+        };
+        let node_metadata = NodeMetadata::new(pubkey, now, "foo".to_string()).unwrap();
+        let other = NodeMetadata::new_rand(&mut rng, None);
+        assert_eq!(node_metadata.overrides(&make_crds_value(other)), None);
+        let other = NodeMetadata::new(pubkey, now, "bar".to_string()).unwrap();
+        assert_eq!(node_metadata.overrides(&make_crds_value(other)), None);
+        let other = NodeMetadata::new(pubkey, now + 1, "bar".to_string()).unwrap();
+        assert_eq!(node_metadata.overrides(&make_crds_value(other)), None);
+        // Updates are rate limited.
+        let other = NodeMetadata::new(pubkey, now - 1, "bar".to_string()).unwrap();
+        assert_eq!(
+            node_metadata.overrides(&make_crds_value(other)),
+            Some(false)
+        );
+        let other = NodeMetadata::new(
+            pubkey,
+            now - NODE_METADATA_MIN_UPDATE_INTERVAL_MS,
+            "bar".to_string(),
+        )
+        .unwrap();
+        assert_eq!(node_metadata.overrides(&make_crds_value(other)), Some(true));
+    }
+}
@@ -66,6 +66,8 @@ impl PushActiveSet {
         // Gossip nodes to be sampled for each push active set.
         nodes: &[Pubkey],
         stakes: &HashMap<Pubkey, u64>,
+        // Exponent of the stake bucket in the sampling weights.
+        stake_weight_exponent: u32,
     ) {
         let num_bloom_filter_items = cluster_size.max(Self::MIN_NUM_BLOOM_ITEMS);
         // Active set of nodes to push to are sampled from these gossip nodes,
@@ -86,13 +88,15 @@ impl PushActiveSet {
                     // bucket <- get_stake_bucket(min stake of {
                     //  this node, crds value owner and gossip peer
                     // })
-                    // weight <- (bucket + 1)^2
+                    // weight <- (bucket + 1)^stake_weight_exponent
                     // min stake of {...} is a proxy for how much we care about
                     // the link, and tries to mirror similar logic on the
                     // receiving end when pruning incoming links:
                     // https://github.com/solana-labs/solana/blob/81394cf92/gossip/src/received_cache.rs#L100-L105
                     let bucket = bucket.min(k) as u64;
-                    bucket.saturating_add(1).saturating_pow(2)
+                    bucket
+                        .saturating_add(1)
+                        .saturating_pow(stake_weight_exponent)
                 })
                 .collect();
             entry.rotate(rng, size, num_bloom_filter_items, nodes, &weights);
@@ -175,7 +179,10 @@ fn get_stake_bucket(stake: Option<&u64>) -> usize {
 
 #[cfg(test)]
 mod tests {
-    use {super::*, rand::SeedableRng, rand_chacha::ChaChaRng, std::iter::repeat_with};
+    use {
+        super::*, crate::crds_gossip_push::CRDS_GOSSIP_PUSH_STAKE_WEIGHT_EXPONENT,
+        rand::SeedableRng, rand_chacha::ChaChaRng, std::iter::repeat_with,
+    };
 
     #[test]
     fn test_get_stake_bucket() {
@@ -212,7 +219,14 @@ mod tests {
         stakes.insert(pubkey, rng.gen_range(1..MAX_STAKE));
         let mut active_set = PushActiveSet::default();
         assert!(active_set.0.iter().all(|entry| entry.0.is_empty()));
-        active_set.rotate(&mut rng, 5, CLUSTER_SIZE, &nodes, &stakes);
+        active_set.rotate(
+            &mut rng,
+            5,
+            CLUSTER_SIZE,
+            &nodes,
+            &stakes,
+            CRDS_GOSSIP_PUSH_STAKE_WEIGHT_EXPONENT,
+        );
         assert!(active_set.0.iter().all(|entry| entry.0.len() == 5));
         // Assert that for all entries, each filter already prunes the key.
         for entry in &active_set.0 {
@@ -237,7 +251,14 @@ mod tests {
         assert!(active_set
             .get_nodes(&pubkey, other, |_| false, &stakes)
             .eq([13, 18, 16, 0].into_iter().map(|k| &nodes[k])));
-        active_set.rotate(&mut rng, 7, CLUSTER_SIZE, &nodes, &stakes);
+        active_set.rotate(
+            &mut rng,
+            7,
+            CLUSTER_SIZE,
+            &nodes,
+            &stakes,
+            CRDS_GOSSIP_PUSH_STAKE_WEIGHT_EXPONENT,
+        );
         assert!(active_set.0.iter().all(|entry| entry.0.len() == 7));
         assert!(active_set
             .get_nodes(&pubkey, origin, |_| false, &stakes)
@@ -0,0 +1,494 @@
+//! Grinding for vanity keypairs.
+//!
+//! Each thread derives its keypairs in batches, from a batch of random seeds,
+//! and only updates the counters it shares with the other threads once per
+//! batch. With a checkpoint file, the progress of the search is saved
+//! periodically, so that a long search can be resumed after it was stopped.
+
+use {
+    bip39::{Language, Mnemonic, MnemonicType, Seed},
+    rand::{rngs::OsRng, RngCore},
+    solana_sdk::{
+        derivation_path::DerivationPath,
+        signature::{
+            keypair_from_seed, keypair_from_seed_and_derivation_path, write_keypair_file, Keypair,
+            Signer,
+        },
+        timing::AtomicInterval,
+    },
+    std::{
+        error, fmt, fs,
+        path::{Path, PathBuf},
+        str::FromStr,
+        sync::{
+            atomic::{AtomicBool, AtomicU64, Ordering},
+            Arc, Mutex,
+        },
+        thread,
+        time::Instant,
+    },
+};
+
+/// Number of keypairs each thread derives between updates of the counters
+/// shared with the other threads
+const GRIND_BATCH_SIZE: u64 = 256;
+const SEED_LEN: usize = 32;
+const REPORT_ATTEMPTS_INTERVAL: u64 = 1_000_000;
+const CHECKPOINT_INTERVAL_MS: u64 = 10_000;
+const CHECKPOINT_HEADER: &str = "# solana-keygen grind checkpoint";
+
+pub(crate) struct GrindMatch {
+    pub(crate) starts: String,
+    pub(crate) ends: String,
+    pub(crate) count: AtomicU64,
+}
+
+impl GrindMatch {
+    /// Whether the base58 encoded `pubkey` starts and ends like this match,
+    /// without allocating
+    fn is_match(&self, pubkey: &str, ignore_case: bool) -> bool {
+        let pubkey = pubkey.as_bytes();
+        let starts = self.starts.as_bytes();
+        let ends = self.ends.as_bytes();
+        if pubkey.len() < starts.len() || pubkey.len() < ends.len() {
+            return false;
+        }
+        let prefix = &pubkey[..starts.len()];
+        let suffix = &pubkey[pubkey.len() - ends.len()..];
+        if ignore_case {
+            prefix.eq_ignore_ascii_case(starts) && suffix.eq_ignore_ascii_case(ends)
+        } else {
+            prefix == starts && suffix == ends
+        }
+    }
+
+    /// Claims one of the keypairs left to find, or returns false if another
+    /// thread found the last one already
+    fn claim(&self) -> bool {
+        self.count
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |count| {
+                count.checked_sub(1)
+            })
+            .is_ok()
+    }
+
+    fn is_done(&self) -> bool {
+        self.count.load(Ordering::Relaxed) == 0
+    }
+}
+
+pub(crate) struct GrindConfig {
+    pub(crate) num_threads: usize,
+    pub(crate) ignore_case: bool,
+    /// Skip the pubkeys of length 44, which none of the prefixes can match
+    pub(crate) skip_len_44_pubkeys: bool,
+    pub(crate) use_mnemonic: bool,
+    pub(crate) mnemonic_type: MnemonicType,
+    pub(crate) language: Language,
+    pub(crate) passphrase: String,
+    pub(crate) passphrase_message: String,
+    pub(crate) derivation_path: Option<DerivationPath>,
+    pub(crate) no_outfile: bool,
+    pub(crate) checkpoint_path: Option<PathBuf>,
+}
+
+/// The progress of a search, which a checkpoint file records
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct GrindCheckpoint {
+    ignore_case: bool,
+    attempts: u64,
+    found: u64,
+    elapsed_secs: u64,
+    /// The prefix, the suffix and the number of keypairs left to find of
+    /// each match
+    matches: Vec<(String, String, u64)>,
+}
+
+impl GrindCheckpoint {
+    /// The checkpoint at `path`, or `None` if there is none
+    fn read(path: &Path) -> Result<Option<Self>, Box<dyn error::Error>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let checkpoint = fs::read_to_string(path)
+            .map_err(|err| format!("Unable to read checkpoint {path:?}: {err}"))?;
+        Ok(Some(checkpoint.parse().map_err(|err| {
+            format!("Invalid checkpoint {path:?}: {err}")
+        })?))
+    }
+
+    /// Replaces the checkpoint at `path`, so that it is never left half
+    /// written
+    fn write(&self, path: &Path) -> std::io::Result<()> {
+        let tmp_path = path.with_extension("tmp");
+        fs::write(&tmp_path, self.to_string())?;
+        fs::rename(tmp_path, path)
+    }
+
+    /// Resumes the search of this checkpoint, which must be for the same
+    /// keypairs as `grind_matches`
+    fn resume(&self, grind_matches: &[GrindMatch], ignore_case: bool) -> Result<(), String> {
+        let mut matches: Vec<_> = self
+            .matches
+            .iter()
+            .map(|(starts, ends, _count)| (starts.as_str(), ends.as_str()))
+            .collect();
+        let mut expected_matches: Vec<_> = grind_matches
+            .iter()
+            .map(|grind_match| (grind_match.starts.as_str(), grind_match.ends.as_str()))
+            .collect();
+        matches.sort_unstable();
+        expected_matches.sort_unstable();
+        if self.ignore_case != ignore_case || matches != expected_matches {
+            return Err("the checkpoint is for a search for other keypairs".to_string());
+        }
+        for grind_match in grind_matches {
+            let (_starts, _ends, count) = self
+                .matches
+                .iter()
+                .find(|(starts, ends, _count)| {
+                    *starts == grind_match.starts && *ends == grind_match.ends
+                })
+                .unwrap();
+            grind_match.count.store(*count, Ordering::Relaxed);
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for GrindCheckpoint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{CHECKPOINT_HEADER}")?;
+        writeln!(f, "ignore_case={}", self.ignore_case)?;
+        writeln!(f, "attempts={}", self.attempts)?;
+        writeln!(f, "found={}", self.found)?;
+        writeln!(f, "elapsed_secs={}", self.elapsed_secs)?;
+        for (starts, ends, count) in &self.matches {
+            writeln!(f, "match={starts}:{ends}:{count}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for GrindCheckpoint {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines();
+        if lines.next() != Some(CHECKPOINT_HEADER) {
+            return Err("missing header".to_string());
+        }
+        let parse_error = |line: &str| format!("unexpected line: {line}");
+        let mut checkpoint = GrindCheckpoint {
+            ignore_case: false,
+            attempts: 0,
+            found: 0,
+            elapsed_secs: 0,
+            matches: vec![],
+        };
+        for line in lines {
+            let (key, value) = line.split_once('=').ok_or_else(|| parse_error(line))?;
+            match key {
+                "ignore_case" => {
+                    checkpoint.ignore_case = value.parse().map_err(|_| parse_error(line))?
+                }
+                "attempts" => checkpoint.attempts = value.parse().map_err(|_| parse_error(line))?,
+                "found" => checkpoint.found = value.parse().map_err(|_| parse_error(line))?,
+                "elapsed_secs" => {
+                    checkpoint.elapsed_secs = value.parse().map_err(|_| parse_error(line))?
+                }
+                "match" => {
+                    let args: Vec<&str> = value.split(':').collect();
+                    let [starts, ends, count] = args[..] else {
+                        return Err(parse_error(line));
+                    };
+                    let count = count.parse().map_err(|_| parse_error(line))?;
+                    checkpoint
+                        .matches
+                        .push((starts.to_string(), ends.to_string(), count));
+                }
+                _ => return Err(parse_error(line)),
+            }
+        }
+        Ok(checkpoint)
+    }
+}
+
+struct Grinder {
+    grind_matches: Vec<GrindMatch>,
+    config: GrindConfig,
+    attempts: AtomicU64,
+    found: AtomicU64,
+    start: Instant,
+    /// Time spent searching before the search was resumed
+    resumed_elapsed_secs: u64,
+    done: AtomicBool,
+    last_checkpoint: AtomicInterval,
+    checkpoint_lock: Mutex<()>,
+}
+
+impl Grinder {
+    fn run(&self) {
+        let mut seeds = vec![0; GRIND_BATCH_SIZE as usize * SEED_LEN];
+        while !self.done.load(Ordering::Relaxed) {
+            for (keypair, phrase) in self.derive_batch(&mut seeds) {
+                self.check(&keypair, &phrase);
+            }
+            self.finish_batch();
+        }
+    }
+
+    /// Derives a batch of keypairs, along with their seed phrases when using
+    /// mnemonics
+    fn derive_batch(&self, seeds: &mut [u8]) -> Vec<(Keypair, String)> {
+        if self.config.use_mnemonic {
+            (0..GRIND_BATCH_SIZE)
+                .map(|_| {
+                    let mnemonic = Mnemonic::new(self.config.mnemonic_type, self.config.language);
+                    let seed = Seed::new(&mnemonic, &self.config.passphrase);
+                    let keypair = match self.config.derivation_path {
+                        Some(_) => keypair_from_seed_and_derivation_path(
+                            seed.as_bytes(),
+                            self.config.derivation_path.clone(),
+                        ),
+                        None => keypair_from_seed(seed.as_bytes()),
+                    }
+                    .unwrap();
+                    (keypair, mnemonic.phrase().to_string())
+                })
+                .collect()
+        } else {
+            OsRng.fill_bytes(seeds);
+            seeds
+                .chunks_exact(SEED_LEN)
+                .map(|seed| (keypair_from_seed(seed).unwrap(), String::new()))
+                .collect()
+        }
+    }
+
+    fn check(&self, keypair: &Keypair, phrase: &str) {
+        // Skip keypairs that will never match the user specified prefix
+        if self.config.skip_len_44_pubkeys
+            && keypair.pubkey() >= crate::smallest_length_44_public_key::PUBKEY
+        {
+            return;
+        }
+        let pubkey = bs58::encode(keypair.pubkey()).into_string();
+        for grind_match in &self.grind_matches {
+            if grind_match.is_done()
+                || !grind_match.is_match(&pubkey, self.config.ignore_case)
+                || !grind_match.claim()
+            {
+                continue;
+            }
+            self.found.fetch_add(1, Ordering::Relaxed);
+            if !self.config.no_outfile {
+                write_keypair_file(keypair, &format!("{}.json", keypair.pubkey())).unwrap();
+                println!("Wrote keypair to {}", &format!("{}.json", keypair.pubkey()));
+            }
+            if self.config.use_mnemonic {
+                let divider = String::from_utf8(vec![b'='; phrase.len()]).unwrap();
+                println!("{}\nFound matching key {}", &divider, keypair.pubkey());
+                println!(
+                    "\nSave this seed phrase{} to recover your new keypair:\n{}\n{}",
+                    self.config.passphrase_message, phrase, &divider
+                );
+            }
+            // so that the keypair is not searched for again after resuming
+            self.save_checkpoint();
+        }
+    }
+
+    fn finish_batch(&self) {
+        let attempts = self
+            .attempts
+            .fetch_add(GRIND_BATCH_SIZE, Ordering::Relaxed)
+            .saturating_add(GRIND_BATCH_SIZE);
+        if attempts / REPORT_ATTEMPTS_INTERVAL
+            != attempts.saturating_sub(GRIND_BATCH_SIZE) / REPORT_ATTEMPTS_INTERVAL
+        {
+            println!(
+                "Searched {} keypairs in {}s. {} matches found.",
+                attempts,
+                self.elapsed_secs(),
+                self.found.load(Ordering::Relaxed),
+            );
+        }
+        if self.grind_matches.iter().all(GrindMatch::is_done) {
+            self.done.store(true, Ordering::Relaxed);
+        } else if self.last_checkpoint.should_update(CHECKPOINT_INTERVAL_MS) {
+            self.save_checkpoint();
+        }
+    }
+
+    fn elapsed_secs(&self) -> u64 {
+        self.resumed_elapsed_secs
+            .saturating_add(self.start.elapsed().as_secs())
+    }
+
+    fn save_checkpoint(&self) {
+        let Some(checkpoint_path) = &self.config.checkpoint_path else {
+            return;
+        };
+        let _lock = self.checkpoint_lock.lock().unwrap();
+        let checkpoint = GrindCheckpoint {
+            ignore_case: self.config.ignore_case,
+            attempts: self.attempts.load(Ordering::Relaxed),
+            found: self.found.load(Ordering::Relaxed),
+            elapsed_secs: self.elapsed_secs(),
+            matches: self
+                .grind_matches
+                .iter()
+                .map(|grind_match| {
+                    (
+                        grind_match.starts.clone(),
+                        grind_match.ends.clone(),
+                        grind_match.count.load(Ordering::Relaxed),
+                    )
+                })
+                .collect(),
+        };
+        if let Err(err) = checkpoint.write(checkpoint_path) {
+            eprintln!("Unable to write checkpoint {checkpoint_path:?}: {err}");
+        }
+    }
+}
+
+/// Searches for the keypairs of `grind_matches` on `config.num_threads`
+/// threads, resuming the search of the checkpoint file, if any
+pub(crate) fn grind(
+    grind_matches: Vec<GrindMatch>,
+    config: GrindConfig,
+) -> Result<(), Box<dyn error::Error>> {
+    let checkpoint = match &config.checkpoint_path {
+        Some(checkpoint_path) => GrindCheckpoint::read(checkpoint_path)?,
+        None => None,
+    };
+    if let Some(checkpoint) = &checkpoint {
+        checkpoint
+            .resume(&grind_matches, config.ignore_case)
+            .map_err(|err| format!("Unable to resume the search: {err}"))?;
+        println!(
+            "Resuming search after {} keypairs in {}s. {} matches found.",
+            checkpoint.attempts, checkpoint.elapsed_secs, checkpoint.found,
+        );
+    }
+    let num_threads = config.num_threads;
+    let grinder = Arc::new(Grinder {
+        grind_matches,
+        config,
+        attempts: AtomicU64::new(checkpoint.as_ref().map_or(0, |c| c.attempts)),
+        found: AtomicU64::new(checkpoint.as_ref().map_or(0, |c| c.found)),
+        start: Instant::now(),
+        resumed_elapsed_secs: checkpoint.as_ref().map_or(0, |c| c.elapsed_secs),
+        done: AtomicBool::new(false),
+        last_checkpoint: AtomicInterval::default(),
+        checkpoint_lock: Mutex::default(),
+    });
+    if !grinder.grind_matches.iter().all(GrindMatch::is_done) {
+        let thread_handles: Vec<_> = (0..num_threads)
+            .map(|_| {
+                let grinder = grinder.clone();
+                thread::spawn(move || grinder.run())
+            })
+            .collect();
+        for thread_handle in thread_handles {
+            thread_handle.join().unwrap();
+        }
+    }
+
+    // the search is complete, there is nothing left to resume
+    if let Some(checkpoint_path) = &grinder.config.checkpoint_path {
+        let _lock = grinder.checkpoint_lock.lock().unwrap();
+        if checkpoint_path.exists() {
+            fs::remove_file(checkpoint_path)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, tempfile::tempdir};
+
+    fn new_grind_match(starts: &str, ends: &str, count: u64) -> GrindMatch {
+        GrindMatch {
+            starts: starts.to_string(),
+            ends: ends.to_string(),
+            count: AtomicU64::new(count),
+        }
+    }
+
+    #[test]
+    fn test_grind_match() {
+        let pubkey = "So11111111111111111111111111111111111111112";
+        assert!(new_grind_match("So1", "", 1).is_match(pubkey, false));
+        assert!(new_grind_match("", "112", 1).is_match(pubkey, false));
+        assert!(new_grind_match("So1", "112", 1).is_match(pubkey, false));
+        assert!(!new_grind_match("so1", "112", 1).is_match(pubkey, false));
+        assert!(new_grind_match("so1", "112", 1).is_match(pubkey, true));
+        assert!(!new_grind_match("So1", "113", 1).is_match(pubkey, false));
+        assert!(!new_grind_match(pubkey, "1", 1).is_match("So1", false));
+
+        let grind_match = new_grind_match("So1", "", 2);
+        assert!(grind_match.claim());
+        assert!(!grind_match.is_done());
+        assert!(grind_match.claim());
+        assert!(grind_match.is_done());
+        assert!(!grind_match.claim());
+        assert_eq!(grind_match.count.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn test_grind_checkpoint() {
+        let checkpoint = GrindCheckpoint {
+            ignore_case: true,
+            attempts: 42_000_000,
+            found: 1,
+            elapsed_secs: 600,
+            matches: vec![
+                ("sol".to_string(), "".to_string(), 3),
+                ("".to_string(), "ana".to_string(), 0),
+                ("sol".to_string(), "ana".to_string(), 1),
+            ],
+        };
+        let dir = tempdir().unwrap();
+        let checkpoint_path = dir.path().join("grind.checkpoint");
+        assert_eq!(GrindCheckpoint::read(&checkpoint_path).unwrap(), None);
+        checkpoint.write(&checkpoint_path).unwrap();
+        assert_eq!(
+            GrindCheckpoint::read(&checkpoint_path).unwrap(),
+            Some(checkpoint)
+        );
+        assert!("attempts=1".parse::<GrindCheckpoint>().is_err());
+        assert!(format!("{CHECKPOINT_HEADER}\nmatch=sol:4")
+            .parse::<GrindCheckpoint>()
+            .is_err());
+    }
+
+    #[test]
+    fn test_grind_checkpoint_resume() {
+        let checkpoint = GrindCheckpoint {
+            ignore_case: false,
+            attempts: 1_000,
+            found: 1,
+            elapsed_secs: 1,
+            matches: vec![
+                ("sol".to_string(), "".to_string(), 3),
+                ("sol".to_string(), "ana".to_string(), 0),
+            ],
+        };
+        let grind_matches = vec![
+            new_grind_match("sol", "ana", 1),
+            new_grind_match("sol", "", 4),
+        ];
+        checkpoint.resume(&grind_matches, false).unwrap();
+        assert_eq!(grind_matches[0].count.load(Ordering::Relaxed), 0);
+        assert_eq!(grind_matches[1].count.load(Ordering::Relaxed), 3);
+
+        assert!(checkpoint.resume(&grind_matches, true).is_err());
+        assert!(checkpoint
+            .resume(&[new_grind_match("sol", "", 4)], false)
+            .is_err());
+    }
+}
@@ -3,6 +3,7 @@
 use {
     bip39::{Mnemonic, MnemonicType, Seed},
     clap::{crate_description, crate_name, value_parser, Arg, ArgMatches, Command},
+    grind::{grind, GrindConfig, GrindMatch},
     solana_clap_v3_utils::{
         input_parsers::STDOUT_OUTFILE_TOKEN,
         input_validators::is_prompt_signer_source,
@@ -35,16 +36,14 @@ use {
     std::{
         collections::HashSet,
         error,
+        path::PathBuf,
         rc::Rc,
-        sync::{
-            atomic::{AtomicBool, AtomicU64, Ordering},
-            Arc,
-        },
-        thread,
-        time::Instant,
+        sync::atomic::{AtomicU64, Ordering},
     },
 };
 
+mod grind;
+
 mod smallest_length_44_public_key {
     use solana_sdk::{pubkey, pubkey::Pubkey};
 
@@ -57,12 +56,6 @@ mod smallest_length_44_public_key {
     }
 }
 
-struct GrindMatch {
-    starts: String,
-    ends: String,
-    count: AtomicU64,
-}
-
 fn get_keypair_from_matches(
     matches: &ArgMatches,
     config: Config,
@@ -344,6 +337,13 @@ fn app<'a>(num_threads: &'a str, crate_version: &'a str) -> Command<'a> {
                         .default_value(num_threads)
                         .help("Specify the number of grind threads"),
                 )
+                .arg(
+                    Arg::new("checkpoint")
+                        .long("checkpoint")
+                        .value_name("FILEPATH")
+                        .takes_value(true)
+                        .help("Periodically save the progress of the search to this file, and resume the search it records if it exists. The file is removed once all the keypairs are found"),
+                )
                 .arg(
                     Arg::new("use_mnemonic")
                         .long("use-mnemonic")
@@ -617,105 +617,22 @@ fn do_main(matches: &ArgMatches) -> Result<(), Box<dyn error::Error>> {
                 .filter_map(|s| s.ok())
                 .all(|s| s.len() > 32);
 
-            let grind_matches_thread_safe = Arc::new(grind_matches);
-            let attempts = Arc::new(AtomicU64::new(1));
-            let found = Arc::new(AtomicU64::new(0));
-            let start = Instant::now();
-            let done = Arc::new(AtomicBool::new(false));
-
-            let thread_handles: Vec<_> = (0..num_threads)
-                .map(|_| {
-                    let done = done.clone();
-                    let attempts = attempts.clone();
-                    let found = found.clone();
-                    let grind_matches_thread_safe = grind_matches_thread_safe.clone();
-                    let passphrase = passphrase.clone();
-                    let passphrase_message = passphrase_message.clone();
-                    let derivation_path = derivation_path.clone();
-
-                    thread::spawn(move || loop {
-                        if done.load(Ordering::Relaxed) {
-                            break;
-                        }
-                        let attempts = attempts.fetch_add(1, Ordering::Relaxed);
-                        if attempts % 1_000_000 == 0 {
-                            println!(
-                                "Searched {} keypairs in {}s. {} matches found.",
-                                attempts,
-                                start.elapsed().as_secs(),
-                                found.load(Ordering::Relaxed),
-                            );
-                        }
-                        let (keypair, phrase) = if use_mnemonic {
-                            let mnemonic = Mnemonic::new(mnemonic_type, language);
-                            let seed = Seed::new(&mnemonic, &passphrase);
-                            let keypair = match derivation_path {
-                                Some(_) => keypair_from_seed_and_derivation_path(seed.as_bytes(), derivation_path.clone()),
-                                None => keypair_from_seed(seed.as_bytes()),
-                            }.unwrap();
-                            (keypair, mnemonic.phrase().to_string())
-                        } else {
-                            (Keypair::new(), "".to_string())
-                        };
-                        // Skip keypairs that will never match the user specified prefix
-                        if skip_len_44_pubkeys && keypair.pubkey() >= smallest_length_44_public_key::PUBKEY {
-                            continue;
-                        }
-                        let mut pubkey = bs58::encode(keypair.pubkey()).into_string();
-                        if ignore_case {
-                            pubkey = pubkey.to_lowercase();
-                        }
-                        let mut total_matches_found = 0;
-                        for i in 0..grind_matches_thread_safe.len() {
-                            if grind_matches_thread_safe[i].count.load(Ordering::Relaxed) == 0 {
-                                total_matches_found += 1;
-                                continue;
-                            }
-                            if (!grind_matches_thread_safe[i].starts.is_empty()
-                                && grind_matches_thread_safe[i].ends.is_empty()
-                                && pubkey.starts_with(&grind_matches_thread_safe[i].starts))
-                                || (grind_matches_thread_safe[i].starts.is_empty()
-                                    && !grind_matches_thread_safe[i].ends.is_empty()
-                                    && pubkey.ends_with(&grind_matches_thread_safe[i].ends))
-                                || (!grind_matches_thread_safe[i].starts.is_empty()
-                                    && !grind_matches_thread_safe[i].ends.is_empty()
-                                    && pubkey.starts_with(&grind_matches_thread_safe[i].starts)
-                                    && pubkey.ends_with(&grind_matches_thread_safe[i].ends))
-                            {
-                                let _found = found.fetch_add(1, Ordering::Relaxed);
-                                grind_matches_thread_safe[i]
-                                    .count
-                                    .fetch_sub(1, Ordering::Relaxed);
-                                if !no_outfile {
-                                    write_keypair_file(&keypair, &format!("{}.json", keypair.pubkey()))
-                                    .unwrap();
-                                    println!(
-                                        "Wrote keypair to {}",
-                                        &format!("{}.json", keypair.pubkey())
-                                    );
-                                }
-                                if use_mnemonic {
-                                    let divider = String::from_utf8(vec![b'='; phrase.len()]).unwrap();
-                                    println!(
-                                        "{}\nFound matching key {}",
-                                        &divider, keypair.pubkey());
-                                    println!(
-                                        "\nSave this seed phrase{} to recover your new keypair:\n{}\n{}",
-                                        passphrase_message, phrase, &divider
-                                    );
-                                }
-                            }
-                        }
-                        if total_matches_found == grind_matches_thread_safe.len() {
-                            done.store(true, Ordering::Relaxed);
-                        }
-                    })
-                })
-                .collect();
-
-            for thread_handle in thread_handles {
-                thread_handle.join().unwrap();
-            }
+            grind(
+                grind_matches,
+                GrindConfig {
+                    num_threads,
+                    ignore_case,
+                    skip_len_44_pubkeys,
+                    use_mnemonic,
+                    mnemonic_type,
+                    language,
+                    passphrase,
+                    passphrase_message,
+                    derivation_path,
+                    no_outfile,
+                    checkpoint_path: matches.value_of("checkpoint").map(PathBuf::from),
+                },
+            )?;
         }
         ("verify", matches) => {
             let keypair = get_keypair_from_matches(matches, config, &mut wallet_manager)?;
@@ -1111,5 +1028,37 @@ mod tests {
             "b:1",
         ])
         .unwrap();
+
+        process_test_command(&[
+            "solana-keygen",
+            "grind",
+            "--no-outfile",
+            "--no-bip39-passphrase",
+            "--use-mnemonic",
+            "--ignore-case",
+            "--starts-and-ends-with",
+            "a:b:1",
+        ])
+        .unwrap();
+    }
+
+    #[test]
+    fn test_grind_checkpoint() {
+        let checkpoint_dir = tempdir().unwrap();
+        let checkpoint_path = tmp_outfile_path(&checkpoint_dir, "grind.checkpoint");
+        process_test_command(&[
+            "solana-keygen",
+            "grind",
+            "--no-outfile",
+            "--no-bip39-passphrase",
+            "--use-mnemonic",
+            "--starts-with",
+            "a:1",
+            "--checkpoint",
+            &checkpoint_path,
+        ])
+        .unwrap();
+        // the search completed
+        assert!(!std::path::Path::new(&checkpoint_path).exists());
     }
 }
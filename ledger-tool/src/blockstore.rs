@@ -14,7 +14,10 @@ use {
     log::*,
     regex::Regex,
     serde_json::json,
-    solana_clap_utils::{hidden_unless_forced, input_validators::is_slot},
+    solana_clap_utils::{
+        hidden_unless_forced,
+        input_validators::{is_parsable, is_slot},
+    },
     solana_cli_output::OutputFormat,
     solana_ledger::{
         ancestor_iterator::AncestorIterator,
@@ -24,7 +27,7 @@ use {
         shred::Shred,
     },
     solana_sdk::{
-        clock::{Slot, UnixTimestamp},
+        clock::{Epoch, Slot, UnixTimestamp},
         hash::Hash,
     },
     std::{
@@ -133,6 +136,7 @@ fn analyze_storage(database: &Database) {
     analyze_column::<BlockHeight>(database, "BlockHeight");
     analyze_column::<ProgramCosts>(database, "ProgramCosts");
     analyze_column::<OptimisticSlots>(database, "OptimisticSlots");
+    analyze_column::<EpochStakeHistory>(database, "EpochStakeHistory");
 }
 
 fn raw_key_to_slot(key: &[u8], column_name: &str) -> Option<Slot> {
@@ -350,6 +354,29 @@ pub fn blockstore_subcommands<'a, 'b>(hidden: bool) -> Vec<App<'a, 'b>> {
             .about("Print all the duplicate slots in the ledger")
             .settings(&hidden)
             .arg(&starting_slot_arg),
+        SubCommand::with_name("epoch-stake-history")
+            .about(
+                "Print the stake history entry and rewards partitions recorded for each epoch \
+                 as its boundary was rooted",
+            )
+            .settings(&hidden)
+            .arg(
+                Arg::with_name("starting_epoch")
+                    .long("starting-epoch")
+                    .value_name("EPOCH")
+                    .takes_value(true)
+                    .validator(is_parsable::<Epoch>)
+                    .default_value("0")
+                    .help("Start at this epoch"),
+            )
+            .arg(
+                Arg::with_name("ending_epoch")
+                    .long("ending-epoch")
+                    .value_name("EPOCH")
+                    .takes_value(true)
+                    .validator(is_parsable::<Epoch>)
+                    .help("The last epoch to iterate to"),
+            ),
         SubCommand::with_name("json")
             .about("Print the ledger in JSON format")
             .settings(&hidden)
@@ -725,6 +752,48 @@ pub fn blockstore_process_command(ledger_path: &Path, matches: &ArgMatches<'_>)
                 println!("{slot}");
             }
         }
+        ("epoch-stake-history", Some(arg_matches)) => {
+            let blockstore =
+                crate::open_blockstore(&ledger_path, arg_matches, AccessType::Secondary);
+            let starting_epoch = value_t_or_exit!(arg_matches, "starting_epoch", Epoch);
+            let ending_epoch = value_t!(arg_matches, "ending_epoch", Epoch).unwrap_or(Epoch::MAX);
+
+            println!(
+                "{:>8} {:>12} {:>20} {:>20} {:>20} {:>20} {:>10}",
+                "Epoch",
+                "Slot",
+                "Effective",
+                "Activating",
+                "Deactivating",
+                "Total Rewards",
+                "Partitions"
+            );
+            for (epoch, meta) in blockstore
+                .epoch_stake_history_iterator(starting_epoch)
+                .unwrap()
+                .take_while(|(epoch, _)| *epoch <= ending_epoch)
+            {
+                let (total_rewards, num_partitions) = meta
+                    .rewards_partitions
+                    .map(|partitions| {
+                        (
+                            partitions.total_rewards.to_string(),
+                            partitions.num_partitions.to_string(),
+                        )
+                    })
+                    .unwrap_or_else(|| ("-".to_string(), "-".to_string()));
+                println!(
+                    "{:>8} {:>12} {:>20} {:>20} {:>20} {:>20} {:>10}",
+                    epoch,
+                    meta.slot,
+                    meta.stake_history_entry.effective,
+                    meta.stake_history_entry.activating,
+                    meta.stake_history_entry.deactivating,
+                    total_rewards,
+                    num_partitions
+                );
+            }
+        }
         ("json", Some(arg_matches)) => {
             let starting_slot = value_t_or_exit!(arg_matches, "starting_slot", Slot);
             let ending_slot = value_t!(arg_matches, "ending_slot", Slot).unwrap_or(Slot::MAX);
@@ -10,12 +10,13 @@ use {
             output_account, AccountsOutputConfig, AccountsOutputMode, AccountsOutputStreamer,
         },
         program::*,
+        stake_history::StakeHistoryVerifier,
     },
     clap::{
         crate_description, crate_name, value_t, value_t_or_exit, values_t_or_exit, App,
         AppSettings, Arg, ArgMatches, SubCommand,
     },
-    dashmap::DashMap,
+    dashmap::{DashMap, DashSet},
     log::*,
     serde::Serialize,
     solana_account_decoder::UiAccountEncoding,
@@ -34,6 +35,7 @@ use {
     },
     solana_cli_output::OutputFormat,
     solana_core::{
+        banking_simulation::{BankingSimulator, SimulatedBlock},
         system_monitor_service::{SystemMonitorService, SystemMonitorStatsReportConfig},
         validator::BlockVerificationMethod,
     },
@@ -99,6 +101,7 @@ mod ledger_path;
 mod ledger_utils;
 mod output;
 mod program;
+mod stake_history;
 
 fn parse_encoding_format(matches: &ArgMatches<'_>) -> UiAccountEncoding {
     match matches.value_of("encoding") {
@@ -502,6 +505,87 @@ fn compute_slot_cost(blockstore: &Blockstore, slot: Slot) -> Result<(), String>
     Ok(())
 }
 
+/// Loads the transactions of `slot`, resolving the addresses of transactions
+/// using address lookup tables from their transaction status, if recorded.
+/// Returns the transactions and the number that failed to be sanitized.
+fn load_slot_transactions(
+    blockstore: &Blockstore,
+    slot: Slot,
+) -> Result<(Vec<SanitizedTransaction>, usize), String> {
+    if blockstore.is_dead(slot) {
+        return Err(format!("Slot: {slot}, Dead slot"));
+    }
+
+    let (entries, _num_shreds, _is_full) = blockstore
+        .get_slot_entries_with_shred_info(slot, 0, false)
+        .map_err(|err| format!("Slot: {slot}, Failed to load entries, err {err:?}"))?;
+
+    let mut num_failed = 0;
+    let transactions = entries
+        .into_iter()
+        .flat_map(|entry| entry.transactions)
+        .filter_map(|transaction| {
+            let uses_lookup_tables = transaction
+                .message
+                .address_table_lookups()
+                .is_some_and(|lookups| !lookups.is_empty());
+            let status = uses_lookup_tables
+                .then(|| transaction.signatures.first())
+                .flatten()
+                .and_then(|signature| {
+                    blockstore
+                        .read_transaction_status((*signature, slot))
+                        .ok()
+                        .flatten()
+                });
+            let address_loader = match status {
+                Some(status) => SimpleAddressLoader::Enabled(status.loaded_addresses),
+                None => SimpleAddressLoader::Disabled,
+            };
+            SanitizedTransaction::try_create(
+                transaction,
+                MessageHash::Compute,
+                None,
+                address_loader,
+            )
+            .map_err(|err| {
+                warn!("Slot: {slot}, Failed to sanitize transaction: {err:?}");
+                num_failed += 1;
+            })
+            .ok()
+        })
+        .collect();
+    Ok((transactions, num_failed))
+}
+
+fn print_simulated_block(label: &str, block: &SimulatedBlock, recorded_cost: u64) {
+    println!(
+        "{label}: Transactions: {}, Votes: {}, Included: {}, Dropped: {}, Scheduling passes: {}",
+        block.num_transactions,
+        block.num_votes,
+        block.num_included,
+        block.num_dropped(),
+        block.num_scheduling_passes,
+    );
+    println!(
+        "  Dropped on: compute budget: {}, block limit: {}, account limit: {}, vote limit: {}, \
+         account data limit: {}, unscheduled: {}",
+        block.num_dropped_on_compute_budget,
+        block.num_dropped_on_block_limit,
+        block.num_dropped_on_account_limit,
+        block.num_dropped_on_vote_limit,
+        block.num_dropped_on_account_data_limit,
+        block.num_unscheduled,
+    );
+    println!(
+        "  Block cost: {} of {} ({:.2}% fill), recorded block cost: {recorded_cost}",
+        block.block_cost,
+        block.block_cost_limit,
+        block.block_fill_percent(),
+    );
+    println!("  Cost per worker: {:?}", block.cost_per_worker);
+}
+
 /// Finds the accounts needed to replay slots `snapshot_slot` to `ending_slot`.
 /// Removes all other accounts from accounts_db, and updates the accounts hash
 /// and capitalization. This is used by the --minimize option in create-snapshot
@@ -1297,6 +1381,90 @@ fn main() {
                         .conflicts_with("no_snapshot"),
                 ),
         )
+        .subcommand(
+            SubCommand::with_name("create-dev-snapshot")
+                .about(
+                    "Create a snapshot containing only selected programs and the accounts \
+                     needed to boot a validator from it",
+                )
+                .arg(&no_snapshot_arg)
+                .arg(&account_paths_arg)
+                .arg(&accounts_hash_cache_path_arg)
+                .arg(&accounts_index_bins)
+                .arg(&accounts_index_limit)
+                .arg(&disable_disk_index)
+                .arg(&accountsdb_verify_refcounts)
+                .arg(&accounts_db_skip_initial_hash_calc_arg)
+                .arg(&accountsdb_skip_shrink)
+                .arg(&hard_forks_arg)
+                .arg(&max_genesis_archive_unpacked_size_arg)
+                .arg(&snapshot_version_arg)
+                .arg(&maximum_full_snapshot_archives_to_retain)
+                .arg(&maximum_incremental_snapshot_archives_to_retain)
+                .arg(&use_snapshot_archives_at_startup)
+                .arg(
+                    Arg::with_name("snapshot_slot")
+                        .index(1)
+                        .value_name("SLOT")
+                        .validator(|value| {
+                            if value.parse::<Slot>().is_ok() || value == "ROOT" {
+                                Ok(())
+                            } else {
+                                Err(format!(
+                                    "Unable to parse as a number or the keyword ROOT, provided: \
+                                     {value}"
+                                ))
+                            }
+                        })
+                        .takes_value(true)
+                        .required(true)
+                        .help(
+                            "Slot at which to create the snapshot; accepts keyword ROOT for the \
+                             highest root",
+                        ),
+                )
+                .arg(
+                    Arg::with_name("output_directory")
+                        .index(2)
+                        .value_name("DIR")
+                        .takes_value(true)
+                        .help(
+                            "Output directory for the snapshot \
+                            [default: --snapshot-archive-path if present else --ledger directory]",
+                        ),
+                )
+                .arg(
+                    Arg::with_name("programs")
+                        .long("program")
+                        .takes_value(true)
+                        .value_name("PUBKEY")
+                        .validator(is_pubkey)
+                        .multiple(true)
+                        .required(true)
+                        .help(
+                            "Program to keep in the snapshot, along with its programdata and \
+                             all the accounts it owns",
+                        ),
+                )
+                .arg(
+                    Arg::with_name("accounts")
+                        .long("account")
+                        .takes_value(true)
+                        .value_name("PUBKEY")
+                        .validator(is_pubkey)
+                        .multiple(true)
+                        .help("Additional account to keep in the snapshot"),
+                )
+                .arg(
+                    Arg::with_name("snapshot_archive_format")
+                        .long("snapshot-archive-format")
+                        .possible_values(SUPPORTED_ARCHIVE_COMPRESSION)
+                        .default_value(DEFAULT_ARCHIVE_COMPRESSION)
+                        .value_name("ARCHIVE_TYPE")
+                        .takes_value(true)
+                        .help("Snapshot archive format to use."),
+                ),
+        )
         .subcommand(
             SubCommand::with_name("accounts")
                 .about("Print account stats and contents after processing the ledger")
@@ -1419,6 +1587,62 @@ fn main() {
                         .help("Output file in the csv format"),
                 ),
         )
+        .subcommand(
+            SubCommand::with_name("verify-stake-history")
+                .about(
+                    "Replay the ledger and verify that the stake history sysvar entry of each \
+                     epoch matches the activating, effective and deactivating stake \
+                     recalculated from the stake delegations",
+                )
+                .arg(&no_snapshot_arg)
+                .arg(&account_paths_arg)
+                .arg(&accounts_hash_cache_path_arg)
+                .arg(&accounts_index_bins)
+                .arg(&accounts_index_limit)
+                .arg(&disable_disk_index)
+                .arg(&accountsdb_verify_refcounts)
+                .arg(&accounts_db_test_skip_rewrites_but_include_in_bank_hash)
+                .arg(&accounts_db_skip_initial_hash_calc_arg)
+                .arg(&halt_at_slot_arg)
+                .arg(&hard_forks_arg)
+                .arg(&max_genesis_archive_unpacked_size_arg)
+                .arg(&geyser_plugin_args)
+                .arg(&use_snapshot_archives_at_startup),
+        )
+        .subcommand(
+            SubCommand::with_name("simulate-block-production")
+                .about(
+                    "Feed the transactions of each block in a slot range through the banking \
+                     stage's transaction scheduler, without executing them, and report how \
+                     much of each block could be filled",
+                )
+                .arg(
+                    Arg::with_name("starting_slot")
+                        .long("starting-slot")
+                        .value_name("SLOT")
+                        .validator(is_slot)
+                        .takes_value(true)
+                        .default_value("0")
+                        .help("Start at this slot"),
+                )
+                .arg(
+                    Arg::with_name("ending_slot")
+                        .long("ending-slot")
+                        .value_name("SLOT")
+                        .validator(is_slot)
+                        .takes_value(true)
+                        .help("The last slot to simulate, default to the last slot in ledger"),
+                )
+                .arg(
+                    Arg::with_name("num_workers")
+                        .long("num-workers")
+                        .value_name("NUMBER")
+                        .validator(is_parsable::<usize>)
+                        .takes_value(true)
+                        .default_value("4")
+                        .help("Number of banking threads consuming scheduled transactions"),
+                ),
+        )
         .subcommand(
             SubCommand::with_name("compute-slot-cost")
                 .about(
@@ -2180,6 +2404,137 @@ fn main() {
                         compute_shred_version(&genesis_config.hash(), Some(&bank.hard_forks()))
                     );
                 }
+                ("create-dev-snapshot", Some(arg_matches)) => {
+                    let output_directory = value_t!(arg_matches, "output_directory", PathBuf)
+                        .unwrap_or_else(|_| {
+                            snapshot_archive_path
+                                .clone()
+                                .unwrap_or_else(|| ledger_path.clone())
+                        });
+                    let program_ids = pubkeys_of(arg_matches, "programs").unwrap_or_default();
+                    let additional_accounts: DashSet<_> = pubkeys_of(arg_matches, "accounts")
+                        .unwrap_or_default()
+                        .into_iter()
+                        .collect();
+                    let snapshot_version = arg_matches.value_of("snapshot_version").map_or(
+                        SnapshotVersion::default(),
+                        |s| {
+                            s.parse::<SnapshotVersion>().unwrap_or_else(|e| {
+                                eprintln!("Error: {e}");
+                                exit(1)
+                            })
+                        },
+                    );
+                    let snapshot_archive_format = {
+                        let archive_format_str =
+                            value_t_or_exit!(arg_matches, "snapshot_archive_format", String);
+                        ArchiveFormat::from_cli_arg(&archive_format_str).unwrap_or_else(|| {
+                            panic!("Archive format not recognized: {archive_format_str}")
+                        })
+                    };
+                    let maximum_full_snapshot_archives_to_retain = value_t_or_exit!(
+                        arg_matches,
+                        "maximum_full_snapshots_to_retain",
+                        NonZeroUsize
+                    );
+                    let maximum_incremental_snapshot_archives_to_retain = value_t_or_exit!(
+                        arg_matches,
+                        "maximum_incremental_snapshots_to_retain",
+                        NonZeroUsize
+                    );
+                    let genesis_config = open_genesis_config_by(&ledger_path, arg_matches);
+                    let mut process_options = parse_process_options(&ledger_path, arg_matches);
+
+                    let blockstore = Arc::new(open_blockstore(
+                        &ledger_path,
+                        arg_matches,
+                        get_access_type(&process_options),
+                    ));
+
+                    let snapshot_slot = if Some("ROOT") == arg_matches.value_of("snapshot_slot") {
+                        blockstore
+                            .rooted_slot_iterator(0)
+                            .expect("Failed to get rooted slot iterator")
+                            .last()
+                            .expect("Failed to get root")
+                    } else {
+                        value_t_or_exit!(arg_matches, "snapshot_slot", Slot)
+                    };
+                    process_options.halt_at_slot = Some(snapshot_slot);
+
+                    info!(
+                        "Creating dev snapshot of slot {} with {} programs in {}",
+                        snapshot_slot,
+                        program_ids.len(),
+                        output_directory.display()
+                    );
+
+                    let (bank_forks, _) = load_and_process_ledger_or_exit(
+                        arg_matches,
+                        &genesis_config,
+                        blockstore,
+                        process_options,
+                        snapshot_archive_path,
+                        incremental_snapshot_archive_path,
+                    );
+                    let bank = bank_forks
+                        .read()
+                        .unwrap()
+                        .get(snapshot_slot)
+                        .unwrap_or_else(|| {
+                            eprintln!("Error: Slot {snapshot_slot} is not available");
+                            exit(1);
+                        });
+
+                    for program_id in &program_ids {
+                        if bank.get_account(program_id).is_none() {
+                            eprintln!("Error: Program {program_id} does not exist");
+                            exit(1);
+                        }
+                    }
+
+                    let (_, minimize_measure) = measure!(
+                        SnapshotMinimizer::minimize_to_programs(
+                            &bank,
+                            &program_ids,
+                            additional_accounts,
+                        ),
+                        "minimize to programs"
+                    );
+                    info!("{minimize_measure}");
+
+                    println!(
+                        "Creating a version {} dev snapshot of slot {}",
+                        snapshot_version,
+                        bank.slot(),
+                    );
+                    let full_snapshot_archive_info =
+                        snapshot_bank_utils::bank_to_full_snapshot_archive(
+                            ledger_path,
+                            &bank,
+                            Some(snapshot_version),
+                            output_directory.clone(),
+                            output_directory,
+                            snapshot_archive_format,
+                            maximum_full_snapshot_archives_to_retain,
+                            maximum_incremental_snapshot_archives_to_retain,
+                        )
+                        .unwrap_or_else(|err| {
+                            eprintln!("Unable to create snapshot: {err}");
+                            exit(1);
+                        });
+
+                    println!(
+                        "Successfully created dev snapshot for slot {}, hash {}: {}",
+                        bank.slot(),
+                        bank.hash(),
+                        full_snapshot_archive_info.path().display(),
+                    );
+                    println!(
+                        "Shred version: {}",
+                        compute_shred_version(&genesis_config.hash(), Some(&bank.hard_forks()))
+                    );
+                }
                 ("accounts", Some(arg_matches)) => {
                     let process_options = parse_process_options(&ledger_path, arg_matches);
                     let genesis_config = open_genesis_config_by(&ledger_path, arg_matches);
@@ -2738,6 +3093,118 @@ fn main() {
                         println!("Capitalization: {}", Sol(bank.capitalization()));
                     }
                 }
+                ("verify-stake-history", Some(arg_matches)) => {
+                    let mut process_options = parse_process_options(&ledger_path, arg_matches);
+                    let verifier = Arc::new(StakeHistoryVerifier::default());
+                    process_options.slot_callback = Some({
+                        let verifier = verifier.clone();
+                        Arc::new(move |bank: &Bank| verifier.check_epoch_boundary(bank))
+                    });
+                    let genesis_config = open_genesis_config_by(&ledger_path, arg_matches);
+                    let blockstore = open_blockstore(
+                        &ledger_path,
+                        arg_matches,
+                        get_access_type(&process_options),
+                    );
+                    let (bank_forks, _) = load_and_process_ledger_or_exit(
+                        arg_matches,
+                        &genesis_config,
+                        Arc::new(blockstore),
+                        process_options,
+                        snapshot_archive_path,
+                        incremental_snapshot_archive_path,
+                    );
+                    let working_bank = bank_forks.read().unwrap().working_bank();
+                    // Also check the epochs whose boundaries precede the
+                    // starting snapshot
+                    verifier.check_all(&working_bank);
+
+                    let (epochs, divergences) = verifier.finish();
+                    match (epochs.first(), epochs.last()) {
+                        (Some(first), Some(last)) => println!(
+                            "Checked the stake history of {} epochs, from epoch {first} to \
+                             {last}, up to slot {}",
+                            epochs.len(),
+                            working_bank.slot(),
+                        ),
+                        _ => println!(
+                            "No epoch boundary found to check the stake history, up to slot {}",
+                            working_bank.slot()
+                        ),
+                    }
+                    if !divergences.is_empty() {
+                        for divergence in &divergences {
+                            eprintln!("{divergence}");
+                        }
+                        eprintln!(
+                            "Error: {} stake history entries diverged",
+                            divergences.len()
+                        );
+                        exit(1);
+                    }
+                }
+                ("simulate-block-production", Some(arg_matches)) => {
+                    let blockstore =
+                        open_blockstore(&ledger_path, arg_matches, AccessType::Secondary);
+                    let starting_slot = value_t_or_exit!(arg_matches, "starting_slot", Slot);
+                    let ending_slot =
+                        value_t!(arg_matches, "ending_slot", Slot).unwrap_or(Slot::MAX);
+                    let num_workers = value_t_or_exit!(arg_matches, "num_workers", usize);
+                    if num_workers == 0 {
+                        eprintln!("Error: --num-workers must be greater than 0");
+                        exit(1);
+                    }
+                    let simulator = BankingSimulator::new(num_workers, FeatureSet::all_enabled());
+
+                    let slots = blockstore
+                        .slot_meta_iterator(starting_slot)
+                        .unwrap_or_else(|err| {
+                            eprintln!("Failed to load slot metas from {starting_slot}: {err:?}");
+                            exit(1);
+                        })
+                        .map(|(slot, _)| slot)
+                        .take_while(|slot| *slot <= ending_slot);
+                    let mut total = SimulatedBlock::default();
+                    let mut total_recorded_cost = 0;
+                    let mut num_slots = 0;
+                    let mut num_failed_to_sanitize = 0;
+                    for slot in slots {
+                        let (transactions, num_failed) =
+                            match load_slot_transactions(&blockstore, slot) {
+                                Ok(transactions) => transactions,
+                                Err(err) => {
+                                    eprintln!("{err}");
+                                    continue;
+                                }
+                            };
+                        num_failed_to_sanitize += num_failed;
+                        let recorded_cost: u64 = transactions
+                            .iter()
+                            .map(|transaction| {
+                                CostModel::calculate_cost(transaction, &FeatureSet::all_enabled())
+                                    .sum()
+                            })
+                            .sum();
+
+                        let block = simulator.simulate_block(transactions, CostTracker::default());
+                        print_simulated_block(&format!("Slot {slot}"), &block, recorded_cost);
+
+                        num_slots += 1;
+                        total_recorded_cost += recorded_cost;
+                        total.accumulate(&block);
+                    }
+                    print_simulated_block(
+                        &format!("Total over {num_slots} slots"),
+                        &total,
+                        total_recorded_cost,
+                    );
+                    if num_failed_to_sanitize > 0 {
+                        println!(
+                            "  {num_failed_to_sanitize} transactions could not be sanitized and \
+                             were not simulated"
+                        );
+                    }
+                }
                 ("compute-slot-cost", Some(arg_matches)) => {
                     let blockstore =
                         open_blockstore(&ledger_path, arg_matches, AccessType::Secondary);
@@ -0,0 +1,119 @@
+//! Verifies the stake history sysvar by recalculating its entries from the
+//! stake delegations cached in the epoch stakes at each epoch boundary.
+
+use {
+    log::*,
+    solana_runtime::bank::Bank,
+    solana_sdk::{
+        account::from_account,
+        clock::{Epoch, Slot},
+        stake_history::{StakeHistory, StakeHistoryEntry, MAX_ENTRIES},
+        sysvar,
+    },
+    std::{collections::BTreeMap, fmt, sync::Mutex},
+};
+
+/// A stake history sysvar entry which does not match the entry recalculated
+/// from the stake delegations
+#[derive(Debug)]
+pub struct StakeHistoryDivergence {
+    pub epoch: Epoch,
+    /// Slot of the bank whose stake history sysvar was checked
+    pub slot: Slot,
+    pub calculated: StakeHistoryEntry,
+    /// None if the sysvar has no entry for the epoch
+    pub sysvar: Option<StakeHistoryEntry>,
+}
+
+impl fmt::Display for StakeHistoryDivergence {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let format_entry = |entry: &StakeHistoryEntry| {
+            format!(
+                "effective: {}, activating: {}, deactivating: {}",
+                entry.effective, entry.activating, entry.deactivating
+            )
+        };
+        writeln!(f, "Epoch {} (checked at slot {}):", self.epoch, self.slot)?;
+        writeln!(f, "  calculated: {}", format_entry(&self.calculated))?;
+        match &self.sysvar {
+            Some(entry) => write!(f, "  sysvar:     {}", format_entry(entry)),
+            None => write!(f, "  sysvar:     missing"),
+        }
+    }
+}
+
+/// Collects the result of checking each epoch's stake history entry, as
+/// banks are replayed
+#[derive(Debug, Default)]
+pub struct StakeHistoryVerifier {
+    /// The divergence found, if any, by epoch checked
+    checked_epochs: Mutex<BTreeMap<Epoch, Option<StakeHistoryDivergence>>>,
+}
+
+impl StakeHistoryVerifier {
+    /// Checks the stake history entry of the previous epoch if `bank` is the
+    /// first bank of its epoch
+    pub fn check_epoch_boundary(&self, bank: &Bank) {
+        let parent_epoch = bank.parent().map(|parent| parent.epoch());
+        if parent_epoch != Some(bank.epoch()) {
+            self.check(bank, Some(bank.epoch()));
+        }
+    }
+
+    /// Checks the stake history entries of all the epochs that can be
+    /// recalculated from the epoch stakes of `bank`
+    pub fn check_all(&self, bank: &Bank) {
+        self.check(bank, None);
+    }
+
+    fn check(&self, bank: &Bank, stakes_epoch: Option<Epoch>) {
+        let Some(stake_history) = bank
+            .get_account(&sysvar::stake_history::id())
+            .and_then(|account| from_account::<StakeHistory, _>(&account))
+        else {
+            warn!(
+                "Slot {}: failed to load the stake history sysvar",
+                bank.slot()
+            );
+            return;
+        };
+        let new_rate_activation_epoch = bank.new_warmup_cooldown_rate_epoch();
+        let mut checked_epochs = self.checked_epochs.lock().unwrap();
+        for epoch_stakes in bank.epoch_stakes_map().values() {
+            let stakes = epoch_stakes.stakes();
+            if stakes_epoch.is_some_and(|stakes_epoch| stakes_epoch != stakes.epoch()) {
+                continue;
+            }
+            let Some((epoch, calculated)) =
+                stakes.calculate_prev_epoch_stake_history_entry(new_rate_activation_epoch)
+            else {
+                continue;
+            };
+            if checked_epochs.contains_key(&epoch) {
+                continue;
+            }
+            let sysvar = stake_history.get(epoch).cloned();
+            // Once full, the sysvar drops the entries of the oldest epochs
+            if sysvar.is_none() && stake_history.len() >= MAX_ENTRIES {
+                continue;
+            }
+            let divergence =
+                (sysvar.as_ref() != Some(&calculated)).then(|| StakeHistoryDivergence {
+                    epoch,
+                    slot: bank.slot(),
+                    calculated,
+                    sysvar,
+                });
+            checked_epochs.insert(epoch, divergence);
+        }
+    }
+
+    /// Returns the epochs checked, and the divergences found in order of
+    /// epoch, resetting the verifier
+    pub fn finish(&self) -> (Vec<Epoch>, Vec<StakeHistoryDivergence>) {
+        let checked_epochs = std::mem::take(&mut *self.checked_epochs.lock().unwrap());
+        let epochs = checked_epochs.keys().copied().collect();
+        let divergences = checked_epochs.into_values().flatten().collect();
+        (epochs, divergences)
+    }
+}
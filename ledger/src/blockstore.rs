@@ -49,7 +49,7 @@ use {
     solana_sdk::{
         account::ReadableAccount,
         address_lookup_table::state::AddressLookupTable,
-        clock::{Slot, UnixTimestamp, DEFAULT_TICKS_PER_SECOND},
+        clock::{Epoch, Slot, UnixTimestamp, DEFAULT_TICKS_PER_SECOND},
         genesis_config::{GenesisConfig, DEFAULT_GENESIS_ARCHIVE, DEFAULT_GENESIS_FILE},
         hash::Hash,
         pubkey::Pubkey,
@@ -144,7 +144,7 @@ impl std::fmt::Display for InsertDataShredError {
 pub enum PossibleDuplicateShred {
     Exists(Shred), // Blockstore has another shred in its spot
     LastIndexConflict(/* original */ Shred, /* conflict */ Vec<u8>), // The index of this shred conflicts with `slot_meta.last_index`
-    ErasureConflict(/* original */ Shred, /* conflict */ Vec<u8>), // The coding shred has a conflict in the erasure_meta
+    ErasureConflict(/* original */ Shred, /* conflict */ Vec<u8>), // The shred has a conflict in the erasure_meta
     MerkleRootConflict(/* original */ Shred, /* conflict */ Vec<u8>), // Merkle root conflict in the same fec set
 }
 
@@ -240,6 +240,7 @@ pub struct Blockstore {
     optimistic_slots_cf: LedgerColumn<cf::OptimisticSlots>,
     max_root: AtomicU64,
     merkle_root_meta_cf: LedgerColumn<cf::MerkleRootMeta>,
+    epoch_stake_history_cf: LedgerColumn<cf::EpochStakeHistory>,
     insert_shreds_lock: Mutex<()>,
     new_shreds_signals: Mutex<Vec<Sender<bool>>>,
     completed_slots_senders: Mutex<Vec<CompletedSlotsSender>>,
@@ -342,6 +343,7 @@ impl Blockstore {
         let bank_hash_cf = db.column();
         let optimistic_slots_cf = db.column();
         let merkle_root_meta_cf = db.column();
+        let epoch_stake_history_cf = db.column();
 
         let db = Arc::new(db);
 
@@ -380,6 +382,7 @@ impl Blockstore {
             bank_hash_cf,
             optimistic_slots_cf,
             merkle_root_meta_cf,
+            epoch_stake_history_cf,
             new_shreds_signals: Mutex::default(),
             completed_slots_senders: Mutex::default(),
             shred_timing_point_sender: None,
@@ -754,6 +757,7 @@ impl Blockstore {
         self.bank_hash_cf.submit_rocksdb_cf_metrics();
         self.optimistic_slots_cf.submit_rocksdb_cf_metrics();
         self.merkle_root_meta_cf.submit_rocksdb_cf_metrics();
+        self.epoch_stake_history_cf.submit_rocksdb_cf_metrics();
     }
 
     /// Report the accumulated RPC API metrics
@@ -1297,45 +1301,11 @@ impl Blockstore {
 
         if !erasure_meta.check_coding_shred(&shred) {
             metrics.num_coding_shreds_invalid_erasure_config += 1;
-            let conflicting_shred = self.find_conflicting_coding_shred(
+            self.report_erasure_conflict(
                 &shred,
-                slot,
                 erasure_meta,
                 just_received_shreds,
-            );
-            if let Some(conflicting_shred) = conflicting_shred {
-                if !self.has_duplicate_shreds_in_slot(slot) {
-                    if self
-                        .store_duplicate_slot(
-                            slot,
-                            conflicting_shred.clone(),
-                            shred.payload().clone(),
-                        )
-                        .is_err()
-                    {
-                        warn!("bad duplicate store..");
-                    }
-
-                    duplicate_shreds.push(PossibleDuplicateShred::ErasureConflict(
-                        shred.clone(),
-                        conflicting_shred,
-                    ));
-                }
-            } else {
-                datapoint_info!("bad-conflict-shred", ("slot", slot, i64));
-            }
-
-            // ToDo: This is a potential slashing condition
-            warn!("Received multiple erasure configs for the same erasure set!!!");
-            warn!(
-                "Slot: {}, shred index: {}, erasure_set: {:?}, \
-                is_duplicate: {}, stored config: {:#?}, new shred: {:#?}",
-                slot,
-                shred.index(),
-                erasure_set,
-                self.has_duplicate_shreds_in_slot(slot),
-                erasure_meta.config(),
-                shred,
+                duplicate_shreds,
             );
             return false;
         }
@@ -1365,7 +1335,51 @@ impl Blockstore {
         result
     }
 
-    fn find_conflicting_coding_shred(
+    /// Stores and reports `shred` as a duplicate of the shred which set the
+    /// erasure config of its erasure set, which `shred` is inconsistent with.
+    fn report_erasure_conflict(
+        &self,
+        shred: &Shred,
+        erasure_meta: &ErasureMeta,
+        just_received_shreds: &HashMap<ShredId, Shred>,
+        duplicate_shreds: &mut Vec<PossibleDuplicateShred>,
+    ) {
+        let slot = shred.slot();
+        let conflicting_shred =
+            self.find_conflicting_erasure_shred(shred, slot, erasure_meta, just_received_shreds);
+        if let Some(conflicting_shred) = conflicting_shred {
+            if !self.has_duplicate_shreds_in_slot(slot) {
+                if self
+                    .store_duplicate_slot(slot, conflicting_shred.clone(), shred.payload().clone())
+                    .is_err()
+                {
+                    warn!("bad duplicate store..");
+                }
+
+                duplicate_shreds.push(PossibleDuplicateShred::ErasureConflict(
+                    shred.clone(),
+                    conflicting_shred,
+                ));
+            }
+        } else {
+            datapoint_info!("bad-conflict-shred", ("slot", slot, i64));
+        }
+
+        // ToDo: This is a potential slashing condition
+        warn!("Received multiple erasure configs for the same erasure set!!!");
+        warn!(
+            "Slot: {}, shred index: {}, erasure_set: {:?}, \
+            is_duplicate: {}, stored config: {:#?}, new shred: {:#?}",
+            slot,
+            shred.index(),
+            shred.erasure_set(),
+            self.has_duplicate_shreds_in_slot(slot),
+            erasure_meta.config(),
+            shred,
+        );
+    }
+
+    fn find_conflicting_erasure_shred(
         &self,
         shred: &Shred,
         slot: Slot,
@@ -1373,21 +1387,32 @@ impl Blockstore {
         just_received_shreds: &HashMap<ShredId, Shred>,
     ) -> Option<Vec<u8>> {
         // Search for the shred which set the initial erasure config, either inserted,
-        // or in the current batch in just_received_shreds.
-        for coding_index in erasure_meta.coding_shreds_indices() {
-            let maybe_shred = self.get_coding_shred(slot, coding_index);
-            if let Ok(Some(shred_data)) = maybe_shred {
-                let potential_shred = Shred::new_from_serialized_shred(shred_data).unwrap();
-                if shred.erasure_mismatch(&potential_shred).unwrap() {
-                    return Some(potential_shred.into_payload());
-                }
+        // or in the current batch in just_received_shreds. Besides coding shreds, data
+        // shreds with an erasure config header may have set it.
+        let coding_shreds = erasure_meta
+            .coding_shreds_indices()
+            .map(|index| (index, ShredType::Code));
+        let data_shreds = erasure_meta
+            .data_shreds_indices()
+            .map(|index| (index, ShredType::Data));
+        for (index, shred_type) in coding_shreds.chain(data_shreds) {
+            let maybe_shred = match shred_type {
+                ShredType::Code => self.get_coding_shred(slot, index),
+                ShredType::Data => self.get_data_shred(slot, index),
+            };
+            let potential_shred = if let Ok(Some(shred_data)) = maybe_shred {
+                Shred::new_from_serialized_shred(shred_data).unwrap()
             } else if let Some(potential_shred) = {
-                let key = ShredId::new(slot, u32::try_from(coding_index).unwrap(), ShredType::Code);
+                let key = ShredId::new(slot, u32::try_from(index).unwrap(), shred_type);
                 just_received_shreds.get(&key)
             } {
-                if shred.erasure_mismatch(potential_shred).unwrap() {
-                    return Some(potential_shred.payload().clone());
-                }
+                potential_shred.clone()
+            } else {
+                continue;
+            };
+            // Data shreds without an erasure config can not have set it.
+            if shred.erasure_mismatch(&potential_shred).unwrap_or_default() {
+                return Some(potential_shred.into_payload());
             }
         }
         None
@@ -1517,6 +1542,30 @@ impl Blockstore {
                     return Err(InsertDataShredError::InvalidShred);
                 }
             }
+
+            // Data shreds which carry the erasure config of their batch must be
+            // consistent with the erasure meta, same as coding shreds
+            if ErasureMeta::from_data_shred(&shred).is_some() {
+                let erasure_meta = match erasure_metas.entry(erasure_set) {
+                    HashMapEntry::Occupied(entry) => Some(entry.into_mut()),
+                    HashMapEntry::Vacant(entry) => self
+                        .erasure_meta(erasure_set)
+                        .expect("Expect database get to succeed")
+                        .map(|meta| entry.insert(WorkingEntry::Clean(meta))),
+                };
+                if let Some(erasure_meta) = erasure_meta {
+                    let erasure_meta = erasure_meta.as_ref();
+                    if !erasure_meta.check_data_shred(&shred) {
+                        self.report_erasure_conflict(
+                            &shred,
+                            erasure_meta,
+                            just_inserted_shreds,
+                            duplicate_shreds,
+                        );
+                        return Err(InsertDataShredError::InvalidShred);
+                    }
+                }
+            }
         }
 
         let newly_completed_data_sets = self.insert_data_shred(
@@ -1529,12 +1578,17 @@ impl Blockstore {
         merkle_root_metas
             .entry(erasure_set)
             .or_insert(WorkingEntry::Dirty(MerkleRootMeta::from_shred(&shred)));
+        let erasure_meta = ErasureMeta::from_data_shred(&shred);
         just_inserted_shreds.insert(shred.id(), shred);
         index_meta_working_set_entry.did_insert_occur = true;
         slot_meta_entry.did_insert_occur = true;
         if let HashMapEntry::Vacant(entry) = erasure_metas.entry(erasure_set) {
             if let Some(meta) = self.erasure_meta(erasure_set).unwrap() {
                 entry.insert(WorkingEntry::Clean(meta));
+            } else if let Some(meta) = erasure_meta {
+                // Data shreds which carry the erasure config of their batch
+                // initialize the erasure meta before any coding shreds arrive.
+                entry.insert(WorkingEntry::Dirty(meta));
             }
         }
         Ok(newly_completed_data_sets)
@@ -3065,6 +3119,34 @@ impl Blockstore {
         self.program_costs_cf.delete(*key)
     }
 
+    pub fn read_epoch_stake_history(&self, epoch: Epoch) -> Result<Option<EpochStakeHistoryMeta>> {
+        self.epoch_stake_history_cf.get(epoch)
+    }
+
+    pub fn write_epoch_stake_history(
+        &self,
+        epoch: Epoch,
+        epoch_stake_history: &EpochStakeHistoryMeta,
+    ) -> Result<()> {
+        self.epoch_stake_history_cf.put(epoch, epoch_stake_history)
+    }
+
+    /// Returns an iterator over the recorded epoch stake history, starting at `epoch`
+    pub fn epoch_stake_history_iterator(
+        &self,
+        epoch: Epoch,
+    ) -> Result<impl Iterator<Item = (Epoch, EpochStakeHistoryMeta)> + '_> {
+        let epoch_stake_history_iterator = self
+            .db
+            .iter::<cf::EpochStakeHistory>(IteratorMode::From(epoch, IteratorDirection::Forward))?;
+        Ok(epoch_stake_history_iterator.map(|(epoch, bytes)| {
+            let epoch_stake_history = deserialize(&bytes).unwrap_or_else(|err| {
+                panic!("Could not deserialize EpochStakeHistoryMeta for epoch {epoch}: {err:?}")
+            });
+            (epoch, epoch_stake_history)
+        }))
+    }
+
     /// Returns the entry vector for the slot starting with `shred_start_index`
     pub fn get_slot_entries(&self, slot: Slot, shred_start_index: u64) -> Result<Vec<Entry>> {
         self.get_slot_entries_with_shred_info(slot, shred_start_index, false)
@@ -4807,12 +4889,14 @@ pub mod tests {
             packet::PACKET_DATA_SIZE,
             pubkey::Pubkey,
             signature::Signature,
+            stake_history::StakeHistoryEntry,
             transaction::{Transaction, TransactionError},
             transaction_context::TransactionReturnData,
         },
         solana_storage_proto::convert::generated,
         solana_transaction_status::{
-            InnerInstruction, InnerInstructions, Reward, Rewards, TransactionTokenBalance,
+            InnerInstruction, InnerInstructions, Reward, Rewards, StakeDelegation,
+            TransactionStakeChange, TransactionTokenBalance,
         },
         std::{cmp::Ordering, thread::Builder, time::Duration},
         test_case::test_case,
@@ -7385,6 +7469,125 @@ pub mod tests {
         );
     }
 
+    #[test]
+    fn test_insert_data_shred_with_erasure_config() {
+        let ledger_path = get_tmp_ledger_path_auto_delete!();
+        let blockstore = Blockstore::open(ledger_path.path()).unwrap();
+
+        let slot = 1;
+        let entries = make_slot_entries_with_transactions(10);
+        let shredder = Shredder::new(slot, 0, 0, 0)
+            .unwrap()
+            .with_erasure_config(true);
+        let (data_shreds, coding_shreds) = shredder.entries_to_shreds(
+            &Keypair::new(),
+            &entries,
+            true, // is_last_in_slot
+            None, // chained_merkle_root
+            0,    // next_shred_index
+            0,    // next_code_index
+            true, // merkle_variant
+            &ReedSolomonCache::default(),
+            &mut ProcessShredsStats::default(),
+        );
+        let erasure_set = data_shreds[0].erasure_set();
+
+        // The erasure meta is populated from a single data shred.
+        blockstore
+            .insert_shreds(data_shreds[..1].to_vec(), None, false)
+            .unwrap();
+        assert_eq!(
+            blockstore.erasure_meta(erasure_set).unwrap(),
+            ErasureMeta::from_coding_shred(&coding_shreds[0])
+        );
+
+        // Coding shreds of the batch are consistent with it.
+        blockstore
+            .insert_shreds(coding_shreds, None, false)
+            .unwrap();
+        assert!(!blockstore.has_duplicate_shreds_in_slot(slot));
+    }
+
+    #[test]
+    fn test_check_insert_data_shred_erasure_conflict() {
+        let ledger_path = get_tmp_ledger_path_auto_delete!();
+        let blockstore = Blockstore::open(ledger_path.path()).unwrap();
+
+        let slot = 1;
+        let make_shreds = |num_entries| {
+            Shredder::new(slot, 0, 0, 0)
+                .unwrap()
+                .with_erasure_config(true)
+                .entries_to_shreds(
+                    &Keypair::new(),
+                    &make_slot_entries_with_transactions(num_entries),
+                    false, // is_last_in_slot
+                    None,  // chained_merkle_root
+                    0,     // next_shred_index
+                    0,     // next_code_index
+                    true,  // merkle_variant
+                    &ReedSolomonCache::default(),
+                    &mut ProcessShredsStats::default(),
+                )
+        };
+        let (data_shreds, coding_shreds) = make_shreds(10);
+        let (other_data_shreds, _) = make_shreds(1);
+        let coding_shred = coding_shreds[0].clone();
+        // at a different index than the conflicting one, which is not a duplicate
+        let consistent_data_shred = data_shreds[1].clone();
+        let conflicting_data_shred = other_data_shreds[0].clone();
+        assert_eq!(
+            coding_shred.erasure_set(),
+            conflicting_data_shred.erasure_set()
+        );
+
+        // The erasure meta was set by a coding shred of the erasure set, without
+        // a merkle root meta to catch the conflict first
+        let erasure_set = coding_shred.erasure_set();
+        let mut erasure_metas = HashMap::from([(
+            erasure_set,
+            WorkingEntry::Dirty(ErasureMeta::from_coding_shred(&coding_shred).unwrap()),
+        )]);
+        let mut just_received_shreds = HashMap::from([(coding_shred.id(), coding_shred.clone())]);
+        let mut index_working_set = HashMap::new();
+        let mut slot_meta_working_set = HashMap::new();
+        let mut write_batch = blockstore.db.batch().unwrap();
+        let mut index_meta_time_us = 0;
+        let mut duplicates = vec![];
+        let mut check_insert_data_shred = |shred: &Shred, duplicates: &mut Vec<_>| {
+            blockstore.check_insert_data_shred(
+                shred.clone(),
+                &mut erasure_metas,
+                &mut HashMap::new(),
+                &mut index_working_set,
+                &mut slot_meta_working_set,
+                &mut write_batch,
+                &mut just_received_shreds,
+                &mut index_meta_time_us,
+                false,
+                duplicates,
+                None,
+                ShredSource::Turbine,
+            )
+        };
+
+        assert!(check_insert_data_shred(&consistent_data_shred, &mut duplicates).is_ok());
+        assert!(duplicates.is_empty());
+
+        assert_matches!(
+            check_insert_data_shred(&conflicting_data_shred, &mut duplicates),
+            Err(InsertDataShredError::InvalidShred)
+        );
+        assert_eq!(
+            duplicates,
+            vec![PossibleDuplicateShred::ErasureConflict(
+                conflicting_data_shred.clone(),
+                coding_shred.payload().clone(),
+            )]
+        );
+        assert!(blockstore.has_duplicate_shreds_in_slot(slot));
+    }
+
     #[test]
     fn test_should_insert_coding_shred() {
         let ledger_path = get_tmp_ledger_path_auto_delete!();
@@ -7798,6 +8001,7 @@ pub mod tests {
                     loaded_addresses: LoadedAddresses::default(),
                     return_data: Some(TransactionReturnData::default()),
                     compute_units_consumed,
+                    stake_changes: None,
                 }
                 .into();
                 blockstore
@@ -7817,6 +8021,7 @@ pub mod tests {
                     loaded_addresses: LoadedAddresses::default(),
                     return_data: Some(TransactionReturnData::default()),
                     compute_units_consumed,
+                    stake_changes: None,
                 }
                 .into();
                 blockstore
@@ -7836,6 +8041,7 @@ pub mod tests {
                     loaded_addresses: LoadedAddresses::default(),
                     return_data: Some(TransactionReturnData::default()),
                     compute_units_consumed,
+                    stake_changes: None,
                 }
                 .into();
                 blockstore
@@ -7857,6 +8063,7 @@ pub mod tests {
                         loaded_addresses: LoadedAddresses::default(),
                         return_data: Some(TransactionReturnData::default()),
                         compute_units_consumed,
+                        stake_changes: None,
                     },
                 }
             })
@@ -7978,6 +8185,16 @@ pub mod tests {
         };
         let compute_units_consumed_1 = Some(3812649u64);
         let compute_units_consumed_2 = Some(42u64);
+        let stake_changes_vec = vec![TransactionStakeChange {
+            account_index: 1,
+            pre_delegation: None,
+            post_delegation: Some(StakeDelegation {
+                voter: Pubkey::new_unique().to_string(),
+                stake: 42,
+                activation_epoch: 3,
+                deactivation_epoch: u64::MAX,
+            }),
+        }];
 
         // result not found
         assert!(transaction_status_cf
@@ -7999,6 +8216,7 @@ pub mod tests {
             loaded_addresses: test_loaded_addresses.clone(),
             return_data: Some(test_return_data.clone()),
             compute_units_consumed: compute_units_consumed_1,
+            stake_changes: Some(stake_changes_vec.clone()),
         }
         .into();
         assert!(transaction_status_cf
@@ -8019,6 +8237,7 @@ pub mod tests {
             loaded_addresses,
             return_data,
             compute_units_consumed,
+            stake_changes,
         } = transaction_status_cf
             .get_protobuf((Signature::default(), 0))
             .unwrap()
@@ -8037,6 +8256,7 @@ pub mod tests {
         assert_eq!(loaded_addresses, test_loaded_addresses);
         assert_eq!(return_data.unwrap(), test_return_data);
         assert_eq!(compute_units_consumed, compute_units_consumed_1);
+        assert_eq!(stake_changes.unwrap(), stake_changes_vec);
 
         // insert value
         let status = TransactionStatusMeta {
@@ -8052,6 +8272,7 @@ pub mod tests {
             loaded_addresses: test_loaded_addresses.clone(),
             return_data: Some(test_return_data.clone()),
             compute_units_consumed: compute_units_consumed_2,
+            stake_changes: Some(stake_changes_vec.clone()),
         }
         .into();
         assert!(transaction_status_cf
@@ -8072,6 +8293,7 @@ pub mod tests {
             loaded_addresses,
             return_data,
             compute_units_consumed,
+            stake_changes,
         } = transaction_status_cf
             .get_protobuf((Signature::from([2u8; 64]), 9))
             .unwrap()
@@ -8092,6 +8314,7 @@ pub mod tests {
         assert_eq!(loaded_addresses, test_loaded_addresses);
         assert_eq!(return_data.unwrap(), test_return_data);
         assert_eq!(compute_units_consumed, compute_units_consumed_2);
+        assert_eq!(stake_changes.unwrap(), stake_changes_vec);
     }
 
     #[test]
@@ -8185,6 +8408,7 @@ pub mod tests {
             loaded_addresses: LoadedAddresses::default(),
             return_data: Some(TransactionReturnData::default()),
             compute_units_consumed: Some(42u64),
+            stake_changes: None,
         }
         .into();
 
@@ -8361,6 +8585,7 @@ pub mod tests {
             loaded_addresses: LoadedAddresses::default(),
             return_data: Some(TransactionReturnData::default()),
             compute_units_consumed: Some(42u64),
+            stake_changes: None,
         }
         .into();
 
@@ -8488,6 +8713,7 @@ pub mod tests {
             loaded_addresses: LoadedAddresses::default(),
             return_data: Some(TransactionReturnData::default()),
             compute_units_consumed: Some(42u64),
+            stake_changes: None,
         }
         .into();
 
@@ -8659,6 +8885,7 @@ pub mod tests {
                     loaded_addresses: LoadedAddresses::default(),
                     return_data: return_data.clone(),
                     compute_units_consumed: Some(42),
+                    stake_changes: None,
                 }
                 .into();
                 blockstore
@@ -8680,6 +8907,7 @@ pub mod tests {
                         loaded_addresses: LoadedAddresses::default(),
                         return_data,
                         compute_units_consumed: Some(42),
+                        stake_changes: None,
                     },
                 }
             })
@@ -8781,6 +9009,7 @@ pub mod tests {
                     loaded_addresses: LoadedAddresses::default(),
                     return_data: return_data.clone(),
                     compute_units_consumed: Some(42u64),
+                    stake_changes: None,
                 }
                 .into();
                 blockstore
@@ -8802,6 +9031,7 @@ pub mod tests {
                         loaded_addresses: LoadedAddresses::default(),
                         return_data,
                         compute_units_consumed: Some(42u64),
+                        stake_changes: None,
                     },
                 }
             })
@@ -9476,6 +9706,7 @@ pub mod tests {
                 loaded_addresses: LoadedAddresses::default(),
                 return_data: Some(TransactionReturnData::default()),
                 compute_units_consumed: None,
+                stake_changes: None,
             }
             .into();
             transaction_status_cf
@@ -9654,6 +9885,57 @@ pub mod tests {
         }
     }
 
+    #[test]
+    fn test_epoch_stake_history() {
+        let ledger_path = get_tmp_ledger_path_auto_delete!();
+        let blockstore = Blockstore::open(ledger_path.path()).unwrap();
+
+        assert_eq!(blockstore.read_epoch_stake_history(0).unwrap(), None);
+
+        let epoch_stake_history: Vec<_> = (1..=5)
+            .map(|epoch| {
+                let meta = EpochStakeHistoryMeta {
+                    slot: epoch * 32,
+                    parent_slot: epoch * 32 - 1,
+                    stake_history_entry: StakeHistoryEntry {
+                        effective: 1_000 * epoch,
+                        activating: 10 * epoch,
+                        deactivating: epoch,
+                    },
+                    rewards_partitions: (epoch % 2 == 0).then_some(EpochRewardsPartitionsMeta {
+                        total_rewards: 42 * epoch,
+                        distribution_starting_block_height: epoch * 32 + 1,
+                        num_partitions: 4,
+                    }),
+                };
+                blockstore
+                    .write_epoch_stake_history(epoch - 1, &meta)
+                    .unwrap();
+                (epoch - 1, meta)
+            })
+            .collect();
+
+        assert_eq!(
+            blockstore.read_epoch_stake_history(2).unwrap().as_ref(),
+            Some(&epoch_stake_history[2].1)
+        );
+        assert_eq!(blockstore.read_epoch_stake_history(5).unwrap(), None);
+        assert_eq!(
+            blockstore
+                .epoch_stake_history_iterator(0)
+                .unwrap()
+                .collect::<Vec<_>>(),
+            epoch_stake_history
+        );
+        assert_eq!(
+            blockstore
+                .epoch_stake_history_iterator(3)
+                .unwrap()
+                .collect::<Vec<_>>(),
+            epoch_stake_history[3..]
+        );
+    }
+
     #[test]
     fn test_lowest_slot() {
         let ledger_path = get_tmp_ledger_path_auto_delete!();
@@ -10212,6 +10494,7 @@ pub mod tests {
                 },
                 owner: Pubkey::new_unique().to_string(),
                 program_id: Pubkey::new_unique().to_string(),
+                extensions: vec![],
             }]),
             post_token_balances: Some(vec![TransactionTokenBalance {
                 account_index: 0,
@@ -10224,6 +10507,7 @@ pub mod tests {
                 },
                 owner: Pubkey::new_unique().to_string(),
                 program_id: Pubkey::new_unique().to_string(),
+                extensions: vec![],
             }]),
             rewards: Some(vec![Reward {
                 pubkey: "My11111111111111111111111111111111111111111".to_string(),
@@ -10238,6 +10522,7 @@ pub mod tests {
                 data: vec![1, 2, 3],
             }),
             compute_units_consumed: Some(23456),
+            stake_changes: None,
         };
         let deprecated_status: StoredTransactionStatusMeta = status.clone().try_into().unwrap();
         let protobuf_status: generated::TransactionStatusMeta = status.into();
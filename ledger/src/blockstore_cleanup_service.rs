@@ -3,6 +3,9 @@
 //! can be done quickly and should have a fairly stable correlation to actual bytes.
 //! Once the shred count (and thus roughly the byte count) reaches a threshold,
 //! the services begins removing data in FIFO order.
+//!
+//! Alternatively, the service retains a number of complete epochs, and removes
+//! older epochs as a whole once the root moves into a new epoch.
 
 use {
     crate::{
@@ -11,7 +14,7 @@ use {
     },
     crossbeam_channel::{Receiver, RecvTimeoutError},
     solana_measure::measure::Measure,
-    solana_sdk::clock::Slot,
+    solana_sdk::{clock::Slot, epoch_schedule::EpochSchedule},
     std::{
         string::ToString,
         sync::{
@@ -40,6 +43,18 @@ pub const DEFAULT_MIN_MAX_LEDGER_SHREDS: u64 = 50_000_000;
 // and starve other blockstore users.
 pub const DEFAULT_PURGE_SLOT_INTERVAL: u64 = 512;
 
+/// How much of the ledger `BlockstoreCleanupService` retains
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BlockstoreRetention {
+    /// Retain about this many data shreds
+    MaxShreds(u64),
+    /// Retain the epoch of the latest root and this many complete epochs before it
+    Epochs {
+        num_epochs: u64,
+        epoch_schedule: EpochSchedule,
+    },
+}
+
 pub struct BlockstoreCleanupService {
     t_cleanup: JoinHandle<()>,
 }
@@ -50,13 +65,24 @@ impl BlockstoreCleanupService {
         blockstore: Arc<Blockstore>,
         max_ledger_shreds: u64,
         exit: Arc<AtomicBool>,
+    ) -> Self {
+        Self::new_with_retention(
+            new_root_receiver,
+            blockstore,
+            BlockstoreRetention::MaxShreds(max_ledger_shreds),
+            exit,
+        )
+    }
+
+    pub fn new_with_retention(
+        new_root_receiver: Receiver<Slot>,
+        blockstore: Arc<Blockstore>,
+        retention: BlockstoreRetention,
+        exit: Arc<AtomicBool>,
     ) -> Self {
         let mut last_purge_slot = 0;
 
-        info!(
-            "BlockstoreCleanupService active. max ledger shreds={}",
-            max_ledger_shreds
-        );
+        info!("BlockstoreCleanupService active. retention={:?}", retention);
 
         let t_cleanup = Builder::new()
             .name("solBstoreClean".to_string())
@@ -64,13 +90,27 @@ impl BlockstoreCleanupService {
                 if exit.load(Ordering::Relaxed) {
                     break;
                 }
-                if let Err(e) = Self::cleanup_ledger(
-                    &new_root_receiver,
-                    &blockstore,
-                    max_ledger_shreds,
-                    &mut last_purge_slot,
-                    DEFAULT_PURGE_SLOT_INTERVAL,
-                ) {
+                let result = match &retention {
+                    BlockstoreRetention::MaxShreds(max_ledger_shreds) => Self::cleanup_ledger(
+                        &new_root_receiver,
+                        &blockstore,
+                        *max_ledger_shreds,
+                        &mut last_purge_slot,
+                        DEFAULT_PURGE_SLOT_INTERVAL,
+                    ),
+                    BlockstoreRetention::Epochs {
+                        num_epochs,
+                        epoch_schedule,
+                    } => Self::cleanup_ledger_epochs(
+                        &new_root_receiver,
+                        &blockstore,
+                        *num_epochs,
+                        epoch_schedule,
+                        &mut last_purge_slot,
+                        DEFAULT_PURGE_SLOT_INTERVAL,
+                    ),
+                };
+                if let Err(e) = result {
                     match e {
                         RecvTimeoutError::Disconnected => break,
                         RecvTimeoutError::Timeout => (),
@@ -272,6 +312,94 @@ impl BlockstoreCleanupService {
         Ok(())
     }
 
+    /// The first slot of the oldest epoch to retain when the latest root is
+    /// `root`, or None if all epochs up to the root's are retained
+    fn lowest_retained_slot(
+        root: Slot,
+        num_epochs: u64,
+        epoch_schedule: &EpochSchedule,
+    ) -> Option<Slot> {
+        let lowest_retained_epoch = epoch_schedule
+            .get_epoch(root)
+            .checked_sub(num_epochs)
+            .filter(|epoch| *epoch > 0)?;
+        Some(epoch_schedule.get_first_slot_in_epoch(lowest_retained_epoch))
+    }
+
+    /// Checks for new roots and, if the last cleanup was at least
+    /// `purge_interval` slots ago, purges all epochs older than the
+    /// `num_epochs` complete epochs before the epoch of the new root.
+    ///
+    /// `lowest_cleanup_slot` is raised past the purged epochs before purging,
+    /// so readers never see an epoch partially purged. Like `cleanup_ledger`,
+    /// this purges with `PurgeType::CompactionFilter`, leaving the
+    /// transaction_status and address_signatures columns to the compaction
+    /// filters rather than deleting every key of the purged epochs inline.
+    pub fn cleanup_ledger_epochs(
+        new_root_receiver: &Receiver<Slot>,
+        blockstore: &Arc<Blockstore>,
+        num_epochs: u64,
+        epoch_schedule: &EpochSchedule,
+        last_purge_slot: &mut u64,
+        purge_interval: u64,
+    ) -> Result<(), RecvTimeoutError> {
+        let root = Self::receive_new_roots(new_root_receiver)?;
+        if root - *last_purge_slot <= purge_interval {
+            return Ok(());
+        }
+        *last_purge_slot = root;
+
+        let Some(lowest_retained_slot) =
+            Self::lowest_retained_slot(root, num_epochs, epoch_schedule)
+        else {
+            return Ok(());
+        };
+        let lowest_slot = blockstore.lowest_slot();
+        if lowest_slot >= lowest_retained_slot {
+            return Ok(());
+        }
+        let lowest_cleanup_slot = lowest_retained_slot - 1;
+
+        let disk_utilization_pre = blockstore.storage_size();
+        info!(
+            "purge: last_root={}, purging epochs before {}, slots [{}, {}], disk_utilization={:?}",
+            root,
+            epoch_schedule.get_epoch(lowest_retained_slot),
+            lowest_slot,
+            lowest_cleanup_slot,
+            disk_utilization_pre
+        );
+
+        let mut purge_time = Measure::start("purge_slots");
+        *blockstore.lowest_cleanup_slot.write().unwrap() = lowest_cleanup_slot;
+        blockstore.purge_slots(0, lowest_cleanup_slot, PurgeType::CompactionFilter);
+        // Update only after purge operation, see `cleanup_ledger`
+        blockstore.set_max_expired_slot(lowest_cleanup_slot);
+        purge_time.stop();
+        info!("{}", purge_time);
+
+        let disk_utilization_post = blockstore.storage_size();
+        datapoint_info!(
+            "ledger_epoch_retention",
+            ("root", root, i64),
+            ("lowest_retained_slot", lowest_retained_slot, i64),
+            ("purged_slots", lowest_retained_slot - lowest_slot, i64),
+            ("purge_us", purge_time.as_us(), i64),
+            (
+                "disk_utilization_pre",
+                disk_utilization_pre.unwrap_or_default(),
+                i64
+            ),
+            (
+                "disk_utilization_post",
+                disk_utilization_post.unwrap_or_default(),
+                i64
+            ),
+        );
+
+        Ok(())
+    }
+
     fn report_disk_metrics(
         pre: BlockstoreResult<u64>,
         post: BlockstoreResult<u64>,
@@ -419,6 +547,77 @@ mod tests {
             .for_each(|(slot, _)| assert!(slot > 40));
     }
 
+    #[test]
+    fn test_lowest_retained_slot() {
+        let epoch_schedule = EpochSchedule::custom(32, 32, false);
+        // nothing is purged before the retained epochs are complete
+        assert_eq!(
+            BlockstoreCleanupService::lowest_retained_slot(40, 1, &epoch_schedule),
+            None
+        );
+        assert_eq!(
+            BlockstoreCleanupService::lowest_retained_slot(70, 1, &epoch_schedule),
+            Some(32)
+        );
+        assert_eq!(
+            BlockstoreCleanupService::lowest_retained_slot(100, 1, &epoch_schedule),
+            Some(64)
+        );
+        assert_eq!(
+            BlockstoreCleanupService::lowest_retained_slot(100, 0, &epoch_schedule),
+            Some(96)
+        );
+        assert_eq!(
+            BlockstoreCleanupService::lowest_retained_slot(100, 3, &epoch_schedule),
+            None
+        );
+    }
+
+    #[test]
+    fn test_cleanup_epochs() {
+        solana_logger::setup();
+        let ledger_path = get_tmp_ledger_path_auto_delete!();
+        let blockstore = Arc::new(Blockstore::open(ledger_path.path()).unwrap());
+        let (shreds, _) = make_many_slot_entries(0, 110, 5);
+        blockstore.insert_shreds(shreds, None, false).unwrap();
+        let epoch_schedule = EpochSchedule::custom(32, 32, false);
+        let (sender, receiver) = unbounded();
+
+        // too soon after the last purge
+        let mut last_purge_slot = 95;
+        sender.send(100).unwrap();
+        BlockstoreCleanupService::cleanup_ledger_epochs(
+            &receiver,
+            &blockstore,
+            1,
+            &epoch_schedule,
+            &mut last_purge_slot,
+            10,
+        )
+        .unwrap();
+        assert_eq!(last_purge_slot, 95);
+        assert_eq!(blockstore.lowest_slot(), 1);
+
+        // keep epoch 3 of the root, and epoch 2 before it
+        let mut last_purge_slot = 0;
+        sender.send(100).unwrap();
+        BlockstoreCleanupService::cleanup_ledger_epochs(
+            &receiver,
+            &blockstore,
+            1,
+            &epoch_schedule,
+            &mut last_purge_slot,
+            10,
+        )
+        .unwrap();
+        assert_eq!(last_purge_slot, 100);
+        blockstore
+            .slot_meta_iterator(0)
+            .unwrap()
+            .for_each(|(slot, _)| assert!(slot >= 64));
+        assert!(blockstore.meta(64).unwrap().is_some());
+    }
+
     #[test]
     fn test_cleanup_speed() {
         solana_logger::setup();
@@ -29,7 +29,7 @@ use {
     serde::{de::DeserializeOwned, Serialize},
     solana_accounts_db::hardened_unpack::UnpackError,
     solana_sdk::{
-        clock::{Slot, UnixTimestamp},
+        clock::{Epoch, Slot, UnixTimestamp},
         pubkey::Pubkey,
         signature::Signature,
     },
@@ -106,6 +106,8 @@ const PROGRAM_COSTS_CF: &str = "program_costs";
 const OPTIMISTIC_SLOTS_CF: &str = "optimistic_slots";
 /// Column family for merkle roots
 const MERKLE_ROOT_META_CF: &str = "merkle_root_meta";
+/// Column family for per-epoch stake history
+const EPOCH_STAKE_HISTORY_CF: &str = "epoch_stake_history";
 
 #[derive(Error, Debug)]
 pub enum BlockstoreError {
@@ -355,6 +357,16 @@ pub mod columns {
     /// * value type: [`blockstore_meta::MerkleRootMeta`]`
     pub struct MerkleRootMeta;
 
+    #[derive(Debug)]
+    /// The epoch stake history column
+    ///
+    /// Records the stake history entry and rewards partitions of each epoch
+    /// as of the first rooted bank of the following epoch.
+    ///
+    /// * index type: `u64` (an `Epoch`, not a slot)
+    /// * value type: [`blockstore_meta::EpochStakeHistoryMeta`]
+    pub struct EpochStakeHistory;
+
     // When adding a new column ...
     // - Add struct below and implement `Column` and `ColumnName` traits
     // - Add descriptor in Rocks::cf_descriptors() and name in Rocks::columns()
@@ -494,6 +506,7 @@ impl Rocks {
             new_cf_descriptor::<ProgramCosts>(options, oldest_slot),
             new_cf_descriptor::<OptimisticSlots>(options, oldest_slot),
             new_cf_descriptor::<MerkleRootMeta>(options, oldest_slot),
+            new_cf_descriptor::<EpochStakeHistory>(options, oldest_slot),
         ];
 
         // If the access type is Secondary, we don't need to open all of the
@@ -567,6 +580,7 @@ impl Rocks {
             ProgramCosts::NAME,
             OptimisticSlots::NAME,
             MerkleRootMeta::NAME,
+            EpochStakeHistory::NAME,
         ]
     }
 
@@ -1142,6 +1156,34 @@ impl Column for columns::ProgramCosts {
     }
 }
 
+impl ColumnName for columns::EpochStakeHistory {
+    const NAME: &'static str = EPOCH_STAKE_HISTORY_CF;
+}
+impl TypedColumn for columns::EpochStakeHistory {
+    type Type = blockstore_meta::EpochStakeHistoryMeta;
+}
+impl Column for columns::EpochStakeHistory {
+    type Index = Epoch;
+
+    fn key(epoch: Epoch) -> Vec<u8> {
+        let mut key = vec![0; 8];
+        BigEndian::write_u64(&mut key[..], epoch);
+        key
+    }
+
+    fn index(key: &[u8]) -> Self::Index {
+        BigEndian::read_u64(&key[..8])
+    }
+
+    fn slot(_index: Self::Index) -> Slot {
+        unimplemented!()
+    }
+
+    fn as_index(epoch: u64) -> Self::Index {
+        epoch
+    }
+}
+
 impl Column for columns::ShredCode {
     type Index = (Slot, u64);
 
@@ -1,10 +1,11 @@
 use {
-    crate::shred::{Shred, ShredType},
+    crate::shred::{ErasureSetInfo, Shred, ShredType},
     bitflags::bitflags,
     serde::{Deserialize, Deserializer, Serialize, Serializer},
     solana_sdk::{
         clock::{Slot, UnixTimestamp},
         hash::Hash,
+        stake_history::StakeHistoryEntry,
     },
     std::{
         collections::BTreeSet,
@@ -338,24 +339,35 @@ impl SlotMeta {
     }
 }
 
+impl From<ErasureSetInfo> for ErasureMeta {
+    fn from(erasure_set_info: ErasureSetInfo) -> Self {
+        let config = ErasureConfig {
+            num_data: usize::from(erasure_set_info.num_data_shreds),
+            num_coding: usize::from(erasure_set_info.num_coding_shreds),
+        };
+        ErasureMeta {
+            set_index: u64::from(erasure_set_info.fec_set_index),
+            config,
+            first_coding_index: u64::from(erasure_set_info.first_coding_index),
+            __unused_size: 0,
+        }
+    }
+}
+
 impl ErasureMeta {
     pub(crate) fn from_coding_shred(shred: &Shred) -> Option<Self> {
         match shred.shred_type() {
             ShredType::Data => None,
-            ShredType::Code => {
-                let config = ErasureConfig {
-                    num_data: usize::from(shred.num_data_shreds().ok()?),
-                    num_coding: usize::from(shred.num_coding_shreds().ok()?),
-                };
-                let first_coding_index = u64::from(shred.first_coding_index()?);
-                let erasure_meta = ErasureMeta {
-                    set_index: u64::from(shred.fec_set_index()),
-                    config,
-                    first_coding_index,
-                    __unused_size: 0,
-                };
-                Some(erasure_meta)
-            }
+            ShredType::Code => shred.erasure_set_info().map(Self::from),
+        }
+    }
+
+    // Returns None unless the data shred carries the erasure config of its
+    // erasure batch.
+    pub(crate) fn from_data_shred(shred: &Shred) -> Option<Self> {
+        match shred.shred_type() {
+            ShredType::Data => shred.erasure_set_info().map(Self::from),
+            ShredType::Code => None,
         }
     }
 
@@ -369,13 +381,25 @@ impl ErasureMeta {
         self == &other
     }
 
-    /// Returns true if both shreds are coding shreds and have a
-    /// consistent erasure config
-    pub fn check_erasure_consistency(shred1: &Shred, shred2: &Shred) -> bool {
-        let Some(coding_shred) = Self::from_coding_shred(shred1) else {
-            return false;
+    // Returns true unless the data shred carries an erasure config which is
+    // inconsistent with the erasure-meta.
+    pub(crate) fn check_data_shred(&self, shred: &Shred) -> bool {
+        let Some(mut other) = Self::from_data_shred(shred) else {
+            return shred.is_data();
         };
-        coding_shred.check_coding_shred(shred2)
+        other.__unused_size = self.__unused_size;
+        self == &other
+    }
+
+    /// Returns true if both shreds carry the erasure config of their erasure
+    /// batch, as all coding shreds do, and the configs are consistent
+    pub fn check_erasure_consistency(shred1: &Shred, shred2: &Shred) -> bool {
+        match (shred1.erasure_set_info(), shred2.erasure_set_info()) {
+            (Some(erasure_set_info1), Some(erasure_set_info2)) => {
+                erasure_set_info1 == erasure_set_info2
+            }
+            _ => false,
+        }
     }
 
     pub(crate) fn config(&self) -> ErasureConfig {
@@ -505,6 +529,29 @@ pub struct ProgramCost {
     pub cost: u64,
 }
 
+/// Stake history and rewards provenance recorded when an epoch boundary is rooted
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct EpochStakeHistoryMeta {
+    /// The first slot of the following epoch, whose bank wrote `stake_history_entry`
+    pub slot: Slot,
+    /// The last slot of the epoch
+    pub parent_slot: Slot,
+    /// Effective, activating and deactivating stake totals for the epoch
+    pub stake_history_entry: StakeHistoryEntry,
+    /// Partitioned rewards distribution for the epoch, if rewards were partitioned
+    pub rewards_partitions: Option<EpochRewardsPartitionsMeta>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct EpochRewardsPartitionsMeta {
+    /// Total rewards to be distributed, in lamports
+    pub total_rewards: u64,
+    /// Block height of the first block distributing rewards
+    pub distribution_starting_block_height: u64,
+    /// Number of partitions, ie. blocks, rewards are distributed over
+    pub num_partitions: u64,
+}
+
 #[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
 pub struct OptimisticSlotMetaV0 {
     pub hash: Hash,
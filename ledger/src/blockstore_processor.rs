@@ -6,7 +6,8 @@ use {
         blockstore_meta::SlotMeta,
         entry_notifier_service::{EntryNotification, EntryNotifierSender},
         leader_schedule_cache::LeaderScheduleCache,
-        token_balances::collect_token_balances,
+        stake_delegations::collect_stake_delegations,
+        token_balances::{collect_token_balances, TokenMintInfo},
         use_snapshot_archives_at_startup::UseSnapshotArchivesAtStartup,
     },
     chrono_humanize::{Accuracy, HumanTime, Tense},
@@ -58,7 +59,9 @@ use {
         },
     },
     solana_svm::runtime_config::RuntimeConfig,
-    solana_transaction_status::token_balances::TransactionTokenBalancesSet,
+    solana_transaction_status::{
+        stake_changes::TransactionStakeDelegationsSet, token_balances::TransactionTokenBalancesSet,
+    },
     solana_vote::{vote_account::VoteAccountsHashMap, vote_sender_types::ReplayVoteSender},
     std::{
         borrow::Cow,
@@ -149,10 +152,15 @@ pub fn execute_batch(
     } = batch;
     let record_token_balances = transaction_status_sender.is_some();
 
-    let mut mint_decimals: HashMap<Pubkey, u8> = HashMap::new();
+    let mut mint_infos: HashMap<Pubkey, TokenMintInfo> = HashMap::new();
 
     let pre_token_balances = if record_token_balances {
-        collect_token_balances(bank, batch, &mut mint_decimals)
+        collect_token_balances(bank, batch, &mut mint_infos)
+    } else {
+        vec![]
+    };
+    let pre_stake_delegations = if record_token_balances {
+        collect_stake_delegations(bank, batch)
     } else {
         vec![]
     };
@@ -190,13 +198,21 @@ pub fn execute_batch(
     if let Some(transaction_status_sender) = transaction_status_sender {
         let transactions = batch.sanitized_transactions().to_vec();
         let post_token_balances = if record_token_balances {
-            collect_token_balances(bank, batch, &mut mint_decimals)
+            collect_token_balances(bank, batch, &mut mint_infos)
+        } else {
+            vec![]
+        };
+
+        let post_stake_delegations = if record_token_balances {
+            collect_stake_delegations(bank, batch)
         } else {
             vec![]
         };
 
         let token_balances =
             TransactionTokenBalancesSet::new(pre_token_balances, post_token_balances);
+        let stake_delegations =
+            TransactionStakeDelegationsSet::new(pre_stake_delegations, post_stake_delegations);
 
         transaction_status_sender.send_transaction_status_batch(
             bank.clone(),
@@ -204,6 +220,7 @@ pub fn execute_batch(
             execution_results,
             balances,
             token_balances,
+            stake_delegations,
             rent_debits,
             transaction_indexes.to_vec(),
         );
@@ -700,6 +717,8 @@ pub struct ProcessOptions {
     /// This is useful for debugging.
     pub run_final_accounts_hash_calc: bool,
     pub use_snapshot_archives_at_startup: UseSnapshotArchivesAtStartup,
+    /// Called with each bank replayed from the blockstore, once it is frozen
+    pub slot_callback: Option<ProcessCallback>,
 }
 
 pub fn test_process_blockstore(
@@ -1808,6 +1827,9 @@ fn process_single_slot(
         result?
     }
     bank.freeze(); // all banks handled by this routine are created from complete slots
+    if let Some(slot_callback) = &opts.slot_callback {
+        slot_callback(bank);
+    }
     if blockstore.is_primary_access() {
         blockstore.insert_bank_hash(bank.slot(), bank.hash(), false);
     }
@@ -1828,6 +1850,7 @@ pub struct TransactionStatusBatch {
     pub execution_results: Vec<Option<TransactionExecutionDetails>>,
     pub balances: TransactionBalancesSet,
     pub token_balances: TransactionTokenBalancesSet,
+    pub stake_delegations: TransactionStakeDelegationsSet,
     pub rent_debits: Vec<RentDebits>,
     pub transaction_indexes: Vec<usize>,
 }
@@ -1845,6 +1868,7 @@ impl TransactionStatusSender {
         execution_results: Vec<TransactionExecutionResult>,
         balances: TransactionBalancesSet,
         token_balances: TransactionTokenBalancesSet,
+        stake_delegations: TransactionStakeDelegationsSet,
         rent_debits: Vec<RentDebits>,
         transaction_indexes: Vec<usize>,
     ) {
@@ -1864,6 +1888,7 @@ impl TransactionStatusSender {
                     .collect(),
                 balances,
                 token_balances,
+                stake_delegations,
                 rent_debits,
                 transaction_indexes,
             }))
@@ -28,6 +28,7 @@ pub mod shred;
 mod shredder;
 pub mod sigverify_shreds;
 pub mod slot_stats;
+pub mod stake_delegations;
 mod staking_utils;
 pub mod token_balances;
 mod transaction_address_lookup_table_scanner;
@@ -48,6 +48,10 @@
 //!
 //! So, given a) - c), we must restrict data shred's payload length such that the entire coding
 //! payload can fit into one coding shred / packet.
+//!
+//! Merkle data shreds may also carry an erasure config header right after the data header, with
+//! the same erasure config as the coding shreds of the erasure batch. This way the erasure batch
+//! of a data shred is known without having received any of the coding shreds.
 
 pub(crate) use self::merkle::SIZE_OF_MERKLE_ROOT;
 #[cfg(test)]
@@ -98,6 +102,7 @@ pub const SIZE_OF_NONCE: usize = std::mem::size_of::<Nonce>();
 const SIZE_OF_COMMON_SHRED_HEADER: usize = 83;
 const SIZE_OF_DATA_SHRED_HEADERS: usize = 88;
 const SIZE_OF_CODING_SHRED_HEADERS: usize = 89;
+const SIZE_OF_ERASURE_CONFIG_HEADER: usize = 8;
 const SIZE_OF_SIGNATURE: usize = SIGNATURE_BYTES;
 const SIZE_OF_SHRED_VARIANT: usize = 1;
 const SIZE_OF_SHRED_SLOT: usize = 8;
@@ -105,6 +110,15 @@ const SIZE_OF_SHRED_SLOT: usize = 8;
 const OFFSET_OF_SHRED_VARIANT: usize = SIZE_OF_SIGNATURE;
 const OFFSET_OF_SHRED_SLOT: usize = SIZE_OF_SIGNATURE + SIZE_OF_SHRED_VARIANT;
 const OFFSET_OF_SHRED_INDEX: usize = OFFSET_OF_SHRED_SLOT + SIZE_OF_SHRED_SLOT;
+const OFFSET_OF_SHRED_VERSION: usize = OFFSET_OF_SHRED_INDEX + 4;
+const OFFSET_OF_FEC_SET_INDEX: usize = OFFSET_OF_SHRED_VERSION + 2;
+// Offsets within the data shred header.
+const OFFSET_OF_PARENT_OFFSET: usize = SIZE_OF_COMMON_SHRED_HEADER;
+const OFFSET_OF_SHRED_FLAGS: usize = OFFSET_OF_PARENT_OFFSET + 2;
+// Offset of the erasure config header, either within the coding shred header
+// or right after the data shred header.
+const OFFSET_OF_CODING_ERASURE_CONFIG: usize = SIZE_OF_COMMON_SHRED_HEADER;
+const OFFSET_OF_DATA_ERASURE_CONFIG: usize = SIZE_OF_DATA_SHRED_HEADERS;
 
 // Shreds are uniformly split into erasure batches with a "target" number of
 // data shreds per each batch as below. The actual number of data shreds in
@@ -135,6 +149,8 @@ pub enum Error {
     ErasureError(#[from] reed_solomon_erasure::Error),
     #[error("Invalid data size: {size}, payload: {payload}")]
     InvalidDataSize { size: u16, payload: usize },
+    #[error("Invalid erasure config: {0:?}")]
+    InvalidErasureConfig(ErasureSetInfo),
     #[error("Invalid erasure shard index: {0:?}")]
     InvalidErasureShardIndex(/*headers:*/ Box<dyn Debug + Send>),
     #[error("Invalid merkle proof")]
@@ -200,8 +216,14 @@ enum ShredVariant {
     //   0b0110_????  MerkleCode chained
     //   0b1000_????  MerkleData
     //   0b1001_????  MerkleData chained
+    //   0b1100_????  MerkleData with erasure config
+    //   0b1101_????  MerkleData chained with erasure config
     MerkleCode(/*proof_size:*/ u8, /*chained:*/ bool), // 0b01?0_????
-    MerkleData(/*proof_size:*/ u8, /*chained:*/ bool), // 0b100?_????
+    MerkleData(
+        /*proof_size:*/ u8,
+        /*chained:*/ bool,
+        /*erasure_config:*/ bool,
+    ), // 0b1?0?_????
 }
 
 /// A common header that is present in data and code shred headers
@@ -220,7 +242,16 @@ struct ShredCommonHeader {
 struct DataShredHeader {
     parent_offset: u16,
     flags: ShredFlags,
-    size: u16, // common shred header + data shred header + data
+    size: u16, // common shred header + data shred header + [erasure config header] + data
+}
+
+/// The erasure config header of Merkle data shreds which carry the erasure
+/// config of their erasure batch explicitly, as coding shreds do.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+struct ErasureConfigHeader {
+    num_data_shreds: u16,
+    num_coding_shreds: u16,
+    first_coding_index: u32,
 }
 
 /// The coding shred header has FEC information
@@ -281,6 +312,27 @@ impl ShredId {
     }
 }
 
+/// The erasure batch that a shred belongs to, as carried by coding shreds and
+/// by data shreds with an erasure config header.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct ErasureSetInfo {
+    pub fec_set_index: u32,
+    pub first_coding_index: u32,
+    pub num_data_shreds: u16,
+    pub num_coding_shreds: u16,
+}
+
+impl ErasureSetInfo {
+    fn new(fec_set_index: u32, header: &ErasureConfigHeader) -> Self {
+        Self {
+            fec_set_index,
+            first_coding_index: header.first_coding_index,
+            num_data_shreds: header.num_data_shreds,
+            num_coding_shreds: header.num_coding_shreds,
+        }
+    }
+}
+
 /// Tuple which identifies erasure coding set that the shred belongs to.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub(crate) struct ErasureSetId(Slot, /*fec_set_index:*/ u32);
@@ -474,6 +526,25 @@ impl Shred {
         self.common_header().version
     }
 
+    /// Returns the erasure batch of the shred if the shred carries its
+    /// erasure config, i.e. if it is a coding shred or a data shred with an
+    /// erasure config header.
+    pub fn erasure_set_info(&self) -> Option<ErasureSetInfo> {
+        let fec_set_index = self.fec_set_index();
+        match self {
+            Self::ShredCode(shred) => Some(ErasureSetInfo {
+                fec_set_index,
+                first_coding_index: shred.first_coding_index()?,
+                num_data_shreds: shred.num_data_shreds(),
+                num_coding_shreds: shred.num_coding_shreds(),
+            }),
+            Self::ShredData(shred) => {
+                let header = shred.erasure_config_header()?;
+                Some(ErasureSetInfo::new(fec_set_index, header))
+            }
+        }
+    }
+
     // Identifier for the erasure coding set that the shred belongs to.
     pub(crate) fn erasure_set(&self) -> ErasureSetId {
         ErasureSetId(self.slot(), self.fec_set_index())
@@ -540,10 +611,24 @@ impl Shred {
     }
 
     // Returns true if the erasure coding of the two shreds mismatch.
+    // Returns true if the erasure configs of the two shreds mismatch. Data
+    // shreds are only comparable if they carry the erasure config of their
+    // erasure batch.
     pub(crate) fn erasure_mismatch(&self, other: &Self) -> Result<bool, Error> {
         match (self, other) {
             (Self::ShredCode(shred), Self::ShredCode(other)) => Ok(shred.erasure_mismatch(other)),
-            _ => Err(Error::InvalidShredType),
+            _ => {
+                let (Some(erasure_set_info), Some(other_erasure_set_info)) =
+                    (self.erasure_set_info(), other.erasure_set_info())
+                else {
+                    return Err(Error::InvalidShredType);
+                };
+                // Only merkle data shreds carry an erasure config, so as with
+                // merkle coding shreds, either the signatures match or one
+                // fails sigverify.
+                Ok(erasure_set_info != other_erasure_set_info
+                    || self.signature() != other.signature())
+            }
         }
     }
 
@@ -625,7 +710,7 @@ pub mod layout {
     }
 
     pub fn get_version(shred: &[u8]) -> Option<u16> {
-        <[u8; 2]>::try_from(shred.get(77..79)?)
+        <[u8; 2]>::try_from(shred.get(OFFSET_OF_SHRED_VERSION..)?.get(..2)?)
             .map(u16::from_le_bytes)
             .ok()
     }
@@ -633,7 +718,7 @@ pub mod layout {
     // The caller should verify first that the shred is data and not code!
     pub(super) fn get_parent_offset(shred: &[u8]) -> Option<u16> {
         debug_assert_eq!(get_shred_type(shred).unwrap(), ShredType::Data);
-        <[u8; 2]>::try_from(shred.get(83..85)?)
+        <[u8; 2]>::try_from(shred.get(OFFSET_OF_PARENT_OFFSET..)?.get(..2)?)
             .map(u16::from_le_bytes)
             .ok()
     }
@@ -647,6 +732,112 @@ pub mod layout {
         ))
     }
 
+    /// Typed accessors to the headers of a serialized shred, so that the
+    /// shred does not need to be deserialized or its fields looked up at raw
+    /// offsets. The headers are bounds checked once when the view is created.
+    #[derive(Clone, Copy, Debug)]
+    pub struct ShredView<'a> {
+        shred: &'a [u8],
+        shred_variant: ShredVariant,
+    }
+
+    impl<'a> ShredView<'a> {
+        pub fn new(shred: &'a [u8]) -> Result<Self, Error> {
+            let shred_variant = get_shred_variant(shred)?;
+            let size_of_headers = match shred_variant {
+                ShredVariant::LegacyCode | ShredVariant::MerkleCode(..) => {
+                    SIZE_OF_CODING_SHRED_HEADERS
+                }
+                ShredVariant::LegacyData
+                | ShredVariant::MerkleData(_, _, /*erasure_config:*/ false) => {
+                    SIZE_OF_DATA_SHRED_HEADERS
+                }
+                ShredVariant::MerkleData(_, _, /*erasure_config:*/ true) => {
+                    SIZE_OF_DATA_SHRED_HEADERS + SIZE_OF_ERASURE_CONFIG_HEADER
+                }
+            };
+            if shred.len() < size_of_headers {
+                return Err(Error::InvalidPayloadSize(shred.len()));
+            }
+            Ok(Self {
+                shred,
+                shred_variant,
+            })
+        }
+
+        #[inline]
+        fn get<const N: usize>(&self, offset: usize) -> [u8; N] {
+            // Offsets are within the headers whose size is checked in new.
+            <[u8; N]>::try_from(&self.shred[offset..offset + N]).unwrap()
+        }
+
+        #[inline]
+        pub fn id(&self) -> ShredId {
+            ShredId(self.slot(), self.index(), self.shred_type())
+        }
+
+        #[inline]
+        pub fn shred_type(&self) -> ShredType {
+            ShredType::from(self.shred_variant)
+        }
+
+        #[inline]
+        pub fn slot(&self) -> Slot {
+            Slot::from_le_bytes(self.get(OFFSET_OF_SHRED_SLOT))
+        }
+
+        #[inline]
+        pub fn index(&self) -> u32 {
+            u32::from_le_bytes(self.get(OFFSET_OF_SHRED_INDEX))
+        }
+
+        #[inline]
+        pub fn version(&self) -> u16 {
+            u16::from_le_bytes(self.get(OFFSET_OF_SHRED_VERSION))
+        }
+
+        #[inline]
+        pub fn fec_set_index(&self) -> u32 {
+            u32::from_le_bytes(self.get(OFFSET_OF_FEC_SET_INDEX))
+        }
+
+        /// Returns None for coding shreds.
+        pub fn parent_offset(&self) -> Option<u16> {
+            (self.shred_type() == ShredType::Data)
+                .then(|| u16::from_le_bytes(self.get(OFFSET_OF_PARENT_OFFSET)))
+        }
+
+        /// Same as Shred::erasure_set_info but without deserializing the
+        /// shred; the erasure config is not sanitized.
+        pub fn erasure_set_info(&self) -> Option<ErasureSetInfo> {
+            let offset = match self.shred_variant {
+                ShredVariant::LegacyCode | ShredVariant::MerkleCode(..) => {
+                    OFFSET_OF_CODING_ERASURE_CONFIG
+                }
+                ShredVariant::MerkleData(_, _, /*erasure_config:*/ true) => {
+                    OFFSET_OF_DATA_ERASURE_CONFIG
+                }
+                ShredVariant::LegacyData
+                | ShredVariant::MerkleData(_, _, /*erasure_config:*/ false) => return None,
+            };
+            let num_data_shreds = u16::from_le_bytes(self.get(offset));
+            let num_coding_shreds = u16::from_le_bytes(self.get(offset + 2));
+            let first_coding_index = match self.shred_type() {
+                ShredType::Code => {
+                    let position = u16::from_le_bytes(self.get(offset + 4));
+                    self.index().checked_sub(u32::from(position))?
+                }
+                ShredType::Data => u32::from_le_bytes(self.get(offset + 4)),
+            };
+            Some(ErasureSetInfo {
+                fec_set_index: self.fec_set_index(),
+                first_coding_index,
+                num_data_shreds,
+                num_coding_shreds,
+            })
+        }
+    }
+
     pub(crate) fn get_signed_data(shred: &[u8]) -> Option<SignedData> {
         let data = match get_shred_variant(shred).ok()? {
             ShredVariant::LegacyCode | ShredVariant::LegacyData => {
@@ -658,9 +849,13 @@ pub mod layout {
                     self::merkle::ShredCode::get_merkle_root(shred, proof_size, chained)?;
                 SignedData::MerkleRoot(merkle_root)
             }
-            ShredVariant::MerkleData(proof_size, chained) => {
-                let merkle_root =
-                    self::merkle::ShredData::get_merkle_root(shred, proof_size, chained)?;
+            ShredVariant::MerkleData(proof_size, chained, erasure_config) => {
+                let merkle_root = self::merkle::ShredData::get_merkle_root(
+                    shred,
+                    proof_size,
+                    chained,
+                    erasure_config,
+                )?;
                 SignedData::MerkleRoot(merkle_root)
             }
         };
@@ -686,7 +881,7 @@ pub mod layout {
         if get_shred_type(shred)? != ShredType::Data {
             return Err(Error::InvalidShredType);
         }
-        let Some(flags) = shred.get(85) else {
+        let Some(flags) = shred.get(OFFSET_OF_SHRED_FLAGS) else {
             return Err(Error::InvalidPayloadSize(shred.len()));
         };
         Ok(flags & ShredFlags::SHRED_TICK_REFERENCE_MASK.bits())
@@ -698,8 +893,8 @@ pub mod layout {
             ShredVariant::MerkleCode(proof_size, chained) => {
                 merkle::ShredCode::get_merkle_root(shred, proof_size, chained)
             }
-            ShredVariant::MerkleData(proof_size, chained) => {
-                merkle::ShredData::get_merkle_root(shred, proof_size, chained)
+            ShredVariant::MerkleData(proof_size, chained, erasure_config) => {
+                merkle::ShredData::get_merkle_root(shred, proof_size, chained, erasure_config)
             }
         }
     }
@@ -719,7 +914,7 @@ pub mod layout {
         let shred = get_shred(packet).unwrap();
         let merkle_proof_size = match get_shred_variant(shred).unwrap() {
             ShredVariant::LegacyCode | ShredVariant::LegacyData => None,
-            ShredVariant::MerkleCode(proof_size, _) | ShredVariant::MerkleData(proof_size, _) => {
+            ShredVariant::MerkleCode(proof_size, _) | ShredVariant::MerkleData(proof_size, ..) => {
                 Some(proof_size)
             }
         };
@@ -815,8 +1010,10 @@ impl From<ShredVariant> for u8 {
             ShredVariant::LegacyData => u8::from(ShredType::Data),
             ShredVariant::MerkleCode(proof_size, false) => proof_size | 0x40,
             ShredVariant::MerkleCode(proof_size, true) => proof_size | 0x60,
-            ShredVariant::MerkleData(proof_size, false) => proof_size | 0x80,
-            ShredVariant::MerkleData(proof_size, true) => proof_size | 0x90,
+            ShredVariant::MerkleData(proof_size, false, false) => proof_size | 0x80,
+            ShredVariant::MerkleData(proof_size, true, false) => proof_size | 0x90,
+            ShredVariant::MerkleData(proof_size, false, true) => proof_size | 0xC0,
+            ShredVariant::MerkleData(proof_size, true, true) => proof_size | 0xD0,
         }
     }
 }
@@ -836,9 +1033,17 @@ impl TryFrom<u8> for ShredVariant {
                 )),
                 0x60 => Ok(ShredVariant::MerkleCode(proof_size, /*chained:*/ true)),
                 0x80 => Ok(ShredVariant::MerkleData(
-                    proof_size, /*chained:*/ false,
+                    proof_size, /*chained:*/ false, /*erasure_config:*/ false,
+                )),
+                0x90 => Ok(ShredVariant::MerkleData(
+                    proof_size, /*chained:*/ true, /*erasure_config:*/ false,
+                )),
+                0xC0 => Ok(ShredVariant::MerkleData(
+                    proof_size, /*chained:*/ false, /*erasure_config:*/ true,
+                )),
+                0xD0 => Ok(ShredVariant::MerkleData(
+                    proof_size, /*chained:*/ true, /*erasure_config:*/ true,
                 )),
-                0x90 => Ok(ShredVariant::MerkleData(proof_size, /*chained:*/ true)),
                 _ => Err(Error::InvalidShredVariant),
             }
         }
@@ -882,6 +1087,7 @@ pub(crate) fn make_merkle_shreds_from_entries(
     reference_tick: u8,
     is_last_in_slot: bool,
     chained_merkle_root: Option<Hash>,
+    erasure_config: bool,
     next_shred_index: u32,
     next_code_index: u32,
     reed_solomon_cache: &ReedSolomonCache,
@@ -894,6 +1100,7 @@ pub(crate) fn make_merkle_shreds_from_entries(
         thread_pool,
         keypair,
         chained_merkle_root,
+        erasure_config,
         &entries[..],
         slot,
         parent_slot,
@@ -917,6 +1124,7 @@ pub fn should_discard_shred(
     shred_version: u16,
     should_drop_legacy_shreds: impl Fn(Slot) -> bool,
     enable_chained_merkle_shreds: impl Fn(Slot) -> bool,
+    enable_shred_erasure_config: impl Fn(Slot) -> bool,
     stats: &mut ShredFetchStats,
 ) -> bool {
     debug_assert!(root < max_slot);
@@ -1006,16 +1214,26 @@ pub fn should_discard_shred(
             stats.num_shreds_merkle_code_chained =
                 stats.num_shreds_merkle_code_chained.saturating_add(1);
         }
-        ShredVariant::MerkleData(_, /*chained:*/ false) => {
+        ShredVariant::MerkleData(_, /*chained:*/ false, /*erasure_config:*/ false) => {
             stats.num_shreds_merkle_data = stats.num_shreds_merkle_data.saturating_add(1);
         }
-        ShredVariant::MerkleData(_, /*chained:*/ true) => {
+        ShredVariant::MerkleData(_, /*chained:*/ true, /*erasure_config:*/ false) => {
             if !enable_chained_merkle_shreds(slot) {
                 return true;
             }
             stats.num_shreds_merkle_data_chained =
                 stats.num_shreds_merkle_data_chained.saturating_add(1);
         }
+        ShredVariant::MerkleData(_, chained, /*erasure_config:*/ true) => {
+            if (chained && !enable_chained_merkle_shreds(slot))
+                || !enable_shred_erasure_config(slot)
+            {
+                return true;
+            }
+            stats.num_shreds_merkle_data_erasure_config = stats
+                .num_shreds_merkle_data_erasure_config
+                .saturating_add(1);
+        }
     }
     false
 }
@@ -1031,7 +1249,9 @@ pub fn max_entries_per_n_shred(
     shred_data_size: Option<usize>,
 ) -> u64 {
     // Default 32:32 erasure batches yields 64 shreds; log2(64) = 6.
-    let merkle_variant = Some((/*proof_size:*/ 6, /*chained:*/ false));
+    let merkle_variant = Some((
+        /*proof_size:*/ 6, /*chained:*/ false, /*erasure_config:*/ false,
+    ));
     let data_buffer_size = ShredData::capacity(merkle_variant).unwrap();
     let shred_data_size = shred_data_size.unwrap_or(data_buffer_size) as u64;
     let vec_size = bincode::serialized_size(&vec![entry]).unwrap();
@@ -1144,6 +1364,29 @@ mod tests {
             SIZE_OF_SHRED_INDEX,
             bincode::serialized_size(&common_header.index).unwrap() as usize
         );
+        let erasure_config_header = ErasureConfigHeader {
+            num_data_shreds: u16::MAX,
+            num_coding_shreds: u16::MAX,
+            first_coding_index: u32::MAX,
+        };
+        assert_eq!(
+            SIZE_OF_ERASURE_CONFIG_HEADER,
+            serialized_size(&erasure_config_header).unwrap() as usize
+        );
+        let common_header = ShredCommonHeader {
+            version: 0x1234,
+            fec_set_index: 0x5678_9abc,
+            ..common_header
+        };
+        let bytes = bincode::serialize(&common_header).unwrap();
+        assert_eq!(
+            bytes[OFFSET_OF_SHRED_VERSION..][..2],
+            common_header.version.to_le_bytes()
+        );
+        assert_eq!(
+            bytes[OFFSET_OF_FEC_SET_INDEX..],
+            common_header.fec_set_index.to_le_bytes()
+        );
     }
 
     #[test]
@@ -1217,6 +1460,7 @@ mod tests {
             shred_version,
             |_| false, // should_drop_legacy_shreds
             |_| true,  // enable_chained_merkle_shreds
+            |_| true,  // enable_shred_erasure_config
             &mut stats
         ));
         assert_eq!(stats, ShredFetchStats::default());
@@ -1229,6 +1473,7 @@ mod tests {
             shred_version,
             |_| false, // should_drop_legacy_shreds
             |_| true,  // enable_chained_merkle_shreds
+            |_| true,  // enable_shred_erasure_config
             &mut stats
         ));
         assert_eq!(stats.index_overrun, 1);
@@ -1241,6 +1486,7 @@ mod tests {
             shred_version,
             |_| false, // should_drop_legacy_shreds
             |_| true,  // enable_chained_merkle_shreds
+            |_| true,  // enable_shred_erasure_config
             &mut stats
         ));
         assert_eq!(stats.index_overrun, 2);
@@ -1253,6 +1499,7 @@ mod tests {
             shred_version,
             |_| false, // should_drop_legacy_shreds
             |_| true,  // enable_chained_merkle_shreds
+            |_| true,  // enable_shred_erasure_config
             &mut stats
         ));
         assert_eq!(stats.index_overrun, 3);
@@ -1265,6 +1512,7 @@ mod tests {
             shred_version,
             |_| false, // should_drop_legacy_shreds
             |_| true,  // enable_chained_merkle_shreds
+            |_| true,  // enable_shred_erasure_config
             &mut stats
         ));
         assert_eq!(stats.index_overrun, 4);
@@ -1277,6 +1525,7 @@ mod tests {
             shred_version,
             |_| false, // should_drop_legacy_shreds
             |_| true,  // enable_chained_merkle_shreds
+            |_| true,  // enable_shred_erasure_config
             &mut stats
         ));
         assert_eq!(stats.bad_parent_offset, 1);
@@ -1299,6 +1548,7 @@ mod tests {
             shred_version,
             |_| false, // should_drop_legacy_shreds
             |_| true,  // enable_chained_merkle_shreds
+            |_| true,  // enable_shred_erasure_config
             &mut stats
         ));
 
@@ -1320,6 +1570,7 @@ mod tests {
             shred_version,
             |_| false, // should_drop_legacy_shreds
             |_| true,  // enable_chained_merkle_shreds
+            |_| true,  // enable_shred_erasure_config
             &mut stats
         ));
         assert_eq!(1, stats.index_out_of_bounds);
@@ -1342,6 +1593,7 @@ mod tests {
             shred_version,
             |_| false, // should_drop_legacy_shreds
             |_| true,  // enable_chained_merkle_shreds
+            |_| true,  // enable_shred_erasure_config
             &mut stats
         ));
         packet.buffer_mut()[OFFSET_OF_SHRED_VARIANT] = u8::MAX;
@@ -1353,6 +1605,7 @@ mod tests {
             shred_version,
             |_| false, // should_drop_legacy_shreds
             |_| true,  // enable_chained_merkle_shreds
+            |_| true,  // enable_shred_erasure_config
             &mut stats
         ));
         assert_eq!(1, stats.bad_shred_type);
@@ -1366,6 +1619,7 @@ mod tests {
             shred_version,
             |_| false, // should_drop_legacy_shreds
             |_| true,  // enable_chained_merkle_shreds
+            |_| true,  // enable_shred_erasure_config
             &mut stats
         ));
         assert_eq!(1, stats.bad_shred_type);
@@ -1494,58 +1748,104 @@ mod tests {
         }
         // Merkle data shred.
         assert_eq!(
-            u8::from(ShredVariant::MerkleData(10, /*chained:*/ false)),
+            u8::from(ShredVariant::MerkleData(
+                10, /*chained:*/ false, /*erasure_config:*/ false,
+            )),
             0b1000_1010
         );
         assert_eq!(
-            u8::from(ShredVariant::MerkleData(10, /*chained:*/ true)),
+            u8::from(ShredVariant::MerkleData(
+                10, /*chained:*/ true, /*erasure_config:*/ false,
+            )),
             0b1001_1010
         );
-        for chained in [false, true] {
+        assert_eq!(
+            u8::from(ShredVariant::MerkleData(
+                10, /*chained:*/ false, /*erasure_config:*/ true,
+            )),
+            0b1100_1010
+        );
+        assert_eq!(
+            u8::from(ShredVariant::MerkleData(
+                10, /*chained:*/ true, /*erasure_config:*/ true,
+            )),
+            0b1101_1010
+        );
+        for (chained, erasure_config) in iproduct!([false, true], [false, true]) {
             assert_eq!(
-                ShredType::from(ShredVariant::MerkleData(10, chained)),
+                ShredType::from(ShredVariant::MerkleData(10, chained, erasure_config)),
                 ShredType::Data
             );
         }
         assert_matches!(
             ShredVariant::try_from(0b1000_1010),
-            Ok(ShredVariant::MerkleData(10, /*chained:*/ false))
+            Ok(ShredVariant::MerkleData(
+                10, /*chained:*/ false, /*erasure_config:*/ false,
+            ))
         );
         assert_matches!(
             ShredVariant::try_from(0b1001_1010),
-            Ok(ShredVariant::MerkleData(10, /*chained:*/ true))
+            Ok(ShredVariant::MerkleData(
+                10, /*chained:*/ true, /*erasure_config:*/ false,
+            ))
+        );
+        assert_matches!(
+            ShredVariant::try_from(0b1100_1010),
+            Ok(ShredVariant::MerkleData(
+                10, /*chained:*/ false, /*erasure_config:*/ true,
+            ))
+        );
+        assert_matches!(
+            ShredVariant::try_from(0b1101_1010),
+            Ok(ShredVariant::MerkleData(
+                10, /*chained:*/ true, /*erasure_config:*/ true,
+            ))
+        );
+        assert_matches!(
+            ShredVariant::try_from(0b1110_1010),
+            Err(Error::InvalidShredVariant)
         );
-        let buf = bincode::serialize(&ShredVariant::MerkleData(10, /*chained:*/ false)).unwrap();
+        let buf = bincode::serialize(&ShredVariant::MerkleData(
+            10, /*chained:*/ false, /*erasure_config:*/ false,
+        ))
+        .unwrap();
         assert_eq!(buf, vec![0b1000_1010]);
         assert_matches!(
             bincode::deserialize::<ShredVariant>(&[0b1000_1010]),
-            Ok(ShredVariant::MerkleData(10, /*chained:*/ false))
+            Ok(ShredVariant::MerkleData(
+                10, /*chained:*/ false, /*erasure_config:*/ false,
+            ))
         );
-        let buf = bincode::serialize(&ShredVariant::MerkleData(10, /*chained:*/ true)).unwrap();
-        assert_eq!(buf, vec![0b1001_1010]);
+        let buf = bincode::serialize(&ShredVariant::MerkleData(
+            10, /*chained:*/ true, /*erasure_config:*/ true,
+        ))
+        .unwrap();
+        assert_eq!(buf, vec![0b1101_1010]);
         assert_matches!(
-            bincode::deserialize::<ShredVariant>(&[0b1001_1010]),
-            Ok(ShredVariant::MerkleData(10, /*chained:*/ true))
+            bincode::deserialize::<ShredVariant>(&[0b1101_1010]),
+            Ok(ShredVariant::MerkleData(
+                10, /*chained:*/ true, /*erasure_config:*/ true,
+            ))
         );
-        for (proof_size, chained) in iproduct!(0..=15u8, [false, true]) {
-            let byte = proof_size | if chained { 0b1001_0000 } else { 0b1000_0000 };
-            assert_eq!(
-                u8::from(ShredVariant::MerkleData(proof_size, chained)),
-                byte
-            );
-            assert_eq!(
-                ShredType::from(ShredVariant::MerkleData(proof_size, chained)),
-                ShredType::Data
-            );
-            assert_eq!(
-                ShredVariant::try_from(byte).unwrap(),
-                ShredVariant::MerkleData(proof_size, chained)
-            );
-            let buf = bincode::serialize(&ShredVariant::MerkleData(proof_size, chained)).unwrap();
+        for (proof_size, chained, erasure_config) in
+            iproduct!(0..=15u8, [false, true], [false, true])
+        {
+            let byte = proof_size
+                | if chained { 0b0001_0000 } else { 0 }
+                | if erasure_config {
+                    0b1100_0000
+                } else {
+                    0b1000_0000
+                };
+            let shred_variant = ShredVariant::MerkleData(proof_size, chained, erasure_config);
+            assert_eq!(u8::from(shred_variant), byte);
+            assert_eq!(ShredType::from(shred_variant), ShredType::Data);
+            assert_eq!(ShredVariant::try_from(byte).unwrap(), shred_variant);
+            let buf = bincode::serialize(&shred_variant).unwrap();
             assert_eq!(buf, vec![byte]);
             assert_eq!(
                 bincode::deserialize::<ShredVariant>(&[byte]).unwrap(),
-                ShredVariant::MerkleData(proof_size, chained)
+                shred_variant
             );
         }
     }
@@ -9,9 +9,10 @@ use {
             traits::{
                 Shred as ShredTrait, ShredCode as ShredCodeTrait, ShredData as ShredDataTrait,
             },
-            CodingShredHeader, DataShredHeader, Error, ProcessShredsStats, ShredCommonHeader,
-            ShredFlags, ShredVariant, DATA_SHREDS_PER_FEC_BLOCK, SIZE_OF_CODING_SHRED_HEADERS,
-            SIZE_OF_DATA_SHRED_HEADERS, SIZE_OF_SIGNATURE,
+            CodingShredHeader, DataShredHeader, ErasureConfigHeader, ErasureSetInfo, Error,
+            ProcessShredsStats, ShredCommonHeader, ShredFlags, ShredVariant,
+            DATA_SHREDS_PER_FEC_BLOCK, SIZE_OF_CODING_SHRED_HEADERS, SIZE_OF_DATA_SHRED_HEADERS,
+            SIZE_OF_ERASURE_CONFIG_HEADER, SIZE_OF_SIGNATURE,
         },
         shredder::{self, ReedSolomonCache},
     },
@@ -50,7 +51,9 @@ const MERKLE_HASH_PREFIX_NODE: &[u8] = b"\x01SOLANA_MERKLE_SHREDS_NODE";
 
 type MerkleProofEntry = [u8; 20];
 
-// Layout: {common, data} headers | data buffer
+// Layout: {common, data} headers
+//     | [erasure config header if erasure_config]
+//     | data buffer
 //     | [Merkle root of the previous erasure batch if chained]
 //     | Merkle proof
 // The slice past signature till the end of the data buffer is erasure coded.
@@ -60,6 +63,7 @@ type MerkleProofEntry = [u8; 20];
 pub struct ShredData {
     common_header: ShredCommonHeader,
     data_header: DataShredHeader,
+    erasure_config_header: Option<ErasureConfigHeader>,
     payload: Vec<u8>,
 }
 
@@ -138,20 +142,35 @@ impl ShredData {
     // proof_size is the number of merkle proof entries.
     fn proof_size(&self) -> Result<u8, Error> {
         match self.common_header.shred_variant {
-            ShredVariant::MerkleData(proof_size, _) => Ok(proof_size),
+            ShredVariant::MerkleData(proof_size, ..) => Ok(proof_size),
             _ => Err(Error::InvalidShredVariant),
         }
     }
 
+    // Size of the headers preceding the data buffer, including the erasure
+    // config header if present.
+    fn size_of_headers(erasure_config: bool) -> usize {
+        Self::SIZE_OF_HEADERS
+            + if erasure_config {
+                SIZE_OF_ERASURE_CONFIG_HEADER
+            } else {
+                0
+            }
+    }
+
     // Maximum size of ledger data that can be embedded in a data-shred.
     // Also equal to:
     //   ShredCode::capacity(proof_size).unwrap()
-    //       - ShredData::SIZE_OF_HEADERS
+    //       - ShredData::size_of_headers(erasure_config)
     //       + SIZE_OF_SIGNATURE
-    pub(super) fn capacity(proof_size: u8, chained: bool) -> Result<usize, Error> {
+    pub(super) fn capacity(
+        proof_size: u8,
+        chained: bool,
+        erasure_config: bool,
+    ) -> Result<usize, Error> {
         Self::SIZE_OF_PAYLOAD
             .checked_sub(
-                Self::SIZE_OF_HEADERS
+                Self::size_of_headers(erasure_config)
                     + if chained { SIZE_OF_MERKLE_ROOT } else { 0 }
                     + usize::from(proof_size) * SIZE_OF_MERKLE_PROOF_ENTRY,
             )
@@ -160,25 +179,53 @@ impl ShredData {
 
     // Where the merkle proof starts in the shred binary.
     fn proof_offset(&self) -> Result<usize, Error> {
-        let ShredVariant::MerkleData(proof_size, chained) = self.common_header.shred_variant else {
+        let ShredVariant::MerkleData(proof_size, chained, erasure_config) =
+            self.common_header.shred_variant
+        else {
             return Err(Error::InvalidShredVariant);
         };
-        Self::get_proof_offset(proof_size, chained)
+        Self::get_proof_offset(proof_size, chained, erasure_config)
     }
 
-    fn get_proof_offset(proof_size: u8, chained: bool) -> Result<usize, Error> {
-        Ok(Self::SIZE_OF_HEADERS
-            + Self::capacity(proof_size, chained)?
+    fn get_proof_offset(
+        proof_size: u8,
+        chained: bool,
+        erasure_config: bool,
+    ) -> Result<usize, Error> {
+        Ok(Self::size_of_headers(erasure_config)
+            + Self::capacity(proof_size, chained, erasure_config)?
             + if chained { SIZE_OF_MERKLE_ROOT } else { 0 })
     }
 
     fn chained_merkle_root_offset(&self) -> Result<usize, Error> {
-        let ShredVariant::MerkleData(proof_size, /*chained:*/ true) =
+        let ShredVariant::MerkleData(proof_size, /*chained:*/ true, erasure_config) =
             self.common_header.shred_variant
         else {
             return Err(Error::InvalidShredVariant);
         };
-        Ok(Self::SIZE_OF_HEADERS + Self::capacity(proof_size, /*chained:*/ true)?)
+        Ok(Self::size_of_headers(erasure_config)
+            + Self::capacity(proof_size, /*chained:*/ true, erasure_config)?)
+    }
+
+    pub(super) fn erasure_config_header(&self) -> Option<&ErasureConfigHeader> {
+        self.erasure_config_header.as_ref()
+    }
+
+    fn set_erasure_config_header(&mut self, header: ErasureConfigHeader) -> Result<(), Error> {
+        let ShredVariant::MerkleData(_, _, /*erasure_config:*/ true) =
+            self.common_header.shred_variant
+        else {
+            return Err(Error::InvalidShredVariant);
+        };
+        let Some(buffer) = self
+            .payload
+            .get_mut(Self::SIZE_OF_HEADERS..Self::size_of_headers(/*erasure_config:*/ true))
+        else {
+            return Err(Error::InvalidPayloadSize(self.payload.len()));
+        };
+        bincode::serialize_into(buffer, &header)?;
+        self.erasure_config_header = Some(header);
+        Ok(())
     }
 
     fn set_chained_merkle_root(&mut self, chained_merkle_root: &Hash) -> Result<(), Error> {
@@ -225,16 +272,22 @@ impl ShredData {
         // Deserialize headers.
         let mut cursor = Cursor::new(&shard[..]);
         let common_header: ShredCommonHeader = deserialize_from_with_limit(&mut cursor)?;
-        let ShredVariant::MerkleData(proof_size, chained) = common_header.shred_variant else {
+        let ShredVariant::MerkleData(proof_size, chained, erasure_config) =
+            common_header.shred_variant
+        else {
             return Err(Error::InvalidShredVariant);
         };
         if ShredCode::capacity(proof_size, chained)? != shard_size {
             return Err(Error::InvalidShardSize(shard_size));
         }
         let data_header = deserialize_from_with_limit(&mut cursor)?;
+        let erasure_config_header = erasure_config
+            .then(|| deserialize_from_with_limit(&mut cursor))
+            .transpose()?;
         let mut shred = Self {
             common_header,
             data_header,
+            erasure_config_header,
             payload: shard,
         };
         if let Some(chained_merkle_root) = chained_merkle_root {
@@ -261,10 +314,15 @@ impl ShredData {
         Ok(())
     }
 
-    pub(super) fn get_merkle_root(shred: &[u8], proof_size: u8, chained: bool) -> Option<Hash> {
+    pub(super) fn get_merkle_root(
+        shred: &[u8],
+        proof_size: u8,
+        chained: bool,
+        erasure_config: bool,
+    ) -> Option<Hash> {
         debug_assert_eq!(
             shred::layout::get_shred_variant(shred).unwrap(),
-            ShredVariant::MerkleData(proof_size, chained)
+            ShredVariant::MerkleData(proof_size, chained, erasure_config)
         );
         // Shred index in the erasure batch.
         let index = {
@@ -276,7 +334,7 @@ impl ShredData {
                 .map(usize::try_from)?
                 .ok()?
         };
-        let proof_offset = Self::get_proof_offset(proof_size, chained).ok()?;
+        let proof_offset = Self::get_proof_offset(proof_size, chained, erasure_config).ok()?;
         let proof = get_merkle_proof(shred, proof_offset, proof_size).ok()?;
         let node = get_merkle_node(shred, SIZE_OF_SIGNATURE..proof_offset).ok()?;
         get_merkle_root(index, node, proof).ok()
@@ -445,8 +503,8 @@ impl<'a> ShredTrait<'a> for ShredData {
     impl_shred_common!();
 
     // Also equal to:
-    // ShredData::SIZE_OF_HEADERS
-    //       + ShredData::capacity(proof_size, chained).unwrap()
+    // ShredData::size_of_headers(erasure_config)
+    //       + ShredData::capacity(proof_size, chained, erasure_config).unwrap()
     //       + if chained { SIZE_OF_MERKLE_ROOT } else { 0 }
     //       + usize::from(proof_size) * SIZE_OF_MERKLE_PROOF_ENTRY
     const SIZE_OF_PAYLOAD: usize =
@@ -461,13 +519,17 @@ impl<'a> ShredTrait<'a> for ShredData {
         payload.truncate(Self::SIZE_OF_PAYLOAD);
         let mut cursor = Cursor::new(&payload[..]);
         let common_header: ShredCommonHeader = deserialize_from_with_limit(&mut cursor)?;
-        if !matches!(common_header.shred_variant, ShredVariant::MerkleData(..)) {
+        let ShredVariant::MerkleData(_, _, erasure_config) = common_header.shred_variant else {
             return Err(Error::InvalidShredVariant);
-        }
+        };
         let data_header = deserialize_from_with_limit(&mut cursor)?;
+        let erasure_config_header = erasure_config
+            .then(|| deserialize_from_with_limit(&mut cursor))
+            .transpose()?;
         let shred = Self {
             common_header,
             data_header,
+            erasure_config_header,
             payload,
         };
         shred.sanitize()?;
@@ -485,10 +547,13 @@ impl<'a> ShredTrait<'a> for ShredData {
         if self.payload.len() != Self::SIZE_OF_PAYLOAD {
             return Err(Error::InvalidPayloadSize(self.payload.len()));
         }
-        let ShredVariant::MerkleData(proof_size, chained) = self.common_header.shred_variant else {
+        let ShredVariant::MerkleData(proof_size, chained, erasure_config) =
+            self.common_header.shred_variant
+        else {
             return Err(Error::InvalidShredVariant);
         };
-        let offset = Self::SIZE_OF_HEADERS + Self::capacity(proof_size, chained)?;
+        let offset = Self::size_of_headers(erasure_config)
+            + Self::capacity(proof_size, chained, erasure_config)?;
         let mut shard = self.payload;
         shard.truncate(offset);
         shard.drain(..SIZE_OF_SIGNATURE);
@@ -499,20 +564,29 @@ impl<'a> ShredTrait<'a> for ShredData {
         if self.payload.len() != Self::SIZE_OF_PAYLOAD {
             return Err(Error::InvalidPayloadSize(self.payload.len()));
         }
-        let ShredVariant::MerkleData(proof_size, chained) = self.common_header.shred_variant else {
+        let ShredVariant::MerkleData(proof_size, chained, erasure_config) =
+            self.common_header.shred_variant
+        else {
             return Err(Error::InvalidShredVariant);
         };
-        let offset = Self::SIZE_OF_HEADERS + Self::capacity(proof_size, chained)?;
+        let offset = Self::size_of_headers(erasure_config)
+            + Self::capacity(proof_size, chained, erasure_config)?;
         self.payload
             .get(SIZE_OF_SIGNATURE..offset)
             .ok_or(Error::InvalidPayloadSize(self.payload.len()))
     }
 
     fn sanitize(&self) -> Result<(), Error> {
-        let shred_variant = self.common_header.shred_variant;
-        if !matches!(shred_variant, ShredVariant::MerkleData(..)) {
+        let ShredVariant::MerkleData(_, _, erasure_config) = self.common_header.shred_variant
+        else {
+            return Err(Error::InvalidShredVariant);
+        };
+        if erasure_config != self.erasure_config_header.is_some() {
             return Err(Error::InvalidShredVariant);
         }
+        if let Some(header) = &self.erasure_config_header {
+            sanitize_erasure_config_header(&self.common_header, header)?;
+        }
         let _ = self.merkle_proof()?;
         shred_data::sanitize(self)
     }
@@ -605,21 +679,24 @@ impl ShredDataTrait for ShredData {
     }
 
     fn data(&self) -> Result<&[u8], Error> {
-        let ShredVariant::MerkleData(proof_size, chained) = self.common_header.shred_variant else {
+        let ShredVariant::MerkleData(proof_size, chained, erasure_config) =
+            self.common_header.shred_variant
+        else {
             return Err(Error::InvalidShredVariant);
         };
-        let data_buffer_size = Self::capacity(proof_size, chained)?;
+        let size_of_headers = Self::size_of_headers(erasure_config);
+        let data_buffer_size = Self::capacity(proof_size, chained, erasure_config)?;
         let size = usize::from(self.data_header.size);
         if size > self.payload.len()
-            || size < Self::SIZE_OF_HEADERS
-            || size > Self::SIZE_OF_HEADERS + data_buffer_size
+            || size < size_of_headers
+            || size > size_of_headers + data_buffer_size
         {
             return Err(Error::InvalidDataSize {
                 size: self.data_header.size,
                 payload: self.payload.len(),
             });
         }
-        Ok(&self.payload[Self::SIZE_OF_HEADERS..size])
+        Ok(&self.payload[size_of_headers..size])
     }
 }
 
@@ -762,9 +839,10 @@ pub(super) fn recover(
             && version == &common_header.version
             && fec_set_index == &common_header.fec_set_index
             && match shred {
-                Shred::ShredData(_) => {
-                    shred_variant == &ShredVariant::MerkleData(proof_size, chained)
-                }
+                Shred::ShredData(_) => matches!(
+                    shred_variant,
+                    ShredVariant::MerkleData(p, c, _) if *p == proof_size && *c == chained
+                ),
                 Shred::ShredCode(shred) => {
                     let CodingShredHeader {
                         num_data_shreds,
@@ -824,8 +902,16 @@ pub(super) fn recover(
                     version,
                     fec_set_index,
                 } = shred.common_header;
-                if shred_variant != ShredVariant::MerkleData(proof_size, chained)
-                    || common_header.slot != slot
+                // Data shreds of a batch may carry its erasure config, which
+                // then should match the coding shreds'.
+                if !matches!(
+                    shred_variant,
+                    ShredVariant::MerkleData(p, c, _) if p == proof_size && c == chained
+                ) || shred.erasure_config_header.is_some_and(|header| {
+                    header.num_data_shreds != coding_header.num_data_shreds
+                        || header.num_coding_shreds != coding_header.num_coding_shreds
+                        || header.first_coding_index != common_header.index
+                }) || common_header.slot != slot
                     || common_header.version != version
                     || common_header.fec_set_index != fec_set_index
                 {
@@ -886,6 +972,33 @@ pub(super) fn recover(
         .collect())
 }
 
+fn sanitize_erasure_config_header(
+    common_header: &ShredCommonHeader,
+    header: &ErasureConfigHeader,
+) -> Result<(), Error> {
+    let ShredVariant::MerkleData(proof_size, _, /*erasure_config:*/ true) =
+        common_header.shred_variant
+    else {
+        return Err(Error::InvalidShredVariant);
+    };
+    let num_data_shreds = usize::from(header.num_data_shreds);
+    let num_coding_shreds = usize::from(header.num_coding_shreds);
+    let erasure_shard_index = common_header
+        .index
+        .checked_sub(common_header.fec_set_index)
+        .map(usize::try_from);
+    if num_data_shreds == 0
+        || num_coding_shreds == 0
+        || num_coding_shreds > 8 * DATA_SHREDS_PER_FEC_BLOCK
+        || get_proof_size(num_data_shreds + num_coding_shreds) != proof_size
+        || !matches!(erasure_shard_index, Some(Ok(index)) if index < num_data_shreds)
+    {
+        let erasure_set_info = ErasureSetInfo::new(common_header.fec_set_index, header);
+        return Err(Error::InvalidErasureConfig(erasure_set_info));
+    }
+    Ok(())
+}
+
 // Maps number of (code + data) shreds to merkle_proof.len().
 fn get_proof_size(num_shreds: usize) -> u8 {
     let bits = usize::BITS - num_shreds.leading_zeros();
@@ -903,6 +1016,8 @@ pub(super) fn make_shreds_from_data(
     keypair: &Keypair,
     // The Merkle root of the previous erasure batch if chained.
     chained_merkle_root: Option<Hash>,
+    // If data shreds should carry the erasure config of their batch.
+    erasure_config: bool,
     mut data: &[u8], // Serialized &[Entry]
     slot: Slot,
     parent_slot: Slot,
@@ -917,15 +1032,19 @@ pub(super) fn make_shreds_from_data(
     fn new_shred_data(
         common_header: ShredCommonHeader,
         mut data_header: DataShredHeader,
+        erasure_config: bool,
         data: &[u8],
     ) -> ShredData {
-        let size = ShredData::SIZE_OF_HEADERS + data.len();
+        let offset = ShredData::size_of_headers(erasure_config);
+        let size = offset + data.len();
         let mut payload = vec![0u8; ShredData::SIZE_OF_PAYLOAD];
-        payload[ShredData::SIZE_OF_HEADERS..size].copy_from_slice(data);
+        payload[offset..size].copy_from_slice(data);
         data_header.size = size as u16;
+        // The erasure config header is populated in make_erasure_batch.
         ShredData {
             common_header,
             data_header,
+            erasure_config_header: None,
             payload,
         }
     }
@@ -934,11 +1053,11 @@ pub(super) fn make_shreds_from_data(
     let erasure_batch_size =
         shredder::get_erasure_batch_size(DATA_SHREDS_PER_FEC_BLOCK, is_last_in_slot);
     let proof_size = get_proof_size(erasure_batch_size);
-    let data_buffer_size = ShredData::capacity(proof_size, chained)?;
+    let data_buffer_size = ShredData::capacity(proof_size, chained, erasure_config)?;
     let chunk_size = DATA_SHREDS_PER_FEC_BLOCK * data_buffer_size;
     let mut common_header = ShredCommonHeader {
         signature: Signature::default(),
-        shred_variant: ShredVariant::MerkleData(proof_size, chained),
+        shred_variant: ShredVariant::MerkleData(proof_size, chained, erasure_config),
         slot,
         index: next_shred_index,
         version: shred_version,
@@ -967,7 +1086,7 @@ pub(super) fn make_shreds_from_data(
         let (chunk, rest) = data.split_at(chunk_size);
         common_header.fec_set_index = common_header.index;
         for shred in chunk.chunks(data_buffer_size) {
-            let shred = new_shred_data(common_header, data_header, shred);
+            let shred = new_shred_data(common_header, data_header, erasure_config, shred);
             shreds.push(shred);
             common_header.index += 1;
         }
@@ -980,7 +1099,8 @@ pub(super) fn make_shreds_from_data(
         // which can embed the remaining data.
         let (proof_size, data_buffer_size) = (1u8..32)
             .find_map(|proof_size| {
-                let data_buffer_size = ShredData::capacity(proof_size, chained).ok()?;
+                let data_buffer_size =
+                    ShredData::capacity(proof_size, chained, erasure_config).ok()?;
                 let num_data_shreds = (data.len() + data_buffer_size - 1) / data_buffer_size;
                 let num_data_shreds = num_data_shreds.max(1);
                 let erasure_batch_size =
@@ -989,7 +1109,7 @@ pub(super) fn make_shreds_from_data(
                     .then_some((proof_size, data_buffer_size))
             })
             .ok_or(Error::UnknownProofSize)?;
-        common_header.shred_variant = ShredVariant::MerkleData(proof_size, chained);
+        common_header.shred_variant = ShredVariant::MerkleData(proof_size, chained, erasure_config);
         common_header.fec_set_index = common_header.index;
         let chunks = if data.is_empty() {
             // Generate one data shred with empty data.
@@ -998,7 +1118,7 @@ pub(super) fn make_shreds_from_data(
             Either::Right(data.chunks(data_buffer_size))
         };
         for shred in chunks {
-            let shred = new_shred_data(common_header, data_header, shred);
+            let shred = new_shred_data(common_header, data_header, erasure_config, shred);
             shreds.push(shred);
             common_header.index += 1;
         }
@@ -1009,7 +1129,7 @@ pub(super) fn make_shreds_from_data(
     // Only the very last shred may have residual data buffer.
     debug_assert!(shreds.iter().rev().skip(1).all(|shred| {
         let proof_size = shred.proof_size().unwrap();
-        let capacity = ShredData::capacity(proof_size, chained).unwrap();
+        let capacity = ShredData::capacity(proof_size, chained, erasure_config).unwrap();
         shred.data().unwrap().len() == capacity
     }));
     // Adjust flags for the very last shred.
@@ -1132,19 +1252,30 @@ fn make_erasure_batch(
     let erasure_batch_size = shredder::get_erasure_batch_size(num_data_shreds, is_last_in_slot);
     let num_coding_shreds = erasure_batch_size - num_data_shreds;
     let proof_size = get_proof_size(erasure_batch_size);
-    debug_assert!(shreds
-        .iter()
-        .all(|shred| shred.common_header.shred_variant
-            == ShredVariant::MerkleData(proof_size, chained)));
     let mut common_header = match shreds.first() {
         None => return Err(Error::from(TooFewShards)),
         Some(shred) => shred.common_header,
     };
+    let ShredVariant::MerkleData(_, _, erasure_config) = common_header.shred_variant else {
+        return Err(Error::InvalidShredVariant);
+    };
+    debug_assert!(shreds.iter().all(|shred| shred.common_header.shred_variant
+        == ShredVariant::MerkleData(proof_size, chained, erasure_config)));
     if let Some(hash) = chained_merkle_root {
         for shred in &mut shreds {
             shred.set_chained_merkle_root(&hash)?;
         }
     }
+    if erasure_config {
+        let header = ErasureConfigHeader {
+            num_data_shreds: num_data_shreds as u16,
+            num_coding_shreds: num_coding_shreds as u16,
+            first_coding_index: next_code_index,
+        };
+        for shred in &mut shreds {
+            shred.set_erasure_config_header(header)?;
+        }
+    }
     // Generate erasure codings for encoded shard of data shreds.
     let data: Vec<_> = shreds
         .iter()
@@ -1229,9 +1360,9 @@ mod test {
     };
 
     // Total size of a data shred including headers and merkle proof.
-    fn shred_data_size_of_payload(proof_size: u8, chained: bool) -> usize {
-        ShredData::SIZE_OF_HEADERS
-            + ShredData::capacity(proof_size, chained).unwrap()
+    fn shred_data_size_of_payload(proof_size: u8, chained: bool, erasure_config: bool) -> usize {
+        ShredData::size_of_headers(erasure_config)
+            + ShredData::capacity(proof_size, chained, erasure_config).unwrap()
             + if chained { SIZE_OF_MERKLE_ROOT } else { 0 }
             + usize::from(proof_size) * SIZE_OF_MERKLE_PROOF_ENTRY
     }
@@ -1240,10 +1371,10 @@ mod test {
     // All payload excluding merkle proof and the signature are erasure coded.
     // Therefore the data capacity is equal to erasure encoded shard size minus
     // size of erasure encoded header.
-    fn shred_data_capacity(proof_size: u8, chained: bool) -> usize {
-        const SIZE_OF_ERASURE_ENCODED_HEADER: usize =
-            ShredData::SIZE_OF_HEADERS - SIZE_OF_SIGNATURE;
-        ShredCode::capacity(proof_size, chained).unwrap() - SIZE_OF_ERASURE_ENCODED_HEADER
+    fn shred_data_capacity(proof_size: u8, chained: bool, erasure_config: bool) -> usize {
+        let size_of_erasure_encoded_header =
+            ShredData::size_of_headers(erasure_config) - SIZE_OF_SIGNATURE;
+        ShredCode::capacity(proof_size, chained).unwrap() - size_of_erasure_encoded_header
     }
 
     fn shred_data_size_of_erasure_encoded_slice(proof_size: u8, chained: bool) -> usize {
@@ -1253,24 +1384,28 @@ mod test {
             - usize::from(proof_size) * SIZE_OF_MERKLE_PROOF_ENTRY
     }
 
-    #[test_case(false)]
-    #[test_case(true)]
-    fn test_shred_data_size_of_payload(chained: bool) {
+    #[test_case(false, false)]
+    #[test_case(false, true)]
+    #[test_case(true, false)]
+    #[test_case(true, true)]
+    fn test_shred_data_size_of_payload(chained: bool, erasure_config: bool) {
         for proof_size in 0..0x15 {
             assert_eq!(
                 ShredData::SIZE_OF_PAYLOAD,
-                shred_data_size_of_payload(proof_size, chained)
+                shred_data_size_of_payload(proof_size, chained, erasure_config)
             );
         }
     }
 
-    #[test_case(false)]
-    #[test_case(true)]
-    fn test_shred_data_capacity(chained: bool) {
+    #[test_case(false, false)]
+    #[test_case(false, true)]
+    #[test_case(true, false)]
+    #[test_case(true, true)]
+    fn test_shred_data_capacity(chained: bool, erasure_config: bool) {
         for proof_size in 0..0x15 {
             assert_eq!(
-                ShredData::capacity(proof_size, chained).unwrap(),
-                shred_data_capacity(proof_size, chained)
+                ShredData::capacity(proof_size, chained, erasure_config).unwrap(),
+                shred_data_capacity(proof_size, chained, erasure_config)
             );
         }
     }
@@ -1353,10 +1488,12 @@ mod test {
         let keypair = Keypair::new();
         let num_shreds = num_data_shreds + num_coding_shreds;
         let proof_size = get_proof_size(num_shreds);
-        let capacity = ShredData::capacity(proof_size, chained).unwrap();
+        let capacity = ShredData::capacity(proof_size, chained, /*erasure_config:*/ false).unwrap();
         let common_header = ShredCommonHeader {
             signature: Signature::default(),
-            shred_variant: ShredVariant::MerkleData(proof_size, chained),
+            shred_variant: ShredVariant::MerkleData(
+                proof_size, chained, /*erasure_config:*/ false,
+            ),
             slot: 145_865_705,
             index: 1835,
             version: rng.gen(),
@@ -1394,6 +1531,7 @@ mod test {
             let shred = ShredData {
                 common_header,
                 data_header,
+                erasure_config_header: None,
                 payload,
             };
             shreds.push(Shred::ShredData(shred));
@@ -1529,17 +1667,22 @@ mod test {
                 &mut rng,
                 data_size,
                 chained,
+                false, // erasure_config
                 is_last_in_slot,
                 &reed_solomon_cache,
             );
         }
     }
 
-    #[test_case(false, false)]
-    #[test_case(false, true)]
-    #[test_case(true, false)]
-    #[test_case(true, true)]
-    fn test_make_shreds_from_data_rand(chained: bool, is_last_in_slot: bool) {
+    #[test_case(false, false, false)]
+    #[test_case(false, false, true)]
+    #[test_case(false, true, false)]
+    #[test_case(false, true, true)]
+    #[test_case(true, false, false)]
+    #[test_case(true, false, true)]
+    #[test_case(true, true, false)]
+    #[test_case(true, true, true)]
+    fn test_make_shreds_from_data_rand(chained: bool, erasure_config: bool, is_last_in_slot: bool) {
         let mut rng = rand::thread_rng();
         let reed_solomon_cache = ReedSolomonCache::default();
         for _ in 0..32 {
@@ -1548,6 +1691,7 @@ mod test {
                 &mut rng,
                 data_size,
                 chained,
+                erasure_config,
                 is_last_in_slot,
                 &reed_solomon_cache,
             );
@@ -1567,6 +1711,7 @@ mod test {
                 &mut rng,
                 data_size,
                 chained,
+                false, // erasure_config
                 is_last_in_slot,
                 &reed_solomon_cache,
             );
@@ -1577,6 +1722,7 @@ mod test {
         rng: &mut R,
         data_size: usize,
         chained: bool,
+        erasure_config: bool,
         is_last_in_slot: bool,
         reed_solomon_cache: &ReedSolomonCache,
     ) {
@@ -1595,6 +1741,7 @@ mod test {
             &thread_pool,
             &keypair,
             chained_merkle_root,
+            erasure_config,
             &data[..],
             slot,
             parent_slot,
@@ -1635,7 +1782,7 @@ mod test {
                 slot,
                 index,
                 version,
-                fec_set_index: _,
+                fec_set_index,
             } = *shred.common_header();
             let shred_type = ShredType::from(shred_variant);
             let key = ShredId::new(slot, index, shred_type);
@@ -1658,6 +1805,10 @@ mod test {
             let data = shred::layout::get_signed_data(shred).unwrap();
             assert_eq!(data, SignedData::MerkleRoot(merkle_root));
             assert!(signature.verify(pubkey.as_ref(), data.as_ref()));
+            let view = shred::layout::ShredView::new(shred).unwrap();
+            assert_eq!(view.id(), key);
+            assert_eq!(view.version(), version);
+            assert_eq!(view.fec_set_index(), fec_set_index);
         }
         // Verify common, data and coding headers.
         let mut num_data_shreds = 0;
@@ -1680,8 +1831,9 @@ mod test {
                     assert_eq!(common_header.index, next_shred_index + num_data_shreds);
                     assert_eq!(
                         common_header.shred_variant,
-                        ShredVariant::MerkleData(proof_size, chained)
+                        ShredVariant::MerkleData(proof_size, chained, erasure_config)
                     );
+                    assert_eq!(shred.erasure_config_header.is_some(), erasure_config);
                     assert!(common_header.fec_set_index <= common_header.index);
                     assert_eq!(
                         Slot::from(shred.data_header.parent_offset),
@@ -1705,6 +1857,30 @@ mod test {
             }
         }
         assert!(num_coding_shreds >= num_data_shreds);
+        // Assert that data shreds with an erasure config header carry the
+        // same erasure config as the coding shreds.
+        for batch in shreds
+            .iter()
+            .map(|shred| shred::Shred::from(shred.clone()))
+            .group_by(shred::Shred::fec_set_index)
+            .into_iter()
+            .map(|(_, shreds)| shreds.collect::<Vec<_>>())
+        {
+            let (code, data): (Vec<_>, Vec<_>) = batch.iter().partition(|shred| shred.is_code());
+            let erasure_set_info = code[0].erasure_set_info().unwrap();
+            assert_eq!(erasure_set_info.num_data_shreds as usize, data.len());
+            assert_eq!(erasure_set_info.num_coding_shreds as usize, code.len());
+            assert_eq!(erasure_set_info.first_coding_index, code[0].index());
+            for shred in &batch {
+                let view = shred::layout::ShredView::new(shred.payload()).unwrap();
+                if shred.is_data() && !erasure_config {
+                    assert_eq!(shred.erasure_set_info(), None);
+                } else {
+                    assert_eq!(shred.erasure_set_info(), Some(erasure_set_info));
+                }
+                assert_eq!(view.erasure_set_info(), shred.erasure_set_info());
+            }
+        }
         // Assert that only the last shred is LAST_SHRED_IN_SLOT.
         assert_eq!(
             data_shreds
@@ -4,8 +4,8 @@ use {
         common::dispatch,
         legacy, merkle,
         traits::{Shred as _, ShredData as ShredDataTrait},
-        DataShredHeader, Error, ShredCommonHeader, ShredFlags, ShredType, ShredVariant, SignedData,
-        MAX_DATA_SHREDS_PER_SLOT,
+        DataShredHeader, ErasureConfigHeader, Error, ShredCommonHeader, ShredFlags, ShredType,
+        ShredVariant, SignedData, MAX_DATA_SHREDS_PER_SLOT,
     },
     solana_sdk::{clock::Slot, hash::Hash, signature::Signature},
 };
@@ -85,6 +85,15 @@ impl ShredData {
         (flags & ShredFlags::SHRED_TICK_REFERENCE_MASK).bits()
     }
 
+    // Returns None for legacy shreds and Merkle shreds which do not carry
+    // the erasure config of their erasure batch.
+    pub(super) fn erasure_config_header(&self) -> Option<&ErasureConfigHeader> {
+        match self {
+            Self::Legacy(_) => None,
+            Self::Merkle(shred) => shred.erasure_config_header(),
+        }
+    }
+
     // Possibly trimmed payload;
     // Should only be used when storing shreds to blockstore.
     pub(super) fn bytes_to_store(&self) -> &[u8] {
@@ -112,11 +121,17 @@ impl ShredData {
     // merkle_proof_size is the number of merkle proof entries.
     // None indicates a legacy data-shred.
     pub fn capacity(
-        merkle_variant: Option<(/*proof_size:*/ u8, /*chained:*/ bool)>,
+        merkle_variant: Option<(
+            /*proof_size:*/ u8,
+            /*chained:*/ bool,
+            /*erasure_config:*/ bool,
+        )>,
     ) -> Result<usize, Error> {
         match merkle_variant {
             None => Ok(legacy::ShredData::CAPACITY),
-            Some((proof_size, chained)) => merkle::ShredData::capacity(proof_size, chained),
+            Some((proof_size, chained, erasure_config)) => {
+                merkle::ShredData::capacity(proof_size, chained, erasure_config)
+            }
         }
     }
 
@@ -38,6 +38,7 @@ pub struct ShredFetchStats {
     pub(crate) num_shreds_merkle_code_chained: usize,
     pub(crate) num_shreds_merkle_data: usize,
     pub(crate) num_shreds_merkle_data_chained: usize,
+    pub(crate) num_shreds_merkle_data_erasure_config: usize,
     pub ping_count: usize,
     pub ping_err_verify_count: usize,
     pub(crate) index_bad_deserialize: usize,
@@ -137,6 +138,11 @@ impl ShredFetchStats {
                 self.num_shreds_merkle_data_chained,
                 i64
             ),
+            (
+                "num_shreds_merkle_data_erasure_config",
+                self.num_shreds_merkle_data_erasure_config,
+                i64
+            ),
             ("ping_count", self.ping_count, i64),
             ("ping_err_verify_count", self.ping_err_verify_count, i64),
             ("slot_bad_deserialize", self.slot_bad_deserialize, i64),
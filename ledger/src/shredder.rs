@@ -48,6 +48,8 @@ pub struct Shredder {
     parent_slot: Slot,
     version: u16,
     reference_tick: u8,
+    // If Merkle data shreds should carry the erasure config of their batch.
+    erasure_config: bool,
 }
 
 impl Shredder {
@@ -65,10 +67,18 @@ impl Shredder {
                 parent_slot,
                 reference_tick,
                 version,
+                erasure_config: false,
             })
         }
     }
 
+    /// Emit Merkle data shreds with an erasure config header, which must be
+    /// gated on the enable_shred_erasure_config feature.
+    pub fn with_erasure_config(mut self, erasure_config: bool) -> Self {
+        self.erasure_config = erasure_config;
+        self
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn entries_to_shreds(
         &self,
@@ -96,6 +106,7 @@ impl Shredder {
                 self.reference_tick,
                 is_last_in_slot,
                 chained_merkle_root,
+                self.erasure_config,
                 next_shred_index,
                 next_code_index,
                 reed_solomon_cache,
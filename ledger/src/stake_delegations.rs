@@ -0,0 +1,162 @@
+use {
+    solana_measure::measure::Measure,
+    solana_metrics::datapoint_debug,
+    solana_runtime::{bank::Bank, transaction_batch::TransactionBatch},
+    solana_sdk::{
+        account::ReadableAccount,
+        account_utils::StateMut,
+        pubkey::Pubkey,
+        stake::{self, state::StakeStateV2},
+    },
+    solana_transaction_status::{
+        stake_changes::{TransactionStakeDelegation, TransactionStakeDelegations},
+        StakeDelegation,
+    },
+};
+
+/// The delegations of the stake accounts of the transactions of `batch`, for
+/// the transactions which may change them
+pub fn collect_stake_delegations(
+    bank: &Bank,
+    batch: &TransactionBatch,
+) -> TransactionStakeDelegations {
+    let mut delegations: TransactionStakeDelegations = vec![];
+    let mut collect_time = Measure::start("collect_stake_delegations");
+
+    for transaction in batch.sanitized_transactions() {
+        let account_keys = transaction.message().account_keys();
+        let has_stake_program = account_keys
+            .iter()
+            .any(|account_id| account_id == &stake::program::id());
+
+        let mut transaction_delegations: Vec<TransactionStakeDelegation> = vec![];
+        if has_stake_program {
+            for (index, account_id) in account_keys.iter().enumerate() {
+                if !transaction.message().is_writable(index) {
+                    continue;
+                }
+
+                if let Some(delegation) = collect_stake_delegation_from_account(bank, account_id) {
+                    transaction_delegations.push(TransactionStakeDelegation {
+                        account_index: index as u8,
+                        delegation,
+                    });
+                }
+            }
+        }
+        delegations.push(transaction_delegations);
+    }
+    collect_time.stop();
+    datapoint_debug!(
+        "collect_stake_delegations",
+        ("collect_time_us", collect_time.as_us(), i64),
+    );
+    delegations
+}
+
+fn collect_stake_delegation_from_account(
+    bank: &Bank,
+    account_id: &Pubkey,
+) -> Option<StakeDelegation> {
+    let account = bank.get_account(account_id)?;
+
+    if account.owner() != &stake::program::id() {
+        return None;
+    }
+
+    let stake_state: StakeStateV2 = account.state().ok()?;
+    let delegation = stake_state.delegation()?;
+    Some(StakeDelegation {
+        voter: delegation.voter_pubkey.to_string(),
+        stake: delegation.stake,
+        activation_epoch: delegation.activation_epoch,
+        deactivation_epoch: delegation.deactivation_epoch,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use {
+        super::*,
+        solana_sdk::{
+            account::{Account, AccountSharedData},
+            genesis_config::create_genesis_config,
+            stake::{
+                stake_flags::StakeFlags,
+                state::{Authorized, Delegation, Lockup, Meta, Stake},
+            },
+        },
+    };
+
+    #[test]
+    fn test_collect_stake_delegation_from_account() {
+        let (mut genesis_config, _mint_keypair) = create_genesis_config(500);
+
+        let voter_pubkey = Pubkey::new_unique();
+        let meta = Meta {
+            rent_exempt_reserve: 42,
+            authorized: Authorized::auto(&Pubkey::new_unique()),
+            lockup: Lockup::default(),
+        };
+        let stake = Stake {
+            delegation: Delegation {
+                voter_pubkey,
+                stake: 100,
+                activation_epoch: 2,
+                deactivation_epoch: u64::MAX,
+                ..Delegation::default()
+            },
+            credits_observed: 10,
+        };
+        let stake_account = |stake_state: StakeStateV2, owner: &Pubkey| -> Account {
+            let mut account = AccountSharedData::new(1_000, StakeStateV2::size_of(), owner);
+            account.set_state(&stake_state).unwrap();
+            account.into()
+        };
+
+        let delegated_pubkey = Pubkey::new_unique();
+        genesis_config.add_account(
+            delegated_pubkey,
+            stake_account(
+                StakeStateV2::Stake(meta, stake, StakeFlags::empty()),
+                &stake::program::id(),
+            ),
+        );
+        let initialized_pubkey = Pubkey::new_unique();
+        genesis_config.add_account(
+            initialized_pubkey,
+            stake_account(StakeStateV2::Initialized(meta), &stake::program::id()),
+        );
+        let other_owner_pubkey = Pubkey::new_unique();
+        genesis_config.add_account(
+            other_owner_pubkey,
+            stake_account(
+                StakeStateV2::Stake(meta, stake, StakeFlags::empty()),
+                &Pubkey::new_unique(),
+            ),
+        );
+        let bank = Bank::new_for_tests(&genesis_config);
+
+        assert_eq!(
+            collect_stake_delegation_from_account(&bank, &delegated_pubkey),
+            Some(StakeDelegation {
+                voter: voter_pubkey.to_string(),
+                stake: 100,
+                activation_epoch: 2,
+                deactivation_epoch: u64::MAX,
+            })
+        );
+        assert_eq!(
+            collect_stake_delegation_from_account(&bank, &initialized_pubkey),
+            None
+        );
+        assert_eq!(
+            collect_stake_delegation_from_account(&bank, &other_owner_pubkey),
+            None
+        );
+        assert_eq!(
+            collect_stake_delegation_from_account(&bank, &Pubkey::new_unique()),
+            None
+        );
+    }
+}
@@ -1,24 +1,41 @@
 use {
     solana_account_decoder::parse_token::{
-        is_known_spl_token_id, token_amount_to_ui_amount, UiTokenAmount,
+        is_known_spl_token_id, token_amount_to_ui_amount, token_amount_to_ui_amount_with_interest,
+        UiTokenAmount,
     },
     solana_measure::measure::Measure,
     solana_metrics::datapoint_debug,
     solana_runtime::{bank::Bank, transaction_batch::TransactionBatch},
     solana_sdk::{account::ReadableAccount, pubkey::Pubkey},
     solana_transaction_status::{
-        token_balances::TransactionTokenBalances, TransactionTokenBalance,
+        token_balances::TransactionTokenBalances, TokenBalanceExtension, TransactionTokenBalance,
     },
     spl_token_2022::{
-        extension::StateWithExtensions,
+        extension::{
+            interest_bearing_mint::InterestBearingConfig,
+            transfer_fee::{TransferFeeAmount, TransferFeeConfig},
+            BaseStateWithExtensions, StateWithExtensions,
+        },
         state::{Account as TokenAccount, Mint},
     },
     std::collections::HashMap,
 };
 
-fn get_mint_decimals(bank: &Bank, mint: &Pubkey) -> Option<u8> {
+/// What the token balances of a mint are reported with
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TokenMintInfo {
+    decimals: u8,
+    interest_bearing_config: Option<InterestBearingConfig>,
+    transfer_fee_config: Option<TransferFeeConfig>,
+}
+
+fn get_mint_info(bank: &Bank, mint: &Pubkey) -> Option<TokenMintInfo> {
     if mint == &spl_token::native_mint::id() {
-        Some(spl_token::native_mint::DECIMALS)
+        Some(TokenMintInfo {
+            decimals: spl_token::native_mint::DECIMALS,
+            interest_bearing_config: None,
+            transfer_fee_config: None,
+        })
     } else {
         let mint_account = bank.get_account(mint)?;
 
@@ -26,18 +43,20 @@ fn get_mint_decimals(bank: &Bank, mint: &Pubkey) -> Option<u8> {
             return None;
         }
 
-        let decimals = StateWithExtensions::<Mint>::unpack(mint_account.data())
-            .map(|mint| mint.base.decimals)
-            .ok()?;
+        let mint = StateWithExtensions::<Mint>::unpack(mint_account.data()).ok()?;
 
-        Some(decimals)
+        Some(TokenMintInfo {
+            decimals: mint.base.decimals,
+            interest_bearing_config: mint.get_extension::<InterestBearingConfig>().ok().copied(),
+            transfer_fee_config: mint.get_extension::<TransferFeeConfig>().ok().copied(),
+        })
     }
 }
 
 pub fn collect_token_balances(
     bank: &Bank,
     batch: &TransactionBatch,
-    mint_decimals: &mut HashMap<Pubkey, u8>,
+    mint_infos: &mut HashMap<Pubkey, TokenMintInfo>,
 ) -> TransactionTokenBalances {
     let mut balances: TransactionTokenBalances = vec![];
     let mut collect_time = Measure::start("collect_token_balances");
@@ -58,7 +77,8 @@ pub fn collect_token_balances(
                     ui_token_amount,
                     owner,
                     program_id,
-                }) = collect_token_balance_from_account(bank, account_id, mint_decimals)
+                    extensions,
+                }) = collect_token_balance_from_account(bank, account_id, mint_infos)
                 {
                     transaction_balances.push(TransactionTokenBalance {
                         account_index: index as u8,
@@ -66,6 +86,7 @@ pub fn collect_token_balances(
                         ui_token_amount,
                         owner,
                         program_id,
+                        extensions,
                     });
                 }
             }
@@ -86,12 +107,13 @@ struct TokenBalanceData {
     owner: String,
     ui_token_amount: UiTokenAmount,
     program_id: String,
+    extensions: Vec<TokenBalanceExtension>,
 }
 
 fn collect_token_balance_from_account(
     bank: &Bank,
     account_id: &Pubkey,
-    mint_decimals: &mut HashMap<Pubkey, u8>,
+    mint_infos: &mut HashMap<Pubkey, TokenMintInfo>,
 ) -> Option<TokenBalanceData> {
     let account = bank.get_account(account_id)?;
 
@@ -102,17 +124,48 @@ fn collect_token_balance_from_account(
     let token_account = StateWithExtensions::<TokenAccount>::unpack(account.data()).ok()?;
     let mint = token_account.base.mint;
 
-    let decimals = mint_decimals.get(&mint).cloned().or_else(|| {
-        let decimals = get_mint_decimals(bank, &mint)?;
-        mint_decimals.insert(mint, decimals);
-        Some(decimals)
+    let mint_info = mint_infos.get(&mint).cloned().or_else(|| {
+        let mint_info = get_mint_info(bank, &mint)?;
+        mint_infos.insert(mint, mint_info);
+        Some(mint_info)
     })?;
 
+    let amount = token_account.base.amount;
+    let ui_token_amount = match &mint_info.interest_bearing_config {
+        Some(interest_bearing_config) => token_amount_to_ui_amount_with_interest(
+            amount,
+            mint_info.decimals,
+            interest_bearing_config,
+            bank.clock().unix_timestamp,
+        ),
+        None => token_amount_to_ui_amount(amount, mint_info.decimals),
+    };
+
+    let mut extensions = vec![];
+    if let Ok(transfer_fee_amount) = token_account.get_extension::<TransferFeeAmount>() {
+        extensions.push(TokenBalanceExtension::TransferFeeAmount {
+            withheld_amount: transfer_fee_amount.withheld_amount.into(),
+        });
+    }
+    if let Some(transfer_fee_config) = &mint_info.transfer_fee_config {
+        let transfer_fee = transfer_fee_config.get_epoch_fee(bank.epoch());
+        extensions.push(TokenBalanceExtension::TransferFeeConfig {
+            transfer_fee_basis_points: transfer_fee.transfer_fee_basis_points.into(),
+            maximum_fee: transfer_fee.maximum_fee.into(),
+        });
+    }
+    if let Some(interest_bearing_config) = &mint_info.interest_bearing_config {
+        extensions.push(TokenBalanceExtension::InterestBearingConfig {
+            rate: interest_bearing_config.current_rate.into(),
+        });
+    }
+
     Some(TokenBalanceData {
         mint: token_account.base.mint.to_string(),
         owner: token_account.base.owner.to_string(),
-        ui_token_amount: token_amount_to_ui_amount(token_account.base.amount, decimals),
+        ui_token_amount,
         program_id: account.owner().to_string(),
+        extensions,
     })
 }
 
@@ -125,7 +178,8 @@ mod test {
         spl_token_2022::{
             extension::{
                 immutable_owner::ImmutableOwner, memo_transfer::MemoTransfer,
-                mint_close_authority::MintCloseAuthority, ExtensionType, StateWithExtensionsMut,
+                mint_close_authority::MintCloseAuthority, transfer_fee::TransferFee, ExtensionType,
+                StateWithExtensionsMut,
             },
             solana_program::{program_option::COption, program_pack::Pack},
         },
@@ -231,27 +285,23 @@ mod test {
         genesis_config.accounts = accounts;
 
         let bank = Bank::new_for_tests(&genesis_config);
-        let mut mint_decimals = HashMap::new();
+        let mut mint_infos = HashMap::new();
 
         // Account is not owned by spl_token (nor does it have TokenAccount state)
         assert_eq!(
-            collect_token_balance_from_account(&bank, &account_pubkey, &mut mint_decimals),
+            collect_token_balance_from_account(&bank, &account_pubkey, &mut mint_infos),
             None
         );
 
         // Mint does not have TokenAccount state
         assert_eq!(
-            collect_token_balance_from_account(&bank, &mint_pubkey, &mut mint_decimals),
+            collect_token_balance_from_account(&bank, &mint_pubkey, &mut mint_infos),
             None
         );
 
         // TokenAccount owned by spl_token::id() works
         assert_eq!(
-            collect_token_balance_from_account(
-                &bank,
-                &spl_token_account_pubkey,
-                &mut mint_decimals
-            ),
+            collect_token_balance_from_account(&bank, &spl_token_account_pubkey, &mut mint_infos),
             Some(TokenBalanceData {
                 mint: mint_pubkey.to_string(),
                 owner: token_owner.to_string(),
@@ -262,22 +312,19 @@ mod test {
                     ui_amount_string: "0.42".to_string(),
                 },
                 program_id: spl_token::id().to_string(),
+                extensions: vec![],
             })
         );
 
         // TokenAccount is not owned by known spl-token program_id
         assert_eq!(
-            collect_token_balance_from_account(&bank, &other_account_pubkey, &mut mint_decimals),
+            collect_token_balance_from_account(&bank, &other_account_pubkey, &mut mint_infos),
             None
         );
 
         // TokenAccount's mint is not owned by known spl-token program_id
         assert_eq!(
-            collect_token_balance_from_account(
-                &bank,
-                &other_mint_account_pubkey,
-                &mut mint_decimals
-            ),
+            collect_token_balance_from_account(&bank, &other_mint_account_pubkey, &mut mint_infos),
             None
         );
     }
@@ -425,27 +472,23 @@ mod test {
         genesis_config.accounts = accounts;
 
         let bank = Bank::new_for_tests(&genesis_config);
-        let mut mint_decimals = HashMap::new();
+        let mut mint_infos = HashMap::new();
 
         // Account is not owned by spl_token (nor does it have TokenAccount state)
         assert_eq!(
-            collect_token_balance_from_account(&bank, &account_pubkey, &mut mint_decimals),
+            collect_token_balance_from_account(&bank, &account_pubkey, &mut mint_infos),
             None
         );
 
         // Mint does not have TokenAccount state
         assert_eq!(
-            collect_token_balance_from_account(&bank, &mint_pubkey, &mut mint_decimals),
+            collect_token_balance_from_account(&bank, &mint_pubkey, &mut mint_infos),
             None
         );
 
         // TokenAccount owned by spl_token_2022::id() works
         assert_eq!(
-            collect_token_balance_from_account(
-                &bank,
-                &spl_token_account_pubkey,
-                &mut mint_decimals
-            ),
+            collect_token_balance_from_account(&bank, &spl_token_account_pubkey, &mut mint_infos),
             Some(TokenBalanceData {
                 mint: mint_pubkey.to_string(),
                 owner: token_owner.to_string(),
@@ -456,23 +499,127 @@ mod test {
                     ui_amount_string: "0.42".to_string(),
                 },
                 program_id: spl_token_2022::id().to_string(),
+                extensions: vec![],
             })
         );
 
         // TokenAccount is not owned by known spl-token program_id
         assert_eq!(
-            collect_token_balance_from_account(&bank, &other_account_pubkey, &mut mint_decimals),
+            collect_token_balance_from_account(&bank, &other_account_pubkey, &mut mint_infos),
             None
         );
 
         // TokenAccount's mint is not owned by known spl-token program_id
         assert_eq!(
-            collect_token_balance_from_account(
-                &bank,
-                &other_mint_account_pubkey,
-                &mut mint_decimals
-            ),
+            collect_token_balance_from_account(&bank, &other_mint_account_pubkey, &mut mint_infos),
             None
         );
     }
+
+    #[test]
+    fn test_collect_token_balance_with_balance_extensions() {
+        const SECONDS_PER_YEAR: i64 = 31_556_736;
+        let (mut genesis_config, _mint_keypair) = create_genesis_config(500);
+
+        let mint_size = ExtensionType::try_calculate_account_len::<Mint>(&[
+            ExtensionType::TransferFeeConfig,
+            ExtensionType::InterestBearingConfig,
+        ])
+        .unwrap();
+        let mut mint_data = vec![0; mint_size];
+        let mut mint_state =
+            StateWithExtensionsMut::<Mint>::unpack_uninitialized(&mut mint_data).unwrap();
+        mint_state.base = Mint {
+            mint_authority: COption::None,
+            supply: 4242,
+            decimals: 2,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        };
+        mint_state.pack_base();
+        mint_state.init_account_type().unwrap();
+        let transfer_fee = TransferFee {
+            epoch: 0.into(),
+            maximum_fee: 1_000.into(),
+            transfer_fee_basis_points: 50.into(),
+        };
+        let transfer_fee_config = mint_state
+            .init_extension::<TransferFeeConfig>(true)
+            .unwrap();
+        transfer_fee_config.older_transfer_fee = transfer_fee;
+        transfer_fee_config.newer_transfer_fee = transfer_fee;
+        // a year of interest accrued by genesis
+        let interest_start = genesis_config.creation_time - SECONDS_PER_YEAR;
+        let interest_bearing_config = mint_state
+            .init_extension::<InterestBearingConfig>(true)
+            .unwrap();
+        interest_bearing_config.initialization_timestamp = interest_start.into();
+        interest_bearing_config.last_update_timestamp = interest_start.into();
+        interest_bearing_config.pre_update_average_rate = 500.into();
+        interest_bearing_config.current_rate = 500.into();
+
+        let mint_pubkey = Pubkey::new_unique();
+        let token_owner = Pubkey::new_unique();
+        let account_size = ExtensionType::try_calculate_account_len::<TokenAccount>(&[
+            ExtensionType::TransferFeeAmount,
+        ])
+        .unwrap();
+        let mut account_data = vec![0; account_size];
+        let mut account_state =
+            StateWithExtensionsMut::<TokenAccount>::unpack_uninitialized(&mut account_data)
+                .unwrap();
+        account_state.base = TokenAccount {
+            mint: mint_pubkey,
+            owner: token_owner,
+            amount: 100,
+            delegate: COption::None,
+            state: spl_token_2022::state::AccountState::Initialized,
+            is_native: COption::None,
+            delegated_amount: 0,
+            close_authority: COption::None,
+        };
+        account_state.pack_base();
+        account_state.init_account_type().unwrap();
+        let transfer_fee_amount = account_state
+            .init_extension::<TransferFeeAmount>(true)
+            .unwrap();
+        transfer_fee_amount.withheld_amount = 7.into();
+
+        let token_account_pubkey = Pubkey::new_unique();
+        for (pubkey, data) in [
+            (mint_pubkey, mint_data),
+            (token_account_pubkey, account_data),
+        ] {
+            genesis_config.accounts.insert(
+                pubkey,
+                Account {
+                    lamports: 100,
+                    data,
+                    owner: spl_token_2022::id(),
+                    executable: false,
+                    rent_epoch: 0,
+                },
+            );
+        }
+
+        let bank = Bank::new_for_tests(&genesis_config);
+        let mut mint_infos = HashMap::new();
+        let token_balance =
+            collect_token_balance_from_account(&bank, &token_account_pubkey, &mut mint_infos)
+                .unwrap();
+        assert_eq!(token_balance.ui_token_amount.amount, "100");
+        let ui_amount = token_balance.ui_token_amount.ui_amount.unwrap();
+        assert!(ui_amount > 1.05 && ui_amount < 1.06);
+        assert_eq!(
+            token_balance.extensions,
+            vec![
+                TokenBalanceExtension::TransferFeeAmount { withheld_amount: 7 },
+                TokenBalanceExtension::TransferFeeConfig {
+                    transfer_fee_basis_points: 50,
+                    maximum_fee: 1_000,
+                },
+                TokenBalanceExtension::InterestBearingConfig { rate: 500 },
+            ]
+        );
+    }
 }
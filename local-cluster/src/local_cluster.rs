@@ -19,6 +19,7 @@ use {
     },
     solana_ledger::{create_new_tmp_ledger, shred::Shred},
     solana_runtime::{
+        bank_forks::SetHashesPerTickError,
         genesis_utils::{
             create_genesis_config_with_vote_accounts_and_cluster_type, GenesisConfigInfo,
             ValidatorVoteKeypairs,
@@ -28,7 +29,7 @@ use {
     solana_sdk::{
         account::{Account, AccountSharedData},
         client::SyncClient,
-        clock::{DEFAULT_DEV_SLOTS_PER_EPOCH, DEFAULT_TICKS_PER_SLOT},
+        clock::{Epoch, DEFAULT_DEV_SLOTS_PER_EPOCH, DEFAULT_TICKS_PER_SLOT},
         commitment_config::CommitmentConfig,
         epoch_schedule::EpochSchedule,
         feature_set,
@@ -399,6 +400,28 @@ impl LocalCluster {
         }
     }
 
+    /// Change the hashes per tick of all the running validators from
+    /// `activation_epoch` on, which should be far enough ahead for all of
+    /// them to still have their root in an earlier epoch.
+    pub fn set_hashes_per_tick(
+        &self,
+        hashes_per_tick: u64,
+        activation_epoch: Epoch,
+    ) -> std::result::Result<(), SetHashesPerTickError> {
+        for validator in self
+            .validators
+            .values()
+            .filter_map(|node| node.validator.as_ref())
+        {
+            validator
+                .bank_forks
+                .write()
+                .unwrap()
+                .set_hashes_per_tick(hashes_per_tick, activation_epoch)?;
+        }
+        Ok(())
+    }
+
     /// Set up validator without voting or staking accounts
     pub fn add_validator_listener(
         &mut self,
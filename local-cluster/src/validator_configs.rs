@@ -19,8 +19,10 @@ pub fn safe_clone_config(config: &ValidatorConfig) -> ValidatorConfig {
         pubsub_config: config.pubsub_config.clone(),
         snapshot_config: config.snapshot_config.clone(),
         max_ledger_shreds: config.max_ledger_shreds,
+        max_ledger_epochs: config.max_ledger_epochs,
         broadcast_stage_type: config.broadcast_stage_type.clone(),
         turbine_disabled: config.turbine_disabled.clone(),
+        turbine_tree_override: config.turbine_tree_override.clone(),
         enforce_ulimit_nofile: config.enforce_ulimit_nofile,
         fixed_leader_schedule: config.fixed_leader_schedule.clone(),
         wait_for_supermajority: config.wait_for_supermajority,
@@ -38,6 +40,8 @@ pub fn safe_clone_config(config: &ValidatorConfig) -> ValidatorConfig {
         debug_keys: config.debug_keys.clone(),
         contact_debug_interval: config.contact_debug_interval,
         contact_save_interval: config.contact_save_interval,
+        gossip_push_config: config.gossip_push_config,
+        node_metadata: config.node_metadata.clone(),
         send_transaction_service_config: config.send_transaction_service_config.clone(),
         no_poh_speed_test: config.no_poh_speed_test,
         no_os_memory_stats_reporting: config.no_os_memory_stats_reporting,
@@ -52,6 +56,8 @@ pub fn safe_clone_config(config: &ValidatorConfig) -> ValidatorConfig {
         accounts_db_force_initial_clean: config.accounts_db_force_initial_clean,
         tpu_coalesce: config.tpu_coalesce,
         staked_nodes_overrides: config.staked_nodes_overrides.clone(),
+        forwarding_policy: config.forwarding_policy.clone(),
+        block_production_limits: config.block_production_limits.clone(),
         validator_exit: Arc::new(RwLock::new(Exit::default())),
         poh_hashes_per_batch: config.poh_hashes_per_batch,
         process_ledger_before_services: config.process_ledger_before_services,
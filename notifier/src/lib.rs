@@ -23,14 +23,29 @@
 /// ```bash
 /// export TWILIO_CONFIG='ACCOUNT=<account>,TOKEN=<securityToken>,TO=<receivingNumber>,FROM=<sendingNumber>'
 /// ```
+///
+/// Webhooks with templated JSON bodies and commands are supported as well, see [`transport`].
+///
+/// Each channel only receives notifications of at least its minimum severity, which defaults to
+/// `info` and is set with the `_MIN_SEVERITY` variable of the channel:
+/// ```bash
+/// export SLACK_MIN_SEVERITY=warning
+/// export PAGERDUTY_MIN_SEVERITY=critical
+/// ```
+/// The variables of the other channels are `DISCORD_MIN_SEVERITY`, `TELEGRAM_MIN_SEVERITY`,
+/// `TWILIO_MIN_SEVERITY`, `LOG_NOTIFIER_MIN_SEVERITY`, `WEBHOOK_MIN_SEVERITY` and
+/// `NOTIFICATION_COMMAND_MIN_SEVERITY`.
 use log::*;
 use {
     reqwest::{blocking::Client, StatusCode},
     serde_json::json,
     solana_sdk::hash::Hash,
-    std::{env, str::FromStr, thread::sleep, time::Duration},
+    std::{env, fmt, str::FromStr, thread::sleep, time::Duration},
+    transport::{CommandTransport, NotificationTransport, WebhookTransport},
 };
 
+pub mod transport;
+
 struct TelegramWebHook {
     bot_token: String,
     chat_id: String,
@@ -91,6 +106,7 @@ enum NotificationChannel {
     Telegram(TelegramWebHook),
     Twilio(TwilioWebHook),
     Log(Level),
+    Transport(Box<dyn NotificationTransport>),
 }
 
 #[derive(Clone)]
@@ -99,9 +115,59 @@ pub enum NotificationType {
     Resolve { incident: Hash },
 }
 
+/// Severity of a notification, which routes it to the channels accepting it
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Info,
+    Warning,
+    #[default]
+    Critical,
+}
+
+impl Severity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Info => "info",
+            Self::Warning => "warning",
+            Self::Critical => "critical",
+        }
+    }
+}
+
+impl FromStr for Severity {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "info" => Ok(Self::Info),
+            "warning" => Ok(Self::Warning),
+            "critical" => Ok(Self::Critical),
+            _ => Err(format!(
+                "invalid severity \"{s}\", expected info, warning or critical"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+fn get_min_severity(var: String) -> Severity {
+    match env::var(&var) {
+        Ok(min_severity) => Severity::from_str(&min_severity).unwrap_or_else(|err| {
+            warn!("{}: {}, defaulting to info", var, err);
+            Severity::Info
+        }),
+        Err(_) => Severity::Info,
+    }
+}
+
 pub struct Notifier {
     client: Client,
-    notifiers: Vec<NotificationChannel>,
+    notifiers: Vec<(NotificationChannel, Severity)>,
 }
 
 impl Default for Notifier {
@@ -115,34 +181,38 @@ impl Notifier {
         info!("Initializing {}Notifier", env_prefix);
 
         let mut notifiers = vec![];
+        let mut add = |channel, name: &str| {
+            let min_severity = get_min_severity(format!("{env_prefix}{name}_MIN_SEVERITY"));
+            notifiers.push((channel, min_severity));
+        };
 
         if let Ok(webhook) = env::var(format!("{env_prefix}DISCORD_WEBHOOK")) {
-            notifiers.push(NotificationChannel::Discord(webhook));
+            add(NotificationChannel::Discord(webhook), "DISCORD");
         }
         if let Ok(webhook) = env::var(format!("{env_prefix}SLACK_WEBHOOK")) {
-            notifiers.push(NotificationChannel::Slack(webhook));
+            add(NotificationChannel::Slack(webhook), "SLACK");
         }
         if let Ok(routing_key) = env::var(format!("{env_prefix}PAGERDUTY_INTEGRATION_KEY")) {
-            notifiers.push(NotificationChannel::PagerDuty(routing_key));
+            add(NotificationChannel::PagerDuty(routing_key), "PAGERDUTY");
         }
 
         if let (Ok(bot_token), Ok(chat_id)) = (
             env::var(format!("{env_prefix}TELEGRAM_BOT_TOKEN")),
             env::var(format!("{env_prefix}TELEGRAM_CHAT_ID")),
         ) {
-            notifiers.push(NotificationChannel::Telegram(TelegramWebHook {
-                bot_token,
-                chat_id,
-            }));
+            add(
+                NotificationChannel::Telegram(TelegramWebHook { bot_token, chat_id }),
+                "TELEGRAM",
+            );
         }
 
         if let Ok(Some(webhook)) = get_twilio_config() {
-            notifiers.push(NotificationChannel::Twilio(webhook));
+            add(NotificationChannel::Twilio(webhook), "TWILIO");
         }
 
         if let Ok(log_level) = env::var(format!("{env_prefix}LOG_NOTIFIER_LEVEL")) {
             match Level::from_str(&log_level) {
-                Ok(level) => notifiers.push(NotificationChannel::Log(level)),
+                Ok(level) => add(NotificationChannel::Log(level), "LOG_NOTIFIER"),
                 Err(e) => warn!(
                     "could not parse specified log notifier level string ({}): {}",
                     log_level, e
@@ -150,6 +220,25 @@ impl Notifier {
             }
         }
 
+        if let Ok(url) = env::var(format!("{env_prefix}WEBHOOK_URL")) {
+            let template = env::var(format!("{env_prefix}WEBHOOK_TEMPLATE"))
+                .unwrap_or_else(|_| transport::DEFAULT_WEBHOOK_TEMPLATE.to_string());
+            match WebhookTransport::new(url, &template) {
+                Ok(webhook) => add(NotificationChannel::Transport(Box::new(webhook)), "WEBHOOK"),
+                Err(err) => warn!("could not set up webhook notifications: {}", err),
+            }
+        }
+
+        if let Ok(command) = env::var(format!("{env_prefix}NOTIFICATION_COMMAND")) {
+            match CommandTransport::new(&command) {
+                Ok(command) => add(
+                    NotificationChannel::Transport(Box::new(command)),
+                    "NOTIFICATION_COMMAND",
+                ),
+                Err(err) => warn!("could not set up command notifications: {}", err),
+            }
+        }
+
         info!("{} notifiers", notifiers.len());
 
         Notifier {
@@ -162,8 +251,32 @@ impl Notifier {
         self.notifiers.is_empty()
     }
 
+    /// Also sends the notifications of at least `min_severity` with `transport`
+    pub fn add_transport(
+        &mut self,
+        transport: Box<dyn NotificationTransport>,
+        min_severity: Severity,
+    ) {
+        info!("Adding {} notifier", transport.name());
+        self.notifiers
+            .push((NotificationChannel::Transport(transport), min_severity));
+    }
+
     pub fn send(&self, msg: &str, notification_type: &NotificationType) {
-        for notifier in &self.notifiers {
+        self.send_with_severity(msg, notification_type, Severity::default())
+    }
+
+    /// Sends `msg` to the channels accepting notifications of `severity`
+    pub fn send_with_severity(
+        &self,
+        msg: &str,
+        notification_type: &NotificationType,
+        severity: Severity,
+    ) {
+        for (notifier, min_severity) in &self.notifiers {
+            if severity < *min_severity {
+                continue;
+            }
             match notifier {
                 NotificationChannel::Discord(webhook) => {
                     for line in msg.split('\n') {
@@ -208,7 +321,7 @@ impl Notifier {
                         NotificationType::Resolve { ref incident } => incident.clone().to_string(),
                     };
 
-                    let data = json!({"payload":{"summary":msg,"source":"solana-watchtower","severity":"critical"},"routing_key":routing_key,"event_action":event_action,"dedup_key":dedup_key});
+                    let data = json!({"payload":{"summary":msg,"source":"solana-watchtower","severity":severity.as_str()},"routing_key":routing_key,"event_action":event_action,"dedup_key":dedup_key});
                     let url = "https://events.pagerduty.com/v2/enqueue";
 
                     if let Err(err) = self.client.post(url).json(&data).send() {
@@ -242,7 +355,27 @@ impl Notifier {
                 NotificationChannel::Log(level) => {
                     log!(*level, "{}", msg)
                 }
+                NotificationChannel::Transport(transport) => {
+                    transport.send(msg, notification_type, severity)
+                }
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_severity() {
+        assert_eq!(Severity::from_str("Warning"), Ok(Severity::Warning));
+        assert_eq!(
+            Severity::from_str(Severity::Critical.as_str()),
+            Ok(Severity::Critical)
+        );
+        assert!(Severity::from_str("page").is_err());
+        assert!(Severity::Info < Severity::Warning);
+        assert!(Severity::Warning < Severity::Critical);
+    }
+}
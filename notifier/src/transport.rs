@@ -0,0 +1,212 @@
+//! Notification transports other than the built-in channels
+//!
+//! A [`NotificationTransport`] delivers notifications to a paging system. Besides the ones
+//! added with [`crate::Notifier::add_transport`], the `Notifier` sets up these transports from
+//! environment variables:
+//!
+//! A webhook, receiving a `POST` request with a JSON body built from a template:
+//! ```bash
+//! export WEBHOOK_URL=...
+//! export WEBHOOK_TEMPLATE='{"summary":"{{message}}","severity":"{{severity}}"}'
+//! ```
+//!
+//! A command, run with the notification in its environment variables
+//! `SOLANA_NOTIFICATION_MESSAGE`, `SOLANA_NOTIFICATION_EVENT`, `SOLANA_NOTIFICATION_INCIDENT`
+//! and `SOLANA_NOTIFICATION_SEVERITY`. It blocks further notifications until it exits:
+//! ```bash
+//! export NOTIFICATION_COMMAND='/usr/local/bin/page-oncall --team validators'
+//! ```
+use {
+    crate::{NotificationType, Severity},
+    log::*,
+    reqwest::blocking::Client,
+    serde_json::Value,
+    std::process::Command,
+};
+
+/// Template of the body of webhook requests when `WEBHOOK_TEMPLATE` is not defined
+pub const DEFAULT_WEBHOOK_TEMPLATE: &str = r#"{"message":"{{message}}","event":"{{event}}","incident":"{{incident}}","severity":"{{severity}}"}"#;
+
+/// Delivers notifications to a paging system
+pub trait NotificationTransport: Send + Sync {
+    /// Name of the transport, for logs
+    fn name(&self) -> &str;
+
+    /// Delivers the notification `msg`, logging failures
+    fn send(&self, msg: &str, notification_type: &NotificationType, severity: Severity);
+}
+
+fn event(notification_type: &NotificationType) -> &'static str {
+    match notification_type {
+        NotificationType::Trigger { .. } => "trigger",
+        NotificationType::Resolve { .. } => "resolve",
+    }
+}
+
+fn incident(notification_type: &NotificationType) -> String {
+    match notification_type {
+        NotificationType::Trigger { incident } | NotificationType::Resolve { incident } => {
+            incident.to_string()
+        }
+    }
+}
+
+/// Sends notifications as JSON `POST` requests to a URL
+pub struct WebhookTransport {
+    client: Client,
+    url: String,
+    template: Value,
+}
+
+impl WebhookTransport {
+    /// The request bodies are `template` with the placeholders `{{message}}`, `{{event}}`,
+    /// `{{incident}}` and `{{severity}}` in its strings replaced by the notification's
+    pub fn new(url: String, template: &str) -> Result<Self, String> {
+        let template = serde_json::from_str(template)
+            .map_err(|err| format!("webhook template is not valid JSON: {err}"))?;
+        Ok(Self {
+            client: Client::new(),
+            url,
+            template,
+        })
+    }
+
+    fn render(&self, msg: &str, notification_type: &NotificationType, severity: Severity) -> Value {
+        let incident = incident(notification_type);
+        let placeholders = [
+            ("{{message}}", msg),
+            ("{{event}}", event(notification_type)),
+            ("{{incident}}", incident.as_str()),
+            ("{{severity}}", severity.as_str()),
+        ];
+        render_template(&self.template, &placeholders)
+    }
+}
+
+fn render_template(template: &Value, placeholders: &[(&str, &str)]) -> Value {
+    match template {
+        Value::String(s) => Value::String(
+            placeholders
+                .iter()
+                .fold(s.clone(), |s, (placeholder, value)| {
+                    s.replace(placeholder, value)
+                }),
+        ),
+        Value::Array(values) => Value::Array(
+            values
+                .iter()
+                .map(|value| render_template(value, placeholders))
+                .collect(),
+        ),
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(key, value)| (key.clone(), render_template(value, placeholders)))
+                .collect(),
+        ),
+        value => value.clone(),
+    }
+}
+
+impl NotificationTransport for WebhookTransport {
+    fn name(&self) -> &str {
+        "webhook"
+    }
+
+    fn send(&self, msg: &str, notification_type: &NotificationType, severity: Severity) {
+        let data = self.render(msg, notification_type, severity);
+        match self.client.post(&self.url).json(&data).send() {
+            Ok(response) if !response.status().is_success() => {
+                warn!("Webhook responded with status {}", response.status());
+            }
+            Ok(_) => {}
+            Err(err) => warn!("Failed to send webhook notification: {:?}", err),
+        }
+    }
+}
+
+/// Runs a command for each notification
+pub struct CommandTransport {
+    program: String,
+    args: Vec<String>,
+}
+
+impl CommandTransport {
+    /// `command` is the program to run followed by its arguments, separated by whitespace
+    pub fn new(command: &str) -> Result<Self, String> {
+        let mut words = command.split_whitespace().map(str::to_string);
+        let program = words
+            .next()
+            .ok_or_else(|| "notification command is empty".to_string())?;
+        Ok(Self {
+            program,
+            args: words.collect(),
+        })
+    }
+}
+
+impl NotificationTransport for CommandTransport {
+    fn name(&self) -> &str {
+        &self.program
+    }
+
+    fn send(&self, msg: &str, notification_type: &NotificationType, severity: Severity) {
+        let status = Command::new(&self.program)
+            .args(&self.args)
+            .env("SOLANA_NOTIFICATION_MESSAGE", msg)
+            .env("SOLANA_NOTIFICATION_EVENT", event(notification_type))
+            .env("SOLANA_NOTIFICATION_INCIDENT", incident(notification_type))
+            .env("SOLANA_NOTIFICATION_SEVERITY", severity.as_str())
+            .status();
+        match status {
+            Ok(status) if !status.success() => {
+                warn!("Notification command {} failed: {}", self.program, status);
+            }
+            Ok(_) => {}
+            Err(err) => warn!(
+                "Failed to run notification command {}: {:?}",
+                self.program, err
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, serde_json::json, solana_sdk::hash::Hash};
+
+    #[test]
+    fn test_webhook_template() {
+        let transport = WebhookTransport::new(
+            "http://localhost".to_string(),
+            r#"{"summary":"watchtower: {{message}}","details":[{"severity":"{{severity}}","event":"{{event}}"}],"key":"{{incident}}","count":1}"#,
+        )
+        .unwrap();
+        let incident = Hash::new_unique();
+        assert_eq!(
+            transport.render(
+                "balance \"low\"",
+                &NotificationType::Trigger { incident },
+                Severity::Warning
+            ),
+            json!({
+                "summary": "watchtower: balance \"low\"",
+                "details": [{"severity": "warning", "event": "trigger"}],
+                "key": incident.to_string(),
+                "count": 1,
+            })
+        );
+
+        assert!(WebhookTransport::new("http://localhost".to_string(), "{\"summary\":").is_err());
+        assert!(
+            WebhookTransport::new("http://localhost".to_string(), DEFAULT_WEBHOOK_TEMPLATE).is_ok()
+        );
+    }
+
+    #[test]
+    fn test_command_transport() {
+        let transport = CommandTransport::new("  page-oncall --team  validators ").unwrap();
+        assert_eq!(transport.program, "page-oncall");
+        assert_eq!(transport.args, vec!["--team", "validators"]);
+        assert!(CommandTransport::new(" ").is_err());
+    }
+}
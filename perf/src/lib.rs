@@ -8,6 +8,7 @@ pub mod perf_libs;
 pub mod recycler;
 pub mod recycler_cache;
 pub mod sigverify;
+pub mod sigverify_backend;
 pub mod test_tx;
 pub mod thread;
 
@@ -1,7 +1,7 @@
 //! The `sigverify` module provides digital signature verification functions.
 //! By default, signatures are verified in parallel using all available CPU
 //! cores.  When perf-libs are available signature verification is offloaded
-//! to the GPU.
+//! to the GPU, and other backends can be registered, see `sigverify_backend`.
 //!
 use {
     crate::{
@@ -9,6 +9,7 @@ use {
         packet::{Packet, PacketBatch, PacketFlags, PACKET_DATA_SIZE},
         perf_libs,
         recycler::Recycler,
+        sigverify_backend,
     },
     rayon::{prelude::*, ThreadPool},
     solana_metrics::inc_new_counter_debug,
@@ -598,7 +599,7 @@ pub fn mark_disabled(batches: &mut [PacketBatch], r: &[Vec<u8>]) {
     }
 }
 
-pub fn ed25519_verify(
+pub fn ed25519_verify_gpu(
     batches: &mut [PacketBatch],
     recycler: &Recycler<TxOffset>,
     recycler_out: &Recycler<PinnedVec<u8>>,
@@ -608,22 +609,6 @@ pub fn ed25519_verify(
     let Some(api) = perf_libs::api() else {
         return ed25519_verify_cpu(batches, reject_non_vote, valid_packet_count);
     };
-    let total_packet_count = count_packets_in_batches(batches);
-    // micro-benchmarks show GPU time for smallest batch around 15-20ms
-    // and CPU speed for 64-128 sigverifies around 10-20ms. 64 is a nice
-    // power-of-two number around that accounting for the fact that the CPU
-    // may be busy doing other things while being a real validator
-    // TODO: dynamically adjust this crossover
-    let maybe_valid_percentage = 100usize
-        .wrapping_mul(valid_packet_count)
-        .checked_div(total_packet_count);
-    let Some(valid_percentage) = maybe_valid_percentage else {
-        return;
-    };
-    if valid_percentage < 90 || valid_packet_count < 64 {
-        ed25519_verify_cpu(batches, reject_non_vote, valid_packet_count);
-        return;
-    }
 
     let (signature_offsets, pubkey_offsets, msg_start_offsets, msg_sizes, sig_lens) =
         generate_offsets(batches, recycler, reject_non_vote);
@@ -675,6 +660,24 @@ pub fn ed25519_verify(
     inc_new_counter_debug!("ed25519_verify_gpu", valid_packet_count);
 }
 
+/// Verifies the signatures of the packets which are not discarded yet with
+/// the backend chosen for `valid_packet_count`, see `sigverify_backend`
+pub fn ed25519_verify(
+    batches: &mut [PacketBatch],
+    recycler: &Recycler<TxOffset>,
+    recycler_out: &Recycler<PinnedVec<u8>>,
+    reject_non_vote: bool,
+    valid_packet_count: usize,
+) {
+    sigverify_backend::verify_packets(
+        batches,
+        recycler,
+        recycler_out,
+        reject_non_vote,
+        valid_packet_count,
+    )
+}
+
 #[cfg(test)]
 #[allow(clippy::arithmetic_side_effects)]
 mod tests {
@@ -0,0 +1,299 @@
+//! Backends verifying the signatures of packets.
+//!
+//! Each call to `sigverify::ed25519_verify` is dispatched to the first backend
+//! which accepts its packet counts: the backends registered with
+//! [`register_sigverify_backend`], the most recently registered first, so
+//! that operators can plug in specialized hardware, then the GPU backend, and
+//! finally the CPU backend, which accepts any packets.
+
+use {
+    crate::{
+        cuda_runtime::PinnedVec,
+        packet::PacketBatch,
+        perf_libs,
+        recycler::Recycler,
+        sigverify::{self, TxOffset},
+    },
+    solana_sdk::timing::AtomicInterval,
+    std::{
+        sync::{
+            atomic::{AtomicU64, Ordering},
+            Arc, RwLock,
+        },
+        time::Instant,
+    },
+};
+
+// micro-benchmarks show GPU time for smallest batch around 15-20ms
+// and CPU speed for 64-128 sigverifies around 10-20ms. 64 is a nice
+// power-of-two number around that accounting for the fact that the CPU
+// may be busy doing other things while being a real validator
+// TODO: dynamically adjust this crossover
+const GPU_MIN_VALID_PACKET_COUNT: usize = 64;
+const GPU_MIN_VALID_PERCENTAGE: usize = 90;
+
+/// Verifies the signatures of packets
+pub trait SigVerifyBackend: Send + Sync {
+    /// Name of the backend, for logs and metrics
+    fn name(&self) -> &'static str;
+
+    /// Whether to verify batches with `total_packet_count` packets, of which
+    /// `valid_packet_count` are not discarded yet, with this backend
+    fn accepts(&self, valid_packet_count: usize, total_packet_count: usize) -> bool;
+
+    /// Verifies the signatures of the packets which are not discarded yet,
+    /// discarding the packets which fail verification
+    fn verify(
+        &self,
+        batches: &mut [PacketBatch],
+        recycler: &Recycler<TxOffset>,
+        recycler_out: &Recycler<PinnedVec<u8>>,
+        reject_non_vote: bool,
+        valid_packet_count: usize,
+    );
+}
+
+/// Verifies signatures on the sigverify thread pool
+#[derive(Debug, Default)]
+pub struct CpuSigVerifyBackend;
+
+impl SigVerifyBackend for CpuSigVerifyBackend {
+    fn name(&self) -> &'static str {
+        "cpu"
+    }
+
+    fn accepts(&self, _valid_packet_count: usize, _total_packet_count: usize) -> bool {
+        true
+    }
+
+    fn verify(
+        &self,
+        batches: &mut [PacketBatch],
+        _recycler: &Recycler<TxOffset>,
+        _recycler_out: &Recycler<PinnedVec<u8>>,
+        reject_non_vote: bool,
+        valid_packet_count: usize,
+    ) {
+        sigverify::ed25519_verify_cpu(batches, reject_non_vote, valid_packet_count)
+    }
+}
+
+/// Verifies signatures on the GPU with the perf libs, for batches large and
+/// valid enough to make up for the GPU latency
+#[derive(Debug, Default)]
+pub struct GpuSigVerifyBackend;
+
+impl SigVerifyBackend for GpuSigVerifyBackend {
+    fn name(&self) -> &'static str {
+        "gpu"
+    }
+
+    fn accepts(&self, valid_packet_count: usize, total_packet_count: usize) -> bool {
+        let valid_percentage = 100usize
+            .wrapping_mul(valid_packet_count)
+            .checked_div(total_packet_count)
+            .unwrap_or_default();
+        perf_libs::api().is_some()
+            && valid_packet_count >= GPU_MIN_VALID_PACKET_COUNT
+            && valid_percentage >= GPU_MIN_VALID_PERCENTAGE
+    }
+
+    fn verify(
+        &self,
+        batches: &mut [PacketBatch],
+        recycler: &Recycler<TxOffset>,
+        recycler_out: &Recycler<PinnedVec<u8>>,
+        reject_non_vote: bool,
+        valid_packet_count: usize,
+    ) {
+        sigverify::ed25519_verify_gpu(
+            batches,
+            recycler,
+            recycler_out,
+            reject_non_vote,
+            valid_packet_count,
+        )
+    }
+}
+
+#[derive(Default)]
+struct SigVerifyBackendStats {
+    last_report: AtomicInterval,
+    num_dispatches: AtomicU64,
+    num_packets: AtomicU64,
+    verify_us: AtomicU64,
+}
+
+impl SigVerifyBackendStats {
+    fn maybe_report(&self, name: &'static str) {
+        const REPORT_INTERVAL_MS: u64 = 2000;
+        if !self.last_report.should_update(REPORT_INTERVAL_MS) {
+            return;
+        }
+        let num_packets = self.num_packets.swap(0, Ordering::Relaxed);
+        let verify_us = self.verify_us.swap(0, Ordering::Relaxed);
+        let packets_per_second = num_packets
+            .saturating_mul(1_000_000)
+            .checked_div(verify_us)
+            .unwrap_or_default();
+        datapoint_info!(
+            "sigverify-backend",
+            "backend" => name,
+            (
+                "num_dispatches",
+                self.num_dispatches.swap(0, Ordering::Relaxed),
+                i64
+            ),
+            ("num_packets", num_packets, i64),
+            ("verify_us", verify_us, i64),
+            ("packets_per_second", packets_per_second, i64),
+        );
+    }
+}
+
+#[derive(Clone)]
+struct Backend {
+    backend: Arc<dyn SigVerifyBackend>,
+    stats: Arc<SigVerifyBackendStats>,
+}
+
+impl Backend {
+    fn new(backend: Arc<dyn SigVerifyBackend>) -> Self {
+        Self {
+            backend,
+            stats: Arc::default(),
+        }
+    }
+}
+
+lazy_static! {
+    static ref REGISTERED_BACKENDS: RwLock<Vec<Backend>> = RwLock::default();
+    static ref GPU_BACKEND: Backend = Backend::new(Arc::new(GpuSigVerifyBackend));
+    static ref CPU_BACKEND: Backend = Backend::new(Arc::new(CpuSigVerifyBackend));
+}
+
+/// Verify the packets `backend` accepts with it, before trying the backends
+/// registered earlier and the default ones
+pub fn register_sigverify_backend(backend: Arc<dyn SigVerifyBackend>) {
+    info!("registering sigverify backend: {}", backend.name());
+    REGISTERED_BACKENDS
+        .write()
+        .unwrap()
+        .insert(0, Backend::new(backend));
+}
+
+/// The backend which verifies batches with `total_packet_count` packets, of
+/// which `valid_packet_count` are not discarded yet
+fn backend_for(valid_packet_count: usize, total_packet_count: usize) -> Backend {
+    let registered_backends = REGISTERED_BACKENDS.read().unwrap();
+    registered_backends
+        .iter()
+        .chain([&*GPU_BACKEND])
+        .find(|backend| {
+            backend
+                .backend
+                .accepts(valid_packet_count, total_packet_count)
+        })
+        .unwrap_or(&CPU_BACKEND)
+        .clone()
+}
+
+pub(crate) fn verify_packets(
+    batches: &mut [PacketBatch],
+    recycler: &Recycler<TxOffset>,
+    recycler_out: &Recycler<PinnedVec<u8>>,
+    reject_non_vote: bool,
+    valid_packet_count: usize,
+) {
+    let total_packet_count = sigverify::count_packets_in_batches(batches);
+    if total_packet_count == 0 {
+        return;
+    }
+    let Backend { backend, stats } = backend_for(valid_packet_count, total_packet_count);
+    let verify_start = Instant::now();
+    backend.verify(
+        batches,
+        recycler,
+        recycler_out,
+        reject_non_vote,
+        valid_packet_count,
+    );
+    let verify_us = verify_start.elapsed().as_micros() as u64;
+    stats.num_dispatches.fetch_add(1, Ordering::Relaxed);
+    stats
+        .num_packets
+        .fetch_add(valid_packet_count as u64, Ordering::Relaxed);
+    stats.verify_us.fetch_add(verify_us, Ordering::Relaxed);
+    stats.maybe_report(backend.name());
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{packet::to_packet_batches, test_tx::test_tx},
+        std::iter::repeat_with,
+    };
+
+    /// Verifies batches of exactly `packet_count` packets on the CPU,
+    /// counting them
+    struct CountingBackend {
+        packet_count: usize,
+        num_packets: AtomicU64,
+    }
+
+    impl SigVerifyBackend for CountingBackend {
+        fn name(&self) -> &'static str {
+            "counting"
+        }
+
+        fn accepts(&self, valid_packet_count: usize, _total_packet_count: usize) -> bool {
+            valid_packet_count == self.packet_count
+        }
+
+        fn verify(
+            &self,
+            batches: &mut [PacketBatch],
+            _recycler: &Recycler<TxOffset>,
+            _recycler_out: &Recycler<PinnedVec<u8>>,
+            reject_non_vote: bool,
+            valid_packet_count: usize,
+        ) {
+            self.num_packets
+                .fetch_add(valid_packet_count as u64, Ordering::Relaxed);
+            sigverify::ed25519_verify_cpu(batches, reject_non_vote, valid_packet_count)
+        }
+    }
+
+    #[test]
+    fn test_register_sigverify_backend() {
+        // a packet count the other tests, which may run meanwhile, do not verify
+        const PACKET_COUNT: usize = 4_099;
+        let backend = Arc::new(CountingBackend {
+            packet_count: PACKET_COUNT,
+            num_packets: AtomicU64::default(),
+        });
+        register_sigverify_backend(backend.clone());
+        assert_eq!(
+            backend_for(PACKET_COUNT, PACKET_COUNT).backend.name(),
+            "counting"
+        );
+        assert_ne!(backend_for(10, 10).backend.name(), "counting");
+
+        let recycler = Recycler::default();
+        let recycler_out = Recycler::default();
+        let txs: Vec<_> = repeat_with(test_tx).take(PACKET_COUNT).collect();
+        let mut batches = to_packet_batches(&txs, 128);
+        sigverify::ed25519_verify(&mut batches, &recycler, &recycler_out, false, 10);
+        assert_eq!(backend.num_packets.load(Ordering::Relaxed), 0);
+        sigverify::ed25519_verify(&mut batches, &recycler, &recycler_out, false, PACKET_COUNT);
+        assert_eq!(
+            backend.num_packets.load(Ordering::Relaxed),
+            PACKET_COUNT as u64
+        );
+        assert!(batches
+            .iter()
+            .flat_map(PacketBatch::iter)
+            .all(|packet| !packet.meta().discard()));
+    }
+}
@@ -41,6 +41,18 @@ pub struct ComputeBudget {
     pub sha256_byte_cost: u64,
     /// Maximum number of slices hashed per syscall
     pub sha256_max_slices: u64,
+    /// Base number of compute units consumed to call Blake3
+    pub blake3_base_cost: u64,
+    /// Incremental number of units consumed by Blake3 (based on bytes)
+    pub blake3_byte_cost: u64,
+    /// Maximum number of slices hashed per Blake3 syscall
+    pub blake3_max_slices: u64,
+    /// Base number of compute units consumed to call Keccak256
+    pub keccak256_base_cost: u64,
+    /// Incremental number of units consumed by Keccak256 (based on bytes)
+    pub keccak256_byte_cost: u64,
+    /// Maximum number of slices hashed per Keccak256 syscall
+    pub keccak256_max_slices: u64,
     /// Maximum SBF to BPF call depth
     pub max_call_depth: usize,
     /// Size of a stack frame in bytes, must match the size specified in the LLVM SBF backend
@@ -140,6 +152,12 @@ impl ComputeBudget {
             sha256_base_cost: 85,
             sha256_byte_cost: 1,
             sha256_max_slices: 20_000,
+            blake3_base_cost: 85,
+            blake3_byte_cost: 1,
+            blake3_max_slices: 20_000,
+            keccak256_base_cost: 85,
+            keccak256_byte_cost: 1,
+            keccak256_max_slices: 20_000,
             max_call_depth: 64,
             stack_frame_size: 4_096,
             log_pubkey_units: 100,
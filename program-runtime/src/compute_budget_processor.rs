@@ -5,10 +5,12 @@ use {
     },
     solana_sdk::{
         borsh1::try_from_slice_unchecked,
+        clock::Slot,
         compute_budget::{self, ComputeBudgetInstruction},
         entrypoint::HEAP_LENGTH as MIN_HEAP_FRAME_BYTES,
         fee::FeeBudgetLimits,
         instruction::{CompiledInstruction, InstructionError},
+        message::SanitizedMessage,
         pubkey::Pubkey,
         transaction::TransactionError,
     },
@@ -111,6 +113,10 @@ pub fn process_compute_budget_instructions<'a>(
                     }
                     updated_loaded_accounts_data_size_limit = Some(bytes);
                 }
+                // Neither affects the limits. They are validated by
+                // get_fee_sponsor_index and get_max_slot respectively.
+                Ok(ComputeBudgetInstruction::SetFeePayer)
+                | Ok(ComputeBudgetInstruction::SetMaxSlot(_)) => (),
                 _ => return Err(invalid_instruction_data_error),
             }
         } else {
@@ -146,6 +152,79 @@ pub fn process_compute_budget_instructions<'a>(
     })
 }
 
+/// Returns the index of the account designated as fee payer by a
+/// `SetFeePayer` instruction, if any, in place of the first signer.
+/// The designated account must be a writable signer of the message.
+/// Until fee sponsorship is enabled the instruction is invalid.
+pub fn get_fee_sponsor_index(
+    message: &SanitizedMessage,
+    enable_fee_sponsorship: bool,
+) -> Result<Option<usize>, TransactionError> {
+    let mut fee_sponsor_index = None;
+    for (i, (program_id, instruction)) in message.program_instructions_iter().enumerate() {
+        if !compute_budget::check_id(program_id)
+            || !matches!(
+                try_from_slice_unchecked(&instruction.data),
+                Ok(ComputeBudgetInstruction::SetFeePayer)
+            )
+        {
+            continue;
+        }
+        if !enable_fee_sponsorship {
+            return Err(TransactionError::InstructionError(
+                i as u8,
+                InstructionError::InvalidInstructionData,
+            ));
+        }
+        if fee_sponsor_index.is_some() {
+            return Err(TransactionError::DuplicateInstruction(i as u8));
+        }
+        let &[account_index] = instruction.accounts.as_slice() else {
+            return Err(TransactionError::InstructionError(
+                i as u8,
+                InstructionError::InvalidInstructionData,
+            ));
+        };
+        let account_index = usize::from(account_index);
+        if !message.is_signer(account_index) || !message.is_writable(account_index) {
+            return Err(TransactionError::InvalidAccountForFee);
+        }
+        fee_sponsor_index = Some(account_index);
+    }
+    Ok(fee_sponsor_index)
+}
+
+/// Returns the last slot in which the transaction may be processed, if set by
+/// a `SetMaxSlot` instruction.
+/// Until transaction max slots are enabled the instruction is invalid.
+pub fn get_max_slot<'a>(
+    instructions: impl Iterator<Item = (&'a Pubkey, &'a CompiledInstruction)>,
+    enable_transaction_max_slot: bool,
+) -> Result<Option<Slot>, TransactionError> {
+    let mut max_slot = None;
+    for (i, (program_id, instruction)) in instructions.enumerate() {
+        if !compute_budget::check_id(program_id) {
+            continue;
+        }
+        let Ok(ComputeBudgetInstruction::SetMaxSlot(slot)) =
+            try_from_slice_unchecked(&instruction.data)
+        else {
+            continue;
+        };
+        if !enable_transaction_max_slot {
+            return Err(TransactionError::InstructionError(
+                i as u8,
+                InstructionError::InvalidInstructionData,
+            ));
+        }
+        if max_slot.is_some() {
+            return Err(TransactionError::DuplicateInstruction(i as u8));
+        }
+        max_slot = Some(slot);
+    }
+    Ok(max_slot)
+}
+
 fn sanitize_requested_heap_size(bytes: u32) -> bool {
     (u32::try_from(MIN_HEAP_FRAME_BYTES).unwrap()..=MAX_HEAP_FRAME_BYTES).contains(&bytes)
         && bytes % 1024 == 0
@@ -156,9 +235,10 @@ mod tests {
     use {
         super::*,
         solana_sdk::{
+            fee_sponsorship::new_sponsored_message,
             hash::Hash,
-            instruction::Instruction,
-            message::Message,
+            instruction::{AccountMeta, Instruction},
+            message::{LegacyMessage, Message},
             pubkey::Pubkey,
             signature::Keypair,
             signer::Signer,
@@ -497,4 +577,140 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn test_process_set_fee_payer_instruction() {
+        // The fee payer does not change the limits
+        test!(
+            &[
+                Instruction::new_with_bincode(Pubkey::new_unique(), &0_u8, vec![]),
+                ComputeBudgetInstruction::set_fee_payer(&Pubkey::new_unique()),
+            ],
+            Ok(ComputeBudgetLimits {
+                compute_unit_limit: DEFAULT_INSTRUCTION_COMPUTE_UNIT_LIMIT,
+                ..ComputeBudgetLimits::default()
+            })
+        );
+    }
+
+    #[test]
+    fn test_get_max_slot() {
+        let payer = Pubkey::new_unique();
+        let sanitize = |instructions: &[Instruction]| {
+            SanitizedMessage::Legacy(LegacyMessage::new(Message::new(instructions, Some(&payer))))
+        };
+        let transfer = system_instruction::transfer(&payer, &Pubkey::new_unique(), 2);
+
+        let message = sanitize(&[transfer.clone()]);
+        assert_eq!(
+            get_max_slot(message.program_instructions_iter(), true),
+            Ok(None)
+        );
+
+        let message = sanitize(&[
+            transfer.clone(),
+            ComputeBudgetInstruction::set_compute_unit_limit(1),
+            ComputeBudgetInstruction::set_max_slot(42),
+        ]);
+        assert_eq!(
+            get_max_slot(message.program_instructions_iter(), true),
+            Ok(Some(42))
+        );
+        assert_eq!(
+            get_max_slot(message.program_instructions_iter(), false),
+            Err(TransactionError::InstructionError(
+                2,
+                InstructionError::InvalidInstructionData
+            ))
+        );
+        // Does not change the limits
+        assert_eq!(
+            process_compute_budget_instructions(message.program_instructions_iter()),
+            Ok(ComputeBudgetLimits {
+                compute_unit_limit: 1,
+                ..ComputeBudgetLimits::default()
+            })
+        );
+
+        let message = sanitize(&[
+            transfer,
+            ComputeBudgetInstruction::set_max_slot(42),
+            ComputeBudgetInstruction::set_max_slot(43),
+        ]);
+        assert_eq!(
+            get_max_slot(message.program_instructions_iter(), true),
+            Err(TransactionError::DuplicateInstruction(2))
+        );
+    }
+
+    #[test]
+    fn test_get_fee_sponsor_index() {
+        let user = Pubkey::new_unique();
+        let sponsor = Pubkey::new_unique();
+        let instructions = [system_instruction::transfer(
+            &user,
+            &Pubkey::new_unique(),
+            2,
+        )];
+        let sanitize = |message| SanitizedMessage::Legacy(LegacyMessage::new(message));
+
+        let message = sanitize(Message::new(&instructions, Some(&user)));
+        assert_eq!(get_fee_sponsor_index(&message, true), Ok(None));
+        assert_eq!(get_fee_sponsor_index(&message, false), Ok(None));
+
+        let message = sanitize(new_sponsored_message(&instructions, &user, &sponsor));
+        assert_eq!(message.account_keys()[1], sponsor);
+        assert_eq!(get_fee_sponsor_index(&message, true), Ok(Some(1)));
+        assert_eq!(
+            get_fee_sponsor_index(&message, false),
+            Err(TransactionError::InstructionError(
+                1,
+                InstructionError::InvalidInstructionData
+            ))
+        );
+
+        // The sponsor must be a writable signer
+        for account_meta in [
+            AccountMeta::new(sponsor, false),
+            AccountMeta::new_readonly(sponsor, true),
+        ] {
+            let mut set_fee_payer = ComputeBudgetInstruction::set_fee_payer(&sponsor);
+            set_fee_payer.accounts = vec![account_meta];
+            let message = sanitize(Message::new(
+                &[instructions[0].clone(), set_fee_payer],
+                Some(&user),
+            ));
+            assert_eq!(
+                get_fee_sponsor_index(&message, true),
+                Err(TransactionError::InvalidAccountForFee)
+            );
+        }
+
+        // Only one account may be designated
+        let mut set_fee_payer = ComputeBudgetInstruction::set_fee_payer(&sponsor);
+        set_fee_payer.accounts.push(AccountMeta::new(user, true));
+        let message = sanitize(Message::new(
+            &[instructions[0].clone(), set_fee_payer],
+            Some(&user),
+        ));
+        assert_eq!(
+            get_fee_sponsor_index(&message, true),
+            Err(TransactionError::InstructionError(
+                1,
+                InstructionError::InvalidInstructionData
+            ))
+        );
+        let message = sanitize(Message::new(
+            &[
+                instructions[0].clone(),
+                ComputeBudgetInstruction::set_fee_payer(&sponsor),
+                ComputeBudgetInstruction::set_fee_payer(&user),
+            ],
+            Some(&user),
+        ));
+        assert_eq!(
+            get_fee_sponsor_index(&message, true),
+            Err(TransactionError::DuplicateInstruction(2))
+        );
+    }
 }
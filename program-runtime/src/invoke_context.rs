@@ -6,7 +6,7 @@ use {
         log_collector::LogCollector,
         stable_log,
         sysvar_cache::SysvarCache,
-        timings::{ExecuteDetailsTimings, ExecuteTimings},
+        timings::{ExecuteDetailsTimings, ExecuteTimings, SyscallUnits},
     },
     solana_measure::measure::Measure,
     solana_rbpf::{
@@ -120,6 +120,11 @@ impl BpfAllocator {
         Self { len, pos: 0 }
     }
 
+    /// Number of bytes allocated so far
+    pub fn used(&self) -> u64 {
+        self.pos
+    }
+
     pub fn alloc(&mut self, layout: Layout) -> Result<u64, AllocErr> {
         let bytes_to_align = (self.pos as *const u8).align_offset(layout.align()) as u64;
         if self
@@ -160,6 +165,7 @@ pub struct InvokeContext<'a> {
     compute_budget: ComputeBudget,
     current_compute_budget: ComputeBudget,
     compute_meter: RefCell<u64>,
+    syscall_units: RefCell<SyscallUnits>,
     pub programs_loaded_for_tx_batch: &'a LoadedProgramsForTxBatch,
     pub programs_modified_by_tx: &'a mut LoadedProgramsForTxBatch,
     pub feature_set: Arc<FeatureSet>,
@@ -190,6 +196,7 @@ impl<'a> InvokeContext<'a> {
             current_compute_budget: compute_budget,
             compute_budget,
             compute_meter: RefCell::new(compute_budget.compute_unit_limit),
+            syscall_units: RefCell::default(),
             programs_loaded_for_tx_batch,
             programs_modified_by_tx,
             feature_set,
@@ -518,6 +525,8 @@ impl<'a> InvokeContext<'a> {
         };
         let post_remaining_units = self.get_remaining();
         *compute_units_consumed = pre_remaining_units.saturating_sub(post_remaining_units);
+        self.transaction_context
+            .set_current_instruction_compute_units_consumed(*compute_units_consumed)?;
 
         if builtin_id == program_id && result.is_ok() && *compute_units_consumed == 0 {
             return Err(InstructionError::BuiltinProgramsMustConsumeComputeUnits);
@@ -550,6 +559,23 @@ impl<'a> InvokeContext<'a> {
         Ok(())
     }
 
+    /// Consume compute units charged by a syscall, attributing them to
+    /// `category`
+    pub fn consume_syscall_units(
+        &self,
+        category: SyscallCategory,
+        amount: u64,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.consume_checked(amount)?;
+        self.syscall_units.borrow_mut().add(category, amount);
+        Ok(())
+    }
+
+    /// Take the compute units charged by syscalls since the last call
+    pub fn take_syscall_units(&self) -> SyscallUnits {
+        self.syscall_units.take()
+    }
+
     /// Set compute units
     ///
     /// Only use for tests and benchmarks
@@ -23,6 +23,7 @@ use {
     std::{
         collections::HashMap,
         fmt::{Debug, Formatter},
+        str::FromStr,
         sync::{
             atomic::{AtomicU64, Ordering},
             Arc, Condvar, Mutex, RwLock,
@@ -90,24 +91,27 @@ pub enum LoadedProgramType {
 
 impl Debug for LoadedProgramType {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "LoadedProgramType::{}", self.name())
+    }
+}
+
+impl LoadedProgramType {
+    /// The name of the variant, without its contents
+    pub fn name(&self) -> &'static str {
         match self {
-            LoadedProgramType::FailedVerification(_) => {
-                write!(f, "LoadedProgramType::FailedVerification")
-            }
-            LoadedProgramType::Closed => write!(f, "LoadedProgramType::Closed"),
-            LoadedProgramType::DelayVisibility => write!(f, "LoadedProgramType::DelayVisibility"),
-            LoadedProgramType::Unloaded(_) => write!(f, "LoadedProgramType::Unloaded"),
-            LoadedProgramType::LegacyV0(_) => write!(f, "LoadedProgramType::LegacyV0"),
-            LoadedProgramType::LegacyV1(_) => write!(f, "LoadedProgramType::LegacyV1"),
-            LoadedProgramType::Typed(_) => write!(f, "LoadedProgramType::Typed"),
+            LoadedProgramType::FailedVerification(_) => "FailedVerification",
+            LoadedProgramType::Closed => "Closed",
+            LoadedProgramType::DelayVisibility => "DelayVisibility",
+            LoadedProgramType::Unloaded(_) => "Unloaded",
+            LoadedProgramType::LegacyV0(_) => "LegacyV0",
+            LoadedProgramType::LegacyV1(_) => "LegacyV1",
+            LoadedProgramType::Typed(_) => "Typed",
             #[cfg(test)]
-            LoadedProgramType::TestLoaded(_) => write!(f, "LoadedProgramType::TestLoaded"),
-            LoadedProgramType::Builtin(_) => write!(f, "LoadedProgramType::Builtin"),
+            LoadedProgramType::TestLoaded(_) => "TestLoaded",
+            LoadedProgramType::Builtin(_) => "Builtin",
         }
     }
-}
 
-impl LoadedProgramType {
     /// Returns a reference to its environment if it has one
     pub fn get_environment(&self) -> Option<&ProgramRuntimeEnvironment> {
         match self {
@@ -170,6 +174,8 @@ pub struct Stats {
     pub prunes_environment: AtomicU64,
     /// the [SecondLevel] was empty because all slot versions got pruned
     pub empty_entries: AtomicU64,
+    /// a program was recompiled for the upcoming environments of the next epoch
+    pub recompilations: AtomicU64,
 }
 
 impl Stats {
@@ -186,6 +192,7 @@ impl Stats {
         let prunes_orphan = self.prunes_orphan.load(Ordering::Relaxed);
         let prunes_environment = self.prunes_environment.load(Ordering::Relaxed);
         let empty_entries = self.empty_entries.load(Ordering::Relaxed);
+        let recompilations = self.recompilations.load(Ordering::Relaxed);
         datapoint_info!(
             "loaded-programs-cache-stats",
             ("slot", slot, i64),
@@ -200,10 +207,11 @@ impl Stats {
             ("prunes_orphan", prunes_orphan, i64),
             ("prunes_environment", prunes_environment, i64),
             ("empty_entries", empty_entries, i64),
+            ("recompilations", recompilations, i64),
         );
         debug!(
-            "Loaded Programs Cache Stats -- Hits: {}, Misses: {}, Evictions: {}, Reloads: {}, Insertions: {} Lost-Insertions: {}, Replacements: {}, One-Hit-Wonders: {}, Prunes-Orphan: {}, Prunes-Environment: {}, Empty: {}",
-            hits, misses, evictions, reloads, insertions, lost_insertions, replacements, one_hit_wonders, prunes_orphan, prunes_environment, empty_entries
+            "Loaded Programs Cache Stats -- Hits: {}, Misses: {}, Evictions: {}, Reloads: {}, Insertions: {} Lost-Insertions: {}, Replacements: {}, One-Hit-Wonders: {}, Prunes-Orphan: {}, Prunes-Environment: {}, Empty: {}, Recompilations: {}",
+            hits, misses, evictions, reloads, insertions, lost_insertions, replacements, one_hit_wonders, prunes_orphan, prunes_environment, empty_entries, recompilations
         );
         if log_enabled!(log::Level::Trace) && !self.evictions.is_empty() {
             let mut evictions = self.evictions.iter().collect::<Vec<_>>();
@@ -230,6 +238,31 @@ impl Stats {
     }
 }
 
+/// Totals of some [Stats] counters since the cache was created, which unlike
+/// [Stats] are not reset at every slot
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CumulativeStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+    pub recompilations: u64,
+}
+
+impl CumulativeStats {
+    fn accumulate(&mut self, stats: &Stats) {
+        self.hits = self.hits.saturating_add(stats.hits.load(Ordering::Relaxed));
+        self.misses = self
+            .misses
+            .saturating_add(stats.misses.load(Ordering::Relaxed));
+        self.evictions = self
+            .evictions
+            .saturating_add(stats.evictions.values().sum());
+        self.recompilations = self
+            .recompilations
+            .saturating_add(stats.recompilations.load(Ordering::Relaxed));
+    }
+}
+
 /// Time measurements for loading a single [LoadedProgram].
 #[derive(Debug, Default)]
 pub struct LoadProgramMetrics {
@@ -471,6 +504,70 @@ impl LoadedProgram {
     }
 }
 
+/// How [LoadedPrograms] chooses which compiled programs to unload once it holds
+/// more of them than its capacity
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EvictionStrategy {
+    /// Repeatedly picks two programs at random and unloads the one with the
+    /// lower usage counter, decayed by the time since it was last used
+    #[default]
+    RandomSelection,
+    /// Unloads the programs which were used the longest time ago
+    LeastRecentlyUsed,
+    /// Unloads the programs which were used by the fewest transactions
+    LeastFrequentlyUsed,
+}
+
+impl EvictionStrategy {
+    pub const NAMES: &'static [&'static str] = &["random", "lru", "lfu"];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::RandomSelection => "random",
+            Self::LeastRecentlyUsed => "lru",
+            Self::LeastFrequentlyUsed => "lfu",
+        }
+    }
+}
+
+impl FromStr for EvictionStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "random" => Ok(Self::RandomSelection),
+            "lru" => Ok(Self::LeastRecentlyUsed),
+            "lfu" => Ok(Self::LeastFrequentlyUsed),
+            _ => Err(format!("unknown eviction strategy: {s}")),
+        }
+    }
+}
+
+/// Tunables of [LoadedPrograms]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LoadedProgramsConfig {
+    /// How many compiled programs are kept after each eviction
+    pub capacity: usize,
+    pub eviction_strategy: EvictionStrategy,
+}
+
+impl Default for LoadedProgramsConfig {
+    fn default() -> Self {
+        Self {
+            capacity: MAX_LOADED_ENTRY_COUNT,
+            eviction_strategy: EvictionStrategy::default(),
+        }
+    }
+}
+
+#[cfg(RUSTC_WITH_SPECIALIZATION)]
+impl solana_frozen_abi::abi_example::AbiExample for LoadedProgramsConfig {
+    fn example() -> Self {
+        // LoadedProgramsConfig is not Serialize so just rely on Default.
+        Self::default()
+    }
+}
+
 /// Globally shared RBPF config and syscall registry
 ///
 /// This is only valid in an epoch range as long as no feature affecting RBPF is activated.
@@ -591,6 +688,10 @@ pub struct LoadedPrograms<FG: ForkGraph> {
     pub programs_to_recompile: Vec<(Pubkey, Arc<LoadedProgram>)>,
     /// Statistics counters
     pub stats: Stats,
+    /// Statistics counters of the slots before the one in `stats`
+    cumulative_stats: CumulativeStats,
+    /// Capacity and eviction strategy
+    pub config: LoadedProgramsConfig,
     /// Reference to the block store
     pub fork_graph: Option<Arc<RwLock<FG>>>,
     /// Coordinates TX batches waiting for others to complete their task during cooperative loading
@@ -603,6 +704,7 @@ impl<FG: ForkGraph> Debug for LoadedPrograms<FG> {
             .field("root slot", &self.latest_root_slot)
             .field("root epoch", &self.latest_root_epoch)
             .field("stats", &self.stats)
+            .field("config", &self.config)
             .field("cache", &self.entries)
             .finish()
     }
@@ -691,6 +793,8 @@ impl<FG: ForkGraph> LoadedPrograms<FG> {
             upcoming_environments: None,
             programs_to_recompile: Vec::default(),
             stats: Stats::default(),
+            cumulative_stats: CumulativeStats::default(),
+            config: LoadedProgramsConfig::default(),
             fork_graph: None,
             loading_task_waiter: Arc::new(LoadingTaskWaiter::default()),
         }
@@ -700,6 +804,20 @@ impl<FG: ForkGraph> LoadedPrograms<FG> {
         self.fork_graph = Some(fork_graph);
     }
 
+    /// Resets the statistics counters for the next slot, after adding them to the
+    /// cumulative ones
+    pub fn reset_stats(&mut self) {
+        self.cumulative_stats.accumulate(&self.stats);
+        self.stats.reset();
+    }
+
+    /// Returns the statistics counters accumulated since the cache was created
+    pub fn get_cumulative_stats(&self) -> CumulativeStats {
+        let mut cumulative_stats = self.cumulative_stats;
+        cumulative_stats.accumulate(&self.stats);
+        cumulative_stats
+    }
+
     /// Returns the current environments depending on the given epoch
     pub fn get_environments_for_epoch(&self, epoch: Epoch) -> &ProgramRuntimeEnvironments {
         if epoch != self.latest_root_epoch {
@@ -1016,14 +1134,52 @@ impl<FG: ForkGraph> LoadedPrograms<FG> {
             .collect()
     }
 
+    /// Returns all slot versions of all programs, including tombstones and unloaded programs.
+    pub fn get_all_entries(&self) -> Vec<(Pubkey, Arc<LoadedProgram>)> {
+        self.entries
+            .iter()
+            .flat_map(|(id, second_level)| {
+                second_level
+                    .slot_versions
+                    .iter()
+                    .map(move |program| (*id, program.clone()))
+            })
+            .collect()
+    }
+
+    /// Unloads programs to reduce the cache usage to the given percentage of its capacity,
+    /// following the configured eviction strategy
+    pub fn evict(&mut self, shrink_to: PercentageInteger, now: Slot) {
+        match self.config.eviction_strategy {
+            EvictionStrategy::RandomSelection => {
+                self.evict_using_2s_random_selection(shrink_to, now)
+            }
+            EvictionStrategy::LeastRecentlyUsed => self
+                .sort_and_unload_by_key(shrink_to, |program| {
+                    program.latest_access_slot.load(Ordering::Relaxed)
+                }),
+            EvictionStrategy::LeastFrequentlyUsed => self.sort_and_unload(shrink_to),
+        }
+    }
+
     /// Unloads programs which were used infrequently
     pub fn sort_and_unload(&mut self, shrink_to: PercentageInteger) {
+        self.sort_and_unload_by_key(shrink_to, |program| {
+            program.tx_usage_counter.load(Ordering::Relaxed)
+        })
+    }
+
+    /// Unloads the programs with the lowest keys first
+    fn sort_and_unload_by_key<K: Ord>(
+        &mut self,
+        shrink_to: PercentageInteger,
+        mut key: impl FnMut(&LoadedProgram) -> K,
+    ) {
         let mut sorted_candidates = self.get_flattened_entries(true, true);
-        sorted_candidates
-            .sort_by_cached_key(|(_id, program)| program.tx_usage_counter.load(Ordering::Relaxed));
+        sorted_candidates.sort_by_cached_key(|(_id, program)| key(program));
         let num_to_unload = sorted_candidates
             .len()
-            .saturating_sub(shrink_to.apply_to(MAX_LOADED_ENTRY_COUNT));
+            .saturating_sub(shrink_to.apply_to(self.config.capacity));
         self.unload_program_entries(sorted_candidates.iter().take(num_to_unload));
     }
 
@@ -1033,7 +1189,7 @@ impl<FG: ForkGraph> LoadedPrograms<FG> {
         let mut candidates = self.get_flattened_entries(true, true);
         let num_to_unload = candidates
             .len()
-            .saturating_sub(shrink_to.apply_to(MAX_LOADED_ENTRY_COUNT));
+            .saturating_sub(shrink_to.apply_to(self.config.capacity));
         fn random_index_and_usage_counter(
             candidates: &[(Pubkey, Arc<LoadedProgram>)],
             now: Slot,
@@ -1138,9 +1294,10 @@ impl<FG: ForkGraph> solana_frozen_abi::abi_example::AbiExample for LoadedProgram
 mod tests {
     use {
         crate::loaded_programs::{
-            BlockRelation, ForkGraph, LoadedProgram, LoadedProgramMatchCriteria, LoadedProgramType,
-            LoadedPrograms, LoadedProgramsForTxBatch, ProgramRuntimeEnvironment,
-            ProgramRuntimeEnvironments, DELAY_VISIBILITY_SLOT_OFFSET,
+            BlockRelation, CumulativeStats, EvictionStrategy, ForkGraph, LoadedProgram,
+            LoadedProgramMatchCriteria, LoadedProgramType, LoadedPrograms, LoadedProgramsConfig,
+            LoadedProgramsForTxBatch, ProgramRuntimeEnvironment, ProgramRuntimeEnvironments,
+            DELAY_VISIBILITY_SLOT_OFFSET,
         },
         assert_matches::assert_matches,
         percentage::Percentage,
@@ -1596,6 +1753,72 @@ mod tests {
         assert_eq!(num_tombstones, 30);
     }
 
+    #[test]
+    fn test_cumulative_stats() {
+        let mut cache = new_mock_cache::<TestForkGraph>();
+        let program = Pubkey::new_unique();
+        for slot in 0..3 {
+            cache.stats.hits.fetch_add(2, Ordering::Relaxed);
+            cache.stats.misses.fetch_add(1, Ordering::Relaxed);
+            cache.stats.evictions.insert(program, 1);
+            cache
+                .stats
+                .recompilations
+                .fetch_add(slot, Ordering::Relaxed);
+            cache.reset_stats();
+        }
+        cache.stats.hits.fetch_add(1, Ordering::Relaxed);
+
+        assert_eq!(cache.stats.hits.load(Ordering::Relaxed), 1);
+        assert_eq!(cache.stats.misses.load(Ordering::Relaxed), 0);
+        assert_eq!(
+            cache.get_cumulative_stats(),
+            CumulativeStats {
+                hits: 7,
+                misses: 3,
+                evictions: 3,
+                recompilations: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn test_eviction_strategies() {
+        // (usage counter, latest access slot) of each program
+        let usages = [(10, 1), (1, 9), (5, 5), (8, 2)];
+        let programs = usages.map(|_| Pubkey::new_unique());
+        let evict = |eviction_strategy| {
+            let mut cache = new_mock_cache::<TestForkGraph>();
+            cache.config = LoadedProgramsConfig {
+                capacity: 2,
+                eviction_strategy,
+            };
+            for (program, (usage_counter, latest_access_slot)) in programs.iter().zip(usages) {
+                let entry = new_test_loaded_program_with_usage(0, 1, AtomicU64::new(usage_counter));
+                entry.update_access_slot(latest_access_slot);
+                cache.assign_program(*program, entry);
+            }
+            cache.evict(Percentage::from(100), 10);
+            let mut unloaded = cache
+                .get_all_entries()
+                .into_iter()
+                .filter(|(_, entry)| matches!(entry.program, LoadedProgramType::Unloaded(_)))
+                .map(|(program, _)| programs.iter().position(|p| *p == program).unwrap())
+                .collect::<Vec<_>>();
+            unloaded.sort();
+            unloaded
+        };
+
+        assert_eq!(evict(EvictionStrategy::LeastRecentlyUsed), vec![0, 3]);
+        assert_eq!(evict(EvictionStrategy::LeastFrequentlyUsed), vec![1, 2]);
+        assert_eq!(evict(EvictionStrategy::RandomSelection).len(), 2);
+
+        for name in EvictionStrategy::NAMES {
+            assert_eq!(name.parse::<EvictionStrategy>().unwrap().as_str(), *name);
+        }
+        assert!("mru".parse::<EvictionStrategy>().is_err());
+    }
+
     #[test]
     fn test_usage_count_of_unloaded_program() {
         let mut cache = new_mock_cache::<TestForkGraph>();
@@ -5,7 +5,7 @@ use {
         loaded_programs::LoadedProgramsForTxBatch,
         log_collector::LogCollector,
         sysvar_cache::SysvarCache,
-        timings::{ExecuteDetailsTimings, ExecuteTimings},
+        timings::{ExecuteDetailsTimings, ExecuteTimings, InstructionProfile},
     },
     serde::{Deserialize, Serialize},
     solana_measure::measure::Measure,
@@ -132,6 +132,15 @@ impl MessageProcessor {
                         invoke_context.transaction_context.push()?;
                         invoke_context.transaction_context.pop()
                     })
+                    .map(|_| {
+                        timings
+                            .details
+                            .per_instruction_profiles
+                            .push(InstructionProfile {
+                                program_id: *program_id,
+                                ..InstructionProfile::default()
+                            });
+                    })
             } else {
                 let mut time = Measure::start("execute_instruction");
                 let mut compute_units_consumed = 0;
@@ -151,6 +160,15 @@ impl MessageProcessor {
                     compute_units_consumed,
                     result.is_err(),
                 );
+                timings
+                    .details
+                    .per_instruction_profiles
+                    .push(InstructionProfile {
+                        program_id: *program_id,
+                        compute_units_consumed,
+                        syscall_units: invoke_context.take_syscall_units(),
+                        heap_high_water_mark: invoke_context.timings.heap_high_water_mark,
+                    });
                 invoke_context.timings = {
                     timings.details.accumulate(&invoke_context.timings);
                     ExecuteDetailsTimings::default()
@@ -8,12 +8,13 @@ use {
         instruction::InstructionError,
         pubkey::Pubkey,
         sysvar::{
-            clock::Clock, epoch_rewards::EpochRewards, epoch_schedule::EpochSchedule, rent::Rent,
+            clock::Clock, epoch_rewards::EpochRewards, epoch_schedule::EpochSchedule,
+            epoch_stake::EpochStake, rent::Rent, restart_history::RestartHistory,
             slot_hashes::SlotHashes, stake_history::StakeHistory, Sysvar, SysvarId,
         },
         transaction_context::{IndexOfAccount, InstructionContext, TransactionContext},
     },
-    std::sync::Arc,
+    std::{collections::HashMap, sync::Arc},
 };
 
 #[cfg(RUSTC_WITH_SPECIALIZATION)]
@@ -37,6 +38,12 @@ pub struct SysvarCache {
     recent_blockhashes: Option<Arc<RecentBlockhashes>>,
     stake_history: Option<Arc<StakeHistory>>,
     last_restart_slot: Option<Arc<LastRestartSlot>>,
+    /// Commissions of the staked vote accounts at the start of the current
+    /// epoch, which are not held by any sysvar account
+    epoch_start_commissions: Option<Arc<HashMap<Pubkey, u8>>>,
+    /// Account data of the sysvars loaded by `fill_missing_entries`, for
+    /// partial reads through `sol_get_sysvar`
+    sysvar_data: HashMap<Pubkey, Arc<Vec<u8>>>,
 }
 
 impl SysvarCache {
@@ -134,10 +141,42 @@ impl SysvarCache {
         self.stake_history = Some(Arc::new(stake_history));
     }
 
+    pub fn get_epoch_start_commissions(
+        &self,
+    ) -> Result<Arc<HashMap<Pubkey, u8>>, InstructionError> {
+        self.epoch_start_commissions
+            .clone()
+            .ok_or(InstructionError::UnsupportedSysvar)
+    }
+
+    pub fn set_epoch_start_commissions(&mut self, epoch_start_commissions: HashMap<Pubkey, u8>) {
+        self.epoch_start_commissions = Some(Arc::new(epoch_start_commissions));
+    }
+
+    /// Returns the account data of the sysvar `sysvar_id`, if it was loaded
+    pub fn get_sysvar_data(&self, sysvar_id: &Pubkey) -> Option<Arc<Vec<u8>>> {
+        self.sysvar_data.get(sysvar_id).cloned()
+    }
+
     pub fn fill_missing_entries<F: FnMut(&Pubkey, &mut dyn FnMut(&[u8]))>(
         &mut self,
         mut get_account_data: F,
     ) {
+        // Only read partially through `sol_get_sysvar`, so not deserialized
+        let partially_read_sysvars: Vec<_> = [EpochStake::id(), RestartHistory::id()]
+            .into_iter()
+            .filter(|sysvar_id| !self.sysvar_data.contains_key(sysvar_id))
+            .collect();
+        let mut sysvar_data = std::mem::take(&mut self.sysvar_data);
+        let mut get_account_data = |pubkey: &Pubkey, callback: &mut dyn FnMut(&[u8])| {
+            get_account_data(pubkey, &mut |data: &[u8]| {
+                sysvar_data
+                    .entry(*pubkey)
+                    .or_insert_with(|| Arc::new(data.to_vec()));
+                callback(data);
+            });
+        };
+
         if self.clock.is_none() {
             get_account_data(&Clock::id(), &mut |data: &[u8]| {
                 if let Ok(clock) = bincode::deserialize(data) {
@@ -205,6 +244,10 @@ impl SysvarCache {
                 }
             });
         }
+        for sysvar_id in partially_read_sysvars {
+            get_account_data(&sysvar_id, &mut |_data: &[u8]| {});
+        }
+        self.sysvar_data = sysvar_data;
     }
 
     pub fn reset(&mut self) {
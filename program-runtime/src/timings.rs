@@ -80,6 +80,84 @@ impl core::fmt::Debug for Metrics {
     }
 }
 
+/// Used as an index for `SyscallUnits`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Sequence)]
+pub enum SyscallCategory {
+    Logging,
+    Memory,
+    Cpi,
+    Sysvar,
+    ProgramAddress,
+    Hashing,
+    Crypto,
+    ReturnData,
+    Introspection,
+}
+
+/// Compute units charged by syscalls, per `SyscallCategory`
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct SyscallUnits([u64; SyscallCategory::CARDINALITY]);
+
+impl SyscallUnits {
+    pub fn add(&mut self, category: SyscallCategory, units: u64) {
+        saturating_add_assign!(self[category], units);
+    }
+
+    pub fn accumulate(&mut self, other: &SyscallUnits) {
+        for (t1, t2) in self.0.iter_mut().zip(other.0.iter()) {
+            saturating_add_assign!(*t1, *t2);
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (SyscallCategory, u64)> + '_ {
+        enum_iterator::all::<SyscallCategory>().zip(self.0.iter().copied())
+    }
+
+    pub fn total(&self) -> u64 {
+        self.0
+            .iter()
+            .fold(0u64, |total, units| total.saturating_add(*units))
+    }
+}
+
+impl Index<SyscallCategory> for SyscallUnits {
+    type Output = u64;
+    fn index(&self, index: SyscallCategory) -> &Self::Output {
+        self.0.index(index as usize)
+    }
+}
+
+impl IndexMut<SyscallCategory> for SyscallUnits {
+    fn index_mut(&mut self, index: SyscallCategory) -> &mut Self::Output {
+        self.0.index_mut(index as usize)
+    }
+}
+
+impl Default for SyscallUnits {
+    fn default() -> Self {
+        SyscallUnits([0; SyscallCategory::CARDINALITY])
+    }
+}
+
+impl core::fmt::Debug for SyscallUnits {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+/// Resources used by a top-level instruction, including the instructions it
+/// invoked
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct InstructionProfile {
+    pub program_id: Pubkey,
+    pub compute_units_consumed: u64,
+    /// Compute units charged by syscalls, which are only recorded for SBF
+    /// programs
+    pub syscall_units: SyscallUnits,
+    /// The most bytes of heap used by a single SBF program invocation
+    pub heap_high_water_mark: u64,
+}
+
 // The auxiliary variable that must always be provided to eager_macro_rules! must use the
 // identifier `eager_1`. Macros declared with `eager_macro_rules!` can then be used inside
 // an eager! block.
@@ -394,6 +472,9 @@ pub struct ExecuteDetailsTimings {
     pub create_executor_verify_code_us: u64,
     pub create_executor_jit_compile_us: u64,
     pub per_program_timings: HashMap<Pubkey, ProgramTiming>,
+    // The most bytes of heap used by a single SBF program invocation
+    pub heap_high_water_mark: u64,
+    pub per_instruction_profiles: Vec<InstructionProfile>,
 }
 
 impl ExecuteDetailsTimings {
@@ -428,6 +509,9 @@ impl ExecuteDetailsTimings {
             let program_timing = self.per_program_timings.entry(*id).or_default();
             program_timing.accumulate_program_timings(other);
         }
+        self.heap_high_water_mark = self.heap_high_water_mark.max(other.heap_high_water_mark);
+        self.per_instruction_profiles
+            .extend(other.per_instruction_profiles.iter().cloned());
     }
 
     pub fn accumulate_program(
@@ -529,6 +613,14 @@ mod tests {
         other_execute_details_timings.deserialize_us = us;
         other_execute_details_timings.changed_account_count = account_count;
         other_execute_details_timings.total_account_count = account_count;
+        other_execute_details_timings.heap_high_water_mark = 4096;
+        other_execute_details_timings
+            .per_instruction_profiles
+            .push(InstructionProfile {
+                program_id,
+                compute_units_consumed,
+                ..InstructionProfile::default()
+            });
 
         // Accumulate the other instance into the current instance
         execute_details_timings.accumulate(&other_execute_details_timings);
@@ -548,4 +640,30 @@ mod tests {
         let check_us = timings.metrics.index(ExecuteTimingType::CheckUs);
         assert_eq!(3, *check_us);
     }
+
+    #[test]
+    fn test_syscall_units() {
+        let mut syscall_units = SyscallUnits::default();
+        syscall_units.add(SyscallCategory::Logging, 100);
+        syscall_units.add(SyscallCategory::Cpi, 1_000);
+        syscall_units.add(SyscallCategory::Logging, 100);
+        assert_eq!(syscall_units[SyscallCategory::Logging], 200);
+        assert_eq!(syscall_units[SyscallCategory::Hashing], 0);
+        assert_eq!(syscall_units.total(), 1_200);
+
+        let mut other = SyscallUnits::default();
+        other.add(SyscallCategory::Hashing, 85);
+        other.accumulate(&syscall_units);
+        assert_eq!(
+            other
+                .iter()
+                .filter(|(_, units)| *units > 0)
+                .collect::<Vec<_>>(),
+            vec![
+                (SyscallCategory::Logging, 200),
+                (SyscallCategory::Cpi, 1_000),
+                (SyscallCategory::Hashing, 85),
+            ]
+        );
+    }
 }
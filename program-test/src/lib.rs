@@ -27,9 +27,12 @@ use {
         genesis_utils::{create_genesis_config_with_leader_ex, GenesisConfigInfo},
     },
     solana_sdk::{
-        account::{create_account_shared_data_for_test, Account, AccountSharedData},
+        account::{
+            create_account_shared_data_for_test, Account, AccountSharedData, ReadableAccount,
+        },
         account_info::AccountInfo,
-        clock::{Epoch, Slot},
+        bpf_loader_upgradeable::{self, UpgradeableLoaderState},
+        clock::{Epoch, Slot, MAX_PROCESSING_AGE},
         entrypoint::{deserialize, ProgramResult, SUCCESS},
         feature_set::FEATURE_NAMES,
         fee_calculator::{FeeCalculator, FeeRateGovernor, DEFAULT_TARGET_LAMPORTS_PER_SIGNATURE},
@@ -43,7 +46,12 @@ use {
         rent::Rent,
         signature::{Keypair, Signer},
         stable_layout::stable_instruction::StableInstruction,
-        sysvar::{Sysvar, SysvarId},
+        stake_history::StakeHistoryEntry,
+        system_instruction::MAX_PERMITTED_DATA_LENGTH,
+        sysvar::{
+            Sysvar, SysvarId, OFFSET_EXCEEDS_SYSVAR, OFFSET_LENGTH_EXCEEDS_SYSVAR, SYSVAR_NOT_FOUND,
+        },
+        transaction::{TransactionError, VersionedTransaction},
     },
     solana_svm::runtime_config::RuntimeConfig,
     solana_vote_program::vote_state::{self, VoteState, VoteStateVersions},
@@ -53,7 +61,7 @@ use {
         convert::TryFrom,
         fs::File,
         io::{self, Read},
-        mem::transmute,
+        mem::{size_of, transmute},
         path::{Path, PathBuf},
         sync::{
             atomic::{AtomicBool, Ordering},
@@ -67,8 +75,11 @@ use {
 // Export types so test clients can limit their solana crate dependencies
 pub use {
     solana_banks_client::{BanksClient, BanksClientError},
-    solana_banks_interface::BanksTransactionResultWithMetadata,
-    solana_program_runtime::invoke_context::InvokeContext,
+    solana_banks_interface::{BanksTransactionResultWithMetadata, TransactionSimulationConfig},
+    solana_program_runtime::{
+        invoke_context::InvokeContext,
+        timings::{InstructionProfile, SyscallCategory, SyscallUnits},
+    },
     solana_rbpf::{
         error::EbpfError,
         vm::{get_runtime_environment_key, EbpfVm},
@@ -408,6 +419,48 @@ impl solana_sdk::program_stubs::SyscallStubs for SyscallStubs {
         )
     }
 
+    fn sol_get_sysvar(
+        &self,
+        sysvar_id_addr: *const u8,
+        var_addr: *mut u8,
+        offset: u64,
+        length: u64,
+    ) -> u64 {
+        let invoke_context = get_invoke_context();
+        let budget = invoke_context.get_compute_budget();
+        let sysvar_id_cost = size_of::<Pubkey>() as u64 / budget.cpi_bytes_per_unit;
+        let sysvar_buf_cost = length / budget.cpi_bytes_per_unit;
+        if invoke_context
+            .consume_checked(
+                budget.sysvar_base_cost
+                    + sysvar_id_cost
+                    + sysvar_buf_cost.max(budget.mem_op_base_cost),
+            )
+            .is_err()
+        {
+            panic!("Exceeded compute budget");
+        }
+
+        let sysvar_id = unsafe { &*(sysvar_id_addr as *const Pubkey) };
+        let Some(sysvar_data) = invoke_context.get_sysvar_cache().get_sysvar_data(sysvar_id) else {
+            return SYSVAR_NOT_FOUND;
+        };
+        let (offset, length) = (offset as usize, length as usize);
+        if offset > sysvar_data.len() {
+            return OFFSET_EXCEEDS_SYSVAR;
+        }
+        let Some(data) = offset
+            .checked_add(length)
+            .and_then(|end| sysvar_data.get(offset..end))
+        else {
+            return OFFSET_LENGTH_EXCEEDS_SYSVAR;
+        };
+        unsafe {
+            std::ptr::copy_nonoverlapping(data.as_ptr(), var_addr, length);
+        }
+        SUCCESS
+    }
+
     fn sol_get_return_data(&self) -> Option<(Pubkey, Vec<u8>)> {
         let (program_id, data) = get_invoke_context().transaction_context.get_return_data();
         Some((*program_id, data.to_vec()))
@@ -431,6 +484,40 @@ impl solana_sdk::program_stubs::SyscallStubs for SyscallStubs {
         let invoke_context = get_invoke_context();
         invoke_context.get_stack_height().try_into().unwrap()
     }
+
+    fn sol_get_caller_program_id(&self) -> Option<Pubkey> {
+        let invoke_context = get_invoke_context();
+        let transaction_context = &invoke_context.transaction_context;
+        let caller_nesting_level = transaction_context
+            .get_instruction_context_stack_height()
+            .checked_sub(2)?;
+        let instruction_context = transaction_context
+            .get_instruction_context_at_nesting_level(caller_nesting_level)
+            .unwrap();
+        Some(
+            *instruction_context
+                .get_last_program_key(transaction_context)
+                .unwrap(),
+        )
+    }
+
+    fn sol_get_minimum_balance_for_rent_exemption(&self, data_len: u64) -> u64 {
+        let invoke_context = get_invoke_context();
+        if invoke_context
+            .consume_checked(invoke_context.get_compute_budget().syscall_base_cost)
+            .is_err()
+        {
+            panic!("Exceeded compute budget");
+        }
+        if data_len > MAX_PERMITTED_DATA_LENGTH {
+            panic!("Invalid data length {data_len}, the maximum is {MAX_PERMITTED_DATA_LENGTH}");
+        }
+        invoke_context
+            .get_sysvar_cache()
+            .get_rent()
+            .unwrap()
+            .minimum_balance(data_len as usize)
+    }
 }
 
 pub fn find_file(filename: &str) -> Option<PathBuf> {
@@ -1018,6 +1105,50 @@ impl<T> Drop for DroppableTask<T> {
     }
 }
 
+/// The state of all accounts of a [`ProgramTestContext`], saved by
+/// [`ProgramTestContext::snapshot`] to be rolled back to with
+/// [`ProgramTestContext::restore`]
+#[derive(Clone)]
+pub struct ProgramTestSnapshot {
+    accounts: HashMap<Pubkey, AccountSharedData>,
+}
+
+/// The compute units and heap used by a transaction processed with
+/// [`ProgramTestContext::process_transaction_with_profile`]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TransactionProfile {
+    /// The resources used by each instruction of the transaction, in order
+    pub instructions: Vec<InstructionProfile>,
+}
+
+impl TransactionProfile {
+    /// Compute units consumed by all instructions
+    pub fn compute_units_consumed(&self) -> u64 {
+        self.instructions
+            .iter()
+            .map(|instruction| instruction.compute_units_consumed)
+            .sum()
+    }
+
+    /// Compute units charged by syscalls in all instructions, per category
+    pub fn syscall_units(&self) -> SyscallUnits {
+        let mut syscall_units = SyscallUnits::default();
+        for instruction in &self.instructions {
+            syscall_units.accumulate(&instruction.syscall_units);
+        }
+        syscall_units
+    }
+
+    /// The most bytes of heap used by a single program invocation
+    pub fn heap_high_water_mark(&self) -> u64 {
+        self.instructions
+            .iter()
+            .map(|instruction| instruction.heap_high_water_mark)
+            .max()
+            .unwrap_or_default()
+    }
+}
+
 pub struct ProgramTestContext {
     pub banks_client: BanksClient,
     pub last_blockhash: Hash,
@@ -1113,6 +1244,72 @@ impl ProgramTestContext {
         bank.store_account(address, account);
     }
 
+    /// Save the state of all accounts, including the clock and the other
+    /// sysvars, so that many scenarios can branch off fixtures set up once
+    pub fn snapshot(&self) -> ProgramTestSnapshot {
+        let bank_forks = self.bank_forks.read().unwrap();
+        let bank = bank_forks.working_bank();
+        let accounts = bank
+            .get_all_accounts()
+            .unwrap()
+            .into_iter()
+            .map(|(pubkey, account, _slot)| (pubkey, account))
+            .collect();
+        ProgramTestSnapshot { accounts }
+    }
+
+    /// Roll all accounts, including the clock and the other sysvars, back to
+    /// their state in `snapshot`, subverting normal runtime checks. Accounts
+    /// created since the snapshot are removed.
+    ///
+    /// The working bank is not replaced, so its slot and blockhashes are
+    /// unchanged.
+    pub fn restore(&mut self, snapshot: &ProgramTestSnapshot) {
+        let bank_forks = self.bank_forks.read().unwrap();
+        let bank = bank_forks.working_bank();
+        let accounts: HashMap<_, _> = bank
+            .get_all_accounts()
+            .unwrap()
+            .into_iter()
+            .map(|(pubkey, account, _slot)| (pubkey, account))
+            .collect();
+
+        let mut restored_accounts = HashSet::new();
+        for pubkey in accounts.keys() {
+            if !snapshot.accounts.contains_key(pubkey) {
+                bank.store_account(pubkey, &AccountSharedData::default());
+                restored_accounts.insert(*pubkey);
+            }
+        }
+        for (pubkey, account) in &snapshot.accounts {
+            if accounts.get(pubkey) != Some(account) {
+                bank.store_account(pubkey, account);
+                restored_accounts.insert(*pubkey);
+            }
+        }
+
+        // Evict the restored programs from the program cache, including the
+        // upgradeable programs whose program data was restored, so that the
+        // restored versions are loaded again
+        let restored_programs: Vec<_> = snapshot
+            .accounts
+            .iter()
+            .filter(|(_pubkey, account)| bpf_loader_upgradeable::check_id(account.owner()))
+            .filter_map(|(pubkey, account)| match account.deserialize_data() {
+                Ok(UpgradeableLoaderState::Program {
+                    programdata_address,
+                }) if restored_accounts.contains(&programdata_address) => Some(*pubkey),
+                _ => None,
+            })
+            .chain(restored_accounts.iter().copied())
+            .collect();
+        bank.loaded_programs_cache
+            .write()
+            .unwrap()
+            .remove_programs(restored_programs.into_iter());
+        bank.reload_sysvar_cache_for_tests();
+    }
+
     /// Create or overwrite a sysvar, subverting normal runtime checks.
     ///
     /// This method exists to make it easier to set up artificial situations
@@ -1125,6 +1322,37 @@ impl ProgramTestContext {
         bank.set_sysvar_for_tests(sysvar);
     }
 
+    /// Process a transaction on the working bank, and profile the compute
+    /// units consumed by each of its instructions, the compute units charged
+    /// by syscalls per category, and the heap used, so that compute
+    /// regressions can be tracked in CI.
+    ///
+    /// Syscalls and heap usage are only recorded for SBF programs, see
+    /// [`ProgramTest::prefer_bpf`].
+    pub fn process_transaction_with_profile(
+        &mut self,
+        transaction: impl Into<VersionedTransaction>,
+    ) -> Result<TransactionProfile, TransactionError> {
+        let bank_forks = self.bank_forks.read().unwrap();
+        let bank = bank_forks.working_bank();
+        let batch = bank.prepare_entry_batch(vec![transaction.into()])?;
+        let mut timings = ExecuteTimings::default();
+        let (transaction_results, _balances) = bank.load_execute_and_commit_transactions(
+            &batch,
+            MAX_PROCESSING_AGE,
+            false, // collect_balances
+            false, // enable_cpi_recording
+            false, // enable_log_recording
+            false, // enable_return_data_recording
+            &mut timings,
+            None,
+        );
+        transaction_results.execution_results[0].flattened_result()?;
+        Ok(TransactionProfile {
+            instructions: timings.details.per_instruction_profiles,
+        })
+    }
+
     /// Force the working bank ahead to a new slot
     pub fn warp_to_slot(&mut self, warp_slot: Slot) -> Result<(), ProgramTestError> {
         let mut bank_forks = self.bank_forks.write().unwrap();
@@ -1206,12 +1434,45 @@ impl ProgramTestContext {
         Ok(())
     }
 
+    /// Force the working bank ahead to the first slot of a new epoch
+    ///
+    /// The stake history entries of the epochs skipped over are synthesized
+    /// as if they had elapsed without any change to the stake delegations, so
+    /// that stake delegated before the warp is warmed up or cooled down
+    /// consistently. With the stake of the bootstrap validator, delegations
+    /// made before the warp are usually fully active after it.
     pub fn warp_to_epoch(&mut self, warp_epoch: Epoch) -> Result<(), ProgramTestError> {
+        self.warp_to_epoch_with_stake_history(warp_epoch, |_epoch, entry| entry)
+    }
+
+    /// Same as [`Self::warp_to_epoch`], except that `stake_history_entry` is
+    /// called with each skipped epoch and its synthesized stake history entry,
+    /// and returns the entry to record instead
+    pub fn warp_to_epoch_with_stake_history<F>(
+        &mut self,
+        warp_epoch: Epoch,
+        stake_history_entry: F,
+    ) -> Result<(), ProgramTestError>
+    where
+        F: FnMut(Epoch, StakeHistoryEntry) -> StakeHistoryEntry,
+    {
         let warp_slot = self
             .genesis_config
             .epoch_schedule
             .get_first_slot_in_epoch(warp_epoch);
-        self.warp_to_slot(warp_slot)
+        let working_epoch = self.bank_forks.read().unwrap().working_bank().epoch();
+        self.warp_to_slot(warp_slot)?;
+
+        // Warping only crosses the first and last epoch boundaries, which
+        // record the entries of the working epoch and of the epoch before the
+        // warp epoch. The latter is calculated again along with the others, as
+        // it depends on them.
+        let bank = self.bank_forks.read().unwrap().working_bank();
+        bank.synthesize_stake_history_for_tests(
+            working_epoch.saturating_add(1)..warp_epoch,
+            stake_history_entry,
+        );
+        Ok(())
     }
 
     /// warp forward one more slot and force reward interval end
@@ -1,10 +1,10 @@
 use {
-    solana_program_test::ProgramTest,
+    solana_program_test::{ProgramTest, SyscallCategory},
     solana_sdk::{
         instruction::{AccountMeta, Instruction},
         pubkey::Pubkey,
         signature::{Keypair, Signer},
-        system_instruction,
+        system_instruction, system_program,
         sysvar::rent,
         transaction::Transaction,
     },
@@ -59,3 +59,66 @@ async fn max_compute_units() {
         .await
         .unwrap();
 }
+
+#[tokio::test]
+async fn profile_compute_units() {
+    let mut context = ProgramTest::default().start_with_context().await;
+
+    let token_2022_id = Pubkey::try_from("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb").unwrap();
+    let mint = Keypair::new();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let space = 82;
+    let transaction = Transaction::new_signed_with_payer(
+        &[
+            system_instruction::create_account(
+                &context.payer.pubkey(),
+                &mint.pubkey(),
+                rent.minimum_balance(space),
+                space as u64,
+                &token_2022_id,
+            ),
+            Instruction::new_with_bytes(
+                token_2022_id,
+                &[0; 35], // initialize mint
+                vec![
+                    AccountMeta::new(mint.pubkey(), false),
+                    AccountMeta::new_readonly(rent::id(), false),
+                ],
+            ),
+        ],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &mint],
+        context.last_blockhash,
+    );
+
+    let profile = context
+        .process_transaction_with_profile(transaction)
+        .unwrap();
+    assert_eq!(profile.instructions.len(), 2);
+    let (create_account, initialize_mint) = (&profile.instructions[0], &profile.instructions[1]);
+
+    // The system program is a builtin, so it makes no syscalls
+    assert_eq!(create_account.program_id, system_program::id());
+    assert_eq!(create_account.syscall_units.total(), 0);
+    assert_eq!(create_account.heap_high_water_mark, 0);
+
+    // Token-2022 is an SBF program, which logs the instruction it processes
+    assert_eq!(initialize_mint.program_id, token_2022_id);
+    assert!(initialize_mint.syscall_units[SyscallCategory::Logging] > 0);
+    assert!(initialize_mint.syscall_units.total() < initialize_mint.compute_units_consumed);
+    assert!(initialize_mint.heap_high_water_mark <= 32 * 1024);
+
+    assert_eq!(
+        profile.compute_units_consumed(),
+        create_account.compute_units_consumed + initialize_mint.compute_units_consumed
+    );
+    assert_eq!(profile.syscall_units(), initialize_mint.syscall_units);
+
+    let mint_account = context
+        .banks_client
+        .get_account(mint.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(mint_account.owner, token_2022_id);
+}
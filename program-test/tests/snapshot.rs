@@ -0,0 +1,81 @@
+use {
+    solana_program_test::{ProgramTest, ProgramTestContext},
+    solana_sdk::{
+        clock::Clock, pubkey::Pubkey, signature::Signer, system_instruction,
+        transaction::Transaction,
+    },
+};
+
+async fn transfer(context: &mut ProgramTestContext, recipient: &Pubkey, lamports: u64) {
+    let transaction = Transaction::new_signed_with_payer(
+        &[system_instruction::transfer(
+            &context.payer.pubkey(),
+            recipient,
+            lamports,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn restore_snapshot() {
+    let mut context = ProgramTest::default().start_with_context().await;
+
+    // set up a fixture
+    let recipient = Pubkey::new_unique();
+    transfer(&mut context, &recipient, 1_000_000_000).await;
+    let clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    context.set_sysvar(&Clock {
+        unix_timestamp: 42,
+        ..clock.clone()
+    });
+    let payer_balance = context
+        .banks_client
+        .get_balance(context.payer.pubkey())
+        .await
+        .unwrap();
+    let snapshot = context.snapshot();
+
+    for lamports in [1, 2] {
+        // run a scenario
+        let new_account = Pubkey::new_unique();
+        transfer(&mut context, &recipient, lamports).await;
+        transfer(&mut context, &new_account, 1_000_000_000).await;
+        context.set_sysvar(&Clock {
+            unix_timestamp: 1_000,
+            ..clock.clone()
+        });
+        assert_eq!(
+            context.banks_client.get_balance(recipient).await.unwrap(),
+            1_000_000_000 + lamports
+        );
+
+        // and roll it back
+        context.restore(&snapshot);
+        assert_eq!(
+            context.banks_client.get_balance(recipient).await.unwrap(),
+            1_000_000_000
+        );
+        assert_eq!(
+            context.banks_client.get_account(new_account).await.unwrap(),
+            None
+        );
+        assert_eq!(
+            context
+                .banks_client
+                .get_balance(context.payer.pubkey())
+                .await
+                .unwrap(),
+            payer_balance
+        );
+        let restored_clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+        assert_eq!(restored_clock.unix_timestamp, 42);
+    }
+}
@@ -22,6 +22,7 @@ use {
             instruction as stake_instruction,
             state::{StakeActivationStatus, StakeStateV2},
         },
+        stake_history::StakeHistoryEntry,
         sysvar::{
             clock,
             stake_history::{self, StakeHistory},
@@ -210,6 +211,55 @@ async fn stake_rewards_from_warp() {
     );
 }
 
+#[tokio::test]
+async fn stake_history_synthesized_from_warp() {
+    let program_test = ProgramTest::default();
+    let mut context = program_test.start_with_context().await;
+
+    context.warp_to_slot(100).unwrap();
+    let vote_address = setup_vote(&mut context).await;
+
+    let user_keypair = Keypair::new();
+    let stake_lamports = 1_000_000_000_000;
+    let stake_address =
+        setup_stake(&mut context, &user_keypair, &vote_address, stake_lamports).await;
+
+    let clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    let activation_epoch = clock.epoch;
+    let warp_epoch = activation_epoch + 5;
+    context.warp_to_epoch(warp_epoch).unwrap();
+
+    // every epoch skipped over has an entry, and the stake is fully active
+    let stake_history: StakeHistory = context.banks_client.get_sysvar().await.unwrap();
+    for epoch in activation_epoch..warp_epoch {
+        assert!(stake_history.get(epoch).is_some());
+    }
+    let account = context
+        .banks_client
+        .get_account(stake_address)
+        .await
+        .expect("account exists")
+        .unwrap();
+    let stake_state: StakeStateV2 = deserialize(&account.data).unwrap();
+    let stake = stake_state.stake().unwrap();
+    assert_eq!(
+        stake
+            .delegation
+            .stake_activating_and_deactivating(warp_epoch, &stake_history, None),
+        StakeActivationStatus::with_effective(stake.delegation.stake),
+    );
+
+    // the synthesized entries can be overridden
+    let entry = StakeHistoryEntry::with_effective(42);
+    context
+        .warp_to_epoch_with_stake_history(warp_epoch + 3, |_epoch, _entry| entry.clone())
+        .unwrap();
+    let stake_history: StakeHistory = context.banks_client.get_sysvar().await.unwrap();
+    assert_ne!(stake_history.get(warp_epoch), Some(&entry));
+    assert_eq!(stake_history.get(warp_epoch + 1), Some(&entry));
+    assert_eq!(stake_history.get(warp_epoch + 2), Some(&entry));
+}
+
 #[tokio::test]
 async fn stake_rewards_filter_bench_100() {
     stake_rewards_filter_bench_core(100).await;
@@ -0,0 +1,220 @@
+use {
+    assert_matches::assert_matches,
+    common::{add_upgradeable_loader_account, assert_ix_error, setup_test_context},
+    solana_program_test::*,
+    solana_sdk::{
+        account::{ReadableAccount, WritableAccount},
+        bpf_loader_upgradeable::{id, truncate_program_data, UpgradeableLoaderState},
+        instruction::InstructionError,
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+        transaction::Transaction,
+    },
+};
+
+mod common;
+
+const PADDING_BYTES: usize = 1_000;
+
+/// Adds a program with `PADDING_BYTES` of unused space after its data
+async fn add_padded_program(
+    context: &mut ProgramTestContext,
+    upgrade_authority_address: Option<Pubkey>,
+) -> (Pubkey, Pubkey, usize) {
+    let program_file = find_file("noop.so").expect("Failed to find the file");
+    let data = read_file(program_file);
+
+    let program_address = Pubkey::new_unique();
+    let (programdata_address, _) = Pubkey::find_program_address(&[program_address.as_ref()], &id());
+    add_upgradeable_loader_account(
+        context,
+        &program_address,
+        &UpgradeableLoaderState::Program {
+            programdata_address,
+        },
+        UpgradeableLoaderState::size_of_program(),
+        |_| {},
+    )
+    .await;
+    let programdata_data_offset = UpgradeableLoaderState::size_of_programdata_metadata();
+    add_upgradeable_loader_account(
+        context,
+        &programdata_address,
+        &UpgradeableLoaderState::ProgramData {
+            slot: 0,
+            upgrade_authority_address,
+        },
+        UpgradeableLoaderState::size_of_programdata(data.len() + PADDING_BYTES),
+        |account| {
+            account.data_as_mut_slice()[programdata_data_offset..][..data.len()]
+                .copy_from_slice(&data)
+        },
+    )
+    .await;
+    (program_address, programdata_address, data.len())
+}
+
+#[tokio::test]
+async fn test_truncate_program_data() {
+    let mut context = setup_test_context().await;
+    let upgrade_authority = Keypair::new();
+    let (program_address, programdata_address, program_len) =
+        add_padded_program(&mut context, Some(upgrade_authority.pubkey())).await;
+    let recipient_address = Pubkey::new_unique();
+
+    let client = &mut context.banks_client;
+    let payer = &context.payer;
+    let recent_blockhash = context.last_blockhash;
+    let rent = client.get_rent().await.unwrap();
+    let original_lamports = client
+        .get_account(programdata_address)
+        .await
+        .unwrap()
+        .unwrap()
+        .lamports();
+    let transaction = Transaction::new_signed_with_payer(
+        &[truncate_program_data(
+            &program_address,
+            &upgrade_authority.pubkey(),
+            &recipient_address,
+            program_len as u32,
+        )],
+        Some(&payer.pubkey()),
+        &[payer, &upgrade_authority],
+        recent_blockhash,
+    );
+
+    assert_matches!(client.process_transaction(transaction).await, Ok(()));
+    let new_len = UpgradeableLoaderState::size_of_programdata(program_len);
+    let updated_program_data_account = client
+        .get_account(programdata_address)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(updated_program_data_account.data().len(), new_len);
+    assert_eq!(
+        updated_program_data_account.lamports(),
+        rent.minimum_balance(new_len)
+    );
+    let recipient_account = client
+        .get_account(recipient_address)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        recipient_account.lamports(),
+        original_lamports - rent.minimum_balance(new_len)
+    );
+}
+
+#[tokio::test]
+async fn test_truncate_program_data_not_shrinking() {
+    let mut context = setup_test_context().await;
+    let upgrade_authority = Keypair::new();
+    let (program_address, _, program_len) =
+        add_padded_program(&mut context, Some(upgrade_authority.pubkey())).await;
+
+    assert_ix_error(
+        &mut context,
+        truncate_program_data(
+            &program_address,
+            &upgrade_authority.pubkey(),
+            &Pubkey::new_unique(),
+            (program_len + PADDING_BYTES) as u32,
+        ),
+        Some(&upgrade_authority),
+        InstructionError::InvalidRealloc,
+        "should fail because the program data would not shrink",
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_truncate_program_data_past_program_end() {
+    let mut context = setup_test_context().await;
+    let upgrade_authority = Keypair::new();
+    let (program_address, _, program_len) =
+        add_padded_program(&mut context, Some(upgrade_authority.pubkey())).await;
+
+    assert_ix_error(
+        &mut context,
+        truncate_program_data(
+            &program_address,
+            &upgrade_authority.pubkey(),
+            &Pubkey::new_unique(),
+            (program_len / 2) as u32,
+        ),
+        Some(&upgrade_authority),
+        InstructionError::InvalidAccountData,
+        "should fail because the truncated program can't be deployed",
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_truncate_program_data_not_upgradeable() {
+    let mut context = setup_test_context().await;
+    let upgrade_authority = Keypair::new();
+    let (program_address, _, program_len) = add_padded_program(&mut context, None).await;
+
+    assert_ix_error(
+        &mut context,
+        truncate_program_data(
+            &program_address,
+            &upgrade_authority.pubkey(),
+            &Pubkey::new_unique(),
+            program_len as u32,
+        ),
+        Some(&upgrade_authority),
+        InstructionError::Immutable,
+        "should fail because the program is not upgradeable",
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_truncate_program_data_with_wrong_authority() {
+    let mut context = setup_test_context().await;
+    let upgrade_authority = Keypair::new();
+    let (program_address, _, program_len) =
+        add_padded_program(&mut context, Some(upgrade_authority.pubkey())).await;
+    let wrong_authority = Keypair::new();
+
+    assert_ix_error(
+        &mut context,
+        truncate_program_data(
+            &program_address,
+            &wrong_authority.pubkey(),
+            &Pubkey::new_unique(),
+            program_len as u32,
+        ),
+        Some(&wrong_authority),
+        InstructionError::IncorrectAuthority,
+        "should fail because the authority does not match",
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_truncate_program_data_without_authority_signature() {
+    let mut context = setup_test_context().await;
+    let upgrade_authority = Keypair::new();
+    let (program_address, _, program_len) =
+        add_padded_program(&mut context, Some(upgrade_authority.pubkey())).await;
+
+    let mut instruction = truncate_program_data(
+        &program_address,
+        &upgrade_authority.pubkey(),
+        &Pubkey::new_unique(),
+        program_len as u32,
+    );
+    instruction.accounts[3].is_signer = false;
+    assert_ix_error(
+        &mut context,
+        instruction,
+        None,
+        InstructionError::MissingRequiredSignature,
+        "should fail because the authority did not sign",
+    )
+    .await;
+}
@@ -0,0 +1,196 @@
+//! Diagnostics for programs which fail to load or verify.
+//!
+//! A failed deployment returns [`InstructionError::InvalidAccountData`]
+//! whatever the cause, so the cause is logged instead, both as a plain log
+//! message and as a structured log which RPC decodes for simulation results.
+//!
+//! [`InstructionError::InvalidAccountData`]: solana_sdk::instruction::InstructionError::InvalidAccountData
+
+use {
+    solana_program_runtime::{ic_logger_msg, log_collector::LogCollector, stable_log},
+    solana_rbpf::{elf::ElfError, error::EbpfError, verifier::VerifierError},
+    solana_sdk::log::{encode_structured_log, LogLevel},
+    std::{cell::RefCell, error::Error, fmt, rc::Rc},
+};
+
+/// The target of the structured logs of failed deployments
+pub const DEPLOY_DIAGNOSTIC_TARGET: &str = "deploy";
+
+const REBUILD_HINT: &str = "rebuild the program with a supported release of the platform tools";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DeployStage {
+    /// Parsing, relocating and linking the ELF
+    Load,
+    /// Verifying the program's instructions
+    Verify,
+}
+
+impl DeployStage {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Load => "load",
+            Self::Verify => "verify",
+        }
+    }
+}
+
+/// Why a program failed to load or verify, and how its build might be fixed
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DeployDiagnostic {
+    pub stage: DeployStage,
+    pub error: String,
+    pub hint: Option<String>,
+}
+
+impl DeployDiagnostic {
+    /// Diagnose an error returned while loading or verifying a program
+    pub fn new(err: &(dyn Error + 'static)) -> Self {
+        if let Some(err) = err.downcast_ref::<ElfError>() {
+            Self::from_elf_error(err)
+        } else if let Some(EbpfError::ElfError(err)) = err.downcast_ref::<EbpfError>() {
+            Self::from_elf_error(err)
+        } else if let Some(err) = err.downcast_ref::<VerifierError>() {
+            Self::from_verifier_error(err)
+        } else {
+            Self {
+                stage: DeployStage::Load,
+                error: err.to_string(),
+                hint: None,
+            }
+        }
+    }
+
+    pub fn from_elf_error(err: &ElfError) -> Self {
+        let hint = match err {
+            ElfError::UnresolvedSymbol(symbol, _, _) => Some(format!(
+                "`{symbol}` is neither a function of the program nor a syscall enabled on this \
+                 cluster"
+            )),
+            ElfError::UnknownRelocation(_) => Some(REBUILD_HINT.to_string()),
+            ElfError::UnsupportedSBPFVersion => {
+                Some("the program's SBPF version is not enabled on this cluster".to_string())
+            }
+            _ => None,
+        };
+        Self {
+            stage: DeployStage::Load,
+            error: err.to_string(),
+            hint,
+        }
+    }
+
+    pub fn from_verifier_error(err: &VerifierError) -> Self {
+        let hint = match err {
+            VerifierError::CannotWriteR10(_) => Some(
+                "the frame pointer r10 is read-only, the stack may only be accessed relative to it"
+                    .to_string(),
+            ),
+            VerifierError::UnknownOpCode(_, _) => Some(REBUILD_HINT.to_string()),
+            _ => None,
+        };
+        Self {
+            stage: DeployStage::Verify,
+            error: err.to_string(),
+            hint,
+        }
+    }
+
+    /// Log the diagnostic, followed by its structured log
+    pub fn log(&self, log_collector: &Option<Rc<RefCell<LogCollector>>>) {
+        ic_logger_msg!(log_collector, "{}", self);
+        let mut fields = vec![("stage", self.stage.as_str())];
+        if let Some(hint) = &self.hint {
+            fields.push(("hint", hint.as_str()));
+        }
+        encode_structured_log(
+            LogLevel::Error,
+            DEPLOY_DIAGNOSTIC_TARGET,
+            &self.error,
+            &fields,
+            |data| stable_log::program_data(log_collector, data),
+        );
+    }
+}
+
+impl fmt::Display for DeployDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.stage {
+            DeployStage::Load => write!(f, "ELF error: {}", self.error)?,
+            DeployStage::Verify => write!(f, "Verifier error: {}", self.error)?,
+        }
+        if let Some(hint) = &self.hint {
+            write!(f, " ({hint})")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, solana_sdk::log::STRUCTURED_LOG_TAG};
+
+    #[test]
+    fn test_deploy_diagnostic() {
+        let err: Box<dyn Error> =
+            Box::new(ElfError::UnresolvedSymbol("sol_foo_".to_string(), 1, 2));
+        let diagnostic = DeployDiagnostic::new(err.as_ref());
+        assert_eq!(diagnostic.stage, DeployStage::Load);
+        assert!(diagnostic.hint.as_ref().unwrap().contains("`sol_foo_`"));
+        let err: Box<dyn Error> = Box::new(EbpfError::ElfError(ElfError::UnresolvedSymbol(
+            "sol_foo_".to_string(),
+            1,
+            2,
+        )));
+        assert_eq!(DeployDiagnostic::new(err.as_ref()), diagnostic);
+
+        let err: Box<dyn Error> = Box::new(VerifierError::CannotWriteR10(3));
+        let diagnostic = DeployDiagnostic::new(err.as_ref());
+        assert_eq!(diagnostic.stage, DeployStage::Verify);
+        assert!(diagnostic.to_string().starts_with("Verifier error: "));
+        assert!(diagnostic.hint.is_some());
+
+        let err: Box<dyn Error> = Box::new(VerifierError::NoProgram);
+        assert_eq!(DeployDiagnostic::new(err.as_ref()).hint, None);
+
+        let err: Box<dyn Error> = "something else".into();
+        assert_eq!(
+            DeployDiagnostic::new(err.as_ref()),
+            DeployDiagnostic {
+                stage: DeployStage::Load,
+                error: "something else".to_string(),
+                hint: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_deploy_diagnostic_log() {
+        let log_collector = Some(LogCollector::new_ref());
+        let diagnostic = DeployDiagnostic::from_verifier_error(&VerifierError::CannotWriteR10(3));
+        diagnostic.log(&log_collector);
+
+        let expected_log_collector = Some(LogCollector::new_ref());
+        ic_logger_msg!(expected_log_collector, "{}", diagnostic);
+        stable_log::program_data(
+            &expected_log_collector,
+            &[
+                STRUCTURED_LOG_TAG,
+                &[LogLevel::Error as u8],
+                b"deploy",
+                diagnostic.error.as_bytes(),
+                b"stage",
+                b"verify",
+                b"hint",
+                diagnostic.hint.as_ref().unwrap().as_bytes(),
+            ],
+        );
+        assert_eq!(
+            log_collector.unwrap().borrow().get_recorded_content(),
+            expected_log_collector
+                .unwrap()
+                .borrow()
+                .get_recorded_content(),
+        );
+    }
+}
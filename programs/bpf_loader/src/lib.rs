@@ -1,10 +1,12 @@
 #![deny(clippy::arithmetic_side_effects)]
 #![deny(clippy::indexing_slicing)]
 
+pub mod diagnostics;
 pub mod serialization;
 pub mod syscalls;
 
 use {
+    diagnostics::DeployDiagnostic,
     solana_measure::measure::Measure,
     solana_program_runtime::{
         ic_logger_msg, ic_msg,
@@ -35,8 +37,10 @@ use {
         entrypoint::{MAX_PERMITTED_DATA_INCREASE, SUCCESS},
         feature_set::{
             bpf_account_data_direct_mapping, deprecate_executable_meta_update_in_bpf_loader,
-            enable_bpf_loader_set_authority_checked_ix, FeatureSet,
+            enable_bpf_loader_set_authority_checked_ix, enable_bpf_loader_truncate_program_data_ix,
+            enable_bpf_loader_verify_buffer_hash_ix, FeatureSet,
         },
+        hash::hash,
         instruction::{AccountMeta, InstructionError},
         loader_upgradeable_instruction::UpgradeableLoaderInstruction,
         native_loader,
@@ -96,7 +100,7 @@ pub fn load_program_from_bytes(
         )
     }
     .map_err(|err| {
-        ic_logger_msg!(log_collector, "{}", err);
+        DeployDiagnostic::new(err.as_ref()).log(&log_collector);
         InstructionError::InvalidAccountData
     })?;
     Ok(loaded_program)
@@ -124,14 +128,14 @@ macro_rules! deploy_program {
             $new_programdata,
             Arc::new(deployment_program_runtime_environment),
         ).map_err(|err| {
-            ic_logger_msg!($invoke_context.get_log_collector(), "{}", err);
+            DeployDiagnostic::from_elf_error(&err).log(&$invoke_context.get_log_collector());
             InstructionError::InvalidAccountData
         })?;
         load_elf_time.stop();
         load_program_metrics.load_elf_us = load_elf_time.as_us();
         let mut verify_code_time = Measure::start("verify_code_time");
         executable.verify::<RequisiteVerifier>().map_err(|err| {
-            ic_logger_msg!($invoke_context.get_log_collector(), "{}", err);
+            DeployDiagnostic::from_verifier_error(&err).log(&$invoke_context.get_log_collector());
             InstructionError::InvalidAccountData
         })?;
         verify_code_time.stop();
@@ -1311,6 +1315,241 @@ fn process_loader_upgradeable_instruction(
                 additional_bytes
             );
         }
+        UpgradeableLoaderInstruction::TruncateProgramData { max_data_len } => {
+            if !invoke_context
+                .feature_set
+                .is_active(&enable_bpf_loader_truncate_program_data_ix::id())
+            {
+                return Err(InstructionError::InvalidInstructionData);
+            }
+
+            const PROGRAM_DATA_ACCOUNT_INDEX: IndexOfAccount = 0;
+            const PROGRAM_ACCOUNT_INDEX: IndexOfAccount = 1;
+            const RECIPIENT_ACCOUNT_INDEX: IndexOfAccount = 2;
+            const AUTHORITY_ACCOUNT_INDEX: IndexOfAccount = 3;
+
+            instruction_context.check_number_of_instruction_accounts(4)?;
+            if instruction_context
+                .get_index_of_instruction_account_in_transaction(PROGRAM_DATA_ACCOUNT_INDEX)?
+                == instruction_context
+                    .get_index_of_instruction_account_in_transaction(RECIPIENT_ACCOUNT_INDEX)?
+            {
+                ic_logger_msg!(
+                    log_collector,
+                    "Recipient is the same as the ProgramData account"
+                );
+                return Err(InstructionError::InvalidArgument);
+            }
+            let authority_key = Some(
+                *transaction_context.get_key_of_account_at_index(
+                    instruction_context
+                        .get_index_of_instruction_account_in_transaction(AUTHORITY_ACCOUNT_INDEX)?,
+                )?,
+            );
+
+            let programdata_account = instruction_context
+                .try_borrow_instruction_account(transaction_context, PROGRAM_DATA_ACCOUNT_INDEX)?;
+            let programdata_key = *programdata_account.get_key();
+
+            if program_id != programdata_account.get_owner() {
+                ic_logger_msg!(log_collector, "ProgramData owner is invalid");
+                return Err(InstructionError::InvalidAccountOwner);
+            }
+            if !programdata_account.is_writable() {
+                ic_logger_msg!(log_collector, "ProgramData is not writable");
+                return Err(InstructionError::InvalidArgument);
+            }
+
+            let program_account = instruction_context
+                .try_borrow_instruction_account(transaction_context, PROGRAM_ACCOUNT_INDEX)?;
+            if !program_account.is_writable() {
+                ic_logger_msg!(log_collector, "Program account is not writable");
+                return Err(InstructionError::InvalidArgument);
+            }
+            if program_account.get_owner() != program_id {
+                ic_logger_msg!(log_collector, "Program account not owned by loader");
+                return Err(InstructionError::InvalidAccountOwner);
+            }
+            let program_key = *program_account.get_key();
+            match program_account.get_state()? {
+                UpgradeableLoaderState::Program {
+                    programdata_address,
+                } => {
+                    if programdata_address != programdata_key {
+                        ic_logger_msg!(
+                            log_collector,
+                            "Program account does not match ProgramData account"
+                        );
+                        return Err(InstructionError::InvalidArgument);
+                    }
+                }
+                _ => {
+                    ic_logger_msg!(log_collector, "Invalid Program account");
+                    return Err(InstructionError::InvalidAccountData);
+                }
+            }
+            drop(program_account);
+
+            let old_len = programdata_account.get_data().len();
+            let new_len = UpgradeableLoaderState::size_of_programdata(max_data_len as usize);
+            if max_data_len == 0 || new_len >= old_len {
+                ic_logger_msg!(
+                    log_collector,
+                    "Truncated ProgramData length of {} bytes must be nonzero and less than the current length of {} bytes",
+                    new_len,
+                    old_len
+                );
+                return Err(InstructionError::InvalidRealloc);
+            }
+
+            let clock_slot = invoke_context
+                .get_sysvar_cache()
+                .get_clock()
+                .map(|clock| clock.slot)?;
+
+            let upgrade_authority_address = if let UpgradeableLoaderState::ProgramData {
+                slot,
+                upgrade_authority_address,
+            } = programdata_account.get_state()?
+            {
+                if clock_slot == slot {
+                    ic_logger_msg!(log_collector, "Program was deployed in this block already");
+                    return Err(InstructionError::InvalidArgument);
+                }
+                if upgrade_authority_address.is_none() {
+                    ic_logger_msg!(
+                        log_collector,
+                        "Cannot truncate ProgramData accounts that are not upgradeable"
+                    );
+                    return Err(InstructionError::Immutable);
+                }
+                if upgrade_authority_address != authority_key {
+                    ic_logger_msg!(log_collector, "Incorrect upgrade authority provided");
+                    return Err(InstructionError::IncorrectAuthority);
+                }
+                if !instruction_context.is_instruction_account_signer(AUTHORITY_ACCOUNT_INDEX)? {
+                    ic_logger_msg!(log_collector, "Upgrade authority did not sign");
+                    return Err(InstructionError::MissingRequiredSignature);
+                }
+                upgrade_authority_address
+            } else {
+                ic_logger_msg!(log_collector, "ProgramData state is invalid");
+                return Err(InstructionError::InvalidAccountData);
+            };
+            drop(programdata_account);
+
+            let mut programdata_account = instruction_context
+                .try_borrow_instruction_account(transaction_context, PROGRAM_DATA_ACCOUNT_INDEX)?;
+            programdata_account.set_data_length(new_len, &invoke_context.feature_set)?;
+
+            let programdata_data_offset = UpgradeableLoaderState::size_of_programdata_metadata();
+
+            deploy_program!(
+                invoke_context,
+                program_key,
+                program_id,
+                UpgradeableLoaderState::size_of_program().saturating_add(new_len),
+                clock_slot,
+                {
+                    drop(programdata_account);
+                },
+                programdata_account
+                    .get_data()
+                    .get(programdata_data_offset..)
+                    .ok_or(InstructionError::AccountDataTooSmall)?,
+            );
+
+            let mut programdata_account = instruction_context
+                .try_borrow_instruction_account(transaction_context, PROGRAM_DATA_ACCOUNT_INDEX)?;
+            programdata_account.set_state(
+                &UpgradeableLoaderState::ProgramData {
+                    slot: clock_slot,
+                    upgrade_authority_address,
+                },
+                &invoke_context.feature_set,
+            )?;
+
+            let refund = {
+                let rent = invoke_context.get_sysvar_cache().get_rent()?;
+                let min_balance = rent.minimum_balance(new_len).max(1);
+                programdata_account
+                    .get_lamports()
+                    .saturating_sub(min_balance)
+            };
+            programdata_account.checked_sub_lamports(refund, &invoke_context.feature_set)?;
+            drop(programdata_account);
+            let mut recipient_account = instruction_context
+                .try_borrow_instruction_account(transaction_context, RECIPIENT_ACCOUNT_INDEX)?;
+            recipient_account.checked_add_lamports(refund, &invoke_context.feature_set)?;
+
+            ic_logger_msg!(
+                log_collector,
+                "Truncated ProgramData account by {} bytes",
+                old_len.saturating_sub(new_len)
+            );
+        }
+        UpgradeableLoaderInstruction::VerifyBufferHash {
+            expected_hash,
+            program_len,
+        } => {
+            if !invoke_context
+                .feature_set
+                .is_active(&enable_bpf_loader_verify_buffer_hash_ix::id())
+            {
+                return Err(InstructionError::InvalidInstructionData);
+            }
+
+            instruction_context.check_number_of_instruction_accounts(1)?;
+            let buffer =
+                instruction_context.try_borrow_instruction_account(transaction_context, 0)?;
+            if buffer.get_owner() != program_id {
+                ic_logger_msg!(log_collector, "Buffer account not owned by loader");
+                return Err(InstructionError::IncorrectProgramId);
+            }
+            if !matches!(buffer.get_state()?, UpgradeableLoaderState::Buffer { .. }) {
+                ic_logger_msg!(log_collector, "Invalid Buffer account");
+                return Err(InstructionError::InvalidArgument);
+            }
+            let buffer_data = buffer
+                .get_data()
+                .get(UpgradeableLoaderState::size_of_buffer_metadata()..)
+                .ok_or(InstructionError::AccountDataTooSmall)?;
+
+            let compute_budget = invoke_context.get_compute_budget();
+            let hash_cost = compute_budget.sha256_base_cost.saturating_add(
+                compute_budget
+                    .sha256_byte_cost
+                    .saturating_mul((buffer_data.len() as u64).saturating_div(2)),
+            );
+            invoke_context
+                .consume_checked(hash_cost)
+                .map_err(|_| InstructionError::ComputationalBudgetExceeded)?;
+
+            let program_len = program_len as usize;
+            let (program_data, padding) = if program_len <= buffer_data.len() {
+                buffer_data.split_at(program_len)
+            } else {
+                ic_logger_msg!(log_collector, "Buffer data too small for program length");
+                return Err(InstructionError::AccountDataTooSmall);
+            };
+            if padding.iter().any(|byte| *byte != 0) {
+                ic_logger_msg!(
+                    log_collector,
+                    "Buffer data past the program length is not zero"
+                );
+                return Err(InstructionError::InvalidAccountData);
+            }
+            let buffer_hash = hash(program_data);
+            if buffer_hash != expected_hash {
+                ic_logger_msg!(
+                    log_collector,
+                    "Buffer hash {} does not match expected hash {}",
+                    buffer_hash,
+                    expected_hash
+                );
+                return Err(InstructionError::InvalidAccountData);
+            }
+        }
     }
 
     Ok(())
@@ -1405,6 +1644,7 @@ fn execute<'a, 'b: 'a>(
 
     let mut create_vm_time = Measure::start("create_vm");
     let mut execute_time;
+    let heap_used;
     let execution_result = {
         let compute_meter_prev = invoke_context.get_remaining();
         create_vm!(vm, executable, regions, accounts_metadata, invoke_context,);
@@ -1419,6 +1659,7 @@ fn execute<'a, 'b: 'a>(
 
         execute_time = Measure::start("execute");
         let (compute_units_consumed, result) = vm.execute_program(executable, !use_jit);
+        heap_used = estimate_heap_usage(&vm.memory_mapping, vm.context_object_pointer);
         drop(vm);
         ic_logger_msg!(
             log_collector,
@@ -1518,10 +1759,40 @@ fn execute<'a, 'b: 'a>(
     timings.deserialize_us = timings
         .deserialize_us
         .saturating_add(deserialize_time.as_us());
+    timings.heap_high_water_mark = timings.heap_high_water_mark.max(heap_used);
 
     execute_or_deserialize_result
 }
 
+/// Estimates how many bytes of the heap a program used, either through the
+/// `sol_alloc_free_` syscall, or through the default bump allocator of the
+/// program entrypoint, which allocates downwards from the end of the heap and
+/// stores its position at the start of the heap
+fn estimate_heap_usage(memory_mapping: &MemoryMapping, invoke_context: &InvokeContext) -> u64 {
+    let heap_end =
+        MM_HEAP_START.saturating_add(u64::from(invoke_context.get_compute_budget().heap_size));
+    let bump_allocated = match memory_mapping.map(
+        AccessType::Load,
+        MM_HEAP_START,
+        mem::size_of::<u64>() as u64,
+    ) {
+        ProgramResult::Ok(host_addr) => {
+            let pos = unsafe { std::ptr::read_unaligned(host_addr as *const u64) };
+            if (MM_HEAP_START..=heap_end).contains(&pos) {
+                heap_end.saturating_sub(pos)
+            } else {
+                0
+            }
+        }
+        ProgramResult::Err(_) => 0,
+    };
+    let syscall_allocated = invoke_context
+        .get_syscall_context()
+        .map(|syscall_context| syscall_context.allocator.used())
+        .unwrap_or_default();
+    bump_allocated.max(syscall_allocated)
+}
+
 pub mod test_utils {
     use {
         super::*, solana_program_runtime::loaded_programs::DELAY_VISIBILITY_SLOT_OFFSET,
@@ -1581,7 +1852,8 @@ mod tests {
         assert_matches::assert_matches,
         rand::Rng,
         solana_program_runtime::{
-            invoke_context::mock_process_instruction, with_mock_invoke_context,
+            compute_budget::ComputeBudget, invoke_context::mock_process_instruction,
+            with_mock_invoke_context,
         },
         solana_rbpf::vm::ContextObject,
         solana_sdk::{
@@ -1591,6 +1863,7 @@ mod tests {
             },
             account_utils::StateMut,
             clock::Clock,
+            feature_set::enable_sbpf_v2_deployment_and_execution,
             instruction::{AccountMeta, InstructionError},
             pubkey::Pubkey,
             rent::Rent,
@@ -1823,6 +2096,41 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_sbpf_version_feature_gate() {
+        let mut file = File::open("test_elfs/out/noop_aligned.so").expect("file open failed");
+        let mut elf = Vec::new();
+        file.read_to_end(&mut elf).unwrap();
+        // tag the program as SBPFv2 through the `e_flags` of its ELF header
+        const E_FLAGS_OFFSET: usize = 48;
+        const EF_SBPF_V2: u32 = 0x20;
+        elf[E_FLAGS_OFFSET..E_FLAGS_OFFSET + 4].copy_from_slice(&EF_SBPF_V2.to_le_bytes());
+
+        let mut feature_set = FeatureSet::all_enabled();
+        feature_set.deactivate(&enable_sbpf_v2_deployment_and_execution::id());
+        for deployment in [true, false] {
+            let environment = create_program_runtime_environment_v1(
+                &feature_set,
+                &ComputeBudget::default(),
+                deployment,
+                false, /* debugging_features */
+            )
+            .unwrap();
+            assert!(!environment.get_config().enable_sbpf_v2);
+            assert!(Executable::<InvokeContext>::load(&elf, Arc::new(environment)).is_err());
+        }
+
+        let environment = create_program_runtime_environment_v1(
+            &FeatureSet::all_enabled(),
+            &ComputeBudget::default(),
+            true,  /* deployment */
+            false, /* debugging_features */
+        )
+        .unwrap();
+        assert!(environment.get_config().enable_sbpf_v1);
+        assert!(environment.get_config().enable_sbpf_v2);
+    }
+
     #[test]
     fn test_bpf_loader_upgradeable_initialize_buffer() {
         let loader_id = bpf_loader_upgradeable::id();
@@ -3423,6 +3731,142 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_bpf_loader_upgradeable_verify_buffer_hash() {
+        let loader_id = bpf_loader_upgradeable::id();
+        let program_data = b"program data";
+        let program_len = program_data.len() as u32;
+        let buffer_address = Pubkey::new_unique();
+        let mut buffer_account = AccountSharedData::new(
+            1,
+            UpgradeableLoaderState::size_of_buffer(program_data.len()),
+            &loader_id,
+        );
+        buffer_account
+            .set_state(&UpgradeableLoaderState::Buffer {
+                authority_address: Some(Pubkey::new_unique()),
+            })
+            .unwrap();
+        buffer_account
+            .data_as_mut_slice()
+            .get_mut(UpgradeableLoaderState::size_of_buffer_metadata()..)
+            .unwrap()
+            .copy_from_slice(program_data);
+        let instruction_accounts = vec![AccountMeta {
+            pubkey: buffer_address,
+            is_signer: false,
+            is_writable: false,
+        }];
+        let verify_buffer_hash = |expected_hash, program_len, buffer_account, expected_result| {
+            process_instruction(
+                &loader_id,
+                &[],
+                &bincode::serialize(&UpgradeableLoaderInstruction::VerifyBufferHash {
+                    expected_hash,
+                    program_len,
+                })
+                .unwrap(),
+                vec![(buffer_address, buffer_account)],
+                instruction_accounts.clone(),
+                expected_result,
+            )
+        };
+
+        // Case: Hash matches
+        verify_buffer_hash(
+            hash(program_data),
+            program_len,
+            buffer_account.clone(),
+            Ok(()),
+        );
+
+        // Case: Hash mismatch
+        verify_buffer_hash(
+            hash(b"other program data"),
+            program_len,
+            buffer_account.clone(),
+            Err(InstructionError::InvalidAccountData),
+        );
+
+        // Case: Program length exceeds the buffer
+        verify_buffer_hash(
+            hash(program_data),
+            program_len.saturating_add(1),
+            buffer_account.clone(),
+            Err(InstructionError::AccountDataTooSmall),
+        );
+
+        // Case: Buffer padded past the program, as with a maximum length
+        let mut padded_account = AccountSharedData::new(
+            1,
+            UpgradeableLoaderState::size_of_buffer(program_data.len().saturating_mul(2)),
+            &loader_id,
+        );
+        padded_account
+            .set_state(&UpgradeableLoaderState::Buffer {
+                authority_address: Some(Pubkey::new_unique()),
+            })
+            .unwrap();
+        padded_account
+            .data_as_mut_slice()
+            .get_mut(UpgradeableLoaderState::size_of_buffer_metadata()..)
+            .unwrap()
+            .get_mut(..program_data.len())
+            .unwrap()
+            .copy_from_slice(program_data);
+        verify_buffer_hash(
+            hash(program_data),
+            program_len,
+            padded_account.clone(),
+            Ok(()),
+        );
+
+        // Case: Program ending in zero bytes, which only its length tells
+        // apart from the padding
+        let zero_terminated_program_data = [program_data.as_slice(), &[0]].concat();
+        verify_buffer_hash(
+            hash(&zero_terminated_program_data),
+            program_len.saturating_add(1),
+            padded_account.clone(),
+            Ok(()),
+        );
+
+        // Case: Non-zero data past the program length
+        let mut trailing_account = padded_account;
+        *trailing_account.data_as_mut_slice().last_mut().unwrap() = 1;
+        verify_buffer_hash(
+            hash(program_data),
+            program_len,
+            trailing_account,
+            Err(InstructionError::InvalidAccountData),
+        );
+
+        // Case: Not a buffer
+        let mut programdata_account = buffer_account.clone();
+        programdata_account
+            .set_state(&UpgradeableLoaderState::ProgramData {
+                slot: 0,
+                upgrade_authority_address: None,
+            })
+            .unwrap();
+        verify_buffer_hash(
+            hash(program_data),
+            program_len,
+            programdata_account,
+            Err(InstructionError::InvalidArgument),
+        );
+
+        // Case: Not owned by the loader
+        let mut foreign_account = buffer_account;
+        foreign_account.set_owner(Pubkey::new_unique());
+        verify_buffer_hash(
+            hash(program_data),
+            program_len,
+            foreign_account,
+            Err(InstructionError::IncorrectProgramId),
+        );
+    }
+
     #[test]
     fn test_bpf_loader_upgradeable_close() {
         let instruction = bincode::serialize(&UpgradeableLoaderInstruction::Close).unwrap();
@@ -8,7 +8,10 @@ use {
         memory_region::{MemoryRegion, MemoryState},
     },
     solana_sdk::{
-        feature_set::{enable_bpf_loader_set_authority_checked_ix, FeatureSet},
+        feature_set::{
+            enable_bpf_loader_set_authority_checked_ix, enable_bpf_loader_truncate_program_data_ix,
+            enable_bpf_loader_verify_buffer_hash_ix, FeatureSet,
+        },
         stable_layout::stable_instruction::StableInstruction,
         syscalls::{
             MAX_CPI_ACCOUNT_INFOS, MAX_CPI_INSTRUCTION_ACCOUNTS, MAX_CPI_INSTRUCTION_DATA_LEN,
@@ -169,6 +172,7 @@ impl<'a, 'b> CallerAccount<'a, 'b> {
 
             consume_compute_meter(
                 invoke_context,
+                SyscallCategory::Cpi,
                 (data.len() as u64)
                     .checked_div(invoke_context.get_compute_budget().cpi_bytes_per_unit)
                     .unwrap_or(u64::MAX),
@@ -292,6 +296,7 @@ impl<'a, 'b> CallerAccount<'a, 'b> {
 
         consume_compute_meter(
             invoke_context,
+            SyscallCategory::Cpi,
             account_info
                 .data_len
                 .checked_div(invoke_context.get_compute_budget().cpi_bytes_per_unit)
@@ -457,6 +462,7 @@ impl SyscallInvokeSigned for SyscallInvokeSignedRust {
         {
             consume_compute_meter(
                 invoke_context,
+                SyscallCategory::Cpi,
                 (ix_data_len)
                     .checked_div(invoke_context.get_compute_budget().cpi_bytes_per_unit)
                     .unwrap_or(u64::MAX),
@@ -671,6 +677,7 @@ impl SyscallInvokeSigned for SyscallInvokeSignedC {
         {
             consume_compute_meter(
                 invoke_context,
+                SyscallCategory::Cpi,
                 (ix_data_len)
                     .checked_div(invoke_context.get_compute_budget().cpi_bytes_per_unit)
                     .unwrap_or(u64::MAX),
@@ -887,6 +894,7 @@ where
             // Use the known account
             consume_compute_meter(
                 invoke_context,
+                SyscallCategory::Cpi,
                 (callee_account.get_data().len() as u64)
                     .checked_div(invoke_context.get_compute_budget().cpi_bytes_per_unit)
                     .unwrap_or(u64::MAX),
@@ -1046,6 +1054,18 @@ fn check_authorized_program(
                     && bpf_loader_upgradeable::is_set_authority_checked_instruction(
                         instruction_data,
                     ))
+                || (invoke_context
+                    .feature_set
+                    .is_active(&enable_bpf_loader_verify_buffer_hash_ix::id())
+                    && bpf_loader_upgradeable::is_verify_buffer_hash_instruction(
+                        instruction_data,
+                    ))
+                || (invoke_context
+                    .feature_set
+                    .is_active(&enable_bpf_loader_truncate_program_data_ix::id())
+                    && bpf_loader_upgradeable::is_truncate_program_data_instruction(
+                        instruction_data,
+                    ))
                 || bpf_loader_upgradeable::is_close_instruction(instruction_data)))
         || is_precompile(program_id, |feature_id: &Pubkey| {
             invoke_context.feature_set.is_active(feature_id)
@@ -1072,6 +1092,7 @@ fn cpi_common<S: SyscallInvokeSigned>(
     // changes so the callee can see them.
     consume_compute_meter(
         invoke_context,
+        SyscallCategory::Cpi,
         invoke_context.get_compute_budget().invoke_units,
     )?;
 
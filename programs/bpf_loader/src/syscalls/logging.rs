@@ -16,7 +16,7 @@ declare_builtin_function!(
             .get_compute_budget()
             .syscall_base_cost
             .max(len);
-        consume_compute_meter(invoke_context, cost)?;
+        consume_compute_meter(invoke_context, SyscallCategory::Logging, cost)?;
 
         translate_string_and_do(
             memory_mapping,
@@ -45,7 +45,7 @@ declare_builtin_function!(
         _memory_mapping: &mut MemoryMapping,
     ) -> Result<u64, Error> {
         let cost = invoke_context.get_compute_budget().log_64_units;
-        consume_compute_meter(invoke_context, cost)?;
+        consume_compute_meter(invoke_context, SyscallCategory::Logging, cost)?;
 
         stable_log::program_log(
             &invoke_context.get_log_collector(),
@@ -68,7 +68,7 @@ declare_builtin_function!(
         _memory_mapping: &mut MemoryMapping,
     ) -> Result<u64, Error> {
         let cost = invoke_context.get_compute_budget().syscall_base_cost;
-        consume_compute_meter(invoke_context, cost)?;
+        consume_compute_meter(invoke_context, SyscallCategory::Logging, cost)?;
 
         ic_logger_msg!(
             invoke_context.get_log_collector(),
@@ -92,7 +92,7 @@ declare_builtin_function!(
         memory_mapping: &mut MemoryMapping,
     ) -> Result<u64, Error> {
         let cost = invoke_context.get_compute_budget().log_pubkey_units;
-        consume_compute_meter(invoke_context, cost)?;
+        consume_compute_meter(invoke_context, SyscallCategory::Logging, cost)?;
 
         let pubkey = translate_type::<Pubkey>(
             memory_mapping,
@@ -118,7 +118,11 @@ declare_builtin_function!(
     ) -> Result<u64, Error> {
         let budget = invoke_context.get_compute_budget();
 
-        consume_compute_meter(invoke_context, budget.syscall_base_cost)?;
+        consume_compute_meter(
+            invoke_context,
+            SyscallCategory::Logging,
+            budget.syscall_base_cost,
+        )?;
 
         let untranslated_fields = translate_slice::<&[u8]>(
             memory_mapping,
@@ -129,12 +133,14 @@ declare_builtin_function!(
 
         consume_compute_meter(
             invoke_context,
+            SyscallCategory::Logging,
             budget
                 .syscall_base_cost
                 .saturating_mul(untranslated_fields.len() as u64),
         )?;
         consume_compute_meter(
             invoke_context,
+            SyscallCategory::Logging,
             untranslated_fields
                 .iter()
                 .fold(0, |total, e| total.saturating_add(e.len() as u64)),
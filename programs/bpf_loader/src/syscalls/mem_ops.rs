@@ -10,7 +10,7 @@ fn mem_op_consume(invoke_context: &mut InvokeContext, n: u64) -> Result<(), Erro
         n.checked_div(compute_budget.cpi_bytes_per_unit)
             .unwrap_or(u64::MAX),
     );
-    consume_compute_meter(invoke_context, cost)
+    consume_compute_meter(invoke_context, SyscallCategory::Memory, cost)
 }
 
 declare_builtin_function!(
@@ -7,13 +7,17 @@ pub use self::{
     sysvar::{
         SyscallGetClockSysvar, SyscallGetEpochRewardsSysvar, SyscallGetEpochScheduleSysvar,
         SyscallGetFeesSysvar, SyscallGetLastRestartSlotSysvar, SyscallGetRentSysvar,
+        SyscallGetSysvar,
     },
 };
 #[allow(deprecated)]
 use {
     solana_program_runtime::{
-        compute_budget::ComputeBudget, ic_logger_msg, ic_msg, invoke_context::InvokeContext,
-        stable_log, timings::ExecuteTimings,
+        compute_budget::ComputeBudget,
+        ic_logger_msg, ic_msg,
+        invoke_context::InvokeContext,
+        stable_log,
+        timings::{ExecuteTimings, SyscallCategory},
     },
     solana_rbpf::{
         declare_builtin_function,
@@ -37,7 +41,10 @@ use {
             self, blake3_syscall_enabled, curve25519_syscall_enabled,
             disable_deploy_of_alloc_free_syscall, disable_fees_sysvar,
             enable_alt_bn128_compression_syscall, enable_alt_bn128_syscall,
-            enable_big_mod_exp_syscall, enable_partitioned_epoch_reward, enable_poseidon_syscall,
+            enable_batched_hash_syscalls, enable_big_mod_exp_syscall,
+            enable_get_caller_program_id_syscall,
+            enable_get_minimum_balance_for_rent_exemption_syscall, enable_partitioned_epoch_reward,
+            enable_poseidon_syscall, enable_sbpf_v2_deployment_and_execution, epoch_stake_sysvar,
             error_on_syscall_bpf_function_hash_collisions, last_restart_slot_sysvar,
             reject_callx_r10, remaining_compute_units_syscall_enabled, switch_to_new_elf_parser,
         },
@@ -51,7 +58,10 @@ use {
         secp256k1_recover::{
             Secp256k1RecoverError, SECP256K1_PUBLIC_KEY_LENGTH, SECP256K1_SIGNATURE_LENGTH,
         },
-        sysvar::{Sysvar, SysvarId},
+        system_instruction::MAX_PERMITTED_DATA_LENGTH,
+        sysvar::{
+            Sysvar, SysvarId, OFFSET_EXCEEDS_SYSVAR, OFFSET_LENGTH_EXCEEDS_SYSVAR, SYSVAR_NOT_FOUND,
+        },
         transaction_context::{IndexOfAccount, InstructionAccount},
     },
     std::{
@@ -123,6 +133,8 @@ pub enum SyscallError {
     InvalidPointer,
     #[error("Arithmetic overflow")]
     ArithmeticOverflow,
+    #[error("Invalid incremental hasher state")]
+    InvalidHasherState,
 }
 
 type Error = Box<dyn std::error::Error>;
@@ -187,13 +199,13 @@ impl HasherImpl for Blake3Hasher {
     }
 
     fn get_base_cost(compute_budget: &ComputeBudget) -> u64 {
-        compute_budget.sha256_base_cost
+        compute_budget.blake3_base_cost
     }
     fn get_byte_cost(compute_budget: &ComputeBudget) -> u64 {
-        compute_budget.sha256_byte_cost
+        compute_budget.blake3_byte_cost
     }
     fn get_max_slices(compute_budget: &ComputeBudget) -> u64 {
-        compute_budget.sha256_max_slices
+        compute_budget.blake3_max_slices
     }
 }
 
@@ -214,18 +226,82 @@ impl HasherImpl for Keccak256Hasher {
     }
 
     fn get_base_cost(compute_budget: &ComputeBudget) -> u64 {
-        compute_budget.sha256_base_cost
+        compute_budget.keccak256_base_cost
     }
     fn get_byte_cost(compute_budget: &ComputeBudget) -> u64 {
-        compute_budget.sha256_byte_cost
+        compute_budget.keccak256_byte_cost
     }
     fn get_max_slices(compute_budget: &ComputeBudget) -> u64 {
-        compute_budget.sha256_max_slices
+        compute_budget.keccak256_max_slices
+    }
+}
+
+/// A hasher whose state lives in program memory, see `SyscallHashUpdate`
+pub trait IncrementalHasherImpl: Copy {
+    const NAME: &'static str;
+    type Output: AsRef<[u8]>;
+
+    fn try_update(&mut self, val: &[u8]) -> Result<(), SyscallError>;
+    fn try_finalize(&self) -> Result<Self::Output, SyscallError>;
+    fn get_base_cost(compute_budget: &ComputeBudget) -> u64;
+    fn get_byte_cost(compute_budget: &ComputeBudget) -> u64;
+    fn get_max_slices(compute_budget: &ComputeBudget) -> u64;
+}
+
+impl IncrementalHasherImpl for blake3::IncrementalHasher {
+    const NAME: &'static str = "Blake3";
+    type Output = blake3::Hash;
+
+    fn try_update(&mut self, val: &[u8]) -> Result<(), SyscallError> {
+        blake3::IncrementalHasher::try_update(self, &[val])
+            .map_err(|_| SyscallError::InvalidHasherState)
+    }
+
+    fn try_finalize(&self) -> Result<Self::Output, SyscallError> {
+        blake3::IncrementalHasher::try_finalize(self).map_err(|_| SyscallError::InvalidHasherState)
+    }
+
+    fn get_base_cost(compute_budget: &ComputeBudget) -> u64 {
+        compute_budget.blake3_base_cost
+    }
+    fn get_byte_cost(compute_budget: &ComputeBudget) -> u64 {
+        compute_budget.blake3_byte_cost
+    }
+    fn get_max_slices(compute_budget: &ComputeBudget) -> u64 {
+        compute_budget.blake3_max_slices
+    }
+}
+
+impl IncrementalHasherImpl for keccak::IncrementalHasher {
+    const NAME: &'static str = "Keccak256";
+    type Output = keccak::Hash;
+
+    fn try_update(&mut self, val: &[u8]) -> Result<(), SyscallError> {
+        keccak::IncrementalHasher::try_update(self, &[val])
+            .map_err(|_| SyscallError::InvalidHasherState)
+    }
+
+    fn try_finalize(&self) -> Result<Self::Output, SyscallError> {
+        keccak::IncrementalHasher::try_finalize(self).map_err(|_| SyscallError::InvalidHasherState)
+    }
+
+    fn get_base_cost(compute_budget: &ComputeBudget) -> u64 {
+        compute_budget.keccak256_base_cost
+    }
+    fn get_byte_cost(compute_budget: &ComputeBudget) -> u64 {
+        compute_budget.keccak256_byte_cost
+    }
+    fn get_max_slices(compute_budget: &ComputeBudget) -> u64 {
+        compute_budget.keccak256_max_slices
     }
 }
 
-fn consume_compute_meter(invoke_context: &InvokeContext, amount: u64) -> Result<(), Error> {
-    invoke_context.consume_checked(amount)?;
+fn consume_compute_meter(
+    invoke_context: &InvokeContext,
+    category: SyscallCategory,
+    amount: u64,
+) -> Result<(), Error> {
+    invoke_context.consume_syscall_units(category, amount)?;
     Ok(())
 }
 
@@ -260,6 +336,12 @@ pub fn create_program_runtime_environment_v1<'a>(
     let enable_poseidon_syscall = feature_set.is_active(&enable_poseidon_syscall::id());
     let remaining_compute_units_syscall_enabled =
         feature_set.is_active(&remaining_compute_units_syscall_enabled::id());
+    let get_caller_program_id_syscall_enabled =
+        feature_set.is_active(&enable_get_caller_program_id_syscall::id());
+    let get_sysvar_syscall_enabled = feature_set.is_active(&epoch_stake_sysvar::id());
+    let get_minimum_balance_for_rent_exemption_syscall_enabled =
+        feature_set.is_active(&enable_get_minimum_balance_for_rent_exemption_syscall::id());
+    let batched_hash_syscalls_enabled = feature_set.is_active(&enable_batched_hash_syscalls::id());
     // !!! ATTENTION !!!
     // When adding new features for RBPF here,
     // also add them to `Bank::apply_builtin_program_feature_transitions()`.
@@ -280,7 +362,16 @@ pub fn create_program_runtime_environment_v1<'a>(
             .is_active(&error_on_syscall_bpf_function_hash_collisions::id()),
         reject_callx_r10: feature_set.is_active(&reject_callx_r10::id()),
         enable_sbpf_v1: true,
-        enable_sbpf_v2: false,
+        // The SBPF version of a program is the one the toolchain compiled it
+        // for, which it records in the `e_flags` of the ELF header. The ELF is
+        // stored verbatim in the program account and carried over by every
+        // deployment and upgrade, while the loader's account metadata has no
+        // version field to keep it in, and could not gain one without changing
+        // the layout of every existing program account. So the version is read
+        // from the ELF each time a program is loaded, and this only gates which
+        // versions are accepted: the same environment loads and verifies each
+        // program under the rules of the version it was compiled for.
+        enable_sbpf_v2: feature_set.is_active(&enable_sbpf_v2_deployment_and_execution::id()),
         optimize_rodata: false,
         new_elf_parser: feature_set.is_active(&switch_to_new_elf_parser::id()),
         aligned_memory_mapping: !feature_set.is_active(&bpf_account_data_direct_mapping::id()),
@@ -327,6 +418,44 @@ pub fn create_program_runtime_environment_v1<'a>(
         SyscallHash::vm::<Blake3Hasher>,
     )?;
 
+    // Batched and incremental hashing
+    register_feature_gated_function!(
+        result,
+        batched_hash_syscalls_enabled,
+        *b"sol_blake3_batch",
+        SyscallHashBatch::vm::<Blake3Hasher>,
+    )?;
+    register_feature_gated_function!(
+        result,
+        batched_hash_syscalls_enabled,
+        *b"sol_blake3_update",
+        SyscallHashUpdate::vm::<blake3::IncrementalHasher>,
+    )?;
+    register_feature_gated_function!(
+        result,
+        batched_hash_syscalls_enabled,
+        *b"sol_blake3_finalize",
+        SyscallHashFinalize::vm::<blake3::IncrementalHasher>,
+    )?;
+    register_feature_gated_function!(
+        result,
+        batched_hash_syscalls_enabled,
+        *b"sol_keccak256_batch",
+        SyscallHashBatch::vm::<Keccak256Hasher>,
+    )?;
+    register_feature_gated_function!(
+        result,
+        batched_hash_syscalls_enabled,
+        *b"sol_keccak256_update",
+        SyscallHashUpdate::vm::<keccak::IncrementalHasher>,
+    )?;
+    register_feature_gated_function!(
+        result,
+        batched_hash_syscalls_enabled,
+        *b"sol_keccak256_finalize",
+        SyscallHashFinalize::vm::<keccak::IncrementalHasher>,
+    )?;
+
     // Elliptic Curve Operations
     register_feature_gated_function!(
         result,
@@ -375,6 +504,20 @@ pub fn create_program_runtime_environment_v1<'a>(
         SyscallGetEpochRewardsSysvar::vm,
     )?;
 
+    register_feature_gated_function!(
+        result,
+        get_sysvar_syscall_enabled,
+        *b"sol_get_sysvar",
+        SyscallGetSysvar::vm,
+    )?;
+
+    register_feature_gated_function!(
+        result,
+        get_minimum_balance_for_rent_exemption_syscall_enabled,
+        *b"sol_get_minimum_balance_for_rent_exemption",
+        SyscallGetMinimumBalanceForRentExemption::vm,
+    )?;
+
     // Memory ops
     result.register_function_hashed(*b"sol_memcpy_", SyscallMemcpy::vm)?;
     result.register_function_hashed(*b"sol_memmove_", SyscallMemmove::vm)?;
@@ -390,6 +533,14 @@ pub fn create_program_runtime_environment_v1<'a>(
     // Stack height
     result.register_function_hashed(*b"sol_get_stack_height", SyscallGetStackHeight::vm)?;
 
+    // Caller program id
+    register_feature_gated_function!(
+        result,
+        get_caller_program_id_syscall_enabled,
+        *b"sol_get_caller_program_id",
+        SyscallGetCallerProgramId::vm,
+    )?;
+
     // Return data
     result.register_function_hashed(*b"sol_set_return_data", SyscallSetReturnData::vm)?;
     result.register_function_hashed(*b"sol_get_return_data", SyscallGetReturnData::vm)?;
@@ -603,7 +754,7 @@ declare_builtin_function!(
         _arg5: u64,
         memory_mapping: &mut MemoryMapping,
     ) -> Result<u64, Error> {
-        consume_compute_meter(invoke_context, len)?;
+        consume_compute_meter(invoke_context, SyscallCategory::Logging, len)?;
 
         translate_string_and_do(
             memory_mapping,
@@ -698,7 +849,7 @@ declare_builtin_function!(
         let cost = invoke_context
             .get_compute_budget()
             .create_program_address_units;
-        consume_compute_meter(invoke_context, cost)?;
+        consume_compute_meter(invoke_context, SyscallCategory::ProgramAddress, cost)?;
 
         let (seeds, program_id) = translate_and_check_program_address_inputs(
             seeds_addr,
@@ -737,7 +888,7 @@ declare_builtin_function!(
         let cost = invoke_context
             .get_compute_budget()
             .create_program_address_units;
-        consume_compute_meter(invoke_context, cost)?;
+        consume_compute_meter(invoke_context, SyscallCategory::ProgramAddress, cost)?;
 
         let (seeds, program_id) = translate_and_check_program_address_inputs(
             seeds_addr,
@@ -781,7 +932,7 @@ declare_builtin_function!(
                 }
             }
             bump_seed[0] = bump_seed[0].saturating_sub(1);
-            consume_compute_meter(invoke_context, cost)?;
+            consume_compute_meter(invoke_context, SyscallCategory::ProgramAddress, cost)?;
         }
         Ok(1)
     }
@@ -800,7 +951,7 @@ declare_builtin_function!(
         memory_mapping: &mut MemoryMapping,
     ) -> Result<u64, Error> {
         let cost = invoke_context.get_compute_budget().secp256k1_recover_cost;
-        consume_compute_meter(invoke_context, cost)?;
+        consume_compute_meter(invoke_context, SyscallCategory::Crypto, cost)?;
 
         let hash = translate_slice::<u8>(
             memory_mapping,
@@ -866,7 +1017,7 @@ declare_builtin_function!(
                 let cost = invoke_context
                     .get_compute_budget()
                     .curve25519_edwards_validate_point_cost;
-                consume_compute_meter(invoke_context, cost)?;
+                consume_compute_meter(invoke_context, SyscallCategory::Crypto, cost)?;
 
                 let point = translate_type::<edwards::PodEdwardsPoint>(
                     memory_mapping,
@@ -884,7 +1035,7 @@ declare_builtin_function!(
                 let cost = invoke_context
                     .get_compute_budget()
                     .curve25519_ristretto_validate_point_cost;
-                consume_compute_meter(invoke_context, cost)?;
+                consume_compute_meter(invoke_context, SyscallCategory::Crypto, cost)?;
 
                 let point = translate_type::<ristretto::PodRistrettoPoint>(
                     memory_mapping,
@@ -926,7 +1077,7 @@ declare_builtin_function!(
                     let cost = invoke_context
                         .get_compute_budget()
                         .curve25519_edwards_add_cost;
-                    consume_compute_meter(invoke_context, cost)?;
+                    consume_compute_meter(invoke_context, SyscallCategory::Crypto, cost)?;
 
                     let left_point = translate_type::<edwards::PodEdwardsPoint>(
                         memory_mapping,
@@ -954,7 +1105,7 @@ declare_builtin_function!(
                     let cost = invoke_context
                         .get_compute_budget()
                         .curve25519_edwards_subtract_cost;
-                    consume_compute_meter(invoke_context, cost)?;
+                    consume_compute_meter(invoke_context, SyscallCategory::Crypto, cost)?;
 
                     let left_point = translate_type::<edwards::PodEdwardsPoint>(
                         memory_mapping,
@@ -982,7 +1133,7 @@ declare_builtin_function!(
                     let cost = invoke_context
                         .get_compute_budget()
                         .curve25519_edwards_multiply_cost;
-                    consume_compute_meter(invoke_context, cost)?;
+                    consume_compute_meter(invoke_context, SyscallCategory::Crypto, cost)?;
 
                     let scalar = translate_type::<scalar::PodScalar>(
                         memory_mapping,
@@ -1014,7 +1165,7 @@ declare_builtin_function!(
                     let cost = invoke_context
                         .get_compute_budget()
                         .curve25519_ristretto_add_cost;
-                    consume_compute_meter(invoke_context, cost)?;
+                    consume_compute_meter(invoke_context, SyscallCategory::Crypto, cost)?;
 
                     let left_point = translate_type::<ristretto::PodRistrettoPoint>(
                         memory_mapping,
@@ -1042,7 +1193,7 @@ declare_builtin_function!(
                     let cost = invoke_context
                         .get_compute_budget()
                         .curve25519_ristretto_subtract_cost;
-                    consume_compute_meter(invoke_context, cost)?;
+                    consume_compute_meter(invoke_context, SyscallCategory::Crypto, cost)?;
 
                     let left_point = translate_type::<ristretto::PodRistrettoPoint>(
                         memory_mapping,
@@ -1072,7 +1223,7 @@ declare_builtin_function!(
                     let cost = invoke_context
                         .get_compute_budget()
                         .curve25519_ristretto_multiply_cost;
-                    consume_compute_meter(invoke_context, cost)?;
+                    consume_compute_meter(invoke_context, SyscallCategory::Crypto, cost)?;
 
                     let scalar = translate_type::<scalar::PodScalar>(
                         memory_mapping,
@@ -1143,7 +1294,7 @@ declare_builtin_function!(
                             .curve25519_edwards_msm_incremental_cost
                             .saturating_mul(points_len.saturating_sub(1)),
                     );
-                consume_compute_meter(invoke_context, cost)?;
+                consume_compute_meter(invoke_context, SyscallCategory::Crypto, cost)?;
 
                 let scalars = translate_slice::<scalar::PodScalar>(
                     memory_mapping,
@@ -1181,7 +1332,7 @@ declare_builtin_function!(
                             .curve25519_ristretto_msm_incremental_cost
                             .saturating_mul(points_len.saturating_sub(1)),
                     );
-                consume_compute_meter(invoke_context, cost)?;
+                consume_compute_meter(invoke_context, SyscallCategory::Crypto, cost)?;
 
                 let scalars = translate_slice::<scalar::PodScalar>(
                     memory_mapping,
@@ -1234,7 +1385,7 @@ declare_builtin_function!(
             .checked_div(budget.cpi_bytes_per_unit)
             .unwrap_or(u64::MAX)
             .saturating_add(budget.syscall_base_cost);
-        consume_compute_meter(invoke_context, cost)?;
+        consume_compute_meter(invoke_context, SyscallCategory::ReturnData, cost)?;
 
         if len > MAX_RETURN_DATA as u64 {
             return Err(SyscallError::ReturnDataTooLarge(len, MAX_RETURN_DATA as u64).into());
@@ -1278,7 +1429,11 @@ declare_builtin_function!(
     ) -> Result<u64, Error> {
         let budget = invoke_context.get_compute_budget();
 
-        consume_compute_meter(invoke_context, budget.syscall_base_cost)?;
+        consume_compute_meter(
+            invoke_context,
+            SyscallCategory::ReturnData,
+            budget.syscall_base_cost,
+        )?;
 
         let (program_id, return_data) = invoke_context.transaction_context.get_return_data();
         let length = length.min(return_data.len() as u64);
@@ -1287,7 +1442,7 @@ declare_builtin_function!(
                 .saturating_add(size_of::<Pubkey>() as u64)
                 .checked_div(budget.cpi_bytes_per_unit)
                 .unwrap_or(u64::MAX);
-            consume_compute_meter(invoke_context, cost)?;
+            consume_compute_meter(invoke_context, SyscallCategory::ReturnData, cost)?;
 
             let return_data_result = translate_slice_mut::<u8>(
                 memory_mapping,
@@ -1342,7 +1497,11 @@ declare_builtin_function!(
     ) -> Result<u64, Error> {
         let budget = invoke_context.get_compute_budget();
 
-        consume_compute_meter(invoke_context, budget.syscall_base_cost)?;
+        consume_compute_meter(
+            invoke_context,
+            SyscallCategory::Introspection,
+            budget.syscall_base_cost,
+        )?;
 
         // Reverse iterate through the instruction trace,
         // ignoring anything except instructions on the same level
@@ -1480,12 +1639,55 @@ declare_builtin_function!(
     ) -> Result<u64, Error> {
         let budget = invoke_context.get_compute_budget();
 
-        consume_compute_meter(invoke_context, budget.syscall_base_cost)?;
+        consume_compute_meter(
+            invoke_context,
+            SyscallCategory::Introspection,
+            budget.syscall_base_cost,
+        )?;
 
         Ok(invoke_context.get_stack_height() as u64)
     }
 );
 
+declare_builtin_function!(
+    /// Get the program id of the program which invoked the current one
+    SyscallGetCallerProgramId,
+    fn rust(
+        invoke_context: &mut InvokeContext,
+        program_id_addr: u64,
+        _arg2: u64,
+        _arg3: u64,
+        _arg4: u64,
+        _arg5: u64,
+        memory_mapping: &mut MemoryMapping,
+    ) -> Result<u64, Error> {
+        let budget = invoke_context.get_compute_budget();
+
+        consume_compute_meter(
+            invoke_context,
+            SyscallCategory::Introspection,
+            budget.syscall_base_cost,
+        )?;
+
+        // Transaction-level instructions have no caller
+        let Some(caller_nesting_level) = invoke_context.get_stack_height().checked_sub(2) else {
+            return Ok(false as u64);
+        };
+        let transaction_context = &invoke_context.transaction_context;
+        let caller_program_id = *transaction_context
+            .get_instruction_context_at_nesting_level(caller_nesting_level)?
+            .get_last_program_key(transaction_context)?;
+
+        let program_id = translate_type_mut::<Pubkey>(
+            memory_mapping,
+            program_id_addr,
+            invoke_context.get_check_aligned(),
+        )?;
+        *program_id = caller_program_id;
+        Ok(true as u64)
+    }
+);
+
 declare_builtin_function!(
     /// alt_bn128 group operations
     SyscallAltBn128,
@@ -1530,7 +1732,7 @@ declare_builtin_function!(
             }
         };
 
-        consume_compute_meter(invoke_context, cost)?;
+        consume_compute_meter(invoke_context, SyscallCategory::Crypto, cost)?;
 
         let input = translate_slice::<u8>(
             memory_mapping,
@@ -1602,6 +1804,7 @@ declare_builtin_function!(
         let budget = invoke_context.get_compute_budget();
         consume_compute_meter(
             invoke_context,
+            SyscallCategory::Crypto,
             budget.syscall_base_cost.saturating_add(
                 input_len
                     .saturating_mul(input_len)
@@ -1677,7 +1880,7 @@ declare_builtin_function!(
             );
             return Err(SyscallError::ArithmeticOverflow.into());
         };
-        consume_compute_meter(invoke_context, cost.to_owned())?;
+        consume_compute_meter(invoke_context, SyscallCategory::Hashing, cost.to_owned())?;
 
         let hash_result = translate_slice_mut::<u8>(
             memory_mapping,
@@ -1727,7 +1930,11 @@ declare_builtin_function!(
         _memory_mapping: &mut MemoryMapping,
     ) -> Result<u64, Error> {
         let budget = invoke_context.get_compute_budget();
-        consume_compute_meter(invoke_context, budget.syscall_base_cost)?;
+        consume_compute_meter(
+            invoke_context,
+            SyscallCategory::Introspection,
+            budget.syscall_base_cost,
+        )?;
 
         use solana_rbpf::vm::ContextObject;
         Ok(invoke_context.get_remaining())
@@ -1773,7 +1980,7 @@ declare_builtin_function!(
             }
         };
 
-        consume_compute_meter(invoke_context, cost)?;
+        consume_compute_meter(invoke_context, SyscallCategory::Crypto, cost)?;
 
         let input = translate_slice::<u8>(
             memory_mapping,
@@ -1862,7 +2069,7 @@ declare_builtin_function!(
             return Err(SyscallError::TooManySlices.into());
         }
 
-        consume_compute_meter(invoke_context, hash_base_cost)?;
+        consume_compute_meter(invoke_context, SyscallCategory::Hashing, hash_base_cost)?;
 
         let hash_result = translate_slice_mut::<u8>(
             memory_mapping,
@@ -1892,7 +2099,7 @@ declare_builtin_function!(
                             .expect("div by non-zero literal"),
                     ),
                 );
-                consume_compute_meter(invoke_context, cost)?;
+                consume_compute_meter(invoke_context, SyscallCategory::Hashing, cost)?;
                 hasher.hash(bytes);
             }
         }
@@ -1901,6 +2108,182 @@ declare_builtin_function!(
     }
 );
 
+declare_builtin_function!(
+    // Generic Batched Hashing Syscall, hashing each slice separately
+    SyscallHashBatch<H: HasherImpl>,
+    fn rust(
+        invoke_context: &mut InvokeContext,
+        vals_addr: u64,
+        vals_len: u64,
+        results_addr: u64,
+        _arg4: u64,
+        _arg5: u64,
+        memory_mapping: &mut MemoryMapping,
+    ) -> Result<u64, Error> {
+        let compute_budget = invoke_context.get_compute_budget();
+        let hash_base_cost = H::get_base_cost(compute_budget);
+        let hash_byte_cost = H::get_byte_cost(compute_budget);
+        let hash_max_slices = H::get_max_slices(compute_budget);
+        if hash_max_slices < vals_len {
+            ic_msg!(
+                invoke_context,
+                "{} Hashing {} sequences in one syscall is over the limit {}",
+                H::NAME,
+                vals_len,
+                hash_max_slices,
+            );
+            return Err(SyscallError::TooManySlices.into());
+        }
+
+        consume_compute_meter(invoke_context, SyscallCategory::Hashing, hash_base_cost)?;
+
+        let hash_results = translate_slice_mut::<u8>(
+            memory_mapping,
+            results_addr,
+            (size_of::<H::Output>() as u64).saturating_mul(vals_len),
+            invoke_context.get_check_aligned(),
+        )?;
+        if vals_len > 0 {
+            let vals = translate_slice::<&[u8]>(
+                memory_mapping,
+                vals_addr,
+                vals_len,
+                invoke_context.get_check_aligned(),
+            )?;
+            for (val, hash_result) in vals
+                .iter()
+                .zip(hash_results.chunks_exact_mut(size_of::<H::Output>()))
+            {
+                let bytes = translate_slice::<u8>(
+                    memory_mapping,
+                    val.as_ptr() as u64,
+                    val.len() as u64,
+                    invoke_context.get_check_aligned(),
+                )?;
+                let cost = compute_budget.mem_op_base_cost.max(
+                    hash_byte_cost.saturating_mul(
+                        (val.len() as u64)
+                            .checked_div(2)
+                            .expect("div by non-zero literal"),
+                    ),
+                );
+                consume_compute_meter(invoke_context, SyscallCategory::Hashing, cost)?;
+                let mut hasher = H::create_hasher();
+                hasher.hash(bytes);
+                hash_result.copy_from_slice(hasher.result().as_ref());
+            }
+        }
+        Ok(0)
+    }
+);
+
+declare_builtin_function!(
+    // Generic Incremental Hashing Syscall, hashing slices into a state in program memory
+    SyscallHashUpdate<H: IncrementalHasherImpl>,
+    fn rust(
+        invoke_context: &mut InvokeContext,
+        state_addr: u64,
+        vals_addr: u64,
+        vals_len: u64,
+        _arg4: u64,
+        _arg5: u64,
+        memory_mapping: &mut MemoryMapping,
+    ) -> Result<u64, Error> {
+        let compute_budget = invoke_context.get_compute_budget();
+        let hash_byte_cost = H::get_byte_cost(compute_budget);
+        let hash_max_slices = H::get_max_slices(compute_budget);
+        if hash_max_slices < vals_len {
+            ic_msg!(
+                invoke_context,
+                "{} Hashing {} sequences in one syscall is over the limit {}",
+                H::NAME,
+                vals_len,
+                hash_max_slices,
+            );
+            return Err(SyscallError::TooManySlices.into());
+        }
+
+        consume_compute_meter(
+            invoke_context,
+            SyscallCategory::Hashing,
+            compute_budget.syscall_base_cost,
+        )?;
+
+        let mut hasher = *translate_type::<H>(
+            memory_mapping,
+            state_addr,
+            invoke_context.get_check_aligned(),
+        )?;
+        if vals_len > 0 {
+            let vals = translate_slice::<&[u8]>(
+                memory_mapping,
+                vals_addr,
+                vals_len,
+                invoke_context.get_check_aligned(),
+            )?;
+            for val in vals.iter() {
+                let bytes = translate_slice::<u8>(
+                    memory_mapping,
+                    val.as_ptr() as u64,
+                    val.len() as u64,
+                    invoke_context.get_check_aligned(),
+                )?;
+                let cost = compute_budget.mem_op_base_cost.max(
+                    hash_byte_cost.saturating_mul(
+                        (val.len() as u64)
+                            .checked_div(2)
+                            .expect("div by non-zero literal"),
+                    ),
+                );
+                consume_compute_meter(invoke_context, SyscallCategory::Hashing, cost)?;
+                hasher.try_update(bytes)?;
+            }
+        }
+        *translate_type_mut::<H>(
+            memory_mapping,
+            state_addr,
+            invoke_context.get_check_aligned(),
+        )? = hasher;
+        Ok(0)
+    }
+);
+
+declare_builtin_function!(
+    // Generic Incremental Hashing Syscall, writing the hash of a state in program memory
+    SyscallHashFinalize<H: IncrementalHasherImpl>,
+    fn rust(
+        invoke_context: &mut InvokeContext,
+        state_addr: u64,
+        result_addr: u64,
+        _arg3: u64,
+        _arg4: u64,
+        _arg5: u64,
+        memory_mapping: &mut MemoryMapping,
+    ) -> Result<u64, Error> {
+        let compute_budget = invoke_context.get_compute_budget();
+        consume_compute_meter(
+            invoke_context,
+            SyscallCategory::Hashing,
+            H::get_base_cost(compute_budget),
+        )?;
+
+        let hasher = translate_type::<H>(
+            memory_mapping,
+            state_addr,
+            invoke_context.get_check_aligned(),
+        )?;
+        let hash = hasher.try_finalize()?;
+        let hash_result = translate_slice_mut::<u8>(
+            memory_mapping,
+            result_addr,
+            size_of::<H::Output>() as u64,
+            invoke_context.get_check_aligned(),
+        )?;
+        hash_result.copy_from_slice(hash.as_ref());
+        Ok(0)
+    }
+);
+
 #[cfg(test)]
 #[allow(clippy::arithmetic_side_effects)]
 #[allow(clippy::indexing_slicing)]
@@ -1914,7 +2297,13 @@ mod tests {
         core::slice,
         solana_program_runtime::{invoke_context::InvokeContext, with_mock_invoke_context},
         solana_rbpf::{
-            error::EbpfError, memory_region::MemoryRegion, program::SBPFVersion, vm::Config,
+            assembler::assemble,
+            elf::Executable,
+            error::{EbpfError, ProgramResult},
+            memory_region::MemoryRegion,
+            program::SBPFVersion,
+            verifier::RequisiteVerifier,
+            vm::Config,
         },
         solana_sdk::{
             account::{create_account_shared_data_for_test, AccountSharedData},
@@ -1925,7 +2314,13 @@ mod tests {
             program::check_type_assumptions,
             stable_layout::stable_instruction::StableInstruction,
             sysvar::{
-                self, clock::Clock, epoch_rewards::EpochRewards, epoch_schedule::EpochSchedule,
+                self,
+                clock::Clock,
+                epoch_rewards::EpochRewards,
+                epoch_schedule::EpochSchedule,
+                epoch_stake::{
+                    EpochStake, VoteAccountStake, TOTAL_STAKE_OFFSET, VOTE_ACCOUNT_STAKE_SIZE,
+                },
             },
         },
         std::{mem, str::FromStr},
@@ -1969,6 +2364,42 @@ mod tests {
         pub len: usize,
     }
 
+    #[test]
+    fn test_create_program_runtime_environment_v1_sbpf_v2() {
+        let environment = Arc::new(
+            create_program_runtime_environment_v1(
+                &FeatureSet::all_enabled(),
+                &ComputeBudget::default(),
+                true,  /* deployment */
+                false, /* debugging_features */
+            )
+            .unwrap(),
+        );
+        assert!(environment.get_config().enable_sbpf_v1);
+        assert!(environment.get_config().enable_sbpf_v2);
+
+        // SBPFv1 programs keep loading in the same environment
+        let elf = std::fs::read("test_elfs/out/noop_aligned.so").unwrap();
+        let executable = Executable::<InvokeContext>::load(&elf, environment.clone()).unwrap();
+        assert_eq!(executable.get_sbpf_version(), &SBPFVersion::V1);
+
+        // SBPFv2 programs are verified and executed
+        let executable = assemble::<InvokeContext>(
+            "
+            mov64 r0, 0
+            exit",
+            environment,
+        )
+        .unwrap();
+        assert_eq!(executable.get_sbpf_version(), &SBPFVersion::V2);
+        executable.verify::<RequisiteVerifier>().unwrap();
+        prepare_mockup!(invoke_context, _program_id, bpf_loader::id());
+        crate::create_vm!(vm, &executable, vec![], vec![], &mut invoke_context);
+        let mut vm = vm.unwrap();
+        let (_, result) = vm.execute_program(&executable, true);
+        assert_matches!(result, ProgramResult::Ok(0));
+    }
+
     #[test]
     fn test_translate() {
         const START: u64 = 0x100000000;
@@ -2562,6 +2993,225 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_syscall_hash_batch() {
+        let config = Config::default();
+        prepare_mockup!(invoke_context, program_id, bpf_loader::id());
+
+        let bytes1 = "Gaggablaghblagh!";
+        let bytes2 = "flurbos";
+
+        let mock_slice1 = MockSlice {
+            vm_addr: 0x300000000,
+            len: bytes1.len(),
+        };
+        let mock_slice2 = MockSlice {
+            vm_addr: 0x400000000,
+            len: bytes2.len(),
+        };
+        let bytes_to_hash = [mock_slice1, mock_slice2];
+        let mut hash_results = [[0u8; HASH_BYTES]; 2];
+        let ro_len = bytes_to_hash.len() as u64;
+        let ro_va = 0x100000000;
+        let rw_va = 0x200000000;
+        let mut memory_mapping = MemoryMapping::new(
+            vec![
+                MemoryRegion::new_readonly(bytes_of_slice(&bytes_to_hash), ro_va),
+                MemoryRegion::new_writable(bytes_of_slice_mut(&mut hash_results), rw_va),
+                MemoryRegion::new_readonly(bytes1.as_bytes(), bytes_to_hash[0].vm_addr),
+                MemoryRegion::new_readonly(bytes2.as_bytes(), bytes_to_hash[1].vm_addr),
+            ],
+            &config,
+            &SBPFVersion::V2,
+        )
+        .unwrap();
+
+        let budget = invoke_context.get_compute_budget();
+        let batch_cost = |base_cost: u64, byte_cost: u64| {
+            base_cost
+                + [bytes1.len(), bytes2.len()]
+                    .iter()
+                    .map(|len| budget.mem_op_base_cost.max(byte_cost * (*len as u64 / 2)))
+                    .sum::<u64>()
+        };
+        let keccak256_cost = batch_cost(budget.keccak256_base_cost, budget.keccak256_byte_cost);
+        let blake3_cost = batch_cost(budget.blake3_base_cost, budget.blake3_byte_cost);
+        invoke_context.mock_set_remaining(keccak256_cost);
+        let result = SyscallHashBatch::rust::<Keccak256Hasher>(
+            &mut invoke_context,
+            ro_va,
+            ro_len,
+            rw_va,
+            0,
+            0,
+            &mut memory_mapping,
+        );
+        result.unwrap();
+        assert_eq!(hash_results[0], keccak::hash(bytes1.as_bytes()).to_bytes());
+        assert_eq!(hash_results[1], keccak::hash(bytes2.as_bytes()).to_bytes());
+
+        invoke_context.mock_set_remaining(blake3_cost);
+        let result = SyscallHashBatch::rust::<Blake3Hasher>(
+            &mut invoke_context,
+            ro_va,
+            ro_len + 1, // AccessViolation
+            rw_va,
+            0,
+            0,
+            &mut memory_mapping,
+        );
+        assert_access_violation!(result, rw_va, 3 * HASH_BYTES as u64);
+        invoke_context.mock_set_remaining(blake3_cost);
+        let result = SyscallHashBatch::rust::<Blake3Hasher>(
+            &mut invoke_context,
+            ro_va,
+            ro_len,
+            rw_va,
+            0,
+            0,
+            &mut memory_mapping,
+        );
+        result.unwrap();
+        assert_eq!(hash_results[0], blake3::hash(bytes1.as_bytes()).to_bytes());
+        assert_eq!(hash_results[1], blake3::hash(bytes2.as_bytes()).to_bytes());
+
+        let result = SyscallHashBatch::rust::<Blake3Hasher>(
+            &mut invoke_context,
+            ro_va,
+            ro_len,
+            rw_va,
+            0,
+            0,
+            &mut memory_mapping,
+        );
+        assert_matches!(
+            result,
+            Result::Err(error) if error.downcast_ref::<InstructionError>().unwrap() == &InstructionError::ComputationalBudgetExceeded
+        );
+    }
+
+    #[test]
+    fn test_syscall_hash_incremental() {
+        let bytes1 = [1u8; 1500];
+        let bytes2 = [2u8; 700];
+        let vals: &[&[u8]] = &[&bytes1, &bytes2, &bytes1, &bytes2];
+        // buf_len, after the 25 lanes, is larger than a block
+        check_syscall_hash_incremental::<keccak::IncrementalHasher>(
+            &bytes1,
+            &bytes2,
+            200,
+            keccak::hashv(vals).to_bytes(),
+        );
+        // buf_len, after the chunk counter, is larger than a chunk
+        check_syscall_hash_incremental::<blake3::IncrementalHasher>(
+            &bytes1,
+            &bytes2,
+            9,
+            blake3::hashv(vals).to_bytes(),
+        );
+    }
+
+    fn check_syscall_hash_incremental<H: IncrementalHasherImpl + Default>(
+        bytes1: &[u8],
+        bytes2: &[u8],
+        invalid_state_offset: usize,
+        expected_hash: [u8; HASH_BYTES],
+    ) {
+        let config = Config::default();
+        prepare_mockup!(invoke_context, program_id, bpf_loader::id());
+
+        let mock_slice1 = MockSlice {
+            vm_addr: 0x300000000,
+            len: bytes1.len(),
+        };
+        let mock_slice2 = MockSlice {
+            vm_addr: 0x400000000,
+            len: bytes2.len(),
+        };
+        let bytes_to_hash = [mock_slice1, mock_slice2];
+        let mut state = H::default();
+        let mut invalid_state = H::default();
+        bytes_of_mut(&mut invalid_state)[invalid_state_offset] = 0xff;
+        let mut hash_result = [0u8; HASH_BYTES];
+        let ro_len = bytes_to_hash.len() as u64;
+        let ro_va = 0x100000000;
+        let state_va = 0x200000000;
+        let invalid_state_va = 0x500000000;
+        let result_va = 0x600000000;
+        let mut memory_mapping = MemoryMapping::new(
+            vec![
+                MemoryRegion::new_readonly(bytes_of_slice(&bytes_to_hash), ro_va),
+                MemoryRegion::new_writable(bytes_of_mut(&mut state), state_va),
+                MemoryRegion::new_readonly(bytes1, bytes_to_hash[0].vm_addr),
+                MemoryRegion::new_readonly(bytes2, bytes_to_hash[1].vm_addr),
+                MemoryRegion::new_writable(bytes_of_mut(&mut invalid_state), invalid_state_va),
+                MemoryRegion::new_writable(bytes_of_slice_mut(&mut hash_result), result_va),
+            ],
+            &config,
+            &SBPFVersion::V2,
+        )
+        .unwrap();
+
+        let budget = invoke_context.get_compute_budget();
+        let update_cost = budget.syscall_base_cost
+            + H::get_byte_cost(budget) * (bytes1.len() as u64 / 2)
+            + H::get_byte_cost(budget) * (bytes2.len() as u64 / 2);
+        let finalize_cost = H::get_base_cost(budget);
+        invoke_context.mock_set_remaining(2 * update_cost + finalize_cost);
+        for _ in 0..2 {
+            let result = SyscallHashUpdate::rust::<H>(
+                &mut invoke_context,
+                state_va,
+                ro_va,
+                ro_len,
+                0,
+                0,
+                &mut memory_mapping,
+            );
+            result.unwrap();
+        }
+        let result = SyscallHashFinalize::rust::<H>(
+            &mut invoke_context,
+            state_va,
+            result_va,
+            0,
+            0,
+            0,
+            &mut memory_mapping,
+        );
+        result.unwrap();
+        assert_eq!(hash_result, expected_hash);
+
+        let result = SyscallHashFinalize::rust::<H>(
+            &mut invoke_context,
+            state_va,
+            result_va,
+            0,
+            0,
+            0,
+            &mut memory_mapping,
+        );
+        assert_matches!(
+            result,
+            Result::Err(error) if error.downcast_ref::<InstructionError>().unwrap() == &InstructionError::ComputationalBudgetExceeded
+        );
+
+        invoke_context.mock_set_remaining(update_cost);
+        let result = SyscallHashUpdate::rust::<H>(
+            &mut invoke_context,
+            invalid_state_va,
+            ro_va,
+            ro_len,
+            0,
+            0,
+            &mut memory_mapping,
+        );
+        assert_matches!(
+            result,
+            Result::Err(error) if error.downcast_ref::<SyscallError>().unwrap() == &SyscallError::InvalidHasherState
+        );
+    }
+
     #[test]
     fn test_syscall_edwards_curve_point_validation() {
         use solana_zk_token_sdk::curve25519::curve_syscall_traits::CURVE25519_EDWARDS;
@@ -3248,6 +3898,80 @@ mod tests {
         assert_eq!(*result, SyscallError::InvalidLength);
     }
 
+    #[test]
+    fn test_syscall_ristretto_multiscalar_multiplication_cost() {
+        use solana_zk_token_sdk::curve25519::curve_syscall_traits::CURVE25519_RISTRETTO;
+
+        let config = Config::default();
+        prepare_mockup!(invoke_context, program_id, bpf_loader::id());
+
+        let scalar: [u8; 32] = [
+            254, 198, 23, 138, 67, 243, 184, 110, 236, 115, 236, 205, 205, 215, 79, 114, 45, 250,
+            78, 137, 3, 107, 136, 237, 49, 126, 117, 223, 37, 191, 88, 6,
+        ];
+        let scalars = [scalar; 8];
+        let scalars_va = 0x100000000;
+
+        let ristretto_point: [u8; 32] = [
+            130, 35, 97, 25, 18, 199, 33, 239, 85, 143, 119, 111, 49, 51, 224, 40, 167, 185, 240,
+            179, 25, 194, 213, 41, 14, 155, 104, 18, 181, 197, 15, 112,
+        ];
+        let ristretto_points = [ristretto_point; 8];
+        let ristretto_points_va = 0x200000000;
+
+        let mut result_point: [u8; 32] = [0; 32];
+        let result_point_va = 0x300000000;
+
+        let mut memory_mapping = MemoryMapping::new(
+            vec![
+                MemoryRegion::new_readonly(bytes_of_slice(&scalars), scalars_va),
+                MemoryRegion::new_readonly(bytes_of_slice(&ristretto_points), ristretto_points_va),
+                MemoryRegion::new_writable(bytes_of_slice_mut(&mut result_point), result_point_va),
+            ],
+            &config,
+            &SBPFVersion::V2,
+        )
+        .unwrap();
+
+        // each point beyond the first costs the incremental cost
+        for points_len in [1, 2, 8] {
+            let cost = invoke_context
+                .get_compute_budget()
+                .curve25519_ristretto_msm_base_cost
+                + invoke_context
+                    .get_compute_budget()
+                    .curve25519_ristretto_msm_incremental_cost
+                    * (points_len - 1);
+
+            invoke_context.mock_set_remaining(cost);
+            let result = SyscallCurveMultiscalarMultiplication::rust(
+                &mut invoke_context,
+                CURVE25519_RISTRETTO,
+                scalars_va,
+                ristretto_points_va,
+                points_len,
+                result_point_va,
+                &mut memory_mapping,
+            );
+            assert_eq!(0, result.unwrap());
+
+            invoke_context.mock_set_remaining(cost - 1);
+            let result = SyscallCurveMultiscalarMultiplication::rust(
+                &mut invoke_context,
+                CURVE25519_RISTRETTO,
+                scalars_va,
+                ristretto_points_va,
+                points_len,
+                result_point_va,
+                &mut memory_mapping,
+            );
+            assert_matches!(
+                result,
+                Result::Err(error) if error.downcast_ref::<InstructionError>().unwrap() == &InstructionError::ComputationalBudgetExceeded
+            );
+        }
+    }
+
     fn create_filled_type<T: Default>(zero_init: bool) -> T {
         let mut val = T::default();
         let p = &mut val as *mut _ as *mut u8;
@@ -3509,6 +4233,110 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_syscall_get_sysvar_partial() {
+        let config = Config::default();
+        let vote_account = VoteAccountStake {
+            vote_pubkey: Pubkey::new_unique(),
+            stake: 42,
+        };
+        let src_epoch_stake = EpochStake {
+            epoch: 3,
+            total_stake: 100,
+            vote_accounts: vec![VoteAccountStake::default(), vote_account],
+        };
+        let transaction_accounts = vec![(
+            sysvar::epoch_stake::id(),
+            create_account_shared_data_for_test(&src_epoch_stake),
+        )];
+        with_mock_invoke_context!(invoke_context, transaction_context, transaction_accounts);
+
+        const SYSVAR_ID_VA: u64 = 0x100000000;
+        const VAR_VA: u64 = 0x200000000;
+        let mut get_sysvar = |sysvar_id: Pubkey, offset: usize, length: usize| {
+            let mut var = vec![0u8; length];
+            let mut memory_mapping = MemoryMapping::new(
+                vec![
+                    MemoryRegion::new_readonly(sysvar_id.as_ref(), SYSVAR_ID_VA),
+                    MemoryRegion::new_writable(&mut var, VAR_VA),
+                ],
+                &config,
+                &SBPFVersion::V2,
+            )
+            .unwrap();
+            let result = SyscallGetSysvar::rust(
+                &mut invoke_context,
+                SYSVAR_ID_VA,
+                VAR_VA,
+                offset as u64,
+                length as u64,
+                0,
+                &mut memory_mapping,
+            )
+            .unwrap();
+            (result, var)
+        };
+
+        assert_eq!(
+            get_sysvar(sysvar::epoch_stake::id(), TOTAL_STAKE_OFFSET, 8),
+            (SUCCESS, 100u64.to_le_bytes().to_vec())
+        );
+        let offset = EpochStake::vote_account_offset(1);
+        let (result, var) = get_sysvar(sysvar::epoch_stake::id(), offset, VOTE_ACCOUNT_STAKE_SIZE);
+        assert_eq!(result, SUCCESS);
+        assert_eq!(
+            bincode::deserialize::<VoteAccountStake>(&var).unwrap(),
+            vote_account
+        );
+        // The account data is padded to the size of a full sysvar
+        let size = EpochStake::size_of();
+        assert_eq!(
+            get_sysvar(sysvar::epoch_stake::id(), size - 8, 8),
+            (SUCCESS, vec![0; 8])
+        );
+        assert_eq!(
+            get_sysvar(sysvar::epoch_stake::id(), size - 8, 9).0,
+            OFFSET_LENGTH_EXCEEDS_SYSVAR
+        );
+        assert_eq!(
+            get_sysvar(sysvar::epoch_stake::id(), size, 0),
+            (SUCCESS, vec![])
+        );
+        assert_eq!(
+            get_sysvar(sysvar::epoch_stake::id(), size + 1, 0).0,
+            OFFSET_EXCEEDS_SYSVAR
+        );
+        assert_eq!(
+            get_sysvar(sysvar::epoch_stake::id(), usize::MAX, 8).0,
+            OFFSET_EXCEEDS_SYSVAR
+        );
+        assert_eq!(get_sysvar(sysvar::clock::id(), 0, 8).0, SYSVAR_NOT_FOUND);
+
+        // The range is checked against the sysvar before the destination is
+        // translated
+        let sysvar_id = sysvar::epoch_stake::id();
+        let mut var = [0u8; 8];
+        let mut memory_mapping = MemoryMapping::new(
+            vec![
+                MemoryRegion::new_readonly(sysvar_id.as_ref(), SYSVAR_ID_VA),
+                MemoryRegion::new_writable(&mut var, VAR_VA),
+            ],
+            &config,
+            &SBPFVersion::V2,
+        )
+        .unwrap();
+        let result = SyscallGetSysvar::rust(
+            &mut invoke_context,
+            SYSVAR_ID_VA,
+            VAR_VA,
+            0,
+            size as u64 + 1,
+            0,
+            &mut memory_mapping,
+        );
+        assert_eq!(result.unwrap(), OFFSET_LENGTH_EXCEEDS_SYSVAR);
+    }
+
     type BuiltinFunctionRustInterface<'a> = fn(
         &mut InvokeContext<'a>,
         u64,
@@ -3809,6 +4637,150 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_syscall_get_caller_program_id() {
+        let transaction_accounts = (0..2)
+            .map(|_| {
+                (
+                    Pubkey::new_unique(),
+                    AccountSharedData::new(0, 0, &bpf_loader::id()),
+                )
+            })
+            .collect::<Vec<_>>();
+        let caller_program_id = transaction_accounts[0].0;
+        with_mock_invoke_context!(invoke_context, transaction_context, transaction_accounts);
+        let syscall_base_cost = invoke_context.get_compute_budget().syscall_base_cost;
+
+        const PROGRAM_ID_VA: u64 = 0x100000000;
+        let mut id_buffer = vec![0; 32];
+        let config = Config::default();
+        let mut memory_mapping = MemoryMapping::new(
+            vec![MemoryRegion::new_writable(&mut id_buffer, PROGRAM_ID_VA)],
+            &config,
+            &SBPFVersion::V2,
+        )
+        .unwrap();
+
+        // a transaction-level instruction has no caller
+        invoke_context
+            .transaction_context
+            .get_next_instruction_context()
+            .unwrap()
+            .configure(&[0], &[], &[]);
+        invoke_context.transaction_context.push().unwrap();
+        invoke_context.mock_set_remaining(syscall_base_cost);
+        let result = SyscallGetCallerProgramId::rust(
+            &mut invoke_context,
+            PROGRAM_ID_VA,
+            0,
+            0,
+            0,
+            0,
+            &mut memory_mapping,
+        );
+        assert_eq!(result.unwrap(), 0);
+        assert_eq!(id_buffer, [0; 32]);
+
+        // an instruction invoked through CPI is called by the program above it
+        invoke_context
+            .transaction_context
+            .get_next_instruction_context()
+            .unwrap()
+            .configure(&[1], &[], &[]);
+        invoke_context.transaction_context.push().unwrap();
+        invoke_context.mock_set_remaining(syscall_base_cost);
+        let result = SyscallGetCallerProgramId::rust(
+            &mut invoke_context,
+            PROGRAM_ID_VA,
+            0,
+            0,
+            0,
+            0,
+            &mut memory_mapping,
+        );
+        assert_eq!(result.unwrap(), 1);
+        assert_eq!(id_buffer, caller_program_id.to_bytes());
+
+        // the syscall is metered
+        invoke_context.mock_set_remaining(0);
+        let result = SyscallGetCallerProgramId::rust(
+            &mut invoke_context,
+            PROGRAM_ID_VA,
+            0,
+            0,
+            0,
+            0,
+            &mut memory_mapping,
+        );
+        assert_matches!(
+            result,
+            Result::Err(error) if error.downcast_ref::<InstructionError>().unwrap() == &InstructionError::ComputationalBudgetExceeded
+        );
+    }
+
+    #[test]
+    fn test_syscall_get_minimum_balance_for_rent_exemption() {
+        let rent = Rent {
+            lamports_per_byte_year: 42,
+            exemption_threshold: 3.0,
+            burn_percent: 50,
+        };
+        let transaction_accounts = vec![(
+            sysvar::rent::id(),
+            create_account_shared_data_for_test(&rent),
+        )];
+        with_mock_invoke_context!(invoke_context, transaction_context, transaction_accounts);
+        let syscall_base_cost = invoke_context.get_compute_budget().syscall_base_cost;
+        let config = Config::default();
+        let mut memory_mapping = MemoryMapping::new(vec![], &config, &SBPFVersion::V2).unwrap();
+
+        for data_len in [0, 165, MAX_PERMITTED_DATA_LENGTH] {
+            invoke_context.mock_set_remaining(syscall_base_cost);
+            let result = SyscallGetMinimumBalanceForRentExemption::rust(
+                &mut invoke_context,
+                data_len,
+                0,
+                0,
+                0,
+                0,
+                &mut memory_mapping,
+            );
+            assert_eq!(result.unwrap(), rent.minimum_balance(data_len as usize));
+        }
+
+        // data lengths no account can have are rejected
+        invoke_context.mock_set_remaining(syscall_base_cost);
+        let result = SyscallGetMinimumBalanceForRentExemption::rust(
+            &mut invoke_context,
+            MAX_PERMITTED_DATA_LENGTH + 1,
+            0,
+            0,
+            0,
+            0,
+            &mut memory_mapping,
+        );
+        assert_matches!(
+            result,
+            Result::Err(error) if error.downcast_ref::<SyscallError>().unwrap() == &SyscallError::InvalidLength
+        );
+
+        // the syscall is metered
+        invoke_context.mock_set_remaining(0);
+        let result = SyscallGetMinimumBalanceForRentExemption::rust(
+            &mut invoke_context,
+            0,
+            0,
+            0,
+            0,
+            0,
+            &mut memory_mapping,
+        );
+        assert_matches!(
+            result,
+            Result::Err(error) if error.downcast_ref::<InstructionError>().unwrap() == &InstructionError::ComputationalBudgetExceeded
+        );
+    }
+
     #[test]
     fn test_create_program_address() {
         // These tests duplicate the direct tests in solana_program::pubkey
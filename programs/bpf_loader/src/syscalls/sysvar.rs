@@ -9,6 +9,7 @@ fn get_sysvar<T: std::fmt::Debug + Sysvar + SysvarId + Clone>(
 ) -> Result<u64, Error> {
     consume_compute_meter(
         invoke_context,
+        SyscallCategory::Sysvar,
         invoke_context
             .get_compute_budget()
             .sysvar_base_cost
@@ -156,3 +157,89 @@ declare_builtin_function!(
         )
     }
 );
+
+declare_builtin_function!(
+    /// Get a slice of the account data of a sysvar
+    SyscallGetSysvar,
+    fn rust(
+        invoke_context: &mut InvokeContext,
+        sysvar_id_addr: u64,
+        var_addr: u64,
+        offset: u64,
+        length: u64,
+        _arg5: u64,
+        memory_mapping: &mut MemoryMapping,
+    ) -> Result<u64, Error> {
+        let check_aligned = invoke_context.get_check_aligned();
+        let budget = invoke_context.get_compute_budget();
+
+        let sysvar_id_cost = (size_of::<Pubkey>() as u64)
+            .checked_div(budget.cpi_bytes_per_unit)
+            .unwrap_or(u64::MAX);
+        let sysvar_buf_cost = length
+            .checked_div(budget.cpi_bytes_per_unit)
+            .unwrap_or(u64::MAX);
+        consume_compute_meter(
+            invoke_context,
+            SyscallCategory::Sysvar,
+            budget
+                .sysvar_base_cost
+                .saturating_add(sysvar_id_cost)
+                .saturating_add(sysvar_buf_cost.max(budget.mem_op_base_cost)),
+        )?;
+
+        let sysvar_id = translate_type::<Pubkey>(memory_mapping, sysvar_id_addr, check_aligned)?;
+        let Some(sysvar_data) = invoke_context.get_sysvar_cache().get_sysvar_data(sysvar_id) else {
+            return Ok(SYSVAR_NOT_FOUND);
+        };
+        // Check the range against the sysvar before translating it, so that a
+        // range too long for the sysvar is reported as such rather than as an
+        // access violation
+        let Some(data) = sysvar_data.get(offset as usize..) else {
+            return Ok(OFFSET_EXCEEDS_SYSVAR);
+        };
+        let Some(data) = data.get(..length as usize) else {
+            return Ok(OFFSET_LENGTH_EXCEEDS_SYSVAR);
+        };
+
+        let var = translate_slice_mut::<u8>(memory_mapping, var_addr, length, check_aligned)?;
+        if !is_nonoverlapping(
+            sysvar_id as *const _ as usize,
+            size_of::<Pubkey>(),
+            var.as_ptr() as usize,
+            var.len(),
+        ) {
+            return Err(SyscallError::CopyOverlapping.into());
+        }
+        var.copy_from_slice(data);
+
+        Ok(SUCCESS)
+    }
+);
+
+declare_builtin_function!(
+    /// Get the minimum balance for an account of a given data length to be
+    /// rent exempt
+    SyscallGetMinimumBalanceForRentExemption,
+    fn rust(
+        invoke_context: &mut InvokeContext,
+        data_len: u64,
+        _arg2: u64,
+        _arg3: u64,
+        _arg4: u64,
+        _arg5: u64,
+        _memory_mapping: &mut MemoryMapping,
+    ) -> Result<u64, Error> {
+        consume_compute_meter(
+            invoke_context,
+            SyscallCategory::Sysvar,
+            invoke_context.get_compute_budget().syscall_base_cost,
+        )?;
+        if data_len > MAX_PERMITTED_DATA_LENGTH {
+            return Err(SyscallError::InvalidLength.into());
+        }
+
+        let rent = invoke_context.get_sysvar_cache().get_rent()?;
+        Ok(rent.minimum_balance(data_len as usize))
+    }
+);
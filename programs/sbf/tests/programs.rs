@@ -137,8 +137,8 @@ fn execute_transactions(
 ) -> Vec<Result<ConfirmedTransactionWithStatusMeta, TransactionError>> {
     let batch = bank.prepare_batch_for_tests(txs.clone());
     let mut timings = ExecuteTimings::default();
-    let mut mint_decimals = HashMap::new();
-    let tx_pre_token_balances = collect_token_balances(&bank, &batch, &mut mint_decimals);
+    let mut mint_infos = HashMap::new();
+    let tx_pre_token_balances = collect_token_balances(&bank, &batch, &mut mint_infos);
     let (
         TransactionResults {
             execution_results, ..
@@ -158,7 +158,7 @@ fn execute_transactions(
         &mut timings,
         None,
     );
-    let tx_post_token_balances = collect_token_balances(&bank, &batch, &mut mint_decimals);
+    let tx_post_token_balances = collect_token_balances(&bank, &batch, &mut mint_infos);
 
     izip!(
         txs.iter(),
@@ -221,6 +221,7 @@ fn execute_transactions(
                         loaded_addresses: LoadedAddresses::default(),
                         return_data,
                         compute_units_consumed: Some(executed_units),
+                        stake_changes: None,
                     };
 
                     Ok(ConfirmedTransactionWithStatusMeta {
@@ -2,14 +2,14 @@ use {
     crate::{
         config,
         stake_state::{
-            authorize, authorize_with_seed, deactivate, deactivate_delinquent, delegate,
-            initialize, merge, new_warmup_cooldown_rate_epoch, redelegate, set_lockup, split,
-            withdraw,
+            authorize, authorize_with_seed, deactivate, deactivate_delinquent, deactivate_multiple,
+            delegate, initialize, merge, new_warmup_cooldown_rate_epoch, redelegate, set_lockup,
+            split, split_with_funding, withdraw,
         },
     },
     log::*,
     solana_program_runtime::{
-        declare_process_instruction, sysvar_cache::get_sysvar_with_account_check,
+        declare_process_instruction, ic_msg, sysvar_cache::get_sysvar_with_account_check,
     },
     solana_sdk::{
         feature_set,
@@ -20,6 +20,7 @@ use {
             instruction::{LockupArgs, StakeInstruction},
             program::id,
             state::{Authorized, Lockup},
+            MAX_DEACTIVATE_MULTIPLE_STAKE_ACCOUNTS,
         },
         transaction_context::{IndexOfAccount, InstructionContext, TransactionContext},
     },
@@ -396,6 +397,57 @@ declare_process_instruction!(Entrypoint, DEFAULT_COMPUTE_UNITS, |invoke_context|
                 Err(InstructionError::InvalidInstructionData)
             }
         }
+        Ok(StakeInstruction::SplitWithFunding(lamports)) => {
+            if invoke_context
+                .feature_set
+                .is_active(&feature_set::stake_split_with_funding::id())
+            {
+                let me = get_stake_account()?;
+                instruction_context.check_number_of_instruction_accounts(5)?;
+                drop(me);
+                split_with_funding(invoke_context, 0, lamports, 1, 2, &signers)
+            } else {
+                Err(InstructionError::InvalidInstructionData)
+            }
+        }
+        Ok(StakeInstruction::DeactivateMultiple) => {
+            if invoke_context
+                .feature_set
+                .is_active(&feature_set::stake_deactivate_multiple::id())
+            {
+                instruction_context.check_number_of_instruction_accounts(2)?;
+                let num_stake_accounts = instruction_context
+                    .get_number_of_instruction_accounts()
+                    .saturating_sub(1);
+                if usize::from(num_stake_accounts) > MAX_DEACTIVATE_MULTIPLE_STAKE_ACCOUNTS {
+                    ic_msg!(
+                        invoke_context,
+                        "cannot deactivate more than {} stake accounts at once",
+                        MAX_DEACTIVATE_MULTIPLE_STAKE_ACCOUNTS
+                    );
+                    return Err(InstructionError::InvalidArgument);
+                }
+                // each stake account beyond the first costs as much as a separate `Deactivate`
+                invoke_context
+                    .consume_checked(
+                        DEFAULT_COMPUTE_UNITS
+                            .saturating_mul(u64::from(num_stake_accounts).saturating_sub(1)),
+                    )
+                    .map_err(|_| InstructionError::ComputationalBudgetExceeded)?;
+
+                let clock = invoke_context.get_sysvar_cache().get_clock()?;
+                deactivate_multiple(
+                    invoke_context,
+                    transaction_context,
+                    instruction_context,
+                    1,
+                    &clock,
+                    &signers,
+                )
+            } else {
+                Err(InstructionError::InvalidInstructionData)
+            }
+        }
         Err(err) => Err(err),
     }
 });
@@ -408,7 +460,7 @@ mod tests {
             config,
             stake_state::{
                 authorized_from, create_stake_history_from_delegations, from, new_stake,
-                stake_from, Delegation, Meta, Stake, StakeStateV2,
+                new_stake_history_entry, stake_from, Delegation, Meta, Stake, StakeStateV2,
             },
         },
         assert_matches::assert_matches,
@@ -1081,6 +1133,29 @@ mod tests {
             Err(InstructionError::NotEnoughAccountKeys),
         );
 
+        // Tests correct number of accounts are provided in split_with_funding
+        let split_address = Pubkey::new_unique();
+        let funding_address = Pubkey::new_unique();
+        let split_with_funding_instruction = &instruction::split_with_funding(
+            &stake_address,
+            &Pubkey::new_unique(),
+            withdrawal_amount,
+            &split_address,
+            &funding_address,
+        )[2];
+        process_instruction(
+            Arc::clone(&feature_set),
+            &split_with_funding_instruction.data,
+            vec![
+                (stake_address, stake_account.clone()),
+                (split_address, AccountSharedData::default()),
+                (funding_address, AccountSharedData::default()),
+                (system_program::id(), AccountSharedData::default()),
+            ],
+            split_with_funding_instruction.accounts[..4].to_vec(),
+            Err(InstructionError::NotEnoughAccountKeys),
+        );
+
         // Tests correct number of accounts are provided in deactivate_delinquent
         process_instruction(
             Arc::clone(&feature_set),
@@ -3583,6 +3658,102 @@ mod tests {
         );
     }
 
+    #[test_case(feature_set_old_warmup_cooldown(); "old_warmup_cooldown")]
+    #[test_case(feature_set_all_enabled(); "all_enabled")]
+    fn test_deactivate_multiple(feature_set: Arc<FeatureSet>) {
+        let authority_address = solana_sdk::pubkey::new_rand();
+        let minimum_delegation = crate::get_minimum_delegation(&feature_set);
+        let rent_exempt_reserve = Rent::default().minimum_balance(StakeStateV2::size_of());
+        let clock = Clock {
+            epoch: 1,
+            ..Clock::default()
+        };
+        let new_stake_account = |staker: &Pubkey| {
+            AccountSharedData::new_data_with_space(
+                rent_exempt_reserve + minimum_delegation,
+                &StakeStateV2::Stake(
+                    Meta {
+                        rent_exempt_reserve,
+                        ..Meta::auto(staker)
+                    },
+                    new_stake(
+                        minimum_delegation,
+                        &solana_sdk::pubkey::new_rand(),
+                        &VoteState::default(),
+                        0,
+                    ),
+                    StakeFlags::empty(),
+                ),
+                StakeStateV2::size_of(),
+                &id(),
+            )
+            .unwrap()
+        };
+        let stake_addresses: Vec<_> = (0..MAX_DEACTIVATE_MULTIPLE_STAKE_ACCOUNTS + 1)
+            .map(|_| solana_sdk::pubkey::new_rand())
+            .collect();
+        let other_stake_address = solana_sdk::pubkey::new_rand();
+        let transaction_accounts: Vec<_> = stake_addresses
+            .iter()
+            .map(|stake_address| (*stake_address, new_stake_account(&authority_address)))
+            .chain([
+                (
+                    other_stake_address,
+                    new_stake_account(&solana_sdk::pubkey::new_rand()),
+                ),
+                (authority_address, AccountSharedData::default()),
+                (clock::id(), create_account_shared_data_for_test(&clock)),
+            ])
+            .collect();
+        let process_deactivate_multiple = |stake_addresses: &[Pubkey], expected_result| {
+            let instruction = instruction::deactivate_stakes(stake_addresses, &authority_address);
+            process_instruction(
+                Arc::clone(&feature_set),
+                &instruction.data,
+                transaction_accounts.clone(),
+                instruction.accounts,
+                expected_result,
+            )
+        };
+
+        // should pass, deactivating every stake account
+        let accounts = process_deactivate_multiple(
+            &stake_addresses[..MAX_DEACTIVATE_MULTIPLE_STAKE_ACCOUNTS],
+            Ok(()),
+        );
+        for account in &accounts[..MAX_DEACTIVATE_MULTIPLE_STAKE_ACCOUNTS] {
+            assert_eq!(
+                stake_from(account).unwrap().delegation.deactivation_epoch,
+                clock.epoch
+            );
+        }
+        assert_eq!(
+            stake_from(&accounts[MAX_DEACTIVATE_MULTIPLE_STAKE_ACCOUNTS])
+                .unwrap()
+                .delegation
+                .deactivation_epoch,
+            u64::MAX
+        );
+
+        // should fail, too many stake accounts
+        process_deactivate_multiple(&stake_addresses, Err(InstructionError::InvalidArgument));
+
+        // should fail, no stake accounts
+        process_deactivate_multiple(&[], Err(InstructionError::NotEnoughAccountKeys));
+
+        // should fail, a stake account with another stake authority
+        process_deactivate_multiple(
+            &[stake_addresses[0], other_stake_address],
+            Err(InstructionError::MissingRequiredSignature),
+        );
+
+        // should fail, a stake account deactivated twice
+        process_deactivate_multiple(
+            &[stake_addresses[0], stake_addresses[0]],
+            Err(StakeError::AlreadyDeactivated.into()),
+        );
+    }
+
     #[test_case(feature_set_old_warmup_cooldown_no_minimum_delegation(); "old_warmup_cooldown_no_min_delegation")]
     #[test_case(feature_set_old_warmup_cooldown(); "old_warmup_cooldown")]
     #[test_case(feature_set_all_enabled(); "all_enabled")]
@@ -7948,4 +8119,200 @@ mod tests {
             Err(StakeError::RedelegateToSameVoteAccount.into()),
         );
     }
+
+    #[test_case(feature_set_old_warmup_cooldown(); "old_warmup_cooldown")]
+    #[test_case(feature_set_all_enabled(); "all_enabled")]
+    fn test_redelegate_stake_history(feature_set: Arc<FeatureSet>) {
+        let minimum_delegation = crate::get_minimum_delegation(&feature_set);
+        let rent = Rent::default();
+        let rent_exempt_reserve = rent.minimum_balance(StakeStateV2::size_of());
+        let current_epoch = 100;
+        let delegated_stake = 2 * minimum_delegation;
+        let bootstrap_stake = 100 * delegated_stake;
+        let new_rate_activation_epoch =
+            if feature_set.is_active(&feature_set::reduce_stake_warmup_cooldown::id()) {
+                Some(0)
+            } else {
+                None
+            };
+        let source_delegation = Delegation::new(&Pubkey::new_unique(), delegated_stake, 90);
+        // the stake warmed up alongside plenty of bootstrap stake, and has been fully effective
+        // for a while
+        let stake_history = create_stake_history_from_delegations(
+            Some(bootstrap_stake),
+            0..current_epoch,
+            &[source_delegation],
+            new_rate_activation_epoch,
+        );
+
+        let authorized_staker = Pubkey::new_unique();
+        let stake_address = Pubkey::new_unique();
+        let uninitialized_stake_address = Pubkey::new_unique();
+        let new_vote_address = Pubkey::new_unique();
+        let stake_account = AccountSharedData::new_data_with_space(
+            rent_exempt_reserve + delegated_stake,
+            &StakeStateV2::Stake(
+                Meta {
+                    rent_exempt_reserve,
+                    ..Meta::auto(&authorized_staker)
+                },
+                Stake {
+                    delegation: source_delegation,
+                    credits_observed: 0,
+                },
+                StakeFlags::empty(),
+            ),
+            StakeStateV2::size_of(),
+            &id(),
+        )
+        .unwrap();
+        let new_vote_account = AccountSharedData::new_data_with_space(
+            1, /* lamports */
+            &VoteStateVersions::new_current(VoteState::default()),
+            VoteState::size_of(),
+            &solana_vote_program::id(),
+        )
+        .unwrap();
+        let sysvar_accounts = vec![
+            (
+                stake_history::id(),
+                create_account_shared_data_for_test(&stake_history),
+            ),
+            (rent::id(), create_account_shared_data_for_test(&rent)),
+            (
+                clock::id(),
+                create_account_shared_data_for_test(&Clock {
+                    epoch: current_epoch,
+                    ..Clock::default()
+                }),
+            ),
+            (
+                epoch_schedule::id(),
+                create_account_shared_data_for_test(&EpochSchedule::default()),
+            ),
+        ];
+        #[allow(deprecated)]
+        let mut transaction_accounts = vec![
+            (stake_address, stake_account),
+            (
+                uninitialized_stake_address,
+                AccountSharedData::new(rent_exempt_reserve + 42, StakeStateV2::size_of(), &id()),
+            ),
+            (new_vote_address, new_vote_account),
+            (authorized_staker, AccountSharedData::default()),
+            (
+                stake_config::id(),
+                config::create_account(0, &stake_config::Config::default()),
+            ),
+        ];
+        transaction_accounts.extend(sysvar_accounts.iter().cloned());
+        let instruction = instruction::redelegate(
+            &stake_address,
+            &authorized_staker,
+            &new_vote_address,
+            &uninitialized_stake_address,
+        )
+        .pop()
+        .unwrap();
+        let output_accounts = process_instruction(
+            Arc::clone(&feature_set),
+            &instruction.data,
+            transaction_accounts,
+            instruction.accounts,
+            Ok(()),
+        );
+
+        // the source stake account cools down as usual, though its lamports have moved on
+        assert_eq!(output_accounts[0].lamports(), rent_exempt_reserve);
+        let source_stake = stake_from(&output_accounts[0]).unwrap();
+        assert_eq!(source_stake.delegation.stake, delegated_stake);
+        assert_eq!(source_stake.delegation.deactivation_epoch, current_epoch);
+
+        // while the destination stake account warms up from this epoch on, including the lamports
+        // it already had
+        assert_eq!(
+            output_accounts[1].lamports(),
+            rent_exempt_reserve + 42 + delegated_stake
+        );
+        let StakeStateV2::Stake(meta, stake, stake_flags) =
+            output_accounts[1].deserialize_data().unwrap()
+        else {
+            panic!("Invalid output_accounts[1] data");
+        };
+        assert_eq!(meta.rent_exempt_reserve, rent_exempt_reserve);
+        assert_eq!(
+            stake_flags,
+            StakeFlags::MUST_FULLY_ACTIVATE_BEFORE_DEACTIVATION_IS_PERMITTED
+        );
+        assert_eq!(stake.delegation.voter_pubkey, new_vote_address);
+        assert_eq!(stake.delegation.stake, delegated_stake + 42);
+        assert_eq!(stake.delegation.activation_epoch, current_epoch);
+        assert_eq!(stake.delegation.deactivation_epoch, u64::MAX);
+
+        // the stake history entry of the epoch accounts for both the deactivating and the
+        // activating stake, and the effective stake did not change yet
+        let delegations = [source_stake.delegation, stake.delegation];
+        let entry = new_stake_history_entry(
+            current_epoch,
+            delegations.iter().chain(
+                [Delegation {
+                    activation_epoch: u64::MAX,
+                    stake: bootstrap_stake,
+                    ..Delegation::default()
+                }]
+                .iter(),
+            ),
+            &stake_history,
+            new_rate_activation_epoch,
+        );
+        assert_eq!(
+            entry,
+            StakeHistoryEntry {
+                effective: bootstrap_stake + delegated_stake,
+                activating: delegated_stake + 42,
+                deactivating: delegated_stake,
+            }
+        );
+
+        // by the next epoch, the stake has moved from one delegation to the other
+        let next_stake_history = create_stake_history_from_delegations(
+            Some(bootstrap_stake),
+            0..current_epoch + 1,
+            &[source_stake.delegation, stake.delegation],
+            new_rate_activation_epoch,
+        );
+        assert_eq!(next_stake_history.get(current_epoch), Some(&entry));
+        assert_eq!(
+            source_stake.delegation.stake_activating_and_deactivating(
+                current_epoch + 1,
+                &next_stake_history,
+                new_rate_activation_epoch,
+            ),
+            StakeActivationStatus::default(),
+        );
+        assert_eq!(
+            stake.delegation.stake_activating_and_deactivating(
+                current_epoch + 1,
+                &next_stake_history,
+                new_rate_activation_epoch,
+            ),
+            StakeActivationStatus::with_effective(delegated_stake + 42),
+        );
+
+        // the destination stake may not be deactivated before it is fully active
+        let mut transaction_accounts = vec![
+            (uninitialized_stake_address, output_accounts[1].clone()),
+            (authorized_staker, AccountSharedData::default()),
+        ];
+        transaction_accounts.extend(sysvar_accounts);
+        let instruction =
+            instruction::deactivate_stake(&uninitialized_stake_address, &authorized_staker);
+        process_instruction(
+            Arc::clone(&feature_set),
+            &instruction.data,
+            transaction_accounts,
+            instruction.accounts,
+            Err(StakeError::RedelegatedStakeMustFullyActivateBeforeDeactivationIsPermitted.into()),
+        );
+    }
 }
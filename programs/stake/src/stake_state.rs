@@ -25,6 +25,7 @@ use {
             tools::{acceptable_reference_epoch_credits, eligible_for_deactivate_delinquent},
         },
         stake_history::{StakeHistory, StakeHistoryEntry},
+        system_instruction,
         transaction_context::{
             BorrowedAccount, IndexOfAccount, InstructionContext, TransactionContext,
         },
@@ -692,6 +693,27 @@ pub fn deactivate(
     }
 }
 
+pub fn deactivate_multiple(
+    invoke_context: &InvokeContext,
+    transaction_context: &TransactionContext,
+    instruction_context: &InstructionContext,
+    first_stake_account_index: IndexOfAccount,
+    clock: &Clock,
+    signers: &HashSet<Pubkey>,
+) -> Result<(), InstructionError> {
+    for stake_account_index in
+        first_stake_account_index..instruction_context.get_number_of_instruction_accounts()
+    {
+        let mut stake_account = instruction_context
+            .try_borrow_instruction_account(transaction_context, stake_account_index)?;
+        if *stake_account.get_owner() != id() {
+            return Err(InstructionError::InvalidAccountOwner);
+        }
+        deactivate(invoke_context, &mut stake_account, clock, signers)?;
+    }
+    Ok(())
+}
+
 pub fn set_lockup(
     stake_account: &mut BorrowedAccount,
     lockup: &LockupArgs,
@@ -879,6 +901,57 @@ pub fn split(
     Ok(())
 }
 
+pub fn split_with_funding(
+    invoke_context: &mut InvokeContext,
+    stake_account_index: IndexOfAccount,
+    lamports: u64,
+    split_index: IndexOfAccount,
+    funding_index: IndexOfAccount,
+    signers: &HashSet<Pubkey>,
+) -> Result<(), InstructionError> {
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+    let stake_account = instruction_context
+        .try_borrow_instruction_account(transaction_context, stake_account_index)?;
+    let is_full_split = lamports == stake_account.get_lamports();
+    drop(stake_account);
+    let split =
+        instruction_context.try_borrow_instruction_account(transaction_context, split_index)?;
+    let split_key = *split.get_key();
+    let rent = invoke_context.get_sysvar_cache().get_rent()?;
+    let required_lamports = rent
+        .minimum_balance(split.get_data().len())
+        .saturating_sub(split.get_lamports());
+    drop(split);
+    let funding_account =
+        instruction_context.try_borrow_instruction_account(transaction_context, funding_index)?;
+    if !funding_account.is_signer() {
+        return Err(InstructionError::MissingRequiredSignature);
+    }
+    let funding_key = *funding_account.get_key();
+    drop(funding_account);
+
+    // splitting off the entire balance moves the source's rent-exempt reserve along with it
+    if !is_full_split && required_lamports > 0 {
+        invoke_context.native_invoke(
+            system_instruction::transfer(&funding_key, &split_key, required_lamports).into(),
+            &[],
+        )?;
+    }
+
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+    split(
+        invoke_context,
+        transaction_context,
+        instruction_context,
+        stake_account_index,
+        lamports,
+        split_index,
+        signers,
+    )
+}
+
 pub fn merge(
     invoke_context: &InvokeContext,
     transaction_context: &TransactionContext,
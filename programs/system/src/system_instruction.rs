@@ -21,6 +21,24 @@ pub fn advance_nonce_account(
     account: &mut BorrowedAccount,
     signers: &HashSet<Pubkey>,
     invoke_context: &InvokeContext,
+) -> Result<(), InstructionError> {
+    do_advance_nonce_account(account, None, signers, invoke_context)
+}
+
+pub fn advance_and_authorize_nonce_account(
+    account: &mut BorrowedAccount,
+    nonce_authority: &Pubkey,
+    signers: &HashSet<Pubkey>,
+    invoke_context: &InvokeContext,
+) -> Result<(), InstructionError> {
+    do_advance_nonce_account(account, Some(nonce_authority), signers, invoke_context)
+}
+
+fn do_advance_nonce_account(
+    account: &mut BorrowedAccount,
+    new_authority: Option<&Pubkey>,
+    signers: &HashSet<Pubkey>,
+    invoke_context: &InvokeContext,
 ) -> Result<(), InstructionError> {
     if !account.is_writable() {
         ic_msg!(
@@ -52,7 +70,7 @@ pub fn advance_nonce_account(
             }
 
             let new_data = nonce::state::Data::new(
-                data.authority,
+                new_authority.copied().unwrap_or(data.authority),
                 next_durable_nonce,
                 invoke_context.lamports_per_signature,
             );
@@ -1085,6 +1103,106 @@ mod test {
         assert_eq!(result, Err(InstructionError::MissingRequiredSignature));
     }
 
+    #[test]
+    fn advance_and_authorize_inx_ok() {
+        prepare_mockup!(invoke_context, instruction_accounts, rent);
+        push_instruction_context!(
+            invoke_context,
+            transaction_context,
+            instruction_context,
+            instruction_accounts
+        );
+        let mut nonce_account = instruction_context
+            .try_borrow_instruction_account(transaction_context, NONCE_ACCOUNT_INDEX)
+            .unwrap();
+        let mut signers = HashSet::new();
+        signers.insert(*nonce_account.get_key());
+        set_invoke_context_blockhash!(invoke_context, 31);
+        let authorized = *nonce_account.get_key();
+        initialize_nonce_account(&mut nonce_account, &authorized, &rent, &invoke_context).unwrap();
+        set_invoke_context_blockhash!(invoke_context, 63);
+        let new_authority = Pubkey::new_unique();
+        advance_and_authorize_nonce_account(
+            &mut nonce_account,
+            &new_authority,
+            &signers,
+            &invoke_context,
+        )
+        .unwrap();
+        let versions = nonce_account.get_state::<Versions>().unwrap();
+        let data = nonce::state::Data::new(
+            new_authority,
+            DurableNonce::from_blockhash(&invoke_context.blockhash),
+            invoke_context.lamports_per_signature,
+        );
+        // Both the stored nonce and the authority are replaced
+        assert_eq!(versions.state(), &State::Initialized(data));
+
+        // The previous authority can no longer advance the nonce
+        set_invoke_context_blockhash!(invoke_context, 0);
+        let result = advance_nonce_account(&mut nonce_account, &signers, &invoke_context);
+        assert_eq!(result, Err(InstructionError::MissingRequiredSignature));
+    }
+
+    #[test]
+    fn advance_and_authorize_inx_too_early_fail() {
+        prepare_mockup!(invoke_context, instruction_accounts, rent);
+        push_instruction_context!(
+            invoke_context,
+            transaction_context,
+            instruction_context,
+            instruction_accounts
+        );
+        let mut nonce_account = instruction_context
+            .try_borrow_instruction_account(transaction_context, NONCE_ACCOUNT_INDEX)
+            .unwrap();
+        let mut signers = HashSet::new();
+        signers.insert(*nonce_account.get_key());
+        set_invoke_context_blockhash!(invoke_context, 31);
+        let authorized = *nonce_account.get_key();
+        initialize_nonce_account(&mut nonce_account, &authorized, &rent, &invoke_context).unwrap();
+        let result = advance_and_authorize_nonce_account(
+            &mut nonce_account,
+            &Pubkey::new_unique(),
+            &signers,
+            &invoke_context,
+        );
+        assert_eq!(result, Err(SystemError::NonceBlockhashNotExpired.into()));
+        // The authority is left unchanged on failure
+        let versions = nonce_account.get_state::<Versions>().unwrap();
+        let State::Initialized(ref data) = versions.state() else {
+            panic!("nonce account should be initialized");
+        };
+        assert_eq!(data.authority, authorized);
+    }
+
+    #[test]
+    fn advance_and_authorize_inx_bad_authority_fail() {
+        prepare_mockup!(invoke_context, instruction_accounts, rent);
+        push_instruction_context!(
+            invoke_context,
+            transaction_context,
+            instruction_context,
+            instruction_accounts
+        );
+        let mut nonce_account = instruction_context
+            .try_borrow_instruction_account(transaction_context, NONCE_ACCOUNT_INDEX)
+            .unwrap();
+        let mut signers = HashSet::new();
+        signers.insert(*nonce_account.get_key());
+        set_invoke_context_blockhash!(invoke_context, 31);
+        let authorized = Pubkey::default();
+        initialize_nonce_account(&mut nonce_account, &authorized, &rent, &invoke_context).unwrap();
+        set_invoke_context_blockhash!(invoke_context, 63);
+        let result = advance_and_authorize_nonce_account(
+            &mut nonce_account,
+            &Pubkey::new_unique(),
+            &signers,
+            &invoke_context,
+        );
+        assert_eq!(result, Err(InstructionError::MissingRequiredSignature));
+    }
+
     #[test]
     fn verify_nonce_ok() {
         prepare_mockup!(invoke_context, instruction_accounts, rent);
@@ -1,7 +1,7 @@
 use {
     crate::system_instruction::{
-        advance_nonce_account, authorize_nonce_account, initialize_nonce_account,
-        withdraw_nonce_account,
+        advance_and_authorize_nonce_account, advance_nonce_account, authorize_nonce_account,
+        initialize_nonce_account, withdraw_nonce_account,
     },
     log::*,
     solana_program_runtime::{
@@ -9,6 +9,7 @@ use {
         sysvar_cache::get_sysvar_with_account_check,
     },
     solana_sdk::{
+        feature_set,
         instruction::InstructionError,
         nonce,
         program_utils::limited_deserialize,
@@ -468,6 +469,31 @@ declare_process_instruction!(Entrypoint, DEFAULT_COMPUTE_UNITS, |invoke_context|
                 instruction_context.try_borrow_instruction_account(transaction_context, 0)?;
             authorize_nonce_account(&mut me, &nonce_authority, &signers, invoke_context)
         }
+        SystemInstruction::AdvanceAndAuthorizeNonceAccount(nonce_authority) => {
+            if !invoke_context
+                .feature_set
+                .is_active(&feature_set::enable_advance_and_authorize_nonce_ix::id())
+            {
+                return Err(InstructionError::InvalidInstructionData);
+            }
+            instruction_context.check_number_of_instruction_accounts(1)?;
+            let mut me =
+                instruction_context.try_borrow_instruction_account(transaction_context, 0)?;
+            #[allow(deprecated)]
+            let recent_blockhashes = get_sysvar_with_account_check::recent_blockhashes(
+                invoke_context,
+                instruction_context,
+                1,
+            )?;
+            if recent_blockhashes.is_empty() {
+                ic_msg!(
+                    invoke_context,
+                    "Advance nonce account: recent blockhash list is empty",
+                );
+                return Err(SystemError::NonceNoRecentBlockhashes.into());
+            }
+            advance_and_authorize_nonce_account(&mut me, &nonce_authority, &signers, invoke_context)
+        }
         SystemInstruction::UpgradeNonceAccount => {
             instruction_context.check_number_of_instruction_accounts(1)?;
             let mut nonce_account =
@@ -1770,6 +1796,101 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_process_advance_and_authorize_ix_ok() {
+        let nonce_address = Pubkey::new_unique();
+        let nonce_account = nonce_account::create_account(1_000_000).into_inner();
+        #[allow(deprecated)]
+        let blockhash_id = sysvar::recent_blockhashes::id();
+        let accounts = process_instruction(
+            &serialize(&SystemInstruction::InitializeNonceAccount(nonce_address)).unwrap(),
+            vec![
+                (nonce_address, nonce_account),
+                (blockhash_id, create_default_recent_blockhashes_account()),
+                (sysvar::rent::id(), create_default_rent_account()),
+            ],
+            vec![
+                AccountMeta {
+                    pubkey: nonce_address,
+                    is_signer: true,
+                    is_writable: true,
+                },
+                AccountMeta {
+                    pubkey: blockhash_id,
+                    is_signer: false,
+                    is_writable: false,
+                },
+                AccountMeta {
+                    pubkey: sysvar::rent::id(),
+                    is_signer: false,
+                    is_writable: false,
+                },
+            ],
+            Ok(()),
+        );
+        let new_authority = Pubkey::new_unique();
+        let instruction_data = serialize(&SystemInstruction::AdvanceAndAuthorizeNonceAccount(
+            new_authority,
+        ))
+        .unwrap();
+        let transaction_accounts = vec![
+            (nonce_address, accounts[0].clone()),
+            (blockhash_id, create_default_recent_blockhashes_account()),
+        ];
+        let instruction_accounts = vec![
+            AccountMeta {
+                pubkey: nonce_address,
+                is_signer: true,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: blockhash_id,
+                is_signer: false,
+                is_writable: false,
+            },
+        ];
+
+        // Rejected before the feature is activated
+        mock_process_instruction(
+            &system_program::id(),
+            Vec::new(),
+            &instruction_data,
+            transaction_accounts.clone(),
+            instruction_accounts.clone(),
+            Err(InstructionError::InvalidInstructionData),
+            Entrypoint::vm,
+            |invoke_context: &mut InvokeContext| {
+                invoke_context.blockhash = hash(&serialize(&0).unwrap());
+                invoke_context.feature_set =
+                    std::sync::Arc::new(feature_set::FeatureSet::default());
+            },
+            |_invoke_context| {},
+        );
+
+        let accounts = mock_process_instruction(
+            &system_program::id(),
+            Vec::new(),
+            &instruction_data,
+            transaction_accounts,
+            instruction_accounts,
+            Ok(()),
+            Entrypoint::vm,
+            |invoke_context: &mut InvokeContext| {
+                invoke_context.blockhash = hash(&serialize(&0).unwrap());
+            },
+            |_invoke_context| {},
+        );
+        let versions = accounts[0].deserialize_data::<NonceVersions>().unwrap();
+        let NonceState::Initialized(ref data) = versions.state() else {
+            panic!("nonce account should be initialized");
+        };
+        assert_eq!(data.authority, new_authority);
+        assert_eq!(
+            data.durable_nonce,
+            DurableNonce::from_blockhash(&hash(&serialize(&0).unwrap()))
+        );
+    }
+
     #[test]
     fn test_process_authorize_bad_account_data_fail() {
         let nonce_address = Pubkey::new_unique();
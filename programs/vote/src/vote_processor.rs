@@ -9,6 +9,7 @@ use {
         sysvar_cache::get_sysvar_with_account_check,
     },
     solana_sdk::{
+        feature_set,
         instruction::InstructionError,
         program_utils::limited_deserialize,
         pubkey::Pubkey,
@@ -141,9 +142,15 @@ declare_process_instruction!(Entrypoint, DEFAULT_COMPUTE_UNITS, |invoke_context|
         VoteInstruction::UpdateCommission(commission) => {
             let sysvar_cache = invoke_context.get_sysvar_cache();
 
+            let epoch_start_commission = sysvar_cache
+                .get_epoch_start_commissions()
+                .ok()
+                .and_then(|commissions| commissions.get(me.get_key()).copied());
+
             vote_state::update_commission(
                 &mut me,
                 commission,
+                epoch_start_commission,
                 &signers,
                 sysvar_cache.get_epoch_schedule()?.as_ref(),
                 sysvar_cache.get_clock()?.as_ref(),
@@ -192,6 +199,26 @@ declare_process_instruction!(Entrypoint, DEFAULT_COMPUTE_UNITS, |invoke_context|
                 &invoke_context.feature_set,
             )
         }
+        VoteInstruction::TowerSync(tower_sync)
+        | VoteInstruction::TowerSyncSwitch(tower_sync, _) => {
+            if !invoke_context
+                .feature_set
+                .is_active(&feature_set::enable_tower_sync_ix::id())
+            {
+                return Err(InstructionError::InvalidInstructionData);
+            }
+            let sysvar_cache = invoke_context.get_sysvar_cache();
+            let slot_hashes = sysvar_cache.get_slot_hashes()?;
+            let clock = sysvar_cache.get_clock()?;
+            vote_state::process_tower_sync(
+                &mut me,
+                slot_hashes.slot_hashes(),
+                &clock,
+                tower_sync,
+                &signers,
+                &invoke_context.feature_set,
+            )
+        }
 
         VoteInstruction::Withdraw(lamports) => {
             instruction_context.check_number_of_instruction_accounts(2)?;
@@ -241,12 +268,13 @@ mod tests {
             vote_error::VoteError,
             vote_instruction::{
                 authorize, authorize_checked, compact_update_vote_state,
-                compact_update_vote_state_switch, create_account_with_config, update_commission,
-                update_validator_identity, update_vote_state, update_vote_state_switch, vote,
-                vote_switch, withdraw, CreateVoteAccountConfig, VoteInstruction,
+                compact_update_vote_state_switch, create_account_with_config, tower_sync,
+                tower_sync_switch, update_commission, update_validator_identity, update_vote_state,
+                update_vote_state_switch, vote, vote_switch, withdraw, CreateVoteAccountConfig,
+                VoteInstruction,
             },
             vote_state::{
-                self, Lockout, Vote, VoteAuthorize, VoteAuthorizeCheckedWithSeedArgs,
+                self, Lockout, TowerSync, Vote, VoteAuthorize, VoteAuthorizeCheckedWithSeedArgs,
                 VoteAuthorizeWithSeedArgs, VoteInit, VoteState, VoteStateUpdate, VoteStateVersions,
             },
         },
@@ -494,7 +522,17 @@ mod tests {
                     true,
                 ),
                 (
-                    serialize(&VoteInstruction::CompactUpdateVoteState(vote_state_update)).unwrap(),
+                    serialize(&VoteInstruction::CompactUpdateVoteState(
+                        vote_state_update.clone(),
+                    ))
+                    .unwrap(),
+                    true,
+                ),
+                (
+                    serialize(&VoteInstruction::TowerSync(TowerSync::from(
+                        vote_state_update,
+                    )))
+                    .unwrap(),
                     true,
                 ),
             ],
@@ -859,6 +897,57 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_tower_sync_feature_gate() {
+        let (vote_pubkey, vote_account) = create_test_account();
+        let tower_sync = TowerSync::from(vec![(1, 1)]);
+        let slot_hashes = SlotHashes::new(&[(1, tower_sync.hash)]);
+        let transaction_accounts = vec![
+            (vote_pubkey, vote_account),
+            (
+                sysvar::slot_hashes::id(),
+                account::create_account_shared_data_for_test(&slot_hashes),
+            ),
+            (sysvar::clock::id(), create_default_clock_account()),
+        ];
+        let instruction_accounts = vec![AccountMeta {
+            pubkey: vote_pubkey,
+            is_signer: true,
+            is_writable: true,
+        }];
+
+        for instruction_data in [
+            serialize(&VoteInstruction::TowerSync(tower_sync.clone())).unwrap(),
+            serialize(&VoteInstruction::TowerSyncSwitch(
+                tower_sync.clone(),
+                Hash::default(),
+            ))
+            .unwrap(),
+        ] {
+            // rejected until the feature is activated
+            process_instruction_disabled_features(
+                &instruction_data,
+                transaction_accounts.clone(),
+                instruction_accounts.clone(),
+                Err(InstructionError::InvalidInstructionData),
+            );
+
+            let accounts = process_instruction(
+                &instruction_data,
+                transaction_accounts.clone(),
+                instruction_accounts.clone(),
+                Ok(()),
+            );
+            let vote_state: VoteState = StateMut::<VoteStateVersions>::state(&accounts[0])
+                .unwrap()
+                .convert_to_current();
+            assert_eq!(
+                vote_state.votes,
+                vec![vote_state::LandedVote::from(Lockout::new(1))]
+            );
+        }
+    }
+
     #[test]
     fn test_authorize_voter() {
         let (vote_pubkey, vote_account) = create_test_account();
@@ -1904,6 +1993,21 @@ mod tests {
             Err(InstructionError::InvalidAccountData),
         );
 
+        process_instruction_as_one_arg(
+            &tower_sync(&Pubkey::default(), &Pubkey::default(), TowerSync::default()),
+            Err(InstructionError::InvalidAccountData),
+        );
+
+        process_instruction_as_one_arg(
+            &tower_sync_switch(
+                &Pubkey::default(),
+                &Pubkey::default(),
+                TowerSync::default(),
+                Hash::default(),
+            ),
+            Err(InstructionError::InvalidAccountData),
+        );
+
         process_instruction_as_one_arg(
             &update_validator_identity(
                 &Pubkey::new_unique(),
@@ -28,13 +28,18 @@ use {
     },
 };
 
-#[frozen_abi(digest = "2AuJFjx7SYrJ2ugCfH1jFh3Lr9UHMEPfKwwk1NcjqND1")]
+// Maximum number of percentage points the commission may increase by within an epoch
+pub const MAX_COMMISSION_INCREASE: u8 = 5;
+
+#[frozen_abi(digest = "3fYPrw1K52k7TdN3A3BiFoDfaVDBLkYjzE3ggJeVWmcR")]
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, AbiEnumVisitor, AbiExample)]
 pub enum VoteTransaction {
     Vote(Vote),
     VoteStateUpdate(VoteStateUpdate),
     #[serde(with = "serde_compact_vote_state_update")]
     CompactVoteStateUpdate(VoteStateUpdate),
+    #[serde(with = "serde_tower_sync")]
+    TowerSync(TowerSync),
 }
 
 impl VoteTransaction {
@@ -43,6 +48,7 @@ impl VoteTransaction {
             VoteTransaction::Vote(vote) => vote.slots.clone(),
             VoteTransaction::VoteStateUpdate(vote_state_update) => vote_state_update.slots(),
             VoteTransaction::CompactVoteStateUpdate(vote_state_update) => vote_state_update.slots(),
+            VoteTransaction::TowerSync(tower_sync) => tower_sync.slots(),
         }
     }
 
@@ -53,6 +59,7 @@ impl VoteTransaction {
             | VoteTransaction::CompactVoteStateUpdate(vote_state_update) => {
                 vote_state_update.lockouts[i].slot()
             }
+            VoteTransaction::TowerSync(tower_sync) => tower_sync.lockouts[i].slot(),
         }
     }
 
@@ -63,6 +70,7 @@ impl VoteTransaction {
             | VoteTransaction::CompactVoteStateUpdate(vote_state_update) => {
                 vote_state_update.lockouts.len()
             }
+            VoteTransaction::TowerSync(tower_sync) => tower_sync.lockouts.len(),
         }
     }
 
@@ -73,6 +81,7 @@ impl VoteTransaction {
             | VoteTransaction::CompactVoteStateUpdate(vote_state_update) => {
                 vote_state_update.lockouts.is_empty()
             }
+            VoteTransaction::TowerSync(tower_sync) => tower_sync.lockouts.is_empty(),
         }
     }
 
@@ -81,6 +90,7 @@ impl VoteTransaction {
             VoteTransaction::Vote(vote) => vote.hash,
             VoteTransaction::VoteStateUpdate(vote_state_update) => vote_state_update.hash,
             VoteTransaction::CompactVoteStateUpdate(vote_state_update) => vote_state_update.hash,
+            VoteTransaction::TowerSync(tower_sync) => tower_sync.hash,
         }
     }
 
@@ -91,6 +101,7 @@ impl VoteTransaction {
             | VoteTransaction::CompactVoteStateUpdate(vote_state_update) => {
                 vote_state_update.timestamp
             }
+            VoteTransaction::TowerSync(tower_sync) => tower_sync.timestamp,
         }
     }
 
@@ -101,6 +112,7 @@ impl VoteTransaction {
             | VoteTransaction::CompactVoteStateUpdate(vote_state_update) => {
                 vote_state_update.timestamp = ts
             }
+            VoteTransaction::TowerSync(tower_sync) => tower_sync.timestamp = ts,
         }
     }
 
@@ -111,6 +123,7 @@ impl VoteTransaction {
             | VoteTransaction::CompactVoteStateUpdate(vote_state_update) => {
                 vote_state_update.last_voted_slot()
             }
+            VoteTransaction::TowerSync(tower_sync) => tower_sync.last_voted_slot(),
         }
     }
 
@@ -131,6 +144,12 @@ impl From<VoteStateUpdate> for VoteTransaction {
     }
 }
 
+impl From<TowerSync> for VoteTransaction {
+    fn from(tower_sync: TowerSync) -> Self {
+        VoteTransaction::TowerSync(tower_sync)
+    }
+}
+
 // utility function, used by Stakes, tests
 pub fn from<T: ReadableAccount>(account: &T) -> Option<VoteState> {
     VoteState::deserialize(account.data()).ok()
@@ -869,9 +888,13 @@ pub fn update_validator_identity<S: std::hash::BuildHasher>(
 }
 
 /// Update the vote account's commission
+///
+/// `epoch_start_commission` is the commission of the vote account at the start
+/// of the current epoch, which bounds the commission increases of the epoch
 pub fn update_commission<S: std::hash::BuildHasher>(
     vote_account: &mut BorrowedAccount,
     commission: u8,
+    epoch_start_commission: Option<u8>,
     signers: &HashSet<Pubkey, S>,
     epoch_schedule: &EpochSchedule,
     clock: &Clock,
@@ -912,6 +935,20 @@ pub fn update_commission<S: std::hash::BuildHasher>(
     // current authorized withdrawer must say "yay"
     verify_authorized_signer(&vote_state.authorized_withdrawer, signers)?;
 
+    if feature_set.is_active(&feature_set::limit_commission_increase::id())
+        && is_commission_increase(&vote_state, commission)
+    {
+        if !is_commission_update_allowed(clock.slot, epoch_schedule) {
+            return Err(VoteError::CommissionUpdateTooLate.into());
+        }
+        // Only vote accounts without stake at the start of the current epoch
+        // are missing, and they have no stake to earn rewards with yet
+        let epoch_start_commission = epoch_start_commission.unwrap_or(vote_state.commission);
+        if !is_commission_increase_within_limit(epoch_start_commission, commission) {
+            return Err(VoteError::CommissionIncreaseTooLarge.into());
+        }
+    }
+
     vote_state.commission = commission;
 
     set_vote_account_state(vote_account, vote_state, feature_set)
@@ -922,6 +959,13 @@ pub fn is_commission_increase(vote_state: &VoteState, commission: u8) -> bool {
     commission > vote_state.commission
 }
 
+/// Given a proposed new commission, returns true if it does not raise the
+/// commission by more than `MAX_COMMISSION_INCREASE` percentage points over
+/// the commission at the start of the epoch
+pub fn is_commission_increase_within_limit(epoch_start_commission: u8, commission: u8) -> bool {
+    commission.saturating_sub(epoch_start_commission) <= MAX_COMMISSION_INCREASE
+}
+
 /// Given the current slot and epoch schedule, determine if a commission change
 /// is allowed
 pub fn is_commission_update_allowed(slot: Slot, epoch_schedule: &EpochSchedule) -> bool {
@@ -1122,6 +1166,46 @@ pub fn do_process_vote_state_update(
     )
 }
 
+pub fn process_tower_sync<S: std::hash::BuildHasher>(
+    vote_account: &mut BorrowedAccount,
+    slot_hashes: &[SlotHash],
+    clock: &Clock,
+    tower_sync: TowerSync,
+    signers: &HashSet<Pubkey, S>,
+    feature_set: &FeatureSet,
+) -> Result<(), InstructionError> {
+    let mut vote_state = verify_and_get_vote_state(vote_account, clock, signers)?;
+    do_process_tower_sync(
+        &mut vote_state,
+        slot_hashes,
+        clock.epoch,
+        clock.slot,
+        tower_sync,
+        Some(feature_set),
+    )?;
+    set_vote_account_state(vote_account, vote_state, feature_set)
+}
+
+pub fn do_process_tower_sync(
+    vote_state: &mut VoteState,
+    slot_hashes: &[SlotHash],
+    epoch: u64,
+    slot: u64,
+    tower_sync: TowerSync,
+    feature_set: Option<&FeatureSet>,
+) -> Result<(), VoteError> {
+    // A tower sync carries the same proposed tower as a vote state update, only
+    // in a more compact wire format, so it is validated and applied identically
+    do_process_vote_state_update(
+        vote_state,
+        slot_hashes,
+        epoch,
+        slot,
+        VoteStateUpdate::from(tower_sync),
+        feature_set,
+    )
+}
+
 // This function is used:
 // a. In many tests.
 // b. In the genesis tool that initializes a cluster to create the bootstrap validator.
@@ -1480,6 +1564,7 @@ mod tests {
             update_commission(
                 &mut borrowed_account,
                 11,
+                None,
                 &signers,
                 &epoch_schedule,
                 &first_half_clock,
@@ -1501,6 +1586,7 @@ mod tests {
             update_commission(
                 &mut borrowed_account,
                 12,
+                None,
                 &signers,
                 &epoch_schedule,
                 &second_half_clock,
@@ -1522,6 +1608,7 @@ mod tests {
             update_commission(
                 &mut borrowed_account,
                 10,
+                None,
                 &signers,
                 &epoch_schedule,
                 &first_half_clock,
@@ -1543,6 +1630,7 @@ mod tests {
             update_commission(
                 &mut borrowed_account,
                 9,
+                None,
                 &signers,
                 &epoch_schedule,
                 &second_half_clock,
@@ -1565,6 +1653,7 @@ mod tests {
             update_commission(
                 &mut borrowed_account,
                 9,
+                None,
                 &signers,
                 &epoch_schedule,
                 &second_half_clock,
@@ -1582,6 +1671,267 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_update_commission_increase_limit() {
+        let node_pubkey = Pubkey::new_unique();
+        let withdrawer_pubkey = Pubkey::new_unique();
+        let clock = Clock::default();
+        let vote_state = VoteState::new(
+            &VoteInit {
+                node_pubkey,
+                authorized_voter: withdrawer_pubkey,
+                authorized_withdrawer: withdrawer_pubkey,
+                commission: 10,
+            },
+            &clock,
+        );
+
+        let serialized =
+            bincode::serialize(&VoteStateVersions::Current(Box::new(vote_state.clone()))).unwrap();
+        let serialized_len = serialized.len();
+        let rent = Rent::default();
+        let lamports = rent.minimum_balance(serialized_len);
+        let mut vote_account = AccountSharedData::new(lamports, serialized_len, &id());
+        vote_account.set_data_from_slice(&serialized);
+
+        let processor_account = AccountSharedData::new(0, 0, &solana_sdk::native_loader::id());
+        let transaction_context = TransactionContext::new(
+            vec![(id(), processor_account), (node_pubkey, vote_account)],
+            rent,
+            0,
+            0,
+        );
+        let mut instruction_context = InstructionContext::default();
+        instruction_context.configure(
+            &[0],
+            &[InstructionAccount {
+                index_in_transaction: 1,
+                index_in_caller: 1,
+                index_in_callee: 0,
+                is_signer: false,
+                is_writable: true,
+            }],
+            &[],
+        );
+        let mut borrowed_account = instruction_context
+            .try_borrow_instruction_account(&transaction_context, 0)
+            .unwrap();
+
+        let epoch_schedule = std::sync::Arc::new(EpochSchedule::without_warmup());
+        let first_half_clock = std::sync::Arc::new(Clock {
+            slot: epoch_schedule.slots_per_epoch / 4,
+            ..Clock::default()
+        });
+        let second_half_clock = std::sync::Arc::new(Clock {
+            slot: (epoch_schedule.slots_per_epoch * 3) / 4,
+            ..Clock::default()
+        });
+
+        let mut feature_set = FeatureSet::default();
+        feature_set.activate(&feature_set::allow_commission_decrease_at_any_time::id(), 1);
+        let signers: HashSet<Pubkey> = vec![withdrawer_pubkey].into_iter().collect();
+        let commission = |borrowed_account: &BorrowedAccount| {
+            borrowed_account
+                .get_state::<VoteStateVersions>()
+                .unwrap()
+                .convert_to_current()
+                .commission
+        };
+
+        // Large increase in second half of epoch -- allowed without the feature
+        assert_matches!(
+            update_commission(
+                &mut borrowed_account,
+                100,
+                None,
+                &signers,
+                &epoch_schedule,
+                &second_half_clock,
+                &feature_set
+            ),
+            Ok(())
+        );
+        assert_eq!(commission(&borrowed_account), 100);
+        assert_matches!(
+            update_commission(
+                &mut borrowed_account,
+                10,
+                None,
+                &signers,
+                &epoch_schedule,
+                &second_half_clock,
+                &feature_set
+            ),
+            Ok(())
+        );
+
+        feature_set.activate(&feature_set::limit_commission_increase::id(), 1);
+        let epoch_start_commission = Some(10);
+
+        // Increase above the limit in first half of epoch -- disallowed
+        assert_eq!(
+            update_commission(
+                &mut borrowed_account,
+                11 + MAX_COMMISSION_INCREASE,
+                epoch_start_commission,
+                &signers,
+                &epoch_schedule,
+                &first_half_clock,
+                &feature_set
+            ),
+            Err(VoteError::CommissionIncreaseTooLarge.into())
+        );
+        assert_eq!(commission(&borrowed_account), 10);
+
+        // Increase within the limit in second half of epoch -- disallowed
+        assert_eq!(
+            update_commission(
+                &mut borrowed_account,
+                10 + MAX_COMMISSION_INCREASE,
+                epoch_start_commission,
+                &signers,
+                &epoch_schedule,
+                &second_half_clock,
+                &feature_set
+            ),
+            Err(VoteError::CommissionUpdateTooLate.into())
+        );
+        assert_eq!(commission(&borrowed_account), 10);
+
+        // Several increases within the limit in first half of epoch -- allowed
+        // while their sum stays within the limit
+        assert_matches!(
+            update_commission(
+                &mut borrowed_account,
+                12,
+                epoch_start_commission,
+                &signers,
+                &epoch_schedule,
+                &first_half_clock,
+                &feature_set
+            ),
+            Ok(())
+        );
+        assert_eq!(commission(&borrowed_account), 12);
+        assert_matches!(
+            update_commission(
+                &mut borrowed_account,
+                10 + MAX_COMMISSION_INCREASE,
+                epoch_start_commission,
+                &signers,
+                &epoch_schedule,
+                &first_half_clock,
+                &feature_set
+            ),
+            Ok(())
+        );
+        assert_eq!(commission(&borrowed_account), 10 + MAX_COMMISSION_INCREASE);
+        assert_eq!(
+            update_commission(
+                &mut borrowed_account,
+                11 + MAX_COMMISSION_INCREASE,
+                epoch_start_commission,
+                &signers,
+                &epoch_schedule,
+                &first_half_clock,
+                &feature_set
+            ),
+            Err(VoteError::CommissionIncreaseTooLarge.into())
+        );
+        assert_eq!(commission(&borrowed_account), 10 + MAX_COMMISSION_INCREASE);
+
+        // Decreasing and increasing again does not reset the limit of the epoch
+        assert_matches!(
+            update_commission(
+                &mut borrowed_account,
+                10,
+                epoch_start_commission,
+                &signers,
+                &epoch_schedule,
+                &first_half_clock,
+                &feature_set
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            update_commission(
+                &mut borrowed_account,
+                11 + MAX_COMMISSION_INCREASE,
+                epoch_start_commission,
+                &signers,
+                &epoch_schedule,
+                &first_half_clock,
+                &feature_set
+            ),
+            Err(VoteError::CommissionIncreaseTooLarge.into())
+        );
+        assert_matches!(
+            update_commission(
+                &mut borrowed_account,
+                10 + MAX_COMMISSION_INCREASE,
+                epoch_start_commission,
+                &signers,
+                &epoch_schedule,
+                &first_half_clock,
+                &feature_set
+            ),
+            Ok(())
+        );
+        assert_eq!(commission(&borrowed_account), 10 + MAX_COMMISSION_INCREASE);
+
+        // The next epoch starts from the commission reached in this one
+        let epoch_start_commission = Some(10 + MAX_COMMISSION_INCREASE);
+        assert_matches!(
+            update_commission(
+                &mut borrowed_account,
+                10 + 2 * MAX_COMMISSION_INCREASE,
+                epoch_start_commission,
+                &signers,
+                &epoch_schedule,
+                &first_half_clock,
+                &feature_set
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            commission(&borrowed_account),
+            10 + 2 * MAX_COMMISSION_INCREASE
+        );
+
+        // Without an epoch start commission, the current commission is the baseline
+        assert_matches!(
+            update_commission(
+                &mut borrowed_account,
+                10 + 3 * MAX_COMMISSION_INCREASE,
+                None,
+                &signers,
+                &epoch_schedule,
+                &first_half_clock,
+                &feature_set
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            commission(&borrowed_account),
+            10 + 3 * MAX_COMMISSION_INCREASE
+        );
+
+        // Large decrease in second half of epoch -- still allowed
+        assert_matches!(
+            update_commission(
+                &mut borrowed_account,
+                0,
+                epoch_start_commission,
+                &signers,
+                &epoch_schedule,
+                &second_half_clock,
+                &feature_set
+            ),
+            Ok(())
+        );
+        assert_eq!(commission(&borrowed_account), 0);
+    }
+
     #[test]
     fn test_vote_double_lockout_after_expiration() {
         let voter_pubkey = solana_sdk::pubkey::new_rand();
@@ -440,6 +440,142 @@ async fn test_range_proof_u64() {
     .await;
 }
 
+#[tokio::test]
+async fn test_range_proof_u64_batch() {
+    let amounts = [1_u64, 23_u64, 456_u64];
+    let success_proof_data: Vec<_> = amounts
+        .iter()
+        .map(|amount| {
+            let (commitment, opening) = Pedersen::new(*amount);
+            RangeProofU64Data::new(&commitment, *amount, &opening).unwrap()
+        })
+        .collect();
+
+    // a proof of a different amount than the one committed to fails the whole batch
+    let mut fail_proof_data = success_proof_data.clone();
+    let (commitment, opening) = Pedersen::new(7_u64);
+    fail_proof_data[1] = RangeProofU64Data::new(&commitment, 8_u64, &opening).unwrap();
+
+    let mut program_test = ProgramTest::default();
+    program_test.set_compute_max_units(500_000);
+    let success_proof_account = Pubkey::new_unique();
+    program_test.add_account(
+        success_proof_account,
+        Account {
+            lamports: 1_000_000_000,
+            data: bytemuck::cast_slice(&success_proof_data).to_vec(),
+            owner: Pubkey::new_unique(),
+            ..Account::default()
+        },
+    );
+    let fail_proof_account = Pubkey::new_unique();
+    program_test.add_account(
+        fail_proof_account,
+        Account {
+            lamports: 1_000_000_000,
+            data: bytemuck::cast_slice(&fail_proof_data).to_vec(),
+            owner: Pubkey::new_unique(),
+            ..Account::default()
+        },
+    );
+    let mut context = program_test.start_with_context().await;
+
+    let client = &mut context.banks_client;
+    let payer = &context.payer;
+    let recent_blockhash = context.last_blockhash;
+
+    // a single proof fits in the instruction data
+    let instructions = vec![verify_range_proof_u64_batch(&success_proof_data[..1])];
+    let transaction = Transaction::new_signed_with_payer(
+        &instructions.with_max_compute_unit_limit(),
+        Some(&payer.pubkey()),
+        &[payer],
+        recent_blockhash,
+    );
+    client.process_transaction(transaction).await.unwrap();
+
+    let instructions = vec![verify_range_proof_u64_batch(&fail_proof_data[1..2])];
+    let transaction = Transaction::new_signed_with_payer(
+        &instructions.with_max_compute_unit_limit(),
+        Some(&payer.pubkey()),
+        &[payer],
+        recent_blockhash,
+    );
+    let err = client
+        .process_transaction(transaction)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(0, InstructionError::InvalidInstructionData)
+    );
+
+    // verify all the proofs in an account, and a subset of them at an offset
+    let num_proofs = amounts.len() as u32;
+    let proof_size = size_of::<RangeProofU64Data>() as u32;
+    for (offset, num_proofs) in [(0, num_proofs), (proof_size, num_proofs - 1)] {
+        let instructions = vec![verify_range_proof_u64_batch_from_account(
+            &success_proof_account,
+            offset,
+            num_proofs,
+        )];
+        let transaction = Transaction::new_signed_with_payer(
+            &instructions.with_max_compute_unit_limit(),
+            Some(&payer.pubkey()),
+            &[payer],
+            recent_blockhash,
+        );
+        client.process_transaction(transaction).await.unwrap();
+    }
+
+    // try to verify a batch with an invalid proof from an account
+    let instructions = vec![verify_range_proof_u64_batch_from_account(
+        &fail_proof_account,
+        0,
+        num_proofs,
+    )];
+    let transaction = Transaction::new_signed_with_payer(
+        &instructions.with_max_compute_unit_limit(),
+        Some(&payer.pubkey()),
+        &[payer],
+        recent_blockhash,
+    );
+    let err = client
+        .process_transaction(transaction)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(0, InstructionError::InvalidInstructionData)
+    );
+
+    // try to read more proofs than the account holds, or no proofs at all
+    for (num_proofs, expected_err) in [
+        (num_proofs + 1, InstructionError::InvalidAccountData),
+        (0, InstructionError::InvalidInstructionData),
+    ] {
+        let instructions = vec![verify_range_proof_u64_batch_from_account(
+            &success_proof_account,
+            0,
+            num_proofs,
+        )];
+        let transaction = Transaction::new_signed_with_payer(
+            &instructions.with_max_compute_unit_limit(),
+            Some(&payer.pubkey()),
+            &[payer],
+            recent_blockhash,
+        );
+        let err = client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(err, TransactionError::InstructionError(0, expected_err));
+    }
+}
+
 #[tokio::test]
 async fn test_batched_range_proof_u64() {
     let amount_1 = 23_u64;
@@ -31,8 +31,11 @@ pub const VERIFY_CIPHERTEXT_COMMITMENT_EQUALITY_COMPUTE_UNITS: u64 = 6_400;
 pub const VERIFY_GROUPED_CIPHERTEXT_2_HANDLES_VALIDITY_COMPUTE_UNITS: u64 = 6_400;
 pub const VERIFY_BATCHED_GROUPED_CIPHERTEXT_2_HANDLES_VALIDITY_COMPUTE_UNITS: u64 = 13_000;
 pub const VERIFY_FEE_SIGMA_COMPUTE_UNITS: u64 = 6_500;
+pub const VERIFY_RANGE_PROOF_U64_BATCH_BASE_COMPUTE_UNITS: u64 = 80_000;
+pub const VERIFY_RANGE_PROOF_U64_BATCH_PER_PROOF_COMPUTE_UNITS: u64 = 25_000;
 
 const INSTRUCTION_DATA_LENGTH_WITH_PROOF_ACCOUNT: usize = 5;
+const INSTRUCTION_DATA_LENGTH_WITH_PROOF_BATCH_ACCOUNT: usize = 9;
 
 fn process_verify_proof<T, U>(invoke_context: &mut InvokeContext) -> Result<(), InstructionError>
 where
@@ -137,6 +140,78 @@ where
     Ok(())
 }
 
+fn process_verify_range_proof_u64_batch(
+    invoke_context: &mut InvokeContext,
+) -> Result<(), InstructionError> {
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+    let instruction_data = instruction_context.get_instruction_data();
+
+    // if instruction data is exactly 9 bytes, then read the proofs from an account
+    let proof_data = if instruction_data.len() == INSTRUCTION_DATA_LENGTH_WITH_PROOF_BATCH_ACCOUNT {
+        let proof_data_account =
+            instruction_context.try_borrow_instruction_account(transaction_context, 0)?;
+
+        // the first byte is the instruction discriminator
+        let proof_data_offset = u32::from_le_bytes(
+            instruction_data[1..INSTRUCTION_DATA_LENGTH_WITH_PROOF_ACCOUNT]
+                .try_into()
+                .map_err(|_| InstructionError::InvalidInstructionData)?,
+        );
+        let num_proofs = u32::from_le_bytes(
+            instruction_data[INSTRUCTION_DATA_LENGTH_WITH_PROOF_ACCOUNT
+                ..INSTRUCTION_DATA_LENGTH_WITH_PROOF_BATCH_ACCOUNT]
+                .try_into()
+                .map_err(|_| InstructionError::InvalidInstructionData)?,
+        );
+        let proof_data_start: usize = proof_data_offset
+            .try_into()
+            .map_err(|_| InstructionError::InvalidInstructionData)?;
+        let proof_data_end = usize::try_from(num_proofs)
+            .ok()
+            .and_then(|num_proofs| num_proofs.checked_mul(std::mem::size_of::<RangeProofU64Data>()))
+            .and_then(|proof_data_len| proof_data_start.checked_add(proof_data_len))
+            .ok_or(InstructionError::InvalidInstructionData)?;
+        let proof_data_raw = proof_data_account
+            .get_data()
+            .get(proof_data_start..proof_data_end)
+            .ok_or(InstructionError::InvalidAccountData)?;
+
+        bytemuck::try_cast_slice::<u8, RangeProofU64Data>(proof_data_raw)
+            .map_err(|_| {
+                ic_msg!(invoke_context, "invalid proof data");
+                InstructionError::InvalidInstructionData
+            })?
+            .to_vec()
+    } else {
+        instruction_data
+            .get(1..)
+            .and_then(|data| bytemuck::try_cast_slice::<u8, RangeProofU64Data>(data).ok())
+            .ok_or_else(|| {
+                ic_msg!(invoke_context, "invalid proof data");
+                InstructionError::InvalidInstructionData
+            })?
+            .to_vec()
+    };
+
+    if proof_data.is_empty() {
+        ic_msg!(invoke_context, "no proofs to verify");
+        return Err(InstructionError::InvalidInstructionData);
+    }
+
+    let num_proofs = u64::try_from(proof_data.len()).unwrap();
+    invoke_context
+        .consume_checked(
+            VERIFY_RANGE_PROOF_U64_BATCH_PER_PROOF_COMPUTE_UNITS.saturating_mul(num_proofs),
+        )
+        .map_err(|_| InstructionError::ComputationalBudgetExceeded)?;
+
+    RangeProofU64Data::verify_batch(&proof_data).map_err(|err| {
+        ic_msg!(invoke_context, "proof verification failed: {:?}", err);
+        InstructionError::InvalidInstructionData
+    })
+}
+
 fn process_close_proof_context(invoke_context: &mut InvokeContext) -> Result<(), InstructionError> {
     let transaction_context = &invoke_context.transaction_context;
     let instruction_context = transaction_context.get_current_instruction_context()?;
@@ -344,5 +419,19 @@ declare_process_instruction!(Entrypoint, 0, |invoke_context| {
             ic_msg!(invoke_context, "VerifyFeeSigma");
             process_verify_proof::<FeeSigmaProofData, FeeSigmaProofContext>(invoke_context)
         }
+        ProofInstruction::VerifyRangeProofU64Batch => {
+            if !invoke_context
+                .feature_set
+                .is_active(&feature_set::enable_zk_range_proof_batch_verification::id())
+            {
+                return Err(InstructionError::InvalidInstructionData);
+            }
+
+            invoke_context
+                .consume_checked(VERIFY_RANGE_PROOF_U64_BATCH_BASE_COMPUTE_UNITS)
+                .map_err(|_| InstructionError::ComputationalBudgetExceeded)?;
+            ic_msg!(invoke_context, "VerifyRangeProofU64Batch");
+            process_verify_range_proof_u64_batch(invoke_context)
+        }
     }
 });
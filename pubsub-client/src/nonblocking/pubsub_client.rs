@@ -179,8 +179,8 @@ use {
     solana_rpc_client_api::{
         config::{
             RpcAccountInfoConfig, RpcBlockSubscribeConfig, RpcBlockSubscribeFilter,
-            RpcProgramAccountsConfig, RpcSignatureSubscribeConfig, RpcTransactionLogsConfig,
-            RpcTransactionLogsFilter,
+            RpcProgramAccountsConfig, RpcSignatureSubscribeConfig, RpcSysvarSubscribeConfig,
+            RpcSysvarSubscribeFilter, RpcTransactionLogsConfig, RpcTransactionLogsFilter,
         },
         error_object::RpcErrorObject,
         filter::maybe_map_filters,
@@ -479,6 +479,25 @@ impl PubsubClient {
         self.subscribe("root", json!([])).await
     }
 
+    /// Subscribe to sysvar events.
+    ///
+    /// Receives messages of type [`UiAccount`], with the sysvar data decoded
+    /// to JSON, whenever the selected sysvar is updated.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the [`sysvarSubscribe`] RPC method.
+    ///
+    /// [`sysvarSubscribe`]: https://solana.com/docs/rpc/websocket#sysvarsubscribe
+    pub async fn sysvar_subscribe(
+        &self,
+        sysvar: RpcSysvarSubscribeFilter,
+        config: Option<RpcSysvarSubscribeConfig>,
+    ) -> SubscribeResult<'_, RpcResponse<UiAccount>> {
+        let params = json!([sysvar, config]);
+        self.subscribe("sysvar", params).await
+    }
+
     /// Subscribe to transaction confirmation events.
     ///
     /// Receives messages of type [`RpcSignatureResult`] when a transaction
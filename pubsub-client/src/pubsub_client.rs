@@ -100,8 +100,8 @@ use {
     solana_rpc_client_api::{
         config::{
             RpcAccountInfoConfig, RpcBlockSubscribeConfig, RpcBlockSubscribeFilter,
-            RpcProgramAccountsConfig, RpcSignatureSubscribeConfig, RpcTransactionLogsConfig,
-            RpcTransactionLogsFilter,
+            RpcProgramAccountsConfig, RpcSignatureSubscribeConfig, RpcSysvarSubscribeConfig,
+            RpcSysvarSubscribeFilter, RpcTransactionLogsConfig, RpcTransactionLogsFilter,
         },
         filter,
         response::{
@@ -321,6 +321,12 @@ pub type VoteSubscription = (PubsubVoteClientSubscription, Receiver<RpcVote>);
 pub type PubsubRootClientSubscription = PubsubClientSubscription<Slot>;
 pub type RootSubscription = (PubsubRootClientSubscription, Receiver<Slot>);
 
+pub type PubsubSysvarClientSubscription = PubsubClientSubscription<RpcResponse<UiAccount>>;
+pub type SysvarSubscription = (
+    PubsubSysvarClientSubscription,
+    Receiver<RpcResponse<UiAccount>>,
+);
+
 /// A client for subscribing to messages from the RPC server.
 ///
 /// See the [module documentation][self].
@@ -663,6 +669,57 @@ impl PubsubClient {
         Ok((result, receiver))
     }
 
+    /// Subscribe to sysvar events.
+    ///
+    /// Receives messages of type [`UiAccount`], with the sysvar data decoded
+    /// to JSON, whenever the selected sysvar is updated.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the [`sysvarSubscribe`] RPC method.
+    ///
+    /// [`sysvarSubscribe`]: https://solana.com/docs/rpc/websocket/sysvarsubscribe
+    pub fn sysvar_subscribe(
+        url: &str,
+        sysvar: RpcSysvarSubscribeFilter,
+        config: Option<RpcSysvarSubscribeConfig>,
+    ) -> Result<SysvarSubscription, PubsubClientError> {
+        let url = Url::parse(url)?;
+        let socket = connect_with_retry(url)?;
+        let (sender, receiver) = unbounded();
+
+        let socket = Arc::new(RwLock::new(socket));
+        let socket_clone = socket.clone();
+        let exit = Arc::new(AtomicBool::new(false));
+        let exit_clone = exit.clone();
+        let body = json!({
+            "jsonrpc":"2.0",
+            "id":1,
+            "method":"sysvarSubscribe",
+            "params":[
+                sysvar,
+                config
+            ]
+        })
+        .to_string();
+        let subscription_id = PubsubSysvarClientSubscription::send_subscribe(&socket_clone, body)?;
+
+        let t_cleanup = std::thread::spawn(move || {
+            Self::cleanup_with_sender(exit_clone, &socket_clone, sender)
+        });
+
+        let result = PubsubClientSubscription {
+            message_type: PhantomData,
+            operation: "sysvar",
+            socket,
+            subscription_id,
+            t_cleanup: Some(t_cleanup),
+            exit,
+        };
+
+        Ok((result, receiver))
+    }
+
     /// Subscribe to transaction confirmation events.
     ///
     /// Receives messages of type [`RpcSignatureResult`] when a transaction
@@ -11,6 +11,7 @@ use {
         nonblocking::quic_client::{
             QuicClient, QuicClientCertificate,
             QuicClientConnection as NonblockingQuicClientConnection, QuicLazyInitializedEndpoint,
+            QuicRetryConfig,
         },
         quic_client::QuicClientConnection as BlockingQuicClientConnection,
     },
@@ -68,10 +69,11 @@ impl ConnectionPool for QuicPool {
         config: &Self::NewConnectionConfig,
         addr: &SocketAddr,
     ) -> Arc<Self::BaseClientConnection> {
-        Arc::new(Quic(Arc::new(QuicClient::new(
+        Arc::new(Quic(Arc::new(QuicClient::new_with_retry_config(
             self.endpoint.clone(),
             *addr,
             config.compute_max_parallel_streams(),
+            config.retry_config,
         ))))
     }
 }
@@ -85,6 +87,9 @@ pub struct QuicConfig {
     // The optional specified endpoint for the quic based client connections
     // If not specified, the connection cache will create as needed.
     client_endpoint: Option<Endpoint>,
+
+    // How connections are made and retried
+    retry_config: QuicRetryConfig,
 }
 
 impl Clone for QuicConfig {
@@ -95,6 +100,7 @@ impl Clone for QuicConfig {
             maybe_staked_nodes: self.maybe_staked_nodes.clone(),
             maybe_client_pubkey: self.maybe_client_pubkey,
             client_endpoint: self.client_endpoint.clone(),
+            retry_config: self.retry_config,
         }
     }
 }
@@ -110,6 +116,7 @@ impl NewConnectionConfig for QuicConfig {
             maybe_staked_nodes: None,
             maybe_client_pubkey: None,
             client_endpoint: None,
+            retry_config: QuicRetryConfig::default(),
         })
     }
 }
@@ -172,6 +179,14 @@ impl QuicConfig {
     pub fn update_client_endpoint(&mut self, client_endpoint: Endpoint) {
         self.client_endpoint = Some(client_endpoint);
     }
+
+    pub fn set_retry_config(&mut self, retry_config: QuicRetryConfig) {
+        self.retry_config = retry_config;
+    }
+
+    pub fn retry_config(&self) -> &QuicRetryConfig {
+        &self.retry_config
+    }
 }
 
 pub struct Quic(Arc<QuicClient>);
@@ -259,7 +274,7 @@ mod tests {
             QUIC_MAX_UNSTAKED_CONCURRENT_STREAMS, QUIC_MIN_STAKED_CONCURRENT_STREAMS,
             QUIC_TOTAL_STAKED_CONCURRENT_STREAMS,
         },
-        std::collections::HashMap,
+        std::{collections::HashMap, time::Duration},
     };
 
     #[test]
@@ -305,4 +320,29 @@ mod tests {
             QUIC_MIN_STAKED_CONCURRENT_STREAMS
         );
     }
+
+    #[test]
+    fn test_retry_config_backoff() {
+        let retry_config = QuicRetryConfig::default();
+        assert_eq!(retry_config.backoff(0), Duration::ZERO);
+        assert_eq!(retry_config.backoff(3), Duration::ZERO);
+
+        let retry_config = QuicRetryConfig {
+            max_connection_attempts: 5,
+            initial_backoff: Duration::from_millis(100),
+            backoff_multiplier: 2,
+            max_backoff: Duration::from_millis(500),
+            ..QuicRetryConfig::default()
+        };
+        assert_eq!(retry_config.backoff(0), Duration::ZERO);
+        assert_eq!(retry_config.backoff(1), Duration::from_millis(100));
+        assert_eq!(retry_config.backoff(2), Duration::from_millis(200));
+        assert_eq!(retry_config.backoff(3), Duration::from_millis(400));
+        assert_eq!(retry_config.backoff(4), Duration::from_millis(500));
+        assert_eq!(retry_config.backoff(usize::MAX), Duration::from_millis(500));
+
+        let mut connection_config = QuicConfig::new().unwrap();
+        connection_config.set_retry_config(retry_config);
+        assert_eq!(connection_config.clone().retry_config(), &retry_config);
+    }
 }
@@ -33,11 +33,60 @@ use {
         net::{IpAddr, Ipv4Addr, SocketAddr, UdpSocket},
         sync::{atomic::Ordering, Arc},
         thread,
+        time::Duration,
     },
     thiserror::Error,
-    tokio::{sync::OnceCell, time::timeout},
+    tokio::{
+        sync::OnceCell,
+        time::{sleep, timeout},
+    },
 };
 
+/// The number of times a send may connect or reconnect before giving up
+pub const DEFAULT_MAX_CONNECTION_ATTEMPTS: usize = 2;
+
+/// How a `QuicClient` connects, and how it retries when a connection fails
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct QuicRetryConfig {
+    /// How long to wait for a connection handshake to complete
+    pub handshake_timeout: Duration,
+    /// The number of times a send may connect or reconnect before giving up
+    pub max_connection_attempts: usize,
+    /// How long to wait before the first reconnection, zero to reconnect immediately
+    pub initial_backoff: Duration,
+    /// The factor each successive wait is multiplied by
+    pub backoff_multiplier: u32,
+    /// The longest time to wait before reconnecting
+    pub max_backoff: Duration,
+}
+
+impl Default for QuicRetryConfig {
+    fn default() -> Self {
+        Self {
+            handshake_timeout: QUIC_CONNECTION_HANDSHAKE_TIMEOUT,
+            max_connection_attempts: DEFAULT_MAX_CONNECTION_ATTEMPTS,
+            initial_backoff: Duration::ZERO,
+            backoff_multiplier: 2,
+            max_backoff: Duration::from_secs(1),
+        }
+    }
+}
+
+impl QuicRetryConfig {
+    /// How long to wait before making connection attempt number `attempt`,
+    /// where the first attempt is 0
+    pub fn backoff(&self, attempt: usize) -> Duration {
+        if attempt == 0 {
+            return Duration::ZERO;
+        }
+        let exponent = u32::try_from(attempt.saturating_sub(1)).unwrap_or(u32::MAX);
+        let multiplier = self.backoff_multiplier.saturating_pow(exponent);
+        self.initial_backoff
+            .saturating_mul(multiplier)
+            .min(self.max_backoff)
+    }
+}
+
 pub struct SkipServerVerification;
 
 impl SkipServerVerification {
@@ -172,6 +221,7 @@ impl QuicNewConnection {
     async fn make_connection(
         endpoint: Arc<QuicLazyInitializedEndpoint>,
         addr: SocketAddr,
+        handshake_timeout: Duration,
         stats: &ClientStats,
     ) -> Result<Self, QuicError> {
         let mut make_connection_measure = Measure::start("make_connection_measure");
@@ -179,8 +229,7 @@ impl QuicNewConnection {
 
         let connecting = endpoint.connect(addr, "connect")?;
         stats.total_connections.fetch_add(1, Ordering::Relaxed);
-        if let Ok(connecting_result) = timeout(QUIC_CONNECTION_HANDSHAKE_TIMEOUT, connecting).await
-        {
+        if let Ok(connecting_result) = timeout(handshake_timeout, connecting).await {
             if connecting_result.is_err() {
                 stats.connection_errors.fetch_add(1, Ordering::Relaxed);
             }
@@ -196,6 +245,7 @@ impl QuicNewConnection {
                 connection: Arc::new(connection),
             })
         } else {
+            stats.handshake_timeouts.fetch_add(1, Ordering::Relaxed);
             Err(ConnectionError::TimedOut.into())
         }
     }
@@ -210,13 +260,14 @@ impl QuicNewConnection {
     async fn make_connection_0rtt(
         &mut self,
         addr: SocketAddr,
+        handshake_timeout: Duration,
         stats: &ClientStats,
     ) -> Result<Arc<Connection>, QuicError> {
         let connecting = self.endpoint.connect(addr, "connect")?;
         stats.total_connections.fetch_add(1, Ordering::Relaxed);
         let connection = match connecting.into_0rtt() {
             Ok((connection, zero_rtt)) => {
-                if let Ok(zero_rtt) = timeout(QUIC_CONNECTION_HANDSHAKE_TIMEOUT, zero_rtt).await {
+                if let Ok(zero_rtt) = timeout(handshake_timeout, zero_rtt).await {
                     if zero_rtt {
                         stats.zero_rtt_accepts.fetch_add(1, Ordering::Relaxed);
                     } else {
@@ -224,17 +275,17 @@ impl QuicNewConnection {
                     }
                     connection
                 } else {
+                    stats.handshake_timeouts.fetch_add(1, Ordering::Relaxed);
                     return Err(ConnectionError::TimedOut.into());
                 }
             }
             Err(connecting) => {
                 stats.connection_errors.fetch_add(1, Ordering::Relaxed);
 
-                if let Ok(connecting_result) =
-                    timeout(QUIC_CONNECTION_HANDSHAKE_TIMEOUT, connecting).await
-                {
+                if let Ok(connecting_result) = timeout(handshake_timeout, connecting).await {
                     connecting_result?
                 } else {
+                    stats.handshake_timeouts.fetch_add(1, Ordering::Relaxed);
                     return Err(ConnectionError::TimedOut.into());
                 }
             }
@@ -250,6 +301,7 @@ pub struct QuicClient {
     addr: SocketAddr,
     stats: Arc<ClientStats>,
     chunk_size: usize,
+    retry_config: QuicRetryConfig,
 }
 
 impl QuicClient {
@@ -257,6 +309,15 @@ impl QuicClient {
         endpoint: Arc<QuicLazyInitializedEndpoint>,
         addr: SocketAddr,
         chunk_size: usize,
+    ) -> Self {
+        Self::new_with_retry_config(endpoint, addr, chunk_size, QuicRetryConfig::default())
+    }
+
+    pub fn new_with_retry_config(
+        endpoint: Arc<QuicLazyInitializedEndpoint>,
+        addr: SocketAddr,
+        chunk_size: usize,
+        retry_config: QuicRetryConfig,
     ) -> Self {
         Self {
             endpoint,
@@ -264,6 +325,7 @@ impl QuicClient {
             addr,
             stats: Arc::new(ClientStats::default()),
             chunk_size,
+            retry_config,
         }
     }
 
@@ -291,7 +353,15 @@ impl QuicClient {
         let mut connection_try_count = 0;
         let mut last_connection_id = 0;
         let mut last_error = None;
-        while connection_try_count < 2 {
+        let max_connection_attempts = self.retry_config.max_connection_attempts.max(1);
+        while connection_try_count < max_connection_attempts {
+            let backoff = self.retry_config.backoff(connection_try_count);
+            if last_error.is_some() && !backoff.is_zero() {
+                sleep(backoff).await;
+                stats
+                    .connection_backoff_ms
+                    .fetch_add(backoff.as_millis() as u64, Ordering::Relaxed);
+            }
             let connection = {
                 let mut conn_guard = self.connection.lock().await;
 
@@ -300,7 +370,14 @@ impl QuicClient {
                     Some(conn) => {
                         if conn.connection.stable_id() == last_connection_id {
                             // this is the problematic connection we had used before, create a new one
-                            let conn = conn.make_connection_0rtt(self.addr, stats).await;
+                            stats.connection_retries.fetch_add(1, Ordering::Relaxed);
+                            let conn = conn
+                                .make_connection_0rtt(
+                                    self.addr,
+                                    self.retry_config.handshake_timeout,
+                                    stats,
+                                )
+                                .await;
                             match conn {
                                 Ok(conn) => {
                                     info!(
@@ -331,6 +408,7 @@ impl QuicClient {
                         let conn = QuicNewConnection::make_connection(
                             self.endpoint.clone(),
                             self.addr,
+                            self.retry_config.handshake_timeout,
                             stats,
                         )
                         .await;
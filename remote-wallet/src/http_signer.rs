@@ -0,0 +1,183 @@
+//! Remote signing services
+//!
+//! The keypair URL `https://signer.example.com/v1?key=0/0` signs with the key
+//! at the derivation path `m/44'/501'/0'/0'` of the service at
+//! `https://signer.example.com/v1`. Services speak JSON-RPC 2.0 over `POST`
+//! requests to their URL, with the methods:
+//!
+//! - `getPubkey`, with the params `{"derivationPath": "m/44'/501'/0'/0'",
+//!   "confirmKey": false}`, returns the base58 pubkey of the key, after the
+//!   user confirms it if `confirmKey`
+//! - `signMessage`, with the params `{"derivationPath": "m/44'/501'/0'/0'",
+//!   "message": "<base64>"}`, returns the base58 signature of the serialized
+//!   transaction message
+//! - `signOffchainMessage`, with the same params, returns the base58
+//!   signature of the serialized off-chain message
+//!
+//! Services report the user rejecting a request with the error code `4001`.
+
+use {
+    crate::{remote_signer::RemoteSigner, remote_wallet::RemoteWalletError},
+    base64::{prelude::BASE64_STANDARD, Engine},
+    reqwest::blocking::Client,
+    serde_json::{json, Value},
+    solana_sdk::{derivation_path::DerivationPath, pubkey::Pubkey, signature::Signature},
+    std::{str::FromStr, time::Duration},
+};
+
+/// JSON-RPC error code of requests the user rejected
+pub const USER_CANCEL_ERROR_CODE: i64 = 4001;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Signer using a remote signing service
+pub struct HttpSigner {
+    url: String,
+    client: Client,
+}
+
+impl HttpSigner {
+    pub fn new(url: String) -> Result<Self, RemoteWalletError> {
+        let client = Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .map_err(|err| RemoteWalletError::RemoteSigner(err.to_string()))?;
+        Ok(Self { url, client })
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    fn send(&self, method: &str, params: Value) -> Result<String, RemoteWalletError> {
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": method,
+            "params": params,
+        });
+        let response: Value = self
+            .client
+            .post(&self.url)
+            .json(&request)
+            .send()
+            .and_then(|response| response.json())
+            .map_err(|err| RemoteWalletError::RemoteSigner(err.to_string()))?;
+        parse_response(response)
+    }
+
+    fn sign(
+        &self,
+        method: &str,
+        derivation_path: &DerivationPath,
+        data: &[u8],
+    ) -> Result<Signature, RemoteWalletError> {
+        let signature = self.send(
+            method,
+            json!({
+                "derivationPath": format!("{derivation_path:?}"),
+                "message": BASE64_STANDARD.encode(data),
+            }),
+        )?;
+        Signature::from_str(&signature)
+            .map_err(|_| RemoteWalletError::Protocol("Signer returned an invalid signature"))
+    }
+}
+
+/// The result of the JSON-RPC `response`
+fn parse_response(mut response: Value) -> Result<String, RemoteWalletError> {
+    if let Some(error) = response.get("error") {
+        if error.get("code").and_then(Value::as_i64) == Some(USER_CANCEL_ERROR_CODE) {
+            return Err(RemoteWalletError::UserCancel);
+        }
+        let message = error
+            .get("message")
+            .and_then(Value::as_str)
+            .unwrap_or("unknown error");
+        return Err(RemoteWalletError::RemoteSigner(message.to_string()));
+    }
+    match response.get_mut("result").map(Value::take) {
+        Some(Value::String(result)) => Ok(result),
+        _ => Err(RemoteWalletError::Protocol("Signer returned no result")),
+    }
+}
+
+impl RemoteSigner for HttpSigner {
+    fn name(&self) -> &str {
+        &self.url
+    }
+
+    fn get_pubkey(
+        &self,
+        derivation_path: &DerivationPath,
+        confirm_key: bool,
+    ) -> Result<Pubkey, RemoteWalletError> {
+        let pubkey = self.send(
+            "getPubkey",
+            json!({
+                "derivationPath": format!("{derivation_path:?}"),
+                "confirmKey": confirm_key,
+            }),
+        )?;
+        Pubkey::from_str(&pubkey)
+            .map_err(|_| RemoteWalletError::Protocol("Signer returned an invalid pubkey"))
+    }
+
+    fn sign_message(
+        &self,
+        derivation_path: &DerivationPath,
+        data: &[u8],
+    ) -> Result<Signature, RemoteWalletError> {
+        self.sign("signMessage", derivation_path, data)
+    }
+
+    fn sign_offchain_message(
+        &self,
+        derivation_path: &DerivationPath,
+        message: &[u8],
+    ) -> Result<Signature, RemoteWalletError> {
+        self.sign("signOffchainMessage", derivation_path, message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, assert_matches::assert_matches};
+
+    #[test]
+    fn test_parse_response() {
+        let pubkey = Pubkey::new_unique().to_string();
+        assert_eq!(
+            parse_response(json!({"jsonrpc": "2.0", "id": 1, "result": pubkey})).unwrap(),
+            pubkey
+        );
+        assert_matches!(
+            parse_response(json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "error": {"code": USER_CANCEL_ERROR_CODE, "message": "rejected"},
+            })),
+            Err(RemoteWalletError::UserCancel)
+        );
+        assert_matches!(
+            parse_response(json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "error": {"code": -32601, "message": "Method not found"},
+            })),
+            Err(RemoteWalletError::RemoteSigner(message)) if message == "Method not found"
+        );
+        assert_matches!(
+            parse_response(json!({"jsonrpc": "2.0", "id": 1, "result": 42})),
+            Err(RemoteWalletError::Protocol(_))
+        );
+    }
+
+    #[test]
+    fn test_derivation_path_param() {
+        assert_eq!(
+            format!("{:?}", DerivationPath::new_bip44(Some(0), Some(0))),
+            "m/44'/501'/0'/0'"
+        );
+    }
+}
@@ -1,7 +1,9 @@
 #![allow(clippy::arithmetic_side_effects)]
 #![allow(dead_code)]
+pub mod http_signer;
 pub mod ledger;
 pub mod ledger_error;
 pub mod locator;
 pub mod remote_keypair;
+pub mod remote_signer;
 pub mod remote_wallet;
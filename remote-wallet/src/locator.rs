@@ -1,4 +1,5 @@
 use {
+    crate::remote_signer::registered_manufacturer,
     solana_sdk::pubkey::{ParsePubkeyError, Pubkey},
     std::{
         convert::{Infallible, TryFrom, TryInto},
@@ -12,6 +13,8 @@ use {
 pub enum Manufacturer {
     Unknown,
     Ledger,
+    /// The manufacturer of a registered `HidBackend`
+    Other(&'static str),
 }
 
 impl Default for Manufacturer {
@@ -39,7 +42,9 @@ impl FromStr for Manufacturer {
         let s = s.to_ascii_lowercase();
         match s.as_str() {
             MANUFACTURER_LEDGER => Ok(Self::Ledger),
-            _ => Err(ManufacturerError),
+            s => registered_manufacturer(s)
+                .map(Self::Other)
+                .ok_or(ManufacturerError),
         }
     }
 }
@@ -56,6 +61,7 @@ impl AsRef<str> for Manufacturer {
         match self {
             Self::Unknown => MANUFACTURER_UNKNOWN,
             Self::Ledger => MANUFACTURER_LEDGER,
+            Self::Other(manufacturer) => manufacturer,
         }
     }
 }
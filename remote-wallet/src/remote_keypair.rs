@@ -1,5 +1,6 @@
 use {
     crate::{
+        http_signer::HttpSigner,
         ledger::get_ledger_from_info,
         locator::{Locator, Manufacturer},
         remote_wallet::{
@@ -12,6 +13,7 @@ use {
         pubkey::Pubkey,
         signature::{Signature, Signer, SignerError},
     },
+    std::rc::Rc,
 };
 
 pub struct RemoteKeypair {
@@ -30,6 +32,7 @@ impl RemoteKeypair {
     ) -> Result<Self, RemoteWalletError> {
         let pubkey = match &wallet_type {
             RemoteWalletType::Ledger(wallet) => wallet.get_pubkey(&derivation_path, confirm_key)?,
+            RemoteWalletType::Remote(signer) => signer.get_pubkey(&derivation_path, confirm_key)?,
         };
 
         Ok(Self {
@@ -51,6 +54,16 @@ impl Signer for RemoteKeypair {
             RemoteWalletType::Ledger(wallet) => wallet
                 .sign_message(&self.derivation_path, message)
                 .map_err(|e| e.into()),
+            RemoteWalletType::Remote(signer) => {
+                let signature = signer.sign_message(&self.derivation_path, message)?;
+                if !signature.verify(self.pubkey.as_ref(), message) {
+                    return Err(SignerError::Protocol(format!(
+                        "{} returned an invalid signature",
+                        signer.name()
+                    )));
+                }
+                Ok(signature)
+            }
         }
     }
 
@@ -76,7 +89,38 @@ pub fn generate_remote_keypair(
             confirm_key,
             path,
         )?)
+    } else if let Manufacturer::Other(_) = remote_wallet_info.manufacturer {
+        let device_info = wallet_manager.select_device(&remote_wallet_info, keypair_name)?;
+        let signer = wallet_manager.get_remote_signer(&device_info.host_device_path)?;
+        let path = format!(
+            "{}{}",
+            device_info.get_pretty_path(),
+            derivation_path.get_query()
+        );
+        Ok(RemoteKeypair::new(
+            RemoteWalletType::Remote(signer),
+            derivation_path,
+            confirm_key,
+            path,
+        )?)
     } else {
         Err(RemoteWalletError::DeviceTypeMismatch)
     }
 }
+
+/// Keypair signing with the remote signing service at `url`, see
+/// [`crate::http_signer`]
+pub fn generate_http_signer_keypair(
+    url: String,
+    derivation_path: DerivationPath,
+    confirm_key: bool,
+) -> Result<RemoteKeypair, RemoteWalletError> {
+    let path = format!("{}{}", url, derivation_path.get_query());
+    let signer = HttpSigner::new(url)?;
+    RemoteKeypair::new(
+        RemoteWalletType::Remote(Rc::new(signer)),
+        derivation_path,
+        confirm_key,
+        path,
+    )
+}
@@ -0,0 +1,170 @@
+//! Signers other than Ledger devices.
+//!
+//! A [`RemoteSigner`] signs with the keys of a device or a service. Besides
+//! the Ledger devices, which `RemoteWalletManager` supports natively, these
+//! are:
+//!
+//! - the HID devices of the backends registered with
+//!   [`register_hid_backend`], found by `RemoteWalletManager::update_devices`
+//!   and selected by the keypair URL `usb://<manufacturer>`, where
+//!   `<manufacturer>` is the one of the backend
+//! - the remote signing services selected by `http://` and `https://`
+//!   keypair URLs, see [`crate::http_signer`]
+
+use {
+    crate::remote_wallet::RemoteWalletError,
+    solana_sdk::{derivation_path::DerivationPath, pubkey::Pubkey, signature::Signature},
+    std::fmt,
+};
+#[cfg(feature = "hidapi")]
+use {
+    crate::remote_wallet::RemoteWalletInfo,
+    lazy_static::lazy_static,
+    std::{
+        rc::Rc,
+        sync::{Arc, RwLock},
+    },
+};
+
+/// Signs with the keys a device or a service manages
+pub trait RemoteSigner {
+    /// Name of the signer, for logs and prompts
+    fn name(&self) -> &str;
+
+    /// The pubkey of the key at `derivation_path`, which the signer shows to
+    /// the user for confirmation if `confirm_key`
+    fn get_pubkey(
+        &self,
+        derivation_path: &DerivationPath,
+        confirm_key: bool,
+    ) -> Result<Pubkey, RemoteWalletError>;
+
+    /// Signs the serialized transaction message `data` with the key at
+    /// `derivation_path`
+    fn sign_message(
+        &self,
+        derivation_path: &DerivationPath,
+        data: &[u8],
+    ) -> Result<Signature, RemoteWalletError>;
+
+    /// Signs the serialized off-chain message `message` with the key at
+    /// `derivation_path`
+    fn sign_offchain_message(
+        &self,
+        derivation_path: &DerivationPath,
+        message: &[u8],
+    ) -> Result<Signature, RemoteWalletError>;
+}
+
+impl fmt::Debug for dyn RemoteSigner {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "RemoteSigner({})", self.name())
+    }
+}
+
+/// Connects to the HID devices of a manufacturer
+#[cfg(feature = "hidapi")]
+pub trait HidBackend: Send + Sync {
+    /// Name of the manufacturer, which selects its devices in `usb://`
+    /// keypair URLs
+    fn manufacturer(&self) -> &'static str;
+
+    /// Whether the device with these ids is one of the manufacturer's wallets
+    fn is_valid_device(&self, vendor_id: u16, product_id: u16) -> bool;
+
+    /// Reads the info of the opened `device` and returns the signer using it
+    fn open(
+        &self,
+        device: hidapi::HidDevice,
+        device_info: &hidapi::DeviceInfo,
+    ) -> Result<(RemoteWalletInfo, Rc<dyn RemoteSigner>), RemoteWalletError>;
+}
+
+#[cfg(feature = "hidapi")]
+lazy_static! {
+    static ref HID_BACKENDS: RwLock<Vec<Arc<dyn HidBackend>>> = RwLock::default();
+}
+
+/// Finds and signs with the devices of `backend` too. Fails if a backend was
+/// already registered for its manufacturer.
+#[cfg(feature = "hidapi")]
+pub fn register_hid_backend(backend: Arc<dyn HidBackend>) -> Result<(), RemoteWalletError> {
+    let manufacturer = backend.manufacturer();
+    if manufacturer.parse::<crate::locator::Manufacturer>().is_ok() {
+        return Err(RemoteWalletError::InvalidInput(format!(
+            "a backend is already registered for {manufacturer}"
+        )));
+    }
+    HID_BACKENDS.write().unwrap().push(backend);
+    Ok(())
+}
+
+#[cfg(feature = "hidapi")]
+pub(crate) fn hid_backends() -> Vec<Arc<dyn HidBackend>> {
+    HID_BACKENDS.read().unwrap().clone()
+}
+
+/// The manufacturer of a registered backend named `name`, ignoring case
+pub(crate) fn registered_manufacturer(name: &str) -> Option<&'static str> {
+    #[cfg(feature = "hidapi")]
+    {
+        HID_BACKENDS
+            .read()
+            .unwrap()
+            .iter()
+            .map(|backend| backend.manufacturer())
+            .find(|manufacturer| manufacturer.eq_ignore_ascii_case(name))
+    }
+    #[cfg(not(feature = "hidapi"))]
+    {
+        let _ = name;
+        None
+    }
+}
+
+#[cfg(all(test, feature = "hidapi"))]
+mod tests {
+    use {
+        super::*,
+        crate::locator::{Locator, Manufacturer},
+    };
+
+    struct TestBackend;
+
+    impl HidBackend for TestBackend {
+        fn manufacturer(&self) -> &'static str {
+            "test-wallet"
+        }
+
+        fn is_valid_device(&self, _vendor_id: u16, _product_id: u16) -> bool {
+            false
+        }
+
+        fn open(
+            &self,
+            _device: hidapi::HidDevice,
+            _device_info: &hidapi::DeviceInfo,
+        ) -> Result<(RemoteWalletInfo, Rc<dyn RemoteSigner>), RemoteWalletError> {
+            Err(RemoteWalletError::InvalidDevice)
+        }
+    }
+
+    #[test]
+    fn test_register_hid_backend() {
+        assert!(Locator::new_from_path("usb://test-wallet").is_err());
+
+        register_hid_backend(Arc::new(TestBackend)).unwrap();
+        assert!(register_hid_backend(Arc::new(TestBackend)).is_err());
+        assert_eq!(
+            Locator::new_from_path("usb://Test-Wallet").unwrap(),
+            Locator {
+                manufacturer: Manufacturer::Other("test-wallet"),
+                pubkey: None,
+            }
+        );
+        assert_eq!(
+            Manufacturer::Other("test-wallet").to_string(),
+            "test-wallet"
+        );
+    }
+}
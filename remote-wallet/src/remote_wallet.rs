@@ -1,11 +1,17 @@
 #[cfg(feature = "hidapi")]
-use {crate::ledger::is_valid_ledger, parking_lot::Mutex, std::sync::Arc};
+use {
+    crate::{ledger::is_valid_ledger, remote_signer::hid_backends},
+    parking_lot::Mutex,
+    std::sync::Arc,
+};
 use {
     crate::{
         ledger::LedgerWallet,
         ledger_error::LedgerError,
         locator::{Locator, LocatorError, Manufacturer},
+        remote_signer::RemoteSigner,
     },
+    dialoguer::{theme::ColorfulTheme, Select},
     log::*,
     parking_lot::RwLock,
     solana_sdk::{
@@ -61,6 +67,9 @@ pub enum RemoteWalletError {
 
     #[error(transparent)]
     LocatorError(#[from] LocatorError),
+
+    #[error("remote signer error: {0}")]
+    RemoteSigner(String),
 }
 
 #[cfg(feature = "hidapi")]
@@ -80,6 +89,7 @@ impl From<RemoteWalletError> for SignerError {
             RemoteWalletError::LedgerError(e) => SignerError::Protocol(e.to_string()),
             RemoteWalletError::NoDeviceFound => SignerError::NoDeviceFound,
             RemoteWalletError::Protocol(e) => SignerError::Protocol(e.to_string()),
+            RemoteWalletError::RemoteSigner(e) => SignerError::Connection(e),
             RemoteWalletError::UserCancel => {
                 SignerError::UserCancel("remote wallet operation rejected by the user".to_string())
             }
@@ -145,6 +155,36 @@ impl RemoteWalletManager {
             }
         }
 
+        for backend in hid_backends() {
+            for device_info in usb.device_list().filter(|&device_info| {
+                backend.is_valid_device(device_info.vendor_id(), device_info.product_id())
+            }) {
+                let result = usb
+                    .open_path(device_info.path())
+                    .map_err(RemoteWalletError::from)
+                    .and_then(|device| backend.open(device, device_info));
+                match result {
+                    Ok((info, signer)) => {
+                        let path = device_info.path().to_str().unwrap().to_string();
+                        trace!("Found device: {:?}", info);
+                        detected_devices.push(Device {
+                            path,
+                            info,
+                            wallet_type: RemoteWalletType::Remote(signer),
+                        })
+                    }
+                    Err(err) => {
+                        error!(
+                            "Error connecting to {} device to read info: {}",
+                            backend.manufacturer(),
+                            err
+                        );
+                        errors.push(err)
+                    }
+                }
+            }
+        }
+
         let num_curr_devices = detected_devices.len();
         *self.devices.write() = detected_devices;
 
@@ -168,7 +208,6 @@ impl RemoteWalletManager {
     }
 
     /// Get a particular wallet
-    #[allow(unreachable_patterns)]
     pub fn get_ledger(
         &self,
         host_device_path: &str,
@@ -184,6 +223,73 @@ impl RemoteWalletManager {
             })
     }
 
+    /// Get a particular wallet of a registered `HidBackend`
+    pub fn get_remote_signer(
+        &self,
+        host_device_path: &str,
+    ) -> Result<Rc<dyn RemoteSigner>, RemoteWalletError> {
+        self.devices
+            .read()
+            .iter()
+            .find(|device| device.info.host_device_path == host_device_path)
+            .ok_or(RemoteWalletError::PubkeyNotFound)
+            .and_then(|device| match &device.wallet_type {
+                RemoteWalletType::Remote(signer) => Ok(signer.clone()),
+                _ => Err(RemoteWalletError::DeviceTypeMismatch),
+            })
+    }
+
+    /// Choose a wallet based on matching info fields, prompting the user if
+    /// several match
+    pub fn select_device(
+        &self,
+        info: &RemoteWalletInfo,
+        keypair_name: &str,
+    ) -> Result<RemoteWalletInfo, RemoteWalletError> {
+        let devices = self.list_devices();
+        let mut matches = devices
+            .iter()
+            .filter(|&device_info| device_info.matches(info));
+        if matches
+            .clone()
+            .all(|device_info| device_info.error.is_some())
+        {
+            let first_device = matches.next();
+            if let Some(device) = first_device {
+                return Err(device.error.clone().unwrap());
+            }
+        }
+        let mut matches: Vec<(&RemoteWalletInfo, String)> = matches
+            .filter(|&device_info| device_info.error.is_none())
+            .map(|device_info| {
+                let query_item =
+                    format!("{} ({})", device_info.get_pretty_path(), device_info.model,);
+                (device_info, query_item)
+            })
+            .collect();
+        if matches.is_empty() {
+            return Err(RemoteWalletError::NoDeviceFound);
+        }
+        matches.sort_by(|a, b| a.1.cmp(&b.1));
+        let (device_infos, items): (Vec<&RemoteWalletInfo>, Vec<String>) =
+            matches.into_iter().unzip();
+
+        let device_info = if device_infos.len() > 1 {
+            let selection = Select::with_theme(&ColorfulTheme::default())
+                .with_prompt(format!(
+                    "Multiple hardware wallets found. Please select a device for {keypair_name:?}"
+                ))
+                .default(0)
+                .items(&items[..])
+                .interact()
+                .unwrap();
+            device_infos[selection]
+        } else {
+            device_infos[0]
+        };
+        Ok(device_info.clone())
+    }
+
     /// Get wallet info.
     pub fn get_wallet_info(&self, pubkey: &Pubkey) -> Option<RemoteWalletInfo> {
         self.devices
@@ -262,6 +368,7 @@ pub struct Device {
 #[derive(Debug)]
 pub enum RemoteWalletType {
     Ledger(Rc<LedgerWallet>),
+    Remote(Rc<dyn RemoteSigner>),
 }
 
 /// Remote wallet information.
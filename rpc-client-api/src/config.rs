@@ -4,6 +4,7 @@ use {
     solana_sdk::{
         clock::{Epoch, Slot},
         commitment_config::{CommitmentConfig, CommitmentLevel},
+        pubkey::Pubkey,
     },
     solana_transaction_status::{TransactionDetails, UiTransactionEncoding},
 };
@@ -30,6 +31,8 @@ pub struct RpcSendTransactionConfig {
 pub struct RpcSimulateTransactionAccountsConfig {
     pub encoding: Option<UiAccountEncoding>,
     pub addresses: Vec<String>,
+    #[serde(default)]
+    pub post_data_diff: bool,
 }
 
 #[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -80,6 +83,16 @@ pub struct RpcBlockProductionConfig {
     pub commitment: Option<CommitmentConfig>,
 }
 
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcStakeHistoryConfig {
+    pub first_epoch: Option<Epoch>, // oldest epoch in the stake history if `None`
+    pub last_epoch: Option<Epoch>,  // newest epoch in the stake history if `None`
+    #[serde(flatten)]
+    pub commitment: Option<CommitmentConfig>,
+    pub min_context_slot: Option<Slot>,
+}
+
 #[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RpcGetVoteAccountsConfig {
@@ -145,6 +158,7 @@ pub enum RpcAccountIndex {
     ProgramId,
     SplTokenMint,
     SplTokenOwner,
+    ProgramIdDataSize,
 }
 
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
@@ -164,6 +178,18 @@ pub struct RpcProgramAccountsConfig {
     #[serde(flatten)]
     pub account_config: RpcAccountInfoConfig,
     pub with_context: Option<bool>,
+    pub sort_by: Option<RpcProgramAccountsSortBy>, // `Pubkey` if unset and paging is requested
+    pub limit: Option<usize>,
+    pub before_pubkey: Option<String>, // base-58 encoded Pubkey cursor from a previous page
+    pub before_lamports: Option<u64>,  // lamports of the `before_pubkey` account on that page
+}
+
+/// Results are ordered descending by the selected key, with ties broken by pubkey
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RpcProgramAccountsSortBy {
+    Pubkey,
+    Lamports,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -201,6 +227,26 @@ pub struct RpcSignatureSubscribeConfig {
 pub enum RpcBlockSubscribeFilter {
     All,
     MentionsAccountOrProgram(String),
+    Matches(RpcBlockTransactionFilter),
+}
+
+/// Condition on the transactions of a block notification, with addresses and
+/// program ids as base-58 encoded strings
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RpcBlockTransactionFilter {
+    /// The transaction mentions any of these addresses
+    MentionsAnyAddress(Vec<String>),
+    /// The transaction mentions none of these addresses
+    ExcludesAddresses(Vec<String>),
+    /// The transaction invokes any of these programs
+    InvokesAnyProgram(Vec<String>),
+    /// The transaction invokes none of these programs
+    ExcludesPrograms(Vec<String>),
+    /// Every one of these conditions holds
+    And(Vec<RpcBlockTransactionFilter>),
+    /// Any one of these conditions holds
+    Or(Vec<RpcBlockTransactionFilter>),
 }
 
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
@@ -214,6 +260,23 @@ pub struct RpcBlockSubscribeConfig {
     pub max_supported_transaction_version: Option<u8>,
 }
 
+/// Sysvars that may be streamed with `sysvarSubscribe`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RpcSysvarSubscribeFilter {
+    Clock,
+    EpochSchedule,
+    StakeHistory,
+    EpochRewards,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcSysvarSubscribeConfig {
+    #[serde(flatten)]
+    pub commitment: Option<CommitmentConfig>,
+}
+
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RpcSignaturesForAddressConfig {
@@ -256,6 +319,42 @@ pub trait EncodingConfig {
     fn new_with_encoding(encoding: &Option<UiTransactionEncoding>) -> Self;
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcBlockFilter {
+    #[serde(default, with = "option_pubkey_string")]
+    pub mentions_program_id: Option<Pubkey>,
+    #[serde(default, with = "option_pubkey_string")]
+    pub mentions_address: Option<Pubkey>,
+}
+
+/// (De)serializes an optional pubkey as a base-58 string, so that configs
+/// holding one stay `Copy`
+mod option_pubkey_string {
+    use {
+        serde::{de::Error, Deserialize, Deserializer, Serializer},
+        solana_sdk::pubkey::Pubkey,
+    };
+
+    pub fn serialize<S: Serializer>(
+        pubkey: &Option<Pubkey>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match pubkey {
+            Some(pubkey) => serializer.serialize_some(&pubkey.to_string()),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Pubkey>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(|pubkey| pubkey.parse().map_err(D::Error::custom))
+            .transpose()
+    }
+}
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RpcBlockConfig {
@@ -265,6 +364,7 @@ pub struct RpcBlockConfig {
     #[serde(flatten)]
     pub commitment: Option<CommitmentConfig>,
     pub max_supported_transaction_version: Option<u8>,
+    pub filter: Option<RpcBlockFilter>,
 }
 
 impl EncodingConfig for RpcBlockConfig {
@@ -333,6 +433,14 @@ impl RpcBlocksConfigWrapper {
     }
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcRecentPrioritizationFeesConfig {
+    /// Compute each block's fee from this percentile (0-100) of the fees paid in the block,
+    /// rather than from the minimum fees
+    pub percentile: Option<u8>,
+}
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RpcContextConfig {
@@ -72,6 +72,7 @@ pub enum RpcRequest {
     GetMaxShredInsertSlot,
     GetMinimumBalanceForRentExemption,
     GetMultipleAccounts,
+    GetNodeMetadata,
     GetProgramAccounts,
     #[deprecated(
         since = "1.9.0",
@@ -95,6 +96,7 @@ pub enum RpcRequest {
     GetStorageTurnRate,
     GetSlotsPerSegment,
     GetStakeActivation,
+    GetStakeHistory,
     GetStakeMinimumDelegation,
     GetStoragePubkeysForSlot,
     GetSupply,
@@ -156,6 +158,7 @@ impl fmt::Display for RpcRequest {
             RpcRequest::GetMaxShredInsertSlot => "getMaxShredInsertSlot",
             RpcRequest::GetMinimumBalanceForRentExemption => "getMinimumBalanceForRentExemption",
             RpcRequest::GetMultipleAccounts => "getMultipleAccounts",
+            RpcRequest::GetNodeMetadata => "getNodeMetadata",
             RpcRequest::GetProgramAccounts => "getProgramAccounts",
             RpcRequest::GetRecentBlockhash => "getRecentBlockhash",
             RpcRequest::GetRecentPerformanceSamples => "getRecentPerformanceSamples",
@@ -168,6 +171,7 @@ impl fmt::Display for RpcRequest {
             RpcRequest::GetSlotLeader => "getSlotLeader",
             RpcRequest::GetSlotLeaders => "getSlotLeaders",
             RpcRequest::GetStakeActivation => "getStakeActivation",
+            RpcRequest::GetStakeHistory => "getStakeHistory",
             RpcRequest::GetStakeMinimumDelegation => "getStakeMinimumDelegation",
             RpcRequest::GetStorageTurn => "getStorageTurn",
             RpcRequest::GetStorageTurnRate => "getStorageTurnRate",
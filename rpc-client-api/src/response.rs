@@ -7,11 +7,13 @@ use {
         fee_calculator::{FeeCalculator, FeeRateGovernor},
         hash::Hash,
         inflation::Inflation,
+        inner_instruction::InstructionComputeUnits,
         transaction::{Result, TransactionError},
     },
     solana_transaction_status::{
-        ConfirmedTransactionStatusWithSignature, TransactionConfirmationStatus, UiConfirmedBlock,
-        UiInnerInstructions, UiTransactionReturnData,
+        structured_logs::UiStructuredLog, ConfirmedTransactionStatusWithSignature,
+        TransactionConfirmationStatus, UiConfirmedBlock, UiInnerInstructions,
+        UiTransactionReturnData,
     },
     std::{collections::HashMap, fmt, net::SocketAddr, str::FromStr},
     thiserror::Error,
@@ -273,6 +275,8 @@ pub struct RpcLogsResponse {
     pub signature: String, // Signature as base58 string
     pub err: Option<TransactionError>,
     pub logs: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub structured_logs: Vec<UiStructuredLog>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
@@ -310,6 +314,17 @@ pub struct RpcContactInfo {
     pub shred_version: Option<u16>,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcNodeMetadata {
+    /// Pubkey of the node as a base-58 string
+    pub pubkey: String,
+    /// Application-defined metadata published by the node
+    pub metadata: String,
+    /// Wallclock of the node when it published the metadata, in milliseconds
+    pub wallclock: u64,
+}
+
 /// Map of leader base58 identity pubkeys to the slot indices relative to the first epoch slot
 pub type RpcLeaderSchedule = HashMap<String, Vec<usize>>;
 
@@ -424,6 +439,41 @@ pub struct RpcSimulateTransactionResult {
     pub units_consumed: Option<u64>,
     pub return_data: Option<UiTransactionReturnData>,
     pub inner_instructions: Option<Vec<UiInnerInstructions>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account_diffs: Option<Vec<Option<RpcSimulateTransactionAccountDiff>>>,
+    /// Compute units consumed by each instruction and its inner instructions,
+    /// returned along with `inner_instructions`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instruction_compute_units: Option<Vec<InstructionComputeUnits>>,
+    /// The structured logs among `logs`, such as the diagnostics of a failed
+    /// program deployment, or `None` if there are none
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub structured_logs: Option<Vec<UiStructuredLog>>,
+}
+
+/// Changes made to a single account by a simulated transaction, or `None` in
+/// the containing list if the account was left untouched
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcSimulateTransactionAccountDiff {
+    pub pre_lamports: u64,
+    pub post_lamports: u64,
+    pub pre_owner: String,  // base-58 encoded Pubkey
+    pub post_owner: String, // base-58 encoded Pubkey
+    pub pre_data_len: usize,
+    pub post_data_len: usize,
+    pub data_diffs: Vec<RpcAccountDataDiff>,
+}
+
+/// A contiguous run of account data bytes that differ before and after
+/// simulation. Either side may be shorter than the other if the account was
+/// resized.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcAccountDataDiff {
+    pub offset: usize,
+    pub pre: String,  // base-64 encoded bytes
+    pub post: String, // base-64 encoded bytes
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
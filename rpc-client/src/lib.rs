@@ -1,8 +1,10 @@
 #![allow(clippy::arithmetic_side_effects)]
 
 pub mod http_sender;
+pub mod lookup_table_manager;
 pub mod mock_sender;
 pub mod nonblocking;
+pub mod retry_sender;
 pub mod rpc_client;
 pub mod rpc_sender;
 pub mod spinner;
@@ -0,0 +1,411 @@
+//! Managing the lifecycle of address lookup tables.
+//!
+//! An address lookup table goes through several stages, each of which comes
+//! with timing rules enforced by the address lookup table program:
+//!
+//! - A table is created at an address derived from a recent slot, which must
+//!   still be present in the [`SlotHashes`] sysvar.
+//! - Addresses added to a table in some slot can only be looked up by
+//!   transactions in later slots.
+//! - A deactivated table can only be closed once its deactivation slot has
+//!   been evicted from the [`SlotHashes`] sysvar, about
+//!   [`MAX_ENTRIES`](slot_hashes::MAX_ENTRIES) slots later.
+//!
+//! [`LookupTableManager`] sends the transactions for each stage and waits for
+//! these rules to be satisfied:
+//!
+//! ```no_run
+//! # use solana_rpc_client::{lookup_table_manager::LookupTableManager, rpc_client::RpcClient};
+//! # use solana_sdk::{pubkey::Pubkey, signature::{Keypair, Signer}};
+//! # let rpc_client = RpcClient::new("http://localhost:8899".to_string());
+//! # let payer = Keypair::new();
+//! let manager = LookupTableManager::new(&rpc_client, &payer, &payer);
+//! let lookup_table_address = manager.create()?;
+//! let addresses: Vec<_> = (0..100).map(|_| Pubkey::new_unique()).collect();
+//! manager.extend(&lookup_table_address, &addresses)?;
+//! let lookup_table_account = manager.wait_for_activation(&lookup_table_address)?;
+//! assert_eq!(lookup_table_account.addresses, addresses);
+//!
+//! // ... use the table in v0 transactions ...
+//!
+//! manager.deactivate(&lookup_table_address)?;
+//! manager.wait_for_deactivation(&lookup_table_address)?;
+//! manager.close(&lookup_table_address, &payer.pubkey())?;
+//! # Ok::<(), solana_rpc_client_api::client_error::Error>(())
+//! ```
+
+use {
+    crate::rpc_client::RpcClient,
+    solana_rpc_client_api::{
+        client_error::Result as ClientResult, request::RpcError, response::Response,
+    },
+    solana_sdk::{
+        account::{from_account, Account},
+        address_lookup_table::{
+            instruction::{
+                close_lookup_table, create_lookup_table, deactivate_lookup_table,
+                extend_lookup_table,
+            },
+            state::{AddressLookupTable, LookupTableStatus, LOOKUP_TABLE_MAX_ADDRESSES},
+            AddressLookupTableAccount,
+        },
+        clock::{Slot, DEFAULT_MS_PER_SLOT},
+        commitment_config::CommitmentConfig,
+        instruction::Instruction,
+        message::Message,
+        packet::PACKET_DATA_SIZE,
+        pubkey::Pubkey,
+        signature::Signature,
+        signer::Signer,
+        slot_hashes::{self, SlotHashes},
+        sysvar,
+        transaction::Transaction,
+    },
+    std::{mem::size_of, thread::sleep, time::Duration},
+};
+
+/// A lookup table's state as of the slot it was fetched in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LookupTableState {
+    /// The slot the table was fetched in
+    pub slot: Slot,
+    pub status: LookupTableStatus,
+    /// The slot the table was last extended in; its addresses can be looked
+    /// up by transactions in later slots
+    pub last_extended_slot: Slot,
+    pub authority: Option<Pubkey>,
+    pub addresses: Vec<Pubkey>,
+}
+
+impl LookupTableState {
+    /// Whether all of the table's addresses can be looked up.
+    pub fn is_active(&self) -> bool {
+        self.status == LookupTableStatus::Activated && self.slot > self.last_extended_slot
+    }
+}
+
+/// Creates, extends, deactivates and closes address lookup tables owned by an
+/// authority, with a payer funding their rent and transaction fees.
+pub struct LookupTableManager<'a> {
+    rpc_client: &'a RpcClient,
+    authority: &'a dyn Signer,
+    payer: &'a dyn Signer,
+    poll_interval: Duration,
+}
+
+impl<'a> LookupTableManager<'a> {
+    pub fn new(
+        rpc_client: &'a RpcClient,
+        authority: &'a dyn Signer,
+        payer: &'a dyn Signer,
+    ) -> Self {
+        Self {
+            rpc_client,
+            authority,
+            payer,
+            poll_interval: Duration::from_millis(DEFAULT_MS_PER_SLOT),
+        }
+    }
+
+    /// How long to wait between polls for the status of a table.
+    pub fn poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Create an empty table and return its address.
+    ///
+    /// The table's address is derived from the latest finalized slot, so
+    /// tables created by the same authority in quick succession may collide;
+    /// wait for a new slot to be finalized before creating another one.
+    pub fn create(&self) -> ClientResult<Pubkey> {
+        let recent_slot = self
+            .rpc_client
+            .get_slot_with_commitment(CommitmentConfig::finalized())?;
+        let (instruction, lookup_table_address) =
+            create_lookup_table(self.authority.pubkey(), self.payer.pubkey(), recent_slot);
+        self.send(instruction, &[self.payer])?;
+        Ok(lookup_table_address)
+    }
+
+    /// Add `addresses` to a table, sending as many transactions as needed to
+    /// fit them into packets, and return their signatures.
+    pub fn extend(
+        &self,
+        lookup_table_address: &Pubkey,
+        addresses: &[Pubkey],
+    ) -> ClientResult<Vec<Signature>> {
+        let state = self.get_state(lookup_table_address)?;
+        if state.status != LookupTableStatus::Activated {
+            return Err(RpcError::ForUser(format!(
+                "lookup table {lookup_table_address} is deactivated and cannot be extended"
+            ))
+            .into());
+        }
+        if state.addresses.len() + addresses.len() > LOOKUP_TABLE_MAX_ADDRESSES {
+            return Err(RpcError::ForUser(format!(
+                "lookup table {lookup_table_address} has {} addresses, \
+                 cannot add {} more than the maximum of {LOOKUP_TABLE_MAX_ADDRESSES}",
+                state.addresses.len(),
+                addresses.len(),
+            ))
+            .into());
+        }
+
+        addresses
+            .chunks(self.max_addresses_per_extend(lookup_table_address))
+            .map(|chunk| {
+                let instruction = extend_lookup_table(
+                    *lookup_table_address,
+                    self.authority.pubkey(),
+                    Some(self.payer.pubkey()),
+                    chunk.to_vec(),
+                );
+                self.send(instruction, &[self.payer, self.authority])
+            })
+            .collect()
+    }
+
+    /// Wait until all of a table's addresses can be looked up, and return
+    /// them.
+    pub fn wait_for_activation(
+        &self,
+        lookup_table_address: &Pubkey,
+    ) -> ClientResult<AddressLookupTableAccount> {
+        loop {
+            let state = self.get_state(lookup_table_address)?;
+            if state.status != LookupTableStatus::Activated {
+                return Err(RpcError::ForUser(format!(
+                    "lookup table {lookup_table_address} is deactivated"
+                ))
+                .into());
+            }
+            if state.is_active() {
+                return Ok(AddressLookupTableAccount {
+                    key: *lookup_table_address,
+                    addresses: state.addresses,
+                });
+            }
+            sleep(self.poll_interval);
+        }
+    }
+
+    /// Deactivate a table so that it can be closed once its cooldown is over.
+    pub fn deactivate(&self, lookup_table_address: &Pubkey) -> ClientResult<Signature> {
+        let instruction = deactivate_lookup_table(*lookup_table_address, self.authority.pubkey());
+        self.send(instruction, &[self.payer, self.authority])
+    }
+
+    /// Wait until a deactivated table's cooldown is over.
+    pub fn wait_for_deactivation(&self, lookup_table_address: &Pubkey) -> ClientResult<()> {
+        loop {
+            match self.get_state(lookup_table_address)?.status {
+                LookupTableStatus::Activated => {
+                    return Err(RpcError::ForUser(format!(
+                        "lookup table {lookup_table_address} has not been deactivated"
+                    ))
+                    .into());
+                }
+                LookupTableStatus::Deactivating { remaining_blocks } => {
+                    // skip polling until the cooldown is expected to be over
+                    let remaining_slots = remaining_blocks.saturating_sub(1) as u32;
+                    sleep(
+                        self.poll_interval
+                            .max(Duration::from_millis(DEFAULT_MS_PER_SLOT) * remaining_slots),
+                    );
+                }
+                LookupTableStatus::Deactivated => return Ok(()),
+            }
+        }
+    }
+
+    /// Close a deactivated table whose cooldown is over, draining its
+    /// lamports to `recipient`.
+    pub fn close(
+        &self,
+        lookup_table_address: &Pubkey,
+        recipient: &Pubkey,
+    ) -> ClientResult<Signature> {
+        let status = self.get_state(lookup_table_address)?.status;
+        if status != LookupTableStatus::Deactivated {
+            return Err(RpcError::ForUser(format!(
+                "lookup table {lookup_table_address} cannot be closed yet: {status:?}"
+            ))
+            .into());
+        }
+        let instruction =
+            close_lookup_table(*lookup_table_address, self.authority.pubkey(), *recipient);
+        self.send(instruction, &[self.payer, self.authority])
+    }
+
+    /// Fetch a table along with the [`SlotHashes`] sysvar of the same slot
+    /// to determine its status.
+    pub fn get_state(&self, lookup_table_address: &Pubkey) -> ClientResult<LookupTableState> {
+        let Response { context, value } = self.rpc_client.get_multiple_accounts_with_commitment(
+            &[*lookup_table_address, sysvar::slot_hashes::id()],
+            self.rpc_client.commitment(),
+        )?;
+        let (lookup_table_account, slot_hashes_account) = match &value[..] {
+            [Some(lookup_table_account), Some(slot_hashes_account)] => {
+                (lookup_table_account, slot_hashes_account)
+            }
+            _ => {
+                return Err(RpcError::ForUser(format!(
+                    "lookup table {lookup_table_address} not found"
+                ))
+                .into())
+            }
+        };
+        lookup_table_state(context.slot, lookup_table_account, slot_hashes_account).map_err(|err| {
+            RpcError::ForUser(format!(
+                "failed to deserialize lookup table {lookup_table_address}: {err}"
+            ))
+            .into()
+        })
+    }
+
+    /// The number of addresses that fit into a single extend transaction.
+    fn max_addresses_per_extend(&self, lookup_table_address: &Pubkey) -> usize {
+        let instruction = extend_lookup_table(
+            *lookup_table_address,
+            self.authority.pubkey(),
+            Some(self.payer.pubkey()),
+            vec![],
+        );
+        let message = Message::new(&[instruction], Some(&self.payer.pubkey()));
+        (PACKET_DATA_SIZE.saturating_sub(message.serialized_size_with_signatures())
+            / size_of::<Pubkey>())
+        .clamp(1, LOOKUP_TABLE_MAX_ADDRESSES)
+    }
+
+    fn send(&self, instruction: Instruction, signers: &[&dyn Signer]) -> ClientResult<Signature> {
+        let mut transaction =
+            Transaction::new_with_payer(&[instruction], Some(&self.payer.pubkey()));
+        transaction.try_sign(signers, self.rpc_client.get_latest_blockhash()?)?;
+        self.rpc_client.send_and_confirm_transaction(&transaction)
+    }
+}
+
+fn lookup_table_state(
+    slot: Slot,
+    lookup_table_account: &Account,
+    slot_hashes_account: &Account,
+) -> Result<LookupTableState, String> {
+    let lookup_table = AddressLookupTable::deserialize(&lookup_table_account.data)
+        .map_err(|err| err.to_string())?;
+    let slot_hashes: SlotHashes =
+        from_account(slot_hashes_account).ok_or("invalid slot hashes sysvar")?;
+    Ok(LookupTableState {
+        slot,
+        status: lookup_table.meta.status(slot, &slot_hashes),
+        last_extended_slot: lookup_table.meta.last_extended_slot,
+        authority: lookup_table.meta.authority,
+        addresses: lookup_table.addresses.to_vec(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_sdk::{
+            address_lookup_table::state::LookupTableMeta, hash::Hash, signature::Keypair,
+        },
+    };
+
+    fn lookup_table_account(meta: LookupTableMeta, addresses: Vec<Pubkey>) -> Account {
+        let data = AddressLookupTable {
+            meta,
+            addresses: addresses.into(),
+        }
+        .serialize_for_tests()
+        .unwrap();
+        Account {
+            data,
+            owner: solana_sdk::address_lookup_table::program::id(),
+            ..Account::default()
+        }
+    }
+
+    fn slot_hashes_account(slots: impl Iterator<Item = Slot>) -> Account {
+        let slot_hashes = SlotHashes::new(
+            &slots
+                .map(|slot| (slot, Hash::new_unique()))
+                .collect::<Vec<_>>(),
+        );
+        solana_sdk::account::create_account_for_test(&slot_hashes)
+    }
+
+    #[test]
+    fn test_lookup_table_state() {
+        let authority = Pubkey::new_unique();
+        let addresses = vec![Pubkey::new_unique(), Pubkey::new_unique()];
+        let slot_hashes = slot_hashes_account(0..100);
+
+        // addresses extended in the current slot cannot be looked up yet
+        let meta = LookupTableMeta {
+            last_extended_slot: 100,
+            ..LookupTableMeta::new(authority)
+        };
+        let account = lookup_table_account(meta.clone(), addresses.clone());
+        let state = lookup_table_state(100, &account, &slot_hashes).unwrap();
+        assert_eq!(state.status, LookupTableStatus::Activated);
+        assert_eq!(state.authority, Some(authority));
+        assert_eq!(state.addresses, addresses);
+        assert!(!state.is_active());
+        assert!(lookup_table_state(101, &account, &slot_hashes)
+            .unwrap()
+            .is_active());
+
+        // deactivated tables cool down while their slot is in the slot hashes
+        let account = lookup_table_account(
+            LookupTableMeta {
+                deactivation_slot: 50,
+                ..meta
+            },
+            addresses,
+        );
+        let state = lookup_table_state(100, &account, &slot_hashes).unwrap();
+        assert!(matches!(
+            state.status,
+            LookupTableStatus::Deactivating { .. }
+        ));
+        assert!(!state.is_active());
+        let state = lookup_table_state(
+            slot_hashes::MAX_ENTRIES as Slot + 100,
+            &account,
+            &slot_hashes_account(100..slot_hashes::MAX_ENTRIES as Slot + 100),
+        )
+        .unwrap();
+        assert_eq!(state.status, LookupTableStatus::Deactivated);
+
+        assert!(lookup_table_state(100, &slot_hashes, &slot_hashes).is_err());
+    }
+
+    #[test]
+    fn test_max_addresses_per_extend() {
+        let rpc_client = RpcClient::new_mock("succeeds".to_string());
+        let lookup_table_address = Pubkey::new_unique();
+        let authority = Keypair::new();
+        let payer = Keypair::new();
+
+        for manager in [
+            LookupTableManager::new(&rpc_client, &authority, &payer),
+            LookupTableManager::new(&rpc_client, &payer, &payer),
+        ] {
+            let max_addresses = manager.max_addresses_per_extend(&lookup_table_address);
+            let message_size = |num_addresses| {
+                let instruction = extend_lookup_table(
+                    lookup_table_address,
+                    manager.authority.pubkey(),
+                    Some(manager.payer.pubkey()),
+                    vec![Pubkey::new_unique(); num_addresses],
+                );
+                Message::new(&[instruction], Some(&manager.payer.pubkey()))
+                    .serialized_size_with_signatures()
+            };
+            assert!(message_size(max_addresses) <= PACKET_DATA_SIZE);
+            assert!(message_size(max_addresses + 1) > PACKET_DATA_SIZE);
+        }
+    }
+}
@@ -5,7 +5,7 @@ use {
     async_trait::async_trait,
     base64::{prelude::BASE64_STANDARD, Engine},
     serde_json::{json, Number, Value},
-    solana_account_decoder::{UiAccount, UiAccountEncoding},
+    solana_account_decoder::{parse_sysvar::UiStakeHistoryEntry, UiAccount, UiAccountEncoding},
     solana_rpc_client_api::{
         client_error::Result,
         config::RpcBlockProductionConfig,
@@ -14,9 +14,9 @@ use {
             Response, RpcAccountBalance, RpcBlockProduction, RpcBlockProductionRange, RpcBlockhash,
             RpcConfirmedTransactionStatusWithSignature, RpcContactInfo, RpcFees, RpcIdentity,
             RpcInflationGovernor, RpcInflationRate, RpcInflationReward, RpcKeyedAccount,
-            RpcPerfSample, RpcPrioritizationFee, RpcResponseContext, RpcSimulateTransactionResult,
-            RpcSnapshotSlotInfo, RpcStakeActivation, RpcSupply, RpcVersionInfo, RpcVoteAccountInfo,
-            RpcVoteAccountStatus, StakeActivationState,
+            RpcNodeMetadata, RpcPerfSample, RpcPrioritizationFee, RpcResponseContext,
+            RpcSimulateTransactionResult, RpcSnapshotSlotInfo, RpcStakeActivation, RpcSupply,
+            RpcVersionInfo, RpcVoteAccountInfo, RpcVoteAccountStatus, StakeActivationState,
         },
     },
     solana_sdk::{
@@ -28,6 +28,7 @@ use {
         message::MessageHeader,
         pubkey::Pubkey,
         signature::Signature,
+        stake_history::StakeHistoryEntry,
         sysvar::epoch_schedule::EpochSchedule,
         transaction::{self, Transaction, TransactionError, TransactionVersion},
     },
@@ -228,12 +229,14 @@ impl RpcSender for MockSender {
                             post_balances: vec![499999999999999950, 50, 1],
                             inner_instructions: OptionSerializer::None,
                             log_messages: OptionSerializer::None,
+                            structured_log_messages: OptionSerializer::Skip,
                             pre_token_balances: OptionSerializer::None,
                             post_token_balances: OptionSerializer::None,
                             rewards: OptionSerializer::None,
                             loaded_addresses: OptionSerializer::Skip,
                             return_data: OptionSerializer::Skip,
                             compute_units_consumed: OptionSerializer::Skip,
+                            stake_changes: OptionSerializer::Skip,
                         }),
                 },
                 block_time: Some(1628633791),
@@ -292,6 +295,17 @@ impl RpcSender for MockSender {
                 active: 123,
                 inactive: 12,
             }),
+            "getStakeHistory" => json!(Response {
+                context: RpcResponseContext { slot: 1, api_version: None },
+                value: vec![UiStakeHistoryEntry {
+                    epoch: 1,
+                    stake_history: StakeHistoryEntry {
+                        effective: 123,
+                        activating: 12,
+                        deactivating: 1,
+                    },
+                }],
+            }),
             "getStakeMinimumDelegation" => json!(Response {
                 context: RpcResponseContext { slot: 1, api_version: None },
                 value: 123_456_789,
@@ -351,6 +365,9 @@ impl RpcSender for MockSender {
                     units_consumed: None,
                     return_data: None,
                     inner_instructions: None,
+                    account_diffs: None,
+                    instruction_compute_units: None,
+                    structured_logs: None,
                 },
             })?,
             "getMinimumBalanceForRentExemption" => json![20],
@@ -383,6 +400,11 @@ impl RpcSender for MockSender {
                 feature_set: None,
                 shred_version: None,
             }])?,
+            "getNodeMetadata" => serde_json::to_value(Some(RpcNodeMetadata {
+                pubkey: PUBKEY.to_string(),
+                metadata: "https://example.com".to_string(),
+                wallclock: 1_600_000_000_000,
+            }))?,
             "getBlock" => serde_json::to_value(EncodedConfirmedBlock {
                 previous_blockhash: "mfcyqEXB3DnHXki6KjjmZck6YjmZLvpAByy2fj4nh6B".to_string(),
                 blockhash: "3Eq21vXNB5s86c62bVuUfTeaMif1N2kUqRPBmGRJhyTA".to_string(),
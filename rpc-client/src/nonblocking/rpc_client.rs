@@ -26,9 +26,11 @@ use {
     },
     base64::{prelude::BASE64_STANDARD, Engine},
     bincode::serialize,
+    futures::{stream, StreamExt, TryStreamExt},
     log::*,
     serde_json::{json, Value},
     solana_account_decoder::{
+        parse_sysvar::UiStakeHistoryEntry,
         parse_token::{TokenAccountType, UiTokenAccount, UiTokenAmount},
         UiAccount, UiAccountData, UiAccountEncoding,
     },
@@ -38,7 +40,10 @@ use {
         },
         config::{RpcAccountInfoConfig, *},
         filter::{self, RpcFilterType},
-        request::{RpcError, RpcRequest, RpcResponseErrorData, TokenAccountsFilter},
+        request::{
+            RpcError, RpcRequest, RpcResponseErrorData, TokenAccountsFilter,
+            MAX_GET_SIGNATURE_STATUSES_QUERY_ITEMS, MAX_MULTIPLE_ACCOUNTS,
+        },
         response::*,
     },
     solana_sdk::{
@@ -59,6 +64,7 @@ use {
     },
     solana_vote_program::vote_state::MAX_LOCKOUT_HISTORY,
     std::{
+        future::Future,
         net::SocketAddr,
         str::FromStr,
         time::{Duration, Instant},
@@ -1679,6 +1685,67 @@ impl RpcClient {
         .await
     }
 
+    /// Gets the statuses of a list of transaction signatures of any length.
+    ///
+    /// The signatures are split into batches of at most
+    /// [`MAX_GET_SIGNATURE_STATUSES_QUERY_ITEMS`], up to
+    /// `max_concurrent_requests` of which are requested at once. The returned
+    /// vector of [`TransactionStatus`] has the same length and order as the
+    /// input slice, and the context of the batch at the lowest slot.
+    ///
+    /// If `search_transaction_history` is `true` this searches the node's full
+    /// ledger history like
+    /// [`get_signature_statuses_with_history`][RpcClient::get_signature_statuses_with_history],
+    /// otherwise only recent slots like
+    /// [`get_signature_statuses`][RpcClient::get_signature_statuses].
+    ///
+    /// # RPC Reference
+    ///
+    /// This method is built on the [`getSignatureStatuses`] RPC method.
+    ///
+    /// [`getSignatureStatuses`]: https://solana.com/docs/rpc/http/getsignaturestatuses
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::client_error::Error;
+    /// # use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+    /// # use solana_sdk::signature::Signature;
+    /// # futures::executor::block_on(async {
+    /// #     let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// let signatures = vec![Signature::default(); 1000];
+    /// let statuses = rpc_client
+    ///     .get_signature_statuses_chunked(&signatures, false, 4)
+    ///     .await?
+    ///     .value;
+    /// #     Ok::<(), Error>(())
+    /// # })?;
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub async fn get_signature_statuses_chunked(
+        &self,
+        signatures: &[Signature],
+        search_transaction_history: bool,
+        max_concurrent_requests: usize,
+    ) -> RpcResult<Vec<Option<TransactionStatus>>> {
+        send_chunked(
+            signatures,
+            MAX_GET_SIGNATURE_STATUSES_QUERY_ITEMS,
+            max_concurrent_requests,
+            move |signatures| async move {
+                let signatures: Vec<_> = signatures.iter().map(|s| s.to_string()).collect();
+                self.send(
+                    RpcRequest::GetSignatureStatuses,
+                    json!([signatures, {
+                        "searchTransactionHistory": search_transaction_history
+                    }]),
+                )
+                .await
+            },
+        )
+        .await
+    }
+
     /// Check if a transaction has been processed with the given [commitment level][cl].
     ///
     /// [cl]: https://solana.com/docs/rpc#configuring-state-commitment
@@ -2169,6 +2236,88 @@ impl RpcClient {
         .await
     }
 
+    /// Returns the decoded stake history entries for an epoch range.
+    ///
+    /// Entries are returned newest first, matching the layout of the stake
+    /// history sysvar. This avoids fetching and decoding the entire sysvar
+    /// account when only a few epochs are needed.
+    ///
+    /// This method uses the configured [commitment level][cl].
+    ///
+    /// [cl]: https://solana.com/docs/rpc#configuring-state-commitment
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the [`getStakeHistory`] RPC method.
+    ///
+    /// [`getStakeHistory`]: https://solana.com/docs/rpc/http/getstakehistory
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::client_error::Error;
+    /// # use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+    /// # futures::executor::block_on(async {
+    /// #     let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// let stake_history = rpc_client.get_stake_history(Some(1), Some(3)).await?;
+    /// #     Ok::<(), Error>(())
+    /// # })?;
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub async fn get_stake_history(
+        &self,
+        first_epoch: Option<Epoch>,
+        last_epoch: Option<Epoch>,
+    ) -> ClientResult<Vec<UiStakeHistoryEntry>> {
+        Ok(self
+            .get_stake_history_with_config(RpcStakeHistoryConfig {
+                first_epoch,
+                last_epoch,
+                commitment: Some(self.commitment()),
+                min_context_slot: None,
+            })
+            .await?
+            .value)
+    }
+
+    /// Returns the decoded stake history entries for an epoch range.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the [`getStakeHistory`] RPC method.
+    ///
+    /// [`getStakeHistory`]: https://solana.com/docs/rpc/http/getstakehistory
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::{
+    /// #     client_error::Error,
+    /// #     config::RpcStakeHistoryConfig,
+    /// # };
+    /// # use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+    /// # use solana_sdk::commitment_config::CommitmentConfig;
+    /// # futures::executor::block_on(async {
+    /// #     let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// let config = RpcStakeHistoryConfig {
+    ///     first_epoch: Some(1),
+    ///     last_epoch: None,
+    ///     commitment: Some(CommitmentConfig::finalized()),
+    ///     min_context_slot: None,
+    /// };
+    /// let stake_history = rpc_client.get_stake_history_with_config(config).await?;
+    /// #     Ok::<(), Error>(())
+    /// # })?;
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub async fn get_stake_history_with_config(
+        &self,
+        config: RpcStakeHistoryConfig,
+    ) -> RpcResult<Vec<UiStakeHistoryEntry>> {
+        self.send(RpcRequest::GetStakeHistory, json!([config]))
+            .await
+    }
+
     /// Returns information about the current supply.
     ///
     /// This method uses the configured [commitment level][cl].
@@ -2453,6 +2602,35 @@ impl RpcClient {
         self.send(RpcRequest::GetClusterNodes, Value::Null).await
     }
 
+    /// Returns the application-defined metadata that a node publishes
+    /// through gossip, if any.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the `getNodeMetadata` RPC method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::client_error::Error;
+    /// # use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+    /// # use solana_sdk::pubkey::Pubkey;
+    /// # futures::executor::block_on(async {
+    /// #     let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// let node_pubkey = Pubkey::new_unique();
+    /// let node_metadata = rpc_client.get_node_metadata(&node_pubkey).await?;
+    /// #     Ok::<(), Error>(())
+    /// # })?;
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub async fn get_node_metadata(
+        &self,
+        pubkey: &Pubkey,
+    ) -> ClientResult<Option<RpcNodeMetadata>> {
+        self.send(RpcRequest::GetNodeMetadata, json!([pubkey.to_string()]))
+            .await
+    }
+
     /// Returns identity and transaction information about a confirmed block in the ledger.
     ///
     /// The encodings are returned in [`UiTransactionEncoding::Json`][uite]
@@ -2554,6 +2732,7 @@ impl RpcClient {
     ///     rewards: Some(true),
     ///     commitment: None,
     ///     max_supported_transaction_version: Some(0),
+    ///     filter: None,
     /// };
     /// let block = rpc_client.get_block_with_config(
     ///     slot,
@@ -3581,6 +3760,57 @@ impl RpcClient {
             .await
     }
 
+    /// Returns a list of prioritization fees from recent blocks, like
+    /// [`get_recent_prioritization_fees`], computed according to `config`.
+    ///
+    /// If `config.percentile` is set, the fee of each block is that percentile of the fees paid
+    /// by its transactions, and by the transactions locking each of the provided addresses as
+    /// writable, rather than their minimums.
+    ///
+    /// [`get_recent_prioritization_fees`]: RpcClient::get_recent_prioritization_fees
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the [`getRecentPrioritizationFees`] RPC method.
+    ///
+    /// [`getRecentPrioritizationFees`]: https://solana.com/docs/rpc/http/getrecentprioritizationfees
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::{client_error::Error, config::RpcRecentPrioritizationFeesConfig};
+    /// # use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+    /// # use solana_sdk::signature::{Keypair, Signer};
+    /// # futures::executor::block_on(async {
+    /// #     let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// #     let alice = Keypair::new();
+    /// let config = RpcRecentPrioritizationFeesConfig {
+    ///     percentile: Some(75),
+    /// };
+    /// let prioritization_fees = rpc_client.get_recent_prioritization_fees_with_config(
+    ///     &[alice.pubkey()],
+    ///     config,
+    /// ).await?;
+    /// #     Ok::<(), Error>(())
+    /// # })?;
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub async fn get_recent_prioritization_fees_with_config(
+        &self,
+        addresses: &[Pubkey],
+        config: RpcRecentPrioritizationFeesConfig,
+    ) -> ClientResult<Vec<RpcPrioritizationFee>> {
+        let addresses: Vec<_> = addresses
+            .iter()
+            .map(|address| address.to_string())
+            .collect();
+        self.send(
+            RpcRequest::GetRecentPrioritizationFees,
+            json!([addresses, config]),
+        )
+        .await
+    }
+
     /// Returns the identity pubkey for the current node.
     ///
     /// # RPC Reference
@@ -4180,6 +4410,53 @@ impl RpcClient {
         })
     }
 
+    /// Returns the account information for a list of pubkeys of any length.
+    ///
+    /// The pubkeys are split into batches of at most [`MAX_MULTIPLE_ACCOUNTS`],
+    /// up to `max_concurrent_requests` of which are requested at once. The
+    /// accounts are returned in the order of `pubkeys`, along with the context
+    /// of the batch at the lowest slot.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method is built on the [`getMultipleAccounts`] RPC method.
+    ///
+    /// [`getMultipleAccounts`]: https://solana.com/docs/rpc/http/getmultipleaccounts
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::{
+    /// #     config::RpcAccountInfoConfig,
+    /// #     client_error::Error,
+    /// # };
+    /// # use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+    /// # use solana_sdk::pubkey::Pubkey;
+    /// # futures::executor::block_on(async {
+    /// #     let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// let pubkeys: Vec<_> = (0..1000).map(|_| Pubkey::new_unique()).collect();
+    /// let accounts = rpc_client
+    ///     .get_multiple_accounts_chunked_with_config(&pubkeys, RpcAccountInfoConfig::default(), 4)
+    ///     .await?;
+    /// #     Ok::<(), Error>(())
+    /// # })?;
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub async fn get_multiple_accounts_chunked_with_config(
+        &self,
+        pubkeys: &[Pubkey],
+        config: RpcAccountInfoConfig,
+        max_concurrent_requests: usize,
+    ) -> RpcResult<Vec<Option<Account>>> {
+        send_chunked(
+            pubkeys,
+            MAX_MULTIPLE_ACCOUNTS,
+            max_concurrent_requests,
+            |pubkeys| self.get_multiple_accounts_with_config(pubkeys, config.clone()),
+        )
+        .await
+    }
+
     /// Gets the raw data associated with an account.
     ///
     /// This is equivalent to calling [`get_account`] and then accessing the
@@ -4437,6 +4714,10 @@ impl RpcClient {
     ///         min_context_slot: Some(1234),
     ///     },
     ///     with_context: Some(false),
+    ///     sort_by: None,
+    ///     limit: None,
+    ///     before_pubkey: None,
+    ///     before_lamports: None,
     /// };
     /// let accounts = rpc_client.get_program_accounts_with_config(
     ///     &alice.pubkey(),
@@ -5395,6 +5676,59 @@ where
     Ok(encoded)
 }
 
+/// Send a request for every chunk of `items`, at most `max_concurrent_requests`
+/// at a time, and concatenate their responses in order. The context is the one
+/// of the response at the lowest slot. Fails if a response does not have one
+/// value per item of its chunk, since the values could not be matched to the
+/// items otherwise.
+async fn send_chunked<'a, T, U, F, Fut>(
+    items: &'a [T],
+    chunk_size: usize,
+    max_concurrent_requests: usize,
+    send: F,
+) -> RpcResult<Vec<U>>
+where
+    F: FnMut(&'a [T]) -> Fut,
+    Fut: Future<Output = RpcResult<Vec<U>>>,
+{
+    // An empty list is still requested, for the context
+    let chunks: Vec<_> = if items.is_empty() {
+        vec![items]
+    } else {
+        items.chunks(chunk_size).collect()
+    };
+    let chunk_lens: Vec<_> = chunks.iter().map(|chunk| chunk.len()).collect();
+    let responses: Vec<_> = stream::iter(chunks)
+        .map(send)
+        .buffered(max_concurrent_requests.max(1))
+        .try_collect()
+        .await?;
+
+    let mut context: Option<RpcResponseContext> = None;
+    let mut value = Vec::with_capacity(items.len());
+    for (response, chunk_len) in responses.into_iter().zip(chunk_lens) {
+        if response.value.len() != chunk_len {
+            return Err(RpcError::RpcRequestError(format!(
+                "RPC node returned {} values for a batch of {} items",
+                response.value.len(),
+                chunk_len,
+            ))
+            .into());
+        }
+        if context
+            .as_ref()
+            .map_or(true, |context| response.context.slot < context.slot)
+        {
+            context = Some(response.context);
+        }
+        value.extend(response.value);
+    }
+    Ok(Response {
+        context: context.expect("at least one chunk is requested"),
+        value,
+    })
+}
+
 pub(crate) fn get_rpc_request_str(rpc_addr: SocketAddr, tls: bool) -> String {
     if tls {
         format!("https://{rpc_addr}")
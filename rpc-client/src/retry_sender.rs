@@ -0,0 +1,402 @@
+//! An [`RpcSender`] middleware which throttles and retries requests.
+//!
+//! [`RetrySender`] wraps any other [`RpcSender`], usually an
+//! [`HttpSender`](crate::http_sender::HttpSender), and is passed to
+//! [`RpcClient::new_sender`](crate::rpc_client::RpcClient::new_sender):
+//!
+//! ```
+//! # use solana_rpc_client::{
+//! #     http_sender::HttpSender,
+//! #     retry_sender::{RateLimit, RetryConfig, RetryPolicy, RetrySender},
+//! #     rpc_client::{RpcClient, RpcClientConfig},
+//! # };
+//! # use solana_rpc_client_api::request::RpcRequest;
+//! let mut config = RetryConfig {
+//!     rate_limit: Some(RateLimit {
+//!         requests_per_second: 10,
+//!         burst: 20,
+//!     }),
+//!     ..RetryConfig::default()
+//! };
+//! config
+//!     .method_policies
+//!     .insert(RpcRequest::SendTransaction, RetryPolicy::none());
+//! let sender = RetrySender::new(HttpSender::new("http://localhost:8899"), config);
+//! let rpc_client = RpcClient::new_sender(sender, RpcClientConfig::default());
+//! ```
+
+use {
+    crate::rpc_sender::*,
+    async_trait::async_trait,
+    log::*,
+    reqwest::StatusCode,
+    solana_rpc_client_api::{
+        client_error::{ErrorKind, Result},
+        request::RpcRequest,
+    },
+    std::{
+        collections::HashMap,
+        sync::Mutex,
+        time::{Duration, Instant},
+    },
+    tokio::time::sleep,
+};
+
+/// How often and how long apart a failed request is retried.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Number of times the request is retried after failing
+    pub max_retries: usize,
+    /// Time to wait before the first retry, doubled for every other retry
+    pub initial_backoff: Duration,
+    /// Maximum time to wait before a retry
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(10),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy under which failed requests are not retried.
+    pub const fn none() -> Self {
+        Self {
+            max_retries: 0,
+            initial_backoff: Duration::ZERO,
+            max_backoff: Duration::ZERO,
+        }
+    }
+
+    fn backoff(&self, retry: usize) -> Duration {
+        let factor = 1u32.checked_shl(retry as u32).unwrap_or(u32::MAX);
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+}
+
+/// Client-side token bucket throttling of requests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    /// Number of requests sent per second on average, at least 1
+    pub requests_per_second: u32,
+    /// Number of requests which may be sent at once after being idle
+    pub burst: u32,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RetryConfig {
+    /// Policy of the requests without one in `method_policies`
+    pub default_policy: RetryPolicy,
+    pub method_policies: HashMap<RpcRequest, RetryPolicy>,
+    /// Throttling of every request, retries included
+    pub rate_limit: Option<RateLimit>,
+}
+
+struct TokenBucket {
+    rate_limit: RateLimit,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(rate_limit: RateLimit) -> Self {
+        Self {
+            rate_limit,
+            tokens: f64::from(rate_limit.burst),
+            last_refill: Instant::now(),
+        }
+    }
+
+    /// Take a token, returning how long to wait until it becomes available.
+    /// Tokens may be taken ahead of time, so concurrent requests queue up.
+    fn take(&mut self) -> Duration {
+        let requests_per_second = f64::from(self.rate_limit.requests_per_second.max(1));
+        let now = Instant::now();
+        let refilled = now.duration_since(self.last_refill).as_secs_f64() * requests_per_second;
+        self.tokens = (self.tokens + refilled).min(f64::from(self.rate_limit.burst.max(1)));
+        self.last_refill = now;
+        self.tokens -= 1.0;
+        if self.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-self.tokens / requests_per_second)
+        }
+    }
+}
+
+/// An [`RpcSender`] which throttles the requests of another sender, and
+/// retries those which fail transiently.
+///
+/// Requests which time out, fail to connect, or are answered with a server
+/// error are retried according to the [`RetryPolicy`] of their method. This
+/// includes requests rate limited with `429 Too Many Requests` which the
+/// wrapped sender gave up on, e.g. after [`HttpSender`] exhausted its own
+/// retries honoring the `Retry-After` header.
+///
+/// [`HttpSender`]: crate::http_sender::HttpSender
+pub struct RetrySender<T> {
+    sender: T,
+    default_policy: RetryPolicy,
+    method_policies: HashMap<RpcRequest, RetryPolicy>,
+    token_bucket: Option<Mutex<TokenBucket>>,
+    rate_limited_time: Mutex<Duration>,
+}
+
+impl<T: RpcSender> RetrySender<T> {
+    pub fn new(sender: T, config: RetryConfig) -> Self {
+        Self {
+            sender,
+            default_policy: config.default_policy,
+            method_policies: config.method_policies,
+            token_bucket: config.rate_limit.map(TokenBucket::new).map(Mutex::new),
+            rate_limited_time: Mutex::default(),
+        }
+    }
+
+    fn policy(&self, request: &RpcRequest) -> &RetryPolicy {
+        self.method_policies
+            .get(request)
+            .unwrap_or(&self.default_policy)
+    }
+
+    async fn wait(&self, duration: Duration) {
+        if !duration.is_zero() {
+            sleep(duration).await;
+            *self.rate_limited_time.lock().unwrap() += duration;
+        }
+    }
+
+    async fn throttle(&self) {
+        let duration = match &self.token_bucket {
+            Some(token_bucket) => token_bucket.lock().unwrap().take(),
+            None => Duration::ZERO,
+        };
+        self.wait(duration).await;
+    }
+}
+
+fn is_transient(kind: &ErrorKind) -> bool {
+    match kind {
+        ErrorKind::Io(_) => true,
+        ErrorKind::Reqwest(err) => {
+            err.is_timeout()
+                || err.is_connect()
+                || err.status().map_or(false, |status| {
+                    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+                })
+        }
+        _ => false,
+    }
+}
+
+#[async_trait]
+impl<T: RpcSender + Send + Sync> RpcSender for RetrySender<T> {
+    async fn send(
+        &self,
+        request: RpcRequest,
+        params: serde_json::Value,
+    ) -> Result<serde_json::Value> {
+        let policy = *self.policy(&request);
+        let mut retry = 0;
+        loop {
+            self.throttle().await;
+            match self.sender.send(request, params.clone()).await {
+                Err(err) if retry < policy.max_retries && is_transient(err.kind()) => {
+                    let backoff = policy.backoff(retry);
+                    retry += 1;
+                    debug!(
+                        "{} failed: {}, retry {} of {} in {:?}",
+                        request, err, retry, policy.max_retries, backoff
+                    );
+                    self.wait(backoff).await;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    fn get_transport_stats(&self) -> RpcTransportStats {
+        let mut stats = self.sender.get_transport_stats();
+        let rate_limited_time = *self.rate_limited_time.lock().unwrap();
+        stats.elapsed_time += rate_limited_time;
+        stats.rate_limited_time += rate_limited_time;
+        stats
+    }
+
+    fn url(&self) -> String {
+        self.sender.url()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_rpc_client_api::client_error::Error as ClientError,
+        std::{
+            io,
+            sync::atomic::{AtomicUsize, Ordering},
+        },
+    };
+
+    /// Fails every request with `kind` until `failures` requests failed
+    struct FailingSender {
+        failures: usize,
+        kind: fn() -> ErrorKind,
+        sent: AtomicUsize,
+    }
+
+    impl FailingSender {
+        fn new(failures: usize, kind: fn() -> ErrorKind) -> Self {
+            Self {
+                failures,
+                kind,
+                sent: AtomicUsize::default(),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl RpcSender for FailingSender {
+        async fn send(
+            &self,
+            request: RpcRequest,
+            _params: serde_json::Value,
+        ) -> Result<serde_json::Value> {
+            if self.sent.fetch_add(1, Ordering::Relaxed) < self.failures {
+                Err(ClientError::new_with_request((self.kind)(), request))
+            } else {
+                Ok(serde_json::Value::Null)
+            }
+        }
+
+        fn get_transport_stats(&self) -> RpcTransportStats {
+            RpcTransportStats::default()
+        }
+
+        fn url(&self) -> String {
+            "failing".to_string()
+        }
+    }
+
+    fn io_error() -> ErrorKind {
+        ErrorKind::Io(io::Error::from(io::ErrorKind::ConnectionReset))
+    }
+
+    fn custom_error() -> ErrorKind {
+        ErrorKind::Custom("permanent".to_string())
+    }
+
+    fn fast_policy(max_retries: usize) -> RetryPolicy {
+        RetryPolicy {
+            max_retries,
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(2),
+        }
+    }
+
+    #[test]
+    fn test_backoff() {
+        let policy = RetryPolicy {
+            max_retries: 10,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_millis(500),
+        };
+        assert_eq!(policy.backoff(0), Duration::from_millis(100));
+        assert_eq!(policy.backoff(1), Duration::from_millis(200));
+        assert_eq!(policy.backoff(2), Duration::from_millis(400));
+        assert_eq!(policy.backoff(3), Duration::from_millis(500));
+        assert_eq!(policy.backoff(100), Duration::from_millis(500));
+    }
+
+    #[test]
+    fn test_token_bucket() {
+        let mut token_bucket = TokenBucket::new(RateLimit {
+            requests_per_second: 10,
+            burst: 2,
+        });
+        assert_eq!(token_bucket.take(), Duration::ZERO);
+        assert_eq!(token_bucket.take(), Duration::ZERO);
+        let wait = token_bucket.take();
+        assert!(wait > Duration::ZERO && wait <= Duration::from_millis(100));
+        let wait = token_bucket.take();
+        assert!(wait > Duration::from_millis(100) && wait <= Duration::from_millis(200));
+    }
+
+    #[tokio::test]
+    async fn test_retry_transient_errors() {
+        let sender = RetrySender::new(
+            FailingSender::new(2, io_error),
+            RetryConfig {
+                default_policy: fast_policy(2),
+                ..RetryConfig::default()
+            },
+        );
+        assert!(sender
+            .send(RpcRequest::GetVersion, serde_json::Value::Null)
+            .await
+            .is_ok());
+        assert_eq!(sender.sender.sent.load(Ordering::Relaxed), 3);
+        assert!(sender.get_transport_stats().rate_limited_time > Duration::ZERO);
+
+        let sender = RetrySender::new(
+            FailingSender::new(3, io_error),
+            RetryConfig {
+                default_policy: fast_policy(2),
+                ..RetryConfig::default()
+            },
+        );
+        assert!(sender
+            .send(RpcRequest::GetVersion, serde_json::Value::Null)
+            .await
+            .is_err());
+        assert_eq!(sender.sender.sent.load(Ordering::Relaxed), 3);
+    }
+
+    #[tokio::test]
+    async fn test_no_retry_permanent_errors() {
+        let sender = RetrySender::new(
+            FailingSender::new(1, custom_error),
+            RetryConfig {
+                default_policy: fast_policy(2),
+                ..RetryConfig::default()
+            },
+        );
+        assert!(sender
+            .send(RpcRequest::GetVersion, serde_json::Value::Null)
+            .await
+            .is_err());
+        assert_eq!(sender.sender.sent.load(Ordering::Relaxed), 1);
+    }
+
+    #[tokio::test]
+    async fn test_method_policies() {
+        let sender = RetrySender::new(
+            FailingSender::new(1, io_error),
+            RetryConfig {
+                default_policy: fast_policy(2),
+                method_policies: HashMap::from([(
+                    RpcRequest::SendTransaction,
+                    RetryPolicy::none(),
+                )]),
+                ..RetryConfig::default()
+            },
+        );
+        assert!(sender
+            .send(RpcRequest::SendTransaction, serde_json::Value::Null)
+            .await
+            .is_err());
+        assert!(sender
+            .send(RpcRequest::GetVersion, serde_json::Value::Null)
+            .await
+            .is_ok());
+        assert_eq!(sender.sender.sent.load(Ordering::Relaxed), 2);
+    }
+}
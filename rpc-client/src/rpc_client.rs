@@ -24,6 +24,7 @@ use {
     serde::Serialize,
     serde_json::Value,
     solana_account_decoder::{
+        parse_sysvar::UiStakeHistoryEntry,
         parse_token::{UiTokenAccount, UiTokenAmount},
         UiAccount, UiAccountEncoding,
     },
@@ -1385,6 +1386,54 @@ impl RpcClient {
         self.invoke((self.rpc_client.as_ref()).get_signature_statuses_with_history(signatures))
     }
 
+    /// Gets the statuses of a list of transaction signatures of any length.
+    ///
+    /// The signatures are split into batches of at most
+    /// [`MAX_GET_SIGNATURE_STATUSES_QUERY_ITEMS`], up to
+    /// `max_concurrent_requests` of which are requested at once. The returned
+    /// vector of [`TransactionStatus`] has the same length and order as the
+    /// input slice, and the context of the batch at the lowest slot.
+    ///
+    /// [`MAX_GET_SIGNATURE_STATUSES_QUERY_ITEMS`]: solana_rpc_client_api::request::MAX_GET_SIGNATURE_STATUSES_QUERY_ITEMS
+    ///
+    /// If `search_transaction_history` is `true` this searches the node's full
+    /// ledger history like
+    /// [`get_signature_statuses_with_history`][RpcClient::get_signature_statuses_with_history],
+    /// otherwise only recent slots like
+    /// [`get_signature_statuses`][RpcClient::get_signature_statuses].
+    ///
+    /// # RPC Reference
+    ///
+    /// This method is built on the [`getSignatureStatuses`] RPC method.
+    ///
+    /// [`getSignatureStatuses`]: https://solana.com/docs/rpc/http/getsignaturestatuses
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::client_error::Error;
+    /// # use solana_rpc_client::rpc_client::RpcClient;
+    /// # use solana_sdk::signature::Signature;
+    /// # let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// let signatures = vec![Signature::default(); 1000];
+    /// let statuses = rpc_client
+    ///     .get_signature_statuses_chunked(&signatures, false, 4)?
+    ///     .value;
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn get_signature_statuses_chunked(
+        &self,
+        signatures: &[Signature],
+        search_transaction_history: bool,
+        max_concurrent_requests: usize,
+    ) -> RpcResult<Vec<Option<TransactionStatus>>> {
+        self.invoke((self.rpc_client.as_ref()).get_signature_statuses_chunked(
+            signatures,
+            search_transaction_history,
+            max_concurrent_requests,
+        ))
+    }
+
     /// Check if a transaction has been processed with the given [commitment level][cl].
     ///
     /// [cl]: https://solana.com/docs/rpc#configuring-state-commitment
@@ -1796,6 +1845,73 @@ impl RpcClient {
         self.invoke((self.rpc_client.as_ref()).get_stake_activation(stake_account, epoch))
     }
 
+    /// Returns the decoded stake history entries for an epoch range.
+    ///
+    /// Entries are returned newest first, matching the layout of the stake
+    /// history sysvar. This avoids fetching and decoding the entire sysvar
+    /// account when only a few epochs are needed.
+    ///
+    /// This method uses the configured [commitment level][cl].
+    ///
+    /// [cl]: https://solana.com/docs/rpc#configuring-state-commitment
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the [`getStakeHistory`] RPC method.
+    ///
+    /// [`getStakeHistory`]: https://solana.com/docs/rpc/http/getstakehistory
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::client_error::Error;
+    /// # use solana_rpc_client::rpc_client::RpcClient;
+    /// # let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// let stake_history = rpc_client.get_stake_history(Some(1), Some(3))?;
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn get_stake_history(
+        &self,
+        first_epoch: Option<Epoch>,
+        last_epoch: Option<Epoch>,
+    ) -> ClientResult<Vec<UiStakeHistoryEntry>> {
+        self.invoke((self.rpc_client.as_ref()).get_stake_history(first_epoch, last_epoch))
+    }
+
+    /// Returns the decoded stake history entries for an epoch range.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the [`getStakeHistory`] RPC method.
+    ///
+    /// [`getStakeHistory`]: https://solana.com/docs/rpc/http/getstakehistory
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::{
+    /// #     client_error::Error,
+    /// #     config::RpcStakeHistoryConfig,
+    /// # };
+    /// # use solana_rpc_client::rpc_client::RpcClient;
+    /// # use solana_sdk::commitment_config::CommitmentConfig;
+    /// # let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// let config = RpcStakeHistoryConfig {
+    ///     first_epoch: Some(1),
+    ///     last_epoch: None,
+    ///     commitment: Some(CommitmentConfig::finalized()),
+    ///     min_context_slot: None,
+    /// };
+    /// let stake_history = rpc_client.get_stake_history_with_config(config)?;
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn get_stake_history_with_config(
+        &self,
+        config: RpcStakeHistoryConfig,
+    ) -> RpcResult<Vec<UiStakeHistoryEntry>> {
+        self.invoke((self.rpc_client.as_ref()).get_stake_history_with_config(config))
+    }
+
     /// Returns information about the current supply.
     ///
     /// This method uses the configured [commitment level][cl].
@@ -2017,6 +2133,28 @@ impl RpcClient {
         self.invoke((self.rpc_client.as_ref()).get_cluster_nodes())
     }
 
+    /// Returns the application-defined metadata that a node publishes
+    /// through gossip, if any.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the `getNodeMetadata` RPC method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::client_error::Error;
+    /// # use solana_rpc_client::rpc_client::RpcClient;
+    /// # use solana_sdk::pubkey::Pubkey;
+    /// # let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// let node_pubkey = Pubkey::new_unique();
+    /// let node_metadata = rpc_client.get_node_metadata(&node_pubkey)?;
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn get_node_metadata(&self, pubkey: &Pubkey) -> ClientResult<Option<RpcNodeMetadata>> {
+        self.invoke((self.rpc_client.as_ref()).get_node_metadata(pubkey))
+    }
+
     /// Returns identity and transaction information about a confirmed block in the ledger.
     ///
     /// The encodings are returned in [`UiTransactionEncoding::Json`][uite]
@@ -2106,6 +2244,7 @@ impl RpcClient {
     ///     rewards: Some(true),
     ///     commitment: None,
     ///     max_supported_transaction_version: Some(0),
+    ///     filter: None,
     /// };
     /// let block = rpc_client.get_block_with_config(
     ///     slot,
@@ -2948,6 +3087,49 @@ impl RpcClient {
         self.invoke((self.rpc_client.as_ref()).get_recent_prioritization_fees(addresses))
     }
 
+    /// Returns a list of prioritization fees from recent blocks, like
+    /// [`get_recent_prioritization_fees`], computed according to `config`.
+    ///
+    /// If `config.percentile` is set, the fee of each block is that percentile of the fees paid
+    /// by its transactions, and by the transactions locking each of the provided addresses as
+    /// writable, rather than their minimums.
+    ///
+    /// [`get_recent_prioritization_fees`]: RpcClient::get_recent_prioritization_fees
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the [`getRecentPrioritizationFees`] RPC method.
+    ///
+    /// [`getRecentPrioritizationFees`]: https://solana.com/docs/rpc/http/getrecentprioritizationfees
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::{client_error::Error, config::RpcRecentPrioritizationFeesConfig};
+    /// # use solana_rpc_client::rpc_client::RpcClient;
+    /// # use solana_sdk::signature::{Keypair, Signer};
+    /// # let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// # let alice = Keypair::new();
+    /// let config = RpcRecentPrioritizationFeesConfig {
+    ///     percentile: Some(75),
+    /// };
+    /// let prioritization_fees = rpc_client.get_recent_prioritization_fees_with_config(
+    ///     &[alice.pubkey()],
+    ///     config,
+    /// )?;
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn get_recent_prioritization_fees_with_config(
+        &self,
+        addresses: &[Pubkey],
+        config: RpcRecentPrioritizationFeesConfig,
+    ) -> ClientResult<Vec<RpcPrioritizationFee>> {
+        self.invoke(
+            (self.rpc_client.as_ref())
+                .get_recent_prioritization_fees_with_config(addresses, config),
+        )
+    }
+
     /// Returns the identity pubkey for the current node.
     ///
     /// # RPC Reference
@@ -3412,6 +3594,54 @@ impl RpcClient {
         self.invoke((self.rpc_client.as_ref()).get_multiple_accounts_with_config(pubkeys, config))
     }
 
+    /// Returns the account information for a list of pubkeys of any length.
+    ///
+    /// The pubkeys are split into batches of at most [`MAX_MULTIPLE_ACCOUNTS`],
+    /// up to `max_concurrent_requests` of which are requested at once. The
+    /// accounts are returned in the order of `pubkeys`, along with the context
+    /// of the batch at the lowest slot.
+    ///
+    /// [`MAX_MULTIPLE_ACCOUNTS`]: solana_rpc_client_api::request::MAX_MULTIPLE_ACCOUNTS
+    ///
+    /// # RPC Reference
+    ///
+    /// This method is built on the [`getMultipleAccounts`] RPC method.
+    ///
+    /// [`getMultipleAccounts`]: https://solana.com/docs/rpc/http/getmultipleaccounts
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::{
+    /// #     config::RpcAccountInfoConfig,
+    /// #     client_error::Error,
+    /// # };
+    /// # use solana_rpc_client::rpc_client::RpcClient;
+    /// # use solana_sdk::pubkey::Pubkey;
+    /// # let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// let pubkeys: Vec<_> = (0..1000).map(|_| Pubkey::new_unique()).collect();
+    /// let accounts = rpc_client.get_multiple_accounts_chunked_with_config(
+    ///     &pubkeys,
+    ///     RpcAccountInfoConfig::default(),
+    ///     4,
+    /// )?;
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn get_multiple_accounts_chunked_with_config(
+        &self,
+        pubkeys: &[Pubkey],
+        config: RpcAccountInfoConfig,
+        max_concurrent_requests: usize,
+    ) -> RpcResult<Vec<Option<Account>>> {
+        self.invoke(
+            (self.rpc_client.as_ref()).get_multiple_accounts_chunked_with_config(
+                pubkeys,
+                config,
+                max_concurrent_requests,
+            ),
+        )
+    }
+
     /// Gets the raw data associated with an account.
     ///
     /// This is equivalent to calling [`get_account`] and then accessing the
@@ -3616,6 +3846,10 @@ impl RpcClient {
     ///         min_context_slot: Some(1234),
     ///     },
     ///     with_context: Some(false),
+    ///     sort_by: None,
+    ///     limit: None,
+    ///     before_pubkey: None,
+    ///     before_lamports: None,
     /// };
     /// let accounts = rpc_client.get_program_accounts_with_config(
     ///     &alice.pubkey(),
@@ -4099,7 +4333,10 @@ mod tests {
         jsonrpc_core::{futures::prelude::*, Error, IoHandler, Params},
         jsonrpc_http_server::{AccessControlAllowOrigin, DomainsValidation, ServerBuilder},
         serde_json::{json, Number},
-        solana_rpc_client_api::client_error::ErrorKind,
+        solana_rpc_client_api::{
+            client_error::ErrorKind,
+            request::{RpcError, MAX_MULTIPLE_ACCOUNTS},
+        },
         solana_sdk::{
             instruction::InstructionError,
             signature::{Keypair, Signer},
@@ -4381,6 +4618,10 @@ mod tests {
                             min_context_slot: None,
                         },
                         with_context: None,
+                        sort_by: None,
+                        limit: None,
+                        before_pubkey: None,
+                        before_lamports: None,
                     },
                 )
                 .unwrap();
@@ -4415,10 +4656,101 @@ mod tests {
                             min_context_slot: None,
                         },
                         with_context: Some(true),
+                        sort_by: None,
+                        limit: None,
+                        before_pubkey: None,
+                        before_lamports: None,
                     },
                 )
                 .unwrap();
             assert_eq!(expected_result, result);
         }
     }
+
+    /// Answers `getMultipleAccounts` with accounts owned by the requested
+    /// pubkeys, at the slot of the number of requested pubkeys, leaving out
+    /// the last account if `drop_last`
+    #[derive(Default)]
+    struct OwnerEchoSender {
+        drop_last: bool,
+    }
+
+    #[async_trait::async_trait]
+    impl RpcSender for OwnerEchoSender {
+        async fn send(
+            &self,
+            request: RpcRequest,
+            params: serde_json::Value,
+        ) -> ClientResult<serde_json::Value> {
+            assert_eq!(request, RpcRequest::GetMultipleAccounts);
+            let pubkeys: Vec<String> = serde_json::from_value(params[0].clone()).unwrap();
+            let num_accounts = if self.drop_last {
+                pubkeys.len().saturating_sub(1)
+            } else {
+                pubkeys.len()
+            };
+            let accounts: Vec<_> = pubkeys
+                .iter()
+                .take(num_accounts)
+                .map(|pubkey| {
+                    let owner = Pubkey::from_str(pubkey).unwrap();
+                    let account = Account::new(1, 0, &owner);
+                    UiAccount::encode(&owner, &account, UiAccountEncoding::Base64, None, None)
+                })
+                .collect();
+            Ok(serde_json::to_value(Response {
+                context: RpcResponseContext {
+                    slot: pubkeys.len() as Slot,
+                    api_version: None,
+                },
+                value: accounts,
+            })?)
+        }
+
+        fn get_transport_stats(&self) -> RpcTransportStats {
+            RpcTransportStats::default()
+        }
+
+        fn url(&self) -> String {
+            "owner_echo".to_string()
+        }
+    }
+
+    #[test]
+    fn test_get_multiple_accounts_chunked() {
+        let rpc_client =
+            RpcClient::new_sender(OwnerEchoSender::default(), RpcClientConfig::default());
+        let pubkeys: Vec<_> = (0..(2 * MAX_MULTIPLE_ACCOUNTS + 1))
+            .map(|_| Pubkey::new_unique())
+            .collect();
+        let response = rpc_client
+            .get_multiple_accounts_chunked_with_config(&pubkeys, RpcAccountInfoConfig::default(), 2)
+            .unwrap();
+        // the context is the one of the last, smallest chunk
+        assert_eq!(response.context.slot, 1);
+        let owners: Vec<_> = response
+            .value
+            .into_iter()
+            .map(|account| account.unwrap().owner)
+            .collect();
+        assert_eq!(owners, pubkeys);
+
+        let response = rpc_client
+            .get_multiple_accounts_chunked_with_config(&[], RpcAccountInfoConfig::default(), 2)
+            .unwrap();
+        assert!(response.value.is_empty());
+
+        // a response missing accounts cannot be matched to the pubkeys
+        let rpc_client = RpcClient::new_sender(
+            OwnerEchoSender { drop_last: true },
+            RpcClientConfig::default(),
+        );
+        let err = rpc_client
+            .get_multiple_accounts_chunked_with_config(&pubkeys, RpcAccountInfoConfig::default(), 2)
+            .unwrap_err();
+        assert_matches!(
+            err.kind(),
+            ErrorKind::RpcError(RpcError::RpcRequestError(_))
+        );
+    }
 }
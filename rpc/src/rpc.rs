@@ -10,6 +10,7 @@ use {
     jsonrpc_core::{futures::future, types::error, BoxFuture, Error, Metadata, Result},
     jsonrpc_derive::rpc,
     solana_account_decoder::{
+        parse_sysvar::UiStakeHistoryEntry,
         parse_token::{is_known_spl_token_id, token_amount_to_ui_amount, UiTokenAmount},
         UiAccount, UiAccountEncoding, UiDataSliceConfig, MAX_BASE58_BYTES,
     },
@@ -52,6 +53,7 @@ use {
         commitment::{BlockCommitmentArray, BlockCommitmentCache, CommitmentSlots},
         installed_scheduler_pool::BankWithScheduler,
         non_circulating_supply::calculate_non_circulating_supply,
+        prioritization_fee::MAX_PRIORITIZATION_FEE_PERCENTILE,
         prioritization_fee_cache::PrioritizationFeeCache,
         snapshot_config::SnapshotConfig,
         snapshot_utils,
@@ -59,7 +61,7 @@ use {
     solana_sdk::{
         account::{AccountSharedData, ReadableAccount},
         account_utils::StateMut,
-        clock::{Slot, UnixTimestamp, MAX_RECENT_BLOCKHASHES},
+        clock::{Epoch, Slot, UnixTimestamp, MAX_RECENT_BLOCKHASHES},
         commitment_config::{CommitmentConfig, CommitmentLevel},
         epoch_info::EpochInfo,
         epoch_schedule::EpochSchedule,
@@ -87,10 +89,12 @@ use {
     solana_storage_bigtable::Error as StorageError,
     solana_streamer::socket::SocketAddrSpace,
     solana_transaction_status::{
-        map_inner_instructions, BlockEncodingOptions, ConfirmedBlock,
-        ConfirmedTransactionStatusWithSignature, ConfirmedTransactionWithStatusMeta,
-        EncodedConfirmedTransactionWithStatusMeta, Reward, RewardType, TransactionBinaryEncoding,
-        TransactionConfirmationStatus, TransactionStatus, UiConfirmedBlock, UiTransactionEncoding,
+        map_inner_instructions,
+        structured_logs::{extract_structured_logs, UiStructuredLog},
+        BlockEncodingOptions, ConfirmedBlock, ConfirmedTransactionStatusWithSignature,
+        ConfirmedTransactionWithStatusMeta, EncodedConfirmedTransactionWithStatusMeta, Reward,
+        RewardType, TransactionBinaryEncoding, TransactionConfirmationStatus, TransactionStatus,
+        TransactionWithStatusMeta, UiConfirmedBlock, UiTransactionEncoding,
     },
     solana_vote_program::vote_state::{VoteState, MAX_LOCKOUT_HISTORY},
     spl_token_2022::{
@@ -476,6 +480,11 @@ impl JsonRpcRequestProcessor {
         config: Option<RpcAccountInfoConfig>,
         mut filters: Vec<RpcFilterType>,
         with_context: bool,
+        pagination: Option<(
+            RpcProgramAccountsSortBy,
+            Option<(u64, Pubkey)>,
+            Option<usize>,
+        )>,
     ) -> Result<OptionalContext<Vec<RpcKeyedAccount>>> {
         let RpcAccountInfoConfig {
             encoding,
@@ -489,13 +498,42 @@ impl JsonRpcRequestProcessor {
         })?;
         let encoding = encoding.unwrap_or(UiAccountEncoding::Binary);
         optimize_filters(&mut filters);
-        let keyed_accounts = {
-            if let Some(owner) = get_spl_token_owner_filter(program_id, &filters) {
-                self.get_filtered_spl_token_accounts_by_owner(&bank, program_id, &owner, filters)?
-            } else if let Some(mint) = get_spl_token_mint_filter(program_id, &filters) {
-                self.get_filtered_spl_token_accounts_by_mint(&bank, program_id, &mint, filters)?
-            } else {
-                self.get_filtered_program_accounts(&bank, program_id, filters)?
+        let spl_token_owner = get_spl_token_owner_filter(program_id, &filters);
+        let spl_token_mint = get_spl_token_mint_filter(program_id, &filters);
+        let keyed_accounts = match pagination {
+            // Without a secondary index to narrow the scan, a page of accounts sorted by pubkey is
+            // read by walking the accounts index in order, rather than by loading every account
+            // of the program for each page
+            Some((RpcProgramAccountsSortBy::Pubkey, before, Some(limit)))
+                if spl_token_owner.is_none()
+                    && spl_token_mint.is_none()
+                    && self
+                        .get_program_id_index_key(program_id, &filters)
+                        .is_none() =>
+            {
+                let before = before.map(|(_lamports, pubkey)| pubkey);
+                self.get_filtered_program_accounts_page(
+                    &bank,
+                    program_id,
+                    filters,
+                    before.as_ref(),
+                    limit,
+                )?
+            }
+            _ => {
+                let mut keyed_accounts = if let Some(owner) = spl_token_owner {
+                    self.get_filtered_spl_token_accounts_by_owner(
+                        &bank, program_id, &owner, filters,
+                    )?
+                } else if let Some(mint) = spl_token_mint {
+                    self.get_filtered_spl_token_accounts_by_mint(&bank, program_id, &mint, filters)?
+                } else {
+                    self.get_filtered_program_accounts(&bank, program_id, filters)?
+                };
+                if let Some((sort_by, before, limit)) = pagination {
+                    paginate_program_accounts(&mut keyed_accounts, sort_by, before, limit);
+                }
+                keyed_accounts
             }
         };
         let accounts = if is_known_spl_token_id(program_id)
@@ -639,6 +677,47 @@ impl JsonRpcRequestProcessor {
         bank.epoch_schedule().clone()
     }
 
+    pub fn get_stake_history(
+        &self,
+        config: Option<RpcStakeHistoryConfig>,
+    ) -> Result<RpcResponse<Vec<UiStakeHistoryEntry>>> {
+        let RpcStakeHistoryConfig {
+            first_epoch,
+            last_epoch,
+            commitment,
+            min_context_slot,
+        } = config.unwrap_or_default();
+        if let (Some(first_epoch), Some(last_epoch)) = (first_epoch, last_epoch) {
+            if last_epoch < first_epoch {
+                return Err(Error::invalid_params(format!(
+                    "lastEpoch, {last_epoch}, cannot be less than firstEpoch, {first_epoch}"
+                )));
+            }
+        }
+
+        let bank = self.get_bank_with_config(RpcContextConfig {
+            commitment,
+            min_context_slot,
+        })?;
+        let stake_history = bank
+            .get_stake_history_from_sysvar_cache()
+            .ok_or_else(Error::invalid_request)?;
+        let first_epoch = first_epoch.unwrap_or(Epoch::MIN);
+        let last_epoch = last_epoch.unwrap_or(Epoch::MAX);
+
+        // Entries are stored newest-first; preserve that ordering, matching the jsonParsed
+        // encoding of the sysvar account
+        let entries = stake_history
+            .iter()
+            .filter(|(epoch, _)| (first_epoch..=last_epoch).contains(epoch))
+            .map(|(epoch, entry)| UiStakeHistoryEntry {
+                epoch: *epoch,
+                stake_history: entry.clone(),
+            })
+            .collect();
+        Ok(new_response(&bank, entries))
+    }
+
     pub fn get_balance(
         &self,
         pubkey: &Pubkey,
@@ -1092,6 +1171,14 @@ impl JsonRpcRequestProcessor {
             };
             let commitment = config.commitment.unwrap_or_default();
             check_is_at_least_confirmed(commitment)?;
+            let filter_block = |mut confirmed_block: ConfirmedBlock| -> ConfirmedBlock {
+                if let Some(filter) = &config.filter {
+                    confirmed_block
+                        .transactions
+                        .retain(|transaction| block_filter_matches(filter, transaction));
+                }
+                confirmed_block
+            };
 
             // Block is old enough to be finalized
             if slot
@@ -1105,7 +1192,7 @@ impl JsonRpcRequestProcessor {
                 let result = self.blockstore.get_rooted_block(slot, true);
                 self.check_blockstore_root(&result, slot)?;
                 let encode_block = |confirmed_block: ConfirmedBlock| -> Result<UiConfirmedBlock> {
-                    let mut encoded_block = confirmed_block
+                    let mut encoded_block = filter_block(confirmed_block)
                         .encode_with_options(encoding, encoding_options)
                         .map_err(RpcCustomError::from)?;
                     if slot == 0 {
@@ -1137,6 +1224,7 @@ impl JsonRpcRequestProcessor {
                     return result
                         .ok()
                         .map(ConfirmedBlock::from)
+                        .map(filter_block)
                         .map(|mut confirmed_block| -> Result<UiConfirmedBlock> {
                             if confirmed_block.block_time.is_none()
                                 || confirmed_block.block_height.is_none()
@@ -1989,6 +2077,57 @@ impl JsonRpcRequestProcessor {
         Ok(new_response(&bank, accounts))
     }
 
+    /// The secondary index key to scan for the accounts of `program_id` passing `filters`, if
+    /// such an index is enabled
+    fn get_program_id_index_key(
+        &self,
+        program_id: &Pubkey,
+        filters: &[RpcFilterType],
+    ) -> Option<IndexKey> {
+        let data_size = filters.iter().find_map(|filter_type| match filter_type {
+            RpcFilterType::DataSize(data_size) => Some(*data_size),
+            _ => None,
+        });
+        let account_indexes = &self.config.account_indexes;
+        match data_size {
+            Some(data_size) if account_indexes.contains(&AccountIndex::ProgramIdDataSize) => {
+                Some(IndexKey::ProgramIdDataSize(*program_id, data_size))
+            }
+            _ if account_indexes.contains(&AccountIndex::ProgramId) => {
+                Some(IndexKey::ProgramId(*program_id))
+            }
+            _ => None,
+        }
+    }
+
+    /// Use a set of filters to get at most `limit` keyed program accounts with pubkeys below
+    /// `before` from a bank, in descending pubkey order
+    fn get_filtered_program_accounts_page(
+        &self,
+        bank: &Bank,
+        program_id: &Pubkey,
+        mut filters: Vec<RpcFilterType>,
+        before: Option<&Pubkey>,
+        limit: usize,
+    ) -> RpcCustomResult<Vec<(Pubkey, AccountSharedData)>> {
+        optimize_filters(&mut filters);
+        let filter_closure = |account: &AccountSharedData| {
+            filters
+                .iter()
+                .all(|filter_type| filter_type.allows(account))
+        };
+        bank.get_filtered_program_accounts_before(
+            program_id,
+            filter_closure,
+            before,
+            limit,
+            &ScanConfig::default(),
+        )
+        .map_err(|e| RpcCustomError::ScanError {
+            message: e.to_string(),
+        })
+    }
+
     /// Use a set of filters to get an iterator of keyed program accounts from a bank
     fn get_filtered_program_accounts(
         &self,
@@ -2002,11 +2141,7 @@ impl JsonRpcRequestProcessor {
                 .iter()
                 .all(|filter_type| filter_type.allows(account))
         };
-        if self
-            .config
-            .account_indexes
-            .contains(&AccountIndex::ProgramId)
-        {
+        if let Some(index_key) = self.get_program_id_index_key(program_id, &filters) {
             if !self.config.account_indexes.include_key(program_id) {
                 return Err(RpcCustomError::KeyExcludedFromSecondaryIndex {
                     index_key: program_id.to_string(),
@@ -2014,9 +2149,9 @@ impl JsonRpcRequestProcessor {
             }
             Ok(bank
                 .get_filtered_indexed_accounts(
-                    &IndexKey::ProgramId(*program_id),
+                    &index_key,
                     |account| {
-                        // The program-id account index checks for Account owner on inclusion. However, due
+                        // The program-id account indexes check for Account owner on inclusion. However, due
                         // to the current AccountsDb implementation, an account may remain in storage as a
                         // zero-lamport AccountSharedData::Default() after being wiped and reinitialized in later
                         // updates. We include the redundant filters here to avoid returning these
@@ -2175,10 +2310,23 @@ impl JsonRpcRequestProcessor {
     fn get_recent_prioritization_fees(
         &self,
         pubkeys: Vec<Pubkey>,
+        config: RpcRecentPrioritizationFeesConfig,
     ) -> Result<Vec<RpcPrioritizationFee>> {
-        Ok(self
-            .prioritization_fee_cache
-            .get_prioritization_fees(&pubkeys)
+        let prioritization_fees = match config.percentile {
+            Some(percentile) => {
+                if percentile > MAX_PRIORITIZATION_FEE_PERCENTILE {
+                    return Err(Error::invalid_params(format!(
+                        "Invalid percentile; max {MAX_PRIORITIZATION_FEE_PERCENTILE}"
+                    )));
+                }
+                self.prioritization_fee_cache
+                    .get_prioritization_fees_with_percentile(&pubkeys, percentile)
+            }
+            None => self
+                .prioritization_fee_cache
+                .get_prioritization_fees(&pubkeys),
+        };
+        Ok(prioritization_fees
             .into_iter()
             .map(|(slot, prioritization_fee)| RpcPrioritizationFee {
                 slot,
@@ -2227,6 +2375,41 @@ pub fn verify_pubkey(input: &str) -> Result<Pubkey> {
         .map_err(|e| Error::invalid_params(format!("Invalid param: {e:?}")))
 }
 
+/// Whether `transaction` is one of a block's transactions to return from
+/// `getBlock`, all of the filter's conditions holding
+fn block_filter_matches(filter: &RpcBlockFilter, transaction: &TransactionWithStatusMeta) -> bool {
+    let account_keys = transaction.account_keys();
+    let mentions_address = filter.mentions_address.map_or(true, |address| {
+        account_keys.iter().any(|key| key == &address)
+    });
+    let mentions_program_id = filter.mentions_program_id.map_or(true, |program_id| {
+        let invokes_program = |instruction: &CompiledInstruction| {
+            account_keys.get(usize::from(instruction.program_id_index)) == Some(&program_id)
+        };
+        match transaction {
+            TransactionWithStatusMeta::MissingMetadata(transaction) => {
+                transaction.message.instructions.iter().any(invokes_program)
+            }
+            TransactionWithStatusMeta::Complete(tx_with_meta) => {
+                tx_with_meta
+                    .transaction
+                    .message
+                    .instructions()
+                    .iter()
+                    .any(invokes_program)
+                    || tx_with_meta
+                        .meta
+                        .inner_instructions
+                        .iter()
+                        .flatten()
+                        .flat_map(|inner_instructions| &inner_instructions.instructions)
+                        .any(|inner_instruction| invokes_program(&inner_instruction.instruction))
+            }
+        }
+    });
+    mentions_address && mentions_program_id
+}
+
 fn verify_hash(input: &str) -> Result<Hash> {
     input
         .parse()
@@ -2307,6 +2490,96 @@ fn get_encoded_account(
     }
 }
 
+/// Keeps the keyed accounts which sort after the `(lamports, pubkey)` cursor of the last account
+/// of the previous page, descending by `sort_by`, and returns the first `limit` of them in order.
+/// Only the accounts on the page are sorted.
+fn paginate_program_accounts(
+    keyed_accounts: &mut Vec<(Pubkey, AccountSharedData)>,
+    sort_by: RpcProgramAccountsSortBy,
+    before: Option<(u64, Pubkey)>,
+    limit: Option<usize>,
+) {
+    let sort_key = |pubkey: &Pubkey, lamports: u64| match sort_by {
+        RpcProgramAccountsSortBy::Pubkey => (0, *pubkey),
+        RpcProgramAccountsSortBy::Lamports => (lamports, *pubkey),
+    };
+    let descending = |(a_pubkey, a_account): &(Pubkey, AccountSharedData),
+                      (b_pubkey, b_account): &(Pubkey, AccountSharedData)| {
+        sort_key(b_pubkey, b_account.lamports()).cmp(&sort_key(a_pubkey, a_account.lamports()))
+    };
+
+    if let Some((before_lamports, before_pubkey)) = before {
+        // The cursor holds the balance the account had when the previous page was served, so
+        // neither the account nor its current balance are looked up
+        let cursor = sort_key(&before_pubkey, before_lamports);
+        keyed_accounts.retain(|(pubkey, account)| sort_key(pubkey, account.lamports()) < cursor);
+    }
+
+    if let Some(limit) = limit {
+        if limit < keyed_accounts.len() {
+            keyed_accounts.select_nth_unstable_by(limit, descending);
+            keyed_accounts.truncate(limit);
+        }
+    }
+    keyed_accounts.sort_unstable_by(descending);
+}
+
+/// Describes how a simulated transaction changed an account, or returns `None` if the account
+/// is unchanged. The rent epoch is not compared, since the simulation may update it without the
+/// transaction touching the account.
+fn diff_simulated_account(
+    pre_account: &AccountSharedData,
+    post_account: &AccountSharedData,
+) -> Option<RpcSimulateTransactionAccountDiff> {
+    if pre_account.lamports() == post_account.lamports()
+        && pre_account.owner() == post_account.owner()
+        && pre_account.executable() == post_account.executable()
+        && pre_account.data() == post_account.data()
+    {
+        return None;
+    }
+
+    let pre_data = pre_account.data();
+    let post_data = post_account.data();
+    let mut data_diffs = vec![];
+    let mut offset = 0;
+    let max_len = pre_data.len().max(post_data.len());
+    while offset < max_len {
+        if pre_data.get(offset) == post_data.get(offset) {
+            offset += 1;
+            continue;
+        }
+        let start = offset;
+        while offset < max_len && pre_data.get(offset) != post_data.get(offset) {
+            offset += 1;
+        }
+        let byte_range = |data: &[u8]| {
+            let end = offset.min(data.len());
+            BASE64_STANDARD.encode(data.get(start..end).unwrap_or_default())
+        };
+        data_diffs.push(RpcAccountDataDiff {
+            offset: start,
+            pre: byte_range(pre_data),
+            post: byte_range(post_data),
+        });
+    }
+
+    Some(RpcSimulateTransactionAccountDiff {
+        pre_lamports: pre_account.lamports(),
+        post_lamports: post_account.lamports(),
+        pre_owner: pre_account.owner().to_string(),
+        post_owner: post_account.owner().to_string(),
+        pre_data_len: pre_data.len(),
+        post_data_len: post_data.len(),
+        data_diffs,
+    })
+}
+
+/// The structured logs printed by a simulated transaction, or `None` if there are none
+fn ui_structured_logs(logs: &[String]) -> Option<Vec<UiStructuredLog>> {
+    Some(extract_structured_logs(logs)).filter(|structured_logs| !structured_logs.is_empty())
+}
+
 fn encode_account<T: ReadableAccount>(
     account: &T,
     pubkey: &Pubkey,
@@ -2786,6 +3059,13 @@ pub mod rpc_bank {
             meta: Self::Metadata,
             config: Option<RpcBlockProductionConfig>,
         ) -> Result<RpcResponse<RpcBlockProduction>>;
+
+        #[rpc(meta, name = "getStakeHistory")]
+        fn get_stake_history(
+            &self,
+            meta: Self::Metadata,
+            config: Option<RpcStakeHistoryConfig>,
+        ) -> Result<RpcResponse<Vec<UiStakeHistoryEntry>>>;
     }
 
     pub struct BankDataImpl;
@@ -2948,6 +3228,15 @@ pub mod rpc_bank {
                 },
             ))
         }
+
+        fn get_stake_history(
+            &self,
+            meta: Self::Metadata,
+            config: Option<RpcStakeHistoryConfig>,
+        ) -> Result<RpcResponse<Vec<UiStakeHistoryEntry>>> {
+            debug!("get_stake_history rpc request received");
+            meta.get_stake_history(config)
+        }
     }
 }
 
@@ -3180,15 +3469,20 @@ pub mod rpc_accounts_scan {
                 program_id_str
             );
             let program_id = verify_pubkey(&program_id_str)?;
-            let (config, filters, with_context) = if let Some(config) = config {
-                (
-                    Some(config.account_config),
-                    config.filters.unwrap_or_default(),
-                    config.with_context.unwrap_or_default(),
-                )
-            } else {
-                (None, vec![], false)
-            };
+            let (config, filters, with_context, sort_by, limit, before_pubkey, before_lamports) =
+                if let Some(config) = config {
+                    (
+                        Some(config.account_config),
+                        config.filters.unwrap_or_default(),
+                        config.with_context.unwrap_or_default(),
+                        config.sort_by,
+                        config.limit,
+                        config.before_pubkey,
+                        config.before_lamports,
+                    )
+                } else {
+                    (None, vec![], false, None, None, None, None)
+                };
             if filters.len() > MAX_GET_PROGRAM_ACCOUNT_FILTERS {
                 return Err(Error::invalid_params(format!(
                     "Too many filters provided; max {MAX_GET_PROGRAM_ACCOUNT_FILTERS}"
@@ -3197,7 +3491,31 @@ pub mod rpc_accounts_scan {
             for filter in &filters {
                 verify_filter(filter)?;
             }
-            meta.get_program_accounts(&program_id, config, filters, with_context)
+            let before_pubkey = before_pubkey
+                .map(|before_pubkey| verify_pubkey(&before_pubkey))
+                .transpose()?;
+            // Paging is only deterministic over a defined ordering
+            let sort_by = sort_by.or_else(|| {
+                (limit.is_some() || before_pubkey.is_some())
+                    .then_some(RpcProgramAccountsSortBy::Pubkey)
+            });
+            if sort_by == Some(RpcProgramAccountsSortBy::Lamports)
+                && before_pubkey.is_some()
+                && before_lamports.is_none()
+            {
+                return Err(Error::invalid_params(
+                    "beforeLamports is required to page by lamports",
+                ));
+            }
+            let before = before_pubkey
+                .map(|before_pubkey| (before_lamports.unwrap_or_default(), before_pubkey));
+            meta.get_program_accounts(
+                &program_id,
+                config,
+                filters,
+                with_context,
+                sort_by.map(|sort_by| (sort_by, before, limit)),
+            )
         }
 
         fn get_largest_accounts(
@@ -3289,6 +3607,13 @@ pub mod rpc_full {
         #[rpc(meta, name = "getClusterNodes")]
         fn get_cluster_nodes(&self, meta: Self::Metadata) -> Result<Vec<RpcContactInfo>>;
 
+        #[rpc(meta, name = "getNodeMetadata")]
+        fn get_node_metadata(
+            &self,
+            meta: Self::Metadata,
+            pubkey_str: String,
+        ) -> Result<Option<RpcNodeMetadata>>;
+
         #[rpc(meta, name = "getRecentPerformanceSamples")]
         fn get_recent_performance_samples(
             &self,
@@ -3425,6 +3750,7 @@ pub mod rpc_full {
             &self,
             meta: Self::Metadata,
             pubkey_strs: Option<Vec<String>>,
+            config: Option<RpcRecentPrioritizationFeesConfig>,
         ) -> Result<Vec<RpcPrioritizationFee>>;
     }
 
@@ -3511,6 +3837,23 @@ pub mod rpc_full {
                 .collect())
         }
 
+        fn get_node_metadata(
+            &self,
+            meta: Self::Metadata,
+            pubkey_str: String,
+        ) -> Result<Option<RpcNodeMetadata>> {
+            debug!("get_node_metadata rpc request received: {:?}", pubkey_str);
+            let pubkey = verify_pubkey(&pubkey_str)?;
+            Ok(meta
+                .cluster_info
+                .get_node_metadata(&pubkey)
+                .map(|node_metadata| RpcNodeMetadata {
+                    pubkey: node_metadata.from.to_string(),
+                    metadata: node_metadata.metadata().to_string(),
+                    wallclock: node_metadata.wallclock,
+                }))
+        }
+
         fn get_signature_statuses(
             &self,
             meta: Self::Metadata,
@@ -3684,6 +4027,7 @@ pub mod rpc_full {
                     units_consumed,
                     return_data,
                     inner_instructions: _, // Always `None` due to `enable_cpi_recording = false`
+                    instruction_compute_units: _,
                 } = preflight_bank.simulate_transaction(&transaction, false)
                 {
                     match err {
@@ -3698,11 +4042,14 @@ pub mod rpc_full {
                         message: format!("Transaction simulation failed: {err}"),
                         result: RpcSimulateTransactionResult {
                             err: Some(err),
+                            structured_logs: ui_structured_logs(&logs),
                             logs: Some(logs),
                             accounts: None,
                             units_consumed: Some(units_consumed),
                             return_data: return_data.map(|return_data| return_data.into()),
                             inner_instructions: None,
+                            account_diffs: None,
+                            instruction_compute_units: None,
                         },
                     }
                     .into());
@@ -3771,12 +4118,13 @@ pub mod rpc_full {
                 units_consumed,
                 return_data,
                 inner_instructions,
+                instruction_compute_units,
             } = bank.simulate_transaction(&transaction, enable_cpi_recording);
 
             let account_keys = transaction.message().account_keys();
             let number_of_accounts = account_keys.len();
 
-            let accounts = if let Some(config_accounts) = config_accounts {
+            let (accounts, account_diffs) = if let Some(config_accounts) = config_accounts {
                 let accounts_encoding = config_accounts
                     .encoding
                     .unwrap_or(UiAccountEncoding::Base64);
@@ -3794,32 +4142,53 @@ pub mod rpc_full {
                 }
 
                 if result.is_err() {
-                    Some(vec![None; config_accounts.addresses.len()])
+                    (
+                        Some(vec![None; config_accounts.addresses.len()]),
+                        config_accounts
+                            .post_data_diff
+                            .then(|| vec![None; config_accounts.addresses.len()]),
+                    )
                 } else {
                     let mut post_simulation_accounts_map = HashMap::new();
                     for (pubkey, data) in post_simulation_accounts {
                         post_simulation_accounts_map.insert(pubkey, data);
                     }
 
-                    Some(
-                        config_accounts
-                            .addresses
+                    let pubkeys = config_accounts
+                        .addresses
+                        .iter()
+                        .map(|address_str| verify_pubkey(address_str))
+                        .collect::<Result<Vec<_>>>()?;
+                    let accounts = pubkeys
+                        .iter()
+                        .map(|pubkey| {
+                            get_encoded_account(
+                                bank,
+                                pubkey,
+                                accounts_encoding,
+                                None,
+                                Some(&post_simulation_accounts_map),
+                            )
+                        })
+                        .collect::<Result<Vec<_>>>()?;
+                    let account_diffs = config_accounts.post_data_diff.then(|| {
+                        pubkeys
                             .iter()
-                            .map(|address_str| {
-                                let pubkey = verify_pubkey(address_str)?;
-                                get_encoded_account(
-                                    bank,
-                                    &pubkey,
-                                    accounts_encoding,
-                                    None,
-                                    Some(&post_simulation_accounts_map),
-                                )
+                            .map(|pubkey| {
+                                post_simulation_accounts_map
+                                    .get(pubkey)
+                                    .and_then(|post_account| {
+                                        let pre_account =
+                                            bank.get_account(pubkey).unwrap_or_default();
+                                        diff_simulated_account(&pre_account, post_account)
+                                    })
                             })
-                            .collect::<Result<Vec<_>>>()?,
-                    )
+                            .collect()
+                    });
+                    (Some(accounts), account_diffs)
                 }
             } else {
-                None
+                (None, None)
             };
 
             let inner_instructions = inner_instructions.map(|info| {
@@ -3832,11 +4201,14 @@ pub mod rpc_full {
                 bank,
                 RpcSimulateTransactionResult {
                     err: result.err(),
+                    structured_logs: ui_structured_logs(&logs),
                     logs: Some(logs),
                     accounts,
                     units_consumed: Some(units_consumed),
                     return_data: return_data.map(|return_data| return_data.into()),
                     inner_instructions,
+                    account_diffs,
+                    instruction_compute_units,
                 },
             ))
         }
@@ -4034,6 +4406,7 @@ pub mod rpc_full {
             &self,
             meta: Self::Metadata,
             pubkey_strs: Option<Vec<String>>,
+            config: Option<RpcRecentPrioritizationFeesConfig>,
         ) -> Result<Vec<RpcPrioritizationFee>> {
             let pubkey_strs = pubkey_strs.unwrap_or_default();
             debug!(
@@ -4049,7 +4422,7 @@ pub mod rpc_full {
                 .into_iter()
                 .map(|pubkey_str| verify_pubkey(&pubkey_str))
                 .collect::<Result<Vec<_>>>()?;
-            meta.get_recent_prioritization_fees(pubkeys)
+            meta.get_recent_prioritization_fees(pubkeys, config.unwrap_or_default())
         }
     }
 }
@@ -5197,6 +5570,30 @@ pub mod tests {
         assert_eq!(result, expected);
     }
 
+    #[test]
+    fn test_rpc_get_node_metadata() {
+        let rpc = RpcHandler::start();
+        let request =
+            create_test_request("getNodeMetadata", Some(json!([rpc.identity.to_string()])));
+        let result: Value = parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result, Value::Null);
+
+        rpc.meta
+            .cluster_info
+            .push_node_metadata("https://example.com".to_string())
+            .unwrap();
+        rpc.meta.cluster_info.flush_push_queue();
+        let request =
+            create_test_request("getNodeMetadata", Some(json!([rpc.identity.to_string()])));
+        let result: RpcNodeMetadata = parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result.pubkey, rpc.identity.to_string());
+        assert_eq!(result.metadata, "https://example.com");
+
+        let request = create_test_request("getNodeMetadata", Some(json!(["invalid"])));
+        let response = parse_failure_response(rpc.handle_request_sync(request));
+        assert_eq!(response.0, ErrorCode::InvalidParams.code());
+    }
+
     #[test]
     fn test_rpc_get_recent_performance_samples() {
         let rpc = RpcHandler::start();
@@ -5459,6 +5856,70 @@ pub mod tests {
         assert_eq!(expected, &result);
     }
 
+    #[test]
+    fn test_rpc_get_stake_history() {
+        let rpc = RpcHandler::start();
+        let bank = rpc.working_bank();
+
+        let mut stake_history = StakeHistory::default();
+        for epoch in 0..10 {
+            stake_history.add(
+                epoch,
+                solana_sdk::stake_history::StakeHistoryEntry {
+                    effective: epoch * 100,
+                    activating: epoch * 10,
+                    deactivating: epoch,
+                },
+            );
+        }
+        bank.set_sysvar_for_tests(&stake_history);
+
+        let request = create_test_request("getStakeHistory", None);
+        let result: RpcResponse<Vec<UiStakeHistoryEntry>> =
+            parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result.value.len(), 10);
+        assert_eq!(result.value[0].epoch, 9);
+        assert_eq!(result.value[9].epoch, 0);
+
+        let request = create_test_request(
+            "getStakeHistory",
+            Some(json!([{"firstEpoch": 3, "lastEpoch": 5}])),
+        );
+        let result: RpcResponse<Vec<UiStakeHistoryEntry>> =
+            parse_success_result(rpc.handle_request_sync(request));
+        let expected: Vec<_> = (3..=5)
+            .rev()
+            .map(|epoch| UiStakeHistoryEntry {
+                epoch,
+                stake_history: stake_history.get(epoch).unwrap().clone(),
+            })
+            .collect();
+        assert_eq!(result.value, expected);
+
+        let request = create_test_request("getStakeHistory", Some(json!([{"firstEpoch": 8}])));
+        let result: RpcResponse<Vec<UiStakeHistoryEntry>> =
+            parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(
+            result
+                .value
+                .iter()
+                .map(|entry| entry.epoch)
+                .collect::<Vec<_>>(),
+            vec![9, 8]
+        );
+
+        let request = create_test_request(
+            "getStakeHistory",
+            Some(json!([{"firstEpoch": 5, "lastEpoch": 3}])),
+        );
+        let (code, message) = parse_failure_response(rpc.handle_request_sync(request));
+        assert_eq!(code, ErrorCode::InvalidParams.code());
+        assert_eq!(
+            message,
+            "lastEpoch, 3, cannot be less than firstEpoch, 5".to_string()
+        );
+    }
+
     #[test]
     fn test_rpc_get_leader_schedule() {
         let rpc = RpcHandler::start();
@@ -5874,6 +6335,102 @@ pub mod tests {
         assert_eq!(result.len(), 0);
     }
 
+    #[test]
+    fn test_rpc_get_program_accounts_paginated() {
+        let rpc = RpcHandler::start();
+        let bank = rpc.working_bank();
+
+        // several accounts share each balance, so ties are broken across pages
+        let program_id = Pubkey::new_unique();
+        let mut keys = [1, 1, 1, 2, 2, 3, 5, 5, 8u64]
+            .into_iter()
+            .map(|lamports| {
+                let pubkey = Pubkey::new_unique();
+                bank.store_account(&pubkey, &AccountSharedData::new(lamports, 0, &program_id));
+                (lamports, pubkey)
+            })
+            .collect::<Vec<_>>();
+        let get_page = |config: Value| {
+            let request = create_test_request(
+                "getProgramAccounts",
+                Some(json!([program_id.to_string(), config])),
+            );
+            let result: Vec<RpcKeyedAccount> =
+                parse_success_result(rpc.handle_request_sync(request));
+            result
+                .into_iter()
+                .map(|keyed_account| {
+                    (
+                        keyed_account.account.lamports,
+                        Pubkey::from_str(&keyed_account.pubkey).unwrap(),
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+        let get_all_pages = |sort_by: &str, limit: usize| {
+            let mut pages = vec![get_page(json!({"sortBy": sort_by, "limit": limit}))];
+            while let Some(&(before_lamports, before_pubkey)) = pages.last().unwrap().last() {
+                pages.push(get_page(json!({
+                    "sortBy": sort_by,
+                    "limit": limit,
+                    "beforePubkey": before_pubkey.to_string(),
+                    "beforeLamports": before_lamports,
+                })));
+            }
+            pages
+        };
+
+        // Paging without an explicit `sortBy` orders by pubkey, descending
+        keys.sort_by(|a, b| b.1.cmp(&a.1));
+        let first_page = get_page(json!({"limit": 2}));
+        assert_eq!(first_page, keys[..2]);
+        let second_page =
+            get_page(json!({"limit": 2, "beforePubkey": first_page[1].1.to_string()}));
+        assert_eq!(second_page, keys[2..4]);
+        let pages = get_all_pages("pubkey", 4);
+        assert_eq!(
+            pages.iter().map(Vec::len).collect::<Vec<_>>(),
+            vec![4, 4, 1, 0]
+        );
+        assert_eq!(pages.concat(), keys);
+
+        // Sorting by lamports puts the largest balance first, and pages through ties by pubkey
+        keys.sort_by(|a, b| b.cmp(a));
+        for limit in 1..=keys.len() {
+            let pages = get_all_pages("lamports", limit);
+            assert!(pages[..pages.len() - 1]
+                .iter()
+                .all(|page| !page.is_empty() && page.len() <= limit));
+            assert_eq!(pages.concat(), keys);
+        }
+        let page = get_page(json!({"sortBy": "lamports", "limit": 3}));
+        assert_eq!(page, keys[..3]);
+        let page = get_page(json!({
+            "sortBy": "lamports",
+            "beforePubkey": page[2].1.to_string(),
+            "beforeLamports": page[2].0,
+        }));
+        assert_eq!(page, keys[3..]);
+
+        // The lamports of the cursor are required to page by lamports
+        let request = create_test_request(
+            "getProgramAccounts",
+            Some(json!([
+                program_id.to_string(),
+                {"sortBy": "lamports", "beforePubkey": keys[0].1.to_string()},
+            ])),
+        );
+        let (code, _) = parse_failure_response(rpc.handle_request_sync(request));
+        assert_eq!(code, ErrorCode::InvalidParams.code());
+
+        let request = create_test_request(
+            "getProgramAccounts",
+            Some(json!([program_id.to_string(), {"beforePubkey": "not a pubkey"}])),
+        );
+        let (code, _) = parse_failure_response(rpc.handle_request_sync(request));
+        assert_eq!(code, ErrorCode::InvalidParams.code());
+    }
+
     #[test]
     fn test_rpc_simulate_transaction() {
         let rpc = RpcHandler::start();
@@ -6151,6 +6708,181 @@ pub mod tests {
         assert_eq!(result, expected);
     }
 
+    #[test]
+    fn test_diff_simulated_account() {
+        let owner = Pubkey::new_unique();
+        let pre_account = AccountSharedData::create(1, vec![1, 2, 3, 4], owner, false, 0);
+        assert_eq!(diff_simulated_account(&pre_account, &pre_account), None);
+
+        // Only the rent epoch changed
+        let mut post_account = pre_account.clone();
+        post_account.set_rent_epoch(1);
+        assert_eq!(diff_simulated_account(&pre_account, &post_account), None);
+
+        // Data changed and grew
+        post_account.set_data_from_slice(&[1, 5, 3, 4, 6]);
+        assert_eq!(
+            diff_simulated_account(&pre_account, &post_account),
+            Some(RpcSimulateTransactionAccountDiff {
+                pre_lamports: 1,
+                post_lamports: 1,
+                pre_owner: owner.to_string(),
+                post_owner: owner.to_string(),
+                pre_data_len: 4,
+                post_data_len: 5,
+                data_diffs: vec![
+                    RpcAccountDataDiff {
+                        offset: 1,
+                        pre: BASE64_STANDARD.encode([2]),
+                        post: BASE64_STANDARD.encode([5]),
+                    },
+                    RpcAccountDataDiff {
+                        offset: 4,
+                        pre: String::new(),
+                        post: BASE64_STANDARD.encode([6]),
+                    },
+                ],
+            })
+        );
+
+        // Lamports changed, and adjacent changed bytes are merged into one diff
+        let pre_account = AccountSharedData::create(10, vec![0, 1, 2, 3, 4, 5], owner, false, 0);
+        let post_account =
+            AccountSharedData::create(7, vec![0, 9, 9, 3, 4, 5, 6, 7], owner, false, 0);
+        assert_eq!(
+            diff_simulated_account(&pre_account, &post_account),
+            Some(RpcSimulateTransactionAccountDiff {
+                pre_lamports: 10,
+                post_lamports: 7,
+                pre_owner: owner.to_string(),
+                post_owner: owner.to_string(),
+                pre_data_len: 6,
+                post_data_len: 8,
+                data_diffs: vec![
+                    RpcAccountDataDiff {
+                        offset: 1,
+                        pre: BASE64_STANDARD.encode([1, 2]),
+                        post: BASE64_STANDARD.encode([9, 9]),
+                    },
+                    RpcAccountDataDiff {
+                        offset: 6,
+                        pre: String::new(),
+                        post: BASE64_STANDARD.encode([6, 7]),
+                    },
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn test_rpc_simulate_transaction_with_post_data_diff() {
+        let rpc = RpcHandler::start();
+        let bank = rpc.working_bank();
+        let rent_exempt_amount = bank.get_minimum_balance_for_rent_exemption(0);
+        let recent_blockhash = bank.confirmed_last_blockhash();
+        let RpcHandler {
+            ref meta, ref io, ..
+        } = rpc;
+
+        let bob_pubkey = solana_sdk::pubkey::new_rand();
+        let tx = system_transaction::transfer(
+            &rpc.mint_keypair,
+            &bob_pubkey,
+            rent_exempt_amount,
+            recent_blockhash,
+        );
+        let tx_serialized_encoded = bs58::encode(serialize(&tx).unwrap()).into_string();
+
+        // Simulation bank must be frozen
+        bank.freeze();
+
+        let req = format!(
+            r#"{{"jsonrpc":"2.0",
+                 "id":1,
+                 "method":"simulateTransaction",
+                 "params":[
+                   "{}",
+                   {{
+                     "accounts": {{
+                       "addresses": ["{}", "{}"],
+                       "postDataDiff": true
+                     }}
+                   }}
+                 ]
+            }}"#,
+            tx_serialized_encoded,
+            solana_sdk::pubkey::new_rand(),
+            bob_pubkey,
+        );
+        let res = io.handle_request_sync(&req, meta.clone());
+        let result: Response = serde_json::from_str(&res.expect("actual response"))
+            .expect("actual response deserialization");
+        let RpcSimulateTransactionResult {
+            err, account_diffs, ..
+        } = parse_success_result::<RpcResponse<RpcSimulateTransactionResult>>(result).value;
+        assert_eq!(err, None);
+        assert_eq!(
+            account_diffs,
+            Some(vec![
+                None,
+                Some(RpcSimulateTransactionAccountDiff {
+                    pre_lamports: 0,
+                    post_lamports: rent_exempt_amount,
+                    pre_owner: system_program::id().to_string(),
+                    post_owner: system_program::id().to_string(),
+                    pre_data_len: 0,
+                    post_data_len: 0,
+                    data_diffs: vec![],
+                }),
+            ])
+        );
+
+        // Without `postDataDiff` the field is omitted entirely
+        let req = format!(
+            r#"{{"jsonrpc":"2.0",
+                 "id":1,
+                 "method":"simulateTransaction",
+                 "params":["{}", {{"accounts": {{"addresses": ["{}"]}}}}]
+            }}"#,
+            tx_serialized_encoded, bob_pubkey,
+        );
+        let res = io.handle_request_sync(&req, meta.clone());
+        let result: Value = serde_json::from_str(&res.expect("actual response"))
+            .expect("actual response deserialization");
+        assert!(result["result"]["value"].get("accountDiffs").is_none());
+    }
+
+    #[test]
+    fn test_ui_structured_logs() {
+        let loader_id = solana_sdk::bpf_loader_upgradeable::id();
+        let fields: [&[u8]; 6] = [
+            solana_sdk::log::STRUCTURED_LOG_TAG,
+            &[1],
+            b"deploy",
+            b"invalid opcode",
+            b"stage",
+            b"verify",
+        ];
+        let diagnostic = fields
+            .iter()
+            .map(|field| BASE64_STANDARD.encode(field))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let mut logs = vec![
+            format!("Program {loader_id} invoke [1]"),
+            "Program log: hello".to_string(),
+            format!("Program {loader_id} failed: invalid account data for instruction"),
+        ];
+        assert_eq!(ui_structured_logs(&logs), None);
+
+        logs.insert(2, format!("Program data: {diagnostic}"));
+        let structured_logs = ui_structured_logs(&logs).unwrap();
+        assert_eq!(structured_logs.len(), 1);
+        assert_eq!(structured_logs[0].program_id, Some(loader_id.to_string()));
+        assert_eq!(structured_logs[0].target, "deploy");
+        assert_eq!(structured_logs[0].fields["stage"], "verify");
+    }
+
     #[test]
     fn test_rpc_simulate_transaction_with_parsing_token_accounts() {
         let rpc = RpcHandler::start();
@@ -6492,6 +7224,12 @@ pub mod tests {
                     ],
                     "returnData":null,
                     "unitsConsumed":1200,
+                    "instructionComputeUnits": [
+                        {
+                        "computeUnitsConsumed": 1200,
+                        "innerInstructions": [150, 150, 150]
+                        }
+                    ],
                 }
             },
             "id": 1,
@@ -7343,6 +8081,7 @@ pub mod tests {
                     rewards: Some(false),
                     commitment: None,
                     max_supported_transaction_version: None,
+                    filter: None,
                 },
             ])),
         );
@@ -7366,6 +8105,7 @@ pub mod tests {
                     rewards: Some(true),
                     commitment: None,
                     max_supported_transaction_version: None,
+                    filter: None,
                 },
             ])),
         );
@@ -7377,6 +8117,57 @@ pub mod tests {
         assert_eq!(confirmed_block.rewards.unwrap(), vec![]);
     }
 
+    #[test]
+    fn test_get_block_filter() {
+        let rpc = RpcHandler::start();
+        let confirmed_block_signatures = rpc.create_test_transactions_and_populate_blockstore();
+        let get_block_signatures = |filter: Value| -> Vec<String> {
+            let request = create_test_request(
+                "getBlock",
+                Some(json!([
+                    0u64,
+                    {
+                        "transactionDetails": "signatures",
+                        "filter": filter,
+                    },
+                ])),
+            );
+            let result: Option<UiConfirmedBlock> =
+                parse_success_result(rpc.handle_request_sync(request));
+            result.unwrap().signatures.unwrap()
+        };
+        let [success_signature, ix_error_signature] = [
+            confirmed_block_signatures[0].to_string(),
+            confirmed_block_signatures[1].to_string(),
+        ];
+
+        let signatures = get_block_signatures(json!({
+            "mentionsAddress": rpc.mint_keypair.pubkey().to_string(),
+        }));
+        assert!(signatures.contains(&success_signature));
+        assert!(!signatures.contains(&ix_error_signature));
+
+        let signatures = get_block_signatures(json!({
+            "mentionsProgramId": system_program::id().to_string(),
+        }));
+        assert!(signatures.contains(&success_signature));
+        assert!(signatures.contains(&ix_error_signature));
+
+        // all of the filter's conditions must hold
+        let signatures = get_block_signatures(json!({
+            "mentionsProgramId": Pubkey::new_unique().to_string(),
+            "mentionsAddress": rpc.mint_keypair.pubkey().to_string(),
+        }));
+        assert!(signatures.is_empty());
+
+        let request = create_test_request(
+            "getBlock",
+            Some(json!([0u64, {"filter": {"mentionsAddress": "invalid"}}])),
+        );
+        let (code, _) = parse_failure_response(rpc.handle_request_sync(request));
+        assert_eq!(code, ErrorCode::InvalidParams.code());
+    }
+
     #[test]
     fn test_get_block_production() {
         let rpc = RpcHandler::start();
@@ -9213,5 +10004,57 @@ pub mod tests {
                 },
             ],
         );
+
+        // Each block's transactions paid [0, price]
+        let request = create_test_request(
+            "getRecentPrioritizationFees",
+            Some(json!([[], {"percentile": 100}])),
+        );
+        let mut response: Vec<RpcPrioritizationFee> =
+            parse_success_result(rpc.handle_request_sync(request));
+        assert_fee_vec_eq(
+            &mut response,
+            &mut vec![
+                RpcPrioritizationFee {
+                    slot: slot0,
+                    prioritization_fee: price0,
+                },
+                RpcPrioritizationFee {
+                    slot: slot1,
+                    prioritization_fee: price1,
+                },
+            ],
+        );
+
+        let request = create_test_request(
+            "getRecentPrioritizationFees",
+            Some(json!([[account1.to_string()], {"percentile": 50}])),
+        );
+        let mut response: Vec<RpcPrioritizationFee> =
+            parse_success_result(rpc.handle_request_sync(request));
+        assert_fee_vec_eq(
+            &mut response,
+            &mut vec![
+                RpcPrioritizationFee {
+                    slot: slot0,
+                    prioritization_fee: price0,
+                },
+                RpcPrioritizationFee {
+                    slot: slot1,
+                    prioritization_fee: 0,
+                },
+            ],
+        );
+
+        let request = create_test_request(
+            "getRecentPrioritizationFees",
+            Some(json!([[], {"percentile": 101}])),
+        );
+        let response = parse_failure_response(rpc.handle_request_sync(request));
+        let expected = (
+            ErrorCode::InvalidParams.code(),
+            String::from("Invalid percentile; max 100"),
+        );
+        assert_eq!(response, expected);
     }
 }
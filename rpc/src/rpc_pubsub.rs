@@ -7,9 +7,9 @@ use {
         rpc_pubsub_service::PubSubConfig,
         rpc_subscription_tracker::{
             AccountSubscriptionParams, BlockSubscriptionKind, BlockSubscriptionParams,
-            LogsSubscriptionKind, LogsSubscriptionParams, ProgramSubscriptionParams,
-            SignatureSubscriptionParams, SubscriptionControl, SubscriptionId, SubscriptionParams,
-            SubscriptionToken,
+            BlockTransactionFilter, LogsSubscriptionKind, LogsSubscriptionParams,
+            ProgramSubscriptionParams, SignatureSubscriptionParams, SubscriptionControl,
+            SubscriptionId, SubscriptionParams, SubscriptionToken,
         },
     },
     dashmap::DashMap,
@@ -20,7 +20,8 @@ use {
     solana_rpc_client_api::{
         config::{
             RpcAccountInfoConfig, RpcBlockSubscribeConfig, RpcBlockSubscribeFilter,
-            RpcProgramAccountsConfig, RpcSignatureSubscribeConfig, RpcTransactionLogsConfig,
+            RpcBlockTransactionFilter, RpcProgramAccountsConfig, RpcSignatureSubscribeConfig,
+            RpcSysvarSubscribeConfig, RpcSysvarSubscribeFilter, RpcTransactionLogsConfig,
             RpcTransactionLogsFilter,
         },
         response::{
@@ -28,11 +29,14 @@ use {
             RpcSignatureResult, RpcVersionInfo, RpcVote, SlotInfo, SlotUpdate,
         },
     },
-    solana_sdk::{clock::Slot, pubkey::Pubkey, signature::Signature},
+    solana_sdk::{clock::Slot, pubkey::Pubkey, signature::Signature, sysvar},
     solana_transaction_status::UiTransactionEncoding,
     std::{str::FromStr, sync::Arc},
 };
 
+/// Maximum number of addresses and program ids in a `blockSubscribe` filter
+pub const MAX_BLOCK_SUBSCRIBE_FILTER_PUBKEYS: usize = 100;
+
 // We have to keep both of the following traits to not break backwards compatibility.
 // `RpcSolPubSubInternal` is actually used by the current PubSub API implementation.
 // `RpcSolPubSub` and the corresponding `gen_client` module are preserved
@@ -245,6 +249,32 @@ pub trait RpcSolPubSub {
         meta: Option<Self::Metadata>,
         id: PubSubSubscriptionId,
     ) -> Result<bool>;
+
+    // Get notification every time a sysvar is updated, with the sysvar decoded to JSON
+    #[pubsub(
+        subscription = "sysvarNotification",
+        subscribe,
+        name = "sysvarSubscribe"
+    )]
+    fn sysvar_subscribe(
+        &self,
+        meta: Self::Metadata,
+        subscriber: Subscriber<RpcResponse<UiAccount>>,
+        sysvar: RpcSysvarSubscribeFilter,
+        config: Option<RpcSysvarSubscribeConfig>,
+    );
+
+    // Unsubscribe from sysvar notification subscription.
+    #[pubsub(
+        subscription = "sysvarNotification",
+        unsubscribe,
+        name = "sysvarUnsubscribe"
+    )]
+    fn sysvar_unsubscribe(
+        &self,
+        meta: Option<Self::Metadata>,
+        id: PubSubSubscriptionId,
+    ) -> Result<bool>;
 }
 
 pub use internal::RpcSolPubSubInternal;
@@ -350,6 +380,18 @@ mod internal {
         #[rpc(name = "rootUnsubscribe")]
         fn root_unsubscribe(&self, id: SubscriptionId) -> Result<bool>;
 
+        // Get notification every time a sysvar is updated, with the sysvar decoded to JSON
+        #[rpc(name = "sysvarSubscribe")]
+        fn sysvar_subscribe(
+            &self,
+            sysvar: RpcSysvarSubscribeFilter,
+            config: Option<RpcSysvarSubscribeConfig>,
+        ) -> Result<SubscriptionId>;
+
+        // Unsubscribe from sysvar notification subscription.
+        #[rpc(name = "sysvarUnsubscribe")]
+        fn sysvar_unsubscribe(&self, id: SubscriptionId) -> Result<bool>;
+
         // Get the current solana version running on the node
         #[rpc(name = "getVersion")]
         fn get_version(&self) -> Result<RpcVersionInfo>;
@@ -419,6 +461,31 @@ fn param<T: FromStr>(param_str: &str, thing: &str) -> Result<T> {
     })
 }
 
+fn block_transaction_filter(filter: RpcBlockTransactionFilter) -> Result<BlockTransactionFilter> {
+    let pubkeys = |keys: Vec<String>, thing: &str| -> Result<Vec<Pubkey>> {
+        keys.iter().map(|key| param::<Pubkey>(key, thing)).collect()
+    };
+    let filters = |filters: Vec<RpcBlockTransactionFilter>| -> Result<Vec<BlockTransactionFilter>> {
+        filters.into_iter().map(block_transaction_filter).collect()
+    };
+    Ok(match filter {
+        RpcBlockTransactionFilter::MentionsAnyAddress(keys) => {
+            BlockTransactionFilter::MentionsAnyAddress(pubkeys(keys, "mentions_any_address")?)
+        }
+        RpcBlockTransactionFilter::ExcludesAddresses(keys) => {
+            BlockTransactionFilter::ExcludesAddresses(pubkeys(keys, "excludes_addresses")?)
+        }
+        RpcBlockTransactionFilter::InvokesAnyProgram(keys) => {
+            BlockTransactionFilter::InvokesAnyProgram(pubkeys(keys, "invokes_any_program")?)
+        }
+        RpcBlockTransactionFilter::ExcludesPrograms(keys) => {
+            BlockTransactionFilter::ExcludesPrograms(pubkeys(keys, "excludes_programs")?)
+        }
+        RpcBlockTransactionFilter::And(and) => BlockTransactionFilter::And(filters(and)?),
+        RpcBlockTransactionFilter::Or(or) => BlockTransactionFilter::Or(filters(or)?),
+    })
+}
+
 impl RpcSolPubSubInternal for RpcSolPubSubImpl {
     fn account_subscribe(
         &self,
@@ -553,6 +620,20 @@ impl RpcSolPubSubInternal for RpcSolPubSubImpl {
                         "mentions_account_or_program",
                     )?)
                 }
+                RpcBlockSubscribeFilter::Matches(filter) => {
+                    let filter = block_transaction_filter(filter)?;
+                    if filter.num_pubkeys() > MAX_BLOCK_SUBSCRIBE_FILTER_PUBKEYS {
+                        return Err(Error {
+                            code: ErrorCode::InvalidParams,
+                            message: format!(
+                                "Invalid Request: Only {MAX_BLOCK_SUBSCRIBE_FILTER_PUBKEYS} \
+                                 addresses and program ids supported"
+                            ),
+                            data: None,
+                        });
+                    }
+                    BlockSubscriptionKind::Matches(filter)
+                }
             },
             transaction_details: config.transaction_details.unwrap_or_default(),
             show_rewards: config.show_rewards.unwrap_or_default(),
@@ -590,6 +671,30 @@ impl RpcSolPubSubInternal for RpcSolPubSubImpl {
         self.unsubscribe(id)
     }
 
+    fn sysvar_subscribe(
+        &self,
+        sysvar: RpcSysvarSubscribeFilter,
+        config: Option<RpcSysvarSubscribeConfig>,
+    ) -> Result<SubscriptionId> {
+        let pubkey = match sysvar {
+            RpcSysvarSubscribeFilter::Clock => sysvar::clock::id(),
+            RpcSysvarSubscribeFilter::EpochSchedule => sysvar::epoch_schedule::id(),
+            RpcSysvarSubscribeFilter::StakeHistory => sysvar::stake_history::id(),
+            RpcSysvarSubscribeFilter::EpochRewards => sysvar::epoch_rewards::id(),
+        };
+        let params = AccountSubscriptionParams {
+            pubkey,
+            commitment: config.and_then(|c| c.commitment).unwrap_or_default(),
+            data_slice: None,
+            encoding: UiAccountEncoding::JsonParsed,
+        };
+        self.subscribe(SubscriptionParams::Sysvar(params))
+    }
+
+    fn sysvar_unsubscribe(&self, id: SubscriptionId) -> Result<bool> {
+        self.unsubscribe(id)
+    }
+
     fn get_version(&self) -> Result<RpcVersionInfo> {
         let version = solana_version::Version::default();
         Ok(RpcVersionInfo {
@@ -1082,6 +1187,83 @@ mod tests {
         );
     }
 
+    #[test]
+    #[serial]
+    fn test_sysvar_subscribe() {
+        let GenesisConfigInfo { genesis_config, .. } = create_genesis_config(10_000);
+        let bank = Bank::new_for_tests(&genesis_config);
+        let bank_forks = BankForks::new_rw_arc(bank);
+        let bank0 = bank_forks.read().unwrap().get(0).unwrap();
+        let bank1 = Bank::new_from_parent(bank0, &Pubkey::default(), 1);
+        bank_forks.write().unwrap().insert(bank1);
+        let max_complete_transaction_status_slot = Arc::new(AtomicU64::default());
+        let max_complete_rewards_slot = Arc::new(AtomicU64::default());
+        let rpc_subscriptions = Arc::new(RpcSubscriptions::new_for_tests(
+            Arc::new(AtomicBool::new(false)),
+            max_complete_transaction_status_slot,
+            max_complete_rewards_slot,
+            bank_forks.clone(),
+            Arc::new(RwLock::new(BlockCommitmentCache::new_for_tests_with_slots(
+                1, 1,
+            ))),
+            OptimisticallyConfirmedBank::locked_from_bank_forks_root(&bank_forks),
+        ));
+
+        let (rpc, mut receiver) = rpc_pubsub_service::test_connection(&rpc_subscriptions);
+
+        rpc.sysvar_subscribe(
+            RpcSysvarSubscribeFilter::Clock,
+            Some(RpcSysvarSubscribeConfig {
+                commitment: Some(CommitmentConfig::processed()),
+            }),
+        )
+        .unwrap();
+        rpc.block_until_processed(&rpc_subscriptions);
+
+        // The clock sysvar is rewritten by every new bank
+        let bank1 = bank_forks.read().unwrap().get(1).unwrap();
+        let bank2 = Bank::new_from_parent(bank1, &Pubkey::default(), 2);
+        bank_forks.write().unwrap().insert(bank2);
+        rpc_subscriptions.notify_subscribers(CommitmentSlots {
+            slot: 2,
+            ..CommitmentSlots::default()
+        });
+
+        let account = bank_forks
+            .read()
+            .unwrap()
+            .get(2)
+            .unwrap()
+            .get_account(&sysvar::clock::id())
+            .unwrap();
+        let expected_data =
+            parse_account_data(&sysvar::clock::id(), &sysvar::id(), account.data(), None).unwrap();
+        let expected = json!({
+           "jsonrpc": "2.0",
+           "method": "sysvarNotification",
+           "params": {
+               "result": {
+                   "context": { "slot": 2 },
+                   "value": {
+                       "owner": sysvar::id().to_string(),
+                       "lamports": account.lamports(),
+                       "data": expected_data,
+                       "executable": false,
+                       "rentEpoch": account.rent_epoch(),
+                       "space": account.data().len(),
+                   },
+               },
+               "subscription": 0,
+           }
+        });
+
+        let response = receiver.recv();
+        assert_eq!(
+            expected,
+            serde_json::from_str::<serde_json::Value>(&response).unwrap(),
+        );
+    }
+
     #[test]
     #[serial]
     fn test_account_unsubscribe() {
@@ -208,7 +208,7 @@ fn increment_sent_notification_stats(
     stats: &Arc<SentNotificationStats>,
 ) {
     match params {
-        SubscriptionParams::Account(_) => {
+        SubscriptionParams::Account(_) | SubscriptionParams::Sysvar(_) => {
             stats.num_account.fetch_add(1, Ordering::Relaxed);
         }
         SubscriptionParams::Logs(_) => {
@@ -36,13 +36,17 @@ use {
         snapshot_utils,
     },
     solana_sdk::{
-        exit::Exit, genesis_config::DEFAULT_GENESIS_DOWNLOAD_PATH, hash::Hash,
+        exit::Exit,
+        genesis_config::DEFAULT_GENESIS_DOWNLOAD_PATH,
+        hash::{Hash, Hasher},
         native_token::lamports_to_sol,
     },
     solana_send_transaction_service::send_transaction_service::{self, SendTransactionService},
     solana_storage_bigtable::CredentialType,
     std::{
+        io::{Read, Seek, SeekFrom},
         net::SocketAddr,
+        ops::Range,
         path::{Path, PathBuf},
         sync::{
             atomic::{AtomicBool, AtomicU64, Ordering},
@@ -50,12 +54,20 @@ use {
         },
         thread::{self, Builder, JoinHandle},
     },
+    tokio::io::{AsyncReadExt, AsyncSeekExt},
     tokio_util::codec::{BytesCodec, FramedRead},
 };
 
 const FULL_SNAPSHOT_REQUEST_PATH: &str = "/snapshot.tar.bz2";
 const INCREMENTAL_SNAPSHOT_REQUEST_PATH: &str = "/incremental-snapshot.tar.bz2";
 const LARGEST_ACCOUNTS_CACHE_DURATION: u64 = 60 * 60 * 2;
+/// Largest chunk of a file served in response to a single `Range` request. The chunk is read
+/// once to hash it before its body is streamed, so this bounds the work of a single request.
+const MAX_FILE_GET_CHUNK_SIZE: u64 = 64 * 1024 * 1024;
+/// Size of the buffer chunks are hashed through
+const CHUNK_HASH_BUFFER_SIZE: usize = 1024 * 1024;
+/// Response header carrying the hash of the bytes returned by a `Range` request
+const CHUNK_HASH_HEADER: &str = "x-solana-chunk-hash";
 
 pub struct JsonRpcService {
     thread_hdl: JoinHandle<()>,
@@ -121,6 +133,33 @@ impl RpcRequestMiddleware {
             .unwrap()
     }
 
+    fn range_not_satisfiable(file_length: u64) -> hyper::Response<hyper::Body> {
+        hyper::Response::builder()
+            .status(hyper::StatusCode::RANGE_NOT_SATISFIABLE)
+            .header(
+                hyper::header::CONTENT_RANGE,
+                format!("bytes */{file_length}"),
+            )
+            .body(hyper::Body::empty())
+            .unwrap()
+    }
+
+    /// Parses a `Range` request header of the form `bytes=<start>-[<end>]` into the half-open
+    /// range of bytes to serve, capped at MAX_FILE_GET_CHUNK_SIZE. Returns None if the header is
+    /// malformed or cannot be satisfied by a file of `file_length` bytes.
+    fn parse_range(range: &str, file_length: u64) -> Option<Range<u64>> {
+        let (start, end) = range.trim().strip_prefix("bytes=")?.split_once('-')?;
+        let start = start.trim().parse::<u64>().ok()?;
+        let end = match end.trim() {
+            "" => file_length,
+            end => end.parse::<u64>().ok()?.checked_add(1)?.min(file_length),
+        };
+        if start >= end {
+            return None;
+        }
+        Some(start..end.min(start.saturating_add(MAX_FILE_GET_CHUNK_SIZE)))
+    }
+
     fn strip_leading_slash(path: &str) -> Option<&str> {
         path.strip_prefix('/')
     }
@@ -188,7 +227,9 @@ impl RpcRequestMiddleware {
         }
     }
 
-    fn process_file_get(&self, path: &str) -> RequestMiddlewareAction {
+    /// Serves the file at `path`. If a `Range` header is given, only the requested chunk is
+    /// returned along with its hash, allowing interrupted downloads to resume from an offset.
+    fn process_file_get(&self, path: &str, range: Option<&str>) -> RequestMiddlewareAction {
         let filename = {
             let stem = Self::strip_leading_slash(path).expect("path already verified");
             match path {
@@ -203,35 +244,117 @@ impl RpcRequestMiddleware {
             }
         };
 
-        let file_length = std::fs::metadata(&filename)
-            .map(|m| m.len())
-            .unwrap_or(0)
-            .to_string();
-        info!("get {} -> {:?} ({} bytes)", path, filename, file_length);
+        let file_length = match std::fs::metadata(&filename) {
+            Ok(metadata) => metadata.len(),
+            // A missing file is not found, whichever range is requested
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                info!("get {path} -> {filename:?}: not found");
+                return Self::not_found().into();
+            }
+            Err(_) => 0,
+        };
+        let range = match range.map(|range| (range, Self::parse_range(range, file_length))) {
+            None => None,
+            Some((_, Some(range))) => Some(range),
+            Some((range, None)) => {
+                info!("get {path} -> {filename:?} ({file_length} bytes): bad range {range}");
+                return Self::range_not_satisfiable(file_length).into();
+            }
+        };
+        info!(
+            "get {} -> {:?} ({} bytes, range {:?})",
+            path, filename, file_length, range
+        );
         RequestMiddlewareAction::Respond {
             should_validate_hosts: true,
-            response: Box::pin(async {
+            response: Box::pin(async move {
                 match Self::open_no_follow(filename).await {
                     Err(err) => Ok(if err.kind() == std::io::ErrorKind::NotFound {
                         Self::not_found()
                     } else {
                         Self::internal_server_error()
                     }),
-                    Ok(file) => {
-                        let stream =
-                            FramedRead::new(file, BytesCodec::new()).map_ok(|b| b.freeze());
-                        let body = hyper::Body::wrap_stream(stream);
-
-                        Ok(hyper::Response::builder()
-                            .header(hyper::header::CONTENT_LENGTH, file_length)
-                            .body(body)
-                            .unwrap())
-                    }
+                    Ok(file) => Ok(match range {
+                        None => {
+                            let stream =
+                                FramedRead::new(file, BytesCodec::new()).map_ok(|b| b.freeze());
+                            let body = hyper::Body::wrap_stream(stream);
+
+                            hyper::Response::builder()
+                                .header(hyper::header::CONTENT_LENGTH, file_length)
+                                .header(hyper::header::ACCEPT_RANGES, "bytes")
+                                .body(body)
+                                .unwrap()
+                        }
+                        Some(range) => Self::file_chunk_response(file, range.clone(), file_length)
+                            .await
+                            .unwrap_or_else(|err| {
+                                warn!("failed to read file chunk {range:?}: {err}");
+                                Self::internal_server_error()
+                            }),
+                    }),
                 }
             }),
         }
     }
 
+    /// Responds with the `range` of `file`, streaming the chunk after hashing it off the async
+    /// runtime, since its hash is sent ahead of the body
+    async fn file_chunk_response(
+        file: tokio::fs::File,
+        range: Range<u64>,
+        file_length: u64,
+    ) -> std::io::Result<hyper::Response<hyper::Body>> {
+        let chunk_length = range.end - range.start;
+        let file = file.into_std().await;
+        let (file, chunk_hash) = {
+            let range = range.clone();
+            tokio::task::spawn_blocking(move || Self::hash_file_chunk(file, range))
+                .await
+                .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))??
+        };
+        let mut file = tokio::fs::File::from_std(file);
+        file.seek(SeekFrom::Start(range.start)).await?;
+        let stream =
+            FramedRead::new(file.take(chunk_length), BytesCodec::new()).map_ok(|b| b.freeze());
+
+        Ok(hyper::Response::builder()
+            .status(hyper::StatusCode::PARTIAL_CONTENT)
+            .header(hyper::header::CONTENT_LENGTH, chunk_length)
+            .header(
+                hyper::header::CONTENT_RANGE,
+                format!("bytes {}-{}/{file_length}", range.start, range.end - 1),
+            )
+            .header(hyper::header::ACCEPT_RANGES, "bytes")
+            .header(CHUNK_HASH_HEADER, chunk_hash.to_string())
+            .body(hyper::Body::wrap_stream(stream))
+            .unwrap())
+    }
+
+    /// Hashes the `range` of `file`, returning the file for the chunk to be streamed from
+    fn hash_file_chunk(
+        mut file: std::fs::File,
+        range: Range<u64>,
+    ) -> std::io::Result<(std::fs::File, Hash)> {
+        file.seek(SeekFrom::Start(range.start))?;
+        let mut chunk = (&mut file).take(range.end - range.start);
+        let mut hasher = Hasher::default();
+        let mut buffer = vec![0; CHUNK_HASH_BUFFER_SIZE];
+        let mut hashed_length = 0;
+        loop {
+            let length = chunk.read(&mut buffer)?;
+            if length == 0 {
+                break;
+            }
+            hasher.hash(&buffer[..length]);
+            hashed_length += length as u64;
+        }
+        if hashed_length != range.end - range.start {
+            return Err(std::io::ErrorKind::UnexpectedEof.into());
+        }
+        Ok((file, hasher.result()))
+    }
+
     fn health_check(&self) -> &'static str {
         let response = match self.health.check() {
             RpcHealthStatus::Ok => "ok",
@@ -298,7 +421,11 @@ impl RequestMiddleware for RpcRequestMiddleware {
                 .unwrap()
                 .into()
         } else if self.is_file_get_path(request.uri().path()) {
-            self.process_file_get(request.uri().path())
+            let range = request
+                .headers()
+                .get(hyper::header::RANGE)
+                .and_then(|range| range.to_str().ok());
+            self.process_file_get(request.uri().path(), range)
         } else if request.uri().path() == "/health" {
             hyper::Response::builder()
                 .status(hyper::StatusCode::OK)
@@ -845,7 +972,7 @@ mod tests {
         );
 
         // File does not exist => request should fail.
-        let action = rrm.process_file_get(DEFAULT_GENESIS_DOWNLOAD_PATH);
+        let action = rrm.process_file_get(DEFAULT_GENESIS_DOWNLOAD_PATH, None);
         if let RequestMiddlewareAction::Respond { response, .. } = action {
             let response = runtime.block_on(response);
             let response = response.unwrap();
@@ -860,7 +987,7 @@ mod tests {
         }
 
         // Normal file exist => request should succeed.
-        let action = rrm.process_file_get(DEFAULT_GENESIS_DOWNLOAD_PATH);
+        let action = rrm.process_file_get(DEFAULT_GENESIS_DOWNLOAD_PATH, None);
         if let RequestMiddlewareAction::Respond { response, .. } = action {
             let response = runtime.block_on(response);
             let response = response.unwrap();
@@ -879,7 +1006,7 @@ mod tests {
             symlink::symlink_file("wrong", &genesis_path).unwrap();
 
             // File is a symbolic link => request should fail.
-            let action = rrm.process_file_get(DEFAULT_GENESIS_DOWNLOAD_PATH);
+            let action = rrm.process_file_get(DEFAULT_GENESIS_DOWNLOAD_PATH, None);
             if let RequestMiddlewareAction::Respond { response, .. } = action {
                 let response = runtime.block_on(response);
                 let response = response.unwrap();
@@ -889,4 +1016,106 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_parse_range() {
+        assert_eq!(
+            RpcRequestMiddleware::parse_range("bytes=0-", 10),
+            Some(0..10)
+        );
+        assert_eq!(
+            RpcRequestMiddleware::parse_range("bytes=4-", 10),
+            Some(4..10)
+        );
+        assert_eq!(
+            RpcRequestMiddleware::parse_range("bytes=4-5", 10),
+            Some(4..6)
+        );
+        assert_eq!(
+            RpcRequestMiddleware::parse_range("bytes=4-100", 10),
+            Some(4..10)
+        );
+        assert_eq!(
+            RpcRequestMiddleware::parse_range("bytes=0-", u64::MAX),
+            Some(0..MAX_FILE_GET_CHUNK_SIZE)
+        );
+        assert_eq!(RpcRequestMiddleware::parse_range("bytes=10-", 10), None);
+        assert_eq!(RpcRequestMiddleware::parse_range("bytes=5-4", 10), None);
+        assert_eq!(RpcRequestMiddleware::parse_range("bytes=-4", 10), None);
+        assert_eq!(RpcRequestMiddleware::parse_range("bytes=0-1,4-5", 10), None);
+        assert_eq!(RpcRequestMiddleware::parse_range("items=0-", 10), None);
+    }
+
+    #[test]
+    fn test_process_file_get_range() {
+        let runtime = Runtime::new().unwrap();
+
+        let ledger_path = get_tmp_ledger_path_auto_delete!();
+        let blockstore = Arc::new(Blockstore::open(ledger_path.path()).unwrap());
+        let genesis_path = ledger_path.path().join(DEFAULT_GENESIS_ARCHIVE);
+        let bank_forks = create_bank_forks();
+        let optimistically_confirmed_bank =
+            OptimisticallyConfirmedBank::locked_from_bank_forks_root(&bank_forks);
+        let rrm = RpcRequestMiddleware::new(
+            ledger_path.path().to_path_buf(),
+            None,
+            bank_forks,
+            RpcHealth::stub(optimistically_confirmed_bank, blockstore),
+        );
+
+        let get = |range| {
+            let action = rrm.process_file_get(DEFAULT_GENESIS_DOWNLOAD_PATH, Some(range));
+            if let RequestMiddlewareAction::Respond { response, .. } = action {
+                runtime.block_on(response).unwrap()
+            } else {
+                panic!("Unexpected RequestMiddlewareAction variant");
+            }
+        };
+
+        // File does not exist => not found, whichever range is requested
+        for range in ["bytes=0-", "bytes=4-", "bytes=10-"] {
+            assert_eq!(get(range).status(), hyper::StatusCode::NOT_FOUND);
+        }
+
+        let contents = b"0123456789";
+        {
+            let mut file = std::fs::File::create(genesis_path).unwrap();
+            file.write_all(contents).unwrap();
+        }
+
+        // Resume from an offset => only the tail of the file is returned, with its hash
+        let response = get("bytes=4-");
+        assert_eq!(response.status(), hyper::StatusCode::PARTIAL_CONTENT);
+        let headers = response.headers();
+        assert_eq!(headers[hyper::header::CONTENT_RANGE], "bytes 4-9/10");
+        assert_eq!(headers[hyper::header::CONTENT_LENGTH], "6");
+        assert_eq!(
+            headers[CHUNK_HASH_HEADER],
+            solana_sdk::hash::hash(&contents[4..]).to_string().as_str()
+        );
+        let body = runtime
+            .block_on(hyper::body::to_bytes(response.into_body()))
+            .unwrap();
+        assert_eq!(&body[..], &contents[4..]);
+
+        // Bounded range
+        let response = get("bytes=2-3");
+        assert_eq!(response.status(), hyper::StatusCode::PARTIAL_CONTENT);
+        assert_eq!(
+            response.headers()[hyper::header::CONTENT_RANGE],
+            "bytes 2-3/10"
+        );
+        let body = runtime
+            .block_on(hyper::body::to_bytes(response.into_body()))
+            .unwrap();
+        assert_eq!(&body[..], &contents[2..4]);
+
+        // Offset past the end of the file
+        let response = get("bytes=10-");
+        assert_eq!(response.status(), hyper::StatusCode::RANGE_NOT_SATISFIABLE);
+        assert_eq!(
+            response.headers()[hyper::header::CONTENT_RANGE],
+            "bytes */10"
+        );
+    }
 }
@@ -11,7 +11,9 @@ use {
     solana_sdk::{
         clock::Slot, commitment_config::CommitmentConfig, pubkey::Pubkey, signature::Signature,
     },
-    solana_transaction_status::{TransactionDetails, UiTransactionEncoding},
+    solana_transaction_status::{
+        TransactionDetails, UiTransactionEncoding, VersionedTransactionWithStatusMeta,
+    },
     std::{
         collections::hash_map::{Entry, HashMap},
         fmt,
@@ -50,6 +52,7 @@ pub enum SubscriptionParams {
     SlotsUpdates,
     Root,
     Vote,
+    Sysvar(AccountSubscriptionParams),
 }
 
 impl SubscriptionParams {
@@ -64,12 +67,15 @@ impl SubscriptionParams {
             SubscriptionParams::Block(_) => "blockNotification",
             SubscriptionParams::Root => "rootNotification",
             SubscriptionParams::Vote => "voteNotification",
+            SubscriptionParams::Sysvar(_) => "sysvarNotification",
         }
     }
 
     fn commitment(&self) -> Option<CommitmentConfig> {
         match self {
-            SubscriptionParams::Account(params) => Some(params.commitment),
+            SubscriptionParams::Account(params) | SubscriptionParams::Sysvar(params) => {
+                Some(params.commitment)
+            }
             SubscriptionParams::Logs(params) => Some(params.commitment),
             SubscriptionParams::Program(params) => Some(params.commitment),
             SubscriptionParams::Signature(params) => Some(params.commitment),
@@ -83,7 +89,9 @@ impl SubscriptionParams {
 
     fn is_commitment_watcher(&self) -> bool {
         let commitment = match self {
-            SubscriptionParams::Account(params) => &params.commitment,
+            SubscriptionParams::Account(params) | SubscriptionParams::Sysvar(params) => {
+                &params.commitment
+            }
             SubscriptionParams::Block(params) => &params.commitment,
             SubscriptionParams::Logs(params) => &params.commitment,
             SubscriptionParams::Program(params) => &params.commitment,
@@ -98,7 +106,9 @@ impl SubscriptionParams {
 
     fn is_gossip_watcher(&self) -> bool {
         let commitment = match self {
-            SubscriptionParams::Account(params) => &params.commitment,
+            SubscriptionParams::Account(params) | SubscriptionParams::Sysvar(params) => {
+                &params.commitment
+            }
             SubscriptionParams::Block(params) => &params.commitment,
             SubscriptionParams::Logs(params) => &params.commitment,
             SubscriptionParams::Program(params) => &params.commitment,
@@ -144,6 +154,50 @@ pub struct BlockSubscriptionParams {
 pub enum BlockSubscriptionKind {
     All,
     MentionsAccountOrProgram(Pubkey),
+    Matches(BlockTransactionFilter),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum BlockTransactionFilter {
+    MentionsAnyAddress(Vec<Pubkey>),
+    ExcludesAddresses(Vec<Pubkey>),
+    InvokesAnyProgram(Vec<Pubkey>),
+    ExcludesPrograms(Vec<Pubkey>),
+    And(Vec<BlockTransactionFilter>),
+    Or(Vec<BlockTransactionFilter>),
+}
+
+impl BlockTransactionFilter {
+    pub fn matches(&self, transaction: &VersionedTransactionWithStatusMeta) -> bool {
+        let mentions =
+            |address: &Pubkey| transaction.account_keys().iter().any(|key| key == address);
+        match self {
+            Self::MentionsAnyAddress(addresses) => addresses.iter().any(mentions),
+            Self::ExcludesAddresses(addresses) => !addresses.iter().any(mentions),
+            Self::InvokesAnyProgram(program_ids) => program_ids
+                .iter()
+                .any(|program_id| transaction.invokes_program(program_id)),
+            Self::ExcludesPrograms(program_ids) => !program_ids
+                .iter()
+                .any(|program_id| transaction.invokes_program(program_id)),
+            Self::And(filters) => filters.iter().all(|filter| filter.matches(transaction)),
+            Self::Or(filters) => filters.iter().any(|filter| filter.matches(transaction)),
+        }
+    }
+
+    /// The number of addresses and program ids in the filter
+    pub fn num_pubkeys(&self) -> usize {
+        match self {
+            Self::MentionsAnyAddress(pubkeys)
+            | Self::ExcludesAddresses(pubkeys)
+            | Self::InvokesAnyProgram(pubkeys)
+            | Self::ExcludesPrograms(pubkeys) => pubkeys.len(),
+            Self::And(filters) | Self::Or(filters) => filters
+                .iter()
+                .map(BlockTransactionFilter::num_pubkeys)
+                .sum(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -39,7 +39,8 @@ use {
         transaction,
     },
     solana_transaction_status::{
-        BlockEncodingOptions, ConfirmedBlock, EncodeError, VersionedConfirmedBlock,
+        structured_logs::extract_structured_logs, BlockEncodingOptions, ConfirmedBlock,
+        EncodeError, VersionedConfirmedBlock,
     },
     solana_vote::vote_transaction::VoteTransaction,
     std::{
@@ -327,19 +328,22 @@ fn filter_block_result_txs(
     last_modified_slot: Slot,
     params: &BlockSubscriptionParams,
 ) -> Result<Option<RpcBlockUpdate>, RpcBlockUpdateError> {
-    block.transactions = match params.kind {
+    block.transactions = match &params.kind {
         BlockSubscriptionKind::All => block.transactions,
         BlockSubscriptionKind::MentionsAccountOrProgram(pk) => block
             .transactions
             .into_iter()
-            .filter(|tx| tx.account_keys().iter().any(|key| key == &pk))
+            .filter(|tx| tx.account_keys().iter().any(|key| key == pk))
+            .collect(),
+        BlockSubscriptionKind::Matches(filter) => block
+            .transactions
+            .into_iter()
+            .filter(|tx| filter.matches(tx))
             .collect(),
     };
 
-    if block.transactions.is_empty() {
-        if let BlockSubscriptionKind::MentionsAccountOrProgram(_) = params.kind {
-            return Ok(None);
-        }
+    if block.transactions.is_empty() && params.kind != BlockSubscriptionKind::All {
+        return Ok(None);
     }
 
     let block = ConfirmedBlock::from(block)
@@ -444,6 +448,7 @@ fn filter_logs_results(
     let responses = logs.into_iter().flatten().map(|log| RpcLogsResponse {
         signature: log.signature.to_string(),
         err: log.result.err(),
+        structured_logs: extract_structured_logs(&log.log_messages),
         logs: log.log_messages,
     });
     (responses, last_notified_slot)
@@ -456,7 +461,7 @@ fn initial_last_notified_slot(
     optimistically_confirmed_bank: &RwLock<OptimisticallyConfirmedBank>,
 ) -> Option<Slot> {
     match params {
-        SubscriptionParams::Account(params) => {
+        SubscriptionParams::Account(params) | SubscriptionParams::Sysvar(params) => {
             let slot = if params.commitment.is_finalized() {
                 block_commitment_cache
                     .read()
@@ -971,7 +976,7 @@ impl RpcSubscriptions {
                 None
             };
             match subscription.params() {
-                SubscriptionParams::Account(params) => {
+                SubscriptionParams::Account(params) | SubscriptionParams::Sysvar(params) => {
                     num_accounts_found.fetch_add(1, Ordering::Relaxed);
                     if let Some(slot) = slot {
                         let notified = check_commitment_and_notify(
@@ -1255,15 +1260,16 @@ pub(crate) mod tests {
                 BankNotification, OptimisticallyConfirmedBank, OptimisticallyConfirmedBankTracker,
             },
             rpc::{create_test_transaction_entries, populate_blockstore_for_tests},
-            rpc_pubsub::RpcSolPubSubInternal,
+            rpc_pubsub::{RpcSolPubSubInternal, MAX_BLOCK_SUBSCRIBE_FILTER_PUBKEYS},
             rpc_pubsub_service,
+            rpc_subscription_tracker::BlockTransactionFilter,
         },
         serial_test::serial,
         solana_ledger::get_tmp_ledger_path_auto_delete,
         solana_rpc_client_api::config::{
             RpcAccountInfoConfig, RpcBlockSubscribeConfig, RpcBlockSubscribeFilter,
-            RpcProgramAccountsConfig, RpcSignatureSubscribeConfig, RpcTransactionLogsConfig,
-            RpcTransactionLogsFilter,
+            RpcBlockTransactionFilter, RpcProgramAccountsConfig, RpcSignatureSubscribeConfig,
+            RpcTransactionLogsConfig, RpcTransactionLogsFilter,
         },
         solana_runtime::{
             commitment::BlockCommitment,
@@ -1698,6 +1704,141 @@ pub(crate) mod tests {
             .assert_unsubscribed(&SubscriptionParams::Block(params));
     }
 
+    #[test]
+    #[serial]
+    fn test_check_confirmed_block_subscribe_with_filter() {
+        let exit = Arc::new(AtomicBool::new(false));
+        let GenesisConfigInfo {
+            genesis_config,
+            mint_keypair,
+            ..
+        } = create_genesis_config(10_000);
+        let bank = Bank::new_for_tests(&genesis_config);
+        let rent_exempt_amount = bank.get_minimum_balance_for_rent_exemption(0);
+        let bank_forks = BankForks::new_rw_arc(bank);
+        let optimistically_confirmed_bank =
+            OptimisticallyConfirmedBank::locked_from_bank_forks_root(&bank_forks);
+        let ledger_path = get_tmp_ledger_path_auto_delete!();
+        let blockstore = Blockstore::open(ledger_path.path()).unwrap();
+        let blockstore = Arc::new(blockstore);
+        let max_complete_transaction_status_slot = Arc::new(AtomicU64::default());
+        let max_complete_rewards_slot = Arc::new(AtomicU64::default());
+        let subscriptions = Arc::new(RpcSubscriptions::new_for_tests_with_blockstore(
+            exit,
+            max_complete_transaction_status_slot,
+            max_complete_rewards_slot,
+            blockstore.clone(),
+            bank_forks.clone(),
+            Arc::new(RwLock::new(BlockCommitmentCache::new_for_tests())),
+            optimistically_confirmed_bank,
+        ));
+        let (rpc, mut receiver) = rpc_pubsub_service::test_connection(&subscriptions);
+        let keypair1 = Keypair::new();
+        let filter = RpcBlockSubscribeFilter::Matches(RpcBlockTransactionFilter::And(vec![
+            RpcBlockTransactionFilter::InvokesAnyProgram(vec![system_program::id().to_string()]),
+            RpcBlockTransactionFilter::ExcludesAddresses(vec![keypair1.pubkey().to_string()]),
+        ]));
+        let config = RpcBlockSubscribeConfig {
+            commitment: Some(CommitmentConfig::confirmed()),
+            encoding: Some(UiTransactionEncoding::Json),
+            transaction_details: Some(TransactionDetails::Signatures),
+            show_rewards: None,
+            max_supported_transaction_version: None,
+        };
+        let params = BlockSubscriptionParams {
+            kind: BlockSubscriptionKind::Matches(BlockTransactionFilter::And(vec![
+                BlockTransactionFilter::InvokesAnyProgram(vec![system_program::id()]),
+                BlockTransactionFilter::ExcludesAddresses(vec![keypair1.pubkey()]),
+            ])),
+            commitment: config.commitment.unwrap(),
+            encoding: config.encoding.unwrap(),
+            transaction_details: config.transaction_details.unwrap(),
+            show_rewards: config.show_rewards.unwrap_or_default(),
+            max_supported_transaction_version: config.max_supported_transaction_version,
+        };
+        let sub_id = rpc.block_subscribe(filter, Some(config)).unwrap();
+
+        subscriptions
+            .control
+            .assert_subscribed(&SubscriptionParams::Block(params.clone()));
+
+        let bank = bank_forks.read().unwrap().working_bank();
+        let keypair2 = Keypair::new();
+        let keypair3 = Keypair::new();
+        let max_complete_transaction_status_slot = Arc::new(AtomicU64::new(blockstore.max_root()));
+        bank.transfer(rent_exempt_amount, &mint_keypair, &keypair2.pubkey())
+            .unwrap();
+        populate_blockstore_for_tests(
+            create_test_transaction_entries(
+                vec![&mint_keypair, &keypair1, &keypair2, &keypair3],
+                bank.clone(),
+            )
+            .0,
+            bank,
+            blockstore.clone(),
+            max_complete_transaction_status_slot,
+        );
+
+        let slot = 0;
+        subscriptions.notify_gossip_subscribers(slot);
+        let actual_resp = receiver.recv();
+        let actual_resp = serde_json::from_str::<serde_json::Value>(&actual_resp).unwrap();
+
+        // make sure it filtered out the transaction mentioning keypair1
+        let mut confirmed_block =
+            ConfirmedBlock::from(blockstore.get_complete_block(slot, false).unwrap());
+        confirmed_block.transactions.retain(|tx_with_meta| {
+            !tx_with_meta
+                .account_keys()
+                .iter()
+                .any(|key| key == &keypair1.pubkey())
+        });
+        assert_eq!(confirmed_block.transactions.len(), 1);
+        let block = confirmed_block
+            .encode_with_options(
+                params.encoding,
+                BlockEncodingOptions {
+                    transaction_details: params.transaction_details,
+                    show_rewards: false,
+                    max_supported_transaction_version: None,
+                },
+            )
+            .unwrap();
+        let expected_resp = RpcBlockUpdate {
+            slot,
+            block: Some(block),
+            err: None,
+        };
+        let expected_resp = json!({
+           "jsonrpc": "2.0",
+           "method": "blockNotification",
+           "params": {
+               "result": {
+                   "context": { "slot": slot },
+                   "value": expected_resp,
+               },
+               "subscription": 0,
+           }
+        });
+        assert_eq!(expected_resp, actual_resp);
+
+        rpc.block_unsubscribe(sub_id).unwrap();
+        subscriptions
+            .control
+            .assert_unsubscribed(&SubscriptionParams::Block(params));
+
+        // filters with too many addresses and program ids are rejected
+        let filter = RpcBlockSubscribeFilter::Matches(RpcBlockTransactionFilter::Or(vec![
+            RpcBlockTransactionFilter::MentionsAnyAddress(
+                (0..MAX_BLOCK_SUBSCRIBE_FILTER_PUBKEYS)
+                    .map(|_| Pubkey::new_unique().to_string())
+                    .collect(),
+            ),
+            RpcBlockTransactionFilter::ExcludesPrograms(vec![system_program::id().to_string()]),
+        ]));
+        assert!(rpc.block_subscribe(filter, None).is_err());
+    }
+
     #[test]
     #[serial]
     fn test_check_finalized_block_subscribe() {
@@ -1,5 +1,8 @@
 use {
-    solana_sdk::{clock::Slot, signature::Signature, transaction::SanitizedTransaction},
+    solana_sdk::{
+        clock::Slot, inner_instruction::InstructionComputeUnitsList, signature::Signature,
+        transaction::SanitizedTransaction,
+    },
     solana_transaction_status::TransactionStatusMeta,
     std::sync::Arc,
 };
@@ -11,6 +14,7 @@ pub trait TransactionNotifier {
         transaction_slot_index: usize,
         signature: &Signature,
         transaction_status_meta: &TransactionStatusMeta,
+        instruction_compute_units: Option<&InstructionComputeUnitsList>,
         transaction: &SanitizedTransaction,
     );
 }
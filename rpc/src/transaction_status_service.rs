@@ -8,7 +8,8 @@ use {
         blockstore_processor::{TransactionStatusBatch, TransactionStatusMessage},
     },
     solana_transaction_status::{
-        extract_and_fmt_memos, map_inner_instructions, Reward, TransactionStatusMeta,
+        extract_and_fmt_memos, map_inner_instructions, stake_changes::collect_stake_changes,
+        Reward, TransactionStatusMeta,
     },
     std::{
         sync::{
@@ -71,6 +72,7 @@ impl TransactionStatusService {
                 execution_results,
                 balances,
                 token_balances,
+                stake_delegations,
                 rent_debits,
                 transaction_indexes,
             }) => {
@@ -82,6 +84,8 @@ impl TransactionStatusService {
                     post_balances,
                     pre_token_balances,
                     post_token_balances,
+                    pre_stake_delegations,
+                    post_stake_delegations,
                     rent_debits,
                     transaction_index,
                 ) in izip!(
@@ -91,6 +95,8 @@ impl TransactionStatusService {
                     balances.post_balances,
                     token_balances.pre_token_balances,
                     token_balances.post_token_balances,
+                    stake_delegations.pre_stake_delegations,
+                    stake_delegations.post_stake_delegations,
                     rent_debits,
                     transaction_indexes,
                 ) {
@@ -99,6 +105,7 @@ impl TransactionStatusService {
                             status,
                             log_messages,
                             inner_instructions,
+                            instruction_compute_units,
                             durable_nonce_fee,
                             return_data,
                             executed_units,
@@ -126,6 +133,10 @@ impl TransactionStatusService {
 
                         let pre_token_balances = Some(pre_token_balances);
                         let post_token_balances = Some(post_token_balances);
+                        let stake_changes = Some(collect_stake_changes(
+                            pre_stake_delegations,
+                            post_stake_delegations,
+                        ));
                         let rewards = Some(
                             rent_debits
                                 .into_unordered_rewards_iter()
@@ -152,6 +163,7 @@ impl TransactionStatusService {
                             loaded_addresses,
                             return_data,
                             compute_units_consumed: Some(executed_units),
+                            stake_changes,
                         };
 
                         if let Some(transaction_notifier) = transaction_notifier.as_ref() {
@@ -160,6 +172,7 @@ impl TransactionStatusService {
                                 transaction_index,
                                 transaction.signature(),
                                 &transaction_status_meta,
+                                instruction_compute_units.as_ref(),
                                 &transaction,
                             );
                         }
@@ -218,6 +231,7 @@ pub(crate) mod tests {
             account_utils::StateMut,
             clock::Slot,
             hash::Hash,
+            inner_instruction::{InstructionComputeUnits, InstructionComputeUnitsList},
             instruction::CompiledInstruction,
             message::{LegacyMessage, Message, MessageHeader, SanitizedMessage},
             nonce::{self, state::DurableNonce},
@@ -233,7 +247,9 @@ pub(crate) mod tests {
             },
         },
         solana_transaction_status::{
-            token_balances::TransactionTokenBalancesSet, TransactionStatusMeta,
+            stake_changes::{TransactionStakeDelegation, TransactionStakeDelegationsSet},
+            token_balances::TransactionTokenBalancesSet,
+            StakeDelegation, TransactionStakeChange, TransactionStatusMeta,
             TransactionTokenBalance,
         },
         std::{
@@ -254,7 +270,8 @@ pub(crate) mod tests {
     }
 
     struct TestNotification {
-        _meta: TransactionStatusMeta,
+        meta: TransactionStatusMeta,
+        instruction_compute_units: Option<InstructionComputeUnitsList>,
         transaction: SanitizedTransaction,
     }
 
@@ -277,6 +294,7 @@ pub(crate) mod tests {
             transaction_index: usize,
             signature: &Signature,
             transaction_status_meta: &TransactionStatusMeta,
+            instruction_compute_units: Option<&InstructionComputeUnitsList>,
             transaction: &SanitizedTransaction,
         ) {
             self.notifications.insert(
@@ -286,7 +304,8 @@ pub(crate) mod tests {
                     signature: *signature,
                 },
                 TestNotification {
-                    _meta: transaction_status_meta.clone(),
+                    meta: transaction_status_meta.clone(),
+                    instruction_compute_units: instruction_compute_units.cloned(),
                     transaction: transaction.clone(),
                 },
             );
@@ -361,10 +380,15 @@ pub(crate) mod tests {
             status: Ok(()),
             log_messages: None,
             inner_instructions: None,
+            instruction_compute_units: Some(vec![InstructionComputeUnits {
+                compute_units_consumed: 150,
+                inner_instructions: vec![],
+            }]),
             durable_nonce_fee: Some(DurableNonceFee::from(
                 &NonceFull::from_partial(
                     &rollback_partial,
                     &SanitizedMessage::Legacy(LegacyMessage::new(message)),
+                    0, // fee_payer_index
                     &[(pubkey, nonce_account)],
                     &rent_debits,
                 )
@@ -388,6 +412,7 @@ pub(crate) mod tests {
             ui_token_amount: token_amount_to_ui_amount(42, 2),
             owner: owner.clone(),
             program_id: token_program_id.clone(),
+            extensions: vec![],
         };
 
         let post_token_balance = TransactionTokenBalance {
@@ -396,6 +421,7 @@ pub(crate) mod tests {
             ui_token_amount: token_amount_to_ui_amount(58, 2),
             owner,
             program_id: token_program_id,
+            extensions: vec![],
         };
 
         let token_balances = TransactionTokenBalancesSet {
@@ -403,6 +429,27 @@ pub(crate) mod tests {
             post_token_balances: vec![vec![post_token_balance]],
         };
 
+        let pre_delegation = StakeDelegation {
+            voter: Pubkey::new_unique().to_string(),
+            stake: 1_000,
+            activation_epoch: 1,
+            deactivation_epoch: u64::MAX,
+        };
+        let post_delegation = StakeDelegation {
+            deactivation_epoch: 2,
+            ..pre_delegation.clone()
+        };
+        let stake_delegations = TransactionStakeDelegationsSet {
+            pre_stake_delegations: vec![vec![TransactionStakeDelegation {
+                account_index: 1,
+                delegation: pre_delegation.clone(),
+            }]],
+            post_stake_delegations: vec![vec![TransactionStakeDelegation {
+                account_index: 1,
+                delegation: post_delegation.clone(),
+            }]],
+        };
+
         let slot = bank.slot();
         let signature = *transaction.signature();
         let transaction_index: usize = bank.transaction_count().try_into().unwrap();
@@ -412,6 +459,7 @@ pub(crate) mod tests {
             execution_results: vec![transaction_result],
             balances,
             token_balances,
+            stake_delegations,
             rent_debits: vec![rent_debits],
             transaction_indexes: vec![transaction_index],
         };
@@ -449,5 +497,20 @@ pub(crate) mod tests {
             expected_transaction.signature(),
             result.transaction.signature()
         );
+        assert_eq!(
+            result.instruction_compute_units,
+            Some(vec![InstructionComputeUnits {
+                compute_units_consumed: 150,
+                inner_instructions: vec![],
+            }])
+        );
+        assert_eq!(
+            result.meta.stake_changes,
+            Some(vec![TransactionStakeChange {
+                account_index: 1,
+                pre_delegation: Some(pre_delegation),
+                post_delegation: Some(post_delegation),
+            }])
+        );
     }
 }
@@ -53,8 +53,9 @@ use {
         stake_account::StakeAccount,
         stake_history::StakeHistory,
         stake_weighted_timestamp::{
-            calculate_stake_weighted_timestamp, MaxAllowableDrift,
-            MAX_ALLOWABLE_DRIFT_PERCENTAGE_FAST, MAX_ALLOWABLE_DRIFT_PERCENTAGE_SLOW_V2,
+            calculate_stake_weighted_timestamp, ClockDriftEstimate, MaxAllowableDrift,
+            StakeWeightedTimestamps, MAX_ALLOWABLE_DRIFT_PERCENTAGE_FAST,
+            MAX_ALLOWABLE_DRIFT_PERCENTAGE_SLOW_V2,
         },
         stakes::{InvalidCacheEntryReason, Stakes, StakesCache, StakesEnum},
         status_cache::{SlotDelta, StatusCache},
@@ -76,8 +77,10 @@ use {
             AccountShrinkThreshold, AccountStorageEntry, AccountsDb, AccountsDbConfig,
             CalcAccountsHashDataSource, VerifyAccountsHashAndLamportsConfig,
         },
+        accounts_file::MatchAccountOwnerError,
         accounts_hash::{
-            AccountHash, AccountsHash, CalcAccountsHashConfig, HashStats, IncrementalAccountsHash,
+            AccountHash, AccountsHash, AccountsLtHash, CalcAccountsHashConfig, HashStats,
+            IncrementalAccountsHash,
         },
         accounts_index::{AccountSecondaryIndexes, IndexKey, ScanConfig, ScanResult, ZeroLamport},
         accounts_partition::{self, Partition, PartitionIndex},
@@ -94,12 +97,14 @@ use {
         },
     },
     solana_bpf_loader_program::syscalls::create_program_runtime_environment_v1,
-    solana_cost_model::cost_tracker::CostTracker,
+    solana_cost_model::{compute_cost_model::ComputeCostModel, cost_tracker::CostTracker},
     solana_loader_v4_program::create_program_runtime_environment_v2,
     solana_measure::{measure, measure::Measure, measure_us},
     solana_perf::perf_libs,
     solana_program_runtime::{
-        compute_budget_processor::process_compute_budget_instructions,
+        compute_budget_processor::{
+            get_fee_sponsor_index, get_max_slot, process_compute_budget_instructions,
+        },
         invoke_context::BuiltinFunctionWithContext,
         loaded_programs::{LoadedProgram, LoadedProgramType, LoadedPrograms},
         timings::{ExecuteTimingType, ExecuteTimings},
@@ -128,7 +133,7 @@ use {
         hash::{extend_and_hash, hashv, Hash},
         incinerator,
         inflation::Inflation,
-        inner_instruction::InnerInstructions,
+        inner_instruction::{InnerInstructions, InstructionComputeUnitsList},
         message::{AccountKeys, SanitizedMessage},
         native_loader,
         native_token::LAMPORTS_PER_SOL,
@@ -147,8 +152,12 @@ use {
         slot_hashes::SlotHashes,
         slot_history::{Check, SlotHistory},
         stake::state::Delegation,
+        stake_history::StakeHistoryEntry,
         system_transaction,
-        sysvar::{self, last_restart_slot::LastRestartSlot, Sysvar, SysvarId},
+        sysvar::{
+            self, epoch_stake::EpochStake, last_restart_slot::LastRestartSlot,
+            restart_history::RestartHistory, Sysvar, SysvarId,
+        },
         timing::years_as_slots,
         transaction::{
             self, MessageHash, Result, SanitizedTransaction, Transaction, TransactionError,
@@ -176,7 +185,7 @@ use {
         collections::{HashMap, HashSet},
         convert::TryFrom,
         fmt, mem,
-        ops::{AddAssign, RangeInclusive},
+        ops::{AddAssign, Range, RangeInclusive},
         path::PathBuf,
         slice,
         sync::{
@@ -200,6 +209,7 @@ struct VerifyAccountsHashConfig {
     store_hash_raw_data_for_debug: bool,
 }
 
+mod accounts_lt_hash;
 mod address_lookup_table;
 pub mod bank_hash_details;
 mod builtin_programs;
@@ -315,6 +325,7 @@ pub struct TransactionSimulationResult {
     pub units_consumed: u64,
     pub return_data: Option<TransactionReturnData>,
     pub inner_instructions: Option<Vec<InnerInstructions>>,
+    pub instruction_compute_units: Option<InstructionComputeUnitsList>,
 }
 pub struct TransactionBalancesSet {
     pub pre_balances: TransactionBalances,
@@ -520,6 +531,7 @@ impl PartialEq for Bank {
             stakes_cache,
             epoch_stakes,
             is_delta,
+            accounts_lt_hash,
             // TODO: Confirm if all these fields are intentionally ignored!
             builtin_programs: _,
             runtime_config: _,
@@ -532,6 +544,7 @@ impl PartialEq for Bank {
             transaction_log_collector: _,
             feature_set: _,
             drop_callback: _,
+            missing_account_loader: _,
             freeze_started: _,
             vote_only_bank: _,
             cost_tracker: _,
@@ -576,6 +589,7 @@ impl PartialEq for Bank {
             && *stakes_cache.stakes() == *other.stakes_cache.stakes()
             && epoch_stakes == &other.epoch_stakes
             && is_delta.load(Relaxed) == other.is_delta.load(Relaxed)
+            && *accounts_lt_hash.lock().unwrap() == *other.accounts_lt_hash.lock().unwrap()
     }
 }
 
@@ -610,6 +624,27 @@ impl AbiExample for OptionalDropCallback {
     }
 }
 
+/// Loads accounts which are missing from the bank when transactions load
+/// them, e.g. from a remote cluster
+pub trait MissingAccountLoader: fmt::Debug {
+    /// Called on the transaction processing path, so it should not block for long
+    fn load_missing_account(&self, pubkey: &Pubkey) -> Option<AccountSharedData>;
+
+    /// Called once the account loaded for `pubkey` is stored in a bank, so the
+    /// loader does not need to load it again
+    fn missing_account_stored(&self, _pubkey: &Pubkey) {}
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct OptionalMissingAccountLoader(Option<Arc<dyn MissingAccountLoader + Send + Sync>>);
+
+#[cfg(RUSTC_WITH_SPECIALIZATION)]
+impl AbiExample for OptionalMissingAccountLoader {
+    fn example() -> Self {
+        Self(None)
+    }
+}
+
 #[derive(AbiExample, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub(crate) struct StartBlockHeightAndRewards {
     /// the block height of the slot at which rewards distribution began
@@ -776,6 +811,9 @@ pub struct Bank {
     /// callback function only to be called when dropping and should only be called once
     pub drop_callback: RwLock<OptionalDropCallback>,
 
+    /// loads the accounts which are missing from the bank when transactions load them
+    missing_account_loader: RwLock<OptionalMissingAccountLoader>,
+
     pub freeze_started: AtomicBool,
 
     vote_only_bank: bool,
@@ -793,6 +831,10 @@ pub struct Bank {
     /// the account hash of the accounts that would have been rewritten as bank hash expects.
     skipped_rewrites: Mutex<HashMap<Pubkey, AccountHash>>,
 
+    /// The lattice hash of all accounts, updated with the accounts written in
+    /// this slot when freezing while the `accounts_lt_hash` feature is active
+    accounts_lt_hash: Mutex<AccountsLtHash>,
+
     /// Transaction fee structure
     pub fee_structure: FeeStructure,
 
@@ -927,6 +969,7 @@ impl Bank {
     fn default_with_accounts(accounts: Accounts) -> Self {
         let mut bank = Self {
             skipped_rewrites: Mutex::default(),
+            accounts_lt_hash: Mutex::new(AccountsLtHash::identity()),
             incremental_snapshot_persistence: None,
             rc: BankRc::new(accounts, Slot::default()),
             status_cache: Arc::<RwLock<BankStatusCache>>::default(),
@@ -976,6 +1019,7 @@ impl Bank {
             transaction_log_collector: Arc::<RwLock<TransactionLogCollector>>::default(),
             feature_set: Arc::<FeatureSet>::default(),
             drop_callback: RwLock::new(OptionalDropCallback(None)),
+            missing_account_loader: RwLock::<OptionalMissingAccountLoader>::default(),
             freeze_started: AtomicBool::default(),
             vote_only_bank: false,
             cost_tracker: RwLock::<CostTracker>::default(),
@@ -1064,6 +1108,8 @@ impl Bank {
         bank.update_epoch_schedule();
         bank.update_recent_blockhashes();
         bank.update_last_restart_slot();
+        bank.update_restart_history();
+        bank.update_epoch_stake();
         bank.fill_missing_sysvar_cache_entries();
         bank
     }
@@ -1172,11 +1218,46 @@ impl Bank {
         }
     }
 
+    /// If this bank is in the reward phase, returns the block height at which
+    /// rewards distribution began and the number of partitions (one per block)
+    /// rewards are distributed over
+    pub fn epoch_rewards_partitions(&self) -> Option<(u64, u64)> {
+        match &self.epoch_reward_status {
+            EpochRewardStatus::Active(status) => Some((
+                status.start_block_height,
+                status.stake_rewards_by_partition.len() as u64,
+            )),
+            EpochRewardStatus::Inactive => None,
+        }
+    }
+
     /// For testing only
     pub fn force_reward_interval_end_for_tests(&mut self) {
         self.epoch_reward_status = EpochRewardStatus::Inactive;
     }
 
+    /// For testing only: records stake history entries for `epochs`, which
+    /// were skipped over by warping, in the stakes cache and the sysvar.
+    /// `stake_history_entry` is called with each epoch and the entry
+    /// calculated as if the epoch had elapsed without any change to the
+    /// delegations, and returns the entry to record.
+    pub fn synthesize_stake_history_for_tests<F>(
+        &self,
+        epochs: Range<Epoch>,
+        stake_history_entry: F,
+    ) where
+        F: FnMut(Epoch, StakeHistoryEntry) -> StakeHistoryEntry,
+    {
+        self.stakes_cache.synthesize_stake_history(
+            epochs,
+            self.new_warmup_cooldown_rate_epoch(),
+            stake_history_entry,
+        );
+        self.update_stake_history(None);
+        self.reset_sysvar_cache();
+        self.fill_missing_sysvar_cache_entries();
+    }
+
     fn _new_from_parent(
         parent: Arc<Bank>,
         collector_id: &Pubkey,
@@ -1236,6 +1317,7 @@ impl Bank {
         let accounts_data_size_initial = parent.load_accounts_data_size();
         let mut new = Self {
             skipped_rewrites: Mutex::default(),
+            accounts_lt_hash: Mutex::new(parent.accounts_lt_hash()),
             incremental_snapshot_persistence: None,
             rc,
             status_cache,
@@ -1298,6 +1380,9 @@ impl Bank {
                     .as_ref()
                     .map(|drop_callback| drop_callback.clone_box()),
             )),
+            missing_account_loader: RwLock::new(
+                parent.missing_account_loader.read().unwrap().clone(),
+            ),
             freeze_started: AtomicBool::new(false),
             cost_tracker: RwLock::new(CostTracker::default()),
             accounts_data_size_initial,
@@ -1363,6 +1448,10 @@ impl Bank {
                     drop(loaded_programs_cache);
                     let recompiled = new.load_program(&key, false, Some(program_to_recompile));
                     let mut loaded_programs_cache = new.loaded_programs_cache.write().unwrap();
+                    loaded_programs_cache
+                        .stats
+                        .recompilations
+                        .fetch_add(1, Relaxed);
                     loaded_programs_cache.assign_program(key, recompiled);
                 }
             } else if new.epoch() != loaded_programs_cache.latest_root_epoch
@@ -1412,8 +1501,11 @@ impl Bank {
             new.update_stake_history(Some(parent.epoch()));
             new.update_clock(Some(parent.epoch()));
             new.update_fees();
-            new.update_last_restart_slot()
+            new.update_last_restart_slot();
+            new.update_restart_history();
         });
+        // every bank starts out with a new cost tracker
+        new.apply_cost_tracker_limits();
 
         let (_, fill_sysvar_cache_time_us) = measure_us!(new.fill_missing_sysvar_cache_entries());
         time.stop();
@@ -1451,7 +1543,7 @@ impl Bank {
             .stats
             .submit(parent.slot());
 
-        new.loaded_programs_cache.write().unwrap().stats.reset();
+        new.loaded_programs_cache.write().unwrap().reset_stats();
         new
     }
 
@@ -1499,6 +1591,7 @@ impl Bank {
             self.update_epoch_stakes(leader_schedule_epoch),
             "update_epoch_stakes",
         );
+        self.update_epoch_stake();
 
         let mut rewards_metrics = RewardsMetrics::default();
         // After saving a snapshot of stakes, apply stake rewards and commission
@@ -1671,6 +1764,26 @@ impl Bank {
         *self.drop_callback.write().unwrap() = OptionalDropCallback(callback);
     }
 
+    /// Set the loader of the accounts which transactions load but are missing
+    /// from this bank, which is inherited by its child banks. The loaded
+    /// accounts are stored into this bank, unless it is frozen.
+    pub fn set_missing_account_loader(
+        &self,
+        loader: Option<Arc<dyn MissingAccountLoader + Send + Sync>>,
+    ) {
+        *self.missing_account_loader.write().unwrap() = OptionalMissingAccountLoader(loader);
+    }
+
+    fn load_missing_account(&self, pubkey: &Pubkey) -> Option<AccountSharedData> {
+        let loader = self.missing_account_loader.read().unwrap().0.clone()?;
+        let account = loader.load_missing_account(pubkey)?;
+        if !self.freeze_started() {
+            self.store_account_and_update_capitalization(pubkey, &account);
+            loader.missing_account_stored(pubkey);
+        }
+        Some(account)
+    }
+
     pub fn vote_only_bank(&self) -> bool {
         self.vote_only_bank
     }
@@ -1754,6 +1867,7 @@ impl Bank {
         let stakes_accounts_load_duration = now.elapsed();
         let mut bank = Self {
             skipped_rewrites: Mutex::default(),
+            accounts_lt_hash: Mutex::new(AccountsLtHash::identity()),
             incremental_snapshot_persistence: fields.incremental_snapshot_persistence,
             rc: bank_rc,
             status_cache: Arc::<RwLock<BankStatusCache>>::default(),
@@ -1804,6 +1918,7 @@ impl Bank {
             transaction_log_collector: Arc::<RwLock<TransactionLogCollector>>::default(),
             feature_set: Arc::<FeatureSet>::default(),
             drop_callback: RwLock::new(OptionalDropCallback(None)),
+            missing_account_loader: RwLock::<OptionalMissingAccountLoader>::default(),
             freeze_started: AtomicBool::new(fields.hash != Hash::default()),
             vote_only_bank: false,
             cost_tracker: RwLock::new(CostTracker::default()),
@@ -1835,6 +1950,11 @@ impl Bank {
         );
         bank.fill_missing_sysvar_cache_entries();
         bank.rebuild_skipped_rewrites();
+        if bank.is_accounts_lt_hash_enabled() {
+            // the accounts lattice hash is not in the snapshot, so it is
+            // calculated from the accounts loaded from the snapshot
+            bank.initialize_accounts_lt_hash();
+        }
 
         // Sanity assertions between bank snapshot and genesis config
         // Consider removing from serializable bank state
@@ -2023,6 +2143,46 @@ impl Bank {
             .unwrap_or_default()
     }
 
+    /// Returns the distribution of the recent vote timestamps, projected to
+    /// the slot of this bank and weighted by the stake of the current epoch
+    pub fn get_stake_weighted_timestamps(&self) -> Option<StakeWeightedTimestamps> {
+        let epoch = self.epoch_schedule().get_epoch(self.slot());
+        let stakes = self.epoch_vote_accounts(epoch)?;
+        Some(StakeWeightedTimestamps::new(
+            self.recent_vote_timestamps(),
+            stakes,
+            self.slot(),
+            Duration::from_nanos(self.ns_per_slot as u64),
+        ))
+    }
+
+    /// Estimates how far the `unix_timestamp` of the clock sysvar is from the
+    /// recent vote timestamps, to assess its confidence. Returns `None` if no
+    /// staked vote account voted a timestamp in the last epoch.
+    pub fn clock_drift_estimate(&self) -> Option<ClockDriftEstimate> {
+        let timestamps = self.get_stake_weighted_timestamps()?;
+        let median = timestamps.median()?;
+        let clock = self.clock();
+        let epoch_start_slot = self.epoch_schedule().get_first_slot_in_epoch(clock.epoch);
+        let poh_offset = Duration::from_nanos(self.ns_per_slot as u64)
+            .saturating_mul(self.slot().saturating_sub(epoch_start_slot) as u32);
+        let total_stake = self
+            .epoch_stakes(clock.epoch)
+            .map(|epoch_stakes| epoch_stakes.total_stake())
+            .unwrap_or_default();
+        Some(ClockDriftEstimate {
+            unix_timestamp: clock.unix_timestamp,
+            poh_timestamp: clock
+                .epoch_start_timestamp
+                .saturating_add(poh_offset.as_secs() as i64),
+            lower_quartile: timestamps.percentile(25).unwrap_or(median),
+            median,
+            upper_quartile: timestamps.percentile(75).unwrap_or(median),
+            voted_stake: u64::try_from(timestamps.total_stake()).unwrap_or(u64::MAX),
+            total_stake,
+        })
+    }
+
     fn update_clock(&self, parent_epoch: Option<Epoch>) {
         let mut unix_timestamp = self.clock().unix_timestamp;
         // set epoch_start_timestamp to None to warp timestamp
@@ -2124,6 +2284,86 @@ impl Bank {
         }
     }
 
+    /// Records in the restart history sysvar the hard forks at or before this
+    /// bank's slot which are more recent than its last recorded restart
+    fn update_restart_history(&self) {
+        if !self
+            .feature_set
+            .is_active(&feature_set::restart_history_sysvar::id())
+        {
+            return;
+        }
+        // This account may not exist yet if the feature was just activated
+        let current_restart_history: Option<RestartHistory> = self
+            .get_account(&sysvar::restart_history::id())
+            .and_then(|account| from_account(&account));
+        let last_recorded_slot = current_restart_history
+            .as_ref()
+            .and_then(|restart_history| restart_history.last())
+            .map(|restart| restart.slot);
+
+        let restart_slots: Vec<Slot> = {
+            let slot = self.slot;
+            let hard_forks_r = self.hard_forks.read().unwrap();
+            let restart_slots = hard_forks_r
+                .iter()
+                .map(|(hard_fork, _)| *hard_fork)
+                .filter(|hard_fork| *hard_fork <= slot && Some(*hard_fork) > last_recorded_slot);
+            if current_restart_history.is_some() {
+                restart_slots.collect()
+            } else {
+                // The timestamps of the restarts before the activation are not
+                // known, so only the most recent one is recorded
+                restart_slots.last().into_iter().collect()
+            }
+        };
+
+        // Only need to write if a restart was added or the sysvar is missing
+        if current_restart_history.is_none() || !restart_slots.is_empty() {
+            let mut restart_history = current_restart_history.unwrap_or_default();
+            let unix_timestamp = self.clock().unix_timestamp;
+            for restart_slot in restart_slots {
+                restart_history.add(restart_slot, unix_timestamp);
+            }
+            self.update_sysvar_account(&sysvar::restart_history::id(), |account| {
+                create_account(
+                    &restart_history,
+                    self.inherit_specially_retained_account_fields(account),
+                )
+            });
+        }
+    }
+
+    /// Updates the epoch stake sysvar from the stakes the leader schedule of
+    /// the current epoch was computed from
+    fn update_epoch_stake(&self) {
+        if !self
+            .feature_set
+            .is_active(&feature_set::epoch_stake_sysvar::id())
+        {
+            return;
+        }
+        let epoch = self.epoch();
+        let Some(epoch_stakes) = self.epoch_stakes(epoch) else {
+            return;
+        };
+        let epoch_stake = EpochStake::new(
+            epoch,
+            epoch_stakes.total_stake(),
+            epoch_stakes
+                .stakes()
+                .vote_accounts()
+                .delegated_stakes()
+                .map(|(vote_pubkey, stake)| (*vote_pubkey, stake)),
+        );
+        self.update_sysvar_account(&sysvar::epoch_stake::id(), |account| {
+            create_account(
+                &epoch_stake,
+                self.inherit_specially_retained_account_fields(account),
+            )
+        });
+    }
+
     pub fn set_sysvar_for_tests<T>(&self, sysvar: &T)
     where
         T: Sysvar + SysvarId,
@@ -3593,28 +3833,40 @@ impl Bank {
         self.update_recent_blockhashes_locked(&blockhash_queue);
     }
 
-    fn get_timestamp_estimate(
+    /// Returns the last timestamp voted by each vote account, if voted within
+    /// the last epoch
+    fn recent_vote_timestamps(&self) -> Vec<(Pubkey, (Slot, UnixTimestamp))> {
+        let slots_per_epoch = self.epoch_schedule().slots_per_epoch;
+        let vote_accounts = self.vote_accounts();
+        vote_accounts
+            .iter()
+            .filter_map(|(pubkey, (_, account))| {
+                let vote_state = account.vote_state();
+                let vote_state = vote_state.as_ref().ok()?;
+                let slot_delta = self.slot().checked_sub(vote_state.last_timestamp.slot)?;
+                (slot_delta <= slots_per_epoch).then_some({
+                    (
+                        *pubkey,
+                        (
+                            vote_state.last_timestamp.slot,
+                            vote_state.last_timestamp.timestamp,
+                        ),
+                    )
+                })
+            })
+            .collect()
+    }
+
+    /// Estimates the timestamp of this bank from the stake-weighted median of
+    /// the recent vote timestamps, bounded by `max_allowable_drift` from the
+    /// PoH estimate since `epoch_start_timestamp`
+    pub fn get_timestamp_estimate(
         &self,
         max_allowable_drift: MaxAllowableDrift,
         epoch_start_timestamp: Option<(Slot, UnixTimestamp)>,
     ) -> Option<UnixTimestamp> {
         let mut get_timestamp_estimate_time = Measure::start("get_timestamp_estimate");
-        let slots_per_epoch = self.epoch_schedule().slots_per_epoch;
-        let vote_accounts = self.vote_accounts();
-        let recent_timestamps = vote_accounts.iter().filter_map(|(pubkey, (_, account))| {
-            let vote_state = account.vote_state();
-            let vote_state = vote_state.as_ref().ok()?;
-            let slot_delta = self.slot().checked_sub(vote_state.last_timestamp.slot)?;
-            (slot_delta <= slots_per_epoch).then_some({
-                (
-                    *pubkey,
-                    (
-                        vote_state.last_timestamp.slot,
-                        vote_state.last_timestamp.timestamp,
-                    ),
-                )
-            })
-        });
+        let recent_timestamps = self.recent_vote_timestamps();
         let slot_duration = Duration::from_nanos(self.ns_per_slot as u64);
         let epoch = self.epoch_schedule().get_epoch(self.slot());
         let stakes = self.epoch_vote_accounts(epoch)?;
@@ -3669,6 +3921,9 @@ impl Bank {
             self.distribute_rent_fees();
             self.update_slot_history();
             self.run_incinerator();
+            if self.is_accounts_lt_hash_enabled() {
+                self.update_accounts_lt_hash();
+            }
 
             // freeze is a one-way trip, idempotent
             self.freeze_started.store(true, Relaxed);
@@ -4019,6 +4274,18 @@ impl Bank {
         )
     }
 
+    /// Returns the index of the account paying the fees of the message: the
+    /// sponsor designated by a `SetFeePayer` instruction if any, otherwise
+    /// the first signer
+    pub fn get_fee_payer_index(&self, message: &SanitizedMessage) -> Result<usize> {
+        let fee_sponsor_index = get_fee_sponsor_index(
+            message,
+            self.feature_set
+                .is_active(&feature_set::enable_fee_sponsorship::id()),
+        )?;
+        Ok(fee_sponsor_index.unwrap_or(0))
+    }
+
     #[deprecated(
         since = "1.6.11",
         note = "Please use `get_blockhash_last_valid_block_height`"
@@ -4318,14 +4585,16 @@ impl Bank {
 
         let execution_result = execution_results.pop().unwrap();
         let flattened_result = execution_result.flattened_result();
-        let (logs, return_data, inner_instructions) = match execution_result {
-            TransactionExecutionResult::Executed { details, .. } => (
-                details.log_messages,
-                details.return_data,
-                details.inner_instructions,
-            ),
-            TransactionExecutionResult::NotExecuted(_) => (None, None, None),
-        };
+        let (logs, return_data, inner_instructions, instruction_compute_units) =
+            match execution_result {
+                TransactionExecutionResult::Executed { details, .. } => (
+                    details.log_messages,
+                    details.return_data,
+                    details.inner_instructions,
+                    details.instruction_compute_units,
+                ),
+                TransactionExecutionResult::NotExecuted(_) => (None, None, None, None),
+            };
         let logs = logs.unwrap_or_default();
 
         TransactionSimulationResult {
@@ -4335,6 +4604,7 @@ impl Bank {
             units_consumed,
             return_data,
             inner_instructions,
+            instruction_compute_units,
         }
     }
 
@@ -4407,6 +4677,21 @@ impl Bank {
         hash_queue: &BlockhashQueue,
         error_counters: &mut TransactionErrorMetrics,
     ) -> TransactionCheckResult {
+        // The max slot bounds the lifetime of both blockhash and durable nonce
+        // transactions
+        let max_slot = get_max_slot(
+            tx.message().program_instructions_iter(),
+            self.feature_set
+                .is_active(&feature_set::enable_transaction_max_slot::id()),
+        );
+        match max_slot {
+            Ok(Some(max_slot)) if self.slot() > max_slot => {
+                error_counters.max_slot_exceeded += 1;
+                return (Err(TransactionError::MaxSlotExceeded), None, None);
+            }
+            Ok(_) => (),
+            Err(err) => return (Err(err), None, None),
+        }
         let recent_blockhash = tx.message().recent_blockhash();
         if hash_queue.is_hash_valid_for_age(recent_blockhash, max_age) {
             (
@@ -4840,7 +5125,8 @@ impl Bank {
                 // post-load, fee deducted, pre-execute account state
                 // stored
                 if execution_status.is_err() && !is_nonce {
-                    self.withdraw(tx.message().fee_payer(), fee)?;
+                    let fee_payer_index = self.get_fee_payer_index(tx.message())?;
+                    self.withdraw(&tx.message().account_keys()[fee_payer_index], fee)?;
                 }
 
                 fees += fee;
@@ -4901,6 +5187,7 @@ impl Bank {
         let durable_nonce = DurableNonce::from_blockhash(&last_blockhash);
         self.rc.accounts.store_cached(
             self.slot(),
+            &self.ancestors,
             sanitized_txs,
             &execution_results,
             loaded_txs,
@@ -5966,6 +6253,7 @@ impl Bank {
         }
 
         let mut loaded_programs_cache = self.loaded_programs_cache.write().unwrap();
+        loaded_programs_cache.config = self.runtime_config.loaded_programs_config;
         loaded_programs_cache.latest_root_slot = self.slot();
         loaded_programs_cache.latest_root_epoch = self.epoch();
         loaded_programs_cache.environments.program_runtime_v1 = Arc::new(
@@ -6092,6 +6380,27 @@ impl Bank {
         )
     }
 
+    /// Returns at most `limit` of the accounts owned by `program_id` which pass `filter`, with
+    /// pubkeys below `before`, in descending pubkey order
+    pub fn get_filtered_program_accounts_before<F: Fn(&AccountSharedData) -> bool>(
+        &self,
+        program_id: &Pubkey,
+        filter: F,
+        before: Option<&Pubkey>,
+        limit: usize,
+        config: &ScanConfig,
+    ) -> ScanResult<Vec<TransactionAccount>> {
+        self.rc.accounts.load_by_program_with_filter_before(
+            &self.ancestors,
+            self.bank_id,
+            program_id,
+            filter,
+            before,
+            limit,
+            config,
+        )
+    }
+
     pub fn get_filtered_indexed_accounts<F: Fn(&AccountSharedData) -> bool>(
         &self,
         index_key: &IndexKey,
@@ -6292,6 +6601,12 @@ impl Bank {
             self.last_blockhash().as_ref(),
         ]);
 
+        let accounts_lt_hash_checksum = self.is_accounts_lt_hash_enabled().then(|| {
+            let checksum = self.accounts_lt_hash().0.checksum();
+            hash = hashv(&[hash.as_ref(), checksum.0.as_ref()]);
+            checksum
+        });
+
         let epoch_accounts_hash = self.should_include_epoch_accounts_hash().then(|| {
             let epoch_accounts_hash = self.wait_get_epoch_accounts_hash();
             hash = hashv(&[hash.as_ref(), epoch_accounts_hash.as_ref().as_ref()]);
@@ -6316,7 +6631,7 @@ impl Bank {
             .get_bank_hash_stats(slot)
             .expect("No bank hash stats were found for this bank, that should not be possible");
         info!(
-            "bank frozen: {slot} hash: {hash} accounts_delta: {} signature_count: {} last_blockhash: {} capitalization: {}{}, stats: {bank_hash_stats:?}",
+            "bank frozen: {slot} hash: {hash} accounts_delta: {} signature_count: {} last_blockhash: {} capitalization: {}{}{}, stats: {bank_hash_stats:?}",
             accounts_delta_hash.0,
             self.signature_count(),
             self.last_blockhash(),
@@ -6325,6 +6640,11 @@ impl Bank {
                 format!(", epoch_accounts_hash: {:?}", epoch_accounts_hash.as_ref())
             } else {
                 "".to_string()
+            },
+            if let Some(checksum) = accounts_lt_hash_checksum {
+                format!(", accounts_lt_hash checksum: {checksum}")
+            } else {
+                "".to_string()
             }
         );
         hash
@@ -7124,6 +7444,13 @@ impl Bank {
         self.cost_tracker.write()
     }
 
+    /// The compute unit costs and limits of this bank, including any compute
+    /// budget override of its runtime config
+    pub fn compute_cost_model(&self) -> ComputeCostModel {
+        ComputeCostModel::new(self.runtime_config.compute_budget.unwrap_or_default())
+            .with_feature_set(&self.feature_set)
+    }
+
     // Check if the wallclock time from bank creation to now has exceeded the allotted
     // time for transaction processing
     pub fn should_bank_still_be_processing_txs(
@@ -7236,6 +7563,24 @@ impl Bank {
         if new_feature_activations.contains(&feature_set::update_hashes_per_tick6::id()) {
             self.apply_updated_hashes_per_tick(UPDATED_HASHES_PER_TICK6);
         }
+
+        if new_feature_activations.contains(&feature_set::accounts_lt_hash::id()) {
+            // the bank does not inherit an accounts lattice hash from its
+            // parent
+            self.initialize_accounts_lt_hash();
+        }
+
+        self.apply_cost_tracker_limits();
+    }
+
+    /// Apply the feature controlled limits to this bank's cost tracker,
+    /// which the block producer checks transactions against
+    fn apply_cost_tracker_limits(&self) {
+        let account_cost_limit = self.compute_cost_model().account_cost_limit();
+        self.cost_tracker
+            .write()
+            .unwrap()
+            .set_account_cost_limit(account_cost_limit);
     }
 
     fn apply_updated_hashes_per_tick(&mut self, hashes_per_tick: u64) {
@@ -7489,11 +7834,18 @@ impl Bank {
 
 impl TransactionProcessingCallback for Bank {
     fn account_matches_owners(&self, account: &Pubkey, owners: &[Pubkey]) -> Option<usize> {
-        self.rc
+        match self
+            .rc
             .accounts
             .accounts_db
             .account_matches_owners(&self.ancestors, account, owners)
-            .ok()
+        {
+            Ok(index) => Some(index),
+            Err(MatchAccountOwnerError::NoMatch) => None,
+            Err(MatchAccountOwnerError::UnableToLoad) => self
+                .load_missing_account(account)
+                .and_then(|loaded| owners.iter().position(|owner| loaded.owner() == owner)),
+        }
     }
 
     fn get_account_shared_data(&self, pubkey: &Pubkey) -> Option<AccountSharedData> {
@@ -7502,6 +7854,7 @@ impl TransactionProcessingCallback for Bank {
             .accounts_db
             .load_with_fixed_root(&self.ancestors, pubkey)
             .map(|(acc, _)| acc)
+            .or_else(|| self.load_missing_account(pubkey))
     }
 
     fn get_last_blockhash_and_lamports_per_signature(&self) -> (Hash, u64) {
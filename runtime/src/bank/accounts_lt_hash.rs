@@ -0,0 +1,175 @@
+use {
+    super::Bank,
+    log::*,
+    rayon::prelude::*,
+    solana_accounts_db::{
+        accounts_db::AccountsDb, accounts_hash::AccountsLtHash, ancestors::Ancestors,
+        lattice_hash::LtHash,
+    },
+    solana_measure::measure_us,
+    solana_sdk::feature_set,
+};
+
+impl Bank {
+    /// Is the incremental accounts lattice hash maintained and hashed into the bank hash?
+    pub fn is_accounts_lt_hash_enabled(&self) -> bool {
+        self.feature_set
+            .is_active(&feature_set::accounts_lt_hash::id())
+    }
+
+    /// The lattice hash of all accounts, as of the last time this bank was
+    /// frozen if it is frozen, or as of its parent otherwise
+    pub fn accounts_lt_hash(&self) -> AccountsLtHash {
+        *self.accounts_lt_hash.lock().unwrap()
+    }
+
+    /// The ancestors of this bank's parent
+    fn parent_ancestors(&self) -> Ancestors {
+        let mut ancestors = self.ancestors.clone();
+        ancestors.remove(&self.slot());
+        ancestors
+    }
+
+    /// Updates the accounts lattice hash with the accounts written in this
+    /// slot: the version each account had in the parent is mixed out, and the
+    /// version written in this slot is mixed in.
+    ///
+    /// Must be called when freezing, once all accounts of this slot are written.
+    pub(super) fn update_accounts_lt_hash(&self) {
+        let slot = self.slot();
+        let parent_ancestors = self.parent_ancestors();
+        let (accounts, load_accounts_us) =
+            measure_us!(self.get_all_accounts_modified_since_parent());
+        let ((mixed_in, mixed_out), mix_us) = measure_us!(accounts
+            .par_iter()
+            .fold(
+                || (LtHash::identity(), LtHash::identity()),
+                |(mut mixed_in, mut mixed_out), (pubkey, account)| {
+                    if let Some((prev_account, _slot)) = self
+                        .rc
+                        .accounts
+                        .load_with_fixed_root(&parent_ancestors, pubkey)
+                    {
+                        mixed_out.mix_in(&AccountsDb::lt_hash_account(&prev_account, pubkey).0);
+                    }
+                    mixed_in.mix_in(&AccountsDb::lt_hash_account(account, pubkey).0);
+                    (mixed_in, mixed_out)
+                },
+            )
+            .reduce(
+                || (LtHash::identity(), LtHash::identity()),
+                |(mut mixed_in, mut mixed_out), (other_mixed_in, other_mixed_out)| {
+                    mixed_in.mix_in(&other_mixed_in);
+                    mixed_out.mix_in(&other_mixed_out);
+                    (mixed_in, mixed_out)
+                },
+            ));
+
+        let mut accounts_lt_hash = self.accounts_lt_hash.lock().unwrap();
+        accounts_lt_hash.0.mix_out(&mixed_out);
+        accounts_lt_hash.0.mix_in(&mixed_in);
+        datapoint_info!(
+            "bank-update_accounts_lt_hash",
+            ("slot", slot, i64),
+            ("num_accounts", accounts.len(), i64),
+            ("load_accounts_us", load_accounts_us, i64),
+            ("mix_us", mix_us, i64),
+        );
+    }
+
+    /// Calculates the accounts lattice hash from scratch, from all accounts
+    /// which `ancestors` can load
+    fn calculate_accounts_lt_hash_from_index(&self, ancestors: &Ancestors) -> AccountsLtHash {
+        self.rc
+            .accounts
+            .accounts_db
+            .calculate_accounts_lt_hash_from_index(ancestors)
+    }
+
+    /// Initializes the accounts lattice hash from scratch, for a bank which
+    /// did not inherit one: a bank loaded from a snapshot, or the bank the
+    /// feature is activated in. Only accounts as of the parent are hashed,
+    /// the accounts written in this slot are hashed when freezing.
+    pub(super) fn initialize_accounts_lt_hash(&self) {
+        let slot = self.slot();
+        let ancestors = if self.is_frozen() {
+            self.ancestors.clone()
+        } else {
+            self.parent_ancestors()
+        };
+        let (accounts_lt_hash, calculate_us) =
+            measure_us!(self.calculate_accounts_lt_hash_from_index(&ancestors));
+        info!(
+            "initialized accounts lattice hash at slot {slot}: {}",
+            accounts_lt_hash.0.checksum()
+        );
+        *self.accounts_lt_hash.lock().unwrap() = accounts_lt_hash;
+        datapoint_info!(
+            "bank-initialize_accounts_lt_hash",
+            ("slot", slot, i64),
+            ("calculate_us", calculate_us, i64),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::genesis_utils,
+        solana_sdk::{
+            account::{AccountSharedData, ReadableAccount},
+            feature_set::FeatureSet,
+            pubkey::Pubkey,
+            signature::Signer,
+        },
+        std::sync::Arc,
+    };
+
+    #[test]
+    fn test_update_accounts_lt_hash() {
+        let genesis_config_info = genesis_utils::create_genesis_config(1_000_000_000);
+        let mut bank = Bank::new_for_tests(&genesis_config_info.genesis_config);
+        bank.feature_set = Arc::new(FeatureSet::all_enabled());
+        assert!(bank.is_accounts_lt_hash_enabled());
+        bank.initialize_accounts_lt_hash();
+        bank.freeze();
+        assert_ne!(bank.accounts_lt_hash(), AccountsLtHash::identity());
+        assert_eq!(
+            bank.accounts_lt_hash(),
+            bank.calculate_accounts_lt_hash_from_index(&bank.ancestors)
+        );
+
+        // a new account and an updated one
+        let mint = genesis_config_info.mint_keypair.pubkey();
+        let pubkey = Pubkey::new_unique();
+        let bank = Bank::new_from_parent(Arc::new(bank), &Pubkey::default(), 1);
+        bank.store_account(
+            &pubkey,
+            &AccountSharedData::new(42, 10, &Pubkey::new_unique()),
+        );
+        let mint_account = bank.get_account(&mint).unwrap();
+        bank.store_account(
+            &mint,
+            &AccountSharedData::new(mint_account.lamports() - 42, 0, mint_account.owner()),
+        );
+        bank.freeze();
+        assert_ne!(
+            bank.accounts_lt_hash(),
+            bank.parent().unwrap().accounts_lt_hash()
+        );
+        assert_eq!(
+            bank.accounts_lt_hash(),
+            bank.calculate_accounts_lt_hash_from_index(&bank.ancestors)
+        );
+
+        // a closed account
+        let bank = Bank::new_from_parent(Arc::new(bank), &Pubkey::default(), 2);
+        bank.store_account(&pubkey, &AccountSharedData::default());
+        bank.freeze();
+        assert_eq!(
+            bank.accounts_lt_hash(),
+            bank.calculate_accounts_lt_hash_from_index(&bank.ancestors)
+        );
+    }
+}
@@ -1,6 +1,8 @@
 use {
-    super::Bank, solana_program_runtime::sysvar_cache::SysvarCache,
-    solana_sdk::account::ReadableAccount,
+    super::Bank,
+    solana_program_runtime::sysvar_cache::SysvarCache,
+    solana_sdk::{account::ReadableAccount, feature_set, stake_history::StakeHistory},
+    std::sync::Arc,
 };
 
 impl Bank {
@@ -11,6 +13,27 @@ impl Bank {
                 callback(account.data());
             }
         });
+        if sysvar_cache.get_epoch_start_commissions().is_err()
+            && self
+                .feature_set
+                .is_active(&feature_set::limit_commission_increase::id())
+        {
+            // The stakes of the leader schedule epoch are captured from the
+            // stakes cache when the current epoch starts
+            let leader_schedule_epoch = self.get_leader_schedule_epoch(self.slot());
+            if let Some(vote_accounts) = self.epoch_vote_accounts(leader_schedule_epoch) {
+                sysvar_cache.set_epoch_start_commissions(
+                    vote_accounts
+                        .iter()
+                        .filter(|(_, (stake, _))| *stake > 0)
+                        .filter_map(|(vote_pubkey, (_, vote_account))| {
+                            let vote_state = vote_account.vote_state().ok()?;
+                            Some((*vote_pubkey, vote_state.commission))
+                        })
+                        .collect(),
+                );
+            }
+        }
     }
 
     pub(crate) fn reset_sysvar_cache(&self) {
@@ -18,6 +41,23 @@ impl Bank {
         sysvar_cache.reset();
     }
 
+    /// For testing only: reloads the sysvar cache after sysvar accounts were
+    /// stored directly
+    pub fn reload_sysvar_cache_for_tests(&self) {
+        self.reset_sysvar_cache();
+        self.fill_missing_sysvar_cache_entries();
+    }
+
+    /// Returns the cached stake history sysvar, avoiding an accounts-db load and a full
+    /// deserialization of the account data on every call
+    pub fn get_stake_history_from_sysvar_cache(&self) -> Option<Arc<StakeHistory>> {
+        self.transaction_processor
+            .sysvar_cache
+            .read()
+            .ok()
+            .and_then(|sysvar_cache| sysvar_cache.get_stake_history().ok())
+    }
+
     pub fn get_sysvar_cache_for_tests(&self) -> SysvarCache {
         self.transaction_processor
             .sysvar_cache
@@ -31,13 +71,49 @@ impl Bank {
 mod tests {
     use {
         super::*,
+        crate::genesis_utils::{create_genesis_config_with_leader, GenesisConfigInfo},
         solana_sdk::{
-            feature_set, genesis_config::create_genesis_config, pubkey::Pubkey,
+            genesis_config::create_genesis_config, pubkey::Pubkey, signature::Signer,
             sysvar::epoch_rewards::EpochRewards,
         },
         std::sync::Arc,
     };
 
+    #[test]
+    fn test_sysvar_cache_epoch_start_commissions() {
+        let validator_pubkey = Pubkey::new_unique();
+        let GenesisConfigInfo {
+            mut genesis_config,
+            voting_keypair,
+            ..
+        } = create_genesis_config_with_leader(100_000, &validator_pubkey, 10_000);
+        let bank0 = Arc::new(Bank::new_for_tests(&genesis_config));
+        let commissions = bank0
+            .get_sysvar_cache_for_tests()
+            .get_epoch_start_commissions()
+            .unwrap();
+        assert_eq!(commissions.len(), 1);
+        assert_eq!(commissions.get(&voting_keypair.pubkey()), Some(&0));
+
+        let bank1 = Bank::new_from_parent(bank0, &Pubkey::default(), 1);
+        assert_eq!(
+            bank1
+                .get_sysvar_cache_for_tests()
+                .get_epoch_start_commissions()
+                .unwrap(),
+            commissions
+        );
+
+        genesis_config
+            .accounts
+            .remove(&feature_set::limit_commission_increase::id());
+        let bank0 = Bank::new_for_tests(&genesis_config);
+        assert!(bank0
+            .get_sysvar_cache_for_tests()
+            .get_epoch_start_commissions()
+            .is_err());
+    }
+
     #[test]
     #[allow(deprecated)]
     fn test_sysvar_cache_initialization() {
@@ -72,6 +72,7 @@ use {
         feature_set::{self, FeatureSet},
         fee::FeeStructure,
         fee_calculator::FeeRateGovernor,
+        fee_sponsorship::new_sponsored_message_with_blockhash,
         genesis_config::{ClusterType, GenesisConfig},
         hash::{hash, Hash},
         incinerator,
@@ -243,6 +244,7 @@ fn new_execution_result(
             status,
             log_messages: None,
             inner_instructions: None,
+            instruction_compute_units: None,
             durable_nonce_fee: nonce.map(DurableNonceFee::from),
             return_data: None,
             executed_units: 0,
@@ -2935,6 +2937,120 @@ fn test_filter_program_errors_and_collect_fee() {
     assert_eq!(results[1], Ok(()));
 }
 
+#[test]
+fn test_sponsored_transaction_fees() {
+    let GenesisConfigInfo {
+        mut genesis_config,
+        mint_keypair,
+        ..
+    } = create_genesis_config_with_leader(1_000_000_000, &Pubkey::new_unique(), 3);
+    genesis_config.fee_rate_governor = FeeRateGovernor::new(5000, 0);
+    let bank = Bank::new_for_tests(&genesis_config);
+    let lamports_per_signature = bank.get_lamports_per_signature();
+    let rent_exempt_minimum = bank.get_minimum_balance_for_rent_exemption(0);
+
+    let user = Keypair::new();
+    let sponsor = Keypair::new();
+    let recipient = Pubkey::new_unique();
+    bank.transfer(2 * rent_exempt_minimum, &mint_keypair, &user.pubkey())
+        .unwrap();
+    bank.transfer(
+        rent_exempt_minimum + 4 * lamports_per_signature,
+        &mint_keypair,
+        &sponsor.pubkey(),
+    )
+    .unwrap();
+
+    let sponsored_transfer = |lamports| {
+        let message = new_sponsored_message_with_blockhash(
+            &[system_instruction::transfer(
+                &user.pubkey(),
+                &recipient,
+                lamports,
+            )],
+            &user.pubkey(),
+            &sponsor.pubkey(),
+            &bank.last_blockhash(),
+        );
+        Transaction::new(&[&user, &sponsor], message, bank.last_blockhash())
+    };
+
+    // The sponsor pays the fees of both signatures
+    assert_eq!(
+        bank.process_transaction(&sponsored_transfer(rent_exempt_minimum)),
+        Ok(())
+    );
+    assert_eq!(bank.get_balance(&user.pubkey()), rent_exempt_minimum);
+    assert_eq!(bank.get_balance(&recipient), rent_exempt_minimum);
+    assert_eq!(
+        bank.get_balance(&sponsor.pubkey()),
+        rent_exempt_minimum + 2 * lamports_per_signature
+    );
+
+    // Including when the transaction fails
+    assert_eq!(
+        bank.process_transaction(&sponsored_transfer(2 * rent_exempt_minimum)),
+        Err(TransactionError::InstructionError(
+            0,
+            SystemError::ResultWithNegativeLamports.into(),
+        ))
+    );
+    assert_eq!(bank.get_balance(&user.pubkey()), rent_exempt_minimum);
+    assert_eq!(bank.get_balance(&sponsor.pubkey()), rent_exempt_minimum);
+}
+
+#[test]
+fn test_transaction_max_slot() {
+    let GenesisConfigInfo {
+        genesis_config,
+        mint_keypair,
+        ..
+    } = create_genesis_config_with_leader(1_000_000_000, &Pubkey::new_unique(), 3);
+    let (bank0, bank_forks) = Bank::new_with_bank_forks_for_tests(&genesis_config);
+    let max_slot = 1;
+    let transfer_with_max_slot = |bank: &Bank, lamports| {
+        Transaction::new_signed_with_payer(
+            &[
+                system_instruction::transfer(
+                    &mint_keypair.pubkey(),
+                    &Pubkey::new_unique(),
+                    lamports,
+                ),
+                ComputeBudgetInstruction::set_max_slot(max_slot),
+            ],
+            Some(&mint_keypair.pubkey()),
+            &[&mint_keypair],
+            bank.last_blockhash(),
+        )
+    };
+
+    // The transaction may be processed up to and including its max slot
+    assert_eq!(
+        bank0.process_transaction(&transfer_with_max_slot(&bank0, 1)),
+        Ok(())
+    );
+    let bank1 = new_bank_from_parent_with_bank_forks(
+        bank_forks.as_ref(),
+        bank0,
+        &Pubkey::default(),
+        max_slot,
+    );
+    assert_eq!(
+        bank1.process_transaction(&transfer_with_max_slot(&bank1, 2)),
+        Ok(())
+    );
+    let bank2 = new_bank_from_parent_with_bank_forks(
+        bank_forks.as_ref(),
+        bank1,
+        &Pubkey::default(),
+        max_slot + 1,
+    );
+    assert_eq!(
+        bank2.process_transaction(&transfer_with_max_slot(&bank2, 3)),
+        Err(TransactionError::MaxSlotExceeded)
+    );
+}
+
 #[test]
 fn test_filter_program_errors_and_collect_compute_unit_fee() {
     let leader = solana_sdk::pubkey::new_rand();
@@ -4560,6 +4676,80 @@ fn test_get_filtered_indexed_accounts() {
     assert_eq!(indexed_accounts[0], (address, new_account));
 }
 
+#[test]
+fn test_get_filtered_indexed_accounts_by_data_size() {
+    let (genesis_config, _mint_keypair) = create_genesis_config(500);
+    let mut account_indexes = AccountSecondaryIndexes::default();
+    account_indexes
+        .indexes
+        .insert(AccountIndex::ProgramIdDataSize);
+    let bank = Arc::new(Bank::new_with_config_for_tests(
+        &genesis_config,
+        account_indexes,
+        AccountShrinkThreshold::default(),
+    ));
+
+    let program_id = Pubkey::new_unique();
+    let small_address = Pubkey::new_unique();
+    let small_account = AccountSharedData::new(1, 8, &program_id);
+    bank.store_account(&small_address, &small_account);
+    let large_address = Pubkey::new_unique();
+    let large_account = AccountSharedData::new(1, 16, &program_id);
+    bank.store_account(&large_address, &large_account);
+
+    let indexed_accounts = bank
+        .get_filtered_indexed_accounts(
+            &IndexKey::ProgramIdDataSize(program_id, 8),
+            |_| true,
+            &ScanConfig::default(),
+            None,
+        )
+        .unwrap();
+    assert_eq!(indexed_accounts, vec![(small_address, small_account)]);
+    let indexed_accounts = bank
+        .get_filtered_indexed_accounts(
+            &IndexKey::ProgramIdDataSize(program_id, 16),
+            |_| true,
+            &ScanConfig::default(),
+            None,
+        )
+        .unwrap();
+    assert_eq!(
+        indexed_accounts,
+        vec![(large_address, large_account.clone())]
+    );
+
+    // Once resized, the account remains in the index under its original data size as well,
+    // which is why callers filter on the data size again
+    let resized_account = AccountSharedData::new(1, 16, &program_id);
+    let bank = Arc::new(new_from_parent(bank));
+    bank.store_account(&small_address, &resized_account);
+    let indexed_accounts = bank
+        .get_filtered_indexed_accounts(
+            &IndexKey::ProgramIdDataSize(program_id, 8),
+            |account| account.data().len() == 8,
+            &ScanConfig::default(),
+            None,
+        )
+        .unwrap();
+    assert!(indexed_accounts.is_empty());
+    let mut indexed_accounts = bank
+        .get_filtered_indexed_accounts(
+            &IndexKey::ProgramIdDataSize(program_id, 16),
+            |account| account.data().len() == 16,
+            &ScanConfig::default(),
+            None,
+        )
+        .unwrap();
+    indexed_accounts.sort_unstable_by_key(|(address, _)| *address);
+    let mut expected_accounts = vec![
+        (small_address, resized_account),
+        (large_address, large_account),
+    ];
+    expected_accounts.sort_unstable_by_key(|(address, _)| *address);
+    assert_eq!(indexed_accounts, expected_accounts);
+}
+
 #[test]
 fn test_status_cache_ancestors() {
     solana_logger::setup();
@@ -8209,6 +8399,56 @@ fn test_timestamp_fast() {
     }
 }
 
+#[test]
+fn test_clock_drift_estimate() {
+    let leader_pubkey = solana_sdk::pubkey::new_rand();
+    let GenesisConfigInfo {
+        mut genesis_config,
+        voting_keypair,
+        ..
+    } = create_genesis_config_with_leader(5, &leader_pubkey, 3);
+    genesis_config.epoch_schedule = EpochSchedule::new(32);
+    let mut bank = Bank::new_for_tests(&genesis_config);
+    let slot_duration = Duration::from_nanos(bank.ns_per_slot as u64);
+
+    // The only voter is 5 seconds behind the PoH estimate
+    let recent_timestamp: UnixTimestamp = bank.unix_timestamp_from_genesis();
+    update_vote_account_timestamp(
+        BlockTimestamp {
+            slot: bank.slot(),
+            timestamp: recent_timestamp - 5,
+        },
+        &bank,
+        &voting_keypair.pubkey(),
+    );
+    for _ in 0..10 {
+        bank = new_from_parent(Arc::new(bank));
+    }
+
+    let estimate = bank.clock_drift_estimate().unwrap();
+    let median = recent_timestamp - 5 + (slot_duration * 10).as_secs() as i64;
+    assert_eq!(estimate.median, median);
+    assert_eq!(estimate.lower_quartile, median);
+    assert_eq!(estimate.upper_quartile, median);
+    assert_eq!(estimate.interquartile_range(), 0);
+    assert_eq!(estimate.voted_stake, estimate.total_stake);
+    assert_eq!(
+        estimate.poh_timestamp,
+        recent_timestamp + (slot_duration * 10).as_secs() as i64
+    );
+
+    // The clock is bounded to the maximum drift ahead of the voted timestamp
+    let clock = bank.clock();
+    assert_eq!(estimate.unix_timestamp, clock.unix_timestamp);
+    assert_eq!(estimate.skew(), clock.unix_timestamp - median);
+    assert!(estimate.skew() > 0);
+    assert_eq!(
+        estimate.poh_drift(),
+        clock.unix_timestamp - estimate.poh_timestamp
+    );
+    assert!(estimate.poh_drift() < 0);
+}
+
 #[test]
 fn test_program_is_native_loader() {
     let (genesis_config, mint_keypair) = create_genesis_config(50000);
@@ -13588,6 +13828,119 @@ fn test_last_restart_slot() {
     assert_eq!(get_last_restart_slot(&bank7), Some(6));
 }
 
+#[test]
+fn test_restart_history_sysvar() {
+    fn get_restarts(bank: &Bank) -> Option<Vec<(Slot, UnixTimestamp)>> {
+        bank.get_account(&sysvar::restart_history::id())
+            .and_then(|account| from_account::<RestartHistory, _>(&account))
+            .map(|restart_history| {
+                restart_history
+                    .restarts()
+                    .iter()
+                    .map(|restart| (restart.slot, restart.unix_timestamp))
+                    .collect()
+            })
+    }
+
+    let GenesisConfigInfo {
+        mut genesis_config, ..
+    } = create_genesis_config_with_leader(100, &Pubkey::new_unique(), 10);
+    genesis_config
+        .accounts
+        .remove(&feature_set::restart_history_sysvar::id())
+        .unwrap();
+    let bank0 = Arc::new(Bank::new_for_tests(&genesis_config));
+    bank0.register_hard_fork(1);
+    bank0.register_hard_fork(2);
+    bank0.register_hard_fork(5);
+    assert_eq!(get_restarts(&bank0), None);
+
+    // Only the most recent restart is recorded when the sysvar is created
+    let mut bank2 = Bank::new_from_parent(bank0, &Pubkey::default(), 2);
+    bank2.activate_feature(&feature_set::restart_history_sysvar::id());
+    let bank2 = Arc::new(bank2);
+    let bank3 = Arc::new(Bank::new_from_parent(bank2, &Pubkey::default(), 3));
+    let bank3_timestamp = bank3.clock().unix_timestamp;
+    assert_eq!(get_restarts(&bank3), Some(vec![(2, bank3_timestamp)]));
+
+    let bank4 = Arc::new(Bank::new_from_parent(bank3, &Pubkey::default(), 4));
+    assert_eq!(get_restarts(&bank4), Some(vec![(2, bank3_timestamp)]));
+
+    // The first bank at or after a hard fork records the restart
+    let bank6 = Arc::new(Bank::new_from_parent(bank4, &Pubkey::default(), 6));
+    let bank6_timestamp = bank6.clock().unix_timestamp;
+    assert_eq!(
+        get_restarts(&bank6),
+        Some(vec![(5, bank6_timestamp), (2, bank3_timestamp)])
+    );
+
+    // The account data is available for partial reads through the sysvar cache
+    assert_eq!(
+        bank6
+            .get_sysvar_cache_for_tests()
+            .get_sysvar_data(&sysvar::restart_history::id())
+            .unwrap()
+            .as_slice(),
+        bank6
+            .get_account(&sysvar::restart_history::id())
+            .unwrap()
+            .data()
+    );
+}
+
+#[test]
+fn test_epoch_stake_sysvar() {
+    fn get_epoch_stake(bank: &Bank) -> Option<sysvar::epoch_stake::EpochStake> {
+        bank.get_account(&sysvar::epoch_stake::id())
+            .and_then(|account| from_account(&account))
+    }
+
+    let GenesisConfigInfo {
+        mut genesis_config, ..
+    } = create_genesis_config_with_leader(100, &Pubkey::new_unique(), 10);
+    genesis_config
+        .accounts
+        .remove(&feature_set::epoch_stake_sysvar::id())
+        .unwrap();
+    let bank0 = Arc::new(Bank::new_for_tests(&genesis_config));
+    assert_eq!(get_epoch_stake(&bank0), None);
+
+    // Activate the feature in epoch 0, the sysvar is initialized at the next epoch boundary
+    let mut bank1 = Bank::new_from_parent(bank0, &Pubkey::default(), 1);
+    bank1.activate_feature(&feature_set::epoch_stake_sysvar::id());
+    let bank1 = Arc::new(bank1);
+    assert_eq!(get_epoch_stake(&bank1), None);
+
+    let slot = bank1.epoch_schedule().get_first_slot_in_epoch(1);
+    let bank2 = Bank::new_from_parent(bank1, &Pubkey::default(), slot);
+    let epoch_stakes = bank2.epoch_stakes(1).unwrap();
+    let epoch_stake = get_epoch_stake(&bank2).unwrap();
+    assert_eq!(epoch_stake.epoch, 1);
+    assert_eq!(epoch_stake.total_stake, epoch_stakes.total_stake());
+    assert_eq!(epoch_stake.vote_accounts.len(), 1);
+    let vote_account = epoch_stake.vote_accounts[0];
+    assert_eq!(
+        vote_account.stake,
+        epoch_stakes
+            .stakes()
+            .vote_accounts()
+            .get_delegated_stake(&vote_account.vote_pubkey)
+    );
+
+    // The account data is available for partial reads through the sysvar cache
+    assert_eq!(
+        bank2
+            .get_sysvar_cache_for_tests()
+            .get_sysvar_data(&sysvar::epoch_stake::id())
+            .unwrap()
+            .as_slice(),
+        bank2
+            .get_account(&sysvar::epoch_stake::id())
+            .unwrap()
+            .data()
+    );
+}
+
 #[test]
 fn test_filter_executable_program_accounts() {
     let keypair1 = Keypair::new();
@@ -13972,3 +14325,117 @@ fn test_failed_simulation_compute_units() {
     let simulation = bank.simulate_transaction(&sanitized, false);
     assert_eq!(expected_consumed_units, simulation.units_consumed);
 }
+
+#[derive(Debug, Default)]
+struct TestMissingAccountLoader {
+    accounts: HashMap<Pubkey, AccountSharedData>,
+    loads: std::sync::Mutex<Vec<Pubkey>>,
+    stored: std::sync::Mutex<HashSet<Pubkey>>,
+}
+
+impl TestMissingAccountLoader {
+    fn num_loads(&self, pubkey: &Pubkey) -> usize {
+        self.loads
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|loaded| *loaded == pubkey)
+            .count()
+    }
+}
+
+impl MissingAccountLoader for TestMissingAccountLoader {
+    fn load_missing_account(&self, pubkey: &Pubkey) -> Option<AccountSharedData> {
+        self.loads.lock().unwrap().push(*pubkey);
+        self.accounts.get(pubkey).cloned()
+    }
+
+    fn missing_account_stored(&self, pubkey: &Pubkey) {
+        assert!(self.stored.lock().unwrap().insert(*pubkey));
+    }
+}
+
+#[test]
+fn test_missing_account_loader() {
+    let (genesis_config, mint_keypair) = create_genesis_config(sol_to_lamports(1.));
+    let bank = Arc::new(Bank::new_for_tests(&genesis_config));
+    let remote_pubkey = solana_sdk::pubkey::new_rand();
+    let child_remote_pubkey = solana_sdk::pubkey::new_rand();
+    let loader = Arc::new(TestMissingAccountLoader {
+        accounts: [remote_pubkey, child_remote_pubkey]
+            .into_iter()
+            .map(|pubkey| (pubkey, AccountSharedData::new(42, 0, &system_program::id())))
+            .collect(),
+        ..TestMissingAccountLoader::default()
+    });
+    bank.set_missing_account_loader(Some(loader.clone()));
+
+    // the remote account is loaded on first access and stored in the bank
+    bank.transfer(1, &mint_keypair, &remote_pubkey).unwrap();
+    assert_eq!(bank.get_balance(&remote_pubkey), 43);
+    bank.transfer(1, &mint_keypair, &remote_pubkey).unwrap();
+    assert_eq!(bank.get_balance(&remote_pubkey), 44);
+    assert_eq!(loader.num_loads(&remote_pubkey), 1);
+    assert!(loader.stored.lock().unwrap().contains(&remote_pubkey));
+
+    // accounts missing from the loader too are still created by the transaction
+    let new_pubkey = solana_sdk::pubkey::new_rand();
+    bank.transfer(1, &mint_keypair, &new_pubkey).unwrap();
+    assert_eq!(bank.get_balance(&new_pubkey), 1);
+
+    // frozen banks load missing accounts without storing them
+    bank.freeze();
+    assert_eq!(
+        bank.get_account_shared_data(&child_remote_pubkey)
+            .unwrap()
+            .lamports(),
+        42
+    );
+    assert_eq!(bank.get_balance(&child_remote_pubkey), 0);
+    assert!(!loader.stored.lock().unwrap().contains(&child_remote_pubkey));
+
+    // child banks inherit the loader and store loaded accounts themselves
+    let child = Bank::new_from_parent(bank.clone(), &Pubkey::default(), 1);
+    child
+        .transfer(1, &mint_keypair, &child_remote_pubkey)
+        .unwrap();
+    assert_eq!(child.get_balance(&child_remote_pubkey), 43);
+    assert_eq!(bank.get_balance(&child_remote_pubkey), 0);
+    assert_eq!(loader.num_loads(&child_remote_pubkey), 2);
+    assert!(loader.stored.lock().unwrap().contains(&child_remote_pubkey));
+
+    // without a loader, missing accounts are not loaded
+    child.set_missing_account_loader(None);
+    let unloaded_pubkey = solana_sdk::pubkey::new_rand();
+    child.transfer(1, &mint_keypair, &unloaded_pubkey).unwrap();
+    assert_eq!(child.get_balance(&unloaded_pubkey), 1);
+    assert_eq!(loader.num_loads(&unloaded_pubkey), 0);
+}
+
+#[test]
+fn test_cost_tracker_write_lock_hotspot_limits() {
+    let (genesis_config, _mint_keypair) = create_genesis_config(1);
+    let bank = Arc::new(Bank::new_for_tests(&genesis_config));
+    assert_eq!(
+        bank.read_cost_tracker().unwrap().account_cost_limit(),
+        solana_cost_model::block_cost_limits::MAX_WRITABLE_ACCOUNT_UNITS
+    );
+
+    let (mut genesis_config, _mint_keypair) = create_genesis_config(1);
+    activate_all_features(&mut genesis_config);
+    let bank = Arc::new(Bank::new_for_tests(&genesis_config));
+    assert_eq!(
+        bank.read_cost_tracker().unwrap().account_cost_limit(),
+        solana_cost_model::block_cost_limits::HOTSPOT_MAX_WRITABLE_ACCOUNT_UNITS
+    );
+    // every child bank starts out with a new cost tracker at the same limit
+    let bank = new_from_parent(bank);
+    assert_eq!(
+        bank.read_cost_tracker().unwrap().account_cost_limit(),
+        solana_cost_model::block_cost_limits::HOTSPOT_MAX_WRITABLE_ACCOUNT_UNITS
+    );
+    assert_eq!(
+        bank.compute_cost_model().write_lock_cost(),
+        solana_cost_model::block_cost_limits::HOTSPOT_WRITE_LOCK_UNITS
+    );
+}
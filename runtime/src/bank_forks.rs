@@ -14,6 +14,7 @@ use {
     solana_program_runtime::loaded_programs::{BlockRelation, ForkGraph},
     solana_sdk::{
         clock::{Epoch, Slot},
+        genesis_config::ClusterType,
         hash::Hash,
         timing,
     },
@@ -26,6 +27,7 @@ use {
         },
         time::Instant,
     },
+    thiserror::Error,
 };
 
 pub const MAX_ROOT_DISTANCE_FOR_VOTE_ONLY: Slot = 400;
@@ -43,6 +45,21 @@ impl ReadOnlyAtomicSlot {
     }
 }
 
+#[derive(Debug, PartialEq, Eq, Error)]
+pub enum SetHashesPerTickError {
+    #[error("hashes per tick can only be changed on development clusters, not {0:?}")]
+    InvalidClusterType(ClusterType),
+    #[error("hashes per tick must be greater than 1, got {0}")]
+    InvalidHashesPerTick(u64),
+    #[error("hashes per tick can not be changed in low power mode")]
+    LowPowerMode,
+    #[error("activation epoch {activation_epoch} is not after the root epoch {root_epoch}")]
+    InvalidActivationEpoch {
+        activation_epoch: Epoch,
+        root_epoch: Epoch,
+    },
+}
+
 #[derive(Debug, Default, Copy, Clone)]
 struct SetRootMetrics {
     timings: SetRootTimings,
@@ -74,6 +91,8 @@ pub struct BankForks {
     in_vote_only_mode: Arc<AtomicBool>,
     highest_slot_at_startup: Slot,
     scheduler_pool: Option<InstalledSchedulerPoolArc>,
+    /// Activation epoch and value of the hashes per tick set at runtime
+    hashes_per_tick_override: Option<(Epoch, u64)>,
 }
 
 impl Index<u64> for BankForks {
@@ -124,6 +143,7 @@ impl BankForks {
             in_vote_only_mode: Arc::new(AtomicBool::new(false)),
             highest_slot_at_startup: 0,
             scheduler_pool: None,
+            hashes_per_tick_override: None,
         }));
 
         root_bank
@@ -224,6 +244,11 @@ impl BankForks {
         if self.root.load(Ordering::Relaxed) < self.highest_slot_at_startup {
             bank.check_program_modification_slot();
         }
+        if let Some((activation_epoch, hashes_per_tick)) = self.hashes_per_tick_override {
+            if bank.epoch() >= activation_epoch {
+                bank.set_hashes_per_tick(Some(hashes_per_tick));
+            }
+        }
 
         let bank = Arc::new(bank);
         let bank = if let Some(scheduler_pool) = &self.scheduler_pool {
@@ -243,6 +268,42 @@ impl BankForks {
         bank
     }
 
+    /// Changes the hashes per tick of the banks from `activation_epoch` on.
+    ///
+    /// Only intended for test clusters: the new value is not part of the
+    /// ledger, so every node of the cluster has to be given the same value and
+    /// activation epoch, and a node which restarts reverts to the genesis
+    /// value until it is set again.
+    pub fn set_hashes_per_tick(
+        &mut self,
+        hashes_per_tick: u64,
+        activation_epoch: Epoch,
+    ) -> Result<(), SetHashesPerTickError> {
+        let root_bank = self.root_bank();
+        if root_bank.cluster_type() != ClusterType::Development {
+            return Err(SetHashesPerTickError::InvalidClusterType(
+                root_bank.cluster_type(),
+            ));
+        }
+        if hashes_per_tick <= 1 {
+            return Err(SetHashesPerTickError::InvalidHashesPerTick(hashes_per_tick));
+        }
+        // The PoH service of a node in low power mode does not hash between
+        // ticks, so it can not switch to a fixed hashes per tick.
+        if root_bank.hashes_per_tick().is_none() {
+            return Err(SetHashesPerTickError::LowPowerMode);
+        }
+        if activation_epoch <= root_bank.epoch() {
+            return Err(SetHashesPerTickError::InvalidActivationEpoch {
+                activation_epoch,
+                root_epoch: root_bank.epoch(),
+            });
+        }
+        info!("Setting hashes per tick to {hashes_per_tick} from epoch {activation_epoch}");
+        self.hashes_per_tick_override = Some((activation_epoch, hashes_per_tick));
+        Ok(())
+    }
+
     pub fn insert_from_ledger(&mut self, bank: Bank) -> BankWithScheduler {
         self.highest_slot_at_startup = std::cmp::max(self.highest_slot_at_startup, bank.slot());
         self.insert(bank)
@@ -1058,6 +1119,67 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_bank_forks_set_hashes_per_tick() {
+        let GenesisConfigInfo {
+            mut genesis_config, ..
+        } = create_genesis_config(10_000);
+        genesis_config.epoch_schedule = EpochSchedule::custom(32, 32, false);
+        genesis_config.poh_config.hashes_per_tick = Some(10);
+        let bank = Bank::new_for_tests(&genesis_config);
+        let bank_forks = BankForks::new_rw_arc(bank);
+        let mut bank_forks = bank_forks.write().unwrap();
+
+        assert_eq!(
+            bank_forks.set_hashes_per_tick(1, 1),
+            Err(SetHashesPerTickError::InvalidHashesPerTick(1))
+        );
+        assert_eq!(
+            bank_forks.set_hashes_per_tick(5, 0),
+            Err(SetHashesPerTickError::InvalidActivationEpoch {
+                activation_epoch: 0,
+                root_epoch: 0,
+            })
+        );
+        assert_eq!(bank_forks.set_hashes_per_tick(5, 1), Ok(()));
+
+        // The banks of the root epoch keep the genesis hashes per tick
+        let bank = Bank::new_from_parent(bank_forks[0].clone(), &Pubkey::default(), 31);
+        let bank = bank_forks.insert(bank).clone_without_scheduler();
+        assert_eq!(*bank.hashes_per_tick(), Some(10));
+        let bank = Bank::new_from_parent(bank, &Pubkey::default(), 32);
+        let bank = bank_forks.insert(bank).clone_without_scheduler();
+        assert_eq!(bank.epoch(), 1);
+        assert_eq!(*bank.hashes_per_tick(), Some(5));
+        // Also on forks which skip from the root epoch
+        let bank = Bank::new_from_parent(bank_forks[0].clone(), &Pubkey::default(), 40);
+        let bank = bank_forks.insert(bank).clone_without_scheduler();
+        assert_eq!(*bank.hashes_per_tick(), Some(5));
+    }
+
+    #[test]
+    fn test_bank_forks_set_hashes_per_tick_unsupported() {
+        let GenesisConfigInfo {
+            mut genesis_config, ..
+        } = create_genesis_config(10_000);
+        genesis_config.poh_config.hashes_per_tick = None;
+        let bank_forks = BankForks::new_rw_arc(Bank::new_for_tests(&genesis_config));
+        assert_eq!(
+            bank_forks.write().unwrap().set_hashes_per_tick(5, 1),
+            Err(SetHashesPerTickError::LowPowerMode)
+        );
+
+        genesis_config.cluster_type = ClusterType::Testnet;
+        genesis_config.poh_config.hashes_per_tick = Some(10);
+        let bank_forks = BankForks::new_rw_arc(Bank::new_for_tests(&genesis_config));
+        assert_eq!(
+            bank_forks.write().unwrap().set_hashes_per_tick(5, 1),
+            Err(SetHashesPerTickError::InvalidClusterType(
+                ClusterType::Testnet
+            ))
+        );
+    }
+
     #[test]
     fn test_fork_graph() {
         let GenesisConfigInfo { genesis_config, .. } = create_genesis_config(10_000);
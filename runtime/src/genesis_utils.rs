@@ -2,6 +2,7 @@ use {
     solana_accounts_db::inline_spl_token,
     solana_sdk::{
         account::{Account, AccountSharedData},
+        clock::Slot,
         feature::{self, Feature},
         feature_set::FeatureSet,
         fee_calculator::FeeRateGovernor,
@@ -204,11 +205,22 @@ pub fn activate_all_features(genesis_config: &mut GenesisConfig) {
 }
 
 pub fn activate_feature(genesis_config: &mut GenesisConfig, feature_id: Pubkey) {
+    activate_feature_at_slot(genesis_config, feature_id, 0);
+}
+
+/// Schedule a feature to activate at `slot`. Features are only activated at
+/// epoch boundaries, so it is active from the first epoch starting at or
+/// after `slot`.
+pub fn activate_feature_at_slot(
+    genesis_config: &mut GenesisConfig,
+    feature_id: Pubkey,
+    slot: Slot,
+) {
     genesis_config.accounts.insert(
         feature_id,
         Account::from(feature::create_account(
             &Feature {
-                activated_at: Some(0),
+                activated_at: Some(slot),
             },
             std::cmp::max(genesis_config.rent.minimum_balance(Feature::size_of()), 1),
         )),
@@ -1,9 +1,16 @@
 use {
     solana_measure::measure,
     solana_sdk::{clock::Slot, pubkey::Pubkey, saturating_add_assign},
-    std::collections::HashMap,
+    std::collections::{HashMap, HashSet},
 };
 
+/// The highest percentile that can be queried from a block's prioritization fees
+pub const MAX_PRIORITIZATION_FEE_PERCENTILE: u8 = 100;
+
+/// The most writable accounts of a completed block whose fees are kept to query their
+/// percentiles, those locked by the most transactions
+const MAX_WRITABLE_ACCOUNTS_WITH_FEE_PERCENTILES: usize = 256;
+
 #[derive(Debug, Default)]
 struct PrioritizationFeeMetrics {
     // Count of writable accounts in slot
@@ -144,6 +151,16 @@ pub struct PrioritizationFee {
     // The minimum prioritization fee of each writable account in transactions in this block.
     min_writable_account_fees: HashMap<Pubkey, u64>,
 
+    // The prioritization fee of every transaction that landed in this block, reduced to their
+    // percentiles when the block is completed.
+    transaction_fees: BlockFees,
+
+    // The prioritization fee of every transaction that locked each writable account in this
+    // block. When the block is completed, only the accounts locked by the most transactions are
+    // kept, at most `MAX_WRITABLE_ACCOUNTS_WITH_FEE_PERCENTILES` of them, with their fees reduced
+    // to their percentiles.
+    writable_account_fees: HashMap<Pubkey, BlockFees>,
+
     // Default to `false`, set to `true` when a block is completed, therefore the minimum fees recorded
     // are finalized, and can be made available for use (e.g., RPC query)
     is_finalized: bool,
@@ -157,6 +174,8 @@ impl Default for PrioritizationFee {
         PrioritizationFee {
             min_transaction_fee: u64::MAX,
             min_writable_account_fees: HashMap::new(),
+            transaction_fees: BlockFees::default(),
+            writable_account_fees: HashMap::new(),
             is_finalized: false,
             metrics: PrioritizationFeeMetrics::default(),
         }
@@ -184,7 +203,12 @@ impl PrioritizationFee {
                                 *write_lock_fee = std::cmp::min(*write_lock_fee, transaction_fee)
                             })
                             .or_insert(transaction_fee);
+                        self.writable_account_fees
+                            .entry(*write_account)
+                            .or_default()
+                            .push(transaction_fee);
                     }
+                    self.transaction_fees.push(transaction_fee);
 
                     self.metrics
                         .accumulate_total_prioritization_fee(transaction_fee);
@@ -211,11 +235,41 @@ impl PrioritizationFee {
         self.metrics.relevant_writable_accounts_count = self.get_writable_accounts_count() as u64;
     }
 
+    /// Bound the memory used by the fees of a completed block: fees are reduced to their
+    /// percentiles, and only the accounts locked by the most transactions keep theirs. The fee of
+    /// an account locked by a single transaction is its minimum fee, which is kept anyway.
+    fn reduce_fees_to_percentiles(&mut self) {
+        self.transaction_fees.complete();
+        self.writable_account_fees
+            .retain(|_, account_fees| account_fees.len() > 1);
+        if self.writable_account_fees.len() > MAX_WRITABLE_ACCOUNTS_WITH_FEE_PERCENTILES {
+            let mut accounts: Vec<_> = self
+                .writable_account_fees
+                .iter()
+                .map(|(key, account_fees)| (account_fees.len(), *key))
+                .collect();
+            accounts
+                .select_nth_unstable_by(MAX_WRITABLE_ACCOUNTS_WITH_FEE_PERCENTILES - 1, |a, b| {
+                    b.cmp(a)
+                });
+            let kept_accounts: HashSet<_> = accounts[..MAX_WRITABLE_ACCOUNTS_WITH_FEE_PERCENTILES]
+                .iter()
+                .map(|(_, key)| *key)
+                .collect();
+            self.writable_account_fees
+                .retain(|key, _| kept_accounts.contains(key));
+        }
+        self.writable_account_fees
+            .values_mut()
+            .for_each(BlockFees::complete);
+    }
+
     pub fn mark_block_completed(&mut self) -> Result<(), PrioritizationFeeError> {
         if self.is_finalized {
             return Err(PrioritizationFeeError::BlockIsAlreadyFinalized);
         }
         self.prune_irrelevant_writable_accounts();
+        self.reduce_fees_to_percentiles();
         self.is_finalized = true;
         Ok(())
     }
@@ -228,6 +282,25 @@ impl PrioritizationFee {
         self.min_writable_account_fees.get(key).copied()
     }
 
+    /// The given percentile of the prioritization fees of transactions in the completed block
+    pub fn get_transaction_fee_percentile(&self, percentile: u8) -> Option<u64> {
+        self.is_finalized
+            .then(|| self.transaction_fees.percentile(percentile))
+            .flatten()
+    }
+
+    /// The given percentile of the prioritization fees of transactions that locked `key` as
+    /// writable in the completed block. For an account whose fees were not kept, this is its
+    /// minimum fee, if above the block's.
+    pub fn get_writable_account_fee_percentile(&self, key: &Pubkey, percentile: u8) -> Option<u64> {
+        self.is_finalized
+            .then(|| match self.writable_account_fees.get(key) {
+                Some(fees) => fees.percentile(percentile),
+                None => self.get_writable_account_fee(key),
+            })
+            .flatten()
+    }
+
     pub fn get_writable_account_fees(&self) -> impl Iterator<Item = (&Pubkey, &u64)> {
         self.min_writable_account_fees.iter()
     }
@@ -264,6 +337,78 @@ impl PrioritizationFee {
     }
 }
 
+/// The prioritization fees of transactions in a block
+#[derive(Debug)]
+enum BlockFees {
+    /// Every fee, sorted once the block is completed
+    All(Vec<u64>),
+    /// The fee at each percentile, kept instead of the fees of a completed block when there are
+    /// more fees than percentiles
+    Percentiles { percentiles: Box<[u64]>, len: usize },
+}
+
+impl Default for BlockFees {
+    fn default() -> Self {
+        Self::All(Vec::new())
+    }
+}
+
+impl BlockFees {
+    fn push(&mut self, fee: u64) {
+        match self {
+            Self::All(fees) => fees.push(fee),
+            Self::Percentiles { .. } => unreachable!("fees are only added to uncompleted blocks"),
+        }
+    }
+
+    /// The number of fees
+    fn len(&self) -> usize {
+        match self {
+            Self::All(fees) => fees.len(),
+            Self::Percentiles { len, .. } => *len,
+        }
+    }
+
+    /// Sort the fees, and reduce them to their percentiles if there are more of them
+    fn complete(&mut self) {
+        let Self::All(fees) = self else {
+            return;
+        };
+        fees.sort_unstable();
+        if fees.len() > usize::from(MAX_PRIORITIZATION_FEE_PERCENTILE) + 1 {
+            let percentiles = (0..=MAX_PRIORITIZATION_FEE_PERCENTILE)
+                .filter_map(|percentile| fee_percentile(fees, percentile))
+                .collect();
+            *self = Self::Percentiles {
+                percentiles,
+                len: fees.len(),
+            };
+        }
+    }
+
+    /// The given percentile of the fees of a completed block
+    fn percentile(&self, percentile: u8) -> Option<u64> {
+        match self {
+            Self::All(fees) => fee_percentile(fees, percentile),
+            Self::Percentiles { percentiles, .. } => percentiles
+                .get(usize::from(
+                    percentile.min(MAX_PRIORITIZATION_FEE_PERCENTILE),
+                ))
+                .copied(),
+        }
+    }
+}
+
+/// Nearest-rank percentile of sorted fees, where the 0th percentile is the minimum fee
+fn fee_percentile(sorted_fees: &[u64], percentile: u8) -> Option<u64> {
+    let percentile = percentile.min(MAX_PRIORITIZATION_FEE_PERCENTILE) as usize;
+    let rank = sorted_fees
+        .len()
+        .saturating_mul(percentile)
+        .div_ceil(MAX_PRIORITIZATION_FEE_PERCENTILE as usize);
+    sorted_fees.get(rank.saturating_sub(1)).copied()
+}
+
 #[cfg(test)]
 mod tests {
     use {super::*, solana_sdk::pubkey::Pubkey};
@@ -389,4 +534,126 @@ mod tests {
         assert!(prioritization_fee.mark_block_completed().is_ok());
         assert!(prioritization_fee.mark_block_completed().is_err());
     }
+
+    #[test]
+    fn test_fee_percentile() {
+        assert_eq!(fee_percentile(&[], 50), None);
+        assert_eq!(fee_percentile(&[7], 0), Some(7));
+        assert_eq!(fee_percentile(&[7], 100), Some(7));
+
+        let fees: Vec<u64> = (1..=10).collect();
+        assert_eq!(fee_percentile(&fees, 0), Some(1));
+        assert_eq!(fee_percentile(&fees, 10), Some(1));
+        assert_eq!(fee_percentile(&fees, 11), Some(2));
+        assert_eq!(fee_percentile(&fees, 50), Some(5));
+        assert_eq!(fee_percentile(&fees, 75), Some(8));
+        assert_eq!(fee_percentile(&fees, 100), Some(10));
+        assert_eq!(fee_percentile(&fees, u8::MAX), Some(10));
+    }
+
+    #[test]
+    fn test_prioritization_fee_percentiles() {
+        let write_account_a = Pubkey::new_unique();
+        let write_account_b = Pubkey::new_unique();
+
+        let mut prioritization_fee = PrioritizationFee::default();
+        for (fee, write_accounts) in [
+            (5, vec![write_account_a]),
+            (1, vec![write_account_a, write_account_b]),
+            (9, vec![write_account_b]),
+            (3, vec![write_account_a]),
+        ] {
+            assert!(prioritization_fee.update(fee, &write_accounts).is_ok());
+        }
+        // Percentiles are only available once the block is completed
+        assert!(prioritization_fee
+            .get_transaction_fee_percentile(50)
+            .is_none());
+        assert!(prioritization_fee.mark_block_completed().is_ok());
+
+        assert_eq!(
+            prioritization_fee.get_transaction_fee_percentile(0),
+            Some(1)
+        );
+        assert_eq!(
+            prioritization_fee.get_transaction_fee_percentile(50),
+            Some(3)
+        );
+        assert_eq!(
+            prioritization_fee.get_transaction_fee_percentile(100),
+            Some(9)
+        );
+        // Account a was locked by transactions paying [1, 3, 5]
+        assert_eq!(
+            prioritization_fee.get_writable_account_fee_percentile(&write_account_a, 50),
+            Some(3)
+        );
+        assert_eq!(
+            prioritization_fee.get_writable_account_fee_percentile(&write_account_a, 100),
+            Some(5)
+        );
+        // Account b was locked by transactions paying [1, 9]; unlike its minimum fee, its
+        // distribution is kept even though its minimum fee equals the block's
+        assert_eq!(
+            prioritization_fee.get_writable_account_fee_percentile(&write_account_b, 75),
+            Some(9)
+        );
+        assert!(prioritization_fee
+            .get_writable_account_fee_percentile(&Pubkey::new_unique(), 50)
+            .is_none());
+    }
+
+    #[test]
+    fn test_prioritization_fee_percentiles_reduced() {
+        let write_account = Pubkey::new_unique();
+        let single_write_account = Pubkey::new_unique();
+        let mut prioritization_fee = PrioritizationFee::default();
+        // More fees than percentiles, in an order other than sorted
+        let fees: Vec<u64> = (0..1_000).map(|i| (i * 7) % 1_000 + 1).collect();
+        for fee in &fees {
+            assert!(prioritization_fee.update(*fee, &[write_account]).is_ok());
+        }
+        // More accounts locked by several transactions than are kept
+        let contended_accounts: Vec<_> = (0..=MAX_WRITABLE_ACCOUNTS_WITH_FEE_PERCENTILES)
+            .map(|_| Pubkey::new_unique())
+            .collect();
+        for account in &contended_accounts {
+            assert!(prioritization_fee.update(500, &[*account]).is_ok());
+            assert!(prioritization_fee.update(600, &[*account]).is_ok());
+        }
+        assert!(prioritization_fee
+            .update(700, &[single_write_account])
+            .is_ok());
+        assert!(prioritization_fee.mark_block_completed().is_ok());
+
+        let mut sorted_fees = fees;
+        sorted_fees.sort_unstable();
+        for percentile in 0..=u8::MAX {
+            assert_eq!(
+                prioritization_fee.get_writable_account_fee_percentile(&write_account, percentile),
+                fee_percentile(&sorted_fees, percentile)
+            );
+        }
+        assert!(matches!(
+            prioritization_fee.transaction_fees,
+            BlockFees::Percentiles { len, .. } if len == 1_000 + 2 * contended_accounts.len() + 1
+        ));
+        assert_eq!(
+            prioritization_fee.writable_account_fees.len(),
+            MAX_WRITABLE_ACCOUNTS_WITH_FEE_PERCENTILES
+        );
+        // The account locked by the most transactions is kept
+        assert!(prioritization_fee
+            .writable_account_fees
+            .contains_key(&write_account));
+        // Accounts whose fees were not kept fall back to their minimum fee
+        assert_eq!(
+            prioritization_fee.get_writable_account_fee_percentile(&single_write_account, 100),
+            Some(700)
+        );
+        for account in &contended_accounts {
+            let fee = prioritization_fee.get_writable_account_fee_percentile(account, 100);
+            assert!(fee == Some(500) || fee == Some(600));
+        }
+    }
 }
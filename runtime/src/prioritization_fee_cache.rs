@@ -426,6 +426,42 @@ impl PrioritizationFeeCache {
             .flatten()
             .collect()
     }
+
+    /// Like `get_prioritization_fees`, but from the given percentile of each block's fees rather
+    /// than its minimum fees: the fee of each block is the greater of the percentile of the fees
+    /// of all its transactions, and the percentiles of the fees of transactions that locked each
+    /// of `account_keys` as writable.
+    pub fn get_prioritization_fees_with_percentile(
+        &self,
+        account_keys: &[Pubkey],
+        percentile: u8,
+    ) -> HashMap<Slot, u64> {
+        self.cache
+            .read()
+            .unwrap()
+            .iter()
+            .filter_map(|(slot, slot_prioritization_fee)| {
+                slot_prioritization_fee
+                    .iter()
+                    .find(|prioritization_fee| prioritization_fee.is_finalized())
+                    .map(|prioritization_fee| {
+                        let fee = account_keys
+                            .iter()
+                            .filter_map(|account_key| {
+                                prioritization_fee
+                                    .get_writable_account_fee_percentile(account_key, percentile)
+                            })
+                            .fold(
+                                prioritization_fee
+                                    .get_transaction_fee_percentile(percentile)
+                                    .unwrap_or_default(),
+                                std::cmp::max,
+                            );
+                        (*slot, fee)
+                    })
+            })
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -851,6 +887,82 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_get_prioritization_fees_with_percentile() {
+        solana_logger::setup();
+        let write_account_a = Pubkey::new_unique();
+        let write_account_b = Pubkey::new_unique();
+        let write_account_c = Pubkey::new_unique();
+
+        let GenesisConfigInfo { genesis_config, .. } = create_genesis_config(10_000);
+        let bank0 = Bank::new_for_benches(&genesis_config);
+        let bank_forks = BankForks::new_rw_arc(bank0);
+        let bank = bank_forks.read().unwrap().working_bank();
+        let collector = solana_sdk::pubkey::new_rand();
+        let bank1 = Arc::new(Bank::new_from_parent(bank, &collector, 1));
+
+        let prioritization_fee_cache = PrioritizationFeeCache::default();
+        let txs = vec![
+            build_sanitized_transaction_for_test(8, &write_account_a, &write_account_b),
+            build_sanitized_transaction_for_test(6, &write_account_a, &write_account_c),
+            build_sanitized_transaction_for_test(1, &Pubkey::new_unique(), &Pubkey::new_unique()),
+            build_sanitized_transaction_for_test(2, &Pubkey::new_unique(), &Pubkey::new_unique()),
+        ];
+        sync_update(&prioritization_fee_cache, bank1.clone(), txs.iter());
+        // before block is marked as completed
+        assert!(prioritization_fee_cache
+            .get_prioritization_fees_with_percentile(&[], 50)
+            .is_empty());
+        sync_finalize_priority_fee_for_test(&prioritization_fee_cache, 1, bank1.bank_id());
+
+        // The block's transactions paid [1, 2, 6, 8]
+        assert_eq!(
+            hashmap_of(vec![(1, 1)]),
+            prioritization_fee_cache.get_prioritization_fees_with_percentile(&[], 0)
+        );
+        assert_eq!(
+            hashmap_of(vec![(1, 2)]),
+            prioritization_fee_cache.get_prioritization_fees_with_percentile(&[], 50)
+        );
+        assert_eq!(
+            hashmap_of(vec![(1, 8)]),
+            prioritization_fee_cache.get_prioritization_fees_with_percentile(&[], 100)
+        );
+        // Transactions locking account a paid [6, 8], its minimum fee of 6 is above the block's
+        // median
+        assert_eq!(
+            hashmap_of(vec![(1, 6)]),
+            prioritization_fee_cache
+                .get_prioritization_fees_with_percentile(&[write_account_a], 50)
+        );
+        assert_eq!(
+            hashmap_of(vec![(1, 8)]),
+            prioritization_fee_cache
+                .get_prioritization_fees_with_percentile(&[write_account_a, write_account_b], 50)
+        );
+        assert_eq!(
+            hashmap_of(vec![(1, 6)]),
+            prioritization_fee_cache
+                .get_prioritization_fees_with_percentile(&[write_account_c], 50)
+        );
+        assert_eq!(
+            hashmap_of(vec![(1, 2)]),
+            prioritization_fee_cache
+                .get_prioritization_fees_with_percentile(&[Pubkey::new_unique()], 50)
+        );
+        // The 0th percentile matches the minimum fees
+        for account_keys in [
+            vec![],
+            vec![write_account_a],
+            vec![write_account_b, write_account_c],
+        ] {
+            assert_eq!(
+                prioritization_fee_cache.get_prioritization_fees(&account_keys),
+                prioritization_fee_cache.get_prioritization_fees_with_percentile(&account_keys, 0)
+            );
+        }
+    }
+
     #[test]
     fn test_purge_duplicated_bank() {
         // duplicated bank can exists for same slot before OC.
@@ -110,10 +110,14 @@ pub fn add_bank_snapshot(
                     .map_err(AddBankSnapshotError::HardLinkStorages)?
             );
 
+        // Snapshots of storages holding compressed account data must not be
+        // loaded by versions which do not support it
+        let snapshot_version = snapshot_version.for_storages(snapshot_storages);
+
         let bank_snapshot_serializer =
             move |stream: &mut BufWriter<std::fs::File>| -> snapshot_utils::Result<()> {
                 let serde_style = match snapshot_version {
-                    SnapshotVersion::V1_2_0 => SerdeStyle::Newer,
+                    SnapshotVersion::V1_2_0 | SnapshotVersion::V1_3_0 => SerdeStyle::Newer,
                 };
                 bank_to_stream(
                     serde_style,
@@ -655,8 +659,10 @@ fn bank_fields_from_snapshots(
     deserialize_snapshot_data_files(&snapshot_root_paths, |snapshot_streams| {
         Ok(
             match incremental_snapshot_version.unwrap_or(full_snapshot_version) {
-                SnapshotVersion::V1_2_0 => fields_from_streams(SerdeStyle::Newer, snapshot_streams)
-                    .map(|(bank_fields, _accountsdb_fields)| bank_fields.collapse_into()),
+                SnapshotVersion::V1_2_0 | SnapshotVersion::V1_3_0 => {
+                    fields_from_streams(SerdeStyle::Newer, snapshot_streams)
+                        .map(|(bank_fields, _accountsdb_fields)| bank_fields.collapse_into())
+                }
             }?,
         )
     })
@@ -731,7 +737,7 @@ fn rebuild_bank_from_unarchived_snapshots(
     let bank = deserialize_snapshot_data_files(&snapshot_root_paths, |snapshot_streams| {
         Ok(
             match incremental_snapshot_version.unwrap_or(full_snapshot_version) {
-                SnapshotVersion::V1_2_0 => bank_from_streams(
+                SnapshotVersion::V1_2_0 | SnapshotVersion::V1_3_0 => bank_from_streams(
                     SerdeStyle::Newer,
                     snapshot_streams,
                     account_paths,
@@ -1270,7 +1276,7 @@ mod tests {
         },
         solana_accounts_db::{
             accounts_db::ACCOUNTS_DB_CONFIG_FOR_TESTING,
-            accounts_hash::{CalcAccountsHashConfig, HashStats},
+            accounts_hash::{AccountsLtHash, CalcAccountsHashConfig, HashStats},
             sorted_storages::SortedStorages,
         },
         solana_sdk::{
@@ -1467,6 +1473,100 @@ mod tests {
         assert_eq!(*bank4, roundtrip_bank);
     }
 
+    /// Test roundtrip of bank to a full snapshot, then back again, with the accounts lattice hash
+    /// feature active.  The accounts lattice hash is not in the snapshot, so the roundtrip bank
+    /// must calculate the same one from its accounts for its children to hash the same.
+    #[test]
+    fn test_roundtrip_bank_to_and_from_full_snapshot_accounts_lt_hash() {
+        let collector = Pubkey::new_unique();
+        let key1 = Keypair::new();
+        let key2 = Keypair::new();
+
+        let (mut genesis_config, mint_keypair) = create_genesis_config(sol_to_lamports(1_000_000.));
+        genesis_utils::activate_feature(&mut genesis_config, feature_set::accounts_lt_hash::id());
+        let (bank0, bank_forks) = Bank::new_with_bank_forks_for_tests(&genesis_config);
+        assert!(bank0.is_accounts_lt_hash_enabled());
+        bank0
+            .transfer(sol_to_lamports(1.), &mint_keypair, &key1.pubkey())
+            .unwrap();
+        while !bank0.is_complete() {
+            bank0.register_unique_tick();
+        }
+
+        let slot = 1;
+        let bank1 =
+            new_bank_from_parent_with_bank_forks(bank_forks.as_ref(), bank0, &collector, slot);
+        bank1
+            .transfer(sol_to_lamports(2.), &mint_keypair, &key2.pubkey())
+            .unwrap();
+        while !bank1.is_complete() {
+            bank1.register_unique_tick();
+        }
+        bank1.freeze();
+
+        let (_tmp_dir, accounts_dir) = create_tmp_accounts_dir_for_tests();
+        let bank_snapshots_dir = tempfile::TempDir::new().unwrap();
+        let full_snapshot_archives_dir = tempfile::TempDir::new().unwrap();
+        let incremental_snapshot_archives_dir = tempfile::TempDir::new().unwrap();
+        let snapshot_archive_format = ArchiveFormat::Tar;
+
+        let full_snapshot_archive_info = bank_to_full_snapshot_archive(
+            bank_snapshots_dir.path(),
+            &bank1,
+            None,
+            full_snapshot_archives_dir.path(),
+            incremental_snapshot_archives_dir.path(),
+            snapshot_archive_format,
+            snapshot_utils::DEFAULT_MAX_FULL_SNAPSHOT_ARCHIVES_TO_RETAIN,
+            snapshot_utils::DEFAULT_MAX_INCREMENTAL_SNAPSHOT_ARCHIVES_TO_RETAIN,
+        )
+        .unwrap();
+
+        let (roundtrip_bank, _) = bank_from_snapshot_archives(
+            &[accounts_dir],
+            bank_snapshots_dir.path(),
+            &full_snapshot_archive_info,
+            None,
+            &genesis_config,
+            &RuntimeConfig::default(),
+            None,
+            None,
+            AccountSecondaryIndexes::default(),
+            None,
+            AccountShrinkThreshold::default(),
+            false,
+            false,
+            false,
+            false,
+            Some(ACCOUNTS_DB_CONFIG_FOR_TESTING),
+            None,
+            Arc::default(),
+        )
+        .unwrap();
+        roundtrip_bank.wait_for_initial_accounts_hash_verification_completed_for_tests();
+        assert_ne!(
+            roundtrip_bank.accounts_lt_hash(),
+            AccountsLtHash::identity()
+        );
+        assert_eq!(roundtrip_bank.accounts_lt_hash(), bank1.accounts_lt_hash());
+        assert_eq!(*bank1, roundtrip_bank);
+
+        // the same block on top of either bank hashes the same
+        let slot = slot + 1;
+        let [bank2, roundtrip_bank2] = [bank1, Arc::new(roundtrip_bank)].map(|parent| {
+            let bank = Bank::new_from_parent(parent, &collector, slot);
+            bank.transfer(sol_to_lamports(3.), &mint_keypair, &key1.pubkey())
+                .unwrap();
+            while !bank.is_complete() {
+                bank.register_unique_tick();
+            }
+            bank.freeze();
+            bank
+        });
+        assert_eq!(roundtrip_bank2.accounts_lt_hash(), bank2.accounts_lt_hash());
+        assert_eq!(roundtrip_bank2.hash(), bank2.hash());
+    }
+
     /// Test roundtrip of bank to snapshots, then back again, with incremental snapshots.  In this
     /// version, build up a few slots and take a full snapshot.  Continue on a few more slots and
     /// take an incremental snapshot.  Rebuild the bank from both the incremental snapshot and full
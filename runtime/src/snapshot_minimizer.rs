@@ -12,6 +12,7 @@ use {
         accounts_db::{
             AccountStorageEntry, AccountsDb, GetUniqueAccountsResult, PurgeStats, StoreReclaims,
         },
+        accounts_index::ScanConfig,
         accounts_partition,
     },
     solana_measure::measure,
@@ -60,11 +61,7 @@ impl<'a> SnapshotMinimizer<'a> {
             minimized_account_set: transaction_account_set,
         };
 
-        minimizer.add_accounts(Self::get_active_bank_features, "active bank features");
-        minimizer.add_accounts(Self::get_inactive_bank_features, "inactive bank features");
-        minimizer.add_accounts(Self::get_builtins, "builtin accounts");
-        minimizer.add_accounts(Self::get_static_runtime_accounts, "static runtime accounts");
-        minimizer.add_accounts(Self::get_sdk_accounts, "sdk accounts");
+        minimizer.add_runtime_accounts();
 
         minimizer.add_accounts(
             Self::get_rent_collection_accounts,
@@ -75,11 +72,61 @@ impl<'a> SnapshotMinimizer<'a> {
         minimizer.add_accounts(Self::get_owner_accounts, "owner accounts");
         minimizer.add_accounts(Self::get_programdata_accounts, "programdata accounts");
 
-        minimizer.minimize_accounts_db();
+        minimizer.finish();
+    }
+
+    /// Removes all accounts except the `program_ids` programs, the accounts they own, the
+    /// `additional_account_set` accounts, and the accounts needed to boot a validator from the
+    /// bank: sysvars, features, builtins, and the vote and stake accounts. Accounts written in
+    /// the bank's own slot are kept as well.
+    ///
+    /// This produces a "dev snapshot" of e.g. mainnet state, small enough to quickly boot a test
+    /// validator for developing against the selected programs.
+    ///
+    /// This function will modify accounts_db by removing all other accounts, and update the
+    /// bank's capitalization.
+    pub fn minimize_to_programs(
+        bank: &'a Bank,
+        program_ids: &[Pubkey],
+        additional_account_set: DashSet<Pubkey>,
+    ) {
+        let minimizer = SnapshotMinimizer {
+            bank,
+            starting_slot: bank.slot(),
+            ending_slot: bank.slot(),
+            minimized_account_set: additional_account_set,
+        };
+
+        minimizer.add_accounts(
+            |minimizer| minimizer.get_program_accounts(program_ids),
+            "program accounts",
+        );
+        minimizer.add_runtime_accounts();
+
+        minimizer.add_accounts(Self::get_vote_accounts, "vote accounts");
+        minimizer.add_accounts(Self::get_stake_accounts, "stake accounts");
+        minimizer.add_accounts(Self::get_owner_accounts, "owner accounts");
+        minimizer.add_accounts(Self::get_programdata_accounts, "programdata accounts");
+
+        minimizer.finish();
+    }
+
+    /// Adds the accounts the runtime needs regardless of the transactions processed
+    fn add_runtime_accounts(&self) {
+        self.add_accounts(Self::get_active_bank_features, "active bank features");
+        self.add_accounts(Self::get_inactive_bank_features, "inactive bank features");
+        self.add_accounts(Self::get_builtins, "builtin accounts");
+        self.add_accounts(Self::get_static_runtime_accounts, "static runtime accounts");
+        self.add_accounts(Self::get_sdk_accounts, "sdk accounts");
+    }
+
+    /// Removes the accounts which were not added from accounts_db
+    fn finish(&self) {
+        self.minimize_accounts_db();
 
         // Update accounts_cache and capitalization
-        minimizer.bank.force_flush_accounts_cache();
-        minimizer.bank.set_capitalization();
+        self.bank.force_flush_accounts_cache();
+        self.bank.set_capitalization();
     }
 
     /// Helper function to measure time and number of accounts added
@@ -97,6 +144,21 @@ impl<'a> SnapshotMinimizer<'a> {
         );
     }
 
+    /// Used to get program accounts in `minimize_to_programs`
+    /// Add the `program_ids` and all the accounts they own to `minimized_account_set`.
+    fn get_program_accounts(&self, program_ids: &[Pubkey]) {
+        program_ids.iter().for_each(|program_id| {
+            self.minimized_account_set.insert(*program_id);
+            self.bank
+                .get_program_accounts(program_id, &ScanConfig::default())
+                .expect("scanning program accounts cannot be aborted")
+                .into_par_iter()
+                .for_each(|(pubkey, _account)| {
+                    self.minimized_account_set.insert(pubkey);
+                });
+        });
+    }
+
     /// Used to get active bank feature accounts in `minimize`.
     fn get_active_bank_features(&self) {
         self.bank.feature_set.active.iter().for_each(|(pubkey, _)| {
@@ -556,6 +618,39 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_minimization_get_program_accounts() {
+        solana_logger::setup();
+
+        let (genesis_config, _) = create_genesis_config(1_000_000);
+        let bank = Arc::new(Bank::new_for_tests(&genesis_config));
+
+        let program_id = solana_sdk::pubkey::new_rand();
+        let program_account_pubkey = solana_sdk::pubkey::new_rand();
+        let other_account_pubkey = solana_sdk::pubkey::new_rand();
+        bank.store_account(
+            &program_account_pubkey,
+            &AccountSharedData::new(1, 0, &program_id),
+        );
+        bank.store_account(
+            &other_account_pubkey,
+            &AccountSharedData::new(1, 0, &solana_sdk::pubkey::new_rand()),
+        );
+
+        let minimizer = SnapshotMinimizer {
+            bank: &bank,
+            starting_slot: 0,
+            ending_slot: 0,
+            minimized_account_set: DashSet::new(),
+        };
+        minimizer.get_program_accounts(&[program_id]);
+        assert_eq!(minimizer.minimized_account_set.len(), 2);
+        assert!(minimizer.minimized_account_set.contains(&program_id));
+        assert!(minimizer
+            .minimized_account_set
+            .contains(&program_account_pubkey));
+    }
+
     #[test]
     fn test_minimization_get_owner_accounts() {
         solana_logger::setup();
@@ -9,7 +9,7 @@ use {
     solana_accounts_db::{
         accounts::Accounts,
         accounts_db::{AccountStorageEntry, AccountsDb},
-        accounts_hash::{AccountsHash, AccountsHashKind},
+        accounts_hash::{AccountsHash, AccountsHashKind, AccountsLtHash},
         epoch_accounts_hash::EpochAccountsHash,
     },
     solana_sdk::{
@@ -38,6 +38,9 @@ pub struct AccountsPackage {
     pub epoch_schedule: EpochSchedule,
     pub rent_collector: RentCollector,
     pub is_incremental_accounts_hash_feature_enabled: bool,
+    /// The accounts lattice hash of the bank, if the `accounts_lt_hash`
+    /// feature is active, to cross-check against the snapshot storages
+    pub accounts_lt_hash: Option<AccountsLtHash>,
 
     /// Supplemental information needed for snapshots
     pub snapshot_info: Option<SupplementalSnapshotInfo>,
@@ -153,6 +156,9 @@ impl AccountsPackage {
             epoch_schedule: bank.epoch_schedule().clone(),
             rent_collector: bank.rent_collector().clone(),
             is_incremental_accounts_hash_feature_enabled,
+            accounts_lt_hash: bank
+                .is_accounts_lt_hash_enabled()
+                .then(|| bank.accounts_lt_hash()),
             snapshot_info,
             enqueued: Instant::now(),
         }
@@ -174,6 +180,7 @@ impl AccountsPackage {
             epoch_schedule: EpochSchedule::default(),
             rent_collector: RentCollector::default(),
             is_incremental_accounts_hash_feature_enabled: bool::default(),
+            accounts_lt_hash: Option::default(),
             snapshot_info: Some(SupplementalSnapshotInfo {
                 bank_snapshot_dir: PathBuf::default(),
                 archive_format: ArchiveFormat::Tar,
@@ -61,8 +61,11 @@ pub const SNAPSHOT_ARCHIVE_DOWNLOAD_DIR: &str = "remote";
 pub const MAX_SNAPSHOT_DATA_FILE_SIZE: u64 = 32 * 1024 * 1024 * 1024; // 32 GiB
 const MAX_SNAPSHOT_VERSION_FILE_SIZE: u64 = 8; // byte
 const VERSION_STRING_V1_2_0: &str = "1.2.0";
+const VERSION_STRING_V1_3_0: &str = "1.3.0";
 pub const TMP_SNAPSHOT_ARCHIVE_PREFIX: &str = "tmp-snapshot-archive-";
 pub const BANK_SNAPSHOT_PRE_FILENAME_EXTENSION: &str = "pre";
+/// Size of the buffer that snapshot archives are streamed through on their way to disk
+const ARCHIVE_WRITE_BUFFER_SIZE: usize = 4 * 1024 * 1024;
 // The following unsafes are
 // - Safe because the values are fixed, known non-zero constants
 // - Necessary in order to have a plain NonZeroUsize as the constant, NonZeroUsize
@@ -78,6 +81,8 @@ pub const INCREMENTAL_SNAPSHOT_ARCHIVE_FILENAME_REGEX: &str = r"^incremental-sna
 pub enum SnapshotVersion {
     #[default]
     V1_2_0,
+    /// Same as V1_2_0, but the account storages may hold compressed account data
+    V1_3_0,
 }
 
 impl fmt::Display for SnapshotVersion {
@@ -90,6 +95,7 @@ impl From<SnapshotVersion> for &'static str {
     fn from(snapshot_version: SnapshotVersion) -> &'static str {
         match snapshot_version {
             SnapshotVersion::V1_2_0 => VERSION_STRING_V1_2_0,
+            SnapshotVersion::V1_3_0 => VERSION_STRING_V1_3_0,
         }
     }
 }
@@ -109,6 +115,7 @@ impl FromStr for SnapshotVersion {
         };
         match version_string {
             VERSION_STRING_V1_2_0 => Ok(SnapshotVersion::V1_2_0),
+            VERSION_STRING_V1_3_0 => Ok(SnapshotVersion::V1_3_0),
             _ => Err("unsupported snapshot version"),
         }
     }
@@ -118,6 +125,20 @@ impl SnapshotVersion {
     pub fn as_str(self) -> &'static str {
         <&str as From<Self>>::from(self)
     }
+
+    /// The version of a snapshot of `snapshot_storages`: V1_3_0 if any of
+    /// them hold compressed account data, which versions only supporting
+    /// V1_2_0 cannot read, and `self` otherwise
+    pub fn for_storages(self, snapshot_storages: &[Arc<AccountStorageEntry>]) -> Self {
+        if snapshot_storages
+            .iter()
+            .any(|storage| storage.accounts.has_compressed_accounts())
+        {
+            SnapshotVersion::V1_3_0
+        } else {
+            self
+        }
+    }
 }
 
 /// Information about a bank snapshot. Namely the slot of the bank, the path to the snapshot, and
@@ -489,6 +510,9 @@ pub enum ArchiveSnapshotPackageError {
     #[error("failed to encode archive: {0}")]
     FinishEncoder(#[source] IoError),
 
+    #[error("failed to flush archive file: {0}")]
+    FlushArchiveFile(#[source] IoError),
+
     #[error("failed to query archive metadata '{1}': {0}")]
     QueryArchiveMetadata(#[source] IoError, PathBuf),
 
@@ -757,8 +781,9 @@ pub fn archive_snapshot_package(
     ));
 
     {
-        let mut archive_file = fs::File::create(&archive_path)
+        let archive_file = fs::File::create(&archive_path)
             .map_err(|err| E::CreateArchiveFile(err, archive_path.clone()))?;
+        let mut archive_file = BufWriter::with_capacity(ARCHIVE_WRITE_BUFFER_SIZE, archive_file);
 
         let do_archive_files = |encoder: &mut dyn Write| -> std::result::Result<(), E> {
             let mut archive = tar::Builder::new(encoder);
@@ -777,24 +802,28 @@ pub fn archive_snapshot_package(
             Ok(())
         };
 
-        match snapshot_package.archive_format() {
+        let mut archive_file = match snapshot_package.archive_format() {
             ArchiveFormat::TarBzip2 => {
                 let mut encoder =
                     bzip2::write::BzEncoder::new(archive_file, bzip2::Compression::best());
                 do_archive_files(&mut encoder)?;
-                encoder.finish().map_err(E::FinishEncoder)?;
+                encoder.finish().map_err(E::FinishEncoder)?
             }
             ArchiveFormat::TarGzip => {
                 let mut encoder =
                     flate2::write::GzEncoder::new(archive_file, flate2::Compression::default());
                 do_archive_files(&mut encoder)?;
-                encoder.finish().map_err(E::FinishEncoder)?;
+                encoder.finish().map_err(E::FinishEncoder)?
             }
             ArchiveFormat::TarZstd => {
                 let mut encoder =
                     zstd::stream::Encoder::new(archive_file, 0).map_err(E::CreateEncoder)?;
+                // compress on worker threads while this thread keeps reading the storages
+                encoder
+                    .multithread(zstd_compression_workers())
+                    .map_err(E::CreateEncoder)?;
                 do_archive_files(&mut encoder)?;
-                encoder.finish().map_err(E::FinishEncoder)?;
+                encoder.finish().map_err(E::FinishEncoder)?
             }
             ArchiveFormat::TarLz4 => {
                 let mut encoder = lz4::EncoderBuilder::new()
@@ -802,13 +831,16 @@ pub fn archive_snapshot_package(
                     .build(archive_file)
                     .map_err(E::CreateEncoder)?;
                 do_archive_files(&mut encoder)?;
-                let (_output, result) = encoder.finish();
+                let (output, result) = encoder.finish();
                 result.map_err(E::FinishEncoder)?;
+                output
             }
             ArchiveFormat::Tar => {
                 do_archive_files(&mut archive_file)?;
+                archive_file
             }
         };
+        archive_file.flush().map_err(E::FlushArchiveFile)?;
     }
 
     // Atomically move the archive into position for other validators to find
@@ -1954,10 +1986,42 @@ fn unpack_snapshot_local(
     Ok(unpacked_append_vec_map)
 }
 
+/// Number of worker threads compressing a zstd snapshot archive
+fn zstd_compression_workers() -> u32 {
+    (num_cpus::get() / 4).clamp(1, 16) as u32
+}
+
+/// Detect the format of the archive at `snapshot_tar` from its contents.
+/// Falls back to `archive_format`, which is usually derived from the file name,
+/// if the archive cannot be read or its format is not recognized.
+fn detect_archive_format(snapshot_tar: &Path, archive_format: ArchiveFormat) -> ArchiveFormat {
+    let mut magic_bytes = Vec::with_capacity(ARCHIVE_FORMAT_MAGIC_LEN);
+    let detected_format = fs::File::open(snapshot_tar)
+        .and_then(|file| {
+            file.take(ARCHIVE_FORMAT_MAGIC_LEN as u64)
+                .read_to_end(&mut magic_bytes)
+        })
+        .ok()
+        .and_then(|_| ArchiveFormat::from_magic_bytes(&magic_bytes));
+    match detected_format {
+        Some(detected_format) => {
+            if detected_format != archive_format {
+                warn!(
+                    "snapshot archive '{}' is {detected_format}, not {archive_format}",
+                    snapshot_tar.display(),
+                );
+            }
+            detected_format
+        }
+        None => archive_format,
+    }
+}
+
 fn untar_snapshot_create_shared_buffer(
     snapshot_tar: &Path,
     archive_format: ArchiveFormat,
 ) -> SharedBuffer {
+    let archive_format = detect_archive_format(snapshot_tar, archive_format);
     let open_file = || {
         fs::File::open(snapshot_tar)
             .map_err(|err| {
@@ -2404,6 +2468,25 @@ mod tests {
         assert_eq!(version_from_file, file_content);
     }
 
+    #[test]
+    fn test_snapshot_version_from_str() {
+        for snapshot_version in [SnapshotVersion::V1_2_0, SnapshotVersion::V1_3_0] {
+            assert_eq!(
+                SnapshotVersion::from_str(snapshot_version.as_str()),
+                Ok(snapshot_version)
+            );
+        }
+        assert_eq!(
+            SnapshotVersion::from_str("v1.3.0"),
+            Ok(SnapshotVersion::V1_3_0)
+        );
+        assert!(SnapshotVersion::from_str("1.4.0").is_err());
+        assert_eq!(
+            SnapshotVersion::V1_2_0.for_storages(&[]),
+            SnapshotVersion::V1_2_0
+        );
+    }
+
     #[test]
     fn test_snapshot_version_from_file_over_limit() {
         let over_limit_size = usize::try_from(MAX_SNAPSHOT_VERSION_FILE_SIZE + 1).unwrap();
@@ -2416,6 +2499,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_detect_archive_format() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let archive_path = temp_dir.path().join("archive");
+        let mut encoder =
+            zstd::stream::Encoder::new(fs::File::create(&archive_path).unwrap(), 0).unwrap();
+        encoder.write_all(b"snapshot").unwrap();
+        encoder.finish().unwrap();
+        assert_eq!(
+            detect_archive_format(&archive_path, ArchiveFormat::TarLz4),
+            ArchiveFormat::TarZstd
+        );
+
+        // fall back to the given format if the contents are not recognized
+        fs::write(&archive_path, b"snapshot").unwrap();
+        assert_eq!(
+            detect_archive_format(&archive_path, ArchiveFormat::TarLz4),
+            ArchiveFormat::TarLz4
+        );
+        // or cannot be read
+        assert_eq!(
+            detect_archive_format(&temp_dir.path().join("missing"), ArchiveFormat::Tar),
+            ArchiveFormat::Tar
+        );
+    }
+
     #[test]
     fn test_parse_full_snapshot_archive_filename() {
         assert_eq!(
@@ -18,6 +18,16 @@ pub const TAR_ZSTD_EXTENSION: &str = "tar.zst";
 pub const TAR_LZ4_EXTENSION: &str = "tar.lz4";
 pub const TAR_EXTENSION: &str = "tar";
 
+const BZIP2_MAGIC: &[u8] = b"BZh";
+const GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+const ZSTD_MAGIC: &[u8] = &[0x28, 0xb5, 0x2f, 0xfd];
+const LZ4_MAGIC: &[u8] = &[0x04, 0x22, 0x4d, 0x18];
+const TAR_MAGIC: &[u8] = b"ustar";
+const TAR_MAGIC_OFFSET: usize = 257;
+
+/// Number of leading bytes of an archive needed to detect its format
+pub const ARCHIVE_FORMAT_MAGIC_LEN: usize = TAR_MAGIC_OFFSET + TAR_MAGIC.len();
+
 /// The different archive formats used for snapshots
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Display)]
 pub enum ArchiveFormat {
@@ -47,6 +57,27 @@ impl ArchiveFormat {
             _ => None,
         }
     }
+
+    /// Detect the ArchiveFormat from the leading bytes of an archive,
+    /// which should be at least ARCHIVE_FORMAT_MAGIC_LEN long to detect plain tar archives
+    pub fn from_magic_bytes(bytes: &[u8]) -> Option<ArchiveFormat> {
+        if bytes.starts_with(ZSTD_MAGIC) {
+            Some(ArchiveFormat::TarZstd)
+        } else if bytes.starts_with(LZ4_MAGIC) {
+            Some(ArchiveFormat::TarLz4)
+        } else if bytes.starts_with(GZIP_MAGIC) {
+            Some(ArchiveFormat::TarGzip)
+        } else if bytes.starts_with(BZIP2_MAGIC) {
+            Some(ArchiveFormat::TarBzip2)
+        } else if bytes
+            .get(TAR_MAGIC_OFFSET..)
+            .map_or(false, |bytes| bytes.starts_with(TAR_MAGIC))
+        {
+            Some(ArchiveFormat::Tar)
+        } else {
+            None
+        }
+    }
 }
 
 // Change this to `impl<S: AsRef<str>> TryFrom<S> for ArchiveFormat [...]`
@@ -169,4 +200,33 @@ mod tests {
 
         assert_eq!(ArchiveFormat::from_cli_arg("bad"), None);
     }
+
+    #[test]
+    fn test_from_magic_bytes() {
+        for (magic, expected) in [
+            (BZIP2_MAGIC, ArchiveFormat::TarBzip2),
+            (GZIP_MAGIC, ArchiveFormat::TarGzip),
+            (ZSTD_MAGIC, ArchiveFormat::TarZstd),
+            (LZ4_MAGIC, ArchiveFormat::TarLz4),
+        ] {
+            let mut bytes = magic.to_vec();
+            bytes.resize(ARCHIVE_FORMAT_MAGIC_LEN, 0);
+            assert_eq!(ArchiveFormat::from_magic_bytes(&bytes), Some(expected));
+            assert_eq!(ArchiveFormat::from_magic_bytes(magic), Some(expected));
+        }
+
+        let mut tar_bytes = vec![0; TAR_MAGIC_OFFSET];
+        tar_bytes.extend_from_slice(b"ustar ");
+        assert_eq!(
+            ArchiveFormat::from_magic_bytes(&tar_bytes),
+            Some(ArchiveFormat::Tar)
+        );
+        // too short to contain the tar magic
+        assert_eq!(
+            ArchiveFormat::from_magic_bytes(&tar_bytes[..TAR_MAGIC_OFFSET]),
+            None
+        );
+        assert_eq!(ArchiveFormat::from_magic_bytes(&[]), None);
+        assert_eq!(ArchiveFormat::from_magic_bytes(b"PK\x03\x04"), None);
+    }
 }
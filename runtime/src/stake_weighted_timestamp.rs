@@ -14,15 +14,127 @@ use std::{
 const _MAX_ALLOWABLE_DRIFT_PERCENTAGE: u32 = 50;
 const _MAX_ALLOWABLE_DRIFT_PERCENTAGE_SLOW: u32 = 80;
 
-pub(crate) const MAX_ALLOWABLE_DRIFT_PERCENTAGE_FAST: u32 = 25;
-pub(crate) const MAX_ALLOWABLE_DRIFT_PERCENTAGE_SLOW_V2: u32 = 150;
+pub const MAX_ALLOWABLE_DRIFT_PERCENTAGE_FAST: u32 = 25;
+pub const MAX_ALLOWABLE_DRIFT_PERCENTAGE_SLOW_V2: u32 = 150;
 
-#[derive(Copy, Clone)]
-pub(crate) struct MaxAllowableDrift {
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct MaxAllowableDrift {
     pub fast: u32, // Max allowable drift percentage faster than poh estimate
     pub slow: u32, // Max allowable drift percentage slower than poh estimate
 }
 
+/// The timestamps of recent votes, projected to the same slot and weighted by
+/// the stake of their voters
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StakeWeightedTimestamps {
+    stake_per_timestamp: BTreeMap<UnixTimestamp, u128>,
+    total_stake: u128,
+}
+
+impl StakeWeightedTimestamps {
+    /// Projects each vote timestamp to `slot` at `slot_duration` per slot
+    pub fn new<I, K, V, T>(
+        unique_timestamps: I,
+        stakes: &HashMap<Pubkey, (u64, T /*Account|VoteAccount*/)>,
+        slot: Slot,
+        slot_duration: Duration,
+    ) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Borrow<Pubkey>,
+        V: Borrow<(Slot, UnixTimestamp)>,
+    {
+        let mut stake_per_timestamp: BTreeMap<UnixTimestamp, u128> = BTreeMap::new();
+        let mut total_stake: u128 = 0;
+        for (vote_pubkey, slot_timestamp) in unique_timestamps {
+            let (timestamp_slot, timestamp) = slot_timestamp.borrow();
+            let offset = slot_duration.saturating_mul(slot.saturating_sub(*timestamp_slot) as u32);
+            let estimate = timestamp.saturating_add(offset.as_secs() as i64);
+            let stake = stakes
+                .get(vote_pubkey.borrow())
+                .map(|(stake, _account)| stake)
+                .unwrap_or(&0);
+            stake_per_timestamp
+                .entry(estimate)
+                .and_modify(|stake_sum| *stake_sum = stake_sum.saturating_add(*stake as u128))
+                .or_insert(*stake as u128);
+            total_stake = total_stake.saturating_add(*stake as u128);
+        }
+        Self {
+            stake_per_timestamp,
+            total_stake,
+        }
+    }
+
+    /// Total stake of the voters of the timestamps
+    pub fn total_stake(&self) -> u128 {
+        self.total_stake
+    }
+
+    /// Returns the earliest timestamp such that more than `percentile`% of
+    /// the stake voted it or an earlier timestamp, or `None` if there is none
+    pub fn percentile(&self, percentile: u8) -> Option<UnixTimestamp> {
+        let threshold = self
+            .total_stake
+            .saturating_mul(u128::from(percentile.min(100)))
+            / 100;
+        let mut stake_accumulator: u128 = 0;
+        self.stake_per_timestamp
+            .iter()
+            .find(|(_timestamp, stake)| {
+                stake_accumulator = stake_accumulator.saturating_add(**stake);
+                stake_accumulator > threshold
+            })
+            .map(|(timestamp, _stake)| *timestamp)
+    }
+
+    /// Returns the stake-weighted median timestamp
+    pub fn median(&self) -> Option<UnixTimestamp> {
+        self.percentile(50)
+    }
+}
+
+/// How far the clock of a bank is from the stake-weighted vote timestamps,
+/// and from the PoH estimate since the start of the epoch
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ClockDriftEstimate {
+    /// `unix_timestamp` of the clock sysvar
+    pub unix_timestamp: UnixTimestamp,
+    /// The start of the epoch plus the nominal duration of the slots since
+    pub poh_timestamp: UnixTimestamp,
+    /// The stake-weighted 25th percentile of the vote timestamps
+    pub lower_quartile: UnixTimestamp,
+    /// The stake-weighted median of the vote timestamps, before the drift
+    /// bounds are applied
+    pub median: UnixTimestamp,
+    /// The stake-weighted 75th percentile of the vote timestamps
+    pub upper_quartile: UnixTimestamp,
+    /// Stake of the voters with a recent timestamp
+    pub voted_stake: u64,
+    /// Total stake of the epoch
+    pub total_stake: u64,
+}
+
+impl ClockDriftEstimate {
+    /// Seconds by which `unix_timestamp` is ahead of the median vote
+    /// timestamp, negative if behind
+    pub fn skew(&self) -> i64 {
+        self.unix_timestamp.saturating_sub(self.median)
+    }
+
+    /// Seconds by which `unix_timestamp` is ahead of the PoH estimate,
+    /// negative if behind
+    pub fn poh_drift(&self) -> i64 {
+        self.unix_timestamp.saturating_sub(self.poh_timestamp)
+    }
+
+    /// Seconds between the quartiles of the vote timestamps, within which
+    /// half of the voted stake lies
+    pub fn interquartile_range(&self) -> i64 {
+        self.upper_quartile.saturating_sub(self.lower_quartile)
+    }
+}
+
 pub(crate) fn calculate_stake_weighted_timestamp<I, K, V, T>(
     unique_timestamps: I,
     stakes: &HashMap<Pubkey, (u64, T /*Account|VoteAccount*/)>,
@@ -37,35 +149,9 @@ where
     K: Borrow<Pubkey>,
     V: Borrow<(Slot, UnixTimestamp)>,
 {
-    let mut stake_per_timestamp: BTreeMap<UnixTimestamp, u128> = BTreeMap::new();
-    let mut total_stake: u128 = 0;
-    for (vote_pubkey, slot_timestamp) in unique_timestamps {
-        let (timestamp_slot, timestamp) = slot_timestamp.borrow();
-        let offset = slot_duration.saturating_mul(slot.saturating_sub(*timestamp_slot) as u32);
-        let estimate = timestamp.saturating_add(offset.as_secs() as i64);
-        let stake = stakes
-            .get(vote_pubkey.borrow())
-            .map(|(stake, _account)| stake)
-            .unwrap_or(&0);
-        stake_per_timestamp
-            .entry(estimate)
-            .and_modify(|stake_sum| *stake_sum = stake_sum.saturating_add(*stake as u128))
-            .or_insert(*stake as u128);
-        total_stake = total_stake.saturating_add(*stake as u128);
-    }
-    if total_stake == 0 {
-        return None;
-    }
-    let mut stake_accumulator: u128 = 0;
-    let mut estimate = 0;
+    let timestamps = StakeWeightedTimestamps::new(unique_timestamps, stakes, slot, slot_duration);
     // Populate `estimate` with stake-weighted median timestamp
-    for (timestamp, stake) in stake_per_timestamp.into_iter() {
-        stake_accumulator = stake_accumulator.saturating_add(stake);
-        if stake_accumulator > total_stake / 2 {
-            estimate = timestamp;
-            break;
-        }
-    }
+    let mut estimate = timestamps.median()?;
     // Bound estimate by `max_allowable_drift` since the start of the epoch
     if let Some((epoch_start_slot, epoch_start_timestamp)) = epoch_start_timestamp {
         let poh_estimate_offset =
@@ -809,4 +895,45 @@ pub mod tests {
         .unwrap();
         assert_eq!(bounded, poh_estimate - acceptable_delta);
     }
+
+    #[test]
+    fn test_stake_weighted_timestamps_percentile() {
+        let slot = 10;
+        let slot_duration = Duration::from_millis(400);
+        let pubkeys: Vec<_> = (0..4).map(|_| solana_sdk::pubkey::new_rand()).collect();
+        let stakes: HashMap<Pubkey, (u64, Account)> = pubkeys
+            .iter()
+            .zip([10, 20, 30, 40])
+            .map(|(pubkey, stake)| (*pubkey, (stake, Account::default())))
+            .collect();
+
+        let timestamps = StakeWeightedTimestamps::new(
+            HashMap::<Pubkey, (Slot, UnixTimestamp)>::new(),
+            &stakes,
+            slot,
+            slot_duration,
+        );
+        assert_eq!(timestamps.total_stake(), 0);
+        assert_eq!(timestamps.median(), None);
+
+        // Timestamps are projected to `slot`, 4 seconds after slot 0
+        let unique_timestamps: HashMap<Pubkey, (Slot, UnixTimestamp)> = [
+            (pubkeys[0], (10, 100)),
+            (pubkeys[1], (0, 97)),
+            (pubkeys[2], (10, 102)),
+            (pubkeys[3], (10, 103)),
+            (solana_sdk::pubkey::new_rand(), (10, 0)),
+        ]
+        .into_iter()
+        .collect();
+        let timestamps =
+            StakeWeightedTimestamps::new(&unique_timestamps, &stakes, slot, slot_duration);
+        assert_eq!(timestamps.total_stake(), 100);
+        assert_eq!(timestamps.percentile(0), Some(100));
+        assert_eq!(timestamps.percentile(10), Some(101));
+        assert_eq!(timestamps.percentile(25), Some(101));
+        assert_eq!(timestamps.median(), Some(102));
+        assert_eq!(timestamps.percentile(75), Some(103));
+        assert_eq!(timestamps.percentile(100), None);
+    }
 }
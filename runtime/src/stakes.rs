@@ -19,7 +19,7 @@ use {
     solana_vote::vote_account::{VoteAccount, VoteAccounts},
     std::{
         collections::{HashMap, HashSet},
-        ops::Add,
+        ops::{Add, Range},
         sync::{Arc, RwLock, RwLockReadGuard},
     },
     thiserror::Error,
@@ -62,6 +62,38 @@ impl StakesCache {
         self.0.read().unwrap()
     }
 
+    /// Records the stake history entries of `epochs`, in order, as calculated
+    /// from the stake delegations and the entries of the previous epochs.
+    /// Each entry is passed through `override_entry` before being recorded.
+    pub(crate) fn synthesize_stake_history<F>(
+        &self,
+        epochs: Range<Epoch>,
+        new_rate_activation_epoch: Option<Epoch>,
+        mut override_entry: F,
+    ) where
+        F: FnMut(Epoch, StakeActivationStatus) -> StakeActivationStatus,
+    {
+        let mut stakes = self.0.write().unwrap();
+        let stakes = &mut *stakes;
+        for epoch in epochs {
+            let entry = stakes.stake_delegations.values().fold(
+                StakeActivationStatus::default(),
+                |acc, stake_account| {
+                    acc + stake_account
+                        .delegation()
+                        .stake_activating_and_deactivating(
+                            epoch,
+                            &stakes.stake_history,
+                            new_rate_activation_epoch,
+                        )
+                },
+            );
+            stakes
+                .stake_history
+                .add(epoch, override_entry(epoch, entry));
+        }
+    }
+
     pub(crate) fn check_and_store(
         &self,
         pubkey: &Pubkey,
@@ -476,6 +508,51 @@ impl StakesEnum {
             StakesEnum::Delegations(stakes) => stakes.staked_nodes(),
         }
     }
+
+    pub fn epoch(&self) -> Epoch {
+        match self {
+            StakesEnum::Accounts(stakes) => stakes.epoch,
+            StakesEnum::Delegations(stakes) => stakes.epoch,
+        }
+    }
+
+    pub fn history(&self) -> &StakeHistory {
+        match self {
+            StakesEnum::Accounts(stakes) => &stakes.stake_history,
+            StakesEnum::Delegations(stakes) => &stakes.stake_history,
+        }
+    }
+
+    /// Recalculates, from the stake delegations, the stake history entry of
+    /// the epoch prior to the current one, as added when the current epoch
+    /// was activated. Returns None for epoch 0, which has no prior epoch.
+    pub fn calculate_prev_epoch_stake_history_entry(
+        &self,
+        new_rate_activation_epoch: Option<Epoch>,
+    ) -> Option<(Epoch, StakeActivationStatus)> {
+        let prev_epoch = self.epoch().checked_sub(1)?;
+        let stake_history = self.history();
+        let activation_status = |delegation: Delegation| {
+            delegation.stake_activating_and_deactivating(
+                prev_epoch,
+                stake_history,
+                new_rate_activation_epoch,
+            )
+        };
+        let stake_history_entry = match self {
+            StakesEnum::Accounts(stakes) => stakes
+                .stake_delegations
+                .values()
+                .map(|stake_account| activation_status(stake_account.delegation()))
+                .fold(StakeActivationStatus::default(), Add::add),
+            StakesEnum::Delegations(stakes) => stakes
+                .stake_delegations
+                .values()
+                .map(|delegation| activation_status(*delegation))
+                .fold(StakeActivationStatus::default(), Add::add),
+        };
+        Some((prev_epoch, stake_history_entry))
+    }
 }
 
 impl From<Stakes<StakeAccount>> for Stakes<Delegation> {
@@ -946,6 +1023,43 @@ pub(crate) mod tests {
         }
     }
 
+    #[test]
+    fn test_calculate_prev_epoch_stake_history_entry() {
+        let stakes_cache = StakesCache::default();
+        assert_eq!(
+            StakesEnum::from(stakes_cache.stakes().clone())
+                .calculate_prev_epoch_stake_history_entry(None),
+            None
+        );
+
+        let ((vote_pubkey, vote_account), (stake_pubkey, stake_account)) =
+            create_staked_node_accounts(10);
+        stakes_cache.check_and_store(&vote_pubkey, &vote_account, None);
+        stakes_cache.check_and_store(&stake_pubkey, &stake_account, None);
+
+        let thread_pool = ThreadPoolBuilder::new().num_threads(1).build().unwrap();
+        for epoch in 1..5 {
+            stakes_cache.activate_epoch(epoch, &thread_pool, None);
+            let stakes = stakes_cache.stakes().clone();
+            let expected = stakes.history().get(epoch - 1).cloned().unwrap();
+            let stakes = StakesEnum::from(stakes);
+            assert_eq!(
+                stakes.calculate_prev_epoch_stake_history_entry(None),
+                Some((epoch - 1, expected.clone()))
+            );
+            let stakes = match stakes {
+                StakesEnum::Accounts(stakes) => {
+                    StakesEnum::from(Stakes::<Delegation>::from(stakes))
+                }
+                StakesEnum::Delegations(_) => unreachable!(),
+            };
+            assert_eq!(
+                stakes.calculate_prev_epoch_stake_history_entry(None),
+                Some((epoch - 1, expected))
+            );
+        }
+    }
+
     #[test]
     fn test_stakes_not_delegate() {
         let stakes_cache = StakesCache::new(Stakes {
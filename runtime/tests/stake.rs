@@ -285,6 +285,109 @@ fn test_stake_create_and_split_to_existing_system_account() {
     );
 }
 
+#[test]
+fn test_stake_split_with_funding() {
+    solana_logger::setup();
+
+    let GenesisConfigInfo {
+        genesis_config,
+        mint_keypair: staker_keypair,
+        ..
+    } = create_genesis_config_with_leader(
+        100_000_000_000,
+        &solana_sdk::pubkey::new_rand(),
+        1_000_000,
+    );
+
+    let staker_pubkey = staker_keypair.pubkey();
+
+    let bank = Bank::new_with_bank_forks_for_tests(&genesis_config).0;
+    let bank_client = BankClient::new_shared(bank.clone());
+
+    let stake_keypair = Keypair::new();
+    let stake_pubkey = stake_keypair.pubkey();
+    let authorized = Authorized::auto(&staker_pubkey);
+
+    let rent_exempt_reserve = bank
+        .rent_collector()
+        .rent
+        .minimum_balance(StakeStateV2::size_of());
+    let minimum_delegation = solana_stake_program::get_minimum_delegation(&bank.feature_set);
+    let lamports = 2 * (rent_exempt_reserve + minimum_delegation);
+
+    let message = Message::new(
+        &stake_instruction::create_account(
+            &staker_pubkey,
+            &stake_pubkey,
+            &authorized,
+            &Lockup::default(),
+            lamports,
+        ),
+        Some(&staker_pubkey),
+    );
+    bank_client
+        .send_and_confirm_message(&[&staker_keypair, &stake_keypair], message)
+        .expect("failed to create stake account");
+
+    let funding_keypair = Keypair::new();
+    let funding_pubkey = funding_keypair.pubkey();
+    bank_client
+        .transfer_and_confirm(rent_exempt_reserve, &staker_keypair, &funding_pubkey)
+        .unwrap();
+
+    // the split stake account's reserve comes from the funding account, so it receives exactly
+    // the split amount on top of its reserve
+    let split_stake_keypair = Keypair::new();
+    let split_stake_pubkey = split_stake_keypair.pubkey();
+    let split_lamports = minimum_delegation;
+    let message = Message::new(
+        &stake_instruction::split_with_funding(
+            &stake_pubkey,
+            &staker_pubkey,
+            split_lamports,
+            &split_stake_pubkey,
+            &funding_pubkey,
+        ),
+        Some(&staker_pubkey),
+    );
+    bank_client
+        .send_and_confirm_message(
+            &[&staker_keypair, &split_stake_keypair, &funding_keypair],
+            message,
+        )
+        .expect("failed to split stake with funding");
+    assert_eq!(
+        bank.get_balance(&split_stake_pubkey),
+        rent_exempt_reserve + split_lamports
+    );
+    assert_eq!(bank.get_balance(&stake_pubkey), lamports - split_lamports);
+    assert_eq!(bank.get_balance(&funding_pubkey), 0);
+    let split_stake_state: StakeStateV2 = bank
+        .get_account(&split_stake_pubkey)
+        .unwrap()
+        .state()
+        .unwrap();
+    assert_eq!(
+        split_stake_state.meta().unwrap().rent_exempt_reserve,
+        rent_exempt_reserve
+    );
+
+    // the funding account must sign
+    let split_stake_keypair = Keypair::new();
+    let mut instructions = stake_instruction::split_with_funding(
+        &stake_pubkey,
+        &staker_pubkey,
+        split_lamports,
+        &split_stake_keypair.pubkey(),
+        &funding_pubkey,
+    );
+    instructions[2].accounts[2].is_signer = false;
+    let message = Message::new(&instructions, Some(&staker_pubkey));
+    assert!(bank_client
+        .send_and_confirm_message(&[&staker_keypair, &split_stake_keypair], message)
+        .is_err());
+}
+
 #[test]
 fn test_stake_account_lifetime() {
     let stake_keypair = Keypair::new();
@@ -162,6 +162,345 @@ pub fn extend_and_hash(id: &Hash, val: &[u8]) -> Hash {
     hash(&hash_data)
 }
 
+/// Return the Blake3 hash of each of the given values, in one system call
+/// when called from within a program.
+pub fn hash_batch(vals: &[&[u8]]) -> Vec<Hash> {
+    // Perform the calculation inline, calling this from within a program is
+    // not supported
+    #[cfg(not(target_os = "solana"))]
+    {
+        vals.iter().map(|val| hash(val)).collect()
+    }
+    // Call via a system call to perform the calculation
+    #[cfg(target_os = "solana")]
+    {
+        let mut hash_results = vec![Hash::default(); vals.len()];
+        unsafe {
+            crate::syscalls::sol_blake3_batch(
+                vals as *const _ as *const u8,
+                vals.len() as u64,
+                hash_results.as_mut_ptr() as *mut u8,
+            );
+        }
+        hash_results
+    }
+}
+
+/// Size of the chunks hashed at the leaves of the Blake3 tree.
+const CHUNK_LEN: usize = 1024;
+/// Size of the blocks compressed within a chunk.
+#[cfg(not(target_os = "solana"))]
+const BLOCK_LEN: usize = 64;
+/// Maximum depth of the Blake3 tree, for up to 2^64 bytes of input.
+const MAX_DEPTH: usize = 54;
+
+#[cfg(not(target_os = "solana"))]
+const CHUNK_START: u32 = 1 << 0;
+#[cfg(not(target_os = "solana"))]
+const CHUNK_END: u32 = 1 << 1;
+#[cfg(not(target_os = "solana"))]
+const PARENT: u32 = 1 << 2;
+#[cfg(not(target_os = "solana"))]
+const ROOT: u32 = 1 << 3;
+
+#[cfg(not(target_os = "solana"))]
+const IV: [u32; 8] = [
+    0x6A09E667, 0xBB67AE85, 0x3C6EF372, 0xA54FF53A, 0x510E527F, 0x9B05688C, 0x1F83D9AB, 0x5BE0CD19,
+];
+
+#[cfg(not(target_os = "solana"))]
+const MSG_PERMUTATION: [usize; 16] = [2, 6, 3, 10, 7, 0, 4, 13, 1, 11, 12, 5, 9, 14, 15, 8];
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("invalid incremental blake3 hasher state")]
+pub struct InvalidHasherStateError;
+
+/// A Blake3 hasher whose state lives in program memory.
+///
+/// Unlike [`Hasher`], the state can be kept across calls to [`update`],
+/// each of them costing compute units in proportion to the bytes hashed,
+/// and is only hashed into the final result by [`finalize`].
+///
+/// [`update`]: IncrementalHasher::update
+/// [`finalize`]: IncrementalHasher::finalize
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IncrementalHasher {
+    /// Number of chunks hashed into `cv_stack`
+    chunk_counter: u64,
+    /// Number of bytes in `buf`
+    buf_len: u64,
+    /// Number of chaining values in `cv_stack`
+    cv_stack_len: u64,
+    /// Bytes of the current chunk, only hashed once more input arrives
+    buf: [u8; CHUNK_LEN],
+    /// Chaining values of the subtrees still to be merged
+    cv_stack: [[u8; HASH_BYTES]; MAX_DEPTH],
+}
+
+impl Default for IncrementalHasher {
+    fn default() -> Self {
+        Self {
+            chunk_counter: 0,
+            buf_len: 0,
+            cv_stack_len: 0,
+            buf: [0; CHUNK_LEN],
+            cv_stack: [[0; HASH_BYTES]; MAX_DEPTH],
+        }
+    }
+}
+
+impl IncrementalHasher {
+    /// Hash the given values into the state.
+    pub fn update(&mut self, vals: &[&[u8]]) {
+        // Perform the calculation inline, calling this from within a program is
+        // not supported
+        #[cfg(not(target_os = "solana"))]
+        {
+            self.try_update(vals).expect("valid hasher state");
+        }
+        // Call via a system call to perform the calculation
+        #[cfg(target_os = "solana")]
+        unsafe {
+            crate::syscalls::sol_blake3_update(
+                self as *mut _ as *mut u8,
+                vals as *const _ as *const u8,
+                vals.len() as u64,
+            );
+        }
+    }
+
+    /// Return the hash of all the values given to [`update`] so far.
+    ///
+    /// [`update`]: IncrementalHasher::update
+    pub fn finalize(&self) -> Hash {
+        // Perform the calculation inline, calling this from within a program is
+        // not supported
+        #[cfg(not(target_os = "solana"))]
+        {
+            self.try_finalize().expect("valid hasher state")
+        }
+        // Call via a system call to perform the calculation
+        #[cfg(target_os = "solana")]
+        {
+            let mut hash_result = [0; HASH_BYTES];
+            unsafe {
+                crate::syscalls::sol_blake3_finalize(
+                    self as *const _ as *const u8,
+                    &mut hash_result as *mut _ as *mut u8,
+                );
+            }
+            Hash::new_from_array(hash_result)
+        }
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    fn check(&self) -> Result<(), InvalidHasherStateError> {
+        if self.buf_len > CHUNK_LEN as u64
+            || self.cv_stack_len > MAX_DEPTH as u64
+            || self.cv_stack_len != u64::from(self.chunk_counter.count_ones())
+            || (self.chunk_counter > 0 && self.buf_len == 0)
+        {
+            return Err(InvalidHasherStateError);
+        }
+        Ok(())
+    }
+
+    /// Hash the given values into the state, failing if the state was not
+    /// produced by this hasher.
+    #[cfg(not(target_os = "solana"))]
+    pub fn try_update(&mut self, vals: &[&[u8]]) -> Result<(), InvalidHasherStateError> {
+        self.check()?;
+        for val in vals {
+            let mut input = *val;
+            while !input.is_empty() {
+                if self.buf_len as usize == CHUNK_LEN {
+                    self.push_chunk()?;
+                }
+                let buf_len = self.buf_len as usize;
+                let take = CHUNK_LEN.saturating_sub(buf_len).min(input.len());
+                self.buf[buf_len..buf_len.saturating_add(take)].copy_from_slice(&input[..take]);
+                self.buf_len = self.buf_len.saturating_add(take as u64);
+                input = &input[take..];
+            }
+        }
+        Ok(())
+    }
+
+    /// Hash the full chunk in `buf` and merge its chaining value with the
+    /// completed subtrees of the same size.
+    #[cfg(not(target_os = "solana"))]
+    fn push_chunk(&mut self) -> Result<(), InvalidHasherStateError> {
+        let mut cv = chunk_cv(&self.buf, self.chunk_counter, 0);
+        self.chunk_counter = self
+            .chunk_counter
+            .checked_add(1)
+            .ok_or(InvalidHasherStateError)?;
+        let mut total_chunks = self.chunk_counter;
+        while total_chunks & 1 == 0 {
+            self.cv_stack_len = self.cv_stack_len.saturating_sub(1);
+            cv = parent_cv(&self.cv_stack[self.cv_stack_len as usize], &cv, 0);
+            total_chunks >>= 1;
+        }
+        *self
+            .cv_stack
+            .get_mut(self.cv_stack_len as usize)
+            .ok_or(InvalidHasherStateError)? = cv;
+        self.cv_stack_len = self.cv_stack_len.saturating_add(1);
+        self.buf_len = 0;
+        Ok(())
+    }
+
+    /// Return the hash of all the values given so far, failing if the state
+    /// was not produced by this hasher.
+    #[cfg(not(target_os = "solana"))]
+    pub fn try_finalize(&self) -> Result<Hash, InvalidHasherStateError> {
+        self.check()?;
+        let buf = &self.buf[..self.buf_len as usize];
+        let cv_stack = &self.cv_stack[..self.cv_stack_len as usize];
+        if cv_stack.is_empty() {
+            return Ok(Hash(chunk_cv(buf, self.chunk_counter, ROOT)));
+        }
+        let mut cv = chunk_cv(buf, self.chunk_counter, 0);
+        for (depth, left) in cv_stack.iter().enumerate().rev() {
+            cv = parent_cv(left, &cv, if depth == 0 { ROOT } else { 0 });
+        }
+        Ok(Hash(cv))
+    }
+}
+
+/// The chaining value of a chunk, or its hash if `flags` is `ROOT`.
+#[cfg(not(target_os = "solana"))]
+fn chunk_cv(chunk: &[u8], chunk_counter: u64, flags: u32) -> [u8; HASH_BYTES] {
+    let mut cv = IV;
+    // the empty chunk is compressed as a single empty block
+    let mut offset: usize = 0;
+    loop {
+        let end = offset.saturating_add(BLOCK_LEN);
+        let block = &chunk[offset..end.min(chunk.len())];
+        let is_last = end >= chunk.len();
+        let mut block_flags = if offset == 0 { CHUNK_START } else { 0 };
+        if is_last {
+            block_flags |= CHUNK_END | flags;
+        }
+        let mut block_bytes = [0; BLOCK_LEN];
+        block_bytes[..block.len()].copy_from_slice(block);
+        cv = first_8_words(compress(
+            &cv,
+            &block_bytes,
+            chunk_counter,
+            block.len() as u32,
+            block_flags,
+        ));
+        if is_last {
+            break;
+        }
+        offset = end;
+    }
+    words_to_bytes(&cv)
+}
+
+/// The chaining value of the parent of two subtrees, or its hash if `flags`
+/// is `ROOT`.
+#[cfg(not(target_os = "solana"))]
+fn parent_cv(left: &[u8; HASH_BYTES], right: &[u8; HASH_BYTES], flags: u32) -> [u8; HASH_BYTES] {
+    let mut block = [0; BLOCK_LEN];
+    block[..HASH_BYTES].copy_from_slice(left);
+    block[HASH_BYTES..].copy_from_slice(right);
+    words_to_bytes(&first_8_words(compress(
+        &IV,
+        &block,
+        0,
+        BLOCK_LEN as u32,
+        PARENT | flags,
+    )))
+}
+
+#[cfg(not(target_os = "solana"))]
+fn first_8_words(words: [u32; 16]) -> [u32; 8] {
+    let mut first = [0; 8];
+    first.copy_from_slice(&words[..8]);
+    first
+}
+
+#[cfg(not(target_os = "solana"))]
+fn words_to_bytes(words: &[u32; 8]) -> [u8; HASH_BYTES] {
+    let mut bytes = [0; HASH_BYTES];
+    for (bytes, word) in bytes.chunks_exact_mut(4).zip(words.iter()) {
+        bytes.copy_from_slice(&word.to_le_bytes());
+    }
+    bytes
+}
+
+/// The quarter-round of the Blake3 compression function.
+#[cfg(not(target_os = "solana"))]
+fn g(state: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize, mx: u32, my: u32) {
+    state[a] = state[a].wrapping_add(state[b]).wrapping_add(mx);
+    state[d] = (state[d] ^ state[a]).rotate_right(16);
+    state[c] = state[c].wrapping_add(state[d]);
+    state[b] = (state[b] ^ state[c]).rotate_right(12);
+    state[a] = state[a].wrapping_add(state[b]).wrapping_add(my);
+    state[d] = (state[d] ^ state[a]).rotate_right(8);
+    state[c] = state[c].wrapping_add(state[d]);
+    state[b] = (state[b] ^ state[c]).rotate_right(7);
+}
+
+/// The Blake3 compression function, as in its specification.
+#[cfg(not(target_os = "solana"))]
+fn compress(
+    cv: &[u32; 8],
+    block: &[u8; BLOCK_LEN],
+    counter: u64,
+    block_len: u32,
+    flags: u32,
+) -> [u32; 16] {
+    let mut m = [0u32; 16];
+    for (word, bytes) in m.iter_mut().zip(block.chunks_exact(4)) {
+        *word = u32::from_le_bytes(bytes.try_into().unwrap());
+    }
+    let mut state = [
+        cv[0],
+        cv[1],
+        cv[2],
+        cv[3],
+        cv[4],
+        cv[5],
+        cv[6],
+        cv[7],
+        IV[0],
+        IV[1],
+        IV[2],
+        IV[3],
+        counter as u32,
+        (counter >> 32) as u32,
+        block_len,
+        flags,
+    ];
+    for round in 0..7 {
+        if round > 0 {
+            let mut permuted = [0; 16];
+            for (word, source) in permuted.iter_mut().zip(MSG_PERMUTATION) {
+                *word = m[source];
+            }
+            m = permuted;
+        }
+        g(&mut state, 0, 4, 8, 12, m[0], m[1]);
+        g(&mut state, 1, 5, 9, 13, m[2], m[3]);
+        g(&mut state, 2, 6, 10, 14, m[4], m[5]);
+        g(&mut state, 3, 7, 11, 15, m[6], m[7]);
+        g(&mut state, 0, 5, 10, 15, m[8], m[9]);
+        g(&mut state, 1, 6, 11, 12, m[10], m[11]);
+        g(&mut state, 2, 7, 8, 13, m[12], m[13]);
+        g(&mut state, 3, 4, 9, 14, m[14], m[15]);
+    }
+    let (output, input) = state.split_at_mut(8);
+    for ((word, input_word), cv_word) in output.iter_mut().zip(input.iter_mut()).zip(cv) {
+        *word ^= *input_word;
+        *input_word ^= cv_word;
+    }
+    state
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -221,4 +560,61 @@ mod tests {
         let ext_hash = extend_and_hash(&val_hash, ext.as_bytes());
         assert!(ext_hash == hash(&hash_ext));
     }
+
+    #[test]
+    fn test_hash_batch() {
+        let vals: &[&[u8]] = &[b"", b"gHiljKpq", &[7u8; 3000]];
+        assert_eq!(
+            hash_batch(vals),
+            vals.iter().map(|val| hash(val)).collect::<Vec<_>>()
+        );
+        assert!(hash_batch(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_incremental_hasher() {
+        let data: Vec<u8> = (0..9000u32).map(|i| (i % 251) as u8).collect();
+        for len in [0, 1, 63, 64, 65, 1023, 1024, 1025, 2048, 3000, 4097, 9000] {
+            let data = &data[..len];
+            for split in [1, 7, 512, 1024, 1500] {
+                let mut hasher = IncrementalHasher::default();
+                for vals in data.chunks(split).collect::<Vec<_>>().chunks(3) {
+                    hasher.update(vals);
+                }
+                assert_eq!(
+                    hasher.finalize().0,
+                    *blake3::hash(data).as_bytes(),
+                    "len {len} split {split}"
+                );
+            }
+        }
+
+        let mut hasher = IncrementalHasher::default();
+        hasher.update(&[&data[..2000]]);
+        let mut forked = hasher;
+        forked.update(&[&data[2000..]]);
+        assert_eq!(hasher.finalize(), hash(&data[..2000]));
+        assert_eq!(forked.finalize(), hash(&data));
+    }
+
+    #[test]
+    fn test_incremental_hasher_invalid_state() {
+        let mut hasher = IncrementalHasher::default();
+        hasher.update(&[&[1u8; 2049]]);
+        assert!(hasher.try_finalize().is_ok());
+
+        let mut invalid = hasher;
+        invalid.buf_len = CHUNK_LEN as u64 + 1;
+        assert_eq!(invalid.try_finalize(), Err(InvalidHasherStateError));
+
+        let mut invalid = hasher;
+        invalid.cv_stack_len = 2;
+        assert_eq!(invalid.try_update(&[b"a"]), Err(InvalidHasherStateError));
+
+        let mut invalid = hasher;
+        invalid.chunk_counter = (1 << (MAX_DEPTH + 1)) - 2;
+        invalid.cv_stack_len = MAX_DEPTH as u64;
+        invalid.buf_len = CHUNK_LEN as u64;
+        assert_eq!(invalid.try_update(&[b"a"]), Err(InvalidHasherStateError));
+    }
 }
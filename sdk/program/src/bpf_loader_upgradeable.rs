@@ -16,6 +16,7 @@
 //! [`loader_upgradeable_instruction`]: crate::loader_upgradeable_instruction
 
 use crate::{
+    hash::Hash,
     instruction::{AccountMeta, Instruction, InstructionError},
     loader_upgradeable_instruction::UpgradeableLoaderInstruction,
     pubkey::Pubkey,
@@ -240,6 +241,36 @@ pub fn is_set_authority_checked_instruction(instruction_data: &[u8]) -> bool {
     !instruction_data.is_empty() && 7 == instruction_data[0]
 }
 
+pub fn is_verify_buffer_hash_instruction(instruction_data: &[u8]) -> bool {
+    !instruction_data.is_empty() && 8 == instruction_data[0]
+}
+
+pub fn is_truncate_program_data_instruction(instruction_data: &[u8]) -> bool {
+    !instruction_data.is_empty() && 9 == instruction_data[0]
+}
+
+/// Returns the instruction required to check that a buffer's program data
+/// hashes to `expected_hash`.
+///
+/// Place it before the deploy or upgrade instruction using the buffer, in the
+/// same transaction.  The expected hash of a program is
+/// `solana_program::hash::hash(&program_data)`, and `program_len` is
+/// `program_data.len()`; the buffer's zero padding past it is not hashed.
+pub fn verify_buffer_hash(
+    buffer_address: &Pubkey,
+    expected_hash: Hash,
+    program_len: u32,
+) -> Instruction {
+    Instruction::new_with_bincode(
+        id(),
+        &UpgradeableLoaderInstruction::VerifyBufferHash {
+            expected_hash,
+            program_len,
+        },
+        vec![AccountMeta::new_readonly(*buffer_address, false)],
+    )
+}
+
 /// Returns the instructions required to set a buffers's authority.
 pub fn set_buffer_authority(
     buffer_address: &Pubkey,
@@ -375,6 +406,29 @@ pub fn extend_program(
     )
 }
 
+/// Returns the instruction required to shrink a program's data account to
+/// `max_data_len` bytes of program data, refunding the excess rent to
+/// `recipient_address`.
+pub fn truncate_program_data(
+    program_address: &Pubkey,
+    authority_address: &Pubkey,
+    recipient_address: &Pubkey,
+    max_data_len: u32,
+) -> Instruction {
+    let (program_data_address, _) =
+        Pubkey::find_program_address(&[program_address.as_ref()], &id());
+    Instruction::new_with_bincode(
+        id(),
+        &UpgradeableLoaderInstruction::TruncateProgramData { max_data_len },
+        vec![
+            AccountMeta::new(program_data_address, false),
+            AccountMeta::new(*program_address, false),
+            AccountMeta::new(*recipient_address, false),
+            AccountMeta::new_readonly(*authority_address, true),
+        ],
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use {super::*, bincode::serialized_size};
@@ -527,4 +581,27 @@ mod tests {
             UpgradeableLoaderInstruction::Upgrade {},
         );
     }
+
+    #[test]
+    fn test_is_verify_buffer_hash_instruction() {
+        assert!(!is_verify_buffer_hash_instruction(&[]));
+        assert!(!is_verify_buffer_hash_instruction(
+            &bincode::serialize(&UpgradeableLoaderInstruction::SetAuthorityChecked).unwrap()
+        ));
+        let instruction = verify_buffer_hash(&Pubkey::new_unique(), Hash::new_unique(), 0);
+        assert!(is_verify_buffer_hash_instruction(&instruction.data));
+    }
+
+    #[test]
+    fn test_is_truncate_program_data_instruction() {
+        assert!(!is_truncate_program_data_instruction(&[]));
+        let instruction = truncate_program_data(
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            42,
+        );
+        assert!(is_truncate_program_data_instruction(&instruction.data));
+        assert!(!is_verify_buffer_hash_instruction(&instruction.data));
+    }
 }
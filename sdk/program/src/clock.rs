@@ -20,7 +20,10 @@
 //!
 //! [oracle]: https://docs.solanalabs.com/implemented-proposals/validator-timestamp-oracle
 
-use solana_sdk_macro::CloneZeroed;
+use {
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_sdk_macro::CloneZeroed,
+};
 
 /// The default tick rate that the cluster attempts to achieve (160 per second).
 ///
@@ -176,7 +179,18 @@ pub type UnixTimestamp = i64;
 ///
 /// All members of `Clock` start from 0 upon network boot.
 #[repr(C)]
-#[derive(Serialize, Deserialize, Debug, CloneZeroed, Default, PartialEq, Eq)]
+#[derive(
+    Serialize,
+    Deserialize,
+    BorshSerialize,
+    BorshDeserialize,
+    Debug,
+    CloneZeroed,
+    Default,
+    PartialEq,
+    Eq,
+)]
+#[borsh(crate = "borsh")]
 pub struct Clock {
     /// The current `Slot`.
     pub slot: Slot,
@@ -201,7 +215,7 @@ pub struct Clock {
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use {super::*, crate::sysvar::Sysvar};
 
     #[test]
     fn test_clone() {
@@ -215,4 +229,19 @@ mod tests {
         let cloned_clock = clock.clone();
         assert_eq!(cloned_clock, clock);
     }
+
+    #[test]
+    fn test_borsh_layout() {
+        let clock = Clock {
+            slot: 1,
+            epoch_start_timestamp: -2,
+            epoch: 3,
+            leader_schedule_epoch: 4,
+            unix_timestamp: i64::MAX,
+        };
+        let borsh_serialized = borsh::to_vec(&clock).unwrap();
+        assert_eq!(borsh_serialized, bincode::serialize(&clock).unwrap());
+        assert_eq!(borsh_serialized.len(), Clock::size_of());
+        assert_eq!(Clock::try_from_slice(&borsh_serialized).unwrap(), clock);
+    }
 }
@@ -1,6 +1,6 @@
 //! Converting custom error codes to enums.
 
-use num_traits::FromPrimitive;
+use {crate::pubkey::Pubkey, num_traits::FromPrimitive};
 
 /// Allows custom errors to be decoded back to their original enum.
 ///
@@ -31,6 +31,65 @@ pub trait DecodeError<E> {
     fn type_of() -> &'static str;
 }
 
+/// An error enum which a single program returns as custom errors.
+///
+/// Custom error codes are only meaningful to the program that returned them,
+/// and most programs number their errors from zero, so the same code means
+/// different things from different programs. Implementing this trait ties the
+/// enum to its program, so that a custom error is only decoded to it when it
+/// was returned by that program.
+///
+/// Clients can combine the errors of many programs in a registry, such as the
+/// one in the `solana-transaction-status` crate, and decode the errors of
+/// failed transactions using the program id of the failing instruction.
+///
+/// # Examples
+///
+/// ```
+/// use {
+///     num_derive::FromPrimitive,
+///     solana_program::{decode_error::ProgramCustomError, pubkey::Pubkey},
+/// };
+///
+/// mod my_program {
+///     solana_program::declare_id!("MyProgram1111111111111111111111111111111111");
+/// }
+///
+/// #[derive(Debug, PartialEq, Eq, FromPrimitive)]
+/// enum MyError {
+///     InvalidAmount,
+///     Overflow,
+/// }
+///
+/// impl ProgramCustomError for MyError {
+///     const PROGRAM_ID: Pubkey = my_program::ID;
+/// }
+///
+/// assert_eq!(
+///     MyError::decode_program_error(&my_program::ID, 1),
+///     Some(MyError::Overflow)
+/// );
+/// // the same code returned by another program is not a `MyError`
+/// assert_eq!(MyError::decode_program_error(&Pubkey::new_unique(), 1), None);
+/// ```
+pub trait ProgramCustomError: Sized {
+    /// The id of the program which returns these errors
+    const PROGRAM_ID: Pubkey;
+
+    /// Decode the custom error `custom` returned by `program_id`, if it is one
+    /// of these errors
+    fn decode_program_error(program_id: &Pubkey, custom: u32) -> Option<Self>
+    where
+        Self: FromPrimitive,
+    {
+        if *program_id == Self::PROGRAM_ID {
+            Self::from_u32(custom)
+        } else {
+            None
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use {super::*, num_derive::FromPrimitive};
@@ -54,4 +113,30 @@ mod tests {
         let option: Option<TestEnum> = TestEnum::decode_custom_error_to_enum(3);
         assert_eq!(option, None);
     }
+
+    #[test]
+    fn test_decode_program_error() {
+        #[derive(Debug, FromPrimitive, PartialEq, Eq)]
+        enum TestEnum {
+            A,
+            B,
+        }
+        impl ProgramCustomError for TestEnum {
+            const PROGRAM_ID: Pubkey =
+                Pubkey::from_str_const("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+        }
+
+        assert_eq!(
+            TestEnum::decode_program_error(&TestEnum::PROGRAM_ID, 1),
+            Some(TestEnum::B)
+        );
+        assert_eq!(
+            TestEnum::decode_program_error(&TestEnum::PROGRAM_ID, 2),
+            None
+        );
+        // the same code from another program is not decoded
+        let other_program_id =
+            Pubkey::from_str_const("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+        assert_eq!(TestEnum::decode_program_error(&other_program_id, 1), None);
+    }
 }
@@ -139,6 +139,76 @@ macro_rules! entrypoint {
     };
 }
 
+/// Declare a program entrypoint that parses accounts on demand.
+///
+/// This is an alternative to [`entrypoint!`] for programs which are passed
+/// accounts they do not need to read, such as large accounts only forwarded
+/// to a CPI or checked by key. Rather than deserializing every account up
+/// front, the provided function is handed an [`InstructionContext`] which
+/// reads accounts one at a time, and can step over accounts without
+/// constructing an [`AccountInfo`] for them.
+///
+/// [`entrypoint!`]: crate::entrypoint!
+/// [`InstructionContext`]: crate::entrypoint::InstructionContext
+///
+/// The argument is the name of a function with this type signature:
+///
+/// ```ignore
+/// fn process_instruction(
+///     context: InstructionContext, // Cursor over the serialized program input
+/// ) -> ProgramResult;
+/// ```
+///
+/// Like [`entrypoint!`], this macro sets up a global allocator and panic
+/// handler, which can be disabled with the `custom-heap` and `custom-panic`
+/// features, and should be gated behind a `no-entrypoint` feature.
+///
+/// # Examples
+///
+/// ```no_run
+/// #[cfg(not(feature = "no-entrypoint"))]
+/// pub mod entrypoint {
+///
+///     use solana_program::{
+///         entrypoint::{InstructionContext, ProgramResult},
+///         entrypoint_lazy,
+///         msg,
+///         program_error::ProgramError,
+///     };
+///
+///     entrypoint_lazy!(process_instruction);
+///
+///     pub fn process_instruction(mut context: InstructionContext) -> ProgramResult {
+///         let authority = context.next_account()?;
+///         if !authority.is_signer {
+///             return Err(ProgramError::MissingRequiredSignature);
+///         }
+///         // the remaining accounts are never deserialized
+///         let instruction_data = context.instruction_data();
+///         msg!("{} bytes of instruction data", instruction_data.len());
+///
+///         Ok(())
+///     }
+///
+/// }
+/// ```
+#[macro_export]
+macro_rules! entrypoint_lazy {
+    ($process_instruction:ident) => {
+        /// # Safety
+        #[no_mangle]
+        pub unsafe extern "C" fn entrypoint(input: *mut u8) -> u64 {
+            let context = unsafe { $crate::entrypoint::InstructionContext::new(input) };
+            match $process_instruction(context) {
+                Ok(()) => $crate::entrypoint::SUCCESS,
+                Err(error) => error.into(),
+            }
+        }
+        $crate::custom_heap_default!();
+        $crate::custom_panic_default!();
+    };
+}
+
 /// Define the default global allocator.
 ///
 /// The default global allocator is enabled only if the calling crate has not
@@ -370,10 +440,314 @@ pub unsafe fn deserialize<'a>(input: *mut u8) -> (&'a Pubkey, Vec<AccountInfo<'a
     (program_id, accounts, instruction_data)
 }
 
+/// Offset of the data length of a serialized account, from just after its
+/// duplicate marker
+const ACCOUNT_DATA_LEN_OFFSET: usize =
+    3 * size_of::<u8>() + size_of::<u32>() + 2 * size_of::<Pubkey>() + size_of::<u64>();
+
+/// An account of the instruction which has been read or stepped over
+enum LazyAccount<'a> {
+    Parsed(AccountInfo<'a>),
+    /// Offset of an account which has not been parsed, just after its
+    /// duplicate marker
+    Skipped(usize),
+    /// Index of the account this one duplicates
+    Duplicate(usize),
+}
+
+/// The input to a program declared with [`entrypoint_lazy!`], parsed on
+/// demand.
+///
+/// Accounts are read in order with [`InstructionContext::next_account`].
+/// Accounts the program does not need can be stepped over with
+/// [`InstructionContext::skip_accounts`], which only reads their data length.
+/// The instruction data and program id are serialized after the accounts, so
+/// reading either of them skips any accounts which have not been read yet.
+///
+/// [`entrypoint_lazy!`]: crate::entrypoint_lazy!
+pub struct InstructionContext<'a> {
+    input: *mut u8,
+    /// Offset of the next unread account, or of the instruction data once
+    /// every account has been read
+    offset: usize,
+    num_accounts: usize,
+    accounts: Vec<LazyAccount<'a>>,
+}
+
+impl<'a> InstructionContext<'a> {
+    /// # Safety
+    ///
+    /// `input` must point to program input serialized by the runtime which
+    /// lives for `'a`, and nothing else may deserialize it.
+    pub unsafe fn new(input: *mut u8) -> Self {
+        #[allow(clippy::cast_ptr_alignment)]
+        let num_accounts = *(input as *const u64) as usize;
+        Self {
+            input,
+            offset: size_of::<u64>(),
+            num_accounts,
+            accounts: Vec::with_capacity(num_accounts),
+        }
+    }
+
+    /// Number of accounts which have not been read or skipped yet
+    pub fn remaining_accounts(&self) -> usize {
+        self.num_accounts.saturating_sub(self.accounts.len())
+    }
+
+    /// Parse the next account
+    pub fn next_account(&mut self) -> Result<AccountInfo<'a>, ProgramError> {
+        if self.remaining_accounts() == 0 {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+        let index = self.accounts.len();
+        unsafe { self.step() };
+        Ok(self.resolve(index))
+    }
+
+    /// Step over the next `count` accounts, or all the remaining accounts if
+    /// there are fewer, without parsing them
+    pub fn skip_accounts(&mut self, count: usize) {
+        for _ in 0..count.min(self.remaining_accounts()) {
+            unsafe { self.step() };
+        }
+    }
+
+    /// The instruction data, skipping any accounts not read yet
+    pub fn instruction_data(&mut self) -> &'a [u8] {
+        self.skip_accounts(self.remaining_accounts());
+        unsafe {
+            #[allow(clippy::cast_ptr_alignment)]
+            let instruction_data_len = *(self.input.add(self.offset) as *const u64) as usize;
+            from_raw_parts(
+                self.input.add(self.offset.saturating_add(size_of::<u64>())),
+                instruction_data_len,
+            )
+        }
+    }
+
+    /// The id of the executing program, skipping any accounts not read yet
+    pub fn program_id(&mut self) -> &'a Pubkey {
+        let instruction_data_len = self.instruction_data().len();
+        unsafe {
+            &*(self.input.add(
+                self.offset
+                    .saturating_add(size_of::<u64>())
+                    .saturating_add(instruction_data_len),
+            ) as *const Pubkey)
+        }
+    }
+
+    /// Record the account at `self.offset` as skipped, and advance past it
+    #[allow(clippy::arithmetic_side_effects)]
+    unsafe fn step(&mut self) {
+        let dup_info = *self.input.add(self.offset);
+        self.offset += size_of::<u8>();
+        if dup_info == NON_DUP_MARKER {
+            self.accounts.push(LazyAccount::Skipped(self.offset));
+
+            #[allow(clippy::cast_ptr_alignment)]
+            let data_len =
+                *(self.input.add(self.offset + ACCOUNT_DATA_LEN_OFFSET) as *const u64) as usize;
+            self.offset += ACCOUNT_DATA_LEN_OFFSET + size_of::<u64>();
+            self.offset += data_len + MAX_PERMITTED_DATA_INCREASE;
+            self.offset += (self.offset as *const u8).align_offset(BPF_ALIGN_OF_U128); // padding
+            self.offset += size_of::<u64>(); // rent epoch
+        } else {
+            self.accounts
+                .push(LazyAccount::Duplicate(usize::from(dup_info)));
+            self.offset += 7; // padding
+        }
+    }
+
+    /// Parse the account at `index` if it has not been already, so that
+    /// every duplicate of an account shares the same `AccountInfo`
+    fn resolve(&mut self, index: usize) -> AccountInfo<'a> {
+        match self.accounts[index] {
+            LazyAccount::Parsed(ref account) => account.clone(),
+            LazyAccount::Skipped(offset) => {
+                let account = unsafe { parse_account(self.input, offset) };
+                self.accounts[index] = LazyAccount::Parsed(account.clone());
+                account
+            }
+            LazyAccount::Duplicate(original) => self.resolve(original),
+        }
+    }
+}
+
+/// Parse a non-duplicate account serialized at `offset`, just after its
+/// duplicate marker
+///
+/// # Safety
+#[allow(clippy::arithmetic_side_effects)]
+unsafe fn parse_account<'a>(input: *mut u8, mut offset: usize) -> AccountInfo<'a> {
+    let is_signer = *input.add(offset) != 0;
+    offset += size_of::<u8>();
+
+    let is_writable = *input.add(offset) != 0;
+    offset += size_of::<u8>();
+
+    let executable = *input.add(offset) != 0;
+    offset += size_of::<u8>();
+
+    let original_data_len_offset = offset;
+    offset += size_of::<u32>();
+
+    let key: &Pubkey = &*(input.add(offset) as *const Pubkey);
+    offset += size_of::<Pubkey>();
+
+    let owner: &Pubkey = &*(input.add(offset) as *const Pubkey);
+    offset += size_of::<Pubkey>();
+
+    #[allow(clippy::cast_ptr_alignment)]
+    let lamports = Rc::new(RefCell::new(&mut *(input.add(offset) as *mut u64)));
+    offset += size_of::<u64>();
+
+    #[allow(clippy::cast_ptr_alignment)]
+    let data_len = *(input.add(offset) as *const u64) as usize;
+    offset += size_of::<u64>();
+
+    // See `deserialize`
+    *(input.add(original_data_len_offset) as *mut u32) = data_len as u32;
+
+    let data = Rc::new(RefCell::new({
+        from_raw_parts_mut(input.add(offset), data_len)
+    }));
+    offset += data_len + MAX_PERMITTED_DATA_INCREASE;
+    offset += (offset as *const u8).align_offset(BPF_ALIGN_OF_U128); // padding
+
+    #[allow(clippy::cast_ptr_alignment)]
+    let rent_epoch = *(input.add(offset) as *const u64);
+
+    AccountInfo {
+        key,
+        is_signer,
+        is_writable,
+        lamports,
+        data,
+        owner,
+        executable,
+        rent_epoch,
+    }
+}
+
 #[cfg(test)]
 mod test {
     use {super::*, std::alloc::GlobalAlloc};
 
+    /// Serialize program input the way the runtime does, where `positions`
+    /// lists the index into `accounts` of each account of the instruction
+    #[allow(clippy::type_complexity)]
+    fn serialize_input(
+        accounts: &[(Pubkey, bool, Vec<u8>)],
+        positions: &[usize],
+        instruction_data: &[u8],
+        program_id: &Pubkey,
+    ) -> Vec<u64> {
+        let mut bytes = vec![];
+        bytes.extend_from_slice(&(positions.len() as u64).to_le_bytes());
+        for (position, index) in positions.iter().enumerate() {
+            let first_position = positions.iter().position(|i| i == index).unwrap();
+            if first_position < position {
+                bytes.push(first_position as u8);
+                bytes.extend_from_slice(&[0; 7]);
+                continue;
+            }
+            let (key, is_signer, data) = &accounts[*index];
+            bytes.push(NON_DUP_MARKER);
+            bytes.extend_from_slice(&[*is_signer as u8, 1, 0]);
+            bytes.extend_from_slice(&[0; 4]);
+            bytes.extend_from_slice(key.as_ref());
+            bytes.extend_from_slice(program_id.as_ref());
+            bytes.extend_from_slice(&(*index as u64 + 1).to_le_bytes());
+            bytes.extend_from_slice(&(data.len() as u64).to_le_bytes());
+            bytes.extend_from_slice(data);
+            bytes.resize(bytes.len() + MAX_PERMITTED_DATA_INCREASE, 0);
+            bytes.resize(bytes.len().next_multiple_of(BPF_ALIGN_OF_U128), 0);
+            bytes.extend_from_slice(&u64::MAX.to_le_bytes());
+        }
+        bytes.extend_from_slice(&(instruction_data.len() as u64).to_le_bytes());
+        bytes.extend_from_slice(instruction_data);
+        bytes.extend_from_slice(program_id.as_ref());
+
+        let mut input = vec![0u64; bytes.len().div_ceil(size_of::<u64>())];
+        unsafe {
+            std::ptr::copy_nonoverlapping(
+                bytes.as_ptr(),
+                input.as_mut_ptr() as *mut u8,
+                bytes.len(),
+            )
+        };
+        input
+    }
+
+    #[test]
+    fn test_instruction_context() {
+        let accounts = vec![
+            (Pubkey::new_unique(), true, vec![1, 2, 3]),
+            (Pubkey::new_unique(), false, vec![4; 100]),
+            (Pubkey::new_unique(), false, vec![]),
+        ];
+        let positions = [0, 1, 2, 1, 0];
+        let instruction_data = [42; 17];
+        let program_id = Pubkey::new_unique();
+
+        let mut input = serialize_input(&accounts, &positions, &instruction_data, &program_id);
+        let (expected_program_id, expected_accounts, expected_instruction_data) =
+            unsafe { deserialize(input.as_mut_ptr() as *mut u8) };
+
+        // reading every account matches `deserialize`
+        let mut input = serialize_input(&accounts, &positions, &instruction_data, &program_id);
+        let mut context = unsafe { InstructionContext::new(input.as_mut_ptr() as *mut u8) };
+        assert_eq!(context.remaining_accounts(), positions.len());
+        for expected in &expected_accounts {
+            let account = context.next_account().unwrap();
+            assert_eq!(account.key, expected.key);
+            assert_eq!(account.is_signer, expected.is_signer);
+            assert_eq!(account.is_writable, expected.is_writable);
+            assert_eq!(account.owner, expected.owner);
+            assert_eq!(account.lamports(), expected.lamports());
+            assert_eq!(*account.data.borrow(), *expected.data.borrow());
+            assert_eq!(account.rent_epoch, expected.rent_epoch);
+            assert_eq!(unsafe { account.original_data_len() }, unsafe {
+                expected.original_data_len()
+            });
+        }
+        assert_eq!(
+            context.next_account().unwrap_err(),
+            ProgramError::NotEnoughAccountKeys
+        );
+        assert_eq!(context.instruction_data(), expected_instruction_data);
+        assert_eq!(context.program_id(), expected_program_id);
+
+        // skipped accounts are parsed when read through a duplicate, and
+        // duplicates share the same account
+        let mut input = serialize_input(&accounts, &positions, &instruction_data, &program_id);
+        let mut context = unsafe { InstructionContext::new(input.as_mut_ptr() as *mut u8) };
+        let first = context.next_account().unwrap();
+        context.skip_accounts(2);
+        assert_eq!(context.remaining_accounts(), 2);
+        let second = context.next_account().unwrap();
+        assert_eq!(second.key, &accounts[1].0);
+        assert_eq!(*second.data.borrow(), &accounts[1].2[..]);
+        let first_duplicate = context.next_account().unwrap();
+        assert!(Rc::ptr_eq(&first.data, &first_duplicate.data));
+        assert!(Rc::ptr_eq(&first.lamports, &first_duplicate.lamports));
+
+        // reading the instruction data skips the remaining accounts
+        let mut input = serialize_input(&accounts, &positions, &instruction_data, &program_id);
+        let mut context = unsafe { InstructionContext::new(input.as_mut_ptr() as *mut u8) };
+        context.next_account().unwrap();
+        assert_eq!(context.program_id(), &program_id);
+        assert_eq!(context.instruction_data(), &instruction_data);
+        assert_eq!(context.remaining_accounts(), 0);
+        context.skip_accounts(1);
+        assert_eq!(
+            context.next_account().unwrap_err(),
+            ProgramError::NotEnoughAccountKeys
+        );
+    }
+
     #[test]
     fn test_bump_allocator() {
         // alloc the entire
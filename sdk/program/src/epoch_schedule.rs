@@ -12,7 +12,10 @@
 //! epochs increasing in slots until they last for [`DEFAULT_SLOTS_PER_EPOCH`].
 
 pub use crate::clock::{Epoch, Slot, DEFAULT_SLOTS_PER_EPOCH};
-use solana_sdk_macro::CloneZeroed;
+use {
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_sdk_macro::CloneZeroed,
+};
 
 /// The default number of slots before an epoch starts to calculate the leader schedule.
 pub const DEFAULT_LEADER_SCHEDULE_SLOT_OFFSET: u64 = DEFAULT_SLOTS_PER_EPOCH;
@@ -29,7 +32,18 @@ pub const MAX_LEADER_SCHEDULE_EPOCH_OFFSET: u64 = 3;
 pub const MINIMUM_SLOTS_PER_EPOCH: u64 = 32;
 
 #[repr(C)]
-#[derive(Debug, CloneZeroed, PartialEq, Eq, Deserialize, Serialize, AbiExample)]
+#[derive(
+    Debug,
+    CloneZeroed,
+    PartialEq,
+    Eq,
+    Deserialize,
+    Serialize,
+    BorshDeserialize,
+    BorshSerialize,
+    AbiExample,
+)]
+#[borsh(crate = "borsh")]
 #[serde(rename_all = "camelCase")]
 pub struct EpochSchedule {
     /// The maximum number of slots in each epoch.
@@ -186,7 +200,7 @@ impl EpochSchedule {
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use {super::*, crate::sysvar::Sysvar};
 
     #[test]
     fn test_epoch_schedule() {
@@ -259,4 +273,19 @@ mod tests {
         let cloned_epoch_schedule = epoch_schedule.clone();
         assert_eq!(cloned_epoch_schedule, epoch_schedule);
     }
+
+    #[test]
+    fn test_borsh_layout() {
+        let epoch_schedule = EpochSchedule::custom(8192, 4096, true);
+        let borsh_serialized = borsh::to_vec(&epoch_schedule).unwrap();
+        assert_eq!(
+            borsh_serialized,
+            bincode::serialize(&epoch_schedule).unwrap()
+        );
+        assert_eq!(borsh_serialized.len(), EpochSchedule::size_of());
+        assert_eq!(
+            EpochSchedule::try_from_slice(&borsh_serialized).unwrap(),
+            epoch_schedule
+        );
+    }
 }
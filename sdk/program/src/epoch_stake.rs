@@ -0,0 +1,150 @@
+//! A type to hold data for the [`EpochStake` sysvar][sv].
+//!
+//! The sysvar ID is declared in [`sysvar::epoch_stake`].
+//!
+//! [sv]: crate::sysvar::epoch_stake
+//! [`sysvar::epoch_stake`]: crate::sysvar::epoch_stake
+
+use crate::{clock::Epoch, pubkey::Pubkey};
+
+/// Maximum number of vote accounts recorded in the sysvar
+pub const MAX_ENTRIES: usize = 512;
+
+/// Byte offset of [`EpochStake::total_stake`] in the sysvar account data
+pub const TOTAL_STAKE_OFFSET: usize = 8;
+/// Byte offset of the number of vote accounts in the sysvar account data
+pub const NUM_VOTE_ACCOUNTS_OFFSET: usize = 16;
+/// Byte offset of the first [`VoteAccountStake`] in the sysvar account data
+pub const VOTE_ACCOUNTS_OFFSET: usize = 24;
+/// Size in bytes of a serialized [`VoteAccountStake`]
+pub const VOTE_ACCOUNT_STAKE_SIZE: usize = 40;
+
+#[repr(C)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default, AbiExample)]
+pub struct VoteAccountStake {
+    pub vote_pubkey: Pubkey,
+    /// Stake delegated to the vote account, in lamports
+    pub stake: u64,
+}
+
+#[repr(C)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default, AbiExample)]
+pub struct EpochStake {
+    /// The epoch whose leader schedule was computed from these stakes
+    pub epoch: Epoch,
+    /// Total stake delegated to all vote accounts, in lamports
+    pub total_stake: u64,
+    /// The vote accounts with the most stake, at most `MAX_ENTRIES`, sorted
+    /// by descending stake and then by pubkey
+    pub vote_accounts: Vec<VoteAccountStake>,
+}
+
+impl EpochStake {
+    /// Creates the sysvar from the total stake and the stake of every vote
+    /// account, keeping only the `MAX_ENTRIES` most staked ones
+    pub fn new<I>(epoch: Epoch, total_stake: u64, vote_accounts: I) -> Self
+    where
+        I: IntoIterator<Item = (Pubkey, u64)>,
+    {
+        let mut vote_accounts: Vec<_> = vote_accounts
+            .into_iter()
+            .filter(|(_, stake)| *stake > 0)
+            .map(|(vote_pubkey, stake)| VoteAccountStake { vote_pubkey, stake })
+            .collect();
+        vote_accounts.sort_unstable_by(|a, b| {
+            b.stake
+                .cmp(&a.stake)
+                .then_with(|| a.vote_pubkey.cmp(&b.vote_pubkey))
+        });
+        vote_accounts.truncate(MAX_ENTRIES);
+        Self {
+            epoch,
+            total_stake,
+            vote_accounts,
+        }
+    }
+
+    /// Byte offset of the vote account at `index` in the sysvar account data
+    pub fn vote_account_offset(index: usize) -> usize {
+        VOTE_ACCOUNTS_OFFSET.saturating_add(index.saturating_mul(VOTE_ACCOUNT_STAKE_SIZE))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_epoch_stake_new() {
+        let pubkeys: Vec<_> = (0..4).map(|_| Pubkey::new_unique()).collect();
+        let epoch_stake = EpochStake::new(
+            7,
+            60,
+            [
+                (pubkeys[0], 10),
+                (pubkeys[1], 0),
+                (pubkeys[2], 30),
+                (pubkeys[3], 10),
+            ],
+        );
+        assert_eq!(epoch_stake.epoch, 7);
+        assert_eq!(epoch_stake.total_stake, 60);
+        assert_eq!(
+            epoch_stake.vote_accounts,
+            vec![
+                VoteAccountStake {
+                    vote_pubkey: pubkeys[2],
+                    stake: 30
+                },
+                VoteAccountStake {
+                    vote_pubkey: pubkeys[0],
+                    stake: 10
+                },
+                VoteAccountStake {
+                    vote_pubkey: pubkeys[3],
+                    stake: 10
+                },
+            ]
+        );
+
+        let epoch_stake = EpochStake::new(
+            0,
+            0,
+            (1..=MAX_ENTRIES as u64 + 1).map(|stake| (Pubkey::new_unique(), stake)),
+        );
+        assert_eq!(epoch_stake.vote_accounts.len(), MAX_ENTRIES);
+        assert_eq!(epoch_stake.vote_accounts[0].stake, MAX_ENTRIES as u64 + 1);
+        assert_eq!(epoch_stake.vote_accounts[MAX_ENTRIES - 1].stake, 2);
+    }
+
+    #[test]
+    fn test_epoch_stake_layout() {
+        let vote_account = VoteAccountStake {
+            vote_pubkey: Pubkey::new_unique(),
+            stake: 42,
+        };
+        let epoch_stake = EpochStake {
+            epoch: 3,
+            total_stake: 100,
+            vote_accounts: vec![VoteAccountStake::default(), vote_account],
+        };
+        let data = bincode::serialize(&epoch_stake).unwrap();
+        assert_eq!(
+            data[TOTAL_STAKE_OFFSET..NUM_VOTE_ACCOUNTS_OFFSET],
+            100u64.to_le_bytes()
+        );
+        assert_eq!(
+            data[NUM_VOTE_ACCOUNTS_OFFSET..VOTE_ACCOUNTS_OFFSET],
+            2u64.to_le_bytes()
+        );
+        let offset = EpochStake::vote_account_offset(1);
+        assert_eq!(
+            bincode::deserialize::<VoteAccountStake>(
+                &data[offset..offset + VOTE_ACCOUNT_STAKE_SIZE]
+            )
+            .unwrap(),
+            vote_account
+        );
+        assert_eq!(data.len(), EpochStake::vote_account_offset(2));
+    }
+}
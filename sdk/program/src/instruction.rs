@@ -512,6 +512,133 @@ impl Instruction {
     }
 }
 
+/// A builder for constructing an [`Instruction`] one account at a time.
+///
+/// Accounts are passed to the program in the order they are added, and
+/// whether each one is writable or a signer is stated explicitly at the call
+/// site, rather than by choosing between [`AccountMeta::new`] and
+/// [`AccountMeta::new_readonly`].
+///
+/// # Examples
+///
+/// ```
+/// # use solana_program::{
+/// #     pubkey::Pubkey,
+/// #     instruction::{AccountMeta, Instruction, InstructionBuilder},
+/// # };
+/// # use borsh::{BorshSerialize, BorshDeserialize};
+/// #
+/// #[derive(BorshSerialize, BorshDeserialize)]
+/// # #[borsh(crate = "borsh")]
+/// pub struct MyInstruction {
+///     pub lamports: u64,
+/// }
+///
+/// pub fn create_instruction(
+///     program_id: &Pubkey,
+///     from: &Pubkey,
+///     to: &Pubkey,
+///     lamports: u64,
+/// ) -> Instruction {
+///     InstructionBuilder::new(*program_id)
+///         .account(*from, true, true)
+///         .account(*to, true, false)
+///         .data_borsh(&MyInstruction { lamports })
+///         .build()
+/// }
+/// #
+/// # let program_id = Pubkey::new_unique();
+/// # let from = Pubkey::new_unique();
+/// # let to = Pubkey::new_unique();
+/// # assert_eq!(
+/// #     create_instruction(&program_id, &from, &to, 42),
+/// #     Instruction::new_with_borsh(
+/// #         program_id,
+/// #         &MyInstruction { lamports: 42 },
+/// #         vec![AccountMeta::new(from, true), AccountMeta::new(to, false)],
+/// #     ),
+/// # );
+/// ```
+#[derive(Debug, Clone)]
+pub struct InstructionBuilder {
+    program_id: Pubkey,
+    accounts: Vec<AccountMeta>,
+    data: Vec<u8>,
+}
+
+impl InstructionBuilder {
+    /// Start building an instruction for the program at `program_id`, with
+    /// no accounts and empty data.
+    pub fn new(program_id: Pubkey) -> Self {
+        Self {
+            program_id,
+            accounts: Vec::new(),
+            data: Vec::new(),
+        }
+    }
+
+    /// Append an account, specifying whether it may be mutated and whether
+    /// the transaction must be signed by it.
+    pub fn account(self, pubkey: Pubkey, is_writable: bool, is_signer: bool) -> Self {
+        self.account_meta(AccountMeta {
+            pubkey,
+            is_signer,
+            is_writable,
+        })
+    }
+
+    /// Append an existing [`AccountMeta`].
+    pub fn account_meta(mut self, account_meta: AccountMeta) -> Self {
+        self.accounts.push(account_meta);
+        self
+    }
+
+    /// Append several existing [`AccountMeta`]s, in order.
+    pub fn accounts(mut self, account_metas: impl IntoIterator<Item = AccountMeta>) -> Self {
+        self.accounts.extend(account_metas);
+        self
+    }
+
+    /// Set the instruction data to a byte slice.
+    pub fn data_bytes(mut self, data: &[u8]) -> Self {
+        self.data = data.to_vec();
+        self
+    }
+
+    /// Set the instruction data to a value encoded with [`borsh`].
+    ///
+    /// [`borsh`]: https://docs.rs/borsh/latest/borsh/
+    ///
+    /// # Panics
+    ///
+    /// Panics if `data` fails to serialize, as [`Instruction::new_with_borsh`] does.
+    pub fn data_borsh<T: BorshSerialize>(mut self, data: &T) -> Self {
+        self.data = borsh::to_vec(data).unwrap();
+        self
+    }
+
+    /// Set the instruction data to a value encoded with [`bincode`].
+    ///
+    /// [`bincode`]: https://docs.rs/bincode/latest/bincode/
+    ///
+    /// # Panics
+    ///
+    /// Panics if `data` fails to serialize, as [`Instruction::new_with_bincode`] does.
+    pub fn data_bincode<T: Serialize>(mut self, data: &T) -> Self {
+        self.data = serialize(data).unwrap();
+        self
+    }
+
+    /// Finish building the instruction.
+    pub fn build(self) -> Instruction {
+        Instruction {
+            program_id: self.program_id,
+            accounts: self.accounts,
+            data: self.data,
+        }
+    }
+}
+
 /// Addition that returns [`InstructionError::InsufficientFunds`] on overflow.
 ///
 /// This is an internal utility function.
@@ -746,3 +873,22 @@ pub fn get_stack_height() -> usize {
         crate::program_stubs::sol_get_stack_height() as usize
     }
 }
+
+/// Get the id of the program which invoked the current program through CPI.
+///
+/// Returns `None` when the current program is executing a transaction-level
+/// instruction, at stack height [`TRANSACTION_LEVEL_STACK_HEIGHT`].
+pub fn get_caller_program_id() -> Option<Pubkey> {
+    #[cfg(target_os = "solana")]
+    {
+        let mut program_id = Pubkey::default();
+        if 1 == unsafe { crate::syscalls::sol_get_caller_program_id(&mut program_id) } {
+            Some(program_id)
+        } else {
+            None
+        }
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    crate::program_stubs::sol_get_caller_program_id()
+}
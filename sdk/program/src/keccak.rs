@@ -161,3 +161,205 @@ pub fn extend_and_hash(id: &Hash, val: &[u8]) -> Hash {
     hash_data.extend_from_slice(val);
     hash(&hash_data)
 }
+
+/// Return the Keccak256 hash of each of the given values, in one system call
+/// when called from within a program.
+pub fn hash_batch(vals: &[&[u8]]) -> Vec<Hash> {
+    // Perform the calculation inline, calling this from within a program is
+    // not supported
+    #[cfg(not(target_os = "solana"))]
+    {
+        vals.iter().map(|val| hash(val)).collect()
+    }
+    // Call via a system call to perform the calculation
+    #[cfg(target_os = "solana")]
+    {
+        let mut hash_results = vec![Hash::default(); vals.len()];
+        unsafe {
+            crate::syscalls::sol_keccak256_batch(
+                vals as *const _ as *const u8,
+                vals.len() as u64,
+                hash_results.as_mut_ptr() as *mut u8,
+            );
+        }
+        hash_results
+    }
+}
+
+/// Number of lanes of the Keccak-f[1600] state.
+const LANES: usize = 25;
+/// Bytes absorbed per permutation by Keccak256.
+const RATE: usize = 136;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("invalid incremental keccak256 hasher state")]
+pub struct InvalidHasherStateError;
+
+/// A Keccak256 hasher whose state lives in program memory.
+///
+/// Unlike [`Hasher`], the state can be kept across calls to [`update`],
+/// each of them costing compute units in proportion to the bytes hashed,
+/// and is only hashed into the final result by [`finalize`].
+///
+/// [`update`]: IncrementalHasher::update
+/// [`finalize`]: IncrementalHasher::finalize
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IncrementalHasher {
+    /// Keccak-f[1600] state
+    lanes: [u64; LANES],
+    /// Number of bytes in `buf`
+    buf_len: u64,
+    /// Bytes of the current block, not yet absorbed into `lanes`
+    buf: [u8; RATE],
+}
+
+impl Default for IncrementalHasher {
+    fn default() -> Self {
+        Self {
+            lanes: [0; LANES],
+            buf_len: 0,
+            buf: [0; RATE],
+        }
+    }
+}
+
+impl IncrementalHasher {
+    /// Hash the given values into the state.
+    pub fn update(&mut self, vals: &[&[u8]]) {
+        // Perform the calculation inline, calling this from within a program is
+        // not supported
+        #[cfg(not(target_os = "solana"))]
+        {
+            self.try_update(vals).expect("valid hasher state");
+        }
+        // Call via a system call to perform the calculation
+        #[cfg(target_os = "solana")]
+        unsafe {
+            crate::syscalls::sol_keccak256_update(
+                self as *mut _ as *mut u8,
+                vals as *const _ as *const u8,
+                vals.len() as u64,
+            );
+        }
+    }
+
+    /// Return the hash of all the values given to [`update`] so far.
+    ///
+    /// [`update`]: IncrementalHasher::update
+    pub fn finalize(&self) -> Hash {
+        // Perform the calculation inline, calling this from within a program is
+        // not supported
+        #[cfg(not(target_os = "solana"))]
+        {
+            self.try_finalize().expect("valid hasher state")
+        }
+        // Call via a system call to perform the calculation
+        #[cfg(target_os = "solana")]
+        {
+            let mut hash_result = [0; HASH_BYTES];
+            unsafe {
+                crate::syscalls::sol_keccak256_finalize(
+                    self as *const _ as *const u8,
+                    &mut hash_result as *mut _ as *mut u8,
+                );
+            }
+            Hash::new_from_array(hash_result)
+        }
+    }
+
+    /// Hash the given values into the state, failing if the state was not
+    /// produced by this hasher.
+    #[cfg(not(target_os = "solana"))]
+    pub fn try_update(&mut self, vals: &[&[u8]]) -> Result<(), InvalidHasherStateError> {
+        if self.buf_len >= RATE as u64 {
+            return Err(InvalidHasherStateError);
+        }
+        for val in vals {
+            let mut input = *val;
+            while !input.is_empty() {
+                let buf_len = self.buf_len as usize;
+                let take = RATE.saturating_sub(buf_len).min(input.len());
+                self.buf[buf_len..buf_len.saturating_add(take)].copy_from_slice(&input[..take]);
+                self.buf_len = self.buf_len.saturating_add(take as u64);
+                input = &input[take..];
+                if self.buf_len as usize == RATE {
+                    absorb_block(&mut self.lanes, &self.buf);
+                    self.buf_len = 0;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Return the hash of all the values given so far, failing if the state
+    /// was not produced by this hasher.
+    #[cfg(not(target_os = "solana"))]
+    pub fn try_finalize(&self) -> Result<Hash, InvalidHasherStateError> {
+        let buf_len = self.buf_len as usize;
+        if buf_len >= RATE {
+            return Err(InvalidHasherStateError);
+        }
+        let mut block = [0; RATE];
+        block[..buf_len].copy_from_slice(&self.buf[..buf_len]);
+        block[buf_len] ^= 0x01;
+        block[RATE - 1] ^= 0x80;
+        let mut lanes = self.lanes;
+        absorb_block(&mut lanes, &block);
+        let mut hash_result = [0; HASH_BYTES];
+        for (bytes, lane) in hash_result.chunks_exact_mut(8).zip(lanes.iter()) {
+            bytes.copy_from_slice(&lane.to_le_bytes());
+        }
+        Ok(Hash::new_from_array(hash_result))
+    }
+}
+
+#[cfg(not(target_os = "solana"))]
+fn absorb_block(lanes: &mut [u64; LANES], block: &[u8; RATE]) {
+    for (lane, bytes) in lanes.iter_mut().zip(block.chunks_exact(8)) {
+        *lane ^= u64::from_le_bytes(bytes.try_into().unwrap());
+    }
+    keccak::f1600(lanes);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hash_batch() {
+        let vals: &[&[u8]] = &[b"", b"gHiljKpq", &[7u8; 300]];
+        assert_eq!(
+            hash_batch(vals),
+            vals.iter().map(|val| hash(val)).collect::<Vec<_>>()
+        );
+        assert!(hash_batch(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_incremental_hasher() {
+        let data: Vec<u8> = (0..1000u32).map(|i| (i % 251) as u8).collect();
+        for len in [0, 1, 135, 136, 137, 272, 500, 1000] {
+            let data = &data[..len];
+            for split in [1, 7, 68, 136, 200] {
+                let mut hasher = IncrementalHasher::default();
+                for vals in data.chunks(split).collect::<Vec<_>>().chunks(3) {
+                    hasher.update(vals);
+                }
+                assert_eq!(hasher.finalize(), hash(data), "len {len} split {split}");
+            }
+        }
+
+        let mut hasher = IncrementalHasher::default();
+        hasher.update(&[&data[..200]]);
+        let mut forked = hasher;
+        forked.update(&[&data[200..]]);
+        assert_eq!(hasher.finalize(), hash(&data[..200]));
+        assert_eq!(forked.finalize(), hash(&data));
+
+        let mut invalid = hasher;
+        invalid.buf_len = RATE as u64;
+        assert_eq!(invalid.try_finalize(), Err(InvalidHasherStateError));
+        assert_eq!(invalid.try_update(&[b"a"]), Err(InvalidHasherStateError));
+    }
+}
@@ -492,6 +492,7 @@ pub mod entrypoint;
 pub mod entrypoint_deprecated;
 pub mod epoch_rewards;
 pub mod epoch_schedule;
+pub mod epoch_stake;
 pub mod feature;
 pub mod fee_calculator;
 pub mod hash;
@@ -518,6 +519,7 @@ pub mod program_stubs;
 pub mod program_utils;
 pub mod pubkey;
 pub mod rent;
+pub mod restart_history;
 pub mod sanitize;
 pub mod secp256k1_program;
 pub mod secp256k1_recover;
@@ -2,6 +2,8 @@
 //!
 //! [ubpfl]: crate::bpf_loader_upgradeable
 
+use crate::hash::Hash;
+
 #[repr(u8)]
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
 pub enum UpgradeableLoaderInstruction {
@@ -160,4 +162,43 @@ pub enum UpgradeableLoaderInstruction {
     ///   1. `[signer]` The current authority.
     ///   2. `[signer]` The new authority.
     SetAuthorityChecked,
+
+    /// Check that a Buffer account holds the expected program data.
+    ///
+    /// Fails unless the sha256 hash of the first `program_len` bytes of the
+    /// Buffer account's data, excluding its metadata, equals
+    /// `expected_hash`, and the bytes past them are all zero.  Buffers
+    /// created with a maximum length larger than the program are padded
+    /// with zeros, which are left out of the hash.  Including this
+    /// instruction before `DeployWithMaxDataLen` or `Upgrade` in the same
+    /// Transaction guarantees that the program deployed is the one whose
+    /// hash was reviewed, even if the buffer was rewritten after the review.
+    ///
+    /// # Account references
+    ///   0. `[]` The Buffer account to check.
+    VerifyBufferHash {
+        /// The sha256 hash of the Buffer account's program data.
+        expected_hash: Hash,
+        /// Length of the program data, excluding the zero padding.
+        program_len: u32,
+    },
+
+    /// Shrink a program's ProgramData account to the specified maximum
+    /// program length, the counterpart of `ExtendProgram`.
+    ///
+    /// The ProgramData account's lamports in excess of its rent exemption
+    /// cost at the new length are transferred to the recipient account.  The
+    /// program is redeployed from the truncated ProgramData account, so the
+    /// new length must still be able to hold the deployed program.
+    ///
+    /// # Account references
+    ///   0. `[writable]` The ProgramData account.
+    ///   1. `[writable]` The ProgramData account's associated Program account.
+    ///   2. `[writable]` The account to deposit the refunded lamports.
+    ///   3. `[signer]` The program's authority.
+    TruncateProgramData {
+        /// New maximum length of the program, excluding the ProgramData
+        /// account's metadata.
+        max_data_len: u32,
+    },
 }
@@ -32,6 +32,14 @@
 //!
 //! [`Pubkey`]: crate::pubkey::Pubkey
 //! [`Pubkey::log`]: crate::pubkey::Pubkey::log
+//!
+//! Logs meant to be consumed by indexers rather than read by people can be
+//! emitted with the [`msg_structured!`] and [`log_event!`] macros, which log a
+//! level, a target and key-value pairs through [`sol_log_data`] in a fixed
+//! schema, rather than as free-form strings.
+//!
+//! [`msg_structured!`]: crate::msg_structured!
+//! [`log_event!`]: crate::log_event!
 
 use crate::account_info::AccountInfo;
 
@@ -88,6 +96,135 @@ macro_rules! msg {
     ($($arg:tt)*) => ($crate::log::sol_log(&format!($($arg)*)));
 }
 
+/// Print a structured log message, tagged with its level and the module
+/// which logged it.
+///
+/// The first argument is a [`LogLevel`] and the second is the message, which
+/// must have type `&str`. Any following `key = value` pairs are logged as
+/// fields, with values formatted with [`format!`], which is relatively
+/// CPU-intensive. The log is encoded as described in [`sol_log_structured`].
+///
+/// [`format!`]: https://doc.rust-lang.org/std/fmt/fn.format.html
+///
+/// # Examples
+///
+/// ```
+/// use solana_program::{log::LogLevel, msg_structured};
+///
+/// let amount = 42;
+/// msg_structured!(LogLevel::Warn, "transfer exceeds daily limit", amount = amount);
+/// ```
+#[macro_export]
+macro_rules! msg_structured {
+    ($level:expr, $message:expr $(, $key:ident = $value:expr)* $(,)?) => {
+        $crate::log::sol_log_structured(
+            $level,
+            ::core::module_path!(),
+            $message,
+            &[$((::core::stringify!($key), &*::std::format!("{}", $value))),*],
+        )
+    };
+}
+
+/// Print a structured log of a named event, made of key-value pairs only.
+///
+/// The first argument is a [`LogLevel`] and the second is the name of the
+/// event, which must have type `&str` and is logged as the target. Any
+/// following `key = value` pairs are logged as with [`msg_structured!`].
+///
+/// # Examples
+///
+/// ```
+/// use solana_program::{log::LogLevel, log_event, pubkey::Pubkey};
+///
+/// let pool = Pubkey::new_unique();
+/// log_event!(LogLevel::Info, "swap", pool = pool, amount_in = 100, amount_out = 99);
+/// ```
+#[macro_export]
+macro_rules! log_event {
+    ($level:expr, $event:expr $(, $key:ident = $value:expr)* $(,)?) => {
+        $crate::log::sol_log_structured(
+            $level,
+            $event,
+            "",
+            &[$((::core::stringify!($key), &*::std::format!("{}", $value))),*],
+        )
+    };
+}
+
+/// Data logged with [`sol_log_data`] whose first field is this tag is a
+/// structured log, as logged by [`sol_log_structured`].
+pub const STRUCTURED_LOG_TAG: &[u8] = b"slog1";
+
+/// The severity of a structured log
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LogLevel {
+    Error = 1,
+    Warn = 2,
+    Info = 3,
+    Debug = 4,
+    Trace = 5,
+}
+
+impl LogLevel {
+    pub fn from_u8(level: u8) -> Option<Self> {
+        match level {
+            1 => Some(Self::Error),
+            2 => Some(Self::Warn),
+            3 => Some(Self::Info),
+            4 => Some(Self::Debug),
+            5 => Some(Self::Trace),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Error => "error",
+            Self::Warn => "warn",
+            Self::Info => "info",
+            Self::Debug => "debug",
+            Self::Trace => "trace",
+        }
+    }
+}
+
+/// Print a structured log.
+///
+/// The log is printed with [`sol_log_data`], as the fields [`STRUCTURED_LOG_TAG`],
+/// the level as a single byte, `target`, `message`, and then the key and
+/// value of each of `fields`, all of them UTF-8 except the level.
+pub fn sol_log_structured(level: LogLevel, target: &str, message: &str, fields: &[(&str, &str)]) {
+    encode_structured_log(level, target, message, fields, sol_log_data);
+}
+
+/// Encode a structured log as the data fields printed by
+/// [`sol_log_structured`], and pass them to `log`.
+///
+/// This lets the runtime print structured logs to its own log collector.
+pub fn encode_structured_log<R>(
+    level: LogLevel,
+    target: &str,
+    message: &str,
+    fields: &[(&str, &str)],
+    log: impl FnOnce(&[&[u8]]) -> R,
+) -> R {
+    let level = [level as u8];
+    let mut data: Vec<&[u8]> = Vec::with_capacity(fields.len().saturating_mul(2).saturating_add(4));
+    data.extend_from_slice(&[
+        STRUCTURED_LOG_TAG,
+        &level,
+        target.as_bytes(),
+        message.as_bytes(),
+    ]);
+    for (key, value) in fields {
+        data.push(key.as_bytes());
+        data.push(value.as_bytes());
+    }
+    log(&data)
+}
+
 /// Print a string to the log.
 #[inline]
 pub fn sol_log(message: &str) {
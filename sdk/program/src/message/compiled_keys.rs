@@ -5,7 +5,7 @@ use crate::{
 };
 use {
     crate::{instruction::Instruction, message::MessageHeader, pubkey::Pubkey},
-    std::collections::BTreeMap,
+    std::collections::{BTreeMap, BTreeSet},
     thiserror::Error,
 };
 
@@ -32,6 +32,8 @@ struct CompiledKeyMeta {
     is_signer: bool,
     is_writable: bool,
     is_invoked: bool,
+    /// The key must not be loaded from an address lookup table
+    is_static: bool,
 }
 
 impl CompiledKeys {
@@ -59,6 +61,14 @@ impl CompiledKeys {
         }
     }
 
+    /// Keeps `key` among the static account keys of the compiled message,
+    /// even if it could be loaded from an address lookup table.
+    pub(crate) fn pin_static_key(&mut self, key: &Pubkey) {
+        if let Some(meta) = self.key_meta_map.get_mut(key) {
+            meta.is_static = true;
+        }
+    }
+
     pub(crate) fn try_into_message_components(
         self,
     ) -> Result<(MessageHeader, Vec<Pubkey>), CompileError> {
@@ -123,11 +133,11 @@ impl CompiledKeys {
     ) -> Result<Option<(MessageAddressTableLookup, LoadedAddresses)>, CompileError> {
         let (writable_indexes, drained_writable_keys) = self
             .try_drain_keys_found_in_lookup_table(&lookup_table_account.addresses, |meta| {
-                !meta.is_signer && !meta.is_invoked && meta.is_writable
+                !meta.is_signer && !meta.is_invoked && !meta.is_static && meta.is_writable
             })?;
         let (readonly_indexes, drained_readonly_keys) = self
             .try_drain_keys_found_in_lookup_table(&lookup_table_account.addresses, |meta| {
-                !meta.is_signer && !meta.is_invoked && !meta.is_writable
+                !meta.is_signer && !meta.is_invoked && !meta.is_static && !meta.is_writable
             })?;
 
         // Don't extract lookup if no keys were found
@@ -148,6 +158,65 @@ impl CompiledKeys {
         )))
     }
 
+    /// Greedily selects the lookup tables which shrink the compiled message
+    /// the most, returning them in the order they should be used.
+    ///
+    /// Sourcing a key from a table saves its 32 byte static key at the cost of
+    /// a 1 byte index, but each table used costs its own 32 byte key plus two
+    /// index list lengths. So a table is only selected if it would load at
+    /// least two keys that no previously selected table already loads.
+    #[cfg(not(target_os = "solana"))]
+    pub(crate) fn select_lookup_tables<'a>(
+        &self,
+        lookup_table_accounts: &'a [AddressLookupTableAccount],
+    ) -> Vec<&'a AddressLookupTableAccount> {
+        const MIN_KEYS_PER_LOOKUP_TABLE: usize = 2;
+
+        let mut remaining_keys: BTreeSet<&Pubkey> = self
+            .key_meta_map
+            .iter()
+            .filter_map(|(key, meta)| {
+                (!meta.is_signer && !meta.is_invoked && !meta.is_static).then_some(key)
+            })
+            .collect();
+        let mut unselected: Vec<&AddressLookupTableAccount> =
+            lookup_table_accounts.iter().collect();
+        let mut selected = Vec::new();
+
+        loop {
+            let count_found_keys = |lookup_table_account: &AddressLookupTableAccount| {
+                remaining_keys
+                    .iter()
+                    .filter(|key| lookup_table_account.addresses.contains(**key))
+                    .count()
+            };
+            // ties go to the table listed first
+            let best = unselected
+                .iter()
+                .enumerate()
+                .map(|(index, lookup_table_account)| {
+                    (index, count_found_keys(lookup_table_account))
+                })
+                .fold(
+                    None,
+                    |best: Option<(usize, usize)>, (index, found)| match best {
+                        Some((_, best_found)) if best_found >= found => best,
+                        _ => Some((index, found)),
+                    },
+                );
+            match best {
+                Some((index, found)) if found >= MIN_KEYS_PER_LOOKUP_TABLE => {
+                    let lookup_table_account = unselected.remove(index);
+                    remaining_keys.retain(|key| !lookup_table_account.addresses.contains(*key));
+                    selected.push(lookup_table_account);
+                }
+                _ => break,
+            }
+        }
+
+        selected
+    }
+
     #[cfg(not(target_os = "solana"))]
     fn try_drain_keys_found_in_lookup_table(
         &mut self,
@@ -192,6 +261,7 @@ mod tests {
             const SIGNER   = 0b00000001;
             const WRITABLE = 0b00000010;
             const INVOKED  = 0b00000100;
+            const STATIC   = 0b00001000;
         }
     }
 
@@ -201,6 +271,7 @@ mod tests {
                 is_signer: flags.contains(KeyFlags::SIGNER),
                 is_writable: flags.contains(KeyFlags::WRITABLE),
                 is_invoked: flags.contains(KeyFlags::INVOKED),
+                is_static: flags.contains(KeyFlags::STATIC),
             }
         }
     }
@@ -481,6 +552,107 @@ mod tests {
         assert!(!compiled_keys.key_meta_map.contains_key(&keys[3]));
     }
 
+    #[test]
+    fn test_try_extract_table_lookup_skips_static_keys() {
+        let keys: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        let mut compiled_keys = CompiledKeys {
+            payer: None,
+            key_meta_map: BTreeMap::from([
+                (keys[0], KeyFlags::WRITABLE.into()),
+                (keys[1], KeyFlags::empty().into()),
+                (keys[2], KeyFlags::empty().into()),
+            ]),
+        };
+        compiled_keys.pin_static_key(&keys[0]);
+        compiled_keys.pin_static_key(&keys[1]);
+        assert_eq!(
+            compiled_keys.key_meta_map[&keys[0]],
+            CompiledKeyMeta::from(KeyFlags::WRITABLE | KeyFlags::STATIC)
+        );
+
+        let lookup_table_account = AddressLookupTableAccount {
+            key: Pubkey::new_unique(),
+            addresses: keys.clone(),
+        };
+        // a single remaining candidate is not worth a table
+        assert!(compiled_keys
+            .select_lookup_tables(std::slice::from_ref(&lookup_table_account))
+            .is_empty());
+        assert_eq!(
+            compiled_keys.try_extract_table_lookup(&lookup_table_account),
+            Ok(Some((
+                MessageAddressTableLookup {
+                    account_key: lookup_table_account.key,
+                    writable_indexes: vec![],
+                    readonly_indexes: vec![2],
+                },
+                LoadedAddresses {
+                    writable: vec![],
+                    readonly: vec![keys[2]],
+                },
+            )))
+        );
+        assert!(compiled_keys.key_meta_map.contains_key(&keys[0]));
+        assert!(compiled_keys.key_meta_map.contains_key(&keys[1]));
+    }
+
+    #[test]
+    fn test_select_lookup_tables() {
+        let keys: Vec<Pubkey> = (0..8).map(|_| Pubkey::new_unique()).collect();
+        let compiled_keys = CompiledKeys {
+            payer: None,
+            key_meta_map: BTreeMap::from([
+                (keys[0], (KeyFlags::SIGNER | KeyFlags::WRITABLE).into()),
+                (keys[1], (KeyFlags::INVOKED).into()),
+                (keys[2], KeyFlags::WRITABLE.into()),
+                (keys[3], KeyFlags::WRITABLE.into()),
+                (keys[4], KeyFlags::empty().into()),
+                (keys[5], KeyFlags::empty().into()),
+                (keys[6], KeyFlags::empty().into()),
+                (keys[7], KeyFlags::empty().into()),
+            ]),
+        };
+        let lookup_table_account = |addresses: &[Pubkey]| AddressLookupTableAccount {
+            key: Pubkey::new_unique(),
+            addresses: addresses.to_vec(),
+        };
+
+        // signers and invoked keys can't be loaded from a table
+        let unloadable = lookup_table_account(&keys[0..2]);
+        // only loads a single key
+        let single = lookup_table_account(&[keys[7], keys[1]]);
+        let small = lookup_table_account(&keys[2..4]);
+        let large = lookup_table_account(&keys[3..7]);
+        // only covers keys the large table already loads
+        let redundant = lookup_table_account(&keys[4..7]);
+        let candidates = vec![unloadable, single, small.clone(), redundant, large.clone()];
+
+        // the large table is selected first, which leaves only one key for
+        // the small table, so it is skipped too
+        assert_eq!(
+            compiled_keys.select_lookup_tables(&candidates),
+            vec![&large]
+        );
+
+        // once the large table can't cover key 3, the small table is worthwhile
+        let large = lookup_table_account(&keys[4..7]);
+        let candidates = vec![large.clone(), small.clone()];
+        assert_eq!(
+            compiled_keys.select_lookup_tables(&candidates),
+            vec![&large, &small]
+        );
+
+        // ties keep the candidate order
+        let other_small = lookup_table_account(&keys[2..4]);
+        let candidates = vec![other_small.clone(), small];
+        assert_eq!(
+            compiled_keys.select_lookup_tables(&candidates),
+            vec![&other_small]
+        );
+
+        assert!(compiled_keys.select_lookup_tables(&[]).is_empty());
+    }
+
     #[test]
     fn test_try_extract_table_lookup_returns_none() {
         let mut compiled_keys = CompiledKeys {
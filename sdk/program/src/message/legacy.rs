@@ -492,6 +492,20 @@ impl Message {
         bincode::serialize(self).unwrap()
     }
 
+    /// The size in bytes of a transaction of this message, once signed by all
+    /// of its required signers.
+    ///
+    /// Transactions must fit into a packet of [`PACKET_DATA_SIZE`] bytes to be
+    /// sent to the network.
+    ///
+    /// [`PACKET_DATA_SIZE`]: https://docs.rs/solana-sdk/latest/solana_sdk/packet/constant.PACKET_DATA_SIZE.html
+    pub fn serialized_size_with_signatures(&self) -> usize {
+        super::serialized_transaction_size(
+            self.header.num_required_signatures,
+            bincode::serialized_size(self).unwrap(),
+        )
+    }
+
     pub fn program_id(&self, instruction_index: usize) -> Option<&Pubkey> {
         Some(
             &self.account_keys[self.instructions.get(instruction_index)?.program_id_index as usize],
@@ -58,6 +58,20 @@ pub use {compiled_keys::CompileError, legacy::Message};
 /// The length of a message header in bytes.
 pub const MESSAGE_HEADER_LENGTH: usize = 3;
 
+/// The length of a transaction signature in bytes, as in
+/// `solana_sdk::signature::SIGNATURE_BYTES`.
+const SIGNATURE_BYTES: usize = 64;
+
+/// The size of a serialized transaction with `num_signatures` signatures over
+/// a message of `message_size` bytes.
+fn serialized_transaction_size(num_signatures: u8, message_size: u64) -> usize {
+    let signatures_len_size =
+        bincode::serialized_size(&crate::short_vec::ShortU16(u16::from(num_signatures))).unwrap();
+    usize::from(num_signatures)
+        .saturating_mul(SIGNATURE_BYTES)
+        .saturating_add(signatures_len_size.saturating_add(message_size) as usize)
+}
+
 /// Describes the organization of a `Message`'s account keys.
 ///
 /// Every [`Instruction`] specifies which accounts it may reference, or
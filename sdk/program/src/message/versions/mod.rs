@@ -136,6 +136,15 @@ impl VersionedMessage {
         bincode::serialize(self).unwrap()
     }
 
+    /// The size in bytes of a transaction of this message, once signed by all
+    /// of its required signers.
+    pub fn serialized_size_with_signatures(&self) -> usize {
+        match self {
+            Self::Legacy(message) => message.serialized_size_with_signatures(),
+            Self::V0(message) => message.serialized_size_with_signatures(),
+        }
+    }
+
     /// Compute the blake3 hash of this transaction's message
     pub fn hash(&self) -> Hash {
         let message_bytes = self.serialize();
@@ -17,11 +17,11 @@ use crate::{
     message::{
         compiled_keys::{CompileError, CompiledKeys},
         legacy::is_builtin_key_or_sysvar,
-        AccountKeys, MessageHeader, MESSAGE_VERSION_PREFIX,
+        serialized_transaction_size, AccountKeys, MessageHeader, MESSAGE_VERSION_PREFIX,
     },
     pubkey::Pubkey,
     sanitize::SanitizeError,
-    short_vec,
+    short_vec, system_instruction,
 };
 pub use loaded::*;
 
@@ -257,10 +257,157 @@ impl Message {
         address_lookup_table_accounts: &[AddressLookupTableAccount],
         recent_blockhash: Hash,
     ) -> Result<Self, CompileError> {
-        let mut compiled_keys = CompiledKeys::compile(instructions, Some(*payer));
+        let compiled_keys = CompiledKeys::compile(instructions, Some(*payer));
+        Self::try_compile_with_lookup_tables(
+            compiled_keys,
+            instructions,
+            address_lookup_table_accounts,
+            recent_blockhash,
+        )
+    }
+
+    /// Create a signable transaction message like [`Message::try_compile`],
+    /// but only use the candidate `address_lookup_table_accounts` which make
+    /// the message smaller.
+    ///
+    /// Tables are chosen greedily: the table which loads the most remaining
+    /// accounts is used first, and tables which would not load at least two
+    /// accounts beyond those already loaded are skipped, since referencing
+    /// them costs more bytes than they save. The chosen table keys and the
+    /// indexes loaded from each are returned in the message's
+    /// `address_table_lookups`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_program::{
+    /// #     address_lookup_table_account::AddressLookupTableAccount,
+    /// #     hash::Hash,
+    /// #     instruction::{AccountMeta, Instruction},
+    /// #     message::v0,
+    /// #     pubkey::Pubkey,
+    /// # };
+    /// let payer = Pubkey::new_unique();
+    /// let accounts: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+    /// let instruction = Instruction::new_with_bytes(
+    ///     Pubkey::new_unique(),
+    ///     &[],
+    ///     accounts.iter().map(|key| AccountMeta::new(*key, false)).collect(),
+    /// );
+    /// let small_table = AddressLookupTableAccount {
+    ///     key: Pubkey::new_unique(),
+    ///     addresses: accounts[..1].to_vec(),
+    /// };
+    /// let large_table = AddressLookupTableAccount {
+    ///     key: Pubkey::new_unique(),
+    ///     addresses: accounts.clone(),
+    /// };
+    ///
+    /// let message = v0::Message::try_compile_with_lookup_table_selection(
+    ///     &payer,
+    ///     &[instruction],
+    ///     &[small_table, large_table.clone()],
+    ///     Hash::default(),
+    /// )?;
+    /// assert_eq!(message.address_table_lookups.len(), 1);
+    /// assert_eq!(message.address_table_lookups[0].account_key, large_table.key);
+    /// # Ok::<(), solana_program::message::CompileError>(())
+    /// ```
+    pub fn try_compile_with_lookup_table_selection(
+        payer: &Pubkey,
+        instructions: &[Instruction],
+        address_lookup_table_accounts: &[AddressLookupTableAccount],
+        recent_blockhash: Hash,
+    ) -> Result<Self, CompileError> {
+        let compiled_keys = CompiledKeys::compile(instructions, Some(*payer));
+        let selected_lookup_table_accounts =
+            compiled_keys.select_lookup_tables(address_lookup_table_accounts);
+        Self::try_compile_with_lookup_tables(
+            compiled_keys,
+            instructions,
+            selected_lookup_table_accounts,
+            recent_blockhash,
+        )
+    }
+
+    /// Create a signable transaction message like [`Message::try_compile`],
+    /// whose lifetime is bounded by a [durable transaction nonce] rather than
+    /// by a recent blockhash.
+    ///
+    /// An [`AdvanceNonceAccount`] instruction is inserted before the given
+    /// `instructions`, as the runtime only recognizes the nonce of a
+    /// transaction from its first instruction. `nonce_hash` is the durable
+    /// nonce currently stored in the nonce account.
+    ///
+    /// The nonce account is kept among the static account keys, even if one
+    /// of the `address_lookup_table_accounts` contains it, so that clients can
+    /// find it without loading the tables. The nonce authority signs the
+    /// transaction, so it is always a static key as well.
+    ///
+    /// [durable transaction nonce]: https://docs.solanalabs.com/implemented-proposals/durable-tx-nonces
+    /// [`AdvanceNonceAccount`]: crate::system_instruction::SystemInstruction::AdvanceNonceAccount
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_program::{
+    /// #     address_lookup_table_account::AddressLookupTableAccount,
+    /// #     hash::Hash,
+    /// #     message::v0,
+    /// #     pubkey::Pubkey,
+    /// #     system_instruction,
+    /// # };
+    /// let payer = Pubkey::new_unique();
+    /// let nonce_account = Pubkey::new_unique();
+    /// let recipient = Pubkey::new_unique();
+    /// let address_lookup_table_account = AddressLookupTableAccount {
+    ///     key: Pubkey::new_unique(),
+    ///     addresses: vec![nonce_account, recipient],
+    /// };
+    ///
+    /// let message = v0::Message::try_compile_with_nonce(
+    ///     &payer,
+    ///     &[system_instruction::transfer(&payer, &recipient, 42)],
+    ///     &nonce_account,
+    ///     &payer,
+    ///     &[address_lookup_table_account],
+    ///     Hash::new_unique(),
+    /// )?;
+    /// assert!(message.account_keys.contains(&nonce_account));
+    /// assert!(!message.account_keys.contains(&recipient));
+    /// # Ok::<(), solana_program::message::CompileError>(())
+    /// ```
+    pub fn try_compile_with_nonce(
+        payer: &Pubkey,
+        instructions: &[Instruction],
+        nonce_account_pubkey: &Pubkey,
+        nonce_authority_pubkey: &Pubkey,
+        address_lookup_table_accounts: &[AddressLookupTableAccount],
+        nonce_hash: Hash,
+    ) -> Result<Self, CompileError> {
+        let instructions: Vec<Instruction> = std::iter::once(
+            system_instruction::advance_nonce_account(nonce_account_pubkey, nonce_authority_pubkey),
+        )
+        .chain(instructions.iter().cloned())
+        .collect();
+        let mut compiled_keys = CompiledKeys::compile(&instructions, Some(*payer));
+        compiled_keys.pin_static_key(nonce_account_pubkey);
+        Self::try_compile_with_lookup_tables(
+            compiled_keys,
+            &instructions,
+            address_lookup_table_accounts,
+            nonce_hash,
+        )
+    }
 
-        let mut address_table_lookups = Vec::with_capacity(address_lookup_table_accounts.len());
-        let mut loaded_addresses_list = Vec::with_capacity(address_lookup_table_accounts.len());
+    fn try_compile_with_lookup_tables<'a>(
+        mut compiled_keys: CompiledKeys,
+        instructions: &[Instruction],
+        address_lookup_table_accounts: impl IntoIterator<Item = &'a AddressLookupTableAccount>,
+        recent_blockhash: Hash,
+    ) -> Result<Self, CompileError> {
+        let mut address_table_lookups = Vec::new();
+        let mut loaded_addresses_list = Vec::new();
         for lookup_table_account in address_lookup_table_accounts {
             if let Some((lookup, loaded_addresses)) =
                 compiled_keys.try_extract_table_lookup(lookup_table_account)?
@@ -289,6 +436,16 @@ impl Message {
         bincode::serialize(&(MESSAGE_VERSION_PREFIX, self)).unwrap()
     }
 
+    /// The size in bytes of a transaction of this message, once signed by all
+    /// of its required signers. Accounts loaded from address lookup tables
+    /// only take up the bytes of their indexes.
+    pub fn serialized_size_with_signatures(&self) -> usize {
+        serialized_transaction_size(
+            self.header.num_required_signatures,
+            bincode::serialized_size(&(MESSAGE_VERSION_PREFIX, self)).unwrap(),
+        )
+    }
+
     /// Returns true if the account at the specified index is called as a program by an instruction
     pub fn is_key_called_as_program(&self, key_index: usize) -> bool {
         if let Ok(key_index) = u8::try_from(key_index) {
@@ -685,4 +842,139 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn test_try_compile_with_lookup_table_selection() {
+        let mut keys = vec![];
+        keys.resize_with(6, Pubkey::new_unique);
+
+        let payer = keys[0];
+        let program_id = keys[1];
+        let instructions = vec![Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(keys[2], false),
+                AccountMeta::new(keys[3], false),
+                AccountMeta::new_readonly(keys[4], false),
+                AccountMeta::new_readonly(keys[5], false),
+            ],
+            data: vec![],
+        }];
+        let single_key_table = AddressLookupTableAccount {
+            key: Pubkey::new_unique(),
+            addresses: vec![keys[5]],
+        };
+        let table = AddressLookupTableAccount {
+            key: Pubkey::new_unique(),
+            addresses: vec![keys[1], keys[2], keys[3], keys[4]],
+        };
+        let address_lookup_table_accounts = vec![single_key_table, table.clone()];
+
+        let recent_blockhash = Hash::new_unique();
+        let message = Message::try_compile_with_lookup_table_selection(
+            &payer,
+            &instructions,
+            &address_lookup_table_accounts,
+            recent_blockhash,
+        )
+        .unwrap();
+        assert_eq!(
+            message.address_table_lookups,
+            vec![MessageAddressTableLookup {
+                account_key: table.key,
+                writable_indexes: vec![1, 2],
+                readonly_indexes: vec![3],
+            }]
+        );
+        assert_eq!(
+            message,
+            Message::try_compile(&payer, &instructions, &[table], recent_blockhash).unwrap()
+        );
+
+        // using every table produces a larger message
+        let message_with_all_tables = Message::try_compile(
+            &payer,
+            &instructions,
+            &address_lookup_table_accounts,
+            recent_blockhash,
+        )
+        .unwrap();
+        assert_eq!(message_with_all_tables.address_table_lookups.len(), 2);
+        assert!(message.serialize().len() < message_with_all_tables.serialize().len());
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_try_compile_with_nonce() {
+        let mut keys = vec![];
+        keys.resize_with(5, Pubkey::new_unique);
+
+        let payer = keys[0];
+        let nonce_account = keys[1];
+        let nonce_authority = keys[2];
+        let program_id = keys[3];
+        let instructions = vec![Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(nonce_account, false),
+                AccountMeta::new(keys[4], false), // loaded from lut
+            ],
+            data: vec![],
+        }];
+        let address_lookup_table_accounts = vec![AddressLookupTableAccount {
+            key: Pubkey::new_unique(),
+            addresses: vec![
+                nonce_account,
+                keys[4],
+                crate::sysvar::recent_blockhashes::id(),
+            ],
+        }];
+
+        let nonce_hash = Hash::new_unique();
+        let message = Message::try_compile_with_nonce(
+            &payer,
+            &instructions,
+            &nonce_account,
+            &nonce_authority,
+            &address_lookup_table_accounts,
+            nonce_hash,
+        )
+        .unwrap();
+        assert_eq!(message.recent_blockhash, nonce_hash);
+        assert_eq!(message.header.num_required_signatures, 2);
+        assert_eq!(message.account_keys[..2], [payer, nonce_authority]);
+        assert_eq!(
+            message.address_table_lookups,
+            vec![MessageAddressTableLookup {
+                account_key: address_lookup_table_accounts[0].key,
+                writable_indexes: vec![1],
+                readonly_indexes: vec![2],
+            }]
+        );
+
+        // the nonce is advanced first, and its account is a static key
+        assert_eq!(message.instructions.len(), 2);
+        let advance_nonce = &message.instructions[0];
+        assert_eq!(
+            message.account_keys[usize::from(advance_nonce.program_id_index)],
+            crate::system_program::id()
+        );
+        assert_eq!(
+            advance_nonce.data,
+            bincode::serialize(&system_instruction::SystemInstruction::AdvanceNonceAccount)
+                .unwrap()
+        );
+        let nonce_account_index = usize::from(advance_nonce.accounts[0]);
+        assert_eq!(message.account_keys[nonce_account_index], nonce_account);
+        assert!(message.is_maybe_writable(nonce_account_index));
+        assert_eq!(
+            message.account_keys[usize::from(advance_nonce.accounts[2])],
+            nonce_authority
+        );
+        assert_eq!(
+            message.instructions[1].accounts[0],
+            advance_nonce.accounts[0]
+        );
+        assert_eq!(message.sanitize(), Ok(()));
+    }
 }
@@ -5,7 +5,7 @@
 use {
     crate::{
         account_info::AccountInfo, entrypoint::ProgramResult, instruction::Instruction,
-        program_error::UNSUPPORTED_SYSVAR, pubkey::Pubkey,
+        program_error::UNSUPPORTED_SYSVAR, pubkey::Pubkey, rent::Rent,
     },
     base64::{prelude::BASE64_STANDARD, Engine},
     itertools::Itertools,
@@ -61,6 +61,15 @@ pub trait SyscallStubs: Sync + Send {
     fn sol_get_last_restart_slot(&self, _var_addr: *mut u8) -> u64 {
         UNSUPPORTED_SYSVAR
     }
+    fn sol_get_sysvar(
+        &self,
+        _sysvar_id_addr: *const u8,
+        _var_addr: *mut u8,
+        _offset: u64,
+        _length: u64,
+    ) -> u64 {
+        UNSUPPORTED_SYSVAR
+    }
     /// # Safety
     unsafe fn sol_memcpy(&self, dst: *mut u8, src: *const u8, n: usize) {
         // cannot be overlapping
@@ -111,6 +120,12 @@ pub trait SyscallStubs: Sync + Send {
     fn sol_get_stack_height(&self) -> u64 {
         0
     }
+    fn sol_get_caller_program_id(&self) -> Option<Pubkey> {
+        None
+    }
+    fn sol_get_minimum_balance_for_rent_exemption(&self, data_len: u64) -> u64 {
+        Rent::default().minimum_balance(data_len as usize)
+    }
 }
 
 struct DefaultSyscallStubs {}
@@ -171,6 +186,18 @@ pub(crate) fn sol_get_last_restart_slot(var_addr: *mut u8) -> u64 {
         .sol_get_last_restart_slot(var_addr)
 }
 
+pub(crate) fn sol_get_sysvar(
+    sysvar_id_addr: *const u8,
+    var_addr: *mut u8,
+    offset: u64,
+    length: u64,
+) -> u64 {
+    SYSCALL_STUBS
+        .read()
+        .unwrap()
+        .sol_get_sysvar(sysvar_id_addr, var_addr, offset, length)
+}
+
 pub(crate) fn sol_memcpy(dst: *mut u8, src: *const u8, n: usize) {
     unsafe {
         SYSCALL_STUBS.read().unwrap().sol_memcpy(dst, src, n);
@@ -218,6 +245,17 @@ pub(crate) fn sol_get_stack_height() -> u64 {
     SYSCALL_STUBS.read().unwrap().sol_get_stack_height()
 }
 
+pub(crate) fn sol_get_caller_program_id() -> Option<Pubkey> {
+    SYSCALL_STUBS.read().unwrap().sol_get_caller_program_id()
+}
+
+pub(crate) fn sol_get_minimum_balance_for_rent_exemption(data_len: u64) -> u64 {
+    SYSCALL_STUBS
+        .read()
+        .unwrap()
+        .sol_get_minimum_balance_for_rent_exemption(data_len)
+}
+
 pub(crate) fn sol_get_epoch_rewards_sysvar(var_addr: *mut u8) -> u64 {
     SYSCALL_STUBS
         .read()
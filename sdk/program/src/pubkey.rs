@@ -28,6 +28,18 @@ const MAX_BASE58_LEN: usize = 44;
 
 const PDA_MARKER: &[u8; 21] = b"ProgramDerivedAddress";
 
+const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+const INVALID_BASE58_DIGIT: u8 = u8::MAX;
+const BASE58_DIGITS: [u8; 256] = {
+    let mut digits = [INVALID_BASE58_DIGIT; 256];
+    let mut i = 0;
+    while i < BASE58_ALPHABET.len() {
+        digits[BASE58_ALPHABET[i] as usize] = i as u8;
+        i += 1;
+    }
+    digits
+};
+
 #[derive(Error, Debug, Serialize, Clone, PartialEq, Eq, FromPrimitive, ToPrimitive)]
 pub enum PubkeyError {
     /// Length of the seed is too long for address generation
@@ -188,6 +200,70 @@ impl Pubkey {
         Self(pubkey_array)
     }
 
+    /// Decode a base58 string into a `Pubkey` in a `const` context.
+    ///
+    /// Unlike [`FromStr`], this can be used to initialize `const` and
+    /// `static` items without `lazy_static` or parsing at runtime. Invalid
+    /// input panics, which is reported as a compile error when evaluated in a
+    /// `const` context. The [`pubkey!`](crate::pubkey) macro performs the same
+    /// validation when the address is a literal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use solana_program::pubkey::Pubkey;
+    ///
+    /// const ID: Pubkey = Pubkey::from_str_const("My11111111111111111111111111111111111111111");
+    /// ```
+    pub const fn from_str_const(s: &str) -> Self {
+        let input = s.as_bytes();
+        if input.len() > MAX_BASE58_LEN {
+            panic!("base58 string is too long for a Pubkey");
+        }
+
+        // big-endian accumulation of the base58 digits
+        let mut bytes = [0u8; PUBKEY_BYTES];
+        let mut leading_ones = 0;
+        let mut leading = true;
+        let mut i = 0;
+        while i < input.len() {
+            let digit = BASE58_DIGITS[input[i] as usize];
+            if digit == INVALID_BASE58_DIGIT {
+                panic!("invalid base58 character in Pubkey");
+            }
+            if leading && digit == 0 {
+                leading_ones += 1;
+            } else {
+                leading = false;
+            }
+
+            let mut carry = digit as u32;
+            let mut j = PUBKEY_BYTES;
+            while j > 0 {
+                j -= 1;
+                carry += bytes[j] as u32 * 58;
+                bytes[j] = (carry & 0xff) as u8;
+                carry >>= 8;
+            }
+            if carry != 0 {
+                panic!("base58 string decodes to more than 32 bytes");
+            }
+            i += 1;
+        }
+
+        // each leading '1' encodes a leading zero byte, so the decoded length
+        // is only 32 bytes if they account for all of the zero bytes up front
+        let mut leading_zero_bytes = 0;
+        while leading_zero_bytes < PUBKEY_BYTES && bytes[leading_zero_bytes] == 0 {
+            leading_zero_bytes += 1;
+        }
+        if leading_ones != leading_zero_bytes {
+            panic!("base58 string does not decode to 32 bytes");
+        }
+
+        Self(bytes)
+    }
+
     #[deprecated(since = "1.3.9", note = "Please use 'Pubkey::new_unique' instead")]
     #[cfg(not(target_os = "solana"))]
     pub fn new_rand() -> Self {
@@ -749,6 +825,55 @@ mod tests {
         assert!(Pubkey::new_unique() != Pubkey::new_unique());
     }
 
+    #[test]
+    fn test_from_str_const() {
+        const ID: Pubkey = Pubkey::from_str_const("My11111111111111111111111111111111111111111");
+        assert_eq!(
+            ID,
+            Pubkey::from_str("My11111111111111111111111111111111111111111").unwrap()
+        );
+        assert_eq!(
+            Pubkey::from_str_const("11111111111111111111111111111111"),
+            Pubkey::default()
+        );
+
+        for pubkey in [
+            Pubkey::new_unique(),
+            Pubkey::from([u8::MAX; 32]),
+            Pubkey::from([
+                0, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21,
+                22, 23, 24, 25, 26, 27, 28, 29, 30,
+            ]),
+        ] {
+            let pubkey_base58_str = pubkey.to_string();
+            assert_eq!(Pubkey::from_str_const(&pubkey_base58_str), pubkey);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid base58 character")]
+    fn test_from_str_const_invalid_character() {
+        Pubkey::from_str_const("Il11111111111111111111111111111111111111111");
+    }
+
+    #[test]
+    #[should_panic(expected = "does not decode to 32 bytes")]
+    fn test_from_str_const_too_short() {
+        Pubkey::from_str_const("1111111111111111111111111111111");
+    }
+
+    #[test]
+    #[should_panic(expected = "more than 32 bytes")]
+    fn test_from_str_const_too_large() {
+        Pubkey::from_str_const(&"z".repeat(MAX_BASE58_LEN));
+    }
+
+    #[test]
+    #[should_panic(expected = "too long")]
+    fn test_from_str_const_too_long() {
+        Pubkey::from_str_const(&"1".repeat(MAX_BASE58_LEN + 1));
+    }
+
     #[test]
     fn pubkey_fromstr() {
         let pubkey = Pubkey::new_unique();
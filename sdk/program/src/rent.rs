@@ -4,11 +4,25 @@
 
 #![allow(clippy::arithmetic_side_effects)]
 
-use {crate::clock::DEFAULT_SLOTS_PER_EPOCH, solana_sdk_macro::CloneZeroed};
+use {
+    crate::clock::DEFAULT_SLOTS_PER_EPOCH,
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_sdk_macro::CloneZeroed,
+};
 
 /// Configuration of network rent.
 #[repr(C)]
-#[derive(Serialize, Deserialize, PartialEq, CloneZeroed, Debug, AbiExample)]
+#[derive(
+    Serialize,
+    Deserialize,
+    BorshSerialize,
+    BorshDeserialize,
+    PartialEq,
+    CloneZeroed,
+    Debug,
+    AbiExample,
+)]
+#[borsh(crate = "borsh")]
 pub struct Rent {
     /// Rental rate in lamports/byte-year.
     pub lamports_per_byte_year: u64,
@@ -151,7 +165,7 @@ impl RentDue {
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use {super::*, crate::sysvar::Sysvar};
 
     #[test]
     fn test_due() {
@@ -224,4 +238,17 @@ mod tests {
         let cloned_rent = rent.clone();
         assert_eq!(cloned_rent, rent);
     }
+
+    #[test]
+    fn test_borsh_layout() {
+        let rent = Rent {
+            lamports_per_byte_year: 1,
+            exemption_threshold: 2.5,
+            burn_percent: 3,
+        };
+        let borsh_serialized = borsh::to_vec(&rent).unwrap();
+        assert_eq!(borsh_serialized, bincode::serialize(&rent).unwrap());
+        assert_eq!(borsh_serialized.len(), Rent::size_of());
+        assert_eq!(Rent::try_from_slice(&borsh_serialized).unwrap(), rent);
+    }
 }
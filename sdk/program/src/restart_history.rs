@@ -0,0 +1,107 @@
+//! A type to hold data for the [`RestartHistory` sysvar][sv].
+//!
+//! The sysvar ID is declared in [`sysvar::restart_history`].
+//!
+//! [sv]: crate::sysvar::restart_history
+//! [`sysvar::restart_history`]: crate::sysvar::restart_history
+
+use crate::clock::{Slot, UnixTimestamp};
+
+/// Maximum number of restarts recorded in the sysvar
+pub const MAX_ENTRIES: usize = 16;
+
+/// Byte offset of the number of restarts in the sysvar account data
+pub const NUM_RESTARTS_OFFSET: usize = 0;
+/// Byte offset of the first [`RestartEntry`] in the sysvar account data
+pub const RESTARTS_OFFSET: usize = 8;
+/// Size in bytes of a serialized [`RestartEntry`]
+pub const RESTART_ENTRY_SIZE: usize = 16;
+
+#[repr(C)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default, AbiExample)]
+pub struct RestartEntry {
+    /// The slot of the hard fork the cluster restarted from
+    pub slot: Slot,
+    /// The timestamp of the first bank which observed the restart
+    pub unix_timestamp: UnixTimestamp,
+}
+
+#[repr(C)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default, AbiExample)]
+pub struct RestartHistory(Vec<RestartEntry>);
+
+impl RestartHistory {
+    /// Records a restart, dropping the oldest one once `MAX_ENTRIES` are
+    /// recorded
+    pub fn add(&mut self, slot: Slot, unix_timestamp: UnixTimestamp) {
+        self.0.insert(
+            0,
+            RestartEntry {
+                slot,
+                unix_timestamp,
+            },
+        );
+        self.0.truncate(MAX_ENTRIES);
+    }
+
+    /// The recorded restarts, most recent first
+    pub fn restarts(&self) -> &[RestartEntry] {
+        &self.0
+    }
+
+    /// The most recent restart, if any is recorded
+    pub fn last(&self) -> Option<&RestartEntry> {
+        self.0.first()
+    }
+
+    /// Byte offset of the restart at `index` in the sysvar account data
+    pub fn restart_offset(index: usize) -> usize {
+        RESTARTS_OFFSET.saturating_add(index.saturating_mul(RESTART_ENTRY_SIZE))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_restart_history_add() {
+        let mut restart_history = RestartHistory::default();
+        assert_eq!(restart_history.last(), None);
+        for slot in 0..MAX_ENTRIES as Slot + 2 {
+            restart_history.add(slot, 100 + slot as UnixTimestamp);
+        }
+        let restarts = restart_history.restarts();
+        assert_eq!(restarts.len(), MAX_ENTRIES);
+        assert_eq!(
+            restart_history.last(),
+            Some(&RestartEntry {
+                slot: MAX_ENTRIES as Slot + 1,
+                unix_timestamp: 101 + MAX_ENTRIES as UnixTimestamp,
+            })
+        );
+        assert_eq!(restarts[MAX_ENTRIES - 1].slot, 2);
+    }
+
+    #[test]
+    fn test_restart_history_layout() {
+        let mut restart_history = RestartHistory::default();
+        restart_history.add(7, -1);
+        restart_history.add(42, 1_700_000_000);
+        let data = bincode::serialize(&restart_history).unwrap();
+        assert_eq!(
+            data[NUM_RESTARTS_OFFSET..RESTARTS_OFFSET],
+            2u64.to_le_bytes()
+        );
+        let offset = RestartHistory::restart_offset(1);
+        assert_eq!(
+            bincode::deserialize::<RestartEntry>(&data[offset..offset + RESTART_ENTRY_SIZE])
+                .unwrap(),
+            RestartEntry {
+                slot: 7,
+                unix_timestamp: -1
+            }
+        );
+        assert_eq!(data.len(), RestartHistory::restart_offset(2));
+    }
+}
@@ -10,7 +10,7 @@ use {
             program::id,
             state::{Authorized, Lockup, StakeAuthorize, StakeStateV2},
         },
-        system_instruction, sysvar,
+        system_instruction, system_program, sysvar,
     },
     log::*,
     num_derive::{FromPrimitive, ToPrimitive},
@@ -293,6 +293,35 @@ pub enum StakeInstruction {
     ///   4. `[SIGNER]` Stake authority
     ///
     Redelegate,
+
+    /// Split u64 tokens and stake off a stake account into another stake account, funding the
+    /// new stake account's rent-exempt reserve from a separate account.
+    ///
+    /// This instruction behaves like `Split`, except that any lamports the split stake account
+    /// lacks for its rent-exempt reserve are first transferred to it from the funding account, so
+    /// that none of the split-off u64 tokens go towards the reserve. The reserve is not funded
+    /// when the entire balance of the stake account is split off, as its own reserve moves along.
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Stake account to be split; must be in the Initialized or Stake state
+    ///   1. `[WRITE]` Uninitialized stake account that will take the split-off amount
+    ///   2. `[WRITE, SIGNER]` Funding account for the rent-exempt reserve
+    ///   3. `[]` System program
+    ///   4. `[SIGNER]` Stake authority
+    SplitWithFunding(u64),
+
+    /// Deactivate several stake accounts which share a stake authority
+    ///
+    /// This instruction behaves like `Deactivate` for each of the given stake accounts, and fails
+    /// unless all of them can be deactivated.
+    ///
+    /// # Account references
+    ///   0. `[SIGNER]` Stake authority
+    ///   1. ..1+N `[WRITE]` Delegated stake accounts to deactivate, at most
+    ///      [`MAX_DEACTIVATE_MULTIPLE_STAKE_ACCOUNTS`]
+    ///
+    /// [`MAX_DEACTIVATE_MULTIPLE_STAKE_ACCOUNTS`]: super::MAX_DEACTIVATE_MULTIPLE_STAKE_ACCOUNTS
+    DeactivateMultiple,
 }
 
 #[derive(Default, Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
@@ -462,6 +491,48 @@ pub fn split(
     ]
 }
 
+fn _split_with_funding(
+    stake_pubkey: &Pubkey,
+    authorized_pubkey: &Pubkey,
+    lamports: u64,
+    split_stake_pubkey: &Pubkey,
+    funding_pubkey: &Pubkey,
+) -> Instruction {
+    let account_metas = vec![
+        AccountMeta::new(*stake_pubkey, false),
+        AccountMeta::new(*split_stake_pubkey, false),
+        AccountMeta::new(*funding_pubkey, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(*authorized_pubkey, true),
+    ];
+
+    Instruction::new_with_bincode(
+        id(),
+        &StakeInstruction::SplitWithFunding(lamports),
+        account_metas,
+    )
+}
+
+pub fn split_with_funding(
+    stake_pubkey: &Pubkey,
+    authorized_pubkey: &Pubkey,
+    lamports: u64,
+    split_stake_pubkey: &Pubkey,
+    funding_pubkey: &Pubkey,
+) -> Vec<Instruction> {
+    vec![
+        system_instruction::allocate(split_stake_pubkey, StakeStateV2::size_of() as u64),
+        system_instruction::assign(split_stake_pubkey, &id()),
+        _split_with_funding(
+            stake_pubkey,
+            authorized_pubkey,
+            lamports,
+            split_stake_pubkey,
+            funding_pubkey,
+        ),
+    ]
+}
+
 pub fn split_with_seed(
     stake_pubkey: &Pubkey,
     authorized_pubkey: &Pubkey,
@@ -714,6 +785,17 @@ pub fn deactivate_stake(stake_pubkey: &Pubkey, authorized_pubkey: &Pubkey) -> In
     Instruction::new_with_bincode(id(), &StakeInstruction::Deactivate, account_metas)
 }
 
+pub fn deactivate_stakes(stake_pubkeys: &[Pubkey], authorized_pubkey: &Pubkey) -> Instruction {
+    let account_metas = std::iter::once(AccountMeta::new_readonly(*authorized_pubkey, true))
+        .chain(
+            stake_pubkeys
+                .iter()
+                .map(|stake_pubkey| AccountMeta::new(*stake_pubkey, false)),
+        )
+        .collect();
+    Instruction::new_with_bincode(id(), &StakeInstruction::DeactivateMultiple, account_metas)
+}
+
 pub fn set_lockup(
     stake_pubkey: &Pubkey,
     lockup: &LockupArgs,
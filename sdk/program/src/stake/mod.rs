@@ -19,3 +19,7 @@ pub mod program {
 /// The minimum number of epochs before stake account that is delegated to a delinquent vote
 /// account may be unstaked with `StakeInstruction::DeactivateDelinquent`
 pub const MINIMUM_DELINQUENT_EPOCHS_FOR_DEACTIVATION: usize = 5;
+
+/// The maximum number of stake accounts that may be deactivated at once with
+/// `StakeInstruction::DeactivateMultiple`
+pub const MAX_DEACTIVATE_MULTIPLE_STAKE_ACCOUNTS: usize = 32;
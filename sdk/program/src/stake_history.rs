@@ -7,11 +7,26 @@
 //! [`sysvar::stake_history`]: crate::sysvar::stake_history
 
 pub use crate::clock::Epoch;
-use std::ops::Deref;
+use {
+    borsh::{io, BorshDeserialize, BorshSerialize},
+    std::ops::{Bound, Deref, RangeBounds},
+};
 
 pub const MAX_ENTRIES: usize = 512; // it should never take as many as 512 epochs to warm up or cool down
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Default, Clone, AbiExample)]
+#[derive(
+    Debug,
+    Serialize,
+    Deserialize,
+    BorshSerialize,
+    BorshDeserialize,
+    PartialEq,
+    Eq,
+    Default,
+    Clone,
+    AbiExample,
+)]
+#[borsh(crate = "borsh")]
 pub struct StakeHistoryEntry {
     pub effective: u64,    // effective stake at this epoch
     pub activating: u64,   // sum of portion of stakes not fully warmed up
@@ -41,6 +56,42 @@ impl StakeHistoryEntry {
             ..Self::default()
         }
     }
+
+    /// Adds each field of `rhs`, returning `None` if any of them overflows
+    pub fn checked_add(&self, rhs: &Self) -> Option<Self> {
+        Some(Self {
+            effective: self.effective.checked_add(rhs.effective)?,
+            activating: self.activating.checked_add(rhs.activating)?,
+            deactivating: self.deactivating.checked_add(rhs.deactivating)?,
+        })
+    }
+
+    /// Subtracts each field of `rhs`, returning `None` if any of them underflows
+    pub fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+        Some(Self {
+            effective: self.effective.checked_sub(rhs.effective)?,
+            activating: self.activating.checked_sub(rhs.activating)?,
+            deactivating: self.deactivating.checked_sub(rhs.deactivating)?,
+        })
+    }
+
+    /// Scales each field by `numerator / denominator`, rounding down.
+    ///
+    /// Intermediate products are computed in `u128`, so this only fails if
+    /// `denominator` is zero or a scaled field does not fit back into a `u64`.
+    pub fn checked_mul_div(&self, numerator: u64, denominator: u64) -> Option<Self> {
+        let scale = |value: u64| -> Option<u64> {
+            let scaled = u128::from(value)
+                .checked_mul(u128::from(numerator))?
+                .checked_div(u128::from(denominator))?;
+            u64::try_from(scaled).ok()
+        };
+        Some(Self {
+            effective: scale(self.effective)?,
+            activating: scale(self.activating)?,
+            deactivating: scale(self.deactivating)?,
+        })
+    }
 }
 
 impl std::ops::Add for StakeHistoryEntry {
@@ -54,6 +105,17 @@ impl std::ops::Add for StakeHistoryEntry {
     }
 }
 
+impl std::ops::Sub for StakeHistoryEntry {
+    type Output = StakeHistoryEntry;
+    fn sub(self, rhs: StakeHistoryEntry) -> Self::Output {
+        Self {
+            effective: self.effective.saturating_sub(rhs.effective),
+            activating: self.activating.saturating_sub(rhs.activating),
+            deactivating: self.deactivating.saturating_sub(rhs.deactivating),
+        }
+    }
+}
+
 #[repr(C)]
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Default, Clone, AbiExample)]
 pub struct StakeHistory(Vec<(Epoch, StakeHistoryEntry)>);
@@ -72,6 +134,65 @@ impl StakeHistory {
         }
         (self.0).truncate(MAX_ENTRIES);
     }
+
+    /// Returns the entry for the most recent epoch, if any
+    pub fn latest(&self) -> Option<&(Epoch, StakeHistoryEntry)> {
+        self.0.first()
+    }
+
+    /// Returns the entry for the oldest epoch still retained, if any
+    pub fn oldest(&self) -> Option<&(Epoch, StakeHistoryEntry)> {
+        self.0.last()
+    }
+
+    /// Iterates over the entries whose epochs fall within `range`.
+    ///
+    /// Like the underlying vector, entries are yielded from the most recent
+    /// epoch to the oldest; use `.rev()` for ascending order.
+    pub fn iter_range<R: RangeBounds<Epoch>>(
+        &self,
+        range: R,
+    ) -> std::slice::Iter<'_, (Epoch, StakeHistoryEntry)> {
+        // entries are sorted by descending epoch, so each bound splits the
+        // vector into a prefix that satisfies it and a suffix that doesn't
+        let start_index = match range.end_bound() {
+            Bound::Included(&end) => self.0.partition_point(|(epoch, _)| *epoch > end),
+            Bound::Excluded(&end) => self.0.partition_point(|(epoch, _)| *epoch >= end),
+            Bound::Unbounded => 0,
+        };
+        let end_index = match range.start_bound() {
+            Bound::Included(&start) => self.0.partition_point(|(epoch, _)| *epoch >= start),
+            Bound::Excluded(&start) => self.0.partition_point(|(epoch, _)| *epoch > start),
+            Bound::Unbounded => self.0.len(),
+        };
+        self.0[start_index..end_index.max(start_index)].iter()
+    }
+}
+
+// Borsh prefixes vectors with a u32 length, but the sysvar is laid out by
+// bincode with a u64 length, so the length is (de)serialized by hand to keep
+// both encodings identical
+impl BorshSerialize for StakeHistory {
+    fn serialize<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+        BorshSerialize::serialize(&(self.0.len() as u64), writer)?;
+        for entry in &self.0 {
+            BorshSerialize::serialize(entry, writer)?;
+        }
+        Ok(())
+    }
+}
+
+impl BorshDeserialize for StakeHistory {
+    fn deserialize_reader<R: io::Read>(reader: &mut R) -> io::Result<Self> {
+        let len: u64 = BorshDeserialize::deserialize_reader(reader)?;
+        let len = usize::try_from(len)
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "Invalid length"))?;
+        let mut entries = Vec::with_capacity(len.min(MAX_ENTRIES));
+        for _ in 0..len {
+            entries.push(BorshDeserialize::deserialize_reader(reader)?);
+        }
+        Ok(Self(entries))
+    }
 }
 
 impl Deref for StakeHistory {
@@ -109,4 +230,193 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn test_stake_history_entry_arithmetic() {
+        let entry = StakeHistoryEntry {
+            effective: 100,
+            activating: 20,
+            deactivating: 10,
+        };
+        let max = StakeHistoryEntry {
+            effective: u64::MAX,
+            activating: u64::MAX,
+            deactivating: u64::MAX,
+        };
+
+        assert_eq!(
+            entry.clone() + entry.clone(),
+            StakeHistoryEntry {
+                effective: 200,
+                activating: 40,
+                deactivating: 20,
+            }
+        );
+        assert_eq!(entry.clone() + max.clone(), max);
+        assert_eq!(
+            entry.clone() - StakeHistoryEntry::with_effective(30),
+            StakeHistoryEntry {
+                effective: 70,
+                activating: 20,
+                deactivating: 10,
+            }
+        );
+        assert_eq!(entry.clone() - max.clone(), StakeHistoryEntry::default());
+
+        assert_eq!(
+            entry.checked_add(&entry),
+            Some(entry.clone() + entry.clone())
+        );
+        assert_eq!(entry.checked_add(&max), None);
+        assert_eq!(
+            entry.checked_sub(&StakeHistoryEntry::with_deactivating(10)),
+            Some(StakeHistoryEntry {
+                effective: 90,
+                activating: 20,
+                deactivating: 0,
+            })
+        );
+        assert_eq!(entry.checked_sub(&max), None);
+    }
+
+    #[test]
+    fn test_stake_history_entry_checked_mul_div() {
+        let entry = StakeHistoryEntry {
+            effective: 100,
+            activating: 20,
+            deactivating: 10,
+        };
+        assert_eq!(
+            entry.checked_mul_div(1, 3),
+            Some(StakeHistoryEntry {
+                effective: 33,
+                activating: 6,
+                deactivating: 3,
+            })
+        );
+        assert_eq!(
+            entry.checked_mul_div(0, 3),
+            Some(StakeHistoryEntry::default())
+        );
+        assert_eq!(entry.checked_mul_div(1, 0), None);
+
+        // intermediate products don't overflow
+        let max = StakeHistoryEntry {
+            effective: u64::MAX,
+            activating: u64::MAX,
+            deactivating: u64::MAX,
+        };
+        assert_eq!(max.checked_mul_div(u64::MAX, u64::MAX), Some(max.clone()));
+        assert_eq!(
+            max.checked_mul_div(u64::MAX / 2, u64::MAX),
+            Some(StakeHistoryEntry {
+                effective: u64::MAX / 2,
+                activating: u64::MAX / 2,
+                deactivating: u64::MAX / 2,
+            })
+        );
+        // results that don't fit back into a u64 fail
+        assert_eq!(max.checked_mul_div(2, 1), None);
+    }
+
+    #[test]
+    fn test_stake_history_accessors() {
+        let empty = StakeHistory::default();
+        assert_eq!(empty.latest(), None);
+        assert_eq!(empty.oldest(), None);
+        assert_eq!(empty.iter_range(..).count(), 0);
+
+        let mut stake_history = StakeHistory::default();
+        for epoch in 10..20 {
+            stake_history.add(epoch, StakeHistoryEntry::with_effective(epoch));
+        }
+        assert_eq!(
+            stake_history.latest(),
+            Some(&(19, StakeHistoryEntry::with_effective(19)))
+        );
+        assert_eq!(
+            stake_history.oldest(),
+            Some(&(10, StakeHistoryEntry::with_effective(10)))
+        );
+
+        let epochs = |range: (Bound<Epoch>, Bound<Epoch>)| {
+            stake_history
+                .iter_range(range)
+                .map(|(epoch, _)| *epoch)
+                .collect::<Vec<_>>()
+        };
+        let all_epochs = (10..20).rev().collect::<Vec<_>>();
+        assert_eq!(epochs((Bound::Unbounded, Bound::Unbounded)), all_epochs);
+        assert_eq!(
+            epochs((Bound::Included(0), Bound::Included(100))),
+            all_epochs
+        );
+        assert_eq!(
+            epochs((Bound::Included(12), Bound::Excluded(15))),
+            vec![14, 13, 12]
+        );
+        assert_eq!(
+            epochs((Bound::Excluded(12), Bound::Included(15))),
+            vec![15, 14, 13]
+        );
+        assert_eq!(
+            epochs((Bound::Included(17), Bound::Unbounded)),
+            vec![19, 18, 17]
+        );
+        assert_eq!(
+            epochs((Bound::Unbounded, Bound::Excluded(12))),
+            vec![11, 10]
+        );
+        assert_eq!(epochs((Bound::Included(15), Bound::Included(15))), vec![15]);
+        assert!(epochs((Bound::Included(15), Bound::Excluded(15))).is_empty());
+        assert!(epochs((Bound::Included(16), Bound::Included(14))).is_empty());
+        assert!(epochs((Bound::Included(20), Bound::Unbounded)).is_empty());
+        assert!(epochs((Bound::Unbounded, Bound::Excluded(10))).is_empty());
+
+        assert_eq!(
+            stake_history.iter_range(12..15).rev().next(),
+            Some(&(12, StakeHistoryEntry::with_effective(12)))
+        );
+    }
+
+    #[test]
+    fn test_borsh_layout() {
+        let entry = StakeHistoryEntry {
+            effective: 1,
+            activating: 2,
+            deactivating: u64::MAX,
+        };
+        let borsh_serialized = borsh::to_vec(&entry).unwrap();
+        assert_eq!(borsh_serialized, bincode::serialize(&entry).unwrap());
+        assert_eq!(
+            StakeHistoryEntry::try_from_slice(&borsh_serialized).unwrap(),
+            entry
+        );
+
+        let empty = StakeHistory::default();
+        let borsh_serialized = borsh::to_vec(&empty).unwrap();
+        assert_eq!(borsh_serialized, bincode::serialize(&empty).unwrap());
+        assert_eq!(
+            StakeHistory::try_from_slice(&borsh_serialized).unwrap(),
+            empty
+        );
+
+        let mut stake_history = StakeHistory::default();
+        for i in 0..MAX_ENTRIES as u64 {
+            stake_history.add(i, StakeHistoryEntry::with_effective_and_activating(i, i));
+        }
+        let borsh_serialized = borsh::to_vec(&stake_history).unwrap();
+        assert_eq!(
+            borsh_serialized,
+            bincode::serialize(&stake_history).unwrap()
+        );
+        assert_eq!(borsh_serialized.len(), 16392); // golden, matches Sysvar::size_of
+        assert_eq!(
+            StakeHistory::try_from_slice(&borsh_serialized).unwrap(),
+            stake_history
+        );
+
+        // truncated data is rejected
+        assert!(StakeHistory::try_from_slice(&borsh_serialized[..100]).is_err());
+    }
 }
@@ -46,11 +46,18 @@ define_syscall!(fn sol_sha256(vals: *const u8, val_len: u64, hash_result: *mut u
 define_syscall!(fn sol_keccak256(vals: *const u8, val_len: u64, hash_result: *mut u8) -> u64);
 define_syscall!(fn sol_secp256k1_recover(hash: *const u8, recovery_id: u64, signature: *const u8, result: *mut u8) -> u64);
 define_syscall!(fn sol_blake3(vals: *const u8, val_len: u64, hash_result: *mut u8) -> u64);
+define_syscall!(fn sol_blake3_batch(vals: *const u8, val_len: u64, hash_results: *mut u8) -> u64);
+define_syscall!(fn sol_blake3_update(state: *mut u8, vals: *const u8, val_len: u64) -> u64);
+define_syscall!(fn sol_blake3_finalize(state: *const u8, hash_result: *mut u8) -> u64);
+define_syscall!(fn sol_keccak256_batch(vals: *const u8, val_len: u64, hash_results: *mut u8) -> u64);
+define_syscall!(fn sol_keccak256_update(state: *mut u8, vals: *const u8, val_len: u64) -> u64);
+define_syscall!(fn sol_keccak256_finalize(state: *const u8, hash_result: *mut u8) -> u64);
 define_syscall!(fn sol_get_clock_sysvar(addr: *mut u8) -> u64);
 define_syscall!(fn sol_get_epoch_schedule_sysvar(addr: *mut u8) -> u64);
 define_syscall!(fn sol_get_fees_sysvar(addr: *mut u8) -> u64);
 define_syscall!(fn sol_get_rent_sysvar(addr: *mut u8) -> u64);
 define_syscall!(fn sol_get_last_restart_slot(addr: *mut u8) -> u64);
+define_syscall!(fn sol_get_sysvar(sysvar_id_addr: *const u8, var_addr: *mut u8, offset: u64, length: u64) -> u64);
 define_syscall!(fn sol_memcpy_(dst: *mut u8, src: *const u8, n: u64));
 define_syscall!(fn sol_memmove_(dst: *mut u8, src: *const u8, n: u64));
 define_syscall!(fn sol_memcmp_(s1: *const u8, s2: *const u8, n: u64, result: *mut i32));
@@ -62,6 +69,8 @@ define_syscall!(fn sol_get_return_data(data: *mut u8, length: u64, program_id: *
 define_syscall!(fn sol_log_data(data: *const u8, data_len: u64));
 define_syscall!(fn sol_get_processed_sibling_instruction(index: u64, meta: *mut ProcessedSiblingInstruction, program_id: *mut Pubkey, data: *mut u8, accounts: *mut AccountMeta) -> u64);
 define_syscall!(fn sol_get_stack_height() -> u64);
+define_syscall!(fn sol_get_caller_program_id(program_id: *mut Pubkey) -> u64);
+define_syscall!(fn sol_get_minimum_balance_for_rent_exemption(data_len: u64) -> u64);
 define_syscall!(fn sol_curve_validate_point(curve_id: u64, point_addr: *const u8, result: *mut u8) -> u64);
 define_syscall!(fn sol_curve_group_op(curve_id: u64, group_op: u64, left_input_addr: *const u8, right_input_addr: *const u8, result_point_addr: *mut u8) -> u64);
 define_syscall!(fn sol_curve_multiscalar_mul(curve_id: u64, scalars_addr: *const u8, points_addr: *const u8, points_len: u64, result_point_addr: *mut u8) -> u64);
@@ -273,6 +273,18 @@ pub enum SystemInstruction {
     /// # Account references
     ///   0. `[WRITE]` Nonce account
     UpgradeNonceAccount,
+
+    /// Consumes a stored nonce, replacing it with a successor, and changes the
+    /// entity authorized to execute nonce instructions on the account in the
+    /// same step
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Nonce account
+    ///   1. `[]` RecentBlockhashes sysvar
+    ///   2. `[SIGNER]` Nonce authority
+    ///
+    /// The `Pubkey` parameter identifies the entity to authorize
+    AdvanceAndAuthorizeNonceAccount(Pubkey),
 }
 
 /// Create an account.
@@ -1754,6 +1766,40 @@ pub fn authorize_nonce_account(
     )
 }
 
+/// Advance the value of a durable transaction nonce and change its authority.
+///
+/// This function produces an [`Instruction`] which must be submitted in a
+/// [`Transaction`] or [invoked] to take effect, containing a serialized
+/// [`SystemInstruction::AdvanceAndAuthorizeNonceAccount`].
+///
+/// [`Transaction`]: https://docs.rs/solana-sdk/latest/solana_sdk/transaction/struct.Transaction.html
+/// [invoked]: crate::program::invoke
+///
+/// Because the stored nonce is replaced at the same time the authority
+/// changes, transactions signed by the previous authority against the old
+/// nonce value can no longer be executed once this instruction lands.
+///
+/// # Required signers
+///
+/// The `authorized_pubkey` signer must sign the transaction.
+pub fn advance_and_authorize_nonce_account(
+    nonce_pubkey: &Pubkey,
+    authorized_pubkey: &Pubkey,
+    new_authority: &Pubkey,
+) -> Instruction {
+    let account_metas = vec![
+        AccountMeta::new(*nonce_pubkey, false),
+        #[allow(deprecated)]
+        AccountMeta::new_readonly(recent_blockhashes::id(), false),
+        AccountMeta::new_readonly(*authorized_pubkey, true),
+    ];
+    Instruction::new_with_bincode(
+        system_program::id(),
+        &SystemInstruction::AdvanceAndAuthorizeNonceAccount(*new_authority),
+        account_metas,
+    )
+}
+
 /// One-time idempotent upgrade of legacy nonce versions in order to bump
 /// them out of chain blockhash domain.
 pub fn upgrade_nonce_account(nonce_pubkey: Pubkey) -> Instruction {
@@ -0,0 +1,119 @@
+//! Stake delegated to vote accounts in the current epoch.
+//!
+//! The _epoch stake sysvar_ provides access to the [`EpochStake`] type: the
+//! total stake and the stake of the most staked vote accounts, from which the
+//! leader schedule of the current epoch was computed. It is updated by the
+//! bank at each epoch boundary.
+//!
+//! The [`Sysvar::from_account_info`] and [`Sysvar::get`] methods always return
+//! [`ProgramError::UnsupportedSysvar`] because this sysvar account is too large
+//! to process on-chain. Instead, [`get_total_stake`], [`get_num_vote_accounts`]
+//! and [`get_vote_account_stake`] read only the requested fields of the sysvar
+//! with [`get_sysvar`], without passing the sysvar account to the program.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # use solana_program::{
+//! #    account_info::AccountInfo,
+//! #    entrypoint::ProgramResult,
+//! #    msg,
+//! #    pubkey::Pubkey,
+//! #    sysvar::epoch_stake,
+//! # };
+//!
+//! fn process_instruction(
+//!     program_id: &Pubkey,
+//!     accounts: &[AccountInfo],
+//!     instruction_data: &[u8],
+//! ) -> ProgramResult {
+//!     let total_stake = epoch_stake::get_total_stake()?;
+//!     if let Some(vote_account) = epoch_stake::get_vote_account_stake(0)? {
+//!         msg!(
+//!             "{} holds {} of {} lamports staked",
+//!             vote_account.vote_pubkey,
+//!             vote_account.stake,
+//!             total_stake
+//!         );
+//!     }
+//!
+//!     Ok(())
+//! }
+//! ```
+
+pub use crate::epoch_stake::{
+    EpochStake, VoteAccountStake, MAX_ENTRIES, NUM_VOTE_ACCOUNTS_OFFSET, TOTAL_STAKE_OFFSET,
+    VOTE_ACCOUNTS_OFFSET, VOTE_ACCOUNT_STAKE_SIZE,
+};
+use crate::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::{Pubkey, PUBKEY_BYTES},
+    sysvar::{get_sysvar, Sysvar},
+};
+
+crate::declare_sysvar_id!("SysvarEpochStake111111111111111111111111111", EpochStake);
+
+impl Sysvar for EpochStake {
+    // override
+    fn size_of() -> usize {
+        EpochStake::vote_account_offset(MAX_ENTRIES)
+    }
+    fn from_account_info(_account_info: &AccountInfo) -> Result<Self, ProgramError> {
+        // This sysvar is too large to bincode::deserialize in-program
+        Err(ProgramError::UnsupportedSysvar)
+    }
+}
+
+fn get_u64(offset: usize) -> Result<u64, ProgramError> {
+    let mut data = [0u8; 8];
+    get_sysvar(&mut data, &id(), offset as u64)?;
+    Ok(u64::from_le_bytes(data))
+}
+
+/// Returns the total stake delegated to all vote accounts in the current epoch
+pub fn get_total_stake() -> Result<u64, ProgramError> {
+    get_u64(TOTAL_STAKE_OFFSET)
+}
+
+/// Returns the number of vote accounts recorded in the sysvar
+pub fn get_num_vote_accounts() -> Result<usize, ProgramError> {
+    get_u64(NUM_VOTE_ACCOUNTS_OFFSET)
+        .and_then(|num| usize::try_from(num).map_err(|_| ProgramError::InvalidAccountData))
+}
+
+/// Returns the vote account at `index` in order of descending stake, or
+/// `None` if fewer vote accounts are recorded
+pub fn get_vote_account_stake(index: usize) -> Result<Option<VoteAccountStake>, ProgramError> {
+    if index >= get_num_vote_accounts()? {
+        return Ok(None);
+    }
+    let mut data = [0u8; VOTE_ACCOUNT_STAKE_SIZE];
+    get_sysvar(
+        &mut data,
+        &id(),
+        EpochStake::vote_account_offset(index) as u64,
+    )?;
+    let (vote_pubkey, stake) = data.split_at(PUBKEY_BYTES);
+    Ok(Some(VoteAccountStake {
+        vote_pubkey: Pubkey::try_from(vote_pubkey).unwrap(),
+        stake: u64::from_le_bytes(stake.try_into().unwrap()),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_size_of() {
+        assert_eq!(
+            EpochStake::size_of(),
+            bincode::serialized_size(&EpochStake {
+                vote_accounts: vec![VoteAccountStake::default(); MAX_ENTRIES],
+                ..EpochStake::default()
+            })
+            .unwrap() as usize
+        );
+    }
+}
@@ -89,11 +89,13 @@ use {
 pub mod clock;
 pub mod epoch_rewards;
 pub mod epoch_schedule;
+pub mod epoch_stake;
 pub mod fees;
 pub mod instructions;
 pub mod last_restart_slot;
 pub mod recent_blockhashes;
 pub mod rent;
+pub mod restart_history;
 pub mod rewards;
 pub mod slot_hashes;
 pub mod slot_history;
@@ -238,6 +240,50 @@ macro_rules! impl_sysvar_get {
     };
 }
 
+/// Return value of `sol_get_sysvar` when the requested range starts within
+/// the sysvar account data but is too long to end within it
+pub const OFFSET_LENGTH_EXCEEDS_SYSVAR: u64 = 1;
+
+/// Return value of `sol_get_sysvar` when the sysvar is not available
+pub const SYSVAR_NOT_FOUND: u64 = 2;
+
+/// Return value of `sol_get_sysvar` when the requested range starts past the
+/// end of the sysvar account data
+pub const OFFSET_EXCEEDS_SYSVAR: u64 = 3;
+
+/// Load `dst.len()` bytes of the account data of the sysvar `sysvar_id`,
+/// starting at `offset`, directly from the runtime.
+///
+/// This allows reading a part of a sysvar which is too large to load with
+/// [`Sysvar::get`], without passing the sysvar account to the program.
+///
+/// # Errors
+///
+/// Returns [`ProgramError::InvalidArgument`] if `offset` is past the end of
+/// the sysvar account data, [`ProgramError::AccountDataTooSmall`] if `dst` is
+/// too long for the data from `offset` on, and
+/// [`ProgramError::UnsupportedSysvar`] if the sysvar is not available.
+pub fn get_sysvar(dst: &mut [u8], sysvar_id: &Pubkey, offset: u64) -> Result<(), ProgramError> {
+    let sysvar_id_addr = sysvar_id as *const _ as *const u8;
+    let var_addr = dst.as_mut_ptr();
+    let length = dst.len() as u64;
+
+    #[cfg(target_os = "solana")]
+    let result =
+        unsafe { crate::syscalls::sol_get_sysvar(sysvar_id_addr, var_addr, offset, length) };
+
+    #[cfg(not(target_os = "solana"))]
+    let result = crate::program_stubs::sol_get_sysvar(sysvar_id_addr, var_addr, offset, length);
+
+    match result {
+        crate::entrypoint::SUCCESS => Ok(()),
+        OFFSET_LENGTH_EXCEEDS_SYSVAR => Err(ProgramError::AccountDataTooSmall),
+        SYSVAR_NOT_FOUND => Err(ProgramError::UnsupportedSysvar),
+        OFFSET_EXCEEDS_SYSVAR => Err(ProgramError::InvalidArgument),
+        e => Err(e.into()),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use {
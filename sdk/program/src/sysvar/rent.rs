@@ -134,3 +134,18 @@ crate::declare_sysvar_id!("SysvarRent111111111111111111111111111111111", Rent);
 impl Sysvar for Rent {
     impl_sysvar_get!(sol_get_rent_sysvar);
 }
+
+/// Get the minimum balance for an account with `data_len` bytes of data to be
+/// rent exempt.
+///
+/// The value is computed by the runtime from the same rent parameters the
+/// bank uses, without copying the whole [`Rent`] sysvar into the program.
+pub fn get_minimum_balance_for_rent_exemption(data_len: usize) -> u64 {
+    #[cfg(target_os = "solana")]
+    unsafe {
+        crate::syscalls::sol_get_minimum_balance_for_rent_exemption(data_len as u64)
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    crate::program_stubs::sol_get_minimum_balance_for_rent_exemption(data_len as u64)
+}
@@ -0,0 +1,128 @@
+//! The most recent restarts of the cluster from a hard fork.
+//!
+//! The _restart history sysvar_ provides access to the [`RestartHistory`]
+//! type: the slot of each of the last [`MAX_ENTRIES`] hard forks the cluster
+//! restarted from, along with the timestamp of the first bank which observed
+//! the restart, most recent first. It extends the [`LastRestartSlot`] sysvar,
+//! which only records the most recent restart slot.
+//!
+//! When the sysvar is first created, only the most recent restart is recorded,
+//! as the timestamps of earlier restarts are not known.
+//!
+//! [`RestartHistory`] does not implement [`Sysvar::get`]. Instead,
+//! [`get_num_restarts`], [`get_restart`] and [`get_last_restart`] read only
+//! the requested entries of the sysvar with [`get_sysvar`], without passing
+//! the sysvar account to the program.
+//!
+//! The `sol_get_sysvar` syscall behind these accessors is only available
+//! once the `epoch_stake_sysvar` feature is active, and programs calling them
+//! fail to load until then. Once it is active, they return
+//! [`ProgramError::UnsupportedSysvar`] until the `restart_history_sysvar`
+//! feature creates the sysvar.
+//!
+//! [`LastRestartSlot`]: crate::last_restart_slot::LastRestartSlot
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # use solana_program::{
+//! #    account_info::AccountInfo,
+//! #    clock::Clock,
+//! #    entrypoint::ProgramResult,
+//! #    msg,
+//! #    program_error::ProgramError,
+//! #    pubkey::Pubkey,
+//! #    sysvar::{restart_history, Sysvar},
+//! # };
+//!
+//! fn process_instruction(
+//!     program_id: &Pubkey,
+//!     accounts: &[AccountInfo],
+//!     instruction_data: &[u8],
+//! ) -> ProgramResult {
+//!     let clock = Clock::get()?;
+//!     if let Some(restart) = restart_history::get_last_restart()? {
+//!         if clock.unix_timestamp.saturating_sub(restart.unix_timestamp) < 3600 {
+//!             msg!("trading is halted after the restart at slot {}", restart.slot);
+//!             return Err(ProgramError::Custom(0));
+//!         }
+//!     }
+//!
+//!     Ok(())
+//! }
+//! ```
+
+pub use crate::restart_history::{
+    RestartEntry, RestartHistory, MAX_ENTRIES, NUM_RESTARTS_OFFSET, RESTARTS_OFFSET,
+    RESTART_ENTRY_SIZE,
+};
+use crate::{
+    program_error::ProgramError,
+    sysvar::{get_sysvar, Sysvar},
+};
+
+crate::declare_sysvar_id!(
+    "SysvarRestartHistory11111111111111111111111",
+    RestartHistory
+);
+
+impl Sysvar for RestartHistory {
+    // override
+    fn size_of() -> usize {
+        RestartHistory::restart_offset(MAX_ENTRIES)
+    }
+}
+
+/// Returns the number of restarts recorded in the sysvar
+///
+/// Requires the `sol_get_sysvar` syscall, see the [module docs](self).
+pub fn get_num_restarts() -> Result<usize, ProgramError> {
+    let mut data = [0u8; 8];
+    get_sysvar(&mut data, &id(), NUM_RESTARTS_OFFSET as u64)?;
+    usize::try_from(u64::from_le_bytes(data)).map_err(|_| ProgramError::InvalidAccountData)
+}
+
+/// Returns the restart at `index`, most recent first, or `None` if fewer
+/// restarts are recorded
+///
+/// Requires the `sol_get_sysvar` syscall, see the [module docs](self).
+pub fn get_restart(index: usize) -> Result<Option<RestartEntry>, ProgramError> {
+    if index >= get_num_restarts()? {
+        return Ok(None);
+    }
+    let mut data = [0u8; RESTART_ENTRY_SIZE];
+    get_sysvar(
+        &mut data,
+        &id(),
+        RestartHistory::restart_offset(index) as u64,
+    )?;
+    let (slot, unix_timestamp) = data.split_at(8);
+    Ok(Some(RestartEntry {
+        slot: u64::from_le_bytes(slot.try_into().unwrap()),
+        unix_timestamp: i64::from_le_bytes(unix_timestamp.try_into().unwrap()),
+    }))
+}
+
+/// Returns the most recent restart, if any is recorded
+///
+/// Requires the `sol_get_sysvar` syscall, see the [module docs](self).
+pub fn get_last_restart() -> Result<Option<RestartEntry>, ProgramError> {
+    get_restart(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_size_of() {
+        let mut restart_history = RestartHistory::default();
+        for slot in 0..MAX_ENTRIES as u64 {
+            restart_history.add(slot, 0);
+        }
+        assert_eq!(
+            RestartHistory::size_of(),
+            bincode::serialized_size(&restart_history).unwrap() as usize
+        );
+    }
+}
@@ -10,7 +10,7 @@ use {
         vote::{
             program::id,
             state::{
-                serde_compact_vote_state_update, Vote, VoteAuthorize,
+                serde_compact_vote_state_update, serde_tower_sync, TowerSync, Vote, VoteAuthorize,
                 VoteAuthorizeCheckedWithSeedArgs, VoteAuthorizeWithSeedArgs, VoteInit,
                 VoteStateUpdate, VoteStateVersions,
             },
@@ -146,6 +146,21 @@ pub enum VoteInstruction {
         #[serde(with = "serde_compact_vote_state_update")] VoteStateUpdate,
         Hash,
     ),
+
+    /// Sync the onchain vote state with local tower
+    ///
+    /// # Account references
+    ///   0. `[Write]` Vote account to vote with
+    ///   1. `[SIGNER]` Vote authority
+    #[serde(with = "serde_tower_sync")]
+    TowerSync(TowerSync),
+
+    /// Sync the onchain vote state with local tower along with a switching proof
+    ///
+    /// # Account references
+    ///   0. `[Write]` Vote account to vote with
+    ///   1. `[SIGNER]` Vote authority
+    TowerSyncSwitch(#[serde(with = "serde_tower_sync")] TowerSync, Hash),
 }
 
 impl VoteInstruction {
@@ -157,7 +172,9 @@ impl VoteInstruction {
                 | Self::UpdateVoteState(_)
                 | Self::UpdateVoteStateSwitch(_, _)
                 | Self::CompactUpdateVoteState(_)
-                | Self::CompactUpdateVoteStateSwitch(_, _),
+                | Self::CompactUpdateVoteStateSwitch(_, _)
+                | Self::TowerSync(_)
+                | Self::TowerSyncSwitch(_, _),
         )
     }
 
@@ -167,7 +184,9 @@ impl VoteInstruction {
             Self::UpdateVoteState(_)
                 | Self::UpdateVoteStateSwitch(_, _)
                 | Self::CompactUpdateVoteState(_)
-                | Self::CompactUpdateVoteStateSwitch(_, _),
+                | Self::CompactUpdateVoteStateSwitch(_, _)
+                | Self::TowerSync(_)
+                | Self::TowerSyncSwitch(_, _),
         )
     }
 
@@ -182,6 +201,9 @@ impl VoteInstruction {
             | Self::CompactUpdateVoteStateSwitch(vote_state_update, _) => {
                 vote_state_update.last_voted_slot()
             }
+            Self::TowerSync(tower_sync) | Self::TowerSyncSwitch(tower_sync, _) => {
+                tower_sync.last_voted_slot()
+            }
             _ => panic!("Tried to get slot on non simple vote instruction"),
         }
     }
@@ -197,6 +219,9 @@ impl VoteInstruction {
             | Self::CompactUpdateVoteStateSwitch(vote_state_update, _) => {
                 vote_state_update.timestamp
             }
+            Self::TowerSync(tower_sync) | Self::TowerSyncSwitch(tower_sync, _) => {
+                tower_sync.timestamp
+            }
             _ => panic!("Tried to get timestamp on non simple vote instruction"),
         }
     }
@@ -514,6 +539,37 @@ pub fn compact_update_vote_state_switch(
     )
 }
 
+pub fn tower_sync(
+    vote_pubkey: &Pubkey,
+    authorized_voter_pubkey: &Pubkey,
+    tower_sync: TowerSync,
+) -> Instruction {
+    let account_metas = vec![
+        AccountMeta::new(*vote_pubkey, false),
+        AccountMeta::new_readonly(*authorized_voter_pubkey, true),
+    ];
+
+    Instruction::new_with_bincode(id(), &VoteInstruction::TowerSync(tower_sync), account_metas)
+}
+
+pub fn tower_sync_switch(
+    vote_pubkey: &Pubkey,
+    authorized_voter_pubkey: &Pubkey,
+    tower_sync: TowerSync,
+    proof_hash: Hash,
+) -> Instruction {
+    let account_metas = vec![
+        AccountMeta::new(*vote_pubkey, false),
+        AccountMeta::new_readonly(*authorized_voter_pubkey, true),
+    ];
+
+    Instruction::new_with_bincode(
+        id(),
+        &VoteInstruction::TowerSyncSwitch(tower_sync, proof_hash),
+        account_metas,
+    )
+}
+
 pub fn withdraw(
     vote_pubkey: &Pubkey,
     authorized_withdrawer_pubkey: &Pubkey,
@@ -204,6 +204,79 @@ impl VoteStateUpdate {
     }
 }
 
+/// The full tower of a validator, sent as a root plus lockout offsets by the
+/// `TowerSync` vote instruction
+#[derive(Serialize, Default, Deserialize, Debug, PartialEq, Eq, Clone, AbiExample)]
+pub struct TowerSync {
+    /// The proposed tower
+    pub lockouts: VecDeque<Lockout>,
+    /// The proposed root
+    pub root: Option<Slot>,
+    /// signature of the bank's state at the last slot
+    pub hash: Hash,
+    /// processing timestamp of last slot
+    pub timestamp: Option<UnixTimestamp>,
+}
+
+impl From<Vec<(Slot, u32)>> for TowerSync {
+    fn from(recent_slots: Vec<(Slot, u32)>) -> Self {
+        TowerSync::from(VoteStateUpdate::from(recent_slots))
+    }
+}
+
+impl From<VoteStateUpdate> for TowerSync {
+    fn from(vote_state_update: VoteStateUpdate) -> Self {
+        let VoteStateUpdate {
+            lockouts,
+            root,
+            hash,
+            timestamp,
+        } = vote_state_update;
+        Self {
+            lockouts,
+            root,
+            hash,
+            timestamp,
+        }
+    }
+}
+
+impl From<TowerSync> for VoteStateUpdate {
+    fn from(tower_sync: TowerSync) -> Self {
+        let TowerSync {
+            lockouts,
+            root,
+            hash,
+            timestamp,
+        } = tower_sync;
+        Self {
+            lockouts,
+            root,
+            hash,
+            timestamp,
+        }
+    }
+}
+
+impl TowerSync {
+    pub fn new(lockouts: VecDeque<Lockout>, root: Option<Slot>, hash: Hash) -> Self {
+        Self {
+            lockouts,
+            root,
+            hash,
+            timestamp: None,
+        }
+    }
+
+    pub fn slots(&self) -> Vec<Slot> {
+        self.lockouts.iter().map(|lockout| lockout.slot()).collect()
+    }
+
+    pub fn last_voted_slot(&self) -> Option<Slot> {
+        self.lockouts.back().map(|l| l.slot())
+    }
+}
+
 #[derive(Default, Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
 pub struct VoteInit {
     pub node_pubkey: Pubkey,
@@ -240,6 +313,16 @@ pub struct BlockTimestamp {
     pub timestamp: UnixTimestamp,
 }
 
+/// An authorized voter and the epochs for which it was, or is, authorized
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+pub struct AuthorizedVoterEpochs {
+    pub authorized_voter: Pubkey,
+    /// first epoch of the range, inclusive
+    pub start_epoch: Epoch,
+    /// end of the range, exclusive; `None` if no later voter has been set
+    pub end_epoch: Option<Epoch>,
+}
+
 // this is how many epochs a voter can be remembered for slashing
 const MAX_ITEMS: usize = 32;
 
@@ -364,6 +447,71 @@ impl VoteState {
         &self.prior_voters
     }
 
+    /// Returns the remembered prior authorized voters followed by the current
+    /// and scheduled authorized voters, oldest first, with the epoch ranges
+    /// in which each of them was authorized
+    ///
+    /// Only the last `MAX_ITEMS` prior voters are remembered, so the history
+    /// of long-lived vote accounts may be truncated.
+    pub fn authorized_voter_history(&self) -> Vec<AuthorizedVoterEpochs> {
+        let current_epoch = self
+            .authorized_voters
+            .first()
+            .map(|(epoch, _)| *epoch)
+            .unwrap_or_default();
+
+        // `prior_voters.idx` points at the newest entry, so the oldest
+        // entries follow it; unset entries are the default pubkey
+        let (newer, older) = self
+            .prior_voters
+            .buf
+            .split_at(self.prior_voters.idx.saturating_add(1));
+        // prior voters whose range has not ended yet were replaced by a voter
+        // which is still scheduled in `authorized_voters`
+        let (expired, pending): (Vec<_>, Vec<_>) = older
+            .iter()
+            .chain(newer)
+            .filter(|(authorized_voter, _, _)| *authorized_voter != Pubkey::default())
+            .partition(|(_, _, end_epoch)| *end_epoch <= current_epoch);
+
+        let mut history: Vec<_> = expired
+            .into_iter()
+            .map(
+                |(authorized_voter, start_epoch, end_epoch)| AuthorizedVoterEpochs {
+                    authorized_voter: *authorized_voter,
+                    start_epoch: *start_epoch,
+                    end_epoch: Some(*end_epoch),
+                },
+            )
+            .collect();
+        for (epoch, authorized_voter) in self.authorized_voters.iter() {
+            let start_epoch = match history.last_mut() {
+                Some(last) if last.end_epoch.is_none() => {
+                    // setting the same voter again does not start a new range
+                    if last.authorized_voter == *authorized_voter {
+                        continue;
+                    }
+                    last.end_epoch = Some(*epoch);
+                    *epoch
+                }
+                // the current voter was authorized when the first pending
+                // prior voter's range started, or else when the last expired
+                // prior voter's range ended
+                last => pending
+                    .first()
+                    .map(|(_, start_epoch, _)| *start_epoch)
+                    .or_else(|| last.and_then(|last| last.end_epoch))
+                    .unwrap_or(*epoch),
+            };
+            history.push(AuthorizedVoterEpochs {
+                authorized_voter: *authorized_voter,
+                start_epoch,
+                end_epoch: None,
+            });
+        }
+        history
+    }
+
     pub fn get_rent_exempt_reserve(rent: &Rent) -> u64 {
         rent.minimum_balance(VoteState::size_of())
     }
@@ -767,24 +915,79 @@ impl VoteState {
     }
 }
 
-pub mod serde_compact_vote_state_update {
+/// Lockouts encoded as slot offsets from the previous lockout, starting from the
+/// root, shared by the compact encodings of `VoteStateUpdate` and `TowerSync`
+mod compact_lockouts {
     use {
-        super::*,
-        crate::{
-            clock::{Slot, UnixTimestamp},
-            serde_varint, short_vec,
-            vote::state::Lockout,
-        },
-        serde::{Deserialize, Deserializer, Serialize, Serializer},
+        crate::{clock::Slot, serde_varint, vote::state::Lockout},
+        serde::{de, ser, Deserialize, Serialize},
+        std::collections::VecDeque,
     };
 
     #[derive(Deserialize, Serialize, AbiExample)]
-    struct LockoutOffset {
+    pub(super) struct LockoutOffset {
         #[serde(with = "serde_varint")]
         offset: Slot,
         confirmation_count: u8,
     }
 
+    pub(super) fn to_lockout_offsets<E: ser::Error>(
+        root: Option<Slot>,
+        lockouts: &VecDeque<Lockout>,
+    ) -> Result<Vec<LockoutOffset>, E> {
+        lockouts
+            .iter()
+            .scan(root.unwrap_or_default(), |slot, lockout| {
+                let Some(offset) = lockout.slot().checked_sub(*slot) else {
+                    return Some(Err(E::custom("Invalid vote lockout")));
+                };
+                let Ok(confirmation_count) = u8::try_from(lockout.confirmation_count()) else {
+                    return Some(Err(E::custom("Invalid confirmation count")));
+                };
+                let lockout_offset = LockoutOffset {
+                    offset,
+                    confirmation_count,
+                };
+                *slot = lockout.slot();
+                Some(Ok(lockout_offset))
+            })
+            .collect()
+    }
+
+    pub(super) fn from_lockout_offsets<E: de::Error>(
+        root: Option<Slot>,
+        lockout_offsets: &[LockoutOffset],
+    ) -> Result<VecDeque<Lockout>, E> {
+        lockout_offsets
+            .iter()
+            .scan(root.unwrap_or_default(), |slot, lockout_offset| {
+                *slot = match slot.checked_add(lockout_offset.offset) {
+                    None => return Some(Err(E::custom("Invalid lockout offset"))),
+                    Some(slot) => slot,
+                };
+                let lockout = Lockout::new_with_confirmation_count(
+                    *slot,
+                    u32::from(lockout_offset.confirmation_count),
+                );
+                Some(Ok(lockout))
+            })
+            .collect()
+    }
+}
+
+pub mod serde_compact_vote_state_update {
+    use {
+        super::{
+            compact_lockouts::{from_lockout_offsets, to_lockout_offsets, LockoutOffset},
+            *,
+        },
+        crate::{
+            clock::{Slot, UnixTimestamp},
+            short_vec,
+        },
+        serde::{Deserialize, Deserializer, Serialize, Serializer},
+    };
+
     #[derive(Deserialize, Serialize)]
     struct CompactVoteStateUpdate {
         root: Slot,
@@ -801,26 +1004,12 @@ pub mod serde_compact_vote_state_update {
     where
         S: Serializer,
     {
-        let lockout_offsets = vote_state_update.lockouts.iter().scan(
-            vote_state_update.root.unwrap_or_default(),
-            |slot, lockout| {
-                let Some(offset) = lockout.slot().checked_sub(*slot) else {
-                    return Some(Err(serde::ser::Error::custom("Invalid vote lockout")));
-                };
-                let Ok(confirmation_count) = u8::try_from(lockout.confirmation_count()) else {
-                    return Some(Err(serde::ser::Error::custom("Invalid confirmation count")));
-                };
-                let lockout_offset = LockoutOffset {
-                    offset,
-                    confirmation_count,
-                };
-                *slot = lockout.slot();
-                Some(Ok(lockout_offset))
-            },
-        );
         let compact_vote_state_update = CompactVoteStateUpdate {
             root: vote_state_update.root.unwrap_or(Slot::MAX),
-            lockout_offsets: lockout_offsets.collect::<Result<_, _>>()?,
+            lockout_offsets: to_lockout_offsets(
+                vote_state_update.root,
+                &vote_state_update.lockouts,
+            )?,
             hash: vote_state_update.hash,
             timestamp: vote_state_update.timestamp,
         };
@@ -838,25 +1027,61 @@ pub mod serde_compact_vote_state_update {
             timestamp,
         } = CompactVoteStateUpdate::deserialize(deserializer)?;
         let root = (root != Slot::MAX).then_some(root);
-        let lockouts =
-            lockout_offsets
-                .iter()
-                .scan(root.unwrap_or_default(), |slot, lockout_offset| {
-                    *slot = match slot.checked_add(lockout_offset.offset) {
-                        None => {
-                            return Some(Err(serde::de::Error::custom("Invalid lockout offset")))
-                        }
-                        Some(slot) => slot,
-                    };
-                    let lockout = Lockout::new_with_confirmation_count(
-                        *slot,
-                        u32::from(lockout_offset.confirmation_count),
-                    );
-                    Some(Ok(lockout))
-                });
         Ok(VoteStateUpdate {
             root,
-            lockouts: lockouts.collect::<Result<_, _>>()?,
+            lockouts: from_lockout_offsets(root, &lockout_offsets)?,
+            hash,
+            timestamp,
+        })
+    }
+}
+
+pub mod serde_tower_sync {
+    use {
+        super::{
+            compact_lockouts::{from_lockout_offsets, to_lockout_offsets, LockoutOffset},
+            *,
+        },
+        crate::{clock::Slot, short_vec},
+        serde::{Deserialize, Deserializer, Serialize, Serializer},
+    };
+
+    #[derive(Deserialize, Serialize)]
+    struct CompactTowerSync {
+        root: Slot,
+        #[serde(with = "short_vec")]
+        lockout_offsets: Vec<LockoutOffset>,
+        hash: Hash,
+        timestamp: Option<UnixTimestamp>,
+    }
+
+    pub fn serialize<S>(tower_sync: &TowerSync, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let compact_tower_sync = CompactTowerSync {
+            root: tower_sync.root.unwrap_or(Slot::MAX),
+            lockout_offsets: to_lockout_offsets(tower_sync.root, &tower_sync.lockouts)?,
+            hash: tower_sync.hash,
+            timestamp: tower_sync.timestamp,
+        };
+        compact_tower_sync.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<TowerSync, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let CompactTowerSync {
+            root,
+            lockout_offsets,
+            hash,
+            timestamp,
+        } = CompactTowerSync::deserialize(deserializer)?;
+        let root = (root != Slot::MAX).then_some(root);
+        Ok(TowerSync {
+            root,
+            lockouts: from_lockout_offsets(root, &lockout_offsets)?,
             hash,
             timestamp,
         })
@@ -1229,6 +1454,64 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_authorized_voter_history() {
+        let original_voter = Pubkey::new_unique();
+        let epoch_offset = 15;
+        let mut vote_state = VoteState::new(
+            &VoteInit {
+                node_pubkey: original_voter,
+                authorized_voter: original_voter,
+                authorized_withdrawer: original_voter,
+                commission: 0,
+            },
+            &Clock::default(),
+        );
+        let epochs = |authorized_voter, start_epoch, end_epoch| AuthorizedVoterEpochs {
+            authorized_voter,
+            start_epoch,
+            end_epoch,
+        };
+        assert_eq!(
+            vote_state.authorized_voter_history(),
+            vec![epochs(original_voter, 0, None)]
+        );
+
+        let new_voter = Pubkey::new_unique();
+        let new_voter2 = Pubkey::new_unique();
+        vote_state
+            .set_new_authorized_voter(&new_voter, 0, epoch_offset, |_| Ok(()))
+            .unwrap();
+        // setting the same voter again extends its range
+        vote_state
+            .set_new_authorized_voter(&new_voter, 2, 2 + epoch_offset, |_| Ok(()))
+            .unwrap();
+        vote_state
+            .set_new_authorized_voter(&new_voter2, 3, 3 + epoch_offset, |_| Ok(()))
+            .unwrap();
+        vote_state
+            .set_new_authorized_voter(&original_voter, 6, 6 + epoch_offset, |_| Ok(()))
+            .unwrap();
+        let expected_history = vec![
+            epochs(original_voter, 0, Some(epoch_offset)),
+            epochs(new_voter, epoch_offset, Some(3 + epoch_offset)),
+            epochs(new_voter2, 3 + epoch_offset, Some(6 + epoch_offset)),
+            epochs(original_voter, 6 + epoch_offset, None),
+        ];
+        assert_eq!(vote_state.authorized_voter_history(), expected_history);
+
+        // purging expired authorized voters does not change the history
+        for epoch in [
+            epoch_offset,
+            3 + epoch_offset,
+            6 + epoch_offset,
+            10 + epoch_offset,
+        ] {
+            vote_state.get_and_update_authorized_voter(epoch).unwrap();
+            assert_eq!(vote_state.authorized_voter_history(), expected_history);
+        }
+    }
+
     #[test]
     fn test_authorized_voter_is_locked_within_epoch() {
         let original_voter = Pubkey::new_unique();
@@ -1422,4 +1705,50 @@ mod tests {
         let bytes = bincode::serialize(&vote).unwrap();
         assert_eq!(vote, bincode::deserialize(&bytes).unwrap());
     }
+
+    #[test]
+    fn test_serde_tower_sync() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..5000 {
+            run_serde_tower_sync(&mut rng);
+        }
+    }
+
+    fn run_serde_tower_sync<R: Rng>(rng: &mut R) {
+        let lockouts: VecDeque<_> = std::iter::repeat_with(|| {
+            let slot = 149_303_885_u64.saturating_add(rng.gen_range(0..10_000));
+            let confirmation_count = rng.gen_range(0..33);
+            Lockout::new_with_confirmation_count(slot, confirmation_count)
+        })
+        .take(32)
+        .sorted_by_key(|lockout| lockout.slot())
+        .collect();
+        let root = rng.gen_ratio(1, 2).then(|| {
+            lockouts[0]
+                .slot()
+                .checked_sub(rng.gen_range(0..1_000))
+                .expect("All slots should be greater than 1_000")
+        });
+        let timestamp = rng.gen_ratio(1, 2).then(|| rng.gen());
+        let hash = Hash::from(rng.gen::<[u8; 32]>());
+        let tower_sync = TowerSync {
+            lockouts,
+            root,
+            hash,
+            timestamp,
+        };
+        #[derive(Debug, Eq, PartialEq, Deserialize, Serialize)]
+        enum VoteInstruction {
+            #[serde(with = "serde_tower_sync")]
+            TowerSync(TowerSync),
+            TowerSyncSwitch(#[serde(with = "serde_tower_sync")] TowerSync, Hash),
+        }
+        let vote = VoteInstruction::TowerSync(tower_sync.clone());
+        let bytes = bincode::serialize(&vote).unwrap();
+        assert_eq!(vote, bincode::deserialize(&bytes).unwrap());
+        let hash = Hash::from(rng.gen::<[u8; 32]>());
+        let vote = VoteInstruction::TowerSyncSwitch(tower_sync, hash);
+        let bytes = bincode::serialize(&vote).unwrap();
+        assert_eq!(vote, bincode::deserialize(&bytes).unwrap());
+    }
 }
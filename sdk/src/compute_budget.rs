@@ -3,7 +3,11 @@
 #![cfg(feature = "full")]
 
 use {
-    crate::instruction::Instruction,
+    crate::{
+        clock::Slot,
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+    },
     borsh::{BorshDeserialize, BorshSerialize},
 };
 
@@ -36,6 +40,15 @@ pub enum ComputeBudgetInstruction {
     SetComputeUnitPrice(u64),
     /// Set a specific transaction-wide account data size limit, in bytes, is allowed to load.
     SetLoadedAccountsDataSizeLimit(u32),
+    /// Designate the transaction's fee payer, in place of its first signer.
+    ///
+    /// # Account references
+    ///   0. `[WRITE, SIGNER]` The account paying the transaction fees
+    SetFeePayer,
+    /// Set the last slot in which the transaction may be processed. Unlike
+    /// the expiry of the recent blockhash, this also bounds the lifetime of a
+    /// transaction using a durable nonce.
+    SetMaxSlot(Slot),
 }
 
 impl ComputeBudgetInstruction {
@@ -65,4 +78,18 @@ impl ComputeBudgetInstruction {
     pub fn set_loaded_accounts_data_size_limit(bytes: u32) -> Instruction {
         Instruction::new_with_borsh(id(), &Self::SetLoadedAccountsDataSizeLimit(bytes), vec![])
     }
+
+    /// Create a `ComputeBudgetInstruction::SetFeePayer` `Instruction`
+    pub fn set_fee_payer(fee_payer: &Pubkey) -> Instruction {
+        Instruction::new_with_borsh(
+            id(),
+            &Self::SetFeePayer,
+            vec![AccountMeta::new(*fee_payer, true)],
+        )
+    }
+
+    /// Create a `ComputeBudgetInstruction::SetMaxSlot` `Instruction`
+    pub fn set_max_slot(slot: Slot) -> Instruction {
+        Instruction::new_with_borsh(id(), &Self::SetMaxSlot(slot), vec![])
+    }
 }
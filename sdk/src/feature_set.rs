@@ -780,6 +780,82 @@ pub mod enable_chained_merkle_shreds {
     solana_sdk::declare_id!("7uZBkJXJ1HkuP6R3MJfZs7mLwymBcDbKdqbF51ZWLier");
 }
 
+pub mod enable_tower_sync_ix {
+    solana_sdk::declare_id!("Hm6rositSJ6dcFo1Ny5JcHJA37wLxwLkXkK8FfMjGjuq");
+}
+
+pub mod limit_commission_increase {
+    solana_sdk::declare_id!("Cyvw3vh1Ywv8CDUq6YqoaXutLD73duMaR3HGGoxDDLSb");
+}
+
+pub mod enable_advance_and_authorize_nonce_ix {
+    solana_sdk::declare_id!("6kgobNFCWf9dNnzLaBcsKiQ4Bz54whDU2Z1cXEnHB2UW");
+}
+
+pub mod enable_get_caller_program_id_syscall {
+    solana_sdk::declare_id!("6WbsfXgrnMJWdiAwpt2VaRHAqeSHgx4yDJynfyRbx2n4");
+}
+
+pub mod enable_bpf_loader_verify_buffer_hash_ix {
+    solana_sdk::declare_id!("P1HtnKgsW7fsR7zq1As6gme99FXVtDvsz614Mv8uS6L");
+}
+
+pub mod enable_bpf_loader_truncate_program_data_ix {
+    solana_sdk::declare_id!("9TsWVXtMKFrCWvpQboVLCZDeeQTDLBYtLScCLdkDDRty");
+}
+
+pub mod enable_sbpf_v2_deployment_and_execution {
+    solana_sdk::declare_id!("6LHLavmFkSdNkRe5ZmA1mZVjxhTaY7cvuQksT4RN9VrQ");
+}
+
+pub mod enable_shred_erasure_config {
+    solana_sdk::declare_id!("HpHXUZqYeoHx24i4AaNZATdBsi39hh5FhVsjGwkx4xwK");
+}
+
+pub mod enable_fee_sponsorship {
+    solana_sdk::declare_id!("33ZeGZx9CP1PoGZsjREuny7JrZgFVHYe9vZEPSxGxs4G");
+}
+
+pub mod enable_transaction_max_slot {
+    solana_sdk::declare_id!("FqCyYdkTha7Kapk1bCSys7SqmuuKwfbwUf49dyZAaKh5");
+}
+
+pub mod enable_zk_range_proof_batch_verification {
+    solana_sdk::declare_id!("G9rCjAVRyzUKdQtnPgnuMbNHwBJvccPoXsfQtUHc4db5");
+}
+
+pub mod epoch_stake_sysvar {
+    solana_sdk::declare_id!("6cgXAJiGnp9ox9TZnpGRxkJZSPyjpEtEsGBRPVVrUhJq");
+}
+
+pub mod enable_get_minimum_balance_for_rent_exemption_syscall {
+    solana_sdk::declare_id!("GBx44g2DNSkR94FtSy9sQwHDBCh3dqNMyc8SDvDAhCuH");
+}
+
+pub mod restart_history_sysvar {
+    solana_sdk::declare_id!("9xNWoHDmEHZRhEfEtZoVmnwnumjBwRGAwtJRxT2E6aLH");
+}
+
+pub mod stake_split_with_funding {
+    solana_sdk::declare_id!("BL7WeVcKYn8MaXRLCuUAPZsB1tsfq2SWsJnZDZH79o94");
+}
+
+pub mod stake_deactivate_multiple {
+    solana_sdk::declare_id!("B1Ee2VfrTKoc6mHu6qj62HP2rxohiPDpnFRGP3VYEL4k");
+}
+
+pub mod cost_model_write_lock_hotspot_limits {
+    solana_sdk::declare_id!("CYMe4eUYGHgmqj5ruFwQ3oyMffzZRf989mrVGgVK754f");
+}
+
+pub mod accounts_lt_hash {
+    solana_sdk::declare_id!("EB1WW5FierCrfHa5ZnBXoyucRoj4KNLdPZEaLbgQgxNh");
+}
+
+pub mod enable_batched_hash_syscalls {
+    solana_sdk::declare_id!("7Hq2ykvLzfFuTDnoZbC1CFBmrCEtMSKEuXwAT3VKPXB5");
+}
+
 lazy_static! {
     /// Map of feature identifiers to user-visible description
     pub static ref FEATURE_NAMES: HashMap<Pubkey, &'static str> = [
@@ -970,6 +1046,25 @@ lazy_static! {
         (cost_model_requested_write_lock_cost::id(), "cost model uses number of requested write locks #34819"),
         (enable_gossip_duplicate_proof_ingestion::id(), "enable gossip duplicate proof ingestion #32963"),
         (enable_chained_merkle_shreds::id(), "Enable chained Merkle shreds #34916"),
+        (enable_tower_sync_ix::id(), "Enable tower sync vote instruction"),
+        (limit_commission_increase::id(), "Limit the size of validator commission increases"),
+        (enable_advance_and_authorize_nonce_ix::id(), "Enable AdvanceAndAuthorizeNonceAccount system instruction"),
+        (enable_get_caller_program_id_syscall::id(), "Enable the sol_get_caller_program_id syscall"),
+        (enable_bpf_loader_verify_buffer_hash_ix::id(), "enable bpf upgradeable loader VerifyBufferHash instruction"),
+        (enable_bpf_loader_truncate_program_data_ix::id(), "enable bpf upgradeable loader TruncateProgramData instruction"),
+        (enable_sbpf_v2_deployment_and_execution::id(), "enable deployment and execution of SBPFv2 programs in the upgradeable loader"),
+        (enable_shred_erasure_config::id(), "emit and accept data shreds carrying their erasure config"),
+        (enable_fee_sponsorship::id(), "allow a signer other than the first to pay transaction fees"),
+        (enable_transaction_max_slot::id(), "allow transactions to set the last slot in which they may be processed"),
+        (enable_zk_range_proof_batch_verification::id(), "Enable zk token proof program to verify a batch of range proofs in one instruction"),
+        (epoch_stake_sysvar::id(), "enable new sysvar epoch_stake and the sol_get_sysvar syscall"),
+        (enable_get_minimum_balance_for_rent_exemption_syscall::id(), "enable the sol_get_minimum_balance_for_rent_exemption syscall"),
+        (restart_history_sysvar::id(), "enable new sysvar restart_history"),
+        (stake_split_with_funding::id(), "enable the stake program SplitWithFunding instruction"),
+        (stake_deactivate_multiple::id(), "enable the stake program DeactivateMultiple instruction"),
+        (cost_model_write_lock_hotspot_limits::id(), "raise the write lock cost and lower the writable account cost limit"),
+        (accounts_lt_hash::id(), "hash the incremental lattice hash of all accounts into the bank hash"),
+        (enable_batched_hash_syscalls::id(), "enable the batched and incremental blake3 and keccak256 syscalls"),
         /*************** ADD NEW FEATURES HERE ***************/
     ]
     .iter()
@@ -84,13 +84,24 @@ impl FeeStructure {
         budget_limits: &FeeBudgetLimits,
         include_loaded_account_data_size_in_fee: bool,
     ) -> u64 {
-        // Fee based on compute units and signatures
-        let congestion_multiplier = if lamports_per_signature == 0 {
-            0.0 // test only
-        } else {
-            1.0 // multiplier that has no effect
-        };
+        self.calculate_fee_details(
+            message,
+            lamports_per_signature,
+            budget_limits,
+            include_loaded_account_data_size_in_fee,
+        )
+        .total_fee()
+    }
 
+    /// Calculate the itemized fee for `SanitizedMessage`
+    #[cfg(not(target_os = "solana"))]
+    pub fn calculate_fee_details(
+        &self,
+        message: &SanitizedMessage,
+        lamports_per_signature: u64,
+        budget_limits: &FeeBudgetLimits,
+        include_loaded_account_data_size_in_fee: bool,
+    ) -> FeeDetails {
         let signature_fee = message
             .num_signatures()
             .saturating_mul(self.lamports_per_signature);
@@ -108,6 +119,14 @@ impl FeeStructure {
         } else {
             0_u64
         };
+        // zero lamports_per_signature waives the whole fee, test only
+        if lamports_per_signature == 0 {
+            return FeeDetails {
+                loaded_accounts_data_size_cost,
+                ..FeeDetails::default()
+            };
+        }
+
         let total_compute_units =
             loaded_accounts_data_size_cost.saturating_add(budget_limits.compute_unit_limit);
         let compute_fee = self
@@ -122,16 +141,71 @@ impl FeeStructure {
                     .unwrap_or_default()
             });
 
-        ((budget_limits
+        FeeDetails {
+            signature_fee,
+            write_lock_fee,
+            compute_fee,
+            prioritization_fee: budget_limits.prioritization_fee,
+            loaded_accounts_data_size_cost,
+        }
+    }
+}
+
+/// The components of a transaction fee, as charged by the bank
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct FeeDetails {
+    /// lamports charged for the message's signatures
+    pub signature_fee: u64,
+    /// lamports charged for the message's write locks
+    pub write_lock_fee: u64,
+    /// lamports charged by the compute fee bin the transaction falls into
+    pub compute_fee: u64,
+    /// lamports charged for the requested compute unit price
+    pub prioritization_fee: u64,
+    /// compute units charged for the requested loaded accounts data size,
+    /// which count towards selecting the compute fee bin
+    pub loaded_accounts_data_size_cost: u64,
+}
+
+impl FeeDetails {
+    /// Total fee in lamports, the sum of the signature, write lock, compute
+    /// and prioritization fees
+    pub fn total_fee(&self) -> u64 {
+        // round trip through f64 to charge exactly what the bank always has,
+        // which only differs from the sum above 2^53 lamports
+        (self
             .prioritization_fee
-            .saturating_add(signature_fee)
-            .saturating_add(write_lock_fee)
-            .saturating_add(compute_fee) as f64)
-            * congestion_multiplier)
+            .saturating_add(self.signature_fee)
+            .saturating_add(self.write_lock_fee)
+            .saturating_add(self.compute_fee) as f64)
             .round() as u64
     }
 }
 
+/// Calculate the itemized fee the bank charges for `message`, without
+/// simulating it.
+///
+/// `budget_limits` can be created from the limits requested by the message's
+/// compute budget instructions, and `lamports_per_signature` is that of the
+/// message's recent blockhash. `include_loaded_account_data_size_in_fee`
+/// should match whether the `include_loaded_accounts_data_size_in_fee_calculation`
+/// feature is active.
+#[cfg(not(target_os = "solana"))]
+pub fn calculate_fee_details(
+    message: &SanitizedMessage,
+    fee_structure: &FeeStructure,
+    budget_limits: &FeeBudgetLimits,
+    lamports_per_signature: u64,
+    include_loaded_account_data_size_in_fee: bool,
+) -> FeeDetails {
+    fee_structure.calculate_fee_details(
+        message,
+        lamports_per_signature,
+        budget_limits,
+        include_loaded_account_data_size_in_fee,
+    )
+}
+
 impl Default for FeeStructure {
     fn default() -> Self {
         Self::new(0.000005, 0.0, vec![(1_400_000, 0.0)])
@@ -147,7 +221,111 @@ impl ::solana_frozen_abi::abi_example::AbiExample for FeeStructure {
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use {
+        super::*,
+        crate::{
+            instruction::{AccountMeta, Instruction},
+            message::Message,
+            pubkey::Pubkey,
+        },
+    };
+
+    #[test]
+    fn test_calculate_fee_details() {
+        let payer = Pubkey::new_unique();
+        let message = SanitizedMessage::try_from(Message::new(
+            &[Instruction::new_with_bytes(
+                Pubkey::new_unique(),
+                &[],
+                vec![
+                    AccountMeta::new(Pubkey::new_unique(), true),
+                    AccountMeta::new(Pubkey::new_unique(), false),
+                ],
+            )],
+            Some(&payer),
+        ))
+        .unwrap();
+        let fee_structure = FeeStructure {
+            lamports_per_signature: 5_000,
+            lamports_per_write_lock: 1_000,
+            compute_fee_bins: vec![
+                FeeBin {
+                    limit: 200_000,
+                    fee: 0,
+                },
+                FeeBin {
+                    limit: 1_400_000,
+                    fee: 100,
+                },
+            ],
+        };
+        let budget_limits = FeeBudgetLimits {
+            loaded_accounts_data_size_limit: 64 * 1024,
+            heap_cost: 100_000,
+            compute_unit_limit: 150_000,
+            prioritization_fee: 42,
+        };
+
+        let fee_details =
+            fee_structure.calculate_fee_details(&message, 5_000, &budget_limits, false);
+        assert_eq!(fee_details.signature_fee, 10_000);
+        assert_eq!(fee_details.write_lock_fee, 3_000);
+        assert_eq!(fee_details.compute_fee, 0);
+        assert_eq!(fee_details.prioritization_fee, 42);
+        assert_eq!(fee_details.loaded_accounts_data_size_cost, 0);
+        assert_eq!(fee_details.total_fee(), 13_042);
+        assert_eq!(
+            fee_details.total_fee(),
+            fee_structure.calculate_fee(&message, 5_000, &budget_limits, false)
+        );
+        assert_eq!(
+            fee_details,
+            calculate_fee_details(&message, &fee_structure, &budget_limits, 5_000, false)
+        );
+
+        // the loaded accounts data size cost pushes the transaction into the
+        // next compute fee bin
+        let fee_details =
+            fee_structure.calculate_fee_details(&message, 5_000, &budget_limits, true);
+        assert_eq!(fee_details.compute_fee, 100);
+        assert_eq!(fee_details.loaded_accounts_data_size_cost, 200_000);
+        assert_eq!(fee_details.total_fee(), 13_142);
+        assert_eq!(
+            fee_details.total_fee(),
+            fee_structure.calculate_fee(&message, 5_000, &budget_limits, true)
+        );
+        assert_eq!(
+            fee_details,
+            calculate_fee_details(&message, &fee_structure, &budget_limits, 5_000, true)
+        );
+
+        assert_eq!(
+            fee_details.total_fee(),
+            fee_details.signature_fee
+                + fee_details.write_lock_fee
+                + fee_details.compute_fee
+                + fee_details.prioritization_fee
+        );
+
+        // zero lamports_per_signature waives the fee, and each of its parts
+        let fee_details = fee_structure.calculate_fee_details(&message, 0, &budget_limits, true);
+        assert_eq!(fee_details.total_fee(), 0);
+        assert_eq!(
+            fee_details,
+            FeeDetails {
+                loaded_accounts_data_size_cost: 200_000,
+                ..FeeDetails::default()
+            }
+        );
+        assert_eq!(
+            fee_structure.calculate_fee(&message, 0, &budget_limits, true),
+            0
+        );
+        assert_eq!(
+            fee_details,
+            calculate_fee_details(&message, &fee_structure, &budget_limits, 0, true)
+        );
+    }
 
     #[test]
     fn test_calculate_memory_usage_cost() {
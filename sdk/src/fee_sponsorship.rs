@@ -0,0 +1,130 @@
+//! Messages whose fees are paid by a sponsor rather than by their first signer.
+//!
+//! A message designates its fee payer with a
+//! [`ComputeBudgetInstruction::SetFeePayer`] instruction, whose only account
+//! is the sponsor. The sponsor signs the transaction to authorize paying its
+//! fees, while the first signer remains the authority of the instructions.
+//! Unless the instructions themselves reference the sponsor, its signature
+//! grants no other authority.
+//!
+//! The instruction is appended after the given instructions, so that the
+//! index of an instruction in a transaction error is the same as in the
+//! unsponsored message.
+#![cfg(feature = "full")]
+
+use crate::{
+    address_lookup_table_account::AddressLookupTableAccount,
+    compute_budget::ComputeBudgetInstruction,
+    hash::Hash,
+    instruction::Instruction,
+    message::{v0, CompileError, Message},
+    pubkey::Pubkey,
+};
+
+fn sponsored_instructions(instructions: &[Instruction], sponsor: &Pubkey) -> Vec<Instruction> {
+    let mut instructions = instructions.to_vec();
+    instructions.push(ComputeBudgetInstruction::set_fee_payer(sponsor));
+    instructions
+}
+
+/// Create a legacy `Message` whose fees are paid by `sponsor`
+pub fn new_sponsored_message(
+    instructions: &[Instruction],
+    first_signer: &Pubkey,
+    sponsor: &Pubkey,
+) -> Message {
+    Message::new(
+        &sponsored_instructions(instructions, sponsor),
+        Some(first_signer),
+    )
+}
+
+/// Create a legacy `Message` whose fees are paid by `sponsor`, with a recent
+/// blockhash
+pub fn new_sponsored_message_with_blockhash(
+    instructions: &[Instruction],
+    first_signer: &Pubkey,
+    sponsor: &Pubkey,
+    recent_blockhash: &Hash,
+) -> Message {
+    Message::new_with_blockhash(
+        &sponsored_instructions(instructions, sponsor),
+        Some(first_signer),
+        recent_blockhash,
+    )
+}
+
+/// Compile a v0 `Message` whose fees are paid by `sponsor`
+pub fn try_compile_sponsored_v0_message(
+    first_signer: &Pubkey,
+    sponsor: &Pubkey,
+    instructions: &[Instruction],
+    address_lookup_table_accounts: &[AddressLookupTableAccount],
+    recent_blockhash: Hash,
+) -> Result<v0::Message, CompileError> {
+    v0::Message::try_compile(
+        first_signer,
+        &sponsored_instructions(instructions, sponsor),
+        address_lookup_table_accounts,
+        recent_blockhash,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{compute_budget, system_instruction},
+    };
+
+    #[test]
+    fn test_new_sponsored_message() {
+        let user = Pubkey::new_unique();
+        let sponsor = Pubkey::new_unique();
+        let recipient = Pubkey::new_unique();
+        let instructions = [system_instruction::transfer(&user, &recipient, 42)];
+        let message = new_sponsored_message(&instructions, &user, &sponsor);
+
+        // Both the user and the sponsor are writable signers, with the user first
+        assert_eq!(message.header.num_required_signatures, 2);
+        assert_eq!(message.header.num_readonly_signed_accounts, 0);
+        assert_eq!(message.account_keys[..2], [user, sponsor]);
+        assert_eq!(message.instructions.len(), 2);
+        let set_fee_payer = &message.instructions[1];
+        assert!(compute_budget::check_id(
+            set_fee_payer.program_id(&message.account_keys)
+        ));
+        assert_eq!(set_fee_payer.accounts, vec![1]);
+
+        let recent_blockhash = Hash::new_unique();
+        let message = Message {
+            recent_blockhash,
+            ..message
+        };
+        assert_eq!(
+            new_sponsored_message_with_blockhash(&instructions, &user, &sponsor, &recent_blockhash),
+            message
+        );
+    }
+
+    #[test]
+    fn test_try_compile_sponsored_v0_message() {
+        let user = Pubkey::new_unique();
+        let sponsor = Pubkey::new_unique();
+        let recipient = Pubkey::new_unique();
+        let instructions = [system_instruction::transfer(&user, &recipient, 42)];
+        let message = try_compile_sponsored_v0_message(
+            &user,
+            &sponsor,
+            &instructions,
+            &[],
+            Hash::new_unique(),
+        )
+        .unwrap();
+
+        assert_eq!(message.header.num_required_signatures, 2);
+        assert_eq!(message.account_keys[..2], [user, sponsor]);
+        assert_eq!(message.instructions.len(), 2);
+        assert_eq!(message.instructions[1].accounts, vec![1]);
+    }
+}
@@ -19,3 +19,19 @@ pub type InnerInstructions = Vec<InnerInstruction>;
 /// A list of compiled instructions that were invoked during each instruction of
 /// a transaction
 pub type InnerInstructionsList = Vec<InnerInstructions>;
+
+/// Compute units consumed by a transaction instruction and by each of its
+/// inner instructions
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstructionComputeUnits {
+    /// Compute units consumed by the transaction instruction, including its
+    /// inner instructions
+    pub compute_units_consumed: u64,
+    /// Compute units consumed by each inner instruction, in the order of
+    /// `InnerInstructions`, including the instructions it invoked in turn
+    pub inner_instructions: Vec<u64>,
+}
+
+/// The compute units consumed by each instruction of a transaction
+pub type InstructionComputeUnitsList = Vec<InstructionComputeUnits>;
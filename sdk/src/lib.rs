@@ -75,6 +75,7 @@ pub mod exit;
 pub mod feature;
 pub mod feature_set;
 pub mod fee;
+pub mod fee_sponsorship;
 pub mod genesis_config;
 pub mod hard_forks;
 pub mod hash;
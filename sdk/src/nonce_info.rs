@@ -67,17 +67,13 @@ impl NonceFull {
     pub fn from_partial(
         partial: &NoncePartial,
         message: &SanitizedMessage,
+        fee_payer_index: usize,
         accounts: &[TransactionAccount],
         rent_debits: &RentDebits,
     ) -> transaction::Result<Self> {
-        let fee_payer = (0..message.account_keys().len()).find_map(|i| {
-            if let Some((k, a)) = &accounts.get(i) {
-                if message.is_non_loader_key(i) {
-                    return Some((k, a));
-                }
-            }
-            None
-        });
+        let fee_payer = accounts
+            .get(fee_payer_index)
+            .filter(|_| message.is_non_loader_key(fee_payer_index));
 
         if let Some((fee_payer_address, fee_payer_account)) = fee_payer {
             let mut fee_payer_account = fee_payer_account.clone();
@@ -221,7 +217,7 @@ mod tests {
             ];
 
             let full =
-                NonceFull::from_partial(&partial, &message, &accounts, &rent_debits).unwrap();
+                NonceFull::from_partial(&partial, &message, 0, &accounts, &rent_debits).unwrap();
             assert_eq!(*full.address(), nonce_address);
             assert_eq!(*full.account(), rent_collected_nonce_account);
             assert_eq!(full.lamports_per_signature(), Some(lamports_per_signature));
@@ -252,7 +248,7 @@ mod tests {
             ];
 
             let full =
-                NonceFull::from_partial(&partial, &message, &accounts, &rent_debits).unwrap();
+                NonceFull::from_partial(&partial, &message, 0, &accounts, &rent_debits).unwrap();
             assert_eq!(*full.address(), nonce_address);
             assert_eq!(*full.account(), nonce_account);
             assert_eq!(full.lamports_per_signature(), Some(lamports_per_signature));
@@ -263,7 +259,7 @@ mod tests {
         {
             let message = new_sanitized_message(&instructions, Some(&nonce_address));
             assert_eq!(
-                NonceFull::from_partial(&partial, &message, &[], &RentDebits::default())
+                NonceFull::from_partial(&partial, &message, 0, &[], &RentDebits::default())
                     .unwrap_err(),
                 TransactionError::AccountNotFound,
             );
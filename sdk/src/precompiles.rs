@@ -4,7 +4,9 @@
 
 use {
     crate::{
-        decode_error::DecodeError, feature_set::FeatureSet, instruction::CompiledInstruction,
+        decode_error::DecodeError,
+        feature_set::FeatureSet,
+        instruction::{CompiledInstruction, Instruction},
         pubkey::Pubkey,
     },
     lazy_static::lazy_static,
@@ -103,6 +105,15 @@ pub fn get_precompiles<'a>() -> &'a [Precompile] {
     &PRECOMPILES
 }
 
+fn get_enabled_precompile<'a>(
+    program_id: &Pubkey,
+    feature_set: &FeatureSet,
+) -> Option<&'a Precompile> {
+    get_precompiles().iter().find(|precompile| {
+        precompile.check_id(program_id, |feature_id| feature_set.is_active(feature_id))
+    })
+}
+
 /// Check that a program is precompiled and if so verify it
 pub fn verify_if_precompile(
     program_id: &Pubkey,
@@ -110,18 +121,90 @@ pub fn verify_if_precompile(
     all_instructions: &[CompiledInstruction],
     feature_set: &FeatureSet,
 ) -> Result<(), PrecompileError> {
-    for precompile in PRECOMPILES.iter() {
-        if precompile.check_id(program_id, |feature_id| feature_set.is_active(feature_id)) {
-            let instruction_datas: Vec<_> = all_instructions
-                .iter()
-                .map(|instruction| instruction.data.as_ref())
-                .collect();
-            return precompile.verify(
-                &precompile_instruction.data,
-                &instruction_datas,
-                feature_set,
+    let Some(precompile) = get_enabled_precompile(program_id, feature_set) else {
+        return Ok(());
+    };
+    let instruction_datas: Vec<_> = all_instructions
+        .iter()
+        .map(|instruction| instruction.data.as_ref())
+        .collect();
+    precompile.verify(
+        &precompile_instruction.data,
+        &instruction_datas,
+        feature_set,
+    )
+}
+
+/// Check that an instruction calls a precompiled program and if so verify it
+/// as the runtime would, e.g. to validate a transaction before submitting it
+///
+/// `all_instructions` are the instructions of the transaction, in order,
+/// since a precompile may verify data held by any of them.
+pub fn verify_instruction_if_precompile(
+    instruction: &Instruction,
+    all_instructions: &[Instruction],
+    feature_set: &FeatureSet,
+) -> Result<(), PrecompileError> {
+    let Some(precompile) = get_enabled_precompile(&instruction.program_id, feature_set) else {
+        return Ok(());
+    };
+    let instruction_datas: Vec<_> = all_instructions
+        .iter()
+        .map(|instruction| instruction.data.as_ref())
+        .collect();
+    precompile.verify(&instruction.data, &instruction_datas, feature_set)
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{ed25519_instruction::new_ed25519_instruction, system_instruction},
+        rand0_7::thread_rng,
+    };
+
+    #[test]
+    fn test_verify_instruction_if_precompile() {
+        let feature_set = FeatureSet::all_enabled();
+        let keypair = ed25519_dalek::Keypair::generate(&mut thread_rng());
+        let mut ed25519_instruction = new_ed25519_instruction(&keypair, b"hello");
+        let transfer_instruction =
+            system_instruction::transfer(&Pubkey::new_unique(), &Pubkey::new_unique(), 42);
+        let instructions = [ed25519_instruction.clone(), transfer_instruction.clone()];
+        for instruction in &instructions {
+            assert_eq!(
+                verify_instruction_if_precompile(instruction, &instructions, &feature_set),
+                Ok(())
             );
         }
+
+        // The message is the last of the instruction data
+        *ed25519_instruction.data.last_mut().unwrap() ^= 1;
+        let instructions = [ed25519_instruction, transfer_instruction];
+        assert_eq!(
+            verify_instruction_if_precompile(&instructions[0], &instructions, &feature_set),
+            Err(PrecompileError::InvalidSignature)
+        );
+        assert_eq!(
+            verify_instruction_if_precompile(&instructions[1], &instructions, &feature_set),
+            Ok(())
+        );
+
+        // Matches the runtime's verification of the compiled instructions
+        let compiled_instructions: Vec<_> = instructions
+            .iter()
+            .map(|instruction| {
+                CompiledInstruction::new_from_raw_parts(0, instruction.data.clone(), vec![])
+            })
+            .collect();
+        assert_eq!(
+            verify_if_precompile(
+                &instructions[0].program_id,
+                &compiled_instructions[0],
+                &compiled_instructions,
+                &feature_set,
+            ),
+            Err(PrecompileError::InvalidSignature)
+        );
     }
-    Ok(())
 }
@@ -0,0 +1,357 @@
+//! Asynchronous signers, for keys held by remote services or hardware.
+
+use {
+    crate::{
+        hash::Hash,
+        message::VersionedMessage,
+        pubkey::Pubkey,
+        signature::Signature,
+        signer::{Signer, SignerError},
+        transaction::{Transaction, VersionedTransaction},
+    },
+    async_trait::async_trait,
+    std::{
+        cmp::Ordering,
+        future::Future,
+        pin::pin,
+        sync::Arc,
+        task::{Context, Poll, Wake, Waker},
+        thread::{self, Thread},
+    },
+};
+
+/// The asynchronous counterpart of [`Signer`], for signers which wait on I/O,
+/// such as a KMS or HSM, to produce signatures.
+///
+/// Every `Signer` which is `Send + Sync` is also an `AsyncSigner`, and any
+/// `AsyncSigner` can be used where a `Signer` is expected by wrapping it in a
+/// [`BlockingSigner`].
+#[async_trait]
+pub trait AsyncSigner: Send + Sync {
+    /// Fallibly gets the implementor's public key
+    async fn try_pubkey(&self) -> Result<Pubkey, SignerError>;
+    /// Fallibly produces an Ed25519 signature over the provided `message` bytes.
+    async fn try_sign_message(&self, message: &[u8]) -> Result<Signature, SignerError>;
+    /// Whether the implementation requires user interaction to sign
+    fn is_interactive(&self) -> bool;
+}
+
+#[async_trait]
+impl<T: Signer + Send + Sync + ?Sized> AsyncSigner for T {
+    async fn try_pubkey(&self) -> Result<Pubkey, SignerError> {
+        Signer::try_pubkey(self)
+    }
+
+    async fn try_sign_message(&self, message: &[u8]) -> Result<Signature, SignerError> {
+        Signer::try_sign_message(self, message)
+    }
+
+    fn is_interactive(&self) -> bool {
+        Signer::is_interactive(self)
+    }
+}
+
+/// Adapts an [`AsyncSigner`] to the synchronous [`Signer`] trait by blocking
+/// the calling thread until each operation completes.
+///
+/// The signer's futures are driven on the calling thread, so they must not
+/// depend on an async runtime that the calling thread is itself running. From
+/// async code, prefer the helpers in this module.
+pub struct BlockingSigner<S: AsyncSigner>(pub S);
+
+impl<S: AsyncSigner> Signer for BlockingSigner<S> {
+    fn try_pubkey(&self) -> Result<Pubkey, SignerError> {
+        block_on(self.0.try_pubkey())
+    }
+
+    fn try_sign_message(&self, message: &[u8]) -> Result<Signature, SignerError> {
+        block_on(self.0.try_sign_message(message))
+    }
+
+    fn is_interactive(&self) -> bool {
+        self.0.is_interactive()
+    }
+}
+
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut context = Context::from_waker(&waker);
+    loop {
+        match future.as_mut().poll(&mut context) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+/// Fetch the pubkeys of `signers`, one at a time
+pub async fn try_pubkeys(signers: &[&dyn AsyncSigner]) -> Result<Vec<Pubkey>, SignerError> {
+    let mut pubkeys = Vec::with_capacity(signers.len());
+    for signer in signers {
+        pubkeys.push(signer.try_pubkey().await?);
+    }
+    Ok(pubkeys)
+}
+
+/// Sign `message` with each of `signers`, one at a time, so that interactive
+/// signers never prompt concurrently
+pub async fn try_sign_message(
+    signers: &[&dyn AsyncSigner],
+    message: &[u8],
+) -> Result<Vec<Signature>, SignerError> {
+    let mut signatures = Vec::with_capacity(signers.len());
+    for signer in signers {
+        signatures.push(signer.try_sign_message(message).await?);
+    }
+    Ok(signatures)
+}
+
+/// Sign `transaction` with `signers`, as [`Transaction::try_sign`] does.
+///
+/// Every required signer must be among `signers`, and every one of `signers`
+/// must be a required signer.
+pub async fn try_sign_transaction(
+    transaction: &mut Transaction,
+    signers: &[&dyn AsyncSigner],
+    recent_blockhash: Hash,
+) -> Result<(), SignerError> {
+    try_partial_sign_transaction(transaction, signers, recent_blockhash).await?;
+    if !transaction.is_signed() {
+        Err(SignerError::NotEnoughSigners)
+    } else {
+        Ok(())
+    }
+}
+
+/// Sign `transaction` with some of its required signers, as
+/// [`Transaction::try_partial_sign`] does.
+pub async fn try_partial_sign_transaction(
+    transaction: &mut Transaction,
+    signers: &[&dyn AsyncSigner],
+    recent_blockhash: Hash,
+) -> Result<(), SignerError> {
+    let pubkeys = try_pubkeys(signers).await?;
+    let positions = transaction.get_signing_keypair_positions(&pubkeys)?;
+    if positions.iter().any(|position| position.is_none()) {
+        return Err(SignerError::KeypairPubkeyMismatch);
+    }
+    if recent_blockhash != transaction.message.recent_blockhash {
+        transaction.message.recent_blockhash = recent_blockhash;
+        transaction
+            .signatures
+            .iter_mut()
+            .for_each(|signature| *signature = Signature::default());
+    }
+
+    let signatures = try_sign_message(signers, &transaction.message_data()).await?;
+    for (position, signature) in positions.into_iter().flatten().zip(signatures) {
+        transaction.signatures[position] = signature;
+    }
+    Ok(())
+}
+
+/// Sign a versioned message, as [`VersionedTransaction::try_new`] does.
+pub async fn try_new_versioned_transaction(
+    message: VersionedMessage,
+    signers: &[&dyn AsyncSigner],
+) -> Result<VersionedTransaction, SignerError> {
+    let num_required_signatures = usize::from(message.header().num_required_signatures);
+    let static_account_keys = message.static_account_keys();
+    if static_account_keys.len() < num_required_signatures {
+        return Err(SignerError::InvalidInput("invalid message".to_string()));
+    }
+    let expected_signer_keys = &static_account_keys[..num_required_signatures];
+
+    match signers.len().cmp(&expected_signer_keys.len()) {
+        Ordering::Greater => Err(SignerError::TooManySigners),
+        Ordering::Less => Err(SignerError::NotEnoughSigners),
+        Ordering::Equal => Ok(()),
+    }?;
+
+    let signer_keys = try_pubkeys(signers).await?;
+    let signature_indexes = expected_signer_keys
+        .iter()
+        .map(|signer_key| {
+            signer_keys
+                .iter()
+                .position(|key| key == signer_key)
+                .ok_or(SignerError::KeypairPubkeyMismatch)
+        })
+        .collect::<Result<Vec<_>, SignerError>>()?;
+
+    let unordered_signatures = try_sign_message(signers, &message.serialize()).await?;
+    let signatures = signature_indexes
+        .into_iter()
+        .map(|index| unordered_signatures[index])
+        .collect();
+
+    Ok(VersionedTransaction {
+        signatures,
+        message,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            instruction::{AccountMeta, Instruction},
+            message::Message,
+            signature::Keypair,
+        },
+        std::sync::atomic::{AtomicBool, Ordering as AtomicOrdering},
+    };
+
+    /// Signs with a keypair, but only after being polled once, like a signer
+    /// waiting on a remote service
+    struct RemoteSigner {
+        keypair: Keypair,
+    }
+
+    struct YieldOnce(AtomicBool);
+
+    impl Future for YieldOnce {
+        type Output = ();
+        fn poll(self: std::pin::Pin<&mut Self>, context: &mut Context<'_>) -> Poll<()> {
+            if self.0.swap(true, AtomicOrdering::Relaxed) {
+                Poll::Ready(())
+            } else {
+                context.waker().wake_by_ref();
+                Poll::Pending
+            }
+        }
+    }
+
+    #[async_trait]
+    impl AsyncSigner for RemoteSigner {
+        async fn try_pubkey(&self) -> Result<Pubkey, SignerError> {
+            YieldOnce(AtomicBool::new(false)).await;
+            Ok(Signer::pubkey(&self.keypair))
+        }
+
+        async fn try_sign_message(&self, message: &[u8]) -> Result<Signature, SignerError> {
+            YieldOnce(AtomicBool::new(false)).await;
+            Ok(Signer::sign_message(&self.keypair, message))
+        }
+
+        fn is_interactive(&self) -> bool {
+            false
+        }
+    }
+
+    fn test_message(payer: &Pubkey, other_signer: &Pubkey) -> Message {
+        Message::new(
+            &[Instruction::new_with_bytes(
+                Pubkey::new_unique(),
+                &[],
+                vec![AccountMeta::new_readonly(*other_signer, true)],
+            )],
+            Some(payer),
+        )
+    }
+
+    #[test]
+    fn test_blocking_signer() {
+        let keypair = Keypair::new();
+        let remote = RemoteSigner {
+            keypair: keypair.insecure_clone(),
+        };
+        let blocking = BlockingSigner(remote);
+        assert_eq!(Signer::pubkey(&blocking), Signer::pubkey(&keypair));
+        assert_eq!(
+            Signer::sign_message(&blocking, b"message"),
+            Signer::sign_message(&keypair, b"message")
+        );
+    }
+
+    #[test]
+    fn test_try_sign_transaction() {
+        let payer = Keypair::new();
+        let remote = RemoteSigner {
+            keypair: Keypair::new(),
+        };
+        let message = test_message(&Signer::pubkey(&payer), &Signer::pubkey(&remote.keypair));
+        let blockhash = Hash::new_unique();
+
+        let mut expected = Transaction::new_unsigned(message.clone());
+        expected.sign(&[&payer, &remote.keypair], blockhash);
+
+        // sync signers are async signers too
+        let mut transaction = Transaction::new_unsigned(message.clone());
+        block_on(try_sign_transaction(
+            &mut transaction,
+            &[&remote, &payer],
+            blockhash,
+        ))
+        .unwrap();
+        assert_eq!(transaction, expected);
+
+        let mut transaction = Transaction::new_unsigned(message.clone());
+        assert_eq!(
+            block_on(try_sign_transaction(
+                &mut transaction,
+                &[&remote],
+                blockhash
+            )),
+            Err(SignerError::NotEnoughSigners)
+        );
+        block_on(try_partial_sign_transaction(
+            &mut transaction,
+            &[&payer],
+            blockhash,
+        ))
+        .unwrap();
+        assert_eq!(transaction, expected);
+
+        let stranger = Keypair::new();
+        assert_eq!(
+            block_on(try_partial_sign_transaction(
+                &mut transaction,
+                &[&stranger],
+                blockhash
+            )),
+            Err(SignerError::KeypairPubkeyMismatch)
+        );
+    }
+
+    #[test]
+    fn test_try_new_versioned_transaction() {
+        let payer = Keypair::new();
+        let remote = RemoteSigner {
+            keypair: Keypair::new(),
+        };
+        let mut message = test_message(&Signer::pubkey(&payer), &Signer::pubkey(&remote.keypair));
+        message.recent_blockhash = Hash::new_unique();
+        let message = VersionedMessage::Legacy(message);
+
+        let transaction = block_on(try_new_versioned_transaction(
+            message.clone(),
+            &[&remote, &payer],
+        ))
+        .unwrap();
+        assert_eq!(
+            transaction,
+            VersionedTransaction::try_new(message.clone(), &[&payer, &remote.keypair]).unwrap()
+        );
+
+        assert_eq!(
+            block_on(try_new_versioned_transaction(message.clone(), &[&payer])),
+            Err(SignerError::NotEnoughSigners)
+        );
+        let stranger = Keypair::new();
+        assert_eq!(
+            block_on(try_new_versioned_transaction(message, &[&payer, &stranger])),
+            Err(SignerError::KeypairPubkeyMismatch)
+        );
+    }
+}
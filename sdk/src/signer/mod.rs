@@ -20,6 +20,7 @@ use {
     thiserror::Error,
 };
 
+pub mod async_signer;
 pub mod keypair;
 pub mod null_signer;
 pub mod presigner;
@@ -169,6 +169,10 @@ pub enum TransactionError {
     /// The total balance before the transaction does not equal the total balance after the transaction
     #[error("Sum of account balances before and after transaction do not match")]
     UnbalancedTransaction,
+
+    /// The last slot in which the transaction may be processed has passed
+    #[error("Transaction max slot exceeded")]
+    MaxSlotExceeded,
 }
 
 impl From<SanitizeError> for TransactionError {
@@ -133,10 +133,11 @@ use {
 };
 
 mod error;
+mod packer;
 mod sanitized;
 mod versioned;
 
-pub use {error::*, sanitized::*, versioned::*};
+pub use {error::*, packer::*, sanitized::*, versioned::*};
 
 #[derive(PartialEq, Eq, Clone, Copy, Debug)]
 pub enum TransactionVerificationMode {
@@ -0,0 +1,273 @@
+//! Packing of instructions into as few transactions as fit into packets.
+
+#![cfg(feature = "full")]
+
+use {
+    crate::{
+        address_lookup_table_account::AddressLookupTableAccount,
+        hash::Hash,
+        instruction::Instruction,
+        message::{v0, Message, VersionedMessage},
+        packet::PACKET_DATA_SIZE,
+        pubkey::Pubkey,
+    },
+    thiserror::Error,
+};
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum PackError {
+    #[error("instruction {0} does not fit into a transaction on its own")]
+    InstructionTooLarge(usize),
+}
+
+/// Packs a sequence of instructions into the fewest messages whose
+/// transactions fit into a packet, keeping the instructions in order.
+///
+/// The size of each message accounts for the signatures of all of its
+/// signers. If address lookup tables are given, messages are compiled as v0
+/// messages which load accounts from those tables that make them smaller;
+/// otherwise they are compiled as legacy messages.
+///
+/// # Examples
+///
+/// ```
+/// # use solana_sdk::{
+/// #     hash::Hash,
+/// #     pubkey::Pubkey,
+/// #     system_instruction,
+/// #     transaction::TransactionPacker,
+/// # };
+/// let payer = Pubkey::new_unique();
+/// let instructions: Vec<_> = (0..100)
+///     .map(|_| system_instruction::transfer(&payer, &Pubkey::new_unique(), 1))
+///     .collect();
+///
+/// let messages = TransactionPacker::new(payer).pack(&instructions, Hash::default())?;
+/// assert!(messages.len() > 1);
+/// assert!(messages
+///     .iter()
+///     .all(|message| message.serialized_size_with_signatures() <= 1232));
+/// # Ok::<(), solana_sdk::transaction::PackError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct TransactionPacker {
+    payer: Pubkey,
+    address_lookup_table_accounts: Vec<AddressLookupTableAccount>,
+    max_transaction_size: usize,
+}
+
+impl TransactionPacker {
+    pub fn new(payer: Pubkey) -> Self {
+        Self {
+            payer,
+            address_lookup_table_accounts: Vec::default(),
+            max_transaction_size: PACKET_DATA_SIZE,
+        }
+    }
+
+    /// Compile v0 messages which may load accounts from these tables.
+    pub fn address_lookup_table_accounts(
+        mut self,
+        address_lookup_table_accounts: Vec<AddressLookupTableAccount>,
+    ) -> Self {
+        self.address_lookup_table_accounts = address_lookup_table_accounts;
+        self
+    }
+
+    /// Limit the size of transactions to fewer than [`PACKET_DATA_SIZE`]
+    /// bytes, e.g. to leave room for signatures or instructions added later.
+    pub fn max_transaction_size(mut self, max_transaction_size: usize) -> Self {
+        self.max_transaction_size = max_transaction_size.min(PACKET_DATA_SIZE);
+        self
+    }
+
+    /// Pack `instructions` into messages, in order, starting a new message
+    /// whenever the next instruction would not fit into the current one.
+    pub fn pack(
+        &self,
+        instructions: &[Instruction],
+        recent_blockhash: Hash,
+    ) -> Result<Vec<VersionedMessage>, PackError> {
+        let mut messages = vec![];
+        let mut start = 0;
+        let mut packed: Option<VersionedMessage> = None;
+        for end in 1..=instructions.len() {
+            match self.try_compile(&instructions[start..end], recent_blockhash) {
+                Some(message) => packed = Some(message),
+                None => {
+                    let message = packed.take().ok_or(PackError::InstructionTooLarge(start))?;
+                    messages.push(message);
+                    start = end - 1;
+                    packed = Some(
+                        self.try_compile(&instructions[start..end], recent_blockhash)
+                            .ok_or(PackError::InstructionTooLarge(start))?,
+                    );
+                }
+            }
+        }
+        messages.extend(packed);
+        Ok(messages)
+    }
+
+    /// Compile a message of `instructions`, if its transaction fits.
+    fn try_compile(
+        &self,
+        instructions: &[Instruction],
+        recent_blockhash: Hash,
+    ) -> Option<VersionedMessage> {
+        // compiling never panics, unlike `Message::new` with too many accounts
+        let message = v0::Message::try_compile_with_lookup_table_selection(
+            &self.payer,
+            instructions,
+            &self.address_lookup_table_accounts,
+            recent_blockhash,
+        )
+        .ok()?;
+        let message = if self.address_lookup_table_accounts.is_empty() {
+            VersionedMessage::Legacy(Message {
+                header: message.header,
+                account_keys: message.account_keys,
+                recent_blockhash: message.recent_blockhash,
+                instructions: message.instructions,
+            })
+        } else {
+            VersionedMessage::V0(message)
+        };
+        (message.serialized_size_with_signatures() <= self.max_transaction_size).then_some(message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            instruction::AccountMeta,
+            signature::{Keypair, Signer},
+            system_instruction,
+            transaction::VersionedTransaction,
+        },
+    };
+
+    fn assert_serialized_size(message: VersionedMessage, signers: &[&Keypair]) {
+        let size = message.serialized_size_with_signatures();
+        let transaction = VersionedTransaction::try_new(message, signers).unwrap();
+        assert_eq!(
+            size,
+            bincode::serialized_size(&transaction).unwrap() as usize
+        );
+    }
+
+    #[test]
+    fn test_serialized_size_with_signatures() {
+        let payer = Keypair::new();
+        let signer = Keypair::new();
+        let instructions = [
+            system_instruction::transfer(&payer.pubkey(), &Pubkey::new_unique(), 1),
+            system_instruction::transfer(&signer.pubkey(), &Pubkey::new_unique(), 1),
+        ];
+        let legacy_message =
+            Message::new_with_blockhash(&instructions, Some(&payer.pubkey()), &Hash::default());
+        assert_serialized_size(VersionedMessage::Legacy(legacy_message), &[&payer, &signer]);
+
+        let address_lookup_table_account = AddressLookupTableAccount {
+            key: Pubkey::new_unique(),
+            addresses: instructions
+                .iter()
+                .map(|instruction| instruction.accounts[1].pubkey)
+                .collect(),
+        };
+        let v0_message = v0::Message::try_compile(
+            &payer.pubkey(),
+            &instructions,
+            &[address_lookup_table_account],
+            Hash::default(),
+        )
+        .unwrap();
+        assert!(!v0_message.address_table_lookups.is_empty());
+        assert_serialized_size(VersionedMessage::V0(v0_message), &[&payer, &signer]);
+    }
+
+    #[test]
+    fn test_pack() {
+        let payer = Pubkey::new_unique();
+        let recipients: Vec<_> = (0..100).map(|_| Pubkey::new_unique()).collect();
+        let instructions: Vec<_> = recipients
+            .iter()
+            .map(|recipient| system_instruction::transfer(&payer, recipient, 1))
+            .collect();
+
+        let messages = TransactionPacker::new(payer)
+            .pack(&instructions, Hash::default())
+            .unwrap();
+        assert!(messages.len() > 1);
+        assert_eq!(
+            messages
+                .iter()
+                .map(|message| message.instructions().len())
+                .sum::<usize>(),
+            instructions.len()
+        );
+        for message in &messages {
+            assert!(message.serialized_size_with_signatures() <= PACKET_DATA_SIZE);
+        }
+        // every message but the last is full
+        for (message, next_message) in messages.iter().zip(messages.iter().skip(1)) {
+            let first_next_instruction = &next_message.instructions()[0];
+            let next_recipient =
+                &next_message.static_account_keys()[first_next_instruction.accounts[1] as usize];
+            let mut message_instructions: Vec<_> = message
+                .instructions()
+                .iter()
+                .map(|instruction| {
+                    system_instruction::transfer(
+                        &payer,
+                        &message.static_account_keys()[instruction.accounts[1] as usize],
+                        1,
+                    )
+                })
+                .collect();
+            message_instructions.push(system_instruction::transfer(&payer, next_recipient, 1));
+            let larger_message =
+                Message::new_with_blockhash(&message_instructions, Some(&payer), &Hash::default());
+            assert!(larger_message.serialized_size_with_signatures() > PACKET_DATA_SIZE);
+        }
+
+        // loading the recipients from a lookup table packs them more densely
+        let address_lookup_table_account = AddressLookupTableAccount {
+            key: Pubkey::new_unique(),
+            addresses: recipients,
+        };
+        let v0_messages = TransactionPacker::new(payer)
+            .address_lookup_table_accounts(vec![address_lookup_table_account])
+            .pack(&instructions, Hash::default())
+            .unwrap();
+        assert!(v0_messages.len() < messages.len());
+        for message in &v0_messages {
+            assert!(matches!(message, VersionedMessage::V0(_)));
+            assert!(message.serialized_size_with_signatures() <= PACKET_DATA_SIZE);
+        }
+    }
+
+    #[test]
+    fn test_pack_instruction_too_large() {
+        let payer = Pubkey::new_unique();
+        let program_id = Pubkey::new_unique();
+        let instructions = [
+            Instruction::new_with_bytes(program_id, &[0; 8], vec![]),
+            Instruction::new_with_bytes(
+                program_id,
+                &[0; PACKET_DATA_SIZE],
+                vec![AccountMeta::new(payer, true)],
+            ),
+        ];
+        assert_eq!(
+            TransactionPacker::new(payer).pack(&instructions, Hash::default()),
+            Err(PackError::InstructionTooLarge(1))
+        );
+        assert_eq!(
+            TransactionPacker::new(payer).pack(&[], Hash::default()),
+            Ok(vec![])
+        );
+    }
+}
@@ -17,15 +17,16 @@ use {
     std::cmp::Ordering,
 };
 
+mod partially_signed;
 mod sanitized;
 
-pub use sanitized::*;
 use {
     crate::program_utils::limited_deserialize,
     solana_program::{
         nonce::NONCED_TX_MARKER_IX_INDEX, system_instruction::SystemInstruction, system_program,
     },
 };
+pub use {partially_signed::*, sanitized::*};
 
 /// Type that serializes to the string "legacy"
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
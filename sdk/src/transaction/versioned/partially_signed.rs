@@ -0,0 +1,420 @@
+//! A transaction whose signatures are collected from several parties.
+
+use {
+    super::VersionedTransaction,
+    crate::{
+        message::VersionedMessage, pubkey::Pubkey, sanitize::SanitizeError, signature::Signature,
+        signer::SignerError, signers::Signers,
+    },
+    serde::Serialize,
+    thiserror::Error,
+};
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum PartialSignError {
+    #[error("message requires more signers than it has static account keys")]
+    InvalidMessage,
+    #[error("invalid message: {0}")]
+    SanitizeError(#[from] SanitizeError),
+    #[error("message requires {expected} signatures but {actual} were collected")]
+    SignatureCountMismatch { expected: usize, actual: usize },
+    #[error("{0} is not a required signer of the message")]
+    NotARequiredSigner(Pubkey),
+    #[error("signature from {0} does not match the message")]
+    InvalidSignature(Pubkey),
+    #[error("{0} has already signed with a different signature")]
+    ConflictingSignature(Pubkey),
+    #[error("cannot merge signatures for different messages")]
+    MessageMismatch,
+    #[error("missing signatures from {0:?}")]
+    MissingSignatures(Vec<Pubkey>),
+    #[error(transparent)]
+    SignerError(#[from] SignerError),
+}
+
+/// A transaction message and the signatures collected for it so far.
+///
+/// Each party with a required signer signs their own copy of the message,
+/// possibly offline, and the copies are then merged. Every signature is
+/// verified against the message as it is added, so a transaction can only be
+/// finalized once each required signer has contributed a valid signature.
+///
+/// Deserialized instances are checked like [`PartiallySignedTransaction::new`]
+/// checks its message, and must have one signature entry per required signer.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(try_from = "UncheckedPartiallySignedTransaction")]
+pub struct PartiallySignedTransaction {
+    message: VersionedMessage,
+    /// One entry per required signer, in message order
+    signatures: Vec<Option<Signature>>,
+}
+
+/// A deserialized `PartiallySignedTransaction`, before its message and
+/// signature count are checked
+#[derive(Deserialize)]
+struct UncheckedPartiallySignedTransaction {
+    message: VersionedMessage,
+    signatures: Vec<Option<Signature>>,
+}
+
+impl TryFrom<UncheckedPartiallySignedTransaction> for PartiallySignedTransaction {
+    type Error = PartialSignError;
+
+    fn try_from(unchecked: UncheckedPartiallySignedTransaction) -> Result<Self, Self::Error> {
+        let UncheckedPartiallySignedTransaction {
+            message,
+            signatures,
+        } = unchecked;
+        let mut partially_signed = Self::new(message)?;
+        if signatures.len() != partially_signed.signatures.len() {
+            return Err(PartialSignError::SignatureCountMismatch {
+                expected: partially_signed.signatures.len(),
+                actual: signatures.len(),
+            });
+        }
+        partially_signed.signatures = signatures;
+        Ok(partially_signed)
+    }
+}
+
+impl PartiallySignedTransaction {
+    /// Start collecting signatures for `message`, which must sanitize.
+    pub fn new(message: VersionedMessage) -> Result<Self, PartialSignError> {
+        let num_required_signatures = usize::from(message.header().num_required_signatures);
+        if message.static_account_keys().len() < num_required_signatures {
+            return Err(PartialSignError::InvalidMessage);
+        }
+        message.sanitize()?;
+        Ok(Self {
+            message,
+            signatures: vec![None; num_required_signatures],
+        })
+    }
+
+    /// Collect the valid signatures of an existing, possibly partially
+    /// signed, transaction. Default signatures are treated as missing.
+    pub fn from_transaction(transaction: VersionedTransaction) -> Result<Self, PartialSignError> {
+        let VersionedTransaction {
+            signatures,
+            message,
+        } = transaction;
+        let mut partially_signed = Self::new(message)?;
+        let signers = partially_signed.required_signers().to_vec();
+        for (pubkey, signature) in signers.into_iter().zip(signatures) {
+            if signature != Signature::default() {
+                partially_signed.add_signature(&pubkey, signature)?;
+            }
+        }
+        Ok(partially_signed)
+    }
+
+    pub fn message(&self) -> &VersionedMessage {
+        &self.message
+    }
+
+    /// The pubkeys which must sign the message, in message order
+    pub fn required_signers(&self) -> &[Pubkey] {
+        self.message
+            .static_account_keys()
+            .get(..self.signatures.len())
+            .unwrap_or_default()
+    }
+
+    /// The required signers which have not signed yet
+    pub fn missing_signers(&self) -> Vec<Pubkey> {
+        self.required_signers()
+            .iter()
+            .zip(&self.signatures)
+            .filter_map(|(pubkey, signature)| signature.is_none().then_some(*pubkey))
+            .collect()
+    }
+
+    pub fn is_fully_signed(&self) -> bool {
+        self.signatures.iter().all(Option::is_some)
+    }
+
+    /// Add a signature produced elsewhere, after checking it against the message.
+    pub fn add_signature(
+        &mut self,
+        pubkey: &Pubkey,
+        signature: Signature,
+    ) -> Result<(), PartialSignError> {
+        let index = self
+            .required_signers()
+            .iter()
+            .position(|signer| signer == pubkey)
+            .ok_or(PartialSignError::NotARequiredSigner(*pubkey))?;
+        if !signature.verify(pubkey.as_ref(), &self.message.serialize()) {
+            return Err(PartialSignError::InvalidSignature(*pubkey));
+        }
+        match self.signatures.get_mut(index) {
+            Some(Some(existing)) if *existing != signature => {
+                Err(PartialSignError::ConflictingSignature(*pubkey))
+            }
+            Some(entry) => {
+                *entry = Some(signature);
+                Ok(())
+            }
+            None => Err(PartialSignError::NotARequiredSigner(*pubkey)),
+        }
+    }
+
+    /// Sign the message with each of `signers`, all of which must be required
+    /// signers of the message.
+    pub fn sign<T: Signers + ?Sized>(&mut self, signers: &T) -> Result<(), PartialSignError> {
+        let pubkeys = signers.try_pubkeys()?;
+        if let Some(pubkey) = pubkeys
+            .iter()
+            .find(|pubkey| !self.required_signers().contains(pubkey))
+        {
+            return Err(PartialSignError::NotARequiredSigner(*pubkey));
+        }
+        let signatures = signers.try_sign_message(&self.message.serialize())?;
+        for (pubkey, signature) in pubkeys.iter().zip(signatures) {
+            self.add_signature(pubkey, signature)?;
+        }
+        Ok(())
+    }
+
+    /// Add the signatures collected by `other` for the same message.
+    pub fn merge(&mut self, other: &Self) -> Result<(), PartialSignError> {
+        if self.message != other.message {
+            return Err(PartialSignError::MessageMismatch);
+        }
+        if self.signatures.len() != other.signatures.len() {
+            return Err(PartialSignError::InvalidMessage);
+        }
+        let signers = self.required_signers().to_vec();
+        for (pubkey, signature) in signers.iter().zip(&other.signatures) {
+            if let Some(signature) = signature {
+                // `other` may have been deserialized, so its signatures are
+                // verified again rather than trusted
+                self.add_signature(pubkey, *signature)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Produce the signed transaction once every required signer has signed.
+    pub fn finalize(self) -> Result<VersionedTransaction, PartialSignError> {
+        let missing_signers = self.missing_signers();
+        if !missing_signers.is_empty() {
+            return Err(PartialSignError::MissingSignatures(missing_signers));
+        }
+        let transaction = VersionedTransaction {
+            signatures: self.signatures.into_iter().flatten().collect(),
+            message: self.message,
+        };
+        // deserialized instances skip `add_signature`, so check once more
+        if let Some((pubkey, _)) = transaction
+            .message
+            .static_account_keys()
+            .iter()
+            .zip(transaction.verify_with_results())
+            .find(|(_, verified)| !verified)
+        {
+            return Err(PartialSignError::InvalidSignature(*pubkey));
+        }
+        Ok(transaction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            hash::Hash,
+            instruction::{AccountMeta, Instruction},
+            message::{v0, Message, MessageHeader},
+            signature::{Keypair, Signer},
+        },
+    };
+
+    fn multisig_message(signers: &[&Keypair]) -> VersionedMessage {
+        let instruction = Instruction::new_with_bytes(
+            Pubkey::new_unique(),
+            &[],
+            signers
+                .iter()
+                .skip(1)
+                .map(|signer| AccountMeta::new_readonly(signer.pubkey(), true))
+                .collect(),
+        );
+        VersionedMessage::V0(
+            v0::Message::try_compile(
+                &signers[0].pubkey(),
+                &[instruction],
+                &[],
+                Hash::new_unique(),
+            )
+            .unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_partially_signed_transaction_merge_and_finalize() {
+        let payer = Keypair::new();
+        let alice = Keypair::new();
+        let bob = Keypair::new();
+        let message = multisig_message(&[&payer, &alice, &bob]);
+
+        let mut payer_copy = PartiallySignedTransaction::new(message.clone()).unwrap();
+        let mut alice_copy = PartiallySignedTransaction::new(message.clone()).unwrap();
+        let mut bob_copy = PartiallySignedTransaction::new(message).unwrap();
+        payer_copy.sign(&[&payer]).unwrap();
+        alice_copy.sign(&[&alice]).unwrap();
+        bob_copy.sign(&[&bob]).unwrap();
+        assert_eq!(
+            payer_copy.missing_signers(),
+            vec![alice.pubkey(), bob.pubkey()]
+        );
+        assert_eq!(
+            payer_copy.clone().finalize(),
+            Err(PartialSignError::MissingSignatures(vec![
+                alice.pubkey(),
+                bob.pubkey()
+            ]))
+        );
+
+        // signatures survive a round trip to another machine
+        let alice_copy: PartiallySignedTransaction =
+            bincode::deserialize(&bincode::serialize(&alice_copy).unwrap()).unwrap();
+        payer_copy.merge(&alice_copy).unwrap();
+        payer_copy.merge(&bob_copy).unwrap();
+        // merging is idempotent
+        payer_copy.merge(&bob_copy).unwrap();
+        assert!(payer_copy.is_fully_signed());
+
+        let transaction = payer_copy.finalize().unwrap();
+        assert!(transaction.verify_with_results().iter().all(|ok| *ok));
+        assert_eq!(transaction.sanitize(), Ok(()));
+    }
+
+    #[test]
+    fn test_partially_signed_transaction_rejects_bad_signatures() {
+        let payer = Keypair::new();
+        let alice = Keypair::new();
+        let mallory = Keypair::new();
+        let message = multisig_message(&[&payer, &alice]);
+        let mut partially_signed = PartiallySignedTransaction::new(message.clone()).unwrap();
+
+        assert_eq!(
+            partially_signed.sign(&[&mallory]),
+            Err(PartialSignError::NotARequiredSigner(mallory.pubkey()))
+        );
+        let wrong_signature = alice.sign_message(b"some other message");
+        assert_eq!(
+            partially_signed.add_signature(&alice.pubkey(), wrong_signature),
+            Err(PartialSignError::InvalidSignature(alice.pubkey()))
+        );
+        assert_eq!(partially_signed.missing_signers().len(), 2);
+
+        let other_message = multisig_message(&[&payer, &alice]);
+        let other = PartiallySignedTransaction::new(other_message).unwrap();
+        assert_eq!(
+            partially_signed.merge(&other),
+            Err(PartialSignError::MessageMismatch)
+        );
+
+        // tampered signatures in a deserialized copy are caught on merge
+        let mut tampered = PartiallySignedTransaction::new(message).unwrap();
+        tampered.signatures[1] = Some(wrong_signature);
+        assert_eq!(
+            partially_signed.merge(&tampered),
+            Err(PartialSignError::InvalidSignature(alice.pubkey()))
+        );
+        // and on finalize
+        tampered.signatures[0] = Some(payer.sign_message(&tampered.message.serialize()));
+        assert_eq!(
+            tampered.finalize(),
+            Err(PartialSignError::InvalidSignature(alice.pubkey()))
+        );
+    }
+
+    #[test]
+    fn test_partially_signed_transaction_from_transaction() {
+        let payer = Keypair::new();
+        let alice = Keypair::new();
+        let mut transaction = crate::transaction::Transaction::new_unsigned(Message::new(
+            &[Instruction::new_with_bytes(
+                Pubkey::new_unique(),
+                &[],
+                vec![AccountMeta::new_readonly(alice.pubkey(), true)],
+            )],
+            Some(&payer.pubkey()),
+        ));
+        transaction.partial_sign(&[&payer], Hash::new_unique());
+
+        let mut partially_signed =
+            PartiallySignedTransaction::from_transaction(transaction.clone().into()).unwrap();
+        assert_eq!(partially_signed.missing_signers(), vec![alice.pubkey()]);
+        partially_signed.sign(&[&alice]).unwrap();
+
+        transaction.partial_sign(&[&alice], transaction.message.recent_blockhash);
+        assert_eq!(partially_signed.finalize(), Ok(transaction.into()));
+    }
+
+    #[test]
+    fn test_partially_signed_transaction_rejects_malformed_input() {
+        let payer = Keypair::new();
+        let alice = Keypair::new();
+        let message = multisig_message(&[&payer, &alice]);
+
+        // too many signature entries for the message
+        let mut partially_signed = PartiallySignedTransaction::new(message.clone()).unwrap();
+        partially_signed.signatures.push(None);
+        let serialized = bincode::serialize(&partially_signed).unwrap();
+        assert!(bincode::deserialize::<PartiallySignedTransaction>(&serialized).is_err());
+
+        // too few
+        partially_signed.signatures.truncate(1);
+        let serialized = bincode::serialize(&partially_signed).unwrap();
+        assert!(bincode::deserialize::<PartiallySignedTransaction>(&serialized).is_err());
+
+        // a message requiring more signers than it has account keys
+        let invalid_message = VersionedMessage::Legacy(Message {
+            header: MessageHeader {
+                num_required_signatures: 3,
+                num_readonly_signed_accounts: 0,
+                num_readonly_unsigned_accounts: 0,
+            },
+            account_keys: vec![payer.pubkey(), alice.pubkey()],
+            ..Message::default()
+        });
+        assert_eq!(
+            PartiallySignedTransaction::new(invalid_message.clone()),
+            Err(PartialSignError::InvalidMessage)
+        );
+        let malformed = PartiallySignedTransaction {
+            message: invalid_message,
+            signatures: vec![None; 3],
+        };
+        assert!(malformed.required_signers().is_empty());
+        let serialized = bincode::serialize(&malformed).unwrap();
+        assert!(bincode::deserialize::<PartiallySignedTransaction>(&serialized).is_err());
+
+        // a message which does not sanitize
+        let unsanitary_message = VersionedMessage::Legacy(Message {
+            header: MessageHeader {
+                num_required_signatures: 1,
+                num_readonly_signed_accounts: 1,
+                num_readonly_unsigned_accounts: 0,
+            },
+            account_keys: vec![payer.pubkey()],
+            ..Message::default()
+        });
+        assert!(matches!(
+            PartiallySignedTransaction::new(unsanitary_message),
+            Err(PartialSignError::SanitizeError(_))
+        ));
+
+        // well-formed input still round trips
+        let partially_signed = PartiallySignedTransaction::new(message).unwrap();
+        let serialized = bincode::serialize(&partially_signed).unwrap();
+        assert_eq!(
+            bincode::deserialize::<PartiallySignedTransaction>(&serialized).unwrap(),
+            partially_signed
+        );
+    }
+}
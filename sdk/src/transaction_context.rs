@@ -357,6 +357,23 @@ impl TransactionContext {
         Ok(())
     }
 
+    /// Records the compute units consumed by the current InstructionContext
+    #[cfg(not(target_os = "solana"))]
+    pub fn set_current_instruction_compute_units_consumed(
+        &mut self,
+        compute_units_consumed: u64,
+    ) -> Result<(), InstructionError> {
+        let index_in_trace = *self
+            .instruction_stack
+            .last()
+            .ok_or(InstructionError::CallDepth)?;
+        self.instruction_trace
+            .get_mut(index_in_trace)
+            .ok_or(InstructionError::CallDepth)?
+            .compute_units_consumed = compute_units_consumed;
+        Ok(())
+    }
+
     /// Pops the current InstructionContext
     #[cfg(not(target_os = "solana"))]
     pub fn pop(&mut self) -> Result<(), InstructionError> {
@@ -457,6 +474,7 @@ pub struct InstructionContext {
     program_accounts: Vec<IndexOfAccount>,
     instruction_accounts: Vec<InstructionAccount>,
     instruction_data: Vec<u8>,
+    compute_units_consumed: u64,
 }
 
 impl InstructionContext {
@@ -480,6 +498,13 @@ impl InstructionContext {
         self.nesting_level.saturating_add(1)
     }
 
+    /// Compute units consumed by this Instruction, including the Instructions it invoked
+    ///
+    /// Only set once the Instruction has finished executing.
+    pub fn get_compute_units_consumed(&self) -> u64 {
+        self.compute_units_consumed
+    }
+
     /// Number of program accounts
     pub fn get_number_of_program_accounts(&self) -> IndexOfAccount {
         self.program_accounts.len() as IndexOfAccount
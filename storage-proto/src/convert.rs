@@ -16,8 +16,9 @@ use {
     },
     solana_transaction_status::{
         ConfirmedBlock, EntrySummary, InnerInstruction, InnerInstructions, Reward, RewardType,
-        TransactionByAddrInfo, TransactionStatusMeta, TransactionTokenBalance,
-        TransactionWithStatusMeta, VersionedConfirmedBlock, VersionedTransactionWithStatusMeta,
+        StakeDelegation, TokenBalanceExtension, TransactionByAddrInfo, TransactionStakeChange,
+        TransactionStatusMeta, TransactionTokenBalance, TransactionWithStatusMeta,
+        VersionedConfirmedBlock, VersionedTransactionWithStatusMeta,
     },
     std::{
         convert::{TryFrom, TryInto},
@@ -374,6 +375,7 @@ impl From<TransactionStatusMeta> for generated::TransactionStatusMeta {
             loaded_addresses,
             return_data,
             compute_units_consumed,
+            stake_changes,
         } = value;
         let err = match status {
             Ok(()) => None,
@@ -416,6 +418,12 @@ impl From<TransactionStatusMeta> for generated::TransactionStatusMeta {
             .collect();
         let return_data_none = return_data.is_none();
         let return_data = return_data.map(|return_data| return_data.into());
+        let stake_changes_none = stake_changes.is_none();
+        let stake_changes = stake_changes
+            .unwrap_or_default()
+            .into_iter()
+            .map(|stake_change| stake_change.into())
+            .collect();
 
         Self {
             err,
@@ -434,6 +442,8 @@ impl From<TransactionStatusMeta> for generated::TransactionStatusMeta {
             return_data,
             return_data_none,
             compute_units_consumed,
+            stake_changes,
+            stake_changes_none,
         }
     }
 }
@@ -466,6 +476,8 @@ impl TryFrom<generated::TransactionStatusMeta> for TransactionStatusMeta {
             return_data,
             return_data_none,
             compute_units_consumed,
+            stake_changes,
+            stake_changes_none,
         } = value;
         let status = match &err {
             None => Ok(()),
@@ -522,6 +534,16 @@ impl TryFrom<generated::TransactionStatusMeta> for TransactionStatusMeta {
         } else {
             return_data.map(|return_data| return_data.into())
         };
+        let stake_changes = if stake_changes_none {
+            None
+        } else {
+            Some(
+                stake_changes
+                    .into_iter()
+                    .map(|stake_change| stake_change.into())
+                    .collect(),
+            )
+        };
         Ok(Self {
             status,
             fee,
@@ -535,6 +557,7 @@ impl TryFrom<generated::TransactionStatusMeta> for TransactionStatusMeta {
             loaded_addresses,
             return_data,
             compute_units_consumed,
+            stake_changes,
         })
     }
 }
@@ -570,6 +593,7 @@ impl From<TransactionTokenBalance> for generated::TokenBalance {
             }),
             owner: value.owner,
             program_id: value.program_id,
+            extensions: value.extensions.into_iter().map(|e| e.into()).collect(),
         }
     }
 }
@@ -599,6 +623,109 @@ impl From<generated::TokenBalance> for TransactionTokenBalance {
             },
             owner: value.owner,
             program_id: value.program_id,
+            extensions: value
+                .extensions
+                .into_iter()
+                .filter_map(|e| e.try_into().ok())
+                .collect(),
+        }
+    }
+}
+
+impl From<TokenBalanceExtension> for generated::TokenBalanceExtension {
+    fn from(value: TokenBalanceExtension) -> Self {
+        use generated::token_balance_extension::Extension;
+        let extension = match value {
+            TokenBalanceExtension::TransferFeeAmount { withheld_amount } => {
+                Extension::TransferFeeAmount(generated::TransferFeeAmountExtension {
+                    withheld_amount,
+                })
+            }
+            TokenBalanceExtension::TransferFeeConfig {
+                transfer_fee_basis_points,
+                maximum_fee,
+            } => Extension::TransferFeeConfig(generated::TransferFeeConfigExtension {
+                transfer_fee_basis_points: transfer_fee_basis_points as u32,
+                maximum_fee,
+            }),
+            TokenBalanceExtension::InterestBearingConfig { rate } => {
+                Extension::InterestBearingConfig(generated::InterestBearingConfigExtension {
+                    rate: rate as i32,
+                })
+            }
+        };
+        Self {
+            extension: Some(extension),
+        }
+    }
+}
+
+impl TryFrom<generated::TokenBalanceExtension> for TokenBalanceExtension {
+    type Error = &'static str;
+
+    fn try_from(value: generated::TokenBalanceExtension) -> std::result::Result<Self, Self::Error> {
+        use generated::token_balance_extension::Extension;
+        Ok(
+            match value.extension.ok_or("Token balance extension not found")? {
+                Extension::TransferFeeAmount(extension) => Self::TransferFeeAmount {
+                    withheld_amount: extension.withheld_amount,
+                },
+                Extension::TransferFeeConfig(extension) => Self::TransferFeeConfig {
+                    transfer_fee_basis_points: extension
+                        .transfer_fee_basis_points
+                        .try_into()
+                        .map_err(|_| "Invalid transfer fee basis points")?,
+                    maximum_fee: extension.maximum_fee,
+                },
+                Extension::InterestBearingConfig(extension) => Self::InterestBearingConfig {
+                    rate: extension
+                        .rate
+                        .try_into()
+                        .map_err(|_| "Invalid interest rate")?,
+                },
+            },
+        )
+    }
+}
+
+impl From<TransactionStakeChange> for generated::StakeChange {
+    fn from(value: TransactionStakeChange) -> Self {
+        Self {
+            account_index: value.account_index as u32,
+            pre_delegation: value.pre_delegation.map(|delegation| delegation.into()),
+            post_delegation: value.post_delegation.map(|delegation| delegation.into()),
+        }
+    }
+}
+
+impl From<generated::StakeChange> for TransactionStakeChange {
+    fn from(value: generated::StakeChange) -> Self {
+        Self {
+            account_index: value.account_index as u8,
+            pre_delegation: value.pre_delegation.map(|delegation| delegation.into()),
+            post_delegation: value.post_delegation.map(|delegation| delegation.into()),
+        }
+    }
+}
+
+impl From<StakeDelegation> for generated::StakeDelegation {
+    fn from(value: StakeDelegation) -> Self {
+        Self {
+            voter: value.voter,
+            stake: value.stake,
+            activation_epoch: value.activation_epoch,
+            deactivation_epoch: value.deactivation_epoch,
+        }
+    }
+}
+
+impl From<generated::StakeDelegation> for StakeDelegation {
+    fn from(value: generated::StakeDelegation) -> Self {
+        Self {
+            voter: value.voter,
+            stake: value.stake,
+            activation_epoch: value.activation_epoch,
+            deactivation_epoch: value.deactivation_epoch,
         }
     }
 }
@@ -818,6 +945,7 @@ impl TryFrom<tx_by_addr::TransactionError> for TransactionError {
             33 => TransactionError::InvalidLoadedAccountsDataSizeLimit,
             34 => TransactionError::ResanitizationNeeded,
             36 => TransactionError::UnbalancedTransaction,
+            37 => TransactionError::MaxSlotExceeded,
             _ => return Err("Invalid TransactionError"),
         })
     }
@@ -936,6 +1064,9 @@ impl From<TransactionError> for tx_by_addr::TransactionError {
                 TransactionError::UnbalancedTransaction => {
                     tx_by_addr::TransactionErrorType::UnbalancedTransaction
                 }
+                TransactionError::MaxSlotExceeded => {
+                    tx_by_addr::TransactionErrorType::MaxSlotExceeded
+                }
             } as i32,
             instruction_error: match transaction_error {
                 TransactionError::InstructionError(index, ref instruction_error) => {
@@ -1250,6 +1381,43 @@ mod test {
         assert_eq!(reward, gen_reward.into());
     }
 
+    #[test]
+    fn test_token_balance_encode() {
+        let mut token_balance = TransactionTokenBalance {
+            account_index: 3,
+            mint: Pubkey::new_unique().to_string(),
+            ui_token_amount: UiTokenAmount {
+                ui_amount: Some(1.5),
+                decimals: 2,
+                amount: "150".to_string(),
+                ui_amount_string: "1.5".to_string(),
+            },
+            owner: Pubkey::new_unique().to_string(),
+            program_id: Pubkey::new_unique().to_string(),
+            extensions: vec![],
+        };
+        let gen_token_balance: generated::TokenBalance = token_balance.clone().into();
+        assert_eq!(token_balance, gen_token_balance.into());
+
+        token_balance.extensions = vec![
+            TokenBalanceExtension::TransferFeeAmount { withheld_amount: 7 },
+            TokenBalanceExtension::TransferFeeConfig {
+                transfer_fee_basis_points: 50,
+                maximum_fee: 1_000,
+            },
+            TokenBalanceExtension::InterestBearingConfig { rate: -300 },
+        ];
+        let gen_token_balance: generated::TokenBalance = token_balance.clone().into();
+        assert_eq!(token_balance, gen_token_balance.into());
+
+        // extensions unknown to this version are skipped
+        let mut gen_token_balance: generated::TokenBalance = token_balance.clone().into();
+        gen_token_balance
+            .extensions
+            .push(generated::TokenBalanceExtension { extension: None });
+        assert_eq!(token_balance, gen_token_balance.into());
+    }
+
     #[test]
     fn test_transaction_by_addr_encode() {
         let info = TransactionByAddrInfo {
@@ -1851,6 +2019,14 @@ mod test {
             transaction_error,
             tx_by_addr_transaction_error.try_into().unwrap()
         );
+
+        let transaction_error = TransactionError::MaxSlotExceeded;
+        let tx_by_addr_transaction_error: tx_by_addr::TransactionError =
+            transaction_error.clone().into();
+        assert_eq!(
+            transaction_error,
+            tx_by_addr_transaction_error.try_into().unwrap()
+        );
     }
 
     #[test]
@@ -9,7 +9,8 @@ use {
         transaction_context::TransactionReturnData,
     },
     solana_transaction_status::{
-        InnerInstructions, Reward, RewardType, TransactionStatusMeta, TransactionTokenBalance,
+        InnerInstructions, Reward, RewardType, TokenBalanceExtension, TransactionStakeChange,
+        TransactionStatusMeta, TransactionTokenBalance,
     },
     std::str::FromStr,
 };
@@ -118,6 +119,8 @@ pub struct StoredTransactionTokenBalance {
     pub owner: String,
     #[serde(deserialize_with = "default_on_eof")]
     pub program_id: String,
+    #[serde(deserialize_with = "default_on_eof")]
+    pub extensions: Vec<TokenBalanceExtension>,
 }
 
 impl From<StoredTransactionTokenBalance> for TransactionTokenBalance {
@@ -128,6 +131,7 @@ impl From<StoredTransactionTokenBalance> for TransactionTokenBalance {
             ui_token_amount,
             owner,
             program_id,
+            extensions,
         } = value;
         Self {
             account_index,
@@ -135,6 +139,7 @@ impl From<StoredTransactionTokenBalance> for TransactionTokenBalance {
             ui_token_amount: ui_token_amount.into(),
             owner,
             program_id,
+            extensions,
         }
     }
 }
@@ -147,6 +152,7 @@ impl From<TransactionTokenBalance> for StoredTransactionTokenBalance {
             ui_token_amount,
             owner,
             program_id,
+            extensions,
         } = value;
         Self {
             account_index,
@@ -154,6 +160,7 @@ impl From<TransactionTokenBalance> for StoredTransactionTokenBalance {
             ui_token_amount: ui_token_amount.into(),
             owner,
             program_id,
+            extensions,
         }
     }
 }
@@ -178,6 +185,8 @@ pub struct StoredTransactionStatusMeta {
     pub return_data: Option<TransactionReturnData>,
     #[serde(deserialize_with = "default_on_eof")]
     pub compute_units_consumed: Option<u64>,
+    #[serde(deserialize_with = "default_on_eof")]
+    pub stake_changes: Option<Vec<TransactionStakeChange>>,
 }
 
 impl From<StoredTransactionStatusMeta> for TransactionStatusMeta {
@@ -194,6 +203,7 @@ impl From<StoredTransactionStatusMeta> for TransactionStatusMeta {
             rewards,
             return_data,
             compute_units_consumed,
+            stake_changes,
         } = value;
         Self {
             status,
@@ -211,6 +221,7 @@ impl From<StoredTransactionStatusMeta> for TransactionStatusMeta {
             loaded_addresses: LoadedAddresses::default(),
             return_data,
             compute_units_consumed,
+            stake_changes,
         }
     }
 }
@@ -231,6 +242,7 @@ impl TryFrom<TransactionStatusMeta> for StoredTransactionStatusMeta {
             loaded_addresses,
             return_data,
             compute_units_consumed,
+            stake_changes,
         } = value;
 
         if !loaded_addresses.is_empty() {
@@ -256,6 +268,7 @@ impl TryFrom<TransactionStatusMeta> for StoredTransactionStatusMeta {
                 .map(|rewards| rewards.into_iter().map(|reward| reward.into()).collect()),
             return_data,
             compute_units_consumed,
+            stake_changes,
         })
     }
 }
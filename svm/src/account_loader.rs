@@ -8,7 +8,7 @@ use {
     log::warn,
     solana_accounts_db::accounts::{LoadedTransaction, TransactionLoadResult, TransactionRent},
     solana_program_runtime::{
-        compute_budget_processor::process_compute_budget_instructions,
+        compute_budget_processor::{get_fee_sponsor_index, process_compute_budget_instructions},
         loaded_programs::LoadedProgramsForTxBatch,
     },
     solana_sdk::{
@@ -87,6 +87,7 @@ pub fn load_accounts<CB: TransactionProcessingCallback>(
                     match NonceFull::from_partial(
                         nonce,
                         tx.message(),
+                        loaded_transaction.fee_payer_index,
                         &loaded_transaction.accounts,
                         &loaded_transaction.rent_debits,
                     ) {
@@ -122,9 +123,13 @@ fn load_transaction_accounts<CB: TransactionProcessingCallback>(
 
     // There is no way to predict what program will execute without an error
     // If a fee can pay for execution then the program will be scheduled
-    let mut validated_fee_payer = false;
+    let mut fee_payer_index = None;
     let mut tx_rent: TransactionRent = 0;
     let message = tx.message();
+    let fee_sponsor_index = get_fee_sponsor_index(
+        message,
+        feature_set.is_active(&feature_set::enable_fee_sponsorship::id()),
+    )?;
     let account_keys = message.account_keys();
     let mut accounts_found = Vec::with_capacity(account_keys.len());
     let mut account_deps = Vec::with_capacity(account_keys.len());
@@ -212,8 +217,12 @@ fn load_transaction_accounts<CB: TransactionProcessingCallback>(
                     error_counters,
                 )?;
 
-                if !validated_fee_payer && message.is_non_loader_key(i) {
-                    if i != 0 {
+                let is_fee_payer = match fee_sponsor_index {
+                    Some(fee_sponsor_index) => i == fee_sponsor_index,
+                    None => fee_payer_index.is_none() && message.is_non_loader_key(i),
+                };
+                if is_fee_payer {
+                    if i != 0 && fee_sponsor_index.is_none() {
                         warn!("Payer index should be 0! {:?}", tx);
                     }
 
@@ -226,7 +235,7 @@ fn load_transaction_accounts<CB: TransactionProcessingCallback>(
                         fee,
                     )?;
 
-                    validated_fee_payer = true;
+                    fee_payer_index = Some(i);
                 }
 
                 callbacks.check_account_access(tx, i, &account, error_counters)?;
@@ -242,10 +251,10 @@ fn load_transaction_accounts<CB: TransactionProcessingCallback>(
         })
         .collect::<Result<Vec<_>>>()?;
 
-    if !validated_fee_payer {
+    let Some(fee_payer_index) = fee_payer_index else {
         error_counters.account_not_found += 1;
         return Err(TransactionError::AccountNotFound);
-    }
+    };
 
     // Appends the account_deps at the end of the accounts,
     // this way they can be accessed in a uniform way.
@@ -323,6 +332,7 @@ fn load_transaction_accounts<CB: TransactionProcessingCallback>(
         program_indices,
         rent: tx_rent,
         rent_debits,
+        fee_payer_index,
     })
 }
 
@@ -458,11 +468,12 @@ mod tests {
         solana_sdk::{
             account::{AccountSharedData, WritableAccount},
             bpf_loader_upgradeable,
-            compute_budget::ComputeBudgetInstruction,
+            compute_budget::{self, ComputeBudgetInstruction},
             epoch_schedule::EpochSchedule,
             feature_set::FeatureSet,
+            fee_sponsorship::new_sponsored_message,
             hash::Hash,
-            instruction::CompiledInstruction,
+            instruction::{AccountMeta, CompiledInstruction, Instruction, InstructionError},
             message::{Message, SanitizedMessage},
             nonce,
             rent::Rent,
@@ -809,6 +820,77 @@ mod tests {
         assert_eq!(*load_res, Err(TransactionError::InsufficientFundsForFee));
     }
 
+    #[test]
+    fn test_load_accounts_fee_sponsor() {
+        let lamports_per_signature = 5000;
+        let mut error_counters = TransactionErrorMetrics::default();
+        let min_balance = RentCollector::default().rent.minimum_balance(0);
+        let user = Keypair::new();
+        let sponsor = Keypair::new();
+
+        let mut compute_budget_account = AccountSharedData::new(40, 1, &native_loader::id());
+        compute_budget_account.set_executable(true);
+        let accounts = vec![
+            (
+                user.pubkey(),
+                AccountSharedData::new(min_balance, 0, &system_program::id()),
+            ),
+            (
+                sponsor.pubkey(),
+                AccountSharedData::new(
+                    min_balance + 2 * lamports_per_signature,
+                    0,
+                    &system_program::id(),
+                ),
+            ),
+            (compute_budget::id(), compute_budget_account),
+        ];
+        let instructions = [Instruction::new_with_bincode(
+            native_loader::id(),
+            &(),
+            vec![AccountMeta::new(user.pubkey(), true)],
+        )];
+        let message = new_sponsored_message(&instructions, &user.pubkey(), &sponsor.pubkey());
+        let tx = Transaction::new(&[&user, &sponsor], message, Hash::default());
+
+        // The sponsor pays the fees of both signatures
+        let loaded_accounts = load_accounts_with_fee(
+            tx.clone(),
+            &accounts,
+            lamports_per_signature,
+            &mut error_counters,
+            None,
+        );
+        assert_eq!(loaded_accounts.len(), 1);
+        let (load_res, _nonce) = &loaded_accounts[0];
+        let loaded_transaction = load_res.as_ref().unwrap();
+        assert_eq!(loaded_transaction.fee_payer_index, 1);
+        assert_eq!(loaded_transaction.accounts[0].0, user.pubkey());
+        assert_eq!(loaded_transaction.accounts[0].1.lamports(), min_balance);
+        assert_eq!(loaded_transaction.accounts[1].0, sponsor.pubkey());
+        assert_eq!(loaded_transaction.accounts[1].1.lamports(), min_balance);
+
+        // Until fee sponsorship is enabled, the instruction is invalid
+        let loaded_accounts = load_accounts_with_fee(
+            tx,
+            &accounts,
+            lamports_per_signature,
+            &mut error_counters,
+            Some(&[feature_set::enable_fee_sponsorship::id()]),
+        );
+        assert_eq!(loaded_accounts.len(), 1);
+        assert_eq!(
+            loaded_accounts[0],
+            (
+                Err(TransactionError::InstructionError(
+                    1,
+                    InstructionError::InvalidInstructionData
+                )),
+                None,
+            )
+        );
+    }
+
     #[test]
     fn test_load_accounts_no_loaders() {
         let mut accounts: Vec<TransactionAccount> = Vec::new();
@@ -1,4 +1,6 @@
-use solana_program_runtime::compute_budget::ComputeBudget;
+use solana_program_runtime::{
+    compute_budget::ComputeBudget, loaded_programs::LoadedProgramsConfig,
+};
 
 /// Encapsulates flags that can be used to tweak the runtime behavior.
 #[derive(AbiExample, Debug, Default, Clone)]
@@ -6,4 +8,5 @@ pub struct RuntimeConfig {
     pub compute_budget: Option<ComputeBudget>,
     pub log_messages_bytes_limit: Option<usize>,
     pub transaction_account_lock_limit: Option<usize>,
+    pub loaded_programs_config: LoadedProgramsConfig,
 }
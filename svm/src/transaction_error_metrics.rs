@@ -25,6 +25,7 @@ pub struct TransactionErrorMetrics {
     pub would_exceed_account_data_block_limit: usize,
     pub max_loaded_accounts_data_size_exceeded: usize,
     pub program_execution_temporarily_restricted: usize,
+    pub max_slot_exceeded: usize,
 }
 
 impl TransactionErrorMetrics {
@@ -86,6 +87,7 @@ impl TransactionErrorMetrics {
             self.program_execution_temporarily_restricted,
             other.program_execution_temporarily_restricted
         );
+        saturating_add_assign!(self.max_slot_exceeded, other.max_slot_exceeded);
     }
 
     pub fn report(&self, id: u32, slot: Slot) {
@@ -172,6 +174,7 @@ impl TransactionErrorMetrics {
                 self.program_execution_temporarily_restricted as i64,
                 i64
             ),
+            ("max_slot_exceeded", self.max_slot_exceeded as i64, i64),
         );
     }
 }
@@ -36,7 +36,10 @@ use {
         feature_set::FeatureSet,
         fee::FeeStructure,
         hash::Hash,
-        inner_instruction::{InnerInstruction, InnerInstructionsList},
+        inner_instruction::{
+            InnerInstruction, InnerInstructionsList, InstructionComputeUnits,
+            InstructionComputeUnitsList,
+        },
         instruction::{CompiledInstruction, InstructionError, TRANSACTION_LEVEL_STACK_HEIGHT},
         loader_v4::{self, LoaderV4State, LoaderV4Status},
         message::SanitizedMessage,
@@ -290,13 +293,10 @@ impl<FG: ForkGraph> TransactionBatchProcessor<FG> {
         execution_time.stop();
 
         const SHRINK_LOADED_PROGRAMS_TO_PERCENTAGE: u8 = 90;
-        self.loaded_programs_cache
-            .write()
-            .unwrap()
-            .evict_using_2s_random_selection(
-                Percentage::from(SHRINK_LOADED_PROGRAMS_TO_PERCENTAGE),
-                self.slot,
-            );
+        self.loaded_programs_cache.write().unwrap().evict(
+            Percentage::from(SHRINK_LOADED_PROGRAMS_TO_PERCENTAGE),
+            self.slot,
+        );
 
         debug!(
             "load: {}us execute: {}us txs_len={}",
@@ -565,12 +565,17 @@ impl<FG: ForkGraph> TransactionBatchProcessor<FG> {
                     .ok()
             });
 
-        let inner_instructions = if enable_cpi_recording {
-            Some(Self::inner_instructions_list_from_instruction_trace(
-                &transaction_context,
-            ))
+        let (inner_instructions, instruction_compute_units) = if enable_cpi_recording {
+            (
+                Some(Self::inner_instructions_list_from_instruction_trace(
+                    &transaction_context,
+                )),
+                Some(Self::instruction_compute_units_from_instruction_trace(
+                    &transaction_context,
+                )),
+            )
         } else {
-            None
+            (None, None)
         };
 
         let ExecutionRecord {
@@ -607,6 +612,7 @@ impl<FG: ForkGraph> TransactionBatchProcessor<FG> {
                 status,
                 log_messages,
                 inner_instructions,
+                instruction_compute_units,
                 durable_nonce_fee,
                 return_data,
                 executed_units,
@@ -895,6 +901,35 @@ impl<FG: ForkGraph> TransactionBatchProcessor<FG> {
         }
         outer_instructions
     }
+
+    /// Extract the compute units consumed by each instruction from a TransactionContext
+    fn instruction_compute_units_from_instruction_trace(
+        transaction_context: &TransactionContext,
+    ) -> InstructionComputeUnitsList {
+        let mut outer_instructions = Vec::<InstructionComputeUnits>::new();
+        for index_in_trace in 0..transaction_context.get_instruction_trace_length() {
+            let Ok(instruction_context) =
+                transaction_context.get_instruction_context_at_index_in_trace(index_in_trace)
+            else {
+                debug_assert!(false);
+                continue;
+            };
+            let compute_units_consumed = instruction_context.get_compute_units_consumed();
+            if instruction_context.get_stack_height() == TRANSACTION_LEVEL_STACK_HEIGHT {
+                outer_instructions.push(InstructionComputeUnits {
+                    compute_units_consumed,
+                    inner_instructions: Vec::new(),
+                });
+            } else if let Some(outer_instruction) = outer_instructions.last_mut() {
+                outer_instruction
+                    .inner_instructions
+                    .push(compute_units_consumed);
+            } else {
+                debug_assert!(false);
+            }
+        }
+        outer_instructions
+    }
 }
 
 #[cfg(test)]
@@ -960,4 +995,54 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn test_instruction_compute_units_from_instruction_trace() {
+        let instruction_trace = [
+            (1, 100),
+            (2, 40),
+            (1, 0),
+            (1, 500),
+            (2, 300),
+            (3, 20),
+            (2, 60),
+        ];
+        let mut transaction_context =
+            TransactionContext::new(vec![], Rent::default(), 3, instruction_trace.len());
+        for (stack_height, compute_units_consumed) in instruction_trace {
+            while stack_height <= transaction_context.get_instruction_context_stack_height() {
+                transaction_context.pop().unwrap();
+            }
+            transaction_context
+                .get_next_instruction_context()
+                .unwrap()
+                .configure(&[], &[], &[]);
+            transaction_context.push().unwrap();
+            transaction_context
+                .set_current_instruction_compute_units_consumed(compute_units_consumed)
+                .unwrap();
+        }
+        let instruction_compute_units =
+            TransactionBatchProcessor::<TestForkGraph>::instruction_compute_units_from_instruction_trace(
+                &transaction_context,
+            );
+
+        assert_eq!(
+            instruction_compute_units,
+            vec![
+                InstructionComputeUnits {
+                    compute_units_consumed: 100,
+                    inner_instructions: vec![40],
+                },
+                InstructionComputeUnits {
+                    compute_units_consumed: 0,
+                    inner_instructions: vec![],
+                },
+                InstructionComputeUnits {
+                    compute_units_consumed: 500,
+                    inner_instructions: vec![300, 20, 60],
+                },
+            ]
+        );
+    }
 }
@@ -33,7 +33,9 @@ use {
     solana_rpc::{rpc::JsonRpcConfig, rpc_pubsub_service::PubSubConfig},
     solana_rpc_client::{nonblocking, rpc_client::RpcClient},
     solana_runtime::{
-        bank_forks::BankForks, genesis_utils::create_genesis_config_with_leader_ex,
+        bank::MissingAccountLoader,
+        bank_forks::BankForks,
+        genesis_utils::{activate_feature_at_slot, create_genesis_config_with_leader_ex},
         snapshot_config::SnapshotConfig,
     },
     solana_sdk::{
@@ -67,7 +69,7 @@ use {
         net::{IpAddr, Ipv4Addr, SocketAddr},
         path::{Path, PathBuf},
         str::FromStr,
-        sync::{Arc, RwLock},
+        sync::{Arc, Mutex, RwLock},
         time::Duration,
     },
     tokio::time::sleep,
@@ -95,6 +97,84 @@ pub struct UpgradeableProgramInfo {
     pub program_path: PathBuf,
 }
 
+/// How long fetching an account missing from the test ledger may block the
+/// transaction which loads it
+const MISSING_ACCOUNT_FETCH_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Fetches accounts missing from the test ledger from a remote cluster
+///
+/// Fetching blocks the loading transaction for up to
+/// `MISSING_ACCOUNT_FETCH_TIMEOUT`. Successfully fetched accounts, including
+/// accounts missing from the cluster too, are cached until they are stored in
+/// the ledger, so they are fetched at most once. Accounts which failed to be
+/// fetched are fetched again the next time they are loaded.
+struct RpcMissingAccountLoader {
+    rpc_client: RpcClient,
+    /// accounts fetched but not stored in the ledger yet, None if missing from the cluster
+    fetched: Mutex<HashMap<Pubkey, Option<AccountSharedData>>>,
+    /// accounts stored in the ledger, which are never fetched again
+    stored: Mutex<HashSet<Pubkey>>,
+}
+
+impl RpcMissingAccountLoader {
+    fn new(json_rpc_url: String) -> Self {
+        Self {
+            rpc_client: RpcClient::new_with_timeout_and_commitment(
+                json_rpc_url,
+                MISSING_ACCOUNT_FETCH_TIMEOUT,
+                CommitmentConfig::confirmed(),
+            ),
+            fetched: Mutex::default(),
+            stored: Mutex::default(),
+        }
+    }
+}
+
+impl std::fmt::Debug for RpcMissingAccountLoader {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("RpcMissingAccountLoader")
+            .field("url", &self.rpc_client.url())
+            .finish()
+    }
+}
+
+impl MissingAccountLoader for RpcMissingAccountLoader {
+    fn load_missing_account(&self, pubkey: &Pubkey) -> Option<AccountSharedData> {
+        // accounts closed after they were stored are not brought back
+        if self.stored.lock().unwrap().contains(pubkey) {
+            return None;
+        }
+        if let Some(account) = self.fetched.lock().unwrap().get(pubkey) {
+            return account.clone();
+        }
+        match self
+            .rpc_client
+            .get_account_with_commitment(pubkey, self.rpc_client.commitment())
+        {
+            Ok(response) => {
+                let account = response.value.map(AccountSharedData::from);
+                if account.is_some() {
+                    info!("Cloned {} from {}", pubkey, self.rpc_client.url());
+                }
+                self.fetched
+                    .lock()
+                    .unwrap()
+                    .insert(*pubkey, account.clone());
+                account
+            }
+            Err(err) => {
+                warn!("Failed to fetch {}: {}", pubkey, err);
+                None
+            }
+        }
+    }
+
+    fn missing_account_stored(&self, pubkey: &Pubkey) {
+        self.stored.lock().unwrap().insert(*pubkey);
+        self.fetched.lock().unwrap().remove(pubkey);
+    }
+}
+
 #[derive(Debug)]
 pub struct TestValidatorNodeConfig {
     gossip_addr: SocketAddr,
@@ -132,6 +212,7 @@ pub struct TestValidatorGenesis {
     programs: Vec<ProgramInfo>,
     upgradeable_programs: Vec<UpgradeableProgramInfo>,
     ticks_per_slot: Option<u64>,
+    hashes_per_tick: Option<u64>,
     epoch_schedule: Option<EpochSchedule>,
     node_config: TestValidatorNodeConfig,
     pub validator_exit: Arc<RwLock<Exit>>,
@@ -142,6 +223,8 @@ pub struct TestValidatorGenesis {
     pub max_genesis_archive_unpacked_size: Option<u64>,
     pub geyser_plugin_config_files: Option<Vec<PathBuf>>,
     deactivate_feature_set: HashSet<Pubkey>,
+    activate_feature_slots: HashMap<Pubkey, Slot>,
+    lazy_clone_url: Option<String>,
     compute_unit_limit: Option<u64>,
     pub log_messages_bytes_limit: Option<usize>,
     pub transaction_account_lock_limit: Option<usize>,
@@ -166,6 +249,7 @@ impl Default for TestValidatorGenesis {
             programs: Vec::<ProgramInfo>::default(),
             upgradeable_programs: Vec::<UpgradeableProgramInfo>::default(),
             ticks_per_slot: Option::<u64>::default(),
+            hashes_per_tick: Option::<u64>::default(),
             epoch_schedule: Option::<EpochSchedule>::default(),
             node_config: TestValidatorNodeConfig::default(),
             validator_exit: Arc::<RwLock<Exit>>::default(),
@@ -176,6 +260,8 @@ impl Default for TestValidatorGenesis {
             max_genesis_archive_unpacked_size: Option::<u64>::default(),
             geyser_plugin_config_files: Option::<Vec<PathBuf>>::default(),
             deactivate_feature_set: HashSet::<Pubkey>::default(),
+            activate_feature_slots: HashMap::<Pubkey, Slot>::default(),
+            lazy_clone_url: Option::<String>::default(),
             compute_unit_limit: Option::<u64>::default(),
             log_messages_bytes_limit: Option::<usize>::default(),
             transaction_account_lock_limit: Option::<usize>::default(),
@@ -195,6 +281,19 @@ impl TestValidatorGenesis {
         self.deactivate_feature_set.extend(deactivate_list);
         self
     }
+
+    /// Schedules features to activate at the given slots during
+    /// `initialize_ledger`, instead of at genesis, so that programs can be
+    /// tested across the activation. Features are only activated at epoch
+    /// boundaries, so each one is active from the first epoch starting at
+    /// or after its slot. If a member of the list is not a Feature it will
+    /// be ignored
+    pub fn activate_features_at_slot(&mut self, activate_list: &[(Pubkey, Slot)]) -> &mut Self {
+        self.activate_feature_slots
+            .extend(activate_list.iter().copied());
+        self
+    }
+
     pub fn ledger_path<P: Into<PathBuf>>(&mut self, ledger_path: P) -> &mut Self {
         self.ledger_path = Some(ledger_path.into());
         self
@@ -225,6 +324,14 @@ impl TestValidatorGenesis {
         self
     }
 
+    /// Hash a fixed number of times per tick instead of sleeping between
+    /// ticks, so that tick verification is exercised. The hashes per tick may
+    /// then be changed at runtime with the `setHashesPerTick` admin RPC.
+    pub fn hashes_per_tick(&mut self, hashes_per_tick: u64) -> &mut Self {
+        self.hashes_per_tick = Some(hashes_per_tick);
+        self
+    }
+
     pub fn epoch_schedule(&mut self, epoch_schedule: EpochSchedule) -> &mut Self {
         self.epoch_schedule = Some(epoch_schedule);
         self
@@ -366,6 +473,17 @@ impl TestValidatorGenesis {
         Ok(self)
     }
 
+    /// Clone accounts from the cluster at `json_rpc_url` the first time a
+    /// transaction loads them, instead of enumerating them up front. Accounts
+    /// which are missing from the ledger are fetched once and stored in it;
+    /// RPC requests for accounts which no transaction has loaded yet are not
+    /// forwarded to the cluster. Each fetch blocks the transaction which loads
+    /// the account for up to 5 seconds
+    pub fn lazy_clone_accounts_from<T: Into<String>>(&mut self, json_rpc_url: T) -> &mut Self {
+        self.lazy_clone_url = Some(json_rpc_url.into());
+        self
+    }
+
     pub fn add_accounts_from_json_files(
         &mut self,
         accounts: &[AccountInfo],
@@ -791,6 +909,9 @@ impl TestValidator {
         if let Some(ticks_per_slot) = config.ticks_per_slot {
             genesis_config.ticks_per_slot = ticks_per_slot;
         }
+        if let Some(hashes_per_tick) = config.hashes_per_tick {
+            genesis_config.poh_config.hashes_per_tick = Some(hashes_per_tick);
+        }
 
         // Remove features tagged to deactivate
         for deactivate_feature_pk in &config.deactivate_feature_set {
@@ -810,6 +931,26 @@ impl TestValidator {
             }
         }
 
+        // Schedule features tagged to activate at a later slot
+        for (activate_feature_pk, activation_slot) in &config.activate_feature_slots {
+            if FEATURE_NAMES.contains_key(activate_feature_pk) {
+                activate_feature_at_slot(
+                    &mut genesis_config,
+                    *activate_feature_pk,
+                    *activation_slot,
+                );
+                info!(
+                    "Feature for {:?} scheduled to activate at slot {}",
+                    activate_feature_pk, activation_slot
+                );
+            } else {
+                warn!(
+                    "Feature {:?} set for activation is not a known Feature public key",
+                    activate_feature_pk
+                );
+            }
+        }
+
         let ledger_path = match &config.ledger_path {
             None => create_new_tmp_ledger!(&genesis_config).0,
             Some(ledger_path) => {
@@ -927,6 +1068,7 @@ impl TestValidator {
                 }),
             log_messages_bytes_limit: config.log_messages_bytes_limit,
             transaction_account_lock_limit: config.transaction_account_lock_limit,
+            ..RuntimeConfig::default()
         };
 
         let mut validator_config = ValidatorConfig {
@@ -991,6 +1133,16 @@ impl TestValidator {
             config.admin_rpc_service_post_init.clone(),
         )?);
 
+        if let Some(json_rpc_url) = &config.lazy_clone_url {
+            let loader: Arc<dyn MissingAccountLoader + Send + Sync> =
+                Arc::new(RpcMissingAccountLoader::new(json_rpc_url.clone()));
+            // banks created from these afterwards inherit the loader
+            let bank_forks = validator.as_ref().unwrap().bank_forks.read().unwrap();
+            for bank in bank_forks.banks().values() {
+                bank.set_missing_account_loader(Some(loader.clone()));
+            }
+        }
+
         // Needed to avoid panics in `solana-responder-gossip` in tests that create a number of
         // test validators concurrently...
         discover_cluster(&gossip, 1, socket_addr_space)
@@ -1147,7 +1299,12 @@ impl Drop for TestValidator {
 
 #[cfg(test)]
 mod test {
-    use super::*;
+    use {
+        super::*,
+        solana_sdk::{
+            feature, feature_set, system_instruction, system_program, transaction::Transaction,
+        },
+    };
 
     #[test]
     fn get_health() {
@@ -1165,6 +1322,55 @@ mod test {
         rpc_client.get_health().await.expect("health");
     }
 
+    #[test]
+    fn activate_features_at_slot() {
+        let feature_id = feature_set::enable_get_minimum_balance_for_rent_exemption_syscall::id();
+        let activation_slot = 1_000_000;
+        let (test_validator, _payer) = TestValidatorGenesis::default()
+            .activate_features_at_slot(&[(feature_id, activation_slot)])
+            .start();
+        let bank = test_validator.bank_forks().read().unwrap().working_bank();
+        assert!(!bank.feature_set.is_active(&feature_id));
+        let feature = feature::from_account(&bank.get_account(&feature_id).unwrap()).unwrap();
+        assert_eq!(feature.activated_at, Some(activation_slot));
+    }
+
+    #[test]
+    fn lazy_clone_accounts() {
+        let remote_address = Pubkey::new_unique();
+        let remote_lamports = sol_to_lamports(1.);
+        let (remote_validator, _payer) = TestValidatorGenesis::default()
+            .add_account(
+                remote_address,
+                AccountSharedData::new(remote_lamports, 0, &system_program::id()),
+            )
+            .start();
+        let (test_validator, payer) = TestValidatorGenesis::default()
+            .lazy_clone_accounts_from(remote_validator.rpc_url())
+            .start();
+        let rpc_client = test_validator.get_rpc_client();
+
+        // the account is only cloned once a transaction loads it
+        assert_eq!(rpc_client.get_balance(&remote_address).unwrap(), 0);
+        let transaction = Transaction::new_signed_with_payer(
+            &[system_instruction::transfer(
+                &payer.pubkey(),
+                &remote_address,
+                1,
+            )],
+            Some(&payer.pubkey()),
+            &[&payer],
+            rpc_client.get_latest_blockhash().unwrap(),
+        );
+        rpc_client
+            .send_and_confirm_transaction(&transaction)
+            .unwrap();
+        assert_eq!(
+            rpc_client.get_balance(&remote_address).unwrap(),
+            remote_lamports + 1
+        );
+    }
+
     #[tokio::test]
     #[should_panic]
     async fn document_tokio_panic() {
@@ -1,6 +1,9 @@
 pub use crate::tpu_client::Result;
 use {
-    crate::tpu_client::{RecentLeaderSlots, TpuClientConfig, MAX_FANOUT_SLOTS},
+    crate::tpu_client::{
+        BundleStatus, RecentLeaderSlots, SentBundle, TpuClientConfig, MAX_BUNDLE_SIZE,
+        MAX_FANOUT_SLOTS,
+    },
     bincode::serialize,
     futures_util::{
         future::{join_all, FutureExt, TryFutureExt},
@@ -10,7 +13,6 @@ use {
     solana_connection_cache::{
         connection_cache::{
             ConnectionCache, ConnectionManager, ConnectionPool, NewConnectionConfig, Protocol,
-            DEFAULT_CONNECTION_POOL_SIZE,
         },
         nonblocking::client_connection::ClientConnection,
     },
@@ -53,7 +55,16 @@ use {
     indicatif::ProgressBar,
     solana_rpc_client::spinner::{self, SendTransactionProgress},
     solana_rpc_client_api::request::MAX_GET_SIGNATURE_STATUSES_QUERY_ITEMS,
-    solana_sdk::{message::Message, signers::Signers, transaction::TransactionError},
+    solana_sdk::{
+        hash::Hash,
+        message::Message,
+        signer::{
+            async_signer::{self, AsyncSigner},
+            Signer,
+        },
+        signers::Signers,
+        transaction::TransactionError,
+    },
 };
 
 #[derive(Error, Debug)]
@@ -70,6 +81,30 @@ pub enum TpuSenderError {
     Custom(String),
 }
 
+/// The signers of the transactions sent by
+/// [`TpuClient::send_and_confirm_messages_with_spinner`] and its async variant
+#[cfg(feature = "spinner")]
+enum MessageSigners<'a, T: Signers + ?Sized> {
+    Sync(&'a T),
+    Async(&'a [&'a dyn AsyncSigner]),
+}
+
+#[cfg(feature = "spinner")]
+impl<T: Signers + ?Sized> MessageSigners<'_, T> {
+    async fn try_sign(
+        &self,
+        transaction: &mut Transaction,
+        blockhash: Hash,
+    ) -> std::result::Result<(), SignerError> {
+        match self {
+            Self::Sync(signers) => transaction.try_sign(*signers, blockhash),
+            Self::Async(signers) => {
+                async_signer::try_sign_transaction(transaction, signers, blockhash).await
+            }
+        }
+    }
+}
+
 struct LeaderTpuCacheUpdateInfo {
     pub(super) maybe_cluster_nodes: Option<ClientResult<Vec<RpcContactInfo>>>,
     pub(super) maybe_epoch_info: Option<ClientResult<EpochInfo>>,
@@ -159,6 +194,13 @@ impl LeaderTpuCache {
         leader_sockets
     }
 
+    // Get the leader of a slot and its TPU socket, if both are known
+    fn get_slot_leader_socket(&self, slot: Slot) -> Option<(Pubkey, SocketAddr)> {
+        let leader = self.get_slot_leader(slot)?;
+        let tpu_socket = self.leader_tpu_map.get(leader)?;
+        Some((*leader, *tpu_socket))
+    }
+
     pub fn get_slot_leader(&self, slot: Slot) -> Option<&Pubkey> {
         if slot >= self.first_slot {
             let index = slot - self.first_slot;
@@ -498,7 +540,7 @@ where
         connection_manager: M,
     ) -> Result<Self> {
         let connection_cache = Arc::new(
-            ConnectionCache::new(name, connection_manager, DEFAULT_CONNECTION_POOL_SIZE).unwrap(),
+            ConnectionCache::new(name, connection_manager, config.connection_pool_size).unwrap(),
         ); // TODO: Handle error properly, as the ConnectionCache ctor is now fallible.
         Self::new_with_connection_cache(rpc_client, websocket_url, config, connection_cache).await
     }
@@ -529,6 +571,32 @@ where
         &self,
         messages: &[Message],
         signers: &T,
+    ) -> Result<Vec<Option<TransactionError>>> {
+        self.send_and_confirm_messages_with_spinner_impl(messages, MessageSigners::Sync(signers))
+            .await
+    }
+
+    /// Like [`TpuClient::send_and_confirm_messages_with_spinner`], but for
+    /// signers which sign asynchronously, such as remote or hardware-backed
+    /// signers
+    #[cfg(feature = "spinner")]
+    pub async fn send_and_confirm_messages_with_async_signers(
+        &self,
+        messages: &[Message],
+        signers: &[&dyn AsyncSigner],
+    ) -> Result<Vec<Option<TransactionError>>> {
+        self.send_and_confirm_messages_with_spinner_impl(
+            messages,
+            MessageSigners::<[&dyn Signer]>::Async(signers),
+        )
+        .await
+    }
+
+    #[cfg(feature = "spinner")]
+    async fn send_and_confirm_messages_with_spinner_impl<T: Signers + ?Sized>(
+        &self,
+        messages: &[Message],
+        signers: MessageSigners<'_, T>,
     ) -> Result<Vec<Option<TransactionError>>> {
         let mut progress = SendTransactionProgress::default();
         let progress_bar = spinner::new_progress_bar();
@@ -551,7 +619,7 @@ where
 
             let mut pending_transactions = HashMap::new();
             for (i, mut transaction) in transactions {
-                transaction.try_sign(signers, blockhash)?;
+                signers.try_sign(&mut transaction, blockhash).await?;
                 pending_transactions.insert(transaction.signatures[0], (i, transaction));
             }
 
@@ -668,6 +736,91 @@ where
         Err(TpuSenderError::Custom("Max retries exceeded".into()))
     }
 
+    /// Serialize and send a bundle of transactions to the leader of the current slot, to land in
+    /// the same block. Unlike the other send methods, the bundle is not fanned out to upcoming
+    /// leaders. The transactions are sent separately, so the leader may receive and process
+    /// them in any order.
+    /// Use `get_bundle_status` to check whether the bundle landed as a whole
+    pub async fn send_bundle(&self, transactions: &[Transaction]) -> Result<SentBundle> {
+        if transactions.is_empty() || transactions.len() > MAX_BUNDLE_SIZE {
+            return Err(TpuSenderError::Custom(format!(
+                "Bundle must contain between 1 and {MAX_BUNDLE_SIZE} transactions"
+            )));
+        }
+        let mut signatures = Vec::with_capacity(transactions.len());
+        let mut blockhashes = Vec::with_capacity(transactions.len());
+        for transaction in transactions {
+            let signature = match transaction.signatures.first() {
+                Some(signature) if transaction.is_signed() => *signature,
+                _ => {
+                    return Err(TpuSenderError::Custom(
+                        "Bundle transactions must be signed".into(),
+                    ))
+                }
+            };
+            if signatures.contains(&signature) {
+                return Err(TpuSenderError::Custom(format!(
+                    "Duplicate transaction in bundle: {signature}"
+                )));
+            }
+            signatures.push(signature);
+            blockhashes.push(transaction.message.recent_blockhash);
+        }
+        let wire_transactions = transactions
+            .iter()
+            .map(|transaction| serialize(transaction).expect("serialization should succeed"))
+            .collect::<Vec<_>>();
+
+        let (slot, leader, addr) = self
+            .leader_tpu_service
+            .current_leader_tpu_socket()
+            .ok_or_else(|| {
+                TpuSenderError::Custom("TPU not available for the current leader".into())
+            })?;
+        let conn = self.connection_cache.get_nonblocking_connection(&addr);
+        conn.send_data_batch(&wire_transactions)
+            .await
+            .map_err(|err| TpuSenderError::Custom(format!("Failed to send bundle: {err}")))?;
+        Ok(SentBundle {
+            signatures,
+            blockhashes,
+            leader,
+            slot,
+        })
+    }
+
+    /// Fetch the status of a bundle sent by `send_bundle`
+    pub async fn get_bundle_status(&self, bundle: &SentBundle) -> Result<BundleStatus> {
+        let statuses = self
+            .rpc_client
+            .get_signature_statuses(&bundle.signatures)
+            .await?
+            .value
+            .into_iter()
+            .map(|status| status.map(|status| (status.slot, status.status)))
+            .collect::<Vec<_>>();
+
+        // Transactions which have not been processed can no longer land once their blockhash
+        // expires
+        let pending_blockhashes = statuses
+            .iter()
+            .zip(&bundle.blockhashes)
+            .filter_map(|(status, blockhash)| status.is_none().then_some(*blockhash))
+            .collect::<HashSet<_>>();
+        let mut expired = false;
+        for blockhash in pending_blockhashes {
+            if !self
+                .rpc_client
+                .is_blockhash_valid(&blockhash, self.rpc_client.commitment())
+                .await?
+            {
+                expired = true;
+                break;
+            }
+        }
+        Ok(BundleStatus::from_statuses(&statuses, expired))
+    }
+
     pub fn rpc_client(&self) -> &RpcClient {
         &self.rpc_client
     }
@@ -760,6 +913,17 @@ impl LeaderTpuService {
             .get_leader_sockets(current_slot, fanout_slots)
     }
 
+    fn current_leader_tpu_socket(&self) -> Option<(Slot, Pubkey, SocketAddr)> {
+        let leader_tpu_cache = self.leader_tpu_cache.read().unwrap();
+        // As in `LeaderTpuCache::get_leader_sockets`, don't target a slot older than the cache
+        let current_slot = std::cmp::max(
+            self.recent_slots.estimated_current_slot(),
+            leader_tpu_cache.first_slot,
+        );
+        let (leader, tpu_socket) = leader_tpu_cache.get_slot_leader_socket(current_slot)?;
+        Some((current_slot, leader, tpu_socket))
+    }
+
     async fn run(
         rpc_client: Arc<RpcClient>,
         recent_slots: RecentLeaderSlots,
@@ -4,9 +4,17 @@ use {
     rayon::iter::{IntoParallelIterator, ParallelIterator},
     solana_connection_cache::connection_cache::{
         ConnectionCache, ConnectionManager, ConnectionPool, NewConnectionConfig,
+        DEFAULT_CONNECTION_POOL_SIZE,
     },
     solana_rpc_client::rpc_client::RpcClient,
-    solana_sdk::{clock::Slot, transaction::Transaction, transport::Result as TransportResult},
+    solana_sdk::{
+        clock::Slot,
+        hash::Hash,
+        pubkey::Pubkey,
+        signature::Signature,
+        transaction::{self, Transaction},
+        transport::Result as TransportResult,
+    },
     std::{
         collections::VecDeque,
         net::UdpSocket,
@@ -38,18 +46,86 @@ pub const DEFAULT_FANOUT_SLOTS: u64 = 12;
 /// Maximum number of slots used to build TPU socket fanout set
 pub const MAX_FANOUT_SLOTS: u64 = 100;
 
+/// Maximum number of transactions in a bundle sent by `TpuClient::send_bundle`
+pub const MAX_BUNDLE_SIZE: usize = 5;
+
 /// Config params for `TpuClient`
 #[derive(Clone, Debug)]
 pub struct TpuClientConfig {
     /// The range of upcoming slots to include when determining which
     /// leaders to send transactions to (min: 1, max: `MAX_FANOUT_SLOTS`)
     pub fanout_slots: u64,
+    /// The number of connections kept open to each leader, when the client
+    /// creates its own connection cache
+    pub connection_pool_size: usize,
 }
 
 impl Default for TpuClientConfig {
     fn default() -> Self {
         Self {
             fanout_slots: DEFAULT_FANOUT_SLOTS,
+            connection_pool_size: DEFAULT_CONNECTION_POOL_SIZE,
+        }
+    }
+}
+
+/// A bundle sent by `TpuClient::send_bundle`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SentBundle {
+    /// Signatures of the bundle's transactions, in bundle order
+    pub signatures: Vec<Signature>,
+    /// Recent blockhashes of the bundle's transactions, in bundle order
+    pub blockhashes: Vec<Hash>,
+    /// The leader the bundle was sent to
+    pub leader: Pubkey,
+    /// The slot the bundle was intended to land in
+    pub slot: Slot,
+}
+
+/// Whether a bundle landed as a whole
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BundleStatus {
+    /// Not all of the bundle's transactions have been processed yet
+    Pending,
+    /// Every transaction of the bundle succeeded in the same block
+    Landed { slot: Slot },
+    /// Some transactions of the bundle succeeded in the same block, but the
+    /// blockhash of another expired before it was processed
+    PartiallyLanded { slot: Slot },
+    /// None of the bundle's transactions were processed before the blockhash
+    /// of one of them expired
+    Expired,
+    /// A transaction of the bundle failed, or the transactions did not land in
+    /// the same block. The bundle should not be relied upon as a whole.
+    Broken,
+}
+
+impl BundleStatus {
+    /// Classify a bundle from the processed slot and result of each of its
+    /// transactions, where `None` is a transaction not yet processed, and
+    /// whether the blockhash of an unprocessed transaction has expired
+    pub fn from_statuses(
+        statuses: &[Option<(Slot, transaction::Result<()>)>],
+        expired: bool,
+    ) -> Self {
+        let mut landed_slot = None;
+        let mut pending = statuses.is_empty();
+        for status in statuses {
+            match status {
+                None => pending = true,
+                Some((_, Err(_))) => return Self::Broken,
+                Some((slot, Ok(()))) => match landed_slot {
+                    None => landed_slot = Some(*slot),
+                    Some(landed_slot) if landed_slot != *slot => return Self::Broken,
+                    Some(_) => {}
+                },
+            }
+        }
+        match (landed_slot, pending) {
+            (Some(slot), false) => Self::Landed { slot },
+            (Some(slot), true) if expired => Self::PartiallyLanded { slot },
+            (None, true) if expired => Self::Expired,
+            _ => Self::Pending,
         }
     }
 }
@@ -111,6 +187,20 @@ where
         self.invoke(self.tpu_client.try_send_wire_transaction(wire_transaction))
     }
 
+    /// Serialize and send a bundle of transactions to the leader of the current slot, to land in
+    /// the same block. Unlike the other send methods, the bundle is not fanned out to upcoming
+    /// leaders. The transactions are sent separately, so the leader may receive and process
+    /// them in any order.
+    /// Use `get_bundle_status` to check whether the bundle landed as a whole
+    pub fn send_bundle(&self, transactions: &[Transaction]) -> Result<SentBundle> {
+        self.invoke(self.tpu_client.send_bundle(transactions))
+    }
+
+    /// Fetch the status of a bundle sent by `send_bundle`
+    pub fn get_bundle_status(&self, bundle: &SentBundle) -> Result<BundleStatus> {
+        self.invoke(self.tpu_client.get_bundle_status(bundle))
+    }
+
     /// Create a new client that disconnects when dropped
     pub fn new(
         name: &'static str,
@@ -239,7 +329,7 @@ impl From<Vec<Slot>> for RecentLeaderSlots {
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use {super::*, solana_sdk::transaction::TransactionError};
 
     fn assert_slot(recent_slots: RecentLeaderSlots, expected_slot: Slot) {
         assert_eq!(recent_slots.estimated_current_slot(), expected_slot);
@@ -270,4 +360,51 @@ mod tests {
         assert_slot(RecentLeaderSlots::from(vec![1, 2, 3, 100]), 3);
         assert_slot(RecentLeaderSlots::from(vec![1, 2, 3, 99, 100]), 3);
     }
+
+    #[test]
+    fn test_bundle_status_from_statuses() {
+        let failed = Err(TransactionError::AccountInUse);
+        assert_eq!(
+            BundleStatus::from_statuses(&[], false),
+            BundleStatus::Pending
+        );
+        assert_eq!(
+            BundleStatus::from_statuses(&[None, None], false),
+            BundleStatus::Pending
+        );
+        assert_eq!(
+            BundleStatus::from_statuses(&[Some((5, Ok(()))), None], false),
+            BundleStatus::Pending
+        );
+        assert_eq!(
+            BundleStatus::from_statuses(&[Some((5, Ok(()))), Some((5, Ok(())))], false),
+            BundleStatus::Landed { slot: 5 }
+        );
+        assert_eq!(
+            BundleStatus::from_statuses(&[Some((5, Ok(()))), Some((6, Ok(())))], false),
+            BundleStatus::Broken
+        );
+        assert_eq!(
+            BundleStatus::from_statuses(&[None, Some((5, failed.clone()))], false),
+            BundleStatus::Broken
+        );
+        assert_eq!(
+            BundleStatus::from_statuses(&[Some((5, Ok(()))), Some((5, failed))], false),
+            BundleStatus::Broken
+        );
+
+        // unprocessed transactions can no longer land
+        assert_eq!(
+            BundleStatus::from_statuses(&[None, None], true),
+            BundleStatus::Expired
+        );
+        assert_eq!(
+            BundleStatus::from_statuses(&[Some((5, Ok(()))), None], true),
+            BundleStatus::PartiallyLanded { slot: 5 }
+        );
+        assert_eq!(
+            BundleStatus::from_statuses(&[Some((5, Ok(()))), Some((5, Ok(())))], true),
+            BundleStatus::Landed { slot: 5 }
+        );
+    }
 }
@@ -248,6 +248,8 @@ fn run_transactions_dos(
             is_final: true,
             max_len: None,
             skip_fee_check: true, // skip_fee_check
+            expected_buffer_hash: None,
+            expected_program_location: None,
         });
 
         process_command(&config).expect("deploy didn't pass");
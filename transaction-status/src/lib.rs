@@ -6,11 +6,12 @@ use {
         option_serializer::OptionSerializer,
         parse_accounts::{parse_legacy_message_accounts, parse_v0_message_accounts, ParsedAccount},
         parse_instruction::{parse, ParsedInstruction},
+        structured_logs::{extract_structured_logs, UiStructuredLog},
     },
     base64::{prelude::BASE64_STANDARD, Engine},
-    solana_account_decoder::parse_token::UiTokenAmount,
+    solana_account_decoder::{parse_token::UiTokenAmount, StringAmount},
     solana_sdk::{
-        clock::{Slot, UnixTimestamp},
+        clock::{Epoch, Slot, UnixTimestamp},
         commitment_config::CommitmentConfig,
         hash::Hash,
         instruction::CompiledInstruction,
@@ -46,6 +47,9 @@ pub mod parse_stake;
 pub mod parse_system;
 pub mod parse_token;
 pub mod parse_vote;
+pub mod program_errors;
+pub mod stake_changes;
+pub mod structured_logs;
 pub mod token_balances;
 
 pub struct BlockEncodingOptions {
@@ -307,6 +311,98 @@ pub struct TransactionTokenBalance {
     pub ui_token_amount: UiTokenAmount,
     pub owner: String,
     pub program_id: String,
+    /// Token-2022 extensions of the account or its mint which affect the balance
+    pub extensions: Vec<TokenBalanceExtension>,
+}
+
+/// A Token-2022 extension of a token account or its mint which affects its balance
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TokenBalanceExtension {
+    /// Transfer fees withheld in the account, which are not part of its amount
+    #[serde(rename_all = "camelCase")]
+    TransferFeeAmount { withheld_amount: u64 },
+    /// Fee the mint charges on transfers in the current epoch, withheld in
+    /// the destination account
+    #[serde(rename_all = "camelCase")]
+    TransferFeeConfig {
+        transfer_fee_basis_points: u16,
+        maximum_fee: u64,
+    },
+    /// Interest the mint accrues, which is part of the ui amount but not of
+    /// the amount
+    #[serde(rename_all = "camelCase")]
+    InterestBearingConfig { rate: i16 },
+}
+
+/// The delegation of a stake account
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StakeDelegation {
+    pub voter: String,
+    pub stake: u64,
+    pub activation_epoch: Epoch,
+    pub deactivation_epoch: Epoch,
+}
+
+/// How a transaction changed the delegation of one of its stake accounts
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionStakeChange {
+    pub account_index: u8,
+    /// None if the account was not delegated before the transaction
+    pub pre_delegation: Option<StakeDelegation>,
+    /// None if the account is not delegated after the transaction
+    pub post_delegation: Option<StakeDelegation>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UiStakeDelegation {
+    pub voter: String,
+    pub stake: StringAmount,
+    pub activation_epoch: StringAmount,
+    pub deactivation_epoch: StringAmount,
+}
+
+impl From<StakeDelegation> for UiStakeDelegation {
+    fn from(delegation: StakeDelegation) -> Self {
+        Self {
+            voter: delegation.voter,
+            stake: delegation.stake.to_string(),
+            activation_epoch: delegation.activation_epoch.to_string(),
+            deactivation_epoch: delegation.deactivation_epoch.to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UiTransactionStakeChange {
+    pub account_index: u8,
+    pub pre_delegation: Option<UiStakeDelegation>,
+    pub post_delegation: Option<UiStakeDelegation>,
+    /// Change of the delegated stake, negative if stake was undelegated
+    pub delegated_stake_change: StringAmount,
+}
+
+impl From<TransactionStakeChange> for UiTransactionStakeChange {
+    fn from(stake_change: TransactionStakeChange) -> Self {
+        let delegated_stake = |delegation: &Option<StakeDelegation>| {
+            delegation
+                .as_ref()
+                .map(|delegation| i128::from(delegation.stake))
+                .unwrap_or_default()
+        };
+        let delegated_stake_change = delegated_stake(&stake_change.post_delegation)
+            - delegated_stake(&stake_change.pre_delegation);
+        Self {
+            account_index: stake_change.account_index,
+            pre_delegation: stake_change.pre_delegation.map(Into::into),
+            post_delegation: stake_change.post_delegation.map(Into::into),
+            delegated_stake_change: delegated_stake_change.to_string(),
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
@@ -325,6 +421,11 @@ pub struct UiTransactionTokenBalance {
         skip_serializing_if = "OptionSerializer::should_skip"
     )]
     pub program_id: OptionSerializer<String>,
+    #[serde(
+        default = "OptionSerializer::skip",
+        skip_serializing_if = "OptionSerializer::should_skip"
+    )]
+    pub extensions: OptionSerializer<Vec<TokenBalanceExtension>>,
 }
 
 impl From<TransactionTokenBalance> for UiTransactionTokenBalance {
@@ -343,6 +444,11 @@ impl From<TransactionTokenBalance> for UiTransactionTokenBalance {
             } else {
                 OptionSerializer::Skip
             },
+            extensions: if !token_balance.extensions.is_empty() {
+                OptionSerializer::Some(token_balance.extensions)
+            } else {
+                OptionSerializer::Skip
+            },
         }
     }
 }
@@ -361,6 +467,7 @@ pub struct TransactionStatusMeta {
     pub loaded_addresses: LoadedAddresses,
     pub return_data: Option<TransactionReturnData>,
     pub compute_units_consumed: Option<u64>,
+    pub stake_changes: Option<Vec<TransactionStakeChange>>,
 }
 
 impl Default for TransactionStatusMeta {
@@ -378,6 +485,7 @@ impl Default for TransactionStatusMeta {
             loaded_addresses: LoadedAddresses::default(),
             return_data: None,
             compute_units_consumed: None,
+            stake_changes: None,
         }
     }
 }
@@ -401,6 +509,11 @@ pub struct UiTransactionStatusMeta {
         skip_serializing_if = "OptionSerializer::should_skip"
     )]
     pub log_messages: OptionSerializer<Vec<String>>,
+    #[serde(
+        default = "OptionSerializer::skip",
+        skip_serializing_if = "OptionSerializer::should_skip"
+    )]
+    pub structured_log_messages: OptionSerializer<Vec<UiStructuredLog>>,
     #[serde(
         default = "OptionSerializer::none",
         skip_serializing_if = "OptionSerializer::should_skip"
@@ -431,6 +544,11 @@ pub struct UiTransactionStatusMeta {
         skip_serializing_if = "OptionSerializer::should_skip"
     )]
     pub compute_units_consumed: OptionSerializer<u64>,
+    #[serde(
+        default = "OptionSerializer::skip",
+        skip_serializing_if = "OptionSerializer::should_skip"
+    )]
+    pub stake_changes: OptionSerializer<Vec<UiTransactionStakeChange>>,
 }
 
 /// A duplicate representation of LoadedAddresses
@@ -458,6 +576,18 @@ impl From<&LoadedAddresses> for UiLoadedAddresses {
     }
 }
 
+/// The structured logs among `log_messages`, skipped if there are none
+fn ui_structured_log_messages(
+    log_messages: &Option<Vec<String>>,
+) -> OptionSerializer<Vec<UiStructuredLog>> {
+    OptionSerializer::or_skip(
+        log_messages
+            .as_deref()
+            .map(extract_structured_logs)
+            .filter(|structured_logs| !structured_logs.is_empty()),
+    )
+}
+
 impl UiTransactionStatusMeta {
     fn parse(meta: TransactionStatusMeta, static_keys: &[Pubkey], show_rewards: bool) -> Self {
         let account_keys = AccountKeys::new(static_keys, Some(&meta.loaded_addresses));
@@ -475,6 +605,7 @@ impl UiTransactionStatusMeta {
                         .collect()
                 })
                 .into(),
+            structured_log_messages: ui_structured_log_messages(&meta.log_messages),
             log_messages: meta.log_messages.into(),
             pre_token_balances: meta
                 .pre_token_balances
@@ -490,6 +621,11 @@ impl UiTransactionStatusMeta {
                 meta.return_data.map(|return_data| return_data.into()),
             ),
             compute_units_consumed: OptionSerializer::or_skip(meta.compute_units_consumed),
+            stake_changes: OptionSerializer::or_skip(
+                meta.stake_changes
+                    .filter(|stake_changes| !stake_changes.is_empty())
+                    .map(|stake_changes| stake_changes.into_iter().map(Into::into).collect()),
+            ),
         }
     }
 
@@ -502,6 +638,7 @@ impl UiTransactionStatusMeta {
             post_balances: meta.post_balances,
             inner_instructions: OptionSerializer::Skip,
             log_messages: OptionSerializer::Skip,
+            structured_log_messages: OptionSerializer::Skip,
             pre_token_balances: meta
                 .pre_token_balances
                 .map(|balance| balance.into_iter().map(Into::into).collect())
@@ -518,6 +655,7 @@ impl UiTransactionStatusMeta {
             loaded_addresses: OptionSerializer::Skip,
             return_data: OptionSerializer::Skip,
             compute_units_consumed: OptionSerializer::Skip,
+            stake_changes: OptionSerializer::Skip,
         }
     }
 }
@@ -534,6 +672,7 @@ impl From<TransactionStatusMeta> for UiTransactionStatusMeta {
                 .inner_instructions
                 .map(|ixs| ixs.into_iter().map(Into::into).collect())
                 .into(),
+            structured_log_messages: ui_structured_log_messages(&meta.log_messages),
             log_messages: meta.log_messages.into(),
             pre_token_balances: meta
                 .pre_token_balances
@@ -549,6 +688,7 @@ impl From<TransactionStatusMeta> for UiTransactionStatusMeta {
                 meta.return_data.map(|return_data| return_data.into()),
             ),
             compute_units_consumed: OptionSerializer::or_skip(meta.compute_units_consumed),
+            stake_changes: OptionSerializer::Skip,
         }
     }
 }
@@ -896,6 +1036,20 @@ impl TransactionWithStatusMeta {
         }
     }
 
+    /// Whether any instruction of the transaction invokes `program_id`,
+    /// including inner instructions if the metadata is present
+    pub fn invokes_program(&self, program_id: &Pubkey) -> bool {
+        match self {
+            Self::MissingMetadata(tx) => tx.message.instructions.iter().any(|instruction| {
+                tx.message
+                    .account_keys
+                    .get(usize::from(instruction.program_id_index))
+                    == Some(program_id)
+            }),
+            Self::Complete(tx_with_meta) => tx_with_meta.invokes_program(program_id),
+        }
+    }
+
     fn build_json_accounts(
         self,
         max_supported_transaction_version: Option<u8>,
@@ -974,6 +1128,27 @@ impl VersionedTransactionWithStatusMeta {
         )
     }
 
+    /// Whether any instruction of the transaction, inner instructions
+    /// included, invokes `program_id`
+    pub fn invokes_program(&self, program_id: &Pubkey) -> bool {
+        let account_keys = self.account_keys();
+        let invokes_program = |instruction: &CompiledInstruction| {
+            account_keys.get(usize::from(instruction.program_id_index)) == Some(program_id)
+        };
+        self.transaction
+            .message
+            .instructions()
+            .iter()
+            .any(invokes_program)
+            || self
+                .meta
+                .inner_instructions
+                .iter()
+                .flatten()
+                .flat_map(|inner_instructions| &inner_instructions.instructions)
+                .any(|inner_instruction| invokes_program(&inner_instruction.instruction))
+    }
+
     fn build_json_accounts(
         self,
         max_supported_transaction_version: Option<u8>,
@@ -1512,6 +1687,99 @@ mod test {
         test_serde::<UiTransactionTokenBalance>(json_input, expected_json_output);
     }
 
+    #[test]
+    fn test_ui_transaction_token_balance_extensions() {
+        let token_balance = TransactionTokenBalance {
+            account_index: 5,
+            mint: "DXM2yVSouSg1twmQgHLKoSReqXhtUroehWxrTgPmmfWi".to_string(),
+            ui_token_amount: UiTokenAmount {
+                ui_amount: Some(1.0),
+                decimals: 0,
+                amount: "1".to_string(),
+                ui_amount_string: "1".to_string(),
+            },
+            owner: String::new(),
+            program_id: String::new(),
+            extensions: vec![
+                TokenBalanceExtension::TransferFeeAmount { withheld_amount: 7 },
+                TokenBalanceExtension::TransferFeeConfig {
+                    transfer_fee_basis_points: 50,
+                    maximum_fee: 1000,
+                },
+                TokenBalanceExtension::InterestBearingConfig { rate: -3 },
+            ],
+        };
+        let ui_token_balance = UiTransactionTokenBalance::from(token_balance);
+        assert_eq!(
+            json!(ui_token_balance),
+            json!({
+                "accountIndex": 5,
+                "mint": "DXM2yVSouSg1twmQgHLKoSReqXhtUroehWxrTgPmmfWi",
+                "uiTokenAmount": {
+                    "amount": "1",
+                    "decimals": 0,
+                    "uiAmount": 1.0,
+                    "uiAmountString": "1",
+                },
+                "extensions": [
+                    {"transferFeeAmount": {"withheldAmount": 7}},
+                    {"transferFeeConfig": {"transferFeeBasisPoints": 50, "maximumFee": 1000}},
+                    {"interestBearingConfig": {"rate": -3}},
+                ],
+            })
+        );
+        let deserialized: UiTransactionTokenBalance =
+            serde_json::from_value(json!(ui_token_balance)).unwrap();
+        assert_eq!(deserialized, ui_token_balance);
+    }
+
+    #[test]
+    fn test_ui_transaction_stake_change() {
+        let voter = "4fYNw3dojWmQ4dXtSGE9epjRGy9pFSx62YypT7avPYvA".to_string();
+        let stake_change = TransactionStakeChange {
+            account_index: 2,
+            pre_delegation: Some(StakeDelegation {
+                voter: voter.clone(),
+                stake: 5_000,
+                activation_epoch: 10,
+                deactivation_epoch: u64::MAX,
+            }),
+            post_delegation: None,
+        };
+        let meta = TransactionStatusMeta {
+            stake_changes: Some(vec![stake_change.clone()]),
+            ..TransactionStatusMeta::default()
+        };
+        let ui_meta = UiTransactionStatusMeta::parse(meta.clone(), &[], false);
+        assert_eq!(
+            json!(ui_meta.stake_changes),
+            json!([{
+                "accountIndex": 2,
+                "preDelegation": {
+                    "voter": voter,
+                    "stake": "5000",
+                    "activationEpoch": "10",
+                    "deactivationEpoch": "18446744073709551615",
+                },
+                "postDelegation": null,
+                "delegatedStakeChange": "-5000",
+            }])
+        );
+        // only the jsonParsed encoding reports stake changes
+        assert_eq!(
+            UiTransactionStatusMeta::from(meta).stake_changes,
+            OptionSerializer::Skip
+        );
+        let meta = TransactionStatusMeta {
+            stake_changes: Some(vec![]),
+            ..TransactionStatusMeta::default()
+        };
+        assert_eq!(
+            UiTransactionStatusMeta::parse(meta, &[], false).stake_changes,
+            OptionSerializer::Skip
+        );
+    }
+
     #[test]
     fn test_ui_transaction_status_meta_ctors_serialization() {
         let meta = TransactionStatusMeta {
@@ -1530,6 +1798,7 @@ mod test {
             },
             return_data: None,
             compute_units_consumed: None,
+            stake_changes: None,
         };
         let expected_json_output_value: serde_json::Value = serde_json::from_str(
             "{\
@@ -188,6 +188,33 @@ pub fn parse_bpf_upgradeable_loader(
                 }),
             })
         }
+        UpgradeableLoaderInstruction::TruncateProgramData { max_data_len } => {
+            check_num_bpf_upgradeable_loader_accounts(&instruction.accounts, 4)?;
+            Ok(ParsedInstructionEnum {
+                instruction_type: "truncateProgramData".to_string(),
+                info: json!({
+                    "maxDataLen": max_data_len,
+                    "programDataAccount": account_keys[instruction.accounts[0] as usize].to_string(),
+                    "programAccount": account_keys[instruction.accounts[1] as usize].to_string(),
+                    "recipient": account_keys[instruction.accounts[2] as usize].to_string(),
+                    "authority": account_keys[instruction.accounts[3] as usize].to_string(),
+                }),
+            })
+        }
+        UpgradeableLoaderInstruction::VerifyBufferHash {
+            expected_hash,
+            program_len,
+        } => {
+            check_num_bpf_upgradeable_loader_accounts(&instruction.accounts, 1)?;
+            Ok(ParsedInstructionEnum {
+                instruction_type: "verifyBufferHash".to_string(),
+                info: json!({
+                    "account": account_keys[instruction.accounts[0] as usize].to_string(),
+                    "expectedHash": expected_hash.to_string(),
+                    "programLen": program_len,
+                }),
+            })
+        }
     }
 }
 
@@ -209,6 +236,7 @@ mod test {
         serde_json::Value,
         solana_sdk::{
             bpf_loader_upgradeable,
+            hash::Hash,
             message::Message,
             pubkey::{self, Pubkey},
             system_program, sysvar,
@@ -581,6 +609,77 @@ mod test {
         .is_err());
     }
 
+    #[test]
+    fn test_parse_bpf_upgradeable_loader_truncate_program_data_ix() {
+        let program_address = Pubkey::new_unique();
+        let authority_address = Pubkey::new_unique();
+        let recipient_address = Pubkey::new_unique();
+        let (programdata_address, _) = Pubkey::find_program_address(
+            &[program_address.as_ref()],
+            &bpf_loader_upgradeable::id(),
+        );
+        let instruction = bpf_loader_upgradeable::truncate_program_data(
+            &program_address,
+            &authority_address,
+            &recipient_address,
+            1024,
+        );
+        let mut message = Message::new(&[instruction], None);
+        assert_eq!(
+            parse_bpf_upgradeable_loader(
+                &message.instructions[0],
+                &AccountKeys::new(&message.account_keys, None)
+            )
+            .unwrap(),
+            ParsedInstructionEnum {
+                instruction_type: "truncateProgramData".to_string(),
+                info: json!({
+                    "maxDataLen": 1024,
+                    "programDataAccount": programdata_address.to_string(),
+                    "programAccount": program_address.to_string(),
+                    "recipient": recipient_address.to_string(),
+                    "authority": authority_address.to_string(),
+                }),
+            }
+        );
+        let keys = message.account_keys.clone();
+        message.instructions[0].accounts.pop();
+        assert!(parse_bpf_upgradeable_loader(
+            &message.instructions[0],
+            &AccountKeys::new(&keys, None)
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_parse_bpf_upgradeable_loader_verify_buffer_hash_ix() {
+        let buffer_address = Pubkey::new_unique();
+        let expected_hash = Hash::new_unique();
+        let instruction =
+            bpf_loader_upgradeable::verify_buffer_hash(&buffer_address, expected_hash, 42);
+        let message = Message::new(&[instruction], None);
+        assert_eq!(
+            parse_bpf_upgradeable_loader(
+                &message.instructions[0],
+                &AccountKeys::new(&message.account_keys, None)
+            )
+            .unwrap(),
+            ParsedInstructionEnum {
+                instruction_type: "verifyBufferHash".to_string(),
+                info: json!({
+                    "account": buffer_address.to_string(),
+                    "expectedHash": expected_hash.to_string(),
+                    "programLen": 42,
+                }),
+            }
+        );
+        assert!(parse_bpf_upgradeable_loader(
+            &message.instructions[0],
+            &AccountKeys::new(&[], None)
+        )
+        .is_err());
+    }
+
     #[test]
     fn test_parse_bpf_upgradeable_loader_set_upgrade_authority_ix() {
         let program_address = Pubkey::new_unique();
@@ -10,6 +10,7 @@ use {
         parse_vote::parse_vote,
     },
     inflector::Inflector,
+    log::*,
     serde_json::Value,
     solana_account_decoder::parse_token::spl_token_ids,
     solana_sdk::{
@@ -19,6 +20,7 @@ use {
     std::{
         collections::HashMap,
         str::{from_utf8, Utf8Error},
+        sync::{Arc, RwLock},
     },
     thiserror::Error,
 };
@@ -58,6 +60,8 @@ lazy_static! {
         m.insert(*VOTE_PROGRAM_ID, ParsableProgram::Vote);
         m
     };
+    static ref REGISTERED_INSTRUCTION_PARSERS: RwLock<HashMap<Pubkey, Arc<dyn InstructionParser>>> =
+        RwLock::default();
 }
 
 #[derive(Error, Debug)]
@@ -71,6 +75,12 @@ pub enum ParseInstructionError {
     #[error("Program not parsable")]
     ProgramNotParsable,
 
+    #[error("{0} instruction not parsable")]
+    RegisteredInstructionNotParsable(String),
+
+    #[error("Program {0} already has an instruction parser")]
+    ProgramAlreadyParsable(Pubkey),
+
     #[error("Internal error, please report")]
     SerdeJsonError(#[from] serde_json::error::Error),
 }
@@ -107,15 +117,77 @@ pub enum ParsableProgram {
     Vote,
 }
 
-pub fn parse(
+/// Parses the instructions of programs which are not parsed natively, e.g.
+/// third-party programs, into the `parsed` field of `jsonParsed` instructions
+pub trait InstructionParser: Send + Sync {
+    /// Name of the program, reported as `program` in parsed instructions
+    fn program_name(&self) -> &str;
+
+    /// Ids of the programs whose instructions this parser parses
+    fn program_ids(&self) -> Vec<Pubkey>;
+
+    /// Parses an instruction of one of `program_ids`. Parsers should return
+    /// `RegisteredInstructionNotParsable` for instructions they do not
+    /// understand, which are then encoded unparsed.
+    fn parse(
+        &self,
+        instruction: &CompiledInstruction,
+        account_keys: &AccountKeys,
+    ) -> Result<Value, ParseInstructionError>;
+}
+
+/// Parse the instructions of the programs returned by `parser.program_ids()`
+/// with `parser`. Fails without registering any program if one of them is
+/// parsed natively or by a parser registered earlier.
+pub fn register_instruction_parser(
+    parser: Arc<dyn InstructionParser>,
+) -> Result<(), ParseInstructionError> {
+    let program_ids = parser.program_ids();
+    let mut registered_parsers = REGISTERED_INSTRUCTION_PARSERS.write().unwrap();
+    if let Some(program_id) = program_ids.iter().find(|program_id| {
+        PARSABLE_PROGRAM_IDS.contains_key(program_id) || registered_parsers.contains_key(program_id)
+    }) {
+        return Err(ParseInstructionError::ProgramAlreadyParsable(*program_id));
+    }
+    for program_id in program_ids {
+        info!(
+            "registering {} instruction parser for program {program_id}",
+            parser.program_name()
+        );
+        registered_parsers.insert(program_id, parser.clone());
+    }
+    Ok(())
+}
+
+fn parse_registered(
     program_id: &Pubkey,
     instruction: &CompiledInstruction,
     account_keys: &AccountKeys,
     stack_height: Option<u32>,
 ) -> Result<ParsedInstruction, ParseInstructionError> {
-    let program_name = PARSABLE_PROGRAM_IDS
+    let parser = REGISTERED_INSTRUCTION_PARSERS
+        .read()
+        .unwrap()
         .get(program_id)
+        .cloned()
         .ok_or(ParseInstructionError::ProgramNotParsable)?;
+    Ok(ParsedInstruction {
+        program: parser.program_name().to_kebab_case(),
+        program_id: program_id.to_string(),
+        parsed: parser.parse(instruction, account_keys)?,
+        stack_height,
+    })
+}
+
+pub fn parse(
+    program_id: &Pubkey,
+    instruction: &CompiledInstruction,
+    account_keys: &AccountKeys,
+    stack_height: Option<u32>,
+) -> Result<ParsedInstruction, ParseInstructionError> {
+    let Some(program_name) = PARSABLE_PROGRAM_IDS.get(program_id) else {
+        return parse_registered(program_id, instruction, account_keys, stack_height);
+    };
     let parsed_json = match program_name {
         ParsableProgram::AddressLookupTable => {
             serde_json::to_value(parse_address_lookup_table(instruction, account_keys)?)?
@@ -202,6 +274,80 @@ mod test {
         assert!(parse(&non_parsable_program_id, &memo_instruction, &no_keys, None).is_err());
     }
 
+    struct TestInstructionParser {
+        program_id: Pubkey,
+    }
+
+    impl InstructionParser for TestInstructionParser {
+        fn program_name(&self) -> &str {
+            "TestProgram"
+        }
+
+        fn program_ids(&self) -> Vec<Pubkey> {
+            vec![self.program_id]
+        }
+
+        fn parse(
+            &self,
+            instruction: &CompiledInstruction,
+            _account_keys: &AccountKeys,
+        ) -> Result<Value, ParseInstructionError> {
+            match instruction.data.as_slice() {
+                [amount] => Ok(json!({ "amount": amount })),
+                _ => Err(ParseInstructionError::RegisteredInstructionNotParsable(
+                    self.program_name().to_string(),
+                )),
+            }
+        }
+    }
+
+    #[test]
+    fn test_register_instruction_parser() {
+        let no_keys = AccountKeys::new(&[], None);
+        let program_id = Pubkey::new_unique();
+        let instruction = CompiledInstruction {
+            program_id_index: 0,
+            accounts: vec![],
+            data: vec![42],
+        };
+        assert!(matches!(
+            parse(&program_id, &instruction, &no_keys, None),
+            Err(ParseInstructionError::ProgramNotParsable)
+        ));
+
+        let parser = Arc::new(TestInstructionParser { program_id });
+        register_instruction_parser(parser.clone()).unwrap();
+        assert_eq!(
+            parse(&program_id, &instruction, &no_keys, Some(2)).unwrap(),
+            ParsedInstruction {
+                program: "test-program".to_string(),
+                program_id: program_id.to_string(),
+                parsed: json!({ "amount": 42 }),
+                stack_height: Some(2),
+            }
+        );
+        let unknown_instruction = CompiledInstruction {
+            data: vec![],
+            ..instruction
+        };
+        assert!(matches!(
+            parse(&program_id, &unknown_instruction, &no_keys, None),
+            Err(ParseInstructionError::RegisteredInstructionNotParsable(_))
+        ));
+
+        // programs parsed natively or by another parser cannot be registered
+        assert!(matches!(
+            register_instruction_parser(parser),
+            Err(ParseInstructionError::ProgramAlreadyParsable(id)) if id == program_id
+        ));
+        assert!(matches!(
+            register_instruction_parser(Arc::new(TestInstructionParser {
+                program_id: *MEMO_V1_PROGRAM_ID,
+            })),
+            Err(ParseInstructionError::ProgramAlreadyParsable(id)) if id == *MEMO_V1_PROGRAM_ID
+        ));
+    }
+
     #[test]
     fn test_parse_memo() {
         let good_memo = "good memo".to_string();
@@ -297,6 +297,34 @@ pub fn parse_stake(
                 }),
             })
         }
+        StakeInstruction::SplitWithFunding(lamports) => {
+            check_num_stake_accounts(&instruction.accounts, 5)?;
+            Ok(ParsedInstructionEnum {
+                instruction_type: "splitWithFunding".to_string(),
+                info: json!({
+                    "stakeAccount": account_keys[instruction.accounts[0] as usize].to_string(),
+                    "newSplitAccount": account_keys[instruction.accounts[1] as usize].to_string(),
+                    "fundingAccount": account_keys[instruction.accounts[2] as usize].to_string(),
+                    "systemProgram": account_keys[instruction.accounts[3] as usize].to_string(),
+                    "stakeAuthority": account_keys[instruction.accounts[4] as usize].to_string(),
+                    "lamports": lamports,
+                }),
+            })
+        }
+        StakeInstruction::DeactivateMultiple => {
+            check_num_stake_accounts(&instruction.accounts, 2)?;
+            let stake_accounts: Vec<String> = instruction.accounts[1..]
+                .iter()
+                .map(|index| account_keys[*index as usize].to_string())
+                .collect();
+            Ok(ParsedInstructionEnum {
+                instruction_type: "deactivateMultiple".to_string(),
+                info: json!({
+                    "stakeAuthority": account_keys[instruction.accounts[0] as usize].to_string(),
+                    "stakeAccounts": stake_accounts,
+                }),
+            })
+        }
     }
 }
 
@@ -316,7 +344,7 @@ mod test {
                 instruction::{self, LockupArgs},
                 state::{Authorized, Lockup, StakeAuthorize},
             },
-            sysvar,
+            system_program, sysvar,
         },
         std::iter::repeat_with,
     };
@@ -530,6 +558,49 @@ mod test {
         assert!(parse_stake(&message.instructions[0], &AccountKeys::new(&keys, None)).is_err());
     }
 
+    #[test]
+    fn test_parse_stake_split_with_funding_ix() {
+        let lamports = 55;
+        let stake_pubkey = Pubkey::new_unique();
+        let authorized_pubkey = Pubkey::new_unique();
+        let split_stake_pubkey = Pubkey::new_unique();
+        let funding_pubkey = Pubkey::new_unique();
+        let instructions = instruction::split_with_funding(
+            &stake_pubkey,
+            &authorized_pubkey,
+            lamports,
+            &split_stake_pubkey,
+            &funding_pubkey,
+        );
+        let mut message = Message::new(&instructions, None);
+        assert_eq!(
+            parse_stake(
+                &message.instructions[2],
+                &AccountKeys::new(&message.account_keys, None)
+            )
+            .unwrap(),
+            ParsedInstructionEnum {
+                instruction_type: "splitWithFunding".to_string(),
+                info: json!({
+                    "stakeAccount": stake_pubkey.to_string(),
+                    "newSplitAccount": split_stake_pubkey.to_string(),
+                    "fundingAccount": funding_pubkey.to_string(),
+                    "systemProgram": system_program::id().to_string(),
+                    "stakeAuthority": authorized_pubkey.to_string(),
+                    "lamports": lamports,
+                }),
+            }
+        );
+        assert!(parse_stake(
+            &message.instructions[2],
+            &AccountKeys::new(&message.account_keys[0..4], None)
+        )
+        .is_err());
+        let keys = message.account_keys.clone();
+        message.instructions[2].accounts.pop();
+        assert!(parse_stake(&message.instructions[2], &AccountKeys::new(&keys, None)).is_err());
+    }
+
     #[test]
     fn test_parse_stake_withdraw_ix() {
         let lamports = 55;
@@ -632,6 +703,36 @@ mod test {
         assert!(parse_stake(&message.instructions[0], &AccountKeys::new(&keys, None)).is_err());
     }
 
+    #[test]
+    fn test_parse_stake_deactivate_multiple_ix() {
+        let stake_pubkeys = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let authority_pubkey = Pubkey::new_unique();
+        let instruction = instruction::deactivate_stakes(&stake_pubkeys, &authority_pubkey);
+        let mut message = Message::new(&[instruction], None);
+        assert_eq!(
+            parse_stake(
+                &message.instructions[0],
+                &AccountKeys::new(&message.account_keys, None)
+            )
+            .unwrap(),
+            ParsedInstructionEnum {
+                instruction_type: "deactivateMultiple".to_string(),
+                info: json!({
+                    "stakeAuthority": authority_pubkey.to_string(),
+                    "stakeAccounts": [stake_pubkeys[0].to_string(), stake_pubkeys[1].to_string()],
+                }),
+            }
+        );
+        assert!(parse_stake(
+            &message.instructions[0],
+            &AccountKeys::new(&message.account_keys[0..2], None)
+        )
+        .is_err());
+        let keys = message.account_keys.clone();
+        message.instructions[0].accounts.truncate(1);
+        assert!(parse_stake(&message.instructions[0], &AccountKeys::new(&keys, None)).is_err());
+    }
+
     #[test]
     fn test_parse_stake_merge_ix() {
         let destination_stake_pubkey = Pubkey::new_unique();
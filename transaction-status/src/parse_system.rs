@@ -133,6 +133,18 @@ pub fn parse_system(
                 }),
             })
         }
+        SystemInstruction::AdvanceAndAuthorizeNonceAccount(authority) => {
+            check_num_system_accounts(&instruction.accounts, 3)?;
+            Ok(ParsedInstructionEnum {
+                instruction_type: "advanceAndAuthorizeNonce".to_string(),
+                info: json!({
+                    "nonceAccount": account_keys[instruction.accounts[0] as usize].to_string(),
+                    "recentBlockhashesSysvar": account_keys[instruction.accounts[1] as usize].to_string(),
+                    "nonceAuthority": account_keys[instruction.accounts[2] as usize].to_string(),
+                    "newAuthorized": authority.to_string(),
+                }),
+            })
+        }
         SystemInstruction::UpgradeNonceAccount => {
             check_num_system_accounts(&instruction.accounts, 1)?;
             Ok(ParsedInstructionEnum {
@@ -668,4 +680,42 @@ mod test {
         message.instructions[0].accounts.pop();
         assert!(parse_system(&message.instructions[0], &AccountKeys::new(&keys, None)).is_err());
     }
+
+    #[test]
+    fn test_parse_system_advance_and_authorize_nonce_account_ix() {
+        let nonce_pubkey = Pubkey::new_unique();
+        let authorized_pubkey = Pubkey::new_unique();
+        let new_authority_pubkey = Pubkey::new_unique();
+
+        let instruction = system_instruction::advance_and_authorize_nonce_account(
+            &nonce_pubkey,
+            &authorized_pubkey,
+            &new_authority_pubkey,
+        );
+        let mut message = Message::new(&[instruction], None);
+        assert_eq!(
+            parse_system(
+                &message.instructions[0],
+                &AccountKeys::new(&message.account_keys, None)
+            )
+            .unwrap(),
+            ParsedInstructionEnum {
+                instruction_type: "advanceAndAuthorizeNonce".to_string(),
+                info: json!({
+                    "nonceAccount": nonce_pubkey.to_string(),
+                    "recentBlockhashesSysvar": sysvar::recent_blockhashes::ID.to_string(),
+                    "nonceAuthority": authorized_pubkey.to_string(),
+                    "newAuthorized": new_authority_pubkey.to_string(),
+                }),
+            }
+        );
+        assert!(parse_system(
+            &message.instructions[0],
+            &AccountKeys::new(&message.account_keys[0..2], None)
+        )
+        .is_err());
+        let keys = message.account_keys.clone();
+        message.instructions[0].accounts.pop();
+        assert!(parse_system(&message.instructions[0], &AccountKeys::new(&keys, None)).is_err());
+    }
 }
@@ -0,0 +1,209 @@
+//! Decoding of custom program errors.
+//!
+//! Custom error codes are only meaningful to the program which returned them,
+//! so a [`ProgramErrorRegistry`] maps program ids to the error enums of those
+//! programs, and decodes a custom error using the program id of the failing
+//! instruction. Errors returned by programs invoked through CPI surface as
+//! errors of the top-level instruction, so they are only decoded when the
+//! top-level program returns them as its own.
+
+use {
+    num_traits::FromPrimitive,
+    solana_sdk::{
+        decode_error::{DecodeError, ProgramCustomError},
+        instruction::InstructionError,
+        message::VersionedMessage,
+        pubkey::Pubkey,
+        transaction::TransactionError,
+    },
+    std::{collections::HashMap, fmt::Display},
+};
+
+/// A custom error decoded by a [`ProgramErrorRegistry`]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DecodedProgramError {
+    pub program_id: String,
+    pub error_type: String,
+    pub code: u32,
+    pub message: String,
+}
+
+struct RegisteredProgram {
+    error_type: &'static str,
+    decode: fn(u32) -> Option<String>,
+}
+
+/// The error enums of programs, by program id
+#[derive(Default)]
+pub struct ProgramErrorRegistry {
+    programs: HashMap<Pubkey, RegisteredProgram>,
+}
+
+impl ProgramErrorRegistry {
+    /// Register the error enum `E`, replacing any error enum previously
+    /// registered for its program
+    pub fn register<E>(&mut self)
+    where
+        E: ProgramCustomError + DecodeError<E> + FromPrimitive + Display,
+    {
+        self.programs.insert(
+            E::PROGRAM_ID,
+            RegisteredProgram {
+                error_type: E::type_of(),
+                decode: |code| E::from_u32(code).map(|error| error.to_string()),
+            },
+        );
+    }
+
+    /// Decode a custom error code returned by `program_id`, if its errors are
+    /// registered
+    pub fn decode_custom_error(
+        &self,
+        program_id: &Pubkey,
+        custom: u32,
+    ) -> Option<DecodedProgramError> {
+        let program = self.programs.get(program_id)?;
+        Some(DecodedProgramError {
+            program_id: program_id.to_string(),
+            error_type: program.error_type.to_string(),
+            code: custom,
+            message: (program.decode)(custom)?,
+        })
+    }
+
+    pub fn decode_instruction_error(
+        &self,
+        program_id: &Pubkey,
+        error: &InstructionError,
+    ) -> Option<DecodedProgramError> {
+        match error {
+            InstructionError::Custom(custom) => self.decode_custom_error(program_id, *custom),
+            _ => None,
+        }
+    }
+
+    /// Decode the custom error of a failed instruction of `message`
+    pub fn decode_transaction_error(
+        &self,
+        message: &VersionedMessage,
+        error: &TransactionError,
+    ) -> Option<DecodedProgramError> {
+        match error {
+            TransactionError::InstructionError(index, error) => {
+                let instruction = message.instructions().get(usize::from(*index))?;
+                let program_id = message
+                    .static_account_keys()
+                    .get(usize::from(instruction.program_id_index))?;
+                self.decode_instruction_error(program_id, error)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Decode `error` returned by `program_id` to the error enum `E`, if it is one
+pub fn decode_instruction_error<E>(program_id: &Pubkey, error: &InstructionError) -> Option<E>
+where
+    E: ProgramCustomError + FromPrimitive,
+{
+    match error {
+        InstructionError::Custom(custom) => E::decode_program_error(program_id, *custom),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {
+        super::*,
+        num_derive::FromPrimitive,
+        solana_sdk::{
+            hash::Hash,
+            instruction::{AccountMeta, Instruction},
+            message::Message,
+        },
+        thiserror::Error,
+    };
+
+    #[derive(Debug, Error, PartialEq, Eq, FromPrimitive)]
+    enum TestError {
+        #[error("first error")]
+        First,
+        #[error("second error")]
+        Second,
+    }
+
+    impl ProgramCustomError for TestError {
+        const PROGRAM_ID: Pubkey =
+            Pubkey::from_str_const("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+    }
+
+    impl<T> DecodeError<T> for TestError {
+        fn type_of() -> &'static str {
+            "TestError"
+        }
+    }
+
+    #[test]
+    fn test_program_error_registry() {
+        let mut registry = ProgramErrorRegistry::default();
+        registry.register::<TestError>();
+
+        let payer = Pubkey::new_unique();
+        let other_program_id = Pubkey::new_unique();
+        let message = VersionedMessage::Legacy(Message::new_with_blockhash(
+            &[
+                Instruction::new_with_bytes(
+                    other_program_id,
+                    &[],
+                    vec![AccountMeta::new(payer, true)],
+                ),
+                Instruction::new_with_bytes(TestError::PROGRAM_ID, &[], vec![]),
+            ],
+            Some(&payer),
+            &Hash::default(),
+        ));
+
+        let error = TransactionError::InstructionError(1, InstructionError::Custom(1));
+        assert_eq!(
+            registry.decode_transaction_error(&message, &error),
+            Some(DecodedProgramError {
+                program_id: TestError::PROGRAM_ID.to_string(),
+                error_type: "TestError".to_string(),
+                code: 1,
+                message: "second error".to_string(),
+            })
+        );
+        assert_eq!(
+            decode_instruction_error::<TestError>(
+                &TestError::PROGRAM_ID,
+                &InstructionError::Custom(1)
+            ),
+            Some(TestError::Second)
+        );
+
+        // the same code from a program without registered errors
+        let error = TransactionError::InstructionError(0, InstructionError::Custom(1));
+        assert_eq!(registry.decode_transaction_error(&message, &error), None);
+        assert_eq!(
+            decode_instruction_error::<TestError>(&other_program_id, &InstructionError::Custom(1)),
+            None
+        );
+
+        // unknown codes, instructions and non-custom errors
+        assert_eq!(
+            registry.decode_custom_error(&TestError::PROGRAM_ID, 2),
+            None
+        );
+        let error = TransactionError::InstructionError(2, InstructionError::Custom(1));
+        assert_eq!(registry.decode_transaction_error(&message, &error), None);
+        assert_eq!(
+            registry.decode_instruction_error(
+                &TestError::PROGRAM_ID,
+                &InstructionError::InvalidArgument
+            ),
+            None
+        );
+    }
+}
@@ -0,0 +1,141 @@
+use {
+    crate::{StakeDelegation, TransactionStakeChange},
+    std::collections::BTreeMap,
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TransactionStakeDelegation {
+    pub account_index: u8,
+    pub delegation: StakeDelegation,
+}
+
+pub type TransactionStakeDelegations = Vec<Vec<TransactionStakeDelegation>>;
+
+pub struct TransactionStakeDelegationsSet {
+    pub pre_stake_delegations: TransactionStakeDelegations,
+    pub post_stake_delegations: TransactionStakeDelegations,
+}
+
+impl TransactionStakeDelegationsSet {
+    pub fn new(
+        pre_stake_delegations: TransactionStakeDelegations,
+        post_stake_delegations: TransactionStakeDelegations,
+    ) -> Self {
+        assert_eq!(pre_stake_delegations.len(), post_stake_delegations.len());
+        Self {
+            pre_stake_delegations,
+            post_stake_delegations,
+        }
+    }
+}
+
+/// The delegations a transaction changed, given the delegations of its stake
+/// accounts before and after it
+pub fn collect_stake_changes(
+    pre_stake_delegations: Vec<TransactionStakeDelegation>,
+    post_stake_delegations: Vec<TransactionStakeDelegation>,
+) -> Vec<TransactionStakeChange> {
+    let mut stake_changes = BTreeMap::<u8, TransactionStakeChange>::new();
+    let pre_stake_delegations = pre_stake_delegations
+        .into_iter()
+        .map(|delegation| (delegation, true));
+    let post_stake_delegations = post_stake_delegations
+        .into_iter()
+        .map(|delegation| (delegation, false));
+    for (
+        TransactionStakeDelegation {
+            account_index,
+            delegation,
+        },
+        is_pre,
+    ) in pre_stake_delegations.chain(post_stake_delegations)
+    {
+        let stake_change = stake_changes
+            .entry(account_index)
+            .or_insert(TransactionStakeChange {
+                account_index,
+                pre_delegation: None,
+                post_delegation: None,
+            });
+        if is_pre {
+            stake_change.pre_delegation = Some(delegation);
+        } else {
+            stake_change.post_delegation = Some(delegation);
+        }
+    }
+    stake_changes
+        .into_values()
+        .filter(|stake_change| stake_change.pre_delegation != stake_change.post_delegation)
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use {super::*, solana_sdk::pubkey::Pubkey};
+
+    fn new_delegation(voter: &Pubkey, stake: u64, deactivation_epoch: u64) -> StakeDelegation {
+        StakeDelegation {
+            voter: voter.to_string(),
+            stake,
+            activation_epoch: 10,
+            deactivation_epoch,
+        }
+    }
+
+    #[test]
+    fn test_collect_stake_changes() {
+        let voter = Pubkey::new_unique();
+        let unchanged = new_delegation(&voter, 42, u64::MAX);
+        let deactivating = new_delegation(&voter, 100, u64::MAX);
+        let deactivated = new_delegation(&voter, 100, 12);
+        let new = new_delegation(&voter, 7, u64::MAX);
+        let pre_stake_delegations = vec![
+            TransactionStakeDelegation {
+                account_index: 1,
+                delegation: unchanged.clone(),
+            },
+            TransactionStakeDelegation {
+                account_index: 2,
+                delegation: deactivating.clone(),
+            },
+            TransactionStakeDelegation {
+                account_index: 4,
+                delegation: new.clone(),
+            },
+        ];
+        let post_stake_delegations = vec![
+            TransactionStakeDelegation {
+                account_index: 1,
+                delegation: unchanged,
+            },
+            TransactionStakeDelegation {
+                account_index: 2,
+                delegation: deactivated.clone(),
+            },
+            TransactionStakeDelegation {
+                account_index: 3,
+                delegation: new.clone(),
+            },
+        ];
+        assert_eq!(
+            collect_stake_changes(pre_stake_delegations, post_stake_delegations),
+            vec![
+                TransactionStakeChange {
+                    account_index: 2,
+                    pre_delegation: Some(deactivating),
+                    post_delegation: Some(deactivated),
+                },
+                TransactionStakeChange {
+                    account_index: 3,
+                    pre_delegation: None,
+                    post_delegation: Some(new.clone()),
+                },
+                TransactionStakeChange {
+                    account_index: 4,
+                    pre_delegation: Some(new),
+                    post_delegation: None,
+                },
+            ]
+        );
+    }
+}
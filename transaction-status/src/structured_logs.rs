@@ -0,0 +1,171 @@
+//! Decoding of structured program logs, as printed by the `msg_structured!`
+//! and `log_event!` macros of `solana-program`.
+
+use {
+    base64::{prelude::BASE64_STANDARD, Engine},
+    solana_sdk::{
+        log::{LogLevel, STRUCTURED_LOG_TAG},
+        pubkey::Pubkey,
+    },
+    std::{collections::BTreeMap, str::FromStr},
+};
+
+const PROGRAM_DATA_PREFIX: &str = "Program data: ";
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct UiStructuredLog {
+    /// The program which printed the log, if it could be determined from the
+    /// surrounding log messages
+    pub program_id: Option<String>,
+    pub level: String,
+    pub target: String,
+    pub message: String,
+    /// Fields of the log; if a key was logged more than once, the last value
+    /// is kept
+    pub fields: BTreeMap<String, String>,
+}
+
+/// Decode a single log message, if it is a structured log
+pub fn parse_structured_log(log_message: &str) -> Option<UiStructuredLog> {
+    let data = log_message
+        .strip_prefix(PROGRAM_DATA_PREFIX)?
+        // empty fields are encoded as empty strings, so split on every space
+        .split(' ')
+        .map(|field| BASE64_STANDARD.decode(field).ok())
+        .collect::<Option<Vec<_>>>()?;
+    let [tag, level, target, message, fields @ ..] = &data[..] else {
+        return None;
+    };
+    if tag != STRUCTURED_LOG_TAG || fields.len() % 2 != 0 {
+        return None;
+    }
+    let level = match level[..] {
+        [level] => LogLevel::from_u8(level)?,
+        _ => return None,
+    };
+    let utf8 = |bytes: &Vec<u8>| String::from_utf8(bytes.clone()).ok();
+    Some(UiStructuredLog {
+        program_id: None,
+        level: level.as_str().to_string(),
+        target: utf8(target)?,
+        message: utf8(message)?,
+        fields: fields
+            .chunks_exact(2)
+            .map(|pair| Some((utf8(&pair[0])?, utf8(&pair[1])?)))
+            .collect::<Option<_>>()?,
+    })
+}
+
+/// Decode the structured logs among a transaction's log messages, attributing
+/// each to the program being invoked when it was printed
+pub fn extract_structured_logs(log_messages: &[String]) -> Vec<UiStructuredLog> {
+    let mut invoke_stack = vec![];
+    let mut structured_logs = vec![];
+    for log_message in log_messages {
+        if let Some(mut structured_log) = parse_structured_log(log_message) {
+            structured_log.program_id = invoke_stack.last().map(ToString::to_string);
+            structured_logs.push(structured_log);
+            continue;
+        }
+        let Some((program_id, status)) = log_message
+            .strip_prefix("Program ")
+            .and_then(|log_message| log_message.split_once(' '))
+        else {
+            continue;
+        };
+        if Pubkey::from_str(program_id).is_err() {
+            continue;
+        }
+        if status.starts_with("invoke [") {
+            invoke_stack.push(program_id);
+        } else if status == "success" || status.starts_with("failed") {
+            invoke_stack.pop();
+        }
+    }
+    structured_logs
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn program_data(fields: &[&[u8]]) -> String {
+        let fields = fields
+            .iter()
+            .map(|field| BASE64_STANDARD.encode(field))
+            .collect::<Vec<_>>();
+        format!("{PROGRAM_DATA_PREFIX}{}", fields.join(" "))
+    }
+
+    #[test]
+    fn test_parse_structured_log() {
+        let log_message = program_data(&[
+            STRUCTURED_LOG_TAG,
+            &[LogLevel::Warn as u8],
+            b"my_program::processor",
+            b"",
+            b"amount",
+            b"42",
+        ]);
+        assert_eq!(
+            parse_structured_log(&log_message),
+            Some(UiStructuredLog {
+                program_id: None,
+                level: "warn".to_string(),
+                target: "my_program::processor".to_string(),
+                message: "".to_string(),
+                fields: BTreeMap::from([("amount".to_string(), "42".to_string())]),
+            })
+        );
+
+        // other program data
+        assert_eq!(parse_structured_log(&program_data(&[b"slog1"])), None);
+        assert_eq!(
+            parse_structured_log(&program_data(&[b"other", &[3], b"", b""])),
+            None
+        );
+        // unpaired fields and unknown levels
+        assert_eq!(
+            parse_structured_log(&program_data(&[STRUCTURED_LOG_TAG, &[3], b"", b"", b"key"])),
+            None
+        );
+        assert_eq!(
+            parse_structured_log(&program_data(&[STRUCTURED_LOG_TAG, &[0], b"", b""])),
+            None
+        );
+        assert_eq!(parse_structured_log("Program log: slog1"), None);
+    }
+
+    #[test]
+    fn test_extract_structured_logs() {
+        let outer = Pubkey::new_unique();
+        let inner = Pubkey::new_unique();
+        let event = |name: &[u8]| program_data(&[STRUCTURED_LOG_TAG, &[3], name, b""]);
+        let log_messages = vec![
+            event(b"before"),
+            format!("Program {outer} invoke [1]"),
+            "Program log: success".to_string(),
+            event(b"outer"),
+            format!("Program {inner} invoke [2]"),
+            event(b"inner"),
+            format!("Program {inner} success"),
+            event(b"outer again"),
+            format!("Program {outer} consumed 2000 of 200000 compute units"),
+            format!("Program {outer} failed: custom program error: 0x1"),
+        ];
+        let structured_logs = extract_structured_logs(&log_messages)
+            .into_iter()
+            .map(|log| (log.target, log.program_id))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            structured_logs,
+            vec![
+                ("before".to_string(), None),
+                ("outer".to_string(), Some(outer.to_string())),
+                ("inner".to_string(), Some(inner.to_string())),
+                ("outer again".to_string(), Some(outer.to_string())),
+            ]
+        );
+    }
+}
@@ -126,6 +126,7 @@ fn bench_retransmitter(bencher: &mut Bencher) {
         shreds_receiver,
         Arc::default(), // solana_rpc::max_slots::MaxSlots
         None,
+        None, // tree_override
     );
 
     let mut index = 0;
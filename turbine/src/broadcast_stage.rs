@@ -8,7 +8,7 @@ use {
         fail_entry_verification_broadcast_run::FailEntryVerificationBroadcastRun,
         standard_broadcast_run::StandardBroadcastRun,
     },
-    crate::cluster_nodes::{self, ClusterNodes, ClusterNodesCache},
+    crate::cluster_nodes::{self, ClusterNodes, ClusterNodesCache, TurbineTreeOverride},
     bytes::Bytes,
     crossbeam_channel::{unbounded, Receiver, RecvError, RecvTimeoutError, Sender},
     itertools::{Either, Itertools},
@@ -116,6 +116,8 @@ impl BroadcastStageType {
         bank_forks: Arc<RwLock<BankForks>>,
         shred_version: u16,
         quic_endpoint_sender: AsyncSender<(SocketAddr, Bytes)>,
+        // Only honored by the standard broadcast run.
+        tree_override: Option<TurbineTreeOverride>,
     ) -> BroadcastStage {
         match self {
             BroadcastStageType::Standard => BroadcastStage::new(
@@ -127,7 +129,7 @@ impl BroadcastStageType {
                 blockstore,
                 bank_forks,
                 quic_endpoint_sender,
-                StandardBroadcastRun::new(shred_version),
+                StandardBroadcastRun::new(shred_version, tree_override),
             ),
 
             BroadcastStageType::FailEntryVerification => BroadcastStage::new(
@@ -695,7 +697,7 @@ pub mod test {
             blockstore.clone(),
             bank_forks,
             quic_endpoint_sender,
-            StandardBroadcastRun::new(0),
+            StandardBroadcastRun::new(0, None),
         );
 
         MockBroadcastStage {
@@ -6,14 +6,17 @@ use {
         *,
     },
     crate::{
-        broadcast_stage::broadcast_utils::UnfinishedSlotInfo, cluster_nodes::ClusterNodesCache,
+        broadcast_stage::broadcast_utils::UnfinishedSlotInfo,
+        cluster_nodes::{check_feature_activation, ClusterNodesCache, TurbineTreeOverride},
     },
     solana_entry::entry::Entry,
     solana_ledger::{
         blockstore,
         shred::{shred_code, ProcessShredsStats, ReedSolomonCache, Shred, ShredFlags, Shredder},
     },
+    solana_runtime::bank::Bank,
     solana_sdk::{
+        feature_set,
         genesis_config::ClusterType,
         hash::Hash,
         signature::Keypair,
@@ -44,11 +47,14 @@ enum BroadcastError {
 }
 
 impl StandardBroadcastRun {
-    pub(super) fn new(shred_version: u16) -> Self {
-        let cluster_nodes_cache = Arc::new(ClusterNodesCache::<BroadcastStage>::new(
-            CLUSTER_NODES_CACHE_NUM_EPOCH_CAP,
-            CLUSTER_NODES_CACHE_TTL,
-        ));
+    pub(super) fn new(shred_version: u16, tree_override: Option<TurbineTreeOverride>) -> Self {
+        let cluster_nodes_cache = Arc::new(
+            ClusterNodesCache::<BroadcastStage>::new(
+                CLUSTER_NODES_CACHE_NUM_EPOCH_CAP,
+                CLUSTER_NODES_CACHE_TTL,
+            )
+            .with_tree_override(tree_override),
+        );
         Self {
             process_shreds_stats: ProcessShredsStats::default(),
             transmit_shreds_stats: Arc::default(),
@@ -71,7 +77,7 @@ impl StandardBroadcastRun {
         &mut self,
         keypair: &Keypair,
         max_ticks_in_slot: u8,
-        cluster_type: ClusterType,
+        bank: &Bank,
         stats: &mut ProcessShredsStats,
     ) -> Vec<Shred> {
         const SHRED_TICK_REFERENCE_MASK: u8 = ShredFlags::SHRED_TICK_REFERENCE_MASK.bits();
@@ -83,12 +89,13 @@ impl StandardBroadcastRun {
                 let reference_tick = max_ticks_in_slot & SHRED_TICK_REFERENCE_MASK;
                 let shredder =
                     Shredder::new(state.slot, state.parent, reference_tick, self.shred_version)
-                        .unwrap();
+                        .unwrap()
+                        .with_erasure_config(should_emit_erasure_config(state.slot, bank));
                 let (mut shreds, coding_shreds) = shredder.entries_to_shreds(
                     keypair,
                     &[],  // entries
                     true, // is_last_in_slot,
-                    should_chain_merkle_shreds(state.slot, cluster_type)
+                    should_chain_merkle_shreds(state.slot, bank.cluster_type())
                         .then_some(state.chained_merkle_root),
                     state.next_shred_index,
                     state.next_code_index,
@@ -114,7 +121,7 @@ impl StandardBroadcastRun {
         blockstore: &Blockstore,
         reference_tick: u8,
         is_slot_end: bool,
-        cluster_type: ClusterType,
+        bank: &Bank,
         process_stats: &mut ProcessShredsStats,
         max_data_shreds_per_slot: u32,
         max_code_shreds_per_slot: u32,
@@ -157,13 +164,14 @@ impl StandardBroadcastRun {
                 (0u32, 0u32, chained_merkle_root)
             }
         };
-        let shredder =
-            Shredder::new(slot, parent_slot, reference_tick, self.shred_version).unwrap();
+        let shredder = Shredder::new(slot, parent_slot, reference_tick, self.shred_version)
+            .unwrap()
+            .with_erasure_config(should_emit_erasure_config(slot, bank));
         let (data_shreds, coding_shreds) = shredder.entries_to_shreds(
             keypair,
             entries,
             is_slot_end,
-            should_chain_merkle_shreds(slot, cluster_type).then_some(chained_merkle_root),
+            should_chain_merkle_shreds(slot, bank.cluster_type()).then_some(chained_merkle_root),
             next_shred_index,
             next_code_index,
             true, // merkle_variant
@@ -256,13 +264,12 @@ impl StandardBroadcastRun {
         let mut process_stats = ProcessShredsStats::default();
 
         let mut to_shreds_time = Measure::start("broadcast_to_shreds");
-        let cluster_type = bank.cluster_type();
 
         // 1) Check if slot was interrupted
         let prev_slot_shreds = self.finish_prev_slot(
             keypair,
             bank.ticks_per_slot() as u8,
-            cluster_type,
+            &bank,
             &mut process_stats,
         );
 
@@ -276,7 +283,7 @@ impl StandardBroadcastRun {
                 blockstore,
                 reference_tick as u8,
                 is_last_in_slot,
-                cluster_type,
+                &bank,
                 &mut process_stats,
                 blockstore::MAX_DATA_SHREDS_PER_SLOT as u32,
                 shred_code::MAX_CODE_SHREDS_PER_SLOT as u32,
@@ -531,6 +538,12 @@ fn should_chain_merkle_shreds(_slot: Slot, _cluster_type: ClusterType) -> bool {
     false
 }
 
+// Shreds are emitted with the erasure config header only once the receiving
+// nodes accept them, i.e. with the same rule as shred_fetch_stage.
+fn should_emit_erasure_config(slot: Slot, bank: &Bank) -> bool {
+    check_feature_activation(&feature_set::enable_shred_erasure_config::id(), slot, bank)
+}
+
 #[cfg(test)]
 mod test {
     use {
@@ -598,7 +611,7 @@ mod test {
     #[test]
     fn test_interrupted_slot_last_shred() {
         let keypair = Arc::new(Keypair::new());
-        let mut run = StandardBroadcastRun::new(0);
+        let mut run = StandardBroadcastRun::new(0, None);
 
         // Set up the slot to be interrupted
         let next_shred_index = 10;
@@ -617,10 +630,11 @@ mod test {
         run.current_slot_and_parent = Some((4, 2));
 
         // Slot 2 interrupted slot 1
+        let bank = Bank::new_for_tests(&create_genesis_config(10_000).genesis_config);
         let shreds = run.finish_prev_slot(
             &keypair,
             0, // max_ticks_in_slot
-            ClusterType::Development,
+            &bank,
             &mut ProcessShredsStats::default(),
         );
         let shred = shreds
@@ -655,7 +669,7 @@ mod test {
         };
 
         // Step 1: Make an incomplete transmission for slot 0
-        let mut standard_broadcast_run = StandardBroadcastRun::new(0);
+        let mut standard_broadcast_run = StandardBroadcastRun::new(0, None);
         standard_broadcast_run
             .test_process_receive_results(
                 &leader_keypair,
@@ -778,7 +792,7 @@ mod test {
         let (bsend, brecv) = unbounded();
         let (ssend, _srecv) = unbounded();
         let mut last_tick_height = 0;
-        let mut standard_broadcast_run = StandardBroadcastRun::new(0);
+        let mut standard_broadcast_run = StandardBroadcastRun::new(0, None);
         let mut process_ticks = |num_ticks| {
             let ticks = create_ticks(num_ticks, 0, genesis_config.hash());
             last_tick_height += (ticks.len() - 1) as u64;
@@ -836,7 +850,7 @@ mod test {
             last_tick_height: ticks.len() as u64,
         };
 
-        let mut standard_broadcast_run = StandardBroadcastRun::new(0);
+        let mut standard_broadcast_run = StandardBroadcastRun::new(0, None);
         standard_broadcast_run
             .test_process_receive_results(
                 &leader_keypair,
@@ -855,7 +869,7 @@ mod test {
     fn entries_to_shreds_max() {
         solana_logger::setup();
         let keypair = Keypair::new();
-        let mut bs = StandardBroadcastRun::new(0);
+        let mut bs = StandardBroadcastRun::new(0, None);
         bs.current_slot_and_parent = Some((1, 0));
         let entries = create_ticks(10_000, 1, solana_sdk::hash::Hash::default());
 
@@ -864,6 +878,7 @@ mod test {
             Blockstore::open(ledger_path.path())
                 .expect("Expected to be able to open database ledger"),
         );
+        let bank = Bank::new_for_tests(&create_genesis_config(10_000).genesis_config);
         let mut stats = ProcessShredsStats::default();
 
         let (data, coding) = bs
@@ -873,7 +888,7 @@ mod test {
                 &blockstore,
                 0,
                 false,
-                ClusterType::Development,
+                &bank,
                 &mut stats,
                 1000,
                 1000,
@@ -889,7 +904,7 @@ mod test {
             &blockstore,
             0,
             false,
-            ClusterType::Development,
+            &bank,
             &mut stats,
             10,
             10,
@@ -58,6 +58,21 @@ pub struct Node {
     stake: u64,
 }
 
+/// Overrides how the retransmit tree of each shred is laid out, so that
+/// multi-node tests and local clusters deterministically reproduce the paths
+/// shreds propagate through. All nodes of the cluster should be configured
+/// with the same override, or shreds may not reach all of them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TurbineTreeOverride {
+    /// Seeds the stake weighted shuffle of the nodes with this seed, instead
+    /// of the seed derived from each shred and its slot leader, so that every
+    /// shred propagates through the same tree.
+    Seed([u8; 32]),
+    /// Lays out the tree with the listed nodes first, in order from the root,
+    /// followed by the other nodes in their usual shuffled order.
+    Topology(Vec<Pubkey>),
+}
+
 pub struct ClusterNodes<T> {
     pubkey: Pubkey, // The local node itself.
     // All staked nodes + other known tvu-peers + the node itself;
@@ -66,6 +81,10 @@ pub struct ClusterNodes<T> {
     // Reverse index from nodes pubkey to their index in self.nodes.
     index: HashMap<Pubkey, /*index:*/ usize>,
     weighted_shuffle: WeightedShuffle</*stake:*/ u64>,
+    // Seed overriding the shuffle seed of each shred; see TurbineTreeOverride.
+    seed_override: Option<[u8; 32]>,
+    // Rank of the nodes in an overridden topology; see TurbineTreeOverride.
+    topology_override: HashMap<Pubkey, /*rank:*/ usize>,
     _phantom: PhantomData<T>,
 }
 
@@ -76,6 +95,7 @@ pub struct ClusterNodesCache<T> {
     // one thread does the computations to update the entry for the epoch.
     cache: Mutex<LruCache<Epoch, Arc<RwLock<CacheEntry<T>>>>>,
     ttl: Duration, // Time to live.
+    tree_override: Option<TurbineTreeOverride>,
 }
 
 pub struct RetransmitPeers<'a> {
@@ -105,6 +125,36 @@ impl Node {
 }
 
 impl<T> ClusterNodes<T> {
+    /// Applies the override to the retransmit trees of all shreds.
+    pub fn with_tree_override(mut self, tree_override: Option<&TurbineTreeOverride>) -> Self {
+        self.seed_override = None;
+        self.topology_override.clear();
+        match tree_override {
+            None => (),
+            Some(TurbineTreeOverride::Seed(seed)) => self.seed_override = Some(*seed),
+            Some(TurbineTreeOverride::Topology(pubkeys)) => {
+                for (rank, pubkey) in pubkeys.iter().enumerate() {
+                    self.topology_override.entry(*pubkey).or_insert(rank);
+                }
+            }
+        }
+        self
+    }
+
+    fn shred_seed(&self, shred: &ShredId, slot_leader: &Pubkey) -> [u8; 32] {
+        self.seed_override
+            .unwrap_or_else(|| shred.seed(slot_leader))
+    }
+
+    // Rank of the node in the overridden topology; nodes not in the topology
+    // rank last.
+    fn topology_rank(&self, node: &Node) -> usize {
+        self.topology_override
+            .get(&node.pubkey())
+            .copied()
+            .unwrap_or(usize::MAX)
+    }
+
     pub(crate) fn submit_metrics(&self, name: &'static str, now: u64) {
         let mut epoch_stakes = 0;
         let mut num_nodes_dead = 0;
@@ -152,7 +202,18 @@ impl ClusterNodes<BroadcastStage> {
     }
 
     pub(crate) fn get_broadcast_peer(&self, shred: &ShredId) -> Option<&ContactInfo> {
-        let shred_seed = shred.seed(&self.pubkey);
+        // The root of an overridden topology is its first node other than
+        // the slot leader, i.e. this node.
+        let root = self
+            .topology_override
+            .iter()
+            .filter(|(pubkey, _)| **pubkey != self.pubkey)
+            .filter_map(|(pubkey, rank)| Some((*rank, *self.index.get(pubkey)?)))
+            .min();
+        if let Some((_rank, index)) = root {
+            return self.nodes[index].contact_info();
+        }
+        let shred_seed = self.shred_seed(shred, &self.pubkey);
         let mut rng = ChaChaRng::from_seed(shred_seed);
         let index = self.weighted_shuffle.first(&mut rng)?;
         self.nodes[index].contact_info()
@@ -187,7 +248,7 @@ impl ClusterNodes<RetransmitStage> {
         shred: &ShredId,
         fanout: usize,
     ) -> Result<RetransmitPeers, Error> {
-        let shred_seed = shred.seed(slot_leader);
+        let shred_seed = self.shred_seed(shred, slot_leader);
         let mut weighted_shuffle = self.weighted_shuffle.clone();
         // Exclude slot leader from list of nodes.
         if slot_leader == &self.pubkey {
@@ -202,17 +263,20 @@ impl ClusterNodes<RetransmitStage> {
         let mut addrs = HashMap::<SocketAddr, Pubkey>::with_capacity(self.nodes.len());
         let mut rng = ChaChaRng::from_seed(shred_seed);
         let protocol = get_broadcast_protocol(shred);
-        let nodes: Vec<_> = weighted_shuffle
+        let mut nodes: Vec<_> = weighted_shuffle
             .shuffle(&mut rng)
             .map(|index| &self.nodes[index])
-            .inspect(|node| {
-                if let Some(node) = node.contact_info() {
-                    if let Ok(addr) = node.tvu(protocol) {
-                        addrs.entry(addr).or_insert(*node.pubkey());
-                    }
-                }
-            })
+            .inspect(|node| insert_tvu_addr(&mut addrs, node, protocol))
             .collect();
+        if !self.topology_override.is_empty() {
+            // The sort is stable, so the nodes not in the topology keep their
+            // shuffled order.
+            nodes.sort_by_key(|node| self.topology_rank(node));
+            addrs.clear();
+            for node in &nodes {
+                insert_tvu_addr(&mut addrs, node, protocol);
+            }
+        }
         let self_index = nodes
             .iter()
             .position(|node| node.pubkey() == self.pubkey)
@@ -257,10 +321,22 @@ pub fn new_cluster_nodes<T: 'static>(
         nodes,
         index,
         weighted_shuffle,
+        seed_override: None,
+        topology_override: HashMap::default(),
         _phantom: PhantomData,
     }
 }
 
+// Maps the tvu address of the node to the node, unless already mapped to a
+// node earlier in the shuffle.
+fn insert_tvu_addr(addrs: &mut HashMap<SocketAddr, Pubkey>, node: &Node, protocol: Protocol) {
+    if let Some(node) = node.contact_info() {
+        if let Ok(addr) = node.tvu(protocol) {
+            addrs.entry(addr).or_insert(*node.pubkey());
+        }
+    }
+}
+
 // All staked nodes + other known tvu-peers + the node itself;
 // sorted by (stake, pubkey) in descending order.
 fn get_nodes(cluster_info: &ClusterInfo, stakes: &HashMap<Pubkey, u64>) -> Vec<Node> {
@@ -338,8 +414,16 @@ impl<T> ClusterNodesCache<T> {
         Self {
             cache: Mutex::new(LruCache::new(cap)),
             ttl,
+            tree_override: None,
         }
     }
+
+    /// Applies the override to the retransmit trees of all cluster nodes
+    /// returned by the cache.
+    pub fn with_tree_override(mut self, tree_override: Option<TurbineTreeOverride>) -> Self {
+        self.tree_override = tree_override;
+        self
+    }
 }
 
 impl<T: 'static> ClusterNodesCache<T> {
@@ -388,10 +472,10 @@ impl<T: 'static> ClusterNodesCache<T> {
             }
             inc_new_counter_info!("cluster_nodes-unknown_epoch_staked_nodes_root", 1);
         }
-        let nodes = Arc::new(new_cluster_nodes::<T>(
-            cluster_info,
-            &epoch_staked_nodes.unwrap_or_default(),
-        ));
+        let nodes = Arc::new(
+            new_cluster_nodes::<T>(cluster_info, &epoch_staked_nodes.unwrap_or_default())
+                .with_tree_override(self.tree_override.as_ref()),
+        );
         *entry = Some((Instant::now(), Arc::clone(&nodes)));
         nodes
     }
@@ -527,7 +611,10 @@ pub fn check_feature_activation(feature: &Pubkey, shred_slot: Slot, root_bank: &
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use {
+        super::*,
+        solana_ledger::shred::{Shred, ShredFlags},
+    };
 
     #[test]
     fn test_cluster_nodes_retransmit() {
@@ -681,4 +768,76 @@ mod tests {
             assert_eq!(retransmit_peers.next(), None);
         }
     }
+
+    #[test]
+    fn test_turbine_tree_override() {
+        let mut rng = rand::thread_rng();
+        let (nodes, stakes, cluster_info) = make_test_cluster(&mut rng, 1_000, None);
+        let slot_leader = *nodes[1].pubkey();
+        let shreds: Vec<_> = [(11, 3), (11, 7), (12, 3)]
+            .into_iter()
+            .map(|(slot, index)| {
+                Shred::new_from_data(slot, index, 1, &[], ShredFlags::empty(), 0, 0, 0).id()
+            })
+            .collect();
+        let get_children = |cluster_nodes: &ClusterNodes<RetransmitStage>, shred: &ShredId| {
+            let RetransmitPeers {
+                root_distance,
+                children,
+                ..
+            } = cluster_nodes
+                .get_retransmit_peers(&slot_leader, shred, /*fanout:*/ 2)
+                .unwrap();
+            let children: Vec<_> = children.into_iter().map(Node::pubkey).collect();
+            (root_distance, children)
+        };
+
+        // All shreds propagate through the same tree.
+        let tree_override = TurbineTreeOverride::Seed([7u8; 32]);
+        let cluster_nodes = new_cluster_nodes::<RetransmitStage>(&cluster_info, &stakes)
+            .with_tree_override(Some(&tree_override));
+        let children = get_children(&cluster_nodes, &shreds[0]);
+        for shred in &shreds[1..] {
+            assert_eq!(get_children(&cluster_nodes, shred), children);
+        }
+        let cluster_nodes = ClusterNodes::<BroadcastStage>::new(&cluster_info, &stakes)
+            .with_tree_override(Some(&tree_override));
+        let root = cluster_nodes.get_broadcast_peer(&shreds[0]).unwrap();
+        for shred in &shreds[1..] {
+            assert_eq!(cluster_nodes.get_broadcast_peer(shred), Some(root));
+        }
+
+        // The tree is laid out in the order of the topology, excluding the
+        // slot leader.
+        let topology: Vec<_> = [1, 2, 3, 0, 4, 5, 6, 7]
+            .into_iter()
+            .map(|k| *nodes[k].pubkey())
+            .collect();
+        let tree_override = TurbineTreeOverride::Topology(topology);
+        let cluster_nodes = new_cluster_nodes::<RetransmitStage>(&cluster_info, &stakes)
+            .with_tree_override(Some(&tree_override));
+        for shred in &shreds {
+            // This node is at index 2 of the tree, in the 1st layer.
+            assert_eq!(
+                get_children(&cluster_nodes, shred),
+                (1, vec![*nodes[5].pubkey(), *nodes[7].pubkey()])
+            );
+        }
+        let cluster_nodes =
+            ClusterNodes::<BroadcastStage>::new(&cluster_info, &stakes).with_tree_override(Some(
+                &TurbineTreeOverride::Topology(vec![*nodes[0].pubkey(), *nodes[2].pubkey()]),
+            ));
+        for shred in &shreds {
+            assert_eq!(
+                cluster_nodes
+                    .get_broadcast_peer(shred)
+                    .map(ContactInfo::pubkey),
+                Some(nodes[2].pubkey())
+            );
+        }
+        // Without the override, the shuffle is as usual.
+        let cluster_nodes = cluster_nodes.with_tree_override(None);
+        assert!(cluster_nodes.topology_override.is_empty());
+        assert_eq!(cluster_nodes.seed_override, None);
+    }
 }
@@ -2,7 +2,9 @@
 #![allow(clippy::rc_buffer)]
 
 use {
-    crate::cluster_nodes::{self, ClusterNodes, ClusterNodesCache, Error, MAX_NUM_TURBINE_HOPS},
+    crate::cluster_nodes::{
+        self, ClusterNodes, ClusterNodesCache, Error, TurbineTreeOverride, MAX_NUM_TURBINE_HOPS,
+    },
     bytes::Bytes,
     crossbeam_channel::{Receiver, RecvTimeoutError},
     itertools::{izip, Itertools},
@@ -207,7 +209,7 @@ fn retransmit(
     let shreds: Vec<_> = shreds
         .into_iter()
         .filter_map(|shred| {
-            let key = shred::layout::get_shred_id(&shred)?;
+            let key = shred::layout::ShredView::new(&shred).ok()?.id();
             if shred_deduper.dedup(key, &shred, MAX_DUPLICATE_COUNT) {
                 stats.num_shreds_skipped += 1;
                 None
@@ -369,6 +371,7 @@ fn retransmit_shred(
 /// * `leader_schedule_cache` - The leader schedule to verify shreds
 /// * `cluster_info` - This structure needs to be updated and populated by the bank and via gossip.
 /// * `r` - Receive channel for shreds to be retransmitted to all the layer 1 nodes.
+/// * `tree_override` - Overrides the retransmit tree, for tests and simulations.
 pub fn retransmitter(
     sockets: Arc<Vec<UdpSocket>>,
     quic_endpoint_sender: AsyncSender<(SocketAddr, Bytes)>,
@@ -378,11 +381,13 @@ pub fn retransmitter(
     shreds_receiver: Receiver<Vec</*shred:*/ Vec<u8>>>,
     max_slots: Arc<MaxSlots>,
     rpc_subscriptions: Option<Arc<RpcSubscriptions>>,
+    tree_override: Option<TurbineTreeOverride>,
 ) -> JoinHandle<()> {
     let cluster_nodes_cache = ClusterNodesCache::<RetransmitStage>::new(
         CLUSTER_NODES_CACHE_NUM_EPOCH_CAP,
         CLUSTER_NODES_CACHE_TTL,
-    );
+    )
+    .with_tree_override(tree_override);
     let mut rng = rand::thread_rng();
     let mut shred_deduper = ShredDeduper::<2>::new(&mut rng, DEDUPER_NUM_BITS);
     let mut stats = RetransmitStats::new(Instant::now());
@@ -432,6 +437,7 @@ impl RetransmitStage {
         retransmit_receiver: Receiver<Vec</*shred:*/ Vec<u8>>>,
         max_slots: Arc<MaxSlots>,
         rpc_subscriptions: Option<Arc<RpcSubscriptions>>,
+        tree_override: Option<TurbineTreeOverride>,
     ) -> Self {
         let retransmit_thread_handle = retransmitter(
             retransmit_sockets,
@@ -442,6 +448,7 @@ impl RetransmitStage {
             retransmit_receiver,
             max_slots,
             rpc_subscriptions,
+            tree_override,
         );
 
         Self {
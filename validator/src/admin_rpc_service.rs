@@ -9,18 +9,25 @@ use {
     jsonrpc_server_utils::tokio,
     log::*,
     serde::{de::Deserializer, Deserialize, Serialize},
-    solana_accounts_db::accounts_index::AccountIndex,
+    solana_accounts_db::{accounts_index::AccountIndex, ancient_append_vecs::AncientStorageTuning},
     solana_core::{
         admin_rpc_post_init::AdminRpcRequestMetadataPostInit,
+        banking_stage::{
+            account_contention::ContentionWindow, forwarding_policy::ForwardingPolicy,
+        },
         consensus::{tower_storage::TowerStorage, Tower},
         repair::repair_service,
         validator::ValidatorStartProgress,
     },
+    solana_cost_model::block_cost_limits::{
+        BlockProductionLimits, MAX_BLOCK_UNITS, MAX_VOTE_UNITS,
+    },
     solana_geyser_plugin_manager::GeyserPluginManagerRequest,
     solana_gossip::contact_info::{ContactInfo, Protocol, SOCKET_ADDR_UNSPECIFIED},
     solana_rpc::rpc::verify_pubkey,
     solana_rpc_client_api::{config::RpcAccountIndex, custom_error::RpcCustomError},
     solana_sdk::{
+        clock::{Epoch, Slot},
         exit::Exit,
         pubkey::Pubkey,
         signature::{read_keypair_file, Keypair, Signer},
@@ -31,12 +38,16 @@ use {
         fmt::{self, Display},
         net::SocketAddr,
         path::{Path, PathBuf},
-        sync::{Arc, RwLock},
+        sync::{atomic::Ordering, Arc, RwLock},
         thread::{self, Builder},
         time::{Duration, SystemTime},
     },
 };
 
+/// Number of most contended accounts listed per window by `accountContention`
+/// when no maximum is requested
+pub const DEFAULT_ACCOUNT_CONTENTION_MAX_ACCOUNTS: usize = 20;
+
 #[derive(Clone)]
 pub struct AdminRpcRequestMetadata {
     pub rpc_addr: Option<SocketAddr>,
@@ -46,6 +57,8 @@ pub struct AdminRpcRequestMetadata {
     pub authorized_voter_keypairs: Arc<RwLock<Vec<Arc<Keypair>>>>,
     pub tower_storage: Arc<dyn TowerStorage>,
     pub staked_nodes_overrides: Arc<RwLock<HashMap<Pubkey, u64>>>,
+    pub forwarding_policy: Arc<RwLock<ForwardingPolicy>>,
+    pub block_production_limits: Arc<RwLock<BlockProductionLimits>>,
     pub post_init: Arc<RwLock<Option<AdminRpcRequestMetadataPostInit>>>,
     pub rpc_to_plugin_manager_sender: Option<Sender<GeyserPluginManagerRequest>>,
 }
@@ -89,6 +102,118 @@ pub struct AdminRpcRepairWhitelist {
     pub whitelist: Vec<Pubkey>,
 }
 
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminRpcAccountsDbReadCacheInfo {
+    pub limit_bytes: usize,
+    pub data_size_bytes: usize,
+    pub entries: usize,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminRpcProgramCacheEntry {
+    pub program_id: String,
+    pub program_type: String,
+    pub deployment_slot: Slot,
+    pub effective_slot: Slot,
+    pub tx_usage_count: u64,
+    pub ix_usage_count: u64,
+    pub latest_access_slot: Slot,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminRpcProgramCacheInfo {
+    pub capacity: usize,
+    pub eviction_strategy: String,
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+    pub recompilations: u64,
+    pub entries: Vec<AdminRpcProgramCacheEntry>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminRpcComputeCost {
+    pub name: String,
+    pub compute_units: u64,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminRpcComputeCostModel {
+    pub signature_cost: u64,
+    pub write_lock_cost: u64,
+    pub account_cost_limit: u64,
+    pub instruction_data_bytes_cost: u64,
+    pub heap_cost: u64,
+    /// named by program id
+    pub builtin_instruction_costs: Vec<AdminRpcComputeCost>,
+    pub syscall_costs: Vec<AdminRpcComputeCost>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminRpcAccountContention {
+    pub account: String,
+    pub lock_conflicts: u64,
+    pub retries: u64,
+    pub forwarded: u64,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminRpcContentionWindow {
+    pub start_slot: Slot,
+    pub end_slot: Slot,
+    /// most contended first
+    pub accounts: Vec<AdminRpcAccountContention>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminRpcAccountContentionInfo {
+    /// current window first
+    pub windows: Vec<AdminRpcContentionWindow>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminRpcBlockProductionLimits {
+    /// limits this node packs its blocks to
+    pub block_cost_limit: u64,
+    pub account_cost_limit: u64,
+    pub vote_cost_limit: u64,
+    /// limits of the protocol
+    pub protocol_block_cost_limit: u64,
+    pub protocol_account_cost_limit: u64,
+    pub protocol_vote_cost_limit: u64,
+}
+
+impl AdminRpcContentionWindow {
+    fn new(window: &ContentionWindow, max_accounts: usize) -> Self {
+        Self {
+            start_slot: window.start_slot,
+            end_slot: window
+                .start_slot
+                .saturating_add(window.num_slots)
+                .saturating_sub(1),
+            accounts: window
+                .most_contended(max_accounts)
+                .into_iter()
+                .map(|(account, contention)| AdminRpcAccountContention {
+                    account: account.to_string(),
+                    lock_conflicts: contention.lock_conflicts,
+                    retries: contention.retries,
+                    forwarded: contention.forwarded,
+                })
+                .collect(),
+        }
+    }
+}
+
 impl From<ContactInfo> for AdminRpcContactInfo {
     fn from(node: ContactInfo) -> Self {
         macro_rules! unwrap_socket {
@@ -140,6 +265,154 @@ impl Display for AdminRpcRepairWhitelist {
     }
 }
 
+impl Display for AdminRpcProgramCacheInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Capacity: {} programs", self.capacity)?;
+        writeln!(f, "Eviction strategy: {}", self.eviction_strategy)?;
+        writeln!(
+            f,
+            "Hits: {}, Misses: {}, Evictions: {}, Recompilations: {}",
+            self.hits, self.misses, self.evictions, self.recompilations
+        )?;
+        writeln!(
+            f,
+            "{:<44}  {:<18}  {:>10}  {:>10}  {:>10}  {:>10}",
+            "Program", "Type", "Deployed", "Accessed", "Tx Uses", "Ix Uses"
+        )?;
+        for entry in &self.entries {
+            writeln!(
+                f,
+                "{:<44}  {:<18}  {:>10}  {:>10}  {:>10}  {:>10}",
+                entry.program_id,
+                entry.program_type,
+                entry.deployment_slot,
+                entry.latest_access_slot,
+                entry.tx_usage_count,
+                entry.ix_usage_count,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for AdminRpcComputeCostModel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Signature: {} CUs", self.signature_cost)?;
+        writeln!(f, "Write lock: {} CUs", self.write_lock_cost)?;
+        writeln!(
+            f,
+            "Writable account limit: {} CUs per block",
+            self.account_cost_limit
+        )?;
+        writeln!(
+            f,
+            "Instruction data: {} bytes per CU",
+            self.instruction_data_bytes_cost
+        )?;
+        writeln!(f, "Heap: {} CUs per 32k page", self.heap_cost)?;
+        writeln!(f, "Builtin instructions:")?;
+        for cost in &self.builtin_instruction_costs {
+            writeln!(f, "  {:<44}  {:>10}", cost.name, cost.compute_units)?;
+        }
+        writeln!(f, "Syscalls:")?;
+        for cost in &self.syscall_costs {
+            writeln!(f, "  {:<44}  {:>10}", cost.name, cost.compute_units)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for AdminRpcAccountContentionInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for window in &self.windows {
+            writeln!(f, "Slots {} to {}:", window.start_slot, window.end_slot)?;
+            writeln!(
+                f,
+                "  {:<44}  {:>14}  {:>10}  {:>10}",
+                "Account", "Lock Conflicts", "Retries", "Forwarded"
+            )?;
+            for account in &window.accounts {
+                writeln!(
+                    f,
+                    "  {:<44}  {:>14}  {:>10}  {:>10}",
+                    account.account, account.lock_conflicts, account.retries, account.forwarded,
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Display for AdminRpcBlockProductionLimits {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "Block limit: {} CUs (protocol limit: {} CUs)",
+            self.block_cost_limit, self.protocol_block_cost_limit
+        )?;
+        writeln!(
+            f,
+            "Writable account limit: {} CUs (protocol limit: {} CUs)",
+            self.account_cost_limit, self.protocol_account_cost_limit
+        )?;
+        writeln!(
+            f,
+            "Vote limit: {} CUs (protocol limit: {} CUs)",
+            self.vote_cost_limit, self.protocol_vote_cost_limit
+        )
+    }
+}
+
+impl Display for AdminRpcAccountsDbReadCacheInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Read cache limit: {} bytes", self.limit_bytes)?;
+        writeln!(f, "Read cache data size: {} bytes", self.data_size_bytes)?;
+        writeln!(f, "Read cache entries: {}", self.entries)
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminRpcAncientStorageInfo {
+    /// None if ancient append vecs are disabled
+    pub offset: Option<i64>,
+    pub max_ancient_slots: usize,
+    pub percent_of_alive_shrunk_data: u64,
+    pub shrink_alive_percent: u64,
+    pub storages: u64,
+    pub bytes: u64,
+    pub wasted_bytes: u64,
+}
+
+impl Display for AdminRpcAncientStorageInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.offset {
+            Some(offset) => writeln!(f, "Ancient slot offset: {offset}")?,
+            None => writeln!(f, "Ancient slot offset: disabled")?,
+        }
+        writeln!(f, "Max ancient storages: {}", self.max_ancient_slots)?;
+        writeln!(
+            f,
+            "Shrink data percent: {}%",
+            self.percent_of_alive_shrunk_data
+        )?;
+        writeln!(f, "Shrink alive percent: {}%", self.shrink_alive_percent)?;
+        writeln!(f, "Ancient storages: {}", self.storages)?;
+        writeln!(f, "Ancient storage size: {} bytes", self.bytes)?;
+        writeln!(f, "Ancient storage wasted: {} bytes", self.wasted_bytes)
+    }
+}
+
+/// Changes to the ancient storage tuning, fields which are None are left unchanged
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminRpcAncientStorageConfig {
+    pub offset: Option<i64>,
+    pub max_ancient_slots: Option<usize>,
+    pub percent_of_alive_shrunk_data: Option<u64>,
+    pub shrink_alive_percent: Option<u64>,
+}
+
 #[rpc]
 pub trait AdminRpc {
     type Metadata;
@@ -205,6 +478,27 @@ pub trait AdminRpc {
     #[rpc(meta, name = "setStakedNodesOverrides")]
     fn set_staked_nodes_overrides(&self, meta: Self::Metadata, path: String) -> Result<()>;
 
+    #[rpc(meta, name = "forwardingPolicy")]
+    fn forwarding_policy(&self, meta: Self::Metadata) -> Result<String>;
+
+    #[rpc(meta, name = "setForwardingPolicy")]
+    fn set_forwarding_policy(&self, meta: Self::Metadata, policy: String) -> Result<()>;
+
+    #[rpc(meta, name = "blockProductionLimits")]
+    fn block_production_limits(
+        &self,
+        meta: Self::Metadata,
+    ) -> Result<AdminRpcBlockProductionLimits>;
+
+    #[rpc(meta, name = "setBlockProductionLimits")]
+    fn set_block_production_limits(
+        &self,
+        meta: Self::Metadata,
+        block_cost_limit: Option<u64>,
+        account_cost_limit: Option<u64>,
+        vote_cost_limit: Option<u64>,
+    ) -> Result<()>;
+
     #[rpc(meta, name = "contactInfo")]
     fn contact_info(&self, meta: Self::Metadata) -> Result<AdminRpcContactInfo>;
 
@@ -223,6 +517,42 @@ pub trait AdminRpc {
     #[rpc(meta, name = "setRepairWhitelist")]
     fn set_repair_whitelist(&self, meta: Self::Metadata, whitelist: Vec<Pubkey>) -> Result<()>;
 
+    #[rpc(meta, name = "programCacheInfo")]
+    fn program_cache_info(&self, meta: Self::Metadata) -> Result<AdminRpcProgramCacheInfo>;
+
+    #[rpc(meta, name = "computeCostModel")]
+    fn compute_cost_model(&self, meta: Self::Metadata) -> Result<AdminRpcComputeCostModel>;
+
+    #[rpc(meta, name = "accountContention")]
+    fn account_contention(
+        &self,
+        meta: Self::Metadata,
+        max_accounts: Option<usize>,
+    ) -> Result<AdminRpcAccountContentionInfo>;
+
+    #[rpc(meta, name = "accountsDbReadCacheInfo")]
+    fn accounts_db_read_cache_info(
+        &self,
+        meta: Self::Metadata,
+    ) -> Result<AdminRpcAccountsDbReadCacheInfo>;
+
+    #[rpc(meta, name = "setAccountsDbReadCacheLimit")]
+    fn set_accounts_db_read_cache_limit(
+        &self,
+        meta: Self::Metadata,
+        limit_bytes: usize,
+    ) -> Result<()>;
+
+    #[rpc(meta, name = "ancientStorageInfo")]
+    fn ancient_storage_info(&self, meta: Self::Metadata) -> Result<AdminRpcAncientStorageInfo>;
+
+    #[rpc(meta, name = "setAncientStorageConfig")]
+    fn set_ancient_storage_config(
+        &self,
+        meta: Self::Metadata,
+        config: AdminRpcAncientStorageConfig,
+    ) -> Result<()>;
+
     #[rpc(meta, name = "getSecondaryIndexKeySize")]
     fn get_secondary_index_key_size(
         &self,
@@ -243,6 +573,14 @@ pub trait AdminRpc {
         meta: Self::Metadata,
         public_tpu_forwards_addr: SocketAddr,
     ) -> Result<()>;
+
+    #[rpc(meta, name = "setHashesPerTick")]
+    fn set_hashes_per_tick(
+        &self,
+        meta: Self::Metadata,
+        hashes_per_tick: u64,
+        activation_epoch: Option<Epoch>,
+    ) -> Result<Epoch>;
 }
 
 pub struct AdminRpcImpl;
@@ -493,6 +831,73 @@ impl AdminRpc for AdminRpcImpl {
         Ok(())
     }
 
+    fn forwarding_policy(&self, meta: Self::Metadata) -> Result<String> {
+        debug!("forwarding_policy request received");
+        Ok(meta.forwarding_policy.read().unwrap().to_string())
+    }
+
+    fn set_forwarding_policy(&self, meta: Self::Metadata, policy: String) -> Result<()> {
+        debug!("set_forwarding_policy request received: {policy}");
+        let policy = policy
+            .parse::<ForwardingPolicy>()
+            .map_err(jsonrpc_core::error::Error::invalid_params)?;
+        *meta.forwarding_policy.write().unwrap() = policy;
+        warn!("Forwarding policy set to {policy}");
+        Ok(())
+    }
+
+    fn block_production_limits(
+        &self,
+        meta: Self::Metadata,
+    ) -> Result<AdminRpcBlockProductionLimits> {
+        debug!("block_production_limits request received");
+        let limits = *meta.block_production_limits.read().unwrap();
+
+        meta.with_post_init(|post_init| {
+            let bank = post_init.bank_forks.read().unwrap().root_bank();
+            let protocol_account_cost_limit = bank.compute_cost_model().account_cost_limit();
+            let limit = |limit: Option<u64>, protocol_limit: u64| {
+                limit.map_or(protocol_limit, |limit| limit.min(protocol_limit))
+            };
+            Ok(AdminRpcBlockProductionLimits {
+                block_cost_limit: limit(limits.block_cost_limit, MAX_BLOCK_UNITS),
+                account_cost_limit: limit(limits.account_cost_limit, protocol_account_cost_limit),
+                vote_cost_limit: limit(limits.vote_cost_limit, MAX_VOTE_UNITS),
+                protocol_block_cost_limit: MAX_BLOCK_UNITS,
+                protocol_account_cost_limit,
+                protocol_vote_cost_limit: MAX_VOTE_UNITS,
+            })
+        })
+    }
+
+    fn set_block_production_limits(
+        &self,
+        meta: Self::Metadata,
+        block_cost_limit: Option<u64>,
+        account_cost_limit: Option<u64>,
+        vote_cost_limit: Option<u64>,
+    ) -> Result<()> {
+        debug!(
+            "set_block_production_limits request received: {block_cost_limit:?} \
+             {account_cost_limit:?} {vote_cost_limit:?}"
+        );
+        let limits = BlockProductionLimits {
+            block_cost_limit,
+            account_cost_limit,
+            vote_cost_limit,
+        };
+
+        meta.with_post_init(|post_init| {
+            let bank = post_init.bank_forks.read().unwrap().root_bank();
+            limits
+                .check(bank.compute_cost_model().account_cost_limit())
+                .map_err(|err| jsonrpc_core::error::Error::invalid_params(err.to_string()))
+        })?;
+        *meta.block_production_limits.write().unwrap() = limits;
+        warn!("Block production limits set to {limits:?}");
+        Ok(())
+    }
+
     fn contact_info(&self, meta: Self::Metadata) -> Result<AdminRpcContactInfo> {
         meta.with_post_init(|post_init| Ok(post_init.cluster_info.my_contact_info().into()))
     }
@@ -549,6 +954,190 @@ impl AdminRpc for AdminRpcImpl {
         })
     }
 
+    fn program_cache_info(&self, meta: Self::Metadata) -> Result<AdminRpcProgramCacheInfo> {
+        debug!("program_cache_info request received");
+
+        meta.with_post_init(|post_init| {
+            let bank = post_init.bank_forks.read().unwrap().root_bank();
+            let loaded_programs_cache = bank.loaded_programs_cache.read().unwrap();
+            let stats = loaded_programs_cache.get_cumulative_stats();
+            let mut entries = loaded_programs_cache
+                .get_all_entries()
+                .into_iter()
+                .map(|(program_id, entry)| AdminRpcProgramCacheEntry {
+                    program_id: program_id.to_string(),
+                    program_type: entry.program.name().to_string(),
+                    deployment_slot: entry.deployment_slot,
+                    effective_slot: entry.effective_slot,
+                    tx_usage_count: entry.tx_usage_counter.load(Ordering::Relaxed),
+                    ix_usage_count: entry.ix_usage_counter.load(Ordering::Relaxed),
+                    latest_access_slot: entry.latest_access_slot.load(Ordering::Relaxed),
+                })
+                .collect::<Vec<_>>();
+            // most used first, which is what matters when tuning the capacity
+            entries.sort_by(|a, b| b.tx_usage_count.cmp(&a.tx_usage_count));
+            Ok(AdminRpcProgramCacheInfo {
+                capacity: loaded_programs_cache.config.capacity,
+                eviction_strategy: loaded_programs_cache
+                    .config
+                    .eviction_strategy
+                    .as_str()
+                    .to_string(),
+                hits: stats.hits,
+                misses: stats.misses,
+                evictions: stats.evictions,
+                recompilations: stats.recompilations,
+                entries,
+            })
+        })
+    }
+
+    fn compute_cost_model(&self, meta: Self::Metadata) -> Result<AdminRpcComputeCostModel> {
+        debug!("compute_cost_model request received");
+
+        meta.with_post_init(|post_init| {
+            let bank = post_init.bank_forks.read().unwrap().root_bank();
+            let compute_cost_model = bank.compute_cost_model();
+            Ok(AdminRpcComputeCostModel {
+                signature_cost: compute_cost_model.signature_cost(),
+                write_lock_cost: compute_cost_model.write_lock_cost(),
+                account_cost_limit: compute_cost_model.account_cost_limit(),
+                instruction_data_bytes_cost: compute_cost_model.instruction_data_bytes_cost(),
+                heap_cost: compute_cost_model.heap_cost(),
+                builtin_instruction_costs: compute_cost_model
+                    .builtin_instruction_costs()
+                    .into_iter()
+                    .map(|(program_id, compute_units)| AdminRpcComputeCost {
+                        name: program_id.to_string(),
+                        compute_units,
+                    })
+                    .collect(),
+                syscall_costs: compute_cost_model
+                    .syscall_costs()
+                    .into_iter()
+                    .map(|(name, compute_units)| AdminRpcComputeCost {
+                        name: name.to_string(),
+                        compute_units,
+                    })
+                    .collect(),
+            })
+        })
+    }
+
+    fn account_contention(
+        &self,
+        meta: Self::Metadata,
+        max_accounts: Option<usize>,
+    ) -> Result<AdminRpcAccountContentionInfo> {
+        debug!("account_contention request received");
+        let max_accounts = max_accounts.unwrap_or(DEFAULT_ACCOUNT_CONTENTION_MAX_ACCOUNTS);
+
+        meta.with_post_init(|post_init| {
+            Ok(AdminRpcAccountContentionInfo {
+                windows: post_init
+                    .account_contention
+                    .windows()
+                    .iter()
+                    .map(|window| AdminRpcContentionWindow::new(window, max_accounts))
+                    .collect(),
+            })
+        })
+    }
+
+    fn accounts_db_read_cache_info(
+        &self,
+        meta: Self::Metadata,
+    ) -> Result<AdminRpcAccountsDbReadCacheInfo> {
+        debug!("accounts_db_read_cache_info request received");
+
+        meta.with_post_init(|post_init| {
+            let bank = post_init.bank_forks.read().unwrap().root_bank();
+            let accounts_db = &bank.accounts().accounts_db;
+            let (data_size_bytes, entries) = accounts_db.read_only_accounts_cache_usage();
+            Ok(AdminRpcAccountsDbReadCacheInfo {
+                limit_bytes: accounts_db.read_only_accounts_cache_max_data_size(),
+                data_size_bytes,
+                entries,
+            })
+        })
+    }
+
+    fn set_accounts_db_read_cache_limit(
+        &self,
+        meta: Self::Metadata,
+        limit_bytes: usize,
+    ) -> Result<()> {
+        debug!("set_accounts_db_read_cache_limit request received: {limit_bytes}");
+
+        meta.with_post_init(|post_init| {
+            let bank = post_init.bank_forks.read().unwrap().root_bank();
+            bank.accounts()
+                .accounts_db
+                .set_read_only_accounts_cache_max_data_size(limit_bytes);
+            warn!("Accounts-db read cache limit set to {limit_bytes} bytes");
+            Ok(())
+        })
+    }
+
+    fn ancient_storage_info(&self, meta: Self::Metadata) -> Result<AdminRpcAncientStorageInfo> {
+        debug!("ancient_storage_info request received");
+
+        meta.with_post_init(|post_init| {
+            let bank = post_init.bank_forks.read().unwrap().root_bank();
+            let accounts_db = &bank.accounts().accounts_db;
+            let tuning = accounts_db.ancient_storage_tuning();
+            let usage = accounts_db.ancient_storage_usage();
+            Ok(AdminRpcAncientStorageInfo {
+                offset: accounts_db.ancient_append_vec_offset(),
+                max_ancient_slots: tuning.max_ancient_slots,
+                percent_of_alive_shrunk_data: tuning.percent_of_alive_shrunk_data,
+                shrink_alive_percent: tuning.shrink_alive_percent,
+                storages: usage.storages,
+                bytes: usage.bytes,
+                wasted_bytes: usage.wasted_bytes,
+            })
+        })
+    }
+
+    fn set_ancient_storage_config(
+        &self,
+        meta: Self::Metadata,
+        config: AdminRpcAncientStorageConfig,
+    ) -> Result<()> {
+        debug!("set_ancient_storage_config request received: {config:?}");
+
+        meta.with_post_init(|post_init| {
+            let bank = post_init.bank_forks.read().unwrap().root_bank();
+            let accounts_db = &bank.accounts().accounts_db;
+            let current = accounts_db.ancient_storage_tuning();
+            let tuning = AncientStorageTuning {
+                max_ancient_slots: config
+                    .max_ancient_slots
+                    .unwrap_or(current.max_ancient_slots),
+                percent_of_alive_shrunk_data: config
+                    .percent_of_alive_shrunk_data
+                    .unwrap_or(current.percent_of_alive_shrunk_data),
+                shrink_alive_percent: config
+                    .shrink_alive_percent
+                    .unwrap_or(current.shrink_alive_percent),
+            };
+            tuning
+                .validate()
+                .map_err(jsonrpc_core::error::Error::invalid_params)?;
+            if let Some(offset) = config.offset {
+                if !accounts_db.set_ancient_append_vec_offset(offset) {
+                    return Err(jsonrpc_core::error::Error::invalid_params(
+                        "Ancient append vecs are disabled",
+                    ));
+                }
+                warn!("Ancient append vec offset set to {offset}");
+            }
+            accounts_db.set_ancient_storage_tuning(tuning);
+            warn!("Ancient storage tuning set to {tuning:?}");
+            Ok(())
+        })
+    }
+
     fn get_secondary_index_key_size(
         &self,
         meta: Self::Metadata,
@@ -676,6 +1265,29 @@ impl AdminRpc for AdminRpcImpl {
             Ok(())
         })
     }
+
+    fn set_hashes_per_tick(
+        &self,
+        meta: Self::Metadata,
+        hashes_per_tick: u64,
+        activation_epoch: Option<Epoch>,
+    ) -> Result<Epoch> {
+        debug!(
+            "set_hashes_per_tick rpc request received: {hashes_per_tick} from epoch \
+             {activation_epoch:?}"
+        );
+
+        meta.with_post_init(|post_init| {
+            let mut bank_forks = post_init.bank_forks.write().unwrap();
+            let activation_epoch =
+                activation_epoch.unwrap_or_else(|| bank_forks.root_bank().epoch() + 1);
+            bank_forks
+                .set_hashes_per_tick(hashes_per_tick, activation_epoch)
+                .map_err(|err| jsonrpc_core::error::Error::invalid_params(err.to_string()))?;
+            warn!("Hashes per tick set to {hashes_per_tick} from epoch {activation_epoch}");
+            Ok(activation_epoch)
+        })
+    }
 }
 
 impl AdminRpcImpl {
@@ -736,6 +1348,7 @@ fn rpc_account_index_from_account_index(account_index: &AccountIndex) -> RpcAcco
         AccountIndex::ProgramId => RpcAccountIndex::ProgramId,
         AccountIndex::SplTokenOwner => RpcAccountIndex::SplTokenOwner,
         AccountIndex::SplTokenMint => RpcAccountIndex::SplTokenMint,
+        AccountIndex::ProgramIdDataSize => RpcAccountIndex::ProgramIdDataSize,
     }
 }
 
@@ -859,7 +1472,11 @@ mod tests {
         super::*,
         serde_json::Value,
         solana_accounts_db::{accounts_index::AccountSecondaryIndexes, inline_spl_token},
-        solana_core::consensus::tower_storage::NullTowerStorage,
+        solana_core::{
+            banking_stage::account_contention::DEFAULT_CONTENTION_WINDOW_SLOTS,
+            consensus::tower_storage::NullTowerStorage,
+        },
+        solana_cost_model::compute_cost_model::ComputeCostModel,
         solana_gossip::cluster_info::ClusterInfo,
         solana_ledger::genesis_utils::{create_genesis_config, GenesisConfigInfo},
         solana_rpc::rpc::create_validator_exit,
@@ -883,6 +1500,7 @@ mod tests {
     #[derive(Default)]
     struct TestConfig {
         account_indexes: AccountSecondaryIndexes,
+        hashes_per_tick: Option<u64>,
     }
 
     struct RpcHandler {
@@ -909,9 +1527,12 @@ mod tests {
             ));
             let exit = Arc::new(AtomicBool::new(false));
             let validator_exit = create_validator_exit(exit);
-            let (bank_forks, vote_keypair) = new_bank_forks_with_config(BankTestConfig {
-                secondary_indexes: config.account_indexes,
-            });
+            let (bank_forks, vote_keypair) = new_bank_forks_with_config(
+                BankTestConfig {
+                    secondary_indexes: config.account_indexes,
+                },
+                config.hashes_per_tick,
+            );
             let vote_account = vote_keypair.pubkey();
             let start_progress = Arc::new(RwLock::new(ValidatorStartProgress::default()));
             let repair_whitelist = Arc::new(RwLock::new(HashSet::new()));
@@ -935,8 +1556,11 @@ mod tests {
                     cluster_slots: Arc::new(
                         solana_core::cluster_slots_service::cluster_slots::ClusterSlots::default(),
                     ),
+                    account_contention: Arc::default(),
                 }))),
                 staked_nodes_overrides: Arc::new(RwLock::new(HashMap::new())),
+                forwarding_policy: Arc::default(),
+                block_production_limits: Arc::default(),
                 rpc_to_plugin_manager_sender: None,
             };
             let mut io = MetaIoHandler::default();
@@ -956,17 +1580,340 @@ mod tests {
 
     fn new_bank_forks_with_config(
         config: BankTestConfig,
+        hashes_per_tick: Option<u64>,
     ) -> (Arc<RwLock<BankForks>>, Arc<Keypair>) {
         let GenesisConfigInfo {
-            genesis_config,
+            mut genesis_config,
             voting_keypair,
             ..
         } = create_genesis_config(1_000_000_000);
+        genesis_config.poh_config.hashes_per_tick = hashes_per_tick;
 
         let bank = Bank::new_for_tests_with_config(&genesis_config, config);
         (BankForks::new_rw_arc(bank), Arc::new(voting_keypair))
     }
 
+    #[test]
+    fn test_accounts_db_read_cache_limit() {
+        let rpc = RpcHandler::start_with_config(TestConfig::default());
+        let bank = rpc.root_bank();
+        let RpcHandler { io, meta, .. } = rpc;
+
+        let get_info = || {
+            let req = r#"{"jsonrpc":"2.0","id":1,"method":"accountsDbReadCacheInfo"}"#;
+            let res = io.handle_request_sync(req, meta.clone());
+            let result: Value = serde_json::from_str(&res.expect("actual response"))
+                .expect("actual response deserialization");
+            serde_json::from_value::<AdminRpcAccountsDbReadCacheInfo>(result["result"].clone())
+                .unwrap()
+        };
+
+        let info = get_info();
+        assert_eq!(
+            info.limit_bytes,
+            bank.accounts()
+                .accounts_db
+                .read_only_accounts_cache_max_data_size()
+        );
+
+        let req = r#"{"jsonrpc":"2.0","id":1,"method":"setAccountsDbReadCacheLimit","params":[0]}"#;
+        let res = io.handle_request_sync(req, meta.clone());
+        let result: Value = serde_json::from_str(&res.expect("actual response"))
+            .expect("actual response deserialization");
+        assert_eq!(result["result"], Value::Null);
+
+        let info = get_info();
+        assert_eq!(info.limit_bytes, 0);
+        assert_eq!(info.data_size_bytes, 0);
+        assert_eq!(info.entries, 0);
+    }
+
+    #[test]
+    fn test_ancient_storage_config() {
+        let rpc = RpcHandler::start_with_config(TestConfig::default());
+        let bank = rpc.root_bank();
+        let RpcHandler { io, meta, .. } = rpc;
+
+        let get_info = || {
+            let req = r#"{"jsonrpc":"2.0","id":1,"method":"ancientStorageInfo"}"#;
+            let res = io.handle_request_sync(req, meta.clone());
+            let result: Value = serde_json::from_str(&res.expect("actual response"))
+                .expect("actual response deserialization");
+            serde_json::from_value::<AdminRpcAncientStorageInfo>(result["result"].clone()).unwrap()
+        };
+
+        let accounts_db = &bank.accounts().accounts_db;
+        let info = get_info();
+        let tuning = accounts_db.ancient_storage_tuning();
+        assert_eq!(info.offset, accounts_db.ancient_append_vec_offset());
+        assert_eq!(info.max_ancient_slots, tuning.max_ancient_slots);
+        assert_eq!(info.shrink_alive_percent, tuning.shrink_alive_percent);
+
+        // an invalid percentage is rejected and changes nothing
+        let req = r#"{"jsonrpc":"2.0","id":1,"method":"setAncientStorageConfig","params":[{"shrinkAlivePercent":101}]}"#;
+        let res = io.handle_request_sync(req, meta.clone());
+        let result: Value = serde_json::from_str(&res.expect("actual response"))
+            .expect("actual response deserialization");
+        assert_eq!(result["error"]["code"], ErrorCode::InvalidParams.code());
+        assert_eq!(accounts_db.ancient_storage_tuning(), tuning);
+
+        let req = r#"{"jsonrpc":"2.0","id":1,"method":"setAncientStorageConfig","params":[{"maxAncientSlots":5,"shrinkAlivePercent":50}]}"#;
+        let res = io.handle_request_sync(req, meta.clone());
+        let result: Value = serde_json::from_str(&res.expect("actual response"))
+            .expect("actual response deserialization");
+        assert_eq!(result["result"], Value::Null);
+
+        let info = get_info();
+        assert_eq!(info.max_ancient_slots, 5);
+        assert_eq!(
+            info.percent_of_alive_shrunk_data,
+            tuning.percent_of_alive_shrunk_data
+        );
+        assert_eq!(info.shrink_alive_percent, 50);
+
+        let req = r#"{"jsonrpc":"2.0","id":1,"method":"setAncientStorageConfig","params":[{"offset":-1000}]}"#;
+        let res = io.handle_request_sync(req, meta.clone());
+        let result: Value = serde_json::from_str(&res.expect("actual response"))
+            .expect("actual response deserialization");
+        assert_eq!(result["result"], Value::Null);
+        assert_eq!(get_info().offset, Some(-1000));
+    }
+
+    #[test]
+    fn test_set_hashes_per_tick() {
+        let set_hashes_per_tick = |rpc: &RpcHandler, params: &str| {
+            let req = format!(
+                r#"{{"jsonrpc":"2.0","id":1,"method":"setHashesPerTick","params":{params}}}"#
+            );
+            let res = rpc.io.handle_request_sync(&req, rpc.meta.clone());
+            let result: Value = serde_json::from_str(&res.expect("actual response"))
+                .expect("actual response deserialization");
+            result
+        };
+
+        // a validator in low power mode can not change its hashes per tick
+        let rpc = RpcHandler::start_with_config(TestConfig::default());
+        let result = set_hashes_per_tick(&rpc, "[10]");
+        assert_eq!(result["error"]["code"], ErrorCode::InvalidParams.code());
+
+        let rpc = RpcHandler::start_with_config(TestConfig {
+            hashes_per_tick: Some(100),
+            ..TestConfig::default()
+        });
+        let root_epoch = rpc.root_bank().epoch();
+        let result = set_hashes_per_tick(&rpc, "[1]");
+        assert_eq!(result["error"]["code"], ErrorCode::InvalidParams.code());
+        let result = set_hashes_per_tick(&rpc, &format!("[10, {root_epoch}]"));
+        assert_eq!(result["error"]["code"], ErrorCode::InvalidParams.code());
+        // the activation epoch defaults to the epoch after the root's
+        let result = set_hashes_per_tick(&rpc, "[10]");
+        assert_eq!(result["result"], root_epoch + 1);
+    }
+
+    #[test]
+    fn test_forwarding_policy() {
+        let rpc = RpcHandler::start_with_config(TestConfig::default());
+        let RpcHandler { io, meta, .. } = rpc;
+
+        let get_policy = || {
+            let req = r#"{"jsonrpc":"2.0","id":1,"method":"forwardingPolicy"}"#;
+            let res = io.handle_request_sync(req, meta.clone());
+            let result: Value = serde_json::from_str(&res.expect("actual response"))
+                .expect("actual response deserialization");
+            result["result"].clone()
+        };
+        assert_eq!(get_policy(), "next-leaders:1");
+
+        let req = r#"{"jsonrpc":"2.0","id":1,"method":"setForwardingPolicy","params":["stake-weighted:3"]}"#;
+        let res = io.handle_request_sync(req, meta.clone());
+        let result: Value = serde_json::from_str(&res.expect("actual response"))
+            .expect("actual response deserialization");
+        assert_eq!(result["result"], Value::Null);
+        assert_eq!(get_policy(), "stake-weighted:3");
+        assert_eq!(
+            *meta.forwarding_policy.read().unwrap(),
+            ForwardingPolicy::StakeWeighted(3)
+        );
+
+        let req =
+            r#"{"jsonrpc":"2.0","id":1,"method":"setForwardingPolicy","params":["everyone"]}"#;
+        let res = io.handle_request_sync(req, meta.clone());
+        let result: Value = serde_json::from_str(&res.expect("actual response"))
+            .expect("actual response deserialization");
+        assert_eq!(result["error"]["code"], ErrorCode::InvalidParams.code());
+        assert_eq!(get_policy(), "stake-weighted:3");
+    }
+
+    #[test]
+    fn test_program_cache_info() {
+        let rpc = RpcHandler::start_with_config(TestConfig::default());
+        let RpcHandler { io, meta, .. } = rpc;
+
+        let req = r#"{"jsonrpc":"2.0","id":1,"method":"programCacheInfo"}"#;
+        let res = io.handle_request_sync(req, meta);
+        let result: Value = serde_json::from_str(&res.expect("actual response"))
+            .expect("actual response deserialization");
+        let info =
+            serde_json::from_value::<AdminRpcProgramCacheInfo>(result["result"].clone()).unwrap();
+        assert_eq!(
+            info.capacity,
+            solana_program_runtime::loaded_programs::MAX_LOADED_ENTRY_COUNT
+        );
+        assert_eq!(info.eviction_strategy, "random");
+        // builtins are cached as soon as the bank is created
+        let system_program = info
+            .entries
+            .iter()
+            .find(|entry| entry.program_id == solana_sdk::system_program::id().to_string())
+            .unwrap();
+        assert_eq!(system_program.program_type, "Builtin");
+    }
+
+    #[test]
+    fn test_block_production_limits() {
+        let rpc = RpcHandler::start_with_config(TestConfig::default());
+        let RpcHandler { io, meta, .. } = rpc;
+
+        let get_limits = || {
+            let req = r#"{"jsonrpc":"2.0","id":1,"method":"blockProductionLimits"}"#;
+            let res = io.handle_request_sync(req, meta.clone());
+            let result: Value = serde_json::from_str(&res.expect("actual response"))
+                .expect("actual response deserialization");
+            serde_json::from_value::<AdminRpcBlockProductionLimits>(result["result"].clone())
+                .unwrap()
+        };
+        let limits = get_limits();
+        assert_eq!(limits.block_cost_limit, MAX_BLOCK_UNITS);
+        assert_eq!(limits.vote_cost_limit, MAX_VOTE_UNITS);
+        assert_eq!(
+            limits.account_cost_limit,
+            limits.protocol_account_cost_limit
+        );
+
+        let req = r#"{"jsonrpc":"2.0","id":1,"method":"setBlockProductionLimits","params":[24000000,null,12000000]}"#;
+        let res = io.handle_request_sync(req, meta.clone());
+        let result: Value = serde_json::from_str(&res.expect("actual response"))
+            .expect("actual response deserialization");
+        assert_eq!(result["result"], Value::Null);
+        assert_eq!(
+            *meta.block_production_limits.read().unwrap(),
+            BlockProductionLimits {
+                block_cost_limit: Some(24_000_000),
+                account_cost_limit: None,
+                vote_cost_limit: Some(12_000_000),
+            }
+        );
+        let limits = get_limits();
+        assert_eq!(limits.block_cost_limit, 24_000_000);
+        assert_eq!(limits.vote_cost_limit, 12_000_000);
+        assert_eq!(limits.protocol_block_cost_limit, MAX_BLOCK_UNITS);
+
+        // limits above the protocol limits are rejected
+        let req = format!(
+            r#"{{"jsonrpc":"2.0","id":1,"method":"setBlockProductionLimits","params":[{},null,null]}}"#,
+            MAX_BLOCK_UNITS + 1
+        );
+        let res = io.handle_request_sync(&req, meta.clone());
+        let result: Value = serde_json::from_str(&res.expect("actual response"))
+            .expect("actual response deserialization");
+        assert_eq!(result["error"]["code"], ErrorCode::InvalidParams.code());
+        assert_eq!(get_limits().block_cost_limit, 24_000_000);
+
+        // limits which are not given are reset to the protocol limits
+        let req = r#"{"jsonrpc":"2.0","id":1,"method":"setBlockProductionLimits","params":[]}"#;
+        let res = io.handle_request_sync(req, meta.clone());
+        let result: Value = serde_json::from_str(&res.expect("actual response"))
+            .expect("actual response deserialization");
+        assert_eq!(result["result"], Value::Null);
+        assert_eq!(
+            *meta.block_production_limits.read().unwrap(),
+            BlockProductionLimits::default()
+        );
+    }
+
+    #[test]
+    fn test_compute_cost_model() {
+        let rpc = RpcHandler::start_with_config(TestConfig::default());
+        let RpcHandler { io, meta, .. } = rpc;
+
+        let req = r#"{"jsonrpc":"2.0","id":1,"method":"computeCostModel"}"#;
+        let res = io.handle_request_sync(req, meta);
+        let result: Value = serde_json::from_str(&res.expect("actual response"))
+            .expect("actual response deserialization");
+        let info =
+            serde_json::from_value::<AdminRpcComputeCostModel>(result["result"].clone()).unwrap();
+        let compute_cost_model = ComputeCostModel::default();
+        assert_eq!(info.signature_cost, compute_cost_model.signature_cost());
+        let system_program = info
+            .builtin_instruction_costs
+            .iter()
+            .find(|cost| cost.name == solana_sdk::system_program::id().to_string())
+            .unwrap();
+        assert_eq!(
+            Some(system_program.compute_units),
+            compute_cost_model.builtin_instruction_cost(&solana_sdk::system_program::id())
+        );
+        let sha256_base_cost = info
+            .syscall_costs
+            .iter()
+            .find(|cost| cost.name == "sha256_base_cost")
+            .unwrap();
+        assert_eq!(
+            Some(sha256_base_cost.compute_units),
+            compute_cost_model.syscall_cost("sha256_base_cost")
+        );
+    }
+
+    #[test]
+    fn test_account_contention() {
+        let rpc = RpcHandler::start_with_config(TestConfig::default());
+        let RpcHandler { io, meta, .. } = rpc;
+
+        let hot = Pubkey::new_unique();
+        let warm = Pubkey::new_unique();
+        {
+            let post_init = meta.post_init.read().unwrap();
+            let account_contention = &post_init.as_ref().unwrap().account_contention;
+            account_contention.record_lock_conflicts(3, [&hot, &warm]);
+            account_contention.record_retries(4, [&hot]);
+        }
+
+        let req = r#"{"jsonrpc":"2.0","id":1,"method":"accountContention"}"#;
+        let res = io.handle_request_sync(req, meta.clone());
+        let result: Value = serde_json::from_str(&res.expect("actual response"))
+            .expect("actual response deserialization");
+        let info =
+            serde_json::from_value::<AdminRpcAccountContentionInfo>(result["result"].clone())
+                .unwrap();
+        assert_eq!(info.windows.len(), 1);
+        let window = &info.windows[0];
+        assert_eq!(window.start_slot, 0);
+        assert_eq!(window.end_slot, DEFAULT_CONTENTION_WINDOW_SLOTS - 1);
+        assert_eq!(
+            window
+                .accounts
+                .iter()
+                .map(|account| (
+                    account.account.clone(),
+                    account.lock_conflicts,
+                    account.retries,
+                    account.forwarded
+                ))
+                .collect::<Vec<_>>(),
+            vec![(hot.to_string(), 1, 1, 0), (warm.to_string(), 1, 0, 0)]
+        );
+
+        let req = r#"{"jsonrpc":"2.0","id":1,"method":"accountContention","params":[1]}"#;
+        let res = io.handle_request_sync(req, meta);
+        let result: Value = serde_json::from_str(&res.expect("actual response"))
+            .expect("actual response deserialization");
+        let info =
+            serde_json::from_value::<AdminRpcAccountContentionInfo>(result["result"].clone())
+                .unwrap();
+        assert_eq!(info.windows[0].accounts.len(), 1);
+        assert_eq!(info.windows[0].accounts[0].account, hot.to_string());
+    }
+
     #[test]
     fn test_secondary_index_key_sizes() {
         for secondary_index_enabled in [true, false] {
@@ -73,6 +73,7 @@ fn main() {
             "program-id" => AccountIndex::ProgramId,
             "spl-token-mint" => AccountIndex::SplTokenMint,
             "spl-token-owner" => AccountIndex::SplTokenOwner,
+            "program-id-data-size" => AccountIndex::ProgramIdDataSize,
             _ => unreachable!(),
         })
         .collect();
@@ -158,6 +159,7 @@ fn main() {
     let enable_block_subscription = matches.is_present("rpc_pubsub_enable_block_subscription");
     let faucet_port = value_t_or_exit!(matches, "faucet_port", u16);
     let ticks_per_slot = value_t!(matches, "ticks_per_slot", u64).ok();
+    let hashes_per_tick = value_t!(matches, "hashes_per_tick", u64).ok();
     let slots_per_epoch = value_t!(matches, "slots_per_epoch", Slot).ok();
     let gossip_host = matches.value_of("gossip_host").map(|gossip_host| {
         solana_net_utils::parse_host(gossip_host).unwrap_or_else(|err| {
@@ -353,6 +355,21 @@ fn main() {
     // Remove this when client support is ready for the enable_partitioned_epoch_reward feature
     features_to_deactivate.push(feature_set::enable_partitioned_epoch_reward::id());
 
+    let mut features_to_activate_at_slot = vec![];
+    if let Some(values) = matches.values_of("activate_feature_at_slot") {
+        for (feature, slot) in values.into_iter().tuples() {
+            let feature = feature.parse::<Pubkey>().unwrap_or_else(|err| {
+                println!("Error: invalid feature {feature}: {err}");
+                exit(1);
+            });
+            let slot = slot.parse::<Slot>().unwrap_or_else(|err| {
+                println!("Error: invalid slot {slot}: {err}");
+                exit(1);
+            });
+            features_to_activate_at_slot.push((feature, slot));
+        }
+    }
+
     if TestValidatorGenesis::ledger_exists(&ledger_path) {
         for (name, long) in &[
             ("bpf_program", "--bpf-program"),
@@ -360,9 +377,11 @@ fn main() {
             ("account", "--account"),
             ("mint_address", "--mint"),
             ("ticks_per_slot", "--ticks-per-slot"),
+            ("hashes_per_tick", "--hashes-per-tick"),
             ("slots_per_epoch", "--slots-per-epoch"),
             ("faucet_sol", "--faucet-sol"),
             ("deactivate_feature", "--deactivate-feature"),
+            ("activate_feature_at_slot", "--activate-feature-at-slot"),
         ] {
             if matches.is_present(name) {
                 println!("{long} argument ignored, ledger already exists");
@@ -402,6 +421,8 @@ fn main() {
             validator_exit: genesis.validator_exit.clone(),
             authorized_voter_keypairs: genesis.authorized_voter_keypairs.clone(),
             staked_nodes_overrides: genesis.staked_nodes_overrides.clone(),
+            forwarding_policy: Arc::default(),
+            block_production_limits: Arc::default(),
             post_init: admin_service_post_init,
             tower_storage: tower_storage.clone(),
             rpc_to_plugin_manager_sender,
@@ -460,7 +481,8 @@ fn main() {
             println!("Error: add_accounts_from_directories failed: {e}");
             exit(1);
         })
-        .deactivate_features(&features_to_deactivate);
+        .deactivate_features(&features_to_deactivate)
+        .activate_features_at_slot(&features_to_activate_at_slot);
 
     genesis.rpc_config(JsonRpcConfig {
         enable_rpc_transaction_history: true,
@@ -509,6 +531,15 @@ fn main() {
         }
     }
 
+    if matches.is_present("clone_on_demand") {
+        genesis.lazy_clone_accounts_from(
+            cluster_rpc_client
+                .as_ref()
+                .expect("bug: --url argument missing?")
+                .url(),
+        );
+    }
+
     if let Some(warp_slot) = warp_slot {
         genesis.warp_slot(warp_slot);
     }
@@ -517,6 +548,10 @@ fn main() {
         genesis.ticks_per_slot(ticks_per_slot);
     }
 
+    if let Some(hashes_per_tick) = hashes_per_tick {
+        genesis.hashes_per_tick(hashes_per_tick);
+    }
+
     if let Some(slots_per_epoch) = slots_per_epoch {
         genesis.epoch_schedule(EpochSchedule::custom(
             slots_per_epoch,
@@ -20,12 +20,22 @@ use {
         keypair::SKIP_SEED_PHRASE_VALIDATION_ARG,
     },
     solana_core::{
+        banking_stage::forwarding_policy::ForwardingPolicy,
         banking_trace::{DirByteLimit, BANKING_TRACE_DIR_DEFAULT_BYTE_LIMIT},
         validator::{BlockProductionMethod, BlockVerificationMethod},
     },
     solana_faucet::faucet::{self, FAUCET_PORT},
+    solana_gossip::{
+        crds_gossip_push::{
+            CRDS_GOSSIP_PRUNE_MSG_TIMEOUT_MS, CRDS_GOSSIP_PUSH_FANOUT,
+            CRDS_GOSSIP_PUSH_STAKE_WEIGHT_EXPONENT, MAX_CRDS_GOSSIP_PUSH_FANOUT,
+            MAX_CRDS_GOSSIP_PUSH_STAKE_WEIGHT_EXPONENT,
+        },
+        node_metadata::MAX_NODE_METADATA_BYTES,
+    },
     solana_ledger::use_snapshot_archives_at_startup,
     solana_net_utils::{MINIMUM_VALIDATOR_PORT_RANGE_WIDTH, VALIDATOR_PORT_RANGE},
+    solana_program_runtime::loaded_programs::EvictionStrategy,
     solana_rpc::{rpc::MAX_REQUEST_BODY_SIZE, rpc_pubsub_service::PubSubConfig},
     solana_rpc_client_api::request::MAX_MULTIPLE_ACCOUNTS,
     solana_runtime::{
@@ -580,6 +590,18 @@ pub fn app<'a>(version: &'a str, default_args: &'a DefaultArgs) -> App<'a, 'a> {
                 /* .default_value() intentionally not used here! */
                 .help("Keep this amount of shreds in root slots."),
         )
+        .arg(
+            Arg::with_name("limit_ledger_epochs")
+                .long("limit-ledger-epochs")
+                .value_name("NUM_EPOCHS")
+                .takes_value(true)
+                .validator(is_parsable::<u64>)
+                .conflicts_with("limit_ledger_size")
+                .help(
+                    "Keep the epoch of the latest root and this many complete epochs before it \
+                     in the ledger, purging older epochs as a whole",
+                ),
+        )
         .arg(
             Arg::with_name("rocksdb_shred_compaction")
                 .long("rocksdb-shred-compaction")
@@ -770,6 +792,57 @@ pub fn app<'a>(version: &'a str, default_args: &'a DefaultArgs) -> App<'a, 'a> {
                       will not push/pull from from validators outside this set. \
                       [default: all validators]")
         )
+        .arg(
+            Arg::with_name("gossip_push_fanout")
+                .long("gossip-push-fanout")
+                .value_name("NUMBER")
+                .takes_value(true)
+                .validator(|value| is_within_range(value, 1..=MAX_CRDS_GOSSIP_PUSH_FANOUT))
+                .default_value(&default_args.gossip_push_fanout)
+                .help("Number of nodes each gossip value is pushed to"),
+        )
+        .arg(
+            Arg::with_name("gossip_push_stake_weight_exponent")
+                .long("gossip-push-stake-weight-exponent")
+                .value_name("EXPONENT")
+                .takes_value(true)
+                .validator(|value| {
+                    is_within_range(value, 0..=MAX_CRDS_GOSSIP_PUSH_STAKE_WEIGHT_EXPONENT as usize)
+                })
+                .default_value(&default_args.gossip_push_stake_weight_exponent)
+                .help("Exponent of the stake weighting when sampling the nodes to push gossip \
+                      values to. 0 samples nodes regardless of their stake, higher values \
+                      favor highly staked nodes"),
+        )
+        .arg(
+            Arg::with_name("gossip_prune_timeout_ms")
+                .long("gossip-prune-timeout-ms")
+                .value_name("MILLISECS")
+                .takes_value(true)
+                .validator(is_parsable::<u64>)
+                .default_value(&default_args.gossip_prune_timeout_ms)
+                .help("Milliseconds a gossip prune message remains valid for"),
+        )
+        .arg(
+            Arg::with_name("node_metadata")
+                .long("node-metadata")
+                .value_name("TEXT")
+                .takes_value(true)
+                .validator(|value| {
+                    if value.len() > MAX_NODE_METADATA_BYTES {
+                        Err(format!(
+                            "node metadata must be at most {MAX_NODE_METADATA_BYTES} bytes"
+                        ))
+                    } else {
+                        Ok(())
+                    }
+                })
+                .help(
+                    "Publish this application-defined metadata, e.g. operator contact info or \
+                     service endpoints, through gossip. Other nodes serve it over RPC with \
+                     getNodeMetadata",
+                ),
+        )
         .arg(
             Arg::with_name("tpu_coalesce_ms")
                 .long("tpu-coalesce-ms")
@@ -817,6 +890,18 @@ pub fn app<'a>(version: &'a str, default_args: &'a DefaultArgs) -> App<'a, 'a> {
                             number of QUIC streams permitted from the peer and vote packet sender stage.
                             Format of the file: `staked_map_id: {<pubkey>: <SOL stake amount>}"),
         )
+        .arg(
+            Arg::with_name("forwarding_policy")
+                .long("forwarding-policy")
+                .value_name("POLICY")
+                .takes_value(true)
+                .validator(is_parsable::<ForwardingPolicy>)
+                .help("How transactions are forwarded while this validator is not the leader: \
+                       next-leaders[:N] forwards to each of the next N leaders, \
+                       stake-weighted[:N] forwards to one of the next N leaders chosen in \
+                       proportion to its stake and disabled does not forward transactions \
+                       [default: next-leaders:1]"),
+        )
         .arg(
             Arg::with_name("bind_address")
                 .long("bind-address")
@@ -1114,6 +1199,17 @@ pub fn app<'a>(version: &'a str, default_args: &'a DefaultArgs) -> App<'a, 'a> {
                 .multiple(true)
                 .help("Specify the configuration file for the Geyser plugin."),
         )
+        .arg(
+            Arg::with_name("instruction_parser_plugin")
+                .long("instruction-parser-plugin")
+                .value_name("FILE")
+                .takes_value(true)
+                .multiple(true)
+                .help(
+                    "Load an instruction parser from the dynamic library FILE, to return the \
+                     instructions of its programs parsed in jsonParsed RPC responses",
+                ),
+        )
         .arg(
             Arg::with_name("snapshot_archive_format")
                 .long("snapshot-archive-format")
@@ -1183,7 +1279,12 @@ pub fn app<'a>(version: &'a str, default_args: &'a DefaultArgs) -> App<'a, 'a> {
                 .long("account-index")
                 .takes_value(true)
                 .multiple(true)
-                .possible_values(&["program-id", "spl-token-owner", "spl-token-mint"])
+                .possible_values(&[
+                    "program-id",
+                    "spl-token-owner",
+                    "spl-token-mint",
+                    "program-id-data-size",
+                ])
                 .value_name("INDEX")
                 .help("Enable an accounts index, indexed by the selected account field"),
         )
@@ -1240,6 +1341,36 @@ pub fn app<'a>(version: &'a str, default_args: &'a DefaultArgs) -> App<'a, 'a> {
                 .help("AppendVecs that are older than (slots_per_epoch - SLOT-OFFSET) are squashed together.")
                 .hidden(hidden_unless_forced()),
         )
+        .arg(
+            Arg::with_name("accounts_db_ancient_max_storages")
+                .long("accounts-db-ancient-max-storages")
+                .value_name("COUNT")
+                .validator(is_parsable::<usize>)
+                .takes_value(true)
+                .help("Number of ancient storages to aim for. Ancient storages beyond this are combined further. \
+                       May be adjusted at runtime with the accounts-db-ancient-storage subcommand.")
+                .hidden(hidden_unless_forced()),
+        )
+        .arg(
+            Arg::with_name("accounts_db_ancient_shrink_data_percent")
+                .long("accounts-db-ancient-shrink-data-percent")
+                .value_name("PERCENT")
+                .validator(is_valid_percentage)
+                .takes_value(true)
+                .help("Percent of the dead data in ancient storages to reclaim each time ancient storages are packed. \
+                       May be adjusted at runtime with the accounts-db-ancient-storage subcommand.")
+                .hidden(hidden_unless_forced()),
+        )
+        .arg(
+            Arg::with_name("accounts_db_ancient_shrink_alive_percent")
+                .long("accounts-db-ancient-shrink-alive-percent")
+                .value_name("PERCENT")
+                .validator(is_valid_percentage)
+                .takes_value(true)
+                .help("Ancient storages with less than this percent of their data alive are shrunk. \
+                       May be adjusted at runtime with the accounts-db-ancient-storage subcommand.")
+                .hidden(hidden_unless_forced()),
+        )
         .arg(
             Arg::with_name("accounts_db_cache_limit_mb")
                 .long("accounts-db-cache-limit-mb")
@@ -1248,6 +1379,40 @@ pub fn app<'a>(version: &'a str, default_args: &'a DefaultArgs) -> App<'a, 'a> {
                 .takes_value(true)
                 .help("How large the write cache for account data can become. If this is exceeded, the cache is flushed more aggressively."),
         )
+        .arg(
+            Arg::with_name("accounts_db_read_cache_limit_mb")
+                .long("accounts-db-read-cache-limit-mb")
+                .value_name("MEGABYTES")
+                .validator(is_parsable::<usize>)
+                .takes_value(true)
+                .help("How large the read cache for frequently loaded account data can become. \
+                       Least recently used accounts are evicted once this is exceeded. \
+                       May be adjusted at runtime with the accounts-db-read-cache subcommand."),
+        )
+        .arg(
+            Arg::with_name("accounts_db_compression")
+                .long("accounts-db-compression")
+                .value_name("COMPRESSION")
+                .takes_value(true)
+                .possible_values(&["none", "lz4", "zstd"])
+                .help("Compress the data of accounts when they are written to storages. \
+                       Storages written without compression remain readable, and storages \
+                       rewritten after compression is turned off are stored uncompressed. \
+                       Snapshots which contain compressed storages are given snapshot \
+                       version 1.3.0, and can only be loaded by validators that support \
+                       account data compression.")
+                .hidden(hidden_unless_forced()),
+        )
+        .arg(
+            Arg::with_name("accounts_db_compression_min_data_len")
+                .long("accounts-db-compression-min-data-len")
+                .value_name("BYTES")
+                .validator(is_parsable::<usize>)
+                .takes_value(true)
+                .requires("accounts_db_compression")
+                .help("Only compress the data of accounts with at least this many bytes of data")
+                .hidden(hidden_unless_forced()),
+        )
         .arg(
             Arg::with_name("accounts_index_scan_results_limit_mb")
                 .long("accounts-index-scan-results-limit-mb")
@@ -1341,6 +1506,26 @@ pub fn app<'a>(version: &'a str, default_args: &'a DefaultArgs) -> App<'a, 'a> {
                 .value_name("BYTES")
                 .help("Maximum number of bytes written to the program log before truncation")
         )
+        .arg(
+            Arg::with_name("program_cache_capacity")
+                .long("program-cache-capacity")
+                .takes_value(true)
+                .validator(is_parsable::<usize>)
+                .value_name("PROGRAMS")
+                .help("How many compiled programs the program cache keeps after each eviction")
+        )
+        .arg(
+            Arg::with_name("program_cache_eviction_strategy")
+                .long("program-cache-eviction-strategy")
+                .takes_value(true)
+                .possible_values(EvictionStrategy::NAMES)
+                .value_name("STRATEGY")
+                .help("How the program cache chooses which compiled programs to unload: \
+                       random picks the less used of two random programs, \
+                       lru unloads the least recently used programs and \
+                       lfu unloads the least frequently used programs \
+                       [default: random]")
+        )
         .arg(
             Arg::with_name("replay_slots_concurrently")
                 .long("replay-slots-concurrently")
@@ -1574,6 +1759,146 @@ pub fn app<'a>(version: &'a str, default_args: &'a DefaultArgs) -> App<'a, 'a> {
                                     running validator instance")
                 )
         )
+        .subcommand(
+            SubCommand::with_name("program-cache")
+                .about("Display the validator's program cache statistics and contents")
+                .arg(
+                    Arg::with_name("output")
+                        .long("output")
+                        .takes_value(true)
+                        .value_name("MODE")
+                        .possible_values(&["json", "json-compact"])
+                        .help("Output display mode")
+                )
+        )
+        .subcommand(
+            SubCommand::with_name("compute-cost-model")
+                .about("Display the compute unit costs of transactions, builtin programs \
+                        and syscalls used by the validator")
+                .arg(
+                    Arg::with_name("output")
+                        .long("output")
+                        .takes_value(true)
+                        .value_name("MODE")
+                        .possible_values(&["json", "json-compact"])
+                        .help("Output display mode")
+                )
+        )
+        .subcommand(
+            SubCommand::with_name("account-contention")
+                .about("Display the accounts which held up the most transactions in the \
+                        banking stage, by lock conflicts, retries and forwarding, per \
+                        window of slots")
+                .arg(
+                    Arg::with_name("max_accounts")
+                        .long("max-accounts")
+                        .takes_value(true)
+                        .value_name("NUMBER")
+                        .validator(is_parsable::<usize>)
+                        .help("Maximum number of accounts displayed per window \
+                               [default: 20]")
+                )
+                .arg(
+                    Arg::with_name("output")
+                        .long("output")
+                        .takes_value(true)
+                        .value_name("MODE")
+                        .possible_values(&["json", "json-compact"])
+                        .help("Output display mode")
+                )
+        )
+        .subcommand(
+            SubCommand::with_name("accounts-db-read-cache")
+                .about("Manage the validator's accounts-db read cache")
+                .setting(AppSettings::SubcommandRequiredElseHelp)
+                .setting(AppSettings::InferSubcommands)
+                .subcommand(
+                    SubCommand::with_name("get")
+                        .about("Display the validator's accounts-db read cache usage and limit")
+                        .arg(
+                            Arg::with_name("output")
+                                .long("output")
+                                .takes_value(true)
+                                .value_name("MODE")
+                                .possible_values(&["json", "json-compact"])
+                                .help("Output display mode")
+                        )
+                )
+                .subcommand(
+                    SubCommand::with_name("set")
+                        .about("Set the validator's accounts-db read cache limit")
+                        .setting(AppSettings::ArgRequiredElseHelp)
+                        .arg(
+                            Arg::with_name("limit_mb")
+                                .long("limit-mb")
+                                .value_name("MEGABYTES")
+                                .validator(is_parsable::<usize>)
+                                .takes_value(true)
+                                .required(true)
+                                .help("How large the read cache for account data can become")
+                        )
+                        .after_help("Note: accounts-db read cache limit changes only apply to the \
+                                    currently running validator instance")
+                )
+        )
+        .subcommand(
+            SubCommand::with_name("accounts-db-ancient-storage")
+                .about("Manage the validator's accounts-db ancient storages")
+                .setting(AppSettings::SubcommandRequiredElseHelp)
+                .setting(AppSettings::InferSubcommands)
+                .subcommand(
+                    SubCommand::with_name("get")
+                        .about("Display the validator's ancient storage usage and tuning")
+                        .arg(
+                            Arg::with_name("output")
+                                .long("output")
+                                .takes_value(true)
+                                .value_name("MODE")
+                                .possible_values(&["json", "json-compact"])
+                                .help("Output display mode")
+                        )
+                )
+                .subcommand(
+                    SubCommand::with_name("set")
+                        .about("Set the validator's ancient storage tuning")
+                        .setting(AppSettings::ArgRequiredElseHelp)
+                        .arg(
+                            Arg::with_name("offset")
+                                .long("offset")
+                                .value_name("SLOT-OFFSET")
+                                .validator(is_parsable::<i64>)
+                                .takes_value(true)
+                                .allow_hyphen_values(true)
+                                .help("Storages older than (slots_per_epoch - SLOT-OFFSET) become ancient")
+                        )
+                        .arg(
+                            Arg::with_name("max_storages")
+                                .long("max-storages")
+                                .value_name("COUNT")
+                                .validator(is_parsable::<usize>)
+                                .takes_value(true)
+                                .help("Number of ancient storages to aim for")
+                        )
+                        .arg(
+                            Arg::with_name("shrink_data_percent")
+                                .long("shrink-data-percent")
+                                .value_name("PERCENT")
+                                .validator(is_valid_percentage)
+                                .takes_value(true)
+                                .help("Percent of the dead data in ancient storages to reclaim each pass")
+                        )
+                        .arg(
+                            Arg::with_name("shrink_alive_percent")
+                                .long("shrink-alive-percent")
+                                .value_name("PERCENT")
+                                .validator(is_valid_percentage)
+                                .takes_value(true)
+                                .help("Ancient storages with less than this percent of their data alive are shrunk")
+                        )
+                        .after_help("Note: ancient storage tuning changes only apply to the \
+                                    currently running validator instance")
+                )
+        )
         .subcommand(
             SubCommand::with_name("init")
                 .about("Initialize the ledger directory then exit")
@@ -1674,6 +1999,83 @@ pub fn app<'a>(version: &'a str, default_args: &'a DefaultArgs) -> App<'a, 'a> {
                 .after_help("Note: the new staked nodes overrides only applies to the \
                          currently running validator instance")
         )
+        .subcommand(
+            SubCommand::with_name("forwarding-policy")
+                .about("Manage how the validator forwards transactions")
+                .setting(AppSettings::SubcommandRequiredElseHelp)
+                .setting(AppSettings::InferSubcommands)
+                .subcommand(
+                    SubCommand::with_name("get")
+                        .about("Display the validator's transaction forwarding policy")
+                )
+                .subcommand(
+                    SubCommand::with_name("set")
+                        .about("Set the validator's transaction forwarding policy")
+                        .arg(
+                            Arg::with_name("policy")
+                                .value_name("POLICY")
+                                .takes_value(true)
+                                .required(true)
+                                .validator(is_parsable::<ForwardingPolicy>)
+                                .help("next-leaders[:N], stake-weighted[:N] or disabled")
+                        )
+                        .after_help("Note: the new forwarding policy only applies to the \
+                                    currently running validator instance")
+                )
+        )
+        .subcommand(
+            SubCommand::with_name("block-production-limits")
+                .about("Manage the compute unit limits which the validator packs its \
+                        blocks to as leader")
+                .setting(AppSettings::SubcommandRequiredElseHelp)
+                .setting(AppSettings::InferSubcommands)
+                .subcommand(
+                    SubCommand::with_name("get")
+                        .about("Display the validator's block production limits")
+                        .arg(
+                            Arg::with_name("output")
+                                .long("output")
+                                .takes_value(true)
+                                .value_name("MODE")
+                                .possible_values(&["json", "json-compact"])
+                                .help("Output display mode")
+                        )
+                )
+                .subcommand(
+                    SubCommand::with_name("set")
+                        .about("Lower the validator's block production limits below the \
+                                protocol limits")
+                        .arg(
+                            Arg::with_name("block_cost_limit")
+                                .long("block-cost-limit")
+                                .value_name("COMPUTE_UNITS")
+                                .takes_value(true)
+                                .validator(is_parsable::<u64>)
+                                .help("Maximum compute units of a block")
+                        )
+                        .arg(
+                            Arg::with_name("account_cost_limit")
+                                .long("account-cost-limit")
+                                .value_name("COMPUTE_UNITS")
+                                .takes_value(true)
+                                .validator(is_parsable::<u64>)
+                                .help("Maximum compute units of each writable account in a block")
+                        )
+                        .arg(
+                            Arg::with_name("vote_cost_limit")
+                                .long("vote-cost-limit")
+                                .value_name("COMPUTE_UNITS")
+                                .takes_value(true)
+                                .validator(is_parsable::<u64>)
+                                .help("Maximum compute units of vote transactions in a block, \
+                                       the rest of the block is left to other transactions")
+                        )
+                        .after_help("Note: limits which are not given are reset to the \
+                                    protocol limits. The new limits only apply to the \
+                                    currently running validator instance, from its next \
+                                    leader slot")
+                )
+        )
         .subcommand(
             SubCommand::with_name("wait-for-restart-window")
                 .about("Monitor the validator for a good time to restart")
@@ -1742,6 +2144,31 @@ pub fn app<'a>(version: &'a str, default_args: &'a DefaultArgs) -> App<'a, 'a> {
                         .multiple(true)
                 )
                 .after_help("Note: At least one arg must be used. Using multiple is ok"),
+        )
+        .subcommand(
+            SubCommand::with_name("set-hashes-per-tick")
+                .about("Change the PoH hashes per tick of a test cluster")
+                .arg(
+                    Arg::with_name("hashes_per_tick")
+                        .value_name("NUM_HASHES")
+                        .takes_value(true)
+                        .required(true)
+                        .validator(is_parsable::<u64>)
+                        .help("Number of PoH hashes per tick, greater than 1")
+                )
+                .arg(
+                    Arg::with_name("epoch")
+                        .long("epoch")
+                        .value_name("EPOCH")
+                        .takes_value(true)
+                        .validator(is_parsable::<u64>)
+                        .help("Epoch from which the new hashes per tick apply \
+                               [default: the epoch after the root's]")
+                )
+                .after_help("Note: only supported on development clusters which were not \
+                             started in low power mode. Every node of the cluster must be \
+                             given the same hashes per tick and epoch, and a node which \
+                             restarts reverts to the genesis hashes per tick"),
         );
 }
 
@@ -1987,6 +2414,10 @@ pub struct DefaultArgs {
 
     pub contact_debug_interval: String,
 
+    pub gossip_push_fanout: String,
+    pub gossip_push_stake_weight_exponent: String,
+    pub gossip_prune_timeout_ms: String,
+
     pub accountsdb_repl_threads: String,
 
     pub snapshot_version: SnapshotVersion,
@@ -2078,6 +2509,9 @@ impl DefaultArgs {
             max_snapshot_download_abort: MAX_SNAPSHOT_DOWNLOAD_ABORT.to_string(),
             snapshot_archive_format: DEFAULT_ARCHIVE_COMPRESSION.to_string(),
             contact_debug_interval: "120000".to_string(),
+            gossip_push_fanout: CRDS_GOSSIP_PUSH_FANOUT.to_string(),
+            gossip_push_stake_weight_exponent: CRDS_GOSSIP_PUSH_STAKE_WEIGHT_EXPONENT.to_string(),
+            gossip_prune_timeout_ms: CRDS_GOSSIP_PRUNE_MSG_TIMEOUT_MS.to_string(),
             snapshot_version: SnapshotVersion::default(),
             rocksdb_shred_compaction: "level".to_string(),
             rocksdb_ledger_compression: "none".to_string(),
@@ -2216,7 +2650,12 @@ pub fn test_app<'a>(version: &'a str, default_args: &'a DefaultTestArgs) -> App<
                 .long("account-index")
                 .takes_value(true)
                 .multiple(true)
-                .possible_values(&["program-id", "spl-token-owner", "spl-token-mint"])
+                .possible_values(&[
+                    "program-id",
+                    "spl-token-owner",
+                    "spl-token-mint",
+                    "program-id-data-size",
+                ])
                 .value_name("INDEX")
                 .help("Enable an accounts index, indexed by the selected account field"),
         )
@@ -2361,6 +2800,28 @@ pub fn test_app<'a>(version: &'a str, default_args: &'a DefaultTestArgs) -> App<
                 .takes_value(true)
                 .help("The number of ticks in a slot"),
         )
+        .arg(
+            Arg::with_name("hashes_per_tick")
+                .long("hashes-per-tick")
+                .value_name("NUM_HASHES")
+                .validator(|value| {
+                    value
+                        .parse::<u64>()
+                        .map_err(|err| format!("error parsing '{value}': {err}"))
+                        .and_then(|hashes| {
+                            if hashes <= 1 {
+                                Err("value must be > 1".to_string())
+                            } else {
+                                Ok(())
+                            }
+                        })
+                })
+                .takes_value(true)
+                .help(
+                    "The number of PoH hashes per tick. By default the validator sleeps \
+                     between ticks instead of hashing",
+                ),
+        )
         .arg(
             Arg::with_name("slots_per_epoch")
                 .long("slots-per-epoch")
@@ -2464,6 +2925,16 @@ pub fn test_app<'a>(version: &'a str, default_args: &'a DefaultTestArgs) -> App<
                      If the ledger already exists then this parameter is silently ignored",
                 ),
         )
+        .arg(
+            Arg::with_name("clone_on_demand")
+                .long("clone-on-demand")
+                .takes_value(false)
+                .requires("json_rpc_url")
+                .help(
+                    "Copy accounts from the cluster referenced by the --url argument the \
+                     first time a transaction loads them, if they are missing from the ledger",
+                ),
+        )
         .arg(
             Arg::with_name("warp_slot")
                 .required(false)
@@ -2549,6 +3020,18 @@ pub fn test_app<'a>(version: &'a str, default_args: &'a DefaultTestArgs) -> App<
                 .multiple(true)
                 .help("deactivate this feature in genesis.")
         )
+        .arg(
+            Arg::with_name("activate_feature_at_slot")
+                .long("activate-feature-at-slot")
+                .value_names(&["FEATURE_PUBKEY", "SLOT"])
+                .takes_value(true)
+                .number_of_values(2)
+                .multiple(true)
+                .help(
+                    "Activate this feature at the first epoch boundary at or after SLOT, \
+                     instead of at genesis.",
+                ),
+        )
         .arg(
             Arg::with_name("compute_unit_limit")
                 .long("compute-unit-limit")
@@ -0,0 +1,75 @@
+//! Loading of instruction parsers from dynamic libraries.
+//!
+//! An instruction parser plugin is a dynamic library exporting a
+//! `_create_instruction_parser` function, which returns a boxed
+//! `InstructionParser`:
+//!
+//! ```ignore
+//! #[no_mangle]
+//! #[allow(improper_ctypes_definitions)]
+//! pub unsafe extern "C" fn _create_instruction_parser() -> *mut dyn InstructionParser {
+//!     Box::into_raw(Box::new(MyProgramParser::default()))
+//! }
+//! ```
+//!
+//! The parsers are registered with `solana_transaction_status`, so that RPC
+//! encodes the instructions of their programs as parsed in `jsonParsed`
+//! responses. A plugin must be built with the same compiler and the same
+//! version of `solana-transaction-status` as the validator.
+
+use {
+    lazy_static::lazy_static,
+    libloading::{Library, Symbol},
+    log::*,
+    solana_transaction_status::parse_instruction::{
+        register_instruction_parser, InstructionParser, ParseInstructionError,
+    },
+    std::{
+        path::{Path, PathBuf},
+        sync::{Arc, Mutex},
+    },
+    thiserror::Error,
+};
+
+lazy_static! {
+    /// The libraries of the loaded plugins, which are never unloaded since
+    /// their parsers stay registered until the validator exits
+    static ref LOADED_LIBRARIES: Mutex<Vec<Library>> = Mutex::default();
+}
+
+#[derive(Error, Debug)]
+pub enum InstructionParserPluginError {
+    #[error("Failed to load instruction parser plugin {0:?}: {1}")]
+    LoadError(PathBuf, String),
+
+    #[error("Failed to register instruction parser plugin {0:?}: {1}")]
+    RegisterError(PathBuf, ParseInstructionError),
+}
+
+/// Loads the instruction parser plugin at `libpath` and registers its parser
+///
+/// # Safety
+///
+/// This loads the dynamically linked library at `libpath` and runs its
+/// `_create_instruction_parser` function, which must return a valid parser.
+pub unsafe fn load_instruction_parser_plugin(
+    libpath: &Path,
+) -> Result<(), InstructionParserPluginError> {
+    type ParserConstructor = unsafe fn() -> *mut dyn InstructionParser;
+
+    let load_error = |err: libloading::Error| {
+        InstructionParserPluginError::LoadError(libpath.to_path_buf(), err.to_string())
+    };
+    let lib = Library::new(libpath).map_err(load_error)?;
+    let parser: Box<dyn InstructionParser> = {
+        let constructor: Symbol<ParserConstructor> =
+            lib.get(b"_create_instruction_parser").map_err(load_error)?;
+        Box::from_raw(constructor())
+    };
+    let program_name = parser.program_name().to_string();
+    register_instruction_parser(Arc::from(parser))
+        .map_err(|err| InstructionParserPluginError::RegisterError(libpath.to_path_buf(), err))?;
+    info!("loaded {program_name} instruction parser plugin from {libpath:?}");
+    LOADED_LIBRARIES.lock().unwrap().push(lib);
+    Ok(())
+}
@@ -13,11 +13,16 @@ use {
             AccountIndex, AccountSecondaryIndexes, AccountSecondaryIndexesIncludeExclude,
             AccountsIndexConfig, IndexLimitMb,
         },
+        ancient_append_vecs::AncientStorageTuning,
+        append_vec::compression::{
+            AccountDataCompression, AccountDataCompressionConfig, DEFAULT_MIN_COMPRESSED_DATA_LEN,
+        },
         partitioned_rewards::TestPartitionedEpochRewards,
         utils::{create_all_accounts_run_and_snapshot_dirs, create_and_canonicalize_directories},
     },
     solana_clap_utils::input_parsers::{keypair_of, keypairs_of, pubkey_of, value_of},
     solana_core::{
+        banking_stage::forwarding_policy::ForwardingPolicy,
         banking_trace::DISABLED_BAKING_TRACE_DIR,
         consensus::tower_storage,
         system_monitor_service::SystemMonitorService,
@@ -27,7 +32,10 @@ use {
             ValidatorConfig, ValidatorStartProgress,
         },
     },
-    solana_gossip::{cluster_info::Node, legacy_contact_info::LegacyContactInfo as ContactInfo},
+    solana_gossip::{
+        cluster_info::Node, crds_gossip_push::CrdsGossipPushConfig,
+        legacy_contact_info::LegacyContactInfo as ContactInfo,
+    },
     solana_ledger::{
         blockstore_cleanup_service::{DEFAULT_MAX_LEDGER_SHREDS, DEFAULT_MIN_MAX_LEDGER_SHREDS},
         blockstore_options::{
@@ -38,6 +46,7 @@ use {
     },
     solana_perf::recycler::enable_recycler_warming,
     solana_poh::poh_service,
+    solana_program_runtime::loaded_programs::{LoadedProgramsConfig, MAX_LOADED_ENTRY_COUNT},
     solana_rpc::{
         rpc::{JsonRpcConfig, RpcBigtableConfig},
         rpc_pubsub_service::PubSubConfig,
@@ -50,7 +59,7 @@ use {
         snapshot_utils::{self, ArchiveFormat, SnapshotVersion},
     },
     solana_sdk::{
-        clock::{Slot, DEFAULT_S_PER_SLOT},
+        clock::{Epoch, Slot, DEFAULT_S_PER_SLOT},
         commitment_config::CommitmentConfig,
         hash::Hash,
         pubkey::Pubkey,
@@ -66,6 +75,7 @@ use {
         bootstrap,
         cli::{app, warn_for_deprecated_arguments, DefaultArgs},
         dashboard::Dashboard,
+        instruction_parser_plugin::load_instruction_parser_plugin,
         ledger_lockfile, lock_ledger, new_spinner_progress_bar, println_name_value,
         redirect_stderr_to_file,
     },
@@ -706,6 +716,92 @@ pub fn main() {
                 });
             return;
         }
+        ("forwarding-policy", Some(forwarding_policy_subcommand_matches)) => {
+            match forwarding_policy_subcommand_matches.subcommand() {
+                ("get", _) => {
+                    let admin_client = admin_rpc_service::connect(&ledger_path);
+                    let forwarding_policy = admin_rpc_service::runtime()
+                        .block_on(async move { admin_client.await?.forwarding_policy().await })
+                        .unwrap_or_else(|err| {
+                            eprintln!("forwardingPolicy request failed: {err}");
+                            exit(1);
+                        });
+                    println!("{forwarding_policy}");
+                    return;
+                }
+                ("set", Some(subcommand_matches)) => {
+                    let policy = value_t_or_exit!(subcommand_matches, "policy", String);
+                    let admin_client = admin_rpc_service::connect(&ledger_path);
+                    admin_rpc_service::runtime()
+                        .block_on(
+                            async move { admin_client.await?.set_forwarding_policy(policy).await },
+                        )
+                        .unwrap_or_else(|err| {
+                            eprintln!("setForwardingPolicy request failed: {err}");
+                            exit(1);
+                        });
+                    return;
+                }
+                _ => unreachable!(),
+            }
+        }
+        ("block-production-limits", Some(block_production_limits_subcommand_matches)) => {
+            match block_production_limits_subcommand_matches.subcommand() {
+                ("get", Some(subcommand_matches)) => {
+                    let output_mode = subcommand_matches.value_of("output");
+                    let admin_client = admin_rpc_service::connect(&ledger_path);
+                    let block_production_limits = admin_rpc_service::runtime()
+                        .block_on(
+                            async move { admin_client.await?.block_production_limits().await },
+                        )
+                        .unwrap_or_else(|err| {
+                            eprintln!("blockProductionLimits request failed: {err}");
+                            exit(1);
+                        });
+                    if let Some(mode) = output_mode {
+                        match mode {
+                            "json" => println!(
+                                "{}",
+                                serde_json::to_string_pretty(&block_production_limits).unwrap()
+                            ),
+                            "json-compact" => print!(
+                                "{}",
+                                serde_json::to_string(&block_production_limits).unwrap()
+                            ),
+                            _ => unreachable!(),
+                        }
+                    } else {
+                        print!("{block_production_limits}");
+                    }
+                    return;
+                }
+                ("set", Some(subcommand_matches)) => {
+                    let block_cost_limit =
+                        value_t!(subcommand_matches, "block_cost_limit", u64).ok();
+                    let account_cost_limit =
+                        value_t!(subcommand_matches, "account_cost_limit", u64).ok();
+                    let vote_cost_limit = value_t!(subcommand_matches, "vote_cost_limit", u64).ok();
+                    let admin_client = admin_rpc_service::connect(&ledger_path);
+                    admin_rpc_service::runtime()
+                        .block_on(async move {
+                            admin_client
+                                .await?
+                                .set_block_production_limits(
+                                    block_cost_limit,
+                                    account_cost_limit,
+                                    vote_cost_limit,
+                                )
+                                .await
+                        })
+                        .unwrap_or_else(|err| {
+                            eprintln!("setBlockProductionLimits request failed: {err}");
+                            exit(1);
+                        });
+                    return;
+                }
+                _ => unreachable!(),
+            }
+        }
         ("set-identity", Some(subcommand_matches)) => {
             let require_tower = subcommand_matches.is_present("require_tower");
 
@@ -862,6 +958,190 @@ pub fn main() {
                 _ => unreachable!(),
             }
         }
+        ("program-cache", Some(subcommand_matches)) => {
+            let output_mode = subcommand_matches.value_of("output");
+            let admin_client = admin_rpc_service::connect(&ledger_path);
+            let program_cache_info = admin_rpc_service::runtime()
+                .block_on(async move { admin_client.await?.program_cache_info().await })
+                .unwrap_or_else(|err| {
+                    eprintln!("Program cache query failed: {err}");
+                    exit(1);
+                });
+            if let Some(mode) = output_mode {
+                match mode {
+                    "json" => println!(
+                        "{}",
+                        serde_json::to_string_pretty(&program_cache_info).unwrap()
+                    ),
+                    "json-compact" => {
+                        print!("{}", serde_json::to_string(&program_cache_info).unwrap())
+                    }
+                    _ => unreachable!(),
+                }
+            } else {
+                print!("{program_cache_info}");
+            }
+            return;
+        }
+        ("compute-cost-model", Some(subcommand_matches)) => {
+            let output_mode = subcommand_matches.value_of("output");
+            let admin_client = admin_rpc_service::connect(&ledger_path);
+            let compute_cost_model = admin_rpc_service::runtime()
+                .block_on(async move { admin_client.await?.compute_cost_model().await })
+                .unwrap_or_else(|err| {
+                    eprintln!("Compute cost model query failed: {err}");
+                    exit(1);
+                });
+            if let Some(mode) = output_mode {
+                match mode {
+                    "json" => println!(
+                        "{}",
+                        serde_json::to_string_pretty(&compute_cost_model).unwrap()
+                    ),
+                    "json-compact" => {
+                        print!("{}", serde_json::to_string(&compute_cost_model).unwrap())
+                    }
+                    _ => unreachable!(),
+                }
+            } else {
+                print!("{compute_cost_model}");
+            }
+            return;
+        }
+        ("account-contention", Some(subcommand_matches)) => {
+            let max_accounts = value_t!(subcommand_matches, "max_accounts", usize).ok();
+            let output_mode = subcommand_matches.value_of("output");
+            let admin_client = admin_rpc_service::connect(&ledger_path);
+            let account_contention = admin_rpc_service::runtime()
+                .block_on(async move { admin_client.await?.account_contention(max_accounts).await })
+                .unwrap_or_else(|err| {
+                    eprintln!("Account contention query failed: {err}");
+                    exit(1);
+                });
+            if let Some(mode) = output_mode {
+                match mode {
+                    "json" => println!(
+                        "{}",
+                        serde_json::to_string_pretty(&account_contention).unwrap()
+                    ),
+                    "json-compact" => {
+                        print!("{}", serde_json::to_string(&account_contention).unwrap())
+                    }
+                    _ => unreachable!(),
+                }
+            } else {
+                print!("{account_contention}");
+            }
+            return;
+        }
+        ("accounts-db-read-cache", Some(read_cache_subcommand_matches)) => {
+            match read_cache_subcommand_matches.subcommand() {
+                ("get", Some(subcommand_matches)) => {
+                    let output_mode = subcommand_matches.value_of("output");
+                    let admin_client = admin_rpc_service::connect(&ledger_path);
+                    let read_cache_info = admin_rpc_service::runtime()
+                        .block_on(
+                            async move { admin_client.await?.accounts_db_read_cache_info().await },
+                        )
+                        .unwrap_or_else(|err| {
+                            eprintln!("Accounts-db read cache query failed: {err}");
+                            exit(1);
+                        });
+                    if let Some(mode) = output_mode {
+                        match mode {
+                            "json" => println!(
+                                "{}",
+                                serde_json::to_string_pretty(&read_cache_info).unwrap()
+                            ),
+                            "json-compact" => {
+                                print!("{}", serde_json::to_string(&read_cache_info).unwrap())
+                            }
+                            _ => unreachable!(),
+                        }
+                    } else {
+                        print!("{read_cache_info}");
+                    }
+                    return;
+                }
+                ("set", Some(subcommand_matches)) => {
+                    const MB: usize = 1_024 * 1_024;
+                    let limit_bytes =
+                        value_t_or_exit!(subcommand_matches, "limit_mb", usize).saturating_mul(MB);
+                    let admin_client = admin_rpc_service::connect(&ledger_path);
+                    admin_rpc_service::runtime()
+                        .block_on(async move {
+                            admin_client
+                                .await?
+                                .set_accounts_db_read_cache_limit(limit_bytes)
+                                .await
+                        })
+                        .unwrap_or_else(|err| {
+                            eprintln!("setAccountsDbReadCacheLimit request failed: {err}");
+                            exit(1);
+                        });
+                    return;
+                }
+                _ => unreachable!(),
+            }
+        }
+        ("accounts-db-ancient-storage", Some(ancient_storage_subcommand_matches)) => {
+            match ancient_storage_subcommand_matches.subcommand() {
+                ("get", Some(subcommand_matches)) => {
+                    let output_mode = subcommand_matches.value_of("output");
+                    let admin_client = admin_rpc_service::connect(&ledger_path);
+                    let ancient_storage_info = admin_rpc_service::runtime()
+                        .block_on(async move { admin_client.await?.ancient_storage_info().await })
+                        .unwrap_or_else(|err| {
+                            eprintln!("Ancient storage query failed: {err}");
+                            exit(1);
+                        });
+                    if let Some(mode) = output_mode {
+                        match mode {
+                            "json" => println!(
+                                "{}",
+                                serde_json::to_string_pretty(&ancient_storage_info).unwrap()
+                            ),
+                            "json-compact" => {
+                                print!("{}", serde_json::to_string(&ancient_storage_info).unwrap())
+                            }
+                            _ => unreachable!(),
+                        }
+                    } else {
+                        print!("{ancient_storage_info}");
+                    }
+                    return;
+                }
+                ("set", Some(subcommand_matches)) => {
+                    let config = admin_rpc_service::AdminRpcAncientStorageConfig {
+                        offset: value_t!(subcommand_matches, "offset", i64).ok(),
+                        max_ancient_slots: value_t!(subcommand_matches, "max_storages", usize).ok(),
+                        percent_of_alive_shrunk_data: value_t!(
+                            subcommand_matches,
+                            "shrink_data_percent",
+                            u64
+                        )
+                        .ok(),
+                        shrink_alive_percent: value_t!(
+                            subcommand_matches,
+                            "shrink_alive_percent",
+                            u64
+                        )
+                        .ok(),
+                    };
+                    let admin_client = admin_rpc_service::connect(&ledger_path);
+                    admin_rpc_service::runtime()
+                        .block_on(async move {
+                            admin_client.await?.set_ancient_storage_config(config).await
+                        })
+                        .unwrap_or_else(|err| {
+                            eprintln!("setAncientStorageConfig request failed: {err}");
+                            exit(1);
+                        });
+                    return;
+                }
+                _ => unreachable!(),
+            }
+        }
         ("set-public-address", Some(subcommand_matches)) => {
             let parse_arg_addr = |arg_name: &str, arg_long: &str| -> Option<SocketAddr> {
                 subcommand_matches.value_of(arg_name).map(|host_port| {
@@ -897,6 +1177,24 @@ pub fn main() {
             );
             return;
         }
+        ("set-hashes-per-tick", Some(subcommand_matches)) => {
+            let hashes_per_tick = value_t_or_exit!(subcommand_matches, "hashes_per_tick", u64);
+            let activation_epoch = value_t!(subcommand_matches, "epoch", Epoch).ok();
+            let admin_client = admin_rpc_service::connect(&ledger_path);
+            let activation_epoch = admin_rpc_service::runtime()
+                .block_on(async move {
+                    admin_client
+                        .await?
+                        .set_hashes_per_tick(hashes_per_tick, activation_epoch)
+                        .await
+                })
+                .unwrap_or_else(|err| {
+                    eprintln!("setHashesPerTick request failed: {err}");
+                    exit(1);
+                });
+            println!("Hashes per tick set to {hashes_per_tick} from epoch {activation_epoch}");
+            return;
+        }
         _ => unreachable!(),
     };
 
@@ -961,6 +1259,9 @@ pub fn main() {
         }
         .staked_map_id,
     ));
+    let forwarding_policy = Arc::new(RwLock::new(
+        value_t!(matches, "forwarding_policy", ForwardingPolicy).unwrap_or_default(),
+    ));
 
     let init_complete_file = matches.value_of("init_complete_file");
 
@@ -1063,6 +1364,15 @@ pub fn main() {
     };
 
     let contact_debug_interval = value_t_or_exit!(matches, "contact_debug_interval", u64);
+    let gossip_push_config = CrdsGossipPushConfig {
+        push_fanout: value_t_or_exit!(matches, "gossip_push_fanout", usize),
+        stake_weight_exponent: value_t_or_exit!(matches, "gossip_push_stake_weight_exponent", u32),
+        prune_timeout: value_t_or_exit!(matches, "gossip_prune_timeout_ms", u64),
+    };
+    if let Err(err) = gossip_push_config.validate() {
+        eprintln!("Invalid gossip push configuration: {err}");
+        exit(1);
+    }
 
     let account_indexes = process_account_indexes(&matches);
 
@@ -1232,7 +1542,35 @@ pub fn main() {
         write_cache_limit_bytes: value_t!(matches, "accounts_db_cache_limit_mb", u64)
             .ok()
             .map(|mb| mb * MB as u64),
+        read_cache_limit_bytes: value_t!(matches, "accounts_db_read_cache_limit_mb", usize)
+            .ok()
+            .map(|mb| mb * MB),
         ancient_append_vec_offset: value_t!(matches, "accounts_db_ancient_append_vecs", i64).ok(),
+        ancient_storage_tuning: {
+            let default_tuning = AncientStorageTuning::default();
+            Some(AncientStorageTuning {
+                max_ancient_slots: value_t!(matches, "accounts_db_ancient_max_storages", usize)
+                    .unwrap_or(default_tuning.max_ancient_slots),
+                percent_of_alive_shrunk_data: value_t!(
+                    matches,
+                    "accounts_db_ancient_shrink_data_percent",
+                    u64
+                )
+                .unwrap_or(default_tuning.percent_of_alive_shrunk_data),
+                shrink_alive_percent: value_t!(
+                    matches,
+                    "accounts_db_ancient_shrink_alive_percent",
+                    u64
+                )
+                .unwrap_or(default_tuning.shrink_alive_percent),
+            })
+        },
+        account_data_compression: Some(AccountDataCompressionConfig {
+            compression: value_t!(matches, "accounts_db_compression", AccountDataCompression)
+                .unwrap_or_default(),
+            min_data_len: value_t!(matches, "accounts_db_compression_min_data_len", usize)
+                .unwrap_or(DEFAULT_MIN_COMPRESSED_DATA_LEN),
+        }),
         exhaustively_verify_refcounts: matches.is_present("accounts_db_verify_refcounts"),
         create_ancient_storage: matches
             .is_present("accounts_db_create_ancient_storage_packed")
@@ -1258,6 +1596,15 @@ pub fn main() {
     };
     let starting_with_geyser_plugins: bool = on_start_geyser_plugin_config_files.is_some();
 
+    if matches.is_present("instruction_parser_plugin") {
+        for libpath in values_t_or_exit!(matches, "instruction_parser_plugin", PathBuf) {
+            if let Err(err) = unsafe { load_instruction_parser_plugin(&libpath) } {
+                eprintln!("{err}");
+                exit(1);
+            }
+        }
+    }
+
     let rpc_bigtable_config = if matches.is_present("enable_rpc_bigtable_ledger_storage")
         || matches.is_present("enable_bigtable_ledger_upload")
     {
@@ -1392,6 +1739,8 @@ pub fn main() {
             || matches.is_present("skip_startup_ledger_verification")),
         debug_keys,
         contact_debug_interval,
+        gossip_push_config,
+        node_metadata: matches.value_of("node_metadata").map(str::to_string),
         send_transaction_service_config: send_transaction_service::Config {
             retry_rate_ms: rpc_send_retry_rate_ms,
             leader_forward_count: value_t_or_exit!(
@@ -1438,9 +1787,16 @@ pub fn main() {
         accounts_shrink_ratio,
         runtime_config: RuntimeConfig {
             log_messages_bytes_limit: value_of(&matches, "log_messages_bytes_limit"),
+            loaded_programs_config: LoadedProgramsConfig {
+                capacity: value_of(&matches, "program_cache_capacity")
+                    .unwrap_or(MAX_LOADED_ENTRY_COUNT),
+                eviction_strategy: value_of(&matches, "program_cache_eviction_strategy")
+                    .unwrap_or_default(),
+            },
             ..RuntimeConfig::default()
         },
         staked_nodes_overrides: staked_nodes_overrides.clone(),
+        forwarding_policy: forwarding_policy.clone(),
         replay_slots_concurrently: matches.is_present("replay_slots_concurrently"),
         use_snapshot_archives_at_startup: value_t_or_exit!(
             matches,
@@ -1638,6 +1994,7 @@ pub fn main() {
         }
         validator_config.max_ledger_shreds = Some(limit_ledger_size);
     }
+    validator_config.max_ledger_epochs = value_t!(matches, "limit_ledger_epochs", u64).ok();
 
     configure_banking_trace_dir_byte_limit(&mut validator_config, &matches);
     validator_config.block_verification_method = value_t!(
@@ -1727,6 +2084,8 @@ pub fn main() {
             post_init: admin_service_post_init.clone(),
             tower_storage: validator_config.tower_storage.clone(),
             staked_nodes_overrides,
+            forwarding_policy,
+            block_production_limits: validator_config.block_production_limits.clone(),
             rpc_to_plugin_manager_sender,
         },
     );
@@ -1928,6 +2287,7 @@ fn process_account_indexes(matches: &ArgMatches) -> AccountSecondaryIndexes {
             "program-id" => AccountIndex::ProgramId,
             "spl-token-mint" => AccountIndex::SplTokenMint,
             "spl-token-owner" => AccountIndex::SplTokenOwner,
+            "program-id-data-size" => AccountIndex::ProgramIdDataSize,
             _ => unreachable!(),
         })
         .collect();
@@ -7,7 +7,7 @@ use {
         signature::Signature,
         transaction::{SanitizedTransaction, Transaction},
     },
-    solana_vote_program::vote_instruction::VoteInstruction,
+    solana_vote_program::{vote_instruction::VoteInstruction, vote_state::VoteStateUpdate},
 };
 
 pub type ParsedVote = (Pubkey, VoteTransaction, Option<Hash>, Signature);
@@ -62,6 +62,14 @@ fn parse_vote_instruction_data(
         VoteInstruction::CompactUpdateVoteStateSwitch(vote_state_update, hash) => {
             Some((VoteTransaction::from(vote_state_update), Some(hash)))
         }
+        VoteInstruction::TowerSync(tower_sync) => Some((
+            VoteTransaction::from(VoteStateUpdate::from(tower_sync)),
+            None,
+        )),
+        VoteInstruction::TowerSyncSwitch(tower_sync, hash) => Some((
+            VoteTransaction::from(VoteStateUpdate::from(tower_sync)),
+            Some(hash),
+        )),
         VoteInstruction::Authorize(_, _)
         | VoteInstruction::AuthorizeChecked(_)
         | VoteInstruction::AuthorizeWithSeed(_)
@@ -11,7 +11,7 @@ use {
     },
     solana_cli_output::display::format_labeled_address,
     solana_metrics::{datapoint_error, datapoint_info},
-    solana_notifier::{NotificationType, Notifier},
+    solana_notifier::{NotificationType, Notifier, Severity},
     solana_rpc_client::rpc_client::RpcClient,
     solana_rpc_client_api::{client_error, response::RpcVoteAccountStatus},
     solana_sdk::{
@@ -22,13 +22,39 @@ use {
     std::{
         collections::HashMap,
         error,
+        str::FromStr,
         thread::sleep,
         time::{Duration, Instant},
     },
 };
 
+/// The alerts watchtower raises, with their default severities
+const ALERTS: &[(&str, Severity)] = &[
+    ("transaction-count", Severity::Critical),
+    ("recent-blockhash", Severity::Critical),
+    ("current-stake", Severity::Critical),
+    ("delinquent", Severity::Critical),
+    ("balance", Severity::Warning),
+    ("rpc-error", Severity::Warning),
+];
+
+fn parse_alert_severity(value: &str) -> Result<(&str, Severity), String> {
+    let (alert, severity) = value
+        .split_once('=')
+        .ok_or_else(|| format!("expected ALERT=SEVERITY, got \"{value}\""))?;
+    if !ALERTS.iter().any(|(name, _)| *name == alert) {
+        return Err(format!("unknown alert \"{alert}\""));
+    }
+    Ok((alert, Severity::from_str(severity)?))
+}
+
+fn is_alert_severity(value: String) -> Result<(), String> {
+    parse_alert_severity(&value).map(|_| ())
+}
+
 struct Config {
     address_labels: HashMap<String, String>,
+    alert_severities: HashMap<String, Severity>,
     ignore_http_bad_gateway: bool,
     interval: Duration,
     json_rpc_url: String,
@@ -65,7 +91,25 @@ fn get_config() -> Config {
         and a sending number owned by that account,
         define environment variable before running `solana-watchtower`:
 
-        export TWILIO_CONFIG='ACCOUNT=<account>,TOKEN=<securityToken>,TO=<receivingNumber>,FROM=<sendingNumber>'")
+        export TWILIO_CONFIG='ACCOUNT=<account>,TOKEN=<securityToken>,TO=<receivingNumber>,FROM=<sendingNumber>'
+
+        To POST a JSON body built from a template to a webhook, with the placeholders
+        {{message}}, {{event}}, {{incident}} and {{severity}}:
+
+        export WEBHOOK_URL=...
+        export WEBHOOK_TEMPLATE='{\"summary\":\"{{message}}\",\"severity\":\"{{severity}}\"}'
+
+        To run a command, with the notification in its SOLANA_NOTIFICATION_MESSAGE,
+        SOLANA_NOTIFICATION_EVENT, SOLANA_NOTIFICATION_INCIDENT and SOLANA_NOTIFICATION_SEVERITY
+        environment variables:
+
+        export NOTIFICATION_COMMAND=...
+
+        To route alerts by severity, set the minimum severity of a channel with the variable
+        named after it, such as:
+
+        export SLACK_MIN_SEVERITY=warning
+        export PAGERDUTY_MIN_SEVERITY=critical")
         .arg({
             let arg = Arg::with_name("config_file")
                 .short("C")
@@ -168,6 +212,19 @@ fn get_config() -> Config {
                 .default_value("")
                 .help("Add this string into all notification messages after \"solana-watchtower\"")
         )
+        .arg(
+            Arg::with_name("alert_severity")
+                .long("alert-severity")
+                .value_name("ALERT=SEVERITY")
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1)
+                .validator(is_alert_severity)
+                .help("Notify the alert with this severity: info, warning or critical. \
+                    The alerts are transaction-count, recent-blockhash, current-stake \
+                    and delinquent, critical by default, and balance and rpc-error, \
+                    warning by default")
+        )
         .get_matches();
 
     let config = if let Some(config_file) = matches.value_of("config_file") {
@@ -199,8 +256,20 @@ fn get_config() -> Config {
 
     let name_suffix = value_t_or_exit!(matches, "name_suffix", String);
 
+    let mut alert_severities: HashMap<String, Severity> = ALERTS
+        .iter()
+        .map(|(alert, severity)| (alert.to_string(), *severity))
+        .collect();
+    if let Some(values) = matches.values_of("alert_severity") {
+        for value in values {
+            let (alert, severity) = parse_alert_severity(value).unwrap();
+            alert_severities.insert(alert.to_string(), severity);
+        }
+    }
+
     let config = Config {
         address_labels: config.address_labels,
+        alert_severities,
         ignore_http_bad_gateway,
         interval,
         json_rpc_url,
@@ -256,6 +325,7 @@ fn main() -> Result<(), Box<dyn error::Error>> {
     let mut last_transaction_count = 0;
     let mut last_recent_blockhash = Hash::default();
     let mut last_notification_msg = "".into();
+    let mut last_notification_severity = Severity::default();
     let mut num_consecutive_failures = 0;
     let mut last_success = Instant::now();
     let mut incident = Hash::new_unique();
@@ -384,11 +454,20 @@ fn main() -> Result<(), Box<dyn error::Error>> {
                 "solana-watchtower{}: Error: {}: {}",
                 config.name_suffix, failure_test_name, failure_error_message
             );
+            let severity = config.alert_severities[*failure_test_name];
             num_consecutive_failures += 1;
             if num_consecutive_failures > config.unhealthy_threshold {
                 datapoint_info!("watchtower-sanity", ("ok", false, bool));
                 if last_notification_msg != notification_msg {
-                    notifier.send(&notification_msg, &NotificationType::Trigger { incident });
+                    // A resolved incident is notified with its highest severity
+                    if last_notification_msg.is_empty() || severity > last_notification_severity {
+                        last_notification_severity = severity;
+                    }
+                    notifier.send_with_severity(
+                        &notification_msg,
+                        &NotificationType::Trigger { incident },
+                        severity,
+                    );
                 }
                 datapoint_error!(
                     "watchtower-sanity-failure",
@@ -414,9 +493,10 @@ fn main() -> Result<(), Box<dyn error::Error>> {
                     humantime::format_duration(alarm_duration)
                 );
                 info!("{}", all_clear_msg);
-                notifier.send(
+                notifier.send_with_severity(
                     &format!("solana-watchtower{}: {}", config.name_suffix, all_clear_msg),
                     &NotificationType::Resolve { incident },
+                    last_notification_severity,
                 );
             }
             last_notification_msg = "".into();
@@ -72,6 +72,37 @@ impl RangeProofU64Data {
     }
 }
 
+#[cfg(not(target_os = "solana"))]
+impl RangeProofU64Data {
+    /// Batch verify the proofs of several `RangeProofU64Data`, which is much cheaper than
+    /// verifying each of them on its own.
+    ///
+    /// If the batch fails to verify, which of the proofs is invalid is not known.
+    pub fn verify_batch(proof_data: &[Self]) -> Result<(), ProofVerificationError> {
+        let bit_size = usize::try_from(u64::BITS).unwrap();
+        let proofs_and_commitments = proof_data
+            .iter()
+            .map(|proof_data| {
+                let commitment: PedersenCommitment = proof_data.context.commitment.try_into()?;
+                let proof: RangeProof = proof_data.proof.try_into()?;
+                Ok((proof, commitment))
+            })
+            .collect::<Result<Vec<_>, ProofVerificationError>>()?;
+
+        RangeProof::verify_batch(proofs_and_commitments.iter().zip(proof_data).map(
+            |((proof, commitment), proof_data)| {
+                (
+                    proof,
+                    vec![commitment],
+                    vec![bit_size],
+                    proof_data.context.new_transcript(),
+                )
+            },
+        ))
+        .map_err(|e| e.into())
+    }
+}
+
 impl ZkProofData<RangeProofContext> for RangeProofU64Data {
     const PROOF_TYPE: ProofType = ProofType::RangeProofU64;
 
@@ -114,4 +145,22 @@ mod test {
         let proof_data = RangeProofU64Data::new(&commitment, amount, &opening).unwrap();
         assert!(proof_data.verify_proof().is_ok());
     }
+
+    #[test]
+    fn test_range_proof_64_batch_verification() {
+        let proof_data: Vec<_> = [0, 1, 55, std::u64::MAX]
+            .into_iter()
+            .map(|amount| {
+                let (commitment, opening) = Pedersen::new(amount);
+                RangeProofU64Data::new(&commitment, amount, &opening).unwrap()
+            })
+            .collect();
+        assert!(RangeProofU64Data::verify_batch(&proof_data).is_ok());
+
+        // swap the commitments of two proofs
+        let mut invalid_proof_data = proof_data.clone();
+        invalid_proof_data[1].context = proof_data[2].context;
+        invalid_proof_data[2].context = proof_data[1].context;
+        assert!(RangeProofU64Data::verify_batch(&invalid_proof_data).is_err());
+    }
 }
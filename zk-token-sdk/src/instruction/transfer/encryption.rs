@@ -1,8 +1,11 @@
 #[cfg(not(target_os = "solana"))]
-use crate::encryption::{
-    elgamal::{DecryptHandle, ElGamalPubkey},
-    grouped_elgamal::{GroupedElGamal, GroupedElGamalCiphertext},
-    pedersen::{PedersenCommitment, PedersenOpening},
+use crate::{
+    encryption::{
+        elgamal::{DecryptHandle, ElGamalCiphertext, ElGamalPubkey},
+        grouped_elgamal::{GroupedElGamal, GroupedElGamalCiphertext},
+        pedersen::{PedersenCommitment, PedersenOpening},
+    },
+    instruction::transfer::Role,
 };
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -49,6 +52,21 @@ impl TransferAmountCiphertext {
         // exactly three decryption handles.
         self.0.handles.get(2).unwrap()
     }
+
+    /// Extracts the ElGamal ciphertext of the amount that can be decrypted by `role`, or `None`
+    /// if the role holds no decryption handle for the amount
+    pub fn to_elgamal_ciphertext(&self, role: Role) -> Option<ElGamalCiphertext> {
+        let handle = match role {
+            Role::Source => self.get_source_handle(),
+            Role::Destination => self.get_destination_handle(),
+            Role::Auditor => self.get_auditor_handle(),
+            Role::WithdrawWithheldAuthority => return None,
+        };
+        Some(ElGamalCiphertext {
+            commitment: *self.get_commitment(),
+            handle: *handle,
+        })
+    }
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -88,4 +106,18 @@ impl FeeEncryption {
         // exactly two decryption handles.
         self.0.handles.get(1).unwrap()
     }
+
+    /// Extracts the ElGamal ciphertext of the fee that can be decrypted by `role`, or `None` if
+    /// the role holds no decryption handle for the fee
+    pub fn to_elgamal_ciphertext(&self, role: Role) -> Option<ElGamalCiphertext> {
+        let handle = match role {
+            Role::Source | Role::Auditor => return None,
+            Role::Destination => self.get_destination_handle(),
+            Role::WithdrawWithheldAuthority => self.get_withdraw_withheld_authority_handle(),
+        };
+        Some(ElGamalCiphertext {
+            commitment: *self.get_commitment(),
+            handle: *handle,
+        })
+    }
 }
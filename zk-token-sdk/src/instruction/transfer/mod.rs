@@ -6,12 +6,15 @@ mod without_fee;
 use {
     crate::{
         encryption::{
-            elgamal::ElGamalCiphertext,
+            auth_encryption::AeKey,
+            elgamal::{ElGamalCiphertext, ElGamalKeypair, ElGamalSecretKey},
             pedersen::{PedersenCommitment, PedersenOpening},
         },
         instruction::errors::InstructionError,
     },
     curve25519_dalek::scalar::Scalar,
+    solana_sdk::{pubkey::Pubkey, signer::Signer},
+    std::error,
 };
 #[cfg(not(target_os = "solana"))]
 pub use {
@@ -33,6 +36,33 @@ pub enum Role {
     WithdrawWithheldAuthority,
 }
 
+/// The encryption keys of a confidential token account
+#[cfg(not(target_os = "solana"))]
+pub struct AccountEncryptionKeys {
+    /// Keypair for the ElGamal encrypted balances and transfer amounts of the account
+    pub elgamal_keypair: ElGamalKeypair,
+    /// Key for the authenticated encryption of the decryptable balance of the account
+    pub ae_key: AeKey,
+}
+
+#[cfg(not(target_os = "solana"))]
+impl AccountEncryptionKeys {
+    /// Deterministically derives the encryption keys of the token account `address` from the
+    /// signer of its owner, using the address as the public seed.
+    ///
+    /// See `ElGamalKeypair::new_from_signer` and `AeKey::new_from_signer` for more context on the
+    /// key derivation.
+    pub fn new_from_signer(
+        signer: &dyn Signer,
+        address: &Pubkey,
+    ) -> Result<Self, Box<dyn error::Error>> {
+        Ok(Self {
+            elgamal_keypair: ElGamalKeypair::new_from_signer(signer, address.as_ref())?,
+            ae_key: AeKey::new_from_signer(signer, address.as_ref())?,
+        })
+    }
+}
+
 /// Takes in a 64-bit number `amount` and a bit length `bit_length`. It returns:
 ///  - the `bit_length` low bits of `amount` interpreted as u64
 ///  - the (64 - `bit_length`) high bits of `amount` interpreted as u64
@@ -104,8 +134,10 @@ pub fn try_combine_lo_hi_u64(
     }
 }
 
+/// Combine two ciphertexts that encrypt the low and high bits of a target number, as split by
+/// `try_split_u64`, into a ciphertext of the target number.
 #[cfg(not(target_os = "solana"))]
-fn try_combine_lo_hi_ciphertexts(
+pub fn try_combine_lo_hi_ciphertexts(
     ciphertext_lo: &ElGamalCiphertext,
     ciphertext_hi: &ElGamalCiphertext,
     bit_length: usize,
@@ -118,6 +150,45 @@ fn try_combine_lo_hi_ciphertexts(
     Ok(ciphertext_lo + &(ciphertext_hi * &Scalar::from(two_power)))
 }
 
+/// Decrypt two ciphertexts that encrypt the low and high bits of a target number, as split by
+/// `try_split_u64`, and combine them into the target number.
+///
+/// Both parts must be positive 32-bit numbers, which holds for transfer amounts and fees.
+#[cfg(not(target_os = "solana"))]
+pub fn try_decrypt_lo_hi_u64(
+    ciphertext_lo: &ElGamalCiphertext,
+    ciphertext_hi: &ElGamalCiphertext,
+    secret: &ElGamalSecretKey,
+    bit_length: usize,
+) -> Result<u64, InstructionError> {
+    let amount_lo = ciphertext_lo
+        .decrypt_u32(secret)
+        .ok_or(InstructionError::Decryption)?;
+    let amount_hi = ciphertext_hi
+        .decrypt_u32(secret)
+        .ok_or(InstructionError::Decryption)?;
+    try_combine_lo_hi_u64(amount_lo, amount_hi, bit_length)
+}
+
+/// Subtract the transfer amount encrypted by `ciphertext_lo` and `ciphertext_hi`, as split by
+/// `try_split_u64`, from the available balance ciphertext of the source account.
+#[cfg(not(target_os = "solana"))]
+pub fn try_subtract_transfer_amount(
+    source_ciphertext: &ElGamalCiphertext,
+    ciphertext_lo: &TransferAmountCiphertext,
+    ciphertext_hi: &TransferAmountCiphertext,
+    bit_length: usize,
+) -> Result<ElGamalCiphertext, InstructionError> {
+    let source_ciphertext_lo = ciphertext_lo
+        .to_elgamal_ciphertext(Role::Source)
+        .ok_or(InstructionError::MissingCiphertext)?;
+    let source_ciphertext_hi = ciphertext_hi
+        .to_elgamal_ciphertext(Role::Source)
+        .ok_or(InstructionError::MissingCiphertext)?;
+    Ok(source_ciphertext
+        - try_combine_lo_hi_ciphertexts(&source_ciphertext_lo, &source_ciphertext_hi, bit_length)?)
+}
+
 #[deprecated(
     since = "1.18.0",
     note = "please use `try_combine_lo_hi_commitments` instead"
@@ -185,7 +256,10 @@ pub struct FeeParameters {
 
 #[cfg(test)]
 mod test {
-    use super::*;
+    use {
+        super::*,
+        solana_sdk::{signature::Keypair, signer::null_signer::NullSigner},
+    };
 
     #[test]
     fn test_split_u64() {
@@ -275,4 +349,112 @@ mod test {
         let err = try_combine_lo_hi_u64(amount_lo, amount_hi, 1).unwrap_err();
         assert_eq!(err, InstructionError::IllegalAmountBitLength);
     }
+
+    #[test]
+    fn test_decrypt_lo_hi_ciphertexts() {
+        let source_keypair = ElGamalKeypair::new_rand();
+        let destination_keypair = ElGamalKeypair::new_rand();
+        let auditor_keypair = ElGamalKeypair::new_rand();
+        let encrypt = |amount| {
+            TransferAmountCiphertext::new(
+                amount,
+                source_keypair.pubkey(),
+                destination_keypair.pubkey(),
+                auditor_keypair.pubkey(),
+            )
+            .0
+        };
+
+        let amount = 0x1234_5678_9abc;
+        let (amount_lo, amount_hi) = try_split_u64(amount, 16).unwrap();
+        let ciphertext_lo = encrypt(amount_lo);
+        let ciphertext_hi = encrypt(amount_hi);
+        assert!(ciphertext_lo
+            .to_elgamal_ciphertext(Role::WithdrawWithheldAuthority)
+            .is_none());
+
+        for (role, keypair) in [
+            (Role::Source, &source_keypair),
+            (Role::Destination, &destination_keypair),
+            (Role::Auditor, &auditor_keypair),
+        ] {
+            let ciphertext_lo = ciphertext_lo.to_elgamal_ciphertext(role).unwrap();
+            let ciphertext_hi = ciphertext_hi.to_elgamal_ciphertext(role).unwrap();
+            assert_eq!(
+                try_decrypt_lo_hi_u64(&ciphertext_lo, &ciphertext_hi, keypair.secret(), 16),
+                Ok(amount)
+            );
+            let ciphertext =
+                try_combine_lo_hi_ciphertexts(&ciphertext_lo, &ciphertext_hi, 16).unwrap();
+            assert_eq!(
+                ciphertext
+                    .subtract_amount(amount)
+                    .decrypt_u32(keypair.secret()),
+                Some(0)
+            );
+        }
+
+        let withdraw_withheld_authority_keypair = ElGamalKeypair::new_rand();
+        let (fee_ciphertext, _) = FeeEncryption::new(
+            7,
+            destination_keypair.pubkey(),
+            withdraw_withheld_authority_keypair.pubkey(),
+        );
+        assert!(fee_ciphertext.to_elgamal_ciphertext(Role::Source).is_none());
+        assert!(fee_ciphertext
+            .to_elgamal_ciphertext(Role::Auditor)
+            .is_none());
+        for (role, keypair) in [
+            (Role::Destination, &destination_keypair),
+            (
+                Role::WithdrawWithheldAuthority,
+                &withdraw_withheld_authority_keypair,
+            ),
+        ] {
+            assert_eq!(
+                fee_ciphertext
+                    .to_elgamal_ciphertext(role)
+                    .unwrap()
+                    .decrypt_u32(keypair.secret()),
+                Some(7)
+            );
+        }
+    }
+
+    #[test]
+    fn test_account_encryption_keys_from_signer() {
+        let owner = Keypair::new();
+        let address = Pubkey::new_unique();
+        let keys = AccountEncryptionKeys::new_from_signer(&owner, &address).unwrap();
+
+        // the same owner and account derive the same keys
+        let derived_keys = AccountEncryptionKeys::new_from_signer(&owner, &address).unwrap();
+        assert_eq!(
+            keys.elgamal_keypair.pubkey(),
+            derived_keys.elgamal_keypair.pubkey()
+        );
+        assert_eq!(
+            derived_keys.ae_key.decrypt(&keys.ae_key.encrypt(55)),
+            Some(55)
+        );
+
+        // other accounts and other owners derive other keys
+        let other_address_keys =
+            AccountEncryptionKeys::new_from_signer(&owner, &Pubkey::new_unique()).unwrap();
+        assert_ne!(
+            keys.elgamal_keypair.pubkey(),
+            other_address_keys.elgamal_keypair.pubkey()
+        );
+        let other_owner_keys =
+            AccountEncryptionKeys::new_from_signer(&Keypair::new(), &address).unwrap();
+        assert_ne!(
+            keys.elgamal_keypair.pubkey(),
+            other_owner_keys.elgamal_keypair.pubkey()
+        );
+
+        // signers which return the default signature are rejected
+        assert!(
+            AccountEncryptionKeys::new_from_signer(&NullSigner::new(&address), &address).is_err()
+        );
+    }
 }
@@ -10,8 +10,8 @@ use {
             errors::InstructionError,
             transfer::{
                 encryption::{FeeEncryption, TransferAmountCiphertext},
-                try_combine_lo_hi_ciphertexts, try_combine_lo_hi_commitments,
-                try_combine_lo_hi_openings, try_combine_lo_hi_u64, try_split_u64, FeeParameters,
+                try_combine_lo_hi_commitments, try_combine_lo_hi_openings, try_combine_lo_hi_u64,
+                try_decrypt_lo_hi_u64, try_split_u64, try_subtract_transfer_amount, FeeParameters,
                 Role,
             },
         },
@@ -117,6 +117,23 @@ pub struct TransferWithFeePubkeys {
     pub withdraw_withheld_authority: pod::ElGamalPubkey,
 }
 
+#[cfg(not(target_os = "solana"))]
+impl TransferWithFeePubkeys {
+    pub fn new(
+        source_pubkey: &ElGamalPubkey,
+        destination_pubkey: &ElGamalPubkey,
+        auditor_pubkey: &ElGamalPubkey,
+        withdraw_withheld_authority_pubkey: &ElGamalPubkey,
+    ) -> Self {
+        Self {
+            source: (*source_pubkey).into(),
+            destination: (*destination_pubkey).into(),
+            auditor: (*auditor_pubkey).into(),
+            withdraw_withheld_authority: (*withdraw_withheld_authority_pubkey).into(),
+        }
+    }
+}
+
 #[cfg(not(target_os = "solana"))]
 impl TransferWithFeeData {
     pub fn new(
@@ -149,23 +166,13 @@ impl TransferWithFeeData {
             .checked_sub(transfer_amount)
             .ok_or(ProofGenerationError::NotEnoughFunds)?;
 
-        let transfer_amount_lo_source = ElGamalCiphertext {
-            commitment: *ciphertext_lo.get_commitment(),
-            handle: *ciphertext_lo.get_source_handle(),
-        };
-
-        let transfer_amount_hi_source = ElGamalCiphertext {
-            commitment: *ciphertext_hi.get_commitment(),
-            handle: *ciphertext_hi.get_source_handle(),
-        };
-
-        let new_source_ciphertext = old_source_ciphertext
-            - try_combine_lo_hi_ciphertexts(
-                &transfer_amount_lo_source,
-                &transfer_amount_hi_source,
-                TRANSFER_AMOUNT_LO_BITS,
-            )
-            .map_err(|_| ProofGenerationError::IllegalAmountBitLength)?;
+        let new_source_ciphertext = try_subtract_transfer_amount(
+            old_source_ciphertext,
+            &ciphertext_lo,
+            &ciphertext_hi,
+            TRANSFER_AMOUNT_LO_BITS,
+        )
+        .map_err(|_| ProofGenerationError::IllegalAmountBitLength)?;
 
         // calculate fee
         //
@@ -196,27 +203,18 @@ impl TransferWithFeeData {
         );
 
         // generate transcript and append all public inputs
-        let pod_transfer_with_fee_pubkeys = TransferWithFeePubkeys {
-            source: (*source_keypair.pubkey()).into(),
-            destination: (*destination_pubkey).into(),
-            auditor: (*auditor_pubkey).into(),
-            withdraw_withheld_authority: (*withdraw_withheld_authority_pubkey).into(),
-        };
-        let pod_ciphertext_lo: pod::TransferAmountCiphertext = ciphertext_lo.into();
-        let pod_ciphertext_hi: pod::TransferAmountCiphertext = ciphertext_hi.into();
-        let pod_new_source_ciphertext: pod::ElGamalCiphertext = new_source_ciphertext.into();
-        let pod_fee_ciphertext_lo: pod::FeeEncryption = fee_ciphertext_lo.into();
-        let pod_fee_ciphertext_hi: pod::FeeEncryption = fee_ciphertext_hi.into();
-
-        let context = TransferWithFeeProofContext {
-            ciphertext_lo: pod_ciphertext_lo,
-            ciphertext_hi: pod_ciphertext_hi,
-            transfer_with_fee_pubkeys: pod_transfer_with_fee_pubkeys,
-            new_source_ciphertext: pod_new_source_ciphertext,
-            fee_ciphertext_lo: pod_fee_ciphertext_lo,
-            fee_ciphertext_hi: pod_fee_ciphertext_hi,
-            fee_parameters: fee_parameters.into(),
-        };
+        let context = TransferWithFeeProofContext::new(
+            (&ciphertext_lo, &ciphertext_hi),
+            TransferWithFeePubkeys::new(
+                source_keypair.pubkey(),
+                destination_pubkey,
+                auditor_pubkey,
+                withdraw_withheld_authority_pubkey,
+            ),
+            &new_source_ciphertext,
+            (&fee_ciphertext_lo, &fee_ciphertext_hi),
+            fee_parameters,
+        );
 
         let mut transcript = context.new_transcript();
 
@@ -244,22 +242,9 @@ impl TransferWithFeeData {
             .ciphertext_lo
             .try_into()
             .map_err(|_| InstructionError::Decryption)?;
-
-        let handle_lo = match role {
-            Role::Source => Some(ciphertext_lo.get_source_handle()),
-            Role::Destination => Some(ciphertext_lo.get_destination_handle()),
-            Role::Auditor => Some(ciphertext_lo.get_auditor_handle()),
-            Role::WithdrawWithheldAuthority => None,
-        };
-
-        if let Some(handle) = handle_lo {
-            Ok(ElGamalCiphertext {
-                commitment: *ciphertext_lo.get_commitment(),
-                handle: *handle,
-            })
-        } else {
-            Err(InstructionError::MissingCiphertext)
-        }
+        ciphertext_lo
+            .to_elgamal_ciphertext(role)
+            .ok_or(InstructionError::MissingCiphertext)
     }
 
     /// Extracts the lo ciphertexts associated with a transfer-with-fee data
@@ -269,22 +254,9 @@ impl TransferWithFeeData {
             .ciphertext_hi
             .try_into()
             .map_err(|_| InstructionError::Decryption)?;
-
-        let handle_hi = match role {
-            Role::Source => Some(ciphertext_hi.get_source_handle()),
-            Role::Destination => Some(ciphertext_hi.get_destination_handle()),
-            Role::Auditor => Some(ciphertext_hi.get_auditor_handle()),
-            Role::WithdrawWithheldAuthority => None,
-        };
-
-        if let Some(handle) = handle_hi {
-            Ok(ElGamalCiphertext {
-                commitment: *ciphertext_hi.get_commitment(),
-                handle: *handle,
-            })
-        } else {
-            Err(InstructionError::MissingCiphertext)
-        }
+        ciphertext_hi
+            .to_elgamal_ciphertext(role)
+            .ok_or(InstructionError::MissingCiphertext)
     }
 
     /// Extracts the lo fee ciphertexts associated with a transfer_with_fee data
@@ -294,24 +266,9 @@ impl TransferWithFeeData {
             .fee_ciphertext_lo
             .try_into()
             .map_err(|_| InstructionError::Decryption)?;
-
-        let fee_handle_lo = match role {
-            Role::Source => None,
-            Role::Destination => Some(fee_ciphertext_lo.get_destination_handle()),
-            Role::Auditor => None,
-            Role::WithdrawWithheldAuthority => {
-                Some(fee_ciphertext_lo.get_withdraw_withheld_authority_handle())
-            }
-        };
-
-        if let Some(handle) = fee_handle_lo {
-            Ok(ElGamalCiphertext {
-                commitment: *fee_ciphertext_lo.get_commitment(),
-                handle: *handle,
-            })
-        } else {
-            Err(InstructionError::MissingCiphertext)
-        }
+        fee_ciphertext_lo
+            .to_elgamal_ciphertext(role)
+            .ok_or(InstructionError::MissingCiphertext)
     }
 
     /// Extracts the hi fee ciphertexts associated with a transfer_with_fee data
@@ -321,24 +278,9 @@ impl TransferWithFeeData {
             .fee_ciphertext_hi
             .try_into()
             .map_err(|_| InstructionError::Decryption)?;
-
-        let fee_handle_hi = match role {
-            Role::Source => None,
-            Role::Destination => Some(fee_ciphertext_hi.get_destination_handle()),
-            Role::Auditor => None,
-            Role::WithdrawWithheldAuthority => {
-                Some(fee_ciphertext_hi.get_withdraw_withheld_authority_handle())
-            }
-        };
-
-        if let Some(handle) = fee_handle_hi {
-            Ok(ElGamalCiphertext {
-                commitment: *fee_ciphertext_hi.get_commitment(),
-                handle: *handle,
-            })
-        } else {
-            Err(InstructionError::MissingCiphertext)
-        }
+        fee_ciphertext_hi
+            .to_elgamal_ciphertext(role)
+            .ok_or(InstructionError::MissingCiphertext)
     }
 
     /// Decrypts transfer amount from transfer-with-fee data
@@ -349,16 +291,7 @@ impl TransferWithFeeData {
     ) -> Result<u64, InstructionError> {
         let ciphertext_lo = self.ciphertext_lo(role)?;
         let ciphertext_hi = self.ciphertext_hi(role)?;
-
-        let amount_lo = ciphertext_lo.decrypt_u32(sk);
-        let amount_hi = ciphertext_hi.decrypt_u32(sk);
-
-        if let (Some(amount_lo), Some(amount_hi)) = (amount_lo, amount_hi) {
-            let shifted_amount_hi = amount_hi << TRANSFER_AMOUNT_LO_BITS;
-            Ok(amount_lo + shifted_amount_hi)
-        } else {
-            Err(InstructionError::Decryption)
-        }
+        try_decrypt_lo_hi_u64(&ciphertext_lo, &ciphertext_hi, sk, TRANSFER_AMOUNT_LO_BITS)
     }
 
     /// Decrypts transfer amount from transfer-with-fee data
@@ -369,16 +302,7 @@ impl TransferWithFeeData {
     ) -> Result<u64, InstructionError> {
         let ciphertext_lo = self.fee_ciphertext_lo(role)?;
         let ciphertext_hi = self.fee_ciphertext_hi(role)?;
-
-        let fee_amount_lo = ciphertext_lo.decrypt_u32(sk);
-        let fee_amount_hi = ciphertext_hi.decrypt_u32(sk);
-
-        if let (Some(fee_amount_lo), Some(fee_amount_hi)) = (fee_amount_lo, fee_amount_hi) {
-            let shifted_fee_amount_hi = fee_amount_hi << FEE_AMOUNT_LO_BITS;
-            Ok(fee_amount_lo + shifted_fee_amount_hi)
-        } else {
-            Err(InstructionError::Decryption)
-        }
+        try_decrypt_lo_hi_u64(&ciphertext_lo, &ciphertext_hi, sk, FEE_AMOUNT_LO_BITS)
     }
 }
 
@@ -433,6 +357,28 @@ impl ZkProofData<TransferWithFeeProofContext> for TransferWithFeeData {
 #[allow(non_snake_case)]
 #[cfg(not(target_os = "solana"))]
 impl TransferWithFeeProofContext {
+    /// Constructs the context of a transfer-with-fee proof from the encrypted low and high bits
+    /// of the transfer amount, the public keys of the transfer, the available balance ciphertext
+    /// of the source account after the transfer, as computed by `try_subtract_transfer_amount`,
+    /// the encrypted low and high bits of the fee, and the fee parameters.
+    pub fn new(
+        (ciphertext_lo, ciphertext_hi): (&TransferAmountCiphertext, &TransferAmountCiphertext),
+        transfer_with_fee_pubkeys: TransferWithFeePubkeys,
+        new_source_ciphertext: &ElGamalCiphertext,
+        (fee_ciphertext_lo, fee_ciphertext_hi): (&FeeEncryption, &FeeEncryption),
+        fee_parameters: FeeParameters,
+    ) -> Self {
+        Self {
+            ciphertext_lo: (*ciphertext_lo).into(),
+            ciphertext_hi: (*ciphertext_hi).into(),
+            transfer_with_fee_pubkeys,
+            new_source_ciphertext: (*new_source_ciphertext).into(),
+            fee_ciphertext_lo: (*fee_ciphertext_lo).into(),
+            fee_ciphertext_hi: (*fee_ciphertext_hi).into(),
+            fee_parameters: fee_parameters.into(),
+        }
+    }
+
     fn new_transcript(&self) -> Transcript {
         let mut transcript = Transcript::new(b"transfer-with-fee-proof");
         transcript.append_message(b"ciphertext-lo", bytes_of(&self.ciphertext_lo));
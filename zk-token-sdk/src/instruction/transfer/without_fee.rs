@@ -9,8 +9,8 @@ use {
         instruction::{
             errors::InstructionError,
             transfer::{
-                encryption::TransferAmountCiphertext, try_combine_lo_hi_ciphertexts, try_split_u64,
-                Role,
+                encryption::TransferAmountCiphertext, try_decrypt_lo_hi_u64, try_split_u64,
+                try_subtract_transfer_amount, Role,
             },
         },
         range_proof::RangeProof,
@@ -87,6 +87,21 @@ pub struct TransferPubkeys {
     pub auditor: pod::ElGamalPubkey,
 }
 
+#[cfg(not(target_os = "solana"))]
+impl TransferPubkeys {
+    pub fn new(
+        source_pubkey: &ElGamalPubkey,
+        destination_pubkey: &ElGamalPubkey,
+        auditor_pubkey: &ElGamalPubkey,
+    ) -> Self {
+        Self {
+            source: (*source_pubkey).into(),
+            destination: (*destination_pubkey).into(),
+            auditor: (*auditor_pubkey).into(),
+        }
+    }
+}
+
 #[cfg(not(target_os = "solana"))]
 impl TransferData {
     #[allow(clippy::too_many_arguments)]
@@ -119,40 +134,20 @@ impl TransferData {
             .checked_sub(transfer_amount)
             .ok_or(ProofGenerationError::NotEnoughFunds)?;
 
-        let transfer_amount_lo_source = ElGamalCiphertext {
-            commitment: *ciphertext_lo.get_commitment(),
-            handle: *ciphertext_lo.get_source_handle(),
-        };
-
-        let transfer_amount_hi_source = ElGamalCiphertext {
-            commitment: *ciphertext_hi.get_commitment(),
-            handle: *ciphertext_hi.get_source_handle(),
-        };
-
-        let new_source_ciphertext = ciphertext_old_source
-            - try_combine_lo_hi_ciphertexts(
-                &transfer_amount_lo_source,
-                &transfer_amount_hi_source,
-                TRANSFER_AMOUNT_LO_BITS,
-            )
-            .map_err(|_| ProofGenerationError::IllegalAmountBitLength)?;
+        let new_source_ciphertext = try_subtract_transfer_amount(
+            ciphertext_old_source,
+            &ciphertext_lo,
+            &ciphertext_hi,
+            TRANSFER_AMOUNT_LO_BITS,
+        )
+        .map_err(|_| ProofGenerationError::IllegalAmountBitLength)?;
 
         // generate transcript and append all public inputs
-        let pod_transfer_pubkeys = TransferPubkeys {
-            source: (*source_keypair.pubkey()).into(),
-            destination: (*destination_pubkey).into(),
-            auditor: (*auditor_pubkey).into(),
-        };
-        let pod_ciphertext_lo: pod::TransferAmountCiphertext = ciphertext_lo.into();
-        let pod_ciphertext_hi: pod::TransferAmountCiphertext = ciphertext_hi.into();
-        let pod_new_source_ciphertext: pod::ElGamalCiphertext = new_source_ciphertext.into();
-
-        let context = TransferProofContext {
-            ciphertext_lo: pod_ciphertext_lo,
-            ciphertext_hi: pod_ciphertext_hi,
-            transfer_pubkeys: pod_transfer_pubkeys,
-            new_source_ciphertext: pod_new_source_ciphertext,
-        };
+        let context = TransferProofContext::new(
+            (&ciphertext_lo, &ciphertext_hi),
+            TransferPubkeys::new(source_keypair.pubkey(), destination_pubkey, auditor_pubkey),
+            &new_source_ciphertext,
+        );
 
         let mut transcript = context.new_transcript();
         let proof = TransferProof::new(
@@ -175,22 +170,9 @@ impl TransferData {
             .ciphertext_lo
             .try_into()
             .map_err(|_| InstructionError::Decryption)?;
-
-        let handle_lo = match role {
-            Role::Source => Some(ciphertext_lo.get_source_handle()),
-            Role::Destination => Some(ciphertext_lo.get_destination_handle()),
-            Role::Auditor => Some(ciphertext_lo.get_auditor_handle()),
-            Role::WithdrawWithheldAuthority => None,
-        };
-
-        if let Some(handle) = handle_lo {
-            Ok(ElGamalCiphertext {
-                commitment: *ciphertext_lo.get_commitment(),
-                handle: *handle,
-            })
-        } else {
-            Err(InstructionError::MissingCiphertext)
-        }
+        ciphertext_lo
+            .to_elgamal_ciphertext(role)
+            .ok_or(InstructionError::MissingCiphertext)
     }
 
     /// Extracts the lo ciphertexts associated with a transfer data
@@ -200,22 +182,9 @@ impl TransferData {
             .ciphertext_hi
             .try_into()
             .map_err(|_| InstructionError::Decryption)?;
-
-        let handle_hi = match role {
-            Role::Source => Some(ciphertext_hi.get_source_handle()),
-            Role::Destination => Some(ciphertext_hi.get_destination_handle()),
-            Role::Auditor => Some(ciphertext_hi.get_auditor_handle()),
-            Role::WithdrawWithheldAuthority => None,
-        };
-
-        if let Some(handle) = handle_hi {
-            Ok(ElGamalCiphertext {
-                commitment: *ciphertext_hi.get_commitment(),
-                handle: *handle,
-            })
-        } else {
-            Err(InstructionError::MissingCiphertext)
-        }
+        ciphertext_hi
+            .to_elgamal_ciphertext(role)
+            .ok_or(InstructionError::MissingCiphertext)
     }
 
     /// Decrypts transfer amount from transfer data
@@ -226,16 +195,7 @@ impl TransferData {
     ) -> Result<u64, InstructionError> {
         let ciphertext_lo = self.ciphertext_lo(role)?;
         let ciphertext_hi = self.ciphertext_hi(role)?;
-
-        let amount_lo = ciphertext_lo.decrypt_u32(sk);
-        let amount_hi = ciphertext_hi.decrypt_u32(sk);
-
-        if let (Some(amount_lo), Some(amount_hi)) = (amount_lo, amount_hi) {
-            let two_power = 1 << TRANSFER_AMOUNT_LO_BITS;
-            Ok(amount_lo + two_power * amount_hi)
-        } else {
-            Err(InstructionError::Decryption)
-        }
+        try_decrypt_lo_hi_u64(&ciphertext_lo, &ciphertext_hi, sk, TRANSFER_AMOUNT_LO_BITS)
     }
 }
 
@@ -274,6 +234,22 @@ impl ZkProofData<TransferProofContext> for TransferData {
 #[allow(non_snake_case)]
 #[cfg(not(target_os = "solana"))]
 impl TransferProofContext {
+    /// Constructs the context of a transfer proof from the encrypted low and high bits of the
+    /// transfer amount, the public keys of the transfer, and the available balance ciphertext of
+    /// the source account after the transfer, as computed by `try_subtract_transfer_amount`.
+    pub fn new(
+        (ciphertext_lo, ciphertext_hi): (&TransferAmountCiphertext, &TransferAmountCiphertext),
+        transfer_pubkeys: TransferPubkeys,
+        new_source_ciphertext: &ElGamalCiphertext,
+    ) -> Self {
+        Self {
+            ciphertext_lo: (*ciphertext_lo).into(),
+            ciphertext_hi: (*ciphertext_hi).into(),
+            transfer_pubkeys,
+            new_source_ciphertext: (*new_source_ciphertext).into(),
+        }
+    }
+
     fn new_transcript(&self) -> Transcript {
         let mut transcript = Transcript::new(b"transfer-proof");
         transcript.append_message(b"ciphertext-lo", bytes_of(&self.ciphertext_lo));
@@ -612,4 +588,49 @@ mod test {
             550000_u64,
         );
     }
+
+    #[test]
+    fn test_transfer_proof_context() {
+        let source_keypair = ElGamalKeypair::new_rand();
+        let destination_keypair = ElGamalKeypair::new_rand();
+        let auditor_keypair = ElGamalKeypair::new_rand();
+
+        let spendable_balance: u64 = 77;
+        let spendable_ciphertext = source_keypair.pubkey().encrypt(spendable_balance);
+        let transfer_data = TransferData::new(
+            55,
+            (spendable_balance, &spendable_ciphertext),
+            &source_keypair,
+            (destination_keypair.pubkey(), auditor_keypair.pubkey()),
+        )
+        .unwrap();
+
+        // the context of the proof can be rebuilt from its ciphertexts and public keys
+        let ciphertext_lo: TransferAmountCiphertext =
+            transfer_data.context.ciphertext_lo.try_into().unwrap();
+        let ciphertext_hi: TransferAmountCiphertext =
+            transfer_data.context.ciphertext_hi.try_into().unwrap();
+        let new_source_ciphertext = try_subtract_transfer_amount(
+            &spendable_ciphertext,
+            &ciphertext_lo,
+            &ciphertext_hi,
+            TRANSFER_AMOUNT_LO_BITS,
+        )
+        .unwrap();
+        assert_eq!(
+            new_source_ciphertext.decrypt_u32(source_keypair.secret()),
+            Some(22)
+        );
+        let context = TransferProofContext::new(
+            (&ciphertext_lo, &ciphertext_hi),
+            TransferPubkeys::new(
+                source_keypair.pubkey(),
+                destination_keypair.pubkey(),
+                auditor_keypair.pubkey(),
+            ),
+            &new_source_ciphertext,
+        );
+        assert_eq!(bytes_of(&context), bytes_of(&transfer_data.context));
+        assert!(transfer_data.verify_proof().is_ok());
+    }
 }
@@ -250,13 +250,60 @@ impl RangeProof {
         })
     }
 
-    #[allow(clippy::many_single_char_names)]
     pub fn verify(
         &self,
         comms: Vec<&PedersenCommitment>,
         bit_lengths: Vec<usize>,
         transcript: &mut Transcript,
     ) -> Result<(), RangeProofVerificationError> {
+        let terms = self.verification_terms(comms, bit_lengths, transcript)?;
+        VerificationTerms::check_combined(&[(terms, Scalar::one())])
+    }
+
+    /// Batch verify independent range proofs, each with respect to its own commitments,
+    /// bit-lengths, and transcript.
+    ///
+    /// The verification equations of the proofs are combined with random weights into a single
+    /// multiscalar multiplication, in which the terms on the generators that every proof uses are
+    /// only computed once. This is much cheaper than verifying each proof on its own. The weights
+    /// are derived from the transcripts of all the proofs, so that a prover cannot choose invalid
+    /// proofs whose errors cancel out. If the batch fails to verify, which of the proofs is
+    /// invalid is not known.
+    pub fn verify_batch<'a>(
+        batch: impl IntoIterator<
+            Item = (
+                &'a RangeProof,
+                Vec<&'a PedersenCommitment>,
+                Vec<usize>,
+                Transcript,
+            ),
+        >,
+    ) -> Result<(), RangeProofVerificationError> {
+        let mut batch_transcript = Transcript::new(b"range-proof-batch");
+        let terms = batch
+            .into_iter()
+            .map(|(proof, comms, bit_lengths, mut transcript)| {
+                let terms = proof.verification_terms(comms, bit_lengths, &mut transcript)?;
+                batch_transcript.append_scalar(b"proof", &transcript.challenge_scalar(b"batch"));
+                Ok(terms)
+            })
+            .collect::<Result<Vec<_>, RangeProofVerificationError>>()?;
+        let weighted_terms: Vec<_> = terms
+            .into_iter()
+            .map(|terms| (terms, batch_transcript.challenge_scalar(b"weight")))
+            .collect();
+        VerificationTerms::check_combined(&weighted_terms)
+    }
+
+    /// Computes the terms of the verification equation of the proof, which holds if and only if
+    /// the multiscalar multiplication of the terms is the identity.
+    #[allow(clippy::many_single_char_names)]
+    fn verification_terms(
+        &self,
+        comms: Vec<&PedersenCommitment>,
+        bit_lengths: Vec<usize>,
+        transcript: &mut Transcript,
+    ) -> Result<VerificationTerms, RangeProofVerificationError> {
         // commitments and bit-lengths must be same length vectors
         if comms.len() != bit_lengths.len() {
             return Err(RangeProofVerificationError::VectorLengthMismatch);
@@ -264,8 +311,9 @@ impl RangeProof {
 
         let m = bit_lengths.len();
         let nm: usize = bit_lengths.iter().sum();
-        let bp_gens = BulletproofGens::new(nm)
-            .map_err(|_| RangeProofVerificationError::MaximumGeneratorLengthExceeded)?;
+        if nm > u32::MAX as usize {
+            return Err(RangeProofVerificationError::MaximumGeneratorLengthExceeded);
+        }
 
         if !nm.is_power_of_two() {
             return Err(RangeProofVerificationError::InvalidBitSize);
@@ -321,37 +369,28 @@ impl RangeProof {
             w * (self.t_x - a * b) + c * (delta(&bit_lengths, &y, &z) - self.t_x);
         let value_commitment_scalars = util::exp_iter(z).take(m).map(|z_exp| c * zz * z_exp);
 
-        let mega_check = RistrettoPoint::optional_multiscalar_mul(
-            iter::once(Scalar::one())
+        Ok(VerificationTerms {
+            g_scalar: basepoint_scalar,
+            h_scalar: -self.e_blinding - c * self.t_x_blinding,
+            gens_g_scalars: gs.collect(),
+            gens_h_scalars: hs.collect(),
+            proof_scalars: iter::once(Scalar::one())
                 .chain(iter::once(x))
                 .chain(iter::once(c * x))
                 .chain(iter::once(c * x * x))
-                .chain(iter::once(-self.e_blinding - c * self.t_x_blinding))
-                .chain(iter::once(basepoint_scalar))
                 .chain(x_sq.iter().cloned())
                 .chain(x_inv_sq.iter().cloned())
-                .chain(gs)
-                .chain(hs)
-                .chain(value_commitment_scalars),
-            iter::once(self.A.decompress())
+                .chain(value_commitment_scalars)
+                .collect(),
+            proof_points: iter::once(self.A.decompress())
                 .chain(iter::once(self.S.decompress()))
                 .chain(iter::once(self.T_1.decompress()))
                 .chain(iter::once(self.T_2.decompress()))
-                .chain(iter::once(Some(*H)))
-                .chain(iter::once(Some(*G)))
                 .chain(self.ipp_proof.L_vec.iter().map(|L| L.decompress()))
                 .chain(self.ipp_proof.R_vec.iter().map(|R| R.decompress()))
-                .chain(bp_gens.G(nm).map(|&x| Some(x)))
-                .chain(bp_gens.H(nm).map(|&x| Some(x)))
-                .chain(comms.iter().map(|V| Some(*V.get_point()))),
-        )
-        .ok_or(RangeProofVerificationError::MultiscalarMul)?;
-
-        if mega_check.is_identity() {
-            Ok(())
-        } else {
-            Err(RangeProofVerificationError::AlgebraicRelation)
-        }
+                .chain(comms.iter().map(|V| Some(*V.get_point())))
+                .collect(),
+        })
     }
 
     // Following the dalek rangeproof library signature for now. The exact method signature can be
@@ -406,6 +445,85 @@ impl RangeProof {
     }
 }
 
+/// The terms of the verification equation of a range proof.
+///
+/// The terms on the Pedersen base points and on the bulletproof generators are kept apart from
+/// the points that are specific to the proof, so that the equations of several proofs can be
+/// combined into a single multiscalar multiplication.
+struct VerificationTerms {
+    /// Scalar on the Pedersen base point `G`
+    g_scalar: Scalar,
+    /// Scalar on the Pedersen blinding point `H`
+    h_scalar: Scalar,
+    /// Scalars on the first bulletproof `G` generators
+    gens_g_scalars: Vec<Scalar>,
+    /// Scalars on the first bulletproof `H` generators
+    gens_h_scalars: Vec<Scalar>,
+    /// Scalars on the points of the proof and its commitments
+    proof_scalars: Vec<Scalar>,
+    /// The points of the proof and its commitments, `None` if a point fails to decompress
+    proof_points: Vec<Option<RistrettoPoint>>,
+}
+
+impl VerificationTerms {
+    /// Checks that the weighted sum of the verification equations holds.
+    fn check_combined(
+        weighted_terms: &[(Self, Scalar)],
+    ) -> Result<(), RangeProofVerificationError> {
+        let nm = weighted_terms
+            .iter()
+            .map(|(terms, _)| terms.gens_g_scalars.len())
+            .max()
+            .unwrap_or_default();
+        let bp_gens = BulletproofGens::new(nm)
+            .map_err(|_| RangeProofVerificationError::MaximumGeneratorLengthExceeded)?;
+
+        let mut g_scalar = Scalar::zero();
+        let mut h_scalar = Scalar::zero();
+        let mut gens_g_scalars = vec![Scalar::zero(); nm];
+        let mut gens_h_scalars = vec![Scalar::zero(); nm];
+        for (terms, weight) in weighted_terms {
+            g_scalar += weight * terms.g_scalar;
+            h_scalar += weight * terms.h_scalar;
+            for (sum, scalar) in gens_g_scalars.iter_mut().zip(&terms.gens_g_scalars) {
+                *sum += weight * scalar;
+            }
+            for (sum, scalar) in gens_h_scalars.iter_mut().zip(&terms.gens_h_scalars) {
+                *sum += weight * scalar;
+            }
+        }
+
+        let mega_check = RistrettoPoint::optional_multiscalar_mul(
+            iter::once(h_scalar)
+                .chain(iter::once(g_scalar))
+                .chain(gens_g_scalars)
+                .chain(gens_h_scalars)
+                .chain(weighted_terms.iter().flat_map(|(terms, weight)| {
+                    terms
+                        .proof_scalars
+                        .iter()
+                        .map(move |scalar| weight * scalar)
+                })),
+            iter::once(Some(*H))
+                .chain(iter::once(Some(*G)))
+                .chain(bp_gens.G(nm).map(|&x| Some(x)))
+                .chain(bp_gens.H(nm).map(|&x| Some(x)))
+                .chain(
+                    weighted_terms
+                        .iter()
+                        .flat_map(|(terms, _)| terms.proof_points.iter().copied()),
+                ),
+        )
+        .ok_or(RangeProofVerificationError::MultiscalarMul)?;
+
+        if mega_check.is_identity() {
+            Ok(())
+        } else {
+            Err(RangeProofVerificationError::AlgebraicRelation)
+        }
+    }
+}
+
 /// Compute
 /// \\[
 /// \delta(y,z) = (z - z^{2}) \langle \mathbf{1}, {\mathbf{y}}^{n \cdot m} \rangle - \sum_{j=0}^{m-1} z^{j+3} \cdot \langle \mathbf{1}, {\mathbf{2}}^{n \cdot m} \rangle
@@ -469,5 +587,109 @@ mod tests {
             .is_ok());
     }
 
+    #[test]
+    fn test_verify_batch() {
+        let (comm_1, open_1) = Pedersen::new(55_u64);
+        let (comm_2, open_2) = Pedersen::new(77_u64);
+        let (comm_3, open_3) = Pedersen::new(99_u64);
+
+        let proof_1 = RangeProof::new(
+            vec![55],
+            vec![64],
+            vec![&open_1],
+            &mut Transcript::new(b"Test 1"),
+        )
+        .unwrap();
+        let proof_2 = RangeProof::new(
+            vec![77, 99],
+            vec![32, 32],
+            vec![&open_2, &open_3],
+            &mut Transcript::new(b"Test 2"),
+        )
+        .unwrap();
+        let proof_3 = RangeProof::new(
+            vec![99],
+            vec![32],
+            vec![&open_3],
+            &mut Transcript::new(b"Test 3"),
+        )
+        .unwrap();
+
+        assert!(RangeProof::verify_batch([
+            (
+                &proof_1,
+                vec![&comm_1],
+                vec![64],
+                Transcript::new(b"Test 1")
+            ),
+            (
+                &proof_2,
+                vec![&comm_2, &comm_3],
+                vec![32, 32],
+                Transcript::new(b"Test 2")
+            ),
+            (
+                &proof_3,
+                vec![&comm_3],
+                vec![32],
+                Transcript::new(b"Test 3")
+            ),
+        ])
+        .is_ok());
+
+        // a single invalid proof fails the batch
+        assert_eq!(
+            RangeProof::verify_batch([
+                (
+                    &proof_1,
+                    vec![&comm_1],
+                    vec![64],
+                    Transcript::new(b"Test 1")
+                ),
+                (
+                    &proof_3,
+                    vec![&comm_2],
+                    vec![32],
+                    Transcript::new(b"Test 3")
+                ),
+            ]),
+            Err(RangeProofVerificationError::AlgebraicRelation)
+        );
+        assert_eq!(
+            RangeProof::verify_batch([
+                (
+                    &proof_1,
+                    vec![&comm_1],
+                    vec![64],
+                    Transcript::new(b"Test 1")
+                ),
+                (
+                    &proof_3,
+                    vec![&comm_3],
+                    vec![32],
+                    Transcript::new(b"Test 1")
+                ),
+            ]),
+            Err(RangeProofVerificationError::AlgebraicRelation)
+        );
+        assert_eq!(
+            RangeProof::verify_batch([
+                (
+                    &proof_1,
+                    vec![&comm_1],
+                    vec![64],
+                    Transcript::new(b"Test 1")
+                ),
+                (
+                    &proof_3,
+                    vec![&comm_3],
+                    vec![64],
+                    Transcript::new(b"Test 3")
+                ),
+            ]),
+            proof_3.verify(vec![&comm_3], vec![64], &mut Transcript::new(b"Test 3"))
+        );
+    }
+
     // TODO: write test for serialization/deserialization
 }
@@ -306,6 +306,28 @@ pub enum ProofInstruction {
     ///   ii. `u32` byte offset if proof is provided as an account
     ///
     VerifyFeeSigma,
+
+    /// Verify a batch of independent 64-bit range proofs.
+    ///
+    /// Each proof is a `RangeProofU64Data` that certifies that its own Pedersen commitment holds
+    /// an unsigned 64-bit number, as in `VerifyRangeProofU64`. Unlike a batched range proof, the
+    /// proofs are generated independently of each other. The verification equations of the proofs
+    /// are combined into a single multiscalar multiplication, so verifying them in one instruction
+    /// costs much less than verifying each of them in a `VerifyRangeProofU64` instruction.
+    ///
+    /// The instruction fails if any of the proofs is invalid. It does not create proof context
+    /// state accounts.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[]` (Optional) Account to read the proofs from
+    ///
+    /// The instruction expects either:
+    ///   i. a sequence of `RangeProofU64Data` if the proofs are provided as instruction data
+    ///   ii. `u32` byte offset followed by the `u32` number of consecutive `RangeProofU64Data` if
+    ///   the proofs are provided as an account
+    ///
+    VerifyRangeProofU64Batch,
 }
 
 /// Pubkeys associated with a context state account to be used as parameters to functions.
@@ -418,6 +440,36 @@ pub fn verify_batched_verify_range_proof_u256(
         .encode_verify_proof(context_state_info, proof_data)
 }
 
+/// Create a `VerifyRangeProofU64Batch` instruction.
+pub fn verify_range_proof_u64_batch(proof_data: &[RangeProofU64Data]) -> Instruction {
+    let mut data = vec![ToPrimitive::to_u8(&ProofInstruction::VerifyRangeProofU64Batch).unwrap()];
+    data.extend_from_slice(bytemuck::cast_slice(proof_data));
+
+    Instruction {
+        program_id: crate::zk_token_proof_program::id(),
+        accounts: vec![],
+        data,
+    }
+}
+
+/// Create a `VerifyRangeProofU64Batch` instruction that reads `num_proofs` consecutive
+/// `RangeProofU64Data` from `proof_account`, starting at byte `offset`.
+pub fn verify_range_proof_u64_batch_from_account(
+    proof_account: &Pubkey,
+    offset: u32,
+    num_proofs: u32,
+) -> Instruction {
+    let mut data = vec![ToPrimitive::to_u8(&ProofInstruction::VerifyRangeProofU64Batch).unwrap()];
+    data.extend_from_slice(&offset.to_le_bytes());
+    data.extend_from_slice(&num_proofs.to_le_bytes());
+
+    Instruction {
+        program_id: crate::zk_token_proof_program::id(),
+        accounts: vec![AccountMeta::new_readonly(*proof_account, false)],
+        data,
+    }
+}
+
 /// Create a `VerifyCiphertextCommitmentEquality` instruction.
 pub fn verify_ciphertext_commitment_equality(
     context_state_info: Option<ContextStateInfo>,